@@ -20,11 +20,34 @@ impl Options {
         })
     }
 
+    /// Options for generating bindings within a workflow crate.
+    ///
+    /// The generated bindings use the runtime support re-exported by the
+    /// `durable` crate, so the workflow crate does not need to depend on
+    /// `wit-bindgen-rt` itself. All generated types derive `Serialize` and
+    /// `Deserialize` so that they can be returned from a transaction. This
+    /// does mean that the workflow crate needs to depend on `serde`.
+    pub fn guest() -> Self {
+        let mut options = Self::new()
+            .with_additional_derive_attribute("serde::Serialize")
+            .with_additional_derive_attribute("serde::Deserialize");
+        options.0.runtime_path = Some("::durable::bindgen::rt".into());
+        options
+    }
+
     pub fn with(mut self, module: impl Into<String>) -> Self {
         self.0.with.push((module.into(), WithOption::Generate));
         self
     }
 
+    /// Make the generated export macro public under the given name so that it
+    /// can be invoked from other crates.
+    pub fn with_pub_export_macro(mut self, name: impl Into<String>) -> Self {
+        self.0.export_macro_name = Some(name.into());
+        self.0.pub_export_macro = true;
+        self
+    }
+
     pub fn with_additional_derive_attribute(mut self, attr: impl Into<String>) -> Self {
         self.0.additional_derive_attributes.push(attr.into());
         self
@@ -46,6 +69,44 @@ pub fn generate(
     _generate(source.as_ref(), out.as_ref(), world.as_ref(), options)
 }
 
+/// Generate guest bindings for `world` from within a build script.
+///
+/// This reads the WIT files in the `wit` directory of the crate being built
+/// and writes the bindings to `$OUT_DIR/bindings.rs`, using
+/// [`Options::guest`]. It is meant to allow workflows to call functions
+/// provided by custom host plugins.
+///
+/// In `build.rs`:
+/// ```no_run
+/// fn main() -> durable_bindgen::Result<()> {
+///     durable_bindgen::build("example:counter/imports")
+/// }
+/// ```
+///
+/// And then within the crate:
+/// ```ignore
+/// mod bindings {
+///     #![allow(clippy::all)]
+///
+///     include!(concat!(env!("OUT_DIR"), "/bindings.rs"));
+/// }
+///
+/// // Host calls must be made within a transaction so that they are not
+/// // repeated when the workflow is restarted.
+/// let count = durable::host_call!(bindings::example::counter::counter::count());
+/// ```
+pub fn build(world: &str) -> anyhow::Result<()> {
+    let manifest_dir = std::env::var_os("CARGO_MANIFEST_DIR")
+        .context("CARGO_MANIFEST_DIR is not set, is this being run from a build script?")?;
+    let out_dir = std::env::var_os("OUT_DIR")
+        .context("OUT_DIR is not set, is this being run from a build script?")?;
+
+    let source = Path::new(&manifest_dir).join("wit");
+    let out = Path::new(&out_dir).join("bindings.rs");
+
+    generate(source, out, world, Options::guest())
+}
+
 fn _generate(source: &Path, out: &Path, world: &str, options: Options) -> anyhow::Result<()> {
     let mut resolve = Resolve::new();
     let (packages, paths) = resolve.push_dir(source)?;
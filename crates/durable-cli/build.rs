@@ -0,0 +1,18 @@
+use std::path::PathBuf;
+
+use durable_migrate::{EmbedOptions, Migrator};
+
+fn main() {
+    let out_dir = PathBuf::from(std::env::var_os("OUT_DIR").unwrap());
+
+    // The migrations live in the runtime crate. They are embedded directly
+    // instead of via include_str! so that this crate can be built on its own.
+    let migrator = Migrator::from_dir("../durable-runtime/migrations")
+        .expect("failed to load database migrations");
+    let embed = migrator.embed(&EmbedOptions {
+        use_includes: false,
+        ..Default::default()
+    });
+
+    std::fs::write(out_dir.join("migrations.rs"), embed).expect("failed to write migrations");
+}
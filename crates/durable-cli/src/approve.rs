@@ -0,0 +1,43 @@
+use anyhow::Context;
+use durable_client::Task;
+use serde_json::value::RawValue;
+
+use crate::CommonOptions;
+
+#[derive(Debug, clap::Args)]
+pub(crate) struct Approve {
+    /// The task that we want to approve.
+    task: i64,
+
+    /// The approval key that the task is waiting on.
+    key: String,
+
+    /// JSON payload to pass to the task along with the approval.
+    payload: Option<String>,
+
+    /// Who is giving the approval. This is recorded for auditing purposes.
+    #[arg(long, env = "USER")]
+    by: Option<String>,
+}
+
+impl Approve {
+    pub async fn run(self, options: &CommonOptions) -> anyhow::Result<()> {
+        let client = options.client().await?;
+        let task = Task::from_id(self.task);
+
+        let payload = self.payload.as_deref().unwrap_or("null");
+        let payload: &RawValue = serde_json::from_str(payload)
+            .context("provided approval payload was not valid json")?;
+
+        match self.by.as_deref() {
+            Some(approver) => {
+                client
+                    .approve_as(&task, &self.key, approver, payload)
+                    .await?
+            }
+            None => client.approve(&task, &self.key, payload).await?,
+        }
+
+        Ok(())
+    }
+}
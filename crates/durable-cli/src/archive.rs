@@ -0,0 +1,297 @@
+use std::io::BufRead;
+use std::path::PathBuf;
+
+use anyhow::Context;
+use durable_migrate::SchemaRename;
+use serde_json::Value;
+use tabled::settings::formatting::AlignmentStrategy;
+use tabled::settings::object::Segment;
+use tabled::settings::{Alignment, Margin, Modify, Padding, Style};
+use tabled::{Table, Tabled};
+
+use crate::CommonOptions;
+
+/// Inspect and restore tasks that were archived before being cleaned up.
+#[derive(Debug, clap::Parser)]
+pub(crate) struct Archive {
+    #[command(subcommand)]
+    pub command: Command,
+}
+
+#[derive(Debug, clap::Subcommand)]
+pub(crate) enum Command {
+    /// List the tasks in the `durable.task_archive` table.
+    List {
+        /// Only list tasks with this name.
+        #[arg(long)]
+        name: Option<String>,
+
+        /// The maximum number of tasks to list.
+        #[arg(long, default_value_t = 100)]
+        limit: i64,
+    },
+
+    /// Print an archived task, along with its events and logs, as JSON.
+    Show {
+        /// The id of the archived task.
+        task: i64,
+    },
+
+    /// Restore archived tasks into the task tables.
+    ///
+    /// Restored tasks keep their original ids. The archive itself is left
+    /// untouched.
+    Restore {
+        /// The ids of the tasks to restore from the `durable.task_archive`
+        /// table.
+        tasks: Vec<i64>,
+
+        /// Restore all the tasks in a JSONL file written by the object store
+        /// archiver instead.
+        #[arg(long, conflicts_with = "tasks")]
+        file: Option<PathBuf>,
+    },
+}
+
+#[derive(Tabled)]
+struct Row {
+    id: i64,
+    name: String,
+    state: String,
+    completed_at: String,
+    archived_at: String,
+}
+
+impl Archive {
+    pub async fn run(self, options: &CommonOptions) -> anyhow::Result<()> {
+        match self.command {
+            Command::List { name, limit } => list(options, name, limit).await,
+            Command::Show { task } => show(options, task).await,
+            Command::Restore { tasks, file } => match file {
+                Some(file) => restore_file(options, file).await,
+                None => restore(options, tasks).await,
+            },
+        }
+    }
+}
+
+async fn list(options: &CommonOptions, name: Option<String>, limit: i64) -> anyhow::Result<()> {
+    let pool = options.pool().await?;
+    let schema = options.schema();
+
+    let rows = sqlx::query_as!(
+        Row,
+        r#"
+        SELECT
+            id,
+            name,
+            state::text as "state!",
+            COALESCE(
+                to_char(completed_at AT TIME ZONE 'UTC', 'YYYY-MM-DD HH24:MI:SS "UTC"'),
+                ''
+            ) as "completed_at!",
+            to_char(archived_at AT TIME ZONE 'UTC', 'YYYY-MM-DD HH24:MI:SS "UTC"')
+                as "archived_at!"
+          FROM durable.task_archive
+         WHERE $1::text IS NULL OR name = $1
+         ORDER BY id DESC
+         LIMIT $2
+        "#,
+        name,
+        limit
+    )
+    .fetch_all(schema.on(&pool))
+    .await?;
+
+    let mut table = Table::new(rows);
+    table
+        .with(
+            Modify::new(Segment::all())
+                .with(Alignment::left())
+                .with(AlignmentStrategy::PerLine),
+        )
+        .with(Style::blank())
+        .with(Margin::new(0, 0, 0, 0))
+        .with(Padding::new(0, 0, 0, 0));
+
+    println!("{table}");
+
+    Ok(())
+}
+
+async fn show(options: &CommonOptions, task: i64) -> anyhow::Result<()> {
+    let pool = options.pool().await?;
+    let schema = options.schema();
+
+    let archived = sqlx::query_scalar!(
+        r#"
+        SELECT jsonb_pretty(jsonb_build_object(
+            'id', id,
+            'task', task,
+            'events', events,
+            'logs', logs
+        )) as "archived!"
+          FROM durable.task_archive
+         WHERE id = $1
+        "#,
+        task
+    )
+    .fetch_optional(schema.on(&pool))
+    .await?;
+
+    match archived {
+        Some(archived) => println!("{archived}"),
+        None => anyhow::bail!("unable to find archived task with id {task}"),
+    }
+
+    Ok(())
+}
+
+async fn restore(options: &CommonOptions, tasks: Vec<i64>) -> anyhow::Result<()> {
+    let pool = options.pool().await?;
+    let schema = options.schema();
+    let mut tx = pool.begin().await?;
+
+    for id in tasks {
+        let archived = sqlx::query!(
+            r#"
+            SELECT
+                task::text as "task!",
+                events::text as "events!",
+                logs::text as "logs!"
+              FROM durable.task_archive
+             WHERE id = $1
+            "#,
+            id
+        )
+        .fetch_optional(schema.on(&mut *tx))
+        .await?
+        .with_context(|| format!("unable to find archived task with id {id}"))?;
+
+        restore_one(
+            &schema,
+            &mut tx,
+            id,
+            &archived.task,
+            &archived.events,
+            &archived.logs,
+        )
+        .await?;
+    }
+
+    tx.commit().await?;
+
+    Ok(())
+}
+
+async fn restore_file(options: &CommonOptions, file: PathBuf) -> anyhow::Result<()> {
+    let pool = options.pool().await?;
+    let schema = options.schema();
+    let reader = std::fs::File::open(&file)
+        .map(std::io::BufReader::new)
+        .with_context(|| format!("failed to open `{}`", file.display()))?;
+
+    let mut tx = pool.begin().await?;
+
+    for (index, line) in reader.lines().enumerate() {
+        let line = line.with_context(|| format!("failed to read `{}`", file.display()))?;
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        // Each line is an archived task as written by the object store archiver.
+        let archived: Value = serde_json::from_str(&line)
+            .with_context(|| format!("line {} is not valid JSON", index + 1))?;
+        let Some(id) = archived["id"].as_i64() else {
+            anyhow::bail!("line {} is not a valid archived task", index + 1);
+        };
+
+        restore_one(
+            &schema,
+            &mut tx,
+            id,
+            &archived["task"].to_string(),
+            &archived["events"].to_string(),
+            &archived["logs"].to_string(),
+        )
+        .await?;
+    }
+
+    tx.commit().await?;
+
+    Ok(())
+}
+
+async fn restore_one(
+    schema: &SchemaRename,
+    conn: &mut sqlx::PgConnection,
+    id: i64,
+    task: &str,
+    events: &str,
+    logs: &str,
+) -> anyhow::Result<()> {
+    // The worker, program, and other tasks that the archived row refers to may
+    // have been deleted since it was archived. References to rows that no
+    // longer exist are cleared.
+    //
+    // Rows archived before a NOT NULL column was added are missing it, so those
+    // columns get their defaults.
+    sqlx::query!(
+        r#"
+        INSERT INTO durable.task
+        SELECT * FROM jsonb_populate_record(
+            NULL::durable.task,
+            jsonb_build_object('attempt', 0, 'labels', '{}'::jsonb, 'metadata', '{}'::jsonb)
+                || $1::text::jsonb
+                || jsonb_build_object(
+                    'running_on', NULL,
+                    'wasm', (
+                        SELECT id
+                          FROM durable.wasm
+                         WHERE id = ($1::text::jsonb->>'wasm')::bigint
+                    ),
+                    'parent_id', (
+                        SELECT id
+                          FROM durable.task
+                         WHERE id = ($1::text::jsonb->>'parent_id')::bigint
+                    ),
+                    'cloned_from', (
+                        SELECT id
+                          FROM durable.task
+                         WHERE id = ($1::text::jsonb->>'cloned_from')::bigint
+                    )
+                )
+        )
+        "#,
+        task
+    )
+    .execute(schema.on(&mut *conn))
+    .await
+    .with_context(|| format!("failed to restore task {id}"))?;
+
+    sqlx::query!(
+        "
+        INSERT INTO durable.event
+        SELECT * FROM jsonb_populate_recordset(NULL::durable.event, $1::text::jsonb)
+        ",
+        events
+    )
+    .execute(schema.on(&mut *conn))
+    .await
+    .with_context(|| format!("failed to restore the events of task {id}"))?;
+
+    sqlx::query!(
+        "
+        INSERT INTO durable.log
+        SELECT * FROM jsonb_populate_recordset(NULL::durable.log, $1::text::jsonb)
+        ",
+        logs
+    )
+    .execute(schema.on(&mut *conn))
+    .await
+    .with_context(|| format!("failed to restore the logs of task {id}"))?;
+
+    println!("restored task {id}");
+
+    Ok(())
+}
@@ -23,12 +23,15 @@ struct Event {
 impl Events {
     pub async fn run(self, options: &CommonOptions) -> anyhow::Result<()> {
         let pool = options.pool().await?;
+        let schema = options.schema();
 
         let mut listener = PgListener::connect_with(&pool).await?;
-        listener.listen("durable:task-complete").await?;
+        listener
+            .listen(&schema.channel("durable:task-complete"))
+            .await?;
 
         let exists = sqlx::query_scalar!("SELECT id FROM durable.task WHERE id = $1", self.task)
-            .fetch_optional(&pool)
+            .fetch_optional(schema.on(&pool))
             .await?
             .is_some();
 
@@ -49,7 +52,7 @@ impl Events {
             "#,
             self.task,
         )
-        .fetch_all(&mut listener)
+        .fetch_all(schema.on(&mut listener))
         .await?;
 
         let mut table = Table::new(events);
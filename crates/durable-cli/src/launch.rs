@@ -1,7 +1,7 @@
 use std::path::PathBuf;
 
 use anyhow::Context;
-use durable_client::{DurableClient, ProgramOptions};
+use durable_client::{LaunchOptions, ProgramOptions};
 use futures_util::TryStreamExt;
 use serde_json::value::RawValue;
 
@@ -18,6 +18,23 @@ pub(crate) struct Launch {
     #[arg(long)]
     data: Option<String>,
 
+    /// The semantic version to record for the program.
+    #[arg(long)]
+    program_version: Option<semver::Version>,
+
+    /// A description to record for the program.
+    #[arg(long)]
+    description: Option<String>,
+
+    /// The git commit that the program was built from.
+    #[arg(long)]
+    git_commit: Option<String>,
+
+    /// Attach a label, given as `key=value`, to the task. This can be passed
+    /// multiple times.
+    #[arg(long = "label", value_parser = crate::task::parse_label)]
+    labels: Vec<(String, String)>,
+
     /// Wait for the workflow to complete and print logs as we go.
     #[arg(long, short = 'f')]
     tail: bool,
@@ -25,8 +42,7 @@ pub(crate) struct Launch {
 
 impl Launch {
     pub async fn run(self, options: &CommonOptions) -> anyhow::Result<()> {
-        let pool = options.pool().await?;
-        let client = DurableClient::new(pool.clone())?;
+        let client = options.client().await?;
 
         if self.name.is_empty() {
             anyhow::bail!("the task name must not be an empty string");
@@ -36,10 +52,29 @@ impl Launch {
         let data: &RawValue =
             serde_json::from_str(data).context("provided task data was not valid json")?;
 
-        let options = ProgramOptions::from_file(&self.wasm)
+        let mut options = ProgramOptions::from_file(&self.wasm)
             .with_context(|| format!("failed to read `{}`", self.wasm.display()))?;
+        if let Some(version) = self.program_version {
+            options = options.version(version);
+        }
+        if let Some(description) = self.description {
+            options = options.description(description);
+        }
+        if let Some(commit) = self.git_commit {
+            options = options.git_commit(commit);
+        }
         let program = client.program(options).await?;
-        let task = client.launch(&self.name, &program, data).await?;
+        let mut launch = LaunchOptions::new(&self.name, data);
+        for (key, value) in self.labels {
+            launch = launch.label(key, value);
+        }
+
+        let task = client
+            .launch_many(&program, [launch])
+            .await?
+            .into_iter()
+            .next()
+            .expect("launch_many returned no tasks");
 
         println!("launched new task with id {}", task.id());
 
@@ -49,6 +84,9 @@ impl Launch {
             while let Some(message) = logs.try_next().await? {
                 print!("{message}")
             }
+
+            let status = task.wait(&client, None).await?;
+            crate::status::print_status(&task, &status);
         }
 
         Ok(())
@@ -0,0 +1,52 @@
+use durable_client::Worker;
+
+use crate::CommonOptions;
+
+/// Show which worker is the leader of the durable cluster.
+#[derive(Debug, clap::Parser)]
+pub(crate) struct Leader {
+    /// Hand leadership off to another worker before showing the leader.
+    ///
+    /// The current leader will not become the leader again unless it is the
+    /// only worker left. Use this before taking the leader down for
+    /// maintenance.
+    #[arg(long)]
+    pub handoff: bool,
+}
+
+impl Leader {
+    pub async fn run(self, options: &CommonOptions) -> anyhow::Result<()> {
+        let client = options.client().await?;
+
+        let leader = if self.handoff {
+            let previous = client.leader().await?;
+            let leader = client.hand_off_leadership().await?;
+
+            if let (Some(previous), Some(leader)) = (&previous, &leader) {
+                if previous.id() == leader.id() {
+                    println!(
+                        "there are no other workers that can take over from worker {}",
+                        leader.id()
+                    );
+                }
+            }
+
+            leader
+        } else {
+            client.leader().await?
+        };
+
+        match leader {
+            Some(leader) => print_worker(&leader),
+            None => println!("there are no workers running"),
+        }
+
+        Ok(())
+    }
+}
+
+fn print_worker(worker: &Worker) {
+    println!("leader is worker {}", worker.id());
+    println!("started at:     {}", worker.started_at());
+    println!("last heartbeat: {}", worker.heartbeat_at());
+}
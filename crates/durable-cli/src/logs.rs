@@ -1,9 +1,12 @@
-use durable_client::{DurableClient, Task};
+use durable_client::{LogFilter, LogLevel, Task};
 use futures_util::stream::BoxStream;
 use futures_util::TryStreamExt;
 
 use crate::CommonOptions;
 
+/// The number of log lines fetched at a time when filtering logs.
+const PAGE_SIZE: i64 = 1000;
+
 /// Print the log messages emitted by a durable task.
 #[derive(Debug, clap::Parser)]
 pub(crate) struct Logs {
@@ -11,16 +14,91 @@ pub(crate) struct Logs {
     pub task: i64,
 
     /// Wait for the workflow to complete and print logs as we go.
-    #[arg(long, short = 'f')]
+    #[arg(long, short = 'f', conflicts_with_all = ["level", "grep", "limit", "verbose"])]
     pub tail: bool,
+
+    /// Only print lines at this level or above.
+    #[arg(long, value_enum)]
+    pub level: Option<Level>,
+
+    /// Only print lines containing this text, ignoring case.
+    #[arg(long)]
+    pub grep: Option<String>,
+
+    /// The maximum number of lines to print.
+    #[arg(long)]
+    pub limit: Option<usize>,
+
+    /// Print the time and level before each line.
+    #[arg(long, short = 'v')]
+    pub verbose: bool,
+}
+
+#[derive(Copy, Clone, Debug, clap::ValueEnum)]
+pub(crate) enum Level {
+    Trace,
+    Debug,
+    Info,
+    Warn,
+    Error,
+}
+
+impl From<Level> for LogLevel {
+    fn from(level: Level) -> Self {
+        match level {
+            Level::Trace => Self::Trace,
+            Level::Debug => Self::Debug,
+            Level::Info => Self::Info,
+            Level::Warn => Self::Warn,
+            Level::Error => Self::Error,
+        }
+    }
 }
 
 impl Logs {
     pub async fn run(self, options: &CommonOptions) -> anyhow::Result<()> {
-        let pool = options.pool().await?;
-        let client = DurableClient::new(pool)?;
+        let client = options.client().await?;
         let task = Task::from_id(self.task);
 
+        if self.level.is_some() || self.grep.is_some() || self.limit.is_some() || self.verbose {
+            let mut filter = LogFilter::new().limit(PAGE_SIZE);
+            if let Some(level) = self.level {
+                filter = filter.level(level.into());
+            }
+            if let Some(grep) = self.grep {
+                filter = filter.contains(grep);
+            }
+
+            let mut remaining = self.limit.unwrap_or(usize::MAX);
+            while remaining > 0 {
+                let entries = task.logs(&client, &filter).await?;
+                let Some(last) = entries.last() else {
+                    break;
+                };
+                filter = filter.after(last);
+
+                for entry in entries.iter().take(remaining) {
+                    if self.verbose {
+                        println!(
+                            "{} {:>5} {}",
+                            entry.created_at().format("%Y-%m-%d %H:%M:%S%.3f UTC"),
+                            entry.level().as_str().to_uppercase(),
+                            entry.message()
+                        );
+                    } else {
+                        println!("{}", entry.message());
+                    }
+                }
+
+                remaining = remaining.saturating_sub(entries.len());
+                if entries.len() < PAGE_SIZE as usize {
+                    break;
+                }
+            }
+
+            return Ok(());
+        }
+
         let mut stream: BoxStream<_> = if self.tail {
             Box::pin(task.follow_logs(&client))
         } else {
@@ -2,14 +2,25 @@ use std::str::FromStr;
 
 use anyhow::Context;
 use clap::Parser;
+use durable_client::DurableClient;
+use durable_migrate::SchemaRename;
 use sqlx::ConnectOptions;
 use tokio::sync::OnceCell;
 use tracing_subscriber::prelude::*;
 
+mod approve;
+mod archive;
 mod events;
 mod launch;
+mod leader;
 mod logs;
+mod migrate;
 mod notify;
+mod pause;
+mod program;
+mod status;
+mod task;
+mod trace;
 
 #[derive(Debug, clap::Parser)]
 struct Args {
@@ -26,6 +37,16 @@ enum Commands {
     Logs(self::logs::Logs),
     Events(self::events::Events),
     Notify(self::notify::Notify),
+    Approve(self::approve::Approve),
+    Status(self::status::Status),
+    Leader(self::leader::Leader),
+    Migrate(self::migrate::Migrate),
+    Archive(self::archive::Archive),
+    Task(self::task::Task),
+    Pause(self::pause::Pause),
+    Program(self::program::Program),
+    Resume(self::pause::Resume),
+    Trace(self::trace::Trace),
 }
 
 #[tokio::main]
@@ -42,6 +63,16 @@ async fn main() -> anyhow::Result<()> {
         Commands::Logs(cmd) => cmd.run(&args.common).await,
         Commands::Events(cmd) => cmd.run(&args.common).await,
         Commands::Notify(cmd) => cmd.run(&args.common).await,
+        Commands::Approve(cmd) => cmd.run(&args.common).await,
+        Commands::Status(cmd) => cmd.run(&args.common).await,
+        Commands::Leader(cmd) => cmd.run(&args.common).await,
+        Commands::Migrate(cmd) => cmd.run(&args.common).await,
+        Commands::Archive(cmd) => cmd.run(&args.common).await,
+        Commands::Task(cmd) => cmd.run(&args.common).await,
+        Commands::Pause(cmd) => cmd.run(&args.common).await,
+        Commands::Program(cmd) => cmd.run(&args.common).await,
+        Commands::Resume(cmd) => cmd.run(&args.common).await,
+        Commands::Trace(cmd) => cmd.run(&args.common).await,
     }
 }
 
@@ -50,6 +81,16 @@ struct CommonOptions {
     #[arg(long, env = "DATABASE_URL")]
     database_url: String,
 
+    /// The postgres schema that durable is installed in.
+    #[arg(long, env = "DURABLE_SCHEMA", default_value = "durable")]
+    schema: String,
+
+    /// The durable instance that launched tasks belong to.
+    ///
+    /// When set, only tasks in this instance are listed.
+    #[arg(long, env = "DURABLE_INSTANCE")]
+    instance: Option<String>,
+
     #[arg(skip)]
     pool: OnceCell<sqlx::PgPool>,
 }
@@ -69,4 +110,19 @@ impl CommonOptions {
             .await
             .cloned()
     }
+
+    pub async fn client(&self) -> anyhow::Result<DurableClient> {
+        let mut client = DurableClient::new(self.pool().await?)?.with_schema(self.schema.clone());
+        if let Some(instance) = &self.instance {
+            client = client.with_instance(instance.clone());
+        }
+
+        Ok(client)
+    }
+
+    /// Rewrites queries against the `durable` schema to use the selected
+    /// schema instead.
+    pub fn schema(&self) -> SchemaRename {
+        SchemaRename::new("durable", self.schema.clone())
+    }
 }
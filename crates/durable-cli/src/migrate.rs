@@ -0,0 +1,213 @@
+use std::time::SystemTime;
+
+use chrono::{DateTime, Utc};
+use durable_migrate::{
+    ChecksumState, MigrationState, Migrator, Options, Table, Target, TransactionMode,
+};
+use tabled::settings::formatting::AlignmentStrategy;
+use tabled::settings::object::Segment;
+use tabled::settings::{Alignment, Margin, Modify, Padding, Style};
+use tabled::Tabled;
+
+use crate::CommonOptions;
+
+mod migrations {
+    include!(concat!(env!("OUT_DIR"), "/migrations.rs"));
+}
+
+/// Manage the durable database schema.
+#[derive(Debug, clap::Parser)]
+pub(crate) struct Migrate {
+    #[command(subcommand)]
+    pub command: Command,
+
+    /// The version to migrate to.
+    ///
+    /// For `up` and `dry-run` this defaults to the latest migration. For
+    /// `down` this defaults to the migration before the one most recently
+    /// applied.
+    #[arg(long, global = true)]
+    pub target_version: Option<u64>,
+
+    /// Allow migrations to be reverted if needed to reach the target version.
+    ///
+    /// This is implied by `down`.
+    #[arg(long, global = true)]
+    pub allow_revert: bool,
+
+    /// The table that applied migrations are recorded in.
+    ///
+    /// This defaults to the `migrations` table within the schema selected by
+    /// `--schema`. Changing this will cause all previously applied migrations
+    /// to be forgotten.
+    #[arg(long, global = true)]
+    pub table: Option<String>,
+}
+
+#[derive(Copy, Clone, Debug, clap::Subcommand)]
+pub(crate) enum Command {
+    /// Apply migrations to bring the database up to the target version.
+    Up,
+
+    /// Revert migrations to bring the database down to the target version.
+    Down,
+
+    /// Show which migrations have been applied to the database.
+    Status,
+
+    /// Run the migrations to the target version within a transaction, then
+    /// roll it back.
+    DryRun,
+}
+
+#[derive(Tabled)]
+struct Row {
+    version: u64,
+    name: String,
+    state: &'static str,
+    applied_at: String,
+    checksum: &'static str,
+}
+
+impl Migrate {
+    pub async fn run(self, options: &CommonOptions) -> anyhow::Result<()> {
+        let pool = options.pool().await?;
+        let mut conn = pool.acquire().await?;
+        let migrator = migrations::MIGRATIONS;
+        let schema = options.schema();
+        let table = match self.table.as_deref() {
+            Some(table) => match table.split_once('.') {
+                Some((schema, name)) => Table::new(schema.to_owned(), name.to_owned()),
+                None => Table::plain(table.to_owned()),
+            },
+            None => Table::new(schema.to().to_owned(), "migrations"),
+        };
+
+        let mut migrate = Options {
+            target: Target::Latest,
+            transaction_mode: TransactionMode::Single,
+            allow_revert: self.allow_revert,
+            migration_table: table,
+            schema_rename: Some(schema).filter(|schema| !schema.is_identity()),
+            ..Options::default()
+        };
+
+        match self.command {
+            Command::Status => return self.status(&migrator, &mut conn, &migrate).await,
+            Command::Up => (),
+            Command::DryRun => migrate.dry_run = true,
+            Command::Down => {
+                migrate.allow_revert = true;
+
+                if self.target_version.is_none() {
+                    let Some(current) = migrator
+                        .read_database_version(&mut conn, &migrate.migration_table)
+                        .await?
+                    else {
+                        anyhow::bail!("the database has no migrations applied");
+                    };
+
+                    let previous = migrator
+                        .migrations()
+                        .iter()
+                        .rev()
+                        .find(|migration| migration.version < current)
+                        .map(|migration| migration.version)
+                        .unwrap_or(0);
+
+                    migrate.target = Target::Version(previous);
+                }
+            }
+        }
+
+        if let Some(version) = self.target_version {
+            migrate.target = Target::Version(version);
+        }
+
+        migrator.run(&mut conn, &migrate).await?;
+
+        let version = migrator
+            .read_database_version(&mut conn, &migrate.migration_table)
+            .await?;
+        let version = match version {
+            Some(version) => version.to_string(),
+            None => "none".into(),
+        };
+
+        match migrate.dry_run {
+            true => println!("dry run succeeded, no changes were made"),
+            false => println!("database is at migration version {version}"),
+        }
+
+        Ok(())
+    }
+
+    async fn status(
+        &self,
+        migrator: &Migrator,
+        conn: &mut sqlx::PgConnection,
+        options: &Options,
+    ) -> anyhow::Result<()> {
+        let report = migrator.status(conn, options).await?;
+        let rows = report.migrations.iter().map(|migration| Row {
+            version: migration.version,
+            name: migration.name.clone(),
+            state: match migration.state {
+                MigrationState::Applied => "applied",
+                MigrationState::Pending => "pending",
+                MigrationState::Unknown => "unknown",
+                _ => "",
+            },
+            applied_at: migration.applied_at.map(format_time).unwrap_or_default(),
+            checksum: match migration.checksum {
+                Some(ChecksumState::Valid) => "ok",
+                Some(ChecksumState::Modified) => "modified",
+                Some(ChecksumState::NotRecorded) => "not recorded",
+                _ => "",
+            },
+        });
+
+        let mut table = tabled::Table::new(rows);
+        table
+            .with(
+                Modify::new(Segment::all())
+                    .with(Alignment::left())
+                    .with(AlignmentStrategy::PerLine),
+            )
+            .with(Style::blank())
+            .with(Margin::new(0, 0, 0, 0))
+            .with(Padding::new(0, 0, 0, 0));
+
+        println!("{table}");
+        println!();
+
+        match report.current_version() {
+            Some(version) => println!("database is at migration version {version}"),
+            None => println!("the database has no migrations applied"),
+        }
+
+        let pending = report.pending().count();
+        if pending != 0 {
+            println!("{pending} migration(s) are pending");
+        }
+
+        if report.unknown().next().is_some() {
+            println!(
+                "the database has migrations applied that are not known to this version of the \
+                 CLI"
+            );
+        }
+
+        if report.modified().next().is_some() {
+            println!("some applied migrations have been modified since they were applied");
+        }
+
+        Ok(())
+    }
+}
+
+fn format_time(time: SystemTime) -> String {
+    DateTime::<Utc>::from(time)
+        .format("%Y-%m-%d %H:%M:%S UTC")
+        .to_string()
+}
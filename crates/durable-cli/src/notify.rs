@@ -1,5 +1,5 @@
 use anyhow::Context;
-use durable_client::{DurableClient, Task};
+use durable_client::Task;
 use serde_json::value::RawValue;
 
 use crate::CommonOptions;
@@ -19,8 +19,7 @@ pub(crate) struct Notify {
 
 impl Notify {
     pub async fn run(self, options: &CommonOptions) -> anyhow::Result<()> {
-        let pool = options.pool().await?;
-        let client = DurableClient::new(pool.clone())?;
+        let client = options.client().await?;
         let task = Task::from_id(self.task);
 
         let data = self.data.as_deref().unwrap_or("null");
@@ -0,0 +1,159 @@
+use durable_client::{DurableClient, PauseTarget};
+
+use crate::CommonOptions;
+
+/// Pause all tasks of a program or queue.
+///
+/// Workers stop claiming the paused tasks and tasks that are already running
+/// are stopped at their next transaction boundary. Use `durable resume` to let
+/// them continue.
+#[derive(Debug, clap::Parser)]
+pub(crate) struct Pause {
+    #[command(subcommand)]
+    command: PauseCommand,
+}
+
+#[derive(Debug, clap::Subcommand)]
+enum PauseCommand {
+    /// Pause all tasks running a program.
+    Program {
+        /// The id of the program to pause.
+        program: i64,
+
+        /// Why the program is being paused. This is shown by `durable pause
+        /// list`.
+        #[arg(long)]
+        reason: Option<String>,
+
+        /// The tenant that the program belongs to.
+        #[arg(long)]
+        tenant: Option<String>,
+    },
+
+    /// Pause all tasks in the queue of a tenant, or the tasks without a tenant
+    /// if none is given.
+    Queue {
+        /// Why the queue is being paused. This is shown by `durable pause
+        /// list`.
+        #[arg(long)]
+        reason: Option<String>,
+
+        /// The tenant whose queue should be paused.
+        #[arg(long)]
+        tenant: Option<String>,
+    },
+
+    /// List the programs and queues that are paused.
+    List {
+        /// List the pauses that apply to this tenant.
+        #[arg(long)]
+        tenant: Option<String>,
+    },
+}
+
+/// Resume the tasks of a program or queue that was paused.
+#[derive(Debug, clap::Parser)]
+pub(crate) struct Resume {
+    #[command(subcommand)]
+    command: ResumeCommand,
+}
+
+#[derive(Debug, clap::Subcommand)]
+enum ResumeCommand {
+    /// Resume the tasks running a program.
+    Program {
+        /// The id of the program to resume.
+        program: i64,
+
+        /// The tenant that the program belongs to.
+        #[arg(long)]
+        tenant: Option<String>,
+    },
+
+    /// Resume the tasks in the queue of a tenant, or the tasks without a
+    /// tenant if none is given.
+    Queue {
+        /// The tenant whose queue should be resumed.
+        #[arg(long)]
+        tenant: Option<String>,
+    },
+}
+
+async fn client(options: &CommonOptions, tenant: Option<String>) -> anyhow::Result<DurableClient> {
+    let mut client = options.client().await?;
+    if let Some(tenant) = tenant {
+        client = client.with_tenant(tenant);
+    }
+
+    Ok(client)
+}
+
+impl Pause {
+    pub async fn run(self, options: &CommonOptions) -> anyhow::Result<()> {
+        match self.command {
+            PauseCommand::Program {
+                program,
+                reason,
+                tenant,
+            } => {
+                let client = client(options, tenant).await?;
+                if client.pause_program(program, reason.as_deref()).await? {
+                    println!("paused program {program}");
+                } else {
+                    println!("program {program} is already paused");
+                }
+            }
+            PauseCommand::Queue { reason, tenant } => {
+                let client = client(options, tenant).await?;
+                if client.pause_queue(reason.as_deref()).await? {
+                    println!("paused the queue");
+                } else {
+                    println!("the queue is already paused");
+                }
+            }
+            PauseCommand::List { tenant } => {
+                let client = client(options, tenant).await?;
+                for pause in client.pauses().await? {
+                    let target = match pause.target() {
+                        PauseTarget::Program(id) => format!("program {id}"),
+                        PauseTarget::Queue(queue) => format!("queue {queue:?}"),
+                        _ => "unknown".to_owned(),
+                    };
+
+                    let created_at = pause.created_at().format("%Y-%m-%d %H:%M:%S UTC");
+                    match pause.reason() {
+                        Some(reason) => println!("{target} paused at {created_at}: {reason}"),
+                        None => println!("{target} paused at {created_at}"),
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Resume {
+    pub async fn run(self, options: &CommonOptions) -> anyhow::Result<()> {
+        match self.command {
+            ResumeCommand::Program { program, tenant } => {
+                let client = client(options, tenant).await?;
+                if client.resume_program(program).await? {
+                    println!("resumed program {program}");
+                } else {
+                    println!("program {program} was not paused");
+                }
+            }
+            ResumeCommand::Queue { tenant } => {
+                let client = client(options, tenant).await?;
+                if client.resume_queue().await? {
+                    println!("resumed the queue");
+                } else {
+                    println!("the queue was not paused");
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
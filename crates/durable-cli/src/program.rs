@@ -0,0 +1,84 @@
+use tabled::settings::formatting::AlignmentStrategy;
+use tabled::settings::object::Segment;
+use tabled::settings::{Alignment, Margin, Modify, Padding, Style};
+use tabled::{Table, Tabled};
+
+use crate::CommonOptions;
+
+/// Inspect the programs that have been uploaded.
+#[derive(Debug, clap::Parser)]
+pub(crate) struct Program {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, clap::Subcommand)]
+enum Command {
+    /// List programs, most recently uploaded first.
+    List {
+        /// Only list programs with this name.
+        #[arg(long)]
+        name: Option<String>,
+
+        /// List the programs belonging to this tenant.
+        #[arg(long)]
+        tenant: Option<String>,
+    },
+}
+
+#[derive(Tabled)]
+struct Row {
+    id: i64,
+    name: String,
+    version: String,
+    git_commit: String,
+    built_at: String,
+    description: String,
+}
+
+impl Program {
+    pub async fn run(self, options: &CommonOptions) -> anyhow::Result<()> {
+        match self.command {
+            Command::List { name, tenant } => {
+                let mut client = options.client().await?;
+                if let Some(tenant) = tenant {
+                    client = client.with_tenant(tenant);
+                }
+
+                let rows = client
+                    .list_programs(name.as_deref())
+                    .await?
+                    .into_iter()
+                    .map(|program| Row {
+                        id: program.id(),
+                        name: program.name().unwrap_or_default().to_owned(),
+                        version: program
+                            .version()
+                            .map(ToString::to_string)
+                            .unwrap_or_default(),
+                        git_commit: program.git_commit().unwrap_or_default().to_owned(),
+                        built_at: program
+                            .built_at()
+                            .map(|time| time.format("%Y-%m-%d %H:%M:%S UTC").to_string())
+                            .unwrap_or_default(),
+                        description: program.description().unwrap_or_default().to_owned(),
+                    });
+
+                let mut table = Table::new(rows);
+                table
+                    .with(
+                        Modify::new(Segment::all())
+                            .with(Alignment::left())
+                            .with(AlignmentStrategy::PerLine),
+                    )
+                    .with(Style::blank())
+                    .with(Margin::new(0, 0, 0, 0))
+                    .with(Padding::new(0, 0, 0, 0));
+
+                println!("{table}");
+
+                Ok(())
+            }
+        }
+    }
+}
@@ -0,0 +1,58 @@
+use std::time::Duration;
+
+use durable_client::{ExitStatus, Task};
+
+use crate::CommonOptions;
+
+/// Show whether a durable task has completed and, if it failed, why.
+#[derive(Debug, clap::Parser)]
+pub(crate) struct Status {
+    /// The id of the task we want to see the status of.
+    pub task: i64,
+
+    /// Wait for the task to complete before printing its status.
+    #[arg(long, short = 'w')]
+    pub wait: bool,
+}
+
+impl Status {
+    pub async fn run(self, options: &CommonOptions) -> anyhow::Result<()> {
+        let client = options.client().await?;
+        let task = Task::from_id(self.task);
+
+        let timeout = if self.wait {
+            None
+        } else {
+            Some(Duration::ZERO)
+        };
+        let status = match task.wait(&client, timeout).await {
+            Ok(status) => status,
+            Err(e) if e.is_timeout() => {
+                println!("task {} is still running", self.task);
+                return Ok(());
+            }
+            Err(e) => return Err(e.into()),
+        };
+
+        print_status(&task, &status);
+
+        Ok(())
+    }
+}
+
+pub(crate) fn print_status(task: &Task, status: &ExitStatus) {
+    let Some(failure) = status.failure() else {
+        println!("task {} completed successfully", task.id());
+        return;
+    };
+
+    println!("task {} failed: {}", task.id(), failure.message());
+
+    if let (Some(index), Some(label)) = (failure.index(), failure.label()) {
+        println!("last transaction: {label:?} (index {index})");
+    }
+
+    if let Some(backtrace) = failure.backtrace() {
+        println!("{backtrace}");
+    }
+}
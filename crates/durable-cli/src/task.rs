@@ -0,0 +1,335 @@
+use durable_client::{Task as TaskHandle, TaskFilter, TaskState};
+use futures_util::TryStreamExt;
+use tabled::settings::formatting::AlignmentStrategy;
+use tabled::settings::object::Segment;
+use tabled::settings::{Alignment, Margin, Modify, Padding, Style};
+use tabled::{Table, Tabled};
+
+use crate::CommonOptions;
+
+/// Manage existing tasks.
+#[derive(Debug, clap::Parser)]
+pub(crate) struct Task {
+    #[command(subcommand)]
+    pub command: Command,
+}
+
+#[derive(Debug, clap::Subcommand)]
+pub(crate) enum Command {
+    /// List tasks, most recent first.
+    List {
+        /// Only list tasks in this state.
+        #[arg(long, value_enum)]
+        state: Option<State>,
+
+        /// Only list tasks with this name.
+        #[arg(long)]
+        name: Option<String>,
+
+        /// Only list tasks with this label, given as `key=value`. This can be
+        /// passed multiple times to require several labels.
+        #[arg(long = "label", value_parser = parse_label)]
+        labels: Vec<(String, String)>,
+
+        /// List the tasks belonging to this tenant.
+        #[arg(long)]
+        tenant: Option<String>,
+
+        /// The maximum number of tasks to list.
+        #[arg(long, default_value_t = 100)]
+        limit: i64,
+    },
+
+    /// Pause a task so that it is not run until it is resumed.
+    ///
+    /// A task that is currently running is stopped at its next transaction
+    /// boundary.
+    Pause {
+        /// The id of the task to pause.
+        task: i64,
+
+        /// The tenant that the task belongs to.
+        #[arg(long)]
+        tenant: Option<String>,
+    },
+
+    /// Resume a paused task.
+    Resume {
+        /// The id of the task to resume.
+        task: i64,
+
+        /// The tenant that the task belongs to.
+        #[arg(long)]
+        tenant: Option<String>,
+    },
+
+    /// Print the metadata that a task has published about itself as JSON.
+    Metadata {
+        /// The id of the task.
+        task: i64,
+
+        /// The tenant that the task belongs to.
+        #[arg(long)]
+        tenant: Option<String>,
+    },
+
+    /// Launch a new task with the same program, name, and data as an existing
+    /// task.
+    ///
+    /// The task must still have a program to run. This is the case for tasks
+    /// that failed or are still running, but not for tasks that completed
+    /// successfully.
+    Rerun {
+        /// The id of the task to run again.
+        task: i64,
+
+        /// The tenant that the task belongs to.
+        #[arg(long)]
+        tenant: Option<String>,
+
+        /// Wait for the new task to complete and print logs as we go.
+        #[arg(long, short = 'f')]
+        tail: bool,
+    },
+
+    /// Print the HTTP requests and SQL statements that a task has made.
+    ///
+    /// This is only recorded by workers that have auditing enabled.
+    Audit {
+        /// The id of the task.
+        task: i64,
+
+        /// The tenant that the task belongs to.
+        #[arg(long)]
+        tenant: Option<String>,
+
+        /// Print each entry as a line of JSON instead.
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+#[derive(Copy, Clone, Debug, clap::ValueEnum)]
+pub(crate) enum State {
+    Ready,
+    Active,
+    Suspended,
+    Complete,
+    Failed,
+}
+
+impl From<State> for TaskState {
+    fn from(state: State) -> Self {
+        match state {
+            State::Ready => Self::Ready,
+            State::Active => Self::Active,
+            State::Suspended => Self::Suspended,
+            State::Complete => Self::Complete,
+            State::Failed => Self::Failed,
+        }
+    }
+}
+
+/// Parse a `key=value` label.
+pub(crate) fn parse_label(label: &str) -> anyhow::Result<(String, String)> {
+    match label.split_once('=') {
+        Some((key, value)) => Ok((key.to_owned(), value.to_owned())),
+        None => anyhow::bail!("expected a label of the form `key=value`"),
+    }
+}
+
+#[derive(Tabled)]
+struct Row {
+    id: i64,
+    name: String,
+    state: String,
+    labels: String,
+    created_at: String,
+}
+
+#[derive(Tabled)]
+struct AuditRow {
+    index: i32,
+    kind: String,
+    operation: String,
+    target: String,
+    rows: String,
+    status: String,
+}
+
+impl Task {
+    pub async fn run(self, options: &CommonOptions) -> anyhow::Result<()> {
+        match self.command {
+            Command::List {
+                state,
+                name,
+                labels,
+                tenant,
+                limit,
+            } => {
+                let mut client = options.client().await?;
+                if let Some(tenant) = tenant {
+                    client = client.with_tenant(tenant);
+                }
+
+                let mut filter = TaskFilter::new().limit(limit);
+                if let Some(state) = state {
+                    filter = filter.state(state.into());
+                }
+                if let Some(name) = name {
+                    filter = filter.name(name);
+                }
+                for (key, value) in labels {
+                    filter = filter.label(key, value);
+                }
+                if let Some(instance) = client.instance() {
+                    filter = filter.instance(instance);
+                }
+
+                let rows = client
+                    .list_tasks(&filter)
+                    .await?
+                    .into_iter()
+                    .map(|task| Row {
+                        id: task.id(),
+                        name: task.name().to_owned(),
+                        state: match task.paused_at() {
+                            Some(_) => format!("{:?} (paused)", task.state()).to_lowercase(),
+                            None => format!("{:?}", task.state()).to_lowercase(),
+                        },
+                        labels: task
+                            .labels()
+                            .iter()
+                            .map(|(key, value)| format!("{key}={value}"))
+                            .collect::<Vec<_>>()
+                            .join(","),
+                        created_at: task
+                            .created_at()
+                            .format("%Y-%m-%d %H:%M:%S UTC")
+                            .to_string(),
+                    });
+
+                let mut table = Table::new(rows);
+                table
+                    .with(
+                        Modify::new(Segment::all())
+                            .with(Alignment::left())
+                            .with(AlignmentStrategy::PerLine),
+                    )
+                    .with(Style::blank())
+                    .with(Margin::new(0, 0, 0, 0))
+                    .with(Padding::new(0, 0, 0, 0));
+
+                println!("{table}");
+
+                Ok(())
+            }
+            Command::Pause { task, tenant } => {
+                let mut client = options.client().await?;
+                if let Some(tenant) = tenant {
+                    client = client.with_tenant(tenant);
+                }
+
+                if TaskHandle::from_id(task).pause(&client).await? {
+                    println!("paused task {task}");
+                } else {
+                    println!("task {task} is already paused or has completed");
+                }
+
+                Ok(())
+            }
+            Command::Resume { task, tenant } => {
+                let mut client = options.client().await?;
+                if let Some(tenant) = tenant {
+                    client = client.with_tenant(tenant);
+                }
+
+                if TaskHandle::from_id(task).resume(&client).await? {
+                    println!("resumed task {task}");
+                } else {
+                    println!("task {task} was not paused");
+                }
+
+                Ok(())
+            }
+            Command::Metadata { task, tenant } => {
+                let mut client = options.client().await?;
+                if let Some(tenant) = tenant {
+                    client = client.with_tenant(tenant);
+                }
+
+                let metadata = TaskHandle::from_id(task).metadata(&client).await?;
+                println!("{}", serde_json::to_string_pretty(&metadata)?);
+
+                Ok(())
+            }
+            Command::Rerun { task, tenant, tail } => {
+                let mut client = options.client().await?;
+                if let Some(tenant) = tenant {
+                    client = client.with_tenant(tenant);
+                }
+
+                let task = TaskHandle::from_id(task).clone_and_launch(&client).await?;
+
+                println!("launched new task with id {}", task.id());
+
+                if tail {
+                    let mut logs = std::pin::pin!(task.follow_logs(&client));
+                    while let Some(message) = logs.try_next().await? {
+                        print!("{message}")
+                    }
+
+                    let status = task.wait(&client, None).await?;
+                    crate::status::print_status(&task, &status);
+                }
+
+                Ok(())
+            }
+            Command::Audit { task, tenant, json } => {
+                let mut client = options.client().await?;
+                if let Some(tenant) = tenant {
+                    client = client.with_tenant(tenant);
+                }
+
+                let entries = TaskHandle::from_id(task).audit_log(&client).await?;
+                if json {
+                    for entry in entries {
+                        println!("{}", serde_json::to_string(&entry)?);
+                    }
+
+                    return Ok(());
+                }
+
+                let optional = |value: Option<String>| value.unwrap_or_else(|| "-".into());
+                let rows = entries.into_iter().map(|entry| AuditRow {
+                    index: entry.index,
+                    kind: entry.kind,
+                    operation: entry.operation,
+                    target: match entry.target {
+                        Some(target) => target,
+                        None => {
+                            optional((!entry.tables.is_empty()).then(|| entry.tables.join(",")))
+                        }
+                    },
+                    rows: optional(entry.rows_affected.map(|rows| rows.to_string())),
+                    status: optional(entry.status.map(|status| status.to_string())),
+                });
+
+                let mut table = Table::new(rows);
+                table
+                    .with(
+                        Modify::new(Segment::all())
+                            .with(Alignment::left())
+                            .with(AlignmentStrategy::PerLine),
+                    )
+                    .with(Style::blank())
+                    .with(Margin::new(0, 0, 0, 0))
+                    .with(Padding::new(0, 0, 0, 0));
+
+                println!("{table}");
+
+                Ok(())
+            }
+        }
+    }
+}
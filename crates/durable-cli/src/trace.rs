@@ -0,0 +1,74 @@
+use std::time::Duration;
+
+use durable_client::Task;
+
+use crate::CommonOptions;
+
+/// Print each transaction in a task's event log.
+///
+/// For every transaction this shows its label, how long it took since the
+/// previous one, whether it was a database transaction, and the value that it
+/// returned.
+#[derive(Debug, clap::Parser)]
+pub(crate) struct Trace {
+    /// The id of the task to trace.
+    pub task: i64,
+
+    /// Print each transaction as a line of JSON instead.
+    #[arg(long)]
+    pub json: bool,
+
+    /// Include the logs emitted during each transaction.
+    #[arg(long)]
+    pub logs: bool,
+}
+
+impl Trace {
+    pub async fn run(self, options: &CommonOptions) -> anyhow::Result<()> {
+        let client = options.client().await?;
+        let trace = Task::from_id(self.task).trace(&client).await?;
+
+        for entry in trace {
+            if self.json {
+                println!("{}", serde_json::to_string(&entry)?);
+                continue;
+            }
+
+            let kind = match entry.is_db {
+                Some(true) => " (db)",
+                _ => "",
+            };
+
+            println!(
+                "#{} {}{kind} +{} at {}",
+                entry.index,
+                entry.label,
+                format_duration(entry.elapsed),
+                entry.created_at.format("%Y-%m-%d %H:%M:%S%.3f UTC")
+            );
+
+            let value = serde_json::to_string_pretty(&entry.value)?;
+            for line in value.lines() {
+                println!("    {line}");
+            }
+
+            if self.logs {
+                if let Some(logs) = &entry.logs {
+                    for line in logs.lines() {
+                        println!("  | {line}");
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn format_duration(duration: Duration) -> String {
+    if duration < Duration::from_secs(1) {
+        format!("{}ms", duration.as_millis())
+    } else {
+        format!("{:.3}s", duration.as_secs_f64())
+    }
+}
@@ -0,0 +1,107 @@
+use chrono::{DateTime, Utc};
+
+use crate::error::ErrorImpl;
+use crate::{DurableClient, DurableError, Task};
+
+/// A single external effect performed by a task, as returned by
+/// [`Task::audit_log`].
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct AuditEntry {
+    /// The index of the transaction that performed this effect.
+    pub index: i32,
+
+    /// The kind of effect. This is `http` for HTTP requests and `sql` for SQL
+    /// statements.
+    pub kind: String,
+
+    /// The HTTP method or SQL command, e.g. `GET` or `INSERT`.
+    ///
+    /// Redirects followed while making an HTTP request are recorded as
+    /// separate entries with the `REDIRECT` operation.
+    pub operation: String,
+
+    /// The URL that an HTTP request was sent to.
+    ///
+    /// Credentials, the query string, and the fragment are removed from the
+    /// URL before it is recorded.
+    pub target: Option<String>,
+
+    /// The tables accessed by a SQL statement.
+    ///
+    /// This is empty for statements that the database could not plan, such as
+    /// DDL statements.
+    pub tables: Vec<String>,
+
+    /// The number of rows affected by a SQL statement.
+    ///
+    /// This is `None` if the statement failed or the task never read its
+    /// results.
+    pub rows_affected: Option<i64>,
+
+    /// The status code of the response to an HTTP request.
+    ///
+    /// This is `None` if the request failed without getting a response.
+    pub status: Option<i32>,
+
+    /// The time at which the transaction that performed this effect was
+    /// recorded.
+    pub created_at: DateTime<Utc>,
+}
+
+impl Task {
+    /// Read the audit log of external effects performed by the task, in the
+    /// order that they were performed.
+    ///
+    /// This is only recorded for tasks run on workers that have auditing
+    /// enabled. Effects are saved along with the transaction that performed
+    /// them, so the log does not include anything done by a transaction that
+    /// has not completed yet.
+    pub async fn audit_log(&self, client: &DurableClient) -> Result<Vec<AuditEntry>, DurableError> {
+        let mut conn = client.pool.acquire().await?;
+
+        let records = sqlx::query!(
+            r#"
+            SELECT
+                a.index,
+                a.kind,
+                a.operation,
+                a.target,
+                a.tables,
+                a.rows_affected,
+                a.status,
+                a.created_at
+             FROM durable.audit_log a
+             JOIN durable.task t ON t.id = a.task_id
+            WHERE a.task_id = $1
+              AND t.tenant IS NOT DISTINCT FROM $2
+            ORDER BY a.index ASC, a.seq ASC
+            "#,
+            self.id,
+            client.tenant()
+        )
+        .fetch_all(client.data.schema.on(&mut *conn))
+        .await?;
+
+        if records.is_empty()
+            && !self
+                .exists(client.tenant(), &client.data.schema, &mut conn)
+                .await?
+        {
+            return Err(ErrorImpl::NonexistantTaskId(self.id).into());
+        }
+
+        Ok(records
+            .into_iter()
+            .map(|record| AuditEntry {
+                index: record.index,
+                kind: record.kind,
+                operation: record.operation,
+                target: record.target,
+                tables: record.tables,
+                rows_affected: record.rows_affected,
+                status: record.status,
+                created_at: record.created_at,
+            })
+            .collect())
+    }
+}
@@ -0,0 +1,243 @@
+use std::time::Duration;
+
+use sqlx::postgres::{PgConnectOptions, PgPoolOptions};
+
+use crate::{DurableClient, DurableError};
+
+/// Builder for a [`DurableClient`] that manages its own connection pool.
+///
+/// This is an alternative to constructing a [`sqlx::PgPool`] yourself and
+/// passing it to [`DurableClient::new`].
+///
+/// ```no_run
+/// # async fn example() -> Result<(), durable_client::DurableError> {
+/// use std::time::Duration;
+///
+/// use durable_client::DurableClientBuilder;
+///
+/// let client = DurableClientBuilder::from_env()?
+///     .max_connections(4)
+///     .statement_timeout(Duration::from_secs(30))
+///     .application_name("my-app")
+///     .connect()
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct DurableClientBuilder {
+    options: PgConnectOptions,
+    pool: PgPoolOptions,
+    statement_timeout: Option<Duration>,
+    search_path: Vec<String>,
+    tenant: Option<String>,
+    instance: Option<String>,
+    max_inline_data: Option<usize>,
+    schema: Option<String>,
+}
+
+impl DurableClientBuilder {
+    /// Create a builder that connects to the database at `url`.
+    pub fn new(url: &str) -> Result<Self, DurableError> {
+        Ok(Self::from_options(url.parse()?))
+    }
+
+    /// Create a builder that connects to the database in the `DATABASE_URL`
+    /// environment variable.
+    pub fn from_env() -> Result<Self, DurableError> {
+        let url = std::env::var("DATABASE_URL").map_err(|e| {
+            sqlx::Error::Configuration(format!("unable to read DATABASE_URL: {e}").into())
+        })?;
+
+        Self::new(&url)
+    }
+
+    /// Create a builder that connects using already parsed connection
+    /// options.
+    pub fn from_options(options: PgConnectOptions) -> Self {
+        Self {
+            options,
+            pool: PgPoolOptions::new(),
+            statement_timeout: None,
+            search_path: Vec::new(),
+            tenant: None,
+            instance: None,
+            max_inline_data: None,
+            schema: None,
+        }
+    }
+
+    /// The maximum number of connections that the pool will hold open.
+    ///
+    /// The default is 10.
+    pub fn max_connections(mut self, max: u32) -> Self {
+        self.pool = self.pool.max_connections(max);
+        self
+    }
+
+    /// The number of connections that the pool tries to keep open at all
+    /// times.
+    ///
+    /// The default is 0.
+    pub fn min_connections(mut self, min: u32) -> Self {
+        self.pool = self.pool.min_connections(min);
+        self
+    }
+
+    /// How long to wait for a connection to become available before giving
+    /// up.
+    ///
+    /// The default is 30s.
+    pub fn acquire_timeout(mut self, timeout: Duration) -> Self {
+        self.pool = self.pool.acquire_timeout(timeout);
+        self
+    }
+
+    /// How long a connection can sit idle in the pool before it is closed.
+    ///
+    /// The default is 10 minutes.
+    pub fn idle_timeout(mut self, timeout: Duration) -> Self {
+        self.pool = self.pool.idle_timeout(timeout);
+        self
+    }
+
+    /// Abort any statement that takes longer than `timeout`.
+    ///
+    /// This sets `statement_timeout` on every connection in the pool. By
+    /// default the server's setting is used.
+    pub fn statement_timeout(mut self, timeout: Duration) -> Self {
+        self.statement_timeout = Some(timeout);
+        self
+    }
+
+    /// The `application_name` reported by connections in the pool.
+    ///
+    /// This shows up in `pg_stat_activity` and in the server logs.
+    pub fn application_name(mut self, name: &str) -> Self {
+        self.options = self.options.application_name(name);
+        self
+    }
+
+    /// Set the `search_path` of connections in the pool to `schemas`.
+    ///
+    /// The client always refers to its own tables by their qualified name, so
+    /// this only affects queries that you run on the client's
+    /// [`pool`](DurableClient::pool).
+    pub fn search_path<I>(mut self, schemas: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        self.search_path = schemas.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Create a client that acts on behalf of `tenant`.
+    ///
+    /// See [`DurableClient::with_tenant`].
+    pub fn tenant(mut self, tenant: impl Into<String>) -> Self {
+        self.tenant = Some(tenant.into());
+        self
+    }
+
+    /// Create a client that launches tasks into `instance`.
+    ///
+    /// See [`DurableClient::with_instance`].
+    pub fn instance(mut self, instance: impl Into<String>) -> Self {
+        self.instance = Some(instance.into());
+        self
+    }
+
+    /// See [`DurableClient::with_max_inline_data`].
+    pub fn max_inline_data(mut self, bytes: usize) -> Self {
+        self.max_inline_data = Some(bytes);
+        self
+    }
+
+    /// Use the durable installation in `schema` instead of the one in the
+    /// `durable` schema.
+    ///
+    /// See [`DurableClient::with_schema`].
+    pub fn schema(mut self, schema: impl Into<String>) -> Self {
+        self.schema = Some(schema.into());
+        self
+    }
+
+    /// Connect to the database and create the client.
+    ///
+    /// This waits until a connection has been established so that
+    /// configuration errors are reported here instead of on first use.
+    pub async fn connect(self) -> Result<DurableClient, DurableError> {
+        let pool = self
+            .pool
+            .clone()
+            .connect_with(self.connect_options())
+            .await?;
+        self.build(pool)
+    }
+
+    /// Create the client without waiting for a connection to be established.
+    ///
+    /// Connections are opened when the client first needs them.
+    pub fn connect_lazy(self) -> Result<DurableClient, DurableError> {
+        let pool = self.pool.clone().connect_lazy_with(self.connect_options());
+        self.build(pool)
+    }
+
+    fn connect_options(&self) -> PgConnectOptions {
+        let mut options = self.options.clone();
+        if let Some(timeout) = self.statement_timeout {
+            let millis = timeout.as_millis().to_string();
+            options = options.options([("statement_timeout", millis.as_str())]);
+        }
+
+        if !self.search_path.is_empty() {
+            let schemas: Vec<_> = self
+                .search_path
+                .iter()
+                .map(|schema| format!("\"{}\"", schema.replace('"', "\"\"")))
+                .collect();
+            options = options.options([("search_path", schemas.join(",").as_str())]);
+        }
+
+        options
+    }
+
+    fn build(self, pool: sqlx::PgPool) -> Result<DurableClient, DurableError> {
+        let mut client = DurableClient::new(pool)?;
+        if let Some(schema) = self.schema {
+            client = client.with_schema(schema);
+        }
+        if let Some(tenant) = self.tenant {
+            client = client.with_tenant(tenant);
+        }
+        if let Some(instance) = self.instance {
+            client = client.with_instance(instance);
+        }
+        if let Some(bytes) = self.max_inline_data {
+            client = client.with_max_inline_data(bytes);
+        }
+
+        Ok(client)
+    }
+}
+
+impl DurableClient {
+    /// Connect to the database at `url` using the default pool settings.
+    ///
+    /// Use [`DurableClientBuilder`] for more control over the connection
+    /// pool.
+    pub async fn connect(url: &str) -> Result<Self, DurableError> {
+        DurableClientBuilder::new(url)?.connect().await
+    }
+
+    /// Create a builder that connects to the database at `url`.
+    pub fn builder(url: &str) -> Result<DurableClientBuilder, DurableError> {
+        DurableClientBuilder::new(url)
+    }
+
+    /// The connection pool used by this client.
+    pub fn pool(&self) -> &sqlx::PgPool {
+        &self.pool
+    }
+}
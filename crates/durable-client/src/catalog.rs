@@ -0,0 +1,202 @@
+use std::borrow::Cow;
+use std::sync::atomic::AtomicI64;
+use std::sync::{Arc, PoisonError};
+
+use chrono::{DateTime, Utc};
+use semver::{Version, VersionReq};
+use weak_table::weak_value_hash_map::Entry;
+
+use crate::error::ErrorImpl;
+use crate::program::{ProgramData, ProgramHash, ProgramMetadata};
+use crate::util::LockCell;
+use crate::{DurableClient, DurableError, Program};
+
+/// A program returned by [`DurableClient::list_programs`].
+#[derive(Clone, Debug)]
+pub struct ProgramSummary {
+    id: i64,
+    name: Option<String>,
+    version: Option<Version>,
+    description: Option<String>,
+    git_commit: Option<String>,
+    built_at: Option<DateTime<Utc>>,
+    last_used: DateTime<Utc>,
+}
+
+impl ProgramSummary {
+    /// The id of the program.
+    pub fn id(&self) -> i64 {
+        self.id
+    }
+
+    /// The name that the program was uploaded with, if any.
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// The semantic version of the program, if it has one.
+    pub fn version(&self) -> Option<&Version> {
+        self.version.as_ref()
+    }
+
+    /// A description of the program, if it has one.
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    /// The git commit that the program was built from, if known.
+    pub fn git_commit(&self) -> Option<&str> {
+        self.git_commit.as_deref()
+    }
+
+    /// When the program was built, if known.
+    pub fn built_at(&self) -> Option<DateTime<Utc>> {
+        self.built_at
+    }
+
+    /// The last time that a client uploaded or used this program.
+    pub fn last_used(&self) -> DateTime<Utc> {
+        self.last_used
+    }
+}
+
+impl DurableClient {
+    /// List the programs belonging to this client's tenant, most recently
+    /// uploaded first.
+    ///
+    /// If `name` is provided then only programs with that name are returned.
+    pub async fn list_programs(
+        &self,
+        name: Option<&str>,
+    ) -> Result<Vec<ProgramSummary>, DurableError> {
+        let records = sqlx::query!(
+            r#"
+            SELECT id, name, version, description, git_commit, built_at, last_used
+             FROM durable.wasm
+            WHERE tenant IS NOT DISTINCT FROM $1
+              AND ($2::text IS NULL OR name = $2)
+            ORDER BY id DESC
+            "#,
+            self.tenant(),
+            name
+        )
+        .fetch_all(self.data.schema.on(&self.pool))
+        .await?;
+
+        Ok(records
+            .into_iter()
+            .map(|record| ProgramSummary {
+                id: record.id,
+                name: record.name,
+                version: record.version.and_then(|version| version.parse().ok()),
+                description: record.description,
+                git_commit: record.git_commit,
+                built_at: record.built_at,
+                last_used: record.last_used,
+            })
+            .collect())
+    }
+
+    /// Find the program with the given name whose version is the highest one
+    /// matching `version`.
+    ///
+    /// `version` is a semver requirement using the same syntax as cargo, e.g.
+    /// `^1.2`, `=1.4.0` or `>=2, <3`. Only programs uploaded with a
+    /// [`version`](crate::ProgramOptions::version) are considered. If multiple
+    /// programs have the same version then the most recently uploaded one is
+    /// returned.
+    ///
+    /// Returns `None` if no program matches.
+    ///
+    /// # Errors
+    /// Returns an error if `version` is not a valid version requirement or if
+    /// an error occurs while communicating with the database.
+    pub async fn program_by_name_and_version(
+        &self,
+        name: &str,
+        version: &str,
+    ) -> Result<Option<Program>, DurableError> {
+        let req = VersionReq::parse(version).map_err(ErrorImpl::InvalidVersionReq)?;
+
+        let candidates = sqlx::query!(
+            r#"
+            SELECT id, hash, version as "version!"
+             FROM durable.wasm
+            WHERE name = $1
+              AND tenant IS NOT DISTINCT FROM $2
+              AND version IS NOT NULL
+            "#,
+            name,
+            self.tenant()
+        )
+        .fetch_all(self.data.schema.on(&self.pool))
+        .await?;
+
+        let best = candidates
+            .into_iter()
+            .filter_map(|record| {
+                let version = Version::parse(&record.version).ok()?;
+                let hash = ProgramHash::try_from(record.hash).ok()?;
+                req.matches(&version).then_some((version, record.id, hash))
+            })
+            .max_by(|a, b| (&a.0, a.1).cmp(&(&b.0, b.1)));
+
+        let Some((_, id, hash)) = best else {
+            return Ok(None);
+        };
+
+        let cached = self
+            .data
+            .programs
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(&hash);
+        if let Some(data) = cached {
+            return Ok(Some(Program::new(data, None)));
+        }
+
+        let record = sqlx::query!(
+            "
+            SELECT wasm, name, version, description, git_commit, built_at, last_used
+             FROM durable.wasm
+            WHERE id = $1
+            ",
+            id
+        )
+        .fetch_optional(self.data.schema.on(&self.pool))
+        .await?;
+
+        // The program may have been cleaned up in the meantime.
+        let Some(record) = record else {
+            return Ok(None);
+        };
+
+        let data = Arc::new(ProgramData {
+            id: AtomicI64::new(id),
+            hash,
+            wasm: Cow::Owned(record.wasm),
+            name: record.name.map(Cow::Owned),
+            metadata: ProgramMetadata {
+                version: record.version.and_then(|version| version.parse().ok()),
+                description: record.description.map(Cow::Owned),
+                git_commit: record.git_commit.map(Cow::Owned),
+                built_at: record.built_at,
+            },
+            tenant: self.data.tenant.clone(),
+            last_used: LockCell::new(record.last_used),
+        });
+
+        let mut programs = self
+            .data
+            .programs
+            .write()
+            .unwrap_or_else(PoisonError::into_inner);
+
+        let data = match programs.entry(hash) {
+            Entry::Vacant(entry) => entry.insert(data),
+            Entry::Occupied(entry) => entry.get_strong(),
+        };
+
+        Ok(Some(Program::new(data, None)))
+    }
+}
@@ -21,6 +21,17 @@ pub enum DurableErrorKind {
     ///
     /// The internal error here is [`sqlx::Error`].
     Database,
+
+    /// An error occurred while precompiling a WASM program.
+    ///
+    /// This is only returned when the `precompile` feature is enabled.
+    Precompile,
+
+    /// An error occurred while fetching a program from a URL or an OCI
+    /// registry, or the fetched program did not match its expected digest.
+    ///
+    /// This is only returned when the `remote` feature is enabled.
+    Fetch,
 }
 
 mod detail {
@@ -31,6 +42,30 @@ mod detail {
         ProgramIsNotAComponent,
         Database(sqlx::Error),
         NonexistantTaskId(i64),
+        NonexistantProgramId(i64),
+        ProgramUnavailable(i64),
+        ProgramTenantMismatch,
+        AlreadyApproved(i64, String),
+        InvalidDataSchema(String),
+        InvalidData(crate::ValidationError),
+        InvalidResult(i64, serde_json::Error),
+        InvalidVersionReq(semver::Error),
+        Timeout,
+        #[cfg(feature = "precompile")]
+        Precompile(wasmtime::Error),
+        #[cfg(feature = "remote")]
+        Fetch(reqwest::Error),
+        #[cfg(feature = "remote")]
+        InvalidUrl(String),
+        #[cfg(feature = "remote")]
+        InvalidReference(String, String),
+        #[cfg(feature = "remote")]
+        Oci(String),
+        #[cfg(feature = "remote")]
+        DigestMismatch {
+            expected: String,
+            actual: String,
+        },
     }
 }
 
@@ -38,6 +73,23 @@ use std::fmt;
 
 pub(crate) use self::detail::DurableError as ErrorImpl;
 
+impl DurableError {
+    /// If this error was caused by task data that did not match the data schema
+    /// of its program, get the details of what was wrong with it.
+    pub fn validation_error(&self) -> Option<&crate::ValidationError> {
+        match &self.0 {
+            ErrorImpl::InvalidData(e) => Some(e),
+            _ => None,
+        }
+    }
+
+    /// Whether this error was caused by a wait timing out before the task
+    /// completed.
+    pub fn is_timeout(&self) -> bool {
+        matches!(self.0, ErrorImpl::Timeout)
+    }
+}
+
 impl fmt::Debug for DurableError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         self.0.fmt(f)
@@ -53,6 +105,41 @@ impl fmt::Display for DurableError {
             }
             ErrorImpl::Database(e) => e.fmt(f),
             ErrorImpl::NonexistantTaskId(id) => write!(f, "no task with id {id}"),
+            ErrorImpl::NonexistantProgramId(id) => write!(f, "no program with id {id}"),
+            ErrorImpl::ProgramUnavailable(id) => {
+                write!(f, "task {id} no longer has a program that can be run")
+            }
+            ErrorImpl::ProgramTenantMismatch => write!(
+                f,
+                "the program was loaded by a client belonging to a different tenant"
+            ),
+            ErrorImpl::AlreadyApproved(id, key) => {
+                write!(f, "task {id} has already been approved for {key:?}")
+            }
+            ErrorImpl::InvalidDataSchema(e) => write!(f, "invalid program data schema: {e}"),
+            ErrorImpl::InvalidData(e) => e.fmt(f),
+            ErrorImpl::InvalidResult(id, e) => {
+                write!(f, "the result of task {id} could not be deserialized: {e}")
+            }
+            ErrorImpl::InvalidVersionReq(e) => write!(f, "invalid version requirement: {e}"),
+            ErrorImpl::Timeout => write!(f, "timed out waiting for the task to complete"),
+            #[cfg(feature = "precompile")]
+            ErrorImpl::Precompile(e) => write!(f, "failed to precompile program: {e}"),
+            #[cfg(feature = "remote")]
+            ErrorImpl::Fetch(e) => write!(f, "failed to fetch program: {e}"),
+            #[cfg(feature = "remote")]
+            ErrorImpl::InvalidUrl(e) => write!(f, "invalid program url: {e}"),
+            #[cfg(feature = "remote")]
+            ErrorImpl::InvalidReference(reference, reason) => {
+                write!(f, "invalid OCI reference {reference:?}: {reason}")
+            }
+            #[cfg(feature = "remote")]
+            ErrorImpl::Oci(e) => write!(f, "failed to pull program from OCI registry: {e}"),
+            #[cfg(feature = "remote")]
+            ErrorImpl::DigestMismatch { expected, actual } => write!(
+                f,
+                "fetched content has digest {actual} but expected {expected}"
+            ),
         }
     }
 }
@@ -64,6 +151,27 @@ impl std::error::Error for DurableError {
             ErrorImpl::ProgramIsNotAComponent => None,
             ErrorImpl::Database(e) => Some(e),
             ErrorImpl::NonexistantTaskId(_) => None,
+            ErrorImpl::NonexistantProgramId(_) => None,
+            ErrorImpl::ProgramUnavailable(_) => None,
+            ErrorImpl::ProgramTenantMismatch => None,
+            ErrorImpl::AlreadyApproved(..) => None,
+            ErrorImpl::InvalidDataSchema(_) => None,
+            ErrorImpl::InvalidData(e) => Some(e),
+            ErrorImpl::InvalidResult(_, e) => Some(e),
+            ErrorImpl::InvalidVersionReq(e) => Some(e),
+            ErrorImpl::Timeout => None,
+            #[cfg(feature = "precompile")]
+            ErrorImpl::Precompile(e) => Some(e.as_ref()),
+            #[cfg(feature = "remote")]
+            ErrorImpl::Fetch(e) => Some(e),
+            #[cfg(feature = "remote")]
+            ErrorImpl::InvalidUrl(_) => None,
+            #[cfg(feature = "remote")]
+            ErrorImpl::InvalidReference(..) => None,
+            #[cfg(feature = "remote")]
+            ErrorImpl::Oci(_) => None,
+            #[cfg(feature = "remote")]
+            ErrorImpl::DigestMismatch { .. } => None,
         }
     }
 }
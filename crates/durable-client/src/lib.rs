@@ -1,8 +1,11 @@
 use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::sync::{Arc, PoisonError, RwLock, Weak};
 
-use chrono::{Duration, Utc};
+use chrono::{DateTime, Duration, Utc};
+use durable_migrate::SchemaRename;
 use error::ErrorImpl;
+use serde_json::value::RawValue;
 use sha2::{Digest, Sha256};
 use sqlx::types::Json;
 use sqlx::Acquire;
@@ -11,16 +14,41 @@ use weak_table::weak_value_hash_map::Entry;
 use weak_table::WeakValueHashMap;
 
 use crate::program::{ProgramData, ProgramHash};
+use crate::schema::DataSchema;
 
+mod audit;
+mod builder;
+mod catalog;
 mod error;
 pub mod event;
+mod list;
+mod logs;
+mod pause;
 mod program;
+#[cfg(feature = "remote")]
+mod remote;
+mod schema;
 mod task;
+mod trace;
 mod util;
+mod worker;
 
+pub use self::audit::AuditEntry;
+pub use self::builder::DurableClientBuilder;
+pub use self::catalog::ProgramSummary;
 pub use self::error::{DurableError, DurableErrorKind};
+pub use self::list::{TaskFilter, TaskSummary};
+pub use self::logs::{LogEntry, LogFilter, LogLevel};
+pub use self::pause::{Pause, PauseTarget};
 pub use self::program::{Program, ProgramOptions};
-pub use self::task::{ExitStatus, Task, TaskState};
+#[cfg(feature = "remote")]
+pub use self::remote::{OciReference, RegistryAuth};
+pub use self::schema::{ValidationError, Violation};
+pub use self::task::{Event, ExitStatus, Failure, Task, TaskState};
+pub use self::trace::TraceEntry;
+pub use self::worker::Worker;
+#[doc(inline)]
+pub use durable_workflow::WorkflowDef;
 
 #[derive(Clone)]
 pub struct DurableClient {
@@ -30,8 +58,15 @@ pub struct DurableClient {
 
 struct ClientData {
     programs: RwLock<WeakValueHashMap<[u8; 32], Weak<ProgramData>>>,
+    tenant: Option<Arc<str>>,
+    instance: Option<Arc<str>>,
+    max_inline_data: usize,
+    schema: SchemaRename,
 }
 
+/// The default for [`DurableClient::with_max_inline_data`].
+pub const DEFAULT_MAX_INLINE_DATA: usize = 1024 * 1024;
+
 impl DurableClient {
     /// Create a new durable client from a PgPool instance.
     pub fn new(pool: sqlx::PgPool) -> Result<Self, DurableError> {
@@ -43,10 +78,134 @@ impl DurableClient {
             pool,
             data: Arc::new(ClientData {
                 programs: RwLock::new(WeakValueHashMap::new()),
+                tenant: None,
+                instance: None,
+                max_inline_data: DEFAULT_MAX_INLINE_DATA,
+                schema: SchemaRename::default(),
             }),
         })
     }
 
+    /// Create a client that acts on behalf of `tenant`.
+    ///
+    /// Programs uploaded and tasks launched by the returned client belong to
+    /// `tenant`. It can only see and interact with tasks that belong to the
+    /// same tenant; any other task behaves as if it does not exist. Clients
+    /// created via [`new`] belong to no tenant and, similarly, only see tasks
+    /// that belong to no tenant.
+    ///
+    /// Tenants are not a security boundary on their own since anyone with
+    /// access to the database can pick any tenant they like. Restricting each
+    /// team to their own tenant requires giving them access through some
+    /// other service, such as the worker's management API, instead.
+    ///
+    /// The returned client shares its connection pool with this one.
+    ///
+    /// [`new`]: DurableClient::new
+    pub fn with_tenant(&self, tenant: impl Into<String>) -> Self {
+        Self {
+            pool: self.pool.clone(),
+            data: Arc::new(ClientData {
+                programs: RwLock::new(WeakValueHashMap::new()),
+                tenant: Some(Arc::from(tenant.into())),
+                instance: self.data.instance.clone(),
+                max_inline_data: self.data.max_inline_data,
+                schema: self.data.schema.clone(),
+            }),
+        }
+    }
+
+    /// Create a client that stores task data larger than `bytes` outside of
+    /// the task table.
+    ///
+    /// Task data is normally stored inline in the `durable.task` table. Data
+    /// whose JSON encoding is larger than this limit is instead stored in the
+    /// separate `durable.task_payload` table so that it doesn't bloat the task
+    /// table. Workers load it from there when they start the task, so this is
+    /// invisible to the workflow itself.
+    ///
+    /// The default limit is [`DEFAULT_MAX_INLINE_DATA`].
+    ///
+    /// The returned client shares its connection pool with this one.
+    pub fn with_max_inline_data(&self, bytes: usize) -> Self {
+        Self {
+            pool: self.pool.clone(),
+            data: Arc::new(ClientData {
+                programs: RwLock::new(WeakValueHashMap::new()),
+                tenant: self.data.tenant.clone(),
+                instance: self.data.instance.clone(),
+                max_inline_data: bytes,
+                schema: self.data.schema.clone(),
+            }),
+        }
+    }
+
+    /// Create a client for the durable installation in the `schema` schema.
+    ///
+    /// By default, durable keeps all of its tables in the `durable` schema.
+    /// Using a different schema allows multiple independent installations of
+    /// durable to share one database. The schema must have been set up by
+    /// running the migrations with the same schema, and workers must be
+    /// configured with it via `Config::schema`.
+    ///
+    /// The returned client shares its connection pool with this one.
+    pub fn with_schema(&self, schema: impl Into<String>) -> Self {
+        Self {
+            pool: self.pool.clone(),
+            data: Arc::new(ClientData {
+                programs: RwLock::new(WeakValueHashMap::new()),
+                tenant: self.data.tenant.clone(),
+                instance: self.data.instance.clone(),
+                max_inline_data: self.data.max_inline_data,
+                schema: SchemaRename::new("durable", schema.into()),
+            }),
+        }
+    }
+
+    /// Create a client that launches tasks into the durable instance
+    /// `instance`.
+    ///
+    /// Workers only run tasks that belong to the same instance as them (see
+    /// `Config::instance`). This allows independent fleets of workers, e.g.
+    /// for a blue/green deployment, to share a single durable installation.
+    /// Tasks launched by clients created via [`new`] belong to no instance
+    /// and are only run by workers that don't belong to an instance either.
+    ///
+    /// Unlike tenants, instances do not restrict which tasks the client can
+    /// see. Use [`TaskFilter::instance`] to list the tasks of an instance.
+    ///
+    /// The returned client shares its connection pool with this one.
+    ///
+    /// [`new`]: DurableClient::new
+    pub fn with_instance(&self, instance: impl Into<String>) -> Self {
+        Self {
+            pool: self.pool.clone(),
+            data: Arc::new(ClientData {
+                programs: RwLock::new(WeakValueHashMap::new()),
+                tenant: self.data.tenant.clone(),
+                instance: Some(Arc::from(instance.into())),
+                max_inline_data: self.data.max_inline_data,
+                schema: self.data.schema.clone(),
+            }),
+        }
+    }
+
+    /// The tenant that this client acts on behalf of, if any.
+    pub fn tenant(&self) -> Option<&str> {
+        self.data.tenant.as_deref()
+    }
+
+    /// The instance that this client launches tasks into, if any.
+    pub fn instance(&self) -> Option<&str> {
+        self.data.instance.as_deref()
+    }
+
+    /// The schema that holds the tables of the durable installation that this
+    /// client uses.
+    pub fn schema(&self) -> &str {
+        self.data.schema.to()
+    }
+
     /// Load a new program for use by workflows.
     ///
     /// You can then use the resulting [`Program`] to launch workflows
@@ -61,6 +220,10 @@ impl DurableClient {
     /// * The WASM program fails to validate.
     /// * The WASM program is not a WASM component.
     /// * An error occurs while communicating with the database.
+    /// * Precompiling the program for one of the configs provided via
+    ///   `ProgramOptions::precompile` fails.
+    /// * The data schema provided via `ProgramOptions::data_schema` is not a
+    ///   valid JSON Schema.
     ///
     /// [`launch`]: DurableClient::launch
     pub async fn program(&self, opts: ProgramOptions) -> Result<Program, DurableError> {
@@ -75,12 +238,30 @@ impl DurableClient {
             return Err(DurableError(ErrorImpl::ProgramIsNotAComponent));
         }
 
+        let schema = opts
+            .data_schema
+            .map(DataSchema::new)
+            .transpose()?
+            .map(Arc::new);
+
         let mut hasher = Sha256::new();
         hasher.update(&opts.wasm);
         let hash: ProgramHash = hasher.finalize().into();
 
         let mut conn = self.pool.acquire().await?;
-        let data = ProgramData::register(hash, opts.wasm, opts.name, &mut conn).await?;
+        let data = ProgramData::register(
+            hash,
+            opts.wasm,
+            opts.name,
+            opts.metadata,
+            self.data.tenant.clone(),
+            &self.data.schema,
+            &mut conn,
+        )
+        .await?;
+        #[cfg(feature = "precompile")]
+        data.precompile(&opts.precompile, &self.data.schema, &mut conn)
+            .await?;
         drop(conn);
 
         let data = Arc::new(data);
@@ -95,7 +276,7 @@ impl DurableClient {
             Entry::Occupied(entry) => entry.get_strong(),
         };
 
-        Ok(Program::new(data))
+        Ok(Program::new(data, schema))
     }
 
     /// Launch a new workflow with the provided program and task data.
@@ -111,6 +292,26 @@ impl DurableClient {
         self._launch(name.as_ref(), program, data).await
     }
 
+    /// Launch a new task running workflow `W`.
+    ///
+    /// The task is named after the workflow and is launched with
+    /// [`W::NAME`](WorkflowDef::NAME) as its entrypoint, so the program must
+    /// export the workflow under that name. Use [`Task::result_typed`] to read
+    /// the output of the workflow once the task completes.
+    pub async fn launch_typed<W>(
+        &self,
+        program: &Program,
+        input: &W::Input,
+    ) -> Result<Task, DurableError>
+    where
+        W: WorkflowDef,
+    {
+        let options = LaunchOptions::new(W::NAME, input).entrypoint(W::NAME);
+        let tasks = self.launch_many(program, std::iter::once(options)).await?;
+
+        Ok(tasks.into_iter().next().expect("no tasks were returned"))
+    }
+
     async fn _launch<T>(
         &self,
         name: &str,
@@ -184,6 +385,10 @@ impl DurableClient {
     where
         T: serde::Serialize,
     {
+        if program.0.tenant != self.data.tenant {
+            return Err(ErrorImpl::ProgramTenantMismatch.into());
+        }
+
         let mut tx = conn.begin().await?;
 
         let now = Utc::now();
@@ -197,7 +402,7 @@ impl DurableClient {
                     RETURNING last_used",
                 program.0.id()
             )
-            .fetch_optional(&mut *tx)
+            .fetch_optional(self.data.schema.on(&mut *tx))
             .await?;
 
             if let Some(record) = record {
@@ -205,42 +410,108 @@ impl DurableClient {
             }
         }
 
-        let (names, data): (Vec<_>, Vec<_>) = input
-            .into_iter()
-            .map(|options| (options.name, Json(options.data)))
-            .unzip();
+        let mut names = Vec::new();
+        let mut data = Vec::new();
+        let mut payloads = Vec::new();
+        let mut contexts = Vec::new();
+        let mut labels = Vec::new();
+        let mut entrypoints = Vec::new();
+        let mut dedupe_keys = Vec::new();
+        let mut run_at = Vec::new();
+        let mut dependencies = Vec::new();
+        for (index, options) in input.into_iter().enumerate() {
+            let value = serde_json::value::to_raw_value(&options.data)
+                .map_err(|e| sqlx::Error::Encode(Box::new(e)))?;
+
+            if let Some(schema) = &program.1 {
+                let data: serde_json::Value = serde_json::from_str(value.get())
+                    .map_err(|e| sqlx::Error::Encode(Box::new(e)))?;
+                schema
+                    .validate(&options.name, &data)
+                    .map_err(ErrorImpl::InvalidData)?;
+            }
+
+            // Oversized data is stored separately. The task row just gets a null
+            // placeholder.
+            if value.get().len() > self.data.max_inline_data {
+                data.push(Json(RawValue::NULL.to_owned()));
+                payloads.push((index, Json(value)));
+            } else {
+                data.push(Json(value));
+            }
 
-        let workflows = loop {
+            names.push(options.name);
+            entrypoints.push(options.entrypoint);
+            dedupe_keys.push(options.dedupe_key);
+            run_at.push(options.run_at);
+            dependencies.push((options.after, options.dependency_failure));
+            contexts.push(
+                Some(options.sql_context)
+                    .filter(|context| !context.is_empty())
+                    .map(Json),
+            );
+            labels.push(Json(options.labels));
+        }
+
+        let records = loop {
             // Create a savepoint so that we can rollback if something goes wrong here.
             let mut stx = tx.begin().await?;
-            let result = sqlx::query_scalar!(
+            let result = sqlx::query!(
                 r#"
-                INSERT INTO durable.task(name, wasm, data, running_on)
+                INSERT INTO durable.task(
+                    name, wasm, data, sql_context, entrypoint, tenant, instance, dedupe_key,
+                    wakeup_at, labels, running_on
+                )
                 SELECT
                     name,
                     $1 as wasm,
                     data,
+                    sql_context,
+                    entrypoint,
+                    $6::text as tenant,
+                    $10::text as instance,
+                    dedupe_key,
+                    wakeup_at,
+                    labels,
                     (
                         SELECT id
                          FROM durable.worker
+                        WHERE worker.instance IS NOT DISTINCT FROM $10::text
                         ORDER BY random(), name
                         LIMIT 1
                         FOR SHARE SKIP LOCKED
                     ) as running_on
-                FROM UNNEST($2::text[], $3::jsonb[]) as t(name, data)
-                RETURNING id
+                FROM UNNEST(
+                    $2::text[],
+                    $3::jsonb[],
+                    $4::jsonb[],
+                    $5::text[],
+                    $7::text[],
+                    $8::timestamptz[],
+                    $9::jsonb[]
+                ) as t(name, data, sql_context, entrypoint, dedupe_key, wakeup_at, labels)
+                ON CONFLICT ((COALESCE(tenant, '')), dedupe_key) WHERE dedupe_key IS NOT NULL
+                DO NOTHING
+                RETURNING id, dedupe_key
                 "#,
                 program.0.id(),
                 &names as &[Cow<str>],
-                &data as &[Json<T>]
+                &data as &[Json<Box<RawValue>>],
+                &contexts as &[Option<Json<BTreeMap<String, String>>>],
+                &entrypoints as &[Option<Cow<str>>],
+                self.tenant(),
+                &dedupe_keys as &[Option<String>],
+                &run_at as &[Option<DateTime<Utc>>],
+                &labels as &[Json<BTreeMap<String, String>>],
+                self.instance()
             )
-            .fetch_all(&mut *stx)
+            .fetch_all(self.data.schema.on(&mut *stx))
             .await;
 
             let error = match result {
                 Ok(records) => {
                     stx.commit().await?;
-                    break records.into_iter().map(|id| Task { id }).collect();
+                    break records;
                 }
                 Err(e) => e,
             };
@@ -260,7 +531,7 @@ impl DurableClient {
                         //
                         // In this case we just need to recreate it in the database.
                         Some("fk_wasm") => {
-                            program.0.reregister(&mut tx).await?;
+                            program.0.reregister(&self.data.schema, &mut tx).await?;
                             continue;
                         }
 
@@ -277,15 +548,249 @@ impl DurableClient {
             }
         };
 
+        // Tasks without a dedupe key are always created and are returned in the same
+        // order as the input. Those with a key may have been skipped because a task
+        // with that key already exists.
+        let inserted: HashSet<i64> = records.iter().map(|record| record.id).collect();
+        let mut created = Vec::new();
+        let mut existing = HashMap::new();
+        for record in records {
+            match record.dedupe_key {
+                Some(key) => {
+                    existing.insert(key, record.id);
+                }
+                None => created.push(record.id),
+            }
+        }
+
+        let missing: Vec<&str> = dedupe_keys
+            .iter()
+            .flatten()
+            .map(|key| key.as_str())
+            .filter(|key| !existing.contains_key(*key))
+            .collect();
+        if !missing.is_empty() {
+            let records = sqlx::query!(
+                r#"
+                SELECT id, dedupe_key as "dedupe_key!"
+                 FROM durable.task
+                WHERE dedupe_key = ANY($1::text[])
+                  AND tenant IS NOT DISTINCT FROM $2
+                "#,
+                &missing as &[&str],
+                self.tenant()
+            )
+            .fetch_all(self.data.schema.on(&mut *tx))
+            .await?;
+
+            existing.extend(
+                records
+                    .into_iter()
+                    .map(|record| (record.dedupe_key, record.id)),
+            );
+        }
+
+        let mut created = created.into_iter();
+        let workflows = dedupe_keys
+            .iter()
+            .map(|key| match key {
+                Some(key) => existing.get(key).copied(),
+                None => created.next(),
+            })
+            .map(|id| id.map(|id| Task { id }))
+            .collect::<Option<Vec<_>>>()
+            .expect("the database did not return a task for every launch");
+
+        // Payloads for tasks that already existed were stored when they were launched.
+        let (payload_ids, payloads): (Vec<i64>, Vec<_>) = payloads
+            .into_iter()
+            .map(|(index, payload)| (workflows[index].id, payload))
+            .filter(|(id, _)| inserted.contains(id))
+            .unzip();
+
+        if !payload_ids.is_empty() {
+            sqlx::query!(
+                "
+                INSERT INTO durable.task_payload(task_id, data)
+                SELECT * FROM UNNEST($1::bigint[], $2::jsonb[])
+                ON CONFLICT DO NOTHING
+                ",
+                &payload_ids,
+                &payloads as &[Json<Box<RawValue>>]
+            )
+            .execute(self.data.schema.on(&mut *tx))
+            .await?;
+        }
+
+        // Tasks that already existed keep whatever dependencies they were originally
+        // launched with.
+        let mut dependents = Vec::new();
+        let mut depends_on = Vec::new();
+        let mut propagate = Vec::new();
+        for (task, (after, policy)) in workflows.iter().zip(dependencies) {
+            if !inserted.contains(&task.id) {
+                continue;
+            }
+
+            for id in after {
+                dependents.push(task.id);
+                depends_on.push(id);
+                propagate.push(policy == DependencyFailure::Propagate);
+            }
+        }
+
+        if !depends_on.is_empty() {
+            let found = sqlx::query_scalar!(
+                "
+                INSERT INTO durable.task_dependency(task_id, depends_on, propagate_failure)
+                SELECT t.task_id, t.depends_on, t.propagate_failure
+                 FROM UNNEST($1::bigint[], $2::bigint[], $3::bool[])
+                    as t(task_id, depends_on, propagate_failure)
+                 JOIN durable.task ON task.id = t.depends_on
+                WHERE task.tenant IS NOT DISTINCT FROM $4
+                ON CONFLICT DO NOTHING
+                RETURNING depends_on
+                ",
+                &dependents,
+                &depends_on,
+                &propagate,
+                self.tenant()
+            )
+            .fetch_all(self.data.schema.on(&mut *tx))
+            .await?;
+
+            let found: HashSet<i64> = found.into_iter().collect();
+            if let Some(&id) = depends_on.iter().find(|id| !found.contains(id)) {
+                return Err(ErrorImpl::NonexistantTaskId(id).into());
+            }
+        }
+
         tx.commit().await?;
         Ok(workflows)
     }
+
+    /// Wait for all of `tasks` to complete.
+    ///
+    /// This returns the exit status of each task in the same order as
+    /// `tasks`. It is more efficient than calling [`Task::wait`] for each task
+    /// since it only needs a single connection to listen for all of them.
+    ///
+    /// If `timeout` is set and any of the tasks have not completed by then,
+    /// then this returns an error for which [`DurableError::is_timeout`] is
+    /// true.
+    pub async fn wait_many(
+        &self,
+        tasks: &[Task],
+        timeout: Option<std::time::Duration>,
+    ) -> Result<Vec<ExitStatus>, DurableError> {
+        crate::task::wait_many(self, tasks, timeout).await
+    }
+
+    /// Approve `key` for a task that is waiting on an approval.
+    ///
+    /// The approval is recorded in the database and then delivered to the task
+    /// as a notification, along with `payload`. Each key can only be approved
+    /// once per task.
+    pub async fn approve<T>(&self, task: &Task, key: &str, payload: &T) -> Result<(), DurableError>
+    where
+        T: ?Sized + serde::Serialize,
+    {
+        self._approve(task, key, None, payload).await
+    }
+
+    /// Approve `key` for a task, recording `approver` as the one who gave the
+    /// approval.
+    ///
+    /// See [`approve`](DurableClient::approve) for details.
+    pub async fn approve_as<T>(
+        &self,
+        task: &Task,
+        key: &str,
+        approver: &str,
+        payload: &T,
+    ) -> Result<(), DurableError>
+    where
+        T: ?Sized + serde::Serialize,
+    {
+        self._approve(task, key, Some(approver), payload).await
+    }
+
+    async fn _approve<T>(
+        &self,
+        task: &Task,
+        key: &str,
+        approver: Option<&str>,
+        payload: &T,
+    ) -> Result<(), DurableError>
+    where
+        T: ?Sized + serde::Serialize,
+    {
+        let mut tx = self.pool.begin().await?;
+
+        let approved_at = sqlx::query_scalar!(
+            "
+            INSERT INTO durable.approval(task_id, key, payload, approved_by)
+            SELECT id, $2, $3, $4
+             FROM durable.task
+            WHERE id = $1
+              AND tenant IS NOT DISTINCT FROM $5
+            ON CONFLICT DO NOTHING
+            RETURNING approved_at
+            ",
+            task.id(),
+            key,
+            Json(payload) as Json<&T>,
+            approver,
+            self.tenant()
+        )
+        .fetch_optional(self.data.schema.on(&mut *tx))
+        .await?;
+
+        let Some(approved_at) = approved_at else {
+            if !task
+                .exists(self.tenant(), &self.data.schema, &mut tx)
+                .await?
+            {
+                return Err(ErrorImpl::NonexistantTaskId(task.id()).into());
+            }
+
+            return Err(ErrorImpl::AlreadyApproved(task.id(), key.to_owned()).into());
+        };
+
+        sqlx::query!(
+            "
+            INSERT INTO durable.notification(task_id, created_at, event, data)
+            VALUES ($1, $2, 'durable:approval', jsonb_build_object(
+                'key', $3::text,
+                'payload', $4::jsonb,
+                'approved_by', $5::text
+            ))
+            ",
+            task.id(),
+            approved_at,
+            key,
+            Json(payload) as Json<&T>,
+            approver
+        )
+        .execute(self.data.schema.on(&mut *tx))
+        .await?;
+
+        tx.commit().await?;
+        Ok(())
+    }
 }
 
 #[derive(Clone, Debug)]
 pub struct LaunchOptions<'a, T> {
     name: Cow<'a, str>,
     data: T,
+    sql_context: BTreeMap<String, String>,
+    labels: BTreeMap<String, String>,
+    entrypoint: Option<Cow<'a, str>>,
+    dedupe_key: Option<String>,
+    run_at: Option<DateTime<Utc>>,
+    after: Vec<i64>,
+    dependency_failure: DependencyFailure,
 }
 
 impl<'a, T> LaunchOptions<'a, T> {
@@ -293,8 +798,151 @@ impl<'a, T> LaunchOptions<'a, T> {
         Self {
             name: name.into(),
             data,
+            sql_context: BTreeMap::new(),
+            labels: BTreeMap::new(),
+            entrypoint: None,
+            dedupe_key: None,
+            run_at: None,
+            after: Vec::new(),
+            dependency_failure: DependencyFailure::Propagate,
         }
     }
+
+    /// Set a SQL configuration parameter for the task.
+    ///
+    /// The runtime sets each of these parameters, as if by `SET LOCAL`, at
+    /// the start of every database transaction run by the task. This allows
+    /// the task's queries to be subject to row-level security policies that
+    /// use `current_setting`:
+    ///
+    /// ```
+    /// # use durable_client::LaunchOptions;
+    /// let options = LaunchOptions::new("send-invoice", ()).sql_context("app.user_id", "42");
+    /// ```
+    ///
+    /// Only custom parameters (those with a `.` in their name) may be set.
+    /// Tasks that have any other parameters set will fail when they start a
+    /// database transaction.
+    pub fn sql_context(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.sql_context.insert(key.into(), value.into());
+        self
+    }
+
+    /// Attach a key/value label to the task.
+    ///
+    /// Labels are not used by the runtime itself. They can be used to filter
+    /// tasks in [`DurableClient::list_tasks`] and are readable by the workflow.
+    ///
+    /// ```
+    /// # use durable_client::LaunchOptions;
+    /// let options = LaunchOptions::new("send-invoice", ()).label("customer", "acme");
+    /// ```
+    ///
+    /// Setting the same key twice keeps the last value.
+    pub fn label(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.labels.insert(key.into(), value.into());
+        self
+    }
+
+    /// Run a specific workflow exported by the program.
+    ///
+    /// Programs can export several named workflows through the
+    /// `durable:core/workflow` interface (see `durable::entrypoints!`). Tasks
+    /// launched with an entrypoint have the runtime call that export with the
+    /// given name instead of running the program's `main` function.
+    ///
+    /// ```
+    /// # use durable_client::LaunchOptions;
+    /// let options = LaunchOptions::new("nightly sync", ()).entrypoint("sync-orders");
+    /// ```
+    ///
+    /// Launching a task with an entrypoint for a program which does not export
+    /// the `durable:core/workflow` interface will cause the task to fail.
+    pub fn entrypoint(mut self, entrypoint: impl Into<Cow<'a, str>>) -> Self {
+        self.entrypoint = Some(entrypoint.into());
+        self
+    }
+
+    /// Make this launch idempotent.
+    ///
+    /// If a task with the same dedupe key has already been launched by a
+    /// client belonging to the same tenant then that task is returned instead
+    /// of creating a new one. This holds regardless of the state of the
+    /// existing task, so long as it has not been deleted.
+    ///
+    /// ```
+    /// # use durable_client::LaunchOptions;
+    /// let options = LaunchOptions::new("send-invoice", ()).dedupe_key("invoice-1234");
+    /// ```
+    ///
+    /// Note that the other launch options are not compared. Launching with an
+    /// existing key but a different name or data still returns the existing
+    /// task.
+    pub fn dedupe_key(mut self, key: impl Into<String>) -> Self {
+        self.dedupe_key = Some(key.into());
+        self
+    }
+
+    /// Don't start the task until the given time.
+    ///
+    /// The task is created immediately, and is visible to the client as a
+    /// ready task, but no worker will pick it up until `run_at` has passed.
+    /// Times in the past have no effect.
+    ///
+    /// ```
+    /// # use durable_client::LaunchOptions;
+    /// let tomorrow = chrono::Utc::now() + chrono::Duration::days(1);
+    /// let options = LaunchOptions::new("send-reminder", ()).run_at(tomorrow);
+    /// ```
+    pub fn run_at(mut self, run_at: DateTime<Utc>) -> Self {
+        self.run_at = Some(run_at);
+        self
+    }
+
+    /// Don't start the task until all of `tasks` have completed successfully.
+    ///
+    /// What happens if one of them fails is controlled by
+    /// [`dependency_failure`](LaunchOptions::dependency_failure). By default,
+    /// the new task is marked as failed without ever running.
+    ///
+    /// ```no_run
+    /// # use durable_client::{DurableClient, LaunchOptions, Program};
+    /// # async fn example(client: &DurableClient, program: &Program) -> Result<(), durable_client::DurableError> {
+    /// let a = client.launch("a", program, &()).await?;
+    /// let c = client.launch("c", program, &()).await?;
+    /// let b = client
+    ///     .launch_many(program, [LaunchOptions::new("b", ()).after(&[a, c])])
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// All the tasks must belong to the same tenant as the client launching
+    /// the new task.
+    pub fn after<'t>(mut self, tasks: impl IntoIterator<Item = &'t Task>) -> Self {
+        self.after.extend(tasks.into_iter().map(|task| task.id()));
+        self
+    }
+
+    /// Control what happens to this task if one of the tasks it was launched
+    /// [`after`](LaunchOptions::after) fails.
+    pub fn dependency_failure(mut self, policy: DependencyFailure) -> Self {
+        self.dependency_failure = policy;
+        self
+    }
+}
+
+/// What to do with a task when one of the tasks that it depends on fails.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub enum DependencyFailure {
+    /// Mark the task as failed without running it.
+    ///
+    /// Any tasks which depend on it will then fail in turn.
+    #[default]
+    Propagate,
+
+    /// Run the task anyway, as if the dependency had completed successfully.
+    Ignore,
 }
 
 fn supported_wasm_features() -> wasmparser::WasmFeatures {
@@ -0,0 +1,209 @@
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Utc};
+use sqlx::types::Json;
+
+use crate::{DurableClient, DurableError, Task, TaskState};
+
+/// The maximum number of tasks returned by a single call to
+/// [`DurableClient::list_tasks`].
+const MAX_LIST_LIMIT: i64 = 1000;
+
+/// Filters for [`DurableClient::list_tasks`].
+///
+/// Tasks must match all of the filters that are set in order to be returned.
+///
+/// ```
+/// # use durable_client::{TaskFilter, TaskState};
+/// let filter = TaskFilter::new()
+///     .state(TaskState::Failed)
+///     .label("customer", "acme")
+///     .limit(20);
+/// ```
+#[derive(Clone, Debug)]
+pub struct TaskFilter {
+    state: Option<TaskState>,
+    name: Option<String>,
+    instance: Option<String>,
+    labels: BTreeMap<String, String>,
+    before: Option<i64>,
+    limit: i64,
+}
+
+impl TaskFilter {
+    pub fn new() -> Self {
+        Self {
+            state: None,
+            name: None,
+            instance: None,
+            labels: BTreeMap::new(),
+            before: None,
+            limit: 100,
+        }
+    }
+
+    /// Only return tasks in the given state.
+    pub fn state(mut self, state: TaskState) -> Self {
+        self.state = Some(state);
+        self
+    }
+
+    /// Only return tasks with exactly this name.
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Only return tasks that belong to this instance.
+    ///
+    /// See [`DurableClient::with_instance`].
+    pub fn instance(mut self, instance: impl Into<String>) -> Self {
+        self.instance = Some(instance.into());
+        self
+    }
+
+    /// Only return tasks that were launched with this label.
+    ///
+    /// This can be called multiple times, in which case tasks must have all
+    /// of the labels.
+    pub fn label(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.labels.insert(key.into(), value.into());
+        self
+    }
+
+    /// Only return tasks with an id less than `id`.
+    ///
+    /// Tasks are returned most recent first, so passing the id of the last
+    /// task in one page returns the next page.
+    pub fn before(mut self, id: i64) -> Self {
+        self.before = Some(id);
+        self
+    }
+
+    /// The maximum number of tasks to return. This defaults to 100 and is
+    /// capped at 1000.
+    pub fn limit(mut self, limit: i64) -> Self {
+        self.limit = limit;
+        self
+    }
+}
+
+impl Default for TaskFilter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A task returned by [`DurableClient::list_tasks`].
+#[derive(Clone, Debug)]
+pub struct TaskSummary {
+    id: i64,
+    name: String,
+    state: TaskState,
+    instance: Option<String>,
+    labels: BTreeMap<String, String>,
+    created_at: DateTime<Utc>,
+    completed_at: Option<DateTime<Utc>>,
+    paused_at: Option<DateTime<Utc>>,
+}
+
+impl TaskSummary {
+    /// A handle to the task.
+    pub fn task(&self) -> Task {
+        Task::from_id(self.id)
+    }
+
+    /// The id of the task.
+    pub fn id(&self) -> i64 {
+        self.id
+    }
+
+    /// The name that the task was launched with.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The state of the task at the time that it was listed.
+    pub fn state(&self) -> TaskState {
+        self.state
+    }
+
+    /// The instance that the task belongs to, if any.
+    pub fn instance(&self) -> Option<&str> {
+        self.instance.as_deref()
+    }
+
+    /// The labels that the task was launched with.
+    pub fn labels(&self) -> &BTreeMap<String, String> {
+        &self.labels
+    }
+
+    /// When the task was launched.
+    pub fn created_at(&self) -> DateTime<Utc> {
+        self.created_at
+    }
+
+    /// When the task completed, if it has.
+    pub fn completed_at(&self) -> Option<DateTime<Utc>> {
+        self.completed_at
+    }
+
+    /// When the task was paused, if it is currently paused.
+    ///
+    /// See [`Task::pause`].
+    pub fn paused_at(&self) -> Option<DateTime<Utc>> {
+        self.paused_at
+    }
+}
+
+impl DurableClient {
+    /// List the tasks belonging to this client's tenant that match `filter`,
+    /// most recent first.
+    pub async fn list_tasks(&self, filter: &TaskFilter) -> Result<Vec<TaskSummary>, DurableError> {
+        let records = sqlx::query!(
+            r#"
+            SELECT
+                id,
+                name,
+                state::text as "state!",
+                instance,
+                labels as "labels: Json<BTreeMap<String, String>>",
+                created_at,
+                completed_at,
+                paused_at
+             FROM durable.task
+            WHERE tenant IS NOT DISTINCT FROM $1
+              AND ($2::text IS NULL OR state::text = $2)
+              AND ($3::text IS NULL OR name = $3)
+              AND ($4::bigint IS NULL OR id < $4)
+              AND labels @> $5
+              AND ($7::text IS NULL OR instance = $7)
+            ORDER BY id DESC
+            LIMIT $6
+            "#,
+            self.tenant(),
+            filter.state.map(TaskState::as_str),
+            filter.name.as_deref(),
+            filter.before,
+            Json(&filter.labels) as Json<&BTreeMap<String, String>>,
+            filter.limit.clamp(1, MAX_LIST_LIMIT),
+            filter.instance.as_deref()
+        )
+        .fetch_all(self.data.schema.on(&self.pool))
+        .await?;
+
+        Ok(records
+            .into_iter()
+            .map(|record| TaskSummary {
+                id: record.id,
+                name: record.name,
+                state: TaskState::from_str(&record.state),
+                instance: record.instance,
+                labels: record.labels.0,
+                created_at: record.created_at,
+                completed_at: record.completed_at,
+                paused_at: record.paused_at,
+            })
+            .collect())
+    }
+}
@@ -0,0 +1,225 @@
+use std::fmt;
+
+use chrono::{DateTime, Utc};
+
+use crate::error::ErrorImpl;
+use crate::{DurableClient, DurableError, Task};
+
+/// The maximum number of log entries returned by a single call to
+/// [`Task::logs`].
+const MAX_LOG_LIMIT: i64 = 10000;
+
+/// The level of a [`LogEntry`].
+///
+/// Tasks write their logs as plain text so the level is inferred from the
+/// start of each line. Lines that start with a level name, such as
+/// `WARN retrying request` or `[error] request failed`, optionally after a
+/// timestamp, are given that level. Errors that caused the task to fail are
+/// always at the [`Error`](LogLevel::Error) level. Everything else is at the
+/// [`Info`](LogLevel::Info) level.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum LogLevel {
+    Trace,
+    Debug,
+    Info,
+    Warn,
+    Error,
+}
+
+impl LogLevel {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Trace => "trace",
+            Self::Debug => "debug",
+            Self::Info => "info",
+            Self::Warn => "warn",
+            Self::Error => "error",
+        }
+    }
+
+    pub(crate) fn from_str(level: &str) -> Self {
+        match level {
+            "trace" => Self::Trace,
+            "debug" => Self::Debug,
+            "warn" => Self::Warn,
+            "error" => Self::Error,
+            _ => Self::Info,
+        }
+    }
+}
+
+impl fmt::Display for LogLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Filters for [`Task::logs`].
+///
+/// Log entries must match all of the filters that are set in order to be
+/// returned.
+///
+/// ```
+/// # use durable_client::{LogFilter, LogLevel};
+/// let filter = LogFilter::new()
+///     .level(LogLevel::Warn)
+///     .contains("timeout")
+///     .limit(50);
+/// ```
+#[derive(Clone, Debug)]
+pub struct LogFilter {
+    level: Option<LogLevel>,
+    contains: Option<String>,
+    after: Option<(i32, i32)>,
+    limit: i64,
+}
+
+impl LogFilter {
+    pub fn new() -> Self {
+        Self {
+            level: None,
+            contains: None,
+            after: None,
+            limit: 1000,
+        }
+    }
+
+    /// Only return entries at `level` or above.
+    pub fn level(mut self, level: LogLevel) -> Self {
+        self.level = Some(level);
+        self
+    }
+
+    /// Only return entries whose message contains `text`, ignoring case.
+    pub fn contains(mut self, text: impl Into<String>) -> Self {
+        self.contains = Some(text.into());
+        self
+    }
+
+    /// Only return entries that come after `entry`.
+    ///
+    /// Entries are returned in the order that they were written, so passing
+    /// the last entry in one page returns the next page.
+    pub fn after(mut self, entry: &LogEntry) -> Self {
+        self.after = Some((entry.index, entry.line));
+        self
+    }
+
+    /// The maximum number of entries to return. This defaults to 1000 and is
+    /// capped at 10000.
+    pub fn limit(mut self, limit: i64) -> Self {
+        self.limit = limit;
+        self
+    }
+}
+
+impl Default for LogFilter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A single line of a task's logs, as returned by [`Task::logs`].
+#[derive(Clone, Debug)]
+pub struct LogEntry {
+    index: i32,
+    line: i32,
+    level: LogLevel,
+    message: String,
+    created_at: DateTime<Utc>,
+}
+
+impl LogEntry {
+    /// The index of the transaction that this entry was written during.
+    ///
+    /// Anything logged outside of a transaction is recorded under the index
+    /// of the next transaction.
+    pub fn index(&self) -> i32 {
+        self.index
+    }
+
+    /// The line number of this entry within the logs written during its
+    /// transaction, starting from 0.
+    pub fn line(&self) -> i32 {
+        self.line
+    }
+
+    /// The level of this entry.
+    pub fn level(&self) -> LogLevel {
+        self.level
+    }
+
+    /// The message, without a trailing newline.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// When the logs containing this entry were first saved.
+    pub fn created_at(&self) -> DateTime<Utc> {
+        self.created_at
+    }
+}
+
+impl Task {
+    /// Read the lines of the task's logs that match `filter`, in the order
+    /// that they were written.
+    ///
+    /// Unlike [`read_logs`](Task::read_logs), this returns the logs one line
+    /// at a time and only returns a single page of them. Use
+    /// [`LogFilter::after`] to fetch the following pages.
+    pub async fn logs(
+        &self,
+        client: &DurableClient,
+        filter: &LogFilter,
+    ) -> Result<Vec<LogEntry>, DurableError> {
+        let mut conn = client.pool.acquire().await?;
+        let (after_index, after_line) = filter.after.unzip();
+
+        let records = sqlx::query!(
+            r#"
+            SELECT index, line, level::text as "level!", message, created_at
+             FROM durable.log_entry
+            WHERE task_id = $1
+              AND EXISTS(
+                SELECT 1
+                 FROM durable.task
+                WHERE id = $1
+                  AND tenant IS NOT DISTINCT FROM $2
+              )
+              AND ($3::text IS NULL OR level >= $3::text::durable.log_level)
+              AND ($4::int IS NULL OR (index, line) > ($4, $5::int))
+              AND ($6::text IS NULL OR strpos(lower(message), lower($6)) > 0)
+            ORDER BY index ASC, line ASC
+            LIMIT $7
+            "#,
+            self.id,
+            client.tenant(),
+            filter.level.map(LogLevel::as_str),
+            after_index,
+            after_line,
+            filter.contains.as_deref(),
+            filter.limit.clamp(1, MAX_LOG_LIMIT)
+        )
+        .fetch_all(client.data.schema.on(&mut *conn))
+        .await?;
+
+        if records.is_empty()
+            && !self
+                .exists(client.tenant(), &client.data.schema, &mut conn)
+                .await?
+        {
+            return Err(ErrorImpl::NonexistantTaskId(self.id).into());
+        }
+
+        Ok(records
+            .into_iter()
+            .map(|record| LogEntry {
+                index: record.index,
+                line: record.line,
+                level: LogLevel::from_str(&record.level),
+                message: record.message,
+                created_at: record.created_at,
+            })
+            .collect())
+    }
+}
@@ -0,0 +1,205 @@
+use chrono::{DateTime, Utc};
+
+use crate::error::ErrorImpl;
+use crate::{DurableClient, DurableError};
+
+/// What a [`Pause`] applies to.
+#[non_exhaustive]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum PauseTarget {
+    /// All tasks running the program with this id.
+    Program(i64),
+
+    /// All tasks in the queue with this name.
+    ///
+    /// Queues are named after the tenant that their tasks belong to. Tasks
+    /// that don't belong to a tenant are in the `""` queue.
+    Queue(String),
+}
+
+/// A pause on a program or queue, returned by [`DurableClient::pauses`].
+#[derive(Clone, Debug)]
+pub struct Pause {
+    target: PauseTarget,
+    reason: Option<String>,
+    created_at: DateTime<Utc>,
+}
+
+impl Pause {
+    /// The program or queue that is paused.
+    pub fn target(&self) -> &PauseTarget {
+        &self.target
+    }
+
+    /// The reason that was given when pausing it, if any.
+    pub fn reason(&self) -> Option<&str> {
+        self.reason.as_deref()
+    }
+
+    /// When it was paused.
+    pub fn created_at(&self) -> DateTime<Utc> {
+        self.created_at
+    }
+}
+
+impl DurableClient {
+    /// The name of the queue that tasks launched by this client go into.
+    fn queue(&self) -> &str {
+        self.tenant().unwrap_or_default()
+    }
+
+    /// Pause all tasks running the program with id `program`.
+    ///
+    /// Workers stop claiming tasks that run the program, and tasks that are
+    /// already running are stopped at their next transaction boundary. They
+    /// pick up where they left off once the program is resumed with
+    /// [`resume_program`](DurableClient::resume_program).
+    ///
+    /// Returns `false` if the program was already paused.
+    pub async fn pause_program(
+        &self,
+        program: i64,
+        reason: Option<&str>,
+    ) -> Result<bool, DurableError> {
+        let mut tx = self.pool.begin().await?;
+        self.check_program(program, &mut tx).await?;
+
+        let result = sqlx::query!(
+            "
+            INSERT INTO durable.program_pause(wasm, reason)
+            VALUES ($1, $2)
+            ON CONFLICT DO NOTHING
+            ",
+            program,
+            reason
+        )
+        .execute(self.data.schema.on(&mut *tx))
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(result.rows_affected() != 0)
+    }
+
+    /// Resume the tasks of a program that was paused with
+    /// [`pause_program`](DurableClient::pause_program).
+    ///
+    /// Returns `false` if the program was not paused.
+    pub async fn resume_program(&self, program: i64) -> Result<bool, DurableError> {
+        let mut tx = self.pool.begin().await?;
+        self.check_program(program, &mut tx).await?;
+
+        let result = sqlx::query!("DELETE FROM durable.program_pause WHERE wasm = $1", program)
+            .execute(self.data.schema.on(&mut *tx))
+            .await?;
+
+        tx.commit().await?;
+
+        Ok(result.rows_affected() != 0)
+    }
+
+    /// Pause all tasks in this client's queue.
+    ///
+    /// This works like [`pause_program`](DurableClient::pause_program) but
+    /// applies to every task belonging to the client's tenant.
+    ///
+    /// Returns `false` if the queue was already paused.
+    pub async fn pause_queue(&self, reason: Option<&str>) -> Result<bool, DurableError> {
+        let result = sqlx::query!(
+            "
+            INSERT INTO durable.queue_pause(queue, reason)
+            VALUES ($1, $2)
+            ON CONFLICT DO NOTHING
+            ",
+            self.queue(),
+            reason
+        )
+        .execute(self.data.schema.on(&self.pool))
+        .await?;
+
+        Ok(result.rows_affected() != 0)
+    }
+
+    /// Resume the tasks in this client's queue after it was paused with
+    /// [`pause_queue`](DurableClient::pause_queue).
+    ///
+    /// Returns `false` if the queue was not paused.
+    pub async fn resume_queue(&self) -> Result<bool, DurableError> {
+        let result = sqlx::query!(
+            "DELETE FROM durable.queue_pause WHERE queue = $1",
+            self.queue()
+        )
+        .execute(self.data.schema.on(&self.pool))
+        .await?;
+
+        Ok(result.rows_affected() != 0)
+    }
+
+    /// List the pauses that apply to this client's programs and queue.
+    pub async fn pauses(&self) -> Result<Vec<Pause>, DurableError> {
+        let programs = sqlx::query!(
+            "
+            SELECT p.wasm, p.reason, p.created_at
+             FROM durable.program_pause p
+             JOIN durable.wasm ON wasm.id = p.wasm
+            WHERE wasm.tenant IS NOT DISTINCT FROM $1
+            ORDER BY p.created_at ASC
+            ",
+            self.tenant()
+        )
+        .fetch_all(self.data.schema.on(&self.pool))
+        .await?;
+
+        let queue = sqlx::query!(
+            "
+            SELECT reason, created_at
+             FROM durable.queue_pause
+            WHERE queue = $1
+            ",
+            self.queue()
+        )
+        .fetch_optional(self.data.schema.on(&self.pool))
+        .await?;
+
+        let mut pauses: Vec<_> = queue
+            .into_iter()
+            .map(|record| Pause {
+                target: PauseTarget::Queue(self.queue().to_owned()),
+                reason: record.reason,
+                created_at: record.created_at,
+            })
+            .collect();
+
+        pauses.extend(programs.into_iter().map(|record| Pause {
+            target: PauseTarget::Program(record.wasm),
+            reason: record.reason,
+            created_at: record.created_at,
+        }));
+
+        Ok(pauses)
+    }
+
+    /// Check that `program` exists and belongs to this client's tenant.
+    async fn check_program(
+        &self,
+        program: i64,
+        conn: &mut sqlx::PgConnection,
+    ) -> Result<(), DurableError> {
+        sqlx::query!(
+            "
+            SELECT id
+             FROM durable.wasm
+            WHERE id = $1
+              AND tenant IS NOT DISTINCT FROM $2
+            FOR SHARE
+            ",
+            program,
+            self.tenant()
+        )
+        .fetch_optional(self.data.schema.on(&mut *conn))
+        .await?
+        .ok_or(ErrorImpl::NonexistantProgramId(program))?;
+
+        Ok(())
+    }
+}
@@ -5,8 +5,10 @@ use std::sync::atomic::{AtomicI64, Ordering};
 use std::sync::Arc;
 
 use chrono::{DateTime, Utc};
+use durable_migrate::SchemaRename;
 use sqlx::PgConnection;
 
+use crate::schema::DataSchema;
 use crate::util::LockCell;
 
 pub(crate) type ProgramHash = [u8; 32];
@@ -15,6 +17,19 @@ pub(crate) type ProgramHash = [u8; 32];
 pub struct ProgramOptions {
     pub(crate) name: Option<Cow<'static, str>>,
     pub(crate) wasm: Cow<'static, [u8]>,
+    pub(crate) data_schema: Option<serde_json::Value>,
+    pub(crate) metadata: ProgramMetadata,
+    #[cfg(feature = "precompile")]
+    pub(crate) precompile: Vec<wasmtime::Config>,
+}
+
+/// Optional build metadata that is stored alongside a program.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct ProgramMetadata {
+    pub(crate) version: Option<semver::Version>,
+    pub(crate) description: Option<Cow<'static, str>>,
+    pub(crate) git_commit: Option<Cow<'static, str>>,
+    pub(crate) built_at: Option<DateTime<Utc>>,
 }
 
 impl ProgramOptions {
@@ -23,6 +38,10 @@ impl ProgramOptions {
         Self {
             wasm: wasm.into(),
             name: None,
+            data_schema: None,
+            metadata: ProgramMetadata::default(),
+            #[cfg(feature = "precompile")]
+            precompile: Vec::new(),
         }
     }
 
@@ -50,20 +69,104 @@ impl ProgramOptions {
 
     /// Set a name to be associated with this program.
     ///
-    /// This name is a user-readable name that can be viewed at a lated date. It
-    /// has no semantic meaning to the runtime.
+    /// This name is a user-readable name that can be viewed at a lated date.
+    /// Workers may also use it to select the SQL policy that applies to tasks
+    /// running the program.
     pub fn name(mut self, name: impl Into<Cow<'static, str>>) -> Self {
         self.name = Some(name.into());
         self
     }
+
+    /// Set a [JSON Schema] that the data of tasks launched with this program
+    /// must match.
+    ///
+    /// The data for each task is validated against the schema when it is
+    /// launched, so data that the workflow would not be able to deserialize
+    /// is rejected up front instead of causing the task to fail later on.
+    /// Launches with invalid data return an error for which
+    /// [`DurableError::validation_error`] returns the details.
+    ///
+    /// The schema is only known to the [`Program`] returned by
+    /// [`DurableClient::program`]. It is not stored in the database, so tasks
+    /// launched by other means are not validated.
+    ///
+    /// [JSON Schema]: https://json-schema.org
+    /// [`DurableError::validation_error`]: crate::DurableError::validation_error
+    /// [`DurableClient::program`]: crate::DurableClient::program
+    pub fn data_schema(mut self, schema: serde_json::Value) -> Self {
+        self.data_schema = Some(schema);
+        self
+    }
+
+    /// Set the semantic version of this program.
+    ///
+    /// Programs with a version can be looked up by name and version
+    /// requirement using [`DurableClient::program_by_name_and_version`].
+    ///
+    /// [`DurableClient::program_by_name_and_version`]: crate::DurableClient::program_by_name_and_version
+    pub fn version(mut self, version: semver::Version) -> Self {
+        self.metadata.version = Some(version);
+        self
+    }
+
+    /// Set a human-readable description of this program.
+    pub fn description(mut self, description: impl Into<Cow<'static, str>>) -> Self {
+        self.metadata.description = Some(description.into());
+        self
+    }
+
+    /// Set the git commit that this program was built from.
+    pub fn git_commit(mut self, commit: impl Into<Cow<'static, str>>) -> Self {
+        self.metadata.git_commit = Some(commit.into());
+        self
+    }
+
+    /// Set the time at which this program was built.
+    pub fn built_at(mut self, built_at: DateTime<Utc>) -> Self {
+        self.metadata.built_at = Some(built_at);
+        self
+    }
+
+    /// Precompile the program using the provided wasmtime config when it is
+    /// uploaded.
+    ///
+    /// The resulting artifact is stored in the database alongside the program.
+    /// Workers that have `load_precompiled_programs` enabled and whose engine
+    /// configuration is compatible with `config` will load the artifact
+    /// directly instead of compiling the program on first use. The config
+    /// should match the one passed to the worker, including the target if the
+    /// workers run on a different platform than the client.
+    ///
+    /// This can be called multiple times in order to precompile the program
+    /// for multiple different worker configurations. Async support is always
+    /// enabled on the worker, so it is also enabled on `config` here.
+    #[cfg(feature = "precompile")]
+    pub fn precompile(mut self, mut config: wasmtime::Config) -> Self {
+        config.async_support(true);
+        self.precompile.push(config);
+        self
+    }
 }
 
 #[derive(Clone, Debug)]
-pub struct Program(pub(crate) Arc<ProgramData>);
+pub struct Program(
+    pub(crate) Arc<ProgramData>,
+    pub(crate) Option<Arc<DataSchema>>,
+);
 
 impl Program {
-    pub(crate) fn new(data: Arc<ProgramData>) -> Self {
-        Self(data)
+    pub(crate) fn new(data: Arc<ProgramData>, schema: Option<Arc<DataSchema>>) -> Self {
+        Self(data, schema)
+    }
+
+    /// The id of the program in the database.
+    pub fn id(&self) -> i64 {
+        self.0.id()
+    }
+
+    /// The semantic version of the program, if it has one.
+    pub fn version(&self) -> Option<&semver::Version> {
+        self.0.metadata.version.as_ref()
     }
 }
 
@@ -73,6 +176,8 @@ pub(crate) struct ProgramData {
     pub(crate) hash: ProgramHash,
     pub(crate) wasm: Cow<'static, [u8]>,
     pub(crate) name: Option<Cow<'static, str>>,
+    pub(crate) metadata: ProgramMetadata,
+    pub(crate) tenant: Option<Arc<str>>,
     pub(crate) last_used: LockCell<DateTime<Utc>>,
 }
 
@@ -85,22 +190,36 @@ impl ProgramData {
         hash: ProgramHash,
         wasm: Cow<'static, [u8]>,
         name: Option<Cow<'static, str>>,
+        metadata: ProgramMetadata,
+        tenant: Option<Arc<str>>,
+        schema: &SchemaRename,
         conn: &mut PgConnection,
     ) -> sqlx::Result<Self> {
         let record = sqlx::query!(
             "
-            INSERT INTO durable.wasm(hash, wasm, name)
-            VALUES ($1, $2, $3)
-            ON CONFLICT ON CONSTRAINT hash_unique
+            INSERT INTO durable.wasm(
+                hash, wasm, name, tenant, version, description, git_commit, built_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            ON CONFLICT (hash, (COALESCE(tenant, '')))
             DO UPDATE
-            SET last_used = CURRENT_TIMESTAMP
+            SET last_used = CURRENT_TIMESTAMP,
+                version = COALESCE(EXCLUDED.version, wasm.version),
+                description = COALESCE(EXCLUDED.description, wasm.description),
+                git_commit = COALESCE(EXCLUDED.git_commit, wasm.git_commit),
+                built_at = COALESCE(EXCLUDED.built_at, wasm.built_at)
             RETURNING id, last_used
             ",
             hash as ProgramHash,
             &wasm as &[u8],
-            name.as_deref()
+            name.as_deref(),
+            tenant.as_deref(),
+            metadata.version.as_ref().map(ToString::to_string),
+            metadata.description.as_deref(),
+            metadata.git_commit.as_deref(),
+            metadata.built_at
         )
-        .fetch_one(&mut *conn)
+        .fetch_one(schema.on(&mut *conn))
         .await?;
 
         Ok(Self {
@@ -108,25 +227,42 @@ impl ProgramData {
             hash,
             wasm,
             name,
+            metadata,
+            tenant,
             last_used: LockCell::new(record.last_used),
         })
     }
 
-    pub async fn reregister(&self, conn: &mut PgConnection) -> sqlx::Result<()> {
+    pub async fn reregister(
+        &self,
+        schema: &SchemaRename,
+        conn: &mut PgConnection,
+    ) -> sqlx::Result<()> {
         let record = sqlx::query!(
             "
-            INSERT INTO durable.wasm(hash, wasm, name)
-            VALUES ($1, $2, $3)
-            ON CONFLICT ON CONSTRAINT hash_unique
+            INSERT INTO durable.wasm(
+                hash, wasm, name, tenant, version, description, git_commit, built_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            ON CONFLICT (hash, (COALESCE(tenant, '')))
             DO UPDATE
-            SET last_used = CURRENT_TIMESTAMP
+            SET last_used = CURRENT_TIMESTAMP,
+                version = COALESCE(EXCLUDED.version, wasm.version),
+                description = COALESCE(EXCLUDED.description, wasm.description),
+                git_commit = COALESCE(EXCLUDED.git_commit, wasm.git_commit),
+                built_at = COALESCE(EXCLUDED.built_at, wasm.built_at)
             RETURNING id, last_used
             ",
             self.hash as ProgramHash,
             &self.wasm as &[u8],
-            self.name.as_deref()
+            self.name.as_deref(),
+            self.tenant.as_deref(),
+            self.metadata.version.as_ref().map(ToString::to_string),
+            self.metadata.description.as_deref(),
+            self.metadata.git_commit.as_deref(),
+            self.metadata.built_at
         )
-        .fetch_one(&mut *conn)
+        .fetch_one(schema.on(&mut *conn))
         .await?;
 
         self.id.store(record.id, Ordering::Release);
@@ -134,4 +270,69 @@ impl ProgramData {
 
         Ok(())
     }
+
+    /// Precompile this program for each of the provided configs and store the
+    /// resulting artifacts in the database.
+    ///
+    /// Configs for which a compatible artifact already exists are skipped.
+    #[cfg(feature = "precompile")]
+    pub async fn precompile(
+        &self,
+        configs: &[wasmtime::Config],
+        schema: &SchemaRename,
+        conn: &mut PgConnection,
+    ) -> Result<(), crate::DurableError> {
+        use crate::error::ErrorImpl;
+
+        if configs.is_empty() {
+            return Ok(());
+        }
+
+        let mut engines = Vec::with_capacity(configs.len());
+        for config in configs {
+            let engine = wasmtime::Engine::new(config).map_err(ErrorImpl::Precompile)?;
+            let compat = durable_migrate::compat_hash(engine.precompile_compatibility_hash());
+            engines.push((engine, compat));
+        }
+
+        let compat: Vec<_> = engines.iter().map(|(_, compat)| compat.to_vec()).collect();
+        let existing = sqlx::query_scalar!(
+            "SELECT compat
+              FROM durable.wasm_artifact
+             WHERE wasm = $1
+               AND compat = ANY($2)",
+            self.id(),
+            &compat
+        )
+        .fetch_all(schema.on(&mut *conn))
+        .await?;
+
+        let wasm: Arc<[u8]> = Arc::from(&*self.wasm);
+        for (engine, compat) in engines {
+            if existing.iter().any(|hash| hash[..] == compat[..]) {
+                continue;
+            }
+
+            let artifact = tokio::task::spawn_blocking({
+                let wasm = wasm.clone();
+                move || engine.precompile_component(&wasm)
+            })
+            .await
+            .expect("component precompilation panicked")
+            .map_err(ErrorImpl::Precompile)?;
+
+            sqlx::query!(
+                "INSERT INTO durable.wasm_artifact(wasm, compat, artifact)
+                VALUES ($1, $2, $3)
+                ON CONFLICT DO NOTHING",
+                self.id(),
+                &compat as &[u8],
+                &artifact
+            )
+            .execute(schema.on(&mut *conn))
+            .await?;
+        }
+
+        Ok(())
+    }
 }
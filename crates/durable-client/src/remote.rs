@@ -0,0 +1,555 @@
+//! Loading programs from URLs and OCI registries.
+//!
+//! This is only available when the `remote` feature is enabled.
+
+use std::fmt;
+use std::str::FromStr;
+
+use reqwest::header::{HeaderValue, ACCEPT, WWW_AUTHENTICATE};
+use reqwest::{Client, RequestBuilder, Response, StatusCode};
+use sha2::{Digest, Sha256};
+
+use crate::error::ErrorImpl;
+use crate::{DurableError, ProgramOptions};
+
+const DOCKER_HUB: &str = "docker.io";
+const DOCKER_HUB_REGISTRY: &str = "registry-1.docker.io";
+
+/// Manifest media types that we know how to handle.
+const MANIFEST_TYPES: &str = "\
+    application/vnd.oci.image.manifest.v1+json, \
+    application/vnd.oci.image.index.v1+json, \
+    application/vnd.docker.distribution.manifest.v2+json, \
+    application/vnd.docker.distribution.manifest.list.v2+json";
+
+/// Layer media types that are known to contain a WASM component.
+const WASM_LAYERS: &[&str] = &[
+    "application/wasm",
+    "application/vnd.wasm.content.layer.v1+wasm",
+];
+
+impl ProgramOptions {
+    /// Load a program from a URL.
+    ///
+    /// The name of the program is set to the last segment of the URL path.
+    ///
+    /// # Errors
+    /// Returns an error if the request fails or the server responds with a
+    /// non-success status code.
+    pub async fn from_url(url: &str) -> Result<Self, DurableError> {
+        let url = reqwest::Url::parse(url).map_err(|e| ErrorImpl::InvalidUrl(e.to_string()))?;
+        let response = Client::new()
+            .get(url.clone())
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(ErrorImpl::Fetch)?;
+        let wasm = response.bytes().await.map_err(ErrorImpl::Fetch)?;
+
+        let mut opts = Self::new(wasm.to_vec());
+        if let Some(name) = url
+            .path_segments()
+            .and_then(|mut segments| segments.next_back())
+            .filter(|name| !name.is_empty())
+        {
+            opts = opts.name(name.to_owned());
+        }
+
+        Ok(opts)
+    }
+
+    /// Load a program from an OCI registry.
+    ///
+    /// The reference uses the same format as container images, e.g.
+    /// `ghcr.io/org/workflow:v1.2.0` or
+    /// `ghcr.io/org/workflow@sha256:<digest>`. The artifact is expected to
+    /// contain a single layer holding the WASM component, as published by
+    /// tools like `wkg oci push` or `oras push`.
+    ///
+    /// The layer contents are always verified against the digest recorded in
+    /// the manifest. If the reference includes a digest then the manifest is
+    /// verified against it as well, so pulling by digest guarantees that
+    /// exactly the expected program is loaded.
+    ///
+    /// The name of the program is set to the last component of the
+    /// repository.
+    ///
+    /// This uses anonymous access to the registry. Use
+    /// [`from_oci_with_auth`](Self::from_oci_with_auth) to pull from a
+    /// private registry.
+    pub async fn from_oci(reference: &str) -> Result<Self, DurableError> {
+        Self::from_oci_with_auth(reference, RegistryAuth::Anonymous).await
+    }
+
+    /// Load a program from an OCI registry using the provided credentials.
+    ///
+    /// See [`from_oci`](Self::from_oci) for details.
+    pub async fn from_oci_with_auth(
+        reference: &str,
+        auth: RegistryAuth,
+    ) -> Result<Self, DurableError> {
+        let reference: OciReference = reference.parse()?;
+        let mut registry = Registry::new(&reference, auth);
+
+        let manifest = registry.manifest(reference.reference()).await?;
+        if let Some(digest) = reference.digest() {
+            verify_digest(digest, &manifest.bytes)?;
+        }
+
+        let manifest = match manifest.into_image() {
+            Ok(image) => image,
+            Err(index) => {
+                let entry = index
+                    .manifests
+                    .iter()
+                    .find(|entry| {
+                        entry
+                            .platform
+                            .as_ref()
+                            .is_some_and(|platform| platform.architecture == "wasm")
+                    })
+                    .or_else(|| index.manifests.first())
+                    .ok_or_else(|| ErrorImpl::Oci("image index has no manifests".into()))?;
+
+                let manifest = registry.manifest(&entry.digest).await?;
+                verify_digest(&entry.digest, &manifest.bytes)?;
+                manifest.into_image().map_err(|_| {
+                    ErrorImpl::Oci("image index refers to another image index".into())
+                })?
+            }
+        };
+
+        let layer = manifest
+            .layers
+            .iter()
+            .find(|layer| WASM_LAYERS.contains(&layer.media_type.as_str()))
+            .or(match manifest.layers.as_slice() {
+                [layer] => Some(layer),
+                _ => None,
+            })
+            .ok_or_else(|| {
+                ErrorImpl::Oci(format!(
+                    "artifact has {} layers but none of them contain a WASM component",
+                    manifest.layers.len()
+                ))
+            })?;
+
+        let wasm = registry.blob(&layer.digest).await?;
+        verify_digest(&layer.digest, &wasm)?;
+
+        let name = reference
+            .repository()
+            .rsplit('/')
+            .next()
+            .unwrap_or(reference.repository())
+            .to_owned();
+
+        Ok(Self::new(wasm).name(name))
+    }
+}
+
+/// Credentials used when pulling a program from an OCI registry.
+#[derive(Clone, Default)]
+#[non_exhaustive]
+pub enum RegistryAuth {
+    /// Access the registry without any credentials.
+    ///
+    /// Registries that require a bearer token will still be asked for an
+    /// anonymous one.
+    #[default]
+    Anonymous,
+
+    /// Authenticate with a username and password (or access token).
+    Basic { username: String, password: String },
+
+    /// Use a pre-existing bearer token for all requests.
+    Bearer(String),
+}
+
+impl RegistryAuth {
+    /// Create basic auth credentials.
+    pub fn basic(username: impl Into<String>, password: impl Into<String>) -> Self {
+        Self::Basic {
+            username: username.into(),
+            password: password.into(),
+        }
+    }
+
+    /// Use a pre-existing bearer token.
+    pub fn bearer(token: impl Into<String>) -> Self {
+        Self::Bearer(token.into())
+    }
+}
+
+impl fmt::Debug for RegistryAuth {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Anonymous => f.write_str("Anonymous"),
+            Self::Basic { username, .. } => f
+                .debug_struct("Basic")
+                .field("username", username)
+                .finish_non_exhaustive(),
+            Self::Bearer(_) => f.write_str("Bearer(..)"),
+        }
+    }
+}
+
+/// A reference to an artifact stored in an OCI registry.
+///
+/// This is parsed from the same format used for container images:
+/// `[registry/]repository[:tag][@digest]`. References without a registry
+/// refer to Docker Hub and references without either a tag or a digest use
+/// the `latest` tag.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OciReference {
+    registry: String,
+    repository: String,
+    tag: Option<String>,
+    digest: Option<String>,
+}
+
+impl OciReference {
+    /// The registry that hosts the artifact.
+    pub fn registry(&self) -> &str {
+        &self.registry
+    }
+
+    /// The repository within the registry.
+    pub fn repository(&self) -> &str {
+        &self.repository
+    }
+
+    /// The tag of the artifact, if the reference included one.
+    pub fn tag(&self) -> Option<&str> {
+        self.tag.as_deref()
+    }
+
+    /// The digest of the artifact manifest, if the reference included one.
+    pub fn digest(&self) -> Option<&str> {
+        self.digest.as_deref()
+    }
+
+    /// The reference used to look up the manifest: the digest if there is
+    /// one, otherwise the tag.
+    fn reference(&self) -> &str {
+        self.digest
+            .as_deref()
+            .or(self.tag.as_deref())
+            .unwrap_or("latest")
+    }
+
+    fn base_url(&self) -> String {
+        let host = match self.registry.as_str() {
+            DOCKER_HUB => DOCKER_HUB_REGISTRY,
+            registry => registry,
+        };
+
+        // Local registries are almost never set up with TLS.
+        let scheme = match host.split(':').next() {
+            Some("localhost" | "127.0.0.1") => "http",
+            _ => "https",
+        };
+
+        format!("{scheme}://{host}/v2/{}", self.repository)
+    }
+}
+
+impl FromStr for OciReference {
+    type Err = DurableError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid =
+            |reason: &str| DurableError::from(ErrorImpl::InvalidReference(s.into(), reason.into()));
+
+        let (rest, digest) = match s.split_once('@') {
+            Some((rest, digest)) => {
+                if !digest.contains(':') {
+                    return Err(invalid("digest must have the form <algorithm>:<hex>"));
+                }
+
+                (rest, Some(digest.to_owned()))
+            }
+            None => (s, None),
+        };
+
+        let (name, tag) = match rest.rfind(':') {
+            Some(idx) if !rest[idx..].contains('/') => {
+                (&rest[..idx], Some(rest[idx + 1..].to_owned()))
+            }
+            _ => (rest, None),
+        };
+
+        let (registry, repository) = match name.split_once('/') {
+            Some((host, path))
+                if host.contains('.') || host.contains(':') || host == "localhost" =>
+            {
+                (host.to_owned(), path.to_owned())
+            }
+            _ => (DOCKER_HUB.to_owned(), name.to_owned()),
+        };
+
+        let repository = if registry == DOCKER_HUB && !repository.contains('/') {
+            format!("library/{repository}")
+        } else {
+            repository
+        };
+
+        if repository.is_empty() || repository.split('/').any(str::is_empty) {
+            return Err(invalid("repository name is empty"));
+        }
+        if tag.as_deref() == Some("") {
+            return Err(invalid("tag is empty"));
+        }
+
+        let tag = match (tag, &digest) {
+            (None, None) => Some("latest".to_owned()),
+            (tag, _) => tag,
+        };
+
+        Ok(Self {
+            registry,
+            repository,
+            tag,
+            digest,
+        })
+    }
+}
+
+impl fmt::Display for OciReference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.registry, self.repository)?;
+        if let Some(tag) = &self.tag {
+            write!(f, ":{tag}")?;
+        }
+        if let Some(digest) = &self.digest {
+            write!(f, "@{digest}")?;
+        }
+
+        Ok(())
+    }
+}
+
+fn verify_digest(expected: &str, data: &[u8]) -> Result<(), DurableError> {
+    let actual = match expected.split_once(':') {
+        Some(("sha256", _)) => format!("sha256:{:x}", Sha256::digest(data)),
+        _ => {
+            return Err(
+                ErrorImpl::Oci(format!("unsupported digest algorithm in {expected:?}")).into(),
+            )
+        }
+    };
+
+    if !actual.eq_ignore_ascii_case(expected) {
+        return Err(ErrorImpl::DigestMismatch {
+            expected: expected.to_owned(),
+            actual,
+        }
+        .into());
+    }
+
+    Ok(())
+}
+
+struct Registry {
+    client: Client,
+    base: String,
+    scope: String,
+    auth: RegistryAuth,
+    token: Option<String>,
+}
+
+impl Registry {
+    fn new(reference: &OciReference, auth: RegistryAuth) -> Self {
+        let token = match &auth {
+            RegistryAuth::Bearer(token) => Some(token.clone()),
+            _ => None,
+        };
+
+        Self {
+            client: Client::new(),
+            base: reference.base_url(),
+            scope: format!("repository:{}:pull", reference.repository()),
+            auth,
+            token,
+        }
+    }
+
+    async fn manifest(&mut self, reference: &str) -> Result<Manifest, DurableError> {
+        let url = format!("{}/manifests/{reference}", self.base);
+        let response = self
+            .get(&url, |request| request.header(ACCEPT, MANIFEST_TYPES))
+            .await?;
+        let bytes = response.bytes().await.map_err(ErrorImpl::Fetch)?;
+        let raw = serde_json::from_slice(&bytes)
+            .map_err(|e| ErrorImpl::Oci(format!("invalid manifest for {reference}: {e}")))?;
+
+        Ok(Manifest {
+            bytes: bytes.to_vec(),
+            raw,
+        })
+    }
+
+    async fn blob(&mut self, digest: &str) -> Result<Vec<u8>, DurableError> {
+        let url = format!("{}/blobs/{digest}", self.base);
+        let response = self.get(&url, |request| request).await?;
+        let bytes = response.bytes().await.map_err(ErrorImpl::Fetch)?;
+
+        Ok(bytes.to_vec())
+    }
+
+    async fn get(
+        &mut self,
+        url: &str,
+        build: impl Fn(RequestBuilder) -> RequestBuilder,
+    ) -> Result<Response, DurableError> {
+        let response = self
+            .send(build(self.client.get(url)))
+            .await
+            .map_err(ErrorImpl::Fetch)?;
+
+        let response = match response.status() {
+            StatusCode::UNAUTHORIZED if !matches!(self.auth, RegistryAuth::Bearer(_)) => {
+                let challenge = response.headers().get(WWW_AUTHENTICATE).cloned();
+                self.authenticate(challenge.as_ref()).await?;
+                self.send(build(self.client.get(url)))
+                    .await
+                    .map_err(ErrorImpl::Fetch)?
+            }
+            _ => response,
+        };
+
+        Ok(response.error_for_status().map_err(ErrorImpl::Fetch)?)
+    }
+
+    async fn send(&self, request: RequestBuilder) -> reqwest::Result<Response> {
+        let request = match (&self.token, &self.auth) {
+            (Some(token), _) => request.bearer_auth(token),
+            (None, RegistryAuth::Basic { username, password }) => {
+                request.basic_auth(username, Some(password))
+            }
+            _ => request,
+        };
+
+        request.send().await
+    }
+
+    /// Respond to a `WWW-Authenticate` challenge by requesting a bearer token
+    /// from the registry's token service.
+    async fn authenticate(&mut self, challenge: Option<&HeaderValue>) -> Result<(), DurableError> {
+        let challenge = challenge
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .ok_or_else(|| ErrorImpl::Oci("registry rejected the provided credentials".into()))?;
+
+        let mut realm = None;
+        let mut query = Vec::new();
+        for (key, value) in parse_challenge(challenge) {
+            match key {
+                "realm" => realm = Some(value),
+                "service" | "scope" => query.push((key, value)),
+                _ => (),
+            }
+        }
+
+        if !query.iter().any(|(key, _)| *key == "scope") {
+            query.push(("scope", &self.scope));
+        }
+
+        let realm = realm.ok_or_else(|| {
+            ErrorImpl::Oci("registry authentication challenge is missing a realm".into())
+        })?;
+
+        let mut request = self.client.get(realm).query(&query);
+        if let RegistryAuth::Basic { username, password } = &self.auth {
+            request = request.basic_auth(username, Some(password));
+        }
+
+        let token: TokenResponse = request
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(ErrorImpl::Fetch)?
+            .json()
+            .await
+            .map_err(ErrorImpl::Fetch)?;
+
+        self.token = Some(token.token.or(token.access_token).ok_or_else(|| {
+            ErrorImpl::Oci("registry token service did not return a token".into())
+        })?);
+
+        Ok(())
+    }
+}
+
+/// Parse the parameters of a `WWW-Authenticate` challenge, e.g.
+/// `realm="https://auth.example.com/token",service="registry.example.com"`.
+fn parse_challenge(mut params: &str) -> Vec<(&str, &str)> {
+    let mut values = Vec::new();
+
+    while let Some((key, rest)) = params.split_once('=') {
+        let key = key.trim().trim_start_matches(',').trim();
+        let (value, rest) = match rest.strip_prefix('"') {
+            Some(rest) => rest.split_once('"').unwrap_or((rest, "")),
+            None => rest.split_once(',').unwrap_or((rest, "")),
+        };
+
+        values.push((key, value));
+        params = rest;
+    }
+
+    values
+}
+
+struct Manifest {
+    bytes: Vec<u8>,
+    raw: RawManifest,
+}
+
+impl Manifest {
+    /// Get the image manifest, or the image index instead if that is what the
+    /// registry returned.
+    fn into_image(self) -> Result<ImageManifest, ImageIndex> {
+        match self.raw.manifests {
+            Some(manifests) => Err(ImageIndex { manifests }),
+            None => Ok(ImageManifest {
+                layers: self.raw.layers,
+            }),
+        }
+    }
+}
+
+#[derive(serde::Deserialize)]
+struct RawManifest {
+    #[serde(default)]
+    layers: Vec<Descriptor>,
+    manifests: Option<Vec<Descriptor>>,
+}
+
+struct ImageManifest {
+    layers: Vec<Descriptor>,
+}
+
+struct ImageIndex {
+    manifests: Vec<Descriptor>,
+}
+
+#[derive(serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Descriptor {
+    #[serde(default)]
+    media_type: String,
+    digest: String,
+    platform: Option<Platform>,
+}
+
+#[derive(serde::Deserialize)]
+struct Platform {
+    #[serde(default)]
+    architecture: String,
+}
+
+#[derive(serde::Deserialize)]
+struct TokenResponse {
+    token: Option<String>,
+    access_token: Option<String>,
+}
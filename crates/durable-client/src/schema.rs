@@ -0,0 +1,117 @@
+use std::fmt;
+
+use serde_json::Value;
+
+use crate::error::ErrorImpl;
+use crate::DurableError;
+
+/// A compiled JSON Schema that the data of tasks launched with a program must
+/// match.
+pub(crate) struct DataSchema {
+    schema: Value,
+    validator: jsonschema::Validator,
+}
+
+impl DataSchema {
+    pub fn new(schema: Value) -> Result<Self, DurableError> {
+        let validator = jsonschema::validator_for(&schema)
+            .map_err(|e| ErrorImpl::InvalidDataSchema(e.to_string()))?;
+
+        Ok(Self { schema, validator })
+    }
+
+    pub fn validate(&self, task: &str, data: &Value) -> Result<(), ValidationError> {
+        let violations: Vec<_> = self
+            .validator
+            .iter_errors(data)
+            .map(|error| Violation {
+                path: error.instance_path.to_string(),
+                message: error.to_string(),
+            })
+            .collect();
+
+        if violations.is_empty() {
+            return Ok(());
+        }
+
+        Err(ValidationError {
+            task: task.to_owned(),
+            violations,
+        })
+    }
+}
+
+impl fmt::Debug for DataSchema {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("DataSchema").field(&self.schema).finish()
+    }
+}
+
+/// The data for a task did not match the data schema of its program.
+///
+/// See [`ProgramOptions::data_schema`](crate::ProgramOptions::data_schema).
+#[derive(Clone, Debug)]
+pub struct ValidationError {
+    task: String,
+    violations: Vec<Violation>,
+}
+
+impl ValidationError {
+    /// The name of the task whose data was invalid.
+    pub fn task(&self) -> &str {
+        &self.task
+    }
+
+    /// All the ways in which the data did not match the schema.
+    pub fn violations(&self) -> &[Violation] {
+        &self.violations
+    }
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "the data for task {:?} does not match the program's data schema",
+            self.task
+        )?;
+
+        for violation in &self.violations {
+            write!(f, "\n  {violation}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl std::error::Error for ValidationError {}
+
+/// A single way in which task data did not match a schema.
+#[derive(Clone, Debug)]
+pub struct Violation {
+    path: String,
+    message: String,
+}
+
+impl Violation {
+    /// A JSON pointer to the part of the data that was invalid.
+    ///
+    /// This is empty if the problem is with the data as a whole.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// A description of the problem.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.path.as_str() {
+            "" => f.write_str(&self.message),
+            path => write!(f, "at {path}: {}", self.message),
+        }
+    }
+}
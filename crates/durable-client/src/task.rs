@@ -1,31 +1,129 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::fmt;
+use std::time::Duration;
+
 use async_stream::try_stream;
+use chrono::{DateTime, Utc};
+use durable_migrate::SchemaRename;
 use futures_core::Stream;
 use futures_util::TryStreamExt;
 use serde::Serialize;
-use serde_json::Value;
+use serde_json::value::RawValue;
 use sqlx::postgres::PgListener;
 use sqlx::types::Json;
+use tokio::time::Instant;
 
 use crate::error::ErrorImpl;
-use crate::event::TaskComplete;
-use crate::{DurableClient, DurableError};
+use crate::event::{self, TaskComplete};
+use crate::{DurableClient, DurableError, WorkflowDef};
 
+/// An event recorded by a task.
 #[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
 pub struct Event {
+    /// The index of this event within the task's event log.
     pub index: i32,
+
+    /// The label of the transaction that recorded this event.
     pub label: String,
-    pub value: Value,
+
+    /// The time at which this event was recorded.
+    pub created_at: DateTime<Utc>,
+
+    /// The value returned by the transaction, as it was stored in the
+    /// database.
+    pub value: Box<RawValue>,
 }
 
-#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+/// The final state of a task, along with the result that it set.
+#[derive(Clone, Debug)]
 pub struct ExitStatus {
     state: TaskState,
+    result: Option<Box<RawValue>>,
+    failure: Option<Failure>,
 }
 
 impl ExitStatus {
     pub fn success(&self) -> bool {
         matches!(self.state, TaskState::Complete)
     }
+
+    /// The state that the task finished in.
+    pub fn state(&self) -> TaskState {
+        self.state
+    }
+
+    /// The result that the task set before it exited, if any.
+    pub fn result(&self) -> Option<&RawValue> {
+        self.result.as_deref()
+    }
+
+    /// Details about why the task failed, if it did.
+    pub fn failure(&self) -> Option<&Failure> {
+        self.failure.as_ref()
+    }
+
+    /// Attempt to deserialize the result of the task.
+    ///
+    /// Returns `None` if the task did not set a result.
+    pub fn result_as<'de, T>(&'de self) -> Option<serde_json::Result<T>>
+    where
+        T: serde::Deserialize<'de>,
+    {
+        self.result
+            .as_deref()
+            .map(|result| serde_json::from_str(result.get()))
+    }
+}
+
+/// Details about why a task failed.
+#[derive(Clone, Debug)]
+pub struct Failure {
+    message: String,
+    label: Option<String>,
+    index: Option<i32>,
+    backtrace: Option<String>,
+}
+
+impl Failure {
+    /// The reason that the task failed.
+    ///
+    /// This is the panic message, trap, or error chain that caused the
+    /// failure.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// The label of the last transaction that the task entered before it
+    /// failed.
+    pub fn label(&self) -> Option<&str> {
+        self.label.as_deref()
+    }
+
+    /// The index of the last transaction that the task entered before it
+    /// failed.
+    pub fn index(&self) -> Option<i32> {
+        self.index
+    }
+
+    /// The wasm backtrace at the point where the task failed.
+    ///
+    /// This is only available when the task failed due to a trap.
+    pub fn backtrace(&self) -> Option<&str> {
+        self.backtrace.as_deref()
+    }
+}
+
+impl fmt::Display for Failure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)?;
+
+        match (&self.label, self.index) {
+            (Some(label), Some(index)) => {
+                write!(f, " (in transaction {index} with label {label:?})")
+            }
+            _ => Ok(()),
+        }
+    }
 }
 
 /// The current state of a task.
@@ -43,7 +141,18 @@ pub enum TaskState {
 }
 
 impl TaskState {
-    fn from_str(state: &str) -> Self {
+    pub(crate) fn as_str(self) -> &'static str {
+        match self {
+            Self::Ready => "ready",
+            Self::Active => "active",
+            Self::Suspended => "suspended",
+            Self::Complete => "complete",
+            Self::Failed => "failed",
+            Self::Unknown => "unknown",
+        }
+    }
+
+    pub(crate) fn from_str(state: &str) -> Self {
         match state {
             "ready" => Self::Ready,
             "active" => Self::Active,
@@ -79,54 +188,473 @@ impl Task {
         self.id
     }
 
+    /// Check whether this task exists and belongs to `tenant`.
+    pub(crate) async fn exists(
+        &self,
+        tenant: Option<&str>,
+        schema: &SchemaRename,
+        conn: &mut sqlx::PgConnection,
+    ) -> Result<bool, DurableError> {
+        let record = sqlx::query!(
+            "
+            SELECT id
+             FROM durable.task
+            WHERE id = $1
+              AND tenant IS NOT DISTINCT FROM $2
+            ",
+            self.id,
+            tenant
+        )
+        .fetch_optional(schema.on(&mut *conn))
+        .await?;
+
+        Ok(record.is_some())
+    }
+
+    /// Read all the events that the task has recorded up to this point.
+    ///
+    /// Events are returned in the order that they were recorded by the task.
+    pub async fn events(&self, client: &DurableClient) -> Result<Vec<Event>, DurableError> {
+        let mut conn = client.pool.acquire().await?;
+
+        let events = sqlx::query!(
+            r#"
+            SELECT
+                index,
+                label,
+                created_at,
+                value as "value!: Json<Box<RawValue>>"
+            FROM durable.event
+            WHERE task_id = $1
+              AND EXISTS(
+                SELECT 1
+                 FROM durable.task
+                WHERE id = $1
+                  AND tenant IS NOT DISTINCT FROM $2
+              )
+            ORDER BY index ASC
+            "#,
+            self.id,
+            client.tenant()
+        )
+        .fetch_all(client.data.schema.on(&mut *conn))
+        .await?;
+
+        if events.is_empty()
+            && !self
+                .exists(client.tenant(), &client.data.schema, &mut conn)
+                .await?
+        {
+            return Err(ErrorImpl::NonexistantTaskId(self.id).into());
+        }
+
+        Ok(events
+            .into_iter()
+            .map(|record| Event {
+                index: record.index,
+                label: record.label,
+                created_at: record.created_at,
+                value: record.value.0,
+            })
+            .collect())
+    }
+
     /// Get a real-time stream of task events as they occur.
-    pub fn events(
+    ///
+    /// This will first emit all the events that the task has already recorded
+    /// and then continue to emit new events as they are recorded. The stream
+    /// completes once the task has completed.
+    ///
+    /// Note that this holds on to a database connection for the whole time it
+    /// is running (for the listener).
+    pub fn events_stream(
         &self,
         client: &DurableClient,
     ) -> impl Stream<Item = Result<Event, DurableError>> + '_ {
         let pool = client.pool.clone();
+        let tenant = client.data.tenant.clone();
+        let schema = client.data.schema.clone();
 
-        try_stream! {
-            let mut conn = pool.acquire().await?;
-            let mut count = 0;
+        try_stream!({
+            let mut done = false;
+            let mut last_seen = -1;
+            let event_channel = schema.channel("durable:event");
+            let complete_channel = schema.channel("durable:task-complete");
+            let mut listener = PgListener::connect_with(&pool).await?;
+            listener
+                .listen_all([&*event_channel, &*complete_channel])
+                .await?;
 
-            let mut events = sqlx::query!(
+            let state = sqlx::query!(
                 r#"
-                SELECT
-                    index,
-                    label,
-                    value as "value!: Json<Value>"
-                FROM durable.event
-                WHERE task_id = $1
-                ORDER BY index ASC
+                SELECT state::text as "state!"
+                 FROM durable.task
+                WHERE id = $1
+                  AND tenant IS NOT DISTINCT FROM $2
                 "#,
-                self.id
+                self.id,
+                tenant.as_deref()
             )
-            .fetch(&mut *conn);
+            .fetch_optional(schema.on(&mut listener))
+            .await?;
 
-            while let Some(record) = events.try_next().await? {
-                count += 1;
+            match state.as_ref().map(|r| r.state.as_str()) {
+                Some("complete" | "failed") => done = true,
+                Some(_) => (),
+                None => {
+                    return Err(ErrorImpl::NonexistantTaskId(self.id()))?;
+                }
+            };
 
-                yield Event {
-                    index: record.index,
-                    label: record.label,
-                    value: record.value.0
+            loop {
+                let results = sqlx::query!(
+                    r#"
+                    SELECT
+                        index,
+                        label,
+                        created_at,
+                        value as "value!: Json<Box<RawValue>>"
+                     FROM durable.event
+                    WHERE task_id = $1
+                      AND index > $2
+                    ORDER BY index ASC
+                    "#,
+                    self.id,
+                    last_seen
+                )
+                .fetch(schema.on(&mut listener));
+
+                for await result in results {
+                    let record = result?;
+                    last_seen = last_seen.max(record.index);
+
+                    yield Event {
+                        index: record.index,
+                        label: record.label,
+                        created_at: record.created_at,
+                        value: record.value.0,
+                    };
                 }
-            }
 
-            drop(events);
+                if done {
+                    break;
+                }
+
+                // Wait until there is something new for us to read.
+                loop {
+                    let notification = match listener.try_recv().await? {
+                        Some(notification) => notification,
+                        None => {
+                            // The listener lost its connection so we may have missed the
+                            // task completing.
+                            let state = sqlx::query!(
+                                r#"SELECT state::text as "state!" FROM durable.task WHERE id = $1"#,
+                                self.id
+                            )
+                            .fetch_one(schema.on(&mut listener))
+                            .await?
+                            .state;
 
-            if count == 0 {
-                let exists = sqlx::query!("SELECT id FROM durable.task WHERE id = $1", self.id)
-                    .fetch_optional(&mut *conn)
-                    .await?
-                    .is_some();
+                            done = state == "complete" || state == "failed";
+                            break;
+                        }
+                    };
 
-                if !exists {
-                    Err(ErrorImpl::NonexistantTaskId(self.id))?
+                    if notification.channel() == event_channel {
+                        match serde_json::from_str::<event::TaskEvent>(notification.payload()) {
+                            Ok(payload) if payload.task_id != self.id => continue,
+                            _ => break,
+                        }
+                    }
+
+                    match serde_json::from_str::<TaskComplete>(notification.payload()) {
+                        Ok(payload) if payload.id != self.id => continue,
+                        Ok(_) => {
+                            done = true;
+                            break;
+                        }
+                        Err(_) => break,
+                    }
                 }
             }
+        })
+    }
+
+    /// Launch a new task that runs the same program as this one, with the
+    /// same name, entrypoint, tenant, and data.
+    ///
+    /// This is meant for re-running a task from scratch, e.g. after it has
+    /// failed. The new task records this one as the task it was cloned from,
+    /// see [`cloned_from`](Task::cloned_from). Dependencies, dedupe keys, and
+    /// delayed start times are not copied over.
+    ///
+    /// The new task is launched into the instance of `client` (see
+    /// [`DurableClient::with_instance`]), which need not be the instance that
+    /// this task was launched into.
+    ///
+    /// # Errors
+    /// Returns an error if the task does not exist or if it no longer has a
+    /// program to run. Tasks that completed successfully drop their reference
+    /// to their program, so only tasks that failed or are still running can be
+    /// cloned.
+    pub async fn clone_and_launch(&self, client: &DurableClient) -> Result<Task, DurableError> {
+        let mut tx = client.pool.begin().await?;
+
+        let record = sqlx::query!(
+            "
+            SELECT wasm
+             FROM durable.task
+            WHERE id = $1
+              AND tenant IS NOT DISTINCT FROM $2
+            FOR SHARE
+            ",
+            self.id,
+            client.tenant()
+        )
+        .fetch_optional(client.data.schema.on(&mut *tx))
+        .await?
+        .ok_or(ErrorImpl::NonexistantTaskId(self.id))?;
+
+        if record.wasm.is_none() {
+            return Err(ErrorImpl::ProgramUnavailable(self.id).into());
+        }
+
+        let id = sqlx::query_scalar!(
+            "
+            INSERT INTO durable.task(
+                name, wasm, data, sql_context, entrypoint, tenant, instance, labels,
+                cloned_from, running_on
+            )
+            SELECT
+                name,
+                wasm,
+                data,
+                sql_context,
+                entrypoint,
+                tenant,
+                $2::text,
+                labels,
+                id,
+                (
+                    SELECT id
+                     FROM durable.worker
+                    WHERE worker.instance IS NOT DISTINCT FROM $2::text
+                    ORDER BY random()
+                    LIMIT 1
+                    FOR SHARE SKIP LOCKED
+                )
+             FROM durable.task
+            WHERE id = $1
+            RETURNING id
+            ",
+            self.id,
+            client.instance()
+        )
+        .fetch_one(client.data.schema.on(&mut *tx))
+        .await?;
+
+        // Oversized task data is stored separately and needs to be copied as well.
+        sqlx::query!(
+            "
+            INSERT INTO durable.task_payload(task_id, data)
+            SELECT $1, data
+             FROM durable.task_payload
+            WHERE task_id = $2
+            ",
+            id,
+            self.id
+        )
+        .execute(client.data.schema.on(&mut *tx))
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(Task { id })
+    }
+
+    /// Get the task that this task was cloned from, if it was launched via
+    /// [`clone_and_launch`](Task::clone_and_launch).
+    ///
+    /// This returns `None` if the original task has since been deleted.
+    pub async fn cloned_from(&self, client: &DurableClient) -> Result<Option<Task>, DurableError> {
+        let record = sqlx::query!(
+            "
+            SELECT cloned_from
+             FROM durable.task
+            WHERE id = $1
+              AND tenant IS NOT DISTINCT FROM $2
+            ",
+            self.id,
+            client.tenant()
+        )
+        .fetch_optional(client.data.schema.on(&client.pool))
+        .await?
+        .ok_or(ErrorImpl::NonexistantTaskId(self.id))?;
+
+        Ok(record.cloned_from.map(Task::from_id))
+    }
+
+    /// Pause the task.
+    ///
+    /// Paused tasks are not scheduled on any worker until they are resumed
+    /// with [`resume`](Task::resume). If the task is currently running then the
+    /// worker running it stops it at its next transaction boundary, and the
+    /// transaction that it was in is run again once the task is resumed.
+    /// Notifications sent to the task while it is paused are kept.
+    ///
+    /// Returns `false` if the task was already paused or has already completed.
+    pub async fn pause(&self, client: &DurableClient) -> Result<bool, DurableError> {
+        let mut tx = client.pool.begin().await?;
+
+        let record = sqlx::query!(
+            r#"
+            SELECT state::text as "state!", paused_at
+             FROM durable.task
+            WHERE id = $1
+              AND tenant IS NOT DISTINCT FROM $2
+            FOR UPDATE
+            "#,
+            self.id,
+            client.tenant()
+        )
+        .fetch_optional(client.data.schema.on(&mut *tx))
+        .await?
+        .ok_or(ErrorImpl::NonexistantTaskId(self.id))?;
+
+        let state = TaskState::from_str(&record.state);
+        if record.paused_at.is_some() || matches!(state, TaskState::Complete | TaskState::Failed) {
+            return Ok(false);
+        }
+
+        // Clearing running_on means that the worker running the task will stop
+        // it the next time it tries to commit a transaction.
+        sqlx::query!(
+            "
+            UPDATE durable.task
+              SET paused_at = CURRENT_TIMESTAMP,
+                  running_on = NULL,
+                  state = CASE WHEN state = 'active' THEN 'suspended' ELSE state END
+            WHERE id = $1
+            ",
+            self.id
+        )
+        .execute(client.data.schema.on(&mut *tx))
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(true)
+    }
+
+    /// Resume a task that was paused with [`pause`](Task::pause).
+    ///
+    /// Tasks that were stopped while running, or that were waiting for a
+    /// notification, are scheduled again immediately. Tasks waiting on a timer
+    /// are woken up once it expires.
+    ///
+    /// Returns `false` if the task was not paused.
+    pub async fn resume(&self, client: &DurableClient) -> Result<bool, DurableError> {
+        let mut tx = client.pool.begin().await?;
+
+        let record = sqlx::query!(
+            r#"
+            SELECT state::text as "state!", paused_at
+             FROM durable.task
+            WHERE id = $1
+              AND tenant IS NOT DISTINCT FROM $2
+            FOR UPDATE
+            "#,
+            self.id,
+            client.tenant()
+        )
+        .fetch_optional(client.data.schema.on(&mut *tx))
+        .await?
+        .ok_or(ErrorImpl::NonexistantTaskId(self.id))?;
+
+        if record.paused_at.is_none() {
+            return Ok(false);
         }
+
+        let resumed = sqlx::query!(
+            r#"
+            UPDATE durable.task
+              SET paused_at = NULL,
+                  state = CASE
+                    WHEN state = 'suspended' AND wakeup_at IS NULL THEN 'ready'
+                    ELSE state
+                  END
+            WHERE id = $1
+            RETURNING state::text as "state!", running_on
+            "#,
+            self.id
+        )
+        .fetch_one(client.data.schema.on(&mut *tx))
+        .await?;
+
+        // Tasks that were already ready don't change state, so the workers need
+        // to be told that they can be claimed again.
+        if TaskState::from_str(&resumed.state) == TaskState::Ready {
+            let payload = serde_json::json!({
+                "id": self.id,
+                "running_on": resumed.running_on,
+            });
+
+            sqlx::query("SELECT pg_notify('durable:task', $1)")
+                .bind(payload.to_string())
+                .execute(client.data.schema.on(&mut *tx))
+                .await?;
+        }
+
+        tx.commit().await?;
+
+        Ok(true)
+    }
+
+    /// Get the labels that the task was launched with.
+    pub async fn labels(
+        &self,
+        client: &DurableClient,
+    ) -> Result<BTreeMap<String, String>, DurableError> {
+        let labels = sqlx::query_scalar!(
+            r#"
+            SELECT labels as "labels: Json<BTreeMap<String, String>>"
+             FROM durable.task
+            WHERE id = $1
+              AND tenant IS NOT DISTINCT FROM $2
+            "#,
+            self.id,
+            client.tenant()
+        )
+        .fetch_optional(client.data.schema.on(&client.pool))
+        .await?
+        .ok_or(ErrorImpl::NonexistantTaskId(self.id))?;
+
+        Ok(labels.0)
+    }
+
+    /// Get the metadata that the task has published about itself.
+    ///
+    /// Workflows update this as they run using `durable::metadata::set`, so
+    /// it may change between calls while the task is running.
+    pub async fn metadata(
+        &self,
+        client: &DurableClient,
+    ) -> Result<BTreeMap<String, Box<RawValue>>, DurableError> {
+        let metadata = sqlx::query_scalar!(
+            r#"
+            SELECT metadata as "metadata: Json<BTreeMap<String, Box<RawValue>>>"
+             FROM durable.task
+            WHERE id = $1
+              AND tenant IS NOT DISTINCT FROM $2
+            "#,
+            self.id,
+            client.tenant()
+        )
+        .fetch_optional(client.data.schema.on(&client.pool))
+        .await?
+        .ok_or(ErrorImpl::NonexistantTaskId(self.id))?;
+
+        Ok(metadata.0)
     }
 
     /// Send a notification to the task.
@@ -140,34 +668,45 @@ impl Task {
         T: ?Sized + Serialize,
     {
         let mut conn = client.pool.acquire().await?;
-        self.notify_with(event, data, &mut conn).await
+        self.notify_with(event, data, client, &mut conn).await
     }
 
     /// Send a notification to the task using the provided connection.
     ///
     /// This is useful for when the task notification is done as part of a
-    /// larger transaction.
+    /// larger transaction. The connection is used in place of the client's
+    /// own pool, the client only determines which tenant the task must belong
+    /// to.
     pub async fn notify_with<T>(
         &self,
         event: &str,
         data: &T,
+        client: &DurableClient,
         conn: &mut sqlx::PgConnection,
     ) -> Result<(), DurableError>
     where
         T: ?Sized + Serialize,
     {
-        sqlx::query!(
+        let result = sqlx::query!(
             "
             INSERT INTO durable.notification(task_id, event, data)
-            VALUES ($1, $2, $3)
+            SELECT id, $2::text, $3::jsonb
+             FROM durable.task
+            WHERE id = $1
+              AND tenant IS NOT DISTINCT FROM $4
             ",
             self.id,
             event,
-            Json(data) as Json<&T>
+            Json(data) as Json<&T>,
+            client.tenant()
         )
-        .execute(&mut *conn)
+        .execute(client.data.schema.on(&mut *conn))
         .await?;
 
+        if result.rows_affected() == 0 {
+            return Err(ErrorImpl::NonexistantTaskId(self.id).into());
+        }
+
         Ok(())
     }
 
@@ -177,6 +716,8 @@ impl Task {
         client: &DurableClient,
     ) -> impl Stream<Item = Result<String, DurableError>> + '_ {
         let pool = client.pool.clone();
+        let tenant = client.data.tenant.clone();
+        let schema = client.data.schema.clone();
 
         try_stream! {
             let mut conn = pool.acquire().await?;
@@ -187,11 +728,18 @@ impl Task {
                 SELECT message
                 FROM durable.log
                 WHERE task_id = $1
+                  AND EXISTS(
+                    SELECT 1
+                     FROM durable.task
+                    WHERE id = $1
+                      AND tenant IS NOT DISTINCT FROM $2
+                  )
                 ORDER BY index ASC
                 "#,
-                self.id
+                self.id,
+                tenant.as_deref()
             )
-            .fetch(&mut *conn);
+            .fetch(schema.on(&mut *conn));
 
             while let Some(record) = events.try_next().await? {
                 count += 1;
@@ -201,15 +749,8 @@ impl Task {
 
             drop(events);
 
-            if count == 0 {
-                let exists = sqlx::query!("SELECT id FROM durable.task WHERE id = $1", self.id)
-                    .fetch_optional(&mut *conn)
-                    .await?
-                    .is_some();
-
-                if !exists {
-                    Err(ErrorImpl::NonexistantTaskId(self.id))?
-                }
+            if count == 0 && !self.exists(tenant.as_deref(), &schema, &mut conn).await? {
+                Err(ErrorImpl::NonexistantTaskId(self.id))?
             }
         }
     }
@@ -223,20 +764,30 @@ impl Task {
         client: &DurableClient,
     ) -> impl Stream<Item = Result<String, DurableError>> + '_ {
         let pool = client.pool.clone();
+        let tenant = client.data.tenant.clone();
+        let schema = client.data.schema.clone();
 
         try_stream!({
             let mut done = false;
             let mut last_seen = -1;
+            let log_channel = schema.channel("durable:log");
+            let complete_channel = schema.channel("durable:task-complete");
             let mut listener = PgListener::connect_with(&pool).await?;
             listener
-                .listen_all(["durable:log", "durable:task-complete"])
+                .listen_all([&*log_channel, &*complete_channel])
                 .await?;
 
             let state = sqlx::query!(
-                r#"SELECT state::text as "state!" FROM durable.task WHERE id = $1"#,
-                self.id
+                r#"
+                SELECT state::text as "state!"
+                 FROM durable.task
+                WHERE id = $1
+                  AND tenant IS NOT DISTINCT FROM $2
+                "#,
+                self.id,
+                tenant.as_deref()
             )
-            .fetch_optional(&mut listener)
+            .fetch_optional(schema.on(&mut listener))
             .await?;
 
             match state.as_ref().map(|r| r.state.as_str()) {
@@ -259,7 +810,7 @@ impl Task {
                     self.id,
                     last_seen
                 )
-                .fetch(&mut listener);
+                .fetch(schema.on(&mut listener));
 
                 for await result in results {
                     let record = result?;
@@ -274,7 +825,7 @@ impl Task {
 
                 let event = listener.try_recv().await?;
                 match event.as_ref() {
-                    Some(event) if event.channel() != "durable:task-complete" => continue,
+                    Some(event) if event.channel() != complete_channel => continue,
                     Some(event) => match serde_json::from_str::<TaskComplete>(event.payload()) {
                         Ok(payload) if payload.id == self.id => {
                             done = true;
@@ -290,7 +841,7 @@ impl Task {
                     r#"SELECT state::text as "state!" FROM durable.task WHERE id = $1"#,
                     self.id
                 )
-                .fetch_one(&mut listener)
+                .fetch_one(schema.on(&mut listener))
                 .await?
                 .state;
 
@@ -301,49 +852,194 @@ impl Task {
         })
     }
 
+    /// Read the result that the task has set, if any.
+    ///
+    /// Tasks set their result by calling `set_result` in the guest. This
+    /// returns `None` if the task has not set a result (yet).
+    pub async fn result(
+        &self,
+        client: &DurableClient,
+    ) -> Result<Option<Box<RawValue>>, DurableError> {
+        let record = sqlx::query!(
+            r#"
+            SELECT result as "result: Json<Box<RawValue>>"
+             FROM durable.task
+            WHERE id = $1
+              AND tenant IS NOT DISTINCT FROM $2
+            "#,
+            self.id,
+            client.tenant()
+        )
+        .fetch_optional(client.data.schema.on(&client.pool))
+        .await?;
+
+        match record {
+            Some(record) => Ok(record.result.map(|result| result.0)),
+            None => Err(ErrorImpl::NonexistantTaskId(self.id).into()),
+        }
+    }
+
+    /// Read the output of a task that was running workflow `W`.
+    ///
+    /// This returns `None` if the task has not set its output (yet). Usually
+    /// you will want to call [`wait`](Task::wait) first.
+    pub async fn result_typed<W>(
+        &self,
+        client: &DurableClient,
+    ) -> Result<Option<W::Output>, DurableError>
+    where
+        W: WorkflowDef,
+    {
+        let Some(result) = self.result(client).await? else {
+            return Ok(None);
+        };
+
+        serde_json::from_str(result.get())
+            .map(Some)
+            .map_err(|e| ErrorImpl::InvalidResult(self.id, e).into())
+    }
+
     /// Wait for the task to complete.
     ///
+    /// This listens for the notification sent when the task exits, so it does
+    /// not poll the database. If `timeout` is set and the task has not
+    /// completed by then, this returns an error for which
+    /// [`DurableError::is_timeout`] is true.
+    ///
     /// Note that depending on the task this could take a long time.
-    pub async fn wait(&self, client: &DurableClient) -> Result<ExitStatus, DurableError> {
-        let mut listener = PgListener::connect_with(&client.pool).await?;
-        listener.listen("durable:task-complete").await?;
+    pub async fn wait(
+        &self,
+        client: &DurableClient,
+        timeout: Option<Duration>,
+    ) -> Result<ExitStatus, DurableError> {
+        let mut statuses = wait_many(client, std::slice::from_ref(self), timeout).await?;
+        Ok(statuses.pop().expect("no exit status was returned"))
+    }
+}
 
-        loop {
-            let record = sqlx::query!(
-                r#"
-                SELECT state::text as "state!"
-                FROM durable.task
-                WHERE id = $1
-                "#,
-                self.id
-            )
-            .fetch_optional(&mut listener)
-            .await?;
+/// Wait for all of `tasks` to complete.
+///
+/// The exit statuses are returned in the same order as `tasks`.
+pub(crate) async fn wait_many(
+    client: &DurableClient,
+    tasks: &[Task],
+    timeout: Option<Duration>,
+) -> Result<Vec<ExitStatus>, DurableError> {
+    let deadline = timeout.map(|timeout| Instant::now() + timeout);
+    let mut statuses: HashMap<i64, ExitStatus> = HashMap::new();
 
-            let state = match record {
-                Some(record) => record.state,
-                None => return Err(ErrorImpl::NonexistantTaskId(self.id).into()),
-            };
-            let state = TaskState::from_str(&state);
+    let mut listener = PgListener::connect_with(&client.pool).await?;
+    listener
+        .listen(&client.data.schema.channel("durable:task-complete"))
+        .await?;
+
+    loop {
+        let pending: Vec<i64> = tasks
+            .iter()
+            .map(|task| task.id)
+            .filter(|id| !statuses.contains_key(id))
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect();
+
+        if pending.is_empty() {
+            break;
+        }
 
-            if matches!(state, TaskState::Complete | TaskState::Failed) {
-                return Ok(ExitStatus { state });
+        let records = sqlx::query!(
+            r#"
+            SELECT
+                id,
+                state::text as "state!",
+                result as "result: Json<Box<RawValue>>",
+                failure_message,
+                failure_label,
+                failure_index,
+                failure_backtrace
+             FROM durable.task
+            WHERE id = ANY($1)
+              AND tenant IS NOT DISTINCT FROM $2
+            "#,
+            &pending,
+            client.tenant()
+        )
+        .fetch_all(client.data.schema.on(&mut listener))
+        .await?;
+
+        if records.len() != pending.len() {
+            let found: HashSet<i64> = records.iter().map(|record| record.id).collect();
+            let missing = pending
+                .iter()
+                .copied()
+                .find(|id| !found.contains(id))
+                .expect("a task was missing but could not be found");
+
+            return Err(ErrorImpl::NonexistantTaskId(missing).into());
+        }
+
+        let mut done = true;
+        for record in records {
+            let state = TaskState::from_str(&record.state);
+            if !matches!(state, TaskState::Complete | TaskState::Failed) {
+                done = false;
+                continue;
             }
 
-            loop {
-                let event = match listener.try_recv().await? {
-                    Some(event) => event,
-                    None => break,
-                };
+            let failure = match state {
+                TaskState::Failed => Some(Failure {
+                    message: record
+                        .failure_message
+                        .unwrap_or_else(|| "the task failed".to_owned()),
+                    label: record.failure_label,
+                    index: record.failure_index,
+                    backtrace: record.failure_backtrace,
+                }),
+                _ => None,
+            };
 
-                let Ok(event) = serde_json::from_str::<TaskComplete>(event.payload()) else {
-                    break;
-                };
+            statuses.insert(
+                record.id,
+                ExitStatus {
+                    state,
+                    result: record.result.map(|result| result.0),
+                    failure,
+                },
+            );
+        }
 
-                if event.id == self.id {
-                    break;
-                }
+        if done {
+            break;
+        }
+
+        // Wait until one of the tasks we are waiting on completes. If the
+        // listener loses its connection then we may have missed a notification
+        // so we need to check the database again.
+        loop {
+            let recv = listener.try_recv();
+            let notification = match deadline {
+                Some(deadline) => match tokio::time::timeout_at(deadline, recv).await {
+                    Ok(notification) => notification?,
+                    Err(_) => return Err(ErrorImpl::Timeout.into()),
+                },
+                None => recv.await?,
+            };
+
+            let Some(notification) = notification else {
+                break;
+            };
+
+            let Ok(event) = serde_json::from_str::<TaskComplete>(notification.payload()) else {
+                break;
+            };
+
+            if pending.contains(&event.id) {
+                break;
             }
         }
     }
+
+    Ok(tasks
+        .iter()
+        .map(|task| statuses[&task.id].clone())
+        .collect())
 }
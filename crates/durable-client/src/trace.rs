@@ -0,0 +1,105 @@
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+use sqlx::types::Json;
+
+use crate::error::ErrorImpl;
+use crate::{DurableClient, DurableError, Task};
+
+/// A single transaction from a task's event log, as returned by
+/// [`Task::trace`].
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct TraceEntry {
+    /// The index of this transaction within the task's event log.
+    pub index: i32,
+
+    /// The label of the transaction.
+    pub label: String,
+
+    /// The time at which the transaction completed and its event was
+    /// recorded.
+    pub created_at: DateTime<Utc>,
+
+    /// The time between the previous event and this one.
+    ///
+    /// For the first event this is the time since the task was launched, so
+    /// it also includes any time that the task spent waiting to be started.
+    pub elapsed: Duration,
+
+    /// The value returned by the transaction.
+    pub value: Value,
+
+    /// Whether this was a database transaction.
+    ///
+    /// This is `None` for events recorded by workers that did not track it.
+    pub is_db: Option<bool>,
+
+    /// The logs emitted by the task during this transaction, if any.
+    ///
+    /// This includes anything logged outside of a transaction since the
+    /// previous transaction completed.
+    pub logs: Option<String>,
+}
+
+impl Task {
+    /// Read the task's event log as a sequence of transactions.
+    ///
+    /// This is meant for inspecting how a task got to where it is, e.g. to
+    /// build a trace viewer. Each entry includes the decoded value of the
+    /// event along with the logs emitted during that transaction and how long
+    /// it took since the previous one.
+    pub async fn trace(&self, client: &DurableClient) -> Result<Vec<TraceEntry>, DurableError> {
+        let mut conn = client.pool.acquire().await?;
+
+        let records = sqlx::query!(
+            r#"
+            SELECT
+                e.index,
+                e.label,
+                e.created_at,
+                e.value as "value!: Json<Value>",
+                e.is_db,
+                l.message as "logs?",
+                t.created_at as task_created_at
+             FROM durable.event e
+             JOIN durable.task t ON t.id = e.task_id
+             LEFT JOIN durable.log l ON l.task_id = e.task_id AND l.index = e.index
+            WHERE e.task_id = $1
+              AND t.tenant IS NOT DISTINCT FROM $2
+            ORDER BY e.index ASC
+            "#,
+            self.id,
+            client.tenant()
+        )
+        .fetch_all(client.data.schema.on(&mut *conn))
+        .await?;
+
+        if records.is_empty()
+            && !self
+                .exists(client.tenant(), &client.data.schema, &mut conn)
+                .await?
+        {
+            return Err(ErrorImpl::NonexistantTaskId(self.id).into());
+        }
+
+        let mut previous = None;
+        Ok(records
+            .into_iter()
+            .map(|record| {
+                let start = previous.unwrap_or(record.task_created_at);
+                previous = Some(record.created_at);
+
+                TraceEntry {
+                    index: record.index,
+                    label: record.label,
+                    created_at: record.created_at,
+                    elapsed: (record.created_at - start).to_std().unwrap_or_default(),
+                    value: record.value.0,
+                    is_db: record.is_db,
+                    logs: record.logs,
+                }
+            })
+            .collect())
+    }
+}
@@ -0,0 +1,89 @@
+use chrono::{DateTime, Utc};
+
+use crate::{DurableClient, DurableError};
+
+/// A worker that is part of the durable cluster.
+#[derive(Clone, Debug)]
+pub struct Worker {
+    id: i64,
+    started_at: DateTime<Utc>,
+    heartbeat_at: DateTime<Utc>,
+}
+
+impl Worker {
+    /// The id of this worker.
+    pub fn id(&self) -> i64 {
+        self.id
+    }
+
+    /// When this worker started.
+    pub fn started_at(&self) -> DateTime<Utc> {
+        self.started_at
+    }
+
+    /// When this worker last reported that it was alive.
+    pub fn heartbeat_at(&self) -> DateTime<Utc> {
+        self.heartbeat_at
+    }
+}
+
+impl DurableClient {
+    /// Get the worker that is currently the leader of the cluster.
+    ///
+    /// Returns `None` if there are no workers running.
+    pub async fn leader(&self) -> Result<Option<Worker>, DurableError> {
+        let leader = sqlx::query_as!(
+            Worker,
+            r#"
+            SELECT
+                id              as "id!",
+                started_at      as "started_at!",
+                heartbeat_at    as "heartbeat_at!"
+             FROM durable.leader
+            "#
+        )
+        .fetch_optional(self.data.schema.on(&self.pool))
+        .await?;
+
+        Ok(leader)
+    }
+
+    /// Hand leadership of the cluster off to another worker.
+    ///
+    /// The current leader stays a member of the cluster but will not become
+    /// the leader again unless it is the only worker left. This is meant to be
+    /// used before taking the leader down for maintenance.
+    ///
+    /// Returns the new leader. This is the same worker as before if there are
+    /// no other workers that are eligible to become the leader.
+    pub async fn hand_off_leadership(&self) -> Result<Option<Worker>, DurableError> {
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query!(
+            "
+            UPDATE durable.worker
+              SET leader_eligible = false
+            WHERE id = (SELECT id FROM durable.leader)
+            "
+        )
+        .execute(self.data.schema.on(&mut *tx))
+        .await?;
+
+        let leader = sqlx::query_as!(
+            Worker,
+            r#"
+            SELECT
+                id              as "id!",
+                started_at      as "started_at!",
+                heartbeat_at    as "heartbeat_at!"
+             FROM durable.leader
+            "#
+        )
+        .fetch_optional(self.data.schema.on(&mut *tx))
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(leader)
+    }
+}
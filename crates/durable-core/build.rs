@@ -1,6 +1,9 @@
 fn main() {
     let target_family = std::env::var("CARGO_CFG_TARGET_FAMILY").unwrap_or_default();
-    if target_family == "wasm" {
+    // The constructor installs the panic hook, which is only available with std.
+    let has_std = std::env::var_os("CARGO_FEATURE_STD").is_some();
+
+    if target_family == "wasm" && has_std {
         cc::Build::new().file("src/ctor.c").compile("durable-ctor");
     }
 }
@@ -9,12 +9,41 @@ pub mod durable {
             static __FORCE_SECTION_REF: fn() = super::super::super::__link_custom_section_describing_imports;
             use super::super::super::_rt;
             pub type Datetime = super::super::super::wasi::clocks::wall_clock::Datetime;
+            /// Options for a transaction started via `transaction-enter2`.
+            #[repr(C)]
+            #[derive(Clone, Copy)]
+            pub struct TransactionOptions {
+                /// Whether this transaction is a database transaction and should
+                /// reserve a database connection so that sql can be used within.
+                pub is_db: bool,
+                /// A statement timeout, in milliseconds, to apply to all queries made
+                /// within the database transaction.
+                ///
+                /// This is ignored if `is-db` is false.
+                pub statement_timeout: Option<u64>,
+                /// Whether the database transaction should be read-only.
+                ///
+                /// This is ignored if `is-db` is false.
+                pub read_only: bool,
+            }
+            impl ::core::fmt::Debug for TransactionOptions {
+                fn fmt(
+                    &self,
+                    f: &mut ::core::fmt::Formatter<'_>,
+                ) -> ::core::fmt::Result {
+                    f.debug_struct("TransactionOptions")
+                        .field("is-db", &self.is_db)
+                        .field("statement-timeout", &self.statement_timeout)
+                        .field("read-only", &self.read_only)
+                        .finish()
+                }
+            }
             #[allow(unused_unsafe, clippy::all)]
             /// Get the task id for the current workflow.
             pub fn task_id() -> i64 {
                 unsafe {
                     #[cfg(target_arch = "wasm32")]
-                    #[link(wasm_import_module = "durable:core/core@2.5.0")]
+                    #[link(wasm_import_module = "durable:core/core@2.7.0")]
                     extern "C" {
                         #[link_name = "task-id"]
                         fn wit_import() -> i64;
@@ -36,7 +65,7 @@ pub mod durable {
                     let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 8]);
                     let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
                     #[cfg(target_arch = "wasm32")]
-                    #[link(wasm_import_module = "durable:core/core@2.5.0")]
+                    #[link(wasm_import_module = "durable:core/core@2.7.0")]
                     extern "C" {
                         #[link_name = "task-name"]
                         fn wit_import(_: *mut u8);
@@ -62,7 +91,7 @@ pub mod durable {
                     let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 8]);
                     let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
                     #[cfg(target_arch = "wasm32")]
-                    #[link(wasm_import_module = "durable:core/core@2.5.0")]
+                    #[link(wasm_import_module = "durable:core/core@2.7.0")]
                     extern "C" {
                         #[link_name = "task-data"]
                         fn wit_import(_: *mut u8);
@@ -88,7 +117,7 @@ pub mod durable {
                     let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 16]);
                     let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
                     #[cfg(target_arch = "wasm32")]
-                    #[link(wasm_import_module = "durable:core/core@2.5.0")]
+                    #[link(wasm_import_module = "durable:core/core@2.7.0")]
                     extern "C" {
                         #[link_name = "task-created-at"]
                         fn wit_import(_: *mut u8);
@@ -107,6 +136,159 @@ pub mod durable {
                 }
             }
             #[allow(unused_unsafe, clippy::all)]
+            /// Get the name of the queue that this task was launched into.
+            ///
+            /// Tasks are queued by tenant, so this is the tenant that the task belongs
+            /// to, or an empty string if it does not belong to one.
+            pub fn task_queue() -> _rt::String {
+                unsafe {
+                    #[repr(align(4))]
+                    struct RetArea([::core::mem::MaybeUninit<u8>; 8]);
+                    let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 8]);
+                    let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(wasm_import_module = "durable:core/core@2.7.0")]
+                    extern "C" {
+                        #[link_name = "task-queue"]
+                        fn wit_import(_: *mut u8);
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    fn wit_import(_: *mut u8) {
+                        unreachable!()
+                    }
+                    wit_import(ptr0);
+                    let l1 = *ptr0.add(0).cast::<*mut u8>();
+                    let l2 = *ptr0.add(4).cast::<usize>();
+                    let len3 = l2;
+                    let bytes3 = _rt::Vec::from_raw_parts(l1.cast(), len3, len3);
+                    _rt::string_lift(bytes3)
+                }
+            }
+            #[allow(unused_unsafe, clippy::all)]
+            /// Get the key/value labels that the task was launched with.
+            pub fn task_labels() -> _rt::Vec<(_rt::String, _rt::String)> {
+                unsafe {
+                    #[repr(align(4))]
+                    struct RetArea([::core::mem::MaybeUninit<u8>; 8]);
+                    let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 8]);
+                    let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(wasm_import_module = "durable:core/core@2.7.0")]
+                    extern "C" {
+                        #[link_name = "task-labels"]
+                        fn wit_import(_: *mut u8);
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    fn wit_import(_: *mut u8) {
+                        unreachable!()
+                    }
+                    wit_import(ptr0);
+                    let l1 = *ptr0.add(0).cast::<*mut u8>();
+                    let l2 = *ptr0.add(4).cast::<usize>();
+                    let base9 = l1;
+                    let len9 = l2;
+                    let mut result9 = _rt::Vec::with_capacity(len9);
+                    for i in 0..len9 {
+                        let base = base9.add(i * 16);
+                        let e9 = {
+                            let l3 = *base.add(0).cast::<*mut u8>();
+                            let l4 = *base.add(4).cast::<usize>();
+                            let len5 = l4;
+                            let bytes5 = _rt::Vec::from_raw_parts(l3.cast(), len5, len5);
+                            let l6 = *base.add(8).cast::<*mut u8>();
+                            let l7 = *base.add(12).cast::<usize>();
+                            let len8 = l7;
+                            let bytes8 = _rt::Vec::from_raw_parts(l6.cast(), len8, len8);
+                            (_rt::string_lift(bytes5), _rt::string_lift(bytes8))
+                        };
+                        result9.push(e9);
+                    }
+                    _rt::cabi_dealloc(base9, len9 * 16, 4);
+                    result9
+                }
+            }
+            #[allow(unused_unsafe, clippy::all)]
+            /// Get the number of times that a worker has started running this task,
+            /// including the current one. This is 1 the first time that the task runs.
+            ///
+            /// This changes whenever the task is resumed, so calling it outside of a
+            /// transaction will trap.
+            pub fn task_attempt() -> u32 {
+                unsafe {
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(wasm_import_module = "durable:core/core@2.7.0")]
+                    extern "C" {
+                        #[link_name = "task-attempt"]
+                        fn wit_import() -> i32;
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    fn wit_import() -> i32 {
+                        unreachable!()
+                    }
+                    let ret = wit_import();
+                    ret as u32
+                }
+            }
+            #[allow(unused_unsafe, clippy::all)]
+            /// Get the id of the worker that is running this task.
+            ///
+            /// This changes whenever the task is resumed, so calling it outside of a
+            /// transaction will trap.
+            pub fn worker_id() -> i64 {
+                unsafe {
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(wasm_import_module = "durable:core/core@2.7.0")]
+                    extern "C" {
+                        #[link_name = "worker-id"]
+                        fn wit_import() -> i64;
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    fn wit_import() -> i64 {
+                        unreachable!()
+                    }
+                    let ret = wit_import();
+                    ret
+                }
+            }
+            #[allow(unused_unsafe, clippy::all)]
+            /// Set `key` in the metadata of the current task to the JSON-encoded
+            /// `value`, or remove it if `value` is none.
+            ///
+            /// Task metadata is a small JSON object that workflows can use to publish
+            /// their current status. It can be read by clients while the task is
+            /// running. The update is applied immediately and is not rolled back along
+            /// with the database changes made by the current transaction.
+            ///
+            /// Calling this outside of a transaction, with a value that is not valid
+            /// JSON, or with a value larger than 16 KiB will trap.
+            pub fn set_metadata(key: &str, value: Option<&str>) {
+                unsafe {
+                    let vec0 = key;
+                    let ptr0 = vec0.as_ptr().cast::<u8>();
+                    let len0 = vec0.len();
+                    let (result2_0, result2_1, result2_2) = match value {
+                        Some(e) => {
+                            let vec1 = e;
+                            let ptr1 = vec1.as_ptr().cast::<u8>();
+                            let len1 = vec1.len();
+                            (1i32, ptr1.cast_mut(), len1)
+                        }
+                        None => (0i32, ::core::ptr::null_mut(), 0usize),
+                    };
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(wasm_import_module = "durable:core/core@2.7.0")]
+                    extern "C" {
+                        #[link_name = "set-metadata"]
+                        fn wit_import(_: *mut u8, _: usize, _: i32, _: *mut u8, _: usize);
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    fn wit_import(_: *mut u8, _: usize, _: i32, _: *mut u8, _: usize) {
+                        unreachable!()
+                    }
+                    wit_import(ptr0.cast_mut(), len0, result2_0, result2_1, result2_2);
+                }
+            }
+            #[allow(unused_unsafe, clippy::all)]
             /// Start a transaction. If this transaction has already executed to completion
             /// then return the data from the last time it was executed.
             ///
@@ -126,7 +308,7 @@ pub mod durable {
                     let len0 = vec0.len();
                     let ptr1 = ret_area.0.as_mut_ptr().cast::<u8>();
                     #[cfg(target_arch = "wasm32")]
-                    #[link(wasm_import_module = "durable:core/core@2.5.0")]
+                    #[link(wasm_import_module = "durable:core/core@2.7.0")]
                     extern "C" {
                         #[link_name = "transaction-enter"]
                         fn wit_import(_: *mut u8, _: usize, _: i32, _: *mut u8);
@@ -166,222 +348,115 @@ pub mod durable {
                 }
             }
             #[allow(unused_unsafe, clippy::all)]
-            /// Complete a transaction, saving the result of this transaction for future use.
+            /// Start a transaction with additional options.
             ///
-            /// Parameters:
-            /// - `data` - JSON-encoded state to save.
-            pub fn transaction_exit(data: &str) {
-                unsafe {
-                    let vec0 = data;
-                    let ptr0 = vec0.as_ptr().cast::<u8>();
-                    let len0 = vec0.len();
-                    #[cfg(target_arch = "wasm32")]
-                    #[link(wasm_import_module = "durable:core/core@2.5.0")]
-                    extern "C" {
-                        #[link_name = "transaction-exit"]
-                        fn wit_import(_: *mut u8, _: usize);
-                    }
-                    #[cfg(not(target_arch = "wasm32"))]
-                    fn wit_import(_: *mut u8, _: usize) {
-                        unreachable!()
-                    }
-                    wit_import(ptr0.cast_mut(), len0);
-                }
-            }
-        }
-        #[allow(dead_code, clippy::all)]
-        pub mod notify {
-            #[used]
-            #[doc(hidden)]
-            static __FORCE_SECTION_REF: fn() = super::super::super::__link_custom_section_describing_imports;
-            use super::super::super::_rt;
-            pub type Datetime = super::super::super::wasi::clocks::wall_clock::Datetime;
-            /// A notification event.
-            #[derive(Clone)]
-            pub struct Event {
-                /// The wall-clock time at which this notification was created.
-                pub created_at: Datetime,
-                /// The name of the event itself.
-                pub event: _rt::String,
-                /// JSON-encoded data associated with the event.
-                pub data: _rt::String,
-            }
-            impl ::core::fmt::Debug for Event {
-                fn fmt(
-                    &self,
-                    f: &mut ::core::fmt::Formatter<'_>,
-                ) -> ::core::fmt::Result {
-                    f.debug_struct("Event")
-                        .field("created-at", &self.created_at)
-                        .field("event", &self.event)
-                        .field("data", &self.data)
-                        .finish()
-                }
-            }
-            /// Errors that can occur as when attempting to notify another task.
-            #[derive(Clone)]
-            pub enum NotifyError {
-                /// There is no task with the requested task id.
-                TaskNotFound,
-                /// There is a task with the requested id, but it is no longer running.
-                TaskDead,
-                /// Other unspecified errors that may occur, such as data not being valid JSON.
-                Other(_rt::String),
-            }
-            impl ::core::fmt::Debug for NotifyError {
-                fn fmt(
-                    &self,
-                    f: &mut ::core::fmt::Formatter<'_>,
-                ) -> ::core::fmt::Result {
-                    match self {
-                        NotifyError::TaskNotFound => {
-                            f.debug_tuple("NotifyError::TaskNotFound").finish()
-                        }
-                        NotifyError::TaskDead => {
-                            f.debug_tuple("NotifyError::TaskDead").finish()
-                        }
-                        NotifyError::Other(e) => {
-                            f.debug_tuple("NotifyError::Other").field(e).finish()
-                        }
-                    }
-                }
-            }
-            impl ::core::fmt::Display for NotifyError {
-                fn fmt(
-                    &self,
-                    f: &mut ::core::fmt::Formatter<'_>,
-                ) -> ::core::fmt::Result {
-                    write!(f, "{:?}", self)
-                }
-            }
-            impl std::error::Error for NotifyError {}
-            #[allow(unused_unsafe, clippy::all)]
-            /// Attempt to read the next available notification, if there is one.
-            /// notification: func() -> option<event>;
-            /// Read the next available notification, blocking until one is available.
-            pub fn notification_blocking() -> Event {
-                unsafe {
-                    #[repr(align(8))]
-                    struct RetArea([::core::mem::MaybeUninit<u8>; 32]);
-                    let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 32]);
-                    let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-                    #[cfg(target_arch = "wasm32")]
-                    #[link(wasm_import_module = "durable:core/notify@2.5.0")]
-                    extern "C" {
-                        #[link_name = "notification-blocking"]
-                        fn wit_import(_: *mut u8);
-                    }
-                    #[cfg(not(target_arch = "wasm32"))]
-                    fn wit_import(_: *mut u8) {
-                        unreachable!()
-                    }
-                    wit_import(ptr0);
-                    let l1 = *ptr0.add(0).cast::<i64>();
-                    let l2 = *ptr0.add(8).cast::<i32>();
-                    let l3 = *ptr0.add(16).cast::<*mut u8>();
-                    let l4 = *ptr0.add(20).cast::<usize>();
-                    let len5 = l4;
-                    let bytes5 = _rt::Vec::from_raw_parts(l3.cast(), len5, len5);
-                    let l6 = *ptr0.add(24).cast::<*mut u8>();
-                    let l7 = *ptr0.add(28).cast::<usize>();
-                    let len8 = l7;
-                    let bytes8 = _rt::Vec::from_raw_parts(l6.cast(), len8, len8);
-                    Event {
-                        created_at: super::super::super::wasi::clocks::wall_clock::Datetime {
-                            seconds: l1 as u64,
-                            nanoseconds: l2 as u32,
-                        },
-                        event: _rt::string_lift(bytes5),
-                        data: _rt::string_lift(bytes8),
-                    }
-                }
-            }
-            #[allow(unused_unsafe, clippy::all)]
-            /// Emit a notification for a task.
-            pub fn notify(
-                task: i64,
-                event: &str,
-                data: &str,
-            ) -> Result<(), NotifyError> {
+            /// This behaves exactly like `transaction-enter` otherwise.
+            pub fn transaction_enter2(
+                label: &str,
+                options: TransactionOptions,
+            ) -> Option<_rt::String> {
                 unsafe {
                     #[repr(align(4))]
-                    struct RetArea([::core::mem::MaybeUninit<u8>; 16]);
-                    let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 16]);
-                    let vec0 = event;
+                    struct RetArea([::core::mem::MaybeUninit<u8>; 12]);
+                    let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 12]);
+                    let vec0 = label;
                     let ptr0 = vec0.as_ptr().cast::<u8>();
                     let len0 = vec0.len();
-                    let vec1 = data;
-                    let ptr1 = vec1.as_ptr().cast::<u8>();
-                    let len1 = vec1.len();
-                    let ptr2 = ret_area.0.as_mut_ptr().cast::<u8>();
+                    let TransactionOptions {
+                        is_db: is_db1,
+                        statement_timeout: statement_timeout1,
+                        read_only: read_only1,
+                    } = options;
+                    let (result2_0, result2_1) = match statement_timeout1 {
+                        Some(e) => (1i32, _rt::as_i64(e)),
+                        None => (0i32, 0i64),
+                    };
+                    let ptr3 = ret_area.0.as_mut_ptr().cast::<u8>();
                     #[cfg(target_arch = "wasm32")]
-                    #[link(wasm_import_module = "durable:core/notify@2.5.0")]
+                    #[link(wasm_import_module = "durable:core/core@2.7.0")]
                     extern "C" {
-                        #[link_name = "notify"]
+                        #[link_name = "transaction-enter2"]
                         fn wit_import(
-                            _: i64,
-                            _: *mut u8,
-                            _: usize,
                             _: *mut u8,
                             _: usize,
+                            _: i32,
+                            _: i32,
+                            _: i64,
+                            _: i32,
                             _: *mut u8,
                         );
                     }
                     #[cfg(not(target_arch = "wasm32"))]
                     fn wit_import(
-                        _: i64,
-                        _: *mut u8,
-                        _: usize,
                         _: *mut u8,
                         _: usize,
+                        _: i32,
+                        _: i32,
+                        _: i64,
+                        _: i32,
                         _: *mut u8,
                     ) {
                         unreachable!()
                     }
                     wit_import(
-                        _rt::as_i64(&task),
                         ptr0.cast_mut(),
                         len0,
-                        ptr1.cast_mut(),
-                        len1,
-                        ptr2,
+                        match is_db1 {
+                            true => 1,
+                            false => 0,
+                        },
+                        result2_0,
+                        result2_1,
+                        match read_only1 {
+                            true => 1,
+                            false => 0,
+                        },
+                        ptr3,
                     );
-                    let l3 = i32::from(*ptr2.add(0).cast::<u8>());
-                    match l3 {
-                        0 => {
-                            let e = ();
-                            Ok(e)
-                        }
+                    let l4 = i32::from(*ptr3.add(0).cast::<u8>());
+                    match l4 {
+                        0 => None,
                         1 => {
                             let e = {
-                                let l4 = i32::from(*ptr2.add(4).cast::<u8>());
-                                let v8 = match l4 {
-                                    0 => NotifyError::TaskNotFound,
-                                    1 => NotifyError::TaskDead,
-                                    n => {
-                                        debug_assert_eq!(n, 2, "invalid enum discriminant");
-                                        let e8 = {
-                                            let l5 = *ptr2.add(8).cast::<*mut u8>();
-                                            let l6 = *ptr2.add(12).cast::<usize>();
-                                            let len7 = l6;
-                                            let bytes7 = _rt::Vec::from_raw_parts(
-                                                l5.cast(),
-                                                len7,
-                                                len7,
-                                            );
-                                            _rt::string_lift(bytes7)
-                                        };
-                                        NotifyError::Other(e8)
-                                    }
-                                };
-                                v8
+                                let l5 = *ptr3.add(4).cast::<*mut u8>();
+                                let l6 = *ptr3.add(8).cast::<usize>();
+                                let len7 = l6;
+                                let bytes7 = _rt::Vec::from_raw_parts(
+                                    l5.cast(),
+                                    len7,
+                                    len7,
+                                );
+                                _rt::string_lift(bytes7)
                             };
-                            Err(e)
+                            Some(e)
                         }
                         _ => _rt::invalid_enum_discriminant(),
                     }
                 }
             }
+            #[allow(unused_unsafe, clippy::all)]
+            /// Complete a transaction, saving the result of this transaction for future use.
+            ///
+            /// Parameters:
+            /// - `data` - JSON-encoded state to save.
+            pub fn transaction_exit(data: &str) {
+                unsafe {
+                    let vec0 = data;
+                    let ptr0 = vec0.as_ptr().cast::<u8>();
+                    let len0 = vec0.len();
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(wasm_import_module = "durable:core/core@2.7.0")]
+                    extern "C" {
+                        #[link_name = "transaction-exit"]
+                        fn wit_import(_: *mut u8, _: usize);
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    fn wit_import(_: *mut u8, _: usize) {
+                        unreachable!()
+                    }
+                    wit_import(ptr0.cast_mut(), len0);
+                }
+            }
         }
     }
 }
@@ -493,6 +568,13 @@ mod _rt {
             String::from_utf8_unchecked(bytes)
         }
     }
+    pub unsafe fn cabi_dealloc(ptr: *mut u8, size: usize, align: usize) {
+        if size == 0 {
+            return;
+        }
+        let layout = alloc::Layout::from_size_align_unchecked(size, align);
+        alloc::dealloc(ptr, layout);
+    }
     pub unsafe fn invalid_enum_discriminant<T>() -> T {
         if cfg!(debug_assertions) {
             panic!("invalid enum discriminant")
@@ -523,28 +605,29 @@ mod _rt {
             self as i64
         }
     }
+    pub use alloc_crate::alloc;
     extern crate alloc as alloc_crate;
 }
 #[cfg(target_arch = "wasm32")]
 #[link_section = "component-type:wit-bindgen:0.30.0:import-core:encoded world"]
 #[doc(hidden)]
-pub static __WIT_BINDGEN_COMPONENT_TYPE: [u8; 709] = *b"\
-\0asm\x0d\0\x01\0\0\x19\x16wit-component-encoding\x04\0\x07\xc3\x04\x01A\x02\x01\
-A\x07\x01B\x05\x01r\x02\x07secondsw\x0bnanosecondsy\x04\0\x08datetime\x03\0\0\x01\
-@\0\0\x01\x04\0\x03now\x01\x02\x04\0\x0aresolution\x01\x02\x03\x01\x1cwasi:clock\
-s/wall-clock@0.2.0\x05\0\x02\x03\0\0\x08datetime\x01B\x0e\x02\x03\x02\x01\x01\x04\
-\0\x08datetime\x03\0\0\x01@\0\0x\x04\0\x07task-id\x01\x02\x01@\0\0s\x04\0\x09tas\
-k-name\x01\x03\x04\0\x09task-data\x01\x03\x01@\0\0\x01\x04\0\x0ftask-created-at\x01\
-\x04\x01ks\x01@\x02\x05labels\x05is-db\x7f\0\x05\x04\0\x11transaction-enter\x01\x06\
-\x01@\x01\x04datas\x01\0\x04\0\x10transaction-exit\x01\x07\x03\x01\x17durable:co\
-re/core@2.5.0\x05\x02\x01B\x0b\x02\x03\x02\x01\x01\x04\0\x08datetime\x03\0\0\x01\
-r\x03\x0acreated-at\x01\x05events\x04datas\x04\0\x05event\x03\0\x02\x01q\x03\x0e\
-task-not-found\0\0\x09task-dead\0\0\x05other\x01s\0\x04\0\x0cnotify-error\x03\0\x04\
-\x01@\0\0\x03\x04\0\x15notification-blocking\x01\x06\x01j\0\x01\x05\x01@\x03\x04\
-taskx\x05events\x04datas\0\x07\x04\0\x06notify\x01\x08\x03\x01\x19durable:core/n\
-otify@2.5.0\x05\x03\x04\x01\x1edurable:core/import-core@2.5.0\x04\0\x0b\x11\x01\0\
-\x0bimport-core\x03\0\0\0G\x09producers\x01\x0cprocessed-by\x02\x0dwit-component\
-\x070.215.0\x10wit-bindgen-rust\x060.30.0";
+pub static __WIT_BINDGEN_COMPONENT_TYPE: [u8; 714] = *b"\
+\0asm\x0d\0\x01\0\0\x19\x16wit-component-encoding\x04\0\x07\xc8\x04\x01A\x02\x01\
+A\x05\x01B\x05\x01r\x02\x07secondsw\x0bnanosecondsy\x04\0\x08datetime\x03\0\0\
+\x01@\0\0\x01\x04\0\x03now\x01\x02\x04\0\x0aresolution\x01\x02\x03\x01\x1cwasi:c\
+locks/wall-clock@0.2.0\x05\0\x02\x03\0\0\x08datetime\x01B\x1d\x02\x03\x02\x01\
+\x01\x04\0\x08datetime\x03\0\0\x01kw\x01r\x03\x05is-db\x7f\x11statement-timeout\
+\x02\x09read-only\x7f\x04\0\x13transaction-options\x03\0\x03\x01@\0\0x\x04\0\x07\
+task-id\x01\x05\x01@\0\0s\x04\0\x09task-name\x01\x06\x04\0\x09task-data\x01\x06\
+\x01@\0\0\x01\x04\0\x0ftask-created-at\x01\x07\x04\0\x0atask-queue\x01\x06\x01o\
+\x02ss\x01p\x08\x01@\0\0\x09\x04\0\x0btask-labels\x01\x0a\x01@\0\0y\x04\0\x0ctas\
+k-attempt\x01\x0b\x04\0\x09worker-id\x01\x05\x01ks\x01@\x02\x03keys\x05value\x0c\
+\x01\0\x04\0\x0cset-metadata\x01\x0d\x01@\x02\x05labels\x05is-db\x7f\0\x0c\x04\0\
+\x11transaction-enter\x01\x0e\x01@\x02\x05labels\x07options\x04\0\x0c\x04\0\x12t\
+ransaction-enter2\x01\x0f\x01@\x01\x04datas\x01\0\x04\0\x10transaction-exit\x01\
+\x10\x03\x01\x17durable:core/core@2.7.0\x05\x02\x04\x01\x1edurable:core/import-c\
+ore@2.7.0\x04\0\x0b\x11\x01\0\x0bimport-core\x03\0\0\0G\x09producers\x01\x0cproc\
+essed-by\x02\x0dwit-component\x070.215.0\x10wit-bindgen-rust\x060.30.0";
 #[inline(never)]
 #[doc(hidden)]
 pub fn __link_custom_section_describing_imports() {
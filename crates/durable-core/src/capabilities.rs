@@ -0,0 +1,78 @@
+//! Discover what the worker running the workflow supports.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::time::Duration;
+
+#[cfg(not(all(feature = "mock", not(target_family = "wasm"))))]
+use crate::capabilities_bindings::durable::core::capabilities as bindings;
+#[cfg(all(feature = "mock", not(target_family = "wasm")))]
+use crate::mock::bindings::durable::core::capabilities as bindings;
+
+/// The interfaces and limits of the worker that is running the workflow.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Capabilities {
+    interfaces: Vec<String>,
+    limits: WorkerLimits,
+}
+
+/// Limits that the worker applies to workflows.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WorkerLimits {
+    /// The maximum number of events that a single task can record.
+    pub max_events: u32,
+
+    /// The maximum number of bytes that a task can log within a single
+    /// transaction.
+    pub max_log_bytes_per_transaction: u64,
+
+    /// The maximum size, in bytes, of a buffer returned by a host function.
+    pub max_returned_buffer_len: u64,
+
+    /// The maximum timeout of an HTTP request.
+    pub max_http_timeout: Duration,
+}
+
+impl Capabilities {
+    /// Query the worker for its capabilities.
+    ///
+    /// Different workers may answer differently, so this should only be
+    /// called from within a transaction.
+    pub fn current() -> Self {
+        let limits = bindings::limits();
+
+        Self {
+            interfaces: bindings::interfaces(),
+            limits: WorkerLimits {
+                max_events: limits.max_events,
+                max_log_bytes_per_transaction: limits.max_log_bytes_per_transaction,
+                max_returned_buffer_len: limits.max_returned_buffer_len,
+                max_http_timeout: Duration::from_millis(limits.max_http_timeout),
+            },
+        }
+    }
+
+    /// The fully qualified names of the interfaces that the worker provides,
+    /// including their versions (e.g. `durable:core/sql@2.7.0`).
+    pub fn interfaces(&self) -> &[String] {
+        &self.interfaces
+    }
+
+    /// Whether the worker provides the interface `name`.
+    ///
+    /// `name` may leave out the version (e.g. `durable:core/object-store`), in
+    /// which case any version of the interface matches.
+    pub fn has_interface(&self, name: &str) -> bool {
+        self.interfaces.iter().any(|interface| {
+            interface == name
+                || interface
+                    .split_once('@')
+                    .is_some_and(|(unversioned, _)| unversioned == name)
+        })
+    }
+
+    /// The limits that the worker applies to the workflow.
+    pub fn limits(&self) -> &WorkerLimits {
+        &self.limits
+    }
+}
@@ -0,0 +1,153 @@
+#[allow(dead_code)]
+pub mod durable {
+    #[allow(dead_code)]
+    pub mod core {
+        #[allow(dead_code, clippy::all)]
+        pub mod capabilities {
+            #[used]
+            #[doc(hidden)]
+            static __FORCE_SECTION_REF: fn() = super::super::super::__link_custom_section_describing_imports;
+            use super::super::super::_rt;
+            /// Limits that the worker applies to workflows.
+            #[repr(C)]
+            #[derive(Clone, Copy)]
+            pub struct WorkerLimits {
+                /// The maximum number of events that a single task can record.
+                pub max_events: u32,
+                /// The maximum number of bytes that a task can log within a single
+                /// transaction.
+                pub max_log_bytes_per_transaction: u64,
+                /// The maximum size, in bytes, of a buffer returned by a host function.
+                pub max_returned_buffer_len: u64,
+                /// The maximum timeout of an HTTP request, in milliseconds.
+                pub max_http_timeout: u64,
+            }
+            impl ::core::fmt::Debug for WorkerLimits {
+                fn fmt(
+                    &self,
+                    f: &mut ::core::fmt::Formatter<'_>,
+                ) -> ::core::fmt::Result {
+                    f.debug_struct("WorkerLimits")
+                        .field("max-events", &self.max_events)
+                        .field(
+                            "max-log-bytes-per-transaction",
+                            &self.max_log_bytes_per_transaction,
+                        )
+                        .field("max-returned-buffer-len", &self.max_returned_buffer_len)
+                        .field("max-http-timeout", &self.max_http_timeout)
+                        .finish()
+                }
+            }
+            #[allow(unused_unsafe, clippy::all)]
+            /// The interfaces that the worker provides to the workflow.
+            ///
+            /// Each one is the fully qualified name of the interface, including its
+            /// version (e.g. `durable:core/sql@2.7.0`).
+            pub fn interfaces() -> _rt::Vec<_rt::String> {
+                unsafe {
+                    #[repr(align(4))]
+                    struct RetArea([::core::mem::MaybeUninit<u8>; 8]);
+                    let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 8]);
+                    let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(wasm_import_module = "durable:core/capabilities@2.7.0")]
+                    extern "C" {
+                        #[link_name = "interfaces"]
+                        fn wit_import(_: *mut u8);
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    fn wit_import(_: *mut u8) {
+                        unreachable!()
+                    }
+                    wit_import(ptr0);
+                    let l1 = *ptr0.add(0).cast::<*mut u8>();
+                    let l2 = *ptr0.add(4).cast::<usize>();
+                    let base6 = l1;
+                    let len6 = l2;
+                    let mut result6 = _rt::Vec::with_capacity(len6);
+                    for i in 0..len6 {
+                        let base = base6.add(i * 8);
+                        let e6 = {
+                            let l3 = *base.add(0).cast::<*mut u8>();
+                            let l4 = *base.add(4).cast::<usize>();
+                            let len5 = l4;
+                            let bytes5 = _rt::Vec::from_raw_parts(l3.cast(), len5, len5);
+                            _rt::string_lift(bytes5)
+                        };
+                        result6.push(e6);
+                    }
+                    _rt::cabi_dealloc(base6, len6 * 8, 4);
+                    result6
+                }
+            }
+            #[allow(unused_unsafe, clippy::all)]
+            /// The limits that the worker applies to the workflow.
+            pub fn limits() -> WorkerLimits {
+                unsafe {
+                    #[repr(align(8))]
+                    struct RetArea([::core::mem::MaybeUninit<u8>; 32]);
+                    let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 32]);
+                    let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(wasm_import_module = "durable:core/capabilities@2.7.0")]
+                    extern "C" {
+                        #[link_name = "limits"]
+                        fn wit_import(_: *mut u8);
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    fn wit_import(_: *mut u8) {
+                        unreachable!()
+                    }
+                    wit_import(ptr0);
+                    let l1 = *ptr0.add(0).cast::<i32>();
+                    let l2 = *ptr0.add(8).cast::<i64>();
+                    let l3 = *ptr0.add(16).cast::<i64>();
+                    let l4 = *ptr0.add(24).cast::<i64>();
+                    WorkerLimits {
+                        max_events: l1 as u32,
+                        max_log_bytes_per_transaction: l2 as u64,
+                        max_returned_buffer_len: l3 as u64,
+                        max_http_timeout: l4 as u64,
+                    }
+                }
+            }
+        }
+    }
+}
+mod _rt {
+    pub use alloc_crate::string::String;
+    pub use alloc_crate::vec::Vec;
+    pub unsafe fn string_lift(bytes: Vec<u8>) -> String {
+        if cfg!(debug_assertions) {
+            String::from_utf8(bytes).unwrap()
+        } else {
+            String::from_utf8_unchecked(bytes)
+        }
+    }
+    pub unsafe fn cabi_dealloc(ptr: *mut u8, size: usize, align: usize) {
+        if size == 0 {
+            return;
+        }
+        let layout = alloc::Layout::from_size_align_unchecked(size, align);
+        alloc::dealloc(ptr, layout);
+    }
+    extern crate alloc as alloc_crate;
+    pub use alloc_crate::alloc;
+}
+#[cfg(target_arch = "wasm32")]
+#[link_section = "component-type:wit-bindgen:0.30.0:import-capabilities:encoded world"]
+#[doc(hidden)]
+pub static __WIT_BINDGEN_COMPONENT_TYPE: [u8; 373] = *b"\
+\0asm\x0d\0\x01\0\0\x19\x16wit-component-encoding\x04\0\x07\xeb\x01\x01A\x02\x01\
+A\x02\x01B\x07\x01r\x04\x0amax-eventsy\x1dmax-log-bytes-per-transactionw\x17max-\
+returned-buffer-lenw\x10max-http-timeoutw\x04\0\x0dworker-limits\x03\0\0\x01ps\
+\x01@\0\0\x02\x04\0\x0ainterfaces\x01\x03\x01@\0\0\x01\x04\0\x06limits\x01\x04\
+\x03\x01\x1fdurable:core/capabilities@2.7.0\x05\0\x04\x01&durable:core/import-ca\
+pabilities@2.7.0\x04\0\x0b\x19\x01\0\x13import-capabilities\x03\0\0\0G\x09produc\
+ers\x01\x0cprocessed-by\x02\x0dwit-component\x070.215.0\x10wit-bindgen-rust\x060\
+.30.0";
+#[inline(never)]
+#[doc(hidden)]
+pub fn __link_custom_section_describing_imports() {
+    wit_bindgen_rt::maybe_link_cabi_realloc();
+}
@@ -0,0 +1,60 @@
+//! Pluggable serialization for transaction results and task data.
+//!
+//! The runtime stores the results of transactions and the data of tasks as
+//! JSON. By default these are encoded using [`serde_json`] via the [`Json`]
+//! codec. Workflows that are built without the `json` feature (e.g. tiny
+//! `no_std` components) can provide their own [`Codec`] instead and use it
+//! with [`transaction_with_codec`] and [`task_data_with`].
+//!
+//! [`transaction_with_codec`]: crate::transaction::transaction_with_codec
+//! [`task_data_with`]: crate::task_data_with
+
+use alloc::string::String;
+use core::fmt;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+/// A serializer used to convert values to and from the JSON stored by the
+/// runtime.
+///
+/// The output of [`encode`](Codec::encode) must be valid JSON. The runtime
+/// will reject any transaction result that is not, which kills the workflow.
+pub trait Codec {
+    /// The error returned when a value cannot be encoded or decoded.
+    type Error: fmt::Display;
+
+    /// Serialize `value` to a JSON string.
+    fn encode<T>(value: &T) -> Result<String, Self::Error>
+    where
+        T: Serialize + ?Sized;
+
+    /// Deserialize a value from a JSON string.
+    fn decode<T>(data: &str) -> Result<T, Self::Error>
+    where
+        T: DeserializeOwned;
+}
+
+/// A [`Codec`] that uses [`serde_json`].
+#[cfg(feature = "json")]
+#[derive(Copy, Clone, Debug, Default)]
+pub struct Json;
+
+#[cfg(feature = "json")]
+impl Codec for Json {
+    type Error = serde_json::Error;
+
+    fn encode<T>(value: &T) -> Result<String, Self::Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        serde_json::to_string(value)
+    }
+
+    fn decode<T>(data: &str) -> Result<T, Self::Error>
+    where
+        T: DeserializeOwned,
+    {
+        serde_json::from_str(data)
+    }
+}
@@ -0,0 +1,121 @@
+#[allow(dead_code)]
+pub mod exports {
+    #[allow(dead_code)]
+    pub mod durable {
+        #[allow(dead_code)]
+        pub mod core {
+            #[allow(dead_code, clippy::all)]
+            pub mod workflow {
+                #[used]
+                #[doc(hidden)]
+                static __FORCE_SECTION_REF: fn() = super::super::super::super::__link_custom_section_describing_imports;
+                use super::super::super::super::_rt;
+                #[doc(hidden)]
+                #[allow(non_snake_case)]
+                pub unsafe fn _export_run_cabi<T: Guest>(
+                    arg0: *mut u8,
+                    arg1: usize,
+                ) -> i32 {
+                    #[cfg(target_arch = "wasm32")] _rt::run_ctors_once();
+                    let len0 = arg1;
+                    let bytes0 = _rt::Vec::from_raw_parts(arg0.cast(), len0, len0);
+                    let result1 = T::run(_rt::string_lift(bytes0));
+                    let result2 = match result1 {
+                        Ok(_) => 0i32,
+                        Err(_) => 1i32,
+                    };
+                    result2
+                }
+                pub trait Guest {
+                    /// Run the workflow with the requested name.
+                    ///
+                    /// Returning an error marks the task as failed, exactly as if `wasi:cli/run`
+                    /// had returned an error.
+                    fn run(entrypoint: _rt::String) -> Result<(), ()>;
+                }
+                #[doc(hidden)]
+                #[macro_export]
+                macro_rules! __export_durable_core_workflow_2_7_0_cabi {
+                    ($ty:ident with_types_in $($path_to_types:tt)*) => {
+                        const _ : () = { #[export_name =
+                        "durable:core/workflow@2.7.0#run"] unsafe extern "C" fn
+                        export_run(arg0 : * mut u8, arg1 : usize,) -> i32 {
+                        $($path_to_types)*:: _export_run_cabi::<$ty > (arg0, arg1) } };
+                    };
+                }
+                #[doc(hidden)]
+                pub use __export_durable_core_workflow_2_7_0_cabi;
+            }
+        }
+    }
+}
+mod _rt {
+    #[cfg(target_arch = "wasm32")]
+    pub fn run_ctors_once() {
+        wit_bindgen_rt::run_ctors_once();
+    }
+    pub use alloc_crate::vec::Vec;
+    pub unsafe fn string_lift(bytes: Vec<u8>) -> String {
+        if cfg!(debug_assertions) {
+            String::from_utf8(bytes).unwrap()
+        } else {
+            String::from_utf8_unchecked(bytes)
+        }
+    }
+    pub use alloc_crate::string::String;
+    extern crate alloc as alloc_crate;
+}
+/// Generates `#[no_mangle]` functions to export the specified type as the
+/// root implementation of all generated traits.
+///
+/// For more information see the documentation of `wit_bindgen::generate!`.
+///
+/// ```rust
+/// # macro_rules! __export_workflow{ ($($t:tt)*) => (); }
+/// # trait Guest {}
+/// struct MyType;
+///
+/// impl Guest for MyType {
+///     // ...
+/// }
+///
+/// __export_workflow!(MyType);
+/// ```
+#[allow(unused_macros)]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __export_export_workflow_impl {
+    ($ty:ident) => {
+        self::__export_workflow!($ty with_types_in self);
+    };
+    ($ty:ident with_types_in $($path_to_types_root:tt)*) => {
+        $($path_to_types_root)*::
+        exports::durable::core::workflow::__export_durable_core_workflow_2_7_0_cabi!($ty
+        with_types_in $($path_to_types_root)*:: exports::durable::core::workflow); const
+        _ : () = { #[cfg(target_arch = "wasm32")] #[link_section =
+        "component-type:wit-bindgen:0.30.0:export-workflow:imports and exports"]
+        #[doc(hidden)] pub static __WIT_BINDGEN_COMPONENT_TYPE : [u8; 242] = *
+        b"\
+\0asm\x0d\0\x01\0\0\x19\x16wit-component-encoding\x04\0\x07m\x01A\x02\x01A\x02\x01\
+B\x03\x01j\0\0\x01@\x01\x0aentrypoints\0\0\x04\0\x03run\x01\x01\x04\x01\x1bdurab\
+le:core/workflow@2.7.0\x05\0\x04\x01\"durable:core/export-workflow@2.7.0\x04\0\x0b\
+\x15\x01\0\x0fexport-workflow\x03\0\0\0G\x09producers\x01\x0cprocessed-by\x02\x0d\
+wit-component\x070.215.0\x10wit-bindgen-rust\x060.30.0";
+        };
+    };
+}
+#[doc(inline)]
+pub use __export_export_workflow_impl as __export_workflow;
+#[cfg(target_arch = "wasm32")]
+#[link_section = "component-type:wit-bindgen:0.30.0:export-workflow-with-all-of-its-exports-removed:encoded world"]
+#[doc(hidden)]
+pub static __WIT_BINDGEN_COMPONENT_TYPE: [u8; 242] = *b"\
+\0asm\x0d\0\x01\0\0\x19\x16wit-component-encoding\x04\0\x07M\x01A\x02\x01A\0\x04\
+\x01Bdurable:core/export-workflow-with-all-of-its-exports-removed@2.7.0\x04\0\x0b\
+5\x01\0/export-workflow-with-all-of-its-exports-removed\x03\0\0\0G\x09producers\x01\
+\x0cprocessed-by\x02\x0dwit-component\x070.215.0\x10wit-bindgen-rust\x060.30.0";
+#[inline(never)]
+#[doc(hidden)]
+pub fn __link_custom_section_describing_imports() {
+    wit_bindgen_rt::maybe_link_cabi_realloc();
+}
@@ -1,15 +1,61 @@
-use std::time::{Duration, SystemTime};
+//! Core bindings for the durable guest APIs.
+//!
+//! # `no_std` support
+//! Disabling the default `std` feature makes this crate `no_std`, so that it
+//! only requires `alloc`. This is meant for tiny components that don't want to
+//! pull in the standard library. Only task data, transactions, and [`abort`]
+//! are available in this mode.
+//!
+//! Transaction results and task data are serialized via a [`Codec`]. The
+//! `json` feature provides one based on `serde_json`, otherwise use
+//! [`transaction_with_codec`] and [`task_data_with`] with your own codec.
+//!
+//! Without `std` panics within a transaction cannot be caught and no panic
+//! hook is installed, so the component needs to provide its own
+//! `#[panic_handler]`.
+//!
+//! [`Codec`]: crate::codec::Codec
+//! [`transaction_with_codec`]: crate::transaction::transaction_with_codec
 
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use core::time::Duration;
+#[cfg(feature = "std")]
+use std::time::SystemTime;
+
+#[cfg(feature = "json")]
 pub use serde_json::value::RawValue;
 
 #[macro_use]
 extern crate serde;
 
-// mod alloc;
+pub mod capabilities;
+pub mod codec;
+#[cfg(feature = "lock")]
+pub mod lock;
+#[cfg(all(feature = "mock", not(target_family = "wasm")))]
+pub mod mock;
+#[cfg(feature = "notify")]
 pub mod notify;
+#[cfg(feature = "ratelimit")]
+pub mod ratelimit;
+// The panic hook and constructor are only needed when running within the
+// durable runtime.
+#[cfg(all(
+    feature = "std",
+    not(all(feature = "mock", not(target_family = "wasm")))
+))]
 mod start;
+#[cfg(feature = "std")]
+pub mod tasks;
 pub mod transaction;
 
+#[cfg(all(feature = "mock", not(target_family = "wasm")))]
+use crate::mock::bindings;
+
+#[cfg(not(all(feature = "mock", not(target_family = "wasm"))))]
 #[allow(unused_imports, unused_braces, clippy::all)]
 mod bindings {
     include!("bindings.rs");
@@ -17,12 +63,83 @@ mod bindings {
     pub use self::durable::core::core::*;
 }
 
+#[cfg(not(all(feature = "mock", not(target_family = "wasm"))))]
+#[allow(unused_imports, unused_braces, clippy::all)]
+mod capabilities_bindings {
+    include!("capabilities_bindings.rs");
+}
+
+/// Bindings for the `durable:core/workflow` export.
+///
+/// These are used by the `durable::entrypoints!` macro and are not meant to be
+/// used directly.
+#[doc(hidden)]
+#[allow(unused_imports, unused_braces, clippy::all)]
+pub mod export_bindings {
+    include!("exports.rs");
+
+    pub use self::exports::durable::core::workflow::Guest;
+}
+
+#[cfg(all(
+    feature = "lock",
+    not(all(feature = "mock", not(target_family = "wasm")))
+))]
+#[allow(unused_imports, unused_braces, clippy::all)]
+mod lock_bindings {
+    include!("lock_bindings.rs");
+}
+
+#[cfg(all(
+    feature = "notify",
+    not(all(feature = "mock", not(target_family = "wasm")))
+))]
+#[allow(unused_imports, unused_braces, clippy::all)]
+mod notify_bindings {
+    include!("notify_bindings.rs");
+}
+
+#[cfg(not(all(feature = "mock", not(target_family = "wasm"))))]
+#[allow(unused_imports, unused_braces, clippy::all)]
+mod panic_bindings {
+    include!("panic_bindings.rs");
+}
+
+#[cfg(all(
+    feature = "ratelimit",
+    not(all(feature = "mock", not(target_family = "wasm")))
+))]
+#[allow(unused_imports, unused_braces, clippy::all)]
+mod ratelimit_bindings {
+    include!("ratelimit_bindings.rs");
+}
+
+#[cfg(not(all(feature = "mock", not(target_family = "wasm"))))]
+#[allow(unused_imports, unused_braces, clippy::all)]
+mod scheduler_bindings {
+    include!("scheduler_bindings.rs");
+}
+
+#[cfg(all(
+    feature = "std",
+    not(all(feature = "mock", not(target_family = "wasm")))
+))]
+#[allow(unused_imports, unused_braces, clippy::all)]
+mod tasks_bindings {
+    include!("tasks_bindings.rs");
+}
+
+#[doc(hidden)]
+pub use wit_bindgen_rt;
+
 #[doc(inline)]
-pub use crate::bindings::durable::core::core::{task_id, task_name};
+pub use crate::bindings::durable::core::core::{task_id, task_labels, task_name, task_queue};
+#[cfg(feature = "json")]
 pub use crate::transaction::transaction;
 
 /// Read the JSON data that this task was created with.
-pub fn task_data() -> Box<RawValue> {
+#[cfg(feature = "json")]
+pub fn task_data() -> alloc::boxed::Box<RawValue> {
     let data = crate::bindings::task_data();
     let data = data.into_boxed_str();
 
@@ -31,19 +148,110 @@ pub fn task_data() -> Box<RawValue> {
     //    transmute is safe on its own.
     // 2. The runtime guarantees that the task data is valid json, so this does not
     //    create an invalid RawValue instance.
-    unsafe { std::mem::transmute(data) }
+    unsafe { core::mem::transmute(data) }
+}
+
+/// Read the data that this task was created with, deserializing it using the
+/// codec `C`.
+pub fn task_data_with<C, T>() -> Result<T, C::Error>
+where
+    C: codec::Codec,
+    T: serde::de::DeserializeOwned,
+{
+    C::decode(&crate::bindings::task_data())
 }
 
 /// Get the timestamp that this task was created at.
+#[cfg(feature = "std")]
 pub fn task_created_at() -> SystemTime {
+    SystemTime::UNIX_EPOCH + task_created_at_unix()
+}
+
+/// Get the time that this task was created at, as a duration since the unix
+/// epoch.
+pub fn task_created_at_unix() -> Duration {
     let datetime = crate::bindings::task_created_at();
-    let duration = Duration::new(datetime.seconds, datetime.nanoseconds);
 
-    SystemTime::UNIX_EPOCH + duration
+    Duration::new(datetime.seconds, datetime.nanoseconds)
+}
+
+/// Get the number of times that a worker has started running this task,
+/// including the current one.
+///
+/// This is 1 the first time that the task runs and goes up every time the task
+/// is resumed after being suspended or after the worker running it went away.
+///
+/// # Traps
+/// This changes whenever the task is resumed, so calling it outside of a
+/// transaction will result in a trap that instantly kills the workflow.
+pub fn task_attempt() -> u32 {
+    crate::bindings::task_attempt()
+}
+
+/// Get the id of the worker that is running this task.
+///
+/// # Traps
+/// This changes whenever the task is resumed, so calling it outside of a
+/// transaction will result in a trap that instantly kills the workflow.
+pub fn worker_id() -> i64 {
+    crate::bindings::worker_id()
+}
+
+/// Set `key` in the metadata of the current task to the JSON-encoded `value`,
+/// or remove it if `value` is `None`.
+///
+/// The update is visible to clients immediately and is not rolled back along
+/// with the database changes made by the current transaction.
+///
+/// # Traps
+/// Calling this outside of a transaction, with a value that is not valid JSON,
+/// or with a value larger than 16 KiB will result in a trap that instantly
+/// kills the workflow.
+pub fn set_metadata(key: &str, value: Option<&str>) {
+    crate::bindings::set_metadata(key, value)
+}
+
+/// Give the worker a chance to run other tasks.
+///
+/// Workers normally preempt long-running workflow code on their own, so this
+/// is only needed if the worker has epoch interruption disabled. Calling it
+/// within a CPU-bound loop lets other tasks make progress and allows the
+/// worker to shut down without waiting for the loop to finish.
+///
+/// This is not recorded in the workflow's event log, so it can be called
+/// anywhere, including within a transaction.
+pub fn yield_now() {
+    #[cfg(not(all(feature = "mock", not(target_family = "wasm"))))]
+    crate::scheduler_bindings::durable::core::scheduler::yield_now();
+}
+
+/// Mark a point at which the workflow can be moved to another worker.
+///
+/// Everything a workflow does within its transactions is already persisted in
+/// its event log, so a task that is restarted only has to replay those results
+/// instead of redoing the work. `checkpoint` lets the worker take advantage of
+/// that: if the worker wants to get rid of the task (e.g. because it is
+/// shutting down) then the task is released at this point and another worker
+/// resumes it from here. Otherwise this returns immediately.
+///
+/// Good places for a checkpoint are right after an expensive transaction has
+/// completed, or between the iterations of a long-running loop.
+///
+/// # Traps
+/// Attempting to call this function within a transaction will result in a trap
+/// that instantly kills the workflow.
+pub fn checkpoint() {
+    #[cfg(not(all(feature = "mock", not(target_family = "wasm"))))]
+    crate::scheduler_bindings::durable::core::scheduler::checkpoint();
 }
 
 /// Immediately abort the workflow with a message.
+#[cfg(feature = "std")]
 pub fn abort(message: &str) -> ! {
+    // Exiting the process would take the whole test harness down with it.
+    #[cfg(all(feature = "mock", not(target_family = "wasm")))]
+    crate::transaction::maybe_txn::<_, ()>("durable::abort", || panic!("{message}"));
+
     crate::transaction::maybe_txn::<_, ()>("durable::abort", || {
         eprintln!("{message}");
 
@@ -54,3 +262,14 @@ pub fn abort(message: &str) -> ! {
     // unreachable instruction.
     std::process::abort()
 }
+
+/// Immediately abort the workflow with a message.
+///
+/// Without std the message is reported to the runtime as the reason that the
+/// task failed before panicking.
+#[cfg(not(feature = "std"))]
+pub fn abort(message: &str) -> ! {
+    crate::panic_bindings::durable::core::panic::report_panic(message, None);
+
+    panic!("{message}")
+}
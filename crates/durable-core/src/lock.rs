@@ -0,0 +1,63 @@
+//! Leases on named locks that are shared between tasks.
+
+use std::time::Duration;
+
+#[cfg(not(all(feature = "mock", not(target_family = "wasm"))))]
+use crate::lock_bindings::durable::core::lock as bindings;
+#[cfg(all(feature = "mock", not(target_family = "wasm")))]
+use crate::mock::bindings::durable::core::lock as bindings;
+
+/// The event of the notification that is sent to tasks waiting on a lock when
+/// a permit for that lock is released.
+///
+/// The notification data is a JSON object with the name of the lock in the
+/// `name` field.
+pub const LOCK_RELEASED_EVENT: &str = "durable:lock-released";
+
+/// The result of [`try_acquire`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum AcquireResult {
+    /// The current task now holds a permit for the lock.
+    Acquired,
+
+    /// All permits for the lock are held by other tasks.
+    Held {
+        /// The time remaining until the first of the held permits expires, if
+        /// any of them have a ttl.
+        expires_in: Option<Duration>,
+    },
+}
+
+/// Attempt to acquire one of `permits` permits for the lock `name`.
+///
+/// If `ttl` is set then the permit expires once that much time has passed.
+/// Permits are also released automatically when the task holding them
+/// completes or fails. If the current task already holds a permit for the lock
+/// then its ttl is refreshed.
+///
+/// If no permit is available then the current task will be sent a
+/// [`LOCK_RELEASED_EVENT`] notification the next time one is released.
+///
+/// # Traps
+/// Attempting to call this function within a transaction will result in a trap
+/// that instantly kills the workflow.
+pub fn try_acquire(name: &str, permits: u32, ttl: Option<Duration>) -> AcquireResult {
+    let ttl = ttl.map(|ttl| ttl.as_millis().try_into().unwrap_or(u64::MAX));
+
+    match bindings::try_acquire(name, permits, ttl) {
+        bindings::AcquireResult::Acquired => AcquireResult::Acquired,
+        bindings::AcquireResult::Held(expires_in) => AcquireResult::Held {
+            expires_in: expires_in.map(Duration::from_millis),
+        },
+    }
+}
+
+/// Release the permit that the current task holds for the lock `name`, if
+/// there is one.
+///
+/// # Traps
+/// Attempting to call this function within a transaction will result in a trap
+/// that instantly kills the workflow.
+pub fn release(name: &str) {
+    bindings::release(name)
+}
@@ -0,0 +1,258 @@
+#[allow(dead_code)]
+pub mod durable {
+    #[allow(dead_code)]
+    pub mod core {
+        #[allow(dead_code, clippy::all)]
+        pub mod lock {
+            #[used]
+            #[doc(hidden)]
+            static __FORCE_SECTION_REF: fn() = super::super::super::__link_custom_section_describing_imports;
+            use super::super::super::_rt;
+            /// The result of attempting to acquire a permit for a lock.
+            #[derive(Clone, Copy)]
+            pub enum AcquireResult {
+                /// The current task now holds a permit for the lock.
+                Acquired,
+                /// All permits for the lock are held by other tasks.
+                ///
+                /// If any of those permits have a ttl then this contains the number of
+                /// milliseconds until the first of them expires.
+                Held(Option<u64>),
+            }
+            impl ::core::fmt::Debug for AcquireResult {
+                fn fmt(
+                    &self,
+                    f: &mut ::core::fmt::Formatter<'_>,
+                ) -> ::core::fmt::Result {
+                    match self {
+                        AcquireResult::Acquired => {
+                            f.debug_tuple("AcquireResult::Acquired").finish()
+                        }
+                        AcquireResult::Held(e) => {
+                            f.debug_tuple("AcquireResult::Held").field(e).finish()
+                        }
+                    }
+                }
+            }
+            #[allow(unused_unsafe, clippy::all)]
+            /// Attempt to acquire one of `permits` permits for the lock `name`.
+            ///
+            /// If `ttl` is set then the permit expires after that many milliseconds.
+            /// Permits are also released when the task holding them completes or
+            /// fails. If the current task already holds a permit for the lock then its
+            /// ttl is refreshed.
+            ///
+            /// If no permit is available, then the current task is sent a notification
+            /// with the event `durable:lock-released` and the data `{ "name": <name> }`
+            /// the next time that a permit for the lock is released.
+            ///
+            /// # Traps
+            /// This function will trap if called from within a transaction.
+            pub fn try_acquire(
+                name: &str,
+                permits: u32,
+                ttl: Option<u64>,
+            ) -> AcquireResult {
+                unsafe {
+                    #[repr(align(8))]
+                    struct RetArea([::core::mem::MaybeUninit<u8>; 24]);
+                    let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 24]);
+                    let vec0 = name;
+                    let ptr0 = vec0.as_ptr().cast::<u8>();
+                    let len0 = vec0.len();
+                    let (result1_0, result1_1) = match ttl {
+                        Some(e) => (1i32, _rt::as_i64(e)),
+                        None => (0i32, 0i64),
+                    };
+                    let ptr2 = ret_area.0.as_mut_ptr().cast::<u8>();
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(wasm_import_module = "durable:core/lock@2.7.0")]
+                    extern "C" {
+                        #[link_name = "try-acquire"]
+                        fn wit_import(
+                            _: *mut u8,
+                            _: usize,
+                            _: i32,
+                            _: i32,
+                            _: i64,
+                            _: *mut u8,
+                        );
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    fn wit_import(
+                        _: *mut u8,
+                        _: usize,
+                        _: i32,
+                        _: i32,
+                        _: i64,
+                        _: *mut u8,
+                    ) {
+                        unreachable!()
+                    }
+                    wit_import(
+                        ptr0.cast_mut(),
+                        len0,
+                        _rt::as_i32(&permits),
+                        result1_0,
+                        result1_1,
+                        ptr2,
+                    );
+                    let l3 = i32::from(*ptr2.add(0).cast::<u8>());
+                    let v7 = match l3 {
+                        0 => AcquireResult::Acquired,
+                        n => {
+                            debug_assert_eq!(n, 1, "invalid enum discriminant");
+                            let e7 = {
+                                let l4 = i32::from(*ptr2.add(8).cast::<u8>());
+                                match l4 {
+                                    0 => None,
+                                    1 => {
+                                        let e = {
+                                            let l5 = *ptr2.add(16).cast::<i64>();
+                                            l5 as u64
+                                        };
+                                        Some(e)
+                                    }
+                                    _ => _rt::invalid_enum_discriminant(),
+                                }
+                            };
+                            AcquireResult::Held(e7)
+                        }
+                    };
+                    v7
+                }
+            }
+            #[allow(unused_unsafe, clippy::all)]
+            /// Release the permit that the current task holds for the lock `name`, if
+            /// there is one.
+            ///
+            /// # Traps
+            /// This function will trap if called from within a transaction.
+            pub fn release(name: &str) {
+                unsafe {
+                    let vec0 = name;
+                    let ptr0 = vec0.as_ptr().cast::<u8>();
+                    let len0 = vec0.len();
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(wasm_import_module = "durable:core/lock@2.7.0")]
+                    extern "C" {
+                        #[link_name = "release"]
+                        fn wit_import(_: *mut u8, _: usize);
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    fn wit_import(_: *mut u8, _: usize) {
+                        unreachable!()
+                    }
+                    wit_import(ptr0.cast_mut(), len0);
+                }
+            }
+        }
+    }
+}
+mod _rt {
+    pub fn as_i32<T: AsI32>(t: T) -> i32 {
+        t.as_i32()
+    }
+    pub trait AsI32 {
+        fn as_i32(self) -> i32;
+    }
+    impl<'a, T: Copy + AsI32> AsI32 for &'a T {
+        fn as_i32(self) -> i32 {
+            (*self).as_i32()
+        }
+    }
+    impl AsI32 for i32 {
+        #[inline]
+        fn as_i32(self) -> i32 {
+            self as i32
+        }
+    }
+    impl AsI32 for u32 {
+        #[inline]
+        fn as_i32(self) -> i32 {
+            self as i32
+        }
+    }
+    impl AsI32 for i16 {
+        #[inline]
+        fn as_i32(self) -> i32 {
+            self as i32
+        }
+    }
+    impl AsI32 for u16 {
+        #[inline]
+        fn as_i32(self) -> i32 {
+            self as i32
+        }
+    }
+    impl AsI32 for i8 {
+        #[inline]
+        fn as_i32(self) -> i32 {
+            self as i32
+        }
+    }
+    impl AsI32 for u8 {
+        #[inline]
+        fn as_i32(self) -> i32 {
+            self as i32
+        }
+    }
+    impl AsI32 for char {
+        #[inline]
+        fn as_i32(self) -> i32 {
+            self as i32
+        }
+    }
+    impl AsI32 for usize {
+        #[inline]
+        fn as_i32(self) -> i32 {
+            self as i32
+        }
+    }
+    pub fn as_i64<T: AsI64>(t: T) -> i64 {
+        t.as_i64()
+    }
+    pub trait AsI64 {
+        fn as_i64(self) -> i64;
+    }
+    impl<'a, T: Copy + AsI64> AsI64 for &'a T {
+        fn as_i64(self) -> i64 {
+            (*self).as_i64()
+        }
+    }
+    impl AsI64 for i64 {
+        #[inline]
+        fn as_i64(self) -> i64 {
+            self as i64
+        }
+    }
+    impl AsI64 for u64 {
+        #[inline]
+        fn as_i64(self) -> i64 {
+            self as i64
+        }
+    }
+    pub unsafe fn invalid_enum_discriminant<T>() -> T {
+        if cfg!(debug_assertions) {
+            panic!("invalid enum discriminant")
+        } else {
+            core::hint::unreachable_unchecked()
+        }
+    }
+}
+#[cfg(target_arch = "wasm32")]
+#[link_section = "component-type:wit-bindgen:0.30.0:import-lock:encoded world"]
+#[doc(hidden)]
+pub static __WIT_BINDGEN_COMPONENT_TYPE: [u8; 311] = *b"\
+\0asm\x0d\0\x01\0\0\x19\x16wit-component-encoding\x04\0\x07\xb5\x01\x01A\x02\x01\
+A\x02\x01B\x07\x01kw\x01q\x02\x08acquired\0\0\x04held\x01\0\0\x04\0\x0eacquire-r\
+esult\x03\0\x01\x01@\x03\x04names\x07permitsy\x03ttl\0\0\x02\x04\0\x0btry-acquir\
+e\x01\x03\x01@\x01\x04names\x01\0\x04\0\x07release\x01\x04\x03\x01\x17durable:co\
+re/lock@2.7.0\x05\0\x04\x01\x1edurable:core/import-lock@2.7.0\x04\0\x0b\x11\x01\
+\0\x0bimport-lock\x03\0\0\0G\x09producers\x01\x0cprocessed-by\x02\x0dwit-compone\
+nt\x070.215.0\x10wit-bindgen-rust\x060.30.0";
+#[inline(never)]
+#[doc(hidden)]
+pub fn __link_custom_section_describing_imports() {
+    wit_bindgen_rt::maybe_link_cabi_realloc();
+}
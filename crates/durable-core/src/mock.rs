@@ -0,0 +1,876 @@
+//! A native mock of the durable runtime for unit testing workflow code.
+//!
+//! When the `mock` feature is enabled and the crate is compiled for a non-wasm
+//! target the bindings to the durable runtime are replaced with an in-memory
+//! implementation. This allows code that uses transactions and notifications
+//! to be tested with a plain `cargo test`, without needing a worker or a
+//! database.
+//!
+//! All mock state is stored per-thread, so each test sees its own task,
+//! event log, and notification queue.
+//!
+//! ```ignore
+//! use durable_core::mock::{self, MockTask};
+//!
+//! mock::reset();
+//! mock::set_task(MockTask::new(1, "my-task").data(&serde_json::json!({ "a": 5 })));
+//! mock::push_notification("approved", &true);
+//!
+//! run_my_workflow();
+//!
+//! let labels: Vec<_> = mock::events().into_iter().map(|e| e.label).collect();
+//! assert_eq!(labels, ["load-config", "send-email"]);
+//! ```
+//!
+//! # Limitations
+//! - Transactions run exactly as they would the first time a workflow is
+//!   executed. Use [`push_event`] to have a transaction return a previously
+//!   recorded value instead.
+//! - Waiting for a notification when none are queued panics instead of blocking
+//!   forever. Waiting with a timeout returns immediately instead.
+//! - Launched child tasks never run. Use [`push_child_complete`] to have the
+//!   workflow observe a child completing.
+//! - There are no other tasks competing for locks or rate limits, so acquiring
+//!   either always succeeds immediately.
+//! - Database queries made through `durable-sqlx` return the canned results
+//!   registered with `durable_sqlx::mock` instead of touching a database.
+
+use std::cell::RefCell;
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+use std::time::{Duration, SystemTime};
+
+use serde::Serialize;
+use serde_json::value::RawValue;
+
+thread_local! {
+    static STATE: RefCell<MockState> = RefCell::new(MockState::default());
+}
+
+#[derive(Default)]
+struct MockState {
+    task: MockTask,
+    events: Vec<MockEvent>,
+    index: usize,
+    active: Option<String>,
+    notifications: VecDeque<MockNotification>,
+    sent: Vec<SentNotification>,
+    launched: Vec<LaunchedTask>,
+    result: Option<Box<RawValue>>,
+    locks: BTreeSet<String>,
+    interfaces: Option<Vec<String>>,
+    metadata: BTreeMap<String, Box<RawValue>>,
+}
+
+fn with_state<R>(func: impl FnOnce(&mut MockState) -> R) -> R {
+    STATE.with(|state| func(&mut state.borrow_mut()))
+}
+
+fn to_raw_value<T: ?Sized + Serialize>(value: &T) -> Box<RawValue> {
+    serde_json::value::to_raw_value(value).expect("failed to serialize mock value to json")
+}
+
+/// Details about the task that the mock runtime reports to the workflow.
+#[derive(Clone, Debug)]
+pub struct MockTask {
+    id: i64,
+    name: String,
+    data: Box<RawValue>,
+    created_at: SystemTime,
+    queue: String,
+    labels: BTreeMap<String, String>,
+    attempt: u32,
+    worker_id: i64,
+}
+
+impl MockTask {
+    pub fn new(id: i64, name: impl Into<String>) -> Self {
+        Self {
+            id,
+            name: name.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Set the JSON data that the task was created with.
+    pub fn data<T: ?Sized + Serialize>(mut self, data: &T) -> Self {
+        self.data = to_raw_value(data);
+        self
+    }
+
+    /// Set the timestamp at which the task was created.
+    pub fn created_at(mut self, created_at: SystemTime) -> Self {
+        self.created_at = created_at;
+        self
+    }
+
+    /// Set the queue that the task was launched into.
+    pub fn queue(mut self, queue: impl Into<String>) -> Self {
+        self.queue = queue.into();
+        self
+    }
+
+    /// Add a label to the task.
+    pub fn label(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.labels.insert(key.into(), value.into());
+        self
+    }
+
+    /// Set the attempt that the task is on. This defaults to 1.
+    pub fn attempt(mut self, attempt: u32) -> Self {
+        self.attempt = attempt;
+        self
+    }
+
+    /// Set the id of the worker that the task is running on.
+    pub fn worker_id(mut self, worker_id: i64) -> Self {
+        self.worker_id = worker_id;
+        self
+    }
+}
+
+impl Default for MockTask {
+    fn default() -> Self {
+        Self {
+            id: 0,
+            name: "mock".into(),
+            data: to_raw_value(&()),
+            created_at: SystemTime::UNIX_EPOCH,
+            queue: String::new(),
+            labels: BTreeMap::new(),
+            attempt: 1,
+            worker_id: 0,
+        }
+    }
+}
+
+/// An event within the mock event log.
+#[derive(Clone, Debug)]
+pub struct MockEvent {
+    /// The label of the transaction that recorded this event.
+    pub label: String,
+
+    /// The JSON value stored for this event, exactly as the runtime would
+    /// store it.
+    pub value: Box<RawValue>,
+}
+
+#[derive(Clone, Debug)]
+struct MockNotification {
+    created_at: SystemTime,
+    event: String,
+    data: Box<RawValue>,
+}
+
+/// A notification that the workflow sent to another task.
+#[derive(Clone, Debug)]
+pub struct SentNotification {
+    pub task: i64,
+    pub event: String,
+    pub data: Box<RawValue>,
+}
+
+/// A child task that the workflow launched.
+#[derive(Clone, Debug)]
+pub struct LaunchedTask {
+    pub id: i64,
+    pub name: String,
+    pub entrypoint: Option<String>,
+    pub data: Box<RawValue>,
+}
+
+/// Reset all mock state for the current thread.
+pub fn reset() {
+    with_state(|state| *state = MockState::default());
+}
+
+/// Set the task that the workflow is running as.
+pub fn set_task(task: MockTask) {
+    with_state(|state| state.task = task);
+}
+
+/// Append a previously recorded transaction result to the event log.
+///
+/// When the workflow reaches this point in the event log, the transaction with
+/// the matching label will return `value` without running. A transaction with
+/// a different label panics, just like it would for a non-deterministic
+/// workflow running within the real runtime.
+pub fn push_event<T: ?Sized + Serialize>(label: &str, value: &T) {
+    #[derive(Serialize)]
+    #[serde(tag = "type", content = "data", rename_all = "kebab-case")]
+    enum TransactionResult<'a, T: ?Sized> {
+        Value(&'a T),
+    }
+
+    let value = to_raw_value(&TransactionResult::Value(value));
+    with_state(|state| {
+        state.events.push(MockEvent {
+            label: label.to_owned(),
+            value,
+        })
+    });
+}
+
+/// Get all events in the event log, including those that were added via
+/// [`push_event`].
+pub fn events() -> Vec<MockEvent> {
+    with_state(|state| state.events.clone())
+}
+
+/// Queue up a notification to be delivered to the workflow.
+pub fn push_notification<T: ?Sized + Serialize>(event: &str, data: &T) {
+    let notification = MockNotification {
+        created_at: SystemTime::now(),
+        event: event.to_owned(),
+        data: to_raw_value(data),
+    };
+
+    with_state(|state| state.notifications.push_back(notification));
+}
+
+/// Get all notifications that the workflow has sent to other tasks.
+pub fn sent_notifications() -> Vec<SentNotification> {
+    with_state(|state| state.sent.clone())
+}
+
+/// Get all child tasks that the workflow has launched.
+///
+/// Child tasks are assigned sequential ids starting just after the id of the
+/// mock task.
+pub fn launched_tasks() -> Vec<LaunchedTask> {
+    with_state(|state| state.launched.clone())
+}
+
+/// Queue up a notification that the child task `id` has completed.
+///
+/// `result` is the result that the child task set, or `Err(())` if the child
+/// task failed.
+pub fn push_child_complete<T: ?Sized + Serialize>(id: i64, result: Result<&T, ()>) {
+    let data = match result {
+        Ok(result) => serde_json::json!({ "id": id, "success": true, "result": result }),
+        Err(()) => serde_json::json!({ "id": id, "success": false, "result": null }),
+    };
+
+    push_notification(crate::tasks::CHILD_COMPLETE_EVENT, &data);
+}
+
+/// Get the result that the workflow has set, if any.
+pub fn task_result() -> Option<Box<RawValue>> {
+    with_state(|state| state.result.clone())
+}
+
+/// Get the names of all locks that the workflow currently holds.
+pub fn held_locks() -> Vec<String> {
+    with_state(|state| state.locks.iter().cloned().collect())
+}
+
+/// Get the metadata that the workflow has set on the task.
+pub fn task_metadata() -> BTreeMap<String, Box<RawValue>> {
+    with_state(|state| state.metadata.clone())
+}
+
+/// Set the interfaces that the mock worker reports through
+/// [`Capabilities`](crate::capabilities::Capabilities).
+///
+/// By default the mock reports the `durable:core` interfaces that it mocks.
+pub fn set_interfaces<I>(interfaces: I)
+where
+    I: IntoIterator,
+    I::Item: Into<String>,
+{
+    let interfaces = interfaces.into_iter().map(Into::into).collect();
+    with_state(|state| state.interfaces = Some(interfaces));
+}
+
+fn datetime(time: SystemTime) -> bindings::wasi::clocks::wall_clock::Datetime {
+    let duration = time
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap_or(Duration::ZERO);
+
+    bindings::wasi::clocks::wall_clock::Datetime {
+        seconds: duration.as_secs(),
+        nanoseconds: duration.subsec_nanos(),
+    }
+}
+
+fn assert_in_transaction(function: &str) {
+    with_state(|state| {
+        if state.active.is_none() {
+            panic!("attempted to run impure function `{function}` outside of a transaction");
+        }
+    })
+}
+
+fn assert_not_in_transaction(function: &str) {
+    with_state(|state| {
+        if let Some(label) = &state.active {
+            panic!("{function} called from within transaction {label:?}");
+        }
+    })
+}
+
+/// Native replacements for the functions in the generated bindings.
+#[allow(clippy::module_inception)]
+pub(crate) mod bindings {
+    pub use self::durable::core::core::*;
+
+    pub mod durable {
+        pub mod core {
+            pub mod core {
+                use crate::mock::{assert_in_transaction, datetime, with_state};
+
+                pub type Datetime = crate::mock::bindings::wasi::clocks::wall_clock::Datetime;
+
+                /// Get the task ID for the current task.
+                pub fn task_id() -> i64 {
+                    with_state(|state| state.task.id)
+                }
+
+                /// Get the task name for the current task.
+                pub fn task_name() -> String {
+                    with_state(|state| state.task.name.clone())
+                }
+
+                pub fn task_data() -> String {
+                    with_state(|state| state.task.data.get().to_owned())
+                }
+
+                pub fn task_created_at() -> Datetime {
+                    datetime(with_state(|state| state.task.created_at))
+                }
+
+                pub fn task_queue() -> String {
+                    with_state(|state| state.task.queue.clone())
+                }
+
+                pub fn task_labels() -> Vec<(String, String)> {
+                    with_state(|state| {
+                        state
+                            .task
+                            .labels
+                            .iter()
+                            .map(|(key, value)| (key.clone(), value.clone()))
+                            .collect()
+                    })
+                }
+
+                pub fn task_attempt() -> u32 {
+                    assert_in_transaction("durable::task_attempt");
+                    with_state(|state| state.task.attempt)
+                }
+
+                pub fn worker_id() -> i64 {
+                    assert_in_transaction("durable::worker_id");
+                    with_state(|state| state.task.worker_id)
+                }
+
+                pub fn set_metadata(key: &str, value: Option<&str>) {
+                    assert_in_transaction("durable::metadata::set");
+
+                    let value = value.map(|value| {
+                        serde_json::from_str::<Box<serde_json::value::RawValue>>(value)
+                            .expect("metadata value was not valid json")
+                    });
+
+                    with_state(|state| match value {
+                        Some(value) => {
+                            state.metadata.insert(key.to_owned(), value);
+                        }
+                        None => {
+                            state.metadata.remove(key);
+                        }
+                    });
+                }
+
+                pub fn transaction_enter(label: &str, _is_db: bool) -> Option<String> {
+                    with_state(|state| {
+                        if let Some(active) = &state.active {
+                            panic!(
+                                "attempted to start transaction {label:?} while already within \
+                                 transaction {active:?}"
+                            );
+                        }
+
+                        let index = state.index;
+                        match state.events.get(index) {
+                            Some(event) if event.label != label => panic!(
+                                "workflow execution is non-deterministic: stored event at index \
+                                 {index} has label {:?} but the workflow requested {label:?}",
+                                event.label
+                            ),
+                            Some(event) => {
+                                state.index += 1;
+                                Some(event.value.get().to_owned())
+                            }
+                            None => {
+                                state.active = Some(label.to_owned());
+                                None
+                            }
+                        }
+                    })
+                }
+
+                // The mock has no database, so only is_db is used.
+                #[derive(Clone, Copy, Debug)]
+                #[allow(dead_code)]
+                pub struct TransactionOptions {
+                    pub is_db: bool,
+                    pub statement_timeout: Option<u64>,
+                    pub read_only: bool,
+                }
+
+                pub fn transaction_enter2(
+                    label: &str,
+                    options: TransactionOptions,
+                ) -> Option<String> {
+                    transaction_enter(label, options.is_db)
+                }
+
+                pub fn transaction_exit(data: &str) {
+                    let value = serde_json::from_str::<Box<serde_json::value::RawValue>>(data)
+                        .expect("transaction_exit called with invalid json");
+
+                    with_state(|state| {
+                        let label = state
+                            .active
+                            .take()
+                            .expect("transaction_exit called outside of a transaction");
+
+                        state.events.push(crate::mock::MockEvent { label, value });
+                        state.index += 1;
+                    })
+                }
+            }
+
+            pub mod notify {
+                use std::fmt;
+
+                use crate::mock::{
+                    assert_not_in_transaction, datetime, with_state, SentNotification,
+                };
+
+                pub type Datetime = crate::mock::bindings::wasi::clocks::wall_clock::Datetime;
+
+                #[derive(Clone)]
+                pub struct Event {
+                    pub created_at: Datetime,
+                    pub event: String,
+                    pub data: String,
+                }
+
+                #[derive(Clone)]
+                #[allow(dead_code)]
+                pub enum NotifyError {
+                    TaskNotFound,
+                    TaskDead,
+                    Other(String),
+                }
+
+                impl fmt::Debug for NotifyError {
+                    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                        match self {
+                            Self::TaskNotFound => f.write_str("TaskNotFound"),
+                            Self::TaskDead => f.write_str("TaskDead"),
+                            Self::Other(msg) => f.debug_tuple("Other").field(msg).finish(),
+                        }
+                    }
+                }
+
+                pub fn notification_blocking() -> Event {
+                    assert_not_in_transaction("notification_blocking");
+
+                    let notification = with_state(|state| state.notifications.pop_front()).expect(
+                        "the workflow is waiting for a notification but none have been queued \
+                         with durable_core::mock::push_notification",
+                    );
+
+                    Event {
+                        created_at: datetime(notification.created_at),
+                        event: notification.event,
+                        data: notification.data.get().to_owned(),
+                    }
+                }
+
+                pub fn notification_blocking_until(_deadline: Datetime) -> Option<Event> {
+                    assert_not_in_transaction("notification_blocking_until");
+
+                    let notification = with_state(|state| state.notifications.pop_front())?;
+
+                    Some(Event {
+                        created_at: datetime(notification.created_at),
+                        event: notification.event,
+                        data: notification.data.get().to_owned(),
+                    })
+                }
+
+                pub fn notify(task: i64, event: &str, data: &str) -> Result<(), NotifyError> {
+                    assert_not_in_transaction("notify");
+
+                    let data = serde_json::from_str(data)
+                        .map_err(|e| NotifyError::Other(format!("invalid json data: {e}")))?;
+
+                    with_state(|state| {
+                        state.sent.push(SentNotification {
+                            task,
+                            event: event.to_owned(),
+                            data,
+                        })
+                    });
+
+                    Ok(())
+                }
+            }
+
+            pub mod lock {
+                use crate::mock::{assert_not_in_transaction, with_state};
+
+                #[derive(Clone, Copy, Debug)]
+                pub enum AcquireResult {
+                    Acquired,
+                    #[allow(dead_code)]
+                    Held(Option<u64>),
+                }
+
+                pub fn try_acquire(name: &str, permits: u32, _ttl: Option<u64>) -> AcquireResult {
+                    assert_not_in_transaction("try_acquire");
+                    assert!(permits > 0, "try_acquire called with zero permits");
+
+                    with_state(|state| state.locks.insert(name.to_owned()));
+                    AcquireResult::Acquired
+                }
+
+                pub fn release(name: &str) {
+                    assert_not_in_transaction("release");
+
+                    with_state(|state| state.locks.remove(name));
+                }
+            }
+
+            pub mod capabilities {
+                use crate::mock::with_state;
+
+                /// The interfaces that are mocked by this module.
+                const MOCKED_INTERFACES: &[&str] = &[
+                    "durable:core/capabilities@2.7.0",
+                    "durable:core/core@2.7.0",
+                    "durable:core/lock@2.7.0",
+                    "durable:core/notify@2.7.0",
+                    "durable:core/panic@2.7.0",
+                    "durable:core/ratelimit@2.7.0",
+                    "durable:core/scheduler@2.7.0",
+                    "durable:core/tasks@2.7.0",
+                ];
+
+                #[derive(Clone, Copy, Debug)]
+                pub struct WorkerLimits {
+                    pub max_events: u32,
+                    pub max_log_bytes_per_transaction: u64,
+                    pub max_returned_buffer_len: u64,
+                    pub max_http_timeout: u64,
+                }
+
+                pub fn interfaces() -> Vec<String> {
+                    with_state(|state| match &state.interfaces {
+                        Some(interfaces) => interfaces.clone(),
+                        None => MOCKED_INTERFACES
+                            .iter()
+                            .map(|&name| name.to_owned())
+                            .collect(),
+                    })
+                }
+
+                /// The default limits of the runtime.
+                pub fn limits() -> WorkerLimits {
+                    WorkerLimits {
+                        max_events: i32::MAX as u32,
+                        max_log_bytes_per_transaction: 128 * 1024,
+                        max_returned_buffer_len: 8 * 1024 * 1024,
+                        max_http_timeout: 60 * 1000,
+                    }
+                }
+            }
+
+            pub mod ratelimit {
+                use crate::mock::assert_not_in_transaction;
+
+                pub fn try_acquire(_name: &str) -> Option<u64> {
+                    assert_not_in_transaction("try_acquire");
+
+                    None
+                }
+            }
+
+            pub mod tasks {
+                use crate::mock::{assert_not_in_transaction, with_state, LaunchedTask};
+
+                #[derive(Clone, Debug)]
+                pub struct Child<'a> {
+                    pub name: &'a str,
+                    pub entrypoint: Option<&'a str>,
+                    pub data: &'a str,
+                }
+
+                pub fn launch(children: &[Child<'_>]) -> Vec<i64> {
+                    assert_not_in_transaction("launch");
+
+                    with_state(|state| {
+                        children
+                            .iter()
+                            .map(|child| {
+                                let id = state.task.id + 1 + state.launched.len() as i64;
+                                let data = serde_json::from_str(child.data)
+                                    .expect("launch called with invalid json data");
+
+                                state.launched.push(LaunchedTask {
+                                    id,
+                                    name: child.name.to_owned(),
+                                    entrypoint: child.entrypoint.map(|e| e.to_owned()),
+                                    data,
+                                });
+
+                                id
+                            })
+                            .collect()
+                    })
+                }
+
+                pub fn set_result(data: &str) {
+                    assert_not_in_transaction("set_result");
+
+                    let data = serde_json::from_str(data)
+                        .expect("set_result called with invalid json data");
+                    with_state(|state| state.result = Some(data));
+                }
+            }
+        }
+    }
+
+    pub mod wasi {
+        pub mod clocks {
+            pub mod wall_clock {
+                #[derive(Clone, Copy, Debug)]
+                pub struct Datetime {
+                    pub seconds: u64,
+                    pub nanoseconds: u32,
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transaction::transaction;
+
+    #[test]
+    fn records_transactions() {
+        reset();
+        set_task(MockTask::new(7, "test").data(&[1, 2, 3]));
+
+        assert_eq!(crate::task_id(), 7);
+        assert_eq!(crate::task_data().get(), "[1,2,3]");
+
+        let value = transaction("first", || 5);
+        assert_eq!(value, 5);
+
+        let events = events();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].label, "first");
+    }
+
+    #[test]
+    fn replays_stored_events() {
+        reset();
+        push_event("stored", &"recorded");
+
+        let value = transaction("stored", || "fresh".to_owned());
+        assert_eq!(value, "recorded");
+    }
+
+    #[test]
+    #[should_panic(expected = "non-deterministic")]
+    fn mismatched_label_panics() {
+        reset();
+        push_event("expected", &0);
+
+        transaction("other", || 0);
+    }
+
+    #[test]
+    fn notifications() {
+        reset();
+        push_notification("ping", &42);
+
+        let notification = crate::notify::wait();
+        assert_eq!(notification.event, "ping");
+        assert_eq!(notification.json::<i32>().unwrap(), 42);
+
+        crate::notify::notify(3, "pong", &43).unwrap();
+        let sent = sent_notifications();
+        assert_eq!(sent.len(), 1);
+        assert_eq!(sent[0].task, 3);
+        assert_eq!(sent[0].data.get(), "43");
+    }
+
+    #[test]
+    fn child_tasks() {
+        reset();
+        set_task(MockTask::new(10, "parent"));
+
+        let data = serde_json::value::to_raw_value(&[1, 2]).unwrap();
+        let ids = crate::tasks::launch(&[
+            crate::tasks::Child {
+                name: "a",
+                entrypoint: Some("child"),
+                data: &data,
+            },
+            crate::tasks::Child {
+                name: "b",
+                entrypoint: None,
+                data: &data,
+            },
+        ]);
+        assert_eq!(ids, [11, 12]);
+
+        let launched = launched_tasks();
+        assert_eq!(launched[0].entrypoint.as_deref(), Some("child"));
+        assert_eq!(launched[1].name, "b");
+
+        push_child_complete(11, Ok(&"done"));
+        let notification = crate::notify::wait();
+        let complete: crate::tasks::ChildComplete = notification.json().unwrap();
+        assert_eq!(notification.event, crate::tasks::CHILD_COMPLETE_EVENT);
+        assert!(complete.success);
+        assert_eq!(complete.result.unwrap().get(), "\"done\"");
+
+        crate::tasks::set_result(&data);
+        assert_eq!(task_result().unwrap().get(), "[1,2]");
+    }
+
+    #[test]
+    fn locks_and_rate_limits() {
+        reset();
+
+        let result = crate::lock::try_acquire("resource", 1, Some(Duration::from_secs(5)));
+        assert_eq!(result, crate::lock::AcquireResult::Acquired);
+        assert_eq!(held_locks(), ["resource"]);
+
+        crate::lock::release("resource");
+        assert!(held_locks().is_empty());
+
+        assert_eq!(crate::ratelimit::try_acquire("api"), None);
+    }
+
+    #[test]
+    fn capabilities() {
+        use crate::capabilities::Capabilities;
+
+        reset();
+
+        let capabilities = Capabilities::current();
+        assert!(capabilities.has_interface("durable:core/lock"));
+        assert!(capabilities.has_interface("durable:core/lock@2.7.0"));
+        assert!(!capabilities.has_interface("durable:core/sql"));
+        assert_eq!(
+            capabilities.limits().max_http_timeout,
+            Duration::from_secs(60)
+        );
+
+        set_interfaces(["durable:core/sql@2.7.0"]);
+        let capabilities = Capabilities::current();
+        assert_eq!(capabilities.interfaces(), ["durable:core/sql@2.7.0"]);
+        assert!(!capabilities.has_interface("durable:core/lock"));
+    }
+
+    #[test]
+    fn task_metadata() {
+        reset();
+        set_task(
+            MockTask::new(3, "metadata")
+                .queue("tenant-a")
+                .label("team", "billing")
+                .attempt(2)
+                .worker_id(11),
+        );
+
+        assert_eq!(crate::task_queue(), "tenant-a");
+        assert_eq!(
+            crate::task_labels(),
+            [("team".to_owned(), "billing".to_owned())]
+        );
+
+        let (attempt, worker) =
+            transaction("metadata", || (crate::task_attempt(), crate::worker_id()));
+        assert_eq!(attempt, 2);
+        assert_eq!(worker, 11);
+    }
+
+    #[test]
+    fn set_task_metadata() {
+        reset();
+
+        transaction("metadata", || {
+            crate::set_metadata("order", Some(r#"{"id":5}"#));
+            crate::set_metadata("step", Some(r#""charge""#));
+            crate::set_metadata("order", None);
+        });
+
+        let metadata = super::task_metadata();
+        assert_eq!(metadata.len(), 1);
+        assert_eq!(metadata["step"].get(), r#""charge""#);
+    }
+
+    #[test]
+    #[should_panic(expected = "outside of a transaction")]
+    fn task_attempt_outside_transaction() {
+        reset();
+        crate::task_attempt();
+    }
+
+    #[test]
+    fn notification_timeout() {
+        reset();
+        push_notification("ping", &());
+
+        let timeout = Duration::from_secs(60);
+        assert!(crate::notify::wait_timeout(timeout).is_some());
+        assert!(crate::notify::wait_timeout(timeout).is_none());
+    }
+
+    #[test]
+    fn custom_codec() {
+        use crate::codec::Codec;
+        use crate::transaction::{transaction_with_codec, TransactionOptions};
+
+        // Stores everything as pretty-printed json.
+        struct Pretty;
+
+        impl Codec for Pretty {
+            type Error = serde_json::Error;
+
+            fn encode<T>(value: &T) -> Result<String, Self::Error>
+            where
+                T: Serialize + ?Sized,
+            {
+                serde_json::to_string_pretty(value)
+            }
+
+            fn decode<T>(data: &str) -> Result<T, Self::Error>
+            where
+                T: serde::de::DeserializeOwned,
+            {
+                serde_json::from_str(data)
+            }
+        }
+
+        reset();
+        set_task(MockTask::new(1, "codec").data(&[4, 5]));
+
+        let data: Vec<i32> = crate::task_data_with::<Pretty, _>().unwrap();
+        assert_eq!(data, [4, 5]);
+
+        let value = transaction_with_codec::<Pretty, _, _>(TransactionOptions::new("pretty"), || 3);
+        assert_eq!(value, 3);
+        assert!(events()[0].value.get().contains('\n'));
+    }
+}
@@ -5,7 +5,10 @@ use std::time::{Duration, SystemTime};
 
 use serde_json::value::RawValue;
 
-use crate::bindings::durable::core::notify;
+#[cfg(all(feature = "mock", not(target_family = "wasm")))]
+use crate::mock::bindings::durable::core::notify;
+#[cfg(not(all(feature = "mock", not(target_family = "wasm"))))]
+use crate::notify_bindings::durable::core::notify;
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Notification {
@@ -31,7 +34,42 @@ impl Notification {
 /// Attempting to call this function within a transaction will result in a trap
 /// that instantly kills the workflow.
 pub fn wait() -> Notification {
-    let event = crate::bindings::durable::core::notify::notification_blocking();
+    notification(notify::notification_blocking())
+}
+
+/// Block this task until a new notification arrives or until `deadline` has
+/// passed.
+///
+/// Returns `None` if no notification arrived before `deadline`.
+///
+/// # Traps
+/// Attempting to call this function within a transaction will result in a trap
+/// that instantly kills the workflow.
+pub fn wait_until(deadline: SystemTime) -> Option<Notification> {
+    let duration = deadline
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap_or(Duration::ZERO);
+    let deadline = notify::Datetime {
+        seconds: duration.as_secs(),
+        nanoseconds: duration.subsec_nanos(),
+    };
+
+    notify::notification_blocking_until(deadline).map(notification)
+}
+
+/// Block this task until a new notification arrives or until `timeout` has
+/// elapsed.
+///
+/// Returns `None` if no notification arrived within `timeout`.
+///
+/// # Traps
+/// Attempting to call this function within a transaction will result in a trap
+/// that instantly kills the workflow.
+pub fn wait_timeout(timeout: Duration) -> Option<Notification> {
+    wait_until(SystemTime::now() + timeout)
+}
+
+fn notification(event: notify::Event) -> Notification {
     let data = event.data.into_boxed_str();
 
     let _: &RawValue = serde_json::from_str(&data).expect(
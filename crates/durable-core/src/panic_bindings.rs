@@ -0,0 +1,178 @@
+#[allow(dead_code)]
+pub mod durable {
+    #[allow(dead_code)]
+    pub mod core {
+        #[allow(dead_code, clippy::all)]
+        pub mod panic {
+            #[used]
+            #[doc(hidden)]
+            static __FORCE_SECTION_REF: fn() = super::super::super::__link_custom_section_describing_imports;
+            use super::super::super::_rt;
+            /// The location in the source code where a panic occurred.
+            #[derive(Clone)]
+            pub struct Location<'a> {
+                pub file: &'a str,
+                pub line: u32,
+                pub column: u32,
+            }
+            impl<'a> ::core::fmt::Debug for Location<'a> {
+                fn fmt(
+                    &self,
+                    f: &mut ::core::fmt::Formatter<'_>,
+                ) -> ::core::fmt::Result {
+                    f.debug_struct("Location")
+                        .field("file", &self.file)
+                        .field("line", &self.line)
+                        .field("column", &self.column)
+                        .finish()
+                }
+            }
+            #[allow(unused_unsafe, clippy::all)]
+            /// Report that the workflow has panicked.
+            ///
+            /// This is meant to be called from the panic hook before the workflow
+            /// traps. The runtime records the message and location as the reason that
+            /// the task failed instead of the trap that follows.
+            ///
+            /// Only the first panic reported by a task is kept.
+            pub fn report_panic(message: &str, location: Option<Location<'_>>) {
+                unsafe {
+                    let vec0 = message;
+                    let ptr0 = vec0.as_ptr().cast::<u8>();
+                    let len0 = vec0.len();
+                    let (result3_0, result3_1, result3_2, result3_3, result3_4) = match location {
+                        Some(e) => {
+                            let Location {
+                                file: file1,
+                                line: line1,
+                                column: column1,
+                            } = e;
+                            let vec2 = file1;
+                            let ptr2 = vec2.as_ptr().cast::<u8>();
+                            let len2 = vec2.len();
+                            (
+                                1i32,
+                                ptr2.cast_mut(),
+                                len2,
+                                _rt::as_i32(line1),
+                                _rt::as_i32(column1),
+                            )
+                        }
+                        None => (0i32, ::core::ptr::null_mut(), 0usize, 0i32, 0i32),
+                    };
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(wasm_import_module = "durable:core/panic@2.7.0")]
+                    extern "C" {
+                        #[link_name = "report-panic"]
+                        fn wit_import(
+                            _: *mut u8,
+                            _: usize,
+                            _: i32,
+                            _: *mut u8,
+                            _: usize,
+                            _: i32,
+                            _: i32,
+                        );
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    fn wit_import(
+                        _: *mut u8,
+                        _: usize,
+                        _: i32,
+                        _: *mut u8,
+                        _: usize,
+                        _: i32,
+                        _: i32,
+                    ) {
+                        unreachable!()
+                    }
+                    wit_import(
+                        ptr0.cast_mut(),
+                        len0,
+                        result3_0,
+                        result3_1,
+                        result3_2,
+                        result3_3,
+                        result3_4,
+                    );
+                }
+            }
+        }
+    }
+}
+mod _rt {
+    pub fn as_i32<T: AsI32>(t: T) -> i32 {
+        t.as_i32()
+    }
+    pub trait AsI32 {
+        fn as_i32(self) -> i32;
+    }
+    impl<'a, T: Copy + AsI32> AsI32 for &'a T {
+        fn as_i32(self) -> i32 {
+            (*self).as_i32()
+        }
+    }
+    impl AsI32 for i32 {
+        #[inline]
+        fn as_i32(self) -> i32 {
+            self as i32
+        }
+    }
+    impl AsI32 for u32 {
+        #[inline]
+        fn as_i32(self) -> i32 {
+            self as i32
+        }
+    }
+    impl AsI32 for i16 {
+        #[inline]
+        fn as_i32(self) -> i32 {
+            self as i32
+        }
+    }
+    impl AsI32 for u16 {
+        #[inline]
+        fn as_i32(self) -> i32 {
+            self as i32
+        }
+    }
+    impl AsI32 for i8 {
+        #[inline]
+        fn as_i32(self) -> i32 {
+            self as i32
+        }
+    }
+    impl AsI32 for u8 {
+        #[inline]
+        fn as_i32(self) -> i32 {
+            self as i32
+        }
+    }
+    impl AsI32 for char {
+        #[inline]
+        fn as_i32(self) -> i32 {
+            self as i32
+        }
+    }
+    impl AsI32 for usize {
+        #[inline]
+        fn as_i32(self) -> i32 {
+            self as i32
+        }
+    }
+}
+#[cfg(target_arch = "wasm32")]
+#[link_section = "component-type:wit-bindgen:0.30.0:import-panic:encoded world"]
+#[doc(hidden)]
+pub static __WIT_BINDGEN_COMPONENT_TYPE: [u8; 286] = *b"\
+\0asm\x0d\0\x01\0\0\x19\x16wit-component-encoding\x04\0\x07\x9b\x01\x01A\x02\x01\
+A\x02\x01B\x05\x01r\x03\x04files\x04liney\x06columny\x04\0\x08location\x03\0\0\
+\x01k\x01\x01@\x02\x07messages\x08location\x02\x01\0\x04\0\x0creport-panic\x01\
+\x03\x03\x01\x18durable:core/panic@2.7.0\x05\0\x04\x01\x1fdurable:core/import-pa\
+nic@2.7.0\x04\0\x0b\x12\x01\0\x0cimport-panic\x03\0\0\0G\x09producers\x01\x0cpro\
+cessed-by\x02\x0dwit-component\x070.215.0\x10wit-bindgen-rust\x060.30.0";
+#[inline(never)]
+#[doc(hidden)]
+pub fn __link_custom_section_describing_imports() {
+    wit_bindgen_rt::maybe_link_cabi_realloc();
+}
@@ -0,0 +1,25 @@
+//! Token bucket rate limits that are shared between tasks.
+
+use std::time::Duration;
+
+#[cfg(all(feature = "mock", not(target_family = "wasm")))]
+use crate::mock::bindings::durable::core::ratelimit as bindings;
+#[cfg(not(all(feature = "mock", not(target_family = "wasm"))))]
+use crate::ratelimit_bindings::durable::core::ratelimit as bindings;
+
+/// Attempt to take a token from the rate limit `name`.
+///
+/// Rate limits are configured on the worker. Tasks that have to wait for a
+/// token are given one in the order that they started waiting, provided that
+/// they keep calling this function.
+///
+/// Returns `None` if a token was taken. Otherwise, this returns how long the
+/// current task should wait before calling this function again.
+///
+/// # Traps
+/// Attempting to call this function within a transaction, or with the name of a
+/// rate limit that is not configured on the worker, will result in a trap that
+/// instantly kills the workflow.
+pub fn try_acquire(name: &str) -> Option<Duration> {
+    bindings::try_acquire(name).map(Duration::from_millis)
+}
@@ -0,0 +1,84 @@
+#[allow(dead_code)]
+pub mod durable {
+    #[allow(dead_code)]
+    pub mod core {
+        #[allow(dead_code, clippy::all)]
+        pub mod ratelimit {
+            #[used]
+            #[doc(hidden)]
+            static __FORCE_SECTION_REF: fn() = super::super::super::__link_custom_section_describing_imports;
+            use super::super::super::_rt;
+            #[allow(unused_unsafe, clippy::all)]
+            /// Attempt to take a token from the rate limit `name`.
+            ///
+            /// Rate limits are configured on the worker. Tasks that have to wait for a
+            /// token are given one in the order that they started waiting, provided
+            /// that they keep calling this function.
+            ///
+            /// Returns `none` if a token was taken. Otherwise, this returns the number
+            /// of milliseconds that the current task should wait before calling this
+            /// function again.
+            ///
+            /// # Traps
+            /// This function will trap if called from within a transaction or if there
+            /// is no rate limit named `name` configured on the worker.
+            pub fn try_acquire(name: &str) -> Option<u64> {
+                unsafe {
+                    #[repr(align(8))]
+                    struct RetArea([::core::mem::MaybeUninit<u8>; 16]);
+                    let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 16]);
+                    let vec0 = name;
+                    let ptr0 = vec0.as_ptr().cast::<u8>();
+                    let len0 = vec0.len();
+                    let ptr1 = ret_area.0.as_mut_ptr().cast::<u8>();
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(wasm_import_module = "durable:core/ratelimit@2.7.0")]
+                    extern "C" {
+                        #[link_name = "try-acquire"]
+                        fn wit_import(_: *mut u8, _: usize, _: *mut u8);
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    fn wit_import(_: *mut u8, _: usize, _: *mut u8) {
+                        unreachable!()
+                    }
+                    wit_import(ptr0.cast_mut(), len0, ptr1);
+                    let l2 = i32::from(*ptr1.add(0).cast::<u8>());
+                    match l2 {
+                        0 => None,
+                        1 => {
+                            let e = {
+                                let l3 = *ptr1.add(8).cast::<i64>();
+                                l3 as u64
+                            };
+                            Some(e)
+                        }
+                        _ => _rt::invalid_enum_discriminant(),
+                    }
+                }
+            }
+        }
+    }
+}
+mod _rt {
+    pub unsafe fn invalid_enum_discriminant<T>() -> T {
+        if cfg!(debug_assertions) {
+            panic!("invalid enum discriminant")
+        } else {
+            core::hint::unreachable_unchecked()
+        }
+    }
+}
+#[cfg(target_arch = "wasm32")]
+#[link_section = "component-type:wit-bindgen:0.30.0:import-ratelimit:encoded world"]
+#[doc(hidden)]
+pub static __WIT_BINDGEN_COMPONENT_TYPE: [u8; 246] = *b"\
+\0asm\x0d\0\x01\0\0\x19\x16wit-component-encoding\x04\0\x07p\x01A\x02\x01A\x02\
+\x01B\x03\x01kw\x01@\x01\x04names\0\0\x04\0\x0btry-acquire\x01\x01\x03\x01\x1cdu\
+rable:core/ratelimit@2.7.0\x05\0\x04\x01#durable:core/import-ratelimit@2.7.0\x04\
+\0\x0b\x16\x01\0\x10import-ratelimit\x03\0\0\0G\x09producers\x01\x0cprocessed-by\
+\x02\x0dwit-component\x070.215.0\x10wit-bindgen-rust\x060.30.0";
+#[inline(never)]
+#[doc(hidden)]
+pub fn __link_custom_section_describing_imports() {
+    wit_bindgen_rt::maybe_link_cabi_realloc();
+}
@@ -0,0 +1,73 @@
+#[allow(dead_code)]
+pub mod durable {
+    #[allow(dead_code)]
+    pub mod core {
+        #[allow(dead_code, clippy::all)]
+        pub mod scheduler {
+            #[used]
+            #[doc(hidden)]
+            static __FORCE_SECTION_REF: fn() = super::super::super::__link_custom_section_describing_imports;
+            #[allow(unused_unsafe, clippy::all)]
+            /// Give the worker a chance to run other tasks before continuing.
+            ///
+            /// Workflows are already preempted periodically if the worker has epoch
+            /// interruption enabled. This allows a workflow to yield at a point of its
+            /// choosing, which is useful when the worker has it disabled.
+            pub fn yield_now() {
+                unsafe {
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(wasm_import_module = "durable:core/scheduler@2.7.0")]
+                    extern "C" {
+                        #[link_name = "yield-now"]
+                        fn wit_import();
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    fn wit_import() {
+                        unreachable!()
+                    }
+                    wit_import();
+                }
+            }
+            #[allow(unused_unsafe, clippy::all)]
+            /// Mark a point at which the workflow can safely be moved to another
+            /// worker.
+            ///
+            /// Everything the workflow has done so far is already persisted in its
+            /// event log. If the worker wants to get rid of the task (e.g. because it
+            /// is shutting down) then the task is released here and will be resumed
+            /// by another worker, replaying its completed transactions from the log.
+            /// Otherwise this returns immediately.
+            ///
+            /// This cannot be called from within a transaction.
+            pub fn checkpoint() {
+                unsafe {
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(wasm_import_module = "durable:core/scheduler@2.7.0")]
+                    extern "C" {
+                        #[link_name = "checkpoint"]
+                        fn wit_import();
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    fn wit_import() {
+                        unreachable!()
+                    }
+                    wit_import();
+                }
+            }
+        }
+    }
+}
+#[cfg(target_arch = "wasm32")]
+#[link_section = "component-type:wit-bindgen:0.30.0:import-scheduler:encoded world"]
+#[doc(hidden)]
+pub static __WIT_BINDGEN_COMPONENT_TYPE: [u8; 250] = *b"\
+\0asm\x0d\0\x01\0\0\x19\x16wit-component-encoding\x04\0\x07t\x01A\x02\x01A\x02\
+\x01B\x03\x01@\0\x01\0\x04\0\x09yield-now\x01\0\x04\0\x0acheckpoint\x01\0\x03\
+\x01\x1cdurable:core/scheduler@2.7.0\x05\0\x04\x01#durable:core/import-scheduler\
+@2.7.0\x04\0\x0b\x16\x01\0\x10import-scheduler\x03\0\0\0G\x09producers\x01\x0cpr\
+ocessed-by\x02\x0dwit-component\x070.215.0\x10wit-bindgen-rust\x060.30.0";
+#[inline(never)]
+#[doc(hidden)]
+pub fn __link_custom_section_describing_imports() {
+    wit_bindgen_rt::maybe_link_cabi_realloc();
+}
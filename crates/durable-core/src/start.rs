@@ -1,5 +1,7 @@
 use std::io::Write;
 
+use crate::panic_bindings::durable::core::panic::{report_panic, Location};
+
 // PanicInfo has been deprecated and renamed to PanicHookInfo but only in 1.82
 // or newer.
 //
@@ -32,6 +34,15 @@ fn durable_panic_hook(info: &PanicInfo) {
         "Box<dyn Any>"
     };
 
+    // This needs to happen first so that the runtime sees the transaction that
+    // the workflow was in when it panicked, rather than the one below.
+    let location = info.location().map(|location| Location {
+        file: location.file(),
+        line: location.line(),
+        column: location.column(),
+    });
+    report_panic(msg, location);
+
     crate::transaction::maybe_txn("durable::panic", || {
         use std::fmt::Write;
 
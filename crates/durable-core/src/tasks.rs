@@ -0,0 +1,80 @@
+//! Launch child tasks from within a workflow.
+
+use serde_json::value::RawValue;
+
+#[cfg(all(feature = "mock", not(target_family = "wasm")))]
+use crate::mock::bindings::durable::core::tasks as bindings;
+#[cfg(not(all(feature = "mock", not(target_family = "wasm"))))]
+use crate::tasks_bindings::durable::core::tasks as bindings;
+
+/// The event of the notification that is sent to a parent task when one of
+/// its children completes.
+///
+/// The notification data can be deserialized as a [`ChildComplete`].
+pub const CHILD_COMPLETE_EVENT: &str = "durable:child-complete";
+
+/// A child task to be launched by [`launch`].
+#[derive(Copy, Clone, Debug)]
+pub struct Child<'a> {
+    /// The name of the child task.
+    pub name: &'a str,
+
+    /// The workflow exported by the program that the child task should run.
+    ///
+    /// If this is `None` then the child task runs the program's `main`
+    /// function.
+    pub entrypoint: Option<&'a str>,
+
+    /// The JSON data for the child task.
+    pub data: &'a RawValue,
+}
+
+/// The data of a [`CHILD_COMPLETE_EVENT`] notification.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ChildComplete {
+    /// The id of the child task that completed.
+    pub id: i64,
+
+    /// Whether the child task completed successfully.
+    pub success: bool,
+
+    /// The result that the child task set via [`set_result`], if any.
+    #[serde(default)]
+    pub result: Option<Box<RawValue>>,
+}
+
+/// Launch child tasks that run the same program as the current task.
+///
+/// The child tasks belong to the same tenant, and have the same SQL context, as
+/// the current task. Once each child completes, the current task is sent a
+/// [`CHILD_COMPLETE_EVENT`] notification.
+///
+/// Returns the ids of the child tasks, in the same order as `children`.
+///
+/// # Traps
+/// Attempting to call this function within a transaction will result in a trap
+/// that instantly kills the workflow.
+pub fn launch(children: &[Child<'_>]) -> Vec<i64> {
+    let children: Vec<_> = children
+        .iter()
+        .map(|child| bindings::Child {
+            name: child.name,
+            entrypoint: child.entrypoint,
+            data: child.data.get(),
+        })
+        .collect();
+
+    bindings::launch(&children)
+}
+
+/// Set the result of the current task.
+///
+/// The result is included in the notification sent to the parent task, if
+/// there is one, once the current task completes.
+///
+/// # Traps
+/// Attempting to call this function within a transaction will result in a trap
+/// that instantly kills the workflow.
+pub fn set_result(data: &RawValue) {
+    bindings::set_result(data.get())
+}
@@ -0,0 +1,177 @@
+#[allow(dead_code)]
+pub mod durable {
+    #[allow(dead_code)]
+    pub mod core {
+        #[allow(dead_code, clippy::all)]
+        pub mod tasks {
+            #[used]
+            #[doc(hidden)]
+            static __FORCE_SECTION_REF: fn() = super::super::super::__link_custom_section_describing_imports;
+            use super::super::super::_rt;
+            /// A child task to be launched.
+            #[derive(Clone)]
+            pub struct Child<'a> {
+                /// The name of the child task.
+                pub name: &'a str,
+                /// The workflow exported by the program that the child task should
+                /// run. If this is `none` then the child task runs the program's
+                /// `wasi:cli/run` export.
+                pub entrypoint: Option<&'a str>,
+                /// JSON-encoded data for the child task.
+                pub data: &'a str,
+            }
+            impl<'a> ::core::fmt::Debug for Child<'a> {
+                fn fmt(
+                    &self,
+                    f: &mut ::core::fmt::Formatter<'_>,
+                ) -> ::core::fmt::Result {
+                    f.debug_struct("Child")
+                        .field("name", &self.name)
+                        .field("entrypoint", &self.entrypoint)
+                        .field("data", &self.data)
+                        .finish()
+                }
+            }
+            #[allow(unused_unsafe, clippy::all)]
+            /// Launch child tasks that run the same program as the current task.
+            ///
+            /// The child tasks belong to the same tenant, and have the same SQL
+            /// context, as the current task. When a child task completes, the current
+            /// task is sent a notification with the event `durable:child-complete`
+            /// and the data
+            ///
+            /// ```json
+            /// { "id": <child task id>, "success": <bool>, "result": <result or null> }
+            /// ```
+            ///
+            /// Returns the ids of the child tasks, in the same order as `children`.
+            ///
+            /// # Traps
+            /// This function will trap if called from within a transaction or if the
+            /// data for any child is not valid JSON.
+            pub fn launch(children: &[Child<'_>]) -> _rt::Vec<i64> {
+                unsafe {
+                    #[repr(align(4))]
+                    struct RetArea([::core::mem::MaybeUninit<u8>; 8]);
+                    let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 8]);
+                    let vec4 = children;
+                    let len4 = vec4.len();
+                    let layout4 = _rt::alloc::Layout::from_size_align_unchecked(
+                        vec4.len() * 28,
+                        4,
+                    );
+                    let result4 = if layout4.size() != 0 {
+                        let ptr = _rt::alloc::alloc(layout4).cast::<u8>();
+                        if ptr.is_null() {
+                            _rt::alloc::handle_alloc_error(layout4);
+                        }
+                        ptr
+                    } else {
+                        { ::core::ptr::null_mut() }
+                    };
+                    for (i, e) in vec4.into_iter().enumerate() {
+                        let base = result4.add(i * 28);
+                        {
+                            let Child {
+                                name: name0,
+                                entrypoint: entrypoint0,
+                                data: data0,
+                            } = e;
+                            let vec1 = name0;
+                            let ptr1 = vec1.as_ptr().cast::<u8>();
+                            let len1 = vec1.len();
+                            *base.add(4).cast::<usize>() = len1;
+                            *base.add(0).cast::<*mut u8>() = ptr1.cast_mut();
+                            match entrypoint0 {
+                                Some(e) => {
+                                    *base.add(8).cast::<u8>() = (1i32) as u8;
+                                    let vec2 = e;
+                                    let ptr2 = vec2.as_ptr().cast::<u8>();
+                                    let len2 = vec2.len();
+                                    *base.add(16).cast::<usize>() = len2;
+                                    *base.add(12).cast::<*mut u8>() = ptr2.cast_mut();
+                                }
+                                None => {
+                                    *base.add(8).cast::<u8>() = (0i32) as u8;
+                                }
+                            };
+                            let vec3 = data0;
+                            let ptr3 = vec3.as_ptr().cast::<u8>();
+                            let len3 = vec3.len();
+                            *base.add(24).cast::<usize>() = len3;
+                            *base.add(20).cast::<*mut u8>() = ptr3.cast_mut();
+                        }
+                    }
+                    let ptr5 = ret_area.0.as_mut_ptr().cast::<u8>();
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(wasm_import_module = "durable:core/tasks@2.7.0")]
+                    extern "C" {
+                        #[link_name = "launch"]
+                        fn wit_import(_: *mut u8, _: usize, _: *mut u8);
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    fn wit_import(_: *mut u8, _: usize, _: *mut u8) {
+                        unreachable!()
+                    }
+                    wit_import(result4, len4, ptr5);
+                    let l6 = *ptr5.add(0).cast::<*mut u8>();
+                    let l7 = *ptr5.add(4).cast::<usize>();
+                    let len8 = l7;
+                    if layout4.size() != 0 {
+                        _rt::alloc::dealloc(result4.cast(), layout4);
+                    }
+                    _rt::Vec::from_raw_parts(l6.cast(), len8, len8)
+                }
+            }
+            #[allow(unused_unsafe, clippy::all)]
+            /// Set the JSON-encoded result of the current task.
+            ///
+            /// The result is included in the notification sent to the parent task,
+            /// if there is one, once the current task completes. Calling this more
+            /// than once replaces the previous result.
+            ///
+            /// # Traps
+            /// This function will trap if called from within a transaction or if
+            /// `data` is not valid JSON.
+            pub fn set_result(data: &str) {
+                unsafe {
+                    let vec0 = data;
+                    let ptr0 = vec0.as_ptr().cast::<u8>();
+                    let len0 = vec0.len();
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(wasm_import_module = "durable:core/tasks@2.7.0")]
+                    extern "C" {
+                        #[link_name = "set-result"]
+                        fn wit_import(_: *mut u8, _: usize);
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    fn wit_import(_: *mut u8, _: usize) {
+                        unreachable!()
+                    }
+                    wit_import(ptr0.cast_mut(), len0);
+                }
+            }
+        }
+    }
+}
+mod _rt {
+    pub use alloc_crate::alloc;
+    pub use alloc_crate::vec::Vec;
+    extern crate alloc as alloc_crate;
+}
+#[cfg(target_arch = "wasm32")]
+#[link_section = "component-type:wit-bindgen:0.30.0:import-tasks:encoded world"]
+#[doc(hidden)]
+pub static __WIT_BINDGEN_COMPONENT_TYPE: [u8; 304] = *b"\
+\0asm\x0d\0\x01\0\0\x19\x16wit-component-encoding\x04\0\x07\xad\x01\x01A\x02\x01\
+A\x02\x01B\x09\x01ks\x01r\x03\x04names\x0aentrypoint\0\x04datas\x04\0\x05child\
+\x03\0\x01\x01p\x02\x01px\x01@\x01\x08children\x03\0\x04\x04\0\x06launch\x01\x05\
+\x01@\x01\x04datas\x01\0\x04\0\x0aset-result\x01\x06\x03\x01\x18durable:core/tas\
+ks@2.7.0\x05\0\x04\x01\x1fdurable:core/import-tasks@2.7.0\x04\0\x0b\x12\x01\0\
+\x0cimport-tasks\x03\0\0\0G\x09producers\x01\x0cprocessed-by\x02\x0dwit-componen\
+t\x070.215.0\x10wit-bindgen-rust\x060.30.0";
+#[inline(never)]
+#[doc(hidden)]
+pub fn __link_custom_section_describing_imports() {
+    wit_bindgen_rt::maybe_link_cabi_realloc();
+}
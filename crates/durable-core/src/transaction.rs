@@ -1,11 +1,41 @@
-use std::cell::UnsafeCell;
-use std::ops::{Deref, DerefMut};
-use std::panic::AssertUnwindSafe;
+use alloc::format;
+use alloc::string::String;
+#[cfg(feature = "std")]
+use core::cell::Cell;
+#[cfg(not(feature = "std"))]
+use core::sync::atomic::{AtomicBool, Ordering};
+use core::time::Duration;
 
 use serde::de::DeserializeOwned;
 use serde::Serialize;
 
-static IN_TRANSACTION: SyncUnsafeCell<bool> = SyncUnsafeCell::new(false);
+use crate::codec::Codec;
+#[cfg(feature = "json")]
+use crate::codec::Json;
+
+// Workflows are only run in a single-threaded environment, so on wasm this is
+// just a plain static. Using a thread local means that tests using the mock
+// runtime can still run on multiple threads at once.
+#[cfg(feature = "std")]
+thread_local! {
+    static IN_TRANSACTION: Cell<bool> = const { Cell::new(false) };
+}
+
+// Thread locals are not available without std. The mock runtime requires std
+// so this is only ever used within a single-threaded workflow.
+#[cfg(not(feature = "std"))]
+static IN_TRANSACTION: AtomicBool = AtomicBool::new(false);
+
+/// Set whether we are in a transaction, returning the previous value.
+#[cfg(feature = "std")]
+fn set_in_transaction(value: bool) -> bool {
+    IN_TRANSACTION.with(|in_txn| in_txn.replace(value))
+}
+
+#[cfg(not(feature = "std"))]
+fn set_in_transaction(value: bool) -> bool {
+    IN_TRANSACTION.swap(value, Ordering::Relaxed)
+}
 
 /// Create an execute a transaction.
 ///
@@ -14,38 +44,34 @@ static IN_TRANSACTION: SyncUnsafeCell<bool> = SyncUnsafeCell::new(false);
 /// middle of a transaction then the transaction will be retried from the start.
 /// Once the transaction has completed, however, it will not be executed again.
 /// This means that transactions provide at-least-once semantics.
+#[cfg(feature = "json")]
 pub fn transaction<F, T>(label: &str, func: F) -> T
 where
     F: Fn() -> T,
     T: Serialize + DeserializeOwned,
 {
-    let opts = TransactionOptions {
-        label,
-        is_txn: false,
-    };
+    transaction_with(TransactionOptions::new(label), func)
+}
 
-    transaction_with(opts, func)
+#[cfg(feature = "std")]
+pub fn in_transaction() -> bool {
+    IN_TRANSACTION.with(Cell::get)
 }
 
+#[cfg(not(feature = "std"))]
 pub fn in_transaction() -> bool {
-    // SAFETY: Workflows are only run in a single-threaded environment so this is
-    //         safe.
-    unsafe { std::ptr::read(IN_TRANSACTION.get()) }
+    IN_TRANSACTION.load(Ordering::Relaxed)
 }
 
 /// Run `func` in a transaction unless we are already running in one.
+#[cfg(feature = "json")]
 pub fn maybe_txn<F, T>(label: &str, func: F) -> T
 where
     F: Fn() -> T,
     T: Serialize + DeserializeOwned,
 {
     if !in_transaction() {
-        let opts = TransactionOptions {
-            label,
-            is_txn: false,
-        };
-
-        transaction_with(opts, func)
+        transaction_with(TransactionOptions::new(label), func)
     } else {
         func()
     }
@@ -54,6 +80,8 @@ where
 pub struct TransactionOptions<'a> {
     label: &'a str,
     is_txn: bool,
+    statement_timeout: Option<Duration>,
+    read_only: bool,
 }
 
 impl<'a> TransactionOptions<'a> {
@@ -61,6 +89,8 @@ impl<'a> TransactionOptions<'a> {
         Self {
             label,
             is_txn: false,
+            statement_timeout: None,
+            read_only: false,
         }
     }
 
@@ -68,14 +98,55 @@ impl<'a> TransactionOptions<'a> {
         self.is_txn = is_db_txn;
         self
     }
+
+    /// Abort any query within the database transaction that takes longer than
+    /// `timeout`.
+    ///
+    /// This is applied via postgres' `statement_timeout` setting. It has no
+    /// effect unless this is a database transaction.
+    pub fn statement_timeout(mut self, timeout: Duration) -> Self {
+        self.statement_timeout = Some(timeout);
+        self
+    }
+
+    /// Make the database transaction read-only.
+    ///
+    /// Any attempt to modify the database within the transaction will result
+    /// in an error. This has no effect unless this is a database transaction.
+    pub fn read_only(mut self) -> Self {
+        self.read_only = true;
+        self
+    }
+
+    fn enter(&self) -> Option<String> {
+        use crate::bindings::{transaction_enter, transaction_enter2};
+
+        // Only use the newer function when it is needed so that workflows which
+        // don't use these options still run on older runtimes.
+        if self.statement_timeout.is_none() && !self.read_only {
+            return transaction_enter(self.label, self.is_txn);
+        }
+
+        let statement_timeout = self
+            .statement_timeout
+            .map(|timeout| timeout.as_millis().try_into().unwrap_or(u64::MAX));
+
+        transaction_enter2(
+            self.label,
+            crate::bindings::TransactionOptions {
+                is_db: self.is_txn,
+                statement_timeout,
+                read_only: self.read_only,
+            },
+        )
+    }
 }
 
 struct InTxnGuard(());
 
 impl InTxnGuard {
     pub fn new() -> Self {
-        let in_txn = unsafe { std::ptr::replace(IN_TRANSACTION.get(), true) };
-        if in_txn {
+        if set_in_transaction(true) {
             panic!("attempted to start a transaction while aready within another");
         }
 
@@ -85,14 +156,32 @@ impl InTxnGuard {
 
 impl Drop for InTxnGuard {
     fn drop(&mut self) {
-        unsafe { std::ptr::write(IN_TRANSACTION.get(), false) };
+        set_in_transaction(false);
     }
 }
 
+/// Create and execute a transaction with the provided options.
+///
+/// Transaction results are serialized using the [`Json`] codec.
+#[cfg(feature = "json")]
 pub fn transaction_with<F, T>(opts: TransactionOptions, func: F) -> T
 where
     F: Fn() -> T,
     T: Serialize + DeserializeOwned,
+{
+    transaction_with_codec::<Json, F, T>(opts, func)
+}
+
+/// Create and execute a transaction, serializing its result using the codec
+/// `C`.
+///
+/// Without the `std` feature panics cannot be caught, so a panic within the
+/// transaction kills the workflow without recording the transaction.
+pub fn transaction_with_codec<C, F, T>(opts: TransactionOptions, func: F) -> T
+where
+    C: Codec,
+    F: Fn() -> T,
+    T: Serialize + DeserializeOwned,
 {
     #[derive(Serialize, Deserialize)]
     #[serde(tag = "type", content = "data", rename_all = "kebab-case")]
@@ -101,31 +190,27 @@ where
         Panic(E),
     }
 
-    // We need to replace panics with unknown panic payloads with a known panic
-    // payload so that future retries get the exact same panic.
-    const UNKNOWN_PANIC_MESSAGE: &str = "the transaction panicked with an unknown payload";
-
-    if let Some(data) = crate::bindings::transaction_enter(opts.label, opts.is_txn) {
-        let data: TransactionResult<T> = match serde_json::from_str(&data) {
+    if let Some(data) = opts.enter() {
+        let data: TransactionResult<T> = match C::decode(&data) {
             Ok(data) => data,
             Err(e) => unreachable!("saved task data was invalid json: {e}"),
         };
 
         match data {
             TransactionResult::Value(data) => return data,
-            TransactionResult::Panic(payload) => {
-                std::panic::resume_unwind(Box::new(payload));
-            }
+            TransactionResult::Panic(payload) => resume_panic(payload),
         }
     }
 
     let _guard = InTxnGuard::new();
-    let result = std::panic::catch_unwind(AssertUnwindSafe(|| {
+    let result = catch_panic(|| {
         let data = func();
 
-        serde_json::to_string(&TransactionResult::<_, String>::Value(&data))
-            .expect("failed to serialize the transaction result to json")
-    }));
+        match C::encode(&TransactionResult::<_, String>::Value(&data)) {
+            Ok(json) => json,
+            Err(e) => panic!("failed to serialize the transaction result to json: {e}"),
+        }
+    });
 
     match result {
         Ok(json) => {
@@ -136,58 +221,61 @@ where
             //
             // This is also relevant for the seralization wrappers of some resources that
             // track the transaction they were created in.
-            let result = serde_json::from_str::<TransactionResult<T>>(&json)
-                .expect("failed to deserialize the transaction result from json");
+            let result = match C::decode::<TransactionResult<T>>(&json) {
+                Ok(result) => result,
+                Err(e) => panic!("failed to deserialize the transaction result from json: {e}"),
+            };
 
             match result {
                 TransactionResult::Value(data) => data,
                 TransactionResult::Panic(_) => unreachable!(),
             }
         }
-        Err(payload) => {
-            let message: Box<String> = match payload.downcast::<String>() {
-                Ok(message) => message,
-                Err(payload) => {
-                    if let Some(&message) = payload.downcast_ref::<&str>() {
-                        Box::new(message.to_owned())
-                    } else {
-                        Box::new(UNKNOWN_PANIC_MESSAGE.to_owned())
-                    }
-                }
-            };
-
-            let json = match serde_json::to_string(&TransactionResult::<(), _>::Panic(&message)) {
+        Err(message) => {
+            let json = match C::encode(&TransactionResult::<(), _>::Panic(&message)) {
                 Ok(json) => json,
                 Err(e) => crate::abort(&format!("failed to serialize panic message to json: {e}")),
             };
 
             crate::bindings::transaction_exit(&json);
-            std::panic::resume_unwind(message);
+            resume_panic(message);
         }
     }
 }
 
-struct SyncUnsafeCell<T>(UnsafeCell<T>);
+/// Run `func`, converting any panic into its message.
+#[cfg(feature = "std")]
+fn catch_panic<R>(func: impl FnOnce() -> R) -> Result<R, String> {
+    // We need to replace panics with unknown panic payloads with a known panic
+    // payload so that future retries get the exact same panic.
+    const UNKNOWN_PANIC_MESSAGE: &str = "the transaction panicked with an unknown payload";
 
-impl<T> SyncUnsafeCell<T> {
-    pub const fn new(value: T) -> Self {
-        Self(UnsafeCell::new(value))
-    }
+    std::panic::catch_unwind(std::panic::AssertUnwindSafe(func)).map_err(|payload| {
+        match payload.downcast::<String>() {
+            Ok(message) => *message,
+            Err(payload) => {
+                if let Some(&message) = payload.downcast_ref::<&str>() {
+                    message.to_owned()
+                } else {
+                    UNKNOWN_PANIC_MESSAGE.to_owned()
+                }
+            }
+        }
+    })
 }
 
-unsafe impl<T> Send for SyncUnsafeCell<T> {}
-unsafe impl<T> Sync for SyncUnsafeCell<T> {}
-
-impl<T> Deref for SyncUnsafeCell<T> {
-    type Target = UnsafeCell<T>;
+/// Run `func`. Panics cannot be caught without std so this always succeeds.
+#[cfg(not(feature = "std"))]
+fn catch_panic<R>(func: impl FnOnce() -> R) -> Result<R, String> {
+    Ok(func())
+}
 
-    fn deref(&self) -> &Self::Target {
-        &self.0
-    }
+#[cfg(feature = "std")]
+fn resume_panic(message: String) -> ! {
+    std::panic::resume_unwind(Box::new(message))
 }
 
-impl<T> DerefMut for SyncUnsafeCell<T> {
-    fn deref_mut(&mut self) -> &mut Self::Target {
-        &mut self.0
-    }
+#[cfg(not(feature = "std"))]
+fn resume_panic(message: String) -> ! {
+    panic!("{message}")
 }
@@ -0,0 +1,466 @@
+#[allow(dead_code)]
+pub mod durable {
+    #[allow(dead_code)]
+    pub mod core {
+        #[allow(dead_code, clippy::all)]
+        pub mod email {
+            #[used]
+            #[doc(hidden)]
+            static __FORCE_SECTION_REF: fn() = super::super::super::__link_custom_section_describing_imports;
+            use super::super::super::_rt;
+            /// A file attached to an email.
+            #[derive(Clone)]
+            pub struct Attachment<'a> {
+                /// The name of the file, as shown to the recipient.
+                pub filename: &'a str,
+                /// The MIME type of the file (e.g. `application/pdf`).
+                pub content_type: &'a str,
+                /// The contents of the file.
+                pub data: &'a [u8],
+            }
+            impl<'a> ::core::fmt::Debug for Attachment<'a> {
+                fn fmt(
+                    &self,
+                    f: &mut ::core::fmt::Formatter<'_>,
+                ) -> ::core::fmt::Result {
+                    f.debug_struct("Attachment")
+                        .field("filename", &self.filename)
+                        .field("content-type", &self.content_type)
+                        .field("data", &self.data)
+                        .finish()
+                }
+            }
+            /// An email to be sent.
+            ///
+            /// Addresses may either be bare (`user@example.com`) or include a display
+            /// name (`User <user@example.com>`).
+            #[derive(Clone)]
+            pub struct Message<'a> {
+                /// The sender of the email.
+                ///
+                /// If this is `none` then the default sender configured on the worker
+                /// is used.
+                pub from: Option<&'a str>,
+                pub to: &'a [&'a str],
+                pub cc: &'a [&'a str],
+                pub bcc: &'a [&'a str],
+                pub reply_to: Option<&'a str>,
+                pub subject: &'a str,
+                /// The plain-text body of the email.
+                pub text: Option<&'a str>,
+                /// The HTML body of the email.
+                ///
+                /// If both `text` and `html` are provided then the email is sent with
+                /// both and the recipient's mail client picks which one to show.
+                pub html: Option<&'a str>,
+                pub attachments: &'a [Attachment<'a>],
+            }
+            impl<'a> ::core::fmt::Debug for Message<'a> {
+                fn fmt(
+                    &self,
+                    f: &mut ::core::fmt::Formatter<'_>,
+                ) -> ::core::fmt::Result {
+                    f.debug_struct("Message")
+                        .field("from", &self.from)
+                        .field("to", &self.to)
+                        .field("cc", &self.cc)
+                        .field("bcc", &self.bcc)
+                        .field("reply-to", &self.reply_to)
+                        .field("subject", &self.subject)
+                        .field("text", &self.text)
+                        .field("html", &self.html)
+                        .field("attachments", &self.attachments)
+                        .finish()
+                }
+            }
+            /// Errors that can occur when sending an email.
+            #[derive(Clone)]
+            pub enum EmailError {
+                /// The worker does not have email configured.
+                NotConfigured,
+                /// The message could not be built (e.g. an address was invalid).
+                InvalidMessage(_rt::String),
+                /// The mail server refused to accept the message.
+                Rejected(_rt::String),
+                /// The mail server could not be reached, or the connection failed
+                /// part-way through.
+                Transport(_rt::String),
+            }
+            impl ::core::fmt::Debug for EmailError {
+                fn fmt(
+                    &self,
+                    f: &mut ::core::fmt::Formatter<'_>,
+                ) -> ::core::fmt::Result {
+                    match self {
+                        EmailError::NotConfigured => {
+                            f.debug_tuple("EmailError::NotConfigured").finish()
+                        }
+                        EmailError::InvalidMessage(e) => {
+                            f.debug_tuple("EmailError::InvalidMessage").field(e).finish()
+                        }
+                        EmailError::Rejected(e) => {
+                            f.debug_tuple("EmailError::Rejected").field(e).finish()
+                        }
+                        EmailError::Transport(e) => {
+                            f.debug_tuple("EmailError::Transport").field(e).finish()
+                        }
+                    }
+                }
+            }
+            impl ::core::fmt::Display for EmailError {
+                fn fmt(
+                    &self,
+                    f: &mut ::core::fmt::Formatter<'_>,
+                ) -> ::core::fmt::Result {
+                    write!(f, "{:?}", self)
+                }
+            }
+            impl std::error::Error for EmailError {}
+            #[allow(unused_unsafe, clippy::all)]
+            /// Send an email.
+            ///
+            /// Returns the id of the message that was sent. When sending via SMTP this
+            /// is the `Message-ID` header of the email, which is derived from the task
+            /// and transaction that sent it. If the worker crashes after sending an
+            /// email but before recording that it was sent, then the retried email
+            /// will have the same `Message-ID`, allowing mail clients to de-duplicate
+            /// it.
+            ///
+            /// # Traps
+            /// This function will trap if called from outside of a durable transaction.
+            pub fn send(message: Message<'_>) -> Result<_rt::String, EmailError> {
+                unsafe {
+                    #[repr(align(4))]
+                    struct RetArea([::core::mem::MaybeUninit<u8>; 88]);
+                    let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 88]);
+                    let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
+                    let Message {
+                        from: from1,
+                        to: to1,
+                        cc: cc1,
+                        bcc: bcc1,
+                        reply_to: reply_to1,
+                        subject: subject1,
+                        text: text1,
+                        html: html1,
+                        attachments: attachments1,
+                    } = message;
+                    match from1 {
+                        Some(e) => {
+                            *ptr0.add(0).cast::<u8>() = (1i32) as u8;
+                            let vec2 = e;
+                            let ptr2 = vec2.as_ptr().cast::<u8>();
+                            let len2 = vec2.len();
+                            *ptr0.add(8).cast::<usize>() = len2;
+                            *ptr0.add(4).cast::<*mut u8>() = ptr2.cast_mut();
+                        }
+                        None => {
+                            *ptr0.add(0).cast::<u8>() = (0i32) as u8;
+                        }
+                    };
+                    let vec4 = to1;
+                    let len4 = vec4.len();
+                    let layout4 = _rt::alloc::Layout::from_size_align_unchecked(
+                        vec4.len() * 8,
+                        4,
+                    );
+                    let result4 = if layout4.size() != 0 {
+                        let ptr = _rt::alloc::alloc(layout4).cast::<u8>();
+                        if ptr.is_null() {
+                            _rt::alloc::handle_alloc_error(layout4);
+                        }
+                        ptr
+                    } else {
+                        { ::core::ptr::null_mut() }
+                    };
+                    for (i, e) in vec4.into_iter().enumerate() {
+                        let base = result4.add(i * 8);
+                        {
+                            let vec3 = e;
+                            let ptr3 = vec3.as_ptr().cast::<u8>();
+                            let len3 = vec3.len();
+                            *base.add(4).cast::<usize>() = len3;
+                            *base.add(0).cast::<*mut u8>() = ptr3.cast_mut();
+                        }
+                    }
+                    *ptr0.add(16).cast::<usize>() = len4;
+                    *ptr0.add(12).cast::<*mut u8>() = result4;
+                    let vec6 = cc1;
+                    let len6 = vec6.len();
+                    let layout6 = _rt::alloc::Layout::from_size_align_unchecked(
+                        vec6.len() * 8,
+                        4,
+                    );
+                    let result6 = if layout6.size() != 0 {
+                        let ptr = _rt::alloc::alloc(layout6).cast::<u8>();
+                        if ptr.is_null() {
+                            _rt::alloc::handle_alloc_error(layout6);
+                        }
+                        ptr
+                    } else {
+                        { ::core::ptr::null_mut() }
+                    };
+                    for (i, e) in vec6.into_iter().enumerate() {
+                        let base = result6.add(i * 8);
+                        {
+                            let vec5 = e;
+                            let ptr5 = vec5.as_ptr().cast::<u8>();
+                            let len5 = vec5.len();
+                            *base.add(4).cast::<usize>() = len5;
+                            *base.add(0).cast::<*mut u8>() = ptr5.cast_mut();
+                        }
+                    }
+                    *ptr0.add(24).cast::<usize>() = len6;
+                    *ptr0.add(20).cast::<*mut u8>() = result6;
+                    let vec8 = bcc1;
+                    let len8 = vec8.len();
+                    let layout8 = _rt::alloc::Layout::from_size_align_unchecked(
+                        vec8.len() * 8,
+                        4,
+                    );
+                    let result8 = if layout8.size() != 0 {
+                        let ptr = _rt::alloc::alloc(layout8).cast::<u8>();
+                        if ptr.is_null() {
+                            _rt::alloc::handle_alloc_error(layout8);
+                        }
+                        ptr
+                    } else {
+                        { ::core::ptr::null_mut() }
+                    };
+                    for (i, e) in vec8.into_iter().enumerate() {
+                        let base = result8.add(i * 8);
+                        {
+                            let vec7 = e;
+                            let ptr7 = vec7.as_ptr().cast::<u8>();
+                            let len7 = vec7.len();
+                            *base.add(4).cast::<usize>() = len7;
+                            *base.add(0).cast::<*mut u8>() = ptr7.cast_mut();
+                        }
+                    }
+                    *ptr0.add(32).cast::<usize>() = len8;
+                    *ptr0.add(28).cast::<*mut u8>() = result8;
+                    match reply_to1 {
+                        Some(e) => {
+                            *ptr0.add(36).cast::<u8>() = (1i32) as u8;
+                            let vec9 = e;
+                            let ptr9 = vec9.as_ptr().cast::<u8>();
+                            let len9 = vec9.len();
+                            *ptr0.add(44).cast::<usize>() = len9;
+                            *ptr0.add(40).cast::<*mut u8>() = ptr9.cast_mut();
+                        }
+                        None => {
+                            *ptr0.add(36).cast::<u8>() = (0i32) as u8;
+                        }
+                    };
+                    let vec10 = subject1;
+                    let ptr10 = vec10.as_ptr().cast::<u8>();
+                    let len10 = vec10.len();
+                    *ptr0.add(52).cast::<usize>() = len10;
+                    *ptr0.add(48).cast::<*mut u8>() = ptr10.cast_mut();
+                    match text1 {
+                        Some(e) => {
+                            *ptr0.add(56).cast::<u8>() = (1i32) as u8;
+                            let vec11 = e;
+                            let ptr11 = vec11.as_ptr().cast::<u8>();
+                            let len11 = vec11.len();
+                            *ptr0.add(64).cast::<usize>() = len11;
+                            *ptr0.add(60).cast::<*mut u8>() = ptr11.cast_mut();
+                        }
+                        None => {
+                            *ptr0.add(56).cast::<u8>() = (0i32) as u8;
+                        }
+                    };
+                    match html1 {
+                        Some(e) => {
+                            *ptr0.add(68).cast::<u8>() = (1i32) as u8;
+                            let vec12 = e;
+                            let ptr12 = vec12.as_ptr().cast::<u8>();
+                            let len12 = vec12.len();
+                            *ptr0.add(76).cast::<usize>() = len12;
+                            *ptr0.add(72).cast::<*mut u8>() = ptr12.cast_mut();
+                        }
+                        None => {
+                            *ptr0.add(68).cast::<u8>() = (0i32) as u8;
+                        }
+                    };
+                    let vec17 = attachments1;
+                    let len17 = vec17.len();
+                    let layout17 = _rt::alloc::Layout::from_size_align_unchecked(
+                        vec17.len() * 24,
+                        4,
+                    );
+                    let result17 = if layout17.size() != 0 {
+                        let ptr = _rt::alloc::alloc(layout17).cast::<u8>();
+                        if ptr.is_null() {
+                            _rt::alloc::handle_alloc_error(layout17);
+                        }
+                        ptr
+                    } else {
+                        { ::core::ptr::null_mut() }
+                    };
+                    for (i, e) in vec17.into_iter().enumerate() {
+                        let base = result17.add(i * 24);
+                        {
+                            let Attachment {
+                                filename: filename13,
+                                content_type: content_type13,
+                                data: data13,
+                            } = e;
+                            let vec14 = filename13;
+                            let ptr14 = vec14.as_ptr().cast::<u8>();
+                            let len14 = vec14.len();
+                            *base.add(4).cast::<usize>() = len14;
+                            *base.add(0).cast::<*mut u8>() = ptr14.cast_mut();
+                            let vec15 = content_type13;
+                            let ptr15 = vec15.as_ptr().cast::<u8>();
+                            let len15 = vec15.len();
+                            *base.add(12).cast::<usize>() = len15;
+                            *base.add(8).cast::<*mut u8>() = ptr15.cast_mut();
+                            let vec16 = data13;
+                            let ptr16 = vec16.as_ptr().cast::<u8>();
+                            let len16 = vec16.len();
+                            *base.add(20).cast::<usize>() = len16;
+                            *base.add(16).cast::<*mut u8>() = ptr16.cast_mut();
+                        }
+                    }
+                    *ptr0.add(84).cast::<usize>() = len17;
+                    *ptr0.add(80).cast::<*mut u8>() = result17;
+                    let ptr18 = ret_area.0.as_mut_ptr().cast::<u8>();
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(wasm_import_module = "durable:core/email@2.7.0")]
+                    extern "C" {
+                        #[link_name = "send"]
+                        fn wit_import(_: *mut u8, _: *mut u8);
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    fn wit_import(_: *mut u8, _: *mut u8) {
+                        unreachable!()
+                    }
+                    wit_import(ptr0, ptr18);
+                    let l19 = i32::from(*ptr18.add(0).cast::<u8>());
+                    if layout4.size() != 0 {
+                        _rt::alloc::dealloc(result4.cast(), layout4);
+                    }
+                    if layout6.size() != 0 {
+                        _rt::alloc::dealloc(result6.cast(), layout6);
+                    }
+                    if layout8.size() != 0 {
+                        _rt::alloc::dealloc(result8.cast(), layout8);
+                    }
+                    if layout17.size() != 0 {
+                        _rt::alloc::dealloc(result17.cast(), layout17);
+                    }
+                    match l19 {
+                        0 => {
+                            let e = {
+                                let l20 = *ptr18.add(4).cast::<*mut u8>();
+                                let l21 = *ptr18.add(8).cast::<usize>();
+                                let len22 = l21;
+                                let bytes22 = _rt::Vec::from_raw_parts(
+                                    l20.cast(),
+                                    len22,
+                                    len22,
+                                );
+                                _rt::string_lift(bytes22)
+                            };
+                            Ok(e)
+                        }
+                        1 => {
+                            let e = {
+                                let l23 = i32::from(*ptr18.add(4).cast::<u8>());
+                                let v33 = match l23 {
+                                    0 => EmailError::NotConfigured,
+                                    1 => {
+                                        let e33 = {
+                                            let l24 = *ptr18.add(8).cast::<*mut u8>();
+                                            let l25 = *ptr18.add(12).cast::<usize>();
+                                            let len26 = l25;
+                                            let bytes26 = _rt::Vec::from_raw_parts(
+                                                l24.cast(),
+                                                len26,
+                                                len26,
+                                            );
+                                            _rt::string_lift(bytes26)
+                                        };
+                                        EmailError::InvalidMessage(e33)
+                                    }
+                                    2 => {
+                                        let e33 = {
+                                            let l27 = *ptr18.add(8).cast::<*mut u8>();
+                                            let l28 = *ptr18.add(12).cast::<usize>();
+                                            let len29 = l28;
+                                            let bytes29 = _rt::Vec::from_raw_parts(
+                                                l27.cast(),
+                                                len29,
+                                                len29,
+                                            );
+                                            _rt::string_lift(bytes29)
+                                        };
+                                        EmailError::Rejected(e33)
+                                    }
+                                    n => {
+                                        debug_assert_eq!(n, 3, "invalid enum discriminant");
+                                        let e33 = {
+                                            let l30 = *ptr18.add(8).cast::<*mut u8>();
+                                            let l31 = *ptr18.add(12).cast::<usize>();
+                                            let len32 = l31;
+                                            let bytes32 = _rt::Vec::from_raw_parts(
+                                                l30.cast(),
+                                                len32,
+                                                len32,
+                                            );
+                                            _rt::string_lift(bytes32)
+                                        };
+                                        EmailError::Transport(e33)
+                                    }
+                                };
+                                v33
+                            };
+                            Err(e)
+                        }
+                        _ => _rt::invalid_enum_discriminant(),
+                    }
+                }
+            }
+        }
+    }
+}
+mod _rt {
+    pub use alloc_crate::string::String;
+    pub use alloc_crate::alloc;
+    pub use alloc_crate::vec::Vec;
+    pub unsafe fn string_lift(bytes: Vec<u8>) -> String {
+        if cfg!(debug_assertions) {
+            String::from_utf8(bytes).unwrap()
+        } else {
+            String::from_utf8_unchecked(bytes)
+        }
+    }
+    pub unsafe fn invalid_enum_discriminant<T>() -> T {
+        if cfg!(debug_assertions) {
+            panic!("invalid enum discriminant")
+        } else {
+            core::hint::unreachable_unchecked()
+        }
+    }
+    extern crate alloc as alloc_crate;
+}
+#[cfg(target_arch = "wasm32")]
+#[link_section = "component-type:wit-bindgen:0.30.0:import-email:encoded world"]
+#[doc(hidden)]
+pub static __WIT_BINDGEN_COMPONENT_TYPE: [u8; 455] = *b"\
+\0asm\x0d\0\x01\0\0\x19\x16wit-component-encoding\x04\0\x07\xc4\x02\x01A\x02\x01\
+A\x02\x01B\x0d\x01p}\x01r\x03\x08filenames\x0ccontent-types\x04data\0\x04\0\x0aa\
+ttachment\x03\0\x01\x01ks\x01ps\x01p\x02\x01r\x09\x04from\x03\x02to\x04\x02cc\x04\
+\x03bcc\x04\x08reply-to\x03\x07subjects\x04text\x03\x04html\x03\x0battachments\x05\
+\x04\0\x07message\x03\0\x06\x01q\x04\x0enot-configured\0\0\x0finvalid-message\x01\
+s\0\x08rejected\x01s\0\x09transport\x01s\0\x04\0\x0bemail-error\x03\0\x08\x01j\x01\
+s\x01\x09\x01@\x01\x07message\x07\0\x0a\x04\0\x04send\x01\x0b\x03\x01\x18durable\
+:core/email@2.7.0\x05\0\x04\x01\x1fdurable:core/import-email@2.7.0\x04\0\x0b\x12\
+\x01\0\x0cimport-email\x03\0\0\0G\x09producers\x01\x0cprocessed-by\x02\x0dwit-co\
+mponent\x070.215.0\x10wit-bindgen-rust\x060.30.0";
+#[inline(never)]
+#[doc(hidden)]
+pub fn __link_custom_section_describing_imports() {
+    wit_bindgen_rt::maybe_link_cabi_realloc();
+}
@@ -0,0 +1,363 @@
+//! Send emails as part of your workflow.
+//!
+//! The mail server, along with the credentials used to access it, is
+//! configured on the worker.
+//!
+//! ```no_run
+//! use durable::email::{Attachment, Email};
+//!
+//! let id = Email::new()
+//!     .to("Alice <alice@example.com>")
+//!     .subject("Your daily report")
+//!     .text("Your report is attached.")
+//!     .attach(Attachment::new("report.csv", "text/csv", "a,b,c\n1,2,3\n"))
+//!     .send()
+//!     .expect("failed to send the email");
+//!
+//! println!("sent email {id}");
+//! ```
+//!
+//! Each email is sent within its own durable transaction, unless it is sent
+//! from within an existing transaction. Once an email has been sent that fact
+//! is recorded, so it will not be sent again if the workflow is restarted.
+//!
+//! # Templates
+//! Emails can also be rendered from a [`Template`]. Templates substitute
+//! `{{ name }}` placeholders with the corresponding field of a serializable
+//! value. Values inserted into the HTML body are escaped.
+//!
+//! ```no_run
+//! use durable::email::Template;
+//!
+//! #[derive(serde::Serialize)]
+//! struct Welcome<'a> {
+//!     name: &'a str,
+//! }
+//!
+//! let template = Template::new("Welcome, {{ name }}!")
+//!     .text("Hi {{ name }}, thanks for signing up.")
+//!     .html("<p>Hi {{ name }}, thanks for signing up.</p>");
+//!
+//! template
+//!     .render(&Welcome { name: "Alice" })
+//!     .expect("failed to render the email")
+//!     .to("alice@example.com")
+//!     .send()
+//!     .expect("failed to send the email");
+//! ```
+
+use std::fmt;
+
+use durable_core::transaction;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+mod bindings {
+    #![allow(unused_braces, clippy::all)]
+
+    include!("bindings.rs");
+
+    pub use self::durable::core::email::*;
+}
+
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// An email to be sent.
+///
+/// Addresses may either be bare (`user@example.com`) or include a display
+/// name (`User <user@example.com>`).
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct Email {
+    from: Option<String>,
+    to: Vec<String>,
+    cc: Vec<String>,
+    bcc: Vec<String>,
+    reply_to: Option<String>,
+    subject: String,
+    text: Option<String>,
+    html: Option<String>,
+    attachments: Vec<Attachment>,
+}
+
+impl Email {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the sender of this email.
+    ///
+    /// If this is not set then the default sender configured on the worker is
+    /// used.
+    pub fn from(mut self, address: impl Into<String>) -> Self {
+        self.from = Some(address.into());
+        self
+    }
+
+    /// Add a recipient.
+    pub fn to(mut self, address: impl Into<String>) -> Self {
+        self.to.push(address.into());
+        self
+    }
+
+    /// Add a recipient who is copied on the email.
+    pub fn cc(mut self, address: impl Into<String>) -> Self {
+        self.cc.push(address.into());
+        self
+    }
+
+    /// Add a recipient who is not visible to the other recipients.
+    pub fn bcc(mut self, address: impl Into<String>) -> Self {
+        self.bcc.push(address.into());
+        self
+    }
+
+    /// Set the address that replies should be sent to.
+    pub fn reply_to(mut self, address: impl Into<String>) -> Self {
+        self.reply_to = Some(address.into());
+        self
+    }
+
+    pub fn subject(mut self, subject: impl Into<String>) -> Self {
+        self.subject = subject.into();
+        self
+    }
+
+    /// Set the plain-text body of this email.
+    pub fn text(mut self, text: impl Into<String>) -> Self {
+        self.text = Some(text.into());
+        self
+    }
+
+    /// Set the HTML body of this email.
+    ///
+    /// If both a plain-text and an HTML body are set then the email is sent
+    /// with both and the recipient's mail client picks which one to show.
+    pub fn html(mut self, html: impl Into<String>) -> Self {
+        self.html = Some(html.into());
+        self
+    }
+
+    /// Attach a file to this email.
+    pub fn attach(mut self, attachment: Attachment) -> Self {
+        self.attachments.push(attachment);
+        self
+    }
+
+    /// Send this email.
+    ///
+    /// Returns the id of the message that was sent.
+    pub fn send(&self) -> Result<String> {
+        let label = format!("durable::email::send({})", self.subject);
+        transaction::maybe_txn(&label, || {
+            let to: Vec<_> = self.to.iter().map(|to| to.as_str()).collect();
+            let cc: Vec<_> = self.cc.iter().map(|cc| cc.as_str()).collect();
+            let bcc: Vec<_> = self.bcc.iter().map(|bcc| bcc.as_str()).collect();
+            let attachments: Vec<_> = self
+                .attachments
+                .iter()
+                .map(|attachment| bindings::Attachment {
+                    filename: &attachment.filename,
+                    content_type: &attachment.content_type,
+                    data: &attachment.data,
+                })
+                .collect();
+
+            let message = bindings::Message {
+                from: self.from.as_deref(),
+                to: &to,
+                cc: &cc,
+                bcc: &bcc,
+                reply_to: self.reply_to.as_deref(),
+                subject: &self.subject,
+                text: self.text.as_deref(),
+                html: self.html.as_deref(),
+                attachments: &attachments,
+            };
+
+            bindings::send(message).map_err(Error::from)
+        })
+    }
+}
+
+/// A file attached to an email.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Attachment {
+    filename: String,
+    content_type: String,
+    data: Vec<u8>,
+}
+
+impl Attachment {
+    /// Create a new attachment.
+    ///
+    /// `content_type` is the MIME type of the file (e.g. `application/pdf`).
+    pub fn new(
+        filename: impl Into<String>,
+        content_type: impl Into<String>,
+        data: impl Into<Vec<u8>>,
+    ) -> Self {
+        Self {
+            filename: filename.into(),
+            content_type: content_type.into(),
+            data: data.into(),
+        }
+    }
+}
+
+/// A template that can be rendered into an [`Email`].
+///
+/// See the [crate-level docs](crate#templates) for the template syntax.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Template {
+    subject: String,
+    text: Option<String>,
+    html: Option<String>,
+}
+
+impl Template {
+    /// Create a new template with the provided subject.
+    pub fn new(subject: impl Into<String>) -> Self {
+        Self {
+            subject: subject.into(),
+            text: None,
+            html: None,
+        }
+    }
+
+    /// Set the template for the plain-text body.
+    pub fn text(mut self, text: impl Into<String>) -> Self {
+        self.text = Some(text.into());
+        self
+    }
+
+    /// Set the template for the HTML body.
+    pub fn html(mut self, html: impl Into<String>) -> Self {
+        self.html = Some(html.into());
+        self
+    }
+
+    /// Render this template into an [`Email`] using the fields of `context`.
+    ///
+    /// The returned email has no recipients. They should be added before it
+    /// is sent.
+    pub fn render<T: Serialize + ?Sized>(&self, context: &T) -> Result<Email> {
+        let context = serde_json::to_value(context)
+            .map_err(|e| Error::Template(format!("failed to serialize the context: {e}")))?;
+
+        let mut email = Email::new().subject(render(&self.subject, &context, false)?);
+        if let Some(text) = &self.text {
+            email = email.text(render(text, &context, false)?);
+        }
+        if let Some(html) = &self.html {
+            email = email.html(render(html, &context, true)?);
+        }
+
+        Ok(email)
+    }
+}
+
+fn render(template: &str, context: &Value, escape: bool) -> Result<String> {
+    let mut output = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(start) = rest.find("{{") {
+        output.push_str(&rest[..start]);
+
+        let Some(len) = rest[start..].find("}}") else {
+            return Err(Error::Template(format!(
+                "unterminated placeholder `{}`",
+                &rest[start..]
+            )));
+        };
+
+        let name = rest[start + 2..start + len].trim();
+        let value = name
+            .split('.')
+            .try_fold(context, |value, field| value.get(field))
+            .ok_or_else(|| Error::Template(format!("`{name}` is not present in the context")))?;
+
+        let value = match value {
+            Value::Null => String::new(),
+            Value::String(value) => value.clone(),
+            Value::Bool(_) | Value::Number(_) => value.to_string(),
+            Value::Array(_) | Value::Object(_) => {
+                return Err(Error::Template(format!(
+                    "`{name}` cannot be inserted into a template"
+                )))
+            }
+        };
+
+        if escape {
+            escape_html(&mut output, &value);
+        } else {
+            output.push_str(&value);
+        }
+
+        rest = &rest[start + len + 2..];
+    }
+
+    output.push_str(rest);
+    Ok(output)
+}
+
+fn escape_html(output: &mut String, value: &str) {
+    for c in value.chars() {
+        match c {
+            '&' => output.push_str("&amp;"),
+            '<' => output.push_str("&lt;"),
+            '>' => output.push_str("&gt;"),
+            '"' => output.push_str("&quot;"),
+            '\'' => output.push_str("&#39;"),
+            c => output.push(c),
+        }
+    }
+}
+
+/// An error that occurred while rendering or sending an email.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+#[non_exhaustive]
+pub enum Error {
+    /// The worker does not have email configured.
+    NotConfigured,
+
+    /// The email could not be built (e.g. an address was invalid).
+    InvalidMessage(String),
+
+    /// The mail server refused to accept the email.
+    Rejected(String),
+
+    /// The mail server could not be reached, or the connection failed
+    /// part-way through.
+    Transport(String),
+
+    /// A [`Template`] could not be rendered.
+    Template(String),
+}
+
+impl From<bindings::EmailError> for Error {
+    fn from(error: bindings::EmailError) -> Self {
+        use bindings::EmailError;
+
+        match error {
+            EmailError::NotConfigured => Self::NotConfigured,
+            EmailError::InvalidMessage(message) => Self::InvalidMessage(message),
+            EmailError::Rejected(message) => Self::Rejected(message),
+            EmailError::Transport(message) => Self::Transport(message),
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotConfigured => f.write_str("the worker does not have email configured"),
+            Self::InvalidMessage(message) => write!(f, "invalid email: {message}"),
+            Self::Rejected(message) => write!(f, "the email was rejected: {message}"),
+            Self::Transport(message) => write!(f, "failed to send the email: {message}"),
+            Self::Template(message) => write!(f, "failed to render the email template: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
@@ -153,7 +153,7 @@ pub mod durable {
                     unreachable!();
                     #[cfg(target_arch = "wasm32")]
                     {
-                        #[link(wasm_import_module = "durable:core/http@2.5.0")]
+                        #[link(wasm_import_module = "durable:core/http@2.7.0")]
                         extern "C" {
                             #[link_name = "[resource-drop]http-error2"]
                             fn drop(_: u32);
@@ -193,7 +193,7 @@ pub mod durable {
                     unreachable!();
                     #[cfg(target_arch = "wasm32")]
                     {
-                        #[link(wasm_import_module = "durable:core/http@2.5.0")]
+                        #[link(wasm_import_module = "durable:core/http@2.7.0")]
                         extern "C" {
                             #[link_name = "[resource-drop]http-request2"]
                             fn drop(_: u32);
@@ -202,6 +202,164 @@ pub mod durable {
                     }
                 }
             }
+            /// How redirect responses are handled for a request.
+            #[derive(Clone, Copy)]
+            pub enum RedirectPolicy {
+                /// Don't follow any redirects. Redirect responses are returned as-is.
+                None,
+                /// Follow at most this many redirects. If there are more then the last
+                /// redirect response is returned.
+                Limited(u32),
+                /// Follow redirects until reaching a URL that has already been
+                /// visited.
+                Follow,
+            }
+            impl ::core::fmt::Debug for RedirectPolicy {
+                fn fmt(
+                    &self,
+                    f: &mut ::core::fmt::Formatter<'_>,
+                ) -> ::core::fmt::Result {
+                    match self {
+                        RedirectPolicy::None => {
+                            f.debug_tuple("RedirectPolicy::None").finish()
+                        }
+                        RedirectPolicy::Limited(e) => {
+                            f.debug_tuple("RedirectPolicy::Limited").field(e).finish()
+                        }
+                        RedirectPolicy::Follow => {
+                            f.debug_tuple("RedirectPolicy::Follow").finish()
+                        }
+                    }
+                }
+            }
+            /// Controls how `fetch3` retries a request.
+            #[repr(C)]
+            #[derive(Clone, Copy)]
+            pub struct RetryPolicy {
+                /// The maximum number of times to send the request, including the
+                /// first attempt.
+                pub max_attempts: u32,
+                /// The delay before the first retry, in nanoseconds. This is doubled
+                /// after every retry.
+                pub initial_backoff: u64,
+                /// The maximum delay between two attempts, in nanoseconds.
+                pub max_backoff: u64,
+            }
+            impl ::core::fmt::Debug for RetryPolicy {
+                fn fmt(
+                    &self,
+                    f: &mut ::core::fmt::Formatter<'_>,
+                ) -> ::core::fmt::Result {
+                    f.debug_struct("RetryPolicy")
+                        .field("max-attempts", &self.max_attempts)
+                        .field("initial-backoff", &self.initial_backoff)
+                        .field("max-backoff", &self.max_backoff)
+                        .finish()
+                }
+            }
+            #[derive(Clone)]
+            pub struct FetchResponse {
+                /// The response to the final attempt.
+                pub response: HttpResponse,
+                /// The number of times that the request was sent.
+                pub attempts: u32,
+                /// The URLs that the request was redirected to, in order. The last one
+                /// is the URL that `response` came from.
+                ///
+                /// Only the final URL is known for requests without a redirect policy.
+                pub redirects: _rt::Vec<_rt::String>,
+            }
+            impl ::core::fmt::Debug for FetchResponse {
+                fn fmt(
+                    &self,
+                    f: &mut ::core::fmt::Formatter<'_>,
+                ) -> ::core::fmt::Result {
+                    f.debug_struct("FetchResponse")
+                        .field("response", &self.response)
+                        .field("attempts", &self.attempts)
+                        .field("redirects", &self.redirects)
+                        .finish()
+                }
+            }
+            /// A request to be sent via `fetch-all`.
+            pub struct FetchAllRequest {
+                pub request: HttpRequest2,
+                pub retry: Option<RetryPolicy>,
+            }
+            impl ::core::fmt::Debug for FetchAllRequest {
+                fn fmt(
+                    &self,
+                    f: &mut ::core::fmt::Formatter<'_>,
+                ) -> ::core::fmt::Result {
+                    f.debug_struct("FetchAllRequest")
+                        .field("request", &self.request)
+                        .field("retry", &self.retry)
+                        .finish()
+                }
+            }
+            /// A single server-sent event.
+            #[derive(Clone)]
+            pub struct SseEvent {
+                /// The id of the event, if it set one.
+                pub id: Option<_rt::String>,
+                /// The event type. This is `message` if the event didn't specify one.
+                pub event: _rt::String,
+                /// The event data. Multiple `data` lines are joined with newlines.
+                pub data: _rt::String,
+            }
+            impl ::core::fmt::Debug for SseEvent {
+                fn fmt(
+                    &self,
+                    f: &mut ::core::fmt::Formatter<'_>,
+                ) -> ::core::fmt::Result {
+                    f.debug_struct("SseEvent")
+                        .field("id", &self.id)
+                        .field("event", &self.event)
+                        .field("data", &self.data)
+                        .finish()
+                }
+            }
+            /// A response whose body is a stream of server-sent events.
+            ///
+            /// Unlike other resources, an event stream is not tied to the transaction
+            /// that opened it and can be read from in later transactions.
+            #[derive(Debug)]
+            #[repr(transparent)]
+            pub struct EventStream {
+                handle: _rt::Resource<EventStream>,
+            }
+            impl EventStream {
+                #[doc(hidden)]
+                pub unsafe fn from_handle(handle: u32) -> Self {
+                    Self {
+                        handle: _rt::Resource::from_handle(handle),
+                    }
+                }
+                #[doc(hidden)]
+                pub fn take_handle(&self) -> u32 {
+                    _rt::Resource::take_handle(&self.handle)
+                }
+                #[doc(hidden)]
+                pub fn handle(&self) -> u32 {
+                    _rt::Resource::handle(&self.handle)
+                }
+            }
+            unsafe impl _rt::WasmResource for EventStream {
+                #[inline]
+                unsafe fn drop(_handle: u32) {
+                    #[cfg(not(target_arch = "wasm32"))]
+                    unreachable!();
+                    #[cfg(target_arch = "wasm32")]
+                    {
+                        #[link(wasm_import_module = "durable:core/http@2.7.0")]
+                        extern "C" {
+                            #[link_name = "[resource-drop]event-stream"]
+                            fn drop(_: u32);
+                        }
+                        drop(_handle);
+                    }
+                }
+            }
             #[allow(unused_unsafe, clippy::all)]
             /// Make an HTTP request.
             ///
@@ -271,7 +429,7 @@ pub mod durable {
                     };
                     let ptr10 = ret_area.0.as_mut_ptr().cast::<u8>();
                     #[cfg(target_arch = "wasm32")]
-                    #[link(wasm_import_module = "durable:core/http@2.5.0")]
+                    #[link(wasm_import_module = "durable:core/http@2.7.0")]
                     extern "C" {
                         #[link_name = "fetch"]
                         fn wit_import(
@@ -424,7 +582,7 @@ pub mod durable {
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
                         #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "durable:core/http@2.5.0")]
+                        #[link(wasm_import_module = "durable:core/http@2.7.0")]
                         extern "C" {
                             #[link_name = "[method]http-error2.message"]
                             fn wit_import(_: i32, _: *mut u8);
@@ -448,7 +606,7 @@ pub mod durable {
                 pub fn is_timeout(&self) -> bool {
                     unsafe {
                         #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "durable:core/http@2.5.0")]
+                        #[link(wasm_import_module = "durable:core/http@2.7.0")]
                         extern "C" {
                             #[link_name = "[method]http-error2.is-timeout"]
                             fn wit_import(_: i32) -> i32;
@@ -468,7 +626,7 @@ pub mod durable {
                 pub fn is_builder(&self) -> bool {
                     unsafe {
                         #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "durable:core/http@2.5.0")]
+                        #[link(wasm_import_module = "durable:core/http@2.7.0")]
                         extern "C" {
                             #[link_name = "[method]http-error2.is-builder"]
                             fn wit_import(_: i32) -> i32;
@@ -488,7 +646,7 @@ pub mod durable {
                 pub fn is_request(&self) -> bool {
                     unsafe {
                         #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "durable:core/http@2.5.0")]
+                        #[link(wasm_import_module = "durable:core/http@2.7.0")]
                         extern "C" {
                             #[link_name = "[method]http-error2.is-request"]
                             fn wit_import(_: i32) -> i32;
@@ -509,7 +667,7 @@ pub mod durable {
                 pub fn is_connect(&self) -> bool {
                     unsafe {
                         #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "durable:core/http@2.5.0")]
+                        #[link(wasm_import_module = "durable:core/http@2.7.0")]
                         extern "C" {
                             #[link_name = "[method]http-error2.is-connect"]
                             fn wit_import(_: i32) -> i32;
@@ -541,7 +699,7 @@ pub mod durable {
                         let len1 = vec1.len();
                         let ptr2 = ret_area.0.as_mut_ptr().cast::<u8>();
                         #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "durable:core/http@2.5.0")]
+                        #[link(wasm_import_module = "durable:core/http@2.7.0")]
                         extern "C" {
                             #[link_name = "[static]http-request2.new"]
                             fn wit_import(
@@ -599,7 +757,7 @@ pub mod durable {
                         let len0 = vec0.len();
                         let ptr1 = ret_area.0.as_mut_ptr().cast::<u8>();
                         #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "durable:core/http@2.5.0")]
+                        #[link(wasm_import_module = "durable:core/http@2.7.0")]
                         extern "C" {
                             #[link_name = "[method]http-request2.set-method"]
                             fn wit_import(_: i32, _: *mut u8, _: usize, _: *mut u8);
@@ -642,7 +800,7 @@ pub mod durable {
                         let len0 = vec0.len();
                         let ptr1 = ret_area.0.as_mut_ptr().cast::<u8>();
                         #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "durable:core/http@2.5.0")]
+                        #[link(wasm_import_module = "durable:core/http@2.7.0")]
                         extern "C" {
                             #[link_name = "[method]http-request2.set-url"]
                             fn wit_import(_: i32, _: *mut u8, _: usize, _: *mut u8);
@@ -718,7 +876,7 @@ pub mod durable {
                         }
                         let ptr4 = ret_area.0.as_mut_ptr().cast::<u8>();
                         #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "durable:core/http@2.5.0")]
+                        #[link(wasm_import_module = "durable:core/http@2.7.0")]
                         extern "C" {
                             #[link_name = "[method]http-request2.set-headers"]
                             fn wit_import(_: i32, _: *mut u8, _: usize, _: *mut u8);
@@ -755,7 +913,7 @@ pub mod durable {
                 pub fn set_timeout(&self, timeout: u64) {
                     unsafe {
                         #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "durable:core/http@2.5.0")]
+                        #[link(wasm_import_module = "durable:core/http@2.7.0")]
                         extern "C" {
                             #[link_name = "[method]http-request2.set-timeout"]
                             fn wit_import(_: i32, _: i64);
@@ -777,7 +935,7 @@ pub mod durable {
                         let ptr0 = vec0.as_ptr().cast::<u8>();
                         let len0 = vec0.len();
                         #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "durable:core/http@2.5.0")]
+                        #[link(wasm_import_module = "durable:core/http@2.7.0")]
                         extern "C" {
                             #[link_name = "[method]http-request2.set-body"]
                             fn wit_import(_: i32, _: *mut u8, _: usize);
@@ -790,6 +948,63 @@ pub mod durable {
                     }
                 }
             }
+            impl HttpRequest2 {
+                #[allow(unused_unsafe, clippy::all)]
+                /// Set how redirect responses are handled for this request.
+                ///
+                /// Requests without a redirect policy are handled by the worker's
+                /// HTTP client, which follows up to 10 redirects by default.
+                pub fn set_redirect(&self, policy: RedirectPolicy) {
+                    unsafe {
+                        let (result0_0, result0_1) = match policy {
+                            RedirectPolicy::None => (0i32, 0i32),
+                            RedirectPolicy::Limited(e) => (1i32, _rt::as_i32(e)),
+                            RedirectPolicy::Follow => (2i32, 0i32),
+                        };
+                        #[cfg(target_arch = "wasm32")]
+                        #[link(wasm_import_module = "durable:core/http@2.7.0")]
+                        extern "C" {
+                            #[link_name = "[method]http-request2.set-redirect"]
+                            fn wit_import(_: i32, _: i32, _: i32);
+                        }
+                        #[cfg(not(target_arch = "wasm32"))]
+                        fn wit_import(_: i32, _: i32, _: i32) {
+                            unreachable!()
+                        }
+                        wit_import((self).handle() as i32, result0_0, result0_1);
+                    }
+                }
+            }
+            impl HttpRequest2 {
+                #[allow(unused_unsafe, clippy::all)]
+                /// Set whether the response body is automatically decompressed.
+                ///
+                /// This is enabled by default. When enabled, an `accept-encoding`
+                /// header is added to requests that don't already have one and
+                /// response bodies that use the `gzip`, `deflate`, or `br` encodings
+                /// are decompressed before being returned.
+                pub fn set_decompress(&self, enabled: bool) {
+                    unsafe {
+                        #[cfg(target_arch = "wasm32")]
+                        #[link(wasm_import_module = "durable:core/http@2.7.0")]
+                        extern "C" {
+                            #[link_name = "[method]http-request2.set-decompress"]
+                            fn wit_import(_: i32, _: i32);
+                        }
+                        #[cfg(not(target_arch = "wasm32"))]
+                        fn wit_import(_: i32, _: i32) {
+                            unreachable!()
+                        }
+                        wit_import(
+                            (self).handle() as i32,
+                            match &enabled {
+                                true => 1,
+                                false => 0,
+                            },
+                        );
+                    }
+                }
+            }
             #[allow(unused_unsafe, clippy::all)]
             /// Make an HTTP request.
             ///
@@ -808,7 +1023,7 @@ pub mod durable {
                     let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 24]);
                     let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
                     #[cfg(target_arch = "wasm32")]
-                    #[link(wasm_import_module = "durable:core/http@2.5.0")]
+                    #[link(wasm_import_module = "durable:core/http@2.7.0")]
                     extern "C" {
                         #[link_name = "fetch2"]
                         fn wit_import(_: i32, _: *mut u8);
@@ -872,6 +1087,515 @@ pub mod durable {
                     }
                 }
             }
+            #[allow(unused_unsafe, clippy::all)]
+            /// Make an HTTP request.
+            ///
+            /// This is similar to `fetch2` except that it can retry the request and
+            /// reports which URLs the request was redirected to.
+            ///
+            /// If `retry` is set then requests that fail to connect or that get a 429
+            /// or 5xx response are retried with exponential backoff until
+            /// `max-attempts` is reached. A `Retry-After` header on the response is
+            /// used as the delay instead if it is present. If it asks for a longer
+            /// delay than `max-backoff` then the response is returned without any
+            /// further retries.
+            ///
+            /// # Traps
+            /// This function will trap if called from outside of a durable transaction.
+            pub fn fetch3(
+                request: HttpRequest2,
+                retry: Option<RetryPolicy>,
+            ) -> Result<FetchResponse, HttpError2> {
+                unsafe {
+                    #[repr(align(4))]
+                    struct RetArea([::core::mem::MaybeUninit<u8>; 36]);
+                    let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 36]);
+                    let (result1_0, result1_1, result1_2, result1_3) = match retry {
+                        Some(e) => {
+                            let RetryPolicy {
+                                max_attempts: max_attempts0,
+                                initial_backoff: initial_backoff0,
+                                max_backoff: max_backoff0,
+                            } = e;
+                            (
+                                1i32,
+                                _rt::as_i32(max_attempts0),
+                                _rt::as_i64(initial_backoff0),
+                                _rt::as_i64(max_backoff0),
+                            )
+                        }
+                        None => (0i32, 0i32, 0i64, 0i64),
+                    };
+                    let ptr2 = ret_area.0.as_mut_ptr().cast::<u8>();
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(wasm_import_module = "durable:core/http@2.7.0")]
+                    extern "C" {
+                        #[link_name = "fetch3"]
+                        fn wit_import(_: i32, _: i32, _: i32, _: i64, _: i64, _: *mut u8);
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    fn wit_import(_: i32, _: i32, _: i32, _: i64, _: i64, _: *mut u8) {
+                        unreachable!()
+                    }
+                    wit_import(
+                        (&request).take_handle() as i32,
+                        result1_0,
+                        result1_1,
+                        result1_2,
+                        result1_3,
+                        ptr2,
+                    );
+                    let l3 = i32::from(*ptr2.add(0).cast::<u8>());
+                    match l3 {
+                        0 => {
+                            let e = {
+                                let l4 = i32::from(*ptr2.add(4).cast::<u16>());
+                                let l5 = *ptr2.add(8).cast::<*mut u8>();
+                                let l6 = *ptr2.add(12).cast::<usize>();
+                                let base13 = l5;
+                                let len13 = l6;
+                                let mut result13 = _rt::Vec::with_capacity(len13);
+                                for i in 0..len13 {
+                                    let base = base13.add(i * 16);
+                                    let e13 = {
+                                        let l7 = *base.add(0).cast::<*mut u8>();
+                                        let l8 = *base.add(4).cast::<usize>();
+                                        let len9 = l8;
+                                        let bytes9 = _rt::Vec::from_raw_parts(
+                                            l7.cast(),
+                                            len9,
+                                            len9,
+                                        );
+                                        let l10 = *base.add(8).cast::<*mut u8>();
+                                        let l11 = *base.add(12).cast::<usize>();
+                                        let len12 = l11;
+                                        HttpHeaderResult {
+                                            name: _rt::string_lift(bytes9),
+                                            value: _rt::Vec::from_raw_parts(l10.cast(), len12, len12),
+                                        }
+                                    };
+                                    result13.push(e13);
+                                }
+                                _rt::cabi_dealloc(base13, len13 * 16, 4);
+                                let l14 = *ptr2.add(16).cast::<*mut u8>();
+                                let l15 = *ptr2.add(20).cast::<usize>();
+                                let len16 = l15;
+                                let l17 = *ptr2.add(24).cast::<i32>();
+                                let l18 = *ptr2.add(28).cast::<*mut u8>();
+                                let l19 = *ptr2.add(32).cast::<usize>();
+                                let base23 = l18;
+                                let len23 = l19;
+                                let mut result23 = _rt::Vec::with_capacity(len23);
+                                for i in 0..len23 {
+                                    let base = base23.add(i * 8);
+                                    let e23 = {
+                                        let l20 = *base.add(0).cast::<*mut u8>();
+                                        let l21 = *base.add(4).cast::<usize>();
+                                        let len22 = l21;
+                                        let bytes22 = _rt::Vec::from_raw_parts(
+                                            l20.cast(),
+                                            len22,
+                                            len22,
+                                        );
+                                        _rt::string_lift(bytes22)
+                                    };
+                                    result23.push(e23);
+                                }
+                                _rt::cabi_dealloc(base23, len23 * 8, 4);
+                                FetchResponse {
+                                    response: HttpResponse {
+                                        status: l4 as u16,
+                                        headers: result13,
+                                        body: _rt::Vec::from_raw_parts(l14.cast(), len16, len16),
+                                    },
+                                    attempts: l17 as u32,
+                                    redirects: result23,
+                                }
+                            };
+                            Ok(e)
+                        }
+                        1 => {
+                            let e = {
+                                let l24 = *ptr2.add(4).cast::<i32>();
+                                HttpError2::from_handle(l24 as u32)
+                            };
+                            Err(e)
+                        }
+                        _ => _rt::invalid_enum_discriminant(),
+                    }
+                }
+            }
+            impl EventStream {
+                #[allow(unused_unsafe, clippy::all)]
+                /// The status and headers of the response. The body is always empty.
+                pub fn response(&self) -> HttpResponse {
+                    unsafe {
+                        #[repr(align(4))]
+                        struct RetArea([::core::mem::MaybeUninit<u8>; 20]);
+                        let mut ret_area = RetArea(
+                            [::core::mem::MaybeUninit::uninit(); 20],
+                        );
+                        let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
+                        #[cfg(target_arch = "wasm32")]
+                        #[link(wasm_import_module = "durable:core/http@2.7.0")]
+                        extern "C" {
+                            #[link_name = "[method]event-stream.response"]
+                            fn wit_import(_: i32, _: *mut u8);
+                        }
+                        #[cfg(not(target_arch = "wasm32"))]
+                        fn wit_import(_: i32, _: *mut u8) {
+                            unreachable!()
+                        }
+                        wit_import((self).handle() as i32, ptr0);
+                        let l1 = i32::from(*ptr0.add(0).cast::<u16>());
+                        let l2 = *ptr0.add(4).cast::<*mut u8>();
+                        let l3 = *ptr0.add(8).cast::<usize>();
+                        let base10 = l2;
+                        let len10 = l3;
+                        let mut result10 = _rt::Vec::with_capacity(len10);
+                        for i in 0..len10 {
+                            let base = base10.add(i * 16);
+                            let e10 = {
+                                let l4 = *base.add(0).cast::<*mut u8>();
+                                let l5 = *base.add(4).cast::<usize>();
+                                let len6 = l5;
+                                let bytes6 = _rt::Vec::from_raw_parts(
+                                    l4.cast(),
+                                    len6,
+                                    len6,
+                                );
+                                let l7 = *base.add(8).cast::<*mut u8>();
+                                let l8 = *base.add(12).cast::<usize>();
+                                let len9 = l8;
+                                HttpHeaderResult {
+                                    name: _rt::string_lift(bytes6),
+                                    value: _rt::Vec::from_raw_parts(l7.cast(), len9, len9),
+                                }
+                            };
+                            result10.push(e10);
+                        }
+                        _rt::cabi_dealloc(base10, len10 * 16, 4);
+                        let l11 = *ptr0.add(12).cast::<*mut u8>();
+                        let l12 = *ptr0.add(16).cast::<usize>();
+                        let len13 = l12;
+                        HttpResponse {
+                            status: l1 as u16,
+                            headers: result10,
+                            body: _rt::Vec::from_raw_parts(l11.cast(), len13, len13),
+                        }
+                    }
+                }
+            }
+            impl EventStream {
+                #[allow(unused_unsafe, clippy::all)]
+                /// Read the next event from the stream.
+                ///
+                /// Returns `none` once the server closes the stream. The request timeout
+                /// applies to each call individually rather than to the stream as a
+                /// whole.
+                ///
+                /// # Traps
+                /// This function will trap if called from outside of a durable
+                /// transaction.
+                pub fn next(&self) -> Result<Option<SseEvent>, HttpError2> {
+                    unsafe {
+                        #[repr(align(4))]
+                        struct RetArea([::core::mem::MaybeUninit<u8>; 36]);
+                        let mut ret_area = RetArea(
+                            [::core::mem::MaybeUninit::uninit(); 36],
+                        );
+                        let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
+                        #[cfg(target_arch = "wasm32")]
+                        #[link(wasm_import_module = "durable:core/http@2.7.0")]
+                        extern "C" {
+                            #[link_name = "[method]event-stream.next"]
+                            fn wit_import(_: i32, _: *mut u8);
+                        }
+                        #[cfg(not(target_arch = "wasm32"))]
+                        fn wit_import(_: i32, _: *mut u8) {
+                            unreachable!()
+                        }
+                        wit_import((self).handle() as i32, ptr0);
+                        let l1 = i32::from(*ptr0.add(0).cast::<u8>());
+                        match l1 {
+                            0 => {
+                                let e = {
+                                    let l2 = i32::from(*ptr0.add(4).cast::<u8>());
+                                    match l2 {
+                                        0 => None,
+                                        1 => {
+                                            let e = {
+                                                let l3 = i32::from(*ptr0.add(8).cast::<u8>());
+                                                let l7 = *ptr0.add(20).cast::<*mut u8>();
+                                                let l8 = *ptr0.add(24).cast::<usize>();
+                                                let len9 = l8;
+                                                let bytes9 = _rt::Vec::from_raw_parts(
+                                                    l7.cast(),
+                                                    len9,
+                                                    len9,
+                                                );
+                                                let l10 = *ptr0.add(28).cast::<*mut u8>();
+                                                let l11 = *ptr0.add(32).cast::<usize>();
+                                                let len12 = l11;
+                                                let bytes12 = _rt::Vec::from_raw_parts(
+                                                    l10.cast(),
+                                                    len12,
+                                                    len12,
+                                                );
+                                                SseEvent {
+                                                    id: match l3 {
+                                                        0 => None,
+                                                        1 => {
+                                                            let e = {
+                                                                let l4 = *ptr0.add(12).cast::<*mut u8>();
+                                                                let l5 = *ptr0.add(16).cast::<usize>();
+                                                                let len6 = l5;
+                                                                let bytes6 = _rt::Vec::from_raw_parts(
+                                                                    l4.cast(),
+                                                                    len6,
+                                                                    len6,
+                                                                );
+                                                                _rt::string_lift(bytes6)
+                                                            };
+                                                            Some(e)
+                                                        }
+                                                        _ => _rt::invalid_enum_discriminant(),
+                                                    },
+                                                    event: _rt::string_lift(bytes9),
+                                                    data: _rt::string_lift(bytes12),
+                                                }
+                                            };
+                                            Some(e)
+                                        }
+                                        _ => _rt::invalid_enum_discriminant(),
+                                    }
+                                };
+                                Ok(e)
+                            }
+                            1 => {
+                                let e = {
+                                    let l13 = *ptr0.add(4).cast::<i32>();
+                                    HttpError2::from_handle(l13 as u32)
+                                };
+                                Err(e)
+                            }
+                            _ => _rt::invalid_enum_discriminant(),
+                        }
+                    }
+                }
+            }
+            #[allow(unused_unsafe, clippy::all)]
+            /// Send a request and return the response as a stream of server-sent
+            /// events.
+            ///
+            /// This returns as soon as the response headers have been received. Redirects
+            /// are followed by the worker's HTTP client and the response is never
+            /// cached or decompressed.
+            ///
+            /// # Traps
+            /// This function will trap if called from outside of a durable transaction.
+            pub fn open_event_stream(
+                request: HttpRequest2,
+            ) -> Result<EventStream, HttpError2> {
+                unsafe {
+                    #[repr(align(4))]
+                    struct RetArea([::core::mem::MaybeUninit<u8>; 8]);
+                    let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 8]);
+                    let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(wasm_import_module = "durable:core/http@2.7.0")]
+                    extern "C" {
+                        #[link_name = "open-event-stream"]
+                        fn wit_import(_: i32, _: *mut u8);
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    fn wit_import(_: i32, _: *mut u8) {
+                        unreachable!()
+                    }
+                    wit_import((&request).take_handle() as i32, ptr0);
+                    let l1 = i32::from(*ptr0.add(0).cast::<u8>());
+                    match l1 {
+                        0 => {
+                            let e = {
+                                let l2 = *ptr0.add(4).cast::<i32>();
+                                EventStream::from_handle(l2 as u32)
+                            };
+                            Ok(e)
+                        }
+                        1 => {
+                            let e = {
+                                let l3 = *ptr0.add(4).cast::<i32>();
+                                HttpError2::from_handle(l3 as u32)
+                            };
+                            Err(e)
+                        }
+                        _ => _rt::invalid_enum_discriminant(),
+                    }
+                }
+            }
+            #[allow(unused_unsafe, clippy::all)]
+            /// Send multiple HTTP requests concurrently.
+            ///
+            /// Each request is sent exactly as it would be by `fetch3`. This returns
+            /// once all of the requests have completed and the results are in the
+            /// same order as the requests. The worker may limit how many of the
+            /// requests are in flight at once.
+            ///
+            /// # Traps
+            /// This function will trap if called from outside of a durable transaction.
+            pub fn fetch_all(
+                requests: _rt::Vec<FetchAllRequest>,
+            ) -> _rt::Vec<Result<FetchResponse, HttpError2>> {
+                unsafe {
+                    #[repr(align(4))]
+                    struct RetArea([::core::mem::MaybeUninit<u8>; 8]);
+                    let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 8]);
+                    let vec1 = requests;
+                    let len1 = vec1.len();
+                    let layout1 = _rt::alloc::Layout::from_size_align_unchecked(
+                        vec1.len() * 40,
+                        8,
+                    );
+                    let result1 = if layout1.size() != 0 {
+                        let ptr = _rt::alloc::alloc(layout1).cast::<u8>();
+                        if ptr.is_null() {
+                            _rt::alloc::handle_alloc_error(layout1);
+                        }
+                        ptr
+                    } else {
+                        { ::core::ptr::null_mut() }
+                    };
+                    for (i, e) in vec1.into_iter().enumerate() {
+                        let base = result1.add(i * 40);
+                        {
+                            let FetchAllRequest { request: request0, retry: retry0 } = e;
+                            *base.add(0).cast::<i32>() = (&request0).take_handle() as i32;
+                            match retry0 {
+                                Some(e) => {
+                                    *base.add(8).cast::<u8>() = (1i32) as u8;
+                                    let RetryPolicy {
+                                        max_attempts: max_attempts0,
+                                        initial_backoff: initial_backoff0,
+                                        max_backoff: max_backoff0,
+                                    } = e;
+                                    *base.add(16).cast::<i32>() = _rt::as_i32(max_attempts0);
+                                    *base.add(24).cast::<i64>() = _rt::as_i64(initial_backoff0);
+                                    *base.add(32).cast::<i64>() = _rt::as_i64(max_backoff0);
+                                }
+                                None => {
+                                    *base.add(8).cast::<u8>() = (0i32) as u8;
+                                }
+                            };
+                        }
+                    }
+                    let ptr2 = ret_area.0.as_mut_ptr().cast::<u8>();
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(wasm_import_module = "durable:core/http@2.7.0")]
+                    extern "C" {
+                        #[link_name = "fetch-all"]
+                        fn wit_import(_: *mut u8, _: usize, _: *mut u8);
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    fn wit_import(_: *mut u8, _: usize, _: *mut u8) {
+                        unreachable!()
+                    }
+                    wit_import(result1, len1, ptr2);
+                    let l0 = *ptr2.add(0).cast::<*mut u8>();
+                    let l1 = *ptr2.add(4).cast::<usize>();
+                    let base25 = l0;
+                    let len25 = l1;
+                    let mut result25 = _rt::Vec::with_capacity(len25);
+                    for i in 0..len25 {
+                        let base = base25.add(i * 36);
+                        let e25 = {
+                            let l3 = i32::from(*base.add(0).cast::<u8>());
+                            match l3 {
+                                0 => {
+                                    let e = {
+                                        let l4 = i32::from(*base.add(4).cast::<u16>());
+                                        let l5 = *base.add(8).cast::<*mut u8>();
+                                        let l6 = *base.add(12).cast::<usize>();
+                                        let base13 = l5;
+                                        let len13 = l6;
+                                        let mut result13 = _rt::Vec::with_capacity(len13);
+                                        for i in 0..len13 {
+                                            let base = base13.add(i * 16);
+                                            let e13 = {
+                                                let l7 = *base.add(0).cast::<*mut u8>();
+                                                let l8 = *base.add(4).cast::<usize>();
+                                                let len9 = l8;
+                                                let bytes9 = _rt::Vec::from_raw_parts(
+                                                    l7.cast(),
+                                                    len9,
+                                                    len9,
+                                                );
+                                                let l10 = *base.add(8).cast::<*mut u8>();
+                                                let l11 = *base.add(12).cast::<usize>();
+                                                let len12 = l11;
+                                                HttpHeaderResult {
+                                                    name: _rt::string_lift(bytes9),
+                                                    value: _rt::Vec::from_raw_parts(l10.cast(), len12, len12),
+                                                }
+                                            };
+                                            result13.push(e13);
+                                        }
+                                        _rt::cabi_dealloc(base13, len13 * 16, 4);
+                                        let l14 = *base.add(16).cast::<*mut u8>();
+                                        let l15 = *base.add(20).cast::<usize>();
+                                        let len16 = l15;
+                                        let l17 = *base.add(24).cast::<i32>();
+                                        let l18 = *base.add(28).cast::<*mut u8>();
+                                        let l19 = *base.add(32).cast::<usize>();
+                                        let base23 = l18;
+                                        let len23 = l19;
+                                        let mut result23 = _rt::Vec::with_capacity(len23);
+                                        for i in 0..len23 {
+                                            let base = base23.add(i * 8);
+                                            let e23 = {
+                                                let l20 = *base.add(0).cast::<*mut u8>();
+                                                let l21 = *base.add(4).cast::<usize>();
+                                                let len22 = l21;
+                                                let bytes22 = _rt::Vec::from_raw_parts(
+                                                    l20.cast(),
+                                                    len22,
+                                                    len22,
+                                                );
+                                                _rt::string_lift(bytes22)
+                                            };
+                                            result23.push(e23);
+                                        }
+                                        _rt::cabi_dealloc(base23, len23 * 8, 4);
+                                        FetchResponse {
+                                            response: HttpResponse {
+                                                status: l4 as u16,
+                                                headers: result13,
+                                                body: _rt::Vec::from_raw_parts(l14.cast(), len16, len16),
+                                            },
+                                            attempts: l17 as u32,
+                                            redirects: result23,
+                                        }
+                                    };
+                                    Ok(e)
+                                }
+                                1 => {
+                                    let e = {
+                                        let l24 = *base.add(4).cast::<i32>();
+                                        HttpError2::from_handle(l24 as u32)
+                                    };
+                                    Err(e)
+                                }
+                                _ => _rt::invalid_enum_discriminant(),
+                            }
+                        };
+                        result25.push(e25);
+                    }
+                    _rt::cabi_dealloc(base25, len25 * 36, 4);
+                    if layout1.size() != 0 {
+                        _rt::alloc::dealloc(result1.cast(), layout1);
+                    }
+                    result25
+                }
+            }
         }
     }
 }
@@ -953,6 +1677,65 @@ mod _rt {
         }
     }
     pub use alloc_crate::alloc;
+    pub fn as_i32<T: AsI32>(t: T) -> i32 {
+        t.as_i32()
+    }
+    pub trait AsI32 {
+        fn as_i32(self) -> i32;
+    }
+    impl<'a, T: Copy + AsI32> AsI32 for &'a T {
+        fn as_i32(self) -> i32 {
+            (*self).as_i32()
+        }
+    }
+    impl AsI32 for i32 {
+        #[inline]
+        fn as_i32(self) -> i32 {
+            self as i32
+        }
+    }
+    impl AsI32 for u32 {
+        #[inline]
+        fn as_i32(self) -> i32 {
+            self as i32
+        }
+    }
+    impl AsI32 for i16 {
+        #[inline]
+        fn as_i32(self) -> i32 {
+            self as i32
+        }
+    }
+    impl AsI32 for u16 {
+        #[inline]
+        fn as_i32(self) -> i32 {
+            self as i32
+        }
+    }
+    impl AsI32 for i8 {
+        #[inline]
+        fn as_i32(self) -> i32 {
+            self as i32
+        }
+    }
+    impl AsI32 for u8 {
+        #[inline]
+        fn as_i32(self) -> i32 {
+            self as i32
+        }
+    }
+    impl AsI32 for char {
+        #[inline]
+        fn as_i32(self) -> i32 {
+            self as i32
+        }
+    }
+    impl AsI32 for usize {
+        #[inline]
+        fn as_i32(self) -> i32 {
+            self as i32
+        }
+    }
     pub fn as_i64<T: AsI64>(t: T) -> i64 {
         t.as_i64()
     }
@@ -1013,30 +1796,43 @@ mod _rt {
 #[cfg(target_arch = "wasm32")]
 #[link_section = "component-type:wit-bindgen:0.30.0:import-http:encoded world"]
 #[doc(hidden)]
-pub static __WIT_BINDGEN_COMPONENT_TYPE: [u8; 1099] = *b"\
-\0asm\x0d\0\x01\0\0\x19\x16wit-component-encoding\x04\0\x07\xc9\x07\x01A\x02\x01\
-A\x02\x01B-\x01p}\x01r\x02\x04names\x05value\0\x04\0\x0bhttp-header\x03\0\x01\x01\
-p\x02\x01k\0\x01kw\x01r\x05\x06methods\x03urls\x07headers\x03\x04body\x04\x07tim\
-eout\x05\x04\0\x0chttp-request\x03\0\x06\x01r\x03\x06status{\x07headers\x03\x04b\
-ody\0\x04\0\x0dhttp-response\x03\0\x08\x01q\x06\x07timeout\0\0\x0einvalid-method\
-\0\0\x0binvalid-url\x01s\0\x13invalid-header-name\0\0\x14invalid-header-value\0\0\
-\x05other\x01s\0\x04\0\x0ahttp-error\x03\0\x0a\x04\0\x0bhttp-error2\x03\x01\x04\0\
-\x0dhttp-request2\x03\x01\x01h\x0c\x01@\x01\x04self\x0e\0s\x04\0\x1b[method]http\
--error2.message\x01\x0f\x01@\x01\x04self\x0e\0\x7f\x04\0\x1e[method]http-error2.\
-is-timeout\x01\x10\x04\0\x1e[method]http-error2.is-builder\x01\x10\x04\0\x1e[met\
-hod]http-error2.is-request\x01\x10\x04\0\x1e[method]http-error2.is-connect\x01\x10\
-\x01i\x0d\x01i\x0c\x01j\x01\x11\x01\x12\x01@\x02\x06methods\x03urls\0\x13\x04\0\x19\
-[static]http-request2.new\x01\x14\x01h\x0d\x01j\0\x01\x12\x01@\x02\x04self\x15\x06\
-methods\0\x16\x04\0\x20[method]http-request2.set-method\x01\x17\x01@\x02\x04self\
-\x15\x03urls\0\x16\x04\0\x1d[method]http-request2.set-url\x01\x18\x01@\x02\x04se\
-lf\x15\x07headers\x03\0\x16\x04\0![method]http-request2.set-headers\x01\x19\x01@\
-\x02\x04self\x15\x07timeoutw\x01\0\x04\0![method]http-request2.set-timeout\x01\x1a\
-\x01@\x02\x04self\x15\x04body\0\x01\0\x04\0\x1e[method]http-request2.set-body\x01\
-\x1b\x01j\x01\x09\x01\x0b\x01@\x01\x07request\x07\0\x1c\x04\0\x05fetch\x01\x1d\x01\
-j\x01\x09\x01\x12\x01@\x01\x07request\x11\0\x1e\x04\0\x06fetch2\x01\x1f\x03\x01\x17\
-durable:core/http@2.5.0\x05\0\x04\x01\x1edurable:core/import-http@2.5.0\x04\0\x0b\
-\x11\x01\0\x0bimport-http\x03\0\0\0G\x09producers\x01\x0cprocessed-by\x02\x0dwit\
--component\x070.215.0\x10wit-bindgen-rust\x060.30.0";
+pub static __WIT_BINDGEN_COMPONENT_TYPE: [u8; 1707] = *b"\
+\0asm\x0d\0\x01\0\0\x19\x16wit-component-encoding\x04\0\x07\xa9\x0c\x01A\x02\x01\
+A\x02\x01BQ\x01p}\x01r\x02\x04names\x05value\0\x04\0\x0bhttp-header\x03\0\x01\
+\x01p\x02\x01k\0\x01kw\x01r\x05\x06methods\x03urls\x07headers\x03\x04body\x04\
+\x07timeout\x05\x04\0\x0chttp-request\x03\0\x06\x01r\x03\x06status{\x07headers\
+\x03\x04body\0\x04\0\x0dhttp-response\x03\0\x08\x01q\x06\x07timeout\0\0\x0einval\
+id-method\0\0\x0binvalid-url\x01s\0\x13invalid-header-name\0\0\x14invalid-header\
+-value\0\0\x05other\x01s\0\x04\0\x0ahttp-error\x03\0\x0a\x04\0\x0bhttp-error2\
+\x03\x01\x01q\x03\x04none\0\0\x07limited\x01y\0\x06follow\0\0\x04\0\x0fredirect-\
+policy\x03\0\x0d\x04\0\x0dhttp-request2\x03\x01\x01r\x03\x0cmax-attemptsy\x0fini\
+tial-backoffw\x0bmax-backoffw\x04\0\x0cretry-policy\x03\0\x10\x01ps\x01r\x03\x08\
+response\x09\x08attemptsy\x09redirects\x12\x04\0\x0efetch-response\x03\0\x13\x01\
+ks\x01r\x03\x02id\x15\x05events\x04datas\x04\0\x09sse-event\x03\0\x16\x04\0\x0ce\
+vent-stream\x03\x01\x01i\x0f\x01k\x11\x01r\x02\x07request\x19\x05retry\x1a\x04\0\
+\x11fetch-all-request\x03\0\x1b\x01h\x0c\x01@\x01\x04self\x1d\0s\x04\0\x1b[metho\
+d]http-error2.message\x01\x1e\x01@\x01\x04self\x1d\0\x7f\x04\0\x1e[method]http-e\
+rror2.is-timeout\x01\x1f\x04\0\x1e[method]http-error2.is-builder\x01\x1f\x04\0\
+\x1e[method]http-error2.is-request\x01\x1f\x04\0\x1e[method]http-error2.is-conne\
+ct\x01\x1f\x01i\x0c\x01j\x01\x19\x01 \x01@\x02\x06methods\x03urls\0!\x04\0\x19[s\
+tatic]http-request2.new\x01\"\x01h\x0f\x01j\0\x01 \x01@\x02\x04self#\x06methods\
+\0$\x04\0 [method]http-request2.set-method\x01%\x01@\x02\x04self#\x03urls\0$\x04\
+\0\x1d[method]http-request2.set-url\x01&\x01@\x02\x04self#\x07headers\x03\0$\x04\
+\0![method]http-request2.set-headers\x01'\x01@\x02\x04self#\x07timeoutw\x01\0\
+\x04\0![method]http-request2.set-timeout\x01(\x01@\x02\x04self#\x04body\0\x01\0\
+\x04\0\x1e[method]http-request2.set-body\x01)\x01@\x02\x04self#\x06policy\x0e\
+\x01\0\x04\0\"[method]http-request2.set-redirect\x01*\x01@\x02\x04self#\x07enabl\
+ed\x7f\x01\0\x04\0$[method]http-request2.set-decompress\x01+\x01h\x18\x01@\x01\
+\x04self,\0\x09\x04\0\x1d[method]event-stream.response\x01-\x01k\x17\x01j\x01.\
+\x01 \x01@\x01\x04self,\0/\x04\0\x19[method]event-stream.next\x010\x01j\x01\x09\
+\x01\x0b\x01@\x01\x07request\x07\01\x04\0\x05fetch\x012\x01j\x01\x09\x01 \x01@\
+\x01\x07request\x19\03\x04\0\x06fetch2\x014\x01j\x01\x14\x01 \x01@\x02\x07reques\
+t\x19\x05retry\x1a\05\x04\0\x06fetch3\x016\x01i\x18\x01j\x017\x01 \x01@\x01\x07r\
+equest\x19\08\x04\0\x11open-event-stream\x019\x01p\x1c\x01p5\x01@\x01\x08request\
+s:\0;\x04\0\x09fetch-all\x01<\x03\x01\x17durable:core/http@2.7.0\x05\0\x04\x01\
+\x1edurable:core/import-http@2.7.0\x04\0\x0b\x11\x01\0\x0bimport-http\x03\0\0\0G\
+\x09producers\x01\x0cprocessed-by\x02\x0dwit-component\x070.215.0\x10wit-bindgen\
+-rust\x060.30.0";
 #[inline(never)]
 #[doc(hidden)]
 pub fn __link_custom_section_describing_imports() {
@@ -0,0 +1,320 @@
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use chrono::{DateTime, TimeDelta, Utc};
+use http::header::{DATE, SET_COOKIE};
+use http::{HeaderMap, HeaderValue};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use url::Url;
+
+/// A store of cookies that can be shared between requests.
+///
+/// Attach a jar to a request with [`RequestBuilder::cookie_jar`]. Cookies in
+/// the jar that match the request URL are sent along with the request, and
+/// any `Set-Cookie` headers in the response are stored back into the jar.
+///
+/// The jar is updated using the response that was recorded in the workflow's
+/// transaction log, so it ends up in the same state when a workflow is
+/// replayed. It can also be serialized, in order to hand it off to another
+/// task.
+///
+/// Cloning a `CookieJar` creates another handle to the same set of cookies.
+///
+/// [`RequestBuilder::cookie_jar`]: crate::RequestBuilder::cookie_jar
+#[derive(Clone, Debug, Default)]
+pub struct CookieJar {
+    cookies: Arc<Mutex<Vec<Cookie>>>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct Cookie {
+    name: String,
+    value: String,
+    domain: String,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    host_only: bool,
+    path: String,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    secure: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    expires: Option<DateTime<Utc>>,
+}
+
+impl CookieJar {
+    /// Create a new, empty, cookie jar.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Vec<Cookie>> {
+        self.cookies.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Add a cookie to the jar, as if it was received in a `Set-Cookie`
+    /// header in a response from `url`.
+    ///
+    /// Invalid cookies are ignored.
+    pub fn add_cookie_str(&self, cookie: &str, url: &Url) {
+        self.store(cookie, url, Some(Utc::now()));
+    }
+
+    /// Get the value of the `Cookie` header that would be sent with a request
+    /// to `url`, if there are any matching cookies.
+    pub fn cookies(&self, url: &Url) -> Option<HeaderValue> {
+        let host = url.host_str()?.to_ascii_lowercase();
+        let secure = url.scheme() == "https";
+        let now = Utc::now();
+
+        let header = self
+            .lock()
+            .iter()
+            .filter(|cookie| !cookie.is_expired(now))
+            .filter(|cookie| cookie.matches(&host, url.path(), secure))
+            .map(|cookie| format!("{}={}", cookie.name, cookie.value))
+            .collect::<Vec<_>>()
+            .join("; ");
+
+        if header.is_empty() {
+            return None;
+        }
+
+        HeaderValue::from_str(&header).ok()
+    }
+
+    /// Remove all cookies from the jar.
+    pub fn clear(&self) {
+        self.lock().clear();
+    }
+
+    /// Store all the cookies set by a response from `url`.
+    ///
+    /// `Max-Age` is relative to the `Date` header of the response so that the
+    /// resulting expiry times are the same when a workflow is replayed.
+    /// Responses without a `Date` header set session cookies instead.
+    pub(crate) fn store_response(&self, url: &Url, headers: &HeaderMap) {
+        let date = headers
+            .get(DATE)
+            .and_then(|value| value.to_str().ok())
+            .and_then(parse_date);
+
+        for value in headers.get_all(SET_COOKIE) {
+            if let Ok(value) = value.to_str() {
+                self.store(value, url, date);
+            }
+        }
+    }
+
+    fn store(&self, header: &str, url: &Url, date: Option<DateTime<Utc>>) {
+        let Some(cookie) = Cookie::parse(header, url, date) else {
+            return;
+        };
+
+        let mut cookies = self.lock();
+        cookies.retain(|existing| {
+            existing.name != cookie.name
+                || existing.domain != cookie.domain
+                || existing.path != cookie.path
+        });
+
+        // A cookie that has already expired is how servers delete cookies.
+        if !cookie.is_expired(date.unwrap_or(DateTime::UNIX_EPOCH)) {
+            cookies.push(cookie);
+        }
+    }
+}
+
+impl Cookie {
+    fn parse(header: &str, url: &Url, date: Option<DateTime<Utc>>) -> Option<Self> {
+        let host = url.host_str()?.to_ascii_lowercase();
+        let mut parts = header.split(';');
+        let (name, value) = parts.next()?.split_once('=')?;
+        let name = name.trim();
+        if name.is_empty() {
+            return None;
+        }
+
+        let mut cookie = Cookie {
+            name: name.to_owned(),
+            value: value.trim().to_owned(),
+            domain: host.clone(),
+            host_only: true,
+            path: default_path(url),
+            secure: false,
+            expires: None,
+        };
+        let mut max_age = None;
+
+        for attr in parts {
+            let (key, value) = match attr.split_once('=') {
+                Some((key, value)) => (key.trim(), value.trim()),
+                None => (attr.trim(), ""),
+            };
+
+            match key.to_ascii_lowercase().as_str() {
+                "domain" => {
+                    let domain = value.trim_start_matches('.').to_ascii_lowercase();
+                    if domain.is_empty() {
+                        continue;
+                    }
+
+                    // Servers may only set cookies for their own domain.
+                    if !domain_matches(&host, &domain) {
+                        return None;
+                    }
+
+                    cookie.domain = domain;
+                    cookie.host_only = false;
+                }
+                "path" if value.starts_with('/') => cookie.path = value.to_owned(),
+                "secure" => cookie.secure = true,
+                "max-age" => max_age = value.parse::<i64>().ok(),
+                "expires" => {
+                    if let Some(expires) = parse_date(value) {
+                        cookie.expires = Some(expires);
+                    }
+                }
+                _ => (),
+            }
+        }
+
+        // Max-Age takes precedence over Expires.
+        match (max_age, date) {
+            (Some(..=0), _) => cookie.expires = Some(DateTime::UNIX_EPOCH),
+            (Some(secs), Some(date)) => {
+                cookie.expires = TimeDelta::try_seconds(secs)
+                    .and_then(|delta| date.checked_add_signed(delta))
+                    .or(Some(DateTime::<Utc>::MAX_UTC));
+            }
+            (Some(_), None) => cookie.expires = None,
+            (None, _) => (),
+        }
+
+        Some(cookie)
+    }
+
+    fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.expires.is_some_and(|expires| expires <= now)
+    }
+
+    fn matches(&self, host: &str, path: &str, secure: bool) -> bool {
+        if self.secure && !secure {
+            return false;
+        }
+
+        let domain = if self.host_only {
+            host == self.domain
+        } else {
+            domain_matches(host, &self.domain)
+        };
+
+        domain && path_matches(path, &self.path)
+    }
+}
+
+fn domain_matches(host: &str, domain: &str) -> bool {
+    match host.strip_suffix(domain) {
+        Some("") => true,
+        Some(prefix) => prefix.ends_with('.'),
+        None => false,
+    }
+}
+
+fn path_matches(path: &str, cookie_path: &str) -> bool {
+    match path.strip_prefix(cookie_path) {
+        Some(rest) => cookie_path.ends_with('/') || rest.is_empty() || rest.starts_with('/'),
+        None => false,
+    }
+}
+
+/// The default cookie path is the directory of the request path.
+fn default_path(url: &Url) -> String {
+    match url.path().rfind('/') {
+        Some(0) | None => "/".to_owned(),
+        Some(index) => url.path()[..index].to_owned(),
+    }
+}
+
+fn parse_date(value: &str) -> Option<DateTime<Utc>> {
+    let date = DateTime::parse_from_rfc2822(value)
+        // Some servers still use the RFC 850 style `Wed, 21-Oct-2015` format.
+        .or_else(|_| DateTime::parse_from_rfc2822(&value.replace('-', " ")))
+        .ok()?;
+
+    Some(date.to_utc())
+}
+
+impl Serialize for CookieJar {
+    fn serialize<S>(&self, ser: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.lock().serialize(ser)
+    }
+}
+
+impl<'de> Deserialize<'de> for CookieJar {
+    fn deserialize<D>(de: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Ok(Self {
+            cookies: Arc::new(Mutex::new(Vec::deserialize(de)?)),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn url(url: &str) -> Url {
+        Url::parse(url).unwrap()
+    }
+
+    #[test]
+    fn matches_domain_and_path() {
+        let jar = CookieJar::new();
+        let origin = url("https://api.example.com/v1/login");
+
+        jar.add_cookie_str("session=abc; Path=/v1", &origin);
+        jar.add_cookie_str("shared=1; Domain=example.com", &origin);
+        jar.add_cookie_str("secret=2; Secure", &origin);
+        jar.add_cookie_str("other=3; Domain=example.org", &origin);
+
+        let cookies = jar
+            .cookies(&url("https://api.example.com/v1/users"))
+            .unwrap();
+        assert_eq!(cookies, "session=abc; shared=1; secret=2");
+
+        let cookies = jar.cookies(&url("http://www.example.com/v1")).unwrap();
+        assert_eq!(cookies, "shared=1");
+
+        assert!(jar.cookies(&url("https://example.net/")).is_none());
+    }
+
+    #[test]
+    fn replaces_and_deletes_cookies() {
+        let jar = CookieJar::new();
+        let origin = url("https://example.com/");
+
+        jar.add_cookie_str("a=1", &origin);
+        jar.add_cookie_str("a=2", &origin);
+        assert_eq!(jar.cookies(&origin).unwrap(), "a=2");
+
+        jar.add_cookie_str("a=; Max-Age=0", &origin);
+        assert!(jar.cookies(&origin).is_none());
+
+        jar.add_cookie_str("b=1; Expires=Wed, 21-Oct-2015 07:28:00 GMT", &origin);
+        assert!(jar.cookies(&origin).is_none());
+    }
+
+    #[test]
+    fn serialization_roundtrip() {
+        let jar = CookieJar::new();
+        let origin = url("https://example.com/");
+        jar.add_cookie_str("a=1; Max-Age=3600", &origin);
+
+        let json = serde_json::to_string(&jar).unwrap();
+        let copy: CookieJar = serde_json::from_str(&json).unwrap();
+        assert_eq!(copy.cookies(&origin).unwrap(), "a=1");
+    }
+}
@@ -12,14 +12,25 @@
 //! ```
 
 use core::fmt;
+use std::borrow::Cow;
 use std::str::{FromStr, Utf8Error};
 use std::string::FromUtf8Error;
 use std::time::Duration;
 
+use base64::prelude::BASE64_STANDARD;
+use base64::Engine;
 use durable_core::transaction;
-use http::header::{InvalidHeaderName, InvalidHeaderValue};
+use http::header::{InvalidHeaderName, InvalidHeaderValue, AUTHORIZATION};
+use percent_encoding::percent_decode_str;
 use serde::{Deserialize, Serialize};
 
+#[cfg(all(feature = "mock", not(target_family = "wasm")))]
+pub mod mock;
+
+#[cfg(all(feature = "mock", not(target_family = "wasm")))]
+use crate::mock::bindings;
+
+#[cfg(not(all(feature = "mock", not(target_family = "wasm"))))]
 mod bindings {
     #![allow(unused_braces, clippy::all)]
 
@@ -28,6 +39,12 @@ mod bindings {
     pub use self::durable::core::http::*;
 }
 
+mod cookie;
+pub mod redirect;
+mod sse;
+
+pub use crate::cookie::CookieJar;
+pub use crate::sse::{Event, EventStream};
 #[doc(inline)]
 pub use http::{HeaderMap, HeaderName, HeaderValue, Method, StatusCode};
 #[doc(inline)]
@@ -40,57 +57,196 @@ fn send(request: &Request) -> Result<Response, Error> {
 
     let label = format!("durable::http::send({} {})", request.method, request.url);
     let result = crate::transaction::maybe_txn(&label, || {
-        let original = &request;
+        let req = http_request(request, &request_headers(request))?;
 
-        let headers: Vec<_> = request
-            .headers
-            .iter()
-            .map(|(name, value)| HttpHeaderParam {
-                name: name.as_str(),
-                value: value.as_bytes(),
-            })
-            .collect();
+        match fetch3(req, request.retry.map(From::from)) {
+            Ok(fetched) => fetch_response(request, fetched),
+            Err(err) => Err(Error::from(err)),
+        }
+    });
 
-        let req = HttpRequest2::new(request.method.as_str(), request.url.as_str())?;
-        req.set_headers(&headers)?;
+    finish_response(request, result)
+}
 
-        if let Some(body) = &request.body {
-            req.set_body(body);
-        }
+/// Send multiple requests concurrently.
+///
+/// The requests are all sent at once by the runtime within a single
+/// transaction, so the whole batch takes about as long as the slowest request
+/// instead of the sum of all of them. The results are returned in the same
+/// order as `requests`.
+///
+/// Each request is otherwise handled exactly as it would be by
+/// [`Request::send`], including retries and redirects. A request failing does
+/// not affect any of the others.
+///
+/// ```no_run
+/// let requests: Vec<_> = (1..=10)
+///     .map(|id| durable::http::get(format!("https://example.com/items/{id}")).build())
+///     .collect::<Result<_, _>>()?;
+///
+/// for response in durable::http::send_all(&requests) {
+///     println!("{}", response?.status());
+/// }
+/// # Ok::<(), durable::http::Error>(())
+/// ```
+pub fn send_all(requests: &[Request]) -> Vec<Result<Response, Error>> {
+    use crate::bindings::*;
 
-        if let Some(timeout) = request.timeout {
-            let timeout = timeout.as_nanos().try_into().unwrap_or(u64::MAX);
-            req.set_timeout(timeout);
-        }
+    if requests.is_empty() {
+        return Vec::new();
+    }
 
-        match fetch2(req) {
-            Ok(response) => {
-                let status = StatusCode::from_u16(response.status)
-                    .map_err(|_| ErrorKind::InvalidStatus(response.status))?;
-                let mut headers = HeaderMap::with_capacity(response.headers.len());
-                for header in response.headers {
-                    let name = HeaderName::from_str(&header.name)
-                        .map_err(|_| ErrorKind::InvalidHeaderName)?;
-                    let value = HeaderValue::from_bytes(&header.value)
-                        .map_err(|_| ErrorKind::InvalidHeaderValue)?;
-
-                    headers.append(name, value);
-                }
+    let label = format!("durable::http::send_all({} requests)", requests.len());
+    let results = crate::transaction::maybe_txn(&label, || {
+        let mut results: Vec<Option<Result<Response, Error>>> = Vec::with_capacity(requests.len());
+        let mut batch = Vec::with_capacity(requests.len());
 
-                Ok(Response {
-                    status,
-                    headers,
-                    body: response.body,
-                    url: original.url.clone(),
-                })
+        for request in requests {
+            match http_request(request, &request_headers(request)) {
+                Ok(req) => {
+                    results.push(None);
+                    batch.push(FetchAllRequest {
+                        request: req,
+                        retry: request.retry.map(From::from),
+                    });
+                }
+                Err(e) => results.push(Some(Err(e))),
             }
-            Err(err) => Err(Error::from(err)),
         }
+
+        let mut fetched = fetch_all(batch).into_iter();
+        requests
+            .iter()
+            .zip(results)
+            .map(|(request, result)| match result {
+                Some(result) => result,
+                None => match fetched.next().expect("runtime returned too few responses") {
+                    Ok(fetched) => fetch_response(request, fetched),
+                    Err(err) => Err(Error::from(err)),
+                },
+            })
+            .collect::<Vec<_>>()
     });
 
+    requests
+        .iter()
+        .zip(results)
+        .map(|(request, result)| finish_response(request, result))
+        .collect()
+}
+
+/// Convert a response from the runtime into a [`Response`].
+fn fetch_response(request: &Request, fetched: bindings::FetchResponse) -> Result<Response> {
+    let bindings::FetchResponse {
+        response,
+        attempts,
+        redirects,
+    } = fetched;
+
+    let status = response_status(response.status)?;
+    let headers = response_headers(response.headers)?;
+
+    let redirects = redirects
+        .iter()
+        .map(|url| Url::parse(url).map_err(|e| ErrorKind::InvalidUri(e.to_string())))
+        .collect::<Result<_, _>>()?;
+
+    Ok(Response {
+        status,
+        headers,
+        body: response.body,
+        url: request.url.clone(),
+        redirects,
+        attempts,
+    })
+}
+
+/// Store any cookies from the response and attach the request URL to errors.
+fn finish_response(request: &Request, result: Result<Response>) -> Result<Response> {
+    if let (Ok(response), Some(jar)) = (&result, &request.cookie_jar) {
+        jar.store_response(response.final_url(), &response.headers);
+    }
+
     result.map_err(|e| e.with_url(request.url.clone()))
 }
 
+/// Create the runtime's representation of a request.
+fn http_request(request: &Request, headers: &HeaderMap) -> Result<bindings::HttpRequest2> {
+    use crate::bindings::*;
+
+    let headers: Vec<_> = headers
+        .iter()
+        .map(|(name, value)| HttpHeaderParam {
+            name: name.as_str(),
+            value: value.as_bytes(),
+        })
+        .collect();
+
+    let req = HttpRequest2::new(request.method.as_str(), request.url.as_str())?;
+    req.set_headers(&headers)?;
+
+    if let Some(body) = &request.body {
+        req.set_body(body);
+    }
+
+    if let Some(timeout) = request.timeout {
+        let timeout = timeout.as_nanos().try_into().unwrap_or(u64::MAX);
+        req.set_timeout(timeout);
+    }
+
+    if let Some(policy) = request.redirect {
+        req.set_redirect(policy.into());
+    }
+
+    if !request.decompress {
+        req.set_decompress(false);
+    }
+
+    Ok(req)
+}
+
+fn response_status(status: u16) -> Result<StatusCode> {
+    StatusCode::from_u16(status).map_err(|_| ErrorKind::InvalidStatus(status).into())
+}
+
+fn response_headers(headers: Vec<bindings::HttpHeaderResult>) -> Result<HeaderMap> {
+    let mut map = HeaderMap::with_capacity(headers.len());
+    for header in headers {
+        let name = HeaderName::from_str(&header.name).map_err(|_| ErrorKind::InvalidHeaderName)?;
+        let value =
+            HeaderValue::from_bytes(&header.value).map_err(|_| ErrorKind::InvalidHeaderValue)?;
+
+        map.append(name, value);
+    }
+
+    Ok(map)
+}
+
+/// Get the headers to send with a request, including the cookies from its
+/// cookie jar.
+fn request_headers(request: &Request) -> Cow<'_, HeaderMap> {
+    const COOKIE: HeaderName = HeaderName::from_static("cookie");
+
+    let jar = request.cookie_jar.as_ref();
+    let Some(cookies) = jar.and_then(|jar| jar.cookies(&request.url)) else {
+        return Cow::Borrowed(&request.headers);
+    };
+
+    let mut headers = request.headers.clone();
+    let value = match headers.get(COOKIE) {
+        Some(existing) => {
+            let mut value = existing.as_bytes().to_vec();
+            value.extend_from_slice(b"; ");
+            value.extend_from_slice(cookies.as_bytes());
+            HeaderValue::from_bytes(&value).unwrap_or(cookies)
+        }
+        None => cookies,
+    };
+
+    headers.insert(COOKIE, value);
+    Cow::Owned(headers)
+}
+
 /// Create a [`RequestBuilder`] for a `GET` request.
 pub fn get(url: impl AsRef<str>) -> RequestBuilder {
     RequestBuilder::get(url.as_ref())
@@ -138,17 +294,37 @@ pub struct Request {
     body: Option<Vec<u8>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     timeout: Option<Duration>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    retry: Option<RetryPolicy>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    redirect: Option<redirect::Policy>,
+    #[serde(default = "default_decompress", skip_serializing_if = "is_true")]
+    decompress: bool,
+    #[serde(skip)]
+    cookie_jar: Option<CookieJar>,
 }
 
 impl Request {
     /// Construct a new request.
-    pub fn new(method: Method, url: Url) -> Self {
+    ///
+    /// If `url` contains a username or password then they are removed from the
+    /// URL and sent in an `Authorization` header instead.
+    pub fn new(method: Method, mut url: Url) -> Self {
+        let mut headers = HeaderMap::new();
+        if let Some(auth) = take_userinfo(&mut url) {
+            headers.insert(AUTHORIZATION, auth);
+        }
+
         Self {
             method,
             url,
-            headers: HeaderMap::new(),
+            headers,
             body: None,
             timeout: None,
+            retry: None,
+            redirect: None,
+            decompress: true,
+            cookie_jar: None,
         }
     }
 
@@ -194,9 +370,150 @@ impl Request {
         &mut self.timeout
     }
 
+    pub fn retry(&self) -> Option<RetryPolicy> {
+        self.retry
+    }
+
+    pub fn retry_mut(&mut self) -> &mut Option<RetryPolicy> {
+        &mut self.retry
+    }
+
+    pub fn redirect(&self) -> Option<redirect::Policy> {
+        self.redirect
+    }
+
+    pub fn redirect_mut(&mut self) -> &mut Option<redirect::Policy> {
+        &mut self.redirect
+    }
+
+    pub fn decompress(&self) -> bool {
+        self.decompress
+    }
+
+    pub fn decompress_mut(&mut self) -> &mut bool {
+        &mut self.decompress
+    }
+
+    pub fn cookie_jar(&self) -> Option<&CookieJar> {
+        self.cookie_jar.as_ref()
+    }
+
+    pub fn cookie_jar_mut(&mut self) -> &mut Option<CookieJar> {
+        &mut self.cookie_jar
+    }
+
     pub fn send(&self) -> Result<Response, Error> {
         send(self)
     }
+
+    /// Send this request and read the response as a stream of server-sent
+    /// events.
+    ///
+    /// See [`EventStream`] for details.
+    pub fn send_sse(&self) -> Result<EventStream, Error> {
+        crate::sse::send(self)
+    }
+}
+
+fn default_decompress() -> bool {
+    true
+}
+
+fn is_true(value: &bool) -> bool {
+    *value
+}
+
+/// Remove the username and password from a URL and convert them into a basic
+/// authorization header.
+fn take_userinfo(url: &mut Url) -> Option<HeaderValue> {
+    if url.username().is_empty() && url.password().is_none() {
+        return None;
+    }
+
+    let decode = |value: &str| percent_decode_str(value).decode_utf8_lossy().into_owned();
+    let username = decode(url.username());
+    let password = url.password().map(decode);
+
+    let _ = url.set_username("");
+    let _ = url.set_password(None);
+
+    Some(basic_auth(username, password))
+}
+
+fn basic_auth<U, P>(username: U, password: Option<P>) -> HeaderValue
+where
+    U: fmt::Display,
+    P: fmt::Display,
+{
+    let credentials = match password {
+        Some(password) => format!("{username}:{password}"),
+        None => format!("{username}:"),
+    };
+
+    let value = format!("Basic {}", BASE64_STANDARD.encode(credentials));
+    let mut value = HeaderValue::try_from(value).expect("base64 is always a valid header value");
+    value.set_sensitive(true);
+    value
+}
+
+/// Controls how a request is retried if it fails with a transient error.
+///
+/// See [`RequestBuilder::retry`] for details.
+#[derive(Copy, Clone, Debug, Serialize, Deserialize)]
+pub struct RetryPolicy {
+    max_attempts: u32,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+}
+
+impl RetryPolicy {
+    /// Create a policy that sends a request at most `max_attempts` times.
+    ///
+    /// The delay before the first retry defaults to 500ms and is doubled after
+    /// every retry, up to a maximum of 30s.
+    pub fn new(max_attempts: u32) -> Self {
+        Self {
+            max_attempts,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(30),
+        }
+    }
+
+    /// Set the delay before the first retry.
+    pub fn initial_backoff(mut self, backoff: Duration) -> Self {
+        self.initial_backoff = backoff;
+        self
+    }
+
+    /// Set the maximum delay between two attempts.
+    ///
+    /// This also limits how long a `Retry-After` header can ask to wait for.
+    /// If the server asks for a longer delay then its response is returned
+    /// without retrying.
+    pub fn max_backoff(mut self, backoff: Duration) -> Self {
+        self.max_backoff = backoff;
+        self
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::new(3)
+    }
+}
+
+impl From<RetryPolicy> for bindings::RetryPolicy {
+    fn from(policy: RetryPolicy) -> Self {
+        Self {
+            max_attempts: policy.max_attempts,
+            initial_backoff: policy
+                .initial_backoff
+                .as_nanos()
+                .try_into()
+                .unwrap_or(u64::MAX),
+            max_backoff: policy.max_backoff.as_nanos().try_into().unwrap_or(u64::MAX),
+        }
+    }
 }
 
 /// A response to a submitted [`Request`].
@@ -209,6 +526,14 @@ pub struct Response {
     headers: HeaderMap,
     #[serde(with = "bytes_or_string")]
     body: Vec<u8>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    redirects: Vec<Url>,
+    #[serde(default = "default_attempts")]
+    attempts: u32,
+}
+
+fn default_attempts() -> u32 {
+    1
 }
 
 impl Response {
@@ -222,6 +547,28 @@ impl Response {
         &self.url
     }
 
+    /// Get the URLs that the request was redirected to, in order.
+    ///
+    /// This is empty if the request was not redirected. Only the final URL is
+    /// known for requests that don't have a [`redirect::Policy`].
+    pub fn redirects(&self) -> &[Url] {
+        &self.redirects
+    }
+
+    /// Get the URL that this response actually came from, after following any
+    /// redirects.
+    pub fn final_url(&self) -> &Url {
+        self.redirects.last().unwrap_or(&self.url)
+    }
+
+    /// Get the number of times that the request was sent in order to get this
+    /// response.
+    ///
+    /// This is always 1 unless the request had a [`RetryPolicy`].
+    pub fn attempts(&self) -> u32 {
+        self.attempts
+    }
+
     /// Get the headers of this response.
     pub fn headers(&self) -> &HeaderMap {
         &self.headers
@@ -370,6 +717,32 @@ impl RequestBuilder {
         })
     }
 
+    /// Enable HTTP basic authentication.
+    pub fn basic_auth<U, P>(self, username: U, password: Option<P>) -> Self
+    where
+        U: fmt::Display,
+        P: fmt::Display,
+    {
+        self.modify(|request| {
+            request
+                .headers
+                .insert(AUTHORIZATION, basic_auth(username, password));
+            Ok(())
+        })
+    }
+
+    /// Enable HTTP bearer authentication.
+    pub fn bearer_auth<T: fmt::Display>(self, token: T) -> Self {
+        self.modify(|request| {
+            let mut value = HeaderValue::try_from(format!("Bearer {token}"))
+                .map_err(|_| ErrorKind::InvalidHeaderValue)?;
+            value.set_sensitive(true);
+
+            request.headers.insert(AUTHORIZATION, value);
+            Ok(())
+        })
+    }
+
     /// Enables a request timeout.
     ///
     /// The timeout is applied from when the request starts connecting until the
@@ -388,6 +761,71 @@ impl RequestBuilder {
         })
     }
 
+    /// Bypass the worker's HTTP response cache for this request.
+    ///
+    /// This sets the `Cache-Control: no-store` header, so the request is always
+    /// sent to the server and the response is not cached. It has no effect on
+    /// workers that don't have an HTTP cache configured.
+    pub fn no_cache(self) -> Self {
+        const CACHE_CONTROL: HeaderName = HeaderName::from_static("cache-control");
+        const NO_STORE: HeaderValue = HeaderValue::from_static("no-store");
+
+        self.modify(|req| {
+            req.headers.insert(CACHE_CONTROL, NO_STORE);
+            Ok(())
+        })
+    }
+
+    /// Set how redirect responses are handled for this request.
+    ///
+    /// See [`redirect::Policy`] for details.
+    pub fn redirect(self, policy: redirect::Policy) -> Self {
+        self.modify(|request| {
+            request.redirect = Some(policy);
+            Ok(())
+        })
+    }
+
+    /// Retry this request if it fails with a transient error.
+    ///
+    /// Requests that fail to connect or that get a `429 Too Many Requests` or
+    /// `5xx` response are retried by the runtime, within the same transaction,
+    /// with exponential backoff. If the response has a `Retry-After` header
+    /// then that delay is used instead.
+    ///
+    /// The number of attempts that were made is available via
+    /// [`Response::attempts`].
+    pub fn retry(self, policy: RetryPolicy) -> Self {
+        self.modify(|request| {
+            request.retry = Some(policy);
+            Ok(())
+        })
+    }
+
+    /// Send cookies from `jar` with this request and store any cookies set by
+    /// the response back into it.
+    ///
+    /// See [`CookieJar`] for details.
+    pub fn cookie_jar(self, jar: &CookieJar) -> Self {
+        self.modify(|request| {
+            request.cookie_jar = Some(jar.clone());
+            Ok(())
+        })
+    }
+
+    /// Disable automatic decompression of the response body.
+    ///
+    /// By default, the runtime asks servers for compressed responses and
+    /// decompresses `gzip`, `deflate`, and `br` encoded bodies before returning
+    /// them. With this set, no `Accept-Encoding` header is added and the body
+    /// is returned exactly as the server sent it.
+    pub fn no_decompress(self) -> Self {
+        self.modify(|request| {
+            request.decompress = false;
+            Ok(())
+        })
+    }
+
     /// Set the request body.
     pub fn body(self, body: Vec<u8>) -> Self {
         self.modify(|request| {
@@ -484,6 +922,30 @@ impl RequestBuilder {
         self.build()?.send()
     }
 
+    /// Construct the request and send it to the target URL, returning the
+    /// response as a stream of [server-sent events].
+    ///
+    /// ```no_run
+    /// let events = durable::http::get("https://example.com/stream")
+    ///     .send_sse()
+    ///     .expect("failed to open the event stream");
+    ///
+    /// for event in events {
+    ///     let event = event.expect("failed to read an event");
+    ///     println!("{}: {}", event.event(), event.data());
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    /// This method fails if there was an error while sending the request, if
+    /// the server responded with an error status, or if an error was stored in
+    /// the builder.
+    ///
+    /// [server-sent events]: https://html.spec.whatwg.org/multipage/server-sent-events.html
+    pub fn send_sse(self) -> Result<EventStream, Error> {
+        self.build()?.send_sse()
+    }
+
     fn modify<F>(mut self, func: F) -> Self
     where
         F: FnOnce(&mut Request) -> Result<(), Error>,
@@ -0,0 +1,62 @@
+//! Control how redirect responses are handled.
+
+use serde::{Deserialize, Serialize};
+
+use crate::bindings;
+
+/// A policy for how redirect responses are handled for a request.
+///
+/// Requests without a policy are handled by the worker's HTTP client, which
+/// follows up to 10 redirects by default. Setting any policy on a request
+/// also means that [`Response::redirects`] includes every URL that the
+/// request was redirected to, instead of just the final one.
+///
+/// [`Response::redirects`]: crate::Response::redirects
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Policy(Kind);
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+enum Kind {
+    None,
+    Limited(u32),
+    Follow,
+}
+
+impl Policy {
+    /// Don't follow any redirects. Redirect responses are returned as-is.
+    pub fn none() -> Self {
+        Self(Kind::None)
+    }
+
+    /// Follow at most `max` redirects. If there are more then the last
+    /// redirect response is returned.
+    pub fn limited(max: u32) -> Self {
+        Self(Kind::Limited(max))
+    }
+
+    /// Follow redirects until reaching a URL that has already been visited.
+    pub fn follow() -> Self {
+        Self(Kind::Follow)
+    }
+}
+
+impl From<Policy> for bindings::RedirectPolicy {
+    fn from(policy: Policy) -> Self {
+        match policy.0 {
+            Kind::None => Self::None,
+            Kind::Limited(max) => Self::Limited(max),
+            Kind::Follow => Self::Follow,
+        }
+    }
+}
+
+impl From<bindings::RedirectPolicy> for Policy {
+    fn from(policy: bindings::RedirectPolicy) -> Self {
+        match policy {
+            bindings::RedirectPolicy::None => Self::none(),
+            bindings::RedirectPolicy::Limited(max) => Self::limited(max),
+            bindings::RedirectPolicy::Follow => Self::follow(),
+        }
+    }
+}
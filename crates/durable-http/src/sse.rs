@@ -0,0 +1,230 @@
+use std::cell::RefCell;
+use std::fmt;
+
+use durable_core::transaction;
+use http::{HeaderMap, HeaderName, HeaderValue, StatusCode};
+use serde::{Deserialize, Serialize};
+
+use crate::{bindings, ErrorKind, Request, Result};
+
+const LAST_EVENT_ID: HeaderName = HeaderName::from_static("last-event-id");
+
+/// A single event received from a server-sent event stream.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Event {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    id: Option<String>,
+    event: String,
+    data: String,
+}
+
+impl Event {
+    /// Get the id of this event, if the server set one.
+    pub fn id(&self) -> Option<&str> {
+        self.id.as_deref()
+    }
+
+    /// Get the type of this event.
+    ///
+    /// This is `message` unless the server specified a different type.
+    pub fn event(&self) -> &str {
+        &self.event
+    }
+
+    /// Get the data of this event.
+    ///
+    /// Events with multiple `data` lines have them joined with newlines.
+    pub fn data(&self) -> &str {
+        &self.data
+    }
+
+    /// Convert this event into its data.
+    pub fn into_data(self) -> String {
+        self.data
+    }
+
+    /// Attempt to deserialize the event data as JSON.
+    pub fn json<'de, T>(&'de self) -> serde_json::Result<T>
+    where
+        T: Deserialize<'de>,
+    {
+        serde_json::from_str(&self.data)
+    }
+}
+
+impl From<bindings::SseEvent> for Event {
+    fn from(event: bindings::SseEvent) -> Self {
+        Self {
+            id: event.id,
+            event: event.event,
+            data: event.data,
+        }
+    }
+}
+
+/// The status and headers of an event stream response.
+#[derive(Serialize, Deserialize)]
+struct StreamHead {
+    #[serde(with = "http_serde_ext::status_code")]
+    status: StatusCode,
+    #[serde(with = "http_serde_ext::header_map")]
+    headers: HeaderMap,
+}
+
+/// An iterator over the events in a server-sent event stream.
+///
+/// This is created by [`RequestBuilder::send_sse`]. Opening the stream and
+/// reading each event from it are all separate transactions, so the events
+/// that a workflow has already seen are replayed from its event log when it is
+/// restarted.
+///
+/// The connection itself does not survive a restart. Instead, the first event
+/// read after the workflow is restarted reopens the stream with a
+/// `Last-Event-ID` header containing the id of the last event that the
+/// workflow saw. Servers that support it will then resume the stream from
+/// where it left off.
+///
+/// The iterator ends once the server closes the stream or after it returns an
+/// error.
+///
+/// [`RequestBuilder::send_sse`]: crate::RequestBuilder::send_sse
+pub struct EventStream {
+    request: Request,
+    head: StreamHead,
+    stream: RefCell<Option<bindings::EventStream>>,
+    last_event_id: Option<String>,
+    done: bool,
+}
+
+impl EventStream {
+    /// Get the status code of the response.
+    pub fn status(&self) -> StatusCode {
+        self.head.status
+    }
+
+    /// Get the headers of the response.
+    pub fn headers(&self) -> &HeaderMap {
+        &self.head.headers
+    }
+
+    /// Get the id of the most recent event that had one.
+    pub fn last_event_id(&self) -> Option<&str> {
+        self.last_event_id.as_deref()
+    }
+
+    fn read_event(&self) -> Result<Option<Event>> {
+        let mut stream = self.stream.borrow_mut();
+
+        // The connection is gone if the workflow was restarted, so pick up
+        // from the last event that we saw.
+        if stream.is_none() {
+            let (new, head) = open(&self.request, self.last_event_id.as_deref())?;
+            check_status(head.status)?;
+            *stream = Some(new);
+        }
+
+        match &*stream {
+            Some(stream) => Ok(stream.next()?.map(Event::from)),
+            None => unreachable!(),
+        }
+    }
+}
+
+impl fmt::Debug for EventStream {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EventStream")
+            .field("url", &self.request.url)
+            .field("status", &self.head.status)
+            .field("last_event_id", &self.last_event_id)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Iterator for EventStream {
+    type Item = Result<Event>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let label = format!("durable::http::EventStream::next({})", self.request.url);
+        let result = transaction::maybe_txn(&label, || self.read_event());
+
+        match &result {
+            Ok(Some(event)) => {
+                if let Some(id) = &event.id {
+                    self.last_event_id = Some(id.clone());
+                }
+            }
+            _ => {
+                self.done = true;
+                self.stream.get_mut().take();
+            }
+        }
+
+        result
+            .map_err(|e| e.with_url(self.request.url.clone()))
+            .transpose()
+    }
+}
+
+impl std::iter::FusedIterator for EventStream {}
+
+pub(crate) fn send(request: &Request) -> Result<EventStream> {
+    let stream = RefCell::new(None);
+    let label = format!(
+        "durable::http::send_sse({} {})",
+        request.method, request.url
+    );
+    let result = transaction::maybe_txn(&label, || -> Result<StreamHead> {
+        let (new, head) = open(request, None)?;
+        *stream.borrow_mut() = Some(new);
+        Ok(head)
+    });
+
+    if let (Ok(head), Some(jar)) = (&result, &request.cookie_jar) {
+        jar.store_response(&request.url, &head.headers);
+    }
+
+    let head = result.map_err(|e| e.with_url(request.url.clone()))?;
+    check_status(head.status).map_err(|e| e.with_url(request.url.clone()))?;
+
+    Ok(EventStream {
+        request: request.clone(),
+        head,
+        stream,
+        last_event_id: None,
+        done: false,
+    })
+}
+
+fn open(
+    request: &Request,
+    last_event_id: Option<&str>,
+) -> Result<(bindings::EventStream, StreamHead)> {
+    let mut headers = crate::request_headers(request);
+    if let Some(id) = last_event_id {
+        let value = HeaderValue::from_str(id).map_err(|_| ErrorKind::InvalidHeaderValue)?;
+        headers.to_mut().insert(LAST_EVENT_ID, value);
+    }
+
+    let req = crate::http_request(request, &headers)?;
+    let stream = bindings::open_event_stream(req)?;
+    let response = stream.response();
+
+    let head = StreamHead {
+        status: crate::response_status(response.status)?,
+        headers: crate::response_headers(response.headers)?,
+    };
+
+    Ok((stream, head))
+}
+
+fn check_status(status: StatusCode) -> Result<()> {
+    if status.is_client_error() || status.is_server_error() {
+        return Err(ErrorKind::Status(status).into());
+    }
+
+    Ok(())
+}
@@ -0,0 +1,289 @@
+//! Make requests to OpenAI-compatible LLM APIs as part of your workflow.
+//!
+//! The providers that workflows can use, along with their API keys, are
+//! configured on the worker. Requests are made using [`durable_http`].
+//!
+//! ```no_run
+//! use durable::llm::{ChatRequest, Client};
+//!
+//! let client = Client::new().expect("no LLM provider is configured");
+//! let response = client
+//!     .chat(
+//!         &ChatRequest::new()
+//!             .system("You are a helpful assistant.")
+//!             .user("Summarize the plot of Hamlet in one sentence."),
+//!     )
+//!     .expect("the chat request failed");
+//!
+//! println!("{}", response.content().unwrap_or_default());
+//! ```
+//!
+//! Each chat request is made within its own durable transaction, unless it is
+//! made from within an existing transaction. The response is recorded, so the
+//! request will not be made again if the workflow is restarted.
+//!
+//! # Streaming
+//! [`Client::chat_stream`] returns the completion as a series of
+//! [`ChatChunk`]s as it is generated. Each chunk is read in a separate
+//! transaction. See [`ChatStream`] for how this interacts with workflow
+//! restarts.
+//!
+//! # Token usage
+//! The number of tokens used by each request is recorded in the
+//! `durable.llm_usage` table, along with the task that made it and the
+//! provider and model that were used.
+
+use std::fmt;
+use std::time::Duration;
+
+use durable_core::transaction;
+use durable_http::{RequestBuilder, StatusCode};
+use serde::{Deserialize, Serialize};
+
+mod bindings {
+    #![allow(unused_braces, clippy::all)]
+
+    include!("bindings.rs");
+
+    pub use self::durable::core::llm::*;
+}
+
+mod stream;
+mod types;
+
+pub use crate::stream::ChatStream;
+pub use crate::types::*;
+
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// The name of the provider used by [`Client::new`].
+pub const DEFAULT_PROVIDER: &str = "default";
+
+/// A client for one of the LLM providers configured on the worker.
+#[derive(Clone)]
+pub struct Client {
+    name: String,
+    provider: bindings::Provider,
+    timeout: Option<Duration>,
+}
+
+impl Client {
+    /// Create a client for the provider named `default`.
+    pub fn new() -> Result<Self> {
+        Self::provider(DEFAULT_PROVIDER)
+    }
+
+    /// Create a client for the provider named `name`.
+    ///
+    /// # Errors
+    /// Returns [`Error::NotConfigured`] if there is no provider with that name
+    /// configured on the worker.
+    pub fn provider(name: impl Into<String>) -> Result<Self> {
+        let name = name.into();
+
+        // This is deliberately not recorded in a transaction so that the API key
+        // never ends up in the event log.
+        match bindings::get_provider(&name) {
+            Some(provider) => Ok(Self {
+                name,
+                provider,
+                timeout: None,
+            }),
+            None => Err(Error::NotConfigured(name)),
+        }
+    }
+
+    /// Get the name of the provider that this client uses.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Set a timeout for requests made by this client.
+    ///
+    /// For streaming requests this is the maximum time to wait for each chunk.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Request a chat completion.
+    pub fn chat(&self, request: &ChatRequest) -> Result<ChatResponse> {
+        let label = format!("durable::llm::chat({})", self.name);
+        transaction::maybe_txn(&label, || {
+            let body = self.body(request, false)?;
+            let response = self.request(&body).send()?;
+            let status = response.status();
+
+            if !status.is_success() {
+                return Err(Error::api(status, response.body()));
+            }
+
+            let response: ChatResponse = response
+                .json()
+                .map_err(|e| Error::InvalidResponse(e.to_string()))?;
+
+            if let Some(usage) = &response.usage {
+                let model = match response.model.as_str() {
+                    "" => body.model.as_deref().unwrap_or_default(),
+                    model => model,
+                };
+
+                bindings::record_usage(
+                    &self.name,
+                    model,
+                    usage.prompt_tokens,
+                    usage.completion_tokens,
+                );
+            }
+
+            Ok(response)
+        })
+    }
+
+    /// Request a chat completion and stream the response as it is generated.
+    ///
+    /// The request asks the provider to include the token usage at the end of
+    /// the stream. It is recorded once that chunk has been read.
+    pub fn chat_stream(&self, request: &ChatRequest) -> Result<ChatStream> {
+        let body = self.body(request, true)?;
+        let events = self.request(&body).send_sse()?;
+        let model = body.model.unwrap_or_default();
+
+        Ok(ChatStream::new(self.name.clone(), model, events))
+    }
+
+    fn body(&self, request: &ChatRequest, stream: bool) -> Result<ChatRequest> {
+        let mut body = request.clone();
+        if body.model.is_none() {
+            body.model = self.provider.default_model.clone();
+        }
+
+        if body.model.is_none() {
+            return Err(Error::MissingModel(self.name.clone()));
+        }
+
+        if stream {
+            body.extra.insert("stream".into(), true.into());
+            body.extra.insert(
+                "stream_options".into(),
+                serde_json::json!({ "include_usage": true }),
+            );
+        } else {
+            body.extra.remove("stream");
+        }
+
+        Ok(body)
+    }
+
+    fn request(&self, body: &ChatRequest) -> RequestBuilder {
+        let url = format!("{}/chat/completions", self.provider.base_url);
+        let mut request = durable_http::post(url).json(body);
+
+        if let Some(key) = &self.provider.api_key {
+            request = request.bearer_auth(key);
+        }
+
+        if let Some(timeout) = self.timeout {
+            request = request.timeout(timeout);
+        }
+
+        request
+    }
+}
+
+impl fmt::Debug for Client {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Client")
+            .field("name", &self.name)
+            .field("base_url", &self.provider.base_url)
+            .field("default_model", &self.provider.default_model)
+            .field("timeout", &self.timeout)
+            .finish_non_exhaustive()
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Error {
+    /// There is no provider with this name configured on the worker.
+    NotConfigured(String),
+
+    /// The request did not specify a model and the provider with this name
+    /// does not have a default model.
+    MissingModel(String),
+
+    /// The HTTP request to the provider failed.
+    Http(durable_http::Error),
+
+    /// The provider responded with an error.
+    Api { status: u16, message: String },
+
+    /// The response from the provider could not be parsed.
+    InvalidResponse(String),
+}
+
+impl Error {
+    fn api(status: StatusCode, body: &[u8]) -> Self {
+        #[derive(Deserialize)]
+        struct ErrorBody {
+            error: ErrorDetail,
+        }
+
+        #[derive(Deserialize)]
+        struct ErrorDetail {
+            message: String,
+        }
+
+        let message = match serde_json::from_slice::<ErrorBody>(body) {
+            Ok(body) => body.error.message,
+            Err(_) => String::from_utf8_lossy(body).into_owned(),
+        };
+
+        Self::Api {
+            status: status.as_u16(),
+            message,
+        }
+    }
+}
+
+impl From<durable_http::Error> for Error {
+    fn from(error: durable_http::Error) -> Self {
+        Self::Http(error)
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotConfigured(name) => {
+                write!(
+                    f,
+                    "no LLM provider named {name:?} is configured on the worker"
+                )
+            }
+            Self::MissingModel(name) => write!(
+                f,
+                "the request did not specify a model and provider {name:?} has no default model"
+            ),
+            Self::Http(e) => write!(f, "the request to the LLM provider failed: {e}"),
+            Self::Api { status, message } => {
+                write!(
+                    f,
+                    "the LLM provider returned an error ({status}): {message}"
+                )
+            }
+            Self::InvalidResponse(message) => {
+                write!(f, "invalid response from the LLM provider: {message}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Http(e) => Some(e),
+            _ => None,
+        }
+    }
+}
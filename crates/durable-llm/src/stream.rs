@@ -0,0 +1,104 @@
+use std::fmt;
+
+use durable_core::transaction;
+use durable_http::EventStream;
+
+use crate::{bindings, ChatChunk, Error, Result};
+
+/// An iterator over the chunks of a streamed chat completion.
+///
+/// This is created by [`Client::chat_stream`]. It is built on top of a
+/// [`durable_http::EventStream`], so each chunk is read in its own transaction
+/// and the chunks that a workflow has already seen are replayed from its event
+/// log when it is restarted.
+///
+/// Most providers do not support resuming a stream part-way through. If a
+/// workflow is restarted in the middle of a stream then the request is sent
+/// again and the remaining chunks come from the new completion, which may not
+/// line up with the chunks that were already seen. Workflows that need the
+/// full completion to be consistent should use [`Client::chat`] instead.
+///
+/// The iterator ends once the provider sends the final `[DONE]` event, or
+/// after it returns an error.
+///
+/// [`Client::chat_stream`]: crate::Client::chat_stream
+/// [`Client::chat`]: crate::Client::chat
+pub struct ChatStream {
+    provider: String,
+    model: String,
+    events: EventStream,
+    done: bool,
+}
+
+impl ChatStream {
+    pub(crate) fn new(provider: String, model: String, events: EventStream) -> Self {
+        Self {
+            provider,
+            model,
+            events,
+            done: false,
+        }
+    }
+
+    fn next_chunk(&mut self) -> Result<Option<ChatChunk>> {
+        let Some(event) = self.events.next().transpose()? else {
+            return Ok(None);
+        };
+
+        if event.data() == "[DONE]" {
+            return Ok(None);
+        }
+
+        let chunk: ChatChunk = event
+            .json()
+            .map_err(|e| Error::InvalidResponse(e.to_string()))?;
+
+        if let Some(usage) = &chunk.usage {
+            let model = match chunk.model.as_str() {
+                "" => &self.model,
+                model => model,
+            };
+
+            let label = format!("durable::llm::record_usage({})", self.provider);
+            transaction::maybe_txn(&label, || {
+                bindings::record_usage(
+                    &self.provider,
+                    model,
+                    usage.prompt_tokens,
+                    usage.completion_tokens,
+                )
+            });
+        }
+
+        Ok(Some(chunk))
+    }
+}
+
+impl fmt::Debug for ChatStream {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ChatStream")
+            .field("provider", &self.provider)
+            .field("model", &self.model)
+            .field("events", &self.events)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Iterator for ChatStream {
+    type Item = Result<ChatChunk>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let result = self.next_chunk();
+        if !matches!(result, Ok(Some(_))) {
+            self.done = true;
+        }
+
+        result.transpose()
+    }
+}
+
+impl std::iter::FusedIterator for ChatStream {}
@@ -1,15 +1,22 @@
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
+use std::time::{Duration, SystemTime};
 
 use sqlx::postgres::PgRow;
 use sqlx::{Connection, Row};
 
 use crate::error::{DivergingMigrationError, ErrorData};
-use crate::{Error, Migrator, Options, Table, Target, TransactionMode};
+use crate::status::{ChecksumState, MigrationReport, MigrationState, MigrationStatus};
+use crate::{
+    ChecksumMode, Error, MigrationFn, MigrationLock, Migrator, Options, Table, Target,
+    TransactionMode,
+};
 
 struct DatabaseMigration {
     version: i64,
     name: String,
     revert: Option<String>,
+    checksum: Option<Vec<u8>>,
+    applied_at: Option<SystemTime>,
 }
 
 enum Operation<'a> {
@@ -18,6 +25,8 @@ enum Operation<'a> {
         name: &'a str,
         sql: &'a str,
         revert: Option<&'a str>,
+        code: Option<MigrationFn>,
+        checksum: [u8; 32],
     },
     Revert {
         version: i64,
@@ -46,13 +55,32 @@ impl Migrator {
             CREATE TABLE IF NOT EXISTS {table}(\
                 version     bigint  NOT NULL PRIMARY KEY CHECK((version >= 0)),\
                 name        text    NOT NULL,\
-                revert      text\
+                revert      text,\
+                checksum    bytea,\
+                applied_at  timestamptz DEFAULT CURRENT_TIMESTAMP\
             )\
             ",
             table = options.migration_table.as_sql()
         );
         sqlx::query(&query).execute(&mut *conn).await?;
 
+        // Migration tables created by older versions won't have the checksum
+        // or applied_at columns. We don't know when the existing migrations
+        // were applied so applied_at is left as NULL for them.
+        let query = format!(
+            "ALTER TABLE {table} \
+                ADD COLUMN IF NOT EXISTS checksum bytea, \
+                ADD COLUMN IF NOT EXISTS applied_at timestamptz",
+            table = options.migration_table.as_sql()
+        );
+        sqlx::query(&query).execute(&mut *conn).await?;
+
+        let query = format!(
+            "ALTER TABLE {table} ALTER COLUMN applied_at SET DEFAULT CURRENT_TIMESTAMP",
+            table = options.migration_table.as_sql()
+        );
+        sqlx::query(&query).execute(&mut *conn).await?;
+
         Ok(())
     }
 
@@ -61,9 +89,40 @@ impl Migrator {
         conn: &mut sqlx::PgConnection,
         options: &Options,
     ) -> Result<Vec<DatabaseMigration>, Error> {
+        let table = options.migration_table.as_sql();
+
+        // The migration table may not exist or be missing some of its columns if
+        // we are only reading from it and it was created by an older version.
+        let columns: Option<Vec<String>> = sqlx::query_scalar(
+            "
+            SELECT array_agg(attname::text)
+              FROM pg_attribute
+             WHERE attrelid = to_regclass($1)
+               AND attnum > 0
+               AND NOT attisdropped
+            ",
+        )
+        .bind(&table)
+        .fetch_one(&mut *conn)
+        .await?;
+
+        let Some(columns) = columns else {
+            return Ok(Vec::new());
+        };
+        let has_column = |name: &str| columns.iter().any(|column| column == name);
+
+        let checksum = match has_column("checksum") {
+            true => "checksum",
+            false => "NULL::bytea",
+        };
+        let applied_at = match has_column("applied_at") {
+            true => "EXTRACT(EPOCH FROM applied_at)::float8",
+            false => "NULL::float8",
+        };
         let query = format!(
-            "SELECT version, name, revert FROM {table} ORDER BY version ASC",
-            table = options.migration_table.as_sql()
+            "SELECT version, name, revert, {checksum} as checksum, {applied_at} as applied_at \
+               FROM {table} \
+              ORDER BY version ASC"
         );
 
         let migrations = sqlx::query(&query)
@@ -72,6 +131,11 @@ impl Migrator {
                     version: record.get::<i64, _>("version"),
                     name: record.get("name"),
                     revert: record.get("revert"),
+                    checksum: record.get("checksum"),
+                    applied_at: record
+                        .get::<Option<f64>, _>("applied_at")
+                        .and_then(|secs| Duration::try_from_secs_f64(secs).ok())
+                        .map(|since| SystemTime::UNIX_EPOCH + since),
                 })
             })
             .fetch_all(&mut *conn)
@@ -102,6 +166,8 @@ impl Migrator {
                     name: &migration.name,
                     sql: &migration.sql,
                     revert: migration.revert.as_deref(),
+                    code: migration.code,
+                    checksum: migration.checksum(),
                 })
                 .collect();
 
@@ -169,6 +235,8 @@ impl Migrator {
                         name: &m.name,
                         sql: &m.sql,
                         revert: m.revert.as_deref(),
+                        code: m.code,
+                        checksum: m.checksum(),
                     })
                     .collect();
 
@@ -215,6 +283,8 @@ impl Migrator {
                     name: &m.name,
                     sql: &m.sql,
                     revert: m.revert.as_deref(),
+                    code: m.code,
+                    checksum: m.checksum(),
                 })
                 .collect();
 
@@ -224,7 +294,242 @@ impl Migrator {
         }
     }
 
+    /// Compare the checksums of applied migrations against the local
+    /// migrations.
+    ///
+    /// If `conn` is provided then missing checksums are filled in and, when
+    /// repairing, mismatched checksums are updated.
+    async fn check_checksums(
+        &self,
+        mut conn: Option<&mut sqlx::PgConnection>,
+        applied: &[DatabaseMigration],
+        options: &Options,
+    ) -> Result<(), Error> {
+        let known = BTreeMap::from_iter(self.migrations.iter().map(|m| (m.version as i64, m)));
+        let query = format!(
+            "UPDATE {table} SET checksum = $2 WHERE version = $1",
+            table = options.migration_table.as_sql()
+        );
+
+        for applied in applied {
+            let Some(known) = known.get(&applied.version) else {
+                continue;
+            };
+            let checksum = known.checksum();
+
+            match applied.checksum.as_deref() {
+                Some(stored) if stored == checksum => continue,
+                // This migration was applied before checksums were recorded.
+                None => (),
+                Some(_) => match options.checksum_mode {
+                    ChecksumMode::Verify => {
+                        return Err(ErrorData::ChecksumMismatch {
+                            version: known.version,
+                            name: known.name.to_string(),
+                        }
+                        .into())
+                    }
+                    ChecksumMode::Warn => {
+                        tracing::warn!(
+                            "migration {} - {} has been modified since it was applied to the \
+                             database",
+                            known.version,
+                            known.name
+                        );
+                        continue;
+                    }
+                    ChecksumMode::Repair => {
+                        tracing::info!(
+                            "repairing checksum for migration {} - {}",
+                            known.version,
+                            known.name
+                        );
+                    }
+                },
+            }
+
+            if let Some(conn) = conn.as_deref_mut() {
+                sqlx::query(&query)
+                    .bind(applied.version)
+                    .bind(&checksum[..])
+                    .execute(&mut *conn)
+                    .await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Record all migrations up to and including `baseline` as applied without
+    /// running them.
+    async fn record_baseline(
+        &self,
+        conn: &mut sqlx::PgConnection,
+        applied: &[DatabaseMigration],
+        baseline: u64,
+        options: &Options,
+    ) -> Result<(), Error> {
+        if !self.migrations.iter().any(|m| m.version == baseline) {
+            return Err(ErrorData::MissingTargetMigration(baseline).into());
+        }
+
+        let applied = BTreeSet::from_iter(applied.iter().map(|m| m.version));
+        let query = format!(
+            "INSERT INTO {table}(version, name, revert, checksum) VALUES ($1, $2, $3, $4)",
+            table = options.migration_table.as_sql()
+        );
+
+        for m in self.migrations.iter().take_while(|m| m.version <= baseline) {
+            let version = i64::try_from(m.version).map_err(ErrorData::VersionOutOfRange)?;
+            if applied.contains(&version) {
+                continue;
+            }
+
+            tracing::debug!("recording migration {version} - {} as applied", m.name);
+
+            sqlx::query(&query)
+                .bind(version)
+                .bind(&*m.name)
+                .bind(m.revert.as_deref())
+                .bind(&m.checksum()[..])
+                .execute(&mut *conn)
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    /// Get the status of every migration, both those known to this migrator
+    /// and those that have been applied to the database.
+    ///
+    /// This only reads from the database and does not take the migration
+    /// lock. Only [`Options::migration_table`] is used from `options`.
+    pub async fn status(
+        &self,
+        conn: &mut sqlx::PgConnection,
+        options: &Options,
+    ) -> Result<MigrationReport, Error> {
+        let applied = self.applied_migrations(conn, options).await?;
+        let applied = BTreeMap::from_iter(applied.into_iter().map(|m| (m.version as u64, m)));
+        let known = BTreeMap::from_iter(self.migrations.iter().map(|m| (m.version, m)));
+
+        let versions = BTreeSet::from_iter(applied.keys().chain(known.keys()).copied());
+        let migrations = versions
+            .into_iter()
+            .map(
+                |version| match (known.get(&version), applied.get(&version)) {
+                    (Some(known), Some(applied)) => MigrationStatus {
+                        version,
+                        name: known.name.to_string(),
+                        state: MigrationState::Applied,
+                        applied_at: applied.applied_at,
+                        checksum: Some(match applied.checksum.as_deref() {
+                            None => ChecksumState::NotRecorded,
+                            Some(stored) if stored == known.checksum() => ChecksumState::Valid,
+                            Some(_) => ChecksumState::Modified,
+                        }),
+                    },
+                    (Some(known), None) => MigrationStatus {
+                        version,
+                        name: known.name.to_string(),
+                        state: MigrationState::Pending,
+                        applied_at: None,
+                        checksum: None,
+                    },
+                    (None, Some(applied)) => MigrationStatus {
+                        version,
+                        name: applied.name.clone(),
+                        state: MigrationState::Unknown,
+                        applied_at: applied.applied_at,
+                        checksum: None,
+                    },
+                    (None, None) => unreachable!(),
+                },
+            )
+            .collect();
+
+        Ok(MigrationReport { migrations })
+    }
+
+    /// Take the session-level advisory lock used to serialize migrators.
+    async fn acquire_lock(
+        conn: &mut sqlx::PgConnection,
+        lock: &MigrationLock,
+    ) -> Result<(), Error> {
+        let acquired: bool = sqlx::query_scalar("SELECT pg_try_advisory_lock($1)")
+            .bind(lock.key)
+            .fetch_one(&mut *conn)
+            .await?;
+
+        if acquired {
+            return Ok(());
+        }
+
+        tracing::info!(
+            "waiting for another migrator to release the migration lock (advisory lock {})",
+            lock.key
+        );
+
+        // The advisory lock outlives the transaction, we only use it to scope
+        // the lock timeout.
+        let mut tx = conn.begin().await?;
+
+        if let Some(timeout) = lock.timeout {
+            // A lock_timeout of 0 disables the timeout so make sure that we
+            // always wait for at least 1ms.
+            let millis = timeout.as_millis().max(1);
+            sqlx::query(&format!("SET LOCAL lock_timeout = {millis}"))
+                .execute(&mut *tx)
+                .await?;
+        }
+
+        let result = sqlx::query("SELECT pg_advisory_lock($1)")
+            .bind(lock.key)
+            .execute(&mut *tx)
+            .await;
+
+        match result {
+            Ok(_) => (),
+            // lock_not_available
+            Err(sqlx::Error::Database(e)) if e.code().as_deref() == Some("55P03") => {
+                return Err(ErrorData::LockTimeout {
+                    key: lock.key,
+                    timeout: lock.timeout.unwrap_or_default(),
+                }
+                .into())
+            }
+            Err(e) => return Err(e.into()),
+        }
+
+        tx.commit().await?;
+
+        Ok(())
+    }
+
     pub async fn run(&self, conn: &mut sqlx::PgConnection, options: &Options) -> Result<(), Error> {
+        let Some(lock) = &options.lock else {
+            return self.run_locked(conn, options).await;
+        };
+
+        Self::acquire_lock(&mut *conn, lock).await?;
+
+        let result = self.run_locked(&mut *conn, options).await;
+        let unlock = sqlx::query("SELECT pg_advisory_unlock($1)")
+            .bind(lock.key)
+            .execute(&mut *conn)
+            .await;
+
+        result?;
+        unlock?;
+
+        Ok(())
+    }
+
+    async fn run_locked(
+        &self,
+        conn: &mut sqlx::PgConnection,
+        options: &Options,
+    ) -> Result<(), Error> {
         let mut tx = None;
 
         let conn = if options.dry_run || options.transaction_mode == TransactionMode::Single {
@@ -234,8 +539,16 @@ impl Migrator {
         };
 
         self.setup(&mut *conn, options).await?;
-        let applied = self.applied_migrations(&mut *conn, options).await?;
+        let mut applied = self.applied_migrations(&mut *conn, options).await?;
+        if let Some(baseline) = options.baseline {
+            self.record_baseline(&mut *conn, &applied, baseline, options)
+                .await?;
+            applied = self.applied_migrations(&mut *conn, options).await?;
+        }
+
         let operations = self.operations(&applied, options)?;
+        self.check_checksums(Some(&mut *conn), &applied, options)
+            .await?;
 
         if !options.allow_revert {
             let has_reverts = operations.iter().any(|op| op.is_revert());
@@ -255,19 +568,32 @@ impl Migrator {
                         name,
                         sql,
                         revert,
+                        code,
+                        checksum,
                     } => {
                         tracing::debug!("running migration {version} - {name}");
 
-                        sqlx::raw_sql(sql).execute(&mut *tx).await?;
+                        let sql = options.rewrite(sql);
+                        sqlx::raw_sql(&sql).execute(&mut *tx).await?;
+
+                        if let Some(code) = code {
+                            code(&mut tx).await.map_err(|source| ErrorData::Code {
+                                version: version as u64,
+                                name: name.to_owned(),
+                                source,
+                            })?;
+                        }
 
                         let query = format!(
-                            "INSERT INTO {table}(version, name, revert) VALUES ($1, $2, $3) ",
+                            "INSERT INTO {table}(version, name, revert, checksum) \
+                             VALUES ($1, $2, $3, $4)",
                             table = options.migration_table.as_sql()
                         );
                         sqlx::query(&query)
                             .bind(version)
                             .bind(name)
                             .bind(revert)
+                            .bind(&checksum[..])
                             .execute(&mut *tx)
                             .await?;
                     }
@@ -287,7 +613,8 @@ impl Migrator {
                             .fetch_one(&mut *tx)
                             .await?;
 
-                        sqlx::raw_sql(revert).execute(&mut *tx).await?;
+                        let revert = options.rewrite(revert);
+                        sqlx::raw_sql(&revert).execute(&mut *tx).await?;
                     }
                 }
 
@@ -331,6 +658,10 @@ impl Migrator {
             prefer_local_revert: false,
             target: Target::Latest,
             transaction_mode: TransactionMode::Single,
+            checksum_mode: ChecksumMode::Warn,
+            baseline: None,
+            lock: None,
+            schema_rename: None,
         };
 
         let applied = self.applied_migrations(&mut *conn, &options).await?;
@@ -338,6 +669,7 @@ impl Migrator {
         // Emit an error if our migrations are invalid or the diverge from those in the
         // database.
         let _ = self.operations(&applied, &options)?;
+        self.check_checksums(None, &applied, &options).await?;
 
         Ok(applied.last().map(|migration| migration.version as u64))
     }
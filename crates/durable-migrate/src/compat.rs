@@ -0,0 +1,40 @@
+//! Compatibility hashes for precompiled programs.
+//!
+//! Both `durable-client` and `durable-runtime` depend on this crate, so this
+//! is the one place that defines how a precompiled artifact is matched up with
+//! the engines that are able to load it.
+
+use std::hash::{Hash, Hasher};
+
+use sha2::{Digest, Sha256};
+
+/// Compute a stable hash identifying which precompiled artifacts can be loaded
+/// by an engine.
+///
+/// `compat` should be the value returned by
+/// `wasmtime::Engine::precompile_compatibility_hash` for the engine.
+///
+/// Clients store this hash alongside the artifacts they precompile and workers
+/// look artifacts up by the hash of their own engine, so changing its output
+/// means that existing artifacts will no longer be used.
+pub fn compat_hash(compat: impl Hash) -> [u8; 32] {
+    let mut hasher = Sha256Hasher(Sha256::new());
+    compat.hash(&mut hasher);
+    hasher.0.finalize().into()
+}
+
+struct Sha256Hasher(Sha256);
+
+impl Hasher for Sha256Hasher {
+    fn write(&mut self, bytes: &[u8]) {
+        self.0.update(bytes);
+    }
+
+    /// Returns the first 8 bytes of the digest of everything written so far.
+    fn finish(&self) -> u64 {
+        let digest = self.0.clone().finalize();
+        let mut bytes = [0u8; 8];
+        bytes.copy_from_slice(&digest[..8]);
+        u64::from_le_bytes(bytes)
+    }
+}
@@ -5,9 +5,9 @@ use std::ffi::OsString;
 use std::fmt;
 use std::path::{Path, PathBuf};
 
-use crate::{Migrator, Options};
+use crate::{ChecksumMode, MigrationLock, Migrator, Options};
 
-used_in_docs!(Migrator, Options);
+used_in_docs!(ChecksumMode, MigrationLock, Migrator, Options);
 
 #[derive(Debug, thiserror::Error)]
 #[error(transparent)]
@@ -24,6 +24,9 @@ impl Error {
             ErrorData::MissingDownMigration { .. } => todo!(),
             ErrorData::MissingTargetMigration(_) => todo!(),
             ErrorData::WouldRevert => ErrorKind::WouldRevert,
+            ErrorData::ChecksumMismatch { .. } => ErrorKind::ChecksumMismatch,
+            ErrorData::Code { .. } => ErrorKind::Code,
+            ErrorData::LockTimeout { .. } => ErrorKind::LockTimeout,
         }
     }
 
@@ -66,6 +69,23 @@ pub enum ErrorKind {
     /// reverting an applied migration but that has been disallowed by the
     /// provided [`Options`].
     WouldRevert,
+
+    /// A migration that has been applied to the database has been changed
+    /// since it was applied.
+    ///
+    /// If the change was intentional then the stored checksums can be updated
+    /// by running the migrator with [`ChecksumMode::Repair`].
+    ChecksumMismatch,
+
+    /// The Rust function of a migration returned an error.
+    ///
+    /// You can get at the error it returned by calling the `source` method of
+    /// [`Error`].
+    Code,
+
+    /// Timed out while waiting for another migrator to release the migration
+    /// lock.
+    LockTimeout,
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -92,6 +112,26 @@ pub(crate) enum ErrorData {
          permitted"
     )]
     WouldRevert,
+    #[error(
+        "migration {version} {name:?} has been modified since it was applied to the database \
+         (its checksum no longer matches), run with `ChecksumMode::Repair` if this was intended"
+    )]
+    ChecksumMismatch { version: u64, name: String },
+    #[error("rust code for migration {version} {name:?} failed")]
+    Code {
+        version: u64,
+        name: String,
+        #[source]
+        source: crate::BoxError,
+    },
+    #[error(
+        "timed out after {timeout:?} waiting for the migration lock (advisory lock {key}), \
+         another migrator is likely already running against this database"
+    )]
+    LockTimeout {
+        key: i64,
+        timeout: std::time::Duration,
+    },
 }
 
 #[cfg(feature = "migrate")]
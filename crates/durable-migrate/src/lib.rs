@@ -10,8 +10,12 @@
 //!   (e.g. `_sqlx_migrations`) is not already being used.
 //! - Migrations can be run individually in transactions, or all as one big
 //!   transaction.
+//! - Concurrent migrators are serialized using a postgres advisory lock, so
+//!   multiple instances of an application can safely migrate on startup.
 //! - Migrations can be reverted, but this will not automatically be done unless
 //!   specifically requested.
+//! - A checksum of each migration is recorded when it is applied so that
+//!   changes to migrations that have already been applied can be detected.
 //! - Migrations can be statically embedded via a build script so that it is not
 //!   necessary for users of your library or application to keep around a bunch
 //!   of migration files they may not even know about.
@@ -44,6 +48,22 @@
 //! - a non-revertible migration with version 1 and name "do setup", and,
 //! - a revertible migration with version 2 and name "add new table column".
 //!
+//! ## Rust migrations
+//! Some migrations need to transform data in ways that are awkward to express
+//! in SQL. A migration may also carry a Rust function, set via
+//! [`Migration::code`], that is run with a connection to the database. The
+//! function runs after the migration's SQL, in the same transaction, and
+//! migrations are still applied strictly in version order regardless of
+//! whether they contain SQL, Rust, or both.
+//!
+//! When migrations are loaded from a directory, each Rust migration still
+//! needs an `.up.sql` file (which may be empty) to give it a version and a
+//! name. The function is then registered for that version with
+//! [`EmbedOptions::function`] so that the embedded migrations refer to it.
+//!
+//! Only the SQL of a migration is covered by its checksum, so changes to the
+//! Rust function of an applied migration are not detected.
+//!
 //! # Applying migrations to a database
 //! Once you have created a [`Migrator`] you can use it to migrate a database by
 //! calling [`Migrator::run`]. You can control what this will do by configuring
@@ -114,6 +134,52 @@
 //! # }
 //! ```
 //!
+//! ## Repairing checksums
+//! If a migration that has already been applied to the database is changed
+//! then its checksum will no longer match the one stored in the database and
+//! [`Migrator::run`] will return an error. If the change was intentional (e.g.
+//! a comment was fixed up) then running with [`ChecksumMode::Repair`] will
+//! update the stored checksums to match the current migrations.
+//! ```
+//! # use sqlx::Connection;
+//! use durable_migrate::{ChecksumMode, Migrator, Options};
+//!
+//! # async fn wrap() -> Result<(), Box<dyn std::error::Error>> {
+//! let mut conn = sqlx::PgConnection::connect("postgres://your-database.example.com").await?;
+//! let migrator = Migrator::from_dir("migrations")?;
+//! let options = Options {
+//!     checksum_mode: ChecksumMode::Repair,
+//!     ..Options::default()
+//! };
+//!
+//! migrator.run(&mut conn, &options).await?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! ## Adopting an existing database
+//! If the database already has the schema created by some of the migrations
+//! then they can be recorded as applied, without being run, by using
+//! [`Options::baseline`].
+//! ```
+//! # use sqlx::Connection;
+//! use durable_migrate::{Migrator, Options};
+//!
+//! # async fn wrap() -> Result<(), Box<dyn std::error::Error>> {
+//! let mut conn = sqlx::PgConnection::connect("postgres://your-database.example.com").await?;
+//! let migrator = Migrator::from_dir("migrations")?;
+//!
+//! migrator.run(&mut conn, &Options::baseline(5)).await?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! ## Running migrations concurrently
+//! [`Migrator::run`] holds a postgres advisory lock for as long as it is
+//! running, so if multiple migrators are started at the same time then they
+//! will run one after the other. The lock key and how long to wait for it can
+//! be configured via [`Options::lock`].
+//!
 //! # Embedding migrations
 //! This crate supports embedding migrations via a build script. To do so, you
 //! will want a build script that looks roughly like
@@ -135,6 +201,13 @@
 //! }
 //! ```
 //!
+//! Rust migrations are registered by the path at which the function can be
+//! found from the module that includes the generated code:
+//! ```
+//! # use durable_migrate::EmbedOptions;
+//! let options = EmbedOptions::default().function(3, "crate::migrations::backfill_names");
+//! ```
+//!
 //! Then, you can include the migrations by doing
 //! ```no_compile
 //! include!(concat!(env!("OUT_DIR"), "/migrations.rs"));
@@ -151,7 +224,11 @@
 
 use std::borrow::Cow;
 use std::collections::BTreeMap;
+use std::fmt;
+use std::future::Future;
 use std::path::{Path, PathBuf};
+use std::pin::Pin;
+use std::time::Duration;
 
 /// Helper macro used to silence `unused_import` warnings when an item is
 /// only imported in order to refer to it within a doc comment.
@@ -168,9 +245,17 @@ macro_rules! used_in_docs {
 
 #[cfg(feature = "migrate")]
 mod apply;
+mod compat;
 mod error;
+mod rename;
+mod status;
 
+pub use self::compat::compat_hash;
 pub use self::error::{DivergingMigrationError, Error, ErrorKind, MigratorFromDirError};
+#[cfg(feature = "migrate")]
+pub use self::rename::Renamed;
+pub use self::rename::SchemaRename;
+pub use self::status::{ChecksumState, MigrationReport, MigrationState, MigrationStatus};
 
 /// The migration target version that we want to bring the database to.
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
@@ -200,6 +285,64 @@ pub enum TransactionMode {
     Individual,
 }
 
+/// Controls what happens when a migration that has already been applied to the
+/// database has been changed since.
+///
+/// Changes are detected by comparing the checksum of the migration SQL against
+/// the checksum that was recorded when it was applied. Migrations that were
+/// applied before checksums were recorded have their checksum filled in the
+/// next time the migrator runs.
+#[non_exhaustive]
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub enum ChecksumMode {
+    /// Emit an error if the checksums do not match.
+    #[default]
+    Verify,
+
+    /// Log a warning if the checksums do not match, but continue anyway.
+    Warn,
+
+    /// Update the checksums stored in the database to match the current
+    /// migrations.
+    ///
+    /// Use this once you have confirmed that the changes to the applied
+    /// migrations are intentional.
+    Repair,
+}
+
+/// The postgres advisory lock that is held while migrations are being run.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MigrationLock {
+    /// The key of the advisory lock.
+    ///
+    /// All migrators that manage the same migration table should use the same
+    /// key. By default, this is [`MigrationLock::DEFAULT_KEY`].
+    pub key: i64,
+
+    /// How long to wait for the lock if another migrator is holding it.
+    ///
+    /// If the lock is not acquired within this time then migrating fails with
+    /// an error of kind [`ErrorKind::LockTimeout`]. If `None`, then this waits
+    /// indefinitely.
+    ///
+    /// This is `None` by default.
+    pub timeout: Option<Duration>,
+}
+
+impl MigrationLock {
+    /// The lock key used by default. This is the string `durable` as bytes.
+    pub const DEFAULT_KEY: i64 = 0x0064_7572_6162_6c65;
+}
+
+impl Default for MigrationLock {
+    fn default() -> Self {
+        Self {
+            key: Self::DEFAULT_KEY,
+            timeout: None,
+        }
+    }
+}
+
 /// Describes a table in SQL.
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct Table {
@@ -268,6 +411,12 @@ pub struct Options {
     /// How migrations are run within transactions.
     pub transaction_mode: TransactionMode,
 
+    /// What to do if an applied migration has been changed since it was
+    /// applied.
+    ///
+    /// This is [`ChecksumMode::Verify`] by default.
+    pub checksum_mode: ChecksumMode,
+
     /// The table to store migration data in.
     ///
     /// If this is ever changed, then the migration framework will forget all
@@ -276,6 +425,67 @@ pub struct Options {
     ///
     /// By default this is `migrations`.
     pub migration_table: Table,
+
+    /// Record all migrations up to and including this version as applied
+    /// without actually running them.
+    ///
+    /// This is meant for adopting the migrator on a database that already has
+    /// the schema from those migrations, e.g. because it was created by hand
+    /// or by a different tool. Migrations that have already been recorded as
+    /// applied are left as-is. See [`Options::baseline`].
+    ///
+    /// This is `None` by default.
+    pub baseline: Option<u64>,
+
+    /// The advisory lock to hold while migrating.
+    ///
+    /// This prevents multiple migrators from running against the same database
+    /// at the same time. If `None`, then no lock is taken.
+    ///
+    /// By default, this uses [`MigrationLock::default`].
+    pub lock: Option<MigrationLock>,
+
+    /// Move the objects created by the migrations into a different schema.
+    ///
+    /// The SQL of each migration is rewritten with [`SchemaRename::rewrite`]
+    /// right before it is run. Checksums and the revert migrations stored in
+    /// the database still use the original SQL, so the same database can be
+    /// migrated with or without a rename. Rust migrations are not affected.
+    ///
+    /// This is `None` by default.
+    pub schema_rename: Option<SchemaRename>,
+}
+
+impl Options {
+    /// Options that record all migrations up to and including `version` as
+    /// applied without running them.
+    ///
+    /// The target is also set to `version` so that no other migrations are
+    /// run. Set [`Options::target`] as well to continue migrating past the
+    /// baseline:
+    /// ```
+    /// use durable_migrate::{Options, Target};
+    ///
+    /// let options = Options {
+    ///     target: Target::Latest,
+    ///     ..Options::baseline(5)
+    /// };
+    /// ```
+    pub fn baseline(version: u64) -> Self {
+        Self {
+            target: Target::Version(version),
+            baseline: Some(version),
+            ..Self::default()
+        }
+    }
+
+    #[cfg_attr(not(feature = "migrate"), allow(dead_code))]
+    fn rewrite<'a>(&self, sql: &'a str) -> Cow<'a, str> {
+        match &self.schema_rename {
+            Some(rename) => rename.rewrite(sql),
+            None => Cow::Borrowed(sql),
+        }
+    }
 }
 
 impl Default for Options {
@@ -287,17 +497,82 @@ impl Default for Options {
             target: Target::Latest,
             migration_table: Table::plain("migrations"),
             transaction_mode: TransactionMode::Single,
+            checksum_mode: ChecksumMode::Verify,
+            baseline: None,
+            lock: Some(MigrationLock::default()),
+            schema_rename: None,
         }
     }
 }
 
+/// The error type returned by Rust migrations.
+pub type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+/// The future returned by a [`MigrationFn`].
+pub type MigrationFuture<'c> = Pin<Box<dyn Future<Output = Result<(), BoxError>> + Send + 'c>>;
+
+/// A migration written in Rust.
+///
+/// It is passed the connection that the migration is being run on, which will
+/// be within a transaction.
+///
+/// ```
+/// use durable_migrate::MigrationFuture;
+///
+/// fn backfill_names(conn: &mut sqlx::PgConnection) -> MigrationFuture<'_> {
+///     Box::pin(async move {
+///         sqlx::query("UPDATE users SET name = email WHERE name IS NULL")
+///             .execute(&mut *conn)
+///             .await?;
+///
+///         Ok(())
+///     })
+/// }
+/// ```
+#[cfg(feature = "migrate")]
+pub type MigrationFn = for<'c> fn(&'c mut sqlx::PgConnection) -> MigrationFuture<'c>;
+
+/// A migration written in Rust.
+///
+/// Rust migrations can only be run when the `migrate` feature is enabled.
+/// Without it, this type only exists so that [`Migration`] has the same fields
+/// and cannot be used to construct a Rust migration.
+#[cfg(not(feature = "migrate"))]
+pub type MigrationFn = for<'c> fn(&'c mut std::convert::Infallible) -> MigrationFuture<'c>;
+
 /// A single migration.
-#[derive(Clone, Debug)]
+#[derive(Clone)]
 pub struct Migration {
     pub version: u64,
     pub name: Cow<'static, str>,
     pub sql: Cow<'static, str>,
     pub revert: Option<Cow<'static, str>>,
+
+    /// A Rust function to run after `sql` when applying this migration.
+    pub code: Option<MigrationFn>,
+}
+
+impl fmt::Debug for Migration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Migration")
+            .field("version", &self.version)
+            .field("name", &self.name)
+            .field("sql", &self.sql)
+            .field("revert", &self.revert)
+            .field("code", &self.code.is_some())
+            .finish()
+    }
+}
+
+impl Migration {
+    /// The SHA-256 checksum of the SQL for this migration.
+    ///
+    /// This is recorded in the database when the migration is applied.
+    pub fn checksum(&self) -> [u8; 32] {
+        use sha2::{Digest, Sha256};
+
+        Sha256::digest(self.sql.as_bytes()).into()
+    }
 }
 
 struct MigrationSource {
@@ -487,6 +762,7 @@ impl Migrator {
                 name: Cow::Owned(up.name.clone()),
                 sql: Cow::Owned(sql),
                 revert: revert.map(Cow::Owned),
+                code: None,
             });
 
             sources.push(MigrationSource { up: up.path, down });
@@ -541,6 +817,28 @@ pub struct EmbedOptions {
     ///
     /// Defaults to `::durable_migrate`
     pub crate_path: Cow<'static, str>,
+
+    /// Paths to the Rust functions to use for migrations, by version.
+    ///
+    /// See [`EmbedOptions::function`].
+    pub functions: BTreeMap<u64, Cow<'static, str>>,
+}
+
+impl EmbedOptions {
+    /// Register a Rust function to run as part of the migration with version
+    /// `version`.
+    ///
+    /// `path` is the path to a function with the signature of a
+    /// [`MigrationFn`], relative to the location where the generated code is
+    /// included.
+    ///
+    /// # Panics
+    /// [`Migrator::embed`] will panic if there is no migration with this
+    /// version.
+    pub fn function(mut self, version: u64, path: impl Into<Cow<'static, str>>) -> Self {
+        self.functions.insert(version, path.into());
+        self
+    }
 }
 
 impl Default for EmbedOptions {
@@ -550,6 +848,7 @@ impl Default for EmbedOptions {
             use_includes: true,
             print_cargo_directives: true,
             crate_path: "::durable_migrate".into(),
+            functions: BTreeMap::new(),
         }
     }
 }
@@ -566,6 +865,12 @@ impl Migrator {
         let mut content = String::new();
         let sources = self.sources.as_deref();
 
+        for &version in options.functions.keys() {
+            if !self.migrations.iter().any(|m| m.version == version) {
+                panic!("a function was registered for migration {version} but it does not exist");
+            }
+        }
+
         let include_path = |path: &Path| {
             format!(
                 r#"include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/", {:?}))"#,
@@ -605,6 +910,10 @@ pub const {name}: {path}::Migrator = {path}::Migrator::from_static({{
                 }
                 None => "None".into(),
             };
+            let code = match options.functions.get(&migration.version) {
+                Some(path) => format!("Some({path})"),
+                None => "None".into(),
+            };
 
             write!(
                 content,
@@ -613,6 +922,7 @@ pub const {name}: {path}::Migrator = {path}::Migrator::from_static({{
             name: Cow::Borrowed({name:?}),
             sql: Cow::Borrowed({up}),
             revert: {down},
+            code: {code},
         }},
 ",
                 path = options.crate_path,
@@ -0,0 +1,444 @@
+use std::borrow::Cow;
+
+/// Moves SQL written against one schema into a different schema.
+///
+/// This allows SQL that refers to its tables by their qualified name (e.g.
+/// `durable.task`) to be used with a copy of those tables that lives in some
+/// other schema. [`SchemaRename::rewrite`] replaces
+/// - qualified names that use the original schema, quoted or not,
+/// - the schema name in `CREATE SCHEMA` and `DROP SCHEMA` statements, and
+/// - the `<schema>:` prefix of channel names passed to `pg_notify`.
+///
+/// Any other mention of the schema name, such as within a string literal, is
+/// left as-is. Renaming a schema to itself does not change the SQL at all.
+///
+/// ```
+/// use durable_migrate::SchemaRename;
+///
+/// let rename = SchemaRename::new("durable", "staging");
+///
+/// assert_eq!(
+///     rename.rewrite("SELECT * FROM durable.task WHERE id = $1"),
+///     r#"SELECT * FROM "staging".task WHERE id = $1"#
+/// );
+/// assert_eq!(rename.channel("durable:task"), "staging:task");
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct SchemaRename {
+    from: Cow<'static, str>,
+    to: Cow<'static, str>,
+}
+
+impl SchemaRename {
+    /// Rewrite SQL that refers to the `from` schema to use the `to` schema
+    /// instead.
+    pub fn new(from: impl Into<Cow<'static, str>>, to: impl Into<Cow<'static, str>>) -> Self {
+        Self {
+            from: from.into(),
+            to: to.into(),
+        }
+    }
+
+    /// A rename that leaves the `schema` schema in place.
+    pub const fn identity(schema: &'static str) -> Self {
+        Self {
+            from: Cow::Borrowed(schema),
+            to: Cow::Borrowed(schema),
+        }
+    }
+
+    /// The schema that SQL is written against.
+    pub fn from(&self) -> &str {
+        &self.from
+    }
+
+    /// The schema that SQL is moved into.
+    pub fn to(&self) -> &str {
+        &self.to
+    }
+
+    /// Whether this rename leaves SQL unchanged.
+    pub fn is_identity(&self) -> bool {
+        self.from == self.to
+    }
+
+    /// Rewrite `sql` so that it refers to the new schema.
+    pub fn rewrite<'a>(&self, sql: &'a str) -> Cow<'a, str> {
+        if self.is_identity() || self.from.is_empty() {
+            return Cow::Borrowed(sql);
+        }
+
+        let mut output = String::new();
+        let mut last = 0;
+
+        for (start, _) in sql.match_indices(&*self.from) {
+            let end = start + self.from.len();
+            if start < last {
+                continue;
+            }
+
+            let before = &sql[..start];
+            let after = &sql[end..];
+
+            // The name as a quoted identifier: "durable"
+            let quoted = before.ends_with('"') && after.starts_with('"');
+            let (span, before, after) = match quoted {
+                true => (start - 1..end + 1, &before[..start - 1], &after[1..]),
+                false => (start..end, before, after),
+            };
+
+            if before.ends_with(|c| is_identifier_char(c) || matches!(c, '.' | '"'))
+                || after.starts_with(|c| is_identifier_char(c) || c == '"')
+            {
+                continue;
+            }
+
+            // A channel name: pg_notify('durable:task', ...)
+            if !quoted && before.ends_with('\'') && after.starts_with(':') {
+                let call = before[..before.len() - 1].trim_end();
+                if !ends_with_ignore_case(call, "pg_notify(") {
+                    continue;
+                }
+
+                output.push_str(&sql[last..start]);
+                output.push_str(&self.to.replace('\'', "''"));
+                last = end;
+                continue;
+            }
+
+            let qualified = after.starts_with('.');
+            let statement = before.trim_end();
+            let schema = ["schema", "schema if exists", "schema if not exists"]
+                .iter()
+                .any(|keyword| ends_with_keyword(statement, keyword));
+
+            if !qualified && !schema {
+                continue;
+            }
+
+            output.push_str(&sql[last..span.start]);
+            output.push('"');
+            output.push_str(&self.to.replace('"', "\"\""));
+            output.push('"');
+            last = span.end;
+        }
+
+        if last == 0 {
+            return Cow::Borrowed(sql);
+        }
+
+        output.push_str(&sql[last..]);
+        Cow::Owned(output)
+    }
+
+    /// Rename a notification channel of the form `<schema>:<name>`.
+    ///
+    /// Channels that do not start with the original schema are returned
+    /// unchanged.
+    pub fn channel<'a>(&self, channel: &'a str) -> Cow<'a, str> {
+        if self.is_identity() {
+            return Cow::Borrowed(channel);
+        }
+
+        match channel
+            .strip_prefix(&*self.from)
+            .and_then(|rest| rest.strip_prefix(':'))
+        {
+            Some(name) => Cow::Owned(format!("{}:{name}", self.to)),
+            None => Cow::Borrowed(channel),
+        }
+    }
+
+    /// Wrap `executor` so that all queries run through it are rewritten by
+    /// this rename.
+    ///
+    /// ```
+    /// # async fn wrap(pool: sqlx::PgPool) -> Result<(), sqlx::Error> {
+    /// use durable_migrate::SchemaRename;
+    ///
+    /// let rename = SchemaRename::new("durable", "staging");
+    /// sqlx::query("DELETE FROM durable.task WHERE state = 'complete'")
+    ///     .execute(rename.on(&pool))
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "migrate")]
+    pub fn on<E>(&self, executor: E) -> Renamed<E> {
+        Renamed {
+            rename: self.clone(),
+            inner: executor,
+        }
+    }
+}
+
+impl Default for SchemaRename {
+    /// Leaves the `durable` schema in place.
+    fn default() -> Self {
+        Self::identity("durable")
+    }
+}
+
+fn is_identifier_char(c: char) -> bool {
+    c.is_alphanumeric() || matches!(c, '_' | '$')
+}
+
+fn ends_with_ignore_case(text: &str, suffix: &str) -> bool {
+    text.len() >= suffix.len()
+        && text.is_char_boundary(text.len() - suffix.len())
+        && text[text.len() - suffix.len()..].eq_ignore_ascii_case(suffix)
+}
+
+/// Whether `text` ends with the words in `keyword`, separated by any amount
+/// of whitespace.
+fn ends_with_keyword(text: &str, keyword: &str) -> bool {
+    let mut text = text;
+    for word in keyword.split(' ').rev() {
+        text = text.trim_end();
+        if !ends_with_ignore_case(text, word) {
+            return false;
+        }
+
+        text = &text[..text.len() - word.len()];
+        if text.ends_with(is_identifier_char) {
+            return false;
+        }
+    }
+
+    true
+}
+
+#[cfg(feature = "migrate")]
+mod executor {
+    use std::borrow::Cow;
+
+    use futures_core::future::BoxFuture;
+    use futures_core::stream::BoxStream;
+    use futures_util::TryStreamExt;
+    use sqlx::postgres::{PgArguments, PgQueryResult, PgRow, PgStatement, PgTypeInfo};
+    use sqlx::{Describe, Either, Error, Execute, Executor, Postgres, Statement};
+
+    use super::SchemaRename;
+
+    /// An executor that rewrites the queries run through it.
+    ///
+    /// This is created by [`SchemaRename::on`].
+    #[derive(Debug)]
+    pub struct Renamed<E> {
+        pub(super) rename: SchemaRename,
+        pub(super) inner: E,
+    }
+
+    /// A query with its SQL replaced.
+    struct Rewritten<'q> {
+        sql: &'q str,
+        arguments: Option<PgArguments>,
+        persistent: bool,
+    }
+
+    impl<'q> Execute<'q, Postgres> for Rewritten<'q> {
+        fn sql(&self) -> &'q str {
+            self.sql
+        }
+
+        fn statement(&self) -> Option<&PgStatement<'q>> {
+            None
+        }
+
+        fn take_arguments(&mut self) -> Result<Option<PgArguments>, sqlx::error::BoxDynError> {
+            Ok(self.arguments.take())
+        }
+
+        fn persistent(&self) -> bool {
+            self.persistent
+        }
+    }
+
+    impl<'c, E> Executor<'c> for Renamed<E>
+    where
+        E: Executor<'c, Database = Postgres> + 'c,
+    {
+        type Database = Postgres;
+
+        fn fetch_many<'e, 'q: 'e, Q>(
+            self,
+            mut query: Q,
+        ) -> BoxStream<'e, Result<Either<PgQueryResult, PgRow>, Error>>
+        where
+            'c: 'e,
+            Q: 'q + Execute<'q, Postgres>,
+        {
+            let Cow::Owned(sql) = self.rename.rewrite(query.sql()) else {
+                return self.inner.fetch_many(query);
+            };
+
+            let persistent = query.persistent();
+            let arguments = query.take_arguments().map_err(Error::Encode);
+            let inner = self.inner;
+
+            Box::pin(async_stream::try_stream! {
+                let query = Rewritten {
+                    sql: &sql,
+                    arguments: arguments?,
+                    persistent,
+                };
+
+                let mut stream = inner.fetch_many(query);
+                while let Some(item) = stream.try_next().await? {
+                    yield item;
+                }
+            })
+        }
+
+        fn fetch_optional<'e, 'q: 'e, Q>(
+            self,
+            mut query: Q,
+        ) -> BoxFuture<'e, Result<Option<PgRow>, Error>>
+        where
+            'c: 'e,
+            Q: 'q + Execute<'q, Postgres>,
+        {
+            let Cow::Owned(sql) = self.rename.rewrite(query.sql()) else {
+                return self.inner.fetch_optional(query);
+            };
+
+            let persistent = query.persistent();
+            let arguments = query.take_arguments().map_err(Error::Encode);
+            let inner = self.inner;
+
+            Box::pin(async move {
+                let query = Rewritten {
+                    sql: &sql,
+                    arguments: arguments?,
+                    persistent,
+                };
+
+                inner.fetch_optional(query).await
+            })
+        }
+
+        fn prepare_with<'e, 'q: 'e>(
+            self,
+            sql: &'q str,
+            parameters: &'e [PgTypeInfo],
+        ) -> BoxFuture<'e, Result<PgStatement<'q>, Error>>
+        where
+            'c: 'e,
+        {
+            let Cow::Owned(sql) = self.rename.rewrite(sql) else {
+                return self.inner.prepare_with(sql, parameters);
+            };
+            let inner = self.inner;
+
+            Box::pin(async move {
+                let statement = inner.prepare_with(&sql, parameters).await?;
+                Ok(Statement::to_owned(&statement))
+            })
+        }
+
+        fn describe<'e, 'q: 'e>(
+            self,
+            sql: &'q str,
+        ) -> BoxFuture<'e, Result<Describe<Postgres>, Error>>
+        where
+            'c: 'e,
+        {
+            let Cow::Owned(sql) = self.rename.rewrite(sql) else {
+                return self.inner.describe(sql);
+            };
+            let inner = self.inner;
+
+            Box::pin(async move { inner.describe(&sql).await })
+        }
+    }
+}
+
+#[cfg(feature = "migrate")]
+pub use self::executor::Renamed;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rewrite_identity() {
+        let rename = SchemaRename::default();
+        let sql = "SELECT * FROM durable.task";
+
+        assert!(matches!(rename.rewrite(sql), Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn rewrite_qualified_names() {
+        let rename = SchemaRename::new("durable", "tenant_a");
+
+        assert_eq!(
+            rename.rewrite(
+                r#"UPDATE durable.task SET state = 'ready'::durable.task_state FROM "durable"."worker" w"#
+            ),
+            r#"UPDATE "tenant_a".task SET state = 'ready'::"tenant_a".task_state FROM "tenant_a"."worker" w"#
+        );
+        assert_eq!(
+            rename.rewrite("SELECT to_regclass('durable.migrations')"),
+            r#"SELECT to_regclass('"tenant_a".migrations')"#
+        );
+    }
+
+    #[test]
+    fn rewrite_ignores_other_identifiers() {
+        let rename = SchemaRename::new("durable", "tenant_a");
+        let sql = "SELECT durable_id, t.durable, notdurable.x, 'durable' FROM durable_task t";
+
+        assert_eq!(rename.rewrite(sql), sql);
+    }
+
+    #[test]
+    fn rewrite_schema_statements() {
+        let rename = SchemaRename::new("durable", "tenant_a");
+
+        assert_eq!(
+            rename.rewrite("CREATE SCHEMA IF NOT EXISTS durable;"),
+            r#"CREATE SCHEMA IF NOT EXISTS "tenant_a";"#
+        );
+        assert_eq!(
+            rename.rewrite("drop schema \"durable\" cascade"),
+            r#"drop schema "tenant_a" cascade"#
+        );
+    }
+
+    #[test]
+    fn rewrite_channels() {
+        let rename = SchemaRename::new("durable", "tenant_a");
+
+        assert_eq!(
+            rename.rewrite("PERFORM pg_notify(\n    'durable:task', NEW.id::text)"),
+            "PERFORM pg_notify(\n    'tenant_a:task', NEW.id::text)"
+        );
+
+        // Event names that happen to share the prefix are left alone.
+        let sql = "INSERT INTO durable.notification(event) VALUES ('durable:child-complete')";
+        assert_eq!(
+            rename.rewrite(sql),
+            r#"INSERT INTO "tenant_a".notification(event) VALUES ('durable:child-complete')"#
+        );
+    }
+
+    #[test]
+    fn rewrite_quotes_the_schema() {
+        let rename = SchemaRename::new("durable", "My \"Schema\"");
+
+        assert_eq!(
+            rename.rewrite("SELECT 1 FROM durable.task"),
+            r#"SELECT 1 FROM "My ""Schema""".task"#
+        );
+    }
+
+    #[test]
+    fn channel() {
+        let rename = SchemaRename::new("durable", "tenant_a");
+
+        assert_eq!(rename.channel("durable:task"), "tenant_a:task");
+        assert_eq!(rename.channel("other:task"), "other:task");
+        assert_eq!(rename.channel("durable"), "durable");
+    }
+}
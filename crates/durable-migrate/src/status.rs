@@ -0,0 +1,111 @@
+use std::time::SystemTime;
+
+use crate::Migrator;
+
+used_in_docs!(Migrator);
+
+/// The status of every migration, as returned by [`Migrator::status`].
+#[derive(Clone, Debug)]
+pub struct MigrationReport {
+    /// All migrations that are either known to the migrator or have been
+    /// applied to the database, ordered by version.
+    pub migrations: Vec<MigrationStatus>,
+}
+
+impl MigrationReport {
+    /// The version of the latest migration applied to the database.
+    pub fn current_version(&self) -> Option<u64> {
+        self.migrations
+            .iter()
+            .filter(|m| m.state != MigrationState::Pending)
+            .map(|m| m.version)
+            .max()
+    }
+
+    /// Migrations that have not yet been applied to the database.
+    pub fn pending(&self) -> impl Iterator<Item = &MigrationStatus> {
+        self.by_state(MigrationState::Pending)
+    }
+
+    /// Migrations that have been applied to the database.
+    pub fn applied(&self) -> impl Iterator<Item = &MigrationStatus> {
+        self.by_state(MigrationState::Applied)
+    }
+
+    /// Migrations that have been applied to the database but are not known to
+    /// the migrator.
+    pub fn unknown(&self) -> impl Iterator<Item = &MigrationStatus> {
+        self.by_state(MigrationState::Unknown)
+    }
+
+    /// Applied migrations that have been modified since they were applied.
+    pub fn modified(&self) -> impl Iterator<Item = &MigrationStatus> {
+        self.migrations
+            .iter()
+            .filter(|m| m.checksum == Some(ChecksumState::Modified))
+    }
+
+    /// Whether every migration known to the migrator has been applied to the
+    /// database.
+    pub fn is_up_to_date(&self) -> bool {
+        self.pending().next().is_none()
+    }
+
+    fn by_state(&self, state: MigrationState) -> impl Iterator<Item = &MigrationStatus> {
+        self.migrations.iter().filter(move |m| m.state == state)
+    }
+}
+
+/// The status of a single migration.
+#[derive(Clone, Debug)]
+pub struct MigrationStatus {
+    pub version: u64,
+    pub name: String,
+    pub state: MigrationState,
+
+    /// When this migration was applied.
+    ///
+    /// This is `None` for migrations that have not been applied, as well as
+    /// for migrations that were applied before this was recorded.
+    pub applied_at: Option<SystemTime>,
+
+    /// Whether the migration has changed since it was applied.
+    ///
+    /// This is only present for applied migrations that are known to the
+    /// migrator.
+    pub checksum: Option<ChecksumState>,
+}
+
+/// Whether a migration has been applied to the database.
+#[non_exhaustive]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum MigrationState {
+    /// The migration has been applied to the database.
+    Applied,
+
+    /// The migration is known to the migrator but has not been applied to the
+    /// database.
+    Pending,
+
+    /// The migration has been applied to the database but is not known to the
+    /// migrator.
+    ///
+    /// This usually means that the database was migrated by a newer version of
+    /// the application.
+    Unknown,
+}
+
+/// How the checksum stored in the database for an applied migration compares
+/// to the checksum of the migration.
+#[non_exhaustive]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum ChecksumState {
+    /// The checksums match.
+    Valid,
+
+    /// The migration has been modified since it was applied.
+    Modified,
+
+    /// No checksum was recorded when the migration was applied.
+    NotRecorded,
+}
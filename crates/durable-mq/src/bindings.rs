@@ -0,0 +1,272 @@
+#[allow(dead_code)]
+pub mod durable {
+    #[allow(dead_code)]
+    pub mod core {
+        #[allow(dead_code, clippy::all)]
+        pub mod mq {
+            #[used]
+            #[doc(hidden)]
+            static __FORCE_SECTION_REF: fn() = super::super::super::__link_custom_section_describing_imports;
+            use super::super::super::_rt;
+            /// A header attached to a published message.
+            #[derive(Clone)]
+            pub struct Header<'a> {
+                pub name: &'a str,
+                pub value: &'a [u8],
+            }
+            impl<'a> ::core::fmt::Debug for Header<'a> {
+                fn fmt(
+                    &self,
+                    f: &mut ::core::fmt::Formatter<'_>,
+                ) -> ::core::fmt::Result {
+                    f.debug_struct("Header")
+                        .field("name", &self.name)
+                        .field("value", &self.value)
+                        .finish()
+                }
+            }
+            /// Errors that can occur when publishing a message.
+            #[derive(Clone)]
+            pub enum MqError {
+                /// The worker does not have a message queue configured.
+                NotConfigured,
+                /// The worker was built without support for the configured message
+                /// queue.
+                Unsupported(_rt::String),
+                /// The message could not be published as-is (e.g. a header value was
+                /// not valid for the message queue).
+                InvalidMessage(_rt::String),
+                /// The message queue could not be reached, or it did not acknowledge
+                /// the message.
+                Publish(_rt::String),
+            }
+            impl ::core::fmt::Debug for MqError {
+                fn fmt(
+                    &self,
+                    f: &mut ::core::fmt::Formatter<'_>,
+                ) -> ::core::fmt::Result {
+                    match self {
+                        MqError::NotConfigured => {
+                            f.debug_tuple("MqError::NotConfigured").finish()
+                        }
+                        MqError::Unsupported(e) => {
+                            f.debug_tuple("MqError::Unsupported").field(e).finish()
+                        }
+                        MqError::InvalidMessage(e) => {
+                            f.debug_tuple("MqError::InvalidMessage").field(e).finish()
+                        }
+                        MqError::Publish(e) => {
+                            f.debug_tuple("MqError::Publish").field(e).finish()
+                        }
+                    }
+                }
+            }
+            impl ::core::fmt::Display for MqError {
+                fn fmt(
+                    &self,
+                    f: &mut ::core::fmt::Formatter<'_>,
+                ) -> ::core::fmt::Result {
+                    write!(f, "{:?}", self)
+                }
+            }
+            impl std::error::Error for MqError {}
+            #[allow(unused_unsafe, clippy::all)]
+            /// Publish a message to `topic`.
+            ///
+            /// Every message is published with a `durable-idempotency-key` header
+            /// that is derived from the task and transaction that published it. If
+            /// the worker crashes after publishing a message but before recording
+            /// that it was published, then the message that is published when the
+            /// transaction is retried has the same idempotency key so consumers can
+            /// de-duplicate it.
+            ///
+            /// # Traps
+            /// This function will trap if called from outside of a durable transaction.
+            pub fn publish(
+                topic: &str,
+                payload: &[u8],
+                headers: &[Header<'_>],
+            ) -> Result<(), MqError> {
+                unsafe {
+                    #[repr(align(4))]
+                    struct RetArea([::core::mem::MaybeUninit<u8>; 16]);
+                    let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 16]);
+                    let vec0 = topic;
+                    let ptr0 = vec0.as_ptr().cast::<u8>();
+                    let len0 = vec0.len();
+                    let vec1 = payload;
+                    let ptr1 = vec1.as_ptr().cast::<u8>();
+                    let len1 = vec1.len();
+                    let vec5 = headers;
+                    let len5 = vec5.len();
+                    let layout5 = _rt::alloc::Layout::from_size_align_unchecked(
+                        vec5.len() * 16,
+                        4,
+                    );
+                    let result5 = if layout5.size() != 0 {
+                        let ptr = _rt::alloc::alloc(layout5).cast::<u8>();
+                        if ptr.is_null() {
+                            _rt::alloc::handle_alloc_error(layout5);
+                        }
+                        ptr
+                    } else {
+                        { ::core::ptr::null_mut() }
+                    };
+                    for (i, e) in vec5.into_iter().enumerate() {
+                        let base = result5.add(i * 16);
+                        {
+                            let Header { name: name2, value: value2 } = e;
+                            let vec3 = name2;
+                            let ptr3 = vec3.as_ptr().cast::<u8>();
+                            let len3 = vec3.len();
+                            *base.add(4).cast::<usize>() = len3;
+                            *base.add(0).cast::<*mut u8>() = ptr3.cast_mut();
+                            let vec4 = value2;
+                            let ptr4 = vec4.as_ptr().cast::<u8>();
+                            let len4 = vec4.len();
+                            *base.add(12).cast::<usize>() = len4;
+                            *base.add(8).cast::<*mut u8>() = ptr4.cast_mut();
+                        }
+                    }
+                    let ptr6 = ret_area.0.as_mut_ptr().cast::<u8>();
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(wasm_import_module = "durable:core/mq@2.7.0")]
+                    extern "C" {
+                        #[link_name = "publish"]
+                        fn wit_import(
+                            _: *mut u8,
+                            _: usize,
+                            _: *mut u8,
+                            _: usize,
+                            _: *mut u8,
+                            _: usize,
+                            _: *mut u8,
+                        );
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    fn wit_import(
+                        _: *mut u8,
+                        _: usize,
+                        _: *mut u8,
+                        _: usize,
+                        _: *mut u8,
+                        _: usize,
+                        _: *mut u8,
+                    ) {
+                        unreachable!()
+                    }
+                    wit_import(
+                        ptr0.cast_mut(),
+                        len0,
+                        ptr1.cast_mut(),
+                        len1,
+                        result5,
+                        len5,
+                        ptr6,
+                    );
+                    let l7 = i32::from(*ptr6.add(0).cast::<u8>());
+                    if layout5.size() != 0 {
+                        _rt::alloc::dealloc(result5.cast(), layout5);
+                    }
+                    match l7 {
+                        0 => {
+                            let e = ();
+                            Ok(e)
+                        }
+                        1 => {
+                            let e = {
+                                let l8 = i32::from(*ptr6.add(4).cast::<u8>());
+                                let v18 = match l8 {
+                                    0 => MqError::NotConfigured,
+                                    1 => {
+                                        let e18 = {
+                                            let l9 = *ptr6.add(8).cast::<*mut u8>();
+                                            let l10 = *ptr6.add(12).cast::<usize>();
+                                            let len11 = l10;
+                                            let bytes11 = _rt::Vec::from_raw_parts(
+                                                l9.cast(),
+                                                len11,
+                                                len11,
+                                            );
+                                            _rt::string_lift(bytes11)
+                                        };
+                                        MqError::Unsupported(e18)
+                                    }
+                                    2 => {
+                                        let e18 = {
+                                            let l12 = *ptr6.add(8).cast::<*mut u8>();
+                                            let l13 = *ptr6.add(12).cast::<usize>();
+                                            let len14 = l13;
+                                            let bytes14 = _rt::Vec::from_raw_parts(
+                                                l12.cast(),
+                                                len14,
+                                                len14,
+                                            );
+                                            _rt::string_lift(bytes14)
+                                        };
+                                        MqError::InvalidMessage(e18)
+                                    }
+                                    n => {
+                                        debug_assert_eq!(n, 3, "invalid enum discriminant");
+                                        let e18 = {
+                                            let l15 = *ptr6.add(8).cast::<*mut u8>();
+                                            let l16 = *ptr6.add(12).cast::<usize>();
+                                            let len17 = l16;
+                                            let bytes17 = _rt::Vec::from_raw_parts(
+                                                l15.cast(),
+                                                len17,
+                                                len17,
+                                            );
+                                            _rt::string_lift(bytes17)
+                                        };
+                                        MqError::Publish(e18)
+                                    }
+                                };
+                                v18
+                            };
+                            Err(e)
+                        }
+                        _ => _rt::invalid_enum_discriminant(),
+                    }
+                }
+            }
+        }
+    }
+}
+mod _rt {
+    pub use alloc_crate::string::String;
+    pub use alloc_crate::alloc;
+    pub use alloc_crate::vec::Vec;
+    pub unsafe fn string_lift(bytes: Vec<u8>) -> String {
+        if cfg!(debug_assertions) {
+            String::from_utf8(bytes).unwrap()
+        } else {
+            String::from_utf8_unchecked(bytes)
+        }
+    }
+    pub unsafe fn invalid_enum_discriminant<T>() -> T {
+        if cfg!(debug_assertions) {
+            panic!("invalid enum discriminant")
+        } else {
+            core::hint::unreachable_unchecked()
+        }
+    }
+    extern crate alloc as alloc_crate;
+}
+#[cfg(target_arch = "wasm32")]
+#[link_section = "component-type:wit-bindgen:0.30.0:import-mq:encoded world"]
+#[doc(hidden)]
+pub static __WIT_BINDGEN_COMPONENT_TYPE: [u8; 356] = *b"\
+\0asm\x0d\0\x01\0\0\x19\x16wit-component-encoding\x04\0\x07\xe4\x01\x01A\x02\x01\
+A\x02\x01B\x09\x01p}\x01r\x02\x04names\x05value\0\x04\0\x06header\x03\0\x01\x01q\
+\x04\x0enot-configured\0\0\x0bunsupported\x01s\0\x0finvalid-message\x01s\0\x07pu\
+blish\x01s\0\x04\0\x08mq-error\x03\0\x03\x01p\x02\x01j\0\x01\x04\x01@\x03\x05top\
+ics\x07payload\0\x07headers\x05\0\x06\x04\0\x07publish\x01\x07\x03\x01\x15durabl\
+e:core/mq@2.7.0\x05\0\x04\x01\x1cdurable:core/import-mq@2.7.0\x04\0\x0b\x0f\x01\0\
+\x09import-mq\x03\0\0\0G\x09producers\x01\x0cprocessed-by\x02\x0dwit-component\x07\
+0.215.0\x10wit-bindgen-rust\x060.30.0";
+#[inline(never)]
+#[doc(hidden)]
+pub fn __link_custom_section_describing_imports() {
+    wit_bindgen_rt::maybe_link_cabi_realloc();
+}
@@ -0,0 +1,120 @@
+//! Publish messages to a message queue as part of your workflow.
+//!
+//! The message queue (Kafka or NATS), along with the credentials used to
+//! access it, is configured on the worker.
+//!
+//! ```no_run
+//! durable::mq::publish("orders.created", b"order 1234", &[("source", b"workflow")])
+//!     .expect("failed to publish the message");
+//!
+//! #[derive(serde::Serialize)]
+//! struct OrderShipped {
+//!     id: u64,
+//! }
+//!
+//! durable::mq::publish_json("orders.shipped", &OrderShipped { id: 1234 })
+//!     .expect("failed to publish the message");
+//! ```
+//!
+//! Each message is published within its own durable transaction, unless it is
+//! published from within an existing transaction. Once a message has been
+//! published that fact is recorded, so it will not be published again if the
+//! workflow is restarted.
+//!
+//! There is still a small window where the worker may crash after the message
+//! has been published but before that has been recorded. To handle this,
+//! every message carries a `durable-idempotency-key` header which is the same
+//! when the publish is retried. Consumers that need exactly-once processing
+//! should use it to de-duplicate messages.
+
+use std::fmt;
+
+use durable_core::transaction;
+use serde::{Deserialize, Serialize};
+
+mod bindings {
+    #![allow(unused_braces, clippy::all)]
+
+    include!("bindings.rs");
+
+    pub use self::durable::core::mq::*;
+}
+
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// Publish a message to `topic`, along with some headers.
+///
+/// `topic` is a Kafka topic or a NATS subject, depending on which message
+/// queue the worker has been configured with. NATS only supports header values
+/// that are valid UTF-8.
+pub fn publish(topic: &str, payload: impl AsRef<[u8]>, headers: &[(&str, &[u8])]) -> Result<()> {
+    let payload = payload.as_ref();
+    let label = format!("durable::mq::publish({topic})");
+
+    transaction::maybe_txn(&label, || {
+        let headers: Vec<_> = headers
+            .iter()
+            .map(|&(name, value)| bindings::Header { name, value })
+            .collect();
+
+        bindings::publish(topic, payload, &headers).map_err(Error::from)
+    })
+}
+
+/// Publish `value`, serialized as JSON, to `topic`.
+///
+/// The message is published with a `content-type` header of
+/// `application/json`.
+pub fn publish_json<T: Serialize + ?Sized>(topic: &str, value: &T) -> Result<()> {
+    let payload = serde_json::to_vec(value).map_err(|e| Error::InvalidMessage(e.to_string()))?;
+
+    publish(topic, payload, &[("content-type", b"application/json")])
+}
+
+/// An error that occurred while publishing a message.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+#[non_exhaustive]
+pub enum Error {
+    /// The worker does not have a message queue configured.
+    NotConfigured,
+
+    /// The worker was built without support for the configured message
+    /// queue.
+    Unsupported(String),
+
+    /// The message could not be published as-is.
+    InvalidMessage(String),
+
+    /// The message queue could not be reached, or it did not acknowledge the
+    /// message.
+    Publish(String),
+}
+
+impl From<bindings::MqError> for Error {
+    fn from(error: bindings::MqError) -> Self {
+        use bindings::MqError;
+
+        match error {
+            MqError::NotConfigured => Self::NotConfigured,
+            MqError::Unsupported(message) => Self::Unsupported(message),
+            MqError::InvalidMessage(message) => Self::InvalidMessage(message),
+            MqError::Publish(message) => Self::Publish(message),
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotConfigured => {
+                f.write_str("the worker does not have a message queue configured")
+            }
+            Self::Unsupported(message) => f.write_str(message),
+            Self::InvalidMessage(message) => write!(f, "invalid message: {message}"),
+            Self::Publish(message) => write!(f, "failed to publish the message: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
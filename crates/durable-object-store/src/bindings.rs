@@ -0,0 +1,1043 @@
+#[allow(dead_code)]
+pub mod durable {
+    #[allow(dead_code)]
+    pub mod core {
+        #[allow(dead_code, clippy::all)]
+        pub mod object_store {
+            #[used]
+            #[doc(hidden)]
+            static __FORCE_SECTION_REF: fn() = super::super::super::__link_custom_section_describing_imports;
+            use super::super::super::_rt;
+            /// Metadata about an object stored in a bucket.
+            #[derive(Clone)]
+            pub struct ObjectInfo {
+                /// The key of the object within its bucket.
+                pub key: _rt::String,
+                /// The size of the object, in bytes.
+                pub size: u64,
+                /// The entity tag of the object, if the store provided one.
+                pub etag: Option<_rt::String>,
+                /// The time at which the object was last modified, as an ISO 8601
+                /// timestamp.
+                pub last_modified: Option<_rt::String>,
+            }
+            impl ::core::fmt::Debug for ObjectInfo {
+                fn fmt(
+                    &self,
+                    f: &mut ::core::fmt::Formatter<'_>,
+                ) -> ::core::fmt::Result {
+                    f.debug_struct("ObjectInfo")
+                        .field("key", &self.key)
+                        .field("size", &self.size)
+                        .field("etag", &self.etag)
+                        .field("last-modified", &self.last_modified)
+                        .finish()
+                }
+            }
+            /// A single page of results from `list-objects`.
+            #[derive(Clone)]
+            pub struct ListPage {
+                /// The objects within this page.
+                pub objects: _rt::Vec<ObjectInfo>,
+                /// A token that can be passed to `list-objects` to fetch the next page.
+                ///
+                /// This is `none` if this is the last page.
+                pub next_token: Option<_rt::String>,
+            }
+            impl ::core::fmt::Debug for ListPage {
+                fn fmt(
+                    &self,
+                    f: &mut ::core::fmt::Formatter<'_>,
+                ) -> ::core::fmt::Result {
+                    f.debug_struct("ListPage")
+                        .field("objects", &self.objects)
+                        .field("next-token", &self.next_token)
+                        .finish()
+                }
+            }
+            /// An error response returned by the object store.
+            #[derive(Clone)]
+            pub struct ServiceError {
+                /// The HTTP status code of the response.
+                pub status: u16,
+                /// The error code returned by the store (e.g. `AccessDenied`).
+                pub code: Option<_rt::String>,
+                /// A human-readable description of the error.
+                pub message: _rt::String,
+            }
+            impl ::core::fmt::Debug for ServiceError {
+                fn fmt(
+                    &self,
+                    f: &mut ::core::fmt::Formatter<'_>,
+                ) -> ::core::fmt::Result {
+                    f.debug_struct("ServiceError")
+                        .field("status", &self.status)
+                        .field("code", &self.code)
+                        .field("message", &self.message)
+                        .finish()
+                }
+            }
+            /// Errors that can occur when accessing the object store.
+            #[derive(Clone)]
+            pub enum ObjectStoreError {
+                /// The worker does not have an object store configured.
+                NotConfigured,
+                /// The requested object does not exist.
+                NotFound,
+                /// The object is larger than the worker allows workflows to read.
+                ///
+                /// This contains the maximum permitted object size, in bytes.
+                TooLarge(u64),
+                /// The object store responded with an error.
+                Service(ServiceError),
+                /// The request could not be completed (e.g. it timed out).
+                Request(_rt::String),
+            }
+            impl ::core::fmt::Debug for ObjectStoreError {
+                fn fmt(
+                    &self,
+                    f: &mut ::core::fmt::Formatter<'_>,
+                ) -> ::core::fmt::Result {
+                    match self {
+                        ObjectStoreError::NotConfigured => {
+                            f.debug_tuple("ObjectStoreError::NotConfigured").finish()
+                        }
+                        ObjectStoreError::NotFound => {
+                            f.debug_tuple("ObjectStoreError::NotFound").finish()
+                        }
+                        ObjectStoreError::TooLarge(e) => {
+                            f.debug_tuple("ObjectStoreError::TooLarge").field(e).finish()
+                        }
+                        ObjectStoreError::Service(e) => {
+                            f.debug_tuple("ObjectStoreError::Service").field(e).finish()
+                        }
+                        ObjectStoreError::Request(e) => {
+                            f.debug_tuple("ObjectStoreError::Request").field(e).finish()
+                        }
+                    }
+                }
+            }
+            impl ::core::fmt::Display for ObjectStoreError {
+                fn fmt(
+                    &self,
+                    f: &mut ::core::fmt::Formatter<'_>,
+                ) -> ::core::fmt::Result {
+                    write!(f, "{:?}", self)
+                }
+            }
+            impl std::error::Error for ObjectStoreError {}
+            /// The HTTP method that a presigned URL can be used with.
+            #[repr(u8)]
+            #[derive(Clone, Copy, Eq, PartialEq)]
+            pub enum PresignMethod {
+                Get,
+                Put,
+            }
+            impl ::core::fmt::Debug for PresignMethod {
+                fn fmt(
+                    &self,
+                    f: &mut ::core::fmt::Formatter<'_>,
+                ) -> ::core::fmt::Result {
+                    match self {
+                        PresignMethod::Get => {
+                            f.debug_tuple("PresignMethod::Get").finish()
+                        }
+                        PresignMethod::Put => {
+                            f.debug_tuple("PresignMethod::Put").finish()
+                        }
+                    }
+                }
+            }
+            impl PresignMethod {
+                #[doc(hidden)]
+                pub unsafe fn _lift(val: u8) -> PresignMethod {
+                    if !cfg!(debug_assertions) {
+                        return ::core::mem::transmute(val);
+                    }
+                    match val {
+                        0 => PresignMethod::Get,
+                        1 => PresignMethod::Put,
+                        _ => panic!("invalid enum discriminant"),
+                    }
+                }
+            }
+            #[allow(unused_unsafe, clippy::all)]
+            /// Read the contents of an object.
+            ///
+            /// # Traps
+            /// This function will trap if called from outside of a durable transaction.
+            pub fn get(
+                bucket: &str,
+                key: &str,
+            ) -> Result<_rt::Vec<u8>, ObjectStoreError> {
+                unsafe {
+                    #[repr(align(8))]
+                    struct RetArea([::core::mem::MaybeUninit<u8>; 40]);
+                    let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 40]);
+                    let vec0 = bucket;
+                    let ptr0 = vec0.as_ptr().cast::<u8>();
+                    let len0 = vec0.len();
+                    let vec1 = key;
+                    let ptr1 = vec1.as_ptr().cast::<u8>();
+                    let len1 = vec1.len();
+                    let ptr2 = ret_area.0.as_mut_ptr().cast::<u8>();
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(wasm_import_module = "durable:core/object-store@2.7.0")]
+                    extern "C" {
+                        #[link_name = "get"]
+                        fn wit_import(
+                            _: *mut u8,
+                            _: usize,
+                            _: *mut u8,
+                            _: usize,
+                            _: *mut u8,
+                        );
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    fn wit_import(
+                        _: *mut u8,
+                        _: usize,
+                        _: *mut u8,
+                        _: usize,
+                        _: *mut u8,
+                    ) {
+                        unreachable!()
+                    }
+                    wit_import(ptr0.cast_mut(), len0, ptr1.cast_mut(), len1, ptr2);
+                    let l3 = i32::from(*ptr2.add(0).cast::<u8>());
+                    match l3 {
+                        0 => {
+                            let e = {
+                                let l4 = *ptr2.add(8).cast::<*mut u8>();
+                                let l5 = *ptr2.add(12).cast::<usize>();
+                                let len6 = l5;
+                                _rt::Vec::from_raw_parts(l4.cast(), len6, len6)
+                            };
+                            Ok(e)
+                        }
+                        1 => {
+                            let e = {
+                                let l7 = i32::from(*ptr2.add(8).cast::<u8>());
+                                let v20 = match l7 {
+                                    0 => ObjectStoreError::NotConfigured,
+                                    1 => ObjectStoreError::NotFound,
+                                    2 => {
+                                        let e20 = {
+                                            let l8 = *ptr2.add(16).cast::<i64>();
+                                            l8 as u64
+                                        };
+                                        ObjectStoreError::TooLarge(e20)
+                                    }
+                                    3 => {
+                                        let e20 = {
+                                            let l9 = i32::from(*ptr2.add(16).cast::<u16>());
+                                            let l10 = i32::from(*ptr2.add(20).cast::<u8>());
+                                            let l14 = *ptr2.add(32).cast::<*mut u8>();
+                                            let l15 = *ptr2.add(36).cast::<usize>();
+                                            let len16 = l15;
+                                            let bytes16 = _rt::Vec::from_raw_parts(
+                                                l14.cast(),
+                                                len16,
+                                                len16,
+                                            );
+                                            ServiceError {
+                                                status: l9 as u16,
+                                                code: match l10 {
+                                                    0 => None,
+                                                    1 => {
+                                                        let e = {
+                                                            let l11 = *ptr2.add(24).cast::<*mut u8>();
+                                                            let l12 = *ptr2.add(28).cast::<usize>();
+                                                            let len13 = l12;
+                                                            let bytes13 = _rt::Vec::from_raw_parts(
+                                                                l11.cast(),
+                                                                len13,
+                                                                len13,
+                                                            );
+                                                            _rt::string_lift(bytes13)
+                                                        };
+                                                        Some(e)
+                                                    }
+                                                    _ => _rt::invalid_enum_discriminant(),
+                                                },
+                                                message: _rt::string_lift(bytes16),
+                                            }
+                                        };
+                                        ObjectStoreError::Service(e20)
+                                    }
+                                    n => {
+                                        debug_assert_eq!(n, 4, "invalid enum discriminant");
+                                        let e20 = {
+                                            let l17 = *ptr2.add(16).cast::<*mut u8>();
+                                            let l18 = *ptr2.add(20).cast::<usize>();
+                                            let len19 = l18;
+                                            let bytes19 = _rt::Vec::from_raw_parts(
+                                                l17.cast(),
+                                                len19,
+                                                len19,
+                                            );
+                                            _rt::string_lift(bytes19)
+                                        };
+                                        ObjectStoreError::Request(e20)
+                                    }
+                                };
+                                v20
+                            };
+                            Err(e)
+                        }
+                        _ => _rt::invalid_enum_discriminant(),
+                    }
+                }
+            }
+            #[allow(unused_unsafe, clippy::all)]
+            /// Create or replace an object.
+            ///
+            /// # Traps
+            /// This function will trap if called from outside of a durable transaction.
+            pub fn put(
+                bucket: &str,
+                key: &str,
+                body: &[u8],
+                content_type: Option<&str>,
+            ) -> Result<(), ObjectStoreError> {
+                unsafe {
+                    #[repr(align(8))]
+                    struct RetArea([::core::mem::MaybeUninit<u8>; 40]);
+                    let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 40]);
+                    let vec0 = bucket;
+                    let ptr0 = vec0.as_ptr().cast::<u8>();
+                    let len0 = vec0.len();
+                    let vec1 = key;
+                    let ptr1 = vec1.as_ptr().cast::<u8>();
+                    let len1 = vec1.len();
+                    let vec2 = body;
+                    let ptr2 = vec2.as_ptr().cast::<u8>();
+                    let len2 = vec2.len();
+                    let (result4_0, result4_1, result4_2) = match content_type {
+                        Some(e) => {
+                            let vec3 = e;
+                            let ptr3 = vec3.as_ptr().cast::<u8>();
+                            let len3 = vec3.len();
+                            (1i32, ptr3.cast_mut(), len3)
+                        }
+                        None => (0i32, ::core::ptr::null_mut(), 0usize),
+                    };
+                    let ptr5 = ret_area.0.as_mut_ptr().cast::<u8>();
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(wasm_import_module = "durable:core/object-store@2.7.0")]
+                    extern "C" {
+                        #[link_name = "put"]
+                        fn wit_import(
+                            _: *mut u8,
+                            _: usize,
+                            _: *mut u8,
+                            _: usize,
+                            _: *mut u8,
+                            _: usize,
+                            _: i32,
+                            _: *mut u8,
+                            _: usize,
+                            _: *mut u8,
+                        );
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    fn wit_import(
+                        _: *mut u8,
+                        _: usize,
+                        _: *mut u8,
+                        _: usize,
+                        _: *mut u8,
+                        _: usize,
+                        _: i32,
+                        _: *mut u8,
+                        _: usize,
+                        _: *mut u8,
+                    ) {
+                        unreachable!()
+                    }
+                    wit_import(
+                        ptr0.cast_mut(),
+                        len0,
+                        ptr1.cast_mut(),
+                        len1,
+                        ptr2.cast_mut(),
+                        len2,
+                        result4_0,
+                        result4_1,
+                        result4_2,
+                        ptr5,
+                    );
+                    let l6 = i32::from(*ptr5.add(0).cast::<u8>());
+                    match l6 {
+                        0 => {
+                            let e = ();
+                            Ok(e)
+                        }
+                        1 => {
+                            let e = {
+                                let l7 = i32::from(*ptr5.add(8).cast::<u8>());
+                                let v20 = match l7 {
+                                    0 => ObjectStoreError::NotConfigured,
+                                    1 => ObjectStoreError::NotFound,
+                                    2 => {
+                                        let e20 = {
+                                            let l8 = *ptr5.add(16).cast::<i64>();
+                                            l8 as u64
+                                        };
+                                        ObjectStoreError::TooLarge(e20)
+                                    }
+                                    3 => {
+                                        let e20 = {
+                                            let l9 = i32::from(*ptr5.add(16).cast::<u16>());
+                                            let l10 = i32::from(*ptr5.add(20).cast::<u8>());
+                                            let l14 = *ptr5.add(32).cast::<*mut u8>();
+                                            let l15 = *ptr5.add(36).cast::<usize>();
+                                            let len16 = l15;
+                                            let bytes16 = _rt::Vec::from_raw_parts(
+                                                l14.cast(),
+                                                len16,
+                                                len16,
+                                            );
+                                            ServiceError {
+                                                status: l9 as u16,
+                                                code: match l10 {
+                                                    0 => None,
+                                                    1 => {
+                                                        let e = {
+                                                            let l11 = *ptr5.add(24).cast::<*mut u8>();
+                                                            let l12 = *ptr5.add(28).cast::<usize>();
+                                                            let len13 = l12;
+                                                            let bytes13 = _rt::Vec::from_raw_parts(
+                                                                l11.cast(),
+                                                                len13,
+                                                                len13,
+                                                            );
+                                                            _rt::string_lift(bytes13)
+                                                        };
+                                                        Some(e)
+                                                    }
+                                                    _ => _rt::invalid_enum_discriminant(),
+                                                },
+                                                message: _rt::string_lift(bytes16),
+                                            }
+                                        };
+                                        ObjectStoreError::Service(e20)
+                                    }
+                                    n => {
+                                        debug_assert_eq!(n, 4, "invalid enum discriminant");
+                                        let e20 = {
+                                            let l17 = *ptr5.add(16).cast::<*mut u8>();
+                                            let l18 = *ptr5.add(20).cast::<usize>();
+                                            let len19 = l18;
+                                            let bytes19 = _rt::Vec::from_raw_parts(
+                                                l17.cast(),
+                                                len19,
+                                                len19,
+                                            );
+                                            _rt::string_lift(bytes19)
+                                        };
+                                        ObjectStoreError::Request(e20)
+                                    }
+                                };
+                                v20
+                            };
+                            Err(e)
+                        }
+                        _ => _rt::invalid_enum_discriminant(),
+                    }
+                }
+            }
+            #[allow(unused_unsafe, clippy::all)]
+            /// List the objects in a bucket whose keys start with `prefix`.
+            ///
+            /// # Parameters
+            /// - `token` - The `next-token` from a previous page of results.
+            ///
+            /// # Traps
+            /// This function will trap if called from outside of a durable transaction.
+            pub fn list_objects(
+                bucket: &str,
+                prefix: Option<&str>,
+                token: Option<&str>,
+            ) -> Result<ListPage, ObjectStoreError> {
+                unsafe {
+                    #[repr(align(8))]
+                    struct RetArea([::core::mem::MaybeUninit<u8>; 40]);
+                    let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 40]);
+                    let vec0 = bucket;
+                    let ptr0 = vec0.as_ptr().cast::<u8>();
+                    let len0 = vec0.len();
+                    let (result2_0, result2_1, result2_2) = match prefix {
+                        Some(e) => {
+                            let vec1 = e;
+                            let ptr1 = vec1.as_ptr().cast::<u8>();
+                            let len1 = vec1.len();
+                            (1i32, ptr1.cast_mut(), len1)
+                        }
+                        None => (0i32, ::core::ptr::null_mut(), 0usize),
+                    };
+                    let (result4_0, result4_1, result4_2) = match token {
+                        Some(e) => {
+                            let vec3 = e;
+                            let ptr3 = vec3.as_ptr().cast::<u8>();
+                            let len3 = vec3.len();
+                            (1i32, ptr3.cast_mut(), len3)
+                        }
+                        None => (0i32, ::core::ptr::null_mut(), 0usize),
+                    };
+                    let ptr5 = ret_area.0.as_mut_ptr().cast::<u8>();
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(wasm_import_module = "durable:core/object-store@2.7.0")]
+                    extern "C" {
+                        #[link_name = "list-objects"]
+                        fn wit_import(
+                            _: *mut u8,
+                            _: usize,
+                            _: i32,
+                            _: *mut u8,
+                            _: usize,
+                            _: i32,
+                            _: *mut u8,
+                            _: usize,
+                            _: *mut u8,
+                        );
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    fn wit_import(
+                        _: *mut u8,
+                        _: usize,
+                        _: i32,
+                        _: *mut u8,
+                        _: usize,
+                        _: i32,
+                        _: *mut u8,
+                        _: usize,
+                        _: *mut u8,
+                    ) {
+                        unreachable!()
+                    }
+                    wit_import(
+                        ptr0.cast_mut(),
+                        len0,
+                        result2_0,
+                        result2_1,
+                        result2_2,
+                        result4_0,
+                        result4_1,
+                        result4_2,
+                        ptr5,
+                    );
+                    let l6 = i32::from(*ptr5.add(0).cast::<u8>());
+                    match l6 {
+                        0 => {
+                            let e = {
+                                let l7 = *ptr5.add(8).cast::<*mut u8>();
+                                let l8 = *ptr5.add(12).cast::<usize>();
+                                let base21 = l7;
+                                let len21 = l8;
+                                let mut result21 = _rt::Vec::with_capacity(len21);
+                                for i in 0..len21 {
+                                    let base = base21.add(i * 40);
+                                    let e21 = {
+                                        let l9 = *base.add(0).cast::<*mut u8>();
+                                        let l10 = *base.add(4).cast::<usize>();
+                                        let len11 = l10;
+                                        let bytes11 = _rt::Vec::from_raw_parts(
+                                            l9.cast(),
+                                            len11,
+                                            len11,
+                                        );
+                                        let l12 = *base.add(8).cast::<i64>();
+                                        let l13 = i32::from(*base.add(16).cast::<u8>());
+                                        let l17 = i32::from(*base.add(28).cast::<u8>());
+                                        ObjectInfo {
+                                            key: _rt::string_lift(bytes11),
+                                            size: l12 as u64,
+                                            etag: match l13 {
+                                                0 => None,
+                                                1 => {
+                                                    let e = {
+                                                        let l14 = *base.add(20).cast::<*mut u8>();
+                                                        let l15 = *base.add(24).cast::<usize>();
+                                                        let len16 = l15;
+                                                        let bytes16 = _rt::Vec::from_raw_parts(
+                                                            l14.cast(),
+                                                            len16,
+                                                            len16,
+                                                        );
+                                                        _rt::string_lift(bytes16)
+                                                    };
+                                                    Some(e)
+                                                }
+                                                _ => _rt::invalid_enum_discriminant(),
+                                            },
+                                            last_modified: match l17 {
+                                                0 => None,
+                                                1 => {
+                                                    let e = {
+                                                        let l18 = *base.add(32).cast::<*mut u8>();
+                                                        let l19 = *base.add(36).cast::<usize>();
+                                                        let len20 = l19;
+                                                        let bytes20 = _rt::Vec::from_raw_parts(
+                                                            l18.cast(),
+                                                            len20,
+                                                            len20,
+                                                        );
+                                                        _rt::string_lift(bytes20)
+                                                    };
+                                                    Some(e)
+                                                }
+                                                _ => _rt::invalid_enum_discriminant(),
+                                            },
+                                        }
+                                    };
+                                    result21.push(e21);
+                                }
+                                _rt::cabi_dealloc(base21, len21 * 40, 8);
+                                let l22 = i32::from(*ptr5.add(16).cast::<u8>());
+                                ListPage {
+                                    objects: result21,
+                                    next_token: match l22 {
+                                        0 => None,
+                                        1 => {
+                                            let e = {
+                                                let l23 = *ptr5.add(20).cast::<*mut u8>();
+                                                let l24 = *ptr5.add(24).cast::<usize>();
+                                                let len25 = l24;
+                                                let bytes25 = _rt::Vec::from_raw_parts(
+                                                    l23.cast(),
+                                                    len25,
+                                                    len25,
+                                                );
+                                                _rt::string_lift(bytes25)
+                                            };
+                                            Some(e)
+                                        }
+                                        _ => _rt::invalid_enum_discriminant(),
+                                    },
+                                }
+                            };
+                            Ok(e)
+                        }
+                        1 => {
+                            let e = {
+                                let l26 = i32::from(*ptr5.add(8).cast::<u8>());
+                                let v39 = match l26 {
+                                    0 => ObjectStoreError::NotConfigured,
+                                    1 => ObjectStoreError::NotFound,
+                                    2 => {
+                                        let e39 = {
+                                            let l27 = *ptr5.add(16).cast::<i64>();
+                                            l27 as u64
+                                        };
+                                        ObjectStoreError::TooLarge(e39)
+                                    }
+                                    3 => {
+                                        let e39 = {
+                                            let l28 = i32::from(*ptr5.add(16).cast::<u16>());
+                                            let l29 = i32::from(*ptr5.add(20).cast::<u8>());
+                                            let l33 = *ptr5.add(32).cast::<*mut u8>();
+                                            let l34 = *ptr5.add(36).cast::<usize>();
+                                            let len35 = l34;
+                                            let bytes35 = _rt::Vec::from_raw_parts(
+                                                l33.cast(),
+                                                len35,
+                                                len35,
+                                            );
+                                            ServiceError {
+                                                status: l28 as u16,
+                                                code: match l29 {
+                                                    0 => None,
+                                                    1 => {
+                                                        let e = {
+                                                            let l30 = *ptr5.add(24).cast::<*mut u8>();
+                                                            let l31 = *ptr5.add(28).cast::<usize>();
+                                                            let len32 = l31;
+                                                            let bytes32 = _rt::Vec::from_raw_parts(
+                                                                l30.cast(),
+                                                                len32,
+                                                                len32,
+                                                            );
+                                                            _rt::string_lift(bytes32)
+                                                        };
+                                                        Some(e)
+                                                    }
+                                                    _ => _rt::invalid_enum_discriminant(),
+                                                },
+                                                message: _rt::string_lift(bytes35),
+                                            }
+                                        };
+                                        ObjectStoreError::Service(e39)
+                                    }
+                                    n => {
+                                        debug_assert_eq!(n, 4, "invalid enum discriminant");
+                                        let e39 = {
+                                            let l36 = *ptr5.add(16).cast::<*mut u8>();
+                                            let l37 = *ptr5.add(20).cast::<usize>();
+                                            let len38 = l37;
+                                            let bytes38 = _rt::Vec::from_raw_parts(
+                                                l36.cast(),
+                                                len38,
+                                                len38,
+                                            );
+                                            _rt::string_lift(bytes38)
+                                        };
+                                        ObjectStoreError::Request(e39)
+                                    }
+                                };
+                                v39
+                            };
+                            Err(e)
+                        }
+                        _ => _rt::invalid_enum_discriminant(),
+                    }
+                }
+            }
+            #[allow(unused_unsafe, clippy::all)]
+            /// Delete an object.
+            ///
+            /// Deleting an object that does not exist is not an error.
+            ///
+            /// # Traps
+            /// This function will trap if called from outside of a durable transaction.
+            pub fn delete(bucket: &str, key: &str) -> Result<(), ObjectStoreError> {
+                unsafe {
+                    #[repr(align(8))]
+                    struct RetArea([::core::mem::MaybeUninit<u8>; 40]);
+                    let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 40]);
+                    let vec0 = bucket;
+                    let ptr0 = vec0.as_ptr().cast::<u8>();
+                    let len0 = vec0.len();
+                    let vec1 = key;
+                    let ptr1 = vec1.as_ptr().cast::<u8>();
+                    let len1 = vec1.len();
+                    let ptr2 = ret_area.0.as_mut_ptr().cast::<u8>();
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(wasm_import_module = "durable:core/object-store@2.7.0")]
+                    extern "C" {
+                        #[link_name = "delete"]
+                        fn wit_import(
+                            _: *mut u8,
+                            _: usize,
+                            _: *mut u8,
+                            _: usize,
+                            _: *mut u8,
+                        );
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    fn wit_import(
+                        _: *mut u8,
+                        _: usize,
+                        _: *mut u8,
+                        _: usize,
+                        _: *mut u8,
+                    ) {
+                        unreachable!()
+                    }
+                    wit_import(ptr0.cast_mut(), len0, ptr1.cast_mut(), len1, ptr2);
+                    let l3 = i32::from(*ptr2.add(0).cast::<u8>());
+                    match l3 {
+                        0 => {
+                            let e = ();
+                            Ok(e)
+                        }
+                        1 => {
+                            let e = {
+                                let l4 = i32::from(*ptr2.add(8).cast::<u8>());
+                                let v17 = match l4 {
+                                    0 => ObjectStoreError::NotConfigured,
+                                    1 => ObjectStoreError::NotFound,
+                                    2 => {
+                                        let e17 = {
+                                            let l5 = *ptr2.add(16).cast::<i64>();
+                                            l5 as u64
+                                        };
+                                        ObjectStoreError::TooLarge(e17)
+                                    }
+                                    3 => {
+                                        let e17 = {
+                                            let l6 = i32::from(*ptr2.add(16).cast::<u16>());
+                                            let l7 = i32::from(*ptr2.add(20).cast::<u8>());
+                                            let l11 = *ptr2.add(32).cast::<*mut u8>();
+                                            let l12 = *ptr2.add(36).cast::<usize>();
+                                            let len13 = l12;
+                                            let bytes13 = _rt::Vec::from_raw_parts(
+                                                l11.cast(),
+                                                len13,
+                                                len13,
+                                            );
+                                            ServiceError {
+                                                status: l6 as u16,
+                                                code: match l7 {
+                                                    0 => None,
+                                                    1 => {
+                                                        let e = {
+                                                            let l8 = *ptr2.add(24).cast::<*mut u8>();
+                                                            let l9 = *ptr2.add(28).cast::<usize>();
+                                                            let len10 = l9;
+                                                            let bytes10 = _rt::Vec::from_raw_parts(
+                                                                l8.cast(),
+                                                                len10,
+                                                                len10,
+                                                            );
+                                                            _rt::string_lift(bytes10)
+                                                        };
+                                                        Some(e)
+                                                    }
+                                                    _ => _rt::invalid_enum_discriminant(),
+                                                },
+                                                message: _rt::string_lift(bytes13),
+                                            }
+                                        };
+                                        ObjectStoreError::Service(e17)
+                                    }
+                                    n => {
+                                        debug_assert_eq!(n, 4, "invalid enum discriminant");
+                                        let e17 = {
+                                            let l14 = *ptr2.add(16).cast::<*mut u8>();
+                                            let l15 = *ptr2.add(20).cast::<usize>();
+                                            let len16 = l15;
+                                            let bytes16 = _rt::Vec::from_raw_parts(
+                                                l14.cast(),
+                                                len16,
+                                                len16,
+                                            );
+                                            _rt::string_lift(bytes16)
+                                        };
+                                        ObjectStoreError::Request(e17)
+                                    }
+                                };
+                                v17
+                            };
+                            Err(e)
+                        }
+                        _ => _rt::invalid_enum_discriminant(),
+                    }
+                }
+            }
+            #[allow(unused_unsafe, clippy::all)]
+            /// Create a URL that allows anyone holding it to access an object for the
+            /// next `expires-in` seconds.
+            ///
+            /// # Traps
+            /// This function will trap if called from outside of a durable transaction.
+            pub fn presign(
+                bucket: &str,
+                key: &str,
+                method: PresignMethod,
+                expires_in: u64,
+            ) -> Result<_rt::String, ObjectStoreError> {
+                unsafe {
+                    #[repr(align(8))]
+                    struct RetArea([::core::mem::MaybeUninit<u8>; 40]);
+                    let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 40]);
+                    let vec0 = bucket;
+                    let ptr0 = vec0.as_ptr().cast::<u8>();
+                    let len0 = vec0.len();
+                    let vec1 = key;
+                    let ptr1 = vec1.as_ptr().cast::<u8>();
+                    let len1 = vec1.len();
+                    let ptr2 = ret_area.0.as_mut_ptr().cast::<u8>();
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(wasm_import_module = "durable:core/object-store@2.7.0")]
+                    extern "C" {
+                        #[link_name = "presign"]
+                        fn wit_import(
+                            _: *mut u8,
+                            _: usize,
+                            _: *mut u8,
+                            _: usize,
+                            _: i32,
+                            _: i64,
+                            _: *mut u8,
+                        );
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    fn wit_import(
+                        _: *mut u8,
+                        _: usize,
+                        _: *mut u8,
+                        _: usize,
+                        _: i32,
+                        _: i64,
+                        _: *mut u8,
+                    ) {
+                        unreachable!()
+                    }
+                    wit_import(
+                        ptr0.cast_mut(),
+                        len0,
+                        ptr1.cast_mut(),
+                        len1,
+                        method.clone() as i32,
+                        _rt::as_i64(&expires_in),
+                        ptr2,
+                    );
+                    let l3 = i32::from(*ptr2.add(0).cast::<u8>());
+                    match l3 {
+                        0 => {
+                            let e = {
+                                let l4 = *ptr2.add(8).cast::<*mut u8>();
+                                let l5 = *ptr2.add(12).cast::<usize>();
+                                let len6 = l5;
+                                let bytes6 = _rt::Vec::from_raw_parts(
+                                    l4.cast(),
+                                    len6,
+                                    len6,
+                                );
+                                _rt::string_lift(bytes6)
+                            };
+                            Ok(e)
+                        }
+                        1 => {
+                            let e = {
+                                let l7 = i32::from(*ptr2.add(8).cast::<u8>());
+                                let v20 = match l7 {
+                                    0 => ObjectStoreError::NotConfigured,
+                                    1 => ObjectStoreError::NotFound,
+                                    2 => {
+                                        let e20 = {
+                                            let l8 = *ptr2.add(16).cast::<i64>();
+                                            l8 as u64
+                                        };
+                                        ObjectStoreError::TooLarge(e20)
+                                    }
+                                    3 => {
+                                        let e20 = {
+                                            let l9 = i32::from(*ptr2.add(16).cast::<u16>());
+                                            let l10 = i32::from(*ptr2.add(20).cast::<u8>());
+                                            let l14 = *ptr2.add(32).cast::<*mut u8>();
+                                            let l15 = *ptr2.add(36).cast::<usize>();
+                                            let len16 = l15;
+                                            let bytes16 = _rt::Vec::from_raw_parts(
+                                                l14.cast(),
+                                                len16,
+                                                len16,
+                                            );
+                                            ServiceError {
+                                                status: l9 as u16,
+                                                code: match l10 {
+                                                    0 => None,
+                                                    1 => {
+                                                        let e = {
+                                                            let l11 = *ptr2.add(24).cast::<*mut u8>();
+                                                            let l12 = *ptr2.add(28).cast::<usize>();
+                                                            let len13 = l12;
+                                                            let bytes13 = _rt::Vec::from_raw_parts(
+                                                                l11.cast(),
+                                                                len13,
+                                                                len13,
+                                                            );
+                                                            _rt::string_lift(bytes13)
+                                                        };
+                                                        Some(e)
+                                                    }
+                                                    _ => _rt::invalid_enum_discriminant(),
+                                                },
+                                                message: _rt::string_lift(bytes16),
+                                            }
+                                        };
+                                        ObjectStoreError::Service(e20)
+                                    }
+                                    n => {
+                                        debug_assert_eq!(n, 4, "invalid enum discriminant");
+                                        let e20 = {
+                                            let l17 = *ptr2.add(16).cast::<*mut u8>();
+                                            let l18 = *ptr2.add(20).cast::<usize>();
+                                            let len19 = l18;
+                                            let bytes19 = _rt::Vec::from_raw_parts(
+                                                l17.cast(),
+                                                len19,
+                                                len19,
+                                            );
+                                            _rt::string_lift(bytes19)
+                                        };
+                                        ObjectStoreError::Request(e20)
+                                    }
+                                };
+                                v20
+                            };
+                            Err(e)
+                        }
+                        _ => _rt::invalid_enum_discriminant(),
+                    }
+                }
+            }
+        }
+    }
+}
+mod _rt {
+    pub use alloc_crate::string::String;
+    pub use alloc_crate::vec::Vec;
+    pub unsafe fn string_lift(bytes: Vec<u8>) -> String {
+        if cfg!(debug_assertions) {
+            String::from_utf8(bytes).unwrap()
+        } else {
+            String::from_utf8_unchecked(bytes)
+        }
+    }
+    pub unsafe fn invalid_enum_discriminant<T>() -> T {
+        if cfg!(debug_assertions) {
+            panic!("invalid enum discriminant")
+        } else {
+            core::hint::unreachable_unchecked()
+        }
+    }
+    pub unsafe fn cabi_dealloc(ptr: *mut u8, size: usize, align: usize) {
+        if size == 0 {
+            return;
+        }
+        let layout = alloc::Layout::from_size_align_unchecked(size, align);
+        alloc::dealloc(ptr, layout);
+    }
+    pub fn as_i64<T: AsI64>(t: T) -> i64 {
+        t.as_i64()
+    }
+    pub trait AsI64 {
+        fn as_i64(self) -> i64;
+    }
+    impl<'a, T: Copy + AsI64> AsI64 for &'a T {
+        fn as_i64(self) -> i64 {
+            (*self).as_i64()
+        }
+    }
+    impl AsI64 for i64 {
+        #[inline]
+        fn as_i64(self) -> i64 {
+            self as i64
+        }
+    }
+    impl AsI64 for u64 {
+        #[inline]
+        fn as_i64(self) -> i64 {
+            self as i64
+        }
+    }
+    extern crate alloc as alloc_crate;
+    pub use alloc_crate::alloc;
+}
+#[cfg(target_arch = "wasm32")]
+#[link_section = "component-type:wit-bindgen:0.30.0:import-object-store:encoded world"]
+#[doc(hidden)]
+pub static __WIT_BINDGEN_COMPONENT_TYPE: [u8; 712] = *b"\
+\0asm\x0d\0\x01\0\0\x19\x16wit-component-encoding\x04\0\x07\xbe\x04\x01A\x02\x01\
+A\x02\x01B\x1b\x01ks\x01r\x04\x03keys\x04sizew\x04etag\0\x0dlast-modified\0\x04\0\
+\x0bobject-info\x03\0\x01\x01p\x02\x01r\x02\x07objects\x03\x0anext-token\0\x04\0\
+\x09list-page\x03\0\x04\x01r\x03\x06status{\x04code\0\x07messages\x04\0\x0dservi\
+ce-error\x03\0\x06\x01q\x05\x0enot-configured\0\0\x09not-found\0\0\x09too-large\x01\
+w\0\x07service\x01\x07\0\x07request\x01s\0\x04\0\x12object-store-error\x03\0\x08\
+\x01m\x02\x03get\x03put\x04\0\x0epresign-method\x03\0\x0a\x01p}\x01j\x01\x0c\x01\
+\x09\x01@\x02\x06buckets\x03keys\0\x0d\x04\0\x03get\x01\x0e\x01j\0\x01\x09\x01@\x04\
+\x06buckets\x03keys\x04body\x0c\x0ccontent-type\0\0\x0f\x04\0\x03put\x01\x10\x01\
+j\x01\x05\x01\x09\x01@\x03\x06buckets\x06prefix\0\x05token\0\0\x11\x04\0\x0clist\
+-objects\x01\x12\x01@\x02\x06buckets\x03keys\0\x0f\x04\0\x06delete\x01\x13\x01j\x01\
+s\x01\x09\x01@\x04\x06buckets\x03keys\x06method\x0b\x0aexpires-inw\0\x14\x04\0\x07\
+presign\x01\x15\x03\x01\x1fdurable:core/object-store@2.7.0\x05\0\x04\x01&durable\
+:core/import-object-store@2.7.0\x04\0\x0b\x19\x01\0\x13import-object-store\x03\0\
+\0\0G\x09producers\x01\x0cprocessed-by\x02\x0dwit-component\x070.215.0\x10wit-bi\
+ndgen-rust\x060.30.0";
+#[inline(never)]
+#[doc(hidden)]
+pub fn __link_custom_section_describing_imports() {
+    wit_bindgen_rt::maybe_link_cabi_realloc();
+}
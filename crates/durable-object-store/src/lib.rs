@@ -0,0 +1,337 @@
+//! Read and write objects in S3-compatible object storage as part of your
+//! workflow.
+//!
+//! The object store, along with the credentials used to access it, is
+//! configured on the worker. Workflows only ever refer to buckets by name.
+//!
+//! ```no_run
+//! use durable::object_store::Bucket;
+//!
+//! let bucket = Bucket::new("reports");
+//! bucket
+//!     .put_with_content_type("daily.csv", "a,b,c\n1,2,3\n", "text/csv")
+//!     .expect("failed to upload the report");
+//!
+//! let url = bucket
+//!     .presign_get("daily.csv", std::time::Duration::from_secs(3600))
+//!     .expect("failed to presign the report URL");
+//! println!("{url}");
+//! ```
+//!
+//! Each operation is executed within its own durable transaction, unless it
+//! is called from within an existing transaction. Once an operation has
+//! completed its result is recorded, so it will not be repeated if the
+//! workflow is restarted.
+
+use std::fmt;
+use std::time::Duration;
+
+use durable_core::transaction;
+use serde::{Deserialize, Serialize};
+
+mod bindings {
+    #![allow(unused_braces, clippy::all)]
+
+    include!("bindings.rs");
+
+    pub use self::durable::core::object_store::*;
+}
+
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// A bucket within the object store configured on the worker.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Bucket {
+    name: String,
+}
+
+impl Bucket {
+    /// Refer to the bucket named `name`.
+    ///
+    /// This does not check that the bucket exists.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self { name: name.into() }
+    }
+
+    /// The name of this bucket.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Read the contents of the object at `key`.
+    ///
+    /// Returns an error for which [`Error::is_not_found`] is true if the
+    /// object does not exist.
+    pub fn get(&self, key: &str) -> Result<Vec<u8>> {
+        let label = format!("durable::object_store::get({}/{key})", self.name);
+        transaction::maybe_txn(&label, || {
+            bindings::get(&self.name, key).map_err(Error::from)
+        })
+    }
+
+    /// Create or replace the object at `key`.
+    pub fn put(&self, key: &str, body: impl AsRef<[u8]>) -> Result<()> {
+        self.put_impl(key, body.as_ref(), None)
+    }
+
+    /// Create or replace the object at `key`, setting its `Content-Type`.
+    pub fn put_with_content_type(
+        &self,
+        key: &str,
+        body: impl AsRef<[u8]>,
+        content_type: &str,
+    ) -> Result<()> {
+        self.put_impl(key, body.as_ref(), Some(content_type))
+    }
+
+    fn put_impl(&self, key: &str, body: &[u8], content_type: Option<&str>) -> Result<()> {
+        let label = format!("durable::object_store::put({}/{key})", self.name);
+        transaction::maybe_txn(&label, || {
+            bindings::put(&self.name, key, body, content_type).map_err(Error::from)
+        })
+    }
+
+    /// Delete the object at `key`.
+    ///
+    /// Deleting an object that does not exist is not an error.
+    pub fn delete(&self, key: &str) -> Result<()> {
+        let label = format!("durable::object_store::delete({}/{key})", self.name);
+        transaction::maybe_txn(&label, || {
+            bindings::delete(&self.name, key).map_err(Error::from)
+        })
+    }
+
+    /// Fetch a single page of the objects whose keys start with `prefix`.
+    ///
+    /// `token` should be the [`next_token`] of the previous page, or `None`
+    /// to fetch the first page.
+    ///
+    /// [`next_token`]: ListPage::next_token
+    pub fn list_page(&self, prefix: Option<&str>, token: Option<&str>) -> Result<ListPage> {
+        let label = format!(
+            "durable::object_store::list({}/{})",
+            self.name,
+            prefix.unwrap_or_default()
+        );
+        transaction::maybe_txn(&label, || {
+            bindings::list_objects(&self.name, prefix, token)
+                .map(ListPage::from)
+                .map_err(Error::from)
+        })
+    }
+
+    /// Iterate over all objects whose keys start with `prefix`.
+    ///
+    /// Pages are fetched lazily as the iterator is advanced.
+    pub fn list<'a>(&'a self, prefix: &'a str) -> List<'a> {
+        List {
+            bucket: self,
+            prefix,
+            objects: Vec::new().into_iter(),
+            token: None,
+            done: false,
+        }
+    }
+
+    /// Create a URL that can be used to download the object at `key` until
+    /// `expires_in` has elapsed.
+    pub fn presign_get(&self, key: &str, expires_in: Duration) -> Result<String> {
+        self.presign(key, bindings::PresignMethod::Get, expires_in)
+    }
+
+    /// Create a URL that can be used to upload the object at `key` until
+    /// `expires_in` has elapsed.
+    pub fn presign_put(&self, key: &str, expires_in: Duration) -> Result<String> {
+        self.presign(key, bindings::PresignMethod::Put, expires_in)
+    }
+
+    fn presign(
+        &self,
+        key: &str,
+        method: bindings::PresignMethod,
+        expires_in: Duration,
+    ) -> Result<String> {
+        let label = format!("durable::object_store::presign({}/{key})", self.name);
+        transaction::maybe_txn(&label, || {
+            bindings::presign(&self.name, key, method, expires_in.as_secs()).map_err(Error::from)
+        })
+    }
+}
+
+/// Metadata about an object stored in a bucket.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Object {
+    /// The key of the object within its bucket.
+    pub key: String,
+
+    /// The size of the object, in bytes.
+    pub size: u64,
+
+    /// The entity tag of the object, if the store provided one.
+    pub etag: Option<String>,
+
+    /// The time at which the object was last modified, as an ISO 8601
+    /// timestamp.
+    pub last_modified: Option<String>,
+}
+
+/// A single page of results from [`Bucket::list_page`].
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ListPage {
+    /// The objects within this page.
+    pub objects: Vec<Object>,
+
+    /// A token that can be used to fetch the next page.
+    ///
+    /// This is `None` if this is the last page.
+    pub next_token: Option<String>,
+}
+
+impl From<bindings::ObjectInfo> for Object {
+    fn from(info: bindings::ObjectInfo) -> Self {
+        Self {
+            key: info.key,
+            size: info.size,
+            etag: info.etag,
+            last_modified: info.last_modified,
+        }
+    }
+}
+
+impl From<bindings::ListPage> for ListPage {
+    fn from(page: bindings::ListPage) -> Self {
+        Self {
+            objects: page.objects.into_iter().map(Object::from).collect(),
+            next_token: page.next_token,
+        }
+    }
+}
+
+/// An iterator over the objects in a bucket.
+///
+/// This is returned by [`Bucket::list`].
+pub struct List<'a> {
+    bucket: &'a Bucket,
+    prefix: &'a str,
+    objects: std::vec::IntoIter<Object>,
+    token: Option<String>,
+    done: bool,
+}
+
+impl Iterator for List<'_> {
+    type Item = Result<Object>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(object) = self.objects.next() {
+                return Some(Ok(object));
+            }
+
+            if self.done {
+                return None;
+            }
+
+            let page = match self
+                .bucket
+                .list_page(Some(self.prefix), self.token.as_deref())
+            {
+                Ok(page) => page,
+                Err(e) => {
+                    self.done = true;
+                    return Some(Err(e));
+                }
+            };
+
+            self.done = page.next_token.is_none();
+            self.token = page.next_token;
+            self.objects = page.objects.into_iter();
+        }
+    }
+}
+
+/// An error that occurred while accessing the object store.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+#[non_exhaustive]
+pub enum Error {
+    /// The worker does not have an object store configured.
+    NotConfigured,
+
+    /// The requested object does not exist.
+    NotFound,
+
+    /// The object is larger than the worker allows workflows to read.
+    TooLarge {
+        /// The maximum permitted object size, in bytes.
+        max_size: u64,
+    },
+
+    /// The object store responded with an error.
+    Service {
+        /// The HTTP status code of the response.
+        status: u16,
+
+        /// The error code returned by the store (e.g. `AccessDenied`).
+        code: Option<String>,
+
+        /// A human-readable description of the error.
+        message: String,
+    },
+
+    /// The request could not be completed.
+    Request(String),
+}
+
+impl Error {
+    /// Whether this error indicates that the requested object does not
+    /// exist.
+    pub fn is_not_found(&self) -> bool {
+        matches!(self, Self::NotFound)
+    }
+}
+
+impl From<bindings::ObjectStoreError> for Error {
+    fn from(error: bindings::ObjectStoreError) -> Self {
+        use bindings::ObjectStoreError;
+
+        match error {
+            ObjectStoreError::NotConfigured => Self::NotConfigured,
+            ObjectStoreError::NotFound => Self::NotFound,
+            ObjectStoreError::TooLarge(max_size) => Self::TooLarge { max_size },
+            ObjectStoreError::Service(error) => Self::Service {
+                status: error.status,
+                code: error.code,
+                message: error.message,
+            },
+            ObjectStoreError::Request(message) => Self::Request(message),
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotConfigured => {
+                f.write_str("the worker does not have an object store configured")
+            }
+            Self::NotFound => f.write_str("the requested object does not exist"),
+            Self::TooLarge { max_size } => {
+                write!(
+                    f,
+                    "the object is larger than the maximum of {max_size} bytes"
+                )
+            }
+            Self::Service {
+                status,
+                code: Some(code),
+                message,
+            } => write!(f, "object store error {code} ({status}): {message}"),
+            Self::Service {
+                status, message, ..
+            } => write!(f, "object store error ({status}): {message}"),
+            Self::Request(message) => f.write_str(message),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
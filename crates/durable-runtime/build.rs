@@ -41,6 +41,10 @@ fn generate_bindings(out_dir: &Path) -> anyhow::Result<()> {
         "task-name",
         "task-data",
         "task-created-at",
+        "task-queue",
+        "task-labels",
+        "task-attempt",
+        "worker-id",
         "abort",
         // And these ones are from the various wasi p2 interfaces that we export.
         "[method]error.to-debug-string",
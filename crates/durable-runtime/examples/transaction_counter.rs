@@ -0,0 +1,95 @@
+//! An example plugin that counts the transactions executed by a task.
+//!
+//! This exposes a single host function, `example:counter/counter#count`, to
+//! workflows that returns the number of transactions the task has executed on
+//! this worker. It also logs a summary whenever a task completes.
+//!
+//! Run it with a database to point the worker at:
+//! ```sh
+//! DATABASE_URL=postgres://... cargo run -p durable-runtime --example transaction_counter
+//! ```
+
+use durable_runtime::plugin::Plugin;
+use durable_runtime::{Task, TaskStatus, WorkerBuilder};
+use wasmtime::component::Linker;
+use wasmtime::StoreContextMut;
+
+/// The per-task state for the plugin.
+///
+/// This type is private so that no other plugin can access or overwrite it.
+#[derive(Default)]
+struct TransactionCount {
+    count: u32,
+}
+
+struct TransactionCounter;
+
+impl Plugin for TransactionCounter {
+    fn name(&self) -> &str {
+        "example:counter"
+    }
+
+    fn interfaces(&self) -> Vec<String> {
+        vec!["example:counter/counter".to_owned()]
+    }
+
+    fn setup(&self, linker: &mut Linker<Task>, task: &mut Task) -> wasmtime::Result<()> {
+        task.insert_plugin_state(TransactionCount::default());
+
+        linker.instance("example:counter/counter")?.func_wrap(
+            "count",
+            |store: StoreContextMut<Task>, (): ()| {
+                let task = store.data();
+
+                // The count is different each time the task is restarted so
+                // exposing it outside of a transaction would make the workflow
+                // non-deterministic.
+                if task.state.transaction().is_none() {
+                    anyhow::bail!("`count` can only be called within a transaction");
+                }
+
+                let count = task
+                    .plugin_state::<TransactionCount>()
+                    .map(|state| state.count)
+                    .unwrap_or(0);
+
+                Ok((count,))
+            },
+        )?;
+
+        Ok(())
+    }
+
+    fn on_transaction_exit(&self, task: &mut Task) -> wasmtime::Result<()> {
+        if let Some(state) = task.plugin_state_mut::<TransactionCount>() {
+            state.count += 1;
+        }
+
+        Ok(())
+    }
+
+    fn on_task_complete(&self, task: &mut Task, status: TaskStatus) {
+        let count = task
+            .plugin_state::<TransactionCount>()
+            .map(|state| state.count)
+            .unwrap_or(0);
+
+        println!(
+            "task {} exited with status {status:?} after executing {count} transactions",
+            task.state.task_id()
+        );
+    }
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let url = std::env::var("DATABASE_URL")?;
+    let pool = sqlx::PgPool::connect(&url).await?;
+
+    let mut worker = WorkerBuilder::new(pool)
+        .plugin(Box::new(TransactionCounter))
+        .build()
+        .await?;
+
+    worker.run().await
+}
@@ -0,0 +1,2 @@
+-- Drop "wasm_artifact" table
+DROP TABLE durable.wasm_artifact;
@@ -0,0 +1,10 @@
+-- Create "wasm_artifact" table
+CREATE TABLE durable.wasm_artifact(
+    wasm        bigint      NOT NULL,
+    compat      bytea       NOT NULL,
+    artifact    bytea       NOT NULL,
+    created_at  timestamptz NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    PRIMARY KEY(wasm, compat),
+    CONSTRAINT fk_wasm FOREIGN KEY(wasm) REFERENCES durable.wasm(id) ON DELETE CASCADE
+);
@@ -0,0 +1,4 @@
+-- Drop trigger "events_inserted"
+DROP TRIGGER "events_inserted" ON "durable"."event";
+-- Drop "notify_event" function
+DROP FUNCTION "durable"."notify_event";
@@ -0,0 +1,15 @@
+-- Create "notify_event" function
+CREATE FUNCTION "durable"."notify_event" () RETURNS trigger LANGUAGE plpgsql AS $$
+BEGIN
+        PERFORM pg_notify(
+            'durable:event',
+            jsonb_build_object(
+                'task_id', NEW.task_id,
+                'index', NEW.index
+            )::text
+        );
+        RETURN NULL;
+    END;
+$$;
+-- Create trigger "events_inserted"
+CREATE TRIGGER "events_inserted" AFTER INSERT ON "durable"."event" FOR EACH ROW EXECUTE FUNCTION "durable"."notify_event"();
@@ -0,0 +1,2 @@
+-- Modify "event" table
+ALTER TABLE "durable"."event" ADD COLUMN "scratch" bytea NULL;
@@ -0,0 +1,2 @@
+-- Modify "task" table
+ALTER TABLE "durable"."task" ADD COLUMN "entrypoint" text NULL;
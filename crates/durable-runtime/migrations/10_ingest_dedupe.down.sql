@@ -0,0 +1,2 @@
+-- Drop "ingest_dedupe" table
+DROP TABLE "durable"."ingest_dedupe";
@@ -0,0 +1,11 @@
+-- Create "ingest_dedupe" table
+CREATE TABLE durable.ingest_dedupe(
+    source      text        NOT NULL,
+    key         text        NOT NULL,
+    task_id     bigint,
+    created_at  timestamptz NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    PRIMARY KEY(source, key)
+);
+-- Create index "ingest_dedupe_created" to table: "ingest_dedupe"
+CREATE INDEX ingest_dedupe_created ON durable.ingest_dedupe(created_at ASC);
@@ -0,0 +1,9 @@
+-- Modify "task" table
+DROP INDEX "durable"."task_tenant";
+ALTER TABLE "durable"."task" DROP COLUMN "tenant";
+-- Modify "wasm" table
+--
+-- This will fail if multiple tenants have uploaded the same program.
+DROP INDEX "durable"."wasm_hash_tenant";
+ALTER TABLE "durable"."wasm" DROP COLUMN "tenant";
+ALTER TABLE "durable"."wasm" ADD CONSTRAINT "hash_unique" UNIQUE ("hash");
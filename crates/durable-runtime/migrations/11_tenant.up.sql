@@ -0,0 +1,9 @@
+-- Modify "wasm" table
+ALTER TABLE "durable"."wasm" ADD COLUMN "tenant" text NULL;
+ALTER TABLE "durable"."wasm" DROP CONSTRAINT "hash_unique";
+-- Create index "wasm_hash_tenant" to table: "wasm"
+CREATE UNIQUE INDEX wasm_hash_tenant ON durable.wasm(hash, (COALESCE(tenant, '')));
+-- Modify "task" table
+ALTER TABLE "durable"."task" ADD COLUMN "tenant" text NULL;
+-- Create index "task_tenant" to table: "task"
+CREATE INDEX task_tenant ON durable.task(tenant, id) WHERE tenant IS NOT NULL;
@@ -0,0 +1,3 @@
+-- Modify "task" table
+DROP INDEX "durable"."task_dedupe_key";
+ALTER TABLE "durable"."task" DROP COLUMN "dedupe_key";
@@ -0,0 +1,4 @@
+-- Modify "task" table
+ALTER TABLE "durable"."task" ADD COLUMN "dedupe_key" text NULL;
+-- Create index "task_dedupe_key" to table: "task"
+CREATE UNIQUE INDEX task_dedupe_key ON durable.task((COALESCE(tenant, '')), dedupe_key) WHERE dedupe_key IS NOT NULL;
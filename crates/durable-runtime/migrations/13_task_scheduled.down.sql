@@ -0,0 +1,2 @@
+-- Drop index "task_scheduled" from table: "task"
+DROP INDEX "durable"."task_scheduled";
@@ -0,0 +1,2 @@
+-- Create index "task_scheduled" to table: "task"
+CREATE INDEX task_scheduled ON durable.task(wakeup_at ASC) WHERE state = 'ready' AND wakeup_at IS NOT NULL;
@@ -0,0 +1,10 @@
+-- Drop trigger "task_dependency_completed"
+DROP TRIGGER "task_dependency_completed" ON "durable"."task";
+-- Drop trigger "task_dependency_inserted"
+DROP TRIGGER "task_dependency_inserted" ON "durable"."task_dependency";
+-- Drop "complete_task_dependency" function
+DROP FUNCTION "durable"."complete_task_dependency";
+-- Drop "check_task_dependency" function
+DROP FUNCTION "durable"."check_task_dependency";
+-- Drop "task_dependency" table
+DROP TABLE "durable"."task_dependency";
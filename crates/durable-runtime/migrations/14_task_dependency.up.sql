@@ -0,0 +1,95 @@
+-- Create "task_dependency" table
+CREATE TABLE durable.task_dependency(
+    task_id             bigint      NOT NULL,
+    depends_on          bigint      NOT NULL,
+    propagate_failure   boolean     NOT NULL DEFAULT true,
+
+    PRIMARY KEY(task_id, depends_on),
+
+    CONSTRAINT fk_task          FOREIGN KEY(task_id)    REFERENCES durable.task(id)
+        ON DELETE CASCADE,
+    CONSTRAINT fk_depends_on    FOREIGN KEY(depends_on) REFERENCES durable.task(id)
+        ON DELETE CASCADE
+);
+-- Create index "task_dependency_depends_on" to table: "task_dependency"
+CREATE INDEX task_dependency_depends_on ON durable.task_dependency(depends_on);
+-- Create "check_task_dependency" function
+CREATE FUNCTION "durable"."check_task_dependency" () RETURNS trigger LANGUAGE plpgsql AS $$
+DECLARE
+        dependency_state durable.task_state;
+    BEGIN
+        -- Lock the dependency so that it cannot complete until this transaction
+        -- commits. Otherwise, we could miss it failing.
+        SELECT state INTO dependency_state
+         FROM durable.task
+        WHERE id = NEW.depends_on
+        FOR SHARE;
+
+        IF dependency_state = 'failed' AND NEW.propagate_failure THEN
+            -- Errors are logged at index i32::MAX - 1, same as the runtime does.
+            INSERT INTO durable.log(task_id, index, message)
+            VALUES (NEW.task_id, 2147483646, 'dependency ' || NEW.depends_on || E' failed\n')
+            ON CONFLICT DO NOTHING;
+
+            UPDATE durable.task
+              SET state = 'failed',
+                  completed_at = CURRENT_TIMESTAMP,
+                  running_on = NULL
+            WHERE id = NEW.task_id
+              AND state = 'ready';
+        END IF;
+
+        RETURN NULL;
+    END;
+$$;
+-- Create "complete_task_dependency" function
+CREATE FUNCTION "durable"."complete_task_dependency" () RETURNS trigger LANGUAGE plpgsql AS $$
+BEGIN
+        IF NEW.state = 'failed' THEN
+            INSERT INTO durable.log(task_id, index, message)
+            SELECT dep.task_id, 2147483646, 'dependency ' || NEW.id || E' failed\n'
+             FROM durable.task_dependency dep
+             JOIN durable.task ON task.id = dep.task_id
+            WHERE dep.depends_on = NEW.id
+              AND dep.propagate_failure
+              AND task.state = 'ready'
+            ON CONFLICT DO NOTHING;
+
+            UPDATE durable.task
+              SET state = 'failed',
+                  completed_at = CURRENT_TIMESTAMP,
+                  running_on = NULL
+             FROM durable.task_dependency dep
+            WHERE dep.task_id = task.id
+              AND dep.depends_on = NEW.id
+              AND dep.propagate_failure
+              AND task.state = 'ready';
+        END IF;
+
+        -- Let the workers know that these tasks may now be ready to run.
+        PERFORM pg_notify(
+            'durable:task',
+            jsonb_build_object(
+                'id', task.id,
+                'running_on', task.running_on
+            )::text
+        )
+         FROM durable.task_dependency dep
+         JOIN durable.task ON task.id = dep.task_id
+        WHERE dep.depends_on = NEW.id
+          AND task.state = 'ready';
+
+        RETURN NULL;
+    END;
+$$;
+-- Create trigger "task_dependency_inserted"
+CREATE TRIGGER "task_dependency_inserted" AFTER INSERT ON "durable"."task_dependency" FOR EACH ROW EXECUTE FUNCTION "durable"."check_task_dependency"();
+-- Create trigger "task_dependency_completed"
+CREATE TRIGGER "task_dependency_completed"
+    AFTER UPDATE OF "state" ON "durable"."task"
+    FOR EACH ROW WHEN (
+        (new.state = ANY (ARRAY['complete'::durable.task_state, 'failed'::durable.task_state]))
+        AND
+        (NOT (old.state = ANY (ARRAY['complete'::durable.task_state, 'failed'::durable.task_state])))
+    )
+    EXECUTE FUNCTION "durable"."complete_task_dependency"();
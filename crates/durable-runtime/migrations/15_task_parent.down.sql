@@ -0,0 +1,7 @@
+-- Drop trigger "task_child_completed"
+DROP TRIGGER "task_child_completed" ON "durable"."task";
+-- Drop "notify_parent_task" function
+DROP FUNCTION "durable"."notify_parent_task";
+-- Modify "task" table
+DROP INDEX "durable"."task_parent";
+ALTER TABLE "durable"."task" DROP CONSTRAINT "fk_parent", DROP COLUMN "result", DROP COLUMN "parent_id";
@@ -0,0 +1,31 @@
+-- Modify "task" table
+ALTER TABLE "durable"."task" ADD COLUMN "parent_id" bigint NULL, ADD COLUMN "result" jsonb NULL, ADD CONSTRAINT "fk_parent" FOREIGN KEY ("parent_id") REFERENCES "durable"."task" ("id") ON UPDATE NO ACTION ON DELETE SET NULL;
+-- Create index "task_parent" to table: "task"
+CREATE INDEX task_parent ON durable.task(parent_id) WHERE parent_id IS NOT NULL;
+-- Create "notify_parent_task" function
+CREATE FUNCTION "durable"."notify_parent_task" () RETURNS trigger LANGUAGE plpgsql AS $$
+BEGIN
+        INSERT INTO durable.notification(task_id, event, data)
+        SELECT parent.id, 'durable:child-complete', jsonb_build_object(
+            'id', NEW.id,
+            'success', NEW.state = 'complete',
+            'result', NEW.result
+        )
+         FROM durable.task parent
+        WHERE parent.id = NEW.parent_id
+          AND NOT parent.state IN ('complete', 'failed');
+
+        RETURN NULL;
+    END;
+$$;
+-- Create trigger "task_child_completed"
+CREATE TRIGGER "task_child_completed"
+    AFTER UPDATE OF "state" ON "durable"."task"
+    FOR EACH ROW WHEN (
+        (new.parent_id IS NOT NULL)
+        AND
+        (new.state = ANY (ARRAY['complete'::durable.task_state, 'failed'::durable.task_state]))
+        AND
+        (NOT (old.state = ANY (ARRAY['complete'::durable.task_state, 'failed'::durable.task_state])))
+    )
+    EXECUTE FUNCTION "durable"."notify_parent_task"();
@@ -0,0 +1,2 @@
+-- Drop "approval" table
+DROP TABLE "durable"."approval";
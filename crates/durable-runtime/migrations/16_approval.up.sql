@@ -0,0 +1,13 @@
+-- Create "approval" table
+CREATE TABLE durable.approval(
+    task_id         bigint      NOT NULL,
+    key             text        NOT NULL,
+    payload         jsonb       NOT NULL,
+    approved_by     text,
+    approved_at     timestamptz NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    PRIMARY KEY(task_id, key),
+
+    CONSTRAINT fk_task FOREIGN KEY(task_id) REFERENCES durable.task(id)
+        ON DELETE CASCADE
+);
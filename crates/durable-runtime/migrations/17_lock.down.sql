@@ -0,0 +1,12 @@
+-- Drop trigger "lock_lease_deleted"
+DROP TRIGGER "lock_lease_deleted" ON "durable"."lock_lease";
+-- Drop trigger "task_locks_released"
+DROP TRIGGER "task_locks_released" ON "durable"."task";
+-- Drop "notify_lock_released" function
+DROP FUNCTION "durable"."notify_lock_released";
+-- Drop "release_task_locks" function
+DROP FUNCTION "durable"."release_task_locks";
+-- Drop "lock_waiter" table
+DROP TABLE "durable"."lock_waiter";
+-- Drop "lock_lease" table
+DROP TABLE "durable"."lock_lease";
@@ -0,0 +1,65 @@
+-- Create "lock_lease" table
+CREATE TABLE durable.lock_lease(
+    name            text        NOT NULL,
+    task_id         bigint      NOT NULL,
+    acquired_at     timestamptz NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    expires_at      timestamptz,
+
+    PRIMARY KEY(name, task_id),
+
+    CONSTRAINT fk_task FOREIGN KEY(task_id) REFERENCES durable.task(id)
+        ON DELETE CASCADE
+);
+-- Create index "lock_lease_task" to table: "lock_lease"
+CREATE INDEX lock_lease_task ON durable.lock_lease(task_id);
+-- Create "lock_waiter" table
+CREATE TABLE durable.lock_waiter(
+    name            text        NOT NULL,
+    task_id         bigint      NOT NULL,
+    created_at      timestamptz NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    PRIMARY KEY(name, task_id),
+
+    CONSTRAINT fk_task FOREIGN KEY(task_id) REFERENCES durable.task(id)
+        ON DELETE CASCADE
+);
+-- Create index "lock_waiter_task" to table: "lock_waiter"
+CREATE INDEX lock_waiter_task ON durable.lock_waiter(task_id);
+-- Create "release_task_locks" function
+CREATE FUNCTION "durable"."release_task_locks" () RETURNS trigger LANGUAGE plpgsql AS $$
+BEGIN
+        DELETE FROM durable.lock_waiter WHERE task_id = NEW.id;
+        DELETE FROM durable.lock_lease  WHERE task_id = NEW.id;
+
+        RETURN NULL;
+    END;
+$$;
+-- Create "notify_lock_released" function
+CREATE FUNCTION "durable"."notify_lock_released" () RETURNS trigger LANGUAGE plpgsql AS $$
+BEGIN
+        WITH waiters AS (
+            DELETE FROM durable.lock_waiter
+            WHERE name = OLD.name
+              AND task_id != OLD.task_id
+            RETURNING task_id
+        )
+        INSERT INTO durable.notification(task_id, event, data)
+        SELECT task_id, 'durable:lock-released', jsonb_build_object('name', OLD.name)
+         FROM waiters;
+
+        RETURN NULL;
+    END;
+$$;
+-- Create trigger "task_locks_released"
+CREATE TRIGGER "task_locks_released"
+    AFTER UPDATE OF "state" ON "durable"."task"
+    FOR EACH ROW WHEN (
+        (new.state = ANY (ARRAY['complete'::durable.task_state, 'failed'::durable.task_state]))
+        AND
+        (NOT (old.state = ANY (ARRAY['complete'::durable.task_state, 'failed'::durable.task_state])))
+    )
+    EXECUTE FUNCTION "durable"."release_task_locks"();
+-- Create trigger "lock_lease_deleted"
+CREATE TRIGGER "lock_lease_deleted"
+    AFTER DELETE ON "durable"."lock_lease"
+    FOR EACH ROW EXECUTE FUNCTION "durable"."notify_lock_released"();
@@ -0,0 +1,8 @@
+-- Drop trigger "task_rate_limit_queues_left"
+DROP TRIGGER "task_rate_limit_queues_left" ON "durable"."task";
+-- Drop "leave_rate_limit_queues" function
+DROP FUNCTION "durable"."leave_rate_limit_queues";
+-- Drop "rate_limit_waiter" table
+DROP TABLE "durable"."rate_limit_waiter";
+-- Drop "rate_limit" table
+DROP TABLE "durable"."rate_limit";
@@ -0,0 +1,39 @@
+-- Create "rate_limit" table
+CREATE TABLE durable.rate_limit(
+    name            text        NOT NULL,
+    tokens          float8      NOT NULL,
+    updated_at      timestamptz NOT NULL,
+
+    PRIMARY KEY(name)
+);
+-- Create "rate_limit_waiter" table
+CREATE TABLE durable.rate_limit_waiter(
+    name            text        NOT NULL,
+    task_id         bigint      NOT NULL,
+    created_at      timestamptz NOT NULL,
+    retry_at        timestamptz NOT NULL,
+
+    PRIMARY KEY(name, task_id),
+
+    CONSTRAINT fk_task FOREIGN KEY(task_id) REFERENCES durable.task(id)
+        ON DELETE CASCADE
+);
+-- Create index "rate_limit_waiter_task" to table: "rate_limit_waiter"
+CREATE INDEX rate_limit_waiter_task ON durable.rate_limit_waiter(task_id);
+-- Create "leave_rate_limit_queues" function
+CREATE FUNCTION "durable"."leave_rate_limit_queues" () RETURNS trigger LANGUAGE plpgsql AS $$
+BEGIN
+        DELETE FROM durable.rate_limit_waiter WHERE task_id = NEW.id;
+
+        RETURN NULL;
+    END;
+$$;
+-- Create trigger "task_rate_limit_queues_left"
+CREATE TRIGGER "task_rate_limit_queues_left"
+    AFTER UPDATE OF "state" ON "durable"."task"
+    FOR EACH ROW WHEN (
+        (new.state = ANY (ARRAY['complete'::durable.task_state, 'failed'::durable.task_state]))
+        AND
+        (NOT (old.state = ANY (ARRAY['complete'::durable.task_state, 'failed'::durable.task_state])))
+    )
+    EXECUTE FUNCTION "durable"."leave_rate_limit_queues"();
@@ -0,0 +1,2 @@
+-- Drop "llm_usage" table
+DROP TABLE "durable"."llm_usage";
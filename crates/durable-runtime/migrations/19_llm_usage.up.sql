@@ -0,0 +1,16 @@
+-- Create "llm_usage" table
+CREATE TABLE durable.llm_usage(
+    task_id             bigint      NOT NULL,
+    index               int         NOT NULL,
+    seq                 int         NOT NULL,
+    provider            text        NOT NULL,
+    model               text        NOT NULL,
+    prompt_tokens       bigint      NOT NULL,
+    completion_tokens   bigint      NOT NULL,
+    created_at          timestamptz NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    PRIMARY KEY(task_id, index, seq),
+
+    CONSTRAINT fk_task FOREIGN KEY(task_id) REFERENCES durable.task(id)
+        ON DELETE CASCADE
+);
@@ -0,0 +1,2 @@
+-- Drop "task_payload" table
+DROP TABLE "durable"."task_payload";
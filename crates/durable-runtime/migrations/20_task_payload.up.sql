@@ -0,0 +1,10 @@
+-- Create "task_payload" table
+CREATE TABLE durable.task_payload(
+    task_id         bigint      NOT NULL,
+    data            jsonb       NOT NULL,
+
+    PRIMARY KEY(task_id),
+
+    CONSTRAINT fk_task FOREIGN KEY(task_id) REFERENCES durable.task(id)
+        ON DELETE CASCADE
+);
@@ -0,0 +1,2 @@
+-- Modify "task" table
+ALTER TABLE "durable"."task" DROP COLUMN "failure_message", DROP COLUMN "failure_label", DROP COLUMN "failure_index", DROP COLUMN "failure_backtrace";
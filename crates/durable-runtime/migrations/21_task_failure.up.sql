@@ -0,0 +1,2 @@
+-- Modify "task" table
+ALTER TABLE "durable"."task" ADD COLUMN "failure_message" text NULL, ADD COLUMN "failure_label" text NULL, ADD COLUMN "failure_index" integer NULL, ADD COLUMN "failure_backtrace" text NULL;
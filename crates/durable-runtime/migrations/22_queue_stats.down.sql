@@ -0,0 +1,2 @@
+-- Drop "queue_stats" table
+DROP TABLE "durable"."queue_stats";
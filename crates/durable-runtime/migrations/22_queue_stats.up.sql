@@ -0,0 +1,11 @@
+-- Create "queue_stats" table
+CREATE TABLE durable.queue_stats(
+    queue               text        NOT NULL,
+    pending             bigint      NOT NULL,
+    running             bigint      NOT NULL,
+    suspended           bigint      NOT NULL,
+    oldest_pending_at   timestamptz,
+    updated_at          timestamptz NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    PRIMARY KEY(queue)
+);
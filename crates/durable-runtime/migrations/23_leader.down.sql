@@ -0,0 +1,6 @@
+-- Drop trigger "worker_updated"
+DROP TRIGGER "worker_updated" ON "durable"."worker";
+-- Drop "leader" view
+DROP VIEW "durable"."leader";
+-- Modify "worker" table
+ALTER TABLE "durable"."worker" DROP COLUMN "leader_eligible";
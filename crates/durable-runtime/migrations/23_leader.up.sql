@@ -0,0 +1,15 @@
+-- Modify "worker" table
+ALTER TABLE "durable"."worker" ADD COLUMN "leader_eligible" boolean NOT NULL DEFAULT true;
+-- Create "leader" view
+CREATE VIEW "durable"."leader" (
+  "id",
+  "started_at",
+  "heartbeat_at"
+) AS SELECT id,
+    started_at,
+    heartbeat_at
+   FROM durable.worker
+  ORDER BY leader_eligible DESC, started_at, id
+ LIMIT 1;
+-- Create trigger "worker_updated"
+CREATE TRIGGER "worker_updated" AFTER UPDATE OF "leader_eligible" ON "durable"."worker" FOR EACH ROW EXECUTE FUNCTION "durable"."notify_worker"();
@@ -0,0 +1,22 @@
+-- Merge "log" partitions
+ALTER TABLE "durable"."log" DETACH PARTITION "durable"."log_default";
+INSERT INTO "durable"."log_default" ("task_id", "index", "message", "created_at") SELECT "task_id", "index", "message", "created_at" FROM "durable"."log";
+-- Drop "log" table
+DROP TABLE "durable"."log";
+-- Rename "log_default" table
+ALTER TABLE "durable"."log_default" RENAME TO "log";
+ALTER TABLE "durable"."log" RENAME CONSTRAINT "log_default_pkey" TO "log_pkey";
+DROP TRIGGER IF EXISTS "logs_inserted" ON "durable"."log";
+-- Create trigger "logs_inserted"
+CREATE TRIGGER "logs_inserted" AFTER INSERT ON "durable"."log" FOR EACH ROW EXECUTE FUNCTION "durable"."notify_log"();
+-- Merge "event" partitions
+ALTER TABLE "durable"."event" DETACH PARTITION "durable"."event_default";
+INSERT INTO "durable"."event_default" ("task_id", "index", "created_at", "label", "value", "scratch") SELECT "task_id", "index", "created_at", "label", "value", "scratch" FROM "durable"."event";
+-- Drop "event" table
+DROP TABLE "durable"."event";
+-- Rename "event_default" table
+ALTER TABLE "durable"."event_default" RENAME TO "event";
+ALTER TABLE "durable"."event" RENAME CONSTRAINT "event_default_pkey" TO "event_pkey";
+DROP TRIGGER IF EXISTS "events_inserted" ON "durable"."event";
+-- Create trigger "events_inserted"
+CREATE TRIGGER "events_inserted" AFTER INSERT ON "durable"."event" FOR EACH ROW EXECUTE FUNCTION "durable"."notify_event"();
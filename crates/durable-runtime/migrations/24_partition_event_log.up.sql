@@ -0,0 +1,37 @@
+-- Rename "event" table
+--
+-- The existing table becomes the default partition of the new partitioned
+-- table so that none of the existing events need to be copied.
+ALTER TABLE "durable"."event" RENAME TO "event_default";
+ALTER TABLE "durable"."event_default" RENAME CONSTRAINT "event_pkey" TO "event_default_pkey";
+DROP TRIGGER "events_inserted" ON "durable"."event_default";
+-- Create "event" table
+CREATE TABLE "durable"."event" (
+  "task_id" bigint NOT NULL,
+  "index" integer NOT NULL,
+  "created_at" timestamptz NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  "label" text NOT NULL,
+  "value" jsonb NOT NULL,
+  "scratch" bytea NULL,
+  PRIMARY KEY ("task_id", "index"),
+  CONSTRAINT "fk_task" FOREIGN KEY ("task_id") REFERENCES "durable"."task" ("id") ON UPDATE NO ACTION ON DELETE CASCADE
+) PARTITION BY RANGE ("task_id");
+ALTER TABLE "durable"."event" ATTACH PARTITION "durable"."event_default" DEFAULT;
+-- Create trigger "events_inserted"
+CREATE TRIGGER "events_inserted" AFTER INSERT ON "durable"."event" FOR EACH ROW EXECUTE FUNCTION "durable"."notify_event"();
+-- Rename "log" table
+ALTER TABLE "durable"."log" RENAME TO "log_default";
+ALTER TABLE "durable"."log_default" RENAME CONSTRAINT "log_pkey" TO "log_default_pkey";
+DROP TRIGGER "logs_inserted" ON "durable"."log_default";
+-- Create "log" table
+CREATE TABLE "durable"."log" (
+  "task_id" bigint NOT NULL,
+  "index" integer NOT NULL,
+  "message" text NOT NULL,
+  "created_at" timestamptz NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  PRIMARY KEY ("task_id", "index"),
+  CONSTRAINT "fk_task" FOREIGN KEY ("task_id") REFERENCES "durable"."task" ("id") ON UPDATE NO ACTION ON DELETE CASCADE
+) PARTITION BY RANGE ("task_id");
+ALTER TABLE "durable"."log" ATTACH PARTITION "durable"."log_default" DEFAULT;
+-- Create trigger "logs_inserted"
+CREATE TRIGGER "logs_inserted" AFTER INSERT ON "durable"."log" FOR EACH ROW EXECUTE FUNCTION "durable"."notify_log"();
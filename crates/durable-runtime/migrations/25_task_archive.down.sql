@@ -0,0 +1,2 @@
+-- Drop "task_archive" table
+DROP TABLE "durable"."task_archive";
@@ -0,0 +1,17 @@
+-- Create "task_archive" table
+CREATE TABLE durable.task_archive(
+    id              bigint      NOT NULL,
+    name            text        NOT NULL,
+    tenant          text,
+    state   durable.task_state  NOT NULL,
+    completed_at    timestamptz,
+    archived_at     timestamptz NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    task            jsonb       NOT NULL,
+    events          jsonb       NOT NULL,
+    logs            jsonb       NOT NULL,
+
+    PRIMARY KEY(id)
+);
+-- Create index "task_archive_completed_at" to table: "task_archive"
+CREATE INDEX task_archive_completed_at ON durable.task_archive(completed_at);
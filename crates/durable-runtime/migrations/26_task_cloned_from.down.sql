@@ -0,0 +1,3 @@
+-- Modify "task" table
+DROP INDEX "durable"."task_cloned_from";
+ALTER TABLE "durable"."task" DROP CONSTRAINT "fk_cloned_from", DROP COLUMN "cloned_from";
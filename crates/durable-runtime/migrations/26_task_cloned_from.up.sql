@@ -0,0 +1,4 @@
+-- Modify "task" table
+ALTER TABLE "durable"."task" ADD COLUMN "cloned_from" bigint NULL, ADD CONSTRAINT "fk_cloned_from" FOREIGN KEY ("cloned_from") REFERENCES "durable"."task" ("id") ON UPDATE NO ACTION ON DELETE SET NULL;
+-- Create index "task_cloned_from" to table: "task"
+CREATE INDEX task_cloned_from ON durable.task(cloned_from) WHERE cloned_from IS NOT NULL;
@@ -0,0 +1,2 @@
+-- Modify "event" table
+ALTER TABLE "durable"."event" DROP COLUMN "is_db";
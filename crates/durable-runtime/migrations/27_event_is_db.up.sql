@@ -0,0 +1,2 @@
+-- Modify "event" table
+ALTER TABLE "durable"."event" ADD COLUMN "is_db" boolean NULL;
@@ -0,0 +1,2 @@
+-- Modify "task" table
+ALTER TABLE "durable"."task" ADD COLUMN "attempt" integer NOT NULL DEFAULT 0;
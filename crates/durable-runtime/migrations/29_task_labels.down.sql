@@ -0,0 +1,3 @@
+-- Modify "task" table
+DROP INDEX "durable"."task_labels";
+ALTER TABLE "durable"."task" DROP COLUMN "labels";
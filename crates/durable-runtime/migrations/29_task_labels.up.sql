@@ -0,0 +1,4 @@
+-- Modify "task" table
+ALTER TABLE "durable"."task" ADD COLUMN "labels" jsonb NOT NULL DEFAULT '{}';
+-- Create index "task_labels" to table: "task"
+CREATE INDEX task_labels ON durable.task USING gin(labels jsonb_path_ops);
@@ -0,0 +1,8 @@
+-- Drop "queue_pause" table
+DROP TABLE "durable"."queue_pause";
+-- Drop "program_pause" table
+DROP TABLE "durable"."program_pause";
+-- Drop "queue_pause_changed" function
+DROP FUNCTION "durable"."queue_pause_changed";
+-- Drop "program_pause_changed" function
+DROP FUNCTION "durable"."program_pause_changed";
@@ -0,0 +1,80 @@
+-- Create "program_pause" table
+CREATE TABLE durable.program_pause(
+    wasm            bigint      NOT NULL,
+    reason          text,
+    created_at      timestamptz NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    PRIMARY KEY(wasm),
+
+    CONSTRAINT fk_wasm FOREIGN KEY(wasm) REFERENCES durable.wasm(id)
+        ON DELETE CASCADE
+);
+-- Create "queue_pause" table
+CREATE TABLE durable.queue_pause(
+    queue           text        NOT NULL,
+    reason          text,
+    created_at      timestamptz NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    PRIMARY KEY(queue)
+);
+-- Create "program_pause_changed" function
+CREATE FUNCTION "durable"."program_pause_changed" () RETURNS trigger LANGUAGE plpgsql AS $$
+BEGIN
+        IF (TG_OP = 'INSERT') THEN
+            -- Clearing running_on means that the workers running these tasks
+            -- will stop them the next time they try to commit a transaction.
+            UPDATE durable.task
+              SET state = 'ready',
+                  running_on = NULL
+            WHERE wasm = NEW.wasm
+              AND state = 'active';
+        ELSE
+            -- Let the workers know that these tasks can be claimed again.
+            PERFORM pg_notify(
+                'durable:task',
+                jsonb_build_object(
+                    'id', id,
+                    'running_on', running_on
+                )::text
+            )
+             FROM durable.task
+            WHERE wasm = OLD.wasm
+              AND state = 'ready';
+        END IF;
+
+        RETURN NULL;
+    END;
+$$;
+-- Create "queue_pause_changed" function
+CREATE FUNCTION "durable"."queue_pause_changed" () RETURNS trigger LANGUAGE plpgsql AS $$
+BEGIN
+        IF (TG_OP = 'INSERT') THEN
+            UPDATE durable.task
+              SET state = 'ready',
+                  running_on = NULL
+            WHERE COALESCE(tenant, '') = NEW.queue
+              AND state = 'active';
+        ELSE
+            PERFORM pg_notify(
+                'durable:task',
+                jsonb_build_object(
+                    'id', id,
+                    'running_on', running_on
+                )::text
+            )
+             FROM durable.task
+            WHERE COALESCE(tenant, '') = OLD.queue
+              AND state = 'ready';
+        END IF;
+
+        RETURN NULL;
+    END;
+$$;
+-- Create trigger "program_pause_changed"
+CREATE TRIGGER "program_pause_changed"
+    AFTER INSERT OR DELETE ON "durable"."program_pause"
+    FOR EACH ROW EXECUTE FUNCTION "durable"."program_pause_changed"();
+-- Create trigger "queue_pause_changed"
+CREATE TRIGGER "queue_pause_changed"
+    AFTER INSERT OR DELETE ON "durable"."queue_pause"
+    FOR EACH ROW EXECUTE FUNCTION "durable"."queue_pause_changed"();
@@ -0,0 +1,33 @@
+-- Modify "worker" table
+ALTER TABLE "durable"."worker" ADD COLUMN "instance" text NULL;
+-- Modify "task" table
+ALTER TABLE "durable"."task" ADD COLUMN "instance" text NULL;
+-- Modify "notify_notification" function
+CREATE OR REPLACE FUNCTION "durable"."notify_notification" () RETURNS trigger LANGUAGE plpgsql AS $$
+BEGIN
+        PERFORM pg_notify(
+            'durable:notification',
+            jsonb_build_object(
+                'task_id', NEW.task_id,
+                'event', NEW.event
+            )::text
+        );
+
+        -- Wake up the related task when a notification occurs.
+        UPDATE durable.task
+        SET state = 'ready',
+            wakeup_at = NULL,
+            running_on = (
+                SELECT id
+                 FROM durable.worker
+                WHERE worker.instance IS NOT DISTINCT FROM task.instance
+                ORDER BY random()
+                FOR SHARE SKIP LOCKED
+                LIMIT 1
+            )
+        WHERE id = NEW.task_id
+          AND state = 'suspended';
+
+        RETURN NULL;
+    END;
+$$;
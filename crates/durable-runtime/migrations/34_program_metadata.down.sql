@@ -0,0 +1,3 @@
+-- Modify "wasm" table
+DROP INDEX "durable"."wasm_name_version";
+ALTER TABLE "durable"."wasm" DROP COLUMN "built_at", DROP COLUMN "git_commit", DROP COLUMN "description", DROP COLUMN "version";
@@ -0,0 +1,4 @@
+-- Modify "wasm" table
+ALTER TABLE "durable"."wasm" ADD COLUMN "version" text NULL, ADD COLUMN "description" text NULL, ADD COLUMN "git_commit" text NULL, ADD COLUMN "built_at" timestamptz NULL;
+-- Create index "wasm_name_version" to table: "wasm"
+CREATE INDEX wasm_name_version ON durable.wasm(name, (COALESCE(tenant, ''))) WHERE version IS NOT NULL;
@@ -0,0 +1,10 @@
+-- Drop trigger "log_entries_synced"
+DROP TRIGGER "log_entries_synced" ON "durable"."log";
+-- Drop "sync_log_entries" function
+DROP FUNCTION "durable"."sync_log_entries";
+-- Drop "log_entry" table
+DROP TABLE "durable"."log_entry";
+-- Drop "parse_log_level" function
+DROP FUNCTION "durable"."parse_log_level";
+-- Drop enum type "log_level"
+DROP TYPE "durable"."log_level";
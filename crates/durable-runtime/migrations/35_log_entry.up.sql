@@ -0,0 +1,79 @@
+-- Create enum type "log_level"
+CREATE TYPE "durable"."log_level" AS ENUM ('trace', 'debug', 'info', 'warn', 'error');
+-- Create "log_entry" table
+CREATE TABLE "durable"."log_entry" (
+  "task_id" bigint NOT NULL,
+  "index" integer NOT NULL,
+  "line" integer NOT NULL,
+  "level" "durable"."log_level" NOT NULL,
+  "message" text NOT NULL,
+  "created_at" timestamptz NOT NULL,
+  PRIMARY KEY ("task_id", "index", "line"),
+  CONSTRAINT "fk_task" FOREIGN KEY ("task_id") REFERENCES "durable"."task" ("id") ON UPDATE NO ACTION ON DELETE CASCADE
+) PARTITION BY RANGE ("task_id");
+-- Create "log_entry_default" table
+CREATE TABLE "durable"."log_entry_default" PARTITION OF "durable"."log_entry" DEFAULT;
+-- Create index "log_entry_level" to table: "log_entry"
+CREATE INDEX "log_entry_level" ON "durable"."log_entry" ("task_id", "level", "index", "line");
+-- Create index "log_entry_created_at" to table: "log_entry"
+CREATE INDEX "log_entry_created_at" ON "durable"."log_entry" ("task_id", "created_at");
+-- Create "parse_log_level" function
+CREATE FUNCTION "durable"."parse_log_level" ("line" text) RETURNS "durable"."log_level" LANGUAGE sql IMMUTABLE AS $$
+SELECT COALESCE(
+        replace(
+            lower(substring(line FROM '(?i)^(?:\S*\d\S*\s+)?\[?(trace|debug|info|warn|warning|error)\M')),
+            'warning',
+            'warn'
+        ),
+        'info'
+    )::durable.log_level;
+$$;
+-- Create "sync_log_entries" function
+CREATE FUNCTION "durable"."sync_log_entries" () RETURNS trigger LANGUAGE plpgsql AS $$
+BEGIN
+        IF (TG_OP <> 'INSERT') THEN
+            DELETE FROM durable.log_entry
+            WHERE task_id = OLD.task_id
+              AND index = OLD.index;
+        END IF;
+
+        IF (TG_OP <> 'DELETE') THEN
+            INSERT INTO durable.log_entry(task_id, index, line, level, message, created_at)
+            SELECT
+                NEW.task_id,
+                NEW.index,
+                t.line - 1,
+                -- Errors and panics are logged at index i32::MAX - 1 and
+                -- i32::MAX by the runtime.
+                CASE
+                    WHEN NEW.index >= 2147483646 THEN 'error'
+                    ELSE durable.parse_log_level(t.message)
+                END,
+                t.message,
+                NEW.created_at
+             FROM regexp_split_to_table(rtrim(NEW.message, E'\n'), E'\n')
+                WITH ORDINALITY AS t(message, line)
+            WHERE rtrim(NEW.message, E'\n') <> '';
+        END IF;
+
+        RETURN NULL;
+    END;
+$$;
+-- Create trigger "log_entries_synced"
+CREATE TRIGGER "log_entries_synced" AFTER INSERT OR UPDATE OR DELETE ON "durable"."log" FOR EACH ROW EXECUTE FUNCTION "durable"."sync_log_entries"();
+-- Split existing logs into entries
+INSERT INTO "durable"."log_entry" ("task_id", "index", "line", "level", "message", "created_at")
+SELECT
+    l.task_id,
+    l.index,
+    t.line - 1,
+    CASE
+        WHEN l.index >= 2147483646 THEN 'error'
+        ELSE durable.parse_log_level(t.message)
+    END,
+    t.message,
+    l.created_at
+ FROM "durable"."log" l
+CROSS JOIN LATERAL regexp_split_to_table(rtrim(l.message, E'\n'), E'\n')
+    WITH ORDINALITY AS t(message, line)
+WHERE rtrim(l.message, E'\n') <> '';
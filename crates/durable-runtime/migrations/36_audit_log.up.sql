@@ -0,0 +1,18 @@
+-- Create "audit_log" table
+CREATE TABLE durable.audit_log(
+    task_id         bigint      NOT NULL,
+    index           int         NOT NULL,
+    seq             int         NOT NULL,
+    kind            text        NOT NULL,
+    operation       text        NOT NULL,
+    target          text,
+    tables          text[]      NOT NULL DEFAULT '{}',
+    rows_affected   bigint,
+    status          int,
+    created_at      timestamptz NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    PRIMARY KEY(task_id, index, seq),
+
+    CONSTRAINT fk_task FOREIGN KEY(task_id) REFERENCES durable.task(id)
+        ON DELETE CASCADE
+);
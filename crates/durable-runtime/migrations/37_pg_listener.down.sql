@@ -0,0 +1,10 @@
+-- Drop trigger "task_pg_listeners_left"
+DROP TRIGGER "task_pg_listeners_left" ON "durable"."task";
+-- Drop trigger "pg_listener_inserted"
+DROP TRIGGER "pg_listener_inserted" ON "durable"."pg_listener";
+-- Drop "leave_pg_listeners" function
+DROP FUNCTION "durable"."leave_pg_listeners";
+-- Drop "notify_pg_listener" function
+DROP FUNCTION "durable"."notify_pg_listener";
+-- Drop "pg_listener" table
+DROP TABLE "durable"."pg_listener";
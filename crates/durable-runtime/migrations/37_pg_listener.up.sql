@@ -0,0 +1,44 @@
+-- Create "pg_listener" table
+CREATE TABLE durable.pg_listener(
+    channel         text        NOT NULL,
+    task_id         bigint      NOT NULL,
+    created_at      timestamptz NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    PRIMARY KEY(channel, task_id),
+
+    CONSTRAINT fk_task FOREIGN KEY(task_id) REFERENCES durable.task(id)
+        ON DELETE CASCADE
+);
+-- Create index "pg_listener_task" to table: "pg_listener"
+CREATE INDEX pg_listener_task ON durable.pg_listener(task_id);
+-- Create "notify_pg_listener" function
+CREATE FUNCTION "durable"."notify_pg_listener" () RETURNS trigger LANGUAGE plpgsql AS $$
+BEGIN
+        PERFORM pg_notify(
+            'durable:pg-listen',
+            jsonb_build_object('channel', NEW.channel)::text
+        );
+        RETURN NULL;
+    END;
+$$;
+-- Create "leave_pg_listeners" function
+CREATE FUNCTION "durable"."leave_pg_listeners" () RETURNS trigger LANGUAGE plpgsql AS $$
+BEGIN
+        DELETE FROM durable.pg_listener WHERE task_id = NEW.id;
+
+        RETURN NULL;
+    END;
+$$;
+-- Create trigger "pg_listener_inserted"
+CREATE TRIGGER "pg_listener_inserted"
+    AFTER INSERT ON "durable"."pg_listener"
+    FOR EACH ROW EXECUTE FUNCTION "durable"."notify_pg_listener"();
+-- Create trigger "task_pg_listeners_left"
+CREATE TRIGGER "task_pg_listeners_left"
+    AFTER UPDATE OF "state" ON "durable"."task"
+    FOR EACH ROW WHEN (
+        (new.state = ANY (ARRAY['complete'::durable.task_state, 'failed'::durable.task_state]))
+        AND
+        (NOT (old.state = ANY (ARRAY['complete'::durable.task_state, 'failed'::durable.task_state])))
+    )
+    EXECUTE FUNCTION "durable"."leave_pg_listeners"();
@@ -18,12 +18,32 @@ CREATE TABLE durable.worker(
     -- This should _never_ be set externally, as that would cause multiple
     -- workers to think that they are the leader.
     started_at      timestamptz NOT NULL DEFAULT CURRENT_TIMESTAMP,
-    heartbeat_at    timestamptz NOT NULL DEFAULT CURRENT_TIMESTAMP
+    heartbeat_at    timestamptz NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    -- Whether this worker may become the leader.
+    --
+    -- This is cleared to hand leadership off to another worker, e.g. before
+    -- taking the current leader down for maintenance. A worker that is not
+    -- eligible only becomes the leader if no eligible workers remain.
+    leader_eligible boolean     NOT NULL DEFAULT true,
+    -- The durable instance that this worker belongs to.
+    --
+    -- Workers only run tasks that belong to the same instance. NULL is the
+    -- default instance.
+    instance        text
 );
 
 CREATE INDEX worker_started   ON durable.worker(started_at ASC);
 CREATE INDEX worker_heartbeat ON durable.worker(heartbeat_at ASC);
 
+-- The current leader of the cluster.
+--
+-- The leader is the oldest worker that is eligible to be the leader.
+CREATE VIEW durable.leader AS
+    SELECT id, started_at, heartbeat_at
+      FROM durable.worker
+     ORDER BY leader_eligible DESC, started_at ASC, id ASC
+     LIMIT 1;
+
 -- Wasm binaries for use by various tasks.
 --
 -- This allows the binary itself to be shared by multiple jobs, all of which
@@ -36,7 +56,7 @@ CREATE TABLE durable.wasm(
     -- A SHA256 hash of the the wasm program here.
     --
     -- This is to avoid storing duplicate wasm programs in the database.
-    hash        bytea       NOT NULL CONSTRAINT hash_unique UNIQUE,
+    hash        bytea       NOT NULL,
     wasm        bytea       NOT NULL,
 
     -- An optional name for this program.
@@ -50,7 +70,47 @@ CREATE TABLE durable.wasm(
     -- Clients will update this peridically on use. A row will only be
     -- automatically deleted from this table if there are no workflows that use
     -- it and its last_used timestamp is more than a day in the past.
-    last_used   timestamptz NOT NULL DEFAULT CURRENT_TIMESTAMP
+    last_used   timestamptz NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    -- The tenant that uploaded this program.
+    --
+    -- Programs are only shared between clients of the same tenant, so the
+    -- same binary uploaded by two tenants is stored twice.
+    tenant      text,
+
+    -- Optional metadata describing the build of this program.
+    --
+    -- The version is a semantic version which clients use to look up
+    -- programs by name and version requirement. The rest is informational.
+    version     text,
+    description text,
+    git_commit  text,
+    built_at    timestamptz
+);
+
+CREATE UNIQUE INDEX wasm_hash_tenant ON durable.wasm(hash, (COALESCE(tenant, '')));
+CREATE INDEX wasm_name_version ON durable.wasm(name, (COALESCE(tenant, '')))
+    WHERE version IS NOT NULL;
+
+-- Precompiled artifacts for wasm binaries.
+--
+-- Clients can optionally compile a program ahead of time when uploading it.
+-- Workers whose engine configuration matches that used to create an artifact
+-- can then deserialize it directly instead of compiling the program
+-- themselves.
+CREATE TABLE durable.wasm_artifact(
+    wasm        bigint      NOT NULL,
+
+    -- A SHA256 hash of the wasmtime engine compatibility hash.
+    --
+    -- An artifact can only be loaded by an engine whose compatibility hash
+    -- matches the one used to compile it.
+    compat      bytea       NOT NULL,
+    artifact    bytea       NOT NULL,
+    created_at  timestamptz NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    PRIMARY KEY(wasm, compat),
+    CONSTRAINT fk_wasm FOREIGN KEY(wasm) REFERENCES durable.wasm(id) ON DELETE CASCADE
 );
 
 CREATE TYPE durable.task_state AS ENUM(
@@ -67,19 +127,96 @@ CREATE TABLE durable.task(
     state   durable.task_state  NOT NULL DEFAULT 'ready',
     running_on      bigint,
 
+    -- The number of times that a worker has started running this task.
+    attempt         int         NOT NULL DEFAULT 0,
+
     created_at      timestamptz NOT NULL DEFAULT CURRENT_TIMESTAMP,
     completed_at    timestamptz,
+
+    -- For suspended tasks, the time at which they should be woken up. For
+    -- ready tasks, the time before which they may not be started.
     wakeup_at       timestamptz,
 
+    -- The time at which an operator paused this task, if it is paused.
+    --
+    -- Paused tasks are not claimed by workers until they are resumed. Pausing a
+    -- running task clears running_on, so the worker running it stops at the
+    -- next transaction boundary.
+    paused_at       timestamptz,
+
     -- The compiled WASM bytecode.
     --
-    -- This gets set to NULL once the task has executed to completion.
+    -- This gets set to NULL once the task has completed successfully. Failed
+    -- tasks keep it so that they can be retried.
     wasm            bigint,
     data            jsonb       NOT NULL,
 
+    -- Configuration parameters that are set (via SET LOCAL) at the start of
+    -- every database transaction run by the task.
+    sql_context     jsonb,
+
+    -- The name of the workflow exported by the program that this task runs.
+    --
+    -- If NULL then the task runs the program's `wasi:cli/run` export.
+    entrypoint      text,
+
+    -- The tenant that launched this task.
+    --
+    -- Clients only see tasks belonging to their own tenant. Workers may limit
+    -- how many tasks of each tenant they run at once.
+    tenant          text,
+
+    -- The durable instance that this task belongs to.
+    --
+    -- Only workers in the same instance will run this task. This allows
+    -- separate fleets of workers (e.g. for a blue/green deployment) to share
+    -- the same tables.
+    instance        text,
+
+    -- A key provided by the client that launched this task.
+    --
+    -- Launching a task with the same key (within the same tenant) as an
+    -- existing task returns the existing task instead of creating a new one.
+    dedupe_key      text,
+
+    -- The task that launched this task as a child, if any.
+    --
+    -- The parent is sent a `durable:child-complete` notification once this
+    -- task completes.
+    parent_id       bigint,
+
+    -- The task that this task is a copy of, if it was launched by cloning an
+    -- existing task.
+    cloned_from     bigint,
+
+    -- Arbitrary key/value labels provided by the client that launched this
+    -- task. These are stored as a JSON object with string values.
+    labels          jsonb       NOT NULL DEFAULT '{}',
+
+    -- Structured status published by the workflow while it runs. Unlike the
+    -- labels, this is updated by the task itself.
+    metadata        jsonb       NOT NULL DEFAULT '{}',
+
+    -- The JSON result set by the task, if any.
+    result          jsonb,
+
+    -- Details about why the task failed, if it did.
+    --
+    -- The label and index are those of the last transaction that the task
+    -- entered before it failed. The backtrace is only present if the task
+    -- failed due to a wasm trap.
+    failure_message     text,
+    failure_label       text,
+    failure_index       int,
+    failure_backtrace   text,
+
     CONSTRAINT fk_worker FOREIGN KEY(running_on) REFERENCES durable.worker(id)
         ON DELETE SET NULL,
     CONSTRAINT fk_wasm   FOREIGN KEY(wasm)       REFERENCES durable.wasm(id),
+    CONSTRAINT fk_parent FOREIGN KEY(parent_id)  REFERENCES durable.task(id)
+        ON DELETE SET NULL,
+    CONSTRAINT fk_cloned_from FOREIGN KEY(cloned_from) REFERENCES durable.task(id)
+        ON DELETE SET NULL,
 
     CONSTRAINT check_wasm_while_active CHECK (
         wasm IS NOT NULL OR (state IN ('complete', 'failed'))
@@ -92,6 +229,17 @@ CREATE INDEX task_wasm ON durable.task(wasm)
     WHERE wasm IS NOT NULL;
 CREATE INDEX task_suspended ON durable.task(wakeup_at ASC NULLS LAST)
     WHERE state = 'suspended';
+CREATE INDEX task_scheduled ON durable.task(wakeup_at ASC)
+    WHERE state = 'ready' AND wakeup_at IS NOT NULL;
+CREATE INDEX task_tenant ON durable.task(tenant, id)
+    WHERE tenant IS NOT NULL;
+CREATE UNIQUE INDEX task_dedupe_key ON durable.task((COALESCE(tenant, '')), dedupe_key)
+    WHERE dedupe_key IS NOT NULL;
+CREATE INDEX task_parent ON durable.task(parent_id)
+    WHERE parent_id IS NOT NULL;
+CREATE INDEX task_cloned_from ON durable.task(cloned_from)
+    WHERE cloned_from IS NOT NULL;
+CREATE INDEX task_labels ON durable.task USING gin(labels jsonb_path_ops);
 
 CREATE TABLE durable.event(
     task_id         bigint      NOT NULL,
@@ -105,11 +253,27 @@ CREATE TABLE durable.event(
     -- The actual value that was returned by the transaction.
     value           jsonb       NOT NULL,
 
+    -- A snapshot of the task's scratch directory, if the transaction modified
+    -- it.
+    scratch         bytea,
+
+    -- Whether the transaction that recorded this event was a database
+    -- transaction. This is NULL for events recorded by older workers.
+    is_db           boolean,
+
     PRIMARY KEY(task_id, index),
 
     CONSTRAINT fk_task FOREIGN KEY(task_id) REFERENCES durable.task(id)
         ON DELETE CASCADE
-);
+) PARTITION BY RANGE (task_id);
+
+-- Events for tasks that are not covered by any other partition.
+--
+-- The cluster leader creates partitions named `event_<start>_<end>` ahead of
+-- the task ids that are currently in use and drops them once all of their
+-- tasks have been cleaned up. This partition holds everything else, including
+-- all events from before the table was partitioned.
+CREATE TABLE durable.event_default PARTITION OF durable.event DEFAULT;
 
 CREATE TABLE durable.notification(
     task_id         bigint      NOT NULL,
@@ -124,6 +288,25 @@ CREATE TABLE durable.notification(
 
 CREATE INDEX notification_recent ON durable.notification(task_id, created_at ASC);
 
+-- Approvals that have been given to tasks.
+--
+-- Each approval is also delivered to the task as a `durable:approval`
+-- notification. These rows are kept around as an audit record.
+CREATE TABLE durable.approval(
+    task_id         bigint      NOT NULL,
+    key             text        NOT NULL,
+    payload         jsonb       NOT NULL,
+
+    -- Who gave the approval, as reported by the client.
+    approved_by     text,
+    approved_at     timestamptz NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    PRIMARY KEY(task_id, key),
+
+    CONSTRAINT fk_task FOREIGN KEY(task_id) REFERENCES durable.task(id)
+        ON DELETE CASCADE
+);
+
 CREATE TABLE durable.log(
     task_id         bigint      NOT NULL,
     index           int         NOT NULL,
@@ -134,8 +317,306 @@ CREATE TABLE durable.log(
 
     CONSTRAINT fk_task  FOREIGN KEY(task_id) REFERENCES durable.task(id)
         ON DELETE CASCADE
+) PARTITION BY RANGE (task_id);
+
+-- Logs for tasks that are not covered by any other partition.
+--
+-- This is partitioned in the same way as durable.event.
+CREATE TABLE durable.log_default PARTITION OF durable.log DEFAULT;
+
+CREATE TYPE durable.log_level AS ENUM(
+    'trace',
+    'debug',
+    'info',
+    'warn',
+    'error'
+);
+
+-- The lines of durable.log, split out so that they can be filtered and paged
+-- through.
+--
+-- This is kept in sync with durable.log by the log_entries_synced trigger and
+-- should not be written to directly.
+CREATE TABLE durable.log_entry(
+    task_id         bigint              NOT NULL,
+    index           int                 NOT NULL,
+    -- The line number within the log message, starting from 0.
+    line            int                 NOT NULL,
+    level           durable.log_level   NOT NULL,
+    message         text                NOT NULL,
+    created_at      timestamptz         NOT NULL,
+
+    PRIMARY KEY(task_id, index, line),
+
+    CONSTRAINT fk_task  FOREIGN KEY(task_id) REFERENCES durable.task(id)
+        ON DELETE CASCADE
+) PARTITION BY RANGE (task_id);
+
+CREATE TABLE durable.log_entry_default PARTITION OF durable.log_entry DEFAULT;
+
+CREATE INDEX log_entry_level ON durable.log_entry(task_id, level, index, line);
+CREATE INDEX log_entry_created_at ON durable.log_entry(task_id, created_at);
+
+-- Messages from external queues that have already launched a task.
+--
+-- Queues only guarantee at-least-once delivery so the same message may be
+-- received more than once. Workers record the dedupe key of each message in
+-- the same transaction that launches its task and skip any message whose key
+-- is already present. Rows are deleted once they are older than the
+-- configured dedupe window.
+CREATE TABLE durable.ingest_dedupe(
+    source      text        NOT NULL,
+    key         text        NOT NULL,
+
+    -- The task that was launched for this message.
+    --
+    -- This is not a foreign key since the task may be cleaned up before the
+    -- dedupe entry is.
+    task_id     bigint,
+    created_at  timestamptz NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    PRIMARY KEY(source, key)
+);
+
+CREATE INDEX ingest_dedupe_created ON durable.ingest_dedupe(created_at ASC);
+
+-- Dependencies between tasks.
+--
+-- A task is not started until all the tasks it depends on have completed
+-- successfully. If a dependency fails then the task is failed as well, unless
+-- propagate_failure is false, in which case the dependency is treated as if it
+-- had completed.
+CREATE TABLE durable.task_dependency(
+    task_id             bigint      NOT NULL,
+    depends_on          bigint      NOT NULL,
+    propagate_failure   boolean     NOT NULL DEFAULT true,
+
+    PRIMARY KEY(task_id, depends_on),
+
+    CONSTRAINT fk_task          FOREIGN KEY(task_id)    REFERENCES durable.task(id)
+        ON DELETE CASCADE,
+    CONSTRAINT fk_depends_on    FOREIGN KEY(depends_on) REFERENCES durable.task(id)
+        ON DELETE CASCADE
+);
+
+CREATE INDEX task_dependency_depends_on ON durable.task_dependency(depends_on);
+
+-- Leases held by tasks on named locks.
+--
+-- A lock with N permits may have at most N unexpired leases at once. Leases
+-- are deleted when they are released or when the task holding them completes.
+-- Expired leases are cleaned up lazily by the next task that tries to acquire
+-- the lock.
+CREATE TABLE durable.lock_lease(
+    name            text        NOT NULL,
+    task_id         bigint      NOT NULL,
+    acquired_at     timestamptz NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    expires_at      timestamptz,
+
+    PRIMARY KEY(name, task_id),
+
+    CONSTRAINT fk_task FOREIGN KEY(task_id) REFERENCES durable.task(id)
+        ON DELETE CASCADE
+);
+
+CREATE INDEX lock_lease_task ON durable.lock_lease(task_id);
+
+-- Tasks that are blocked waiting for a lease on a lock.
+--
+-- Whenever a lease is released, every waiting task is sent a
+-- `durable:lock-released` notification and removed from this table. Tasks
+-- that fail to acquire the lock afterwards will add themselves back.
+CREATE TABLE durable.lock_waiter(
+    name            text        NOT NULL,
+    task_id         bigint      NOT NULL,
+    created_at      timestamptz NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    PRIMARY KEY(name, task_id),
+
+    CONSTRAINT fk_task FOREIGN KEY(task_id) REFERENCES durable.task(id)
+        ON DELETE CASCADE
+);
+
+CREATE INDEX lock_waiter_task ON durable.lock_waiter(task_id);
+
+-- Tasks that are blocked waiting for a postgres notification on a channel.
+--
+-- Every worker listens on the channels in this table. The first worker to
+-- receive a notification on a channel sends each waiting task a
+-- `durable:pg-notification` notification with the payload and removes it from
+-- this table.
+CREATE TABLE durable.pg_listener(
+    channel         text        NOT NULL,
+    task_id         bigint      NOT NULL,
+    created_at      timestamptz NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    PRIMARY KEY(channel, task_id),
+
+    CONSTRAINT fk_task FOREIGN KEY(task_id) REFERENCES durable.task(id)
+        ON DELETE CASCADE
+);
+
+CREATE INDEX pg_listener_task ON durable.pg_listener(task_id);
+
+-- The token buckets for the rate limits configured on the workers.
+--
+-- Buckets are created the first time that a rate limit is used and are
+-- refilled lazily whenever a task tries to take a token from them.
+CREATE TABLE durable.rate_limit(
+    name            text        NOT NULL,
+    tokens          float8      NOT NULL,
+    updated_at      timestamptz NOT NULL,
+
+    PRIMARY KEY(name)
+);
+
+-- Tasks that are waiting to take a token from a rate limit.
+--
+-- Tokens are handed out in (created_at, task_id) order. Each waiting task is
+-- expected to try again at retry_at. Tasks that don't come back for a while
+-- after that lose their place in line.
+CREATE TABLE durable.rate_limit_waiter(
+    name            text        NOT NULL,
+    task_id         bigint      NOT NULL,
+    created_at      timestamptz NOT NULL,
+    retry_at        timestamptz NOT NULL,
+
+    PRIMARY KEY(name, task_id),
+
+    CONSTRAINT fk_task FOREIGN KEY(task_id) REFERENCES durable.task(id)
+        ON DELETE CASCADE
+);
+
+CREATE INDEX rate_limit_waiter_task ON durable.rate_limit_waiter(task_id);
+
+-- The tokens used by LLM requests made by each task.
+--
+-- Rows are keyed by the transaction that made the request. seq orders the
+-- requests made within a single transaction.
+CREATE TABLE durable.llm_usage(
+    task_id             bigint      NOT NULL,
+    index               int         NOT NULL,
+    seq                 int         NOT NULL,
+    provider            text        NOT NULL,
+    model               text        NOT NULL,
+    prompt_tokens       bigint      NOT NULL,
+    completion_tokens   bigint      NOT NULL,
+    created_at          timestamptz NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    PRIMARY KEY(task_id, index, seq),
+
+    CONSTRAINT fk_task FOREIGN KEY(task_id) REFERENCES durable.task(id)
+        ON DELETE CASCADE
+);
+
+-- The external effects performed by each task, recorded when the worker has
+-- auditing enabled.
+--
+-- Rows are keyed by the transaction that performed the effect. seq orders the
+-- effects within a single transaction. HTTP requests record the URL in target
+-- and the response status, while SQL statements record the tables they
+-- accessed and the number of rows they affected.
+CREATE TABLE durable.audit_log(
+    task_id         bigint      NOT NULL,
+    index           int         NOT NULL,
+    seq             int         NOT NULL,
+    kind            text        NOT NULL,
+    operation       text        NOT NULL,
+    target          text,
+    tables          text[]      NOT NULL DEFAULT '{}',
+    rows_affected   bigint,
+    status          int,
+    created_at      timestamptz NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    PRIMARY KEY(task_id, index, seq),
+
+    CONSTRAINT fk_task FOREIGN KEY(task_id) REFERENCES durable.task(id)
+        ON DELETE CASCADE
+);
+
+-- Task data that was too large to store inline in the task table.
+--
+-- Clients store oversized data here and leave a null placeholder in
+-- task.data. Workers use the data from here instead when it is present.
+CREATE TABLE durable.task_payload(
+    task_id         bigint      NOT NULL,
+    data            jsonb       NOT NULL,
+
+    PRIMARY KEY(task_id),
+
+    CONSTRAINT fk_task FOREIGN KEY(task_id) REFERENCES durable.task(id)
+        ON DELETE CASCADE
+);
+
+-- A snapshot of the tasks waiting to be run, refreshed periodically by the
+-- cluster leader. This is meant to be used as a load signal for autoscaling.
+--
+-- Each queue holds the tasks of a single tenant. Tasks without a tenant are
+-- counted under the empty string.
+CREATE TABLE durable.queue_stats(
+    queue               text        NOT NULL,
+    pending             bigint      NOT NULL,
+    running             bigint      NOT NULL,
+    suspended           bigint      NOT NULL,
+    oldest_pending_at   timestamptz,
+    updated_at          timestamptz NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    PRIMARY KEY(queue)
+);
+
+-- Programs whose tasks have been paused by an operator.
+--
+-- Workers do not claim tasks running a paused program, and tasks that are
+-- already running are stopped at their next transaction boundary. Deleting the
+-- row resumes them.
+CREATE TABLE durable.program_pause(
+    wasm            bigint      NOT NULL,
+    reason          text,
+    created_at      timestamptz NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    PRIMARY KEY(wasm),
+
+    CONSTRAINT fk_wasm FOREIGN KEY(wasm) REFERENCES durable.wasm(id)
+        ON DELETE CASCADE
+);
+
+-- Queues whose tasks have been paused by an operator.
+--
+-- This works the same way as durable.program_pause. Queues are named after the
+-- tenant of their tasks, with tasks without a tenant in the '' queue.
+CREATE TABLE durable.queue_pause(
+    queue           text        NOT NULL,
+    reason          text,
+    created_at      timestamptz NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    PRIMARY KEY(queue)
+);
+
+-- Completed tasks that were archived before being cleaned up.
+--
+-- This is written to by the table archiver (see Config::archive). The task row
+-- and its events and logs are stored as JSON so that they can be restored into
+-- the task tables later on.
+CREATE TABLE durable.task_archive(
+    id              bigint      NOT NULL,
+    name            text        NOT NULL,
+    tenant          text,
+    state   durable.task_state  NOT NULL,
+    completed_at    timestamptz,
+    archived_at     timestamptz NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    -- The row from durable.task, as returned by to_jsonb.
+    task            jsonb       NOT NULL,
+
+    -- The rows from durable.event and durable.log for this task.
+    events          jsonb       NOT NULL,
+    logs            jsonb       NOT NULL,
+
+    PRIMARY KEY(id)
 );
 
+CREATE INDEX task_archive_completed_at ON durable.task_archive(completed_at);
+
 CREATE FUNCTION durable.notify_task() RETURNS trigger as $$
     BEGIN
         PERFORM pg_notify(
@@ -189,6 +670,7 @@ CREATE FUNCTION durable.notify_notification() RETURNS trigger as $$
             running_on = (
                 SELECT id
                  FROM durable.worker
+                WHERE worker.instance IS NOT DISTINCT FROM task.instance
                 ORDER BY random()
                 FOR SHARE SKIP LOCKED
                 LIMIT 1
@@ -213,6 +695,74 @@ CREATE FUNCTION durable.notify_log() RETURNS trigger AS $$
     END;
 $$ LANGUAGE plpgsql;
 
+-- Guess the level of a log line from a level name at the start of the line,
+-- optionally after a timestamp (e.g. `WARN ...`, `[error] ...` or
+-- `2024-01-01T00:00:00Z DEBUG ...`). Lines without one are at the info level.
+CREATE FUNCTION durable.parse_log_level(line text) RETURNS durable.log_level AS $$
+    SELECT COALESCE(
+        replace(
+            lower(substring(line FROM '(?i)^(?:\S*\d\S*\s+)?\[?(trace|debug|info|warn|warning|error)\M')),
+            'warning',
+            'warn'
+        ),
+        'info'
+    )::durable.log_level;
+$$ LANGUAGE sql IMMUTABLE;
+
+CREATE FUNCTION durable.sync_log_entries() RETURNS trigger AS $$
+    BEGIN
+        IF (TG_OP <> 'INSERT') THEN
+            DELETE FROM durable.log_entry
+            WHERE task_id = OLD.task_id
+              AND index = OLD.index;
+        END IF;
+
+        IF (TG_OP <> 'DELETE') THEN
+            INSERT INTO durable.log_entry(task_id, index, line, level, message, created_at)
+            SELECT
+                NEW.task_id,
+                NEW.index,
+                t.line - 1,
+                -- Errors and panics are logged at index i32::MAX - 1 and
+                -- i32::MAX by the runtime.
+                CASE
+                    WHEN NEW.index >= 2147483646 THEN 'error'
+                    ELSE durable.parse_log_level(t.message)
+                END,
+                t.message,
+                NEW.created_at
+             FROM regexp_split_to_table(rtrim(NEW.message, E'\n'), E'\n')
+                WITH ORDINALITY AS t(message, line)
+            WHERE rtrim(NEW.message, E'\n') <> '';
+        END IF;
+
+        RETURN NULL;
+    END;
+$$ LANGUAGE plpgsql;
+
+CREATE FUNCTION durable.notify_event() RETURNS trigger AS $$
+    BEGIN
+        PERFORM pg_notify(
+            'durable:event',
+            jsonb_build_object(
+                'task_id', NEW.task_id,
+                'index', NEW.index
+            )::text
+        );
+        RETURN NULL;
+    END;
+$$ LANGUAGE plpgsql;
+
+CREATE FUNCTION durable.notify_pg_listener() RETURNS trigger as $$
+    BEGIN
+        PERFORM pg_notify(
+            'durable:pg-listen',
+            jsonb_build_object('channel', NEW.channel)::text
+        );
+        RETURN NULL;
+    END;
+$$ LANGUAGE plpgsql;
+
 CREATE FUNCTION durable.notify_worker() RETURNS trigger as $$
     DECLARE
         worker_id   bigint;
@@ -244,6 +794,185 @@ CREATE TRIGGER task_updated
     )
     EXECUTE FUNCTION durable.notify_task();
 
+CREATE FUNCTION durable.check_task_dependency() RETURNS trigger as $$
+    DECLARE
+        dependency_state durable.task_state;
+    BEGIN
+        -- Lock the dependency so that it cannot complete until this transaction
+        -- commits. Otherwise, we could miss it failing.
+        SELECT state INTO dependency_state
+         FROM durable.task
+        WHERE id = NEW.depends_on
+        FOR SHARE;
+
+        IF dependency_state = 'failed' AND NEW.propagate_failure THEN
+            -- Errors are logged at index i32::MAX - 1, same as the runtime does.
+            INSERT INTO durable.log(task_id, index, message)
+            VALUES (NEW.task_id, 2147483646, 'dependency ' || NEW.depends_on || E' failed\n')
+            ON CONFLICT DO NOTHING;
+
+            UPDATE durable.task
+              SET state = 'failed',
+                  completed_at = CURRENT_TIMESTAMP,
+                  running_on = NULL
+            WHERE id = NEW.task_id
+              AND state = 'ready';
+        END IF;
+
+        RETURN NULL;
+    END;
+$$ LANGUAGE plpgsql;
+
+CREATE FUNCTION durable.complete_task_dependency() RETURNS trigger as $$
+    BEGIN
+        IF NEW.state = 'failed' THEN
+            INSERT INTO durable.log(task_id, index, message)
+            SELECT dep.task_id, 2147483646, 'dependency ' || NEW.id || E' failed\n'
+             FROM durable.task_dependency dep
+             JOIN durable.task ON task.id = dep.task_id
+            WHERE dep.depends_on = NEW.id
+              AND dep.propagate_failure
+              AND task.state = 'ready'
+            ON CONFLICT DO NOTHING;
+
+            UPDATE durable.task
+              SET state = 'failed',
+                  completed_at = CURRENT_TIMESTAMP,
+                  running_on = NULL
+             FROM durable.task_dependency dep
+            WHERE dep.task_id = task.id
+              AND dep.depends_on = NEW.id
+              AND dep.propagate_failure
+              AND task.state = 'ready';
+        END IF;
+
+        -- Let the workers know that these tasks may now be ready to run.
+        PERFORM pg_notify(
+            'durable:task',
+            jsonb_build_object(
+                'id', task.id,
+                'running_on', task.running_on
+            )::text
+        )
+         FROM durable.task_dependency dep
+         JOIN durable.task ON task.id = dep.task_id
+        WHERE dep.depends_on = NEW.id
+          AND task.state = 'ready';
+
+        RETURN NULL;
+    END;
+$$ LANGUAGE plpgsql;
+
+CREATE FUNCTION durable.notify_parent_task() RETURNS trigger as $$
+    BEGIN
+        INSERT INTO durable.notification(task_id, event, data)
+        SELECT parent.id, 'durable:child-complete', jsonb_build_object(
+            'id', NEW.id,
+            'success', NEW.state = 'complete',
+            'result', NEW.result
+        )
+         FROM durable.task parent
+        WHERE parent.id = NEW.parent_id
+          AND NOT parent.state IN ('complete', 'failed');
+
+        RETURN NULL;
+    END;
+$$ LANGUAGE plpgsql;
+
+CREATE FUNCTION durable.release_task_locks() RETURNS trigger as $$
+    BEGIN
+        DELETE FROM durable.lock_waiter WHERE task_id = NEW.id;
+        DELETE FROM durable.lock_lease  WHERE task_id = NEW.id;
+
+        RETURN NULL;
+    END;
+$$ LANGUAGE plpgsql;
+
+CREATE FUNCTION durable.notify_lock_released() RETURNS trigger as $$
+    BEGIN
+        WITH waiters AS (
+            DELETE FROM durable.lock_waiter
+            WHERE name = OLD.name
+              AND task_id != OLD.task_id
+            RETURNING task_id
+        )
+        INSERT INTO durable.notification(task_id, event, data)
+        SELECT task_id, 'durable:lock-released', jsonb_build_object('name', OLD.name)
+         FROM waiters;
+
+        RETURN NULL;
+    END;
+$$ LANGUAGE plpgsql;
+
+CREATE FUNCTION durable.leave_rate_limit_queues() RETURNS trigger as $$
+    BEGIN
+        DELETE FROM durable.rate_limit_waiter WHERE task_id = NEW.id;
+
+        RETURN NULL;
+    END;
+$$ LANGUAGE plpgsql;
+
+CREATE FUNCTION durable.leave_pg_listeners() RETURNS trigger as $$
+    BEGIN
+        DELETE FROM durable.pg_listener WHERE task_id = NEW.id;
+
+        RETURN NULL;
+    END;
+$$ LANGUAGE plpgsql;
+
+CREATE FUNCTION durable.program_pause_changed() RETURNS trigger as $$
+    BEGIN
+        IF (TG_OP = 'INSERT') THEN
+            -- Clearing running_on means that the workers running these tasks
+            -- will stop them the next time they try to commit a transaction.
+            UPDATE durable.task
+              SET state = 'ready',
+                  running_on = NULL
+            WHERE wasm = NEW.wasm
+              AND state = 'active';
+        ELSE
+            -- Let the workers know that these tasks can be claimed again.
+            PERFORM pg_notify(
+                'durable:task',
+                jsonb_build_object(
+                    'id', id,
+                    'running_on', running_on
+                )::text
+            )
+             FROM durable.task
+            WHERE wasm = OLD.wasm
+              AND state = 'ready';
+        END IF;
+
+        RETURN NULL;
+    END;
+$$ LANGUAGE plpgsql;
+
+CREATE FUNCTION durable.queue_pause_changed() RETURNS trigger as $$
+    BEGIN
+        IF (TG_OP = 'INSERT') THEN
+            UPDATE durable.task
+              SET state = 'ready',
+                  running_on = NULL
+            WHERE COALESCE(tenant, '') = NEW.queue
+              AND state = 'active';
+        ELSE
+            PERFORM pg_notify(
+                'durable:task',
+                jsonb_build_object(
+                    'id', id,
+                    'running_on', running_on
+                )::text
+            )
+             FROM durable.task
+            WHERE COALESCE(tenant, '') = OLD.queue
+              AND state = 'ready';
+        END IF;
+
+        RETURN NULL;
+    END;
+$$ LANGUAGE plpgsql;
+
 CREATE TRIGGER task_suspended
     AFTER INSERT OR UPDATE OF state ON durable.task
     FOR EACH ROW WHEN (NEW.state = 'suspended')
@@ -266,6 +995,85 @@ CREATE TRIGGER worker_deleted
     AFTER DELETE ON durable.worker
     FOR EACH ROW EXECUTE FUNCTION durable.notify_worker();
 
+CREATE TRIGGER worker_updated
+    AFTER UPDATE OF leader_eligible ON durable.worker
+    FOR EACH ROW EXECUTE FUNCTION durable.notify_worker();
+
 CREATE TRIGGER logs_inserted
     AFTER INSERT ON durable.log
     FOR EACH ROW EXECUTE FUNCTION durable.notify_log();
+
+CREATE TRIGGER log_entries_synced
+    AFTER INSERT OR UPDATE OR DELETE ON durable.log
+    FOR EACH ROW EXECUTE FUNCTION durable.sync_log_entries();
+
+CREATE TRIGGER events_inserted
+    AFTER INSERT ON durable.event
+    FOR EACH ROW EXECUTE FUNCTION durable.notify_event();
+
+CREATE TRIGGER task_dependency_inserted
+    AFTER INSERT ON durable.task_dependency
+    FOR EACH ROW EXECUTE FUNCTION durable.check_task_dependency();
+
+CREATE TRIGGER task_dependency_completed
+    AFTER UPDATE OF state ON durable.task
+    FOR EACH ROW WHEN (
+        NEW.state IN ('complete', 'failed')
+        AND
+        NOT OLD.state IN ('complete', 'failed')
+    )
+    EXECUTE FUNCTION durable.complete_task_dependency();
+
+CREATE TRIGGER task_child_completed
+    AFTER UPDATE OF state ON durable.task
+    FOR EACH ROW WHEN (
+        NEW.parent_id IS NOT NULL
+        AND
+        NEW.state IN ('complete', 'failed')
+        AND
+        NOT OLD.state IN ('complete', 'failed')
+    )
+    EXECUTE FUNCTION durable.notify_parent_task();
+
+CREATE TRIGGER task_locks_released
+    AFTER UPDATE OF state ON durable.task
+    FOR EACH ROW WHEN (
+        NEW.state IN ('complete', 'failed')
+        AND
+        NOT OLD.state IN ('complete', 'failed')
+    )
+    EXECUTE FUNCTION durable.release_task_locks();
+
+CREATE TRIGGER lock_lease_deleted
+    AFTER DELETE ON durable.lock_lease
+    FOR EACH ROW EXECUTE FUNCTION durable.notify_lock_released();
+
+CREATE TRIGGER task_rate_limit_queues_left
+    AFTER UPDATE OF state ON durable.task
+    FOR EACH ROW WHEN (
+        NEW.state IN ('complete', 'failed')
+        AND
+        NOT OLD.state IN ('complete', 'failed')
+    )
+    EXECUTE FUNCTION durable.leave_rate_limit_queues();
+
+CREATE TRIGGER task_pg_listeners_left
+    AFTER UPDATE OF state ON durable.task
+    FOR EACH ROW WHEN (
+        NEW.state IN ('complete', 'failed')
+        AND
+        NOT OLD.state IN ('complete', 'failed')
+    )
+    EXECUTE FUNCTION durable.leave_pg_listeners();
+
+CREATE TRIGGER pg_listener_inserted
+    AFTER INSERT ON durable.pg_listener
+    FOR EACH ROW EXECUTE FUNCTION durable.notify_pg_listener();
+
+CREATE TRIGGER program_pause_changed
+    AFTER INSERT OR DELETE ON durable.program_pause
+    FOR EACH ROW EXECUTE FUNCTION durable.program_pause_changed();
+
+CREATE TRIGGER queue_pause_changed
+    AFTER INSERT OR DELETE ON durable.queue_pause
+    FOR EACH ROW EXECUTE FUNCTION durable.queue_pause_changed();
@@ -0,0 +1,57 @@
+//! An HTTP API for managing the tasks, programs and workers of a durable
+//! cluster.
+//!
+//! See [`ApiConfig`] for the routes that are available.
+
+use std::sync::Arc;
+
+use crate::config::ApiConfig;
+use crate::worker::SharedState;
+
+#[cfg(feature = "api")]
+mod server;
+
+#[cfg(feature = "api")]
+pub use self::server::{router, router_with_schema};
+
+/// The management API server configured on the worker.
+///
+/// Like the webhook server, this is bound when the worker is built and then
+/// served each time the worker is run.
+pub(crate) enum ApiServer {
+    #[cfg(feature = "api")]
+    Http(server::Server),
+}
+
+impl ApiServer {
+    #[allow(unused_variables)]
+    pub(crate) fn bind(
+        config: &ApiConfig,
+        pool: &sqlx::PgPool,
+        schema: &str,
+    ) -> anyhow::Result<Self> {
+        cfg_if::cfg_if! {
+            if #[cfg(feature = "api")] {
+                server::Server::bind(config, pool, schema).map(Self::Http)
+            } else {
+                anyhow::bail!("the worker was built without the `api` feature")
+            }
+        }
+    }
+
+    pub(crate) fn try_clone(&self) -> anyhow::Result<Self> {
+        match *self {
+            #[cfg(feature = "api")]
+            Self::Http(ref server) => server.try_clone().map(Self::Http),
+        }
+    }
+
+    /// Serve requests until the worker is shut down.
+    #[allow(unused_variables)]
+    pub(crate) async fn serve(self, shared: Arc<SharedState>) -> anyhow::Result<()> {
+        match self {
+            #[cfg(feature = "api")]
+            Self::Http(server) => server.serve(shared).await,
+        }
+    }
+}
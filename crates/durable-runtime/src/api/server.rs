@@ -0,0 +1,745 @@
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use anyhow::Context;
+use async_stream::try_stream;
+use axum::body::{Body, Bytes};
+use axum::extract::{DefaultBodyLimit, Extension, Path, Query, Request, State};
+use axum::http::{header, HeaderMap, StatusCode};
+use axum::middleware::{self, Next};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use chrono::{DateTime, Utc};
+use durable_migrate::SchemaRename;
+use futures_util::Stream;
+use serde_json::value::RawValue;
+use sha2::{Digest, Sha256};
+use sqlx::postgres::PgListener;
+use sqlx::PgPool;
+
+use crate::config::ApiConfig;
+use crate::worker::{SharedState, LOG_ERROR_INDEX};
+
+/// The maximum number of tasks returned by a single call to `GET /tasks`.
+const MAX_LIST_LIMIT: i64 = 1000;
+
+const TASK_STATES: &[&str] = &["ready", "active", "suspended", "complete", "failed"];
+
+/// Create a router serving the management API.
+///
+/// See [`ApiConfig`] for the routes that are available. The
+/// [`bind`](ApiConfig::bind) address is ignored.
+pub fn router(pool: PgPool, config: &ApiConfig) -> Router {
+    router_with_schema(pool, config, "durable")
+}
+
+/// Create a router serving the management API for the durable installation
+/// in `schema`.
+///
+/// See [`Config::schema`](crate::Config::schema).
+pub fn router_with_schema(pool: PgPool, config: &ApiConfig, schema: &str) -> Router {
+    let state = Arc::new(ApiState {
+        pool,
+        schema: SchemaRename::new("durable", schema.to_owned()),
+        token: config.token.clone(),
+        tenant_tokens: config.tenant_tokens.clone(),
+    });
+
+    Router::new()
+        .route("/tasks", get(list_tasks))
+        .route("/tasks/:id", get(get_task))
+        .route("/tasks/:id/logs", get(task_logs))
+        .route("/tasks/:id/cancel", post(cancel_task))
+        .route("/tasks/:id/retry", post(retry_task))
+        .route(
+            "/programs",
+            post(upload_program).layer(DefaultBodyLimit::max(config.max_program_size)),
+        )
+        .route("/workers", get(list_workers))
+        .layer(middleware::from_fn_with_state(state.clone(), authorize))
+        .with_state(state)
+}
+
+pub(crate) struct Server {
+    listener: std::net::TcpListener,
+    router: Router,
+}
+
+impl Server {
+    pub(super) fn bind(config: &ApiConfig, pool: &PgPool, schema: &str) -> anyhow::Result<Self> {
+        let listener = std::net::TcpListener::bind(config.bind)
+            .with_context(|| format!("failed to bind the management API to {}", config.bind))?;
+        listener.set_nonblocking(true)?;
+
+        tracing::info!("management API listening on {}", listener.local_addr()?);
+
+        Ok(Self {
+            listener,
+            router: router_with_schema(pool.clone(), config, schema),
+        })
+    }
+
+    pub(super) fn try_clone(&self) -> anyhow::Result<Self> {
+        Ok(Self {
+            listener: self.listener.try_clone()?,
+            router: self.router.clone(),
+        })
+    }
+
+    pub(super) async fn serve(self, shared: Arc<SharedState>) -> anyhow::Result<()> {
+        let listener = tokio::net::TcpListener::from_std(self.listener)?;
+        let shutdown = shared.shutdown.clone();
+
+        axum::serve(listener, self.router)
+            .with_graceful_shutdown(async move { shutdown.wait().await })
+            .await
+            .context("the management API exited with an error")
+    }
+}
+
+struct ApiState {
+    pool: PgPool,
+    schema: SchemaRename,
+    token: Option<String>,
+    tenant_tokens: BTreeMap<String, String>,
+}
+
+/// What a request is allowed to access, as determined by its token.
+#[derive(Clone)]
+enum Scope {
+    /// Everything in the cluster.
+    All,
+
+    /// Only the tasks and programs of a single tenant.
+    Tenant(String),
+}
+
+impl Scope {
+    fn tenant(&self) -> Option<&str> {
+        match self {
+            Self::All => None,
+            Self::Tenant(tenant) => Some(tenant),
+        }
+    }
+
+    fn allows(&self, tenant: Option<&str>) -> bool {
+        match self {
+            Self::All => true,
+            Self::Tenant(scope) => tenant == Some(scope.as_str()),
+        }
+    }
+}
+
+struct ApiError {
+    status: StatusCode,
+    message: String,
+}
+
+impl ApiError {
+    fn new(status: StatusCode, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            message: message.into(),
+        }
+    }
+
+    fn task_not_found(id: i64) -> Self {
+        Self::new(StatusCode::NOT_FOUND, format!("task {id} does not exist"))
+    }
+
+    fn forbidden() -> Self {
+        Self::new(
+            StatusCode::FORBIDDEN,
+            "this token does not have access to the requested resource",
+        )
+    }
+}
+
+impl From<sqlx::Error> for ApiError {
+    fn from(error: sqlx::Error) -> Self {
+        tracing::error!("management API request failed: {error}");
+        Self::new(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "an internal error occurred",
+        )
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let body = serde_json::json!({ "error": self.message });
+        (self.status, Json(body)).into_response()
+    }
+}
+
+type ApiResult<T> = Result<T, ApiError>;
+
+async fn authorize(
+    State(state): State<Arc<ApiState>>,
+    headers: HeaderMap,
+    mut request: Request,
+    next: Next,
+) -> Response {
+    match authenticate(&state, &headers) {
+        Some(scope) => {
+            request.extensions_mut().insert(scope);
+            next.run(request).await
+        }
+        None => ApiError::new(StatusCode::UNAUTHORIZED, "a valid bearer token is required")
+            .into_response(),
+    }
+}
+
+fn authenticate(state: &ApiState, headers: &HeaderMap) -> Option<Scope> {
+    if state.token.is_none() && state.tenant_tokens.is_empty() {
+        return Some(Scope::All);
+    }
+
+    let provided = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))?;
+
+    if let Some(token) = &state.token {
+        if constant_time_eq(provided.as_bytes(), token.as_bytes()) {
+            return Some(Scope::All);
+        }
+    }
+
+    state
+        .tenant_tokens
+        .iter()
+        .find(|(_, token)| constant_time_eq(provided.as_bytes(), token.as_bytes()))
+        .map(|(tenant, _)| Scope::Tenant(tenant.clone()))
+}
+
+/// Compare two byte strings without leaking the position of the first
+/// mismatch through timing.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+
+    a.iter().zip(b).fold(0, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+#[derive(Deserialize)]
+struct ListTasksQuery {
+    state: Option<String>,
+    name: Option<String>,
+    tenant: Option<String>,
+    labels: Option<String>,
+    before: Option<i64>,
+    limit: Option<i64>,
+}
+
+#[derive(Serialize)]
+struct TaskSummary {
+    id: i64,
+    name: String,
+    state: String,
+    tenant: Option<String>,
+    labels: sqlx::types::Json<BTreeMap<String, String>>,
+    running_on: Option<i64>,
+    created_at: DateTime<Utc>,
+    completed_at: Option<DateTime<Utc>>,
+}
+
+/// Parse a label selector of the form `key=value,key=value`.
+fn parse_labels(selector: &str) -> ApiResult<BTreeMap<String, String>> {
+    selector
+        .split(',')
+        .filter(|label| !label.is_empty())
+        .map(|label| match label.split_once('=') {
+            Some((key, value)) => Ok((key.to_owned(), value.to_owned())),
+            None => Err(ApiError::new(
+                StatusCode::BAD_REQUEST,
+                format!("`{label}` is not a valid label, expected `key=value`"),
+            )),
+        })
+        .collect()
+}
+
+async fn list_tasks(
+    State(state): State<Arc<ApiState>>,
+    Extension(scope): Extension<Scope>,
+    Query(query): Query<ListTasksQuery>,
+) -> ApiResult<Json<Vec<TaskSummary>>> {
+    let tenant = match (&scope, query.tenant) {
+        (Scope::All, tenant) => tenant,
+        (Scope::Tenant(scope), Some(tenant)) if tenant != *scope => {
+            return Err(ApiError::forbidden())
+        }
+        (Scope::Tenant(scope), _) => Some(scope.clone()),
+    };
+
+    if let Some(task_state) = &query.state {
+        if !TASK_STATES.contains(&task_state.as_str()) {
+            return Err(ApiError::new(
+                StatusCode::BAD_REQUEST,
+                format!("`{task_state}` is not a valid task state"),
+            ));
+        }
+    }
+
+    let labels = match &query.labels {
+        Some(selector) => parse_labels(selector)?,
+        None => BTreeMap::new(),
+    };
+
+    let limit = query.limit.unwrap_or(100).clamp(1, MAX_LIST_LIMIT);
+    let tasks = sqlx::query_as!(
+        TaskSummary,
+        r#"
+        SELECT
+            id,
+            name,
+            state::text as "state!",
+            tenant,
+            labels as "labels: sqlx::types::Json<BTreeMap<String, String>>",
+            running_on,
+            created_at,
+            completed_at
+         FROM durable.task
+        WHERE ($1::text IS NULL OR state::text = $1)
+          AND ($2::text IS NULL OR name = $2)
+          AND ($3::bigint IS NULL OR id < $3)
+          AND ($5::text IS NULL OR tenant = $5)
+          AND labels @> $6
+        ORDER BY id DESC
+        LIMIT $4
+        "#,
+        query.state,
+        query.name,
+        query.before,
+        limit,
+        tenant,
+        sqlx::types::Json(&labels)
+    )
+    .fetch_all(state.schema.on(&state.pool))
+    .await?;
+
+    Ok(Json(tasks))
+}
+
+#[derive(Serialize)]
+struct TaskDetails {
+    id: i64,
+    name: String,
+    state: String,
+    tenant: Option<String>,
+    labels: sqlx::types::Json<BTreeMap<String, String>>,
+    metadata: sqlx::types::Json<Box<RawValue>>,
+    running_on: Option<i64>,
+    created_at: DateTime<Utc>,
+    completed_at: Option<DateTime<Utc>>,
+    wakeup_at: Option<DateTime<Utc>>,
+    program: Option<i64>,
+    entrypoint: Option<String>,
+    data: sqlx::types::Json<Box<RawValue>>,
+}
+
+async fn get_task(
+    State(state): State<Arc<ApiState>>,
+    Extension(scope): Extension<Scope>,
+    Path(id): Path<i64>,
+) -> ApiResult<Json<TaskDetails>> {
+    let task = sqlx::query_as!(
+        TaskDetails,
+        r#"
+        SELECT
+            id,
+            name,
+            state::text as "state!",
+            tenant,
+            labels as "labels: sqlx::types::Json<BTreeMap<String, String>>",
+            metadata as "metadata: sqlx::types::Json<Box<RawValue>>",
+            running_on,
+            created_at,
+            completed_at,
+            wakeup_at,
+            wasm as program,
+            entrypoint,
+            COALESCE(
+                (SELECT data FROM durable.task_payload WHERE task_id = task.id),
+                data
+            ) as "data!: sqlx::types::Json<Box<RawValue>>"
+         FROM durable.task
+        WHERE id = $1
+        "#,
+        id
+    )
+    .fetch_optional(state.schema.on(&state.pool))
+    .await?;
+
+    task.filter(|task| scope.allows(task.tenant.as_deref()))
+        .map(Json)
+        .ok_or_else(|| ApiError::task_not_found(id))
+}
+
+/// Check that the task exists and that the request is allowed to access it.
+///
+/// Tasks belonging to other tenants are reported as not existing.
+async fn check_task(state: &ApiState, scope: &Scope, id: i64) -> ApiResult<()> {
+    let task = sqlx::query!("SELECT tenant FROM durable.task WHERE id = $1", id)
+        .fetch_optional(state.schema.on(&state.pool))
+        .await?;
+
+    match task {
+        Some(task) if scope.allows(task.tenant.as_deref()) => Ok(()),
+        _ => Err(ApiError::task_not_found(id)),
+    }
+}
+
+#[derive(Deserialize)]
+struct LogsQuery {
+    #[serde(default)]
+    follow: bool,
+}
+
+async fn task_logs(
+    State(state): State<Arc<ApiState>>,
+    Extension(scope): Extension<Scope>,
+    Path(id): Path<i64>,
+    Query(query): Query<LogsQuery>,
+) -> ApiResult<Response> {
+    check_task(&state, &scope, id).await?;
+
+    let headers = [(header::CONTENT_TYPE, "text/plain; charset=utf-8")];
+
+    if !query.follow {
+        let messages = sqlx::query_scalar!(
+            "
+            SELECT message
+             FROM durable.log
+            WHERE task_id = $1
+            ORDER BY index ASC
+            ",
+            id
+        )
+        .fetch_all(state.schema.on(&state.pool))
+        .await?;
+
+        return Ok((headers, messages.concat()).into_response());
+    }
+
+    let body = Body::from_stream(follow_logs(state.pool.clone(), state.schema.clone(), id));
+    Ok((headers, body).into_response())
+}
+
+#[derive(Deserialize)]
+struct LogNotification {
+    #[serde(alias = "id")]
+    task_id: i64,
+}
+
+/// Stream the logs of a task as they are written, finishing once the task
+/// has completed.
+fn follow_logs(
+    pool: PgPool,
+    schema: SchemaRename,
+    id: i64,
+) -> impl Stream<Item = sqlx::Result<Bytes>> {
+    try_stream! {
+        let mut listener = PgListener::connect_with(&pool).await?;
+        let channels = [
+            schema.channel("durable:log"),
+            schema.channel("durable:task-complete"),
+        ];
+        listener
+            .listen_all(channels.iter().map(|channel| &**channel))
+            .await?;
+
+        let mut last_seen = -1;
+        loop {
+            // The state needs to be read before the logs so that we don't miss any logs
+            // written just before the task completed.
+            let state = sqlx::query_scalar!(
+                r#"SELECT state::text as "state!" FROM durable.task WHERE id = $1"#,
+                id
+            )
+            .fetch_optional(schema.on(&mut listener))
+            .await?;
+            let done = !matches!(
+                state.as_deref(),
+                Some("ready" | "active" | "suspended")
+            );
+
+            let records = sqlx::query!(
+                "
+                SELECT message, index
+                 FROM durable.log
+                WHERE task_id = $1
+                  AND index > $2
+                ORDER BY index ASC
+                ",
+                id,
+                last_seen
+            )
+            .fetch_all(schema.on(&mut listener))
+            .await?;
+
+            for record in records {
+                last_seen = last_seen.max(record.index);
+                yield Bytes::from(record.message);
+            }
+
+            if done {
+                break;
+            }
+
+            // Wait until something happens to this task. If we can't tell which task a
+            // notification is for then we just check again.
+            loop {
+                let Some(notification) = listener.try_recv().await? else {
+                    break;
+                };
+
+                match serde_json::from_str::<LogNotification>(notification.payload()) {
+                    Ok(payload) if payload.task_id != id => continue,
+                    _ => break,
+                }
+            }
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct Id {
+    id: i64,
+}
+
+async fn cancel_task(
+    State(state): State<Arc<ApiState>>,
+    Extension(scope): Extension<Scope>,
+    Path(id): Path<i64>,
+) -> ApiResult<Json<Id>> {
+    check_task(&state, &scope, id).await?;
+
+    // Clearing running_on means that the worker running the task will abort it
+    // the next time it tries to commit a transaction.
+    let cancelled = sqlx::query_scalar!(
+        r#"
+        WITH cancelled AS (
+            UPDATE durable.task
+              SET state = 'failed',
+                  completed_at = CURRENT_TIMESTAMP,
+                  running_on = NULL
+            WHERE id = $1
+              AND state NOT IN ('complete', 'failed')
+            RETURNING id
+        ),
+        log AS (
+            INSERT INTO durable.log(task_id, index, message)
+            SELECT id, $2, $3
+             FROM cancelled
+            ON CONFLICT ON CONSTRAINT log_pkey DO NOTHING
+        )
+        SELECT id as "id!"
+         FROM cancelled
+        "#,
+        id,
+        LOG_ERROR_INDEX,
+        "task was cancelled\n"
+    )
+    .fetch_optional(state.schema.on(&state.pool))
+    .await?;
+
+    match cancelled {
+        Some(_) => Ok(Json(Id { id })),
+        None => Err(ApiError::new(
+            StatusCode::CONFLICT,
+            format!("task {id} has already completed"),
+        )),
+    }
+}
+
+async fn retry_task(
+    State(state): State<Arc<ApiState>>,
+    Extension(scope): Extension<Scope>,
+    Path(id): Path<i64>,
+) -> ApiResult<(StatusCode, Json<Id>)> {
+    check_task(&state, &scope, id).await?;
+
+    let retried = sqlx::query_scalar!(
+        "
+        INSERT INTO durable.task(
+            name, wasm, data, sql_context, entrypoint, tenant, instance, labels, running_on
+        )
+        SELECT
+            name,
+            wasm,
+            data,
+            sql_context,
+            entrypoint,
+            tenant,
+            instance,
+            labels,
+            (
+                SELECT id
+                 FROM durable.worker
+                WHERE worker.instance IS NOT DISTINCT FROM task.instance
+                ORDER BY random()
+                LIMIT 1
+                FOR SHARE SKIP LOCKED
+            ) as running_on
+         FROM durable.task
+        WHERE id = $1
+          AND state = 'failed'
+          AND wasm IS NOT NULL
+        RETURNING id
+        ",
+        id
+    )
+    .fetch_optional(state.schema.on(&state.pool))
+    .await?;
+
+    if let Some(id) = retried {
+        return Ok((StatusCode::CREATED, Json(Id { id })));
+    }
+
+    let task = sqlx::query!(
+        r#"
+        SELECT state::text as "state!", wasm
+         FROM durable.task
+        WHERE id = $1
+        "#,
+        id
+    )
+    .fetch_optional(state.schema.on(&state.pool))
+    .await?;
+
+    match task {
+        None => Err(ApiError::task_not_found(id)),
+        Some(task) if task.state != "failed" => Err(ApiError::new(
+            StatusCode::CONFLICT,
+            format!(
+                "task {id} is {} but only failed tasks can be retried",
+                task.state
+            ),
+        )),
+        Some(_) => Err(ApiError::new(
+            StatusCode::CONFLICT,
+            format!("the program for task {id} is no longer available"),
+        )),
+    }
+}
+
+#[derive(Deserialize)]
+struct UploadProgramQuery {
+    name: Option<String>,
+    tenant: Option<String>,
+}
+
+async fn upload_program(
+    State(state): State<Arc<ApiState>>,
+    Extension(scope): Extension<Scope>,
+    Query(query): Query<UploadProgramQuery>,
+    wasm: Bytes,
+) -> ApiResult<(StatusCode, Json<Id>)> {
+    let tenant = match (&scope, query.tenant) {
+        (Scope::All, tenant) => tenant,
+        (Scope::Tenant(scope), Some(tenant)) if tenant != *scope => {
+            return Err(ApiError::forbidden())
+        }
+        (Scope::Tenant(scope), _) => Some(scope.clone()),
+    };
+
+    if !wasm.starts_with(b"\0asm") {
+        return Err(ApiError::new(
+            StatusCode::BAD_REQUEST,
+            "the request body is not a wasm binary",
+        ));
+    }
+
+    let hash = Sha256::digest(&wasm);
+    let id = sqlx::query_scalar!(
+        "
+        INSERT INTO durable.wasm(hash, wasm, name, tenant)
+        VALUES ($1, $2, $3, $4)
+        ON CONFLICT (hash, (COALESCE(tenant, '')))
+        DO UPDATE
+        SET last_used = CURRENT_TIMESTAMP
+        RETURNING id
+        ",
+        hash.as_slice(),
+        &wasm as &[u8],
+        query.name,
+        tenant
+    )
+    .fetch_one(state.schema.on(&state.pool))
+    .await?;
+
+    Ok((StatusCode::CREATED, Json(Id { id })))
+}
+
+#[derive(Serialize)]
+struct WorkerStatus {
+    id: i64,
+    started_at: DateTime<Utc>,
+    heartbeat_at: DateTime<Utc>,
+    leader: bool,
+    leader_eligible: bool,
+    instance: Option<String>,
+    active_tasks: i64,
+}
+
+async fn list_workers(
+    State(state): State<Arc<ApiState>>,
+    Extension(scope): Extension<Scope>,
+) -> ApiResult<Json<Vec<WorkerStatus>>> {
+    if scope.tenant().is_some() {
+        return Err(ApiError::forbidden());
+    }
+
+    let workers = sqlx::query!(
+        r#"
+        SELECT
+            id,
+            started_at,
+            heartbeat_at,
+            leader_eligible,
+            instance,
+            id IS NOT DISTINCT FROM (SELECT id FROM durable.leader) as "leader!",
+            (
+                SELECT COUNT(*)
+                 FROM durable.task
+                WHERE running_on = worker.id
+                  AND state = 'active'
+            ) as "active_tasks!"
+         FROM durable.worker
+        ORDER BY started_at ASC, id ASC
+        "#
+    )
+    .fetch_all(state.schema.on(&state.pool))
+    .await?;
+
+    let workers = workers
+        .into_iter()
+        .map(|worker| WorkerStatus {
+            id: worker.id,
+            started_at: worker.started_at,
+            heartbeat_at: worker.heartbeat_at,
+            leader: worker.leader,
+            leader_eligible: worker.leader_eligible,
+            instance: worker.instance,
+            active_tasks: worker.active_tasks,
+        })
+        .collect();
+
+    Ok(Json(workers))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn token_comparison() {
+        assert!(constant_time_eq(b"hunter2", b"hunter2"));
+        assert!(!constant_time_eq(b"hunter2", b"hunter3"));
+        assert!(!constant_time_eq(b"hunter2", b"hunter22"));
+        assert!(constant_time_eq(b"", b""));
+    }
+}
@@ -0,0 +1,168 @@
+//! Archiving completed tasks before they are cleaned up.
+//!
+//! By default, task cleanup (see [`Config::cleanup_age`]) deletes old tasks
+//! along with their events and logs. If the worker has an [`Archiver`],
+//! either configured via [`Config::archive`] or registered with
+//! [`WorkerBuilder::archiver`], then the cluster leader hands each batch of
+//! old tasks to the archiver before deleting them.
+//!
+//! Each task is archived as an [`ArchivedTask`], which holds the row from
+//! `durable.task` along with all of its events and logs as JSON. The `durable
+//! archive` CLI command can list the archived tasks and restore them into the
+//! task tables.
+//!
+//! # Delivery semantics
+//! Tasks are archived at least once. A batch is only deleted once
+//! [`Archiver::archive`] has returned successfully, but if the worker crashes
+//! before the delete commits then the same tasks will be archived again by
+//! the next leader. If archiving fails then the tasks are left in place and
+//! cleanup is retried later.
+//!
+//! Note that the events and logs that were already pruned by
+//! [`Config::event_retention`] or [`Config::log_retention`] are not included
+//! in the archive.
+//!
+//! [`Config::cleanup_age`]: crate::Config::cleanup_age
+//! [`Config::archive`]: crate::Config::archive
+//! [`Config::event_retention`]: crate::Config::event_retention
+//! [`Config::log_retention`]: crate::Config::log_retention
+//! [`WorkerBuilder::archiver`]: crate::WorkerBuilder::archiver
+
+use std::time::Duration;
+
+use async_trait::async_trait;
+use durable_migrate::SchemaRename;
+use serde_json::Value;
+use sqlx::PgConnection;
+
+use crate::config::{ArchiveConfig, Config};
+use crate::util::IntoPgInterval;
+
+mod object_store;
+mod table;
+
+pub use self::object_store::ObjectStoreArchiver;
+pub use self::table::TableArchiver;
+
+/// A completed task, along with its events and logs.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct ArchivedTask {
+    /// The id of the task.
+    pub id: i64,
+
+    /// The row from `durable.task`.
+    pub task: Value,
+
+    /// The rows from `durable.event` for this task, ordered by index.
+    pub events: Value,
+
+    /// The rows from `durable.log` for this task, ordered by index.
+    pub logs: Value,
+}
+
+/// A place that completed tasks can be archived to.
+#[async_trait]
+pub trait Archiver: Send + Sync {
+    /// Durably store a batch of tasks.
+    ///
+    /// The tasks are deleted from the database once this returns
+    /// successfully. Tasks may be passed to the archiver more than once, so
+    /// archiving the same task again should not be an error.
+    async fn archive(&self, tasks: &[ArchivedTask]) -> anyhow::Result<()>;
+}
+
+/// Create the archiver described by the worker config.
+pub(crate) fn connect(
+    archive: &ArchiveConfig,
+    config: &Config,
+    pool: &sqlx::PgPool,
+    client: &reqwest::Client,
+) -> anyhow::Result<Box<dyn Archiver>> {
+    Ok(match archive {
+        ArchiveConfig::Table => {
+            Box::new(TableArchiver::new(pool.clone()).schema(config.schema.clone()))
+        }
+        ArchiveConfig::ObjectStore(archive) => {
+            let Some(store) = &config.object_store else {
+                anyhow::bail!("archiving to an object store requires `object_store` to be set")
+            };
+
+            Box::new(ObjectStoreArchiver::new(
+                archive.clone(),
+                store.clone(),
+                client.clone(),
+            ))
+        }
+    })
+}
+
+/// Read a batch of tasks that completed more than `age` ago.
+///
+/// The task rows are locked until the transaction that `conn` is part of
+/// completes.
+async fn load_batch(
+    schema: &SchemaRename,
+    conn: &mut PgConnection,
+    age: Duration,
+    limit: i64,
+) -> sqlx::Result<Vec<ArchivedTask>> {
+    sqlx::query_as!(
+        ArchivedTask,
+        r#"
+        SELECT
+            t.id,
+            to_jsonb(t) as "task!",
+            COALESCE((
+                SELECT jsonb_agg(to_jsonb(e) ORDER BY e.index)
+                  FROM durable.event e
+                 WHERE e.task_id = t.id
+            ), '[]') as "events!",
+            COALESCE((
+                SELECT jsonb_agg(to_jsonb(l) ORDER BY l.index)
+                  FROM durable.log l
+                 WHERE l.task_id = t.id
+            ), '[]') as "logs!"
+          FROM durable.task t
+         WHERE t.completed_at < NOW() - $1::interval
+         ORDER BY t.id
+         LIMIT $2
+           FOR UPDATE SKIP LOCKED
+        "#,
+        age.into_pg_interval(),
+        limit
+    )
+    .fetch_all(schema.on(conn))
+    .await
+}
+
+/// Archive a batch of old tasks and then delete them.
+///
+/// Returns the number of tasks that were archived.
+pub(crate) async fn archive_batch(
+    schema: &SchemaRename,
+    conn: &mut PgConnection,
+    archiver: &dyn Archiver,
+    age: Duration,
+    limit: i64,
+) -> anyhow::Result<u64> {
+    let mut tx = sqlx::Connection::begin(conn).await?;
+
+    let tasks = load_batch(schema, &mut *tx, age, limit).await?;
+    if tasks.is_empty() {
+        return Ok(0);
+    }
+
+    archiver.archive(&tasks).await?;
+
+    let ids: Vec<_> = tasks.iter().map(|task| task.id).collect();
+    sqlx::query!("DELETE FROM durable.task WHERE id = ANY($1)", &ids)
+        .execute(schema.on(&mut *tx))
+        .await?;
+
+    tx.commit().await?;
+
+    metrics::counter!("durable.archive.tasks_archived").increment(tasks.len() as u64);
+
+    Ok(tasks.len() as u64)
+}
@@ -0,0 +1,71 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+
+use super::{ArchivedTask, Archiver};
+use crate::plugin::durable::object_store::ObjectStore;
+use crate::{ObjectStoreArchiveConfig, ObjectStoreConfig};
+
+/// How long to wait for an upload to complete.
+const UPLOAD_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// Archives tasks to an S3-compatible object store.
+///
+/// Each batch is uploaded as a JSONL object where every line is an
+/// [`ArchivedTask`]. These files can be restored with
+/// `durable archive restore --file`.
+#[derive(Debug)]
+pub struct ObjectStoreArchiver {
+    archive: ObjectStoreArchiveConfig,
+    store: ObjectStoreConfig,
+    client: reqwest::Client,
+}
+
+impl ObjectStoreArchiver {
+    pub fn new(
+        archive: ObjectStoreArchiveConfig,
+        store: ObjectStoreConfig,
+        client: reqwest::Client,
+    ) -> Self {
+        Self {
+            archive,
+            store,
+            client,
+        }
+    }
+
+    fn key(&self, tasks: &[ArchivedTask]) -> String {
+        let first = tasks.first().map(|task| task.id).unwrap_or_default();
+        let last = tasks.last().map(|task| task.id).unwrap_or_default();
+
+        format!("{}{first}-{last}.jsonl", self.archive.prefix)
+    }
+}
+
+#[async_trait]
+impl Archiver for ObjectStoreArchiver {
+    async fn archive(&self, tasks: &[ArchivedTask]) -> anyhow::Result<()> {
+        if tasks.is_empty() {
+            return Ok(());
+        }
+
+        let mut body = Vec::new();
+        for task in tasks {
+            serde_json::to_writer(&mut body, task)?;
+            body.push(b'\n');
+        }
+
+        let key = self.key(tasks);
+        ObjectStore::new(&self.store, &self.client, UPLOAD_TIMEOUT)
+            .put(
+                &self.archive.bucket,
+                &key,
+                body,
+                Some("application/x-ndjson".into()),
+            )
+            .await
+            .map_err(|e| anyhow::anyhow!("failed to upload `{key}`: {e:?}"))?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,80 @@
+use async_trait::async_trait;
+use durable_migrate::SchemaRename;
+use sqlx::types::Json;
+
+use super::{ArchivedTask, Archiver};
+
+/// Archives tasks to the `durable.task_archive` table.
+///
+/// Archived tasks can be listed and restored with the `durable archive` CLI
+/// command.
+#[derive(Clone, Debug)]
+pub struct TableArchiver {
+    pool: sqlx::PgPool,
+    schema: SchemaRename,
+}
+
+impl TableArchiver {
+    pub fn new(pool: sqlx::PgPool) -> Self {
+        Self {
+            pool,
+            schema: SchemaRename::default(),
+        }
+    }
+
+    /// Archive tasks to the `task_archive` table in `schema` instead of the
+    /// one in the `durable` schema.
+    pub fn schema(mut self, schema: impl Into<String>) -> Self {
+        self.schema = SchemaRename::new("durable", schema.into());
+        self
+    }
+}
+
+#[async_trait]
+impl Archiver for TableArchiver {
+    async fn archive(&self, tasks: &[ArchivedTask]) -> anyhow::Result<()> {
+        let ids: Vec<_> = tasks.iter().map(|task| task.id).collect();
+        let rows: Vec<_> = tasks.iter().map(|task| Json(&task.task)).collect();
+        let events: Vec<_> = tasks.iter().map(|task| Json(&task.events)).collect();
+        let logs: Vec<_> = tasks.iter().map(|task| Json(&task.logs)).collect();
+
+        sqlx::query!(
+            r#"
+            INSERT INTO durable.task_archive(
+                id,
+                name,
+                tenant,
+                state,
+                completed_at,
+                task,
+                events,
+                logs
+            )
+            SELECT
+                a.id,
+                a.task->>'name',
+                a.task->>'tenant',
+                (a.task->>'state')::durable.task_state,
+                (a.task->>'completed_at')::timestamptz,
+                a.task,
+                a.events,
+                a.logs
+              FROM UNNEST($1::bigint[], $2::jsonb[], $3::jsonb[], $4::jsonb[])
+                AS a(id, task, events, logs)
+            ON CONFLICT (id) DO UPDATE
+              SET task = EXCLUDED.task,
+                  events = EXCLUDED.events,
+                  logs = EXCLUDED.logs,
+                  archived_at = CURRENT_TIMESTAMP
+            "#,
+            &ids,
+            &rows as &[Json<&serde_json::Value>],
+            &events as &[Json<&serde_json::Value>],
+            &logs as &[Json<&serde_json::Value>],
+        )
+        .execute(self.schema.on(&self.pool))
+        .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,251 @@
+//! Fault injection for testing how the runtime recovers from failures.
+//!
+//! This is only available when the `chaos` feature is enabled. It is meant to
+//! be used by tests that check that tasks still run their transactions exactly
+//! once when workers crash or lose their database connection.
+//!
+//! A [`Chaos`] instance is attached to a worker via [`WorkerBuilder::chaos`].
+//! Faults are then added to it as [`Injection`]s. Whenever a task reaches a
+//! [`FaultPoint`] that matches one of the injections, its fault is triggered.
+//!
+//! ```ignore
+//! let chaos = Arc::new(Chaos::new());
+//! chaos.inject(Injection::new(FaultPoint::TransactionExit, Fault::KillWorker).index(1));
+//!
+//! let worker = WorkerBuilder::new(pool).chaos(chaos.clone()).build().await?;
+//! ```
+//!
+//! [`WorkerBuilder::chaos`]: crate::WorkerBuilder::chaos
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use crate::error::TaskStatus;
+use crate::task::TaskState;
+
+/// A point during the execution of a task at which a fault can be injected.
+#[non_exhaustive]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum FaultPoint {
+    /// After a task has started a new transaction but before the workflow
+    /// runs the body of the transaction.
+    TransactionEnter,
+
+    /// After the workflow has finished the body of a transaction but before
+    /// its event is committed to the database.
+    TransactionExit,
+}
+
+/// A fault that is injected when a task reaches a [`FaultPoint`].
+#[non_exhaustive]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Fault {
+    /// Stop the worker as if its process had crashed.
+    ///
+    /// The worker shuts down without committing anything for the task, and
+    /// without deleting its entry in the database. Its tasks are only picked
+    /// up by other workers once the entry expires after
+    /// [`Config::heartbeat_timeout`].
+    ///
+    /// [`Config::heartbeat_timeout`]: crate::Config::heartbeat_timeout
+    KillWorker,
+
+    /// Wait for the duration before continuing.
+    ///
+    /// At [`FaultPoint::TransactionExit`] this delays the event commit.
+    Delay(Duration),
+
+    /// Fail the task as if its connection to the database had been lost.
+    ///
+    /// If the task is within a database transaction then its connection is
+    /// terminated, which rolls back the transaction. The task is then retried
+    /// like it would be after any other connection error.
+    DropConnection,
+}
+
+/// A fault along with the conditions under which it is injected.
+#[derive(Clone, Debug)]
+pub struct Injection {
+    point: FaultPoint,
+    fault: Fault,
+    task_id: Option<i64>,
+    index: Option<i32>,
+    label: Option<String>,
+    remaining: Option<u32>,
+}
+
+impl Injection {
+    /// Inject `fault` the first time that any task reaches `point`.
+    pub fn new(point: FaultPoint, fault: Fault) -> Self {
+        Self {
+            point,
+            fault,
+            task_id: None,
+            index: None,
+            label: None,
+            remaining: Some(1),
+        }
+    }
+
+    /// Only inject the fault into the task with this id.
+    pub fn task(mut self, task_id: i64) -> Self {
+        self.task_id = Some(task_id);
+        self
+    }
+
+    /// Only inject the fault in the transaction at this index.
+    pub fn index(mut self, index: i32) -> Self {
+        self.index = Some(index);
+        self
+    }
+
+    /// Only inject the fault in transactions with this label.
+    pub fn label(mut self, label: impl Into<String>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    /// The number of times that the fault is injected before it is removed.
+    ///
+    /// Setting this to `None` injects the fault every time that it matches.
+    /// The default is once.
+    pub fn times(mut self, times: Option<u32>) -> Self {
+        self.remaining = times;
+        self
+    }
+
+    fn matches(&self, point: FaultPoint, task_id: i64, index: i32, label: &str) -> bool {
+        self.point == point
+            && self.task_id.is_none_or(|id| id == task_id)
+            && self.index.is_none_or(|i| i == index)
+            && self.label.as_deref().is_none_or(|l| l == label)
+    }
+}
+
+/// A record of a fault that was injected into a task.
+#[derive(Clone, Debug)]
+pub struct Triggered {
+    pub point: FaultPoint,
+    pub fault: Fault,
+    pub task_id: i64,
+    pub index: i32,
+    pub label: String,
+}
+
+/// A set of faults to inject into the tasks run by a worker.
+///
+/// See the [module docs](self) for how this is used.
+#[derive(Debug, Default)]
+pub struct Chaos {
+    injections: Mutex<Vec<Injection>>,
+    triggered: Mutex<Vec<Triggered>>,
+    killed: AtomicBool,
+}
+
+impl Chaos {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a fault to be injected.
+    ///
+    /// If multiple injections match at the same point then the one that was
+    /// added first is used.
+    pub fn inject(&self, injection: Injection) {
+        self.injections.lock().unwrap().push(injection);
+    }
+
+    /// Remove all faults that have not been injected yet.
+    pub fn clear(&self) {
+        self.injections.lock().unwrap().clear();
+    }
+
+    /// The faults that have been injected so far, in the order that they were
+    /// triggered.
+    pub fn triggered(&self) -> Vec<Triggered> {
+        self.triggered.lock().unwrap().clone()
+    }
+
+    /// Whether a [`Fault::KillWorker`] was triggered since this was last
+    /// called.
+    pub(crate) fn take_killed(&self) -> bool {
+        self.killed.swap(false, Ordering::AcqRel)
+    }
+
+    /// Inject the first fault that matches the current transaction of `state`
+    /// at `point`, if there is one.
+    pub(crate) async fn trigger(
+        &self,
+        point: FaultPoint,
+        state: &mut TaskState,
+    ) -> anyhow::Result<()> {
+        let Some(txn) = state.transaction() else {
+            return Ok(());
+        };
+
+        let task_id = state.task_id();
+        let fault = {
+            let mut injections = self.injections.lock().unwrap();
+            let Some(pos) = injections
+                .iter()
+                .position(|inj| inj.matches(point, task_id, txn.index(), txn.label()))
+            else {
+                return Ok(());
+            };
+
+            let injection = &mut injections[pos];
+            let fault = injection.fault.clone();
+            if let Some(remaining) = &mut injection.remaining {
+                *remaining = remaining.saturating_sub(1);
+                if *remaining == 0 {
+                    injections.remove(pos);
+                }
+            }
+
+            fault
+        };
+
+        tracing::warn!(
+            target: "durable_runtime::chaos",
+            task_id,
+            index = txn.index(),
+            "injecting {fault:?} at {point:?}"
+        );
+
+        self.triggered.lock().unwrap().push(Triggered {
+            point,
+            fault: fault.clone(),
+            task_id,
+            index: txn.index(),
+            label: txn.label().to_owned(),
+        });
+
+        match fault {
+            Fault::KillWorker => {
+                self.killed.store(true, Ordering::Release);
+                state.shared().shutdown.raise();
+
+                Err(anyhow::Error::new(TaskStatus::NotScheduledOnWorker))
+            }
+            Fault::Delay(duration) => {
+                tokio::time::sleep(duration).await;
+                Ok(())
+            }
+            Fault::DropConnection => {
+                if let Some(conn) = state.transaction_mut().and_then(|txn| txn.conn()) {
+                    // This errors out since the backend is terminated while running the query.
+                    let _ = sqlx::query("SELECT pg_terminate_backend(pg_backend_pid())")
+                        .execute(&mut **conn)
+                        .await;
+                }
+
+                Err(sqlx::Error::Io(std::io::Error::new(
+                    std::io::ErrorKind::ConnectionReset,
+                    "the database connection was dropped by fault injection",
+                ))
+                .into())
+            }
+        }
+    }
+}
@@ -1,3 +1,7 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::net::SocketAddr;
+use std::path::PathBuf;
 use std::time::Duration;
 
 use derive_setters::Setters;
@@ -12,12 +16,20 @@ pub struct Config {
     /// The period with which the worker will update its heartbeat timestamp in
     /// the database.
     ///
-    /// The actual update periods will be jittered downwards by up to 1/4 of the
-    /// period to avoid thundering herds on the database server.
+    /// The actual update periods will be jittered downwards by up to
+    /// [`heartbeat_jitter`](Config::heartbeat_jitter) of the period to avoid
+    /// thundering herds on the database server.
     #[serde(default = "default_seconds::<30>")]
     #[serde(with = "duration_seconds")]
     pub heartbeat_interval: Duration,
 
+    /// The fraction of the heartbeat interval by which each heartbeat is
+    /// randomly moved earlier. Values outside of `0.0..=1.0` are clamped.
+    ///
+    /// The default is 0.25.
+    #[serde(default = "default_heartbeat_jitter")]
+    pub heartbeat_jitter: f64,
+
     /// The timeout after which a worker is considered to have disappeared if it
     /// doesn't update its heartbeat timestamp.
     ///
@@ -29,10 +41,30 @@ pub struct Config {
     /// Note that a normal shutdown of a worker will proactively delete its
     /// worker entry in the database and doesn't need to be expired via a
     /// heartbeat.
+    ///
+    /// Tasks that were running on an expired worker are handed back to the
+    /// queue so that the remaining workers can pick them up.
     #[serde(default = "default_seconds::<120>")]
     #[serde(with = "duration_seconds")]
     pub heartbeat_timeout: Duration,
 
+    /// How often the cluster leader sweeps the worker table for workers whose
+    /// heartbeat has expired. Setting this to `None` disables the sweep.
+    ///
+    /// Workers also check on each other as described in
+    /// [`heartbeat_timeout`](Config::heartbeat_timeout), but only one worker
+    /// is watching any given worker at a time. The sweep makes sure that tasks
+    /// on dead workers are reassigned promptly even if several workers die at
+    /// once.
+    ///
+    /// The number of expired workers and reassigned tasks are reported in the
+    /// `durable.workers_expired` and `durable.tasks_reassigned` metrics.
+    ///
+    /// The default interval is 5s.
+    #[serde(default = "default_option_seconds::<5>")]
+    #[serde(with = "option_duration_seconds")]
+    pub worker_sweep_interval: Option<Duration>,
+
     /// The duration that the entry for a binary will be kept around after it
     /// was last used before the a worker attempts to remove it.
     ///
@@ -64,12 +96,28 @@ pub struct Config {
     /// transaction.
     ///
     /// Bytes written after this cap is reached will still succeed in the guest
-    /// but will be silently dropped without being saved.
+    /// but will be silently dropped without being saved. Output written
+    /// outside of a transaction is separately subject to the same limit
+    /// between any two transactions.
     ///
     /// The default limit here is 128KB.
     #[serde(default = "default_usize::<{ 1024 * 128 }>")]
     pub max_log_bytes_per_transaction: usize,
 
+    /// The minimum interval between writes of buffered workflow output to the
+    /// database.
+    ///
+    /// Output that workflows write to stdout or stderr outside of a
+    /// transaction is buffered and saved along with the next transaction. If
+    /// the workflow writes more output at least this long after the buffer was
+    /// last saved, then the buffer is also saved right away so that it is
+    /// visible while the workflow is running.
+    ///
+    /// The default interval is 1 second.
+    #[serde(default = "default_seconds::<1>")]
+    #[serde(with = "duration_seconds")]
+    pub log_flush_interval: Duration,
+
     /// The maximum permitted size, in bytes, of any buffers that are directly
     /// controlled by the workflow program.
     ///
@@ -132,6 +180,67 @@ pub struct Config {
     #[serde(default = "default_u32::<10000>")]
     pub cleanup_batch_limit: u32,
 
+    /// How long the events of completed tasks are kept around before they are
+    /// pruned by the cluster leader. Setting this to `None` keeps them until
+    /// the task itself is cleaned up.
+    ///
+    /// Events are only pruned for tasks that completed successfully, since
+    /// their results have already been recorded. The events of failed tasks
+    /// are kept for debugging. This is meant to be set lower than
+    /// [`cleanup_age`](Config::cleanup_age) for workflows that produce large
+    /// event logs but whose task records need to be kept for longer.
+    ///
+    /// By default events are not pruned separately.
+    #[serde(default)]
+    #[serde(with = "option_duration_seconds")]
+    pub event_retention: Option<Duration>,
+
+    /// How long the logs of completed or failed tasks are kept around before
+    /// they are pruned by the cluster leader. Setting this to `None` keeps them
+    /// until the task itself is cleaned up.
+    ///
+    /// By default logs are not pruned separately.
+    #[serde(default)]
+    #[serde(with = "option_duration_seconds")]
+    pub log_retention: Option<Duration>,
+
+    /// The number of task ids covered by each partition of the
+    /// `durable.event` and `durable.log` tables. Setting this to `None`
+    /// disables partition management.
+    ///
+    /// The cluster leader creates a new partition ahead of the tasks that are
+    /// being launched and drops old partitions once all of their tasks have
+    /// been cleaned up, which returns their space to the OS instead of leaving
+    /// it to be reclaimed by vacuum. Rows that don't fall within any partition
+    /// are stored in the default partition.
+    ///
+    /// The default is 1000000 tasks per partition.
+    #[serde(default = "default_partition_size")]
+    pub partition_size: Option<u64>,
+
+    /// Where completed tasks are archived before they are cleaned up.
+    ///
+    /// This is disabled by default, in which case task cleanup deletes old
+    /// tasks outright. See the [`archive`](crate::archive) module for details.
+    #[serde(default)]
+    #[setters(strip_option)]
+    pub archive: Option<ArchiveConfig>,
+
+    /// How often the cluster leader refreshes the `durable.queue_stats` table.
+    /// Setting this to `None` disables it entirely.
+    ///
+    /// The stats record how many tasks are waiting to be run in each queue and
+    /// how long the oldest of them has been waiting. They are meant to be used
+    /// as a load signal for autoscaling workers. See [`WorkerHandle::stats`]
+    /// for how to read them.
+    ///
+    /// The default interval is 30s.
+    ///
+    /// [`WorkerHandle::stats`]: crate::WorkerHandle::stats
+    #[serde(default = "default_option_seconds::<30>")]
+    #[serde(with = "option_duration_seconds")]
+    pub queue_stats_interval: Option<Duration>,
+
     /// The maximum number of tasks that are allowed to be running on this node
     /// at once.
     ///
@@ -152,6 +261,142 @@ pub struct Config {
     #[serde(default = "default_usize::<2000>")]
     pub max_tasks: usize,
 
+    /// The total amount of linear memory, in bytes, that tasks running on the
+    /// worker may use before it stops starting new tasks.
+    ///
+    /// The worker keeps track of the size of every memory allocated by the
+    /// tasks it is running and reports the total in the `durable.memory_usage`
+    /// metric. Once the total reaches this budget the worker stops claiming
+    /// new tasks, and defers starting tasks it has already claimed, until
+    /// enough running tasks have exited to bring it back below the budget.
+    /// Tasks that are already running are allowed to keep growing their
+    /// memory, so leave some headroom between the budget and the memory
+    /// available to the worker.
+    ///
+    /// Memory is counted as soon as a task allocates it, even if the pages
+    /// have not been touched yet, so this overestimates the resident memory
+    /// used by tasks.
+    ///
+    /// By default the worker has no memory budget.
+    #[serde(default)]
+    #[setters(strip_option)]
+    pub memory_budget: Option<usize>,
+
+    /// The maximum number of tasks that the worker will claim in a single
+    /// query.
+    ///
+    /// If more tasks than this are ready to run then the worker claims them
+    /// over several queries, handling other events in between. This keeps a
+    /// worker with lots of spare capacity from holding row locks on a large
+    /// part of the task table at once and gives other workers a chance to
+    /// claim some of the waiting tasks.
+    ///
+    /// The default is 100 tasks.
+    #[serde(default = "default_usize::<100>")]
+    pub claim_batch_size: usize,
+
+    /// The number of tasks that the worker will claim beyond
+    /// [`max_tasks`](Config::max_tasks).
+    ///
+    /// Under high throughput, a worker that is at capacity has to wait for one
+    /// of its tasks to complete, and then query the database, before it can
+    /// start the next one. With claim-ahead enabled the worker instead keeps a
+    /// small buffer of tasks that it has already claimed and starts one of
+    /// them as soon as a running task completes.
+    ///
+    /// Buffered tasks cannot be run by any other worker. To avoid them getting
+    /// stuck behind long-running tasks, any that have not been started within
+    /// [`claim_ahead_timeout`](Config::claim_ahead_timeout) are released so
+    /// that other workers can claim them. The worker then stops claiming
+    /// ahead until one of its running tasks completes.
+    ///
+    /// This is 0, which disables claim-ahead, by default.
+    #[serde(default)]
+    pub claim_ahead: usize,
+
+    /// How long a task claimed ahead of time can wait for a free slot on the
+    /// worker before it is released.
+    ///
+    /// See [`claim_ahead`](Config::claim_ahead) for details.
+    ///
+    /// The default is 5 seconds.
+    #[serde(default = "default_seconds::<5>")]
+    #[serde(with = "duration_seconds")]
+    pub claim_ahead_timeout: Duration,
+
+    /// Batch up the events recorded at the end of workflow transactions and
+    /// write them in a single query, waiting up to this long to collect a
+    /// batch.
+    ///
+    /// Every transaction exited by a workflow records an event in the
+    /// database. For workflows that run many small transactions the round
+    /// trip for each one can end up dominating their run time. With group
+    /// commit enabled, the events from all the tasks on a worker are written
+    /// together instead. This trades a small amount of latency for each
+    /// individual transaction for a much higher overall throughput.
+    ///
+    /// Events are still written in order for each task, since a task waits
+    /// for its event to be written before it continues. Transactions that
+    /// hold a database connection, made LLM calls, or recorded entries in the
+    /// audit log always record their event directly.
+    ///
+    /// This is disabled by default.
+    #[serde(default)]
+    #[serde(with = "option_duration_seconds")]
+    #[setters(strip_option)]
+    pub group_commit_window: Option<Duration>,
+
+    /// The maximum number of events written by a single group commit.
+    ///
+    /// Once this many events are waiting they are written out immediately,
+    /// without waiting for the rest of the
+    /// [`group_commit_window`](Config::group_commit_window).
+    ///
+    /// The default is 256 events.
+    #[serde(default = "default_usize::<256>")]
+    pub group_commit_max_events: usize,
+
+    /// The maximum number of tasks belonging to each tenant that can run on
+    /// the worker at once.
+    ///
+    /// This keeps a single tenant from using up all of a worker's capacity.
+    /// Like [`max_tasks`](Config::max_tasks), the limit applies to each
+    /// worker separately. Tenants not listed here are limited by
+    /// [`default_tenant_quota`](Config::default_tenant_quota) instead, while
+    /// tasks that do not belong to any tenant are only limited by `max_tasks`.
+    ///
+    /// Tasks over the quota are left for other workers to pick up, or for
+    /// this worker once some of the tenant's tasks have finished.
+    #[serde(default)]
+    pub tenant_quotas: BTreeMap<String, usize>,
+
+    /// The quota for tenants that do not have one set in
+    /// [`tenant_quotas`](Config::tenant_quotas).
+    ///
+    /// By default these tenants are not limited.
+    #[serde(default)]
+    #[setters(strip_option)]
+    pub default_tenant_quota: Option<usize>,
+
+    /// Rate limits that workflows can wait on, keyed by name.
+    ///
+    /// The state of each rate limit is stored in the database, so a rate limit
+    /// is shared by all tasks on all workers. Every worker should be configured
+    /// with the same rate limits. Workflows that use a rate limit which is not
+    /// configured on the worker they are running on will fail.
+    #[serde(default)]
+    pub rate_limits: BTreeMap<String, RateLimit>,
+
+    /// LLM providers that workflows can make chat completion requests to,
+    /// keyed by name.
+    ///
+    /// Workflows only look up the base URL and credentials of a provider here
+    /// and then make the requests themselves. This keeps API keys out of the
+    /// workflow binaries and allows switching providers without redeploying
+    /// them.
+    #[serde(default)]
+    pub llm_providers: BTreeMap<String, LlmProvider>,
+
     /// The maximum number of WASM binaries that can be compiled concurrently.
     ///
     /// Compiling WASM down to machine code is moderately expensive (e.g. a
@@ -166,6 +411,307 @@ pub struct Config {
     #[serde(default = "default_usize::<4>")]
     pub max_concurrent_compilations: usize,
 
+    /// The maximum number of database connections that can be held by
+    /// workflow database transactions at once.
+    ///
+    /// A workflow database transaction holds on to its connection until the
+    /// transaction completes, which may take an arbitrarily long time. The
+    /// worker also needs connections from the same pool for its own
+    /// bookkeeping: recording events, heartbeats, taking new tasks, etc. If
+    /// enough workflows are in the middle of a database transaction then
+    /// they can use up the entire pool, at which point both the workflows
+    /// and the worker stall waiting for connections that never free up.
+    ///
+    /// Setting this to a value smaller than the size of the pool reserves the
+    /// remaining connections for the worker itself. Workflows entering a
+    /// database transaction once the limit is reached will wait until another
+    /// workflow finishes its transaction. The time spent waiting is recorded
+    /// in the `durable.workflow_connection_wait` metric.
+    ///
+    /// By default workflow database transactions are not limited.
+    #[serde(default)]
+    #[setters(strip_option)]
+    pub max_workflow_connections: Option<usize>,
+
+    /// The maximum number of rows that a single query run by a workflow can
+    /// return.
+    ///
+    /// Once a query returns more rows than this, the workflow gets an error
+    /// with the `program_limit_exceeded` (`54000`) error code instead of the
+    /// next row and the rest of the query's results are discarded. This
+    /// protects the worker from workflows that accidentally read an entire
+    /// table.
+    ///
+    /// By default the number of rows is not limited.
+    #[serde(default)]
+    #[setters(strip_option)]
+    pub max_query_rows: Option<u64>,
+
+    /// The maximum number of SQL statements that a workflow can run within a
+    /// single database transaction.
+    ///
+    /// Each statement in a batch counts separately. Statements that would go
+    /// over the limit fail with the `program_limit_exceeded` (`54000`) error
+    /// code without being run.
+    ///
+    /// By default the number of statements is not limited.
+    #[serde(default)]
+    #[setters(strip_option)]
+    pub max_statements_per_transaction: Option<u32>,
+
+    /// The maximum amount of time that a workflow database transaction can
+    /// run for.
+    ///
+    /// Once a transaction has been open for longer than this, any further
+    /// statements run within it, along with any rows still being read, fail
+    /// with the `transaction_timeout` (`25P04`) error code. The statement
+    /// timeout of the transaction is also capped to this, so that a single
+    /// long-running statement gets cancelled by the database.
+    ///
+    /// By default the duration of database transactions is not limited.
+    #[serde(default)]
+    #[serde(with = "option_duration_seconds")]
+    #[setters(strip_option)]
+    pub max_database_transaction_duration: Option<Duration>,
+
+    /// The maximum number of prepared statements that workflow queries can
+    /// leave cached on a single database connection.
+    ///
+    /// Queries run by workflows are prepared and cached on the connection that
+    /// they run on, so that running the same query again can skip parsing and
+    /// planning it. Once a connection has more statements cached than this,
+    /// all of them are evicted. Hits, misses, and evictions are recorded in the
+    /// `durable.statement_cache.hits`, `durable.statement_cache.misses`, and
+    /// `durable.statement_cache.evictions` metrics.
+    ///
+    /// This should be smaller than the `statement_cache_capacity` of the
+    /// pool's connect options (100 by default). Past that point sqlx evicts
+    /// statements on its own, and those evictions show up as cache hits.
+    ///
+    /// By default the cache is only limited by the pool's connect options.
+    #[serde(default)]
+    #[setters(strip_option)]
+    pub max_cached_statements: Option<usize>,
+
+    /// The maximum length, in bytes, of a workflow query that will be cached
+    /// on its connection.
+    ///
+    /// Longer queries are still run as prepared statements, but the statement
+    /// is closed once the query completes. This keeps large, dynamically
+    /// generated queries that are unlikely to be run again from pushing other
+    /// statements out of the cache.
+    ///
+    /// By default queries of any length are cached.
+    #[serde(default)]
+    #[setters(strip_option)]
+    pub max_cached_statement_len: Option<usize>,
+
+    /// The interval at which running workflows are interrupted so that they
+    /// yield back to the worker's executor.
+    ///
+    /// Workflows only yield to the executor when they call into the runtime.
+    /// A workflow stuck in a long CPU-bound loop would otherwise block the
+    /// thread it is running on, delaying every other task on that thread and
+    /// keeping the worker from shutting down. Workflows can also yield
+    /// explicitly by calling `durable::yield_now`.
+    ///
+    /// This uses wasmtime's epoch interruption, which adds a small overhead to
+    /// all workflow code. Setting this to `None`, or to zero in a config file,
+    /// disables it entirely. Note that precompiled program artifacts can only
+    /// be used by workers whose engine has the same epoch interruption setting
+    /// as the one used to precompile them.
+    ///
+    /// The default interval is 10ms.
+    #[serde(default = "default_epoch_interval")]
+    #[serde(with = "option_duration_seconds")]
+    pub epoch_interval: Option<Duration>,
+
+    /// Load precompiled program artifacts from the database instead of
+    /// compiling programs locally, when a compatible one is available.
+    ///
+    /// Clients can precompile programs when uploading them so that workers
+    /// don't need to spend time compiling them on first use. Precompiled
+    /// artifacts contain native code which is loaded without validation, so
+    /// this should only be enabled if everything with write access to the
+    /// database is trusted.
+    ///
+    /// This is disabled by default.
+    #[serde(default)]
+    pub load_precompiled_programs: bool,
+
+    /// Refuse to run workflows that import deprecated versions of the
+    /// `durable:core` interfaces.
+    ///
+    /// Workers support workflows built against older versions of the
+    /// `durable:core` WIT package, so that workers and workflows can be
+    /// upgraded independently. Versions more than a few releases old are
+    /// deprecated. Workflows using them still run, but are logged and
+    /// counted in the `durable.deprecated_interface_imports` metric. Enabling
+    /// this makes tasks running such workflows fail instead.
+    ///
+    /// See [`plugin::durable::version`] for details.
+    ///
+    /// This is disabled by default.
+    ///
+    /// [`plugin::durable::version`]: crate::plugin::durable::version
+    #[serde(default)]
+    pub deny_deprecated_interfaces: bool,
+
+    /// Refuse to run workflows that import functions which the worker does
+    /// not provide.
+    ///
+    /// By default, such imports are defined as functions that trap when they
+    /// are called. This allows workflows to use optional interfaces after
+    /// checking that they are available via `durable:core/capabilities`.
+    /// Enabling this makes the task fail before it starts instead.
+    ///
+    /// This is disabled by default.
+    #[serde(default)]
+    pub strict_imports: bool,
+
+    /// Record an audit log of the external effects performed by each task.
+    ///
+    /// When enabled, every HTTP request made by a task is recorded along with
+    /// the URL it was sent to and the status of the response, and every SQL
+    /// statement is recorded along with the tables it accessed and the number
+    /// of rows it affected. The log is saved along with each transaction and
+    /// can be read with the `durable task audit` command.
+    ///
+    /// Determining the tables accessed by a statement requires asking the
+    /// database to plan it, which adds a round trip to every SQL statement.
+    ///
+    /// This is disabled by default.
+    #[serde(default)]
+    pub audit: bool,
+
+    /// Pre-instantiate programs and keep a pool of instance slots ready for
+    /// the tasks that run them.
+    ///
+    /// Starting a task normally involves resolving all of the program's
+    /// imports and allocating fresh memories and tables for it. With this
+    /// enabled the worker resolves the imports of each program once and
+    /// reuses the result for every task running it, and instances are
+    /// allocated from a pool of slots that is reserved when the worker starts.
+    /// This cuts the startup latency of tasks running hot programs, at the
+    /// cost of reserving the pool up front.
+    ///
+    /// This is disabled by default. See [`InstancePoolConfig`] for details.
+    #[serde(default)]
+    #[setters(strip_option)]
+    pub instance_pool: Option<InstancePoolConfig>,
+
+    /// Host directories that are made available to workflows via the WASI
+    /// filesystem APIs.
+    ///
+    /// By default, workflows have no filesystem access at all. See
+    /// [`Config::preopen`] for details on how workflows see these directories.
+    #[serde(default)]
+    pub preopens: Vec<Preopen>,
+
+    /// An in-memory scratch directory that is private to each task.
+    ///
+    /// This is disabled by default. See [`ScratchDir`] for details.
+    #[serde(default)]
+    #[setters(strip_option)]
+    pub scratch_dir: Option<ScratchDir>,
+
+    /// An in-memory cache for the responses to HTTP requests made by
+    /// workflows.
+    ///
+    /// This is disabled by default. See [`HttpCacheConfig`] for details.
+    #[serde(default)]
+    #[setters(strip_option)]
+    pub http_cache: Option<HttpCacheConfig>,
+
+    /// The S3-compatible object store that workflows can access.
+    ///
+    /// This is disabled by default, in which case all object store operations
+    /// made by workflows will fail.
+    #[serde(default)]
+    #[setters(strip_option)]
+    pub object_store: Option<ObjectStoreConfig>,
+
+    /// How emails sent by workflows are delivered.
+    ///
+    /// This is disabled by default, in which case workflows will get an error
+    /// when attempting to send an email.
+    #[serde(default)]
+    #[setters(strip_option)]
+    pub email: Option<EmailConfig>,
+
+    /// The message queue that workflows can publish messages to.
+    ///
+    /// This is disabled by default, in which case workflows will get an error
+    /// when attempting to publish a message. Each backend also requires the
+    /// corresponding cargo feature (`kafka` or `nats`) to be enabled.
+    #[serde(default)]
+    #[setters(strip_option)]
+    pub mq: Option<MqConfig>,
+
+    /// Message queues that this worker consumes in order to launch new tasks.
+    ///
+    /// Each message received from a source becomes a new task, as described by
+    /// the source's [`TaskMapping`]. See the [`ingest`](crate::ingest) module
+    /// for details.
+    #[serde(default)]
+    pub sources: Vec<SourceConfig>,
+
+    /// How long the dedupe keys of ingested messages are kept around.
+    ///
+    /// A message that is delivered again within this window will not launch
+    /// a second task. Once this has elapsed the key is deleted and a
+    /// redelivered message would launch a new task.
+    ///
+    /// The default window is 7 days.
+    #[serde(default = "default_seconds::<{ 3600 * 24 * 7 }>")]
+    #[serde(with = "duration_seconds")]
+    pub ingest_dedupe_window: Duration,
+
+    /// An HTTP server that launches or notifies tasks when it receives
+    /// webhooks.
+    ///
+    /// This is disabled by default and requires the `webhooks` feature. See
+    /// [`WebhookConfig`] for details.
+    #[serde(default)]
+    #[setters(strip_option)]
+    pub webhooks: Option<WebhookConfig>,
+
+    /// An HTTP API for managing tasks, programs and workers.
+    ///
+    /// This is disabled by default and requires the `api` feature. See
+    /// [`ApiConfig`] for details.
+    #[serde(default)]
+    #[setters(strip_option)]
+    pub api: Option<ApiConfig>,
+
+    /// The postgres schema that the durable tables live in.
+    ///
+    /// This allows more than one durable installation to share a database.
+    /// The schema must have been created by running the migrations with the
+    /// same schema name, and clients need to be pointed at it with
+    /// `DurableClient::with_schema`.
+    ///
+    /// The default is `durable`.
+    #[serde(default = "default_schema")]
+    #[setters(into)]
+    pub schema: String,
+
+    /// The durable instance that this worker belongs to.
+    ///
+    /// Workers only claim tasks that were launched into their own instance.
+    /// This allows separate fleets of workers, such as the blue and green
+    /// halves of a blue/green deployment, to share the same tables without
+    /// running each other's tasks. Tasks that the worker launches from
+    /// ingested messages and webhooks belong to its instance, while child
+    /// tasks and retries keep the instance of the task they came from.
+    ///
+    /// Clients choose the instance that they launch tasks into with
+    /// `DurableClient::with_instance`. By default, workers belong to no
+    /// instance and only run tasks that were launched without one.
+    #[serde(default)]
+    #[setters(strip_option)]
+    pub instance: Option<String>,
+
     /// Print task logs directly to stdout while running.
     ///
     /// This is mainly meant as a debugging option for use in tests.
@@ -177,86 +723,1456 @@ impl Config {
     pub fn new() -> Self {
         Self::default()
     }
-}
 
-impl Default for Config {
-    fn default() -> Self {
-        use serde::Deserialize;
+    /// Make the host directory at `host_path` available to workflows at
+    /// `guest_path`.
+    ///
+    /// The filesystem is not part of the workflow's durable state, so:
+    /// - Reads are not recorded. Directories opened with [`DirPerms::Read`] are
+    ///   meant for data files bundled alongside the worker and must be
+    ///   identical on every worker, otherwise replays of a workflow may
+    ///   diverge.
+    /// - Writes to directories opened with [`DirPerms::ReadWrite`] are
+    ///   performed every time the workflow runs, including when it is replayed
+    ///   after a restart. They are not visible to the workflow when it is
+    ///   resumed on a different worker. Use these directories for scratch
+    ///   output only and make sure that writing the same file again is
+    ///   harmless.
+    ///
+    /// Workflows cannot access anything outside of `host_path`, including via
+    /// symlinks.
+    pub fn preopen(
+        mut self,
+        host_path: impl Into<PathBuf>,
+        guest_path: impl Into<String>,
+        perms: DirPerms,
+    ) -> Self {
+        self.preopens.push(Preopen {
+            host_path: host_path.into(),
+            guest_path: guest_path.into(),
+            perms,
+        });
+        self
+    }
 
-        #[allow(unreachable_patterns)]
-        match Self::deserialize(EmptyMapDeserializer) {
-            Ok(config) => config,
-            Err(e) => match e {},
-        }
+    /// Launch tasks from messages received on a message queue.
+    ///
+    /// See the [`ingest`](crate::ingest) module for details.
+    pub fn source(mut self, source: SourceConfig) -> Self {
+        self.sources.push(source);
+        self
     }
-}
 
-#[test]
-#[cfg(test)]
-fn config_default_does_not_panic() {
-    let _ = Config::default();
-}
+    /// Limit how many tasks belonging to `tenant` can run on the worker at
+    /// once.
+    ///
+    /// See [`tenant_quotas`](Config::tenant_quotas) for details.
+    pub fn tenant_quota(mut self, tenant: impl Into<String>, max_tasks: usize) -> Self {
+        self.tenant_quotas.insert(tenant.into(), max_tasks);
+        self
+    }
 
-const fn default_seconds<const SECONDS: u64>() -> Duration {
-    Duration::from_secs(SECONDS)
-}
+    /// Add a rate limit that workflows can wait on.
+    ///
+    /// See [`rate_limits`](Config::rate_limits) for details.
+    pub fn rate_limit(mut self, name: impl Into<String>, limit: RateLimit) -> Self {
+        self.rate_limits.insert(name.into(), limit);
+        self
+    }
 
-const fn default_option_seconds<const SECONDS: u64>() -> Option<Duration> {
-    Some(default_seconds::<{ SECONDS }>())
-}
+    /// Add an LLM provider that workflows can make requests to.
+    ///
+    /// See [`llm_providers`](Config::llm_providers) for details.
+    pub fn llm_provider(mut self, name: impl Into<String>, provider: LlmProvider) -> Self {
+        self.llm_providers.insert(name.into(), provider);
+        self
+    }
 
-const fn default_u32<const N: u32>() -> u32 {
-    N
+    /// Get the quota for `tenant`, if it has one.
+    pub(crate) fn quota_for(&self, tenant: &str) -> Option<usize> {
+        self.tenant_quotas
+            .get(tenant)
+            .copied()
+            .or(self.default_tenant_quota)
+    }
 }
 
-const fn default_usize<const N: usize>() -> usize {
-    N
+/// A host directory that is made available to workflows.
+///
+/// See [`Config::preopen`] for details.
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Preopen {
+    /// The path of the directory on the host.
+    pub host_path: PathBuf,
+
+    /// The path at which the directory is visible to workflows.
+    pub guest_path: String,
+
+    /// What workflows are permitted to do within the directory.
+    #[serde(default)]
+    pub perms: DirPerms,
 }
 
-mod duration_seconds {
-    use std::time::Duration;
+/// A token bucket rate limit that is shared by all tasks.
+///
+/// The bucket holds up to [`burst`](RateLimit::burst) tokens and is refilled at
+/// a rate of [`tokens`](RateLimit::tokens) tokens every
+/// [`period`](RateLimit::period). Each time a workflow acquires the rate limit
+/// it takes one token from the bucket. Workflows that have to wait for a token
+/// are given one in the order that they started waiting.
+#[derive(Clone, Debug, Setters, serde::Serialize, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RateLimit {
+    /// The number of tokens that are added to the bucket every period.
+    pub tokens: u32,
 
-    use serde::Serialize;
+    /// The period over which [`tokens`](RateLimit::tokens) tokens are added to
+    /// the bucket.
+    #[serde(with = "duration_seconds")]
+    pub period: Duration,
 
-    pub(crate) fn serialize<S>(duration: &Duration, ser: S) -> Result<S::Ok, S::Error>
-    where
-        S: serde::Serializer,
-    {
-        if ser.is_human_readable() && duration.subsec_nanos() == 0 {
-            duration.as_secs().serialize(ser)
-        } else {
-            duration.as_secs_f64().serialize(ser)
+    /// The maximum number of tokens that the bucket can hold.
+    ///
+    /// By default this is the same as [`tokens`](RateLimit::tokens).
+    #[serde(default)]
+    #[setters(strip_option)]
+    pub burst: Option<u32>,
+}
+
+impl RateLimit {
+    pub fn new(tokens: u32, period: Duration) -> Self {
+        Self {
+            tokens,
+            period,
+            burst: None,
         }
     }
 
-    pub(crate) fn deserialize<'de, D>(de: D) -> Result<Duration, D::Error>
-    where
-        D: serde::Deserializer<'de>,
-    {
-        use serde::de::Error;
+    /// The maximum number of tokens that the bucket can hold.
+    pub(crate) fn capacity(&self) -> f64 {
+        self.burst.unwrap_or(self.tokens).into()
+    }
 
-        struct Visitor;
+    /// The number of tokens added to the bucket per second.
+    pub(crate) fn refill_rate(&self) -> f64 {
+        f64::from(self.tokens) / self.period.as_secs_f64()
+    }
+}
 
-        impl serde::de::Visitor<'_> for Visitor {
-            type Value = Duration;
+/// An OpenAI-compatible LLM API that workflows can use.
+#[derive(Clone, Setters, serde::Serialize, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LlmProvider {
+    /// The base URL of the API (e.g. `https://api.openai.com/v1`).
+    ///
+    /// Requests are made to paths relative to this URL, such as
+    /// `{base_url}/chat/completions`.
+    pub base_url: String,
 
-            fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-                write!(f, "a duration in seconds")
-            }
+    /// The API key that is sent as a bearer token with each request.
+    #[serde(default)]
+    #[setters(strip_option, into)]
+    pub api_key: Option<String>,
 
-            fn visit_u64<E: Error>(self, v: u64) -> Result<Self::Value, E> {
-                Ok(Duration::from_secs(v))
-            }
+    /// The model to use for requests that do not specify one.
+    #[serde(default)]
+    #[setters(strip_option, into)]
+    pub default_model: Option<String>,
+}
 
-            fn visit_i64<E: Error>(self, v: i64) -> Result<Self::Value, E> {
-                if v < 0 {
-                    return Err(Error::custom("durations cannot be negative"));
-                }
+impl LlmProvider {
+    pub fn new(base_url: impl Into<String>) -> Self {
+        Self {
+            base_url: base_url.into(),
+            api_key: None,
+            default_model: None,
+        }
+    }
+}
 
-                self.visit_u64(v as u64)
-            }
+impl fmt::Debug for LlmProvider {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LlmProvider")
+            .field("base_url", &self.base_url)
+            .field("api_key", &self.api_key.as_ref().map(|_| "<redacted>"))
+            .field("default_model", &self.default_model)
+            .finish()
+    }
+}
 
-            fn visit_f32<E: Error>(self, v: f32) -> Result<Self::Value, E> {
+/// An in-memory directory that each task gets its own private copy of.
+///
+/// This is meant for libraries that insist on reading from or writing to files
+/// in order to do their work. Unlike with [`Config::preopen`], the contents of
+/// the scratch directory are part of the workflow's durable state:
+/// - Files start out empty and only ever contain what the task itself wrote, so
+///   the directory is rebuilt exactly as it was whenever a workflow is
+///   replayed, on any worker.
+/// - Writes made from within a transaction would be lost when the transaction
+///   is replayed, since its body is not run again. By default these fail with a
+///   read-only filesystem error. If [`snapshot`](ScratchDir::snapshot) is
+///   enabled, the whole directory is instead stored in the event log whenever a
+///   transaction modifies it and restored when that transaction is replayed.
+///
+/// Snapshots are not restored by [`replay`](crate::replay), so workflows that
+/// depend on them may diverge when replayed there.
+#[derive(Clone, Debug, Setters, serde::Serialize, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ScratchDir {
+    /// The path at which the directory is visible to workflows.
+    pub guest_path: String,
+
+    /// The maximum total size, in bytes, of the files within the directory.
+    ///
+    /// Writes past this limit fail with an out of space error. The default
+    /// limit is 16MB.
+    #[serde(default = "default_usize::<{ 16 * 1024 * 1024 }>")]
+    pub max_bytes: usize,
+
+    /// Record the directory in the event log when it is modified within a
+    /// transaction.
+    ///
+    /// Each snapshot contains the entire directory, so keep the directory small
+    /// when enabling this. This is disabled by default.
+    #[serde(default)]
+    pub snapshot: bool,
+}
+
+impl ScratchDir {
+    pub fn new(guest_path: impl Into<String>) -> Self {
+        Self {
+            guest_path: guest_path.into(),
+            max_bytes: default_usize::<{ 16 * 1024 * 1024 }>(),
+            snapshot: false,
+        }
+    }
+}
+
+/// A pool of pre-allocated instance slots for running workflows.
+///
+/// This uses wasmtime's pooling allocator. Each slot holds everything needed
+/// for one running task. Slots whose task has exited are kept warm and are
+/// handed out to tasks running the same program again when possible, which
+/// avoids having to reinitialize the program's memory. Stores themselves are
+/// never reused between tasks, since they hold the state of the task that
+/// created them.
+///
+/// Every running task occupies a slot, so tasks fail to start if the pool is
+/// exhausted. The pool should be at least as large as
+/// [`max_tasks`](Config::max_tasks), which it is by default.
+///
+/// See [`Config::instance_pool`] for details.
+#[derive(Clone, Debug, Default, Setters, serde::Serialize, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct InstancePoolConfig {
+    /// The number of instance slots in the pool.
+    ///
+    /// By default this is the worker's [`max_tasks`](Config::max_tasks).
+    #[serde(default)]
+    #[setters(strip_option)]
+    pub size: Option<u32>,
+
+    /// The maximum number of unused slots that are kept warm for the program
+    /// that last used them.
+    ///
+    /// By default all unused slots are kept warm.
+    #[serde(default)]
+    #[setters(strip_option)]
+    pub warm_slots: Option<u32>,
+
+    /// The maximum size, in bytes, that a workflow's memory can grow to.
+    ///
+    /// Virtual memory for each slot is reserved up front based on this. The
+    /// default limit is 1GB.
+    #[serde(default)]
+    #[setters(strip_option)]
+    pub max_memory_size: Option<usize>,
+}
+
+impl InstancePoolConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Build the wasmtime pooling allocator config for a worker that runs up
+    /// to `max_tasks` tasks at once.
+    pub(crate) fn allocation_config(&self, max_tasks: usize) -> wasmtime::PoolingAllocationConfig {
+        // Components are made up of several core instances, memories and
+        // tables. These are the limits that a single task is allowed to use.
+        const CORE_INSTANCES_PER_TASK: u32 = 16;
+        const MEMORIES_PER_TASK: u32 = 4;
+        const TABLES_PER_TASK: u32 = 8;
+
+        let size = self
+            .size
+            .unwrap_or_else(|| max_tasks.try_into().unwrap_or(u32::MAX))
+            .max(1);
+
+        let mut config = wasmtime::PoolingAllocationConfig::default();
+        config
+            .total_component_instances(size)
+            .total_core_instances(size.saturating_mul(CORE_INSTANCES_PER_TASK))
+            .total_memories(size.saturating_mul(MEMORIES_PER_TASK))
+            .total_tables(size.saturating_mul(TABLES_PER_TASK))
+            .max_core_instances_per_component(CORE_INSTANCES_PER_TASK)
+            .max_memories_per_component(MEMORIES_PER_TASK)
+            .max_tables_per_component(TABLES_PER_TASK)
+            .max_memory_size(self.max_memory_size.unwrap_or(1024 * 1024 * 1024))
+            .max_unused_warm_slots(self.warm_slots.unwrap_or(size));
+        config
+    }
+}
+
+/// An in-memory cache for the responses to HTTP requests made by workflows.
+///
+/// Workflows that are run over and over again, such as during development,
+/// tend to make the same requests each time. With this enabled, the worker
+/// keeps the responses to `GET` and `HEAD` requests around and reuses them for
+/// identical requests made by any task of the same tenant on this worker.
+/// Requests are only considered identical if they have the same method, URL,
+/// and headers.
+///
+/// Responses are cached for as long as their `Cache-Control: max-age`
+/// directive allows. Responses without a `max-age` are cached for
+/// [`ttl`](HttpCacheConfig::ttl), if it is set. Responses marked `no-store` or
+/// `no-cache` are never cached. Requests that send `Cache-Control: no-store`
+/// always go to the server, while requests that send `Cache-Control: no-cache`
+/// go to the server but still update the cache. In `durable-http`,
+/// `RequestBuilder::no_cache` does the former.
+#[derive(Clone, Debug, Setters, serde::Serialize, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct HttpCacheConfig {
+    /// How long to cache responses that don't specify a `max-age`.
+    ///
+    /// By default these responses are not cached.
+    #[serde(default)]
+    #[serde(with = "option_duration_seconds")]
+    #[setters(strip_option)]
+    pub ttl: Option<Duration>,
+
+    /// The maximum total size, in bytes, of the cached responses.
+    ///
+    /// Once this is exceeded the oldest responses are evicted from the cache.
+    /// The default limit is 64MB.
+    #[serde(default = "default_usize::<{ 64 * 1024 * 1024 }>")]
+    pub max_bytes: usize,
+}
+
+impl HttpCacheConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Default for HttpCacheConfig {
+    fn default() -> Self {
+        Self {
+            ttl: None,
+            max_bytes: default_usize::<{ 64 * 1024 * 1024 }>(),
+        }
+    }
+}
+
+/// Connection details for an S3-compatible object store.
+///
+/// Workflows only ever refer to objects by bucket and key, so the credentials
+/// here are never visible to them.
+#[derive(Clone, Setters, serde::Serialize, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ObjectStoreConfig {
+    /// The base URL of the object store (e.g.
+    /// `https://s3.us-east-1.amazonaws.com`).
+    pub endpoint: String,
+
+    /// The region that requests are signed for.
+    pub region: String,
+
+    /// The access key id used to sign requests.
+    pub access_key_id: String,
+
+    /// The secret access key used to sign requests.
+    pub secret_access_key: String,
+
+    /// A session token to include with requests, for use with temporary
+    /// credentials.
+    #[serde(default)]
+    #[setters(strip_option)]
+    pub session_token: Option<String>,
+
+    /// Use path-style URLs (`{endpoint}/{bucket}/{key}`) instead of
+    /// virtual-hosted style URLs (`{bucket}.{endpoint}/{key}`).
+    ///
+    /// Most self-hosted S3-compatible stores require this. It is disabled by
+    /// default.
+    #[serde(default)]
+    pub path_style: bool,
+
+    /// The maximum size, in bytes, of an object that a workflow can read.
+    ///
+    /// Objects read by a workflow are stored in its event log so this should
+    /// be kept relatively small. The default limit is 8MB.
+    #[serde(default = "default_usize::<{ 8 * 1024 * 1024 }>")]
+    pub max_object_size: usize,
+}
+
+impl ObjectStoreConfig {
+    pub fn new(
+        endpoint: impl Into<String>,
+        region: impl Into<String>,
+        access_key_id: impl Into<String>,
+        secret_access_key: impl Into<String>,
+    ) -> Self {
+        Self {
+            endpoint: endpoint.into(),
+            region: region.into(),
+            access_key_id: access_key_id.into(),
+            secret_access_key: secret_access_key.into(),
+            session_token: None,
+            path_style: false,
+            max_object_size: default_usize::<{ 8 * 1024 * 1024 }>(),
+        }
+    }
+}
+
+// Avoid printing credentials as part of the config.
+impl fmt::Debug for ObjectStoreConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ObjectStoreConfig")
+            .field("endpoint", &self.endpoint)
+            .field("region", &self.region)
+            .field("access_key_id", &self.access_key_id)
+            .field("secret_access_key", &"<redacted>")
+            .field(
+                "session_token",
+                &self.session_token.as_ref().map(|_| "<redacted>"),
+            )
+            .field("path_style", &self.path_style)
+            .field("max_object_size", &self.max_object_size)
+            .finish()
+    }
+}
+
+/// Where completed tasks are archived to before they are cleaned up.
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "type", rename_all = "kebab-case")]
+pub enum ArchiveConfig {
+    /// Store archived tasks in the `durable.task_archive` table.
+    Table,
+
+    /// Upload archived tasks to the object store configured in
+    /// [`Config::object_store`].
+    ObjectStore(ObjectStoreArchiveConfig),
+}
+
+impl From<ObjectStoreArchiveConfig> for ArchiveConfig {
+    fn from(config: ObjectStoreArchiveConfig) -> Self {
+        Self::ObjectStore(config)
+    }
+}
+
+/// Configuration for archiving tasks to an object store.
+///
+/// Each batch of archived tasks is uploaded as a single JSONL object, with one
+/// task per line.
+#[derive(Clone, Debug, Setters, serde::Serialize, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ObjectStoreArchiveConfig {
+    /// The bucket that archived tasks are uploaded to.
+    pub bucket: String,
+
+    /// A prefix added to the key of every uploaded object.
+    ///
+    /// Object keys are `{prefix}{first_id}-{last_id}.jsonl`, where the ids are
+    /// those of the first and last task in the batch.
+    #[serde(default)]
+    pub prefix: String,
+}
+
+impl ObjectStoreArchiveConfig {
+    pub fn new(bucket: impl Into<String>) -> Self {
+        Self {
+            bucket: bucket.into(),
+            prefix: String::new(),
+        }
+    }
+}
+
+/// Configuration for sending emails from workflows.
+#[derive(Clone, Debug, Setters, serde::Serialize, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct EmailConfig {
+    /// The sender used for emails that do not specify one.
+    #[serde(default)]
+    #[setters(strip_option, into)]
+    pub default_from: Option<String>,
+
+    /// The service used to deliver emails.
+    pub transport: EmailTransport,
+}
+
+impl EmailConfig {
+    pub fn new(transport: impl Into<EmailTransport>) -> Self {
+        Self {
+            default_from: None,
+            transport: transport.into(),
+        }
+    }
+}
+
+/// The service used to deliver emails sent by workflows.
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "type", rename_all = "kebab-case")]
+pub enum EmailTransport {
+    /// Send emails via an SMTP relay.
+    Smtp(SmtpConfig),
+
+    /// Send emails via the Amazon SES v2 API.
+    Ses(SesConfig),
+}
+
+impl From<SmtpConfig> for EmailTransport {
+    fn from(config: SmtpConfig) -> Self {
+        Self::Smtp(config)
+    }
+}
+
+impl From<SesConfig> for EmailTransport {
+    fn from(config: SesConfig) -> Self {
+        Self::Ses(config)
+    }
+}
+
+/// Connection details for an SMTP relay.
+#[derive(Clone, Setters, serde::Serialize, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SmtpConfig {
+    /// The hostname of the SMTP server.
+    pub host: String,
+
+    /// The port to connect to.
+    ///
+    /// If not set then the default port for the chosen [`SmtpTls`] mode is
+    /// used.
+    #[serde(default)]
+    #[setters(strip_option)]
+    pub port: Option<u16>,
+
+    /// The username used to authenticate with the server.
+    #[serde(default)]
+    #[setters(strip_option, into)]
+    pub username: Option<String>,
+
+    /// The password used to authenticate with the server.
+    #[serde(default)]
+    #[setters(strip_option, into)]
+    pub password: Option<String>,
+
+    /// How the connection to the server is secured.
+    #[serde(default)]
+    pub tls: SmtpTls,
+}
+
+impl SmtpConfig {
+    pub fn new(host: impl Into<String>) -> Self {
+        Self {
+            host: host.into(),
+            port: None,
+            username: None,
+            password: None,
+            tls: SmtpTls::default(),
+        }
+    }
+}
+
+// Avoid printing credentials as part of the config.
+impl fmt::Debug for SmtpConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SmtpConfig")
+            .field("host", &self.host)
+            .field("port", &self.port)
+            .field("username", &self.username)
+            .field("password", &self.password.as_ref().map(|_| "<redacted>"))
+            .field("tls", &self.tls)
+            .finish()
+    }
+}
+
+/// How the connection to an SMTP server is secured.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SmtpTls {
+    /// Connect over TLS from the start (usually port 465).
+    Tls,
+
+    /// Connect in plaintext and then upgrade the connection using `STARTTLS`
+    /// (usually port 587).
+    #[default]
+    Starttls,
+
+    /// Do not use TLS at all.
+    ///
+    /// This sends credentials and messages in plaintext and should only be
+    /// used for local testing.
+    None,
+}
+
+/// Credentials for sending emails via Amazon SES.
+#[derive(Clone, Setters, serde::Serialize, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SesConfig {
+    /// The AWS region to send emails from.
+    pub region: String,
+
+    /// The access key id used to sign requests.
+    pub access_key_id: String,
+
+    /// The secret access key used to sign requests.
+    pub secret_access_key: String,
+
+    /// A session token to include with requests, for use with temporary
+    /// credentials.
+    #[serde(default)]
+    #[setters(strip_option)]
+    pub session_token: Option<String>,
+
+    /// Override the URL of the SES API.
+    ///
+    /// By default this is `https://email.{region}.amazonaws.com`.
+    #[serde(default)]
+    #[setters(strip_option, into)]
+    pub endpoint: Option<String>,
+}
+
+impl SesConfig {
+    pub fn new(
+        region: impl Into<String>,
+        access_key_id: impl Into<String>,
+        secret_access_key: impl Into<String>,
+    ) -> Self {
+        Self {
+            region: region.into(),
+            access_key_id: access_key_id.into(),
+            secret_access_key: secret_access_key.into(),
+            session_token: None,
+            endpoint: None,
+        }
+    }
+}
+
+// Avoid printing credentials as part of the config.
+impl fmt::Debug for SesConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SesConfig")
+            .field("region", &self.region)
+            .field("access_key_id", &self.access_key_id)
+            .field("secret_access_key", &"<redacted>")
+            .field(
+                "session_token",
+                &self.session_token.as_ref().map(|_| "<redacted>"),
+            )
+            .field("endpoint", &self.endpoint)
+            .finish()
+    }
+}
+
+/// The message queue that workflows can publish messages to.
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "type", rename_all = "kebab-case")]
+pub enum MqConfig {
+    /// Publish messages to Kafka.
+    ///
+    /// This requires the `kafka` feature.
+    Kafka(KafkaConfig),
+
+    /// Publish messages to NATS.
+    ///
+    /// This requires the `nats` feature.
+    Nats(NatsConfig),
+}
+
+impl From<KafkaConfig> for MqConfig {
+    fn from(config: KafkaConfig) -> Self {
+        Self::Kafka(config)
+    }
+}
+
+impl From<NatsConfig> for MqConfig {
+    fn from(config: NatsConfig) -> Self {
+        Self::Nats(config)
+    }
+}
+
+/// Connection details for a Kafka cluster.
+#[derive(Clone, Setters, serde::Serialize, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct KafkaConfig {
+    /// A comma-separated list of brokers to bootstrap from.
+    pub brokers: String,
+
+    /// Additional librdkafka producer properties (e.g. `security.protocol` or
+    /// `sasl.username`).
+    ///
+    /// The producer is always created with `enable.idempotence` set to `true`
+    /// unless it is overridden here.
+    #[serde(default)]
+    pub options: BTreeMap<String, String>,
+}
+
+impl KafkaConfig {
+    pub fn new(brokers: impl Into<String>) -> Self {
+        Self {
+            brokers: brokers.into(),
+            options: BTreeMap::new(),
+        }
+    }
+
+    /// Set an additional librdkafka producer property.
+    pub fn option(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.options.insert(key.into(), value.into());
+        self
+    }
+}
+
+// Avoid printing credentials as part of the config.
+impl fmt::Debug for KafkaConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("KafkaConfig")
+            .field("brokers", &self.brokers)
+            .field("options", &redact_kafka_options(&self.options))
+            .finish()
+    }
+}
+
+fn redact_kafka_options(options: &BTreeMap<String, String>) -> BTreeMap<&str, &str> {
+    options
+        .iter()
+        .map(|(key, value)| {
+            let sensitive = key.contains("password") || key.contains("secret");
+            (key.as_str(), if sensitive { "<redacted>" } else { value })
+        })
+        .collect()
+}
+
+/// Connection details for a NATS server.
+#[derive(Clone, Setters, serde::Serialize, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct NatsConfig {
+    /// The URL of the NATS server (e.g. `nats://localhost:4222`).
+    pub url: String,
+
+    /// A token used to authenticate with the server.
+    #[serde(default)]
+    #[setters(strip_option, into)]
+    pub token: Option<String>,
+
+    /// Publish messages via JetStream.
+    ///
+    /// When enabled, publishing waits for the stream to acknowledge the
+    /// message and the idempotency key is also sent as the `Nats-Msg-Id`
+    /// header so that JetStream de-duplicates retried publishes. Otherwise,
+    /// messages are published using core NATS which provides no delivery
+    /// guarantees.
+    #[serde(default)]
+    pub jetstream: bool,
+}
+
+impl NatsConfig {
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            token: None,
+            jetstream: false,
+        }
+    }
+}
+
+// Avoid printing credentials as part of the config.
+impl fmt::Debug for NatsConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("NatsConfig")
+            .field("url", &self.url)
+            .field("token", &self.token.as_ref().map(|_| "<redacted>"))
+            .field("jetstream", &self.jetstream)
+            .finish()
+    }
+}
+
+/// A message queue that the worker launches new tasks from.
+///
+/// See the [`ingest`](crate::ingest) module for details.
+#[derive(Clone, Debug, Setters, serde::Serialize, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SourceConfig {
+    /// A name that uniquely identifies this source within the cluster.
+    ///
+    /// Dedupe keys are scoped to the source name, so renaming a source means
+    /// that messages which are redelivered afterwards may launch a second
+    /// task.
+    pub name: String,
+
+    /// The queue that messages are received from.
+    pub queue: SourceQueue,
+
+    /// How each message is turned into a task.
+    pub mapping: TaskMapping,
+}
+
+impl SourceConfig {
+    pub fn new(
+        name: impl Into<String>,
+        queue: impl Into<SourceQueue>,
+        mapping: TaskMapping,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            queue: queue.into(),
+            mapping,
+        }
+    }
+}
+
+/// The queue that a [`SourceConfig`] receives messages from.
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "type", rename_all = "kebab-case")]
+pub enum SourceQueue {
+    /// Consume messages from Kafka topics as part of a consumer group.
+    ///
+    /// This requires the `kafka` feature.
+    Kafka(KafkaSourceConfig),
+
+    /// Receive messages from an Amazon SQS queue.
+    Sqs(SqsSourceConfig),
+}
+
+impl From<KafkaSourceConfig> for SourceQueue {
+    fn from(config: KafkaSourceConfig) -> Self {
+        Self::Kafka(config)
+    }
+}
+
+impl From<SqsSourceConfig> for SourceQueue {
+    fn from(config: SqsSourceConfig) -> Self {
+        Self::Sqs(config)
+    }
+}
+
+/// Connection details for consuming from a Kafka cluster.
+///
+/// Offsets are committed to the consumer group once the tasks for the
+/// messages before them have been launched. Automatic offset commits are
+/// always disabled.
+#[derive(Clone, Setters, serde::Serialize, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct KafkaSourceConfig {
+    /// A comma-separated list of brokers to bootstrap from.
+    pub brokers: String,
+
+    /// The consumer group that the worker joins.
+    ///
+    /// All workers consuming from the same topics should use the same group
+    /// so that each message is only delivered to one of them.
+    pub group_id: String,
+
+    /// The topics to subscribe to.
+    pub topics: Vec<String>,
+
+    /// The maximum number of messages that are launched as tasks in a single
+    /// database transaction.
+    ///
+    /// The default is 100.
+    #[serde(default = "default_usize::<100>")]
+    pub max_batch_size: usize,
+
+    /// Additional librdkafka consumer properties (e.g. `security.protocol` or
+    /// `auto.offset.reset`).
+    #[serde(default)]
+    pub options: BTreeMap<String, String>,
+}
+
+impl KafkaSourceConfig {
+    pub fn new(
+        brokers: impl Into<String>,
+        group_id: impl Into<String>,
+        topics: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Self {
+        Self {
+            brokers: brokers.into(),
+            group_id: group_id.into(),
+            topics: topics.into_iter().map(Into::into).collect(),
+            max_batch_size: default_usize::<100>(),
+            options: BTreeMap::new(),
+        }
+    }
+
+    /// Set an additional librdkafka consumer property.
+    pub fn option(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.options.insert(key.into(), value.into());
+        self
+    }
+}
+
+// Avoid printing credentials as part of the config.
+impl fmt::Debug for KafkaSourceConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("KafkaSourceConfig")
+            .field("brokers", &self.brokers)
+            .field("group_id", &self.group_id)
+            .field("topics", &self.topics)
+            .field("max_batch_size", &self.max_batch_size)
+            .field("options", &redact_kafka_options(&self.options))
+            .finish()
+    }
+}
+
+/// Connection details for receiving messages from an Amazon SQS queue.
+///
+/// Messages are deleted from the queue once their tasks have been launched.
+/// Messages that are not deleted become visible again once their visibility
+/// timeout expires and are then received again.
+#[derive(Clone, Setters, serde::Serialize, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SqsSourceConfig {
+    /// The URL of the queue (e.g.
+    /// `https://sqs.us-east-1.amazonaws.com/123456789012/my-queue`).
+    pub queue_url: String,
+
+    /// The region that requests are signed for.
+    pub region: String,
+
+    /// The access key id used to sign requests.
+    pub access_key_id: String,
+
+    /// The secret access key used to sign requests.
+    pub secret_access_key: String,
+
+    /// A session token to include with requests, for use with temporary
+    /// credentials.
+    #[serde(default)]
+    #[setters(strip_option)]
+    pub session_token: Option<String>,
+
+    /// How long each receive request waits for messages to arrive.
+    ///
+    /// SQS allows at most 20 seconds, which is also the default.
+    #[serde(default = "default_seconds::<20>")]
+    #[serde(with = "duration_seconds")]
+    pub wait_time: Duration,
+
+    /// Override the visibility timeout of received messages.
+    ///
+    /// If not set then the queue's own visibility timeout is used.
+    #[serde(default)]
+    #[serde(with = "option_duration_seconds")]
+    #[setters(strip_option)]
+    pub visibility_timeout: Option<Duration>,
+
+    /// The maximum number of messages to receive at once.
+    ///
+    /// SQS allows at most 10, which is also the default.
+    #[serde(default = "default_u32::<10>")]
+    pub max_messages: u32,
+}
+
+impl SqsSourceConfig {
+    pub fn new(
+        queue_url: impl Into<String>,
+        region: impl Into<String>,
+        access_key_id: impl Into<String>,
+        secret_access_key: impl Into<String>,
+    ) -> Self {
+        Self {
+            queue_url: queue_url.into(),
+            region: region.into(),
+            access_key_id: access_key_id.into(),
+            secret_access_key: secret_access_key.into(),
+            session_token: None,
+            wait_time: default_seconds::<20>(),
+            visibility_timeout: None,
+            max_messages: default_u32::<10>(),
+        }
+    }
+}
+
+// Avoid printing credentials as part of the config.
+impl fmt::Debug for SqsSourceConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SqsSourceConfig")
+            .field("queue_url", &self.queue_url)
+            .field("region", &self.region)
+            .field("access_key_id", &self.access_key_id)
+            .field("secret_access_key", &"<redacted>")
+            .field(
+                "session_token",
+                &self.session_token.as_ref().map(|_| "<redacted>"),
+            )
+            .field("wait_time", &self.wait_time)
+            .field("visibility_timeout", &self.visibility_timeout)
+            .field("max_messages", &self.max_messages)
+            .finish()
+    }
+}
+
+/// Describes how a message received from a source is turned into a task.
+#[derive(Clone, Debug, Setters, serde::Serialize, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TaskMapping {
+    /// The name of the program that launched tasks run.
+    ///
+    /// This is the name that the program was uploaded with. If there are
+    /// multiple programs with the same name then the most recently used one is
+    /// picked.
+    pub program: String,
+
+    /// The name of launched tasks.
+    ///
+    /// Defaults to the name of the source.
+    #[serde(default)]
+    #[setters(strip_option, into)]
+    pub task_name: Option<String>,
+
+    /// The workflow exported by the program that launched tasks run.
+    ///
+    /// If not set then tasks run the program's `wasi:cli/run` export.
+    #[serde(default)]
+    #[setters(strip_option, into)]
+    pub entrypoint: Option<String>,
+
+    /// How the data of launched tasks is built from the message.
+    #[serde(default)]
+    pub data: DataMapping,
+
+    /// The tenant that launched tasks belong to.
+    ///
+    /// The program is also looked up among the programs uploaded by this
+    /// tenant.
+    #[serde(default)]
+    #[setters(strip_option, into)]
+    pub tenant: Option<String>,
+
+    /// A message header containing the dedupe key of the message.
+    ///
+    /// Messages without this header fall back to the key assigned by the
+    /// source itself (e.g. the message id for SQS or the partition and offset
+    /// for Kafka). Setting this allows messages that were published more than
+    /// once to only launch a single task.
+    #[serde(default)]
+    #[setters(strip_option, into)]
+    pub dedupe_header: Option<String>,
+}
+
+impl TaskMapping {
+    pub fn new(program: impl Into<String>) -> Self {
+        Self {
+            program: program.into(),
+            task_name: None,
+            entrypoint: None,
+            data: DataMapping::default(),
+            tenant: None,
+            dedupe_header: None,
+        }
+    }
+}
+
+/// How the data of an ingested task is built from a message.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum DataMapping {
+    /// The message payload is parsed as JSON and used as the task data.
+    ///
+    /// Messages whose payload is not valid JSON are skipped.
+    #[default]
+    Payload,
+
+    /// The task data is a JSON object describing the whole message:
+    ///
+    /// ```json
+    /// {
+    ///     "source": "<source name>",
+    ///     "key": "<dedupe key>",
+    ///     "headers": { "<name>": "<value>" },
+    ///     "payload": "<base64-encoded payload>"
+    /// }
+    /// ```
+    ///
+    /// Header values that are not valid UTF-8 are lossily converted.
+    Envelope,
+}
+
+/// Configuration for the management HTTP API.
+///
+/// The API lets tools that can't (or shouldn't) talk to the database directly
+/// inspect and manage the cluster. It exposes the following routes:
+/// - `GET /tasks` lists tasks, most recent first. It accepts `state`, `name`,
+///   `tenant`, `labels`, `before` (a task id) and `limit` query parameters.
+///   `labels` only matches tasks that have all of the given labels, written
+///   as `key=value,key=value`.
+/// - `GET /tasks/:id` returns the details of a single task, including the
+///   metadata that the task has published about itself.
+/// - `GET /tasks/:id/logs` returns the logs of a task as plain text. Passing
+///   `follow=true` keeps the response open until the task completes.
+/// - `POST /tasks/:id/cancel` marks a task that has not yet completed as
+///   failed.
+/// - `POST /tasks/:id/retry` launches a new copy of a failed task.
+/// - `POST /programs` uploads a wasm program, with optional `name` and
+///   `tenant` query parameters, and returns its id.
+/// - `GET /workers` lists the workers in the cluster.
+///
+/// Responses are JSON unless noted otherwise. Errors are returned as
+/// `{"error": "<message>"}` with an appropriate status code.
+///
+/// The API is also available as a standalone axum router via
+/// `durable_runtime::api::router` for embedding within another server.
+#[derive(Clone, Setters, serde::Serialize, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ApiConfig {
+    /// The address that the server listens on.
+    pub bind: SocketAddr,
+
+    /// Require requests to carry this token in an `Authorization: Bearer`
+    /// header.
+    ///
+    /// The API allows anyone who can reach it to cancel tasks and upload
+    /// programs, so this should be set unless access is restricted some
+    /// other way.
+    #[serde(default)]
+    #[setters(strip_option, into)]
+    pub token: Option<String>,
+
+    /// Tokens which only grant access to the tasks and programs of a single
+    /// tenant, keyed by tenant name.
+    ///
+    /// Requests made with one of these tokens see tasks belonging to other
+    /// tenants as not existing, upload programs on behalf of their tenant and
+    /// cannot list workers.
+    #[serde(default)]
+    pub tenant_tokens: BTreeMap<String, String>,
+
+    /// The maximum size, in bytes, of an uploaded program.
+    ///
+    /// The default limit is 64MB.
+    #[serde(default = "default_usize::<{ 64 * 1024 * 1024 }>")]
+    pub max_program_size: usize,
+}
+
+impl ApiConfig {
+    pub fn new(bind: SocketAddr) -> Self {
+        Self {
+            bind,
+            token: None,
+            tenant_tokens: BTreeMap::new(),
+            max_program_size: default_usize::<{ 64 * 1024 * 1024 }>(),
+        }
+    }
+
+    /// Add a token which grants access to a single tenant.
+    pub fn tenant_token(mut self, tenant: impl Into<String>, token: impl Into<String>) -> Self {
+        self.tenant_tokens.insert(tenant.into(), token.into());
+        self
+    }
+}
+
+impl fmt::Debug for ApiConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ApiConfig")
+            .field("bind", &self.bind)
+            .field("token", &self.token.as_ref().map(|_| "<redacted>"))
+            .field(
+                "tenant_tokens",
+                &self.tenant_tokens.keys().collect::<Vec<_>>(),
+            )
+            .field("max_program_size", &self.max_program_size)
+            .finish()
+    }
+}
+
+/// Configuration for the webhook HTTP server.
+///
+/// Each route accepts `POST` requests and either launches a new task or sends
+/// a notification to an existing one. Requests are answered with:
+/// - `202 Accepted` once the task has been launched or notified,
+/// - `400 Bad Request` if the body could not be turned into task data,
+/// - `401 Unauthorized` if the route requires a signature and it is missing or
+///   invalid,
+/// - `404 Not Found` if the task to be notified does not exist or has already
+///   completed, and
+/// - `413 Payload Too Large` if the body is larger than
+///   [`max_body_size`](WebhookConfig::max_body_size).
+#[derive(Clone, Debug, Setters, serde::Serialize, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct WebhookConfig {
+    /// The address that the server listens on.
+    pub bind: SocketAddr,
+
+    /// The maximum size, in bytes, of a request body.
+    ///
+    /// The default limit is 1MB.
+    #[serde(default = "default_usize::<{ 1024 * 1024 }>")]
+    pub max_body_size: usize,
+
+    /// The routes that webhooks can be sent to.
+    #[serde(default)]
+    pub routes: Vec<WebhookRoute>,
+}
+
+impl WebhookConfig {
+    pub fn new(bind: SocketAddr) -> Self {
+        Self {
+            bind,
+            max_body_size: default_usize::<{ 1024 * 1024 }>(),
+            routes: Vec::new(),
+        }
+    }
+
+    /// Add a new route to the server.
+    pub fn route(mut self, route: WebhookRoute) -> Self {
+        self.routes.push(route);
+        self
+    }
+}
+
+/// A single route on the webhook server.
+#[derive(Clone, Debug, Setters, serde::Serialize, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct WebhookRoute {
+    /// A name for this route.
+    ///
+    /// This is used in logs and, for routes that launch tasks, to scope the
+    /// dedupe keys of requests in the same way as [`SourceConfig::name`].
+    pub name: String,
+
+    /// The path of the route (e.g. `/hooks/github`).
+    ///
+    /// Routes that notify tasks must contain a `:task_id` segment (e.g.
+    /// `/hooks/payments/:task_id`) which identifies the task to notify.
+    pub path: String,
+
+    /// What happens when a request is received.
+    pub action: WebhookAction,
+
+    /// Require requests to be signed with a shared secret.
+    #[serde(default)]
+    #[setters(strip_option)]
+    pub signature: Option<WebhookSignature>,
+}
+
+impl WebhookRoute {
+    pub fn new(
+        name: impl Into<String>,
+        path: impl Into<String>,
+        action: impl Into<WebhookAction>,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            path: path.into(),
+            action: action.into(),
+            signature: None,
+        }
+    }
+}
+
+/// What a [`WebhookRoute`] does with the requests that it receives.
+///
+/// The request body is treated as the message payload and the request headers
+/// as its headers, so [`DataMapping`] and [`TaskMapping::dedupe_header`] work
+/// the same way as they do for message queues.
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "type", rename_all = "kebab-case")]
+pub enum WebhookAction {
+    /// Launch a new task for each request.
+    Launch(TaskMapping),
+
+    /// Send a notification to the task identified by the route path.
+    Notify(NotifyMapping),
+}
+
+impl From<TaskMapping> for WebhookAction {
+    fn from(mapping: TaskMapping) -> Self {
+        Self::Launch(mapping)
+    }
+}
+
+impl From<NotifyMapping> for WebhookAction {
+    fn from(mapping: NotifyMapping) -> Self {
+        Self::Notify(mapping)
+    }
+}
+
+/// Describes how a webhook request is turned into a task notification.
+#[derive(Clone, Debug, Setters, serde::Serialize, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct NotifyMapping {
+    /// The name of the notification event.
+    pub event: String,
+
+    /// How the data of the notification is built from the request.
+    #[serde(default)]
+    pub data: DataMapping,
+}
+
+impl NotifyMapping {
+    pub fn new(event: impl Into<String>) -> Self {
+        Self {
+            event: event.into(),
+            data: DataMapping::default(),
+        }
+    }
+}
+
+/// An HMAC-SHA256 signature that webhook requests must carry.
+///
+/// The signature is computed over the raw request body and sent as a
+/// hex-encoded header value, optionally prefixed by `sha256=` as is done by
+/// GitHub.
+#[derive(Clone, Setters, serde::Serialize, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct WebhookSignature {
+    /// The secret shared with the sender.
+    pub secret: String,
+
+    /// The header containing the signature.
+    ///
+    /// The default is `x-signature-256`.
+    #[serde(default = "default_signature_header")]
+    #[setters(into)]
+    pub header: String,
+}
+
+impl WebhookSignature {
+    pub fn new(secret: impl Into<String>) -> Self {
+        Self {
+            secret: secret.into(),
+            header: default_signature_header(),
+        }
+    }
+}
+
+// Avoid printing credentials as part of the config.
+impl fmt::Debug for WebhookSignature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WebhookSignature")
+            .field("secret", &"<redacted>")
+            .field("header", &self.header)
+            .finish()
+    }
+}
+
+fn default_schema() -> String {
+    "durable".into()
+}
+
+fn default_signature_header() -> String {
+    "x-signature-256".into()
+}
+
+/// The operations that workflows may perform within a preopened directory.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum DirPerms {
+    /// Workflows may only read files and list directories.
+    #[default]
+    Read,
+
+    /// Workflows may also create, modify, and delete files and directories.
+    ReadWrite,
+}
+
+impl DirPerms {
+    pub(crate) fn writable(self) -> bool {
+        self == Self::ReadWrite
+    }
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        use serde::Deserialize;
+
+        #[allow(unreachable_patterns)]
+        match Self::deserialize(EmptyMapDeserializer) {
+            Ok(config) => config,
+            Err(e) => match e {},
+        }
+    }
+}
+
+#[test]
+#[cfg(test)]
+fn config_default_does_not_panic() {
+    let _ = Config::default();
+}
+
+const fn default_seconds<const SECONDS: u64>() -> Duration {
+    Duration::from_secs(SECONDS)
+}
+
+const fn default_option_seconds<const SECONDS: u64>() -> Option<Duration> {
+    Some(default_seconds::<{ SECONDS }>())
+}
+
+const fn default_heartbeat_jitter() -> f64 {
+    0.25
+}
+
+const fn default_epoch_interval() -> Option<Duration> {
+    Some(Duration::from_millis(10))
+}
+
+const fn default_partition_size() -> Option<u64> {
+    Some(1_000_000)
+}
+
+const fn default_u32<const N: u32>() -> u32 {
+    N
+}
+
+const fn default_usize<const N: usize>() -> usize {
+    N
+}
+
+mod duration_seconds {
+    use std::time::Duration;
+
+    use serde::Serialize;
+
+    pub(crate) fn serialize<S>(duration: &Duration, ser: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        if ser.is_human_readable() && duration.subsec_nanos() == 0 {
+            duration.as_secs().serialize(ser)
+        } else {
+            duration.as_secs_f64().serialize(ser)
+        }
+    }
+
+    pub(crate) fn deserialize<'de, D>(de: D) -> Result<Duration, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        use serde::de::Error;
+
+        struct Visitor;
+
+        impl serde::de::Visitor<'_> for Visitor {
+            type Value = Duration;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                write!(f, "a duration in seconds")
+            }
+
+            fn visit_u64<E: Error>(self, v: u64) -> Result<Self::Value, E> {
+                Ok(Duration::from_secs(v))
+            }
+
+            fn visit_i64<E: Error>(self, v: i64) -> Result<Self::Value, E> {
+                if v < 0 {
+                    return Err(Error::custom("durations cannot be negative"));
+                }
+
+                self.visit_u64(v as u64)
+            }
+
+            fn visit_f32<E: Error>(self, v: f32) -> Result<Self::Value, E> {
                 if v < 0.0 {
                     return Err(Error::custom("durations cannot be negative"));
                 }
@@ -315,19 +2231,402 @@ mod tests {
     fn test_decode_defaults() {
         let toml = r#"
 heartbeat_interval = 30
+heartbeat_jitter = 0.25
 heartbeat_timeout = 120
+worker_sweep_interval = 5
 wasm_entry_ttl = 86400
 max_http_timeout = 60
 max_workflow_events = 2147483647
 max_log_bytes_per_transaction = 131072
+log_flush_interval = 1
 max_returned_buffer_len = 8388608
 suspend_timeout = 60
 suspend_margin = 10
+queue_stats_interval = 30
+partition_size = 1000000
 max_tasks = 2000
+claim_batch_size = 100
+claim_ahead = 0
+claim_ahead_timeout = 5
+group_commit_max_events = 256
 max_concurrent_compilations = 4
+epoch_interval = 0.01
+load_precompiled_programs = false
+deny_deprecated_interfaces = false
+strict_imports = false
+audit = false
 debug_emit_task_logs = false
+preopens = []
+ingest_dedupe_window = 604800
 "#;
 
         let _: Config = toml::from_str(toml).unwrap();
     }
+
+    #[test]
+    fn test_decode_preopens() {
+        let toml = r#"
+[[preopens]]
+host_path = "/srv/data"
+guest_path = "/data"
+
+[[preopens]]
+host_path = "/tmp/scratch"
+guest_path = "/scratch"
+perms = "read-write"
+"#;
+
+        let config: Config = toml::from_str(toml).unwrap();
+        assert_eq!(config.preopens.len(), 2);
+        assert_eq!(config.preopens[0].perms, DirPerms::Read);
+        assert_eq!(config.preopens[1].perms, DirPerms::ReadWrite);
+    }
+
+    #[test]
+    fn test_decode_archive() {
+        let toml = r#"
+[archive]
+type = "object-store"
+bucket = "durable-archive"
+prefix = "tasks/"
+"#;
+
+        let config: Config = toml::from_str(toml).unwrap();
+        match config.archive.unwrap() {
+            ArchiveConfig::ObjectStore(archive) => {
+                assert_eq!(archive.bucket, "durable-archive");
+                assert_eq!(archive.prefix, "tasks/");
+            }
+            archive => panic!("unexpected archive config: {archive:?}"),
+        }
+    }
+
+    #[test]
+    fn test_decode_scratch_dir() {
+        let toml = r#"
+[scratch_dir]
+guest_path = "/tmp"
+snapshot = true
+"#;
+
+        let config: Config = toml::from_str(toml).unwrap();
+        let scratch = config.scratch_dir.unwrap();
+        assert_eq!(scratch.guest_path, "/tmp");
+        assert_eq!(scratch.max_bytes, 16 * 1024 * 1024);
+        assert!(scratch.snapshot);
+    }
+
+    #[test]
+    fn test_decode_rate_limits() {
+        let toml = r#"
+[rate_limits.github-api]
+tokens = 5000
+period = 3600
+
+[rate_limits.partner]
+tokens = 10
+period = 0.5
+burst = 20
+"#;
+
+        let config: Config = toml::from_str(toml).unwrap();
+        let github = &config.rate_limits["github-api"];
+        assert_eq!(github.capacity(), 5000.0);
+        assert_eq!(github.period, Duration::from_secs(3600));
+
+        let partner = &config.rate_limits["partner"];
+        assert_eq!(partner.capacity(), 20.0);
+        assert_eq!(partner.refill_rate(), 20.0);
+    }
+
+    #[test]
+    fn test_decode_llm_providers() {
+        let toml = r#"
+[llm_providers.default]
+base_url = "https://api.openai.com/v1"
+api_key = "sk-test"
+default_model = "gpt-4o-mini"
+
+[llm_providers.local]
+base_url = "http://localhost:11434/v1"
+"#;
+
+        let config: Config = toml::from_str(toml).unwrap();
+        let default = &config.llm_providers["default"];
+        assert_eq!(default.api_key.as_deref(), Some("sk-test"));
+        assert_eq!(default.default_model.as_deref(), Some("gpt-4o-mini"));
+        assert!(!format!("{default:?}").contains("sk-test"));
+
+        let local = &config.llm_providers["local"];
+        assert_eq!(local.base_url, "http://localhost:11434/v1");
+        assert_eq!(local.api_key, None);
+    }
+
+    #[test]
+    fn test_decode_http_cache() {
+        let toml = r#"
+[http_cache]
+ttl = 300
+"#;
+
+        let config: Config = toml::from_str(toml).unwrap();
+        let cache = config.http_cache.unwrap();
+        assert_eq!(cache.ttl, Some(Duration::from_secs(300)));
+        assert_eq!(cache.max_bytes, 64 * 1024 * 1024);
+    }
+
+    #[test]
+    fn test_decode_object_store() {
+        let toml = r#"
+[object_store]
+endpoint = "http://localhost:9000"
+region = "us-east-1"
+access_key_id = "minio"
+secret_access_key = "minio123"
+path_style = true
+"#;
+
+        let config: Config = toml::from_str(toml).unwrap();
+        let store = config.object_store.unwrap();
+        assert_eq!(store.endpoint, "http://localhost:9000");
+        assert!(store.path_style);
+        assert_eq!(store.session_token, None);
+        assert_eq!(store.max_object_size, 8 * 1024 * 1024);
+        assert!(!format!("{store:?}").contains("minio123"));
+    }
+
+    #[test]
+    fn test_decode_email() {
+        let toml = r#"
+[email]
+default_from = "Workflows <workflows@example.com>"
+
+[email.transport]
+type = "smtp"
+host = "smtp.example.com"
+username = "user"
+password = "hunter2"
+"#;
+
+        let config: Config = toml::from_str(toml).unwrap();
+        let email = config.email.unwrap();
+        assert_eq!(
+            email.default_from.as_deref(),
+            Some("Workflows <workflows@example.com>")
+        );
+
+        let EmailTransport::Smtp(smtp) = &email.transport else {
+            panic!("expected an smtp transport, got {:?}", email.transport);
+        };
+        assert_eq!(smtp.host, "smtp.example.com");
+        assert_eq!(smtp.port, None);
+        assert_eq!(smtp.tls, SmtpTls::Starttls);
+        assert!(!format!("{email:?}").contains("hunter2"));
+
+        let toml = r#"
+[email.transport]
+type = "ses"
+region = "us-west-2"
+access_key_id = "akid"
+secret_access_key = "s3cr3t"
+"#;
+
+        let config: Config = toml::from_str(toml).unwrap();
+        let email = config.email.unwrap();
+        assert!(matches!(email.transport, EmailTransport::Ses(_)));
+        assert!(!format!("{email:?}").contains("s3cr3t"));
+    }
+
+    #[test]
+    fn test_decode_mq() {
+        let toml = r#"
+[mq]
+type = "kafka"
+brokers = "localhost:9092"
+
+[mq.options]
+"security.protocol" = "SASL_SSL"
+"sasl.password" = "hunter2"
+"#;
+
+        let config: Config = toml::from_str(toml).unwrap();
+        let Some(MqConfig::Kafka(kafka)) = &config.mq else {
+            panic!("expected a kafka config, got {:?}", config.mq);
+        };
+        assert_eq!(kafka.brokers, "localhost:9092");
+        assert_eq!(kafka.options["security.protocol"], "SASL_SSL");
+        assert!(!format!("{kafka:?}").contains("hunter2"));
+
+        let toml = r#"
+[mq]
+type = "nats"
+url = "nats://localhost:4222"
+jetstream = true
+"#;
+
+        let config: Config = toml::from_str(toml).unwrap();
+        let Some(MqConfig::Nats(nats)) = &config.mq else {
+            panic!("expected a nats config, got {:?}", config.mq);
+        };
+        assert_eq!(nats.url, "nats://localhost:4222");
+        assert!(nats.jetstream);
+    }
+
+    #[test]
+    fn test_decode_sources() {
+        let toml = r#"
+ingest_dedupe_window = 3600
+
+[[sources]]
+name = "orders"
+
+[sources.queue]
+type = "kafka"
+brokers = "localhost:9092"
+group_id = "durable"
+topics = ["orders"]
+
+[sources.queue.options]
+"sasl.password" = "hunter2"
+
+[sources.mapping]
+program = "order-workflow"
+dedupe_header = "durable-idempotency-key"
+
+[[sources]]
+name = "signups"
+
+[sources.queue]
+type = "sqs"
+queue_url = "https://sqs.us-east-1.amazonaws.com/123456789012/signups"
+region = "us-east-1"
+access_key_id = "akid"
+secret_access_key = "s3cr3t"
+visibility_timeout = 60
+
+[sources.mapping]
+program = "signup-workflow"
+task_name = "signup"
+data = "envelope"
+"#;
+
+        let config: Config = toml::from_str(toml).unwrap();
+        assert_eq!(config.ingest_dedupe_window, Duration::from_secs(3600));
+        assert_eq!(config.sources.len(), 2);
+
+        let orders = &config.sources[0];
+        let SourceQueue::Kafka(kafka) = &orders.queue else {
+            panic!("expected a kafka source, got {:?}", orders.queue);
+        };
+        assert_eq!(kafka.topics, ["orders"]);
+        assert_eq!(kafka.max_batch_size, 100);
+        assert!(!format!("{kafka:?}").contains("hunter2"));
+        assert_eq!(orders.mapping.data, DataMapping::Payload);
+        assert_eq!(
+            orders.mapping.dedupe_header.as_deref(),
+            Some("durable-idempotency-key")
+        );
+
+        let signups = &config.sources[1];
+        let SourceQueue::Sqs(sqs) = &signups.queue else {
+            panic!("expected an sqs source, got {:?}", signups.queue);
+        };
+        assert_eq!(sqs.wait_time, Duration::from_secs(20));
+        assert_eq!(sqs.visibility_timeout, Some(Duration::from_secs(60)));
+        assert_eq!(sqs.max_messages, 10);
+        assert!(!format!("{sqs:?}").contains("s3cr3t"));
+        assert_eq!(signups.mapping.task_name.as_deref(), Some("signup"));
+        assert_eq!(signups.mapping.data, DataMapping::Envelope);
+    }
+
+    #[test]
+    fn test_decode_webhooks() {
+        let toml = r#"
+[webhooks]
+bind = "127.0.0.1:8080"
+
+[[webhooks.routes]]
+name = "github"
+path = "/hooks/github"
+
+[webhooks.routes.action]
+type = "launch"
+program = "github-workflow"
+dedupe_header = "x-github-delivery"
+
+[webhooks.routes.signature]
+secret = "hunter2"
+header = "x-hub-signature-256"
+
+[[webhooks.routes]]
+name = "payments"
+path = "/hooks/payments/:task_id"
+
+[webhooks.routes.action]
+type = "notify"
+event = "payment"
+"#;
+
+        let config: Config = toml::from_str(toml).unwrap();
+        let webhooks = config.webhooks.unwrap();
+        assert_eq!(webhooks.bind, "127.0.0.1:8080".parse().unwrap());
+        assert_eq!(webhooks.max_body_size, 1024 * 1024);
+        assert_eq!(webhooks.routes.len(), 2);
+
+        let github = &webhooks.routes[0];
+        let WebhookAction::Launch(mapping) = &github.action else {
+            panic!("expected a launch action, got {:?}", github.action);
+        };
+        assert_eq!(mapping.program, "github-workflow");
+        let signature = github.signature.as_ref().unwrap();
+        assert_eq!(signature.header, "x-hub-signature-256");
+        assert!(!format!("{signature:?}").contains("hunter2"));
+
+        let payments = &webhooks.routes[1];
+        let WebhookAction::Notify(mapping) = &payments.action else {
+            panic!("expected a notify action, got {:?}", payments.action);
+        };
+        assert_eq!(mapping.event, "payment");
+        assert_eq!(mapping.data, DataMapping::Payload);
+        assert!(payments.signature.is_none());
+    }
+
+    #[test]
+    fn test_decode_api() {
+        let toml = r#"
+[api]
+bind = "127.0.0.1:9090"
+token = "hunter2"
+
+[api.tenant_tokens]
+team-a = "correct-horse"
+"#;
+
+        let config: Config = toml::from_str(toml).unwrap();
+        let api = config.api.unwrap();
+        assert_eq!(api.bind, "127.0.0.1:9090".parse().unwrap());
+        assert_eq!(api.token.as_deref(), Some("hunter2"));
+        assert_eq!(
+            api.tenant_tokens.get("team-a").map(String::as_str),
+            Some("correct-horse")
+        );
+        assert_eq!(api.max_program_size, 64 * 1024 * 1024);
+        assert!(!format!("{api:?}").contains("hunter2"));
+        assert!(!format!("{api:?}").contains("correct-horse"));
+    }
+
+    #[test]
+    fn test_decode_tenant_quotas() {
+        let toml = r#"
+default_tenant_quota = 10
+
+[tenant_quotas]
+team-a = 100
+"#;
+
+        let config: Config = toml::from_str(toml).unwrap();
+        assert_eq!(config.quota_for("team-a"), Some(100));
+        assert_eq!(config.quota_for("team-b"), Some(10));
+        assert_eq!(Config::new().quota_for("team-a"), None);
+    }
 }
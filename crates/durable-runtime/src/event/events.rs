@@ -55,7 +55,43 @@ pub struct Log {
     pub index: i32,
 }
 
+/// A workflow event.
+///
+/// This is emitted when a task records a new event into the `event` table. It
+/// is not used by the worker but instead by the client to follow task events.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TaskEvent {
+    pub task_id: i64,
+    pub index: i32,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Worker {
     pub worker_id: i64,
 }
+
+/// A statement cache event.
+///
+/// This is emitted when a program asks for the prepared statements that it
+/// has cached on the workers' database connections to be evicted.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct StatementCache {
+    pub wasm: i64,
+}
+
+/// A postgres listen event.
+///
+/// This is emitted when a task starts waiting for a postgres notification on
+/// `channel`. Workers listen on the channel until every task waiting on it has
+/// had a notification delivered.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PgListen {
+    pub channel: String,
+}
+
+/// A postgres notification sent on a channel that a task is waiting on.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PgNotify {
+    pub channel: String,
+    pub payload: String,
+}
@@ -26,6 +26,23 @@ pub enum Event {
     /// table. It is used by the worker to determine whether it is a leader.
     Worker(Worker),
 
+    /// A `durable:statement-cache` event was emitted.
+    ///
+    /// This occurs when a workflow calls `durable:core/sql.clear-statement-cache`.
+    /// Every worker evicts the statements cached by the program that the
+    /// workflow is running.
+    StatementCache(StatementCache),
+
+    /// A `durable:pg-listen` event was emitted.
+    ///
+    /// This occurs when a workflow calls `durable:core/sql.listen`. Every
+    /// worker starts listening on the requested channel.
+    PgListen(PgListen),
+
+    /// A notification was sent on a channel that the event source was asked
+    /// to [`listen`](EventSource::listen) on.
+    PgNotify(PgNotify),
+
     /// This event should be emitted whenever there is a possibility that an
     /// event was lost (even if it is not known for sure).
     Lagged,
@@ -34,4 +51,19 @@ pub enum Event {
 #[async_trait]
 pub trait EventSource: Send {
     async fn next(&mut self) -> anyhow::Result<Event>;
+
+    /// Start receiving notifications sent on `channel` as [`Event::PgNotify`]
+    /// events.
+    ///
+    /// Event sources that are not backed by postgres can ignore this.
+    async fn listen(&mut self, channel: &str) -> anyhow::Result<()> {
+        let _ = channel;
+        Ok(())
+    }
+
+    /// Stop receiving notifications sent on `channel`.
+    async fn unlisten(&mut self, channel: &str) -> anyhow::Result<()> {
+        let _ = channel;
+        Ok(())
+    }
 }
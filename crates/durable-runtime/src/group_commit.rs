@@ -0,0 +1,199 @@
+//! Group commit for the events recorded at the end of workflow transactions.
+//!
+//! Normally each transaction that a workflow exits records its event with its
+//! own query. Workflows that run many tiny transactions end up spending most
+//! of their time waiting on these round trips. When
+//! [`Config::group_commit_window`] is set, events from all tasks on the
+//! worker are instead queued up here and written to the database in a single
+//! query once the window has elapsed.
+//!
+//! Each task waits for its event to be written before it continues, so a task
+//! never has more than one event queued at once and the order of the events
+//! within a task is preserved.
+//!
+//! [`Config::group_commit_window`]: crate::Config::group_commit_window
+
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use tokio::sync::oneshot;
+
+use crate::error::ClonableAnyhowError;
+use crate::worker::SharedState;
+
+/// An event waiting to be written as part of a group commit.
+pub(crate) struct PendingEvent {
+    pub task_id: i64,
+    pub worker_id: i64,
+    pub index: i32,
+    pub label: String,
+    pub value: String,
+    pub message: Option<String>,
+    pub scratch: Option<Vec<u8>>,
+    pub is_db: bool,
+}
+
+struct Queued {
+    event: PendingEvent,
+
+    /// Receives whether the event was written. It is not written if the task
+    /// is no longer running on this worker.
+    reply: oneshot::Sender<Result<bool, ClonableAnyhowError>>,
+}
+
+pub(crate) struct GroupCommit {
+    window: Duration,
+    max_events: usize,
+    queue: Mutex<Vec<Queued>>,
+}
+
+impl GroupCommit {
+    pub fn new(window: Duration, max_events: usize) -> Self {
+        Self {
+            window,
+            max_events: max_events.max(1),
+            queue: Mutex::new(Vec::new()),
+        }
+    }
+}
+
+/// Queue up an event to be written along with those of other tasks.
+///
+/// Returns whether the event was written. It will not be written if the task
+/// is no longer scheduled on the worker.
+pub(crate) async fn submit(shared: &Arc<SharedState>, event: PendingEvent) -> anyhow::Result<bool> {
+    let Some(group) = &shared.group_commit else {
+        anyhow::bail!("group commit is not enabled on this worker");
+    };
+
+    let (reply, recv) = oneshot::channel();
+    let queued = {
+        let mut queue = group.queue.lock().unwrap();
+        queue.push(Queued { event, reply });
+        queue.len()
+    };
+
+    // The first event in the batch is responsible for flushing it once the window
+    // elapses. A full batch is flushed right away instead. The flush runs in its own
+    // task so that it still completes if the task that started it is dropped.
+    if queued == 1 || queued >= group.max_events {
+        let shared = shared.clone();
+        let delay = if queued == 1 {
+            group.window
+        } else {
+            Duration::ZERO
+        };
+
+        tokio::spawn(async move {
+            tokio::time::sleep(delay).await;
+            flush(&shared).await;
+        });
+    }
+
+    match recv.await {
+        Ok(result) => Ok(result?),
+        Err(_) => anyhow::bail!("the event group commit was dropped before completing"),
+    }
+}
+
+/// Write out all the events that are currently queued.
+async fn flush(shared: &SharedState) {
+    let Some(group) = &shared.group_commit else {
+        return;
+    };
+
+    let batch = std::mem::take(&mut *group.queue.lock().unwrap());
+    if batch.is_empty() {
+        return;
+    }
+
+    shared.metrics.group_commit_size.record(batch.len() as f64);
+
+    let events: Vec<_> = batch.iter().map(|queued| &queued.event).collect();
+    match write(shared, &events).await {
+        Ok(written) => {
+            for queued in batch {
+                let _ = queued
+                    .reply
+                    .send(Ok(written.contains(&queued.event.task_id)));
+            }
+        }
+        Err(e) => {
+            let error = ClonableAnyhowError::new(e);
+            for queued in batch {
+                let _ = queued.reply.send(Err(error.clone()));
+            }
+        }
+    }
+}
+
+/// Write a batch of events, returning the ids of the tasks whose events were
+/// written.
+async fn write(shared: &SharedState, events: &[&PendingEvent]) -> anyhow::Result<Vec<i64>> {
+    let task_ids: Vec<i64> = events.iter().map(|e| e.task_id).collect();
+    let worker_ids: Vec<i64> = events.iter().map(|e| e.worker_id).collect();
+    let indices: Vec<i32> = events.iter().map(|e| e.index).collect();
+    let labels: Vec<&str> = events.iter().map(|e| &*e.label).collect();
+    let values: Vec<&str> = events.iter().map(|e| &*e.value).collect();
+    let messages: Vec<Option<&str>> = events.iter().map(|e| e.message.as_deref()).collect();
+    let scratch: Vec<Option<&[u8]>> = events.iter().map(|e| e.scratch.as_deref()).collect();
+    let is_db: Vec<bool> = events.iter().map(|e| e.is_db).collect();
+
+    // This is the batched version of the query in `TaskState::exit_impl`. Events
+    // are only written for tasks that are still running on the worker that
+    // recorded them.
+    let written = sqlx::query_scalar!(
+        r#"
+        WITH
+            input AS (
+                SELECT *
+                  FROM UNNEST(
+                    $1::int8[],
+                    $2::int8[],
+                    $3::int4[],
+                    $4::text[],
+                    $5::text[],
+                    $6::text[],
+                    $7::bytea[],
+                    $8::bool[]
+                  ) AS t(task_id, worker_id, index, label, value, message, scratch, is_db)
+            ),
+            current_task AS (
+                SELECT input.*
+                  FROM input
+                  JOIN durable.task task
+                    ON task.id = input.task_id
+                   AND task.running_on = input.worker_id
+            ),
+            insert_event AS (
+                INSERT INTO durable.event(task_id, index, label, value, scratch, is_db)
+                SELECT task_id, index, label, value::jsonb, scratch, is_db
+                  FROM current_task
+                RETURNING task_id
+            ),
+            insert_log AS (
+                INSERT INTO durable.log(task_id, index, message)
+                SELECT task_id, index, message
+                  FROM current_task
+                 WHERE message IS NOT NULL
+                ON CONFLICT ON CONSTRAINT log_pkey DO UPDATE
+                SET message = EXCLUDED.message
+                RETURNING task_id
+            )
+        SELECT task_id as "task_id!"
+          FROM current_task
+        "#,
+        &task_ids,
+        &worker_ids,
+        &indices,
+        &labels as &[&str],
+        &values as &[&str],
+        &messages as &[Option<&str>],
+        &scratch as &[Option<&[u8]>],
+        &is_db
+    )
+    .fetch_all(shared.schema.on(&shared.pool))
+    .await?;
+
+    Ok(written)
+}
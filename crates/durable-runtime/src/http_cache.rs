@@ -0,0 +1,345 @@
+//! An in-memory cache for HTTP responses fetched by workflows.
+//!
+//! See [`HttpCacheConfig`] for a description of the caching rules.
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use http::header::{CACHE_CONTROL, VARY};
+use http::{HeaderMap, Method};
+use parking_lot::Mutex;
+
+use crate::HttpCacheConfig;
+
+/// Status codes that are cacheable by default, per RFC 9110.
+const CACHEABLE_STATUSES: &[u16] = &[200, 203, 204, 300, 301, 308, 404, 405, 410, 414, 501];
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub(crate) struct CacheKey {
+    tenant: Option<String>,
+    method: Method,
+    url: String,
+    headers: Vec<(String, Vec<u8>)>,
+}
+
+#[derive(Clone, Debug)]
+pub(crate) struct CachedResponse {
+    pub status: u16,
+    pub headers: HeaderMap,
+    pub body: Vec<u8>,
+
+    /// The URLs that the request was redirected to, in order.
+    pub redirects: Vec<String>,
+}
+
+struct Entry {
+    response: Arc<CachedResponse>,
+    expires_at: Instant,
+    generation: u64,
+    size: usize,
+}
+
+#[derive(Default)]
+struct CacheState {
+    entries: HashMap<CacheKey, Entry>,
+
+    /// Keys in insertion order, used for evicting the oldest entries.
+    ///
+    /// Keys that have since been removed or replaced are left in here and
+    /// skipped when they don't match the generation of the current entry.
+    order: VecDeque<(u64, CacheKey)>,
+    generation: u64,
+    bytes: usize,
+}
+
+impl CacheState {
+    fn remove(&mut self, key: &CacheKey) {
+        if let Some(entry) = self.entries.remove(key) {
+            self.bytes -= entry.size;
+        }
+    }
+
+    fn evict_oldest(&mut self) -> bool {
+        while let Some((generation, key)) = self.order.pop_front() {
+            if self.entries.get(&key).map(|entry| entry.generation) == Some(generation) {
+                self.remove(&key);
+                return true;
+            }
+        }
+
+        false
+    }
+}
+
+pub(crate) struct HttpCache {
+    default_ttl: Option<Duration>,
+    max_bytes: usize,
+    state: Mutex<CacheState>,
+}
+
+impl HttpCache {
+    pub fn new(config: &HttpCacheConfig) -> Self {
+        Self {
+            default_ttl: config.ttl,
+            max_bytes: config.max_bytes,
+            state: Mutex::new(CacheState::default()),
+        }
+    }
+
+    /// Compute the cache key for a request, or `None` if the request should
+    /// bypass the cache entirely.
+    pub fn key(&self, request: &reqwest::Request, tenant: Option<&str>) -> Option<CacheKey> {
+        if !matches!(*request.method(), Method::GET | Method::HEAD) {
+            return None;
+        }
+
+        if request.body().is_some() {
+            return None;
+        }
+
+        if has_directive(request.headers(), "no-store") {
+            return None;
+        }
+
+        let mut headers: Vec<_> = request
+            .headers()
+            .iter()
+            .map(|(name, value)| (name.as_str().to_owned(), value.as_bytes().to_owned()))
+            .collect();
+        headers.sort();
+
+        Some(CacheKey {
+            tenant: tenant.map(|tenant| tenant.to_owned()),
+            method: request.method().clone(),
+            url: request.url().as_str().to_owned(),
+            headers,
+        })
+    }
+
+    /// Look up a fresh cached response for a request.
+    pub fn get(&self, key: &CacheKey, request: &reqwest::Request) -> Option<Arc<CachedResponse>> {
+        let headers = request.headers();
+        if has_directive(headers, "no-cache") || max_age(headers) == Some(0) {
+            return None;
+        }
+
+        let mut state = self.state.lock();
+        let entry = state.entries.get(key)?;
+        if entry.expires_at > Instant::now() {
+            return Some(entry.response.clone());
+        }
+
+        state.remove(key);
+        None
+    }
+
+    /// Store a response in the cache, if it is allowed to be cached.
+    pub fn insert(&self, key: CacheKey, response: Arc<CachedResponse>) {
+        if !CACHEABLE_STATUSES.contains(&response.status) {
+            return;
+        }
+
+        let headers = &response.headers;
+        if has_directive(headers, "no-store") || has_directive(headers, "no-cache") {
+            return;
+        }
+
+        if headers
+            .get_all(VARY)
+            .iter()
+            .any(|value| value.as_bytes().trim_ascii() == b"*")
+        {
+            return;
+        }
+
+        let ttl = match max_age(headers) {
+            Some(secs) => Duration::from_secs(secs),
+            None => match self.default_ttl {
+                Some(ttl) => ttl,
+                None => return,
+            },
+        };
+        if ttl.is_zero() {
+            return;
+        }
+
+        let size = response.body.len()
+            + response
+                .headers
+                .iter()
+                .map(|(name, value)| name.as_str().len() + value.len())
+                .sum::<usize>();
+        if size > self.max_bytes {
+            return;
+        }
+
+        let mut state = self.state.lock();
+        state.remove(&key);
+        while state.bytes + size > self.max_bytes {
+            if !state.evict_oldest() {
+                break;
+            }
+        }
+
+        state.generation += 1;
+        let generation = state.generation;
+        state.bytes += size;
+        state.order.push_back((generation, key.clone()));
+        state.entries.insert(
+            key,
+            Entry {
+                response,
+                expires_at: Instant::now() + ttl,
+                generation,
+                size,
+            },
+        );
+
+        // Drop stale keys once they make up most of the queue so that it
+        // doesn't grow without bound when the same requests are refreshed.
+        if state.order.len() > 2 * state.entries.len() + 16 {
+            let CacheState { entries, order, .. } = &mut *state;
+            order.retain(|(generation, key)| {
+                entries.get(key).map(|entry| entry.generation) == Some(*generation)
+            });
+        }
+    }
+}
+
+/// Iterate over the directives in all `Cache-Control` headers.
+fn directives(headers: &HeaderMap) -> impl Iterator<Item = (String, Option<&str>)> {
+    headers
+        .get_all(CACHE_CONTROL)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .map(|directive| {
+            let (name, value) = match directive.split_once('=') {
+                Some((name, value)) => (name, Some(value.trim().trim_matches('"'))),
+                None => (directive, None),
+            };
+
+            (name.trim().to_ascii_lowercase(), value)
+        })
+}
+
+fn has_directive(headers: &HeaderMap, name: &str) -> bool {
+    directives(headers).any(|(directive, _)| directive == name)
+}
+
+fn max_age(headers: &HeaderMap) -> Option<u64> {
+    directives(headers)
+        .find(|(directive, _)| directive == "max-age")
+        .and_then(|(_, value)| value?.parse().ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use http::HeaderValue;
+
+    use super::*;
+
+    fn request(method: Method, cache_control: Option<&'static str>) -> reqwest::Request {
+        let mut request = reqwest::Request::new(method, "http://example.com/a".parse().unwrap());
+        if let Some(value) = cache_control {
+            request
+                .headers_mut()
+                .insert(CACHE_CONTROL, HeaderValue::from_static(value));
+        }
+        request
+    }
+
+    fn response(cache_control: Option<&'static str>, body: &[u8]) -> Arc<CachedResponse> {
+        let mut headers = HeaderMap::new();
+        if let Some(value) = cache_control {
+            headers.insert(CACHE_CONTROL, HeaderValue::from_static(value));
+        }
+
+        Arc::new(CachedResponse {
+            status: 200,
+            headers,
+            body: body.to_vec(),
+            redirects: Vec::new(),
+        })
+    }
+
+    fn cache(ttl: Option<Duration>, max_bytes: usize) -> HttpCache {
+        let mut config = HttpCacheConfig::new().max_bytes(max_bytes);
+        config.ttl = ttl;
+        HttpCache::new(&config)
+    }
+
+    #[test]
+    fn parses_directives() {
+        let mut headers = HeaderMap::new();
+        headers.append(
+            CACHE_CONTROL,
+            HeaderValue::from_static("public, Max-Age=60"),
+        );
+        headers.append(CACHE_CONTROL, HeaderValue::from_static("no-transform"));
+
+        assert_eq!(max_age(&headers), Some(60));
+        assert!(has_directive(&headers, "no-transform"));
+        assert!(!has_directive(&headers, "no-store"));
+    }
+
+    #[test]
+    fn only_idempotent_requests_are_cached() {
+        let cache = cache(Some(Duration::from_secs(60)), 1024);
+
+        assert!(cache.key(&request(Method::GET, None), None).is_some());
+        assert!(cache.key(&request(Method::HEAD, None), None).is_some());
+        assert!(cache.key(&request(Method::POST, None), None).is_none());
+        assert!(cache
+            .key(&request(Method::GET, Some("no-store")), None)
+            .is_none());
+    }
+
+    #[test]
+    fn respects_cache_control() {
+        let cache = cache(None, 1024);
+        let req = request(Method::GET, None);
+
+        let key = cache.key(&req, None).unwrap();
+        cache.insert(key.clone(), response(None, b"no ttl"));
+        assert!(cache.get(&key, &req).is_none());
+
+        cache.insert(key.clone(), response(Some("no-store, max-age=60"), b"no"));
+        assert!(cache.get(&key, &req).is_none());
+
+        cache.insert(key.clone(), response(Some("max-age=60"), b"yes"));
+        assert_eq!(cache.get(&key, &req).unwrap().body, b"yes");
+
+        let req = request(Method::GET, Some("no-cache"));
+        assert!(cache.get(&key, &req).is_none());
+    }
+
+    #[test]
+    fn keys_are_per_tenant() {
+        let cache = cache(Some(Duration::from_secs(60)), 1024);
+        let req = request(Method::GET, None);
+
+        let a = cache.key(&req, Some("a")).unwrap();
+        let b = cache.key(&req, Some("b")).unwrap();
+        cache.insert(a.clone(), response(None, b"a"));
+
+        assert!(cache.get(&a, &req).is_some());
+        assert!(cache.get(&b, &req).is_none());
+    }
+
+    #[test]
+    fn evicts_oldest_entries() {
+        let cache = cache(Some(Duration::from_secs(60)), 8);
+        let first = request(Method::GET, None);
+        let second = request(Method::HEAD, None);
+
+        let a = cache.key(&first, None).unwrap();
+        let b = cache.key(&second, None).unwrap();
+        cache.insert(a.clone(), response(None, b"12345"));
+        cache.insert(b.clone(), response(None, b"67890"));
+
+        assert!(cache.get(&a, &first).is_none());
+        assert!(cache.get(&b, &second).is_some());
+    }
+}
@@ -0,0 +1,119 @@
+use std::collections::HashMap;
+
+use anyhow::Context;
+use async_trait::async_trait;
+use futures_util::FutureExt;
+use rdkafka::consumer::{CommitMode, Consumer, StreamConsumer};
+use rdkafka::message::{BorrowedMessage, Headers, Message as _};
+use rdkafka::{ClientConfig, Offset, TopicPartitionList};
+
+use super::{Message, TaskSource};
+use crate::config::KafkaSourceConfig;
+
+pub(super) struct KafkaSource {
+    consumer: StreamConsumer,
+    max_batch_size: usize,
+
+    /// The offsets to commit for each partition that messages have been
+    /// received from since the last commit.
+    pending: HashMap<(String, i32), Offset>,
+}
+
+impl KafkaSource {
+    pub(super) fn connect(config: &KafkaSourceConfig) -> anyhow::Result<Self> {
+        let mut client = ClientConfig::new();
+        client
+            .set("bootstrap.servers", &config.brokers)
+            .set("group.id", &config.group_id);
+        for (key, value) in &config.options {
+            client.set(key, value);
+        }
+
+        // Offsets must only be committed once the tasks for the messages before them
+        // have been launched.
+        client.set("enable.auto.commit", "false");
+
+        let consumer: StreamConsumer = client
+            .create()
+            .context("failed to create a kafka consumer")?;
+        let topics: Vec<_> = config.topics.iter().map(String::as_str).collect();
+        consumer
+            .subscribe(&topics)
+            .context("failed to subscribe to kafka topics")?;
+
+        Ok(Self {
+            consumer,
+            max_batch_size: config.max_batch_size.max(1),
+            pending: HashMap::new(),
+        })
+    }
+}
+
+fn convert(message: &BorrowedMessage<'_>) -> Message {
+    let key = format!(
+        "{}/{}/{}",
+        message.topic(),
+        message.partition(),
+        message.offset()
+    );
+    let mut converted = Message::new(key, message.payload().unwrap_or_default());
+
+    if let Some(headers) = message.headers() {
+        for header in headers.iter() {
+            converted = converted.header(header.key, header.value.unwrap_or_default());
+        }
+    }
+
+    converted
+}
+
+#[async_trait]
+impl TaskSource for KafkaSource {
+    async fn receive(&mut self) -> anyhow::Result<Vec<Message>> {
+        let mut messages = Vec::new();
+
+        // Wait for the first message and then take whatever else is already available
+        // without blocking.
+        let mut next = Some(self.consumer.recv().await);
+        while let Some(result) = next {
+            let message = match result {
+                Ok(message) => message,
+                Err(e) if messages.is_empty() => return Err(e.into()),
+                // Hand off what we have so far. The error will show up again on the
+                // next call if it wasn't transient.
+                Err(_) => break,
+            };
+
+            self.pending.insert(
+                (message.topic().to_owned(), message.partition()),
+                Offset::Offset(message.offset() + 1),
+            );
+            messages.push(convert(&message));
+
+            if messages.len() >= self.max_batch_size {
+                break;
+            }
+
+            next = self.consumer.recv().now_or_never();
+        }
+
+        Ok(messages)
+    }
+
+    async fn commit(&mut self) -> anyhow::Result<()> {
+        if self.pending.is_empty() {
+            return Ok(());
+        }
+
+        let offsets = TopicPartitionList::from_topic_map(&self.pending)?;
+
+        // Async commits avoid blocking the executor. If one ends up failing then the
+        // messages get redelivered and are skipped as duplicates.
+        self.consumer
+            .commit(&offsets, CommitMode::Async)
+            .context("failed to commit kafka offsets")?;
+        self.pending.clear();
+
+        Ok(())
+    }
+}
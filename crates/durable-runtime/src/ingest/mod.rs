@@ -0,0 +1,499 @@
+//! Launching tasks from messages received on external message queues.
+//!
+//! A worker can be configured with any number of sources, either via
+//! [`Config::sources`] or by registering a custom [`TaskSource`] with
+//! [`WorkerBuilder::source`]. Each worker consumes from all of its sources
+//! while it runs tasks and turns every message that it receives into a new
+//! task, as described by the source's [`TaskMapping`].
+//!
+//! # Delivery semantics
+//! Tasks are launched at least once for each message:
+//! 1. A batch of messages is received from the source.
+//! 2. Tasks for the whole batch are created within a single database
+//!    transaction.
+//! 3. Once that transaction has committed, the batch is acknowledged to the
+//!    source. For Kafka this commits the consumer offsets and for SQS this
+//!    deletes the messages from the queue.
+//!
+//! If the worker crashes in between the last two steps then the messages will
+//! be delivered again. To avoid launching a second task in that case, each
+//! message has a dedupe key that is recorded in the same transaction that
+//! launches its task. Messages whose key has already been recorded for the
+//! source are acknowledged without launching anything. Dedupe keys are kept
+//! for [`Config::ingest_dedupe_window`].
+//!
+//! Messages that cannot be turned into a task (e.g. because their payload is
+//! not valid JSON) are logged and then acknowledged along with the rest of the
+//! batch so that they do not block the queue.
+//!
+//! [`Config::sources`]: crate::Config::sources
+//! [`Config::ingest_dedupe_window`]: crate::Config::ingest_dedupe_window
+//! [`WorkerBuilder::source`]: crate::WorkerBuilder::source
+
+use std::collections::{BTreeMap, HashSet};
+use std::pin::Pin;
+use std::time::Duration;
+
+use anyhow::Context;
+use async_trait::async_trait;
+use base64::Engine;
+use chrono::Utc;
+use metrics::Counter;
+use serde_json::value::RawValue;
+use sqlx::types::Json;
+
+use crate::config::{DataMapping, SourceConfig, SourceQueue, TaskMapping};
+use crate::flag::ShutdownFuture;
+use crate::worker::SharedState;
+
+#[cfg(feature = "kafka")]
+mod kafka;
+mod sqs;
+
+const MIN_BACKOFF: Duration = Duration::from_secs(1);
+const MAX_BACKOFF: Duration = Duration::from_secs(60);
+
+/// A message received from a [`TaskSource`].
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct Message {
+    /// A key that uniquely identifies this message within its source.
+    ///
+    /// Redeliveries of the same message must have the same key, otherwise
+    /// they will launch a second task.
+    pub key: String,
+
+    /// The body of the message.
+    pub payload: Vec<u8>,
+
+    /// The headers (or attributes) attached to the message.
+    pub headers: Vec<(String, Vec<u8>)>,
+}
+
+impl Message {
+    pub fn new(key: impl Into<String>, payload: impl Into<Vec<u8>>) -> Self {
+        Self {
+            key: key.into(),
+            payload: payload.into(),
+            headers: Vec::new(),
+        }
+    }
+
+    /// Add a header to the message.
+    pub fn header(mut self, name: impl Into<String>, value: impl Into<Vec<u8>>) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    pub(crate) fn dedupe_key(&self, mapping: &TaskMapping) -> String {
+        let header = mapping.dedupe_header.as_deref().and_then(|name| {
+            self.headers
+                .iter()
+                .find(|(header, _)| header == name)
+                .map(|(_, value)| value)
+        });
+
+        match header {
+            Some(value) => String::from_utf8_lossy(value).into_owned(),
+            None => self.key.clone(),
+        }
+    }
+}
+
+/// A queue of messages that tasks are launched from.
+///
+/// Sources only need to provide at-least-once delivery. The worker takes care
+/// of deduplicating messages that are delivered more than once.
+#[async_trait]
+pub trait TaskSource: Send {
+    /// Wait for the next batch of messages.
+    ///
+    /// Returning an empty batch is allowed and results in `receive` being
+    /// called again. Errors are logged and `receive` is retried after a delay.
+    async fn receive(&mut self) -> anyhow::Result<Vec<Message>>;
+
+    /// Acknowledge all messages returned by previous calls to
+    /// [`receive`](TaskSource::receive).
+    ///
+    /// This is only called once the tasks for those messages have been
+    /// launched. Messages that are never acknowledged should eventually be
+    /// delivered again.
+    async fn commit(&mut self) -> anyhow::Result<()>;
+}
+
+/// A [`TaskSource`] along with the details needed to launch its tasks.
+pub(crate) struct Source {
+    source: Box<dyn TaskSource>,
+    launcher: Launcher,
+}
+
+/// Launches the tasks for messages received by a [`Source`].
+///
+/// This is kept separate from the [`TaskSource`] itself since sources are not
+/// required to be `Sync`.
+pub(crate) struct Launcher {
+    name: String,
+    mapping: TaskMapping,
+
+    launched: Counter,
+    duplicates: Counter,
+}
+
+impl Source {
+    pub(crate) fn new(name: String, mapping: TaskMapping, source: Box<dyn TaskSource>) -> Self {
+        Self {
+            source,
+            launcher: Launcher::new(name, mapping),
+        }
+    }
+
+    pub(crate) fn connect(config: &SourceConfig, client: &reqwest::Client) -> anyhow::Result<Self> {
+        let source: Box<dyn TaskSource> = match &config.queue {
+            #[cfg(feature = "kafka")]
+            SourceQueue::Kafka(kafka) => Box::new(kafka::KafkaSource::connect(kafka)?),
+            #[cfg(not(feature = "kafka"))]
+            SourceQueue::Kafka(_) => {
+                anyhow::bail!("the worker was built without the `kafka` feature")
+            }
+            SourceQueue::Sqs(sqs) => Box::new(sqs::SqsSource::new(sqs.clone(), client.clone())?),
+        };
+
+        Ok(Self::new(
+            config.name.clone(),
+            config.mapping.clone(),
+            source,
+        ))
+    }
+
+    pub(crate) fn name(&self) -> &str {
+        &self.launcher.name
+    }
+
+    /// Launch tasks from this source until the worker is shut down.
+    pub(crate) async fn run(&mut self, shared: &SharedState) {
+        let mut shutdown = std::pin::pin!(shared.shutdown.wait());
+        let mut delay = MIN_BACKOFF;
+
+        'outer: loop {
+            let result = tokio::select! {
+                biased;
+
+                _ = shutdown.as_mut() => break 'outer,
+                result = self.source.receive() => result,
+            };
+
+            let messages = match result {
+                Ok(messages) => messages,
+                Err(e) => {
+                    tracing::warn!(
+                        "failed to receive messages from source `{}`: {e:#}",
+                        self.launcher.name
+                    );
+
+                    if !backoff(shutdown.as_mut(), &mut delay).await {
+                        break 'outer;
+                    }
+                    continue;
+                }
+            };
+
+            if messages.is_empty() {
+                continue;
+            }
+
+            // The messages are only acknowledged once their tasks have been launched, so
+            // we keep retrying the same batch until that succeeds.
+            while let Err(e) = self.launcher.launch(shared, &messages).await {
+                tracing::error!(
+                    "failed to launch tasks for {} messages from source `{}`: {e:#}",
+                    messages.len(),
+                    self.launcher.name
+                );
+
+                if !backoff(shutdown.as_mut(), &mut delay).await {
+                    break 'outer;
+                }
+            }
+
+            delay = MIN_BACKOFF;
+
+            // A failed commit means the messages will be delivered again. Their dedupe keys
+            // have already been recorded so this won't launch any extra tasks.
+            if let Err(e) = self.source.commit().await {
+                tracing::warn!(
+                    "failed to commit messages to source `{}`: {e:#}",
+                    self.launcher.name
+                );
+            }
+        }
+    }
+}
+
+impl Launcher {
+    pub(crate) fn new(name: String, mapping: TaskMapping) -> Self {
+        let launched = metrics::counter!("durable.ingest_launched", "source" => name.clone());
+        let duplicates = metrics::counter!("durable.ingest_duplicates", "source" => name.clone());
+
+        Self {
+            name,
+            mapping,
+            launched,
+            duplicates,
+        }
+    }
+
+    pub(crate) fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub(crate) fn mapping(&self) -> &TaskMapping {
+        &self.mapping
+    }
+
+    /// Launch a task for each message in `messages` that has not been seen
+    /// before.
+    async fn launch(&self, shared: &SharedState, messages: &[Message]) -> anyhow::Result<()> {
+        let mut keys = Vec::with_capacity(messages.len());
+        let mut data = Vec::with_capacity(messages.len());
+        for message in messages {
+            let key = message.dedupe_key(&self.mapping);
+
+            match task_data(&self.name, self.mapping.data, message, &key) {
+                Ok(value) => {
+                    keys.push(key);
+                    data.push(Json(value));
+                }
+                Err(e) => tracing::warn!(
+                    "skipping message `{key}` from source `{}` which could not be mapped to a \
+                     task: {e}",
+                    self.name
+                ),
+            }
+        }
+
+        self.launch_tasks(shared, keys, data).await
+    }
+
+    /// Launch a task with the provided data for each dedupe key that has not
+    /// been seen before.
+    pub(crate) async fn launch_tasks(
+        &self,
+        shared: &SharedState,
+        keys: Vec<String>,
+        data: Vec<Json<Box<RawValue>>>,
+    ) -> anyhow::Result<()> {
+        let mut tx = shared.pool.begin().await?;
+
+        let program = sqlx::query!(
+            "
+            SELECT id, last_used
+             FROM durable.wasm
+            WHERE name = $1
+              AND tenant IS NOT DISTINCT FROM $2
+            ORDER BY last_used DESC, id DESC
+            LIMIT 1
+            ",
+            &self.mapping.program,
+            self.mapping.tenant.as_deref()
+        )
+        .fetch_optional(shared.schema.on(&mut *tx))
+        .await?
+        .with_context(|| format!("there is no program named `{}`", self.mapping.program))?;
+
+        // Keep the program from being cleaned up while it is in use here. As with the
+        // client, this only needs to happen occasionally.
+        if program.last_used < Utc::now() - chrono::Duration::hours(1) {
+            sqlx::query!(
+                "UPDATE durable.wasm
+                  SET last_used = CURRENT_TIMESTAMP
+                WHERE id = $1",
+                program.id
+            )
+            .execute(shared.schema.on(&mut *tx))
+            .await?;
+        }
+
+        let mut fresh: HashSet<String> = sqlx::query_scalar!(
+            r#"
+            INSERT INTO durable.ingest_dedupe(source, key)
+            SELECT $1::text, key
+             FROM UNNEST($2::text[]) as t(key)
+            ON CONFLICT DO NOTHING
+            RETURNING key as "key!"
+            "#,
+            &self.name,
+            &keys
+        )
+        .fetch_all(shared.schema.on(&mut *tx))
+        .await?
+        .into_iter()
+        .collect();
+
+        // Removing keys as we go means that duplicates within the same batch only launch
+        // a single task.
+        let valid = keys.len();
+        let (keys, data): (Vec<_>, Vec<_>) = keys
+            .into_iter()
+            .zip(data)
+            .filter(|(key, _)| fresh.remove(key))
+            .unzip();
+
+        let duplicates = valid - keys.len();
+        if duplicates > 0 {
+            tracing::debug!(
+                "skipped {duplicates} duplicate messages from source `{}`",
+                self.name
+            );
+            self.duplicates.increment(duplicates as u64);
+        }
+
+        if keys.is_empty() {
+            tx.commit().await?;
+            return Ok(());
+        }
+
+        let name = self.mapping.task_name.as_deref().unwrap_or(&self.name);
+        let tasks = sqlx::query_scalar!(
+            r#"
+            INSERT INTO durable.task(name, wasm, data, entrypoint, tenant, instance, running_on)
+            SELECT
+                $1::text as name,
+                $2::bigint as wasm,
+                data,
+                $3::text as entrypoint,
+                $6::text as tenant,
+                $7::text as instance,
+                (
+                    SELECT id
+                     FROM durable.worker
+                    WHERE worker.instance IS NOT DISTINCT FROM $7::text
+                    ORDER BY random(), key
+                    LIMIT 1
+                    FOR SHARE SKIP LOCKED
+                ) as running_on
+            FROM UNNEST($4::text[], $5::jsonb[]) as t(key, data)
+            RETURNING id
+            "#,
+            name,
+            program.id,
+            self.mapping.entrypoint.as_deref(),
+            &keys,
+            &data as &[Json<Box<RawValue>>],
+            self.mapping.tenant.as_deref(),
+            shared.config.instance.as_deref()
+        )
+        .fetch_all(shared.schema.on(&mut *tx))
+        .await?;
+
+        sqlx::query!(
+            "
+            UPDATE durable.ingest_dedupe
+              SET task_id = t.task_id
+             FROM UNNEST($2::text[], $3::bigint[]) as t(key, task_id)
+            WHERE ingest_dedupe.source = $1
+              AND ingest_dedupe.key = t.key
+            ",
+            &self.name,
+            &keys,
+            &tasks
+        )
+        .execute(shared.schema.on(&mut *tx))
+        .await?;
+
+        tx.commit().await?;
+
+        tracing::debug!("launched {} tasks from source `{}`", tasks.len(), self.name);
+        self.launched.increment(tasks.len() as u64);
+
+        Ok(())
+    }
+}
+
+/// Build the data for the task launched by `message`.
+pub(crate) fn task_data(
+    source: &str,
+    mapping: DataMapping,
+    message: &Message,
+    key: &str,
+) -> serde_json::Result<Box<RawValue>> {
+    match mapping {
+        DataMapping::Payload => serde_json::from_slice(&message.payload),
+        DataMapping::Envelope => {
+            let headers: BTreeMap<_, _> = message
+                .headers
+                .iter()
+                .map(|(name, value)| (name.as_str(), String::from_utf8_lossy(value)))
+                .collect();
+
+            serde_json::value::to_raw_value(&serde_json::json!({
+                "source": source,
+                "key": key,
+                "headers": headers,
+                "payload": base64::engine::general_purpose::STANDARD.encode(&message.payload),
+            }))
+        }
+    }
+}
+
+/// Wait for `delay` before retrying, doubling it for next time.
+///
+/// Returns false if the worker was shut down in the meantime.
+async fn backoff(shutdown: Pin<&mut ShutdownFuture<'_>>, delay: &mut Duration) -> bool {
+    let sleep = tokio::time::sleep(*delay);
+    *delay = (*delay * 2).min(MAX_BACKOFF);
+
+    tokio::select! {
+        biased;
+
+        _ = shutdown => false,
+        _ = sleep => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dedupe_key_prefers_header() {
+        let mapping = TaskMapping::new("program").dedupe_header("idempotency-key");
+        let message = Message::new("orders/0/17", "{}");
+        assert_eq!(message.dedupe_key(&mapping), "orders/0/17");
+
+        let message = message.header("idempotency-key", "abc");
+        assert_eq!(message.dedupe_key(&mapping), "abc");
+        assert_eq!(
+            message.dedupe_key(&TaskMapping::new("program")),
+            "orders/0/17"
+        );
+    }
+
+    #[test]
+    fn map_payload() {
+        let mapping = DataMapping::Payload;
+        let message = Message::new("1", r#"{"order": 5}"#);
+        let data = task_data("orders", mapping, &message, "1").unwrap();
+        assert_eq!(data.get(), r#"{"order": 5}"#);
+
+        let message = Message::new("2", "not json");
+        assert!(task_data("orders", mapping, &message, "2").is_err());
+    }
+
+    #[test]
+    fn map_envelope() {
+        let mapping = DataMapping::Envelope;
+        let message = Message::new("1", "not json").header("kind", "order");
+        let data = task_data("orders", mapping, &message, "1").unwrap();
+        let data: serde_json::Value = serde_json::from_str(data.get()).unwrap();
+
+        assert_eq!(
+            data,
+            serde_json::json!({
+                "source": "orders",
+                "key": "1",
+                "headers": { "kind": "order" },
+                "payload": "bm90IGpzb24=",
+            })
+        );
+    }
+}
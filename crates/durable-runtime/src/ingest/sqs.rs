@@ -0,0 +1,252 @@
+//! Receiving messages from Amazon SQS via its JSON API.
+//!
+//! See <https://docs.aws.amazon.com/AWSSimpleQueueService/latest/APIReference/API_ReceiveMessage.html>.
+
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use anyhow::Context;
+use async_trait::async_trait;
+use base64::Engine;
+use chrono::Utc;
+use serde::de::DeserializeOwned;
+use url::Url;
+
+use super::{Message, TaskSource};
+use crate::config::SqsSourceConfig;
+use crate::util::sigv4::{self, Signer};
+
+/// The maximum number of entries SQS accepts in a single batch request.
+const MAX_BATCH_ENTRIES: usize = 10;
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct ReceiveMessageResponse {
+    #[serde(default)]
+    messages: Vec<SqsMessage>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct SqsMessage {
+    message_id: String,
+    receipt_handle: String,
+    #[serde(default)]
+    body: String,
+    #[serde(default)]
+    message_attributes: BTreeMap<String, MessageAttribute>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct MessageAttribute {
+    string_value: Option<String>,
+    binary_value: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct DeleteMessageBatchResponse {
+    #[serde(default)]
+    failed: Vec<BatchResultError>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct BatchResultError {
+    code: String,
+    #[serde(default)]
+    message: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct ErrorResponse {
+    #[serde(alias = "Message")]
+    message: String,
+}
+
+pub(super) struct SqsSource {
+    config: SqsSourceConfig,
+    client: reqwest::Client,
+    endpoint: Url,
+
+    /// Receipt handles of the messages that have been received since the last
+    /// commit.
+    pending: Vec<String>,
+}
+
+impl SqsSource {
+    pub(super) fn new(config: SqsSourceConfig, client: reqwest::Client) -> anyhow::Result<Self> {
+        // Requests are sent to the root of the host that the queue lives on.
+        let mut endpoint = Url::parse(&config.queue_url).context("the SQS queue URL is invalid")?;
+        endpoint.set_path("/");
+        endpoint.set_query(None);
+
+        Ok(Self {
+            config,
+            client,
+            endpoint,
+            pending: Vec::new(),
+        })
+    }
+
+    async fn request<T: DeserializeOwned>(
+        &self,
+        action: &str,
+        request: serde_json::Value,
+        timeout: Duration,
+    ) -> anyhow::Result<T> {
+        let body = serde_json::to_vec(&request).expect("a JSON value can always be serialized");
+
+        let now = Utc::now();
+        let amz_date = now.format("%Y%m%dT%H%M%SZ").to_string();
+        let target = format!("AmazonSQS.{action}");
+        let payload_hash = sigv4::sha256_hex(&body);
+        let host = sigv4::host(&self.endpoint);
+
+        let mut headers = vec![
+            ("content-type", "application/x-amz-json-1.0"),
+            ("host", host.as_str()),
+            ("x-amz-content-sha256", payload_hash.as_str()),
+            ("x-amz-date", amz_date.as_str()),
+            ("x-amz-target", target.as_str()),
+        ];
+        if let Some(token) = &self.config.session_token {
+            headers.push(("x-amz-security-token", token));
+        }
+
+        let signer = Signer {
+            access_key_id: &self.config.access_key_id,
+            secret_access_key: &self.config.secret_access_key,
+            session_token: self.config.session_token.as_deref(),
+            region: &self.config.region,
+            service: "sqs",
+        };
+        let authorization =
+            signer.authorization("POST", &self.endpoint, &headers, &payload_hash, now);
+
+        let mut request = self
+            .client
+            .post(self.endpoint.clone())
+            .timeout(timeout)
+            .header(reqwest::header::AUTHORIZATION, authorization);
+        for (name, value) in headers {
+            // reqwest sets the host header itself, based on the URL.
+            if name != "host" {
+                request = request.header(name, value);
+            }
+        }
+
+        let response = request.body(body).send().await?;
+        let status = response.status();
+        let body = response.bytes().await?;
+
+        if !status.is_success() {
+            let message = match serde_json::from_slice::<ErrorResponse>(&body) {
+                Ok(error) => error.message,
+                Err(_) => String::from_utf8_lossy(&body).into_owned(),
+            };
+
+            anyhow::bail!("SQS {action} returned {status}: {message}");
+        }
+
+        serde_json::from_slice(&body)
+            .with_context(|| format!("SQS {action} returned an invalid response"))
+    }
+}
+
+fn convert(message: SqsMessage) -> anyhow::Result<Message> {
+    let mut converted = Message::new(message.message_id, message.body);
+
+    for (name, attribute) in message.message_attributes {
+        let value = match (attribute.string_value, attribute.binary_value) {
+            (Some(value), _) => value.into_bytes(),
+            (None, Some(value)) => base64::engine::general_purpose::STANDARD
+                .decode(value)
+                .with_context(|| format!("message attribute `{name}` is not valid base64"))?,
+            (None, None) => Vec::new(),
+        };
+
+        converted = converted.header(name, value);
+    }
+
+    Ok(converted)
+}
+
+#[async_trait]
+impl TaskSource for SqsSource {
+    async fn receive(&mut self) -> anyhow::Result<Vec<Message>> {
+        let wait_time = self.config.wait_time.min(Duration::from_secs(20));
+        let mut request = serde_json::json!({
+            "QueueUrl": self.config.queue_url,
+            "MaxNumberOfMessages": self.config.max_messages.clamp(1, 10),
+            "WaitTimeSeconds": wait_time.as_secs(),
+            "MessageAttributeNames": ["All"],
+        });
+        if let Some(timeout) = self.config.visibility_timeout {
+            request["VisibilityTimeout"] = timeout.as_secs().into();
+        }
+
+        let response: ReceiveMessageResponse = self
+            .request(
+                "ReceiveMessage",
+                request,
+                wait_time + Duration::from_secs(10),
+            )
+            .await?;
+
+        let mut messages = Vec::with_capacity(response.messages.len());
+        for message in response.messages {
+            self.pending.push(message.receipt_handle.clone());
+
+            match convert(message) {
+                Ok(message) => messages.push(message),
+                // The receipt handle is still pending so the message gets deleted along with
+                // the rest of the batch.
+                Err(e) => tracing::warn!("skipping invalid SQS message: {e:#}"),
+            }
+        }
+
+        Ok(messages)
+    }
+
+    async fn commit(&mut self) -> anyhow::Result<()> {
+        let pending = std::mem::take(&mut self.pending);
+
+        for chunk in pending.chunks(MAX_BATCH_ENTRIES) {
+            let entries: Vec<_> = chunk
+                .iter()
+                .enumerate()
+                .map(|(id, handle)| {
+                    serde_json::json!({
+                        "Id": id.to_string(),
+                        "ReceiptHandle": handle,
+                    })
+                })
+                .collect();
+
+            let response: DeleteMessageBatchResponse = self
+                .request(
+                    "DeleteMessageBatch",
+                    serde_json::json!({
+                        "QueueUrl": self.config.queue_url,
+                        "Entries": entries,
+                    }),
+                    Duration::from_secs(30),
+                )
+                .await?;
+
+            // Messages that could not be deleted will be received again once their
+            // visibility timeout expires.
+            for failed in response.failed {
+                tracing::warn!(
+                    "failed to delete SQS message: {}: {}",
+                    failed.code,
+                    failed.message.unwrap_or_default()
+                );
+            }
+        }
+
+        Ok(())
+    }
+}
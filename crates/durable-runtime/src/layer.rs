@@ -0,0 +1,94 @@
+//! Middleware that wraps the execution of every task run by a worker.
+//!
+//! A [`TaskLayer`] registered with [`WorkerBuilder::layer`] gets called before
+//! the worker starts running a task and again once the task has stopped
+//! running on the worker, along with the outcome. This is meant for things
+//! like audit logging, quota accounting, or tagging metrics, which need to
+//! see every task but don't need access to the task's instance the way a
+//! [`Plugin`] does.
+//!
+//! When multiple layers are registered, [`before`](TaskLayer::before) is
+//! called on each of them in the order that they were registered and
+//! [`after`](TaskLayer::after) is called in the reverse order.
+//!
+//! [`WorkerBuilder::layer`]: crate::WorkerBuilder::layer
+//! [`Plugin`]: crate::plugin::Plugin
+
+use std::collections::BTreeMap;
+use std::time::Instant;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+/// Details about a task that is being run by the worker.
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct TaskInfo {
+    /// The id of the task.
+    pub id: i64,
+
+    /// The name that the task was launched with.
+    pub name: String,
+
+    /// The id of the program that the task is running.
+    pub wasm: i64,
+
+    /// The tenant that the task belongs to, if any.
+    pub tenant: Option<String>,
+
+    /// The labels that the task was launched with.
+    pub labels: BTreeMap<String, String>,
+
+    /// The number of times that a worker has started running this task,
+    /// including this one.
+    pub attempt: i32,
+
+    /// When the task was launched.
+    pub created_at: DateTime<Utc>,
+
+    /// When this worker started running the task.
+    pub started: Instant,
+}
+
+/// How a task stopped running on the worker.
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub enum TaskOutcome {
+    /// The task completed successfully.
+    Completed,
+
+    /// The task failed.
+    Failed {
+        /// The failure message that was saved for the task.
+        message: String,
+    },
+
+    /// The task was suspended and will be resumed later, possibly on another
+    /// worker.
+    Suspended,
+
+    /// The task was taken over by another worker while it was running.
+    Taken,
+}
+
+/// Hooks that run around the execution of every task.
+///
+/// See the [module docs](self) for more details.
+#[async_trait]
+pub trait TaskLayer: Send + Sync {
+    /// Called before the worker starts running `task`.
+    ///
+    /// Returning an error fails the task with that error, the same as if the
+    /// task itself had returned it.
+    async fn before(&self, task: &TaskInfo) -> anyhow::Result<()> {
+        let _ = task;
+        Ok(())
+    }
+
+    /// Called once `task` has stopped running on this worker.
+    ///
+    /// This is called after the outcome has been saved to the database.
+    async fn after(&self, task: &TaskInfo, outcome: &TaskOutcome) {
+        let _ = (task, outcome);
+    }
+}
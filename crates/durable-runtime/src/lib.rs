@@ -3,15 +3,42 @@
 #[macro_use]
 extern crate serde;
 
+pub mod api;
+pub mod archive;
+mod audit;
+#[cfg(feature = "chaos")]
+pub mod chaos;
+#[cfg(not(feature = "chaos"))]
+#[allow(dead_code)]
+mod chaos;
 mod config;
 mod error;
 pub mod event;
 mod flag;
+mod group_commit;
+mod http_cache;
+pub mod ingest;
+pub mod layer;
+pub mod lifecycle;
+mod memory;
 pub mod migrate;
 pub mod plugin;
+pub mod policy;
+pub mod replay;
 mod resource;
+mod retention;
+mod scratch;
+#[cfg(feature = "simulation")]
+pub mod sim;
+#[cfg(not(feature = "simulation"))]
+#[allow(dead_code)]
+mod sim;
+mod sse;
+mod statement_cache;
+mod stats;
 pub mod task;
 pub mod util;
+mod webhook;
 mod worker;
 
 #[allow(
@@ -25,8 +52,15 @@ mod bindings {
     include!(concat!(env!("OUT_DIR"), "/bindings.rs"));
 }
 
-pub use self::config::Config;
+pub use self::config::{
+    ApiConfig, ArchiveConfig, Config, DataMapping, DirPerms, EmailConfig, EmailTransport,
+    HttpCacheConfig, InstancePoolConfig, KafkaConfig, KafkaSourceConfig, LlmProvider, MqConfig,
+    NatsConfig, NotifyMapping, ObjectStoreArchiveConfig, ObjectStoreConfig, Preopen, RateLimit,
+    ScratchDir, SesConfig, SmtpConfig, SmtpTls, SourceConfig, SourceQueue, SqsSourceConfig,
+    TaskMapping, WebhookAction, WebhookConfig, WebhookRoute, WebhookSignature,
+};
 pub use self::error::TaskStatus;
 pub use self::resource::{Resourceable, Resources};
+pub use self::stats::{QueueStats, WorkerStats};
 pub use self::task::Task;
 pub use self::worker::{Worker, WorkerBuilder, WorkerHandle};
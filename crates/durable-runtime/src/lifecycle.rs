@@ -0,0 +1,95 @@
+//! Events describing what is happening on a worker.
+//!
+//! Applications embedding the runtime can subscribe to these with
+//! [`WorkerHandle::subscribe`](crate::WorkerHandle::subscribe) in order to
+//! react to tasks as they run without having to poll the database.
+
+use std::time::Duration;
+
+/// The number of events that can be buffered for each subscriber before it
+/// starts to miss events.
+pub(crate) const CHANNEL_CAPACITY: usize = 1024;
+
+/// An event emitted by a worker.
+///
+/// Events are only emitted for tasks that run on the worker that the
+/// subscription was made on.
+#[non_exhaustive]
+#[derive(Clone, Debug)]
+pub enum WorkerEvent {
+    /// The worker started running a task.
+    ///
+    /// This is emitted every time that the task is started, including when it
+    /// is resumed after being suspended.
+    TaskStarted(TaskStarted),
+
+    /// A task completed successfully.
+    TaskCompleted(TaskCompleted),
+
+    /// A task failed.
+    TaskFailed(TaskFailed),
+
+    /// A task was suspended, either because it is waiting for something or
+    /// because it is being moved to another worker.
+    TaskSuspended(TaskSuspended),
+
+    /// The worker observed a change in the cluster leader.
+    LeaderChanged(LeaderChanged),
+}
+
+#[non_exhaustive]
+#[derive(Clone, Debug)]
+pub struct TaskStarted {
+    pub id: i64,
+    pub name: String,
+
+    /// The number of times that a worker has started running this task,
+    /// including this one.
+    pub attempt: i32,
+
+    /// The tenant that the task belongs to, if any.
+    pub tenant: Option<String>,
+}
+
+#[non_exhaustive]
+#[derive(Clone, Debug)]
+pub struct TaskCompleted {
+    pub id: i64,
+    pub name: String,
+
+    /// How long the task ran on this worker for since it was last started.
+    pub elapsed: Duration,
+}
+
+#[non_exhaustive]
+#[derive(Clone, Debug)]
+pub struct TaskFailed {
+    pub id: i64,
+    pub name: String,
+
+    /// How long the task ran on this worker for since it was last started.
+    pub elapsed: Duration,
+
+    /// The failure message that was saved for the task.
+    pub message: String,
+}
+
+#[non_exhaustive]
+#[derive(Clone, Debug)]
+pub struct TaskSuspended {
+    pub id: i64,
+    pub name: String,
+
+    /// How long the task ran on this worker for since it was last started.
+    pub elapsed: Duration,
+}
+
+#[non_exhaustive]
+#[derive(Clone, Debug)]
+pub struct LeaderChanged {
+    /// The id of the new leader, if there is one.
+    pub leader: Option<i64>,
+
+    /// Whether this worker is the new leader.
+    pub is_self: bool,
+}
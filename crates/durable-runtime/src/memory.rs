@@ -0,0 +1,86 @@
+//! Accounting for the linear memory used by running tasks.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use metrics::Gauge;
+
+/// Tracks the total linear memory of all the tasks running on a worker.
+pub(crate) struct MemoryTracker {
+    used: AtomicUsize,
+    gauge: Gauge,
+}
+
+impl MemoryTracker {
+    pub fn new() -> Self {
+        Self {
+            used: AtomicUsize::new(0),
+            gauge: metrics::gauge!("durable.memory_usage"),
+        }
+    }
+
+    /// The total size, in bytes, of the linear memories of all running tasks.
+    pub fn used(&self) -> usize {
+        self.used.load(Ordering::Relaxed)
+    }
+
+    fn add(&self, bytes: usize) {
+        let used = self.used.fetch_add(bytes, Ordering::Relaxed) + bytes;
+        self.gauge.set(used as f64);
+    }
+
+    fn sub(&self, bytes: usize) {
+        let used = self.used.fetch_sub(bytes, Ordering::Relaxed) - bytes;
+        self.gauge.set(used as f64);
+    }
+}
+
+/// The linear memory used by a single task.
+///
+/// This is installed as the resource limiter of the task's store so that it
+/// sees every memory that the task allocates or grows. The memory is released
+/// from the worker's total once the task is dropped.
+pub(crate) struct TaskMemory {
+    tracker: Arc<MemoryTracker>,
+    used: usize,
+}
+
+impl TaskMemory {
+    pub fn new(tracker: Arc<MemoryTracker>) -> Self {
+        Self { tracker, used: 0 }
+    }
+}
+
+impl wasmtime::ResourceLimiter for TaskMemory {
+    fn memory_growing(
+        &mut self,
+        current: usize,
+        desired: usize,
+        maximum: Option<usize>,
+    ) -> anyhow::Result<bool> {
+        if maximum.is_some_and(|maximum| desired > maximum) {
+            return Ok(false);
+        }
+
+        let grown = desired.saturating_sub(current);
+        self.used += grown;
+        self.tracker.add(grown);
+
+        Ok(true)
+    }
+
+    fn table_growing(
+        &mut self,
+        _current: usize,
+        desired: usize,
+        maximum: Option<usize>,
+    ) -> anyhow::Result<bool> {
+        Ok(maximum.is_none_or(|maximum| desired <= maximum))
+    }
+}
+
+impl Drop for TaskMemory {
+    fn drop(&mut self) {
+        self.tracker.sub(self.used);
+    }
+}
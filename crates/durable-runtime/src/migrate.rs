@@ -1,6 +1,6 @@
 //! Database migrations for the durable runtime.
 
-use durable_migrate::Table;
+use durable_migrate::{SchemaRename, Table};
 
 use self::migrations::MIGRATIONS;
 
@@ -8,13 +8,37 @@ mod migrations {
     include!(concat!(env!("OUT_DIR"), "/migrations.rs"));
 }
 
+mod schema;
+
+pub use self::schema::{SchemaDrift, SchemaObject, SchemaReport, SchemaValidationError};
+
 #[doc(inline)]
 pub use durable_migrate::{
-    DivergingMigrationError, Error, ErrorKind, Options, Target, TransactionMode,
+    ChecksumMode, ChecksumState, DivergingMigrationError, Error, ErrorKind, MigrationLock,
+    MigrationReport, MigrationState, MigrationStatus, Options, Target, TransactionMode,
 };
 
+/// How thoroughly a worker validates the database schema on startup.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum SchemaValidation {
+    /// Do not validate the database at all.
+    Disabled,
+
+    /// Validate the database and log any problems, but start anyway.
+    Warn,
+
+    /// Refuse to start if the database is not at the expected migration
+    /// version or if its schema differs from the expected schema in a way
+    /// that would break the worker.
+    #[default]
+    Enforce,
+}
+
 /// A migrator that comes pre-loaded with migrations relevant to durable.
-pub struct Migrator(durable_migrate::Migrator);
+pub struct Migrator {
+    migrator: durable_migrate::Migrator,
+    schema: SchemaRename,
+}
 
 impl Default for Migrator {
     fn default() -> Self {
@@ -25,7 +49,31 @@ impl Default for Migrator {
 impl Migrator {
     /// Create a migrator with migrations for durable.
     pub const fn new() -> Self {
-        Self(MIGRATIONS)
+        Self {
+            migrator: MIGRATIONS,
+            schema: SchemaRename::identity("durable"),
+        }
+    }
+
+    /// Install durable into `schema` instead of the `durable` schema.
+    ///
+    /// The migrations are rewritten to refer to `schema` before they are
+    /// applied, and the migration history is kept in `schema.migrations`.
+    /// This allows multiple independent durable installations to share a
+    /// single database. Workers using the schema need to have
+    /// [`Config::schema`](crate::Config::schema) set to the same name.
+    pub fn with_schema(mut self, schema: impl Into<String>) -> Self {
+        self.schema = SchemaRename::new("durable", schema.into());
+        self
+    }
+
+    /// The name of the schema that the migrations are applied to.
+    pub fn schema(&self) -> &str {
+        self.schema.to()
+    }
+
+    fn migration_table(&self) -> Table {
+        Table::new(self.schema.to().to_owned(), "migrations")
     }
 
     /// Get a [`Target`] that points to the latest version supported by this
@@ -43,7 +91,7 @@ impl Migrator {
     /// Note that this may be older than the version of the latest migration
     /// applied to the database if the runtime has been downgraded.
     pub fn latest_version(&self) -> u64 {
-        self.0.latest().unwrap()
+        self.migrator.latest().unwrap()
     }
 
     /// Migrate the database.
@@ -59,9 +107,10 @@ impl Migrator {
 
         // Note that changing this means that all previously applied migrations in the
         // database will be forgotten.
-        options.migration_table = Table::new("durable", "migrations");
+        options.migration_table = self.migration_table();
+        options.schema_rename = Some(self.schema.clone()).filter(|rename| !rename.is_identity());
 
-        self.0.run(conn, &options).await
+        self.migrator.run(conn, &options).await
     }
 
     /// Read the latest migration version applied to the database.
@@ -69,7 +118,41 @@ impl Migrator {
         &self,
         conn: &mut sqlx::PgConnection,
     ) -> Result<Option<u64>, Error> {
-        let table = Table::new("durable", "migrations");
-        self.0.read_database_version(conn, &table).await
+        let table = self.migration_table();
+        self.migrator.read_database_version(conn, &table).await
+    }
+
+    /// Get the status of every durable migration, both those known to this
+    /// migrator and those that have been applied to the database.
+    pub async fn status(&self, conn: &mut sqlx::PgConnection) -> Result<MigrationReport, Error> {
+        let options = Options {
+            migration_table: self.migration_table(),
+            ..Options::default()
+        };
+
+        self.migrator.status(conn, &options).await
+    }
+
+    /// Compare the schema in the database against the schema expected by the
+    /// latest migration supported by this migrator.
+    ///
+    /// This checks the tables, columns, indexes, views, functions, triggers,
+    /// and enum types within the `durable` schema and reports every difference
+    /// that it finds. See [`SchemaDrift::is_breaking`] for which differences
+    /// prevent a worker from starting.
+    pub async fn check_schema(&self, conn: &mut sqlx::PgConnection) -> Result<SchemaReport, Error> {
+        // The migrations table won't exist if the migrations have never been
+        // run, or if they were applied by some other tool.
+        let tracked: bool =
+            sqlx::query_scalar("SELECT to_regclass('durable.migrations') IS NOT NULL")
+                .fetch_one(self.schema.on(&mut *conn))
+                .await?;
+        let version = match tracked {
+            true => self.read_database_version(conn).await?,
+            false => None,
+        };
+        let report = schema::check(conn, self.schema(), version, self.latest_version()).await?;
+
+        Ok(report)
     }
 }
@@ -0,0 +1,868 @@
+//! Compare the durable schema in the database against the one that the runtime
+//! expects.
+//!
+//! The expected schema is parsed out of `schema.sql`, which is kept in sync
+//! with the migrations. The actual schema is read from the postgres catalog.
+//! Only the parts of the schema that the runtime depends on are compared:
+//! tables and their columns, indexes, views, functions, triggers, and enum
+//! values.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+
+use sqlx::PgConnection;
+
+const SCHEMA: &str = include_str!("../../schema.sql");
+
+/// Tables in the durable schema that are not part of `schema.sql`.
+const IGNORED_TABLES: &[&str] = &["migrations"];
+
+/// The result of comparing the schema in the database against the schema
+/// expected by this version of the runtime.
+#[derive(Clone, Debug)]
+pub struct SchemaReport {
+    database_version: Option<u64>,
+    expected_version: u64,
+    drift: Vec<SchemaDrift>,
+}
+
+impl SchemaReport {
+    /// The latest migration that has been applied to the database, if any.
+    pub fn database_version(&self) -> Option<u64> {
+        self.database_version
+    }
+
+    /// The migration version that this runtime expects the database to be at.
+    pub fn expected_version(&self) -> u64 {
+        self.expected_version
+    }
+
+    /// Whether the database has been migrated to the expected version.
+    pub fn version_matches(&self) -> bool {
+        self.database_version == Some(self.expected_version)
+    }
+
+    /// All the differences between the database and the expected schema.
+    pub fn drift(&self) -> &[SchemaDrift] {
+        &self.drift
+    }
+
+    /// Whether the database can be used by this runtime.
+    ///
+    /// This is the case if the database is at the expected migration version
+    /// and none of the differences from the expected schema are breaking.
+    pub fn is_compatible(&self) -> bool {
+        self.version_matches() && !self.drift.iter().any(|drift| drift.is_breaking())
+    }
+
+    /// Describe the migration version mismatch, if there is one.
+    pub(crate) fn version_message(&self) -> Option<String> {
+        let expected = self.expected_version;
+
+        match self.database_version {
+            Some(version) if version == expected => None,
+            Some(version) if version > expected => Some(format!(
+                "the database has been migrated to version {version}, which is newer than the \
+                 latest version supported by this worker ({expected}); upgrade the worker or \
+                 revert the newer migrations"
+            )),
+            Some(version) => Some(format!(
+                "the database is at migration version {version} but this worker requires version \
+                 {expected}"
+            )),
+            None => Some(format!(
+                "no durable migrations have been applied to the database (this worker requires \
+                 version {expected})"
+            )),
+        }
+    }
+}
+
+/// An error indicating that the database schema is not compatible with this
+/// version of the runtime.
+#[derive(Clone, Debug)]
+pub struct SchemaValidationError {
+    report: SchemaReport,
+}
+
+impl SchemaValidationError {
+    pub(crate) fn new(report: SchemaReport) -> Self {
+        Self { report }
+    }
+
+    /// The schema report that caused this error.
+    pub fn report(&self) -> &SchemaReport {
+        &self.report
+    }
+}
+
+impl fmt::Display for SchemaValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("the database schema does not match what this durable worker requires:")?;
+
+        if let Some(message) = self.report.version_message() {
+            write!(f, "\n  - {message}")?;
+        }
+
+        for drift in self.report.drift.iter().filter(|drift| drift.is_breaking()) {
+            write!(f, "\n  - {drift}")?;
+        }
+
+        f.write_str(
+            "\nApply the durable migrations for this version of the worker (e.g. with \
+             `durable_runtime::migrate::Migrator` or `WorkerBuilder::migrate`) and restore any \
+             manually modified objects in the `durable` schema.",
+        )
+    }
+}
+
+impl std::error::Error for SchemaValidationError {}
+
+/// A single difference between the schema in the database and the expected
+/// schema.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum SchemaDrift {
+    /// An object that the runtime expects is missing from the database.
+    Missing(SchemaObject),
+
+    /// The database contains an object that the runtime does not know about.
+    Unexpected(SchemaObject),
+
+    /// A column has a different type than expected.
+    ColumnType {
+        table: String,
+        column: String,
+        expected: String,
+        actual: String,
+    },
+
+    /// A column is nullable when it is expected to be `NOT NULL`, or the
+    /// other way around.
+    ColumnNullability {
+        table: String,
+        column: String,
+        nullable: bool,
+    },
+}
+
+impl SchemaDrift {
+    /// Whether this difference could prevent the runtime from working
+    /// correctly.
+    ///
+    /// Objects that the runtime does not know about are not breaking, since
+    /// they are commonly added by operators (e.g. extra indexes).
+    pub fn is_breaking(&self) -> bool {
+        !matches!(self, Self::Unexpected(_))
+    }
+}
+
+impl fmt::Display for SchemaDrift {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Missing(object) => write!(f, "missing {object}"),
+            Self::Unexpected(object) => write!(f, "unexpected {object}"),
+            Self::ColumnType {
+                table,
+                column,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "column `durable.{table}.{column}` has type `{actual}` but should be `{expected}`"
+            ),
+            Self::ColumnNullability {
+                table,
+                column,
+                nullable: true,
+            } => write!(
+                f,
+                "column `durable.{table}.{column}` is nullable but should be NOT NULL"
+            ),
+            Self::ColumnNullability {
+                table,
+                column,
+                nullable: false,
+            } => write!(
+                f,
+                "column `durable.{table}.{column}` is NOT NULL but should be nullable"
+            ),
+        }
+    }
+}
+
+/// An object in the durable schema.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum SchemaObject {
+    Table(String),
+    Column { table: String, column: String },
+    Index(String),
+    View(String),
+    Function(String),
+    Trigger { table: String, trigger: String },
+    EnumValue { ty: String, value: String },
+}
+
+impl fmt::Display for SchemaObject {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Table(table) => write!(f, "table `durable.{table}`"),
+            Self::Column { table, column } => write!(f, "column `durable.{table}.{column}`"),
+            Self::Index(index) => write!(f, "index `durable.{index}`"),
+            Self::View(view) => write!(f, "view `durable.{view}`"),
+            Self::Function(function) => write!(f, "function `durable.{function}`"),
+            Self::Trigger { table, trigger } => {
+                write!(f, "trigger `{trigger}` on `durable.{table}`")
+            }
+            Self::EnumValue { ty, value } => write!(f, "value '{value}' of enum `durable.{ty}`"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Column {
+    ty: String,
+    nullable: bool,
+}
+
+#[derive(Clone, Debug, Default)]
+struct Schema {
+    tables: BTreeMap<String, BTreeMap<String, Column>>,
+    indexes: BTreeSet<String>,
+    views: BTreeSet<String>,
+    functions: BTreeSet<String>,
+    triggers: BTreeSet<(String, String)>,
+    enums: BTreeMap<String, BTreeSet<String>>,
+}
+
+impl Schema {
+    /// The schema that this version of the runtime expects.
+    fn expected() -> Self {
+        Self::parse(SCHEMA)
+    }
+
+    /// Parse the subset of SQL DDL used by `schema.sql`.
+    fn parse(sql: &str) -> Self {
+        let mut schema = Self::default();
+
+        for stmt in statements(sql) {
+            let stmt = stmt.split_whitespace().collect::<Vec<_>>().join(" ");
+
+            if let Some(rest) = strip_prefix(&stmt, "CREATE TABLE ") {
+                let Some((name, body)) = rest.split_once('(') else {
+                    continue;
+                };
+                let body = table_body(body);
+
+                schema.tables.insert(object_name(name), parse_columns(body));
+            } else if let Some(rest) = strip_prefix(&stmt, "CREATE UNIQUE INDEX ")
+                .or_else(|| strip_prefix(&stmt, "CREATE INDEX "))
+            {
+                schema.indexes.insert(object_name(first_word(rest)));
+            } else if let Some(rest) = strip_prefix(&stmt, "CREATE VIEW ")
+                .or_else(|| strip_prefix(&stmt, "CREATE OR REPLACE VIEW "))
+            {
+                schema.views.insert(object_name(first_word(rest)));
+            } else if let Some(rest) = strip_prefix(&stmt, "CREATE FUNCTION ")
+                .or_else(|| strip_prefix(&stmt, "CREATE OR REPLACE FUNCTION "))
+            {
+                let name = rest.split('(').next().unwrap_or(rest);
+                schema.functions.insert(object_name(name));
+            } else if let Some(rest) = strip_prefix(&stmt, "CREATE TRIGGER ") {
+                let name = object_name(first_word(rest));
+                let Some((_, table)) = rest.split_once(" ON ") else {
+                    continue;
+                };
+
+                schema
+                    .triggers
+                    .insert((object_name(first_word(table)), name));
+            } else if let Some(rest) = strip_prefix(&stmt, "CREATE TYPE ") {
+                let Some((name, values)) = rest.split_once(" AS ENUM") else {
+                    continue;
+                };
+
+                let values = values
+                    .split('\'')
+                    .skip(1)
+                    .step_by(2)
+                    .map(|value| value.to_owned())
+                    .collect();
+                schema.enums.insert(object_name(name), values);
+            }
+        }
+
+        schema
+    }
+
+    /// Read the current schema from the database catalog.
+    ///
+    /// `namespace` is the schema that the durable tables were installed into.
+    async fn load(conn: &mut PgConnection, namespace: &str) -> sqlx::Result<Self> {
+        let mut schema = Self::default();
+
+        let columns: Vec<(String, String, String, bool)> = sqlx::query_as(
+            "
+            SELECT
+                c.relname::text,
+                a.attname::text,
+                format_type(a.atttypid, a.atttypmod),
+                a.attnotnull
+              FROM pg_attribute a
+              JOIN pg_class c ON c.oid = a.attrelid
+              JOIN pg_namespace n ON n.oid = c.relnamespace
+             WHERE n.nspname = $1
+               AND c.relkind IN ('r', 'p')
+               AND NOT c.relispartition
+               AND a.attnum > 0
+               AND NOT a.attisdropped
+            ",
+        )
+        .bind(namespace)
+        .fetch_all(&mut *conn)
+        .await?;
+
+        for (table, column, ty, not_null) in columns {
+            if IGNORED_TABLES.contains(&table.as_str()) {
+                continue;
+            }
+
+            // Types from the durable schema are qualified with its name when
+            // it isn't on the search path.
+            let ty = ty.replace('"', "");
+            let ty = match ty.strip_prefix(namespace) {
+                Some(rest) if rest.starts_with('.') => format!("durable{rest}"),
+                _ => ty,
+            };
+
+            schema.tables.entry(table).or_default().insert(
+                column,
+                Column {
+                    ty: normalize_type(&ty),
+                    nullable: !not_null,
+                },
+            );
+        }
+
+        // Indexes that back a constraint (e.g. primary keys) are not declared
+        // separately in schema.sql so they are covered by the table instead.
+        // Partitions are created at runtime and inherit their indexes and
+        // triggers from the parent table, so they are skipped as well.
+        let objects: Vec<(String, String, Option<String>)> = sqlx::query_as(
+            "
+            SELECT 'view', c.relname::text, NULL::text
+              FROM pg_class c
+              JOIN pg_namespace n ON n.oid = c.relnamespace
+             WHERE n.nspname = $1
+               AND c.relkind = 'v'
+            UNION ALL
+            SELECT 'index', i.relname::text, NULL
+              FROM pg_index x
+              JOIN pg_class i ON i.oid = x.indexrelid
+              JOIN pg_namespace n ON n.oid = i.relnamespace
+             WHERE n.nspname = $1
+               AND NOT i.relispartition
+               AND NOT EXISTS(SELECT 1 FROM pg_constraint WHERE conindid = x.indexrelid)
+            UNION ALL
+            SELECT 'function', p.proname::text, NULL
+              FROM pg_proc p
+              JOIN pg_namespace n ON n.oid = p.pronamespace
+             WHERE n.nspname = $1
+            UNION ALL
+            SELECT 'trigger', t.tgname::text, c.relname::text
+              FROM pg_trigger t
+              JOIN pg_class c ON c.oid = t.tgrelid
+              JOIN pg_namespace n ON n.oid = c.relnamespace
+             WHERE n.nspname = $1
+               AND NOT t.tgisinternal
+               AND NOT c.relispartition
+            UNION ALL
+            SELECT 'enum', t.typname::text, e.enumlabel::text
+              FROM pg_enum e
+              JOIN pg_type t ON t.oid = e.enumtypid
+              JOIN pg_namespace n ON n.oid = t.typnamespace
+             WHERE n.nspname = $1
+            ",
+        )
+        .bind(namespace)
+        .fetch_all(&mut *conn)
+        .await?;
+
+        for (kind, name, extra) in objects {
+            match (kind.as_str(), extra) {
+                ("view", _) => {
+                    schema.views.insert(name);
+                }
+                ("index", _) => {
+                    schema.indexes.insert(name);
+                }
+                ("function", _) => {
+                    schema.functions.insert(name);
+                }
+                ("trigger", Some(table)) => {
+                    if !IGNORED_TABLES.contains(&table.as_str()) {
+                        schema.triggers.insert((table, name));
+                    }
+                }
+                ("enum", Some(value)) => {
+                    schema.enums.entry(name).or_default().insert(value);
+                }
+                _ => (),
+            }
+        }
+
+        Ok(schema)
+    }
+
+    /// Compute the differences between `self`, the expected schema, and
+    /// `actual`.
+    fn diff(&self, actual: &Schema) -> Vec<SchemaDrift> {
+        let mut drift = Vec::new();
+
+        for (table, columns) in &self.tables {
+            let Some(actual) = actual.tables.get(table) else {
+                drift.push(SchemaDrift::Missing(SchemaObject::Table(table.clone())));
+                continue;
+            };
+
+            for (column, expected) in columns {
+                let object = || SchemaObject::Column {
+                    table: table.clone(),
+                    column: column.clone(),
+                };
+
+                let Some(actual) = actual.get(column) else {
+                    drift.push(SchemaDrift::Missing(object()));
+                    continue;
+                };
+
+                if actual.ty != expected.ty {
+                    drift.push(SchemaDrift::ColumnType {
+                        table: table.clone(),
+                        column: column.clone(),
+                        expected: expected.ty.clone(),
+                        actual: actual.ty.clone(),
+                    });
+                }
+
+                if actual.nullable != expected.nullable {
+                    drift.push(SchemaDrift::ColumnNullability {
+                        table: table.clone(),
+                        column: column.clone(),
+                        nullable: actual.nullable,
+                    });
+                }
+            }
+
+            for column in actual.keys().filter(|c| !columns.contains_key(*c)) {
+                drift.push(SchemaDrift::Unexpected(SchemaObject::Column {
+                    table: table.clone(),
+                    column: column.clone(),
+                }));
+            }
+        }
+
+        for table in actual.tables.keys() {
+            if !self.tables.contains_key(table) {
+                drift.push(SchemaDrift::Unexpected(SchemaObject::Table(table.clone())));
+            }
+        }
+
+        diff_sets(&self.indexes, &actual.indexes, &mut drift, |index| {
+            SchemaObject::Index(index.clone())
+        });
+        diff_sets(&self.views, &actual.views, &mut drift, |view| {
+            SchemaObject::View(view.clone())
+        });
+        diff_sets(&self.functions, &actual.functions, &mut drift, |function| {
+            SchemaObject::Function(function.clone())
+        });
+        diff_sets(
+            &self.triggers,
+            &actual.triggers,
+            &mut drift,
+            |(table, trigger)| SchemaObject::Trigger {
+                table: table.clone(),
+                trigger: trigger.clone(),
+            },
+        );
+
+        let empty = BTreeSet::new();
+        for (ty, values) in &self.enums {
+            let actual = actual.enums.get(ty).unwrap_or(&empty);
+            diff_sets(values, actual, &mut drift, |value| {
+                SchemaObject::EnumValue {
+                    ty: ty.clone(),
+                    value: value.clone(),
+                }
+            });
+        }
+
+        drift
+    }
+}
+
+fn diff_sets<T: Ord>(
+    expected: &BTreeSet<T>,
+    actual: &BTreeSet<T>,
+    drift: &mut Vec<SchemaDrift>,
+    object: impl Fn(&T) -> SchemaObject,
+) {
+    for item in expected.difference(actual) {
+        drift.push(SchemaDrift::Missing(object(item)));
+    }
+
+    for item in actual.difference(expected) {
+        drift.push(SchemaDrift::Unexpected(object(item)));
+    }
+}
+
+/// Compare the schema in the database against the expected schema.
+pub(super) async fn check(
+    conn: &mut PgConnection,
+    namespace: &str,
+    database_version: Option<u64>,
+    expected_version: u64,
+) -> sqlx::Result<SchemaReport> {
+    let actual = Schema::load(conn, namespace).await?;
+    let drift = Schema::expected().diff(&actual);
+
+    Ok(SchemaReport {
+        database_version,
+        expected_version,
+        drift,
+    })
+}
+
+/// Split a SQL script into statements, with comments removed.
+///
+/// Semicolons within `$$`-quoted function bodies do not end a statement.
+fn statements(sql: &str) -> Vec<String> {
+    let mut stmts = Vec::new();
+    let mut current = String::new();
+    let mut quoted = false;
+
+    for line in sql.lines() {
+        let line = match quoted {
+            true => line,
+            false => line.split("--").next().unwrap_or_default(),
+        };
+
+        let mut parts = line.split("$$").peekable();
+        while let Some(part) = parts.next() {
+            if quoted {
+                current.push_str(part);
+            } else {
+                let mut pieces = part.split(';').peekable();
+                while let Some(piece) = pieces.next() {
+                    current.push_str(piece);
+
+                    if pieces.peek().is_some() {
+                        stmts.push(std::mem::take(&mut current));
+                    }
+                }
+            }
+
+            if parts.peek().is_some() {
+                current.push_str("$$");
+                quoted = !quoted;
+            }
+        }
+
+        current.push('\n');
+    }
+
+    stmts.push(current);
+    stmts.retain(|stmt| !stmt.trim().is_empty());
+    stmts
+}
+
+/// Parse the columns out of the body of a `CREATE TABLE` statement.
+fn parse_columns(body: &str) -> BTreeMap<String, Column> {
+    const CONSTRAINTS: &[&str] = &[
+        "CONSTRAINT",
+        "PRIMARY",
+        "UNIQUE",
+        "CHECK",
+        "FOREIGN",
+        "EXCLUDE",
+    ];
+    const TYPE_END: &[&str] = &[
+        "NOT",
+        "NULL",
+        "DEFAULT",
+        "PRIMARY",
+        "REFERENCES",
+        "UNIQUE",
+        "CHECK",
+        "GENERATED",
+        "CONSTRAINT",
+        "COLLATE",
+    ];
+
+    let mut columns = BTreeMap::new();
+    let mut primary_key = Vec::new();
+
+    for entry in split_top_level(body) {
+        let words: Vec<&str> = entry.split_whitespace().collect();
+        let Some(&name) = words.first() else {
+            continue;
+        };
+        let upper = entry.to_ascii_uppercase();
+
+        if CONSTRAINTS.contains(&name.to_ascii_uppercase().as_str()) {
+            if let Some(cols) = strip_prefix(entry, "PRIMARY KEY") {
+                let cols = cols.trim().trim_start_matches('(').trim_end_matches(')');
+                primary_key.extend(cols.split(',').map(object_name));
+            }
+
+            continue;
+        }
+
+        let ty = words[1..]
+            .iter()
+            .take_while(|word| !TYPE_END.contains(&word.to_ascii_uppercase().as_str()))
+            .copied()
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        columns.insert(
+            object_name(name),
+            Column {
+                ty: normalize_type(&ty),
+                nullable: !upper.contains("NOT NULL") && !upper.contains("PRIMARY KEY"),
+            },
+        );
+    }
+
+    for name in primary_key {
+        if let Some(column) = columns.get_mut(&name) {
+            column.nullable = false;
+        }
+    }
+
+    columns
+}
+
+/// Split a comma-separated list, ignoring commas nested within parentheses.
+fn split_top_level(body: &str) -> Vec<&str> {
+    let mut entries = Vec::new();
+    let mut depth = 0usize;
+    let mut start = 0;
+
+    for (idx, c) in body.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth = depth.saturating_sub(1),
+            ',' if depth == 0 => {
+                entries.push(body[start..idx].trim());
+                start = idx + 1;
+            }
+            _ => (),
+        }
+    }
+
+    entries.push(body[start..].trim());
+    entries.retain(|entry| !entry.is_empty());
+    entries
+}
+
+/// Strip everything after the closing paren of a `CREATE TABLE` body, such as
+/// a `PARTITION BY` clause.
+fn table_body(body: &str) -> &str {
+    let mut depth = 0usize;
+
+    for (idx, c) in body.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' if depth == 0 => return &body[..idx],
+            ')' => depth -= 1,
+            _ => (),
+        }
+    }
+
+    body
+}
+
+/// Normalize a type name to the form used by postgres' `format_type`.
+fn normalize_type(ty: &str) -> String {
+    let ty = ty.trim().to_ascii_lowercase().replace('"', "");
+    let ty = ty.strip_prefix("durable.").unwrap_or(&ty);
+
+    if let Some(inner) = ty.strip_suffix("[]") {
+        return format!("{}[]", normalize_type(inner));
+    }
+
+    match ty {
+        "bigserial" | "serial8" | "int8" => "bigint",
+        "serial" | "serial4" | "int" | "int4" => "integer",
+        "smallserial" | "serial2" | "int2" => "smallint",
+        "float" | "float8" => "double precision",
+        "float4" => "real",
+        "bool" => "boolean",
+        "varchar" => "character varying",
+        "timestamptz" => "timestamp with time zone",
+        "timestamp" => "timestamp without time zone",
+        ty => ty,
+    }
+    .to_owned()
+}
+
+/// Strip the `durable.` schema and any quotes from an object name.
+fn object_name(name: &str) -> String {
+    let name = name.trim().replace('"', "");
+    match name.strip_prefix("durable.") {
+        Some(name) => name.to_owned(),
+        None => name,
+    }
+}
+
+fn first_word(s: &str) -> &str {
+    s.split_whitespace().next().unwrap_or_default()
+}
+
+fn strip_prefix<'a>(s: &'a str, prefix: &str) -> Option<&'a str> {
+    match s.get(..prefix.len()) {
+        Some(head) if head.eq_ignore_ascii_case(prefix) => Some(&s[prefix.len()..]),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_tables_and_columns() {
+        let schema = Schema::expected();
+        let task = schema.tables.get("task").expect("missing the task table");
+
+        assert_eq!(
+            task.get("id"),
+            Some(&Column {
+                ty: "bigint".into(),
+                nullable: false
+            })
+        );
+        assert_eq!(
+            task.get("state"),
+            Some(&Column {
+                ty: "task_state".into(),
+                nullable: false
+            })
+        );
+        assert_eq!(
+            task.get("wakeup_at"),
+            Some(&Column {
+                ty: "timestamp with time zone".into(),
+                nullable: true
+            })
+        );
+        assert!(!task.contains_key("CONSTRAINT"));
+        assert!(!task.contains_key("fk_worker"));
+
+        // Columns in a table-level primary key are implicitly NOT NULL.
+        let event = schema.tables.get("event").expect("missing the event table");
+        assert!(!event["index"].nullable);
+    }
+
+    #[test]
+    fn parses_other_objects() {
+        let schema = Schema::expected();
+
+        assert!(schema.indexes.contains("task_queue"));
+        assert!(schema.indexes.contains("task_dedupe_key"));
+        assert!(schema.views.contains("leader"));
+        assert!(schema.functions.contains("notify_task"));
+        assert!(schema
+            .triggers
+            .contains(&("task".to_owned(), "task_inserted".to_owned())));
+        assert!(schema.enums["task_state"].contains("suspended"));
+    }
+
+    #[test]
+    fn ignores_function_bodies() {
+        let schema = Schema::parse(
+            "
+            CREATE FUNCTION durable.f() RETURNS trigger AS $$
+                BEGIN
+                    -- CREATE TABLE durable.bogus(id int);
+                    PERFORM 1;
+                    RETURN NULL;
+                END;
+            $$ LANGUAGE plpgsql;
+
+            CREATE TABLE durable.t(
+                id  int NOT NULL, -- trailing; comment
+                val float8
+            );
+            ",
+        );
+
+        assert_eq!(schema.functions.len(), 1);
+        assert_eq!(schema.tables.len(), 1);
+        assert_eq!(schema.tables["t"]["val"].ty, "double precision");
+        assert!(schema.tables["t"]["val"].nullable);
+    }
+
+    #[test]
+    fn parses_partitioned_tables() {
+        let schema = Schema::parse(
+            "
+            CREATE TABLE durable.t(
+                id      bigint NOT NULL,
+                value   numeric(10, 2),
+                PRIMARY KEY(id)
+            ) PARTITION BY RANGE (id);
+
+            CREATE TABLE durable.t_default PARTITION OF durable.t DEFAULT;
+            ",
+        );
+
+        assert_eq!(schema.tables.len(), 1);
+        assert_eq!(schema.tables["t"].len(), 2);
+        assert!(!schema.tables["t"]["id"].nullable);
+    }
+
+    #[test]
+    fn diff_reports_missing_and_unexpected() {
+        let expected = Schema::parse(
+            "
+            CREATE TABLE durable.t(id bigint NOT NULL, name text);
+            CREATE INDEX t_name ON durable.t(name);
+            ",
+        );
+        let actual = Schema::parse(
+            "
+            CREATE TABLE durable.t(id int NOT NULL, name text NOT NULL, extra text);
+            CREATE INDEX t_extra ON durable.t(extra);
+            ",
+        );
+
+        let drift = expected.diff(&actual);
+
+        assert!(drift.contains(&SchemaDrift::ColumnType {
+            table: "t".into(),
+            column: "id".into(),
+            expected: "bigint".into(),
+            actual: "integer".into(),
+        }));
+        assert!(drift.contains(&SchemaDrift::ColumnNullability {
+            table: "t".into(),
+            column: "name".into(),
+            nullable: false,
+        }));
+        assert!(drift.contains(&SchemaDrift::Missing(SchemaObject::Index("t_name".into()))));
+        assert!(drift.contains(&SchemaDrift::Unexpected(SchemaObject::Index(
+            "t_extra".into()
+        ))));
+        assert!(
+            drift.contains(&SchemaDrift::Unexpected(SchemaObject::Column {
+                table: "t".into(),
+                column: "extra".into()
+            }))
+        );
+        assert_eq!(drift.iter().filter(|d| d.is_breaking()).count(), 3);
+    }
+}
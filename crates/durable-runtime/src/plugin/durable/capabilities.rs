@@ -0,0 +1,72 @@
+use crate::bindings::durable::core::capabilities::{Host, WorkerLimits};
+use crate::plugin::durable::version::CURRENT_VERSION;
+use crate::{Config, Task};
+
+/// The `durable:core` interfaces that are always available.
+const CORE_INTERFACES: &[&str] = &[
+    "capabilities",
+    "core",
+    "http",
+    "lock",
+    "notify",
+    "panic",
+    "ratelimit",
+    "scheduler",
+    "sql",
+    "tasks",
+];
+
+/// The `durable:core` interfaces that are available with `config`.
+fn durable_interfaces(config: &Config) -> Vec<String> {
+    let mut interfaces: Vec<&str> = CORE_INTERFACES.to_vec();
+
+    if config.object_store.is_some() {
+        interfaces.push("object-store");
+    }
+    if config.email.is_some() {
+        interfaces.push("email");
+    }
+    if config.mq.is_some() {
+        interfaces.push("mq");
+    }
+    if !config.llm_providers.is_empty() {
+        interfaces.push("llm");
+    }
+
+    interfaces
+        .into_iter()
+        .map(|name| format!("durable:core/{name}@{CURRENT_VERSION}"))
+        .collect()
+}
+
+#[async_trait::async_trait]
+impl Host for Task {
+    async fn interfaces(&mut self) -> wasmtime::Result<Vec<String>> {
+        let shared = self.state.shared();
+        let mut interfaces = durable_interfaces(&shared.config);
+
+        for plugin in shared.plugins.iter() {
+            interfaces.extend(plugin.interfaces());
+        }
+
+        interfaces.sort();
+        interfaces.dedup();
+
+        Ok(interfaces)
+    }
+
+    async fn limits(&mut self) -> wasmtime::Result<WorkerLimits> {
+        let config = self.state.config();
+
+        Ok(WorkerLimits {
+            max_events: config.max_workflow_events,
+            max_log_bytes_per_transaction: config.max_log_bytes_per_transaction as u64,
+            max_returned_buffer_len: config.max_returned_buffer_len as u64,
+            max_http_timeout: config
+                .max_http_timeout
+                .as_millis()
+                .try_into()
+                .unwrap_or(u64::MAX),
+        })
+    }
+}
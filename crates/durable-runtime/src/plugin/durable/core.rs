@@ -2,11 +2,15 @@ use std::time::{Duration, SystemTime};
 
 use anyhow::Context;
 use serde_json::value::RawValue;
+use sqlx::types::Json;
 
-use crate::bindings::durable::core::core::Host;
+use crate::bindings::durable::core::core::{self, Host};
 use crate::bindings::wasi::clocks::wall_clock::Datetime;
 use crate::task::{Task, TransactionOptions};
 
+/// The maximum size of a single value within the metadata of a task.
+const MAX_METADATA_VALUE_LEN: usize = 16 * 1024;
+
 #[async_trait::async_trait]
 impl Host for Task {
     fn task_id(&mut self) -> anyhow::Result<i64> {
@@ -35,24 +39,96 @@ impl Host for Task {
         })
     }
 
+    fn task_queue(&mut self) -> anyhow::Result<String> {
+        Ok(self.state.tenant().unwrap_or_default().to_owned())
+    }
+
+    fn task_labels(&mut self) -> anyhow::Result<Vec<(String, String)>> {
+        Ok(self
+            .state
+            .task_labels()
+            .iter()
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect())
+    }
+
+    fn task_attempt(&mut self) -> anyhow::Result<u32> {
+        self.state.assert_in_transaction("durable::task_attempt")?;
+
+        Ok(self.state.task_attempt().try_into().unwrap_or(0))
+    }
+
+    fn worker_id(&mut self) -> anyhow::Result<i64> {
+        self.state.assert_in_transaction("durable::worker_id")?;
+
+        Ok(self.state.worker_id())
+    }
+
+    async fn set_metadata(&mut self, key: String, value: Option<String>) -> anyhow::Result<()> {
+        self.state.assert_in_transaction("durable::metadata::set")?;
+
+        let value: Option<&RawValue> = match &value {
+            Some(value) if value.len() > MAX_METADATA_VALUE_LEN => anyhow::bail!(
+                "metadata value for `{key}` is {} bytes, which is larger than the limit of \
+                 {MAX_METADATA_VALUE_LEN} bytes",
+                value.len()
+            ),
+            Some(value) => {
+                Some(serde_json::from_str(value).context("metadata value was not valid json")?)
+            }
+            None => None,
+        };
+
+        sqlx::query!(
+            r#"
+            UPDATE durable.task
+              SET metadata = CASE
+                    WHEN $3::jsonb IS NULL THEN metadata - $2::text
+                    ELSE metadata || jsonb_build_object($2::text, $3::jsonb)
+                  END
+            WHERE id = $1
+            "#,
+            self.state.task_id(),
+            key,
+            value.map(Json) as Option<Json<&RawValue>>
+        )
+        .execute(self.state.shared().schema.on(self.state.pool()))
+        .await?;
+
+        Ok(())
+    }
+
     async fn transaction_enter(
         &mut self,
         label: String,
         database: bool,
     ) -> anyhow::Result<Option<String>> {
         let options = TransactionOptions::new(label).database(database);
-        let data: Option<Box<RawValue>> = self.state.enter(options).await?;
-        let data = data.map(|v| v.get().to_owned());
+        self.enter_with(options).await
+    }
 
-        let txn = self.state.transaction().map(|txn| txn.index());
-        self.resources.set_txn(txn);
+    async fn transaction_enter2(
+        &mut self,
+        label: String,
+        options: core::TransactionOptions,
+    ) -> anyhow::Result<Option<String>> {
+        let mut txn_options = TransactionOptions::new(label)
+            .database(options.is_db)
+            .read_only(options.read_only);
+        if let Some(timeout) = options.statement_timeout {
+            txn_options = txn_options.statement_timeout(Duration::from_millis(timeout));
+        }
 
-        Ok(data)
+        self.enter_with(txn_options).await
     }
 
     async fn transaction_exit(&mut self, data: String) -> anyhow::Result<()> {
         let data: &RawValue = serde_json::from_str(&data) //
             .context("provided data was not valid json")?;
+        if self.state.transaction().is_some() {
+            self.run_plugin_hooks(|plugin, task| plugin.on_transaction_exit(task))?;
+        }
+
         self.state.exit(data).await?;
 
         let txn = self.state.transaction().map(|txn| txn.index());
@@ -61,3 +137,19 @@ impl Host for Task {
         Ok(())
     }
 }
+
+impl Task {
+    async fn enter_with(&mut self, options: TransactionOptions) -> anyhow::Result<Option<String>> {
+        let data: Option<Box<RawValue>> = self.state.enter(options).await?;
+        let data = data.map(|v| v.get().to_owned());
+
+        let txn = self.state.transaction().map(|txn| txn.index());
+        self.resources.set_txn(txn);
+
+        if data.is_none() {
+            self.run_plugin_hooks(|plugin, task| plugin.on_transaction_enter(task))?;
+        }
+
+        Ok(data)
+    }
+}
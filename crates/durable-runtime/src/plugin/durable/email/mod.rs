@@ -0,0 +1,238 @@
+use std::time::Duration;
+
+use lettre::message::header::ContentType;
+use lettre::message::{Attachment as MimeAttachment, Mailbox, MultiPart, SinglePart};
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{AsyncSmtpTransport, AsyncTransport, Tokio1Executor};
+
+use crate::bindings::durable::core::email::*;
+use crate::config::{EmailConfig, EmailTransport, SmtpConfig, SmtpTls};
+use crate::Task;
+
+mod ses;
+
+/// The body of an email, before attachments are added.
+enum Body {
+    Single(SinglePart),
+    Multi(MultiPart),
+}
+
+fn mailbox(field: &str, address: &str) -> Result<Mailbox, EmailError> {
+    address.parse().map_err(|e| {
+        EmailError::InvalidMessage(format!("invalid {field} address `{address}`: {e}"))
+    })
+}
+
+/// Build the MIME message for `message`.
+///
+/// `id` is used to build a `Message-ID` that is stable for the task and
+/// transaction sending the email.
+fn build(
+    config: &EmailConfig,
+    message: Message,
+    id: impl FnOnce(&str) -> String,
+) -> Result<lettre::Message, EmailError> {
+    let from = match message.from.as_deref().or(config.default_from.as_deref()) {
+        Some(from) => mailbox("from", from)?,
+        None => {
+            return Err(EmailError::InvalidMessage(
+                "the message has no sender and the worker has no default sender configured".into(),
+            ))
+        }
+    };
+
+    let mut builder = lettre::Message::builder()
+        .message_id(Some(id(from.email.domain())))
+        .from(from)
+        .subject(message.subject);
+
+    for to in &message.to {
+        builder = builder.to(mailbox("to", to)?);
+    }
+    for cc in &message.cc {
+        builder = builder.cc(mailbox("cc", cc)?);
+    }
+    for bcc in &message.bcc {
+        builder = builder.bcc(mailbox("bcc", bcc)?);
+    }
+    if let Some(reply_to) = &message.reply_to {
+        builder = builder.reply_to(mailbox("reply-to", reply_to)?);
+    }
+
+    let body = match (message.text, message.html) {
+        (Some(text), Some(html)) => Body::Multi(MultiPart::alternative_plain_html(text, html)),
+        (None, Some(html)) => Body::Single(SinglePart::html(html)),
+        (text, None) => Body::Single(SinglePart::plain(text.unwrap_or_default())),
+    };
+
+    let body = if message.attachments.is_empty() {
+        body
+    } else {
+        let mut mixed = match body {
+            Body::Single(part) => MultiPart::mixed().singlepart(part),
+            Body::Multi(part) => MultiPart::mixed().multipart(part),
+        };
+
+        for attachment in message.attachments {
+            let content_type = ContentType::parse(&attachment.content_type).map_err(|e| {
+                EmailError::InvalidMessage(format!(
+                    "invalid content type `{}` for attachment `{}`: {e}",
+                    attachment.content_type, attachment.filename
+                ))
+            })?;
+
+            mixed = mixed.singlepart(
+                MimeAttachment::new(attachment.filename).body(attachment.data, content_type),
+            );
+        }
+
+        Body::Multi(mixed)
+    };
+
+    let message = match body {
+        Body::Single(part) => builder.singlepart(part),
+        Body::Multi(part) => builder.multipart(part),
+    };
+
+    message.map_err(|e| EmailError::InvalidMessage(e.to_string()))
+}
+
+async fn send_smtp(
+    config: &SmtpConfig,
+    message: lettre::Message,
+    timeout: Duration,
+) -> Result<(), EmailError> {
+    let transport_error = |e: lettre::transport::smtp::Error| EmailError::Transport(e.to_string());
+
+    let mut builder = match config.tls {
+        SmtpTls::Tls => {
+            AsyncSmtpTransport::<Tokio1Executor>::relay(&config.host).map_err(transport_error)?
+        }
+        SmtpTls::Starttls => AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&config.host)
+            .map_err(transport_error)?,
+        SmtpTls::None => AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(&config.host),
+    };
+
+    if let Some(port) = config.port {
+        builder = builder.port(port);
+    }
+    if let Some(username) = &config.username {
+        let password = config.password.clone().unwrap_or_default();
+        builder = builder.credentials(Credentials::new(username.clone(), password));
+    }
+
+    let transport = builder.timeout(Some(timeout)).build();
+    match transport.send(message).await {
+        Ok(_) => Ok(()),
+        Err(e) if e.is_permanent() || e.is_transient() => Err(EmailError::Rejected(e.to_string())),
+        Err(e) => Err(transport_error(e)),
+    }
+}
+
+#[async_trait::async_trait]
+impl Host for Task {
+    async fn send(&mut self, message: Message) -> wasmtime::Result<Result<String, EmailError>> {
+        let txn = self
+            .state
+            .assert_in_transaction("durable:core/email.send")?;
+        let index = txn.index();
+        let task_id = self.state.task_id();
+
+        let config = self.state.config();
+        let Some(email) = &config.email else {
+            return Ok(Err(EmailError::NotConfigured));
+        };
+
+        let message = match build(email, message, |domain| {
+            format!("<durable.{task_id}.{index}@{domain}>")
+        }) {
+            Ok(message) => message,
+            Err(e) => return Ok(Err(e)),
+        };
+
+        let result = match &email.transport {
+            EmailTransport::Smtp(smtp) => {
+                let id = message
+                    .headers()
+                    .get_raw("Message-ID")
+                    .unwrap_or_default()
+                    .to_owned();
+
+                send_smtp(smtp, message, config.max_http_timeout)
+                    .await
+                    .map(|()| id)
+            }
+            EmailTransport::Ses(ses) => {
+                ses::send(ses, self.state.client(), message, config.max_http_timeout).await
+            }
+        };
+
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::SmtpConfig;
+
+    fn message() -> Message {
+        Message {
+            from: None,
+            to: vec!["Alice <alice@example.com>".into()],
+            cc: vec![],
+            bcc: vec!["audit@example.com".into()],
+            reply_to: None,
+            subject: "Your report".into(),
+            text: Some("See attached.".into()),
+            html: Some("<p>See attached.</p>".into()),
+            attachments: vec![Attachment {
+                filename: "report.csv".into(),
+                content_type: "text/csv".into(),
+                data: b"a,b\n1,2\n".to_vec(),
+            }],
+        }
+    }
+
+    #[test]
+    fn build_message() {
+        let config = EmailConfig::new(SmtpConfig::new("localhost"))
+            .default_from("Workflows <workflows@example.org>");
+        let message = build(&config, message(), |domain| {
+            format!("<durable.1.2@{domain}>")
+        })
+        .unwrap();
+
+        let recipients: Vec<_> = message
+            .envelope()
+            .to()
+            .iter()
+            .map(|address| address.to_string())
+            .collect();
+        assert_eq!(recipients, ["alice@example.com", "audit@example.com"]);
+
+        let formatted = String::from_utf8(message.formatted()).unwrap();
+        assert!(formatted.contains("Message-ID: <durable.1.2@example.org>\r\n"));
+        assert!(formatted.contains("From: Workflows <workflows@example.org>\r\n"));
+        assert!(formatted.contains("Subject: Your report\r\n"));
+        assert!(formatted.contains("Content-Type: multipart/mixed;"));
+        assert!(formatted.contains("Content-Type: multipart/alternative;"));
+        assert!(formatted.contains("filename=\"report.csv\""));
+        // Bcc recipients must not be visible to the other recipients.
+        assert!(!formatted.contains("audit@example.com"));
+    }
+
+    #[test]
+    fn build_invalid_message() {
+        let config = EmailConfig::new(SmtpConfig::new("localhost"));
+
+        let error = build(&config, message(), |_| String::new()).unwrap_err();
+        assert!(matches!(error, EmailError::InvalidMessage(_)));
+
+        let mut invalid = message();
+        invalid.from = Some("workflows@example.org".into());
+        invalid.to = vec!["not an address".into()];
+        let error = build(&config, invalid, |_| String::new()).unwrap_err();
+        assert!(matches!(error, EmailError::InvalidMessage(message) if message.contains("to")));
+    }
+}
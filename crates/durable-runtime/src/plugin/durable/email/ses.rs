@@ -0,0 +1,125 @@
+//! Sending emails via the Amazon SES v2 API.
+//!
+//! See <https://docs.aws.amazon.com/ses/latest/APIReference-V2/API_SendEmail.html>.
+
+use std::time::Duration;
+
+use base64::Engine;
+use chrono::Utc;
+use url::Url;
+
+use crate::bindings::durable::core::email::EmailError;
+use crate::config::SesConfig;
+use crate::util::sigv4::{self, Signer};
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct SendEmailResponse {
+    message_id: String,
+}
+
+#[derive(Deserialize)]
+struct ErrorResponse {
+    #[serde(alias = "Message")]
+    message: String,
+}
+
+/// Send `message` via SES, returning the message id that SES assigned to it.
+pub(super) async fn send(
+    config: &SesConfig,
+    client: &reqwest::Client,
+    message: lettre::Message,
+    timeout: Duration,
+) -> Result<String, EmailError> {
+    let endpoint = match &config.endpoint {
+        Some(endpoint) => endpoint.trim_end_matches('/').to_owned(),
+        None => format!("https://email.{}.amazonaws.com", config.region),
+    };
+    let url = Url::parse(&format!("{endpoint}/v2/email/outbound-emails"))
+        .map_err(|e| EmailError::Transport(format!("the SES endpoint is not a valid URL: {e}")))?;
+
+    // Bcc recipients are not included in the message headers so SES needs to
+    // be explicitly told about every recipient.
+    let envelope = message.envelope();
+    let request = serde_json::json!({
+        "FromEmailAddress": envelope.from().map(|from| from.to_string()),
+        "Destination": {
+            "ToAddresses": envelope
+                .to()
+                .iter()
+                .map(|to| to.to_string())
+                .collect::<Vec<_>>(),
+        },
+        "Content": {
+            "Raw": {
+                "Data": base64::engine::general_purpose::STANDARD.encode(message.formatted()),
+            }
+        }
+    });
+    let body = serde_json::to_vec(&request).expect("a JSON value can always be serialized");
+
+    let now = Utc::now();
+    let amz_date = now.format("%Y%m%dT%H%M%SZ").to_string();
+    let payload_hash = sigv4::sha256_hex(&body);
+    let host = sigv4::host(&url);
+
+    let mut headers = vec![
+        ("content-type", "application/json"),
+        ("host", host.as_str()),
+        ("x-amz-content-sha256", payload_hash.as_str()),
+        ("x-amz-date", amz_date.as_str()),
+    ];
+    if let Some(token) = &config.session_token {
+        headers.push(("x-amz-security-token", token));
+    }
+
+    let signer = Signer {
+        access_key_id: &config.access_key_id,
+        secret_access_key: &config.secret_access_key,
+        session_token: config.session_token.as_deref(),
+        region: &config.region,
+        service: "ses",
+    };
+    let authorization = signer.authorization("POST", &url, &headers, &payload_hash, now);
+
+    let mut request = client
+        .post(url)
+        .timeout(timeout)
+        .header(reqwest::header::AUTHORIZATION, authorization);
+    for (name, value) in headers {
+        // reqwest sets the host header itself, based on the URL.
+        if name != "host" {
+            request = request.header(name, value);
+        }
+    }
+
+    let response = request
+        .body(body)
+        .send()
+        .await
+        .map_err(|e| EmailError::Transport(e.to_string()))?;
+    let status = response.status();
+    let body = response
+        .bytes()
+        .await
+        .map_err(|e| EmailError::Transport(e.to_string()))?;
+
+    if status.is_success() {
+        let response: SendEmailResponse = serde_json::from_slice(&body)
+            .map_err(|e| EmailError::Transport(format!("SES returned an invalid response: {e}")))?;
+
+        return Ok(response.message_id);
+    }
+
+    let message = match serde_json::from_slice::<ErrorResponse>(&body) {
+        Ok(error) => error.message,
+        Err(_) => String::from_utf8_lossy(&body).into_owned(),
+    };
+    let message = format!("SES returned {status}: {message}");
+
+    if status.is_client_error() {
+        Err(EmailError::Rejected(message))
+    } else {
+        Err(EmailError::Transport(message))
+    }
+}
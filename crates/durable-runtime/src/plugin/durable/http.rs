@@ -1,10 +1,23 @@
+use std::io::Read;
+use std::ops::{Deref, DerefMut};
+use std::sync::Arc;
 use std::time::Duration;
 
-use http::{HeaderName, HeaderValue, Method};
+use futures_util::StreamExt;
+use http::header::{
+    ACCEPT, ACCEPT_ENCODING, AUTHORIZATION, CONTENT_ENCODING, CONTENT_LENGTH, CONTENT_TYPE, COOKIE,
+    LOCATION, PROXY_AUTHORIZATION, RANGE, TRANSFER_ENCODING, WWW_AUTHENTICATE,
+};
+use http::{HeaderMap, HeaderName, HeaderValue, Method, StatusCode};
 use reqwest::Request;
+use url::Url;
 use wasmtime::component::Resource;
 
+use crate::audit::AuditRecord;
 use crate::bindings::durable::core::http::*;
+use crate::http_cache::CachedResponse;
+use crate::sse::EventParser;
+use crate::worker::SharedState;
 use crate::{Config, Resourceable, Task};
 
 impl Resourceable for HttpError2 {
@@ -16,27 +29,468 @@ impl Resourceable for HttpError2 {
 impl Resourceable for HttpRequest2 {
     const NAME: &'static str = "http-request2";
 
-    type Data = reqwest::Request;
+    type Data = HttpRequestData;
+}
+
+impl Resourceable for EventStream {
+    const NAME: &'static str = "event-stream";
+
+    type Data = EventStreamData;
+}
+
+/// A request that is being built up by a workflow.
+pub struct HttpRequestData {
+    request: Request,
+
+    /// How to handle redirects. If this is `None` then redirects are followed
+    /// by the worker's HTTP client.
+    redirect: Option<Redirects>,
+
+    /// Whether to decompress the response body.
+    decompress: bool,
+}
+
+#[derive(Copy, Clone, Debug)]
+enum Redirects {
+    Limited(usize),
+    Follow,
+}
+
+impl HttpRequestData {
+    fn try_clone(&self) -> Option<Self> {
+        Some(Self {
+            request: self.request.try_clone()?,
+            redirect: self.redirect,
+            decompress: self.decompress,
+        })
+    }
+}
+
+impl From<Request> for HttpRequestData {
+    fn from(request: Request) -> Self {
+        Self {
+            request,
+            redirect: None,
+            decompress: true,
+        }
+    }
+}
+
+impl Deref for HttpRequestData {
+    type Target = Request;
+
+    fn deref(&self) -> &Self::Target {
+        &self.request
+    }
+}
+
+impl DerefMut for HttpRequestData {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.request
+    }
+}
+
+/// A response body that is being read as a stream of server-sent events.
+pub struct EventStreamData {
+    status: u16,
+    headers: HeaderMap,
+
+    // The mutex is only here so that this is Sync. We always have exclusive
+    // access to it when reading.
+    response: tokio::sync::Mutex<reqwest::Response>,
+    parser: EventParser,
+    done: bool,
+
+    /// How long to wait for each event.
+    timeout: Duration,
+}
+
+impl EventStreamData {
+    async fn next(&mut self) -> Result<Option<SseEvent>, DurableHttpError> {
+        loop {
+            if let Some(event) = self.parser.next_event() {
+                return Ok(Some(event));
+            }
+
+            if self.done {
+                return Ok(None);
+            }
+
+            let chunk = self.response.get_mut().chunk();
+            match tokio::time::timeout(self.timeout, chunk).await {
+                Ok(Ok(Some(chunk))) => self.parser.feed(&chunk),
+                Ok(Ok(None)) => self.done = true,
+                Ok(Err(e)) => return Err(e.into()),
+                Err(_) => return Err(DurableHttpError::Timeout),
+            }
+        }
+    }
+}
+
+/// The maximum number of requests from a single `fetch-all` call that are in
+/// flight at once.
+const FETCH_ALL_CONCURRENCY: usize = 16;
+
+/// The parts of a task that are needed in order to send HTTP requests.
+///
+/// Unlike [`Task`], this can be shared between multiple requests that are in
+/// flight at the same time.
+#[derive(Copy, Clone)]
+struct HttpSender<'a> {
+    shared: &'a SharedState,
+    tenant: Option<&'a str>,
+}
+
+impl HttpSender<'_> {
+    async fn fetch2_impl(
+        &self,
+        mut request: HttpRequestData,
+    ) -> Result<Arc<CachedResponse>, DurableHttpError> {
+        const DEFAULT_ACCEPT_ENCODING: HeaderValue = HeaderValue::from_static("gzip, deflate, br");
+
+        let shared = self.shared;
+        let decompress = request.decompress;
+        let headers = request.headers_mut();
+        if decompress && !headers.contains_key(ACCEPT_ENCODING) && !headers.contains_key(RANGE) {
+            headers.insert(ACCEPT_ENCODING, DEFAULT_ACCEPT_ENCODING);
+        }
+
+        // The cache doesn't track redirect or decompression settings so
+        // requests that change them always bypass it.
+        let cache = match (request.redirect, decompress) {
+            (None, true) => shared.http_cache.as_ref(),
+            _ => None,
+        };
+        let key = cache.and_then(|cache| cache.key(&request, self.tenant));
+
+        if let (Some(cache), Some(key)) = (cache, &key) {
+            if let Some(cached) = cache.get(key, &request) {
+                return Ok(cached);
+            }
+        }
+
+        let mut response = match request.redirect {
+            Some(redirect) => self.follow_redirects(request.request, redirect).await?,
+            None => {
+                let original = request.url().clone();
+                let response = shared.execute_http(&shared.client, request.request).await?;
+                let redirects = if *response.url() != original {
+                    vec![response.url().clone()]
+                } else {
+                    Vec::new()
+                };
+
+                read_response(response, redirects).await?
+            }
+        };
+        if decompress {
+            decompress_body(&mut response)?;
+        }
+        let response = Arc::new(response);
+
+        if let (Some(cache), Some(key)) = (cache, key) {
+            cache.insert(key, response.clone());
+        }
+
+        Ok(response)
+    }
+
+    /// Send a request using a client that doesn't follow redirects and then
+    /// follow them here instead, so that we can track which URLs were visited.
+    async fn follow_redirects(
+        &self,
+        mut request: Request,
+        redirect: Redirects,
+    ) -> Result<CachedResponse, DurableHttpError> {
+        let client = &self.shared.redirect_client;
+        let original = request.url().clone();
+        let mut redirects: Vec<Url> = Vec::new();
+
+        loop {
+            let next = request.try_clone();
+            let response = self.shared.execute_http(client, request).await?;
+
+            let limited = match redirect {
+                Redirects::Limited(max) => redirects.len() >= max,
+                Redirects::Follow => false,
+            };
+            let target = match (limited, next) {
+                (false, Some(next)) => redirect_target(&response).map(|url| (url, next)),
+                _ => None,
+            };
+            let Some((location, mut next)) = target else {
+                return read_response(response, redirects).await;
+            };
+
+            // Stop if we end up somewhere we have already been, since following
+            // the redirect would just loop forever.
+            if location == original || redirects.contains(&location) {
+                return read_response(response, redirects).await;
+            }
+
+            let status = response.status();
+            let method = next.method().clone();
+            if (status == StatusCode::SEE_OTHER && method != Method::HEAD)
+                || (matches!(status, StatusCode::MOVED_PERMANENTLY | StatusCode::FOUND)
+                    && method == Method::POST)
+            {
+                *next.method_mut() = Method::GET;
+                *next.body_mut() = None;
+                remove_headers(
+                    next.headers_mut(),
+                    &[
+                        CONTENT_TYPE,
+                        CONTENT_LENGTH,
+                        CONTENT_ENCODING,
+                        TRANSFER_ENCODING,
+                    ],
+                );
+            }
+
+            // Don't leak credentials to a different site.
+            if location.origin() != next.url().origin() {
+                remove_headers(
+                    next.headers_mut(),
+                    &[AUTHORIZATION, COOKIE, PROXY_AUTHORIZATION, WWW_AUTHENTICATE],
+                );
+            }
+
+            *next.url_mut() = location.clone();
+            redirects.push(location);
+            request = next;
+        }
+    }
+
+    async fn fetch_with_retry(
+        &self,
+        mut request: HttpRequestData,
+        policy: RetryPolicy,
+    ) -> Result<(Arc<CachedResponse>, u32), DurableHttpError> {
+        let max_backoff = Duration::from_nanos(policy.max_backoff);
+        let mut backoff = Duration::from_nanos(policy.initial_backoff).min(max_backoff);
+        let mut attempts = 1;
+
+        loop {
+            // Requests built by the guest always have an in-memory body so this
+            // only returns None once we are out of attempts.
+            let next = if attempts < policy.max_attempts {
+                request.try_clone()
+            } else {
+                None
+            };
+
+            let result = self.fetch2_impl(request).await;
+            let delay = match &result {
+                Ok(response) if should_retry(response.status) => match retry_after(response) {
+                    Some(delay) if delay > max_backoff => None,
+                    Some(delay) => Some(delay),
+                    None => Some(backoff),
+                },
+                Err(e) if e.is_connect() => Some(backoff),
+                _ => None,
+            };
+
+            let (Some(next), Some(delay)) = (next, delay) else {
+                return result.map(|response| (response, attempts));
+            };
+
+            tracing::debug!(
+                "retrying HTTP request to {} in {delay:?} (attempt {attempts})",
+                next.url()
+            );
+
+            tokio::time::sleep(delay).await;
+            backoff = backoff.saturating_mul(2).min(max_backoff);
+            request = next;
+            attempts += 1;
+        }
+    }
 }
 
 impl Task {
-    async fn fetch2_impl(&mut self, request: Request) -> Result<HttpResponse, DurableHttpError> {
-        let client = self.state.client();
-        let response = client.execute(request).await?;
+    fn http(&self) -> HttpSender<'_> {
+        HttpSender {
+            shared: self.state.shared(),
+            tenant: self.state.tenant(),
+        }
+    }
 
-        Ok(HttpResponse {
+    async fn open_event_stream_impl(
+        &mut self,
+        request: HttpRequestData,
+    ) -> Result<EventStreamData, DurableHttpError> {
+        const TEXT_EVENT_STREAM: HeaderValue = HeaderValue::from_static("text/event-stream");
+
+        let mut request = request.request;
+
+        // The stream can stay open for a long time so the timeout applies to
+        // each event instead of the whole response.
+        let timeout = request
+            .timeout_mut()
+            .take()
+            .unwrap_or(self.state.config().max_http_timeout);
+        let headers = request.headers_mut();
+        if !headers.contains_key(ACCEPT) {
+            headers.insert(ACCEPT, TEXT_EVENT_STREAM);
+        }
+
+        let shared = self.state.shared();
+        let response = shared.execute_http(&shared.client, request);
+        let response = match tokio::time::timeout(timeout, response).await {
+            Ok(response) => response?,
+            Err(_) => return Err(DurableHttpError::Timeout),
+        };
+
+        Ok(EventStreamData {
             status: response.status().as_u16(),
-            headers: response
-                .headers()
-                .iter()
-                .map(|(name, value)| HttpHeader {
-                    name: name.as_str().to_owned(),
-                    value: value.as_bytes().to_owned(),
-                })
-                .collect(),
-            body: response.bytes().await?.to_vec(),
+            headers: response.headers().clone(),
+            response: tokio::sync::Mutex::new(response),
+            parser: EventParser::default(),
+            done: false,
+            timeout,
+        })
+    }
+
+    /// Capture the details of `request` that are recorded in the audit log,
+    /// if auditing is enabled.
+    fn audit_request(&self, request: &Request) -> Option<(Method, Url)> {
+        self.state
+            .config()
+            .audit
+            .then(|| (request.method().clone(), request.url().clone()))
+    }
+
+    /// Record a request captured by [`audit_request`](Self::audit_request)
+    /// in the audit log for the current transaction.
+    fn record_request(
+        &mut self,
+        request: Option<(Method, Url)>,
+        status: Option<u16>,
+        redirects: &[String],
+    ) {
+        let Some((method, url)) = request else {
+            return;
+        };
+        let Some(txn) = self.state.transaction_mut() else {
+            return;
+        };
+
+        txn.audit.push(AuditRecord::http(&method, &url, status));
+        for redirect in redirects {
+            txn.audit.push(AuditRecord::redirect(redirect));
+        }
+    }
+
+    fn record_response(
+        &mut self,
+        request: Option<(Method, Url)>,
+        response: Option<&CachedResponse>,
+    ) {
+        match response {
+            Some(response) => {
+                self.record_request(request, Some(response.status), &response.redirects)
+            }
+            None => self.record_request(request, None, &[]),
+        }
+    }
+
+    fn http_response(response: &CachedResponse) -> HttpResponse {
+        HttpResponse {
+            status: response.status,
+            headers: http_headers(&response.headers),
+            body: response.body.clone(),
+        }
+    }
+}
+
+fn http_headers(headers: &HeaderMap) -> Vec<HttpHeader> {
+    headers
+        .iter()
+        .map(|(name, value)| HttpHeader {
+            name: name.as_str().to_owned(),
+            value: value.as_bytes().to_owned(),
         })
+        .collect()
+}
+
+async fn read_response(
+    response: reqwest::Response,
+    redirects: Vec<Url>,
+) -> Result<CachedResponse, DurableHttpError> {
+    Ok(CachedResponse {
+        status: response.status().as_u16(),
+        headers: response.headers().clone(),
+        body: response.bytes().await?.to_vec(),
+        redirects: redirects.into_iter().map(String::from).collect(),
+    })
+}
+
+/// Decode the response body according to its `Content-Encoding` header.
+///
+/// Bodies using encodings that we don't support are left as-is.
+fn decompress_body(response: &mut CachedResponse) -> Result<(), DurableHttpError> {
+    if response.body.is_empty() {
+        return Ok(());
+    }
+
+    let Some(encoding) = response.headers.get(CONTENT_ENCODING) else {
+        return Ok(());
+    };
+    let encoding = encoding.to_str().unwrap_or("").trim().to_ascii_lowercase();
+
+    let input = &response.body[..];
+    let mut body = Vec::new();
+    let result = match encoding.as_str() {
+        "gzip" | "x-gzip" => flate2::read::MultiGzDecoder::new(input).read_to_end(&mut body),
+        "deflate" => flate2::read::ZlibDecoder::new(input).read_to_end(&mut body),
+        "br" => brotli_decompressor::Decompressor::new(input, 4096).read_to_end(&mut body),
+        _ => return Ok(()),
+    };
+    result.map_err(DurableHttpError::Decompress)?;
+
+    response.body = body;
+    response.headers.remove(CONTENT_ENCODING);
+    response.headers.remove(CONTENT_LENGTH);
+    Ok(())
+}
+
+/// Get the URL that a response redirects to, if it is a redirect.
+fn redirect_target(response: &reqwest::Response) -> Option<Url> {
+    if !matches!(response.status().as_u16(), 301 | 302 | 303 | 307 | 308) {
+        return None;
+    }
+
+    let location = response.headers().get(LOCATION)?.to_str().ok()?;
+    response.url().join(location).ok()
+}
+
+fn remove_headers(headers: &mut HeaderMap, names: &[HeaderName]) {
+    for name in names {
+        headers.remove(name);
+    }
+}
+
+/// Whether a response with this status should be retried.
+///
+/// `501 Not Implemented` is not going to change on a retry so it is excluded.
+fn should_retry(status: u16) -> bool {
+    status == 429 || ((500..600).contains(&status) && status != 501)
+}
+
+/// Parse the `Retry-After` header of a response, if it has one.
+fn retry_after(response: &CachedResponse) -> Option<Duration> {
+    let value = response.headers.get("retry-after")?.to_str().ok()?.trim();
+
+    if let Ok(seconds) = value.parse() {
+        return Some(Duration::from_secs(seconds));
     }
+
+    let date = chrono::DateTime::parse_from_rfc2822(value).ok()?;
+    Some(
+        (date.to_utc() - chrono::Utc::now())
+            .to_std()
+            .unwrap_or_default(),
+    )
 }
 
 #[async_trait::async_trait]
@@ -129,7 +583,7 @@ impl HostHttpRequest2 for Task {
         let config = self.state.config();
 
         Ok(match HttpRequest2::new(method, url, config) {
-            Ok(request) => Ok(self.resources.insert(request)?),
+            Ok(request) => Ok(self.resources.insert(HttpRequestData::from(request))?),
             Err(e) => Err(self.resources.insert(e)?),
         })
     }
@@ -196,12 +650,71 @@ impl HostHttpRequest2 for Task {
         Ok(())
     }
 
+    async fn set_redirect(
+        &mut self,
+        res: Resource<HttpRequest2>,
+        policy: RedirectPolicy,
+    ) -> wasmtime::Result<()> {
+        let request = self.resources.get_mut(res)?;
+
+        request.redirect = Some(match policy {
+            RedirectPolicy::None => Redirects::Limited(0),
+            RedirectPolicy::Limited(max) => Redirects::Limited(max as usize),
+            RedirectPolicy::Follow => Redirects::Follow,
+        });
+        Ok(())
+    }
+
+    async fn set_decompress(
+        &mut self,
+        res: Resource<HttpRequest2>,
+        enabled: bool,
+    ) -> wasmtime::Result<()> {
+        let request = self.resources.get_mut(res)?;
+
+        request.decompress = enabled;
+        Ok(())
+    }
+
     async fn drop(&mut self, res: Resource<HttpRequest2>) -> wasmtime::Result<()> {
         self.resources.remove(res)?;
         Ok(())
     }
 }
 
+#[async_trait::async_trait]
+impl HostEventStream for Task {
+    async fn response(&mut self, res: Resource<EventStream>) -> wasmtime::Result<HttpResponse> {
+        let stream = self.resources.get(res)?;
+
+        Ok(HttpResponse {
+            status: stream.status,
+            headers: http_headers(&stream.headers),
+            body: Vec::new(),
+        })
+    }
+
+    async fn next(
+        &mut self,
+        res: Resource<EventStream>,
+    ) -> wasmtime::Result<Result<Option<SseEvent>, Resource<HttpError2>>> {
+        self.state
+            .assert_in_transaction("durable:http/event-stream.next")?;
+
+        let stream = self.resources.get_mut(res)?;
+
+        Ok(match stream.next().await {
+            Ok(event) => Ok(event),
+            Err(e) => Err(self.resources.insert(e)?),
+        })
+    }
+
+    async fn drop(&mut self, res: Resource<EventStream>) -> wasmtime::Result<()> {
+        self.resources.remove(res)?;
+        Ok(())
+    }
+}
+
 #[async_trait::async_trait]
 impl Host for Task {
     async fn fetch(
@@ -232,7 +745,20 @@ impl Host for Task {
             Err(e) => return Ok(Err(e.into())),
         };
 
-        Ok(self.fetch2_impl(request).await.map_err(From::from))
+        // The deprecated fetch function predates automatic decompression so we
+        // keep returning bodies exactly as they were received.
+        let request = HttpRequestData {
+            decompress: false,
+            ..HttpRequestData::from(request)
+        };
+
+        let audit = self.audit_request(&request);
+        let result = self.http().fetch2_impl(request).await;
+        self.record_response(audit, result.as_deref().ok());
+        Ok(match result {
+            Ok(response) => Ok(Self::http_response(&response)),
+            Err(e) => Err(e.into()),
+        })
     }
 
     async fn fetch2(
@@ -244,11 +770,110 @@ impl Host for Task {
 
         let request = self.resources.remove(request)?;
 
-        Ok(match self.fetch2_impl(request).await {
-            Ok(response) => Ok(response),
+        let audit = self.audit_request(&request);
+        let result = self.http().fetch2_impl(request).await;
+        self.record_response(audit, result.as_deref().ok());
+        Ok(match result {
+            Ok(response) => Ok(Self::http_response(&response)),
+            Err(e) => Err(self.resources.insert(e)?),
+        })
+    }
+
+    async fn fetch3(
+        &mut self,
+        request: Resource<HttpRequest2>,
+        retry: Option<RetryPolicy>,
+    ) -> wasmtime::Result<Result<FetchResponse, Resource<HttpError2>>> {
+        self.state
+            .assert_in_transaction("durable:http/http.fetch3")?;
+
+        let request = self.resources.remove(request)?;
+        let policy = retry.unwrap_or(RetryPolicy {
+            max_attempts: 1,
+            initial_backoff: 0,
+            max_backoff: 0,
+        });
+
+        let audit = self.audit_request(&request);
+        let result = self.http().fetch_with_retry(request, policy).await;
+        self.record_response(audit, result.as_ref().ok().map(|(response, _)| &**response));
+        Ok(match result {
+            Ok((response, attempts)) => Ok(FetchResponse {
+                response: Self::http_response(&response),
+                attempts,
+                redirects: response.redirects.clone(),
+            }),
+            Err(e) => Err(self.resources.insert(e)?),
+        })
+    }
+
+    async fn open_event_stream(
+        &mut self,
+        request: Resource<HttpRequest2>,
+    ) -> wasmtime::Result<Result<Resource<EventStream>, Resource<HttpError2>>> {
+        self.state
+            .assert_in_transaction("durable:http/http.open-event-stream")?;
+
+        let request = self.resources.remove(request)?;
+        let audit = self.audit_request(&request);
+        let result = self.open_event_stream_impl(request).await;
+        let status = result.as_ref().ok().map(|stream| stream.status);
+        self.record_request(audit, status, &[]);
+
+        // The stream is read from across multiple transactions so it can't be
+        // tied to this one.
+        Ok(match result {
+            Ok(stream) => Ok(self.resources.insert_detached(stream)?),
             Err(e) => Err(self.resources.insert(e)?),
         })
     }
+
+    async fn fetch_all(
+        &mut self,
+        requests: Vec<FetchAllRequest>,
+    ) -> wasmtime::Result<Vec<Result<FetchResponse, Resource<HttpError2>>>> {
+        self.state
+            .assert_in_transaction("durable:http/http.fetch-all")?;
+
+        let mut batch = Vec::with_capacity(requests.len());
+        for FetchAllRequest { request, retry } in requests {
+            let request = self.resources.remove(request)?;
+            let policy = retry.unwrap_or(RetryPolicy {
+                max_attempts: 1,
+                initial_backoff: 0,
+                max_backoff: 0,
+            });
+
+            batch.push((request, policy));
+        }
+
+        let audit: Vec<_> = batch
+            .iter()
+            .map(|(request, _)| self.audit_request(request))
+            .collect();
+
+        let http = self.http();
+        let results: Vec<_> = futures_util::stream::iter(batch)
+            .map(|(request, policy)| http.fetch_with_retry(request, policy))
+            .buffered(FETCH_ALL_CONCURRENCY)
+            .collect()
+            .await;
+
+        let mut responses = Vec::with_capacity(results.len());
+        for (result, audit) in results.into_iter().zip(audit) {
+            self.record_response(audit, result.as_ref().ok().map(|(response, _)| &**response));
+            responses.push(match result {
+                Ok((response, attempts)) => Ok(FetchResponse {
+                    response: Self::http_response(&response),
+                    attempts,
+                    redirects: response.redirects.clone(),
+                }),
+                Err(e) => Err(self.resources.insert(e)?),
+            });
+        }
+
+        Ok(responses)
+    }
 }
 
 impl From<reqwest::Error> for HttpError {
@@ -285,12 +910,15 @@ pub enum DurableHttpError {
     InvalidHeaderName(http::header::InvalidHeaderName),
     InvalidHeaderValue(http::header::InvalidHeaderValue),
     Reqwest(reqwest::Error),
+    Decompress(std::io::Error),
+    Timeout,
 }
 
 impl DurableHttpError {
     fn is_timeout(&self) -> bool {
         match self {
             Self::Reqwest(e) => e.is_timeout(),
+            Self::Timeout => true,
             _ => false,
         }
     }
@@ -298,6 +926,7 @@ impl DurableHttpError {
     fn is_builder(&self) -> bool {
         match self {
             Self::Reqwest(e) => e.is_builder(),
+            Self::Decompress(_) | Self::Timeout => false,
             _ => true,
         }
     }
@@ -356,6 +985,8 @@ impl From<DurableHttpError> for HttpError {
             DurableHttpError::InvalidUrl(err) => HttpError::InvalidUrl(err.to_string()),
             DurableHttpError::Reqwest(err) if err.is_timeout() => HttpError::Timeout,
             DurableHttpError::Reqwest(err) => HttpError::Other(err.to_string()),
+            DurableHttpError::Decompress(err) => HttpError::Other(err.to_string()),
+            DurableHttpError::Timeout => HttpError::Timeout,
         }
     }
 }
@@ -368,6 +999,8 @@ impl std::fmt::Display for DurableHttpError {
             Self::InvalidHeaderName(err) => err.fmt(f),
             Self::InvalidHeaderValue(err) => err.fmt(f),
             Self::Reqwest(err) => err.fmt(f),
+            Self::Decompress(err) => write!(f, "failed to decompress the response body: {err}"),
+            Self::Timeout => f.write_str("operation timed out"),
         }
     }
 }
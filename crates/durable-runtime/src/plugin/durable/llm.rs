@@ -0,0 +1,47 @@
+use crate::bindings::durable::core::llm::{Host, Provider};
+use crate::Task;
+
+/// The tokens used by a single LLM request.
+#[derive(Clone, Debug)]
+pub(crate) struct LlmUsage {
+    pub provider: String,
+    pub model: String,
+    pub prompt_tokens: u32,
+    pub completion_tokens: u32,
+}
+
+#[async_trait::async_trait]
+impl Host for Task {
+    async fn get_provider(&mut self, name: String) -> wasmtime::Result<Option<Provider>> {
+        let Some(provider) = self.state.config().llm_providers.get(&name) else {
+            return Ok(None);
+        };
+
+        Ok(Some(Provider {
+            base_url: provider.base_url.trim_end_matches('/').to_owned(),
+            api_key: provider.api_key.clone(),
+            default_model: provider.default_model.clone(),
+        }))
+    }
+
+    async fn record_usage(
+        &mut self,
+        provider: String,
+        model: String,
+        prompt_tokens: u32,
+        completion_tokens: u32,
+    ) -> wasmtime::Result<()> {
+        let txn = self
+            .state
+            .assert_in_transaction("durable:core/llm.record-usage")?;
+
+        txn.llm_usage.push(LlmUsage {
+            provider,
+            model,
+            prompt_tokens,
+            completion_tokens,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,153 @@
+use chrono::{TimeDelta, Utc};
+
+use crate::bindings::durable::core::lock::{AcquireResult, Host};
+use crate::task::TransactionOptions;
+use crate::Task;
+
+#[async_trait::async_trait]
+impl Host for Task {
+    async fn try_acquire(
+        &mut self,
+        name: String,
+        permits: u32,
+        ttl: Option<u64>,
+    ) -> wasmtime::Result<AcquireResult> {
+        if self.state.transaction().is_some() {
+            anyhow::bail!(
+                "durable:core/lock.try-acquire cannot be called from within a transaction"
+            );
+        }
+
+        let options = TransactionOptions::new("durable:core/lock.try-acquire").database(true);
+        if let Some(result) = self.state.enter::<Option<Option<u64>>>(options).await? {
+            return Ok(match result {
+                None => AcquireResult::Acquired,
+                Some(retry) => AcquireResult::Held(retry),
+            });
+        }
+
+        let task_id = self.state.task_id();
+        let schema = self.state.shared().schema.clone();
+        let txn = self.state.transaction_mut().unwrap();
+        let tx = txn.conn().unwrap();
+
+        let now = Utc::now();
+        // A ttl too large to be represented is the same as no ttl at all.
+        let expires_at = ttl
+            .and_then(|ttl| i64::try_from(ttl).ok())
+            .and_then(TimeDelta::try_milliseconds)
+            .and_then(|ttl| now.checked_add_signed(ttl));
+
+        // Serialize all acquisitions of the same lock. Without this two tasks
+        // could both observe a free permit and take it.
+        sqlx::query("SELECT pg_advisory_xact_lock(hashtextextended('durable:lock:' || $1, 0))")
+            .bind(&name)
+            .execute(&mut **tx)
+            .await?;
+
+        // Remove ourselves from the wait list first so that cleaning up expired
+        // leases doesn't send us a notification.
+        sqlx::query!(
+            "DELETE FROM durable.lock_waiter WHERE name = $1 AND task_id = $2",
+            name,
+            task_id
+        )
+        .execute(schema.on(&mut **tx))
+        .await?;
+
+        sqlx::query!(
+            "DELETE FROM durable.lock_lease WHERE name = $1 AND expires_at <= $2",
+            name,
+            now
+        )
+        .execute(schema.on(&mut **tx))
+        .await?;
+
+        let acquired = sqlx::query_scalar!(
+            r#"
+            INSERT INTO durable.lock_lease(name, task_id, acquired_at, expires_at)
+            SELECT $1::text, $2::bigint, $3::timestamptz, $4::timestamptz
+            WHERE (
+                SELECT COUNT(*)
+                 FROM durable.lock_lease
+                WHERE name = $1
+                  AND task_id != $2
+            ) < $5
+            ON CONFLICT (name, task_id) DO UPDATE
+                SET expires_at = EXCLUDED.expires_at
+            RETURNING task_id
+            "#,
+            name,
+            task_id,
+            now,
+            expires_at,
+            i64::from(permits)
+        )
+        .fetch_optional(schema.on(&mut **tx))
+        .await?;
+
+        let result = match acquired {
+            Some(_) => None,
+            None => {
+                sqlx::query!(
+                    "INSERT INTO durable.lock_waiter(name, task_id) VALUES ($1, $2)",
+                    name,
+                    task_id
+                )
+                .execute(schema.on(&mut **tx))
+                .await?;
+
+                let expiry = sqlx::query_scalar!(
+                    "SELECT MIN(expires_at) FROM durable.lock_lease WHERE name = $1",
+                    name
+                )
+                .fetch_one(schema.on(&mut **tx))
+                .await?;
+
+                // Round up so that the task doesn't wake up just before the
+                // lease actually expires.
+                let retry = expiry.map(|expiry| {
+                    let delta = expiry.signed_duration_since(now) + TimeDelta::microseconds(999);
+                    u64::try_from(delta.num_milliseconds()).unwrap_or(0)
+                });
+
+                Some(retry)
+            }
+        };
+
+        self.state.exit(&result).await?;
+
+        Ok(match result {
+            None => AcquireResult::Acquired,
+            Some(retry) => AcquireResult::Held(retry),
+        })
+    }
+
+    async fn release(&mut self, name: String) -> wasmtime::Result<()> {
+        if self.state.transaction().is_some() {
+            anyhow::bail!("durable:core/lock.release cannot be called from within a transaction");
+        }
+
+        let options = TransactionOptions::new("durable:core/lock.release").database(true);
+        if self.state.enter::<()>(options).await?.is_some() {
+            return Ok(());
+        }
+
+        let task_id = self.state.task_id();
+        let schema = self.state.shared().schema.clone();
+        let txn = self.state.transaction_mut().unwrap();
+        let tx = txn.conn().unwrap();
+
+        sqlx::query!(
+            "DELETE FROM durable.lock_lease WHERE name = $1 AND task_id = $2",
+            name,
+            task_id
+        )
+        .execute(schema.on(&mut **tx))
+        .await?;
+
+        self.state.exit(&()).await?;
+
+        Ok(())
+    }
+}
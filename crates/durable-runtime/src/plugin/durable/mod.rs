@@ -1,6 +1,17 @@
 //! Plugins for built-in runtime functionality.
 
+mod capabilities;
 mod core;
+mod email;
 mod http;
+pub(crate) mod llm;
+mod lock;
+pub(crate) mod mq;
 mod notify;
-mod sql;
+pub(crate) mod object_store;
+mod panic;
+mod ratelimit;
+mod scheduler;
+pub(crate) mod sql;
+mod tasks;
+pub mod version;
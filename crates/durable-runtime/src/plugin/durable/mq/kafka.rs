@@ -0,0 +1,64 @@
+use std::time::Duration;
+
+use rdkafka::message::{Header as KafkaHeader, OwnedHeaders};
+use rdkafka::producer::{FutureProducer, FutureRecord};
+use rdkafka::util::Timeout;
+use rdkafka::ClientConfig;
+
+use super::IDEMPOTENCY_HEADER;
+use crate::bindings::durable::core::mq::{Header, MqError};
+use crate::config::KafkaConfig;
+
+pub(crate) struct KafkaPublisher {
+    producer: FutureProducer,
+}
+
+impl KafkaPublisher {
+    pub(super) fn connect(config: &KafkaConfig) -> Result<Self, MqError> {
+        let mut client = ClientConfig::new();
+        client
+            .set("bootstrap.servers", &config.brokers)
+            .set("enable.idempotence", "true");
+        for (key, value) in &config.options {
+            client.set(key, value);
+        }
+
+        let producer = client
+            .create()
+            .map_err(|e| MqError::Publish(format!("failed to create a kafka producer: {e}")))?;
+
+        Ok(Self { producer })
+    }
+
+    pub(super) async fn publish(
+        &self,
+        topic: String,
+        payload: Vec<u8>,
+        headers: Vec<Header>,
+        key: &str,
+        timeout: Duration,
+    ) -> Result<(), MqError> {
+        let mut kafka_headers = OwnedHeaders::new_with_capacity(headers.len() + 1);
+        for header in &headers {
+            kafka_headers = kafka_headers.insert(KafkaHeader {
+                key: &header.name,
+                value: Some(&header.value),
+            });
+        }
+        kafka_headers = kafka_headers.insert(KafkaHeader {
+            key: IDEMPOTENCY_HEADER,
+            value: Some(key),
+        });
+
+        let record = FutureRecord::<(), _>::to(&topic)
+            .payload(&payload)
+            .headers(kafka_headers);
+
+        self.producer
+            .send(record, Timeout::After(timeout))
+            .await
+            .map_err(|(e, _)| MqError::Publish(e.to_string()))?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,95 @@
+use std::time::Duration;
+
+use crate::bindings::durable::core::mq::*;
+use crate::config::MqConfig;
+use crate::Task;
+
+#[cfg(feature = "kafka")]
+mod kafka;
+#[cfg(feature = "nats")]
+mod nats;
+
+/// The header containing the idempotency key of each published message.
+#[cfg(any(feature = "kafka", feature = "nats"))]
+const IDEMPOTENCY_HEADER: &str = "durable-idempotency-key";
+
+/// A connection to the message queue configured on the worker.
+///
+/// This is created the first time that a workflow publishes a message and is
+/// then shared by all tasks on the worker.
+pub(crate) enum Publisher {
+    #[cfg(feature = "kafka")]
+    Kafka(kafka::KafkaPublisher),
+    #[cfg(feature = "nats")]
+    Nats(Box<nats::NatsPublisher>),
+}
+
+impl Publisher {
+    async fn connect(config: &MqConfig) -> Result<Self, MqError> {
+        match config {
+            #[cfg(feature = "kafka")]
+            MqConfig::Kafka(config) => kafka::KafkaPublisher::connect(config).map(Self::Kafka),
+            #[cfg(feature = "nats")]
+            MqConfig::Nats(config) => nats::NatsPublisher::connect(config)
+                .await
+                .map(|nats| Self::Nats(Box::new(nats))),
+
+            #[allow(unreachable_patterns)]
+            config => Err(MqError::Unsupported(format!(
+                "the worker was built without the `{}` feature",
+                match config {
+                    MqConfig::Kafka(_) => "kafka",
+                    MqConfig::Nats(_) => "nats",
+                }
+            ))),
+        }
+    }
+
+    #[allow(unused_variables)]
+    async fn publish(
+        &self,
+        topic: String,
+        payload: Vec<u8>,
+        headers: Vec<Header>,
+        key: &str,
+        timeout: Duration,
+    ) -> Result<(), MqError> {
+        match *self {
+            #[cfg(feature = "kafka")]
+            Self::Kafka(ref kafka) => kafka.publish(topic, payload, headers, key, timeout).await,
+            #[cfg(feature = "nats")]
+            Self::Nats(ref nats) => nats.publish(topic, payload, headers, key, timeout).await,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl Host for Task {
+    async fn publish(
+        &mut self,
+        topic: String,
+        payload: Vec<u8>,
+        headers: Vec<Header>,
+    ) -> wasmtime::Result<Result<(), MqError>> {
+        let txn = self
+            .state
+            .assert_in_transaction("durable:core/mq.publish")?;
+        let index = txn.index();
+        let key = format!("{}-{index}", self.state.task_id());
+
+        let shared = self.state.shared();
+        let config = &shared.config;
+        let Some(mq) = &config.mq else {
+            return Ok(Err(MqError::NotConfigured));
+        };
+
+        let publisher = match shared.mq.get_or_try_init(|| Publisher::connect(mq)).await {
+            Ok(publisher) => publisher,
+            Err(e) => return Ok(Err(e)),
+        };
+
+        Ok(publisher
+            .publish(topic, payload, headers, &key, config.max_http_timeout)
+            .await)
+    }
+}
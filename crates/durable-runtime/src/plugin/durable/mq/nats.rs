@@ -0,0 +1,81 @@
+use std::time::Duration;
+
+use async_nats::jetstream;
+
+use super::IDEMPOTENCY_HEADER;
+use crate::bindings::durable::core::mq::{Header, MqError};
+use crate::config::NatsConfig;
+
+pub(crate) struct NatsPublisher {
+    client: async_nats::Client,
+    jetstream: Option<jetstream::Context>,
+}
+
+impl NatsPublisher {
+    pub(super) async fn connect(config: &NatsConfig) -> Result<Self, MqError> {
+        let mut options = async_nats::ConnectOptions::new();
+        if let Some(token) = &config.token {
+            options = options.token(token.clone());
+        }
+
+        let client = options
+            .connect(config.url.as_str())
+            .await
+            .map_err(|e| MqError::Publish(format!("failed to connect to nats: {e}")))?;
+        let jetstream = config.jetstream.then(|| jetstream::new(client.clone()));
+
+        Ok(Self { client, jetstream })
+    }
+
+    pub(super) async fn publish(
+        &self,
+        topic: String,
+        payload: Vec<u8>,
+        headers: Vec<Header>,
+        key: &str,
+        timeout: Duration,
+    ) -> Result<(), MqError> {
+        let mut map = async_nats::HeaderMap::new();
+        for header in headers {
+            let value = String::from_utf8(header.value).map_err(|_| {
+                MqError::InvalidMessage(format!(
+                    "the value of header `{}` is not valid UTF-8",
+                    header.name
+                ))
+            })?;
+
+            map.append(header.name, value);
+        }
+        map.insert(IDEMPOTENCY_HEADER, key);
+
+        let publish = async {
+            match &self.jetstream {
+                Some(jetstream) => {
+                    map.insert("Nats-Msg-Id", key);
+                    jetstream
+                        .publish_with_headers(topic, map, payload.into())
+                        .await
+                        .map_err(|e| MqError::Publish(e.to_string()))?
+                        .await
+                        .map_err(|e| MqError::Publish(e.to_string()))?;
+                }
+                None => {
+                    self.client
+                        .publish_with_headers(topic, map, payload.into())
+                        .await
+                        .map_err(|e| MqError::Publish(e.to_string()))?;
+                    self.client
+                        .flush()
+                        .await
+                        .map_err(|e| MqError::Publish(e.to_string()))?;
+                }
+            }
+
+            Ok(())
+        };
+
+        tokio::time::timeout(timeout, publish)
+            .await
+            .map_err(|_| MqError::Publish("timed out while publishing the message".into()))?
+    }
+}
@@ -5,6 +5,7 @@ use tokio::sync::broadcast::error::RecvError;
 use tokio::time::Instant;
 
 use crate::bindings::durable::core::notify::{Event, Host, NotifyError};
+use crate::bindings::wasi::clocks::wall_clock::Datetime;
 use crate::task::TransactionOptions;
 use crate::{Task, TaskStatus};
 
@@ -31,32 +32,36 @@ async fn poll_notification(
         "#,
         task.state.task_id()
     )
-    .fetch_optional(&mut *tx)
+    .fetch_optional(task.state.shared().schema.on(&mut *tx))
     .await?;
 
     Ok(data)
 }
 
-#[async_trait::async_trait]
-impl Host for Task {
-    async fn notification_blocking(&mut self) -> wasmtime::Result<Event> {
-        if self.state.transaction().is_some() {
-            anyhow::bail!(
-                "durable:core/notify.notification-blocking cannot be called from within a \
-                 transaction"
-            );
-        }
-
-        let options = TransactionOptions::new("durable:core/notify.notification-blocking");
-        if let Some(event) = self.state.enter::<EventData>(options).await? {
-            return Ok(event.into());
-        }
+/// Why a task waiting for a notification woke up.
+enum Woken {
+    Notified,
+    Wakeup,
+    Suspend,
+    Closed,
+}
 
+impl Task {
+    /// Wait for the next notification for this task.
+    ///
+    /// If `wakeup_at` is set then this returns `None` once that time has passed
+    /// without a notification arriving. Otherwise, this waits indefinitely.
+    async fn wait_for_notification(
+        &mut self,
+        wakeup_at: Option<DateTime<Utc>>,
+    ) -> anyhow::Result<Option<EventData>> {
         let deadline = Instant::now() + self.state.config().suspend_timeout;
         let task_id = self.state.task_id();
+        let simulated = self.state.is_simulated();
+        let shared = self.state.shared().clone();
         let mut rx = self.state.subscribe_notifications();
 
-        let data = loop {
+        loop {
             let mut tx = self.state.pool().begin().await?;
             let data = poll_notification(&mut *self, &mut tx).await?;
 
@@ -64,61 +69,133 @@ impl Host for Task {
                 let txn = self.state.transaction_mut().unwrap();
                 txn.set_conn(tx)?;
 
-                break data;
+                return Ok(Some(data));
             }
 
             tx.rollback().await?;
 
-            'inner: loop {
-                tokio::select! {
-                    biased;
+            if let Some(wakeup_at) = wakeup_at {
+                if wakeup_at <= self.state.now() {
+                    return Ok(None);
+                }
+            }
 
-                    result = rx.recv() => match result {
-                        Ok(notif) if notif.task_id == task_id => break 'inner,
-                        Ok(_) => continue 'inner,
-                        Err(RecvError::Lagged(_)) => break 'inner,
-                        Err(RecvError::Closed) => {
-                            return Err(anyhow::Error::new(TaskStatus::NotScheduledOnWorker))
+            let woken = self
+                .state
+                .blocking(async {
+                    loop {
+                        tokio::select! {
+                            biased;
+
+                            result = rx.recv() => match result {
+                                Ok(notif) if notif.task_id == task_id => break Woken::Notified,
+                                Ok(_) => continue,
+                                Err(RecvError::Lagged(_)) => break Woken::Notified,
+                                Err(RecvError::Closed) => break Woken::Closed,
+                            },
+                            _ = shared.sleep_until(wakeup_at.unwrap_or(DateTime::<Utc>::MAX_UTC)),
+                                if wakeup_at.is_some() => break Woken::Wakeup,
+                            // Simulated tasks are never suspended since their
+                            // wakeup time is on the virtual clock.
+                            _ = tokio::time::sleep_until(deadline), if !simulated => {
+                                break Woken::Suspend
+                            }
                         }
-                    },
-                    _ = tokio::time::sleep_until(deadline) => ()
-                }
+                    }
+                })
+                .await;
+
+            match woken {
+                Woken::Notified | Woken::Wakeup => continue,
+                Woken::Closed => return Err(anyhow::Error::new(TaskStatus::NotScheduledOnWorker)),
+                Woken::Suspend => (),
+            }
 
-                // The timer expired, so we need to attempt to suspend.
-                let mut tx = self.state.pool().begin().await?;
+            // The timer expired, so we need to attempt to suspend.
+            let mut tx = self.state.pool().begin().await?;
 
-                sqlx::query!(
-                    "UPDATE durable.task
+            sqlx::query!(
+                "UPDATE durable.task
                       SET state = 'suspended',
-                          running_on = NULL
+                          running_on = NULL,
+                          wakeup_at = $2
                     WHERE id = $1
                     ",
-                    self.task_id()
-                )
-                .execute(&mut *tx)
-                .await?;
-
-                if poll_notification(&mut *self, &mut tx).await?.is_some() {
-                    // A new notification barged in while we were updating. Roll back the
-                    // transaction and go through the main loop again.
-                    tx.rollback().await?;
-                    break 'inner;
-                }
-
-                // At this point the lock on the current task will block any
-                // competing transactions until after we are completely
-                // suspended.
-                tx.commit().await?;
+                self.task_id(),
+                wakeup_at
+            )
+            .execute(self.state.shared().schema.on(&mut *tx))
+            .await?;
 
-                return Err(anyhow::Error::new(TaskStatus::Suspend));
+            if poll_notification(&mut *self, &mut tx).await?.is_some() {
+                // A new notification barged in while we were updating. Roll back the
+                // transaction and go through the main loop again.
+                tx.rollback().await?;
+                continue;
             }
-        };
+
+            // At this point the lock on the current task will block any
+            // competing transactions until after we are completely
+            // suspended.
+            tx.commit().await?;
+
+            return Err(anyhow::Error::new(TaskStatus::Suspend));
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl Host for Task {
+    async fn notification_blocking(&mut self) -> wasmtime::Result<Event> {
+        if self.state.transaction().is_some() {
+            anyhow::bail!(
+                "durable:core/notify.notification-blocking cannot be called from within a \
+                 transaction"
+            );
+        }
+
+        let options = TransactionOptions::new("durable:core/notify.notification-blocking");
+        if let Some(event) = self.state.enter::<EventData>(options).await? {
+            return Ok(event.into());
+        }
+
+        let data = self
+            .wait_for_notification(None)
+            .await?
+            .expect("waiting for a notification without a wakeup time returned no notification");
 
         self.exit(&data).await?;
 
         Ok(data.into())
     }
 
+    async fn notification_blocking_until(
+        &mut self,
+        deadline: Datetime,
+    ) -> wasmtime::Result<Option<Event>> {
+        if self.state.transaction().is_some() {
+            anyhow::bail!(
+                "durable:core/notify.notification-blocking-until cannot be called from within a \
+                 transaction"
+            );
+        }
+
+        let options = TransactionOptions::new("durable:core/notify.notification-blocking-until");
+        if let Some(event) = self.state.enter::<Option<EventData>>(options).await? {
+            return Ok(event.map(From::from));
+        }
+
+        let wakeup_at = i64::try_from(deadline.seconds)
+            .ok()
+            .and_then(|seconds| DateTime::from_timestamp(seconds, deadline.nanoseconds))
+            .unwrap_or(DateTime::<Utc>::MAX_UTC);
+        let data = self.wait_for_notification(Some(wakeup_at)).await?;
+
+        self.exit(&data).await?;
+
+        Ok(data.map(From::from))
+    }
+
     async fn notify(
         &mut self,
         task: i64,
@@ -134,6 +211,7 @@ impl Host for Task {
             return Ok(result);
         }
 
+        let schema = self.state.shared().schema.clone();
         let txn = self.state.transaction_mut().unwrap();
         let tx = txn.conn().unwrap();
 
@@ -153,7 +231,7 @@ impl Host for Task {
                 "#,
                 task
             )
-            .fetch_optional(&mut **tx)
+            .fetch_optional(schema.on(&mut **tx))
             .await?;
 
             match state {
@@ -173,7 +251,7 @@ impl Host for Task {
                 event,
                 Json(json) as Json<&RawValue>
             )
-            .execute(&mut **tx)
+            .execute(schema.on(&mut **tx))
             .await;
 
             match result {
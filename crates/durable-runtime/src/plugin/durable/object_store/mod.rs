@@ -0,0 +1,397 @@
+use std::time::Duration;
+
+use chrono::Utc;
+use reqwest::{Method, StatusCode};
+use url::Url;
+
+use crate::bindings::durable::core::object_store::*;
+use crate::util::sigv4::{self, Signer};
+use crate::{ObjectStoreConfig, Task};
+
+mod xml;
+
+/// The longest expiry that S3 allows for a presigned URL.
+const MAX_PRESIGN_EXPIRY: u64 = 7 * 24 * 3600;
+
+pub(crate) struct ObjectStore<'a> {
+    config: &'a ObjectStoreConfig,
+    client: &'a reqwest::Client,
+    timeout: Duration,
+}
+
+impl<'a> ObjectStore<'a> {
+    pub(crate) fn new(
+        config: &'a ObjectStoreConfig,
+        client: &'a reqwest::Client,
+        timeout: Duration,
+    ) -> Self {
+        Self {
+            config,
+            client,
+            timeout,
+        }
+    }
+
+    fn signer(&self) -> Signer<'a> {
+        Signer {
+            access_key_id: &self.config.access_key_id,
+            secret_access_key: &self.config.secret_access_key,
+            session_token: self.config.session_token.as_deref(),
+            region: &self.config.region,
+            service: "s3",
+        }
+    }
+
+    /// Build the URL for `key` within `bucket`.
+    fn url(&self, bucket: &str, key: &str) -> Result<Url, ObjectStoreError> {
+        if bucket.is_empty() || bucket.contains(['/', '?', '#']) {
+            return Err(ObjectStoreError::Request(format!(
+                "`{bucket}` is not a valid bucket name"
+            )));
+        }
+
+        let mut url = Url::parse(&self.config.endpoint).map_err(|e| {
+            ObjectStoreError::Request(format!("the object store endpoint is not a valid URL: {e}"))
+        })?;
+        let base = url.path().trim_end_matches('/').to_owned();
+
+        let path = if self.config.path_style {
+            format!("{base}/{bucket}/{key}")
+        } else {
+            let host = format!("{bucket}.{}", url.host_str().unwrap_or_default());
+            url.set_host(Some(&host)).map_err(|e| {
+                ObjectStoreError::Request(format!("`{bucket}` is not a valid bucket name: {e}"))
+            })?;
+
+            format!("{base}/{key}")
+        };
+
+        url.set_path(&sigv4::encode_path(&path));
+        Ok(url)
+    }
+
+    async fn send(
+        &self,
+        method: Method,
+        url: Url,
+        body: Vec<u8>,
+        content_type: Option<String>,
+    ) -> Result<reqwest::Response, ObjectStoreError> {
+        let now = Utc::now();
+        let amz_date = now.format("%Y%m%dT%H%M%SZ").to_string();
+        let payload_hash = sigv4::sha256_hex(&body);
+        let host = sigv4::host(&url);
+
+        let mut headers = vec![
+            ("host", host.as_str()),
+            ("x-amz-content-sha256", payload_hash.as_str()),
+            ("x-amz-date", amz_date.as_str()),
+        ];
+        if let Some(token) = &self.config.session_token {
+            headers.push(("x-amz-security-token", token));
+        }
+
+        let authorization =
+            self.signer()
+                .authorization(method.as_str(), &url, &headers, &payload_hash, now);
+
+        let mut request = self
+            .client
+            .request(method, url.clone())
+            .timeout(self.timeout)
+            .header(reqwest::header::AUTHORIZATION, authorization);
+        for (name, value) in headers {
+            // reqwest sets the host header itself, based on the URL.
+            if name != "host" {
+                request = request.header(name, value);
+            }
+        }
+        if let Some(content_type) = content_type {
+            request = request.header(reqwest::header::CONTENT_TYPE, content_type);
+        }
+
+        let response = request
+            .body(body)
+            .send()
+            .await
+            .map_err(|e| ObjectStoreError::Request(e.to_string()))?;
+
+        let status = response.status();
+        if status.is_success() {
+            return Ok(response);
+        }
+
+        let body = response.text().await.unwrap_or_default();
+        let code = xml::text(&body, "Code").map(|code| code.into_owned());
+        if status == StatusCode::NOT_FOUND && code.as_deref() == Some("NoSuchKey") {
+            return Err(ObjectStoreError::NotFound);
+        }
+
+        let message = match xml::text(&body, "Message") {
+            Some(message) => message.into_owned(),
+            None => status
+                .canonical_reason()
+                .unwrap_or("unknown error")
+                .to_owned(),
+        };
+
+        Err(ObjectStoreError::Service(ServiceError {
+            status: status.as_u16(),
+            code,
+            message,
+        }))
+    }
+
+    async fn get(&self, bucket: &str, key: &str) -> Result<Vec<u8>, ObjectStoreError> {
+        let max = self.config.max_object_size;
+        let too_large = || ObjectStoreError::TooLarge(max.try_into().unwrap_or(u64::MAX));
+
+        let url = self.url(bucket, key)?;
+        let mut response = self.send(Method::GET, url, Vec::new(), None).await?;
+
+        let len = response.content_length().unwrap_or(0);
+        if len > max.try_into().unwrap_or(u64::MAX) {
+            return Err(too_large());
+        }
+
+        let mut body = Vec::with_capacity(len as usize);
+        while let Some(chunk) = response
+            .chunk()
+            .await
+            .map_err(|e| ObjectStoreError::Request(e.to_string()))?
+        {
+            if body.len() + chunk.len() > max {
+                return Err(too_large());
+            }
+
+            body.extend_from_slice(&chunk);
+        }
+
+        Ok(body)
+    }
+
+    pub(crate) async fn put(
+        &self,
+        bucket: &str,
+        key: &str,
+        body: Vec<u8>,
+        content_type: Option<String>,
+    ) -> Result<(), ObjectStoreError> {
+        let url = self.url(bucket, key)?;
+        self.send(Method::PUT, url, body, content_type).await?;
+        Ok(())
+    }
+
+    async fn delete(&self, bucket: &str, key: &str) -> Result<(), ObjectStoreError> {
+        let url = self.url(bucket, key)?;
+        match self.send(Method::DELETE, url, Vec::new(), None).await {
+            Ok(_) | Err(ObjectStoreError::NotFound) => Ok(()),
+            Err(e) => Err(e),
+        }
+    }
+
+    async fn list(
+        &self,
+        bucket: &str,
+        prefix: Option<&str>,
+        token: Option<&str>,
+    ) -> Result<ListPage, ObjectStoreError> {
+        let mut url = self.url(bucket, "")?;
+        let mut params = vec![("list-type", "2")];
+        if let Some(prefix) = prefix {
+            params.push(("prefix", prefix));
+        }
+        if let Some(token) = token {
+            params.push(("continuation-token", token));
+        }
+        url.set_query(Some(&sigv4::encode_query(params)));
+
+        let response = self.send(Method::GET, url, Vec::new(), None).await?;
+        let body = response
+            .text()
+            .await
+            .map_err(|e| ObjectStoreError::Request(e.to_string()))?;
+
+        let objects = xml::elements(&body, "Contents")
+            .map(|contents| ObjectInfo {
+                key: xml::text(contents, "Key").unwrap_or_default().into_owned(),
+                size: xml::text(contents, "Size")
+                    .and_then(|size| size.parse().ok())
+                    .unwrap_or(0),
+                etag: xml::text(contents, "ETag").map(|etag| etag.into_owned()),
+                last_modified: xml::text(contents, "LastModified").map(|time| time.into_owned()),
+            })
+            .collect();
+
+        let truncated = xml::text(&body, "IsTruncated").as_deref() == Some("true");
+        let next_token = xml::text(&body, "NextContinuationToken")
+            .filter(|_| truncated)
+            .map(|token| token.into_owned());
+
+        Ok(ListPage {
+            objects,
+            next_token,
+        })
+    }
+
+    fn presign(
+        &self,
+        bucket: &str,
+        key: &str,
+        method: PresignMethod,
+        expires_in: u64,
+    ) -> Result<String, ObjectStoreError> {
+        if expires_in > MAX_PRESIGN_EXPIRY {
+            return Err(ObjectStoreError::Request(format!(
+                "presigned URLs cannot expire more than {MAX_PRESIGN_EXPIRY} seconds in the future"
+            )));
+        }
+
+        let method = match method {
+            PresignMethod::Get => "GET",
+            PresignMethod::Put => "PUT",
+        };
+
+        let mut url = self.url(bucket, key)?;
+        self.signer()
+            .presign(method, &mut url, expires_in, Utc::now());
+
+        Ok(url.into())
+    }
+}
+
+impl Task {
+    fn object_store(&self) -> Result<ObjectStore<'_>, ObjectStoreError> {
+        let config = self.state.config();
+
+        Ok(ObjectStore::new(
+            config
+                .object_store
+                .as_ref()
+                .ok_or(ObjectStoreError::NotConfigured)?,
+            self.state.client(),
+            config.max_http_timeout,
+        ))
+    }
+}
+
+#[async_trait::async_trait]
+impl Host for Task {
+    async fn get(
+        &mut self,
+        bucket: String,
+        key: String,
+    ) -> wasmtime::Result<Result<Vec<u8>, ObjectStoreError>> {
+        self.state
+            .assert_in_transaction("durable:core/object-store.get")?;
+
+        Ok(match self.object_store() {
+            Ok(store) => store.get(&bucket, &key).await,
+            Err(e) => Err(e),
+        })
+    }
+
+    async fn put(
+        &mut self,
+        bucket: String,
+        key: String,
+        body: Vec<u8>,
+        content_type: Option<String>,
+    ) -> wasmtime::Result<Result<(), ObjectStoreError>> {
+        self.state
+            .assert_in_transaction("durable:core/object-store.put")?;
+
+        Ok(match self.object_store() {
+            Ok(store) => store.put(&bucket, &key, body, content_type).await,
+            Err(e) => Err(e),
+        })
+    }
+
+    async fn list_objects(
+        &mut self,
+        bucket: String,
+        prefix: Option<String>,
+        token: Option<String>,
+    ) -> wasmtime::Result<Result<ListPage, ObjectStoreError>> {
+        self.state
+            .assert_in_transaction("durable:core/object-store.list-objects")?;
+
+        Ok(match self.object_store() {
+            Ok(store) => {
+                store
+                    .list(&bucket, prefix.as_deref(), token.as_deref())
+                    .await
+            }
+            Err(e) => Err(e),
+        })
+    }
+
+    async fn delete(
+        &mut self,
+        bucket: String,
+        key: String,
+    ) -> wasmtime::Result<Result<(), ObjectStoreError>> {
+        self.state
+            .assert_in_transaction("durable:core/object-store.delete")?;
+
+        Ok(match self.object_store() {
+            Ok(store) => store.delete(&bucket, &key).await,
+            Err(e) => Err(e),
+        })
+    }
+
+    async fn presign(
+        &mut self,
+        bucket: String,
+        key: String,
+        method: PresignMethod,
+        expires_in: u64,
+    ) -> wasmtime::Result<Result<String, ObjectStoreError>> {
+        self.state
+            .assert_in_transaction("durable:core/object-store.presign")?;
+
+        Ok(self
+            .object_store()
+            .and_then(|store| store.presign(&bucket, &key, method, expires_in)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn url(config: &ObjectStoreConfig, bucket: &str, key: &str) -> String {
+        let client = reqwest::Client::new();
+        let store = ObjectStore {
+            config,
+            client: &client,
+            timeout: Duration::from_secs(1),
+        };
+
+        match store.url(bucket, key) {
+            Ok(url) => url.into(),
+            Err(e) => panic!("failed to build url: {e:?}"),
+        }
+    }
+
+    #[test]
+    fn object_urls() {
+        let config =
+            ObjectStoreConfig::new("https://s3.us-east-1.amazonaws.com", "us-east-1", "", "");
+        assert_eq!(
+            url(&config, "bucket", "a b/c.txt"),
+            "https://bucket.s3.us-east-1.amazonaws.com/a%20b/c.txt"
+        );
+
+        let config = ObjectStoreConfig::new("http://localhost:9000/s3/", "us-east-1", "", "")
+            .path_style(true);
+        assert_eq!(
+            url(&config, "bucket", "a+b.txt"),
+            "http://localhost:9000/s3/bucket/a%2Bb.txt"
+        );
+        assert_eq!(
+            url(&config, "bucket", ""),
+            "http://localhost:9000/s3/bucket/"
+        );
+    }
+}
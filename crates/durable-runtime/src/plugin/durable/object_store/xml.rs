@@ -0,0 +1,110 @@
+//! Just enough XML parsing to read S3 responses.
+//!
+//! S3 responses are simple, non-recursive documents so rather than pull in a
+//! full XML parser we just search for the elements that we need.
+
+use std::borrow::Cow;
+
+/// Iterate over the contents of each `<tag>` element within `xml`.
+pub(super) fn elements<'a>(xml: &'a str, tag: &str) -> impl Iterator<Item = &'a str> {
+    let open = format!("<{tag}>");
+    let close = format!("</{tag}>");
+    let mut rest = xml;
+
+    std::iter::from_fn(move || {
+        let start = rest.find(&open)? + open.len();
+        let len = rest[start..].find(&close)?;
+        let content = &rest[start..start + len];
+        rest = &rest[start + len + close.len()..];
+        Some(content)
+    })
+}
+
+/// Get the unescaped text of the first `<tag>` element within `xml`.
+pub(super) fn text<'a>(xml: &'a str, tag: &str) -> Option<Cow<'a, str>> {
+    elements(xml, tag).next().map(unescape)
+}
+
+/// Replace XML entity references in `text`.
+pub(super) fn unescape(text: &str) -> Cow<'_, str> {
+    if !text.contains('&') {
+        return Cow::Borrowed(text);
+    }
+
+    let mut output = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(start) = rest.find('&') {
+        output.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let Some(end) = rest.find(';') else { break };
+        let entity = &rest[1..end];
+        let replacement = match entity {
+            "amp" => Some('&'),
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            _ => entity
+                .strip_prefix("#x")
+                .map(|hex| u32::from_str_radix(hex, 16))
+                .or_else(|| entity.strip_prefix('#').map(str::parse))
+                .and_then(Result::ok)
+                .and_then(char::from_u32),
+        };
+
+        match replacement {
+            Some(c) => {
+                output.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                output.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+
+    output.push_str(rest);
+    Cow::Owned(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_list_response() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<ListBucketResult xmlns="http://s3.amazonaws.com/doc/2006-03-01/">
+  <Name>bucket</Name>
+  <IsTruncated>true</IsTruncated>
+  <Contents>
+    <Key>a &amp; b.txt</Key>
+    <Size>12</Size>
+    <ETag>&quot;abc&quot;</ETag>
+  </Contents>
+  <Contents>
+    <Key>c.txt</Key>
+    <Size>0</Size>
+  </Contents>
+  <NextContinuationToken>token</NextContinuationToken>
+</ListBucketResult>"#;
+
+        let keys: Vec<_> = elements(xml, "Contents")
+            .map(|contents| text(contents, "Key").unwrap())
+            .collect();
+        assert_eq!(keys, ["a & b.txt", "c.txt"]);
+
+        let first = elements(xml, "Contents").next().unwrap();
+        assert_eq!(text(first, "ETag").as_deref(), Some("\"abc\""));
+        assert_eq!(text(xml, "NextContinuationToken").as_deref(), Some("token"));
+        assert_eq!(text(xml, "Missing"), None);
+    }
+
+    #[test]
+    fn unescape_entities() {
+        assert_eq!(unescape("a &lt;b&gt; &#65;&#x42; & c"), "a <b> AB & c");
+    }
+}
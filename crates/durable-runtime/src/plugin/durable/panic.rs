@@ -0,0 +1,21 @@
+use crate::bindings::durable::core::panic::{Host, Location};
+use crate::Task;
+
+#[async_trait::async_trait]
+impl Host for Task {
+    async fn report_panic(
+        &mut self,
+        message: String,
+        location: Option<Location>,
+    ) -> wasmtime::Result<()> {
+        let message = match location {
+            Some(Location { file, line, column }) => {
+                format!("panicked at {file}:{line}:{column}: {message}")
+            }
+            None => format!("panicked: {message}"),
+        };
+
+        self.state.report_panic(message);
+        Ok(())
+    }
+}
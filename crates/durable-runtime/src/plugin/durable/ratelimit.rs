@@ -0,0 +1,146 @@
+use chrono::{DateTime, TimeDelta, Utc};
+
+use crate::bindings::durable::core::ratelimit::Host;
+use crate::task::TransactionOptions;
+use crate::Task;
+
+/// How long after its retry time a waiting task keeps its place in line.
+///
+/// This keeps tasks that have stopped waiting from blocking everyone else
+/// behind them forever.
+const WAITER_GRACE_SECS: i64 = 60;
+
+#[async_trait::async_trait]
+impl Host for Task {
+    async fn try_acquire(&mut self, name: String) -> wasmtime::Result<Option<u64>> {
+        if self.state.transaction().is_some() {
+            anyhow::bail!(
+                "durable:core/ratelimit.try-acquire cannot be called from within a transaction"
+            );
+        }
+
+        let options = TransactionOptions::new("durable:core/ratelimit.try-acquire").database(true);
+        if let Some(retry) = self.state.enter::<Option<u64>>(options).await? {
+            return Ok(retry);
+        }
+
+        let Some(limit) = self.state.config().rate_limits.get(&name).cloned() else {
+            anyhow::bail!("no rate limit named {name:?} is configured on this worker");
+        };
+        let capacity = limit.capacity();
+        let rate = limit.refill_rate();
+        if !(rate.is_finite() && rate > 0.0) {
+            anyhow::bail!("rate limit {name:?} must add a non-zero number of tokens per period");
+        }
+
+        let task_id = self.state.task_id();
+        let schema = self.state.shared().schema.clone();
+        let txn = self.state.transaction_mut().unwrap();
+        let tx = txn.conn().unwrap();
+
+        let now = Utc::now();
+
+        sqlx::query!(
+            "INSERT INTO durable.rate_limit(name, tokens, updated_at)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (name) DO NOTHING",
+            name,
+            capacity,
+            now
+        )
+        .execute(schema.on(&mut **tx))
+        .await?;
+
+        // Locking the bucket serializes everyone taking tokens from it.
+        let bucket = sqlx::query!(
+            "SELECT tokens, updated_at FROM durable.rate_limit WHERE name = $1 FOR UPDATE",
+            name
+        )
+        .fetch_one(schema.on(&mut **tx))
+        .await?;
+
+        sqlx::query!(
+            "DELETE FROM durable.rate_limit_waiter WHERE name = $1 AND retry_at < $2",
+            name,
+            now - TimeDelta::seconds(WAITER_GRACE_SECS)
+        )
+        .execute(schema.on(&mut **tx))
+        .await?;
+
+        // The number of waiting tasks that are ahead of this one in line.
+        let ahead = sqlx::query_scalar!(
+            r#"
+            SELECT COUNT(*) as "count!"
+             FROM durable.rate_limit_waiter w
+            WHERE w.name = $1
+              AND w.task_id != $2
+              AND NOT EXISTS(
+                SELECT 1
+                 FROM durable.rate_limit_waiter me
+                WHERE me.name = $1
+                  AND me.task_id = $2
+                  AND (me.created_at, me.task_id) < (w.created_at, w.task_id)
+              )
+            "#,
+            name,
+            task_id
+        )
+        .fetch_one(schema.on(&mut **tx))
+        .await?;
+
+        // Workers' clocks may not quite agree so time is never allowed to go
+        // backwards here.
+        let now = now.max(bucket.updated_at);
+        let elapsed = (now - bucket.updated_at).to_std().unwrap_or_default();
+        let tokens = (bucket.tokens + elapsed.as_secs_f64() * rate).min(capacity);
+
+        // Tokens are reserved for the tasks that are ahead of this one.
+        let needed = ahead as f64 + 1.0;
+        let (tokens, retry) = if tokens >= needed {
+            sqlx::query!(
+                "DELETE FROM durable.rate_limit_waiter WHERE name = $1 AND task_id = $2",
+                name,
+                task_id
+            )
+            .execute(schema.on(&mut **tx))
+            .await?;
+
+            (tokens - 1.0, None)
+        } else {
+            let retry = ((needed - tokens) / rate * 1000.0).ceil().max(1.0) as u64;
+            let retry_at = i64::try_from(retry)
+                .ok()
+                .and_then(TimeDelta::try_milliseconds)
+                .and_then(|delta| now.checked_add_signed(delta))
+                .unwrap_or(DateTime::<Utc>::MAX_UTC);
+
+            sqlx::query!(
+                "INSERT INTO durable.rate_limit_waiter(name, task_id, created_at, retry_at)
+                VALUES ($1, $2, $3, $4)
+                ON CONFLICT (name, task_id) DO UPDATE
+                    SET retry_at = EXCLUDED.retry_at",
+                name,
+                task_id,
+                now,
+                retry_at
+            )
+            .execute(schema.on(&mut **tx))
+            .await?;
+
+            (tokens, Some(retry))
+        };
+
+        sqlx::query!(
+            "UPDATE durable.rate_limit SET tokens = $2, updated_at = $3 WHERE name = $1",
+            name,
+            tokens,
+            now
+        )
+        .execute(schema.on(&mut **tx))
+        .await?;
+
+        self.state.exit(&retry).await?;
+
+        Ok(retry)
+    }
+}
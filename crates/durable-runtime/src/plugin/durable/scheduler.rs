@@ -0,0 +1,49 @@
+use crate::bindings::durable::core::scheduler::Host;
+use crate::error::TaskStatus;
+use crate::Task;
+
+#[async_trait::async_trait]
+impl Host for Task {
+    async fn yield_now(&mut self) -> wasmtime::Result<()> {
+        tokio::task::yield_now().await;
+        Ok(())
+    }
+
+    async fn checkpoint(&mut self) -> wasmtime::Result<()> {
+        if self.state.transaction().is_some() {
+            anyhow::bail!(
+                "durable:core/scheduler.checkpoint cannot be called from within a transaction"
+            );
+        }
+
+        self.state.flush_logs().await?;
+
+        // Give the rest of the worker a chance to run before deciding whether to keep
+        // the task around.
+        tokio::task::yield_now().await;
+
+        if !self.state.shared().shutdown.is_raised() {
+            return Ok(());
+        }
+
+        // The worker is shutting down, so hand the task back so that another worker
+        // can pick it up from here instead of waiting for this one to go away.
+        let result = sqlx::query!(
+            "UPDATE durable.task
+              SET state = 'ready',
+                  running_on = NULL
+            WHERE id = $1
+              AND running_on = $2",
+            self.state.task_id(),
+            self.state.worker_id()
+        )
+        .execute(self.state.shared().schema.on(self.state.pool()))
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(anyhow::Error::new(TaskStatus::NotScheduledOnWorker));
+        }
+
+        Err(anyhow::Error::new(TaskStatus::Suspend))
+    }
+}
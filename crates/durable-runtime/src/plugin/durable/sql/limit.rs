@@ -0,0 +1,82 @@
+use std::borrow::Cow;
+use std::fmt;
+use std::time::Duration;
+
+use sqlx::error::ErrorKind;
+
+/// An error indicating that a workflow exceeded one of the SQL limits in the
+/// worker's [`Config`](crate::Config).
+///
+/// The workflow sees this as a database error so that it can be handled the
+/// same way as the errors that postgres returns for its own limits.
+#[derive(Clone, Debug)]
+pub(crate) struct LimitExceeded {
+    message: String,
+    code: &'static str,
+}
+
+impl LimitExceeded {
+    pub fn rows(limit: u64) -> Self {
+        Self {
+            message: format!(
+                "query returned more than the maximum of {limit} rows allowed by the worker"
+            ),
+            // program_limit_exceeded
+            code: "54000",
+        }
+    }
+
+    pub fn statements(limit: u32) -> Self {
+        Self {
+            message: format!(
+                "transaction ran more than the maximum of {limit} statements allowed by the worker"
+            ),
+            // program_limit_exceeded
+            code: "54000",
+        }
+    }
+
+    pub fn duration(limit: Duration) -> Self {
+        Self {
+            message: format!(
+                "transaction ran for longer than the maximum of {limit:?} allowed by the worker"
+            ),
+            // transaction_timeout
+            code: "25P04",
+        }
+    }
+}
+
+impl fmt::Display for LimitExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for LimitExceeded {}
+
+impl sqlx::error::DatabaseError for LimitExceeded {
+    fn message(&self) -> &str {
+        &self.message
+    }
+
+    fn code(&self) -> Option<Cow<'_, str>> {
+        Some(Cow::Borrowed(self.code))
+    }
+
+    fn as_error(&self) -> &(dyn std::error::Error + Send + Sync + 'static) {
+        self
+    }
+
+    fn as_error_mut(&mut self) -> &mut (dyn std::error::Error + Send + Sync + 'static) {
+        self
+    }
+
+    fn into_error(self: Box<Self>) -> Box<dyn std::error::Error + Send + Sync + 'static> {
+        self
+    }
+
+    fn kind(&self) -> ErrorKind {
+        ErrorKind::Other
+    }
+}
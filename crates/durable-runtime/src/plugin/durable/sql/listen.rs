@@ -0,0 +1,184 @@
+use tokio::sync::broadcast::error::RecvError;
+use tokio::time::Instant;
+
+use super::PgNotificationData;
+use crate::{Task, TaskStatus};
+
+/// Check whether a postgres notification on `channel` has been delivered to
+/// this task, registering the task as a listener on `channel` if not.
+///
+/// If this returns a notification then the task stops listening on the
+/// channel once `tx` commits.
+async fn poll_pg_notification(
+    task: &mut Task,
+    tx: &mut sqlx::PgConnection,
+    channel: &str,
+) -> anyhow::Result<Option<PgNotificationData>> {
+    let task_id = task.state.task_id();
+    let schema = task.state.shared().schema.clone();
+
+    // Locking our registration blocks any worker that is trying to deliver a
+    // notification to it until this transaction completes. That way a
+    // notification can't be delivered after we have checked for one and then
+    // be left behind in the notification queue.
+    sqlx::query!(
+        "
+        INSERT INTO durable.pg_listener(channel, task_id)
+        VALUES ($1, $2)
+        ON CONFLICT (channel, task_id) DO UPDATE
+            SET created_at = pg_listener.created_at
+        ",
+        channel,
+        task_id
+    )
+    .execute(schema.on(&mut *tx))
+    .await?;
+
+    let payload = sqlx::query_scalar!(
+        r#"
+        WITH delivered AS (
+            DELETE FROM durable.notification
+            WHERE task_id = $1
+              AND event = 'durable:pg-notification'
+              AND data->>'channel' = $2
+            RETURNING created_at, data->>'payload' as payload
+        )
+        SELECT payload as "payload!"
+         FROM delivered
+        ORDER BY created_at ASC
+        LIMIT 1
+        "#,
+        task_id,
+        channel
+    )
+    .fetch_optional(schema.on(&mut *tx))
+    .await?;
+
+    let Some(payload) = payload else {
+        return Ok(None);
+    };
+
+    sqlx::query!(
+        "DELETE FROM durable.pg_listener WHERE channel = $1 AND task_id = $2",
+        channel,
+        task_id
+    )
+    .execute(schema.on(&mut *tx))
+    .await?;
+
+    Ok(Some(PgNotificationData {
+        channel: channel.to_owned(),
+        payload,
+    }))
+}
+
+/// Why a task waiting for a postgres notification woke up.
+enum Woken {
+    Notified,
+    Suspend,
+    Closed,
+}
+
+impl Task {
+    /// Wait for a postgres notification to be sent on `channel`.
+    ///
+    /// Whichever worker receives the notification delivers it to this task via
+    /// the `durable.notification` table, so the task can suspend itself while
+    /// it waits just like it does when waiting for any other notification.
+    pub(super) async fn wait_for_pg_notification(
+        &mut self,
+        channel: &str,
+    ) -> anyhow::Result<PgNotificationData> {
+        let deadline = Instant::now() + self.state.config().suspend_timeout;
+        let task_id = self.state.task_id();
+        let simulated = self.state.is_simulated();
+        let shared = self.state.shared().clone();
+        let mut rx = self.state.subscribe_notifications();
+        let mut channels = shared.pg_channels.subscribe();
+        let mut listening = false;
+
+        loop {
+            let mut tx = self.state.pool().begin().await?;
+            let data = poll_pg_notification(&mut *self, &mut tx, channel).await?;
+
+            if let Some(data) = data {
+                let txn = self.state.transaction_mut().unwrap();
+                txn.set_conn(tx)?;
+
+                return Ok(data);
+            }
+
+            tx.commit().await?;
+
+            let woken = self
+                .state
+                .blocking(async {
+                    loop {
+                        tokio::select! {
+                            biased;
+
+                            result = rx.recv() => match result {
+                                Ok(notif) if notif.task_id == task_id => break Woken::Notified,
+                                Ok(_) => continue,
+                                Err(RecvError::Lagged(_)) => break Woken::Notified,
+                                Err(RecvError::Closed) => break Woken::Closed,
+                            },
+                            // Registering the task has the worker start listening on
+                            // the channel. Until it does, no worker may be listening
+                            // on it, so the task can't suspend itself yet.
+                            open = async {
+                                let open = channels.wait_for(|channels| channels.contains(channel));
+                                open.await.is_ok()
+                            }, if !listening => match open {
+                                true => listening = true,
+                                false => break Woken::Closed,
+                            },
+                            // Simulated tasks are never suspended, the same as when
+                            // they wait for any other notification.
+                            _ = tokio::time::sleep_until(deadline), if !simulated && listening => {
+                                break Woken::Suspend
+                            }
+                        }
+                    }
+                })
+                .await;
+
+            match woken {
+                Woken::Notified => continue,
+                Woken::Closed => return Err(anyhow::Error::new(TaskStatus::NotScheduledOnWorker)),
+                Woken::Suspend => (),
+            }
+
+            // The timer expired, so we need to attempt to suspend. The task stays
+            // registered as a listener, so the worker that receives the
+            // notification will wake it back up.
+            let mut tx = self.state.pool().begin().await?;
+
+            sqlx::query!(
+                "UPDATE durable.task
+                      SET state = 'suspended',
+                          running_on = NULL,
+                          wakeup_at = NULL
+                    WHERE id = $1
+                    ",
+                task_id
+            )
+            .execute(shared.schema.on(&mut *tx))
+            .await?;
+
+            if poll_pg_notification(&mut *self, &mut tx, channel)
+                .await?
+                .is_some()
+            {
+                // A notification barged in while we were updating. Roll back the
+                // transaction and go through the main loop again.
+                tx.rollback().await?;
+                continue;
+            }
+
+            tx.commit().await?;
+
+            return Err(anyhow::Error::new(TaskStatus::Suspend));
+        }
+    }
+}
@@ -1,3 +1,5 @@
+use std::time::{Duration, Instant};
+
 use async_stream::try_stream;
 use chrono::{DateTime, NaiveDateTime, Utc};
 use futures_util::{StreamExt, TryStreamExt};
@@ -6,21 +8,25 @@ use sqlx::postgres::PgTypeInfo;
 use sqlx::types::chrono::FixedOffset;
 use sqlx::types::ipnetwork::IpNetwork;
 use sqlx::types::Json;
-use sqlx::{Column, Row};
+use sqlx::{Column, Connection, Row};
 use uuid::Uuid;
 use value::ValueResource;
 use wasmtime::component::Resource;
 
 use self::type_info::TypeInfoResource;
 use self::value::Value;
+use crate::audit::AuditRecord;
 use crate::bindings::durable::core::sql::{self, Host};
+use crate::policy::{audited_tables, StatementKind};
 use crate::resource::Resourceable;
-use crate::task::QueryResult;
+use crate::task::{QueryResult, QueryStats, TransactionOptions};
 use crate::Task;
 
+pub(crate) mod limit;
+mod listen;
 mod oids;
 mod type_info;
-mod value;
+pub(crate) mod value;
 
 impl Resourceable for sql::TypeInfo {
     const NAME: &'static str = "durable:core/sql.type-info";
@@ -625,6 +631,23 @@ impl sql::HostValue for Task {
         })
     }
 
+    async fn as_raw(
+        &mut self,
+        res: Resource<sql::Value>,
+    ) -> wasmtime::Result<Option<sql::RawValue>> {
+        let value = self.resources.get(res)?;
+        let Value::Unknown(data) = &value.value else {
+            return Ok(None);
+        };
+
+        let oid = value.type_info.type_info.oid().map_or(0, |oid| oid.0);
+
+        Ok(Some(sql::RawValue {
+            oid,
+            data: data.clone(),
+        }))
+    }
+
     async fn null(
         &mut self,
         tyinfo: Resource<sql::TypeInfo>,
@@ -975,16 +998,565 @@ impl Host for Task {
         param_res: Vec<Resource<sql::Value>>,
         options: sql::Options,
     ) -> anyhow::Result<()> {
+        let options = sql::Options2 {
+            limit: options.limit,
+            persistent: options.persistent,
+            collect_stats: false,
+        };
+
+        self.start_query(sql, param_res, options).await
+    }
+
+    async fn query2(
+        &mut self,
+        sql: String,
+        param_res: Vec<Resource<sql::Value>>,
+        options: sql::Options2,
+    ) -> anyhow::Result<()> {
+        self.start_query(sql, param_res, options).await
+    }
+
+    async fn batch(&mut self, statements: Vec<sql::BatchStatement>) -> anyhow::Result<()> {
+        let task_id = self.state.task_id();
+        let shared = self.state.shared().clone();
+        let policy = self.state.sql_policy().cloned();
+        let audit = self.state.config().audit;
+        let txn = self.state.assert_in_transaction("durable::sql::batch")?;
+        txn.abort_copy_in("a batch was started within the same transaction")
+            .await?;
+
+        let mut batch = Vec::with_capacity(statements.len());
+        for statement in statements {
+            let mut params = Vec::with_capacity(statement.params.len());
+            for param in statement.params {
+                params.push(self.resources.remove(param)?);
+            }
+
+            batch.push((statement.sql, params));
+        }
+
+        txn.query_stats = None;
+        if let Err(e) = txn.check_statement_limits(batch.len()) {
+            return txn.start_query(move |_| {
+                Box::pin(futures_util::stream::once(async move { Err(e.into()) }))
+            });
+        }
+
+        if audit {
+            let Some(conn) = txn.conn() else {
+                anyhow::bail!("durable::sql::batch called without a database connection");
+            };
+
+            let mut records = Vec::with_capacity(batch.len());
+            for (sql, params) in &batch {
+                let tables = audited_tables(conn, task_id, StatementKind::Query, sql, params).await;
+                records.push(AuditRecord::sql(sql, tables));
+            }
+
+            txn.audit.push_statements(records);
+        }
+
+        txn.start_query(move |conn| {
+            Box::pin(try_stream! {
+                for (sql, params) in batch {
+                    if let Some(policy) = &policy {
+                        policy
+                            .check(&mut **conn, task_id, StatementKind::Query, &sql, &params)
+                            .await?;
+                    }
+
+                    let cache = &shared.statement_cache;
+                    let persistent = cache.is_cacheable(&sql);
+                    let cached = conn.cached_statements_size();
+
+                    let mut query = sqlx::query(&sql).persistent(persistent);
+                    for param in params {
+                        query = query.bind(param);
+                    }
+
+                    let result = query.execute(&mut **conn).await?;
+                    if persistent {
+                        cache.record(&mut **conn, cached).await?;
+                    }
+
+                    yield sqlx::Either::Left(QueryResult::from(result));
+                }
+            })
+        })
+    }
+
+    async fn last_query_stats(&mut self) -> anyhow::Result<Option<sql::QueryStats>> {
+        let txn = self
+            .state
+            .assert_in_transaction("durable::sql::last_query_stats")?;
+        let Some(stats) = txn.query_stats else {
+            return Ok(None);
+        };
+        let Some(duration) = stats.duration else {
+            return Ok(None);
+        };
+
+        Ok(Some(sql::QueryStats {
+            duration: duration_nanos(duration),
+            execution_time: stats.execution_time.map(duration_nanos),
+            rows_scanned: stats.rows_scanned,
+        }))
+    }
+
+    async fn fetch(&mut self) -> wasmtime::Result<Option<Result<sql::QueryResult, sql::Error>>> {
         let txn = self.state.assert_in_transaction("durable::sql::query")?;
+        if txn.stream().is_some() {
+            if let Err(e) = txn.check_deadline() {
+                txn.take_stream();
+                return Ok(Some(Err(convert_sqlx_error(e.into())?)));
+            }
+        }
+
+        let item = match txn.stream() {
+            Some(stream) => stream.next().await,
+            None => None,
+        };
+
+        let item = match item {
+            Some(item) => item,
+            None => {
+                if let Some(stats) = &mut txn.query_stats {
+                    stats
+                        .duration
+                        .get_or_insert_with(|| stats.started.elapsed());
+                }
+
+                return Ok(None);
+            }
+        };
+
+        match &item {
+            Ok(sqlx::Either::Left(result)) => txn.audit.set_rows_affected(result.rows_affected),
+            Ok(sqlx::Either::Right(_)) => {
+                // The rest of the results are dropped along with the stream.
+                if let Err(e) = txn.check_row_limit() {
+                    txn.take_stream();
+                    return Ok(Some(Err(convert_sqlx_error(e.into())?)));
+                }
+            }
+            Err(_) => (),
+        }
+
+        Ok(Some(match item {
+            Ok(sqlx::Either::Left(result)) => Ok(sql::QueryResult::Count(result.rows_affected)),
+            Ok(sqlx::Either::Right(row)) => {
+                let mut columns = Vec::with_capacity(row.len());
+
+                for (idx, column) in row.columns().iter().enumerate() {
+                    let value: ValueResource = match row.try_get(idx) {
+                        Ok(value) => value,
+                        Err(e) => return Ok(Some(Err(convert_sqlx_error(e)?))),
+                    };
+
+                    let value = self.resources.insert(value)?;
+
+                    columns.push(sql::Column {
+                        name: column.name().to_owned(),
+                        value,
+                    });
+                }
+
+                Ok(sql::QueryResult::Row(sql::Row { columns }))
+            }
+            Err(e) => Err(convert_sqlx_error(e)?),
+        }))
+    }
+
+    async fn savepoint(&mut self) -> anyhow::Result<Result<(), sql::Error>> {
+        self.savepoint_command("durable::sql::savepoint", SavepointCommand::Create)
+            .await
+    }
+
+    async fn release_savepoint(&mut self) -> anyhow::Result<Result<(), sql::Error>> {
+        self.savepoint_command("durable::sql::release_savepoint", SavepointCommand::Release)
+            .await
+    }
+
+    async fn rollback_savepoint(&mut self) -> anyhow::Result<Result<(), sql::Error>> {
+        self.savepoint_command(
+            "durable::sql::rollback_savepoint",
+            SavepointCommand::Rollback,
+        )
+        .await
+    }
+
+    async fn copy_in_start(&mut self, statement: String) -> anyhow::Result<Result<(), sql::Error>> {
+        let task_id = self.state.task_id();
+        let policy = self.state.sql_policy().cloned();
+        let audit = self.state.config().audit;
+        let txn = self
+            .state
+            .assert_in_transaction("durable::sql::copy_in_start")?;
+
+        if let Err(e) = txn.check_statement_limits(1) {
+            return Ok(Err(convert_sqlx_error(e.into())?));
+        }
+
+        if policy.is_some() || audit {
+            if let Err(e) = txn
+                .abort_copy_in("another COPY operation was started")
+                .await
+            {
+                return Ok(Err(convert_sqlx_error(e)?));
+            }
+
+            let Some(conn) = txn.conn() else {
+                anyhow::bail!("durable::sql::copy_in_start called without a database connection");
+            };
+
+            if let Some(policy) = policy {
+                let result = policy
+                    .check(conn, task_id, StatementKind::CopyIn, &statement, &[])
+                    .await;
+
+                if let Err(violation) = result {
+                    return Ok(Err(convert_sqlx_error(violation.into())?));
+                }
+            }
+
+            if audit {
+                let tables =
+                    audited_tables(conn, task_id, StatementKind::CopyIn, &statement, &[]).await;
+                txn.audit
+                    .push_statements([AuditRecord::sql(&statement, tables)]);
+            }
+        }
+
+        Ok(match txn.start_copy_in(&statement).await? {
+            Ok(()) => Ok(()),
+            Err(e) => Err(convert_sqlx_error(e)?),
+        })
+    }
+
+    async fn copy_in_send(&mut self, data: Vec<u8>) -> anyhow::Result<Result<(), sql::Error>> {
+        let txn = self
+            .state
+            .assert_in_transaction("durable::sql::copy_in_send")?;
+        let Some(copy) = txn.copy_in() else {
+            return Ok(Err(no_copy_in_progress()));
+        };
+
+        Ok(match copy.send(data).await {
+            Ok(_) => Ok(()),
+            Err(e) => Err(convert_sqlx_error(e)?),
+        })
+    }
+
+    async fn copy_in_finish(&mut self) -> anyhow::Result<Result<u64, sql::Error>> {
+        let txn = self
+            .state
+            .assert_in_transaction("durable::sql::copy_in_finish")?;
+        let Some(copy) = txn.take_copy_in() else {
+            return Ok(Err(no_copy_in_progress()));
+        };
+
+        Ok(match copy.finish().await {
+            Ok(count) => {
+                txn.audit.set_rows_affected(count);
+                Ok(count)
+            }
+            Err(e) => Err(convert_sqlx_error(e)?),
+        })
+    }
+
+    async fn copy_in_abort(&mut self, message: String) -> anyhow::Result<Result<(), sql::Error>> {
+        let txn = self
+            .state
+            .assert_in_transaction("durable::sql::copy_in_abort")?;
+        let Some(copy) = txn.take_copy_in() else {
+            return Ok(Err(no_copy_in_progress()));
+        };
+
+        Ok(match copy.abort(message).await {
+            Ok(()) => Ok(()),
+            Err(e) => Err(convert_sqlx_error(e)?),
+        })
+    }
+
+    async fn describe(
+        &mut self,
+        sql: String,
+    ) -> anyhow::Result<Result<sql::StatementDescription, sql::Error>> {
+        let task_id = self.state.task_id();
+        let policy = self.state.sql_policy().cloned();
+        let txn = self.state.assert_in_transaction("durable::sql::describe")?;
+        txn.abort_copy_in("a statement was described within the same transaction")
+            .await?;
+
+        let Some(conn) = txn.conn() else {
+            anyhow::bail!("durable::sql::describe called without a database connection");
+        };
+
+        // Describing a statement reveals the columns of the tables that it accesses,
+        // so it is subject to the same policy as running it.
+        if let Some(policy) = policy {
+            let result = policy
+                .check(conn, task_id, StatementKind::Describe, &sql, &[])
+                .await;
+
+            if let Err(violation) = result {
+                return Ok(Err(convert_sqlx_error(violation.into())?));
+            }
+        }
+
+        let describe = match sqlx::Executor::describe(&mut **conn, &sql).await {
+            Ok(describe) => describe,
+            Err(e) => return Ok(Err(convert_sqlx_error(e)?)),
+        };
+
+        // Postgres always reports the full type information for each parameter.
+        let params = match describe.parameters {
+            Some(sqlx::Either::Left(params)) => params,
+            _ => Vec::new(),
+        };
+
+        let mut parameters = Vec::with_capacity(params.len());
+        for param in params {
+            parameters.push(self.resources.insert(TypeInfoResource::from(param))?);
+        }
+
+        let mut columns = Vec::with_capacity(describe.columns.len());
+        for (idx, column) in describe.columns.iter().enumerate() {
+            let type_info = TypeInfoResource::from(column.type_info().clone());
+
+            columns.push(sql::ColumnDescription {
+                name: column.name().to_owned(),
+                type_info: self.resources.insert(type_info)?,
+                nullable: describe.nullable.get(idx).copied().flatten(),
+            });
+        }
+
+        Ok(Ok(sql::StatementDescription {
+            parameters,
+            columns,
+        }))
+    }
+
+    async fn listen(&mut self, channel: String) -> anyhow::Result<sql::PgNotification> {
+        if self.state.transaction().is_some() {
+            anyhow::bail!("durable:core/sql.listen cannot be called from within a transaction");
+        }
+
+        // The worker's event source treats notifications on these channels as its
+        // own events, so they would never be delivered.
+        let reserved = channel
+            .strip_prefix(self.state.shared().schema.to())
+            .is_some_and(|rest| rest.starts_with(':'));
+        if reserved {
+            anyhow::bail!(
+                "durable:core/sql.listen cannot listen on channel `{channel}` since it is \
+                 reserved for use by durable"
+            );
+        }
+
+        let options = TransactionOptions::new("durable:core/sql.listen");
+        if let Some(data) = self.state.enter::<PgNotificationData>(options).await? {
+            return Ok(data.into());
+        }
+
+        let data = self.wait_for_pg_notification(&channel).await?;
+        self.state.exit(&data).await?;
+
+        Ok(data.into())
+    }
+
+    async fn clear_statement_cache(&mut self) -> anyhow::Result<()> {
+        let shared = self.state.shared().clone();
+        let program = self.state.program_id();
+        let payload = serde_json::json!({ "wasm": program }).to_string();
+        let notify = sqlx::query("SELECT pg_notify('durable:statement-cache', $1)").bind(payload);
+
+        // Don't wait for the notification to come back around before clearing
+        // connections on this worker, so that the rest of this task sees the
+        // change right away.
+        shared.statement_cache.invalidate(program);
+
+        if let Some(txn) = self.state.transaction_mut() {
+            txn.abort_copy_in("the statement cache was cleared within the same transaction")
+                .await?;
+
+            if let Some(conn) = txn.conn() {
+                shared.statement_cache.evict(conn).await?;
+
+                // Sending the notification as part of the transaction means that
+                // other workers only clear their connections once any schema
+                // changes made by the transaction are visible. If the transaction
+                // has already failed then those changes will be rolled back, so
+                // there is nothing to clear.
+                let _ = notify.execute(shared.schema.on(&mut **conn)).await;
+                return Ok(());
+            }
+        }
+
+        notify.execute(shared.schema.on(&shared.pool)).await?;
+
+        Ok(())
+    }
+
+    async fn explain(
+        &mut self,
+        sql: String,
+        param_res: Vec<Resource<sql::Value>>,
+    ) -> anyhow::Result<Result<String, sql::Error>> {
+        let task_id = self.state.task_id();
+        let policy = self.state.sql_policy().cloned();
+        let txn = self.state.assert_in_transaction("durable::sql::explain")?;
+        txn.abort_copy_in("a statement was explained within the same transaction")
+            .await?;
+
+        let mut params = Vec::with_capacity(param_res.len());
+        for param in param_res {
+            params.push(self.resources.remove(param)?);
+        }
+
+        if let Err(e) = txn.check_statement_limits(1) {
+            return Ok(Err(convert_sqlx_error(e.into())?));
+        }
+
+        let Some(conn) = txn.conn() else {
+            anyhow::bail!("durable::sql::explain called without a database connection");
+        };
+
+        // The policy needs to see the statement itself. Wrapping it in EXPLAIN
+        // first would turn it into something that the policy cannot plan.
+        if let Some(policy) = policy {
+            let result = policy
+                .check(conn, task_id, StatementKind::Query, &sql, &params)
+                .await;
+
+            if let Err(violation) = result {
+                return Ok(Err(convert_sqlx_error(violation.into())?));
+            }
+        }
+
+        let explain = format!("EXPLAIN (FORMAT JSON) {sql}");
+        let mut query = sqlx::query_scalar(&explain).persistent(false);
+        for param in params {
+            query = query.bind(param);
+        }
+
+        let plan: Json<serde_json::Value> = match query.fetch_one(&mut **conn).await {
+            Ok(plan) => plan,
+            Err(e) => return Ok(Err(convert_sqlx_error(e)?)),
+        };
+
+        Ok(Ok(plan.0.to_string()))
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct PgNotificationData {
+    channel: String,
+    payload: String,
+}
+
+impl From<PgNotificationData> for sql::PgNotification {
+    fn from(data: PgNotificationData) -> Self {
+        Self {
+            channel: data.channel,
+            payload: data.payload,
+        }
+    }
+}
+
+fn no_copy_in_progress() -> sql::Error {
+    sql::Error::Other(
+        "there is no COPY operation in progress within the current transaction".into(),
+    )
+}
+
+enum SavepointCommand {
+    Create,
+    Release,
+    Rollback,
+}
+
+impl Task {
+    async fn start_query(
+        &mut self,
+        sql: String,
+        param_res: Vec<Resource<sql::Value>>,
+        options: sql::Options2,
+    ) -> anyhow::Result<()> {
+        let task_id = self.state.task_id();
+        let shared = self.state.shared().clone();
+        let policy = self.state.sql_policy().cloned();
+        let audit = self.state.config().audit;
+        let txn = self.state.assert_in_transaction("durable::sql::query")?;
+        txn.abort_copy_in("a query was started within the same transaction")
+            .await?;
 
         let mut params = Vec::with_capacity(param_res.len());
         for param in param_res {
             params.push(self.resources.remove(param)?);
         }
 
+        txn.query_stats = None;
+        if let Err(e) = txn.check_statement_limits(1) {
+            return txn.start_query(move |_| {
+                Box::pin(futures_util::stream::once(async move { Err(e.into()) }))
+            });
+        }
+
+        if audit {
+            let Some(conn) = txn.conn() else {
+                anyhow::bail!("durable::sql::query called without a database connection");
+            };
+
+            let tables = audited_tables(conn, task_id, StatementKind::Query, &sql, &params).await;
+            txn.audit.push_statements([AuditRecord::sql(&sql, tables)]);
+        }
+
+        if let Some(policy) = policy {
+            let Some(conn) = txn.conn() else {
+                anyhow::bail!("durable::sql::query called without a database connection");
+            };
+
+            let result = policy
+                .check(conn, task_id, StatementKind::Query, &sql, &params)
+                .await;
+
+            if let Err(violation) = result {
+                return txn.start_query(move |_| {
+                    Box::pin(futures_util::stream::once(
+                        async move { Err(violation.into()) },
+                    ))
+                });
+            }
+        }
+
+        if options.collect_stats {
+            let Some(conn) = txn.conn() else {
+                anyhow::bail!("durable::sql::query called without a database connection");
+            };
+
+            let plan = explain_analyze(conn, &sql, &params).await;
+            let root = plan.as_ref().and_then(|plan| plan.get(0));
+
+            txn.query_stats = Some(QueryStats {
+                started: Instant::now(),
+                duration: None,
+                execution_time: root
+                    .and_then(|root| root.get("Execution Time"))
+                    .and_then(|time| time.as_f64())
+                    .map(|ms| Duration::from_secs_f64(ms / 1000.0)),
+                rows_scanned: root.and_then(|root| root.get("Plan")).map(rows_scanned),
+            });
+        }
+
+        let persistent = options.persistent && shared.statement_cache.is_cacheable(&sql);
+
         txn.start_query(move |conn| {
             Box::pin(try_stream! {
-                let mut query = sqlx::query(&sql).persistent(options.persistent);
+                let cache = &shared.statement_cache;
+                let cached = conn.cached_statements_size();
+
+                let mut query = sqlx::query(&sql).persistent(persistent);
                 for param in params {
                     query = query.bind(param);
                 }
@@ -992,10 +1564,17 @@ impl Host for Task {
                 match options.limit {
                     0 => {
                         let result = query.execute(&mut **conn).await?;
+                        if persistent {
+                            cache.record(&mut **conn, cached).await?;
+                        }
+
                         yield sqlx::Either::Left(QueryResult::from(result));
                     },
                     1 =>  {
                         let row = query.fetch_optional(&mut **conn).await?;
+                        if persistent {
+                            cache.record(&mut **conn, cached).await?;
+                        }
 
                         let count = match &row {
                             Some(_) => 1,
@@ -1021,48 +1600,123 @@ impl Host for Task {
                         while let Some(item) = result.try_next().await? {
                             yield item.map_left(QueryResult::from);
                         }
+
+                        drop(result);
+                        if persistent {
+                            cache.record(&mut **conn, cached).await?;
+                        }
                     }
                 }
             })
         })
     }
 
-    async fn fetch(&mut self) -> wasmtime::Result<Option<Result<sql::QueryResult, sql::Error>>> {
-        let txn = self.state.assert_in_transaction("durable::sql::query")?;
-        let stream = match txn.stream() {
-            Some(stream) => stream,
-            None => return Ok(None),
+    async fn savepoint_command(
+        &mut self,
+        function: &'static str,
+        command: SavepointCommand,
+    ) -> anyhow::Result<Result<(), sql::Error>> {
+        let txn = self.state.assert_in_transaction(function)?;
+        let depth = txn.savepoints;
+
+        let statements = match command {
+            SavepointCommand::Create => vec![format!("SAVEPOINT durable_savepoint_{depth}")],
+            _ if depth == 0 => {
+                return Ok(Err(sql::Error::Other(
+                    "there is no active savepoint within the current transaction".into(),
+                )))
+            }
+            SavepointCommand::Release => {
+                vec![format!("RELEASE SAVEPOINT durable_savepoint_{}", depth - 1)]
+            }
+            // ROLLBACK TO leaves the savepoint in place so we need to release it
+            // afterwards as well.
+            SavepointCommand::Rollback => vec![
+                format!("ROLLBACK TO SAVEPOINT durable_savepoint_{}", depth - 1),
+                format!("RELEASE SAVEPOINT durable_savepoint_{}", depth - 1),
+            ],
         };
 
-        let item = match stream.next().await {
-            Some(item) => item,
-            None => return Ok(None),
+        txn.abort_copy_in("a savepoint command was run within the same transaction")
+            .await?;
+
+        let Some(conn) = txn.conn() else {
+            anyhow::bail!("{function} called without a database connection");
         };
 
-        Ok(Some(match item {
-            Ok(sqlx::Either::Left(result)) => Ok(sql::QueryResult::Count(result.rows_affected)),
-            Ok(sqlx::Either::Right(row)) => {
-                let mut columns = Vec::with_capacity(row.len());
+        for statement in statements {
+            if let Err(e) = sqlx::query(&statement).execute(&mut **conn).await {
+                return Ok(Err(convert_sqlx_error(e)?));
+            }
+        }
 
-                for (idx, column) in row.columns().iter().enumerate() {
-                    let value: ValueResource = match row.try_get(idx) {
-                        Ok(value) => value,
-                        Err(e) => return Ok(Some(Err(convert_sqlx_error(e)?))),
-                    };
+        txn.savepoints = match command {
+            SavepointCommand::Create => depth + 1,
+            _ => depth - 1,
+        };
 
-                    let value = self.resources.insert(value)?;
+        Ok(Ok(()))
+    }
+}
 
-                    columns.push(sql::Column {
-                        name: column.name().to_owned(),
-                        value,
-                    });
-                }
+/// Run `sql` under `EXPLAIN ANALYZE` within a savepoint that is rolled back
+/// afterwards, returning the resulting plan.
+///
+/// Statistics are best-effort, so this returns `None` if anything goes wrong.
+async fn explain_analyze(
+    conn: &mut sqlx::PgConnection,
+    sql: &str,
+    params: &[ValueResource],
+) -> Option<serde_json::Value> {
+    sqlx::query("SAVEPOINT durable_query_stats")
+        .execute(&mut *conn)
+        .await
+        .ok()?;
+
+    let explain = format!("EXPLAIN (ANALYZE, FORMAT JSON) {sql}");
+    let mut query = sqlx::query_scalar(&explain).persistent(false);
+    for param in params {
+        query = query.bind(param.clone());
+    }
+
+    let plan: Result<Json<serde_json::Value>, _> = query.fetch_one(&mut *conn).await;
+
+    let _ = sqlx::query("ROLLBACK TO SAVEPOINT durable_query_stats")
+        .execute(&mut *conn)
+        .await;
+    let _ = sqlx::query("RELEASE SAVEPOINT durable_query_stats")
+        .execute(&mut *conn)
+        .await;
+
+    plan.ok().map(|plan| plan.0)
+}
 
-                Ok(sql::QueryResult::Row(sql::Row { columns }))
-            }
-            Err(e) => Err(convert_sqlx_error(e)?),
-        }))
+/// Count the number of table rows scanned by the nodes within an `EXPLAIN
+/// ANALYZE` plan.
+fn rows_scanned(node: &serde_json::Value) -> u64 {
+    let mut total = 0;
+
+    let is_table_scan = node.get("Relation Name").is_some()
+        && node.get("Node Type").and_then(|ty| ty.as_str()) != Some("ModifyTable");
+    if is_table_scan {
+        let field = |name: &str| node.get(name).and_then(|value| value.as_f64());
+
+        let rows =
+            field("Actual Rows").unwrap_or(0.0) + field("Rows Removed by Filter").unwrap_or(0.0);
+        let loops = field("Actual Loops").unwrap_or(1.0);
+
+        total += (rows * loops) as u64;
+    }
+
+    if let Some(plans) = node.get("Plans").and_then(|plans| plans.as_array()) {
+        total += plans.iter().map(rows_scanned).sum::<u64>();
     }
+
+    total
+}
+
+fn duration_nanos(duration: Duration) -> u64 {
+    duration.as_nanos().try_into().unwrap_or(u64::MAX)
 }
 
 fn convert_sqlx_error(err: sqlx::Error) -> anyhow::Result<sql::Error> {
@@ -4,7 +4,7 @@ use chrono::{DateTime, FixedOffset, NaiveDateTime};
 use serde_json::value::RawValue;
 use sqlx::encode::IsNull;
 use sqlx::error::BoxDynError;
-use sqlx::postgres::PgTypeInfo;
+use sqlx::postgres::{PgTypeInfo, PgValueFormat};
 use sqlx::types::ipnetwork::IpNetwork;
 use sqlx::types::Json;
 use sqlx_postgres::PgTypeKind;
@@ -57,6 +57,10 @@ pub(crate) enum Value {
     UuidArray(Vec<Uuid>),
     JsonArray(Vec<Json<Box<RawValue>>>),
     InetArray(Vec<IpNetwork>),
+
+    /// A value of a type that is not supported by anything above, in the
+    /// postgres binary format for its type.
+    Unknown(Vec<u8>),
 }
 
 macro_rules! for_each_value {
@@ -97,6 +101,8 @@ macro_rules! for_each_value {
             Value::UuidArray($var) => $result,
             Value::JsonArray($var) => $result,
             Value::InetArray($var) => $result,
+
+            Value::Unknown($var) => $result,
         }
     }
 }
@@ -245,6 +251,11 @@ impl<'a> sqlx::Decode<'a, sqlx::Postgres> for ValueResource {
 
             t if matches!(t.kind(), PgTypeKind::Enum(_)) => decode(value).map(Value::Text),
 
+            // Values of any other type are kept in their binary format so that the guest
+            // can still pass them back to the database. The type info that goes along
+            // with them is what tells the database how to interpret them.
+            _ if value.format() == PgValueFormat::Binary => decode(value).map(Value::Unknown),
+
             _ => return Err(Box::new(UnsupportedType::new(type_info))),
         }?;
 
@@ -0,0 +1,117 @@
+use serde_json::value::RawValue;
+use sqlx::types::Json;
+
+use crate::bindings::durable::core::tasks::{Child, Host};
+use crate::task::TransactionOptions;
+use crate::Task;
+
+#[async_trait::async_trait]
+impl Host for Task {
+    async fn launch(&mut self, children: Vec<Child>) -> wasmtime::Result<Vec<i64>> {
+        if self.state.transaction().is_some() {
+            anyhow::bail!("durable:core/tasks.launch cannot be called from within a transaction");
+        }
+
+        let options = TransactionOptions::new("durable:core/tasks.launch").database(true);
+        if let Some(ids) = self.state.enter::<Vec<i64>>(options).await? {
+            return Ok(ids);
+        }
+
+        let mut names = Vec::with_capacity(children.len());
+        let mut entrypoints = Vec::with_capacity(children.len());
+        let mut data = Vec::with_capacity(children.len());
+        for child in &children {
+            let json: &RawValue = serde_json::from_str(&child.data).map_err(|e| {
+                anyhow::anyhow!("durable:core/tasks.launch called with invalid json data: {e}")
+            })?;
+
+            names.push(child.name.as_str());
+            entrypoints.push(child.entrypoint.as_deref());
+            data.push(Json(json));
+        }
+
+        let task_id = self.state.task_id();
+        let schema = self.state.shared().schema.clone();
+        let txn = self.state.transaction_mut().unwrap();
+        let tx = txn.conn().unwrap();
+
+        // The children inherit the program, SQL context, tenant, instance, and
+        // labels of the parent task.
+        let ids = sqlx::query_scalar!(
+            r#"
+            INSERT INTO durable.task(
+                name, wasm, data, sql_context, entrypoint, tenant, instance, labels, parent_id,
+                running_on
+            )
+            SELECT
+                t.name,
+                parent.wasm,
+                t.data,
+                parent.sql_context,
+                t.entrypoint,
+                parent.tenant,
+                parent.instance,
+                parent.labels,
+                parent.id,
+                (
+                    SELECT id
+                     FROM durable.worker
+                    WHERE worker.instance IS NOT DISTINCT FROM parent.instance
+                    ORDER BY random()
+                    LIMIT 1
+                    FOR SHARE SKIP LOCKED
+                ) as running_on
+             FROM UNNEST($2::text[], $3::jsonb[], $4::text[])
+                WITH ORDINALITY as t(name, data, entrypoint, idx)
+             CROSS JOIN durable.task parent
+            WHERE parent.id = $1
+            ORDER BY t.idx
+            RETURNING id
+            "#,
+            task_id,
+            &names as &[&str],
+            &data as &[Json<&RawValue>],
+            &entrypoints as &[Option<&str>]
+        )
+        .fetch_all(schema.on(&mut **tx))
+        .await?;
+
+        self.state.exit(&ids).await?;
+
+        Ok(ids)
+    }
+
+    async fn set_result(&mut self, data: String) -> wasmtime::Result<()> {
+        if self.state.transaction().is_some() {
+            anyhow::bail!(
+                "durable:core/tasks.set-result cannot be called from within a transaction"
+            );
+        }
+
+        let json: &RawValue = serde_json::from_str(&data).map_err(|e| {
+            anyhow::anyhow!("durable:core/tasks.set-result called with invalid json data: {e}")
+        })?;
+
+        let options = TransactionOptions::new("durable:core/tasks.set-result").database(true);
+        if self.state.enter::<()>(options).await?.is_some() {
+            return Ok(());
+        }
+
+        let task_id = self.state.task_id();
+        let schema = self.state.shared().schema.clone();
+        let txn = self.state.transaction_mut().unwrap();
+        let tx = txn.conn().unwrap();
+
+        sqlx::query!(
+            "UPDATE durable.task SET result = $2 WHERE id = $1",
+            task_id,
+            Json(json) as Json<&RawValue>
+        )
+        .execute(schema.on(&mut **tx))
+        .await?;
+
+        self.state.exit(&()).await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,135 @@
+//! Version negotiation for the `durable:core` interfaces imported by
+//! workflows.
+//!
+//! Workflows import the `durable:core` interfaces under the version of the
+//! WIT package that they were built against (e.g. `durable:core/sql@2.2.0`),
+//! while the runtime implements the latest version of the package. Changes to
+//! the package are always backwards compatible, so the linker resolves imports
+//! of any older `2.x` version to the current implementation. This allows
+//! workers and workflow programs to be upgraded independently.
+//!
+//! Before a workflow is instantiated the worker checks the versions that it
+//! imports:
+//! - Imports of a newer version than the runtime implements are rejected with
+//!   an error saying which version is needed, instead of failing to link.
+//! - Imports of a different major version are rejected.
+//! - Imports from more than [`SUPPORTED_PREVIOUS_VERSIONS`] minor versions ago
+//!   are deprecated. They still work, but are logged and counted in the
+//!   `durable.deprecated_interface_imports` metric. Setting
+//!   [`Config::deny_deprecated_interfaces`] rejects them instead.
+//!
+//! [`Config::deny_deprecated_interfaces`]: crate::Config::deny_deprecated_interfaces
+
+use semver::Version;
+use wasmtime::component::Component;
+use wasmtime::Engine;
+
+/// The version of the `durable:core` WIT package implemented by the runtime.
+pub const CURRENT_VERSION: Version = Version::new(2, 7, 0);
+
+/// The number of previous minor versions of the `durable:core` package that
+/// are supported without being deprecated.
+pub const SUPPORTED_PREVIOUS_VERSIONS: u64 = 3;
+
+/// Whether workflows importing `version` of the package are deprecated.
+pub fn is_deprecated(version: &Version) -> bool {
+    version.major == CURRENT_VERSION.major
+        && version.minor + SUPPORTED_PREVIOUS_VERSIONS < CURRENT_VERSION.minor
+}
+
+/// Check the `durable:core` imports of a workflow against the versions that
+/// the runtime supports.
+///
+/// Returns the names of the deprecated interfaces that the workflow imports.
+pub(crate) fn check_imports(
+    engine: &Engine,
+    component: &Component,
+    deny_deprecated: bool,
+) -> anyhow::Result<Vec<String>> {
+    let mut deprecated = Vec::new();
+
+    for (name, _) in component.component_type().imports(engine) {
+        let Some(version) = import_version(name) else {
+            continue;
+        };
+
+        if version.major != CURRENT_VERSION.major {
+            anyhow::bail!(
+                "the workflow imports `{name}` but this worker only supports version {}.x of \
+                 durable:core",
+                CURRENT_VERSION.major
+            );
+        }
+
+        if version > CURRENT_VERSION {
+            anyhow::bail!(
+                "the workflow imports `{name}`, which is newer than the durable:core@\
+                 {CURRENT_VERSION} implemented by this worker. The worker needs to be upgraded to \
+                 run it."
+            );
+        }
+
+        if is_deprecated(&version) {
+            if deny_deprecated {
+                anyhow::bail!(
+                    "the workflow imports `{name}`, which is deprecated. It needs to be rebuilt \
+                     against durable:core@{CURRENT_VERSION}."
+                );
+            }
+
+            deprecated.push(name.to_owned());
+        }
+    }
+
+    Ok(deprecated)
+}
+
+/// Get the version of a `durable:core` interface import.
+///
+/// Returns `None` for imports from other packages.
+fn import_version(name: &str) -> Option<Version> {
+    let (_, version) = name.strip_prefix("durable:core/")?.split_once('@')?;
+
+    Version::parse(version).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn current_version_matches_wit() {
+        let wit = include_str!("../../../wit/imports.wit");
+        let package = format!("package durable:core@{CURRENT_VERSION};");
+
+        assert!(
+            wit.lines().any(|line| line.trim() == package),
+            "CURRENT_VERSION does not match the version of the WIT package"
+        );
+    }
+
+    #[test]
+    fn parses_import_versions() {
+        assert_eq!(
+            import_version("durable:core/sql@2.2.0"),
+            Some(Version::new(2, 2, 0))
+        );
+        assert_eq!(import_version("wasi:cli/run@0.2.0"), None);
+        assert_eq!(import_version("durable:core/sql"), None);
+    }
+
+    #[test]
+    fn deprecates_old_versions() {
+        assert!(!is_deprecated(&CURRENT_VERSION));
+        assert!(!is_deprecated(&Version::new(
+            2,
+            CURRENT_VERSION.minor - SUPPORTED_PREVIOUS_VERSIONS,
+            0
+        )));
+        assert!(is_deprecated(&Version::new(
+            2,
+            CURRENT_VERSION.minor - SUPPORTED_PREVIOUS_VERSIONS - 1,
+            0
+        )));
+    }
+}
@@ -1,6 +1,68 @@
+//! Extension points for the durable runtime.
+//!
+//! Plugins are how host functions get exposed to workflows. Everything that
+//! the runtime itself provides to workflows (the `durable:core` interfaces and
+//! the WASI interfaces) is set up by [`DurablePlugin`], and third-party
+//! plugins are registered the same way via [`WorkerBuilder::plugin`].
+//!
+//! # Writing a plugin
+//! A plugin is a type implementing [`Plugin`]. Every time a task is started,
+//! the worker calls [`Plugin::setup`] with a fresh [`Linker`] and [`Task`].
+//! Within `setup` a plugin should:
+//! 1. Insert any per-task state it needs using [`Task::insert_plugin_state`].
+//!    This state is keyed by its type, so plugins should use a private type for
+//!    it to avoid colliding with other plugins.
+//! 2. Add its host functions to the linker. These can then access the state via
+//!    [`Task::plugin_state`] or [`Task::plugin_state_mut`].
+//!
+//! Workers with an [`instance_pool`] reuse the linker from the first task
+//! running a program for all later tasks running the same program. For those
+//! tasks the worker calls [`Plugin::setup_state`] instead of `setup`, so the
+//! functions that a plugin adds to the linker must not depend on the task
+//! that it was set up for. Plugins can override `setup_state` to skip
+//! building a linker that is thrown away anyway.
+//!
+//! If the plugin hands out WIT resources to the workflow then their data
+//! should be stored in [`Task::resources`]. Implementing [`Resourceable`] for
+//! the resource type allows [`Resources`] to check that a resource created
+//! within a transaction is not used outside of it.
+//!
+//! Host functions must be careful to keep the workflow deterministic. Anything
+//! that interacts with the outside world should only be allowed within a
+//! transaction (see [`TaskState::transaction`]), since the results of
+//! transactions are the only thing that is persisted when a task restarts.
+//!
+//! Plugins can also observe what a task is doing by overriding the lifecycle
+//! hooks on [`Plugin`]. These are called in the following order:
+//! - [`on_task_start`] once the workflow has been instantiated, right before it
+//!   starts running,
+//! - [`on_transaction_enter`] and [`on_transaction_exit`] around every new
+//!   transaction that the workflow executes,
+//! - [`on_task_complete`] once the task has exited.
+//!
+//! Note that a task may be run many times (e.g. if it gets suspended or the
+//! worker running it dies) so `on_task_start` may be called multiple times
+//! for the same task and `on_task_complete` may never be called.
+//!
+//! See `examples/transaction_counter.rs` in this crate for a complete plugin.
+//!
+//! [`WorkerBuilder::plugin`]: crate::WorkerBuilder::plugin
+//! [`instance_pool`]: crate::Config::instance_pool
+//! [`Resourceable`]: crate::Resourceable
+//! [`Resources`]: crate::Resources
+//! [`TaskState::transaction`]: crate::task::TaskState::transaction
+//! [`on_task_start`]: Plugin::on_task_start
+//! [`on_transaction_enter`]: Plugin::on_transaction_enter
+//! [`on_transaction_exit`]: Plugin::on_transaction_exit
+//! [`on_task_complete`]: Plugin::on_task_complete
+
+use std::any::Any;
+
+use anyhow::Context;
 use wasi::WasiResources;
 use wasmtime::component::Linker;
 
+use crate::error::TaskStatus;
 use crate::task::Task;
 
 pub mod durable;
@@ -12,6 +74,8 @@ pub mod wasi;
 /// Plugins allow you to expose custom functions to workers running within the
 /// WASM vm. This trait provides the hooks necessary for a plugin to set itself
 /// up when a task is started.
+///
+/// See the [module docs](self) for details on how to write a plugin.
 pub trait Plugin: Send + Sync {
     /// The name of this plugin.
     ///
@@ -24,6 +88,102 @@ pub trait Plugin: Send + Sync {
     /// This should add any functions exported by this plugin to the linker and
     /// setup any state this plugin needs within the task plugin data.
     fn setup(&self, linker: &mut Linker<Task>, store: &mut Task) -> wasmtime::Result<()>;
+
+    /// Set up the state this plugin needs within a task that is instantiated
+    /// using a linker that has already been set up by this plugin.
+    ///
+    /// This is called instead of [`setup`](Plugin::setup) when the worker
+    /// reuses a pre-instantiated program. The default implementation calls
+    /// `setup` with a scratch linker that is then discarded.
+    fn setup_state(&self, engine: &wasmtime::Engine, task: &mut Task) -> wasmtime::Result<()> {
+        self.setup(&mut Linker::new(engine), task)
+    }
+
+    /// The WIT interfaces that this plugin provides to workflows.
+    ///
+    /// These are reported to workflows by `durable:core/capabilities` so that
+    /// they can check whether the plugin is available before using it. Each
+    /// entry should be the fully qualified name of the interface, including
+    /// its version (e.g. `my:plugin/counter@1.0.0`).
+    fn interfaces(&self) -> Vec<String> {
+        Vec::new()
+    }
+
+    /// Called after the workflow has been instantiated, right before it starts
+    /// running.
+    ///
+    /// Returning an error here prevents the task from running.
+    fn on_task_start(&self, task: &mut Task) -> wasmtime::Result<()> {
+        let _ = task;
+        Ok(())
+    }
+
+    /// Called once the workflow has exited with a final status.
+    ///
+    /// This is not called if the task is suspended or if it was moved to a
+    /// different worker.
+    fn on_task_complete(&self, task: &mut Task, status: TaskStatus) {
+        let _ = (task, status);
+    }
+
+    /// Called after the workflow has started a new transaction.
+    ///
+    /// The transaction is available via [`TaskState::transaction`]. This is not
+    /// called for transactions whose results are read back from the event log.
+    /// Returning an error here will cause the workflow to trap.
+    ///
+    /// [`TaskState::transaction`]: crate::task::TaskState::transaction
+    fn on_transaction_enter(&self, task: &mut Task) -> wasmtime::Result<()> {
+        let _ = task;
+        Ok(())
+    }
+
+    /// Called right before the result of the current transaction is saved.
+    ///
+    /// The transaction is still available via [`TaskState::transaction`].
+    /// Returning an error here will cause the workflow to trap and the
+    /// transaction will not be saved.
+    ///
+    /// [`TaskState::transaction`]: crate::task::TaskState::transaction
+    fn on_transaction_exit(&self, task: &mut Task) -> wasmtime::Result<()> {
+        let _ = task;
+        Ok(())
+    }
+}
+
+impl Task {
+    /// Get the per-task state of type `T` that was inserted by a plugin.
+    pub fn plugin_state<T: Any + Send>(&self) -> Option<&T> {
+        self.plugins.get()
+    }
+
+    /// Mutably get the per-task state of type `T` that was inserted by a
+    /// plugin.
+    pub fn plugin_state_mut<T: Any + Send>(&mut self) -> Option<&mut T> {
+        self.plugins.get_mut()
+    }
+
+    /// Insert per-task plugin state of type `T`, returning the previous
+    /// value, if there was one.
+    pub fn insert_plugin_state<T: Any + Send>(&mut self, state: T) -> Option<T> {
+        self.plugins.insert(state)
+    }
+
+    /// Call `hook` for each plugin registered with the worker, in the order
+    /// that they were registered.
+    pub(crate) fn run_plugin_hooks(
+        &mut self,
+        hook: impl Fn(&dyn Plugin, &mut Task) -> wasmtime::Result<()>,
+    ) -> wasmtime::Result<()> {
+        let shared = self.state.shared().clone();
+
+        for plugin in shared.plugins.iter() {
+            hook(&**plugin, self)
+                .with_context(|| format!("plugin `{}` returned an error", plugin.name()))?;
+        }
+
+        Ok(())
+    }
 }
 
 pub use self::util::PluginMapExt;
@@ -38,8 +198,13 @@ impl Plugin for DurablePlugin {
     fn setup(&self, linker: &mut Linker<Task>, task: &mut Task) -> wasmtime::Result<()> {
         use crate::bindings::Imports;
 
-        task.plugins.insert(WasiResources::new());
+        task.insert_plugin_state(WasiResources::new());
         Imports::add_to_linker(linker, |task| task)?;
         Ok(())
     }
+
+    fn setup_state(&self, _: &wasmtime::Engine, task: &mut Task) -> wasmtime::Result<()> {
+        task.insert_plugin_state(WasiResources::new());
+        Ok(())
+    }
 }
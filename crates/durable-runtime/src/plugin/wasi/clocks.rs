@@ -27,9 +27,9 @@ impl From<SerializableDateTime> for Datetime {
 impl wasi::clocks::wall_clock::Host for Task {
     async fn now(&mut self) -> wasmtime::Result<Datetime> {
         let options = TransactionOptions::new("wasi:clocks/wall-clock.now");
+        let now = SystemTime::from(self.state.now());
         self.state
             .maybe_do_transaction_sync(options, |_| {
-                let now = SystemTime::now();
                 let duration = now
                     .duration_since(SystemTime::UNIX_EPOCH)
                     .unwrap_or(Duration::ZERO);
@@ -54,9 +54,9 @@ impl wasi::clocks::wall_clock::Host for Task {
 impl wasi::clocks::monotonic_clock::Host for Task {
     async fn now(&mut self) -> wasmtime::Result<Instant> {
         let options = TransactionOptions::new("wasi:clocks/monotonic-clock.now");
+        let now = self.state.now();
         self.state
             .maybe_do_transaction_sync(options, |_| {
-                let now = Utc::now();
                 let timestamp = now.timestamp() as u64;
                 let nanos = timestamp * NS_PER_S + now.timestamp_subsec_nanos() as u64;
 
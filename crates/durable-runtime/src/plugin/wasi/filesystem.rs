@@ -1,73 +1,528 @@
+//! A sandboxed implementation of the WASI filesystem.
+//!
+//! Workflows can only access the directories that have been explicitly
+//! preopened in the worker [`Config`](crate::Config). Paths are resolved
+//! relative to the descriptor they are opened from and are never allowed to
+//! escape the preopened directory, whether via `..` or via symlinks.
+//!
+//! None of the operations here are recorded in the workflow's event log. See
+//! [`Config::preopen`](crate::Config::preopen) for the resulting semantics.
+
+use std::fs::{File, Metadata, OpenOptions};
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::path::{Component, Path, PathBuf};
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::Context;
 use wasmtime::component::Resource;
 
+use super::scratch::{is_scratch, SCRATCH_BASE};
+use super::WasiResources;
 use crate::bindings::wasi;
 use crate::bindings::wasi::filesystem::types::*;
+use crate::bindings::wasi::io::streams::StreamError;
+use crate::plugin::PluginMapExt;
 use crate::task::Task;
+use crate::DirPerms;
+
+/// Stream resource ids below this are reserved for stdin and stdout/stderr.
+const FILE_STREAM_BASE: u32 = 2;
+
+/// An open file or directory within a preopened directory.
+pub(super) struct DescriptorData {
+    /// The canonical host path of the preopened directory.
+    root: Arc<Path>,
+
+    /// The host path of this descriptor.
+    path: PathBuf,
+    perms: DirPerms,
+    flags: DescriptorFlags,
+
+    /// The open file, or `None` if this descriptor is a directory.
+    file: Option<File>,
+}
+
+impl DescriptorData {
+    /// Resolve `path` relative to this descriptor.
+    fn resolve(&self, path: &str) -> Result<PathBuf, ErrorCode> {
+        let mut resolved = self.path.clone();
+        for component in Path::new(path).components() {
+            match component {
+                Component::Normal(part) => resolved.push(part),
+                Component::CurDir => (),
+                Component::ParentDir => {
+                    resolved.pop();
+
+                    if !resolved.starts_with(&self.root) {
+                        return Err(ErrorCode::NotPermitted);
+                    }
+                }
+                Component::RootDir | Component::Prefix(_) => return Err(ErrorCode::NotPermitted),
+            }
+        }
+
+        // Make sure that following any symlinks along the way doesn't take us
+        // outside of the preopened directory. Paths that don't exist yet (e.g.
+        // a file that is about to be created) are checked via their closest
+        // existing ancestor.
+        let mut existing = resolved.as_path();
+        let canonical = loop {
+            match existing.canonicalize() {
+                Ok(path) => break path,
+                Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                    // A dangling symlink could point anywhere.
+                    if existing.symlink_metadata().is_ok() {
+                        return Err(ErrorCode::NotPermitted);
+                    }
+
+                    existing = existing.parent().ok_or(ErrorCode::NotPermitted)?;
+                }
+                Err(e) => return Err(error_code(&e)),
+            }
+        };
+
+        if !canonical.starts_with(&self.root) {
+            return Err(ErrorCode::NotPermitted);
+        }
+
+        Ok(resolved)
+    }
+
+    fn check_writable(&self) -> Result<(), ErrorCode> {
+        match self.perms.writable() {
+            true => Ok(()),
+            false => Err(ErrorCode::ReadOnly),
+        }
+    }
+
+    fn file(&self) -> Result<&File, ErrorCode> {
+        self.file.as_ref().ok_or(ErrorCode::IsDirectory)
+    }
+
+    fn child(&self, path: PathBuf, flags: DescriptorFlags, file: Option<File>) -> Self {
+        Self {
+            root: self.root.clone(),
+            path,
+            perms: self.perms,
+            flags,
+            file,
+        }
+    }
+}
+
+/// A stream that reads from or writes to a file.
+pub(super) struct FileStream {
+    file: File,
+    position: u64,
+    append: bool,
+}
+
+impl FileStream {
+    pub(super) fn read(&mut self, len: u64) -> io::Result<Vec<u8>> {
+        self.file.seek(SeekFrom::Start(self.position))?;
+
+        let mut data = Vec::new();
+        (&mut self.file).take(len).read_to_end(&mut data)?;
+        self.position += data.len() as u64;
+
+        Ok(data)
+    }
+
+    pub(super) fn write(&mut self, data: &[u8]) -> io::Result<()> {
+        match self.append {
+            true => self.file.seek(SeekFrom::End(0))?,
+            false => self.file.seek(SeekFrom::Start(self.position))?,
+        };
+
+        self.file.write_all(data)?;
+        self.position += data.len() as u64;
+
+        Ok(())
+    }
+}
+
+impl WasiResources {
+    fn descriptor(&self, res: &Resource<Descriptor>) -> Result<&DescriptorData, ErrorCode> {
+        self.descriptors
+            .get(res.rep() as usize)
+            .ok_or(ErrorCode::BadDescriptor)
+    }
+
+    fn insert_descriptor(&mut self, data: DescriptorData) -> Resource<Descriptor> {
+        Resource::new_own(self.descriptors.insert(data) as u32)
+    }
+
+    fn insert_stream<T: 'static>(&mut self, stream: FileStream) -> Resource<T> {
+        Resource::new_own(self.file_streams.insert(stream) as u32 + FILE_STREAM_BASE)
+    }
+
+    /// Get the file stream with resource id `rep`, if it refers to one.
+    fn file_stream(&mut self, rep: u32) -> Option<&mut FileStream> {
+        let index = rep.checked_sub(FILE_STREAM_BASE)?;
+        self.file_streams.get_mut(index as usize)
+    }
+
+    pub(super) fn remove_file_stream(&mut self, rep: u32) {
+        if rep >= SCRATCH_BASE {
+            self.remove_scratch_stream(rep);
+        } else if let Some(index) = rep.checked_sub(FILE_STREAM_BASE) {
+            self.file_streams.try_remove(index as usize);
+        }
+    }
+
+    /// Convert an IO error into a stream error that the workflow can inspect
+    /// with `filesystem-error-code`.
+    pub(super) fn stream_error(&mut self, error: io::Error) -> StreamError {
+        let id = self.errors.insert(anyhow::Error::new(error));
+        StreamError::LastOperationFailed(Resource::new_own(id as u32))
+    }
+}
+
+pub(super) fn error_code(error: &io::Error) -> ErrorCode {
+    use std::io::ErrorKind;
+
+    match error.kind() {
+        ErrorKind::NotFound => ErrorCode::NoEntry,
+        ErrorKind::PermissionDenied => ErrorCode::Access,
+        ErrorKind::AlreadyExists => ErrorCode::Exist,
+        ErrorKind::InvalidInput => ErrorCode::Invalid,
+        ErrorKind::Interrupted => ErrorCode::Interrupted,
+        ErrorKind::Unsupported => ErrorCode::Unsupported,
+        ErrorKind::OutOfMemory => ErrorCode::InsufficientMemory,
+        ErrorKind::WouldBlock => ErrorCode::WouldBlock,
+        ErrorKind::DirectoryNotEmpty => ErrorCode::NotEmpty,
+        ErrorKind::IsADirectory => ErrorCode::IsDirectory,
+        ErrorKind::NotADirectory => ErrorCode::NotDirectory,
+        ErrorKind::ReadOnlyFilesystem => ErrorCode::ReadOnly,
+        ErrorKind::StorageFull => ErrorCode::InsufficientSpace,
+        ErrorKind::FileTooLarge => ErrorCode::FileTooLarge,
+        ErrorKind::CrossesDevices => ErrorCode::CrossDevice,
+        ErrorKind::InvalidFilename => ErrorCode::NameTooLong,
+        _ => ErrorCode::Io,
+    }
+}
+
+fn descriptor_type(metadata: &Metadata) -> DescriptorType {
+    let ty = metadata.file_type();
+
+    if ty.is_file() {
+        DescriptorType::RegularFile
+    } else if ty.is_dir() {
+        DescriptorType::Directory
+    } else if ty.is_symlink() {
+        DescriptorType::SymbolicLink
+    } else {
+        DescriptorType::Unknown
+    }
+}
+
+fn datetime(time: io::Result<SystemTime>) -> Option<Datetime> {
+    time.ok()?
+        .duration_since(UNIX_EPOCH)
+        .ok()
+        .map(Datetime::from)
+}
+
+fn descriptor_stat(metadata: &Metadata) -> DescriptorStat {
+    DescriptorStat {
+        type_: descriptor_type(metadata),
+        link_count: 1,
+        size: metadata.len(),
+        data_access_timestamp: datetime(metadata.accessed()),
+        data_modification_timestamp: datetime(metadata.modified()),
+        status_change_timestamp: None,
+    }
+}
+
+fn metadata(path: &Path, flags: PathFlags) -> io::Result<Metadata> {
+    match flags.contains(PathFlags::SYMLINK_FOLLOW) {
+        true => path.metadata(),
+        false => path.symlink_metadata(),
+    }
+}
+
+fn metadata_hash(path: &Path) -> Result<MetadataHashValue, ErrorCode> {
+    let path = path.canonicalize().map_err(|e| error_code(&e))?;
+
+    let mut lower = DefaultHasher::new();
+    path.hash(&mut lower);
+
+    let mut upper = DefaultHasher::new();
+    (&path, "upper").hash(&mut upper);
+
+    Ok(MetadataHashValue {
+        lower: lower.finish(),
+        upper: upper.finish(),
+    })
+}
+
+impl Task {
+    fn wasi_resources(&mut self) -> &mut WasiResources {
+        self.plugins.expect_mut::<WasiResources>()
+    }
+
+    /// Read up to `len` bytes from the file stream with resource id `rep`.
+    ///
+    /// Returns `None` if `rep` does not refer to a file stream.
+    pub(super) fn read_file_stream(&mut self, rep: u32, len: u64) -> Option<io::Result<Vec<u8>>> {
+        if rep >= SCRATCH_BASE {
+            return self.read_scratch_stream(rep, len);
+        }
+
+        self.wasi_resources()
+            .file_stream(rep)
+            .map(|stream| stream.read(len))
+    }
+
+    /// Write `data` to the file stream with resource id `rep`.
+    ///
+    /// Returns `None` if `rep` does not refer to a file stream.
+    pub(super) fn write_file_stream(&mut self, rep: u32, data: &[u8]) -> Option<io::Result<()>> {
+        if rep >= SCRATCH_BASE {
+            return self.write_scratch_stream(rep, data);
+        }
+
+        self.wasi_resources()
+            .file_stream(rep)
+            .map(|stream| stream.write(data))
+    }
+
+    fn open_stream<T: 'static>(
+        &mut self,
+        res: Resource<Descriptor>,
+        position: u64,
+        append: bool,
+        required: DescriptorFlags,
+    ) -> Result<Resource<T>, ErrorCode> {
+        let resources = self.wasi_resources();
+        let desc = resources.descriptor(&res)?;
+        if !desc.flags.contains(required) {
+            return Err(ErrorCode::BadDescriptor);
+        }
+
+        let file = desc.file()?.try_clone().map_err(|e| error_code(&e))?;
+        Ok(resources.insert_stream(FileStream {
+            file,
+            position,
+            append,
+        }))
+    }
+
+    fn open_at_impl(
+        &mut self,
+        res: Resource<Descriptor>,
+        path_flags: PathFlags,
+        path: String,
+        open_flags: OpenFlags,
+        flags: DescriptorFlags,
+    ) -> Result<Resource<Descriptor>, ErrorCode> {
+        let resources = self.wasi_resources();
+        let desc = resources.descriptor(&res)?;
+        let path = desc.resolve(&path)?;
+
+        let writes = flags.intersects(DescriptorFlags::WRITE | DescriptorFlags::MUTATE_DIRECTORY)
+            || open_flags.intersects(OpenFlags::CREATE | OpenFlags::TRUNCATE);
+        if writes {
+            desc.check_writable()?;
+        }
+
+        let existing = metadata(&path, path_flags).ok();
+        if open_flags.contains(OpenFlags::DIRECTORY)
+            || existing.as_ref().is_some_and(|m| m.is_dir())
+        {
+            match &existing {
+                Some(metadata) if metadata.is_dir() => (),
+                Some(_) => return Err(ErrorCode::NotDirectory),
+                None => return Err(ErrorCode::NoEntry),
+            }
+
+            if flags.contains(DescriptorFlags::WRITE) {
+                return Err(ErrorCode::IsDirectory);
+            }
+
+            let child = desc.child(path, flags, None);
+            return Ok(resources.insert_descriptor(child));
+        }
+
+        let file = OpenOptions::new()
+            .read(flags.contains(DescriptorFlags::READ) || !flags.contains(DescriptorFlags::WRITE))
+            .write(flags.contains(DescriptorFlags::WRITE))
+            .create(open_flags.contains(OpenFlags::CREATE))
+            .create_new(open_flags.contains(OpenFlags::CREATE | OpenFlags::EXCLUSIVE))
+            .truncate(open_flags.contains(OpenFlags::TRUNCATE))
+            .open(&path)
+            .map_err(|e| error_code(&e))?;
+
+        let child = desc.child(path, flags, Some(file));
+        Ok(resources.insert_descriptor(child))
+    }
+
+    fn read_directory_impl(
+        &mut self,
+        res: Resource<Descriptor>,
+    ) -> Result<Resource<DirectoryEntryStream>, ErrorCode> {
+        let resources = self.wasi_resources();
+        let desc = resources.descriptor(&res)?;
+        if desc.file.is_some() {
+            return Err(ErrorCode::NotDirectory);
+        }
+
+        let mut entries = Vec::new();
+        for entry in std::fs::read_dir(&desc.path).map_err(|e| error_code(&e))? {
+            let entry = entry.map_err(|e| error_code(&e))?;
+            let type_ = match entry.metadata() {
+                Ok(metadata) => descriptor_type(&metadata),
+                Err(_) => DescriptorType::Unknown,
+            };
+
+            entries.push(DirectoryEntry {
+                type_,
+                name: entry.file_name().to_string_lossy().into_owned(),
+            });
+        }
+
+        // The order returned by the OS is arbitrary. Sort the entries so that at
+        // least listing the same directory is consistent.
+        entries.sort_by(|a, b| a.name.cmp(&b.name));
+        entries.reverse();
+
+        let id = resources.dir_streams.insert(entries);
+        Ok(Resource::new_own(id as u32))
+    }
+
+    /// Resolve `path` relative to the descriptor `res` for an operation that
+    /// modifies the directory.
+    fn resolve_mut(
+        &mut self,
+        res: &Resource<Descriptor>,
+        path: &str,
+    ) -> Result<PathBuf, ErrorCode> {
+        let desc = self.wasi_resources().descriptor(res)?;
+        desc.check_writable()?;
+        desc.resolve(path)
+    }
+}
 
-// It is not possible to get access to a file handle within the workflow so all
-// of these are stubs.
 #[async_trait::async_trait]
 impl wasi::filesystem::types::HostDescriptor for Task {
     async fn read_via_stream(
         &mut self,
-        _: Resource<Descriptor>,
-        _: Filesize,
+        res: Resource<Descriptor>,
+        offset: Filesize,
     ) -> wasmtime::Result<Result<Resource<InputStream>, ErrorCode>> {
-        anyhow::bail!("wasi:filesystem/types.descriptor.read-via-stream is not implemented")
+        if is_scratch(&res) {
+            return Ok(self.scratch_open_stream(res, offset, false, DescriptorFlags::READ));
+        }
+
+        Ok(self.open_stream(res, offset, false, DescriptorFlags::READ))
     }
 
     async fn write_via_stream(
         &mut self,
-        _: Resource<Descriptor>,
-        _: Filesize,
+        res: Resource<Descriptor>,
+        offset: Filesize,
     ) -> wasmtime::Result<Result<Resource<OutputStream>, ErrorCode>> {
-        anyhow::bail!("wasi:filesystem/types.descriptor.write-via-stream is not implemented")
+        if is_scratch(&res) {
+            return Ok(self.scratch_open_stream(res, offset, false, DescriptorFlags::WRITE));
+        }
+
+        Ok(self.open_stream(res, offset, false, DescriptorFlags::WRITE))
     }
 
     async fn append_via_stream(
         &mut self,
-        _: Resource<Descriptor>,
+        res: Resource<Descriptor>,
     ) -> wasmtime::Result<Result<Resource<OutputStream>, ErrorCode>> {
-        anyhow::bail!("wasi:filesystem/types.descriptor.append-via-stream is not implemented")
+        if is_scratch(&res) {
+            return Ok(self.scratch_open_stream(res, 0, true, DescriptorFlags::WRITE));
+        }
+
+        Ok(self.open_stream(res, 0, true, DescriptorFlags::WRITE))
     }
 
     async fn advise(
         &mut self,
-        _: Resource<Descriptor>,
+        res: Resource<Descriptor>,
         _: Filesize,
         _: Filesize,
         _: Advice,
     ) -> wasmtime::Result<Result<(), ErrorCode>> {
-        anyhow::bail!("wasi:filesystem/types.descriptor.advise is not implemented")
+        // Advice is only a hint so we are free to ignore it.
+        if is_scratch(&res) {
+            return Ok(self.scratch_get_flags(res).map(|_| ()));
+        }
+
+        Ok(self.wasi_resources().descriptor(&res).map(|_| ()))
     }
 
     async fn sync_data(
         &mut self,
-        _: Resource<Descriptor>,
+        res: Resource<Descriptor>,
     ) -> wasmtime::Result<Result<(), ErrorCode>> {
-        anyhow::bail!("wasi:filesystem/types.descriptor.sync-data is not implemented")
+        // The scratch directory only lives in memory.
+        if is_scratch(&res) {
+            return Ok(self.scratch_get_flags(res).map(|_| ()));
+        }
+
+        let resources = self.wasi_resources();
+        Ok(resources
+            .descriptor(&res)
+            .and_then(|desc| match &desc.file {
+                Some(file) => file.sync_data().map_err(|e| error_code(&e)),
+                None => Ok(()),
+            }))
     }
 
     async fn get_flags(
         &mut self,
-        _: Resource<Descriptor>,
+        res: Resource<Descriptor>,
     ) -> wasmtime::Result<Result<DescriptorFlags, ErrorCode>> {
-        anyhow::bail!("wasi:filesystem/types.descriptor.get-flags is not implemented")
+        if is_scratch(&res) {
+            return Ok(self.scratch_get_flags(res));
+        }
+
+        Ok(self
+            .wasi_resources()
+            .descriptor(&res)
+            .map(|desc| desc.flags))
     }
 
     async fn get_type(
         &mut self,
-        _: Resource<Descriptor>,
+        res: Resource<Descriptor>,
     ) -> wasmtime::Result<Result<DescriptorType, ErrorCode>> {
-        anyhow::bail!("wasi:filesystem/types.descriptor.get-type is not implemented")
+        if is_scratch(&res) {
+            return Ok(self.scratch_stat(res).map(|stat| stat.type_));
+        }
+
+        let resources = self.wasi_resources();
+        Ok(resources.descriptor(&res).and_then(|desc| {
+            desc.path
+                .metadata()
+                .map(|metadata| descriptor_type(&metadata))
+                .map_err(|e| error_code(&e))
+        }))
     }
 
     async fn set_size(
         &mut self,
-        _: Resource<Descriptor>,
-        _: Filesize,
+        res: Resource<Descriptor>,
+        size: Filesize,
     ) -> wasmtime::Result<Result<(), ErrorCode>> {
-        anyhow::bail!("wasi:filesystem/types.descriptor.set-size is not implemented")
+        if is_scratch(&res) {
+            return Ok(self.scratch_set_size(res, size));
+        }
+
+        let resources = self.wasi_resources();
+        Ok(resources.descriptor(&res).and_then(|desc| {
+            if !desc.flags.contains(DescriptorFlags::WRITE) {
+                return Err(ErrorCode::BadDescriptor);
+            }
+
+            desc.file()?.set_len(size).map_err(|e| error_code(&e))
+        }))
     }
 
     async fn set_times(
@@ -76,60 +531,143 @@ impl wasi::filesystem::types::HostDescriptor for Task {
         _: NewTimestamp,
         _: NewTimestamp,
     ) -> wasmtime::Result<Result<(), ErrorCode>> {
-        anyhow::bail!("wasi:filesystem/types.descriptor.set-times is not implemented")
+        Ok(Err(ErrorCode::Unsupported))
     }
 
     async fn read(
         &mut self,
-        _: Resource<Descriptor>,
-        _: Filesize,
-        _: Filesize,
+        res: Resource<Descriptor>,
+        len: Filesize,
+        offset: Filesize,
     ) -> wasmtime::Result<Result<(Vec<u8>, bool), ErrorCode>> {
-        anyhow::bail!("wasi:filesystem/types.descriptor.read is not implemented")
+        if is_scratch(&res) {
+            return Ok(self.scratch_read(res, len, offset));
+        }
+
+        let resources = self.wasi_resources();
+        Ok(resources.descriptor(&res).and_then(|desc| {
+            if !desc.flags.contains(DescriptorFlags::READ) {
+                return Err(ErrorCode::BadDescriptor);
+            }
+
+            let mut file = desc.file()?;
+            file.seek(SeekFrom::Start(offset))
+                .map_err(|e| error_code(&e))?;
+
+            let mut data = Vec::new();
+            file.take(len)
+                .read_to_end(&mut data)
+                .map_err(|e| error_code(&e))?;
+
+            let eof = (data.len() as u64) < len;
+            Ok((data, eof))
+        }))
     }
 
     async fn write(
         &mut self,
-        _: Resource<Descriptor>,
-        _: Vec<u8>,
-        _: Filesize,
+        res: Resource<Descriptor>,
+        data: Vec<u8>,
+        offset: Filesize,
     ) -> wasmtime::Result<Result<Filesize, ErrorCode>> {
-        anyhow::bail!("wasi:filesystem/types.descriptor.write is not implemented")
+        if is_scratch(&res) {
+            return Ok(self.scratch_write(res, data, offset));
+        }
+
+        let resources = self.wasi_resources();
+        Ok(resources.descriptor(&res).and_then(|desc| {
+            if !desc.flags.contains(DescriptorFlags::WRITE) {
+                return Err(ErrorCode::BadDescriptor);
+            }
+
+            let mut file = desc.file()?;
+            file.seek(SeekFrom::Start(offset))
+                .map_err(|e| error_code(&e))?;
+            file.write_all(&data).map_err(|e| error_code(&e))?;
+
+            Ok(data.len() as Filesize)
+        }))
     }
 
     async fn read_directory(
         &mut self,
-        _: Resource<Descriptor>,
+        res: Resource<Descriptor>,
     ) -> wasmtime::Result<Result<Resource<DirectoryEntryStream>, ErrorCode>> {
-        anyhow::bail!("wasi:filesystem/types.descriptor.read-directory is not implemented")
+        if is_scratch(&res) {
+            return Ok(self.scratch_read_directory(res));
+        }
+
+        Ok(self.read_directory_impl(res))
     }
 
-    async fn sync(&mut self, _: Resource<Descriptor>) -> wasmtime::Result<Result<(), ErrorCode>> {
-        anyhow::bail!("wasi:filesystem/types.descriptor.sync is not implemented")
+    async fn sync(&mut self, res: Resource<Descriptor>) -> wasmtime::Result<Result<(), ErrorCode>> {
+        // The scratch directory only lives in memory.
+        if is_scratch(&res) {
+            return Ok(self.scratch_get_flags(res).map(|_| ()));
+        }
+
+        let resources = self.wasi_resources();
+        Ok(resources
+            .descriptor(&res)
+            .and_then(|desc| match &desc.file {
+                Some(file) => file.sync_all().map_err(|e| error_code(&e)),
+                None => Ok(()),
+            }))
     }
 
     async fn create_directory_at(
         &mut self,
-        _: Resource<Descriptor>,
-        _: String,
+        res: Resource<Descriptor>,
+        path: String,
     ) -> wasmtime::Result<Result<(), ErrorCode>> {
-        anyhow::bail!("wasi:filesystem/types.descriptor.create-directory-at is not implemented")
+        if is_scratch(&res) {
+            return Ok(self.scratch_create_directory_at(res, path));
+        }
+
+        Ok(self
+            .resolve_mut(&res, &path)
+            .and_then(|path| std::fs::create_dir(path).map_err(|e| error_code(&e))))
     }
 
     async fn stat(
         &mut self,
-        _: Resource<Descriptor>,
+        res: Resource<Descriptor>,
     ) -> wasmtime::Result<Result<DescriptorStat, ErrorCode>> {
-        anyhow::bail!("wasi:filesystem/types.descriptor.stat is not implemented")
+        if is_scratch(&res) {
+            return Ok(self.scratch_stat(res));
+        }
+
+        let resources = self.wasi_resources();
+        Ok(resources.descriptor(&res).and_then(|desc| {
+            let metadata = match &desc.file {
+                Some(file) => file.metadata(),
+                None => desc.path.metadata(),
+            };
+
+            metadata
+                .map(|metadata| descriptor_stat(&metadata))
+                .map_err(|e| error_code(&e))
+        }))
     }
 
     async fn stat_at(
         &mut self,
-        _: Resource<Descriptor>,
-        _: PathFlags,
-        _: String,
+        res: Resource<Descriptor>,
+        flags: PathFlags,
+        path: String,
     ) -> wasmtime::Result<Result<DescriptorStat, ErrorCode>> {
-        anyhow::bail!("wasi:filesystem/types.descriptor.stat-at is not implemented")
+        if is_scratch(&res) {
+            return Ok(self.scratch_stat_at(res, path));
+        }
+
+        let resources = self.wasi_resources();
+        Ok(resources.descriptor(&res).and_then(|desc| {
+            let path = desc.resolve(&path)?;
+
+            metadata(&path, flags)
+                .map(|metadata| descriptor_stat(&metadata))
+                .map_err(|e| error_code(&e))
+        }))
     }
 
     async fn set_times_at(
@@ -140,7 +678,7 @@ impl wasi::filesystem::types::HostDescriptor for Task {
         _: NewTimestamp,
         _: NewTimestamp,
     ) -> wasmtime::Result<Result<(), ErrorCode>> {
-        anyhow::bail!("wasi:filesystem/types.descriptor.set-times-at is not implemented")
+        Ok(Err(ErrorCode::Unsupported))
     }
 
     async fn link_at(
@@ -151,18 +689,23 @@ impl wasi::filesystem::types::HostDescriptor for Task {
         _: Resource<Descriptor>,
         _: String,
     ) -> wasmtime::Result<Result<(), ErrorCode>> {
-        anyhow::bail!("wasi:filesystem/types.descriptor.link-at is not implemented")
+        // Links could be used to escape the preopened directory.
+        Ok(Err(ErrorCode::NotPermitted))
     }
 
     async fn open_at(
         &mut self,
-        _: Resource<Descriptor>,
-        _: PathFlags,
-        _: String,
-        _: OpenFlags,
-        _: DescriptorFlags,
+        res: Resource<Descriptor>,
+        path_flags: PathFlags,
+        path: String,
+        open_flags: OpenFlags,
+        flags: DescriptorFlags,
     ) -> wasmtime::Result<Result<Resource<Descriptor>, ErrorCode>> {
-        anyhow::bail!("wasi:filesystem/types.descriptor.open-at is not implemented")
+        if is_scratch(&res) {
+            return Ok(self.scratch_open_at(res, path, open_flags, flags));
+        }
+
+        Ok(self.open_at_impl(res, path_flags, path, open_flags, flags))
     }
 
     async fn readlink_at(
@@ -170,25 +713,41 @@ impl wasi::filesystem::types::HostDescriptor for Task {
         _: Resource<Descriptor>,
         _: String,
     ) -> wasmtime::Result<Result<String, ErrorCode>> {
-        anyhow::bail!("wasi:filesystem/types.descriptor.readlink-at is not implemented")
+        // Link targets are host paths, which shouldn't be exposed to the workflow.
+        Ok(Err(ErrorCode::NotPermitted))
     }
 
     async fn remove_directory_at(
         &mut self,
-        _: Resource<Descriptor>,
-        _: String,
+        res: Resource<Descriptor>,
+        path: String,
     ) -> wasmtime::Result<Result<(), ErrorCode>> {
-        anyhow::bail!("wasi:filesystem/types.descriptor.remove-directory-at is not implemented")
+        if is_scratch(&res) {
+            return Ok(self.scratch_remove_directory_at(res, path));
+        }
+
+        Ok(self
+            .resolve_mut(&res, &path)
+            .and_then(|path| std::fs::remove_dir(path).map_err(|e| error_code(&e))))
     }
 
     async fn rename_at(
         &mut self,
-        _: Resource<Descriptor>,
-        _: String,
-        _: Resource<Descriptor>,
-        _: String,
+        old: Resource<Descriptor>,
+        old_path: String,
+        new: Resource<Descriptor>,
+        new_path: String,
     ) -> wasmtime::Result<Result<(), ErrorCode>> {
-        anyhow::bail!("wasi:filesystem/types.descriptor.rename-at is not implemented")
+        if is_scratch(&old) || is_scratch(&new) {
+            return Ok(self.scratch_rename_at(old, old_path, new, new_path));
+        }
+
+        let result = self.resolve_mut(&old, &old_path).and_then(|old_path| {
+            let new_path = self.resolve_mut(&new, &new_path)?;
+            std::fs::rename(old_path, new_path).map_err(|e| error_code(&e))
+        });
+
+        Ok(result)
     }
 
     async fn symlink_at(
@@ -197,42 +756,88 @@ impl wasi::filesystem::types::HostDescriptor for Task {
         _: String,
         _: String,
     ) -> wasmtime::Result<Result<(), ErrorCode>> {
-        anyhow::bail!("wasi:filesystem/types.descriptor.symlink-at is not implemented")
+        // Links could be used to escape the preopened directory.
+        Ok(Err(ErrorCode::NotPermitted))
     }
 
     async fn unlink_file_at(
         &mut self,
-        _: Resource<Descriptor>,
-        _: String,
+        res: Resource<Descriptor>,
+        path: String,
     ) -> wasmtime::Result<Result<(), ErrorCode>> {
-        anyhow::bail!("wasi:filesystem/types.descriptor.unlink-file-at is not implemented")
+        if is_scratch(&res) {
+            return Ok(self.scratch_unlink_file_at(res, path));
+        }
+
+        Ok(self.resolve_mut(&res, &path).and_then(|path| {
+            let metadata = path.symlink_metadata().map_err(|e| error_code(&e))?;
+            if metadata.is_dir() {
+                return Err(ErrorCode::IsDirectory);
+            }
+
+            std::fs::remove_file(path).map_err(|e| error_code(&e))
+        }))
     }
 
     async fn is_same_object(
         &mut self,
-        _: Resource<Descriptor>,
-        _: Resource<Descriptor>,
+        a: Resource<Descriptor>,
+        b: Resource<Descriptor>,
     ) -> wasmtime::Result<bool> {
-        anyhow::bail!("wasi:filesystem/types.descriptor.is-same-object is not implemented")
+        if is_scratch(&a) || is_scratch(&b) {
+            return Ok(self.scratch_is_same_object(a, b));
+        }
+
+        let resources = self.wasi_resources();
+        let (Ok(a), Ok(b)) = (resources.descriptor(&a), resources.descriptor(&b)) else {
+            return Ok(false);
+        };
+
+        Ok(match (a.path.canonicalize(), b.path.canonicalize()) {
+            (Ok(a), Ok(b)) => a == b,
+            _ => false,
+        })
     }
 
     async fn metadata_hash(
         &mut self,
-        _: Resource<Descriptor>,
+        res: Resource<Descriptor>,
     ) -> wasmtime::Result<Result<MetadataHashValue, ErrorCode>> {
-        anyhow::bail!("wasi:filesystem/types.descriptor.metadata-hash is not implemented")
+        if is_scratch(&res) {
+            return Ok(self.scratch_metadata_hash(res));
+        }
+
+        let resources = self.wasi_resources();
+        Ok(resources
+            .descriptor(&res)
+            .and_then(|desc| metadata_hash(&desc.path)))
     }
 
     async fn metadata_hash_at(
         &mut self,
-        _: Resource<Descriptor>,
+        res: Resource<Descriptor>,
         _: PathFlags,
-        _: String,
+        path: String,
     ) -> wasmtime::Result<Result<MetadataHashValue, ErrorCode>> {
-        anyhow::bail!("wasi:filesystem/types.descriptor.metadata-hash-at is not implemented")
+        if is_scratch(&res) {
+            return Ok(self.scratch_metadata_hash_at(res, path));
+        }
+
+        let resources = self.wasi_resources();
+        Ok(resources
+            .descriptor(&res)
+            .and_then(|desc| metadata_hash(&desc.resolve(&path)?)))
     }
 
-    async fn drop(&mut self, _: Resource<Descriptor>) -> wasmtime::Result<()> {
+    async fn drop(&mut self, res: Resource<Descriptor>) -> wasmtime::Result<()> {
+        if is_scratch(&res) {
+            self.wasi_resources().remove_scratch_descriptor(res.rep());
+            return Ok(());
+        }
+
+        self.wasi_resources()
+            .descriptors
+            .try_remove(res.rep() as usize);
         Ok(())
     }
 }
@@ -241,14 +846,21 @@ impl wasi::filesystem::types::HostDescriptor for Task {
 impl wasi::filesystem::types::HostDirectoryEntryStream for Task {
     async fn read_directory_entry(
         &mut self,
-        _: Resource<DirectoryEntryStream>,
+        res: Resource<DirectoryEntryStream>,
     ) -> wasmtime::Result<Result<Option<DirectoryEntry>, ErrorCode>> {
-        anyhow::bail!(
-            "wasi:filesystem/types.directory-entry-stream.read-directory-entry is not implemented"
-        )
+        let resources = self.wasi_resources();
+        let Some(entries) = resources.dir_streams.get_mut(res.rep() as usize) else {
+            return Ok(Err(ErrorCode::BadDescriptor));
+        };
+
+        // Entries are stored in reverse order so that this can pop them off the end.
+        Ok(Ok(entries.pop()))
     }
 
-    async fn drop(&mut self, _: Resource<DirectoryEntryStream>) -> wasmtime::Result<()> {
+    async fn drop(&mut self, res: Resource<DirectoryEntryStream>) -> wasmtime::Result<()> {
+        self.wasi_resources()
+            .dir_streams
+            .try_remove(res.rep() as usize);
         Ok(())
     }
 }
@@ -257,16 +869,100 @@ impl wasi::filesystem::types::HostDirectoryEntryStream for Task {
 impl wasi::filesystem::types::Host for Task {
     async fn filesystem_error_code(
         &mut self,
-        _: Resource<Error>,
+        res: Resource<Error>,
     ) -> wasmtime::Result<Option<ErrorCode>> {
-        // The entire filesystem module is a stub so there are no fs error codes
-        Ok(None)
+        let resources = self.wasi_resources();
+        let error = resources
+            .errors
+            .get(res.rep() as usize)
+            .and_then(|error| error.downcast_ref::<io::Error>());
+
+        Ok(error.map(error_code))
     }
 }
 
 #[async_trait::async_trait]
 impl wasi::filesystem::preopens::Host for Task {
     async fn get_directories(&mut self) -> wasmtime::Result<Vec<(Resource<Descriptor>, String)>> {
-        Ok(Vec::new())
+        let preopens = self.state.config().preopens.clone();
+        let resources = self.wasi_resources();
+
+        let mut directories = Vec::with_capacity(preopens.len());
+        for preopen in preopens {
+            let root = preopen.host_path.canonicalize().with_context(|| {
+                format!(
+                    "failed to open preopened directory `{}`",
+                    preopen.host_path.display()
+                )
+            })?;
+
+            let mut flags = DescriptorFlags::READ;
+            if preopen.perms.writable() {
+                flags |= DescriptorFlags::MUTATE_DIRECTORY;
+            }
+
+            let desc = resources.insert_descriptor(DescriptorData {
+                path: root.clone(),
+                root: root.into(),
+                perms: preopen.perms,
+                flags,
+                file: None,
+            });
+
+            directories.push((desc, preopen.guest_path));
+        }
+
+        if let Some(dir) = &self.state.config().scratch_dir {
+            let guest_path = dir.guest_path.clone();
+            directories.push((self.scratch_root(), guest_path));
+        }
+
+        Ok(directories)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn descriptor(root: &Path) -> DescriptorData {
+        let root = root.canonicalize().unwrap();
+
+        DescriptorData {
+            path: root.clone(),
+            root: root.into(),
+            perms: DirPerms::Read,
+            flags: DescriptorFlags::READ,
+            file: None,
+        }
+    }
+
+    #[test]
+    fn resolve_stays_within_root() {
+        let dir = std::env::temp_dir().join(format!("durable-fs-test-{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("sub")).unwrap();
+        let desc = descriptor(&dir);
+        let root = desc.path.clone();
+
+        assert_eq!(desc.resolve("a.txt"), Ok(root.join("a.txt")));
+        assert_eq!(desc.resolve("sub/../b.txt"), Ok(root.join("b.txt")));
+        assert_eq!(
+            desc.resolve("./sub/new/c.txt"),
+            Ok(root.join("sub/new/c.txt"))
+        );
+        assert_eq!(desc.resolve(".."), Err(ErrorCode::NotPermitted));
+        assert_eq!(desc.resolve("sub/../../x"), Err(ErrorCode::NotPermitted));
+        assert_eq!(desc.resolve("/etc/passwd"), Err(ErrorCode::NotPermitted));
+
+        #[cfg(unix)]
+        {
+            std::os::unix::fs::symlink("/", dir.join("escape")).unwrap();
+            std::os::unix::fs::symlink("/nonexistent", dir.join("dangling")).unwrap();
+
+            assert_eq!(desc.resolve("escape/etc"), Err(ErrorCode::NotPermitted));
+            assert_eq!(desc.resolve("dangling"), Err(ErrorCode::NotPermitted));
+        }
+
+        std::fs::remove_dir_all(&dir).unwrap();
     }
 }
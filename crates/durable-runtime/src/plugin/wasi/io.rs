@@ -37,51 +37,62 @@ impl wasi::io::error::HostError for Task {
 
 impl wasi::io::error::Host for Task {}
 
-// Stub implementation of input streams.
+// Input streams.
 //
-// Workflows have no input streams. However, in order to support the CLI
-// environment we
+// Workflows have no stdin, so the only input streams that can produce any data
+// are those that read from a file in a preopened directory.
 #[async_trait]
 impl wasi::io::streams::HostInputStream for Task {
     fn read(
         &mut self,
-        _: Resource<InputStream>,
-        _: u64,
+        stream: Resource<InputStream>,
+        len: u64,
     ) -> wasmtime::Result<Result<Vec<u8>, StreamError>> {
-        // Workflows have no input streams. We model this by always indicating that
-        // the stream is closed.
-        Ok(Err(StreamError::Closed))
+        let result = match self.read_file_stream(stream.rep(), len) {
+            Some(result) => result,
+            // Stdin is always closed.
+            None => return Ok(Err(StreamError::Closed)),
+        };
+
+        let resources = self.plugins.expect_mut::<WasiResources>();
+        Ok(match result {
+            Ok(data) if data.is_empty() && len != 0 => Err(StreamError::Closed),
+            Ok(data) => Ok(data),
+            Err(e) => Err(resources.stream_error(e)),
+        })
     }
 
     fn blocking_read(
         &mut self,
-        _: Resource<InputStream>,
-        _: u64,
+        stream: Resource<InputStream>,
+        len: u64,
     ) -> wasmtime::Result<Result<Vec<u8>, StreamError>> {
-        Ok(Err(StreamError::Closed))
+        self.read(stream, len)
     }
 
     fn skip(
         &mut self,
-        _: Resource<InputStream>,
-        _: u64,
+        stream: Resource<InputStream>,
+        len: u64,
     ) -> wasmtime::Result<Result<u64, StreamError>> {
-        Ok(Err(StreamError::Closed))
+        Ok(self.read(stream, len)?.map(|data| data.len() as u64))
     }
 
     fn blocking_skip(
         &mut self,
-        _: Resource<InputStream>,
-        _: u64,
+        stream: Resource<InputStream>,
+        len: u64,
     ) -> wasmtime::Result<Result<u64, StreamError>> {
-        Ok(Err(StreamError::Closed))
+        self.skip(stream, len)
     }
 
     fn subscribe(&mut self, _: Resource<InputStream>) -> wasmtime::Result<Resource<Pollable>> {
         Ok(Resource::new_own(u32::MAX))
     }
 
-    async fn drop(&mut self, _: Resource<InputStream>) -> wasmtime::Result<()> {
+    async fn drop(&mut self, stream: Resource<InputStream>) -> wasmtime::Result<()> {
+        let resources = self.plugins.expect_mut::<WasiResources>();
+        resources.remove_file_stream(stream.rep());
         Ok(())
     }
 }
@@ -101,18 +112,23 @@ impl wasi::io::streams::HostOutputStream for Task {
         contents: Vec<u8>,
     ) -> wasmtime::Result<Result<(), StreamError>> {
         if stream.rep() != 1 {
-            return Ok(Err(StreamError::Closed));
+            // Writes to files are not recorded. See Config::preopen and
+            // ScratchDir for details.
+            let result = match self.write_file_stream(stream.rep(), &contents) {
+                Some(result) => result,
+                None => return Ok(Err(StreamError::Closed)),
+            };
+
+            let resources = self.plugins.expect_mut::<WasiResources>();
+            return Ok(result.map_err(|e| resources.stream_error(e)));
         }
 
-        let options = TransactionOptions::new("wasi:io/streams.output-stream.write");
-        self.state
-            .maybe_do_transaction_sync(options, move |state| {
-                let txn = state.transaction_mut().unwrap();
-                let utf8 = String::from_utf8_lossy(&contents);
-                txn.write_logs(&utf8);
-                Ok(())
-            })
-            .await?;
+        // Output written outside of a transaction is buffered instead of being
+        // recorded as an event. See TaskState::write_output.
+        self.state.write_output(&String::from_utf8_lossy(&contents));
+        if self.state.transaction().is_none() {
+            self.state.maybe_flush_logs().await?;
+        }
 
         Ok(Ok(()))
     }
@@ -129,9 +145,14 @@ impl wasi::io::streams::HostOutputStream for Task {
         &mut self,
         stream: Resource<OutputStream>,
     ) -> wasmtime::Result<Result<(), StreamError>> {
-        Ok(match stream.rep() {
-            1 => Ok(()),
-            _ => Err(StreamError::Closed),
+        if stream.rep() == 1 {
+            return Ok(Ok(()));
+        }
+
+        // File streams are unbuffered so there is nothing to flush.
+        Ok(match self.write_file_stream(stream.rep(), &[]) {
+            Some(_) => Ok(()),
+            None => Err(StreamError::Closed),
         })
     }
 
@@ -152,7 +173,8 @@ impl wasi::io::streams::HostOutputStream for Task {
         len: u64,
     ) -> wasmtime::Result<Result<(), StreamError>> {
         if stream.rep() != 1 {
-            return Ok(Err(StreamError::Closed));
+            let zeroes = vec![0u8; len.try_into().unwrap_or(usize::MAX)];
+            return self.write(stream, zeroes).await;
         }
 
         let written = self
@@ -196,7 +218,9 @@ impl wasi::io::streams::HostOutputStream for Task {
         Ok(Err(StreamError::Closed))
     }
 
-    async fn drop(&mut self, _: Resource<OutputStream>) -> wasmtime::Result<()> {
+    async fn drop(&mut self, stream: Resource<OutputStream>) -> wasmtime::Result<()> {
+        let resources = self.plugins.expect_mut::<WasiResources>();
+        resources.remove_file_stream(stream.rep());
         Ok(())
     }
 }
@@ -226,7 +250,7 @@ impl wasi::io::poll::HostPollable for Task {
                     _ => (),
                 }
 
-                let now = Utc::now();
+                let now = state.now();
                 Ok(pollable.timeout <= now)
             })
             .await
@@ -255,6 +279,8 @@ impl wasi::io::poll::HostPollable for Task {
             }
         };
 
+        let simulated = self.state.is_simulated();
+        let now = self.state.now();
         let txn = self.state.transaction_mut().unwrap();
         let is_external = match pollable.txn {
             Some(index) if index != txn.index() => anyhow::bail!(
@@ -264,14 +290,14 @@ impl wasi::io::poll::HostPollable for Task {
             None => true,
         };
 
-        let now = Utc::now();
         let delta = pollable
             .timeout
             .signed_duration_since(now)
             .to_std()
             .unwrap_or(Duration::ZERO);
 
-        if is_external && delta > suspend_timeout + suspend_margin {
+        // Simulated tasks wait for the virtual clock in memory instead.
+        if is_external && !simulated && delta > suspend_timeout + suspend_margin {
             // Avoid holding on to a db connection if we are suspending this task anyway
             txn.take_conn();
 
@@ -284,7 +310,7 @@ impl wasi::io::poll::HostPollable for Task {
             return Err(status.into());
         }
 
-        tokio::time::sleep(delta).await;
+        self.state.sleep_until(pollable.timeout).await;
 
         if entered {
             self.state.exit(&()).await?;
@@ -335,8 +361,9 @@ impl wasi::io::poll::Host for Task {
         };
 
         let suspend_timeout = self.state.config().suspend_timeout;
+        let simulated = self.state.is_simulated();
         let resources = self.plugins.expect::<WasiResources>();
-        let txn = self.state.transaction_mut().unwrap();
+        let txn = self.state.transaction().unwrap();
 
         // Check whether any of the pollables was created within the current
         // transaction. If this is the case then we can't suspend the task because it
@@ -360,7 +387,7 @@ impl wasi::io::poll::Host for Task {
 
         let mut ready = Vec::new();
         loop {
-            let now = Utc::now();
+            let now = self.state.now();
             let mut wakeup: Option<DateTime<Utc>> = None;
 
             for (idx, pollable) in pollables.iter().enumerate() {
@@ -395,9 +422,9 @@ impl wasi::io::poll::Host for Task {
                 .to_std()
                 .unwrap_or(Duration::ZERO);
 
-            if !has_internal && duration > suspend_timeout + SUSPEND_PREWAKE {
+            if !has_internal && !simulated && duration > suspend_timeout + SUSPEND_PREWAKE {
                 // Avoid holding on to a db connection if we are suspending this task anyway
-                let _ = txn.take_conn();
+                let _ = self.state.transaction_mut().unwrap().take_conn();
 
                 let mut conn = self.state.pool().acquire().await?;
                 let status = self.state.suspend(&mut conn, Some(wakeup)).await?;
@@ -406,7 +433,7 @@ impl wasi::io::poll::Host for Task {
             }
 
             tracing::trace!("blocking poll for {}", humantime::format_duration(duration));
-            tokio::time::sleep(duration).await;
+            self.state.sleep_until(wakeup).await;
         }
 
         if entered {
@@ -1,16 +1,24 @@
 use chrono::{DateTime, Utc};
 use slab::Slab;
 
+use crate::bindings::wasi::filesystem::types::DirectoryEntry;
+
 mod cli;
 mod clocks;
 mod filesystem;
 mod io;
 mod random;
+mod scratch;
 
 #[derive(Default)]
 pub(super) struct WasiResources {
     errors: Slab<anyhow::Error>,
     pollables: Slab<Pollable>,
+    descriptors: Slab<filesystem::DescriptorData>,
+    dir_streams: Slab<Vec<DirectoryEntry>>,
+    file_streams: Slab<filesystem::FileStream>,
+    scratch_descriptors: Slab<scratch::ScratchDescriptor>,
+    scratch_streams: Slab<scratch::ScratchStream>,
 }
 
 /// A pollable in WASI.
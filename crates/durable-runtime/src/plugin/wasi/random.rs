@@ -14,7 +14,13 @@ impl wasi::random::random::Host for Task {
 
         let options = TransactionOptions::new("wasi:random/random.get-random-bytes");
         self.state
-            .maybe_do_transaction_sync(options, move |_| {
+            .maybe_do_transaction_sync(options, move |state| {
+                if let Some(sim) = &state.shared().simulation {
+                    let mut data = vec![0u8; len as usize];
+                    sim.fill_random(&mut data);
+                    return Ok(data);
+                }
+
                 let mut data = Vec::with_capacity(len as usize);
                 getrandom::fill_uninit(data.spare_capacity_mut())
                     .context("get-random-bytes: failed to call getrandom")?;
@@ -31,10 +37,14 @@ impl wasi::random::random::Host for Task {
     async fn get_random_u64(&mut self) -> wasmtime::Result<u64> {
         let options = TransactionOptions::new("wasi:random/random.get-random-u64");
         self.state
-            .maybe_do_transaction_sync(options, move |_| {
+            .maybe_do_transaction_sync(options, move |state| {
                 let mut data = [0u8; std::mem::size_of::<u64>()];
 
-                getrandom::fill(&mut data).context("get-random-u64: failed to call getrandom")?;
+                match &state.shared().simulation {
+                    Some(sim) => sim.fill_random(&mut data),
+                    None => getrandom::fill(&mut data)
+                        .context("get-random-u64: failed to call getrandom")?,
+                }
 
                 Ok(u64::from_ne_bytes(data))
             })
@@ -54,9 +64,12 @@ impl wasi::random::insecure::Host for Task {
 
         let options = TransactionOptions::new("wasi:random/random.get-insecure-random-bytes");
         self.state
-            .maybe_do_transaction_sync(options, move |_| {
+            .maybe_do_transaction_sync(options, move |state| {
                 let mut data = vec![0u8; len as usize];
-                rand::rng().fill_bytes(&mut data);
+                match &state.shared().simulation {
+                    Some(sim) => sim.fill_random(&mut data),
+                    None => rand::rng().fill_bytes(&mut data),
+                }
                 Ok(data)
             })
             .await
@@ -65,7 +78,14 @@ impl wasi::random::insecure::Host for Task {
     async fn get_insecure_random_u64(&mut self) -> wasmtime::Result<u64> {
         let options = TransactionOptions::new("wasi:random/random.get-insecure-random-u64");
         self.state
-            .maybe_do_transaction_sync(options, move |_| Ok(rand::rng().next_u64()))
+            .maybe_do_transaction_sync(options, move |state| {
+                let mut data = [0u8; std::mem::size_of::<u64>()];
+                match &state.shared().simulation {
+                    Some(sim) => sim.fill_random(&mut data),
+                    None => rand::rng().fill_bytes(&mut data),
+                }
+                Ok(u64::from_ne_bytes(data))
+            })
             .await
     }
 }
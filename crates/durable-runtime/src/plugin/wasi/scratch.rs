@@ -0,0 +1,468 @@
+//! WASI filesystem support for the task's scratch directory.
+//!
+//! Descriptors and streams that refer to the scratch directory use resource
+//! ids starting at [`SCRATCH_BASE`] so that the filesystem and stream
+//! implementations can tell them apart from those for preopened host
+//! directories.
+
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::io;
+
+use wasmtime::component::Resource;
+
+use super::filesystem::error_code;
+use super::WasiResources;
+use crate::bindings::wasi::filesystem::types::*;
+use crate::plugin::PluginMapExt;
+use crate::scratch::{ScratchFs, ScratchStat};
+use crate::task::Task;
+
+/// The first resource id used for scratch descriptors and streams.
+pub(super) const SCRATCH_BASE: u32 = 1 << 30;
+
+pub(super) fn is_scratch<T: 'static>(res: &Resource<T>) -> bool {
+    res.rep() >= SCRATCH_BASE
+}
+
+/// An open file or directory within the scratch directory.
+///
+/// Descriptors refer to entries by path, so a descriptor for a file that has
+/// been renamed will no longer find it.
+pub(super) struct ScratchDescriptor {
+    path: String,
+    flags: DescriptorFlags,
+    is_dir: bool,
+}
+
+/// A stream that reads from or writes to a file in the scratch directory.
+pub(super) struct ScratchStream {
+    path: String,
+    position: u64,
+    append: bool,
+}
+
+impl WasiResources {
+    fn scratch_descriptor(
+        &self,
+        res: &Resource<Descriptor>,
+    ) -> Result<&ScratchDescriptor, ErrorCode> {
+        res.rep()
+            .checked_sub(SCRATCH_BASE)
+            .and_then(|index| self.scratch_descriptors.get(index as usize))
+            .ok_or(ErrorCode::BadDescriptor)
+    }
+
+    fn insert_scratch_descriptor(&mut self, desc: ScratchDescriptor) -> Resource<Descriptor> {
+        Resource::new_own(self.scratch_descriptors.insert(desc) as u32 + SCRATCH_BASE)
+    }
+
+    pub(super) fn remove_scratch_descriptor(&mut self, rep: u32) {
+        if let Some(index) = rep.checked_sub(SCRATCH_BASE) {
+            self.scratch_descriptors.try_remove(index as usize);
+        }
+    }
+
+    pub(super) fn remove_scratch_stream(&mut self, rep: u32) {
+        if let Some(index) = rep.checked_sub(SCRATCH_BASE) {
+            self.scratch_streams.try_remove(index as usize);
+        }
+    }
+}
+
+fn descriptor_type(is_dir: bool) -> DescriptorType {
+    match is_dir {
+        true => DescriptorType::Directory,
+        false => DescriptorType::RegularFile,
+    }
+}
+
+fn descriptor_stat(stat: ScratchStat) -> DescriptorStat {
+    DescriptorStat {
+        type_: descriptor_type(stat.is_dir),
+        link_count: 1,
+        size: stat.size,
+        data_access_timestamp: None,
+        data_modification_timestamp: None,
+        status_change_timestamp: None,
+    }
+}
+
+fn metadata_hash(path: &str) -> MetadataHashValue {
+    let mut lower = DefaultHasher::new();
+    ("scratch", path).hash(&mut lower);
+
+    let mut upper = DefaultHasher::new();
+    ("scratch", path, "upper").hash(&mut upper);
+
+    MetadataHashValue {
+        lower: lower.finish(),
+        upper: upper.finish(),
+    }
+}
+
+fn resolve(desc: &ScratchDescriptor, path: &str) -> Result<String, ErrorCode> {
+    if !desc.is_dir {
+        return Err(ErrorCode::NotDirectory);
+    }
+
+    ScratchFs::join(&desc.path, path).ok_or(ErrorCode::NotPermitted)
+}
+
+impl Task {
+    /// Get the scratch descriptor for `res` along with the filesystem it
+    /// refers to.
+    fn scratch_parts(
+        &mut self,
+        res: &Resource<Descriptor>,
+    ) -> Result<(&ScratchDescriptor, &mut ScratchFs), ErrorCode> {
+        let desc = self
+            .plugins
+            .expect::<WasiResources>()
+            .scratch_descriptor(res)?;
+        let scratch = self.state.scratch().ok_or(ErrorCode::BadDescriptor)?;
+
+        Ok((desc, scratch))
+    }
+
+    /// Check whether the workflow is currently allowed to modify the scratch
+    /// directory.
+    ///
+    /// Changes made within a transaction are only permitted if they are going
+    /// to be recorded, since they would otherwise be lost when the transaction
+    /// is replayed.
+    fn check_scratch_writable(&self) -> Result<(), ErrorCode> {
+        let snapshot = self
+            .state
+            .config()
+            .scratch_dir
+            .as_ref()
+            .is_some_and(|dir| dir.snapshot);
+
+        match self.state.transaction() {
+            Some(_) if !snapshot => Err(ErrorCode::ReadOnly),
+            _ => Ok(()),
+        }
+    }
+
+    /// Resolve `path` relative to the scratch descriptor `res` for an operation
+    /// that modifies the directory.
+    fn scratch_resolve_mut(
+        &mut self,
+        res: &Resource<Descriptor>,
+        path: &str,
+    ) -> Result<(String, &mut ScratchFs), ErrorCode> {
+        self.check_scratch_writable()?;
+
+        let (desc, scratch) = self.scratch_parts(res)?;
+        if !desc.flags.contains(DescriptorFlags::MUTATE_DIRECTORY) {
+            return Err(ErrorCode::NotPermitted);
+        }
+
+        Ok((resolve(desc, path)?, scratch))
+    }
+
+    pub(super) fn scratch_root(&mut self) -> Resource<Descriptor> {
+        self.plugins
+            .expect_mut::<WasiResources>()
+            .insert_scratch_descriptor(ScratchDescriptor {
+                path: String::new(),
+                flags: DescriptorFlags::READ | DescriptorFlags::MUTATE_DIRECTORY,
+                is_dir: true,
+            })
+    }
+
+    pub(super) fn scratch_open_stream<T: 'static>(
+        &mut self,
+        res: Resource<Descriptor>,
+        position: u64,
+        append: bool,
+        required: DescriptorFlags,
+    ) -> Result<Resource<T>, ErrorCode> {
+        let (desc, _) = self.scratch_parts(&res)?;
+        if !desc.flags.contains(required) {
+            return Err(ErrorCode::BadDescriptor);
+        }
+        if desc.is_dir {
+            return Err(ErrorCode::IsDirectory);
+        }
+
+        let stream = ScratchStream {
+            path: desc.path.clone(),
+            position,
+            append,
+        };
+        let resources = self.plugins.expect_mut::<WasiResources>();
+        let id = resources.scratch_streams.insert(stream) as u32;
+
+        Ok(Resource::new_own(id + SCRATCH_BASE))
+    }
+
+    pub(super) fn scratch_open_at(
+        &mut self,
+        res: Resource<Descriptor>,
+        path: String,
+        open_flags: OpenFlags,
+        flags: DescriptorFlags,
+    ) -> Result<Resource<Descriptor>, ErrorCode> {
+        // Operations on directories check this themselves.
+        let writes = flags.contains(DescriptorFlags::WRITE)
+            || open_flags.intersects(OpenFlags::CREATE | OpenFlags::TRUNCATE);
+        if writes {
+            self.check_scratch_writable()?;
+        }
+
+        let (desc, scratch) = self.scratch_parts(&res)?;
+        let path = resolve(desc, &path)?;
+        let is_dir = scratch
+            .open(
+                &path,
+                open_flags.contains(OpenFlags::CREATE),
+                open_flags.contains(OpenFlags::EXCLUSIVE),
+                open_flags.contains(OpenFlags::TRUNCATE),
+            )
+            .map_err(|e| error_code(&e))?;
+
+        if open_flags.contains(OpenFlags::DIRECTORY) && !is_dir {
+            return Err(ErrorCode::NotDirectory);
+        }
+        if is_dir && flags.contains(DescriptorFlags::WRITE) {
+            return Err(ErrorCode::IsDirectory);
+        }
+
+        let resources = self.plugins.expect_mut::<WasiResources>();
+        Ok(resources.insert_scratch_descriptor(ScratchDescriptor {
+            path,
+            flags,
+            is_dir,
+        }))
+    }
+
+    pub(super) fn scratch_read_directory(
+        &mut self,
+        res: Resource<Descriptor>,
+    ) -> Result<Resource<DirectoryEntryStream>, ErrorCode> {
+        let (desc, scratch) = self.scratch_parts(&res)?;
+        let mut entries: Vec<_> = scratch
+            .read_dir(&desc.path)
+            .map_err(|e| error_code(&e))?
+            .into_iter()
+            .map(|(name, is_dir)| DirectoryEntry {
+                type_: descriptor_type(is_dir),
+                name,
+            })
+            .collect();
+
+        // Entries are already sorted, but the stream pops them off the end.
+        entries.reverse();
+
+        let resources = self.plugins.expect_mut::<WasiResources>();
+        let id = resources.dir_streams.insert(entries);
+        Ok(Resource::new_own(id as u32))
+    }
+
+    pub(super) fn scratch_get_flags(
+        &mut self,
+        res: Resource<Descriptor>,
+    ) -> Result<DescriptorFlags, ErrorCode> {
+        self.scratch_parts(&res).map(|(desc, _)| desc.flags)
+    }
+
+    pub(super) fn scratch_stat(
+        &mut self,
+        res: Resource<Descriptor>,
+    ) -> Result<DescriptorStat, ErrorCode> {
+        let (desc, scratch) = self.scratch_parts(&res)?;
+        scratch
+            .stat(&desc.path)
+            .map(descriptor_stat)
+            .map_err(|e| error_code(&e))
+    }
+
+    pub(super) fn scratch_stat_at(
+        &mut self,
+        res: Resource<Descriptor>,
+        path: String,
+    ) -> Result<DescriptorStat, ErrorCode> {
+        let (desc, scratch) = self.scratch_parts(&res)?;
+        scratch
+            .stat(&resolve(desc, &path)?)
+            .map(descriptor_stat)
+            .map_err(|e| error_code(&e))
+    }
+
+    pub(super) fn scratch_set_size(
+        &mut self,
+        res: Resource<Descriptor>,
+        size: Filesize,
+    ) -> Result<(), ErrorCode> {
+        self.check_scratch_writable()?;
+
+        let (desc, scratch) = self.scratch_parts(&res)?;
+        if !desc.flags.contains(DescriptorFlags::WRITE) {
+            return Err(ErrorCode::BadDescriptor);
+        }
+
+        scratch
+            .set_len(&desc.path, size)
+            .map_err(|e| error_code(&e))
+    }
+
+    pub(super) fn scratch_read(
+        &mut self,
+        res: Resource<Descriptor>,
+        len: Filesize,
+        offset: Filesize,
+    ) -> Result<(Vec<u8>, bool), ErrorCode> {
+        let (desc, scratch) = self.scratch_parts(&res)?;
+        if !desc.flags.contains(DescriptorFlags::READ) {
+            return Err(ErrorCode::BadDescriptor);
+        }
+
+        let data = scratch
+            .read(&desc.path, offset, len)
+            .map_err(|e| error_code(&e))?;
+        let eof = (data.len() as u64) < len;
+
+        Ok((data, eof))
+    }
+
+    pub(super) fn scratch_write(
+        &mut self,
+        res: Resource<Descriptor>,
+        data: Vec<u8>,
+        offset: Filesize,
+    ) -> Result<Filesize, ErrorCode> {
+        self.check_scratch_writable()?;
+
+        let (desc, scratch) = self.scratch_parts(&res)?;
+        if !desc.flags.contains(DescriptorFlags::WRITE) {
+            return Err(ErrorCode::BadDescriptor);
+        }
+
+        scratch
+            .write(&desc.path, Some(offset), &data)
+            .map_err(|e| error_code(&e))?;
+
+        Ok(data.len() as Filesize)
+    }
+
+    pub(super) fn scratch_create_directory_at(
+        &mut self,
+        res: Resource<Descriptor>,
+        path: String,
+    ) -> Result<(), ErrorCode> {
+        let (path, scratch) = self.scratch_resolve_mut(&res, &path)?;
+        scratch.create_dir(&path).map_err(|e| error_code(&e))
+    }
+
+    pub(super) fn scratch_remove_directory_at(
+        &mut self,
+        res: Resource<Descriptor>,
+        path: String,
+    ) -> Result<(), ErrorCode> {
+        let (path, scratch) = self.scratch_resolve_mut(&res, &path)?;
+        scratch.remove_dir(&path).map_err(|e| error_code(&e))
+    }
+
+    pub(super) fn scratch_unlink_file_at(
+        &mut self,
+        res: Resource<Descriptor>,
+        path: String,
+    ) -> Result<(), ErrorCode> {
+        let (path, scratch) = self.scratch_resolve_mut(&res, &path)?;
+        scratch.remove_file(&path).map_err(|e| error_code(&e))
+    }
+
+    pub(super) fn scratch_rename_at(
+        &mut self,
+        old: Resource<Descriptor>,
+        old_path: String,
+        new: Resource<Descriptor>,
+        new_path: String,
+    ) -> Result<(), ErrorCode> {
+        if !is_scratch(&old) || !is_scratch(&new) {
+            return Err(ErrorCode::CrossDevice);
+        }
+
+        let (new_path, _) = self.scratch_resolve_mut(&new, &new_path)?;
+        let (old_path, scratch) = self.scratch_resolve_mut(&old, &old_path)?;
+        scratch
+            .rename(&old_path, &new_path)
+            .map_err(|e| error_code(&e))
+    }
+
+    pub(super) fn scratch_is_same_object(
+        &mut self,
+        a: Resource<Descriptor>,
+        b: Resource<Descriptor>,
+    ) -> bool {
+        let resources = self.plugins.expect::<WasiResources>();
+        match (
+            resources.scratch_descriptor(&a),
+            resources.scratch_descriptor(&b),
+        ) {
+            (Ok(a), Ok(b)) => a.path == b.path,
+            _ => false,
+        }
+    }
+
+    pub(super) fn scratch_metadata_hash(
+        &mut self,
+        res: Resource<Descriptor>,
+    ) -> Result<MetadataHashValue, ErrorCode> {
+        let (desc, scratch) = self.scratch_parts(&res)?;
+        scratch.stat(&desc.path).map_err(|e| error_code(&e))?;
+
+        Ok(metadata_hash(&desc.path))
+    }
+
+    pub(super) fn scratch_metadata_hash_at(
+        &mut self,
+        res: Resource<Descriptor>,
+        path: String,
+    ) -> Result<MetadataHashValue, ErrorCode> {
+        let (desc, scratch) = self.scratch_parts(&res)?;
+        let path = resolve(desc, &path)?;
+        scratch.stat(&path).map_err(|e| error_code(&e))?;
+
+        Ok(metadata_hash(&path))
+    }
+
+    pub(super) fn read_scratch_stream(
+        &mut self,
+        rep: u32,
+        len: u64,
+    ) -> Option<io::Result<Vec<u8>>> {
+        let index = rep.checked_sub(SCRATCH_BASE)?;
+        let resources = self.plugins.expect_mut::<WasiResources>();
+        let stream = resources.scratch_streams.get_mut(index as usize)?;
+        let scratch = self.state.scratch()?;
+
+        Some(
+            scratch
+                .read(&stream.path, stream.position, len)
+                .inspect(|data| stream.position += data.len() as u64),
+        )
+    }
+
+    pub(super) fn write_scratch_stream(&mut self, rep: u32, data: &[u8]) -> Option<io::Result<()>> {
+        let index = rep.checked_sub(SCRATCH_BASE)?;
+        if self.check_scratch_writable().is_err() {
+            return Some(Err(io::ErrorKind::ReadOnlyFilesystem.into()));
+        }
+
+        let resources = self.plugins.expect_mut::<WasiResources>();
+        let stream = resources.scratch_streams.get_mut(index as usize)?;
+        let scratch = self.state.scratch()?;
+        let offset = match stream.append {
+            true => None,
+            false => Some(stream.position),
+        };
+
+        Some(
+            scratch
+                .write(&stream.path, offset, data)
+                .map(|end| stream.position = end),
+        )
+    }
+}
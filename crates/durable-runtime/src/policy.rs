@@ -0,0 +1,1287 @@
+//! Policies that restrict which SQL statements a workflow is allowed to run.
+//!
+//! By default, a workflow can run any statement that the worker's own database
+//! user is permitted to run. Configuring a [`SqlPolicy`] via
+//! [`WorkerBuilder::sql_policy`], [`WorkerBuilder::program_sql_policy`], or
+//! [`WorkerBuilder::queue_sql_policy`] allows the worker to inspect each
+//! statement before it is sent to the database and reject it.
+//!
+//! Rejected statements are never executed. The workflow sees them fail with a
+//! database error that has the `insufficient_privilege` (`42501`) error code.
+//!
+//! # This is not a sandbox
+//! Policies only see the SQL text of a statement and what the database reports
+//! when planning it. Anything that the statement does indirectly is invisible
+//! to them: functions called by triggers, column defaults, operators, and
+//! views all run with the privileges of the worker's database user. The
+//! built-in [`AllowlistPolicy`] rejects calls to functions that are not on its
+//! allowlist, but it cannot see into the functions that it does allow.
+//!
+//! Policies are meant to keep well-behaved workflows from accidentally
+//! touching tables that they shouldn't. If workflows need to be isolated from
+//! data that they must never be able to read, then run the worker as a
+//! database user that has not been granted access to it.
+//!
+//! [`WorkerBuilder::sql_policy`]: crate::WorkerBuilder::sql_policy
+//! [`WorkerBuilder::program_sql_policy`]: crate::WorkerBuilder::program_sql_policy
+//! [`WorkerBuilder::queue_sql_policy`]: crate::WorkerBuilder::queue_sql_policy
+
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::sync::Arc;
+
+use anyhow::Context;
+use async_trait::async_trait;
+use durable_migrate::SchemaRename;
+use sqlx::error::ErrorKind;
+use sqlx::types::Json;
+use sqlx::PgConnection;
+
+use crate::plugin::durable::sql::value::ValueResource;
+
+/// A policy that is checked before a workflow runs a SQL statement.
+#[async_trait]
+pub trait SqlPolicy: Send + Sync {
+    /// Check whether the workflow is allowed to run `statement`.
+    ///
+    /// `conn` is the connection that the statement will be run on, within the
+    /// workflow's database transaction. Policies may run their own queries on
+    /// it but must ensure that they leave the transaction usable afterwards,
+    /// e.g. by running queries that could fail within a savepoint.
+    async fn check(
+        &self,
+        conn: &mut PgConnection,
+        statement: &SqlStatement<'_>,
+    ) -> Result<(), PolicyViolation>;
+}
+
+/// The kind of SQL statement being checked by a [`SqlPolicy`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum StatementKind {
+    /// A regular query, run via `durable:core/sql.query`.
+    Query,
+
+    /// A `COPY ... FROM STDIN` statement.
+    CopyIn,
+
+    /// A statement that is being described via `durable:core/sql.describe`.
+    ///
+    /// The statement is not run and there are no values for its parameters.
+    Describe,
+}
+
+/// A SQL statement that a workflow is attempting to run.
+pub struct SqlStatement<'a> {
+    kind: StatementKind,
+    sql: &'a str,
+    params: &'a [ValueResource],
+    program: Option<&'a str>,
+    task_id: i64,
+}
+
+impl<'a> SqlStatement<'a> {
+    /// The kind of statement being run.
+    pub fn kind(&self) -> StatementKind {
+        self.kind
+    }
+
+    /// The SQL text of the statement.
+    pub fn sql(&self) -> &'a str {
+        self.sql
+    }
+
+    /// The name of the program that is running the statement, if it has one.
+    pub fn program(&self) -> Option<&'a str> {
+        self.program
+    }
+
+    /// The ID of the task that is running the statement.
+    pub fn task_id(&self) -> i64 {
+        self.task_id
+    }
+
+    /// Determine the tables that this statement will access.
+    ///
+    /// For queries, this asks the database to plan the statement (without
+    /// running it) and returns every relation that appears within the plan.
+    /// Views are expanded by the planner, so the tables underneath them are
+    /// returned instead of the views themselves. Tables accessed indirectly
+    /// by functions called from the statement are not included.
+    ///
+    /// This returns an error for anything other than a plain `SELECT`,
+    /// `INSERT`, `UPDATE`, `DELETE`, or `MERGE` statement. That includes all
+    /// DDL and utility statements, as well as `SELECT ... INTO`, since the
+    /// table that it creates would not appear within the plan.
+    pub async fn relations(&self, conn: &mut PgConnection) -> anyhow::Result<Vec<Relation>> {
+        match self.kind {
+            StatementKind::Query => {
+                check_dml(self.sql)?;
+                self.query_relations(conn).await
+            }
+            StatementKind::Describe => {
+                check_dml(self.sql)?;
+                self.describe_relations(conn).await
+            }
+            StatementKind::CopyIn => self.copy_relations(conn).await,
+        }
+    }
+
+    /// Determine the functions that this statement calls directly.
+    ///
+    /// This looks for function calls within the SQL text of the statement, so
+    /// functions called indirectly (e.g. by views, triggers, column defaults,
+    /// or operators) are not included. Names are returned as they were written
+    /// within the statement, with unquoted names folded to lowercase.
+    ///
+    /// Column alias lists that follow a table or function without `AS` (e.g.
+    /// `generate_series(1, 3) g(n)`) are treated as function calls unless they
+    /// follow a plain table name.
+    pub fn functions(&self) -> anyhow::Result<Vec<Function>> {
+        function_calls(self.sql)
+    }
+
+    async fn query_relations(&self, conn: &mut PgConnection) -> anyhow::Result<Vec<Relation>> {
+        sqlx::query("SAVEPOINT durable_sql_policy")
+            .execute(&mut *conn)
+            .await?;
+
+        let explain = format!("EXPLAIN (VERBOSE, FORMAT JSON) {}", self.sql);
+        let mut query = sqlx::query_scalar(&explain).persistent(false);
+        for param in self.params {
+            query = query.bind(param.clone());
+        }
+
+        let plan: Result<Json<serde_json::Value>, _> = query.fetch_one(&mut *conn).await;
+
+        sqlx::query("ROLLBACK TO SAVEPOINT durable_sql_policy")
+            .execute(&mut *conn)
+            .await?;
+        sqlx::query("RELEASE SAVEPOINT durable_sql_policy")
+            .execute(&mut *conn)
+            .await?;
+
+        let plan = plan.context("failed to plan the statement")?;
+        Ok(plan_relations(plan))
+    }
+
+    /// Plan a statement that has no parameter values.
+    ///
+    /// Binding placeholder values would let the planner fold them into the
+    /// plan, which can remove scans of tables entirely (e.g. `WHERE id =
+    /// NULL`). Instead, the statement is prepared and a generic plan is
+    /// requested for it, which does not depend on the parameter values.
+    async fn describe_relations(&self, conn: &mut PgConnection) -> anyhow::Result<Vec<Relation>> {
+        sqlx::query("SAVEPOINT durable_sql_policy")
+            .execute(&mut *conn)
+            .await?;
+
+        let mut prepared = false;
+        let plan = self.generic_plan(conn, &mut prepared).await;
+
+        sqlx::query("ROLLBACK TO SAVEPOINT durable_sql_policy")
+            .execute(&mut *conn)
+            .await?;
+        sqlx::query("RELEASE SAVEPOINT durable_sql_policy")
+            .execute(&mut *conn)
+            .await?;
+
+        // Prepared statements are not transactional so rolling back the savepoint
+        // does not remove it.
+        if prepared {
+            sqlx::query("DEALLOCATE durable_sql_policy")
+                .persistent(false)
+                .execute(&mut *conn)
+                .await?;
+        }
+
+        let plan = plan.context("failed to plan the statement")?;
+        Ok(plan_relations(plan))
+    }
+
+    async fn generic_plan(
+        &self,
+        conn: &mut PgConnection,
+        prepared: &mut bool,
+    ) -> anyhow::Result<Json<serde_json::Value>> {
+        sqlx::query("SET LOCAL plan_cache_mode = force_generic_plan")
+            .execute(&mut *conn)
+            .await?;
+
+        let prepare = format!("PREPARE durable_sql_policy AS {}", self.sql);
+        sqlx::query(&prepare)
+            .persistent(false)
+            .execute(&mut *conn)
+            .await?;
+        *prepared = true;
+
+        let count: i32 = sqlx::query_scalar(
+            "SELECT cardinality(parameter_types) FROM pg_prepared_statements WHERE name = \
+             'durable_sql_policy'",
+        )
+        .fetch_one(&mut *conn)
+        .await?;
+
+        let explain = match count {
+            0 => "EXPLAIN (VERBOSE, FORMAT JSON) EXECUTE durable_sql_policy".to_owned(),
+            _ => format!(
+                "EXPLAIN (VERBOSE, FORMAT JSON) EXECUTE durable_sql_policy({})",
+                vec!["NULL"; count as usize].join(", ")
+            ),
+        };
+
+        let plan = sqlx::query_scalar(&explain)
+            .persistent(false)
+            .fetch_one(&mut *conn)
+            .await?;
+
+        Ok(plan)
+    }
+
+    async fn copy_relations(&self, conn: &mut PgConnection) -> anyhow::Result<Vec<Relation>> {
+        let Some(table) = copy_target(self.sql) else {
+            anyhow::bail!("unable to determine the target table of the COPY statement");
+        };
+
+        let relation = sqlx::query_as!(
+            Relation,
+            r#"
+            SELECT
+                n.nspname as "schema!",
+                c.relname as "name!"
+              FROM pg_catalog.pg_class c
+              JOIN pg_catalog.pg_namespace n ON n.oid = c.relnamespace
+             WHERE c.oid = to_regclass($1)
+            "#,
+            table
+        )
+        .fetch_optional(&mut *conn)
+        .await?;
+
+        match relation {
+            Some(relation) => Ok(vec![relation]),
+            None => anyhow::bail!("table {table} does not exist"),
+        }
+    }
+}
+
+/// A table, view, or other relation within the database.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Relation {
+    /// The schema that the relation is in.
+    pub schema: String,
+
+    /// The name of the relation.
+    pub name: String,
+}
+
+impl fmt::Display for Relation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}", self.schema, self.name)
+    }
+}
+
+/// A function called by a SQL statement.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Function {
+    /// The schema that the function was qualified with, if any.
+    pub schema: Option<String>,
+
+    /// The name of the function.
+    pub name: String,
+}
+
+impl fmt::Display for Function {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.schema {
+            Some(schema) => write!(f, "{schema}.{}", self.name),
+            None => f.write_str(&self.name),
+        }
+    }
+}
+
+/// An error indicating that a [`SqlPolicy`] rejected a statement.
+#[derive(Clone, Debug)]
+pub struct PolicyViolation {
+    message: String,
+}
+
+impl PolicyViolation {
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+        }
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl fmt::Display for PolicyViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for PolicyViolation {}
+
+impl sqlx::error::DatabaseError for PolicyViolation {
+    fn message(&self) -> &str {
+        &self.message
+    }
+
+    fn code(&self) -> Option<Cow<'_, str>> {
+        // insufficient_privilege
+        Some(Cow::Borrowed("42501"))
+    }
+
+    fn as_error(&self) -> &(dyn std::error::Error + Send + Sync + 'static) {
+        self
+    }
+
+    fn as_error_mut(&mut self) -> &mut (dyn std::error::Error + Send + Sync + 'static) {
+        self
+    }
+
+    fn into_error(self: Box<Self>) -> Box<dyn std::error::Error + Send + Sync + 'static> {
+        self
+    }
+
+    fn kind(&self) -> ErrorKind {
+        ErrorKind::Other
+    }
+}
+
+/// A [`SqlPolicy`] that only allows statements which access an allowlisted
+/// set of schemas and tables.
+///
+/// Statements are checked using [`SqlStatement::relations`], so any
+/// statement that cannot be planned by the database (e.g. `CREATE TABLE`) is
+/// rejected.
+///
+/// Functions can read any table that the worker's database user has access
+/// to (e.g. `query_to_xml`), so statements may only call functions from a
+/// small set of built-in functions that don't access tables, along with any
+/// that are allowed via [`function`](AllowlistPolicy::function). Functions
+/// are checked using [`SqlStatement::functions`].
+///
+/// This is not a sandbox, see the [module docs](self) for details.
+///
+/// ```
+/// use durable_runtime::policy::AllowlistPolicy;
+///
+/// let policy = AllowlistPolicy::new()
+///     .schema("billing")
+///     .table("public", "users")
+///     .function("billing.invoice_total");
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct AllowlistPolicy {
+    schemas: HashSet<String>,
+    tables: HashSet<(String, String)>,
+    functions: HashSet<String>,
+}
+
+impl AllowlistPolicy {
+    /// Create a new policy that does not allow access to any tables.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Allow calls to the function `name`.
+    ///
+    /// Calls which qualify the function with a schema other than `pg_catalog`
+    /// are only allowed if `name` includes the same schema (e.g.
+    /// `"billing.invoice_total"`).
+    pub fn function(mut self, name: impl Into<String>) -> Self {
+        self.functions.insert(name.into());
+        self
+    }
+
+    /// Allow access to all tables within `schema`.
+    pub fn schema(mut self, schema: impl Into<String>) -> Self {
+        self.schemas.insert(schema.into());
+        self
+    }
+
+    /// Allow access to the table `schema.table`.
+    pub fn table(mut self, schema: impl Into<String>, table: impl Into<String>) -> Self {
+        self.tables.insert((schema.into(), table.into()));
+        self
+    }
+
+    fn allows(&self, relation: &Relation) -> bool {
+        self.schemas.contains(&relation.schema)
+            || self
+                .tables
+                .contains(&(relation.schema.clone(), relation.name.clone()))
+    }
+
+    fn allows_function(&self, function: &Function) -> bool {
+        match function.schema.as_deref() {
+            None | Some("pg_catalog") => {
+                SAFE_FUNCTIONS.contains(&function.name.as_str())
+                    || self.functions.contains(&function.name)
+            }
+            Some(_) => self.functions.contains(&function.to_string()),
+        }
+    }
+}
+
+#[async_trait]
+impl SqlPolicy for AllowlistPolicy {
+    async fn check(
+        &self,
+        conn: &mut PgConnection,
+        statement: &SqlStatement<'_>,
+    ) -> Result<(), PolicyViolation> {
+        let functions = statement.functions().map_err(|e| {
+            PolicyViolation::new(format!(
+                "statement could not be checked against the SQL access policy: {e:#}"
+            ))
+        })?;
+
+        for function in functions {
+            if !self.allows_function(&function) {
+                return Err(PolicyViolation::new(format!(
+                    "calling {function} is not permitted by the SQL access policy"
+                )));
+            }
+        }
+
+        let relations = statement.relations(conn).await.map_err(|e| {
+            PolicyViolation::new(format!(
+                "statement could not be checked against the SQL access policy: {e:#}"
+            ))
+        })?;
+
+        for relation in relations {
+            if !self.allows(&relation) {
+                return Err(PolicyViolation::new(format!(
+                    "access to {relation} is not permitted by the SQL access policy"
+                )));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// The SQL policies configured on a worker.
+#[derive(Clone, Default)]
+pub(crate) struct SqlPolicies {
+    pub default: Option<Arc<dyn SqlPolicy>>,
+    pub programs: HashMap<String, Arc<dyn SqlPolicy>>,
+    pub queues: HashMap<String, Arc<dyn SqlPolicy>>,
+}
+
+impl SqlPolicies {
+    /// Find the policies that apply to a task running the program with id
+    /// `wasm` on behalf of `tenant`.
+    ///
+    /// The policies for the program and the task's queue both apply. The
+    /// default policy only applies if neither of them has one.
+    pub async fn resolve(
+        &self,
+        schema: &SchemaRename,
+        pool: &sqlx::PgPool,
+        wasm: i64,
+        tenant: Option<&str>,
+    ) -> anyhow::Result<Option<TaskPolicy>> {
+        if self.default.is_none() && self.programs.is_empty() && self.queues.is_empty() {
+            return Ok(None);
+        }
+
+        let program = sqlx::query_scalar!("SELECT name FROM durable.wasm WHERE id = $1", wasm)
+            .fetch_one(schema.on(pool))
+            .await?;
+
+        // Tasks without a tenant are in the '' queue.
+        let queue = tenant.unwrap_or("");
+        let mut policies: Vec<_> = program
+            .as_deref()
+            .and_then(|name| self.programs.get(name))
+            .into_iter()
+            .chain(self.queues.get(queue))
+            .cloned()
+            .collect();
+
+        if policies.is_empty() {
+            policies.extend(self.default.clone());
+        }
+
+        if policies.is_empty() {
+            return Ok(None);
+        }
+
+        Ok(Some(TaskPolicy { program, policies }))
+    }
+}
+
+/// The SQL policies that apply to a single task.
+#[derive(Clone)]
+pub(crate) struct TaskPolicy {
+    program: Option<String>,
+    policies: Vec<Arc<dyn SqlPolicy>>,
+}
+
+impl TaskPolicy {
+    pub async fn check(
+        &self,
+        conn: &mut PgConnection,
+        task_id: i64,
+        kind: StatementKind,
+        sql: &str,
+        params: &[ValueResource],
+    ) -> Result<(), PolicyViolation> {
+        let statement = SqlStatement {
+            kind,
+            sql,
+            params,
+            program: self.program.as_deref(),
+            task_id,
+        };
+
+        for policy in &self.policies {
+            policy.check(conn, &statement).await?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Determine the tables accessed by a statement so that they can be recorded
+/// in the audit log.
+///
+/// Statements that cannot be planned are recorded without any tables.
+pub(crate) async fn audited_tables(
+    conn: &mut PgConnection,
+    task_id: i64,
+    kind: StatementKind,
+    sql: &str,
+    params: &[ValueResource],
+) -> Vec<String> {
+    let statement = SqlStatement {
+        kind,
+        sql,
+        params,
+        program: None,
+        task_id,
+    };
+
+    match statement.relations(conn).await {
+        Ok(relations) => relations.iter().map(Relation::to_string).collect(),
+        Err(_) => Vec::new(),
+    }
+}
+
+fn plan_relations(plan: Json<serde_json::Value>) -> Vec<Relation> {
+    let mut relations = Vec::new();
+    for root in plan.0.as_array().into_iter().flatten() {
+        if let Some(node) = root.get("Plan") {
+            collect_relations(node, &mut relations);
+        }
+    }
+
+    relations
+}
+
+fn collect_relations(node: &serde_json::Value, relations: &mut Vec<Relation>) {
+    let field = |name: &str| node.get(name).and_then(|value| value.as_str());
+
+    if let (Some(schema), Some(name)) = (field("Schema"), field("Relation Name")) {
+        let relation = Relation {
+            schema: schema.to_owned(),
+            name: name.to_owned(),
+        };
+
+        if !relations.contains(&relation) {
+            relations.push(relation);
+        }
+    }
+
+    if let Some(plans) = node.get("Plans").and_then(|plans| plans.as_array()) {
+        for plan in plans {
+            collect_relations(plan, relations);
+        }
+    }
+}
+
+/// The keywords that a statement may start with in order to be planned by
+/// [`SqlStatement::relations`].
+const DML_KEYWORDS: &[&str] = &[
+    "select", "insert", "update", "delete", "merge", "with", "values", "table",
+];
+
+/// Reject anything other than a plain DML statement before it is planned.
+///
+/// `EXPLAIN` will happily plan some statements which do more than read and
+/// modify existing tables: `CREATE TABLE ... AS` and `SELECT ... INTO` both
+/// create a new table that never shows up within the plan. The first are
+/// caught by requiring a DML keyword at the start of the statement. The
+/// second are caught by only allowing `INTO` directly after an `INSERT` or
+/// `MERGE` that starts a statement.
+fn check_dml(sql: &str) -> anyhow::Result<()> {
+    let tokens = tokenize(sql)?;
+
+    let Some(first) = tokens.iter().find(|token| **token != Token::Open) else {
+        anyhow::bail!("the statement is empty");
+    };
+
+    let is_dml = match first {
+        Token::Word(word) => DML_KEYWORDS.iter().any(|kw| word.eq_ignore_ascii_case(kw)),
+        _ => false,
+    };
+    if !is_dml {
+        anyhow::bail!("only SELECT, INSERT, UPDATE, DELETE, and MERGE statements are permitted");
+    }
+
+    // For each open parenthesis, whether it follows AS or MATERIALIZED and so
+    // contains the body of a common table expression.
+    let mut parens = Vec::new();
+    // Whether the previous token was the close of a common table expression.
+    let mut after_cte = false;
+    // Whether the previous token was a keyword that can be followed by INTO.
+    let mut insert = false;
+    let mut prev: Option<Token> = None;
+
+    for token in tokens {
+        let stmt_start = matches!(prev, None | Some(Token::Open)) || after_cte;
+
+        match token {
+            Token::Word(word) if word.eq_ignore_ascii_case("into") && !insert => {
+                anyhow::bail!("SELECT ... INTO statements are not permitted");
+            }
+            Token::Open => {
+                let is_cte = matches!(
+                    prev,
+                    Some(Token::Word(word))
+                        if word.eq_ignore_ascii_case("as")
+                            || word.eq_ignore_ascii_case("materialized")
+                );
+                parens.push(is_cte);
+            }
+            _ => (),
+        }
+
+        insert = match token {
+            Token::Word(word) => {
+                stmt_start
+                    && (word.eq_ignore_ascii_case("insert") || word.eq_ignore_ascii_case("merge"))
+            }
+            _ => false,
+        };
+        after_cte = match token {
+            Token::Close => parens.pop().unwrap_or(false),
+            _ => false,
+        };
+        prev = Some(token);
+    }
+
+    Ok(())
+}
+
+/// Built-in functions that [`AllowlistPolicy`] allows by default.
+///
+/// None of these access any tables.
+#[rustfmt::skip]
+const SAFE_FUNCTIONS: &[&str] = &[
+    // Aggregates and window functions
+    "array_agg", "avg", "bit_and", "bit_or", "bool_and", "bool_or", "count", "cume_dist",
+    "dense_rank", "every", "first_value", "grouping", "json_agg", "json_object_agg", "jsonb_agg",
+    "jsonb_object_agg", "lag", "last_value", "lead", "max", "min", "mode", "nth_value", "ntile",
+    "percent_rank", "percentile_cont", "percentile_disc", "rank", "row_number", "stddev",
+    "stddev_pop", "stddev_samp", "string_agg", "sum", "var_pop", "var_samp", "variance",
+    // Conditional expressions
+    "cast", "coalesce", "greatest", "least", "nullif",
+    // Math
+    "abs", "cbrt", "ceil", "ceiling", "div", "exp", "floor", "ln", "log", "mod", "power", "random",
+    "round", "sign", "sqrt", "trunc", "width_bucket",
+    // Strings
+    "btrim", "char_length", "character_length", "concat", "concat_ws", "decode", "encode", "format",
+    "initcap", "left", "length", "lower", "lpad", "ltrim", "md5", "octet_length", "overlay",
+    "position", "regexp_match", "regexp_matches", "regexp_replace", "regexp_split_to_array",
+    "repeat", "replace", "reverse", "right", "rpad", "rtrim", "split_part", "starts_with", "strpos",
+    "substr", "substring", "to_hex", "translate", "trim", "upper",
+    // Dates and times
+    "age", "clock_timestamp", "date_bin", "date_part", "date_trunc", "extract", "isfinite",
+    "justify_days", "justify_hours", "justify_interval", "make_date", "make_interval", "make_time",
+    "make_timestamp", "make_timestamptz", "now", "statement_timestamp", "to_char", "to_date",
+    "to_number", "to_timestamp", "transaction_timestamp",
+    // JSON
+    "json_array_elements", "json_array_elements_text", "json_array_length", "json_build_array",
+    "json_build_object", "json_each", "json_each_text", "json_extract_path",
+    "json_extract_path_text", "json_object_keys", "json_typeof", "jsonb_array_elements",
+    "jsonb_array_elements_text", "jsonb_array_length", "jsonb_build_array", "jsonb_build_object",
+    "jsonb_each", "jsonb_each_text", "jsonb_extract_path", "jsonb_extract_path_text",
+    "jsonb_insert", "jsonb_object_keys", "jsonb_path_exists", "jsonb_path_query",
+    "jsonb_path_query_array", "jsonb_path_query_first", "jsonb_pretty", "jsonb_set",
+    "jsonb_strip_nulls", "jsonb_typeof", "to_json", "to_jsonb",
+    // Arrays and sets
+    "array_append", "array_cat", "array_length", "array_lower", "array_position", "array_positions",
+    "array_prepend", "array_remove", "array_replace", "array_to_string", "array_upper",
+    "cardinality", "generate_series", "string_to_array", "unnest",
+    // Other
+    "gen_random_uuid",
+];
+
+/// Keywords that can be directly followed by an opening parenthesis without
+/// it being a function call.
+#[rustfmt::skip]
+const SYNTAX_KEYWORDS: &[&str] = &[
+    "all", "and", "any", "array", "as", "between", "by", "case", "conflict", "cube", "distinct",
+    "else", "escape", "except", "exists", "fetch", "filter", "first", "from", "group", "having",
+    "ilike", "in", "insert", "intersect", "into", "is", "join", "lateral", "like", "limit",
+    "materialized", "next", "not", "offset", "on", "only", "or", "over", "overlaps", "recursive",
+    "repeatable", "returning", "rollup", "row", "select", "set", "sets", "similar", "some", "table",
+    "then", "to", "union", "using", "values", "when", "where", "window", "with", "zone",
+];
+
+/// Reserved keywords, which can never be used as the name of a table.
+#[rustfmt::skip]
+const RESERVED_KEYWORDS: &[&str] = &[
+    "all", "analyse", "analyze", "and", "any", "array", "as", "asc", "asymmetric", "authorization",
+    "binary", "both", "case", "cast", "check", "collate", "collation", "column", "concurrently",
+    "constraint", "create", "cross", "current_catalog", "current_date", "current_role",
+    "current_schema", "current_time", "current_timestamp", "current_user", "default", "deferrable",
+    "desc", "distinct", "do", "else", "end", "except", "false", "fetch", "for", "foreign", "freeze",
+    "from", "full", "grant", "group", "having", "ilike", "in", "initially", "inner", "intersect",
+    "into", "is", "isnull", "join", "lateral", "leading", "left", "like", "limit", "localtime",
+    "localtimestamp", "natural", "not", "notnull", "null", "offset", "on", "only", "or", "order",
+    "outer", "overlaps", "placing", "primary", "references", "returning", "right", "select",
+    "session_user", "similar", "some", "symmetric", "system_user", "table", "tablesample", "then",
+    "to", "trailing", "true", "union", "unique", "user", "using", "variadic", "verbose", "when",
+    "where", "window", "with",
+];
+
+/// The keywords that start the main statement after a `WITH` list.
+const STATEMENT_KEYWORDS: &[&str] = &[
+    "select", "insert", "update", "delete", "merge", "values", "table",
+];
+
+/// Find the functions that are called within a SQL statement.
+///
+/// A function call is a (possibly qualified) name that is followed by an
+/// opening parenthesis. There are a few places where a name followed by a
+/// parenthesis is not a function call, all of which are skipped:
+/// - keywords such as `IN (...)` or `EXISTS (...)`,
+/// - the target of an `INSERT INTO`, `MERGE INTO`, or `COPY` statement,
+/// - types with modifiers after `::` or `AS` (e.g. `CAST(x AS numeric(10, 2))`),
+/// - column alias lists after `AS` or after a table name,
+/// - the names of common table expressions with column lists, and
+/// - the sampling method of a `TABLESAMPLE` clause.
+///
+/// Anything else that is ambiguous is treated as a function call.
+fn function_calls(sql: &str) -> anyhow::Result<Vec<Function>> {
+    let tokens = tokenize(sql)?;
+    let is = |word: &str, list: &[&str]| list.iter().any(|kw| word.eq_ignore_ascii_case(kw));
+
+    let mut calls = Vec::new();
+    // For each level of parentheses, whether it contains a WITH list that has
+    // not yet been followed by the main statement.
+    let mut with_lists = vec![false];
+
+    for (i, &token) in tokens.iter().enumerate() {
+        let prev = i.checked_sub(1).map(|j| tokens[j]);
+
+        match token {
+            Token::Word(word) if word.eq_ignore_ascii_case("with") => {
+                // WITH only starts a WITH list at the start of a statement, as
+                // opposed to WITH ORDINALITY, WITH TIES, etc.
+                if matches!(prev, None | Some(Token::Open)) {
+                    *with_lists.last_mut().unwrap() = true;
+                }
+            }
+            Token::Word(word) if is(word, STATEMENT_KEYWORDS) => {
+                *with_lists.last_mut().unwrap() = false;
+            }
+            Token::Close if with_lists.len() > 1 => {
+                with_lists.pop();
+            }
+            Token::Open => {
+                let in_with_list = *with_lists.last().unwrap();
+                with_lists.push(false);
+
+                // Collect the parts of the name before the parenthesis.
+                let mut start = i;
+                let mut parts = Vec::new();
+                loop {
+                    let part = match start.checked_sub(1).map(|j| tokens[j]) {
+                        Some(Token::Word(word)) => word.to_ascii_lowercase(),
+                        Some(Token::Quoted(name)) => name.replace("\"\"", "\""),
+                        _ => break,
+                    };
+
+                    parts.push(part);
+                    start -= 1;
+
+                    if start < 2 || tokens[start - 1] != Token::Dot {
+                        break;
+                    }
+                    start -= 1;
+                }
+
+                let Some(name) = parts.first() else {
+                    continue;
+                };
+
+                if parts.len() == 1
+                    && matches!(tokens[start], Token::Word(word) if is(word, SYNTAX_KEYWORDS))
+                {
+                    continue;
+                }
+
+                let before = start.checked_sub(1).map(|j| tokens[j]);
+                let not_call = match before {
+                    // Types with modifiers and alias lists.
+                    Some(Token::Cast) => true,
+                    Some(Token::Word(word)) if is(word, &["as", "copy", "into", "tablesample"]) => {
+                        true
+                    }
+                    // A column alias list after a table name. A function call can't
+                    // directly follow an identifier.
+                    Some(Token::Word(word)) => {
+                        !is(word, RESERVED_KEYWORDS) && !is(word, SYNTAX_KEYWORDS)
+                    }
+                    Some(Token::Quoted(_)) => true,
+                    _ => false,
+                };
+
+                // The column list of a common table expression.
+                let cte = in_with_list && is_cte_columns(&tokens, i);
+
+                if not_call || cte {
+                    continue;
+                }
+
+                let name = name.clone();
+                parts.reverse();
+                parts.pop();
+                let schema = (!parts.is_empty()).then(|| parts.join("."));
+                let function = Function { schema, name };
+
+                if !calls.contains(&function) {
+                    calls.push(function);
+                }
+            }
+            _ => (),
+        }
+    }
+
+    Ok(calls)
+}
+
+/// Whether the parenthesis at `open` is followed by `AS [NOT] [MATERIALIZED] (`
+/// once it is closed, as the column list of a common table expression is.
+fn is_cte_columns(tokens: &[Token<'_>], open: usize) -> bool {
+    let mut depth = 0;
+    let mut rest = tokens[open..].iter().skip_while(|token| {
+        match token {
+            Token::Open => depth += 1,
+            Token::Close => depth -= 1,
+            _ => (),
+        }
+
+        depth > 0
+    });
+
+    // Skip the closing parenthesis.
+    rest.next();
+
+    if !matches!(rest.next(), Some(Token::Word(word)) if word.eq_ignore_ascii_case("as")) {
+        return false;
+    }
+
+    let mut rest = rest.skip_while(|token| {
+        matches!(token, Token::Word(word)
+            if word.eq_ignore_ascii_case("not") || word.eq_ignore_ascii_case("materialized"))
+    });
+
+    matches!(rest.next(), Some(Token::Open))
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Token<'a> {
+    /// A keyword or unquoted identifier.
+    Word(&'a str),
+    /// A quoted identifier, without the surrounding quotes.
+    Quoted(&'a str),
+    Open,
+    Close,
+    Dot,
+    /// The `::` type cast operator.
+    Cast,
+    /// Anything else: literals, parameters, and operators.
+    Other,
+}
+
+/// Split a SQL statement into tokens, skipping over whitespace and comments.
+///
+/// This only does as much as is needed by [`check_dml`] and
+/// [`function_calls`]. In particular, it makes sure that words within string
+/// literals, quoted identifiers, and comments are never mistaken for keywords.
+fn tokenize(sql: &str) -> anyhow::Result<Vec<Token<'_>>> {
+    let bytes = sql.as_bytes();
+    let is_ident = |b: u8| b.is_ascii_alphanumeric() || b == b'_' || b == b'$' || b >= 0x80;
+
+    // Find the end of a quoted section that starts at `start`, where a doubled
+    // quote is an escaped quote.
+    let quoted = |start: usize, quote: u8, backslash: bool| -> anyhow::Result<usize> {
+        let mut i = start + 1;
+        loop {
+            match bytes.get(i) {
+                Some(b'\\') if backslash => i += 2,
+                Some(&b) if b == quote && bytes.get(i + 1) == Some(&quote) => i += 2,
+                Some(&b) if b == quote => return Ok(i + 1),
+                Some(_) => i += 1,
+                None => anyhow::bail!("the statement contains an unterminated quote"),
+            }
+        }
+    };
+
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        let b = bytes[i];
+
+        match b {
+            _ if b.is_ascii_whitespace() => i += 1,
+            b'-' if bytes.get(i + 1) == Some(&b'-') => {
+                i = sql[i..].find('\n').map_or(bytes.len(), |end| i + end + 1);
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                let mut depth = 0;
+                loop {
+                    match (bytes.get(i), bytes.get(i + 1)) {
+                        (Some(b'/'), Some(b'*')) => {
+                            depth += 1;
+                            i += 2;
+                        }
+                        (Some(b'*'), Some(b'/')) => {
+                            depth -= 1;
+                            i += 2;
+
+                            if depth == 0 {
+                                break;
+                            }
+                        }
+                        (Some(_), _) => i += 1,
+                        (None, _) => {
+                            anyhow::bail!("the statement contains an unterminated comment")
+                        }
+                    }
+                }
+            }
+            b'(' => {
+                tokens.push(Token::Open);
+                i += 1;
+            }
+            b')' => {
+                tokens.push(Token::Close);
+                i += 1;
+            }
+            b'\'' => {
+                i = quoted(i, b'\'', false)?;
+                tokens.push(Token::Other);
+            }
+            b'"' => {
+                let start = i;
+                i = quoted(i, b'"', false)?;
+                tokens.push(Token::Quoted(&sql[start + 1..i - 1]));
+            }
+            b':' if bytes.get(i + 1) == Some(&b':') => {
+                tokens.push(Token::Cast);
+                i += 2;
+            }
+            b'.' if !bytes.get(i + 1).is_some_and(u8::is_ascii_digit) => {
+                tokens.push(Token::Dot);
+                i += 1;
+            }
+            b'$' => {
+                let tag_end = (i + 1..bytes.len())
+                    .find(|&j| !(is_ident(bytes[j]) && bytes[j] != b'$'))
+                    .unwrap_or(bytes.len());
+                let is_dollar_quote = bytes.get(tag_end) == Some(&b'$')
+                    && !bytes.get(i + 1).is_some_and(u8::is_ascii_digit);
+
+                if is_dollar_quote {
+                    let tag = &sql[i..=tag_end];
+                    let Some(end) = sql[tag_end + 1..].find(tag) else {
+                        anyhow::bail!("the statement contains an unterminated quote");
+                    };
+
+                    i = tag_end + 1 + end + tag.len();
+                } else {
+                    // A positional parameter.
+                    i = tag_end;
+                }
+
+                tokens.push(Token::Other);
+            }
+            _ if is_ident(b) && !b.is_ascii_digit() => {
+                let start = i;
+                while bytes.get(i).copied().is_some_and(is_ident) {
+                    i += 1;
+                }
+
+                // String constants with a prefix (e.g. E'...') are a single token.
+                let word = &sql[start..i];
+                match bytes.get(i) {
+                    Some(b'\'') if word.eq_ignore_ascii_case("e") => {
+                        i = quoted(i, b'\'', true)?;
+                        tokens.push(Token::Other);
+                    }
+                    Some(b'\'') if ["b", "x", "n"].iter().any(|p| word.eq_ignore_ascii_case(p)) => {
+                        i = quoted(i, b'\'', false)?;
+                        tokens.push(Token::Other);
+                    }
+                    _ => tokens.push(Token::Word(word)),
+                }
+            }
+            _ if b.is_ascii_digit() || b == b'.' => {
+                while bytes
+                    .get(i)
+                    .is_some_and(|&b| b.is_ascii_alphanumeric() || b == b'.' || b == b'_')
+                {
+                    i += 1;
+                }
+
+                tokens.push(Token::Other);
+            }
+            _ => {
+                tokens.push(Token::Other);
+                i += 1;
+            }
+        }
+    }
+
+    Ok(tokens)
+}
+
+/// Extract the (possibly schema-qualified) table name from a `COPY`
+/// statement, exactly as it was written.
+fn copy_target(sql: &str) -> Option<&str> {
+    let sql = sql.trim_start();
+    let keyword = sql.get(..4)?;
+    if !keyword.eq_ignore_ascii_case("copy") {
+        return None;
+    }
+
+    let rest = &sql[4..];
+    let trimmed = rest.trim_start();
+    if trimmed.len() == rest.len() {
+        return None;
+    }
+
+    let bytes = trimmed.as_bytes();
+    let mut end = 0;
+    loop {
+        if bytes.get(end) == Some(&b'"') {
+            end += 1;
+            loop {
+                match bytes.get(end) {
+                    Some(b'"') if bytes.get(end + 1) == Some(&b'"') => end += 2,
+                    Some(b'"') => break,
+                    Some(_) => end += 1,
+                    None => return None,
+                }
+            }
+            end += 1;
+        } else {
+            let start = end;
+            while bytes
+                .get(end)
+                .is_some_and(|&b| b.is_ascii_alphanumeric() || b == b'_' || b == b'$' || b >= 0x80)
+            {
+                end += 1;
+            }
+
+            if end == start {
+                return None;
+            }
+        }
+
+        if bytes.get(end) != Some(&b'.') {
+            break;
+        }
+
+        end += 1;
+    }
+
+    Some(&trimmed[..end])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn copy_target_parses_names() {
+        assert_eq!(copy_target("COPY users FROM STDIN"), Some("users"));
+        assert_eq!(
+            copy_target("copy public.users(id) FROM STDIN"),
+            Some("public.users")
+        );
+        assert_eq!(
+            copy_target(r#" COPY "my ""schema"""."Users" (id) FROM STDIN"#),
+            Some(r#""my ""schema"""."Users""#)
+        );
+        assert_eq!(copy_target("COPY (SELECT 1) TO STDOUT"), None);
+        assert_eq!(copy_target("COPYusers FROM STDIN"), None);
+        assert_eq!(copy_target(r#"COPY "unterminated FROM STDIN"#), None);
+    }
+
+    #[test]
+    fn check_dml_allows_dml() {
+        for sql in [
+            "SELECT * FROM users",
+            "  (SELECT 1) UNION (SELECT 2)",
+            "insert into users(id) values ($1)",
+            "WITH moved AS (DELETE FROM a RETURNING *) INSERT INTO b SELECT * FROM moved",
+            "WITH x AS MATERIALIZED (INSERT INTO a VALUES (1) RETURNING id) SELECT * FROM x",
+            "MERGE INTO a USING b ON a.id = b.id WHEN NOT MATCHED THEN INSERT VALUES (b.id)",
+            "SELECT 'into', \"into\", $$ into $$, $tag$ into $tag$ FROM t -- into",
+            "SELECT /* into /* nested */ into */ E'\\' into' FROM t",
+        ] {
+            assert!(check_dml(sql).is_ok(), "{sql}");
+        }
+    }
+
+    #[test]
+    fn check_dml_rejects_other_statements() {
+        for sql in [
+            "",
+            "-- SELECT",
+            "CREATE TABLE t AS SELECT * FROM users",
+            "create materialized view v as select 1",
+            "EXPLAIN SELECT 1",
+            "SELECT * INTO t FROM users",
+            "(SELECT 1 INTO t)",
+            "WITH x AS (SELECT 1) SELECT * INTO t FROM x",
+            "SELECT (1) insert INTO t",
+            "SELECT 'unterminated",
+        ] {
+            assert!(check_dml(sql).is_err(), "{sql}");
+        }
+    }
+
+    fn calls(sql: &str) -> Vec<String> {
+        function_calls(sql)
+            .unwrap()
+            .iter()
+            .map(Function::to_string)
+            .collect()
+    }
+
+    #[test]
+    fn function_calls_finds_calls() {
+        let cases: &[(&str, &[&str])] = &[
+            (
+                "SELECT count(*), lower(name) FROM users",
+                &["count", "lower"],
+            ),
+            (
+                "SELECT query_to_xml('select * from durable.task', true, true, '')",
+                &["query_to_xml"],
+            ),
+            (
+                "SELECT * FROM dblink('dbname=x', 'select 1') AS (a int)",
+                &["dblink"],
+            ),
+            (
+                r#"SELECT pg_catalog.Lower(x), "Query_To_Xml"(y) FROM t"#,
+                &["pg_catalog.lower", "Query_To_Xml"],
+            ),
+            (
+                "SELECT DISTINCT ON (id) table_to_xml('t', true, true, '') FROM t",
+                &["table_to_xml"],
+            ),
+            (
+                "INSERT INTO t(a) VALUES (1) ON CONFLICT (a) DO UPDATE SET a = f(1)",
+                &["f"],
+            ),
+            ("SELECT CAST(x AS numeric(10, 2)) FROM t", &["cast"]),
+            (
+                "SELECT * FROM generate_series(1, 3) g(n)",
+                &["generate_series", "g"],
+            ),
+            (
+                "SELECT * FROM unnest($1) WITH ORDINALITY, f(1)",
+                &["unnest", "f"],
+            ),
+        ];
+
+        for (sql, expected) in cases {
+            assert_eq!(calls(sql), *expected, "{sql}");
+        }
+    }
+
+    #[test]
+    fn function_calls_skips_syntax() {
+        for sql in [
+            "SELECT * FROM users WHERE id IN (1, 2) AND NOT EXISTS (SELECT 1)",
+            "INSERT INTO public.users(id, name) VALUES ($1, $2)",
+            r#"COPY "public"."users" ("id") FROM STDIN"#,
+            "SELECT x::numeric(10, 2), y::varchar(20), z::pg_catalog.numeric(3) FROM t",
+            "SELECT * FROM users AS u(a, b) JOIN accounts a(c) USING (c)",
+            "WITH x(a) AS (SELECT 1), y (b) AS NOT MATERIALIZED (SELECT 2) SELECT * FROM x, y",
+            "WITH RECURSIVE r(n) AS (VALUES (1) UNION ALL SELECT n FROM r) SELECT * FROM r",
+            "SELECT id = ANY($1) FROM t TABLESAMPLE bernoulli (10) REPEATABLE (1)",
+            "UPDATE t SET (a, b) = ROW(1, 2) WHERE (a) OVERLAPS (b)",
+            "SELECT 'f(x)', \"quoted\" FROM t -- f(x)",
+            "SELECT 1.5, .5 FROM t",
+        ] {
+            assert_eq!(calls(sql), Vec::<String>::new(), "{sql}");
+        }
+    }
+
+    #[test]
+    fn allowlist_policy_allows_functions() {
+        let policy = AllowlistPolicy::new().function("billing.total");
+        let function = |schema: Option<&str>, name: &str| Function {
+            schema: schema.map(str::to_owned),
+            name: name.to_owned(),
+        };
+
+        assert!(policy.allows_function(&function(None, "lower")));
+        assert!(policy.allows_function(&function(Some("pg_catalog"), "lower")));
+        assert!(policy.allows_function(&function(Some("billing"), "total")));
+        assert!(!policy.allows_function(&function(None, "total")));
+        assert!(!policy.allows_function(&function(Some("public"), "lower")));
+        assert!(!policy.allows_function(&function(None, "query_to_xml")));
+        assert!(!policy.allows_function(&function(None, "dblink")));
+    }
+
+    #[test]
+    fn collect_relations_walks_plan() {
+        let plan = serde_json::json!({
+            "Node Type": "ModifyTable",
+            "Schema": "public",
+            "Relation Name": "users",
+            "Plans": [
+                { "Node Type": "Seq Scan", "Schema": "billing", "Relation Name": "accounts" },
+                { "Node Type": "Seq Scan", "Schema": "public", "Relation Name": "users" },
+                { "Node Type": "Result" }
+            ]
+        });
+
+        let mut relations = Vec::new();
+        collect_relations(&plan, &mut relations);
+
+        assert_eq!(
+            relations,
+            [
+                Relation {
+                    schema: "public".into(),
+                    name: "users".into()
+                },
+                Relation {
+                    schema: "billing".into(),
+                    name: "accounts".into()
+                }
+            ]
+        );
+    }
+}
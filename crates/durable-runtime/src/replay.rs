@@ -0,0 +1,390 @@
+//! Replay workflows against a recorded event log.
+//!
+//! A replay runs a workflow program using only the events that were recorded
+//! by a previous execution of the same task. No database is needed and no new
+//! transactions are ever executed. This makes it possible to check, without
+//! side effects, whether a program (or a new version of it) still makes the
+//! same sequence of transactions as the one that was recorded.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Context;
+use chrono::{DateTime, Utc};
+use serde_json::value::RawValue;
+use sqlx::types::Json;
+use wasmtime::component::{Component, Linker};
+
+use crate::error::TaskStatus;
+use crate::plugin::{DurablePlugin, Plugin};
+use crate::task::{Task, TaskState, LEGACY_OUTPUT_LABEL};
+use crate::worker::{as_task_exit, call_guest, SharedState, TaskData};
+use crate::{Config, Resources};
+
+/// A single event from a recorded task event log.
+#[derive(Clone, Debug)]
+pub struct ReplayEvent {
+    /// The index of this event within the event log.
+    pub index: i32,
+
+    /// The label of the transaction that recorded this event.
+    pub label: String,
+
+    /// The JSON-encoded value returned by the transaction.
+    pub value: Box<RawValue>,
+}
+
+/// Details about the task that is being replayed.
+///
+/// These are returned to the workflow when it asks for details about the task
+/// that it is running as, so they should match the task the event log was
+/// recorded from.
+#[derive(Clone, Debug)]
+pub struct ReplayTask {
+    pub id: i64,
+    pub name: String,
+    pub created_at: DateTime<Utc>,
+    pub data: Box<RawValue>,
+    pub entrypoint: Option<String>,
+    pub labels: BTreeMap<String, String>,
+}
+
+impl ReplayTask {
+    pub fn new(id: i64, name: impl Into<String>, data: Box<RawValue>) -> Self {
+        Self {
+            id,
+            name: name.into(),
+            created_at: Utc::now(),
+            data,
+            entrypoint: None,
+            labels: BTreeMap::new(),
+        }
+    }
+
+    /// Set a label that the task was launched with.
+    pub fn label(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.labels.insert(key.into(), value.into());
+        self
+    }
+
+    /// Set the exported workflow that the task was launched with.
+    pub fn entrypoint(mut self, entrypoint: impl Into<String>) -> Self {
+        self.entrypoint = Some(entrypoint.into());
+        self
+    }
+
+    /// Set the timestamp at which the task was created.
+    pub fn created_at(mut self, created_at: DateTime<Utc>) -> Self {
+        self.created_at = created_at;
+        self
+    }
+}
+
+/// A point at which the workflow did not match the recorded event log.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Divergence {
+    /// The index of the event at which the divergence occurred.
+    pub index: i32,
+
+    /// The label recorded at `index`, if there was one.
+    pub recorded: Option<String>,
+
+    /// The label of the transaction that the workflow requested at `index`.
+    ///
+    /// This is `None` if the workflow exited before reaching `index`.
+    pub requested: Option<String>,
+}
+
+impl fmt::Display for Divergence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (&self.recorded, &self.requested) {
+            (Some(recorded), Some(requested)) => write!(
+                f,
+                "workflow execution is non-deterministic: stored event at index {} has label \
+                 {recorded:?} but the workflow requested {requested:?}",
+                self.index
+            ),
+            (Some(recorded), None) => write!(
+                f,
+                "workflow exited before reaching stored event at index {} with label {recorded:?}",
+                self.index
+            ),
+            (None, Some(requested)) => write!(
+                f,
+                "workflow requested {requested:?} at index {} but there is no stored event there",
+                self.index
+            ),
+            (None, None) => write!(f, "workflow diverged at index {}", self.index),
+        }
+    }
+}
+
+impl std::error::Error for Divergence {}
+
+/// The workflow requested a transaction past the end of the recorded event log.
+#[derive(Clone, Debug)]
+struct EndOfLog {
+    index: i32,
+    label: String,
+}
+
+impl fmt::Display for EndOfLog {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "reached the end of the recorded event log at index {} (requested {:?})",
+            self.index, self.label
+        )
+    }
+}
+
+impl std::error::Error for EndOfLog {}
+
+/// The result of replaying a workflow.
+#[derive(Clone, Debug)]
+pub enum ReplayOutcome {
+    /// The workflow exited after making exactly the transactions in the
+    /// recorded event log.
+    Exited(TaskStatus),
+
+    /// The workflow matched the recorded event log up until its end, and then
+    /// requested another transaction.
+    ///
+    /// This is expected when replaying a task that had not yet completed when
+    /// the event log was recorded.
+    EndOfLog {
+        /// The index of the first transaction that was not in the log.
+        index: i32,
+
+        /// The label of the transaction the workflow requested.
+        label: String,
+    },
+
+    /// The workflow did not match the recorded event log.
+    Diverged(Divergence),
+}
+
+pub(crate) struct ReplayLog {
+    events: Vec<ReplayEvent>,
+}
+
+impl ReplayLog {
+    fn new(events: impl IntoIterator<Item = ReplayEvent>) -> anyhow::Result<Self> {
+        let mut events: Vec<_> = events.into_iter().collect();
+        events.sort_by_key(|event| event.index);
+
+        for (expected, event) in events.iter().enumerate() {
+            if usize::try_from(event.index).ok() != Some(expected) {
+                anyhow::bail!(
+                    "recorded event log is not contiguous: expected an event at index {expected} \
+                     but found one at index {}",
+                    event.index
+                );
+            }
+        }
+
+        Ok(Self { events })
+    }
+
+    /// Get the index of the first event at or after `index` that was not
+    /// created by an older runtime for a write to stdout or stderr.
+    pub(crate) fn skip_legacy_output(&self, mut index: i32) -> i32 {
+        while usize::try_from(index)
+            .ok()
+            .and_then(|i| self.events.get(i))
+            .is_some_and(|event| event.label == LEGACY_OUTPUT_LABEL)
+        {
+            index += 1;
+        }
+
+        index
+    }
+
+    /// Get the recorded event at `index`, validating that it has the requested
+    /// label.
+    pub(crate) fn get(&self, index: i32, label: &str) -> anyhow::Result<&ReplayEvent> {
+        let event = match usize::try_from(index).ok().and_then(|i| self.events.get(i)) {
+            Some(event) => event,
+            None => {
+                return Err(anyhow::Error::new(EndOfLog {
+                    index,
+                    label: label.to_owned(),
+                }))
+            }
+        };
+
+        if event.label != label {
+            return Err(anyhow::Error::new(Divergence {
+                index,
+                recorded: Some(event.label.clone()),
+                requested: Some(label.to_owned()),
+            }));
+        }
+
+        Ok(event)
+    }
+}
+
+/// Replays workflow programs against recorded event logs.
+///
+/// This sets up the same plugins that a worker would, but resolves every
+/// transaction against the provided event log instead of the database.
+pub struct Replay {
+    engine: wasmtime::Engine,
+    config: Config,
+    client: reqwest::Client,
+    plugins: Vec<Arc<dyn Plugin>>,
+}
+
+impl Replay {
+    /// Create a new replay using the provided wasmtime config.
+    ///
+    /// Async support is always enabled on the resulting engine.
+    pub fn new(mut config: wasmtime::Config) -> anyhow::Result<Self> {
+        config.async_support(true);
+
+        Ok(Self {
+            engine: wasmtime::Engine::new(&config)?,
+            config: Config::default(),
+            client: reqwest::Client::default(),
+            plugins: vec![Arc::new(DurablePlugin)],
+        })
+    }
+
+    /// Set the runtime config that will be visible to plugins.
+    pub fn config(mut self, config: Config) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Add a new API plugin to the runtime.
+    pub fn plugin(mut self, plugin: Box<dyn Plugin>) -> Self {
+        self.plugins.push(Arc::from(plugin));
+        self
+    }
+
+    /// The engine that components need to be compiled with in order to be
+    /// replayed.
+    pub fn engine(&self) -> &wasmtime::Engine {
+        &self.engine
+    }
+
+    /// Replay `component` as `task` against the recorded `events`.
+    ///
+    /// # Errors
+    /// This returns an error if the event log is not contiguous, if the
+    /// component cannot be instantiated, or if the workflow fails for any
+    /// reason other than diverging from the event log.
+    pub async fn run(
+        &self,
+        component: &Component,
+        task: ReplayTask,
+        events: impl IntoIterator<Item = ReplayEvent>,
+    ) -> anyhow::Result<ReplayOutcome> {
+        let log = ReplayLog::new(events)?;
+
+        // The replay never touches the database, but plugins still expect there to
+        // be a pool available. Use a lazy pool that fails immediately if it is ever
+        // used.
+        let pool = sqlx::postgres::PgPoolOptions::new()
+            .max_connections(1)
+            .acquire_timeout(Duration::ZERO)
+            .connect_lazy_with(sqlx::postgres::PgConnectOptions::new().host("replay.invalid"));
+
+        let shared = Arc::new(SharedState::new(
+            pool,
+            self.client.clone(),
+            self.config.clone(),
+            self.plugins.clone(),
+        ));
+
+        let entrypoint = task.entrypoint.clone();
+        let data = TaskData {
+            id: task.id,
+            name: task.name,
+            created_at: task.created_at,
+            attempt: 0,
+            labels: Json(task.labels),
+            wasm: -1,
+            data: Json(task.data),
+            sql_context: None,
+            entrypoint: task.entrypoint,
+            tenant: None,
+        };
+        let mut task = Task {
+            state: TaskState::new_replay(shared.clone(), data, log),
+            plugins: Default::default(),
+            resources: Resources::default(),
+        };
+
+        let mut linker = Linker::new(&self.engine);
+        for plugin in self.plugins.iter() {
+            plugin
+                .setup(&mut linker, &mut task)
+                .with_context(|| format!("failed to set up plugin `{}`", plugin.name()))?;
+        }
+
+        if !shared.config.strict_imports {
+            linker
+                .define_unknown_imports_as_traps(component)
+                .context("failed to define unknown imports as traps")?;
+        }
+
+        let mut store = wasmtime::Store::new(&self.engine, task);
+        let instance = linker
+            .instantiate_async(&mut store, component)
+            .await
+            .context("failed to instantiate the wasm component")?;
+
+        store
+            .data_mut()
+            .run_plugin_hooks(|plugin, task| plugin.on_task_start(task))?;
+
+        let status = match call_guest(&mut store, &instance, entrypoint.as_deref()).await {
+            Ok(Ok(())) => TaskStatus::ExitSuccess,
+            Ok(Err(())) => TaskStatus::ExitFailure,
+            Err(e) => {
+                for cause in e.chain() {
+                    if let Some(divergence) = cause.downcast_ref::<Divergence>() {
+                        return Ok(ReplayOutcome::Diverged(divergence.clone()));
+                    }
+
+                    if let Some(end) = cause.downcast_ref::<EndOfLog>() {
+                        return Ok(ReplayOutcome::EndOfLog {
+                            index: end.index,
+                            label: end.label.clone(),
+                        });
+                    }
+                }
+
+                match as_task_exit(&e) {
+                    Some(status) => status,
+                    None => return Err(e),
+                }
+            }
+        };
+
+        // The workflow exited, but it may not have used all the recorded events.
+        let state = &store.data().state;
+        let remaining = state.replay_log().and_then(|log| {
+            let index = log.skip_legacy_output(state.txn_index());
+            log.events.get(usize::try_from(index).ok()?)
+        });
+        if let Some(event) = remaining {
+            return Ok(ReplayOutcome::Diverged(Divergence {
+                index: event.index,
+                recorded: Some(event.label.clone()),
+                requested: None,
+            }));
+        }
+
+        let _ = store.data_mut().run_plugin_hooks(|plugin, task| {
+            plugin.on_task_complete(task, status);
+            Ok(())
+        });
+
+        Ok(ReplayOutcome::Exited(status))
+    }
+}
@@ -129,14 +129,31 @@ impl Resources {
     }
 
     pub fn insert<R>(&mut self, data: R::Data) -> wasmtime::Result<Resource<R>>
+    where
+        R: Resourceable,
+    {
+        self.insert_with_txn(data, self.txn)
+    }
+
+    /// Insert a resource that is not tied to the current transaction, so that
+    /// it can be used from any later transaction.
+    pub fn insert_detached<R>(&mut self, data: R::Data) -> wasmtime::Result<Resource<R>>
+    where
+        R: Resourceable,
+    {
+        self.insert_with_txn(data, None)
+    }
+
+    fn insert_with_txn<R>(
+        &mut self,
+        data: R::Data,
+        txn: Option<i32>,
+    ) -> wasmtime::Result<Resource<R>>
     where
         R: Resourceable,
     {
         let slab: &mut ResourceSlab<R> = self.data.entry().or_default();
-        let index = slab.0.insert(Entry {
-            data,
-            txn: self.txn,
-        });
+        let index = slab.0.insert(Entry { data, txn });
         let index = match u32::try_from(index) {
             Ok(index) => index,
             Err(_) => {
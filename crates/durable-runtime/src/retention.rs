@@ -0,0 +1,301 @@
+//! Pruning of old events and logs, and management of the partitions of the
+//! `durable.event`, `durable.log` and `durable.log_entry` tables.
+
+use std::time::Duration;
+
+use durable_migrate::SchemaRename;
+use sqlx::PgConnection;
+
+use crate::util::IntoPgInterval;
+
+/// The tables that are partitioned by task id.
+const PARTITIONED_TABLES: &[&str] = &["event", "log", "log_entry"];
+
+/// Delete the events of tasks that completed more than `age` ago.
+///
+/// Events are deleted in batches of at most `limit` rows. Returns the total
+/// number of events that were deleted.
+pub(crate) async fn prune_events(
+    schema: &SchemaRename,
+    conn: &mut PgConnection,
+    age: Duration,
+    limit: i64,
+) -> sqlx::Result<u64> {
+    let interval = age.into_pg_interval();
+    let mut total = 0;
+
+    loop {
+        let result = sqlx::query!(
+            r#"
+            DELETE FROM durable.event
+            WHERE (task_id, index) IN (
+                SELECT e.task_id, e.index
+                  FROM durable.event e
+                  JOIN durable.task t ON t.id = e.task_id
+                 WHERE t.state = 'complete'
+                   AND t.completed_at < NOW() - $1::interval
+                 LIMIT $2
+            )
+            "#,
+            interval,
+            limit
+        )
+        .execute(schema.on(&mut *conn))
+        .await?;
+
+        total += result.rows_affected();
+        metrics::counter!("durable.retention.events_pruned").increment(result.rows_affected());
+
+        if result.rows_affected() < limit as u64 {
+            break;
+        }
+    }
+
+    Ok(total)
+}
+
+/// Delete the logs of tasks that finished more than `age` ago.
+///
+/// Unlike events, this also includes the logs of failed tasks. The matching
+/// rows in `durable.log_entry` are deleted along with them by a trigger.
+pub(crate) async fn prune_logs(
+    schema: &SchemaRename,
+    conn: &mut PgConnection,
+    age: Duration,
+    limit: i64,
+) -> sqlx::Result<u64> {
+    let interval = age.into_pg_interval();
+    let mut total = 0;
+
+    loop {
+        let result = sqlx::query!(
+            r#"
+            DELETE FROM durable.log
+            WHERE (task_id, index) IN (
+                SELECT l.task_id, l.index
+                  FROM durable.log l
+                  JOIN durable.task t ON t.id = l.task_id
+                 WHERE t.state IN ('complete', 'failed')
+                   AND t.completed_at < NOW() - $1::interval
+                 LIMIT $2
+            )
+            "#,
+            interval,
+            limit
+        )
+        .execute(schema.on(&mut *conn))
+        .await?;
+
+        total += result.rows_affected();
+        metrics::counter!("durable.retention.logs_pruned").increment(result.rows_affected());
+
+        if result.rows_affected() < limit as u64 {
+            break;
+        }
+    }
+
+    Ok(total)
+}
+
+/// A partition covering the task ids in `start..end`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+struct Partition {
+    start: i64,
+    end: i64,
+}
+
+impl Partition {
+    /// Parse a partition from its table name, e.g. `event_1_1000001`.
+    ///
+    /// Partitions that were not created by [`maintain_partitions`] (such as
+    /// the default partition) are ignored.
+    fn parse(table: &str, name: &str) -> Option<Self> {
+        let rest = name.strip_prefix(table)?.strip_prefix('_')?;
+        let (start, end) = rest.split_once('_')?;
+
+        Some(Self {
+            start: start.parse().ok()?,
+            end: end.parse().ok()?,
+        })
+    }
+
+    fn name(&self, table: &str) -> String {
+        format!("{table}_{}_{}", self.start, self.end)
+    }
+}
+
+/// Create partitions for upcoming task ids and drop the partitions whose
+/// tasks have all been deleted.
+///
+/// A new partition is created once the highest task id comes within `size`
+/// ids of the end of the last partition, so that there is always a partition
+/// ready for newly launched tasks.
+///
+/// Note that creating a partition requires postgres to check that none of the
+/// rows in the default partition belong in the new one. This requires a scan
+/// of the default partition, which may be slow the first time that partitions
+/// are created for an existing database.
+pub(crate) async fn maintain_partitions(
+    schema: &SchemaRename,
+    conn: &mut PgConnection,
+    size: u64,
+) -> sqlx::Result<()> {
+    let size = i64::try_from(size).unwrap_or(i64::MAX);
+    if size == 0 {
+        return Ok(());
+    }
+
+    let max_id = sqlx::query_scalar!(r#"SELECT COALESCE(MAX(id), 0) as "id!" FROM durable.task"#)
+        .fetch_one(schema.on(&mut *conn))
+        .await?;
+
+    for table in PARTITIONED_TABLES {
+        let partitions = partitions(schema, conn, table).await?;
+        let covered = partitions
+            .iter()
+            .map(|partition| partition.end)
+            .filter(|&end| end > max_id)
+            .max();
+
+        let next = match covered {
+            None => Some(Partition {
+                start: max_id + 1,
+                end: max_id.saturating_add(1).saturating_add(size),
+            }),
+            Some(end) if end - max_id < size => Some(Partition {
+                start: end,
+                end: end.saturating_add(size),
+            }),
+            Some(_) => None,
+        };
+
+        if let Some(partition) = next {
+            let name = partition.name(table);
+            let result = sqlx::query(&format!(
+                r#"
+                CREATE TABLE IF NOT EXISTS "durable"."{name}"
+                PARTITION OF "durable"."{table}"
+                FOR VALUES FROM ({}) TO ({})
+                "#,
+                partition.start, partition.end
+            ))
+            .execute(schema.on(&mut *conn))
+            .await;
+
+            match result {
+                Ok(_) => {
+                    tracing::info!("created partition durable.{name}");
+                    metrics::counter!("durable.retention.partitions_created", "table" => *table)
+                        .increment(1);
+                }
+                Err(e) => tracing::warn!("failed to create partition durable.{name}: {e}"),
+            }
+        }
+
+        for partition in partitions {
+            if partition.end > max_id {
+                continue;
+            }
+
+            let in_use = sqlx::query_scalar!(
+                r#"
+                SELECT EXISTS(
+                    SELECT 1
+                      FROM durable.task
+                     WHERE id >= $1 AND id < $2
+                ) as "in_use!"
+                "#,
+                partition.start,
+                partition.end
+            )
+            .fetch_one(schema.on(&mut *conn))
+            .await?;
+
+            if in_use {
+                continue;
+            }
+
+            let name = partition.name(table);
+            match drop_partition(schema, conn, &name).await {
+                Ok(()) => {
+                    tracing::info!("dropped partition durable.{name}");
+                    metrics::counter!("durable.retention.partitions_dropped", "table" => *table)
+                        .increment(1);
+                }
+                Err(e) => tracing::warn!("failed to drop partition durable.{name}: {e}"),
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// List the partitions of `durable.{table}`.
+async fn partitions(
+    schema: &SchemaRename,
+    conn: &mut PgConnection,
+    table: &str,
+) -> sqlx::Result<Vec<Partition>> {
+    let names: Vec<String> = sqlx::query_scalar(
+        "
+        SELECT c.relname::text
+          FROM pg_inherits i
+          JOIN pg_class c ON c.oid = i.inhrelid
+          JOIN pg_class p ON p.oid = i.inhparent
+          JOIN pg_namespace n ON n.oid = p.relnamespace
+         WHERE n.nspname = $2
+           AND p.relname = $1
+        ",
+    )
+    .bind(table)
+    .bind(schema.to())
+    .fetch_all(&mut *conn)
+    .await?;
+
+    let mut partitions: Vec<_> = names
+        .iter()
+        .filter_map(|name| Partition::parse(table, name))
+        .collect();
+    partitions.sort_by_key(|partition| partition.start);
+
+    Ok(partitions)
+}
+
+async fn drop_partition(
+    schema: &SchemaRename,
+    conn: &mut PgConnection,
+    name: &str,
+) -> sqlx::Result<()> {
+    let mut tx = sqlx::Connection::begin(conn).await?;
+
+    // Dropping a partition needs an exclusive lock on the parent table. We
+    // would rather try again later than hold up every event insert while
+    // waiting for it.
+    sqlx::query("SET LOCAL lock_timeout = '5s'")
+        .execute(&mut *tx)
+        .await?;
+    sqlx::query(&format!(r#"DROP TABLE IF EXISTS "durable"."{name}""#))
+        .execute(schema.on(&mut *tx))
+        .await?;
+
+    tx.commit().await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_partition_names() {
+        assert_eq!(
+            Partition::parse("event", "event_1_1000001"),
+            Some(Partition {
+                start: 1,
+                end: 1000001
+            })
+        );
+        assert_eq!(Partition::parse("event", "event_default"), None);
+        assert_eq!(Partition::parse("log", "event_1_2"), None);
+        assert_eq!(Partition { start: 5, end: 10 }.name("log"), "log_5_10");
+    }
+}
@@ -0,0 +1,468 @@
+//! An in-memory scratch filesystem that is private to a single task.
+//!
+//! Since the contents of the filesystem only ever depend on what the workflow
+//! itself has written, they are rebuilt deterministically whenever a workflow
+//! is replayed. The one exception is writes made within a transaction, since
+//! the body of a transaction is skipped when it is replayed. Those are only
+//! permitted if snapshots are enabled, in which case the entire filesystem is
+//! stored alongside the transaction's event and restored from there on
+//! replay. See [`ScratchDir`](crate::ScratchDir) for the user-facing
+//! documentation.
+
+use std::collections::BTreeMap;
+use std::io::{self, ErrorKind};
+
+use anyhow::Context;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Node {
+    Dir,
+    File(Vec<u8>),
+}
+
+/// The type and size of an entry within the filesystem.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub(crate) struct ScratchStat {
+    pub is_dir: bool,
+    pub size: u64,
+}
+
+pub(crate) struct ScratchFs {
+    /// All entries in the filesystem, keyed by their path relative to the root
+    /// directory. The root directory itself has the empty path.
+    nodes: BTreeMap<String, Node>,
+
+    /// The total size of all files in the filesystem.
+    used: usize,
+    max_bytes: usize,
+
+    /// Whether the filesystem has been modified since the last call to
+    /// [`take_modified`](Self::take_modified).
+    modified: bool,
+}
+
+impl ScratchFs {
+    pub fn new(max_bytes: usize) -> Self {
+        Self {
+            nodes: BTreeMap::from([(String::new(), Node::Dir)]),
+            used: 0,
+            max_bytes,
+            modified: false,
+        }
+    }
+
+    /// Resolve `path` relative to the directory at `base`.
+    ///
+    /// Returns `None` if `path` is absolute or would escape the root
+    /// directory.
+    pub fn join(base: &str, path: &str) -> Option<String> {
+        if path.starts_with('/') {
+            return None;
+        }
+
+        let mut parts: Vec<&str> = base.split('/').filter(|part| !part.is_empty()).collect();
+        for part in path.split('/') {
+            match part {
+                "" | "." => (),
+                ".." => {
+                    parts.pop()?;
+                }
+                part => parts.push(part),
+            }
+        }
+
+        Some(parts.join("/"))
+    }
+
+    /// Returns whether the filesystem has been modified since this was last
+    /// called and then resets the flag.
+    pub fn take_modified(&mut self) -> bool {
+        std::mem::take(&mut self.modified)
+    }
+
+    pub fn stat(&self, path: &str) -> io::Result<ScratchStat> {
+        Ok(match self.node(path)? {
+            Node::Dir => ScratchStat {
+                is_dir: true,
+                size: 0,
+            },
+            Node::File(data) => ScratchStat {
+                is_dir: false,
+                size: data.len() as u64,
+            },
+        })
+    }
+
+    /// Open the file or directory at `path`, returning whether it is a
+    /// directory.
+    pub fn open(
+        &mut self,
+        path: &str,
+        create: bool,
+        exclusive: bool,
+        truncate: bool,
+    ) -> io::Result<bool> {
+        match self.nodes.get(path) {
+            Some(_) if create && exclusive => Err(ErrorKind::AlreadyExists.into()),
+            Some(Node::Dir) if truncate => Err(ErrorKind::IsADirectory.into()),
+            Some(Node::Dir) => Ok(true),
+            Some(Node::File(_)) => {
+                if truncate {
+                    self.set_len(path, 0)?;
+                }
+
+                Ok(false)
+            }
+            None if create => {
+                self.check_parent(path)?;
+                self.nodes.insert(path.to_owned(), Node::File(Vec::new()));
+                self.modified = true;
+                Ok(false)
+            }
+            None => Err(ErrorKind::NotFound.into()),
+        }
+    }
+
+    pub fn create_dir(&mut self, path: &str) -> io::Result<()> {
+        if self.nodes.contains_key(path) {
+            return Err(ErrorKind::AlreadyExists.into());
+        }
+
+        self.check_parent(path)?;
+        self.nodes.insert(path.to_owned(), Node::Dir);
+        self.modified = true;
+        Ok(())
+    }
+
+    /// Read up to `len` bytes starting at `offset` from the file at `path`.
+    pub fn read(&self, path: &str, offset: u64, len: u64) -> io::Result<Vec<u8>> {
+        let data = self.file(path)?;
+        let start = usize::try_from(offset)
+            .unwrap_or(usize::MAX)
+            .min(data.len());
+        let len = usize::try_from(len).unwrap_or(usize::MAX);
+        let end = start.saturating_add(len).min(data.len());
+
+        Ok(data[start..end].to_vec())
+    }
+
+    /// Write `data` to the file at `path`, starting at `offset`, or at the end
+    /// of the file if `offset` is `None`.
+    ///
+    /// Returns the offset just past the end of the data that was written.
+    pub fn write(&mut self, path: &str, offset: Option<u64>, data: &[u8]) -> io::Result<u64> {
+        let len = self.file(path)?.len();
+        let start = match offset {
+            Some(offset) => usize::try_from(offset).map_err(|_| ErrorKind::FileTooLarge)?,
+            None => len,
+        };
+        let end = start
+            .checked_add(data.len())
+            .ok_or(ErrorKind::FileTooLarge)?;
+
+        if end > len {
+            self.reserve(end - len)?;
+        }
+
+        let file = self.file_mut(path)?;
+        if end > file.len() {
+            file.resize(end, 0);
+        }
+        file[start..end].copy_from_slice(data);
+        self.modified = true;
+
+        Ok(end as u64)
+    }
+
+    pub fn set_len(&mut self, path: &str, size: u64) -> io::Result<()> {
+        let size = usize::try_from(size).map_err(|_| ErrorKind::FileTooLarge)?;
+        let len = self.file(path)?.len();
+        if size > len {
+            self.reserve(size - len)?;
+        } else {
+            self.used -= len - size;
+        }
+
+        self.file_mut(path)?.resize(size, 0);
+        self.modified = true;
+        Ok(())
+    }
+
+    pub fn remove_file(&mut self, path: &str) -> io::Result<()> {
+        let size = self.file(path)?.len();
+        self.nodes.remove(path);
+        self.used -= size;
+        self.modified = true;
+        Ok(())
+    }
+
+    pub fn remove_dir(&mut self, path: &str) -> io::Result<()> {
+        if path.is_empty() {
+            return Err(ErrorKind::PermissionDenied.into());
+        }
+
+        match self.node(path)? {
+            Node::Dir => (),
+            Node::File(_) => return Err(ErrorKind::NotADirectory.into()),
+        }
+
+        if self.descendants(path).next().is_some() {
+            return Err(ErrorKind::DirectoryNotEmpty.into());
+        }
+
+        self.nodes.remove(path);
+        self.modified = true;
+        Ok(())
+    }
+
+    pub fn rename(&mut self, from: &str, to: &str) -> io::Result<()> {
+        if from.is_empty() || to.is_empty() {
+            return Err(ErrorKind::PermissionDenied.into());
+        }
+
+        let is_dir = self.stat(from)?.is_dir;
+        if from == to {
+            return Ok(());
+        }
+
+        if is_dir && to.starts_with(from) && to.as_bytes()[from.len()] == b'/' {
+            return Err(ErrorKind::InvalidInput.into());
+        }
+
+        self.check_parent(to)?;
+        match self.nodes.get(to) {
+            Some(Node::Dir) if !is_dir => return Err(ErrorKind::IsADirectory.into()),
+            Some(Node::File(_)) if is_dir => return Err(ErrorKind::NotADirectory.into()),
+            Some(Node::Dir) => self.remove_dir(to)?,
+            Some(Node::File(_)) => self.remove_file(to)?,
+            None => (),
+        }
+
+        let mut moved: Vec<String> = self
+            .descendants(from)
+            .map(|(path, _)| path.clone())
+            .collect();
+        moved.push(from.to_owned());
+
+        for path in moved {
+            let node = self.nodes.remove(&path).unwrap();
+            self.nodes
+                .insert(format!("{to}{}", &path[from.len()..]), node);
+        }
+
+        self.modified = true;
+        Ok(())
+    }
+
+    /// List the entries of the directory at `path`, returning their names and
+    /// whether they are directories.
+    pub fn read_dir(&self, path: &str) -> io::Result<Vec<(String, bool)>> {
+        match self.node(path)? {
+            Node::Dir => (),
+            Node::File(_) => return Err(ErrorKind::NotADirectory.into()),
+        }
+
+        let prefix = match path {
+            "" => String::new(),
+            path => format!("{path}/"),
+        };
+
+        Ok(self
+            .descendants(path)
+            .map(|(child, node)| (&child[prefix.len()..], node))
+            .filter(|(name, _)| !name.contains('/'))
+            .map(|(name, node)| (name.to_owned(), *node == Node::Dir))
+            .collect())
+    }
+
+    /// Serialize the entire contents of the filesystem.
+    pub fn snapshot(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(self.used);
+
+        for (path, node) in self.nodes.iter().skip(1) {
+            out.extend_from_slice(&(path.len() as u32).to_le_bytes());
+            out.extend_from_slice(path.as_bytes());
+
+            match node {
+                Node::Dir => out.push(0),
+                Node::File(data) => {
+                    out.push(1);
+                    out.extend_from_slice(&(data.len() as u64).to_le_bytes());
+                    out.extend_from_slice(data);
+                }
+            }
+        }
+
+        out
+    }
+
+    /// Replace the contents of the filesystem with a snapshot previously
+    /// created by [`snapshot`](Self::snapshot).
+    pub fn restore(&mut self, mut snapshot: &[u8]) -> anyhow::Result<()> {
+        fn take<'a>(data: &mut &'a [u8], len: usize) -> anyhow::Result<&'a [u8]> {
+            if data.len() < len {
+                anyhow::bail!("unexpected end of snapshot");
+            }
+
+            let (head, tail) = data.split_at(len);
+            *data = tail;
+            Ok(head)
+        }
+
+        let mut nodes = BTreeMap::from([(String::new(), Node::Dir)]);
+        let mut used = 0;
+
+        while !snapshot.is_empty() {
+            let len = u32::from_le_bytes(take(&mut snapshot, 4)?.try_into().unwrap());
+            let path = std::str::from_utf8(take(&mut snapshot, len as usize)?)
+                .context("snapshot contained a path that was not valid UTF-8")?;
+
+            let node = match take(&mut snapshot, 1)?[0] {
+                0 => Node::Dir,
+                1 => {
+                    let len = u64::from_le_bytes(take(&mut snapshot, 8)?.try_into().unwrap());
+                    let data = take(&mut snapshot, usize::try_from(len)?)?;
+                    used += data.len();
+                    Node::File(data.to_vec())
+                }
+                kind => anyhow::bail!("snapshot contained an unknown node kind {kind}"),
+            };
+
+            nodes.insert(path.to_owned(), node);
+        }
+
+        self.nodes = nodes;
+        self.used = used;
+        self.modified = false;
+        Ok(())
+    }
+
+    fn node(&self, path: &str) -> io::Result<&Node> {
+        self.nodes
+            .get(path)
+            .ok_or_else(|| ErrorKind::NotFound.into())
+    }
+
+    fn file(&self, path: &str) -> io::Result<&Vec<u8>> {
+        match self.node(path)? {
+            Node::File(data) => Ok(data),
+            Node::Dir => Err(ErrorKind::IsADirectory.into()),
+        }
+    }
+
+    fn file_mut(&mut self, path: &str) -> io::Result<&mut Vec<u8>> {
+        match self.nodes.get_mut(path) {
+            Some(Node::File(data)) => Ok(data),
+            Some(Node::Dir) => Err(ErrorKind::IsADirectory.into()),
+            None => Err(ErrorKind::NotFound.into()),
+        }
+    }
+
+    /// Check that the parent of `path` exists and is a directory.
+    fn check_parent(&self, path: &str) -> io::Result<()> {
+        let parent = path
+            .rsplit_once('/')
+            .map(|(parent, _)| parent)
+            .unwrap_or("");
+
+        match self.nodes.get(parent) {
+            Some(Node::Dir) => Ok(()),
+            Some(Node::File(_)) => Err(ErrorKind::NotADirectory.into()),
+            None => Err(ErrorKind::NotFound.into()),
+        }
+    }
+
+    /// Account for `additional` bytes of file data, failing if that would put
+    /// the filesystem over its size limit.
+    fn reserve(&mut self, additional: usize) -> io::Result<()> {
+        match self.used.checked_add(additional) {
+            Some(used) if used <= self.max_bytes => {
+                self.used = used;
+                Ok(())
+            }
+            _ => Err(ErrorKind::StorageFull.into()),
+        }
+    }
+
+    /// Iterate over all entries nested (at any depth) within the directory at
+    /// `path`.
+    fn descendants<'a>(&'a self, path: &str) -> impl Iterator<Item = (&'a String, &'a Node)> {
+        let prefix = match path {
+            "" => String::new(),
+            path => format!("{path}/"),
+        };
+
+        self.nodes
+            .range(prefix.clone()..)
+            .skip_while(|(child, _)| child.is_empty())
+            .take_while(move |(child, _)| child.starts_with(&prefix))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn join_stays_within_root() {
+        assert_eq!(ScratchFs::join("", "a/b"), Some("a/b".into()));
+        assert_eq!(ScratchFs::join("a", "./b/../c"), Some("a/c".into()));
+        assert_eq!(ScratchFs::join("a", ".."), Some("".into()));
+        assert_eq!(ScratchFs::join("a", "../.."), None);
+        assert_eq!(ScratchFs::join("", "/etc"), None);
+    }
+
+    #[test]
+    fn files_and_directories() {
+        let mut fs = ScratchFs::new(1024);
+        fs.create_dir("dir").unwrap();
+        assert!(!fs.open("dir/a.txt", true, false, false).unwrap());
+        assert_eq!(fs.write("dir/a.txt", None, b"hello").unwrap(), 5);
+        assert_eq!(fs.write("dir/a.txt", Some(3), b"p!").unwrap(), 5);
+        assert_eq!(fs.read("dir/a.txt", 0, 100).unwrap(), b"help!");
+
+        fs.create_dir("dir/sub").unwrap();
+        fs.open("dir-file", true, false, false).unwrap();
+        assert_eq!(
+            fs.read_dir("dir").unwrap(),
+            [("a.txt".to_owned(), false), ("sub".to_owned(), true)]
+        );
+        assert_eq!(fs.read_dir("").unwrap().len(), 2);
+
+        let err = fs.remove_dir("dir").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::DirectoryNotEmpty);
+
+        fs.rename("dir", "moved").unwrap();
+        assert_eq!(fs.read("moved/a.txt", 0, 100).unwrap(), b"help!");
+        assert!(fs.stat("moved/sub").unwrap().is_dir);
+        assert_eq!(fs.stat("dir").unwrap_err().kind(), ErrorKind::NotFound);
+    }
+
+    #[test]
+    fn size_limit() {
+        let mut fs = ScratchFs::new(8);
+        fs.open("a", true, false, false).unwrap();
+        fs.write("a", None, b"12345678").unwrap();
+
+        let err = fs.write("a", None, b"9").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::StorageFull);
+
+        fs.set_len("a", 4).unwrap();
+        fs.write("a", None, b"9").unwrap();
+    }
+
+    #[test]
+    fn snapshot_roundtrip() {
+        let mut fs = ScratchFs::new(1024);
+        fs.create_dir("dir").unwrap();
+        fs.open("dir/file", true, false, false).unwrap();
+        fs.write("dir/file", None, b"contents").unwrap();
+        assert!(fs.take_modified());
+        assert!(!fs.take_modified());
+
+        let mut restored = ScratchFs::new(1024);
+        restored.restore(&fs.snapshot()).unwrap();
+        assert_eq!(restored.nodes, fs.nodes);
+        assert_eq!(restored.used, fs.used);
+    }
+}
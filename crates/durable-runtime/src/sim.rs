@@ -0,0 +1,232 @@
+//! Deterministic simulation of the tasks run by a worker.
+//!
+//! This is only available when the `simulation` feature is enabled. It is
+//! meant for reproducible integration tests of workflows that interact with
+//! each other or with the outside world.
+//!
+//! A [`Simulation`] is attached to a worker via
+//! [`WorkerBuilder::simulation`]. While it is attached:
+//! - Workflows see a virtual clock instead of the system clock. It only moves
+//!   forward when [`Simulation::advance`] is called. Tasks sleeping on a timer
+//!   wait for the virtual clock in memory instead of being suspended.
+//! - Only one task runs at a time. Whenever the running task blocks (on a timer
+//!   or while waiting for a notification) or exits, the next task to run is
+//!   picked using a random number generator seeded with the simulation's
+//!   seed.
+//! - HTTP requests made by workflows are answered by the simulation's
+//!   [`HttpHandler`] instead of being sent.
+//! - Values returned by `wasi:random` come from the seeded random number
+//!   generator.
+//!
+//! Runs with the same seed make the same decisions as long as the test itself
+//! is deterministic. In particular, it should use a single worker and run on a
+//! current-thread tokio runtime, which is the default for `#[tokio::test]` and
+//! `#[sqlx::test]`.
+//!
+//! [`WorkerBuilder::simulation`]: crate::WorkerBuilder::simulation
+
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use chrono::{DateTime, TimeDelta, Utc};
+use rand::rngs::StdRng;
+use rand::{Rng, RngCore, SeedableRng};
+use reqwest::ResponseBuilderExt;
+use tokio::sync::{oneshot, watch};
+
+/// Answers the HTTP requests made by workflows in a [`Simulation`].
+pub trait HttpHandler: Send + Sync + 'static {
+    fn handle(&self, request: &reqwest::Request) -> http::Response<Vec<u8>>;
+}
+
+impl<F> HttpHandler for F
+where
+    F: Fn(&reqwest::Request) -> http::Response<Vec<u8>> + Send + Sync + 'static,
+{
+    fn handle(&self, request: &reqwest::Request) -> http::Response<Vec<u8>> {
+        self(request)
+    }
+}
+
+/// The state of a simulated worker.
+///
+/// See the [module docs](self) for details.
+pub struct Simulation {
+    seed: u64,
+    clock: watch::Sender<DateTime<Utc>>,
+    rng: Mutex<StdRng>,
+    scheduler: Mutex<Scheduler>,
+    http: Option<Box<dyn HttpHandler>>,
+}
+
+#[derive(Default)]
+struct Scheduler {
+    running: bool,
+    waiting: Vec<oneshot::Sender<()>>,
+}
+
+impl Simulation {
+    /// Create a new simulation using `seed` for all of its random decisions.
+    ///
+    /// The virtual clock starts at 2024-01-01T00:00:00Z.
+    pub fn new(seed: u64) -> Self {
+        let start = DateTime::from_timestamp(1_704_067_200, 0).expect("start time is valid");
+
+        Self {
+            seed,
+            clock: watch::Sender::new(start),
+            rng: Mutex::new(StdRng::seed_from_u64(seed)),
+            scheduler: Mutex::new(Scheduler::default()),
+            http: None,
+        }
+    }
+
+    /// Start the virtual clock at `start` instead.
+    pub fn start_at(self, start: DateTime<Utc>) -> Self {
+        self.clock.send_replace(start);
+        self
+    }
+
+    /// Answer HTTP requests made by workflows using `handler`.
+    ///
+    /// Without a handler every request gets an empty `404 Not Found`
+    /// response.
+    pub fn http(mut self, handler: impl HttpHandler) -> Self {
+        self.http = Some(Box::new(handler));
+        self
+    }
+
+    /// The seed that the simulation was created with.
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// The current time on the virtual clock.
+    pub fn now(&self) -> DateTime<Utc> {
+        *self.clock.borrow()
+    }
+
+    /// Move the virtual clock forward by `duration`, waking up any tasks whose
+    /// timers have expired.
+    pub fn advance(&self, duration: Duration) {
+        let delta = TimeDelta::from_std(duration).unwrap_or(TimeDelta::MAX);
+
+        self.clock.send_modify(|now| {
+            *now = now
+                .checked_add_signed(delta)
+                .unwrap_or(DateTime::<Utc>::MAX_UTC);
+        });
+    }
+
+    /// The number of tasks that are currently waiting for the virtual clock.
+    pub fn sleeping(&self) -> usize {
+        self.clock.receiver_count()
+    }
+
+    /// Wait until the virtual clock reaches `deadline`.
+    pub(crate) async fn sleep_until(&self, deadline: DateTime<Utc>) {
+        let mut rx = self.clock.subscribe();
+        let _ = rx.wait_for(|now| *now >= deadline).await;
+    }
+
+    pub(crate) fn fill_random(&self, dest: &mut [u8]) {
+        self.rng.lock().unwrap().fill_bytes(dest);
+    }
+
+    /// Wait until it is the caller's turn to run.
+    ///
+    /// The next task gets to run once the returned [`Turn`] is dropped.
+    pub(crate) async fn turn(self: &Arc<Self>) -> Turn {
+        let (tx, rx) = oneshot::channel();
+
+        {
+            let mut scheduler = self.scheduler.lock().unwrap();
+            scheduler.waiting.push(tx);
+
+            if !scheduler.running {
+                self.dispatch(&mut scheduler);
+            }
+        }
+
+        let mut pending = PendingTurn {
+            sim: self,
+            rx: Some(rx),
+        };
+        let _ = pending.rx.as_mut().unwrap().await;
+        pending.rx = None;
+
+        Turn { sim: self.clone() }
+    }
+
+    /// Pick the next task to run out of the ones that are waiting.
+    fn dispatch(&self, scheduler: &mut Scheduler) {
+        scheduler.running = false;
+
+        while !scheduler.waiting.is_empty() {
+            let index = self
+                .rng
+                .lock()
+                .unwrap()
+                .random_range(0..scheduler.waiting.len());
+            let tx = scheduler.waiting.swap_remove(index);
+
+            // Tasks that stopped waiting have dropped their receiver.
+            if tx.send(()).is_ok() {
+                scheduler.running = true;
+                break;
+            }
+        }
+    }
+
+    /// Answer a request made by a workflow.
+    pub(crate) fn respond(&self, request: &reqwest::Request) -> reqwest::Response {
+        let response = match &self.http {
+            Some(handler) => handler.handle(request),
+            None => http::Response::builder()
+                .status(http::StatusCode::NOT_FOUND)
+                .body(Vec::new())
+                .expect("failed to build the response"),
+        };
+
+        // Responses need to record their URL, which can only be set through the
+        // builder.
+        let (mut parts, body) = response.into_parts();
+        let (url, ()) = http::Response::builder()
+            .url(request.url().clone())
+            .body(())
+            .expect("failed to build the response")
+            .into_parts();
+        parts.extensions.extend(url.extensions);
+
+        http::Response::from_parts(parts, body).into()
+    }
+}
+
+/// Permission for a task to run in a [`Simulation`].
+pub(crate) struct Turn {
+    sim: Arc<Simulation>,
+}
+
+impl Drop for Turn {
+    fn drop(&mut self) {
+        let mut scheduler = self.sim.scheduler.lock().unwrap();
+        self.sim.dispatch(&mut scheduler);
+    }
+}
+
+/// Hands the turn on if the task stops waiting after it was picked to run.
+struct PendingTurn<'a> {
+    sim: &'a Simulation,
+    rx: Option<oneshot::Receiver<()>>,
+}
+
+impl Drop for PendingTurn<'_> {
+    fn drop(&mut self) {
+        if let Some(mut rx) = self.rx.take() {
+            if rx.try_recv().is_ok() {
+                let mut scheduler = self.sim.scheduler.lock().unwrap();
+                self.sim.dispatch(&mut scheduler);
+            }
+        }
+    }
+}
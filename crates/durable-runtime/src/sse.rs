@@ -0,0 +1,152 @@
+//! An incremental parser for `text/event-stream` response bodies.
+//!
+//! This follows the parsing rules from the HTML spec. Chunks of the response
+//! body are fed in as they arrive and complete events are read back out.
+
+use crate::bindings::durable::core::http::SseEvent;
+
+#[derive(Default)]
+pub(crate) struct EventParser {
+    buffer: Vec<u8>,
+    event: String,
+    data: String,
+    id: Option<String>,
+}
+
+impl EventParser {
+    pub fn feed(&mut self, chunk: &[u8]) {
+        self.buffer.extend_from_slice(chunk);
+    }
+
+    /// Parse the next complete event out of the data fed in so far.
+    pub fn next_event(&mut self) -> Option<SseEvent> {
+        while let Some(line) = self.next_line() {
+            if let Some(event) = self.process_line(&String::from_utf8_lossy(&line)) {
+                return Some(event);
+            }
+        }
+
+        None
+    }
+
+    fn next_line(&mut self) -> Option<Vec<u8>> {
+        let pos = self
+            .buffer
+            .iter()
+            .position(|&byte| byte == b'\n' || byte == b'\r')?;
+        let end = match (self.buffer[pos], self.buffer.get(pos + 1)) {
+            (b'\r', Some(b'\n')) => pos + 2,
+            // We can't tell whether this is a lone `\r` until the next byte
+            // arrives.
+            (b'\r', None) => return None,
+            _ => pos + 1,
+        };
+
+        let line = self.buffer[..pos].to_vec();
+        self.buffer.drain(..end);
+        Some(line)
+    }
+
+    fn process_line(&mut self, line: &str) -> Option<SseEvent> {
+        if line.is_empty() {
+            return self.dispatch();
+        }
+
+        // Lines starting with a colon are comments.
+        if line.starts_with(':') {
+            return None;
+        }
+
+        let (field, value) = match line.split_once(':') {
+            Some((field, value)) => (field, value.strip_prefix(' ').unwrap_or(value)),
+            None => (line, ""),
+        };
+
+        match field {
+            "event" => self.event = value.to_owned(),
+            "data" => {
+                self.data.push_str(value);
+                self.data.push('\n');
+            }
+            "id" if !value.contains('\0') => self.id = Some(value.to_owned()),
+            _ => (),
+        }
+
+        None
+    }
+
+    fn dispatch(&mut self) -> Option<SseEvent> {
+        let event = std::mem::take(&mut self.event);
+        if self.data.is_empty() {
+            return None;
+        }
+
+        let mut data = std::mem::take(&mut self.data);
+        data.pop();
+
+        Some(SseEvent {
+            id: self.id.take(),
+            event: if event.is_empty() {
+                "message".to_owned()
+            } else {
+                event
+            },
+            data,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(chunks: &[&str]) -> Vec<SseEvent> {
+        let mut parser = EventParser::default();
+        let mut events = Vec::new();
+
+        for chunk in chunks {
+            parser.feed(chunk.as_bytes());
+            while let Some(event) = parser.next_event() {
+                events.push(event);
+            }
+        }
+
+        events
+    }
+
+    #[test]
+    fn parses_events() {
+        let events = parse(&[
+            ": comment\n",
+            "id: 1\ndata: first\ndata:second\n\n",
+            "event: done\ndata: {}\n\n",
+        ]);
+
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].id.as_deref(), Some("1"));
+        assert_eq!(events[0].event, "message");
+        assert_eq!(events[0].data, "first\nsecond");
+        assert_eq!(events[1].id, None);
+        assert_eq!(events[1].event, "done");
+        assert_eq!(events[1].data, "{}");
+    }
+
+    #[test]
+    fn handles_split_chunks() {
+        let events = parse(&["da", "ta: a\r", "\n\r", "\ndata: b\r\r", "event: x\n\n"]);
+
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].data, "a");
+        assert_eq!(events[1].data, "b");
+        assert_eq!(events[1].event, "message");
+    }
+
+    #[test]
+    fn ignores_events_without_data() {
+        let events = parse(&["event: ping\n\n", "data\n\n"]);
+
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].event, "message");
+        assert_eq!(events[0].data, "");
+    }
+}
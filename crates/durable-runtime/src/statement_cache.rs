@@ -0,0 +1,149 @@
+//! Bookkeeping for the prepared statements that workflow queries leave cached
+//! on the worker's database connections.
+//!
+//! sqlx caches prepared statements per connection, but the only things it
+//! exposes about that cache are the number of statements in it and a way to
+//! clear it entirely. The worker compares the size of the cache before and
+//! after each query to tell whether the query hit the cache, and evicts
+//! statements by clearing the connection's cache wholesale.
+//!
+//! Programs can ask for their cached statements to be evicted after changing
+//! the schema. Connections are shared by every program running on the worker
+//! and sqlx does not identify them, so the worker instead remembers which
+//! database backends it has cleared since the request and clears any others
+//! as the program's transactions land on them.
+
+use std::collections::{HashMap, HashSet};
+use std::time::{Duration, Instant};
+
+use metrics::Counter;
+use parking_lot::Mutex;
+use sqlx::{Connection, PgConnection};
+
+use crate::Config;
+
+/// How long to keep clearing connections for a program after it has asked for
+/// its statements to be evicted, if the pool does not limit the lifetime of
+/// its connections.
+const DEFAULT_INVALIDATION_PERIOD: Duration = Duration::from_secs(30 * 60);
+
+pub(crate) struct StatementCache {
+    max_statements: Option<usize>,
+    max_statement_len: Option<usize>,
+
+    /// How long a connection can remain in the pool. Any connection older
+    /// than an invalidation has already been closed once this has passed.
+    invalidation_period: Duration,
+
+    /// Programs whose statements have been evicted, keyed by program id.
+    invalidated: Mutex<HashMap<i64, Invalidation>>,
+
+    hits: Counter,
+    misses: Counter,
+    evictions: Counter,
+}
+
+struct Invalidation {
+    at: Instant,
+
+    /// The backend pids of the connections that have been cleared since.
+    cleared: HashSet<i32>,
+}
+
+impl StatementCache {
+    pub fn new(config: &Config, pool: &sqlx::PgPool) -> Self {
+        Self {
+            max_statements: config.max_cached_statements,
+            max_statement_len: config.max_cached_statement_len,
+            invalidation_period: pool
+                .options()
+                .get_max_lifetime()
+                .unwrap_or(DEFAULT_INVALIDATION_PERIOD),
+            invalidated: Mutex::new(HashMap::new()),
+            hits: metrics::counter!("durable.statement_cache.hits"),
+            misses: metrics::counter!("durable.statement_cache.misses"),
+            evictions: metrics::counter!("durable.statement_cache.evictions"),
+        }
+    }
+
+    /// Whether a query for `sql` should be cached on its connection.
+    pub fn is_cacheable(&self, sql: &str) -> bool {
+        self.max_statement_len.map_or(true, |max| sql.len() <= max)
+    }
+
+    /// Record whether a cached query hit the cache, given the number of
+    /// statements that were cached on `conn` before it ran.
+    ///
+    /// This also evicts the statements cached on `conn` if there are now more
+    /// of them than the worker allows.
+    pub async fn record(&self, conn: &mut PgConnection, cached: usize) -> sqlx::Result<()> {
+        let size = conn.cached_statements_size();
+        if size > cached {
+            self.misses.increment(1);
+        } else {
+            self.hits.increment(1);
+        }
+
+        match self.max_statements {
+            Some(max) if size > max => self.evict(conn).await,
+            _ => Ok(()),
+        }
+    }
+
+    /// Evict all statements cached on `conn`.
+    pub async fn evict(&self, conn: &mut PgConnection) -> sqlx::Result<()> {
+        let size = conn.cached_statements_size();
+        if size == 0 {
+            return Ok(());
+        }
+
+        conn.clear_cached_statements().await?;
+        self.evictions.increment(size as u64);
+        Ok(())
+    }
+
+    /// Evict the statements cached by `program` on every connection.
+    ///
+    /// Connections are cleared lazily by [`revalidate`](Self::revalidate) the
+    /// next time that the program uses them.
+    pub fn invalidate(&self, program: i64) {
+        let invalidation = Invalidation {
+            at: Instant::now(),
+            cleared: HashSet::new(),
+        };
+
+        self.invalidated.lock().insert(program, invalidation);
+    }
+
+    /// Clear the statements cached on `conn` if `program` has invalidated them
+    /// since the connection was last cleared.
+    pub async fn revalidate(&self, program: i64, conn: &mut PgConnection) -> sqlx::Result<()> {
+        {
+            let mut invalidated = self.invalidated.lock();
+            match invalidated.get(&program) {
+                Some(invalidation) if invalidation.at.elapsed() > self.invalidation_period => {
+                    invalidated.remove(&program);
+                    return Ok(());
+                }
+                Some(_) => (),
+                None => return Ok(()),
+            }
+        }
+
+        let pid: i32 = sqlx::query_scalar("SELECT pg_backend_pid()")
+            .persistent(false)
+            .fetch_one(&mut *conn)
+            .await?;
+
+        let stale = match self.invalidated.lock().get_mut(&program) {
+            Some(invalidation) => invalidation.cleared.insert(pid),
+            None => false,
+        };
+
+        if stale {
+            self.evict(conn).await?;
+        }
+
+        Ok(())
+    }
+}
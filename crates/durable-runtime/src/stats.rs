@@ -0,0 +1,219 @@
+//! Load signals that can be used to autoscale workers.
+
+use std::collections::HashSet;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use durable_migrate::SchemaRename;
+use sqlx::PgConnection;
+
+use crate::worker::SharedState;
+
+/// A snapshot of the tasks in a single queue.
+///
+/// Each queue holds the tasks of a single tenant. Tasks that don't belong to
+/// any tenant are in the queue with an empty name.
+#[derive(Clone, Debug)]
+pub struct QueueStats {
+    /// The name of the queue.
+    pub queue: String,
+
+    /// The number of tasks that are ready to run but have not been started.
+    ///
+    /// Tasks that were launched with a delay are only counted once they are
+    /// able to start.
+    pub pending: i64,
+
+    /// The number of tasks that are currently running on a worker.
+    pub running: i64,
+
+    /// The number of tasks that are suspended, waiting on a timer or a
+    /// notification.
+    pub suspended: i64,
+
+    /// When the task that has been pending the longest became ready to run.
+    pub oldest_pending_at: Option<DateTime<Utc>>,
+
+    /// When these stats were computed.
+    pub updated_at: DateTime<Utc>,
+}
+
+impl QueueStats {
+    /// How long the task that has been pending the longest had been waiting
+    /// when these stats were computed.
+    pub fn oldest_pending_age(&self) -> Option<Duration> {
+        let oldest = self.oldest_pending_at?;
+
+        Some((self.updated_at - oldest).to_std().unwrap_or_default())
+    }
+}
+
+/// Load signals for a worker and the cluster that it is part of.
+#[derive(Clone, Debug)]
+pub struct WorkerStats {
+    /// The number of tasks currently running on this worker.
+    pub active_tasks: usize,
+
+    /// The maximum number of tasks that this worker will run at once.
+    pub max_tasks: usize,
+
+    /// The total size, in bytes, of the linear memories of the tasks running
+    /// on this worker.
+    pub memory_usage: usize,
+
+    /// The worker's [`memory_budget`](crate::Config::memory_budget), if it
+    /// has one.
+    pub memory_budget: Option<usize>,
+
+    /// The most recent stats for each queue, as computed by the cluster
+    /// leader.
+    ///
+    /// This is empty if the leader has not computed them yet, or if it has
+    /// queue stats disabled.
+    pub queues: Vec<QueueStats>,
+}
+
+impl WorkerStats {
+    /// The fraction of this worker's task capacity that is in use.
+    pub fn utilization(&self) -> f64 {
+        if self.max_tasks == 0 {
+            return 1.0;
+        }
+
+        self.active_tasks as f64 / self.max_tasks as f64
+    }
+
+    /// The total number of pending tasks across all queues.
+    pub fn pending(&self) -> i64 {
+        self.queues.iter().map(|queue| queue.pending).sum()
+    }
+}
+
+/// Recompute the contents of the `durable.queue_stats` table.
+pub(crate) async fn refresh(
+    schema: &SchemaRename,
+    conn: &mut PgConnection,
+) -> sqlx::Result<Vec<QueueStats>> {
+    let mut tx = sqlx::Connection::begin(conn).await?;
+
+    sqlx::query!("DELETE FROM durable.queue_stats")
+        .execute(schema.on(&mut *tx))
+        .await?;
+
+    let stats = sqlx::query_as!(
+        QueueStats,
+        r#"
+        INSERT INTO durable.queue_stats(
+            queue,
+            pending,
+            running,
+            suspended,
+            oldest_pending_at
+        )
+        SELECT
+            COALESCE(tenant, '') as queue,
+            COUNT(*) FILTER (
+                WHERE state = 'ready'
+                  AND (wakeup_at IS NULL OR wakeup_at <= NOW())
+            ),
+            COUNT(*) FILTER (WHERE state = 'active'),
+            COUNT(*) FILTER (WHERE state = 'suspended'),
+            MIN(COALESCE(wakeup_at, created_at)) FILTER (
+                WHERE state = 'ready'
+                  AND (wakeup_at IS NULL OR wakeup_at <= NOW())
+            )
+          FROM durable.task
+         WHERE state IN ('ready', 'active', 'suspended')
+         GROUP BY COALESCE(tenant, '')
+        RETURNING
+            queue,
+            pending,
+            running,
+            suspended,
+            oldest_pending_at,
+            updated_at
+        "#
+    )
+    .fetch_all(schema.on(&mut *tx))
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(stats)
+}
+
+/// Read the contents of the `durable.queue_stats` table.
+pub(crate) async fn load(
+    schema: &SchemaRename,
+    pool: &sqlx::PgPool,
+) -> sqlx::Result<Vec<QueueStats>> {
+    sqlx::query_as!(
+        QueueStats,
+        "
+        SELECT
+            queue,
+            pending,
+            running,
+            suspended,
+            oldest_pending_at,
+            updated_at
+          FROM durable.queue_stats
+         ORDER BY queue ASC
+        "
+    )
+    .fetch_all(schema.on(pool))
+    .await
+}
+
+/// Export queue stats as metrics.
+///
+/// `reported` holds the queues that were exported last time. Queues that no
+/// longer have any tasks are reset to zero instead of keeping their old
+/// values.
+pub(crate) fn record_metrics(stats: &[QueueStats], reported: &mut HashSet<String>) {
+    let mut current = HashSet::with_capacity(stats.len());
+
+    for queue in stats {
+        let name = queue.queue.clone();
+        let age = queue.oldest_pending_age().unwrap_or_default();
+
+        metrics::gauge!("durable.queue.pending", "queue" => name.clone()).set(queue.pending as f64);
+        metrics::gauge!("durable.queue.running", "queue" => name.clone()).set(queue.running as f64);
+        metrics::gauge!("durable.queue.suspended", "queue" => name.clone())
+            .set(queue.suspended as f64);
+        metrics::gauge!("durable.queue.oldest_pending_age", "queue" => name.clone())
+            .set(age.as_secs_f64());
+
+        current.insert(name);
+    }
+
+    for name in reported.difference(&current) {
+        metrics::gauge!("durable.queue.pending", "queue" => name.clone()).set(0.0);
+        metrics::gauge!("durable.queue.running", "queue" => name.clone()).set(0.0);
+        metrics::gauge!("durable.queue.suspended", "queue" => name.clone()).set(0.0);
+        metrics::gauge!("durable.queue.oldest_pending_age", "queue" => name.clone()).set(0.0);
+    }
+
+    *reported = current;
+}
+
+/// Counts a task as active on this worker for as long as it is alive.
+pub(crate) struct ActiveTaskGuard {
+    shared: Arc<SharedState>,
+}
+
+impl ActiveTaskGuard {
+    pub fn enter(shared: Arc<SharedState>) -> Self {
+        shared.active_tasks.fetch_add(1, Ordering::Relaxed);
+
+        Self { shared }
+    }
+}
+
+impl Drop for ActiveTaskGuard {
+    fn drop(&mut self) {
+        self.shared.active_tasks.fetch_sub(1, Ordering::Relaxed);
+    }
+}
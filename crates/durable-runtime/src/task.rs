@@ -1,7 +1,10 @@
 use std::any::Any;
 use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::future::Future;
 use std::ops::{Deref, DerefMut};
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 use anyhow::Context;
 use chrono::{DateTime, Utc};
@@ -10,17 +13,36 @@ use futures_util::stream::BoxStream;
 use serde::de::DeserializeOwned;
 use serde::Serialize;
 use serde_json::value::RawValue;
+use sqlx::postgres::PgCopyIn;
 use sqlx::types::Json;
 use sqlx::PgConnection;
 use tokio::sync::broadcast::Receiver;
+use tokio::sync::OwnedSemaphorePermit;
 
+use crate::audit::{AuditLog, AuditRecord};
+use crate::chaos::FaultPoint;
 use crate::error::TaskStatus;
 use crate::event::Notification;
+use crate::group_commit::{self, PendingEvent};
+use crate::memory::TaskMemory;
+use crate::plugin::durable::llm::LlmUsage;
+use crate::plugin::durable::sql::limit::LimitExceeded;
+use crate::policy::TaskPolicy;
+use crate::replay::ReplayLog;
 use crate::resource::Resources;
+use crate::scratch::ScratchFs;
+use crate::sim::Turn;
 use crate::util::AsyncFnOnce;
 use crate::worker::{SharedState, TaskData};
 use crate::Config;
 
+/// The label used by older versions of the runtime for the transactions that
+/// they created for each write to stdout or stderr outside of a transaction.
+///
+/// These are skipped over when resuming a task so that tasks started before
+/// output was buffered can still run to completion.
+pub(crate) const LEGACY_OUTPUT_LABEL: &str = "wasi:io/streams.output-stream.write";
+
 pub type QueryStream<'a> =
     BoxStream<'a, Result<sqlx::Either<QueryResult, sqlx::postgres::PgRow>, sqlx::Error>>;
 
@@ -29,6 +51,24 @@ pub struct QueryResult {
     pub rows_affected: u64,
 }
 
+/// Execution statistics for a query run within a transaction.
+#[derive(Copy, Clone, Debug)]
+pub struct QueryStats {
+    /// When the query was started.
+    pub started: Instant,
+
+    /// How long it took to execute the query and fetch all of its results.
+    ///
+    /// This is `None` until all results of the query have been fetched.
+    pub duration: Option<Duration>,
+
+    /// The execution time reported by `EXPLAIN ANALYZE`.
+    pub execution_time: Option<Duration>,
+
+    /// The number of table rows scanned, as reported by `EXPLAIN ANALYZE`.
+    pub rows_scanned: Option<u64>,
+}
+
 impl From<sqlx::postgres::PgQueryResult> for QueryResult {
     fn from(value: sqlx::postgres::PgQueryResult) -> Self {
         Self {
@@ -53,14 +93,62 @@ pub struct Transaction {
     // access conn.
     stream: Option<QueryStream<'static>>,
 
+    // SAFETY NOTE:
+    // Like `stream`, this contains a reference to conn when Some. It must be cleared before it is
+    // safe to access conn.
+    copy_in: Option<PgCopyIn<&'static mut PgConnection>>,
+
     // We box this field so that it has a stable address for `stream` to refer to.
     conn: Option<Box<sqlx::Transaction<'static, sqlx::Postgres>>>,
 
+    // Held for as long as this transaction has a database connection when
+    // `Config::max_workflow_connections` is set.
+    permit: Option<OwnedSemaphorePermit>,
+
     /// The log messages emitted during this transaction.
     ///
     /// These will be committed to the database at the end of the transaction.
     pub logs: String,
 
+    /// The number of savepoints that are currently active within the database
+    /// transaction.
+    pub(crate) savepoints: u32,
+
+    /// Execution statistics for the last query run within this transaction, if
+    /// they were requested.
+    pub(crate) query_stats: Option<QueryStats>,
+
+    /// The number of SQL statements that have been run within the database
+    /// transaction.
+    statements: u32,
+
+    /// The number of rows returned so far by the current query.
+    query_rows: u64,
+
+    /// The time by which the database transaction must complete, if
+    /// `Config::max_database_transaction_duration` is set.
+    deadline: Option<Instant>,
+
+    /// Whether the database transaction is read-only.
+    read_only: bool,
+
+    /// Whether this was entered as a database transaction.
+    ///
+    /// This is recorded along with the event, even if the database transaction
+    /// itself was rolled back.
+    database: bool,
+
+    /// LLM token usage recorded during this transaction.
+    ///
+    /// This is saved to the database along with the transaction event.
+    pub(crate) llm_usage: Vec<LlmUsage>,
+
+    /// The external effects performed during this transaction, if
+    /// `Config::audit` is enabled.
+    ///
+    /// This is saved to the database along with the transaction event.
+    pub(crate) audit: AuditLog,
+
     /// Kept for convenience on some methods.
     shared: Arc<SharedState>,
 }
@@ -71,8 +159,19 @@ impl Transaction {
             label,
             index,
             stream: None,
+            copy_in: None,
             conn: None,
+            permit: None,
             logs: String::new(),
+            savepoints: 0,
+            query_stats: None,
+            statements: 0,
+            query_rows: 0,
+            deadline: None,
+            read_only: false,
+            database: false,
+            llm_usage: Vec::new(),
+            audit: AuditLog::default(),
             shared,
         }
     }
@@ -82,6 +181,52 @@ impl Transaction {
         &self.label
     }
 
+    /// Check that `count` more SQL statements can be run within this
+    /// transaction without going over the limits in the config.
+    ///
+    /// If they can, they are counted against the limit and the row count for
+    /// the current query is reset.
+    pub(crate) fn check_statement_limits(&mut self, count: usize) -> Result<(), LimitExceeded> {
+        self.check_deadline()?;
+
+        let statements = self
+            .statements
+            .saturating_add(count.try_into().unwrap_or(u32::MAX));
+        if let Some(max) = self.shared.config.max_statements_per_transaction {
+            if statements > max {
+                return Err(LimitExceeded::statements(max));
+            }
+        }
+
+        self.statements = statements;
+        self.query_rows = 0;
+        Ok(())
+    }
+
+    /// Count a row returned by the current query against
+    /// `Config::max_query_rows`.
+    pub(crate) fn check_row_limit(&mut self) -> Result<(), LimitExceeded> {
+        self.query_rows += 1;
+
+        match self.shared.config.max_query_rows {
+            Some(max) if self.query_rows > max => Err(LimitExceeded::rows(max)),
+            _ => Ok(()),
+        }
+    }
+
+    /// Check that the database transaction has not been running for longer
+    /// than `Config::max_database_transaction_duration`.
+    pub(crate) fn check_deadline(&self) -> Result<(), LimitExceeded> {
+        let max = self.shared.config.max_database_transaction_duration;
+
+        match (self.deadline, max) {
+            (Some(deadline), Some(max)) if Instant::now() >= deadline => {
+                Err(LimitExceeded::duration(max))
+            }
+            _ => Ok(()),
+        }
+    }
+
     /// Get the index of this transaction within the workflow.
     pub fn index(&self) -> i32 {
         self.index
@@ -95,8 +240,9 @@ impl Transaction {
     /// Note that calling this method will drop any in-progress query stream
     /// within the transaction.
     pub fn conn(&mut self) -> Option<&mut sqlx::Transaction<'static, sqlx::Postgres>> {
-        // Clear the current query stream before accessing `conn`.
+        // Clear the current query stream and copy before accessing `conn`.
         self.stream = None;
+        self.copy_in = None;
         self.conn.as_deref_mut()
     }
 
@@ -109,6 +255,7 @@ impl Transaction {
     /// within the transaction.
     pub fn take_conn(&mut self) -> Option<sqlx::Transaction<'static, sqlx::Postgres>> {
         self.stream = None;
+        self.copy_in = None;
         self.conn.take().map(|c| *c)
     }
 
@@ -122,6 +269,7 @@ impl Transaction {
         txn: sqlx::Transaction<'static, sqlx::Postgres>,
     ) -> anyhow::Result<()> {
         self.stream = None;
+        self.copy_in = None;
         if self.conn.is_some() {
             anyhow::bail!("transaction already had a database connection associated with it");
         }
@@ -172,6 +320,68 @@ impl Transaction {
         Ok(())
     }
 
+    /// Start a new `COPY ... FROM STDIN` operation within this database
+    /// transaction.
+    ///
+    /// Any copy operation that is already in progress will be aborted first.
+    /// Note that accessing the connection via [`conn`](Self::conn) will drop
+    /// the copy without waiting for the database to acknowledge the abort, so
+    /// callers should use [`abort_copy_in`](Self::abort_copy_in) first.
+    pub async fn start_copy_in(&mut self, statement: &str) -> anyhow::Result<sqlx::Result<()>> {
+        if let Err(e) = self
+            .abort_copy_in("another COPY operation was started")
+            .await
+        {
+            return Ok(Err(e));
+        }
+
+        let Some(conn) = self.conn() else {
+            anyhow::bail!("no database connection associated with the current transaction")
+        };
+
+        let copy = match conn.copy_in_raw(statement).await {
+            Ok(copy) => copy,
+            Err(e) => return Ok(Err(e)),
+        };
+
+        // SAFETY: We ensure that self.copy_in does not outlive the transaction it
+        //         was created from.
+        let copy: PgCopyIn<&'static mut PgConnection> = unsafe { std::mem::transmute(copy) };
+        self.copy_in = Some(copy);
+
+        Ok(Ok(()))
+    }
+
+    /// Access the `COPY ... FROM STDIN` operation in progress within this
+    /// transaction, if there is one.
+    pub fn copy_in<'t>(&'t mut self) -> Option<&'t mut PgCopyIn<&'t mut PgConnection>> {
+        let copy = self.copy_in.as_mut()?;
+
+        // SAFETY: The actual lifetime of the connection reference is 't. This is just
+        //         changing it back to match reality.
+        let copy: &'t mut PgCopyIn<&'t mut PgConnection> = unsafe { std::mem::transmute(copy) };
+
+        Some(copy)
+    }
+
+    /// Take the `COPY ... FROM STDIN` operation in progress within this
+    /// transaction, if there is one.
+    pub fn take_copy_in(&mut self) -> Option<PgCopyIn<&'_ mut PgConnection>> {
+        self.copy_in.take()
+    }
+
+    /// Cleanly abort the `COPY ... FROM STDIN` operation in progress within
+    /// this transaction, if there is one.
+    ///
+    /// This returns an error if the database reported something other than the
+    /// expected error in response to aborting the copy.
+    pub async fn abort_copy_in(&mut self, message: &str) -> sqlx::Result<()> {
+        match self.take_copy_in() {
+            Some(copy) => copy.abort(message).await,
+            None => Ok(()),
+        }
+    }
+
     /// Write some logs out to the transaction log field.
     ///
     /// This method will automatically take care of truncating the logs if they
@@ -195,6 +405,15 @@ pub struct Task {
     pub resources: Resources,
 }
 
+/// A panic reported by the workflow.
+pub(crate) struct PanicReport {
+    pub message: String,
+
+    /// The index and label of the last transaction that the workflow entered
+    /// before it panicked.
+    pub transaction: Option<(i32, Cow<'static, str>)>,
+}
+
 pub struct TaskState {
     shared: Arc<SharedState>,
     worker_id: i64,
@@ -203,16 +422,79 @@ pub struct TaskState {
 
     txn_index: i32,
     txn: Option<Transaction>,
+
+    /// The index and label of the last transaction that this task entered.
+    last_txn: Option<(i32, Cow<'static, str>)>,
+
+    /// The first panic that the workflow reported, if any.
+    panic: Option<PanicReport>,
+
+    /// A recorded event log that this task is being replayed against.
+    ///
+    /// When set, transactions are resolved against this log instead of the
+    /// database and the task is never allowed to execute a new transaction.
+    replay: Option<ReplayLog>,
+
+    /// The SQL policy that statements run by this task are checked against.
+    sql_policy: Option<TaskPolicy>,
+
+    /// The task's scratch directory, if one is configured.
+    scratch: Option<ScratchFs>,
+
+    /// Output that the workflow wrote to stdout or stderr outside of a
+    /// transaction since the last transaction completed.
+    ///
+    /// This is saved as part of the log entry for the next transaction, but
+    /// may be flushed to the database before then.
+    pending_logs: String,
+
+    /// The length of `pending_logs` when it was last flushed.
+    flushed_len: usize,
+    last_flush: Option<Instant>,
+
+    /// This task's turn to run, if the worker is running a simulation.
+    turn: Option<Turn>,
+
+    /// The linear memory used by this task.
+    memory: TaskMemory,
 }
 
 impl TaskState {
     pub(crate) fn new(shared: Arc<SharedState>, task: TaskData, worker_id: i64) -> Self {
+        let scratch = shared
+            .config
+            .scratch_dir
+            .as_ref()
+            .map(|dir| ScratchFs::new(dir.max_bytes));
+
         Self {
-            shared,
             task,
             worker_id,
             txn_index: 0,
             txn: None,
+            last_txn: None,
+            panic: None,
+            replay: None,
+            sql_policy: None,
+            scratch,
+            pending_logs: String::new(),
+            flushed_len: 0,
+            last_flush: None,
+            turn: None,
+            memory: TaskMemory::new(shared.memory.clone()),
+            shared,
+        }
+    }
+
+    /// The resource limiter that accounts for the memory used by this task.
+    pub(crate) fn memory_mut(&mut self) -> &mut TaskMemory {
+        &mut self.memory
+    }
+
+    pub(crate) fn new_replay(shared: Arc<SharedState>, task: TaskData, log: ReplayLog) -> Self {
+        Self {
+            replay: Some(log),
+            ..Self::new(shared, task, -1)
         }
     }
 }
@@ -228,6 +510,21 @@ impl TaskState {
         &self.task.name
     }
 
+    /// Get the tenant that the current task belongs to, if any.
+    pub(crate) fn tenant(&self) -> Option<&str> {
+        self.task.tenant.as_deref()
+    }
+
+    /// Get the id of the program that the current task is running.
+    pub(crate) fn program_id(&self) -> i64 {
+        self.task.wasm
+    }
+
+    /// Get the labels that the current task was launched with.
+    pub fn task_labels(&self) -> &BTreeMap<String, String> {
+        &self.task.labels
+    }
+
     /// Get the JSON data associated with the current task.
     pub fn task_data(&self) -> &RawValue {
         &self.task.data
@@ -238,6 +535,15 @@ impl TaskState {
         self.task.created_at
     }
 
+    /// Get the number of times that a worker has started running this task,
+    /// including the current one.
+    ///
+    /// Like [`worker_id`](Self::worker_id), this changes every time the task is
+    /// resumed so it should only be exposed within a transaction.
+    pub fn task_attempt(&self) -> i32 {
+        self.task.attempt
+    }
+
     /// Get the worker id that we are currently runing on.
     ///
     /// Note that it is not safe to expose this to the workflow outside of a
@@ -267,6 +573,56 @@ impl TaskState {
         &self.shared.config
     }
 
+    pub(crate) fn shared(&self) -> &Arc<SharedState> {
+        &self.shared
+    }
+
+    /// Get the index of the next transaction that this task will enter.
+    pub(crate) fn txn_index(&self) -> i32 {
+        self.txn_index
+    }
+
+    /// Access the event log that this task is being replayed against, if any.
+    /// The index and label of the last transaction that this task entered,
+    /// whether or not it has completed.
+    pub(crate) fn last_transaction(&self) -> Option<(i32, &str)> {
+        self.last_txn
+            .as_ref()
+            .map(|(index, label)| (*index, label.as_ref()))
+    }
+
+    /// The first panic reported by the workflow, if any.
+    pub(crate) fn panic(&self) -> Option<&PanicReport> {
+        self.panic.as_ref()
+    }
+
+    /// Record a panic reported by the workflow, unless it has already reported
+    /// one.
+    pub(crate) fn report_panic(&mut self, message: String) {
+        if self.panic.is_none() {
+            self.panic = Some(PanicReport {
+                message,
+                transaction: self.last_txn.clone(),
+            });
+        }
+    }
+
+    pub(crate) fn sql_policy(&self) -> Option<&TaskPolicy> {
+        self.sql_policy.as_ref()
+    }
+
+    pub(crate) fn set_sql_policy(&mut self, policy: Option<TaskPolicy>) {
+        self.sql_policy = policy;
+    }
+
+    pub(crate) fn scratch(&mut self) -> Option<&mut ScratchFs> {
+        self.scratch.as_mut()
+    }
+
+    pub(crate) fn replay_log(&self) -> Option<&ReplayLog> {
+        self.replay.as_ref()
+    }
+
     /// Access the current transaction.
     pub fn transaction(&self) -> Option<&Transaction> {
         self.txn.as_ref()
@@ -282,6 +638,8 @@ impl TaskState {
 pub struct TransactionOptions {
     label: Cow<'static, str>,
     database: bool,
+    statement_timeout: Option<Duration>,
+    read_only: bool,
 }
 
 impl TransactionOptions {
@@ -289,6 +647,8 @@ impl TransactionOptions {
         Self {
             label: label.into(),
             database: false,
+            statement_timeout: None,
+            read_only: false,
         }
     }
 
@@ -296,6 +656,23 @@ impl TransactionOptions {
         self.database = database;
         self
     }
+
+    /// Set a statement timeout that applies to every query made within the
+    /// database transaction.
+    ///
+    /// This has no effect if the transaction is not a database transaction.
+    pub fn statement_timeout(mut self, timeout: Duration) -> Self {
+        self.statement_timeout = Some(timeout);
+        self
+    }
+
+    /// Make the database transaction read-only.
+    ///
+    /// This has no effect if the transaction is not a database transaction.
+    pub fn read_only(mut self, read_only: bool) -> Self {
+        self.read_only = read_only;
+        self
+    }
 }
 
 impl TaskState {
@@ -330,10 +707,24 @@ impl TaskState {
     where
         T: DeserializeOwned,
     {
+        if self.replay.is_some() {
+            return self.enter_replay(options).map(Some);
+        }
+
         let is_db_txn = std::mem::take(&mut options.database);
+        let max_duration = self.config().max_database_transaction_duration;
+        let statement_timeout = match (options.statement_timeout.take(), max_duration) {
+            (Some(timeout), Some(max)) => Some(timeout.min(max)),
+            (timeout, max) => timeout.or(max),
+        };
+        let read_only = std::mem::take(&mut options.read_only);
         let mut tx = None;
+        let mut permit = None;
         let mut conn;
+        let mut deadline = None;
         let conn: &mut PgConnection = if is_db_txn {
+            permit = self.acquire_workflow_permit().await?;
+            deadline = max_duration.map(|max| Instant::now() + max);
             let tx = tx.insert(self.pool().begin().await?);
             tx
         } else {
@@ -345,14 +736,127 @@ impl TaskState {
             return Ok(Some(value));
         }
 
-        if let Some(tx) = tx {
+        if let Some(mut tx) = tx {
+            self.shared
+                .statement_cache
+                .revalidate(self.task.wasm, &mut tx)
+                .await?;
+
+            // These are applied after entering the transaction since they would
+            // otherwise interfere with reading the event log.
+            if read_only {
+                sqlx::query("SET TRANSACTION READ ONLY")
+                    .execute(&mut *tx)
+                    .await?;
+            }
+
+            if let Some(timeout) = statement_timeout {
+                // A statement_timeout of 0 disables the timeout entirely, so make sure
+                // that very short timeouts round up instead.
+                let millis = timeout.as_millis().max(1);
+
+                sqlx::query("SELECT set_config('statement_timeout', $1, true)")
+                    .bind(millis.to_string())
+                    .execute(&mut *tx)
+                    .await?;
+            }
+
+            if let Some(Json(context)) = &self.task.sql_context {
+                if let Some(key) = context.keys().find(|key| !key.contains('.')) {
+                    anyhow::bail!(
+                        "invalid SQL context parameter {key:?}: only custom parameters (e.g. \
+                         `app.user_id`) may be set by a task"
+                    );
+                }
+
+                sqlx::query("SELECT set_config(key, value, true) FROM jsonb_each_text($1)")
+                    .bind(Json(context))
+                    .execute(&mut *tx)
+                    .await?;
+            }
+
             let txn = self.transaction_mut().unwrap();
+            txn.read_only = read_only;
+            txn.deadline = deadline;
+            txn.database = true;
             txn.conn = Some(Box::new(tx));
+            txn.permit = permit;
         }
 
+        self.inject_fault(FaultPoint::TransactionEnter).await?;
+
         Ok(None)
     }
 
+    /// Inject a fault at `point` if the worker was built with fault injection.
+    async fn inject_fault(&mut self, point: FaultPoint) -> anyhow::Result<()> {
+        match self.shared.chaos.clone() {
+            Some(chaos) => chaos.trigger(point, self).await,
+            None => Ok(()),
+        }
+    }
+
+    /// Whether the worker is running a simulation.
+    pub(crate) fn is_simulated(&self) -> bool {
+        self.shared.simulation.is_some()
+    }
+
+    /// The current time, as seen by the workflow.
+    pub(crate) fn now(&self) -> DateTime<Utc> {
+        self.shared.now()
+    }
+
+    /// Wait until this task is scheduled to run, if the worker is running a
+    /// simulation.
+    pub(crate) async fn wait_for_turn(&mut self) {
+        if let Some(sim) = &self.shared.simulation {
+            self.turn = Some(sim.turn().await);
+        }
+    }
+
+    /// Run `future`, letting other tasks run in the meantime if the worker is
+    /// running a simulation.
+    ///
+    /// This should wrap anything that waits on other tasks or on time passing.
+    pub(crate) async fn blocking<F: Future>(&mut self, future: F) -> F::Output {
+        if self.shared.simulation.is_none() {
+            return future.await;
+        }
+
+        self.turn = None;
+        let output = future.await;
+        self.wait_for_turn().await;
+        output
+    }
+
+    /// Wait until [`now`](TaskState::now) reaches `deadline`.
+    pub(crate) async fn sleep_until(&mut self, deadline: DateTime<Utc>) {
+        let shared = self.shared.clone();
+        self.blocking(shared.sleep_until(deadline)).await
+    }
+
+    /// Wait until this task is allowed to hold a database connection for a
+    /// workflow database transaction.
+    ///
+    /// Returns `None` if [`Config::max_workflow_connections`] is not set.
+    async fn acquire_workflow_permit(&self) -> anyhow::Result<Option<OwnedSemaphorePermit>> {
+        let Some(sema) = &self.shared.workflow_sema else {
+            return Ok(None);
+        };
+
+        let metrics = &self.shared.metrics;
+        let start = Instant::now();
+
+        metrics.workflow_connections_waiting.increment(1.0);
+        let permit = sema.clone().acquire_owned().await;
+        metrics.workflow_connections_waiting.decrement(1.0);
+        metrics.workflow_connection_wait.record(start.elapsed());
+
+        Ok(Some(
+            permit.context("the workflow connection semaphore was closed")?,
+        ))
+    }
+
     async fn enter_impl<T>(
         &mut self,
         options: TransactionOptions,
@@ -373,20 +877,31 @@ impl TaskState {
             );
         }
 
-        let record = sqlx::query!(
-            r#"
-            SELECT
-                label,
-                value as "value: Json<Box<RawValue>>"
-             FROM durable.event
-            WHERE task_id = $1
-              AND index = $2
-            "#,
-            self.task_id(),
-            self.txn_index
-        )
-        .fetch_optional(&mut *conn)
-        .await?;
+        let record = loop {
+            let record = sqlx::query!(
+                r#"
+                SELECT
+                    label,
+                    value as "value: Json<Box<RawValue>>",
+                    scratch
+                 FROM durable.event
+                WHERE task_id = $1
+                  AND index = $2
+                "#,
+                self.task_id(),
+                self.txn_index
+            )
+            .fetch_optional(self.shared.schema.on(&mut *conn))
+            .await?;
+
+            match record {
+                Some(record) if record.label == LEGACY_OUTPUT_LABEL => {
+                    self.txn_index += 1;
+                    self.clear_pending_logs();
+                }
+                record => break record,
+            }
+        };
 
         if let Some(record) = record {
             if record.label != options.label {
@@ -399,6 +914,20 @@ impl TaskState {
                 );
             }
 
+            if let (Some(scratch), Some(snapshot)) = (&mut self.scratch, &record.scratch) {
+                scratch.restore(snapshot).with_context(|| {
+                    format!(
+                        "failed to restore the scratch directory snapshot stored at index {}",
+                        self.txn_index
+                    )
+                })?;
+            }
+
+            // Any output since the last transaction was already saved when this event
+            // was recorded.
+            self.clear_pending_logs();
+
+            self.last_txn = Some((self.txn_index, options.label));
             self.txn_index += 1;
             let value: T = serde_json::from_str(record.value.get()).with_context(|| {
                 format!(
@@ -430,6 +959,12 @@ impl TaskState {
                 "entering transaction"
             );
 
+            // Only changes made within the transaction need to be snapshotted.
+            if let Some(scratch) = &mut self.scratch {
+                scratch.take_modified();
+            }
+
+            self.last_txn = Some((self.txn_index, options.label.clone()));
             self.txn = Some(Transaction::new(
                 options.label,
                 self.txn_index,
@@ -439,6 +974,42 @@ impl TaskState {
         }
     }
 
+    /// Enter a transaction while replaying a recorded event log.
+    ///
+    /// Since replays never execute new transactions, this either returns the
+    /// recorded data for the transaction or an error describing why the
+    /// replay cannot continue.
+    fn enter_replay<T>(&mut self, options: TransactionOptions) -> anyhow::Result<T>
+    where
+        T: DeserializeOwned,
+    {
+        if let Some(txn) = self.transaction() {
+            anyhow::bail!(
+                "attempted to start transaction {:?} while already within transaction {:?}",
+                options.label,
+                txn.label
+            );
+        }
+
+        // Replays never save any output.
+        self.clear_pending_logs();
+
+        let log = self.replay.as_ref().expect("task is not being replayed");
+        let index = log.skip_legacy_output(self.txn_index);
+        let event = log.get(index, &options.label)?;
+
+        self.txn_index = index + 1;
+        self.last_txn = Some((index, options.label));
+        let value: T = serde_json::from_str(event.value.get()).with_context(|| {
+            format!(
+                "internal error: failed to deserialize internal event data of type `{}`",
+                std::any::type_name::<T>()
+            )
+        })?;
+
+        Ok(value)
+    }
+
     /// Exit the current transaction.
     ///
     /// # Errors
@@ -450,18 +1021,40 @@ impl TaskState {
     where
         T: ?Sized + Serialize,
     {
+        self.inject_fault(FaultPoint::TransactionExit).await?;
+
+        let group_commit = self.shared.group_commit.is_some();
         let txn = match self.transaction_mut() {
             Some(txn) => txn,
             None => anyhow::bail!("attempted to exit a transaction without having entered one"),
         };
 
+        if group_commit && txn.conn.is_none() && txn.llm_usage.is_empty() && txn.audit.is_empty() {
+            return self.exit_grouped(data).await;
+        }
+
         // If the transaction has a database connection then we need to use that,
         // otherwise grab a new connection from the pool. exit_impl doesn't require that
         // we be in a database transaction, so there is no need to enter one if we are
         // not already in one.
         let mut tx = None;
         let mut conn;
+
+        // A COPY that was never finished leaves the database transaction in an aborted
+        // state, which the check below will then roll back.
+        let _ = txn
+            .abort_copy_in("transaction exited without finishing the COPY operation")
+            .await;
+
+        let read_only = txn.read_only;
         let conn: &mut PgConnection = match txn.take_conn() {
+            // A read-only transaction cannot record the event itself. It also cannot
+            // have made any changes, so there is nothing to lose by rolling it back.
+            Some(txn) if read_only => {
+                txn.rollback().await?;
+                conn = self.shared.pool.acquire().await?;
+                &mut conn
+            }
             Some(mut txn) => {
                 // Check if the transaction is not in an aborted state by running a query
                 if sqlx::query("SELECT 1").execute(&mut *txn).await.is_ok() {
@@ -494,10 +1087,38 @@ impl TaskState {
         Ok(())
     }
 
-    async fn exit_impl<T>(&mut self, data: &T, conn: &mut PgConnection) -> anyhow::Result<()>
+    /// Exit the current transaction by handing its event off to be written
+    /// as part of a group commit.
+    async fn exit_grouped<T>(&mut self, data: &T) -> anyhow::Result<()>
     where
         T: ?Sized + Serialize,
     {
+        let (txn, message, scratch) = self.take_exiting_transaction()?;
+
+        let event = PendingEvent {
+            task_id: self.task_id(),
+            worker_id: self.worker_id,
+            index: self.txn_index,
+            label: txn.label.to_string(),
+            value: serde_json::to_string(data)?,
+            message,
+            scratch,
+            is_db: txn.database,
+        };
+
+        let running_on = match group_commit::submit(&self.shared, event).await? {
+            true => Some(self.worker_id),
+            false => None,
+        };
+
+        self.finish_exit(&txn, running_on)
+    }
+
+    /// Take the transaction that is being exited, along with the logs and
+    /// scratch directory snapshot that should be saved with its event.
+    fn take_exiting_transaction(
+        &mut self,
+    ) -> anyhow::Result<(Transaction, Option<String>, Option<Vec<u8>>)> {
         let mut txn = match self.txn.take() {
             Some(txn) => txn,
             None => anyhow::bail!("attempted to exit a transaction without having entered one"),
@@ -507,18 +1128,56 @@ impl TaskState {
             anyhow::bail!("database transactions are not supported by this operation");
         }
 
-        let logs;
-        let message = if txn.logs.is_empty() {
-            None
-        } else {
-            logs = std::mem::take(&mut txn.logs);
+        if self.config().debug_emit_task_logs {
+            eprint!("{}", txn.logs);
+        }
 
-            if self.config().debug_emit_task_logs {
-                eprint!("{logs}");
-            }
+        let mut logs = std::mem::take(&mut self.pending_logs);
+        logs.push_str(&txn.logs);
+        let message = if logs.is_empty() { None } else { Some(logs) };
 
-            Some(logs)
-        };
+        let scratch = self
+            .scratch
+            .as_mut()
+            .and_then(|scratch| scratch.take_modified().then(|| scratch.snapshot()));
+
+        Ok((txn, message, scratch))
+    }
+
+    /// Finish exiting a transaction once its event has been written.
+    fn finish_exit(&mut self, txn: &Transaction, running_on: Option<i64>) -> anyhow::Result<()> {
+        tracing::trace!(
+            target: "durable_runtime::task::transaction",
+            index = self.txn_index,
+            label = &*txn.label,
+            "exiting transaction"
+        );
+
+        if running_on != Some(self.worker_id) {
+            // This task is no longer running on the current worker. Don't commit anything,
+            // and abort the task.
+            return Err(anyhow::Error::new(TaskStatus::NotScheduledOnWorker));
+        }
+
+        self.txn_index += 1;
+        self.clear_pending_logs();
+
+        Ok(())
+    }
+
+    async fn exit_impl<T>(&mut self, data: &T, conn: &mut PgConnection) -> anyhow::Result<()>
+    where
+        T: ?Sized + Serialize,
+    {
+        let (txn, message, scratch) = self.take_exiting_transaction()?;
+
+        if !txn.llm_usage.is_empty() {
+            self.save_llm_usage(&txn.llm_usage, &mut *conn).await?;
+        }
+
+        if !txn.audit.is_empty() {
+            self.save_audit_log(txn.audit.records(), &mut *conn).await?;
+        }
 
         // This complicated query here does a few different things:
         // 1. It inserts an event into the event table,
@@ -540,12 +1199,14 @@ impl TaskState {
                     LIMIT 1
                 ),
                 insert_event AS (
-                    INSERT INTO durable.event(task_id, index, label, value)
+                    INSERT INTO durable.event(task_id, index, label, value, scratch, is_db)
                     SELECT
                         id as task_id,
                         $2 as index,
                         $3 as label,
-                        $4 as value
+                        $4 as value,
+                        $7 as scratch,
+                        $8 as is_db
                     FROM current_task
                     RETURNING task_id
                 ),
@@ -555,6 +1216,8 @@ impl TaskState {
                     FROM (VALUES ($1, $2, $5)) as t(task_id, index, message)
                     JOIN current_task task ON task.id = task_id
                     WHERE message IS NOT NULL
+                    ON CONFLICT ON CONSTRAINT log_pkey DO UPDATE
+                    SET message = EXCLUDED.message
                     RETURNING task_id
                 )
             SELECT running_on
@@ -567,26 +1230,189 @@ impl TaskState {
             &*txn.label,
             Json(data) as Json<&T>,
             message,
-            self.worker_id
+            self.worker_id,
+            scratch,
+            txn.database
         )
-        .fetch_one(&mut *conn)
+        .fetch_one(self.shared.schema.on(&mut *conn))
         .await?
         .running_on;
 
-        tracing::trace!(
-            target: "durable_runtime::task::transaction",
-            index = self.txn_index,
-            label = &*txn.label,
-            "exiting transaction"
-        );
+        self.finish_exit(&txn, running_on)
+    }
 
-        if running_on != Some(self.worker_id) {
-            // This task is no longer running on the current worker. Don't commit anything,
-            // and abort the task.
-            return Err(anyhow::Error::new(TaskStatus::NotScheduledOnWorker));
+    async fn save_llm_usage(
+        &self,
+        usage: &[LlmUsage],
+        conn: &mut PgConnection,
+    ) -> anyhow::Result<()> {
+        let seq: Vec<i32> = (0..usage.len() as i32).collect();
+        let providers: Vec<&str> = usage.iter().map(|u| &*u.provider).collect();
+        let models: Vec<&str> = usage.iter().map(|u| &*u.model).collect();
+        let prompt: Vec<i64> = usage.iter().map(|u| u.prompt_tokens.into()).collect();
+        let completion: Vec<i64> = usage.iter().map(|u| u.completion_tokens.into()).collect();
+
+        // If the transaction is retried then it will record the same usage under
+        // the same keys, so anything already there can be left alone.
+        sqlx::query!(
+            "
+            INSERT INTO durable.llm_usage(
+                task_id, index, seq, provider, model, prompt_tokens, completion_tokens
+            )
+            SELECT $1, $2, u.*
+              FROM UNNEST($3::int4[], $4::text[], $5::text[], $6::int8[], $7::int8[])
+                AS u(seq, provider, model, prompt_tokens, completion_tokens)
+             WHERE EXISTS(
+                SELECT 1 FROM durable.task WHERE id = $1 AND running_on = $8
+             )
+            ON CONFLICT DO NOTHING
+            ",
+            self.task_id(),
+            self.txn_index,
+            &seq,
+            &providers as &[&str],
+            &models as &[&str],
+            &prompt,
+            &completion,
+            self.worker_id
+        )
+        .execute(self.shared.schema.on(&mut *conn))
+        .await?;
+
+        Ok(())
+    }
+
+    async fn save_audit_log(
+        &self,
+        records: &[AuditRecord],
+        conn: &mut PgConnection,
+    ) -> anyhow::Result<()> {
+        let seq: Vec<i32> = (0..records.len() as i32).collect();
+        let kinds: Vec<&str> = records.iter().map(|r| r.kind).collect();
+        let operations: Vec<&str> = records.iter().map(|r| &*r.operation).collect();
+        let targets: Vec<Option<&str>> = records.iter().map(|r| r.target.as_deref()).collect();
+        let tables: Vec<String> = records.iter().map(|r| r.tables.join(",")).collect();
+        let rows: Vec<Option<i64>> = records.iter().map(|r| r.rows_affected).collect();
+        let status: Vec<Option<i32>> = records.iter().map(|r| r.status).collect();
+
+        // Postgres can't unnest a jagged array, so the tables for each record are
+        // passed in as a comma-separated list instead.
+        sqlx::query!(
+            "
+            INSERT INTO durable.audit_log(
+                task_id, index, seq, kind, operation, target, tables, rows_affected, status
+            )
+            SELECT
+                $1, $2, a.seq, a.kind, a.operation, a.target,
+                string_to_array(NULLIF(a.tables, ''), ','),
+                a.rows_affected, a.status
+              FROM UNNEST(
+                $3::int4[], $4::text[], $5::text[], $6::text[], $7::text[], $8::int8[], $9::int4[]
+              ) AS a(seq, kind, operation, target, tables, rows_affected, status)
+             WHERE EXISTS(
+                SELECT 1 FROM durable.task WHERE id = $1 AND running_on = $10
+             )
+            ON CONFLICT DO NOTHING
+            ",
+            self.task_id(),
+            self.txn_index,
+            &seq,
+            &kinds as &[&str],
+            &operations as &[&str],
+            &targets as &[Option<&str>],
+            &tables,
+            &rows as &[Option<i64>],
+            &status as &[Option<i32>],
+            self.worker_id
+        )
+        .execute(self.shared.schema.on(&mut *conn))
+        .await?;
+
+        Ok(())
+    }
+
+    fn clear_pending_logs(&mut self) {
+        self.pending_logs.clear();
+        self.flushed_len = 0;
+    }
+
+    /// Write output from the workflow to the task logs.
+    ///
+    /// Within a transaction this is saved along with the transaction.
+    /// Otherwise, it is buffered until the next transaction completes or
+    /// [`flush_logs`](Self::flush_logs) is called. Either way, the output
+    /// saved for a single transaction is limited to
+    /// [`Config::max_log_bytes_per_transaction`].
+    pub fn write_output(&mut self, message: &str) {
+        if let Some(txn) = self.transaction_mut() {
+            txn.write_logs(message);
+            return;
         }
 
-        self.txn_index += 1;
+        let remaining = self
+            .config()
+            .max_log_bytes_per_transaction
+            .saturating_sub(self.pending_logs.len());
+        let truncated = truncate_to_prev_char_boundary(message, remaining);
+
+        if self.config().debug_emit_task_logs {
+            eprint!("{truncated}");
+        }
+
+        self.pending_logs.push_str(truncated);
+    }
+
+    /// Flush buffered output to the database if it has been at least
+    /// [`Config::log_flush_interval`] since it was last flushed.
+    pub async fn maybe_flush_logs(&mut self) -> anyhow::Result<()> {
+        let interval = self.config().log_flush_interval;
+        if self
+            .last_flush
+            .is_some_and(|last| last.elapsed() < interval)
+        {
+            return Ok(());
+        }
+
+        self.flush_logs().await
+    }
+
+    /// Save all output written since the last transaction completed to the
+    /// database, including any written within the current transaction.
+    ///
+    /// The output is saved under the index of the next transaction, so it will
+    /// be replaced once that transaction completes. Output that gets written
+    /// again when the workflow is resumed also replaces what was saved here
+    /// instead of being duplicated.
+    pub async fn flush_logs(&mut self) -> anyhow::Result<()> {
+        let mut logs = self.pending_logs.clone();
+        if let Some(txn) = self.transaction() {
+            logs.push_str(&txn.logs);
+        }
+
+        if self.replay.is_some() || logs.len() <= self.flushed_len {
+            return Ok(());
+        }
+
+        sqlx::query!(
+            "
+            INSERT INTO durable.log(task_id, index, message)
+            SELECT id, $2, $3
+              FROM durable.task
+             WHERE id = $1
+               AND running_on = $4
+            ON CONFLICT ON CONSTRAINT log_pkey DO UPDATE
+            SET message = EXCLUDED.message
+            ",
+            self.task_id(),
+            self.txn_index,
+            logs,
+            self.worker_id
+        )
+        .execute(self.shared.schema.on(self.pool()))
+        .await?;
+
+        self.flushed_len = logs.len();
+        self.last_flush = Some(Instant::now());
 
         Ok(())
     }
@@ -613,7 +1439,7 @@ impl TaskState {
             self.task_id(),
             timeout
         )
-        .execute(&mut *conn)
+        .execute(self.shared.schema.on(&mut *conn))
         .await?;
 
         Ok(TaskStatus::Suspend)
@@ -638,6 +1464,10 @@ impl TaskState {
             return func(self);
         }
 
+        if self.replay.is_some() {
+            return self.enter_replay(options);
+        }
+
         let mut conn = self.pool().acquire().await?;
         if let Some(data) = self.enter_impl(options, &mut conn).await? {
             return Ok(data);
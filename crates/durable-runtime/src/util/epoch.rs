@@ -0,0 +1,39 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Increments the epoch of a wasmtime engine at a fixed interval.
+///
+/// This runs on its own thread so that it keeps ticking even if every thread
+/// in the tokio runtime is busy running workflows. The thread exits once the
+/// ticker is dropped.
+pub(crate) struct EpochTicker {
+    stop: Arc<AtomicBool>,
+}
+
+impl EpochTicker {
+    pub fn spawn(engine: &wasmtime::Engine, interval: Duration) -> std::io::Result<Self> {
+        let stop = Arc::new(AtomicBool::new(false));
+        let engine = engine.clone();
+
+        std::thread::Builder::new()
+            .name("durable-epoch".into())
+            .spawn({
+                let stop = stop.clone();
+                move || {
+                    while !stop.load(Ordering::Relaxed) {
+                        std::thread::sleep(interval);
+                        engine.increment_epoch();
+                    }
+                }
+            })?;
+
+        Ok(Self { stop })
+    }
+}
+
+impl Drop for EpochTicker {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+    }
+}
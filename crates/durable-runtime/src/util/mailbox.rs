@@ -33,6 +33,8 @@ impl<T> Mailbox<T> {
 
         // Ensure that _old gets dropped after we release the lock.
         drop(data);
+
+        self.notify.notify_waiters();
     }
 }
 
@@ -1,10 +1,13 @@
 mod asyncfn;
+mod epoch;
 mod interval;
 mod mailbox;
 mod metrics;
 mod serde;
+pub(crate) mod sigv4;
 
 pub use self::asyncfn::AsyncFnOnce;
+pub(crate) use self::epoch::EpochTicker;
 pub(crate) use self::interval::IntoPgInterval;
 pub(crate) use self::mailbox::Mailbox;
 pub(crate) use self::metrics::MetricSpan;
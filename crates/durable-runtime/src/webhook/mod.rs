@@ -0,0 +1,49 @@
+//! An HTTP server that launches or notifies tasks when it receives webhooks.
+//!
+//! See [`WebhookConfig`] for details on how requests are handled.
+
+use std::sync::Arc;
+
+use crate::config::WebhookConfig;
+use crate::worker::SharedState;
+
+#[cfg(feature = "webhooks")]
+mod server;
+
+/// The webhook server configured on the worker.
+///
+/// This is bound when the worker is built so that configuration errors show
+/// up early, and then served each time the worker is run.
+pub(crate) enum WebhookServer {
+    #[cfg(feature = "webhooks")]
+    Http(server::Server),
+}
+
+impl WebhookServer {
+    pub(crate) fn bind(config: &WebhookConfig) -> anyhow::Result<Self> {
+        cfg_if::cfg_if! {
+            if #[cfg(feature = "webhooks")] {
+                server::Server::bind(config).map(Self::Http)
+            } else {
+                let _ = config;
+                anyhow::bail!("the worker was built without the `webhooks` feature")
+            }
+        }
+    }
+
+    pub(crate) fn try_clone(&self) -> anyhow::Result<Self> {
+        match *self {
+            #[cfg(feature = "webhooks")]
+            Self::Http(ref server) => server.try_clone().map(Self::Http),
+        }
+    }
+
+    /// Serve requests until the worker is shut down.
+    #[allow(unused_variables)]
+    pub(crate) async fn serve(self, shared: Arc<SharedState>) -> anyhow::Result<()> {
+        match self {
+            #[cfg(feature = "webhooks")]
+            Self::Http(server) => server.serve(shared).await,
+        }
+    }
+}
@@ -0,0 +1,287 @@
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use anyhow::Context;
+use axum::body::Bytes;
+use axum::extract::{DefaultBodyLimit, RawPathParams, State};
+use axum::http::{HeaderMap, StatusCode};
+use axum::routing::post;
+use axum::Router;
+use hmac::{Hmac, Mac};
+use serde_json::value::RawValue;
+use sha2::Sha256;
+use sqlx::types::Json;
+
+use crate::config::{NotifyMapping, WebhookAction, WebhookConfig, WebhookRoute, WebhookSignature};
+use crate::ingest::{task_data, Launcher, Message};
+use crate::worker::SharedState;
+
+type HandlerResult = Result<StatusCode, (StatusCode, String)>;
+
+pub(crate) struct Server {
+    listener: std::net::TcpListener,
+    router: Router<Arc<SharedState>>,
+}
+
+impl Server {
+    pub(super) fn bind(config: &WebhookConfig) -> anyhow::Result<Self> {
+        let mut router = Router::new();
+        let mut paths = HashSet::new();
+
+        for route in &config.routes {
+            if !route.path.starts_with('/') {
+                anyhow::bail!(
+                    "the path of webhook route `{}` must start with `/`",
+                    route.name
+                );
+            }
+
+            if !paths.insert(route.path.as_str()) {
+                anyhow::bail!("multiple webhook routes have the path `{}`", route.path);
+            }
+
+            if matches!(route.action, WebhookAction::Notify(_))
+                && !route.path.split('/').any(|segment| segment == ":task_id")
+            {
+                anyhow::bail!(
+                    "the path of webhook route `{}` must contain a `:task_id` segment",
+                    route.name
+                );
+            }
+
+            let handler = Arc::new(Handler::new(route));
+            router = router.route(
+                &route.path,
+                post(
+                    move |State(shared): State<Arc<SharedState>>,
+                          params: RawPathParams,
+                          headers: HeaderMap,
+                          body: Bytes| {
+                        let handler = handler.clone();
+                        async move { handler.handle(&shared, params, headers, body).await }
+                    },
+                ),
+            );
+        }
+
+        let router = router.layer(DefaultBodyLimit::max(config.max_body_size));
+
+        let listener = std::net::TcpListener::bind(config.bind)
+            .with_context(|| format!("failed to bind the webhook server to {}", config.bind))?;
+        listener.set_nonblocking(true)?;
+
+        tracing::info!("webhook server listening on {}", listener.local_addr()?);
+
+        Ok(Self { listener, router })
+    }
+
+    pub(super) fn try_clone(&self) -> anyhow::Result<Self> {
+        Ok(Self {
+            listener: self.listener.try_clone()?,
+            router: self.router.clone(),
+        })
+    }
+
+    pub(super) async fn serve(self, shared: Arc<SharedState>) -> anyhow::Result<()> {
+        let listener = tokio::net::TcpListener::from_std(self.listener)?;
+        let shutdown = shared.shutdown.clone();
+
+        axum::serve(listener, self.router.with_state(shared))
+            .with_graceful_shutdown(async move { shutdown.wait().await })
+            .await
+            .context("the webhook server exited with an error")
+    }
+}
+
+enum Action {
+    Launch(Launcher),
+    Notify(NotifyMapping),
+}
+
+struct Handler {
+    name: String,
+    action: Action,
+    signature: Option<WebhookSignature>,
+}
+
+impl Handler {
+    fn new(route: &WebhookRoute) -> Self {
+        let action = match &route.action {
+            WebhookAction::Launch(mapping) => {
+                Action::Launch(Launcher::new(route.name.clone(), mapping.clone()))
+            }
+            WebhookAction::Notify(mapping) => Action::Notify(mapping.clone()),
+        };
+
+        Self {
+            name: route.name.clone(),
+            action,
+            signature: route.signature.clone(),
+        }
+    }
+
+    async fn handle(
+        &self,
+        shared: &SharedState,
+        params: RawPathParams,
+        headers: HeaderMap,
+        body: Bytes,
+    ) -> HandlerResult {
+        if let Some(signature) = &self.signature {
+            if !verify_signature(signature, &headers, &body) {
+                return Err((
+                    StatusCode::UNAUTHORIZED,
+                    "the request signature is missing or invalid".into(),
+                ));
+            }
+        }
+
+        // Requests have no identity of their own so, unless the route uses a dedupe
+        // header, every request is treated as a new message.
+        let key = format!("{:032x}", rand::random::<u128>());
+        let mut message = Message::new(key, body.to_vec());
+        for (name, value) in &headers {
+            message = message.header(name.as_str(), value.as_bytes());
+        }
+
+        let result = match &self.action {
+            Action::Launch(launcher) => self.launch(shared, launcher, &message).await,
+            Action::Notify(mapping) => self.notify(shared, mapping, &params, &message).await,
+        };
+
+        result.map_err(|e| {
+            tracing::error!("failed to handle request to webhook `{}`: {e:#}", self.name);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "an internal error occurred".into(),
+            )
+        })?
+    }
+
+    async fn launch(
+        &self,
+        shared: &SharedState,
+        launcher: &Launcher,
+        message: &Message,
+    ) -> anyhow::Result<HandlerResult> {
+        let key = message.dedupe_key(launcher.mapping());
+        let data = match task_data(&self.name, launcher.mapping().data, message, &key) {
+            Ok(data) => data,
+            Err(e) => return Ok(Err(bad_request(e))),
+        };
+
+        launcher
+            .launch_tasks(shared, vec![key], vec![Json(data)])
+            .await?;
+
+        Ok(Ok(StatusCode::ACCEPTED))
+    }
+
+    async fn notify(
+        &self,
+        shared: &SharedState,
+        mapping: &NotifyMapping,
+        params: &RawPathParams,
+        message: &Message,
+    ) -> anyhow::Result<HandlerResult> {
+        let task_id = params
+            .iter()
+            .find(|(name, _)| *name == "task_id")
+            .and_then(|(_, value)| value.parse::<i64>().ok());
+        let Some(task_id) = task_id else {
+            return Ok(Err(not_found()));
+        };
+
+        let data = match task_data(&self.name, mapping.data, message, &message.key) {
+            Ok(data) => data,
+            Err(e) => return Ok(Err(bad_request(e))),
+        };
+
+        let result = sqlx::query!(
+            "
+            INSERT INTO durable.notification(task_id, event, data)
+            SELECT id, $2::text, $3::jsonb
+             FROM durable.task
+            WHERE id = $1
+              AND state NOT IN ('complete', 'failed')
+            ",
+            task_id,
+            &mapping.event,
+            Json(data) as Json<Box<RawValue>>
+        )
+        .execute(shared.schema.on(&shared.pool))
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Ok(Err(not_found()));
+        }
+
+        Ok(Ok(StatusCode::ACCEPTED))
+    }
+}
+
+fn bad_request(error: serde_json::Error) -> (StatusCode, String) {
+    (
+        StatusCode::BAD_REQUEST,
+        format!("the request body is invalid: {error}"),
+    )
+}
+
+fn not_found() -> (StatusCode, String) {
+    (
+        StatusCode::NOT_FOUND,
+        "the task does not exist or has already completed".into(),
+    )
+}
+
+/// Check that the HMAC-SHA256 signature of `body` matches the one in the
+/// request headers.
+fn verify_signature(signature: &WebhookSignature, headers: &HeaderMap, body: &[u8]) -> bool {
+    let Some(value) = headers
+        .get(signature.header.as_str())
+        .and_then(|value| value.to_str().ok())
+    else {
+        return false;
+    };
+
+    let value = value.trim();
+    let value = value.strip_prefix("sha256=").unwrap_or(value);
+    let Ok(expected) = hex::decode(value) else {
+        return false;
+    };
+
+    let mut mac = Hmac::<Sha256>::new_from_slice(signature.secret.as_bytes())
+        .expect("HMAC accepts keys of any length");
+    mac.update(body);
+    mac.verify_slice(&expected).is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn signature_verification() {
+        // The example from the GitHub webhook documentation.
+        let signature =
+            WebhookSignature::new("It's a Secret to Everybody").header("x-hub-signature-256");
+        let body = b"Hello, World!";
+        let expected = "757107ea0eb2509fc211221cce984b8a37570b6d7586c22c46f4379c8b043e17";
+
+        let mut headers = HeaderMap::new();
+        assert!(!verify_signature(&signature, &headers, body));
+
+        headers.insert("x-hub-signature-256", expected.parse().unwrap());
+        assert!(verify_signature(&signature, &headers, body));
+
+        headers.insert(
+            "x-hub-signature-256",
+            format!("sha256={expected}").parse().unwrap(),
+        );
+        assert!(verify_signature(&signature, &headers, body));
+        assert!(!verify_signature(&signature, &headers, b"Hello, World?"));
+
+        headers.insert("x-hub-signature-256", "sha256=nothex".parse().unwrap());
+        assert!(!verify_signature(&signature, &headers, body));
+    }
+}
@@ -1,5 +1,7 @@
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
 use std::panic::AssertUnwindSafe;
-use std::sync::Arc;
+use std::sync::atomic::{AtomicI64, AtomicUsize, Ordering};
+use std::sync::{Arc, OnceLock};
 use std::time::Duration;
 
 use anyhow::Context;
@@ -7,37 +9,75 @@ use async_trait::async_trait;
 use cache_compute::Cached;
 use cfg_if::cfg_if;
 use chrono::{DateTime, Utc};
+use durable_migrate::SchemaRename;
 use futures_concurrency::future::Join;
-use futures_util::FutureExt;
+use futures_util::{FutureExt, Stream};
 use metrics::{Counter, Gauge, Histogram};
 use rand::Rng;
 use serde_json::value::RawValue;
 use sqlx::postgres::PgNotification;
 use sqlx::types::Json;
-use tokio::sync::{broadcast, mpsc, Mutex, Notify, Semaphore};
+use tokio::sync::{broadcast, mpsc, watch, Mutex, Notify, OwnedSemaphorePermit, Semaphore};
 use tokio::task::JoinSet;
 use tokio::time::{Instant, MissedTickBehavior};
 use tracing::Instrument;
-use wasmtime::component::Component;
+use wasmtime::component::{Component, InstancePre};
 
+use crate::api::ApiServer;
+use crate::archive::{self, Archiver};
+use crate::chaos::Chaos;
 use crate::error::{ClonableAnyhowError, TaskStatus};
 use crate::event::{self, Event, EventSource, Notification};
 use crate::flag::{ShutdownFlag, ShutdownGuard};
+use crate::group_commit::GroupCommit;
+use crate::http_cache::HttpCache;
+use crate::ingest::{Source, TaskSource};
+use crate::layer::{TaskInfo, TaskLayer, TaskOutcome};
+use crate::lifecycle::{
+    self, LeaderChanged, TaskCompleted, TaskFailed, TaskStarted, TaskSuspended, WorkerEvent,
+};
+use crate::memory::MemoryTracker;
+use crate::migrate::{SchemaValidation, SchemaValidationError};
+use crate::plugin::durable::mq::Publisher;
 use crate::plugin::{DurablePlugin, Plugin};
+use crate::policy::{SqlPolicies, SqlPolicy};
+use crate::retention;
+use crate::sim::Simulation;
+use crate::statement_cache::StatementCache;
+use crate::stats::{self, ActiveTaskGuard, WorkerStats};
 use crate::task::{Task, TaskState};
-use crate::util::{IntoPgInterval, Mailbox, MetricSpan};
-use crate::Config;
+use crate::util::{EpochTicker, IntoPgInterval, Mailbox, MetricSpan};
+use crate::webhook::WebhookServer;
+use crate::{Config, TaskMapping};
 
-const LOG_ERROR_INDEX: i32 = i32::MAX - 1;
+pub(crate) const LOG_ERROR_INDEX: i32 = i32::MAX - 1;
 const LOG_PANIC_INDEX: i32 = i32::MAX;
 
 pub(crate) struct SharedState {
     pub shutdown: ShutdownFlag,
     pub pool: sqlx::PgPool,
     pub client: reqwest::Client,
+
+    /// A client that never follows redirects, for requests that need to
+    /// follow them manually.
+    pub(crate) redirect_client: reqwest::Client,
     pub notifications: broadcast::Sender<Notification>,
+
+    /// The channels that the worker's event source is listening on for tasks
+    /// waiting on a postgres notification.
+    pub(crate) pg_channels: watch::Sender<HashSet<String>>,
     pub config: Config,
-    pub plugins: Vec<Box<dyn Plugin>>,
+    pub plugins: Vec<Arc<dyn Plugin>>,
+    pub(crate) layers: Vec<Arc<dyn TaskLayer>>,
+
+    /// Rewrites queries against the `durable` schema to use the configured
+    /// [`schema`](Config::schema) instead.
+    pub(crate) schema: SchemaRename,
+    pub(crate) sql_policies: SqlPolicies,
+    pub(crate) mq: tokio::sync::OnceCell<Publisher>,
+    pub(crate) http_cache: Option<HttpCache>,
+    pub(crate) statement_cache: StatementCache,
+    pub(crate) archiver: Option<Box<dyn Archiver>>,
 
     leader: Mailbox<i64>,
     suspend: Notify,
@@ -48,7 +88,116 @@ pub(crate) struct SharedState {
     /// and compute time needed by a worker if it gets hammered.
     compile_sema: Semaphore,
 
+    /// Limit how many database connections can be held by workflow database
+    /// transactions, so that the worker always has some available for its
+    /// own queries.
+    pub(crate) workflow_sema: Option<Arc<Semaphore>>,
+
+    /// Batches up transaction events, if group commit is enabled.
+    pub(crate) group_commit: Option<GroupCommit>,
+
+    /// Faults to inject into tasks, if the worker was built with any.
+    pub(crate) chaos: Option<Arc<Chaos>>,
+
+    /// The simulation that tasks run in, if the worker was built with one.
+    pub(crate) simulation: Option<Arc<Simulation>>,
+
     pub(crate) metrics: SharedMetrics,
+
+    /// The number of tasks currently running on this worker.
+    pub(crate) active_tasks: AtomicUsize,
+
+    /// The linear memory used by the tasks running on this worker.
+    pub(crate) memory: Arc<MemoryTracker>,
+
+    /// Lifecycle events for anyone embedding the worker.
+    ///
+    /// See [`WorkerHandle::subscribe`].
+    lifecycle: broadcast::Sender<WorkerEvent>,
+
+    /// The id of this worker, or -1 if it is not currently running.
+    worker_id: AtomicI64,
+}
+
+impl SharedState {
+    pub(crate) fn new(
+        pool: sqlx::PgPool,
+        client: reqwest::Client,
+        config: Config,
+        plugins: Vec<Arc<dyn Plugin>>,
+    ) -> Self {
+        Self {
+            shutdown: ShutdownFlag::new(),
+            client,
+            redirect_client: reqwest::Client::builder()
+                .redirect(reqwest::redirect::Policy::none())
+                .build()
+                .expect("failed to build the HTTP client"),
+            notifications: broadcast::channel(128).0,
+            pg_channels: watch::Sender::new(HashSet::new()),
+            leader: Mailbox::new(-1),
+            suspend: Notify::new(),
+            cache: Mutex::new(uluru::LRUCache::new()),
+            compile_sema: Semaphore::new(config.max_concurrent_compilations),
+            workflow_sema: config
+                .max_workflow_connections
+                .map(|limit| Arc::new(Semaphore::new(limit))),
+            group_commit: config
+                .group_commit_window
+                .map(|window| GroupCommit::new(window, config.group_commit_max_events)),
+            chaos: None,
+            simulation: None,
+            http_cache: config.http_cache.as_ref().map(HttpCache::new),
+            statement_cache: StatementCache::new(&config, &pool),
+            schema: SchemaRename::new("durable", config.schema.clone()),
+            pool,
+            config,
+            plugins,
+            layers: Vec::new(),
+            sql_policies: SqlPolicies::default(),
+            mq: tokio::sync::OnceCell::new(),
+            archiver: None,
+            metrics: SharedMetrics::new(),
+            active_tasks: AtomicUsize::new(0),
+            memory: Arc::new(MemoryTracker::new()),
+            lifecycle: broadcast::channel(lifecycle::CHANNEL_CAPACITY).0,
+            worker_id: AtomicI64::new(-1),
+        }
+    }
+
+    /// The current time, as seen by workflows.
+    ///
+    /// This is the virtual clock if the worker is running a simulation.
+    pub(crate) fn now(&self) -> DateTime<Utc> {
+        match &self.simulation {
+            Some(sim) => sim.now(),
+            None => Utc::now(),
+        }
+    }
+
+    /// Wait until [`now`](SharedState::now) reaches `deadline`.
+    pub(crate) async fn sleep_until(&self, deadline: DateTime<Utc>) {
+        match &self.simulation {
+            Some(sim) => sim.sleep_until(deadline).await,
+            None => {
+                let duration = (deadline - Utc::now()).to_std().unwrap_or_default();
+                tokio::time::sleep(duration).await
+            }
+        }
+    }
+
+    /// Send an HTTP request on behalf of a workflow, or have it answered by
+    /// the simulation if there is one.
+    pub(crate) async fn execute_http(
+        &self,
+        client: &reqwest::Client,
+        request: reqwest::Request,
+    ) -> reqwest::Result<reqwest::Response> {
+        match &self.simulation {
+            Some(sim) => Ok(sim.respond(&request)),
+            None => client.execute(request).await,
+        }
+    }
 }
 
 pub(crate) struct SharedMetrics {
@@ -58,6 +207,13 @@ pub(crate) struct SharedMetrics {
     task_failed: Counter,
     task_taken: Counter,
     wasm_compile_latency: Histogram,
+    is_leader: Gauge,
+    pub(crate) workflow_connection_wait: Histogram,
+    pub(crate) workflow_connections_waiting: Gauge,
+    pub(crate) group_commit_size: Histogram,
+    deprecated_interface_imports: Counter,
+    workers_expired: Counter,
+    tasks_reassigned: Counter,
 }
 
 impl SharedMetrics {
@@ -70,6 +226,13 @@ impl SharedMetrics {
             task_taken: metrics::counter!("durable.task_tasken"),
 
             wasm_compile_latency: metrics::histogram!("durable.wasm_compile_latency"),
+            is_leader: metrics::gauge!("durable.is_leader"),
+            workflow_connection_wait: metrics::histogram!("durable.workflow_connection_wait"),
+            workflow_connections_waiting: metrics::gauge!("durable.workflow_connections_waiting"),
+            group_commit_size: metrics::histogram!("durable.group_commit_size"),
+            deprecated_interface_imports: metrics::counter!("durable.deprecated_interface_imports"),
+            workers_expired: metrics::counter!("durable.workers_expired"),
+            tasks_reassigned: metrics::counter!("durable.tasks_reassigned"),
         }
     }
 }
@@ -78,8 +241,78 @@ pub(crate) struct TaskData {
     pub id: i64,
     pub name: String,
     pub created_at: DateTime<Utc>,
+    pub attempt: i32,
+    pub labels: Json<BTreeMap<String, String>>,
     pub wasm: i64,
     pub data: Json<Box<RawValue>>,
+    pub sql_context: Option<Json<BTreeMap<String, String>>>,
+    pub entrypoint: Option<String>,
+    pub tenant: Option<String>,
+}
+
+/// Details about why a task failed.
+///
+/// These are saved to the task row alongside its final state.
+#[derive(Debug, Default)]
+pub(crate) struct TaskFailure {
+    message: String,
+    label: Option<String>,
+    index: Option<i32>,
+    backtrace: Option<String>,
+}
+
+impl TaskFailure {
+    fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            ..Self::default()
+        }
+    }
+
+    /// Build a failure from an error returned while running the task.
+    ///
+    /// The wasm backtrace, if there is one, is split out from the rest of the
+    /// error chain.
+    fn from_error(error: &anyhow::Error) -> Self {
+        let message = error
+            .chain()
+            .filter(|e| !e.is::<wasmtime::WasmBacktrace>())
+            .map(|e| e.to_string())
+            .collect::<Vec<_>>()
+            .join(": ");
+
+        Self {
+            message,
+            backtrace: error
+                .downcast_ref::<wasmtime::WasmBacktrace>()
+                .map(|backtrace| backtrace.to_string()),
+            ..Self::default()
+        }
+    }
+
+    /// Fill in the details that are tracked by the task state.
+    ///
+    /// If the workflow reported a panic then that replaces the message, since
+    /// the error is then just the trap that followed it.
+    fn with_state(mut self, state: &TaskState) -> Self {
+        let transaction = match state.panic() {
+            Some(panic) => {
+                self.message = panic.message.clone();
+                panic
+                    .transaction
+                    .as_ref()
+                    .map(|(index, label)| (*index, label.as_ref()))
+            }
+            None => state.last_transaction(),
+        };
+
+        if let Some((index, label)) = transaction {
+            self.index = Some(index);
+            self.label = Some(label.to_owned());
+        }
+
+        self
+    }
 }
 
 pub struct WorkerBuilder {
@@ -89,8 +322,14 @@ pub struct WorkerBuilder {
     client: Option<reqwest::Client>,
     wasmtime_config: Option<wasmtime::Config>,
     plugins: Vec<Box<dyn Plugin>>,
+    layers: Vec<Box<dyn TaskLayer>>,
+    sql_policies: SqlPolicies,
+    sources: Vec<Source>,
+    archiver: Option<Box<dyn Archiver>>,
+    chaos: Option<Arc<Chaos>>,
+    simulation: Option<Arc<Simulation>>,
     migrate: bool,
-    validate: bool,
+    validation: SchemaValidation,
 }
 
 impl WorkerBuilder {
@@ -102,8 +341,14 @@ impl WorkerBuilder {
             client: None,
             wasmtime_config: None,
             plugins: vec![Box::new(DurablePlugin)],
+            layers: Vec::new(),
+            sql_policies: SqlPolicies::default(),
+            sources: Vec::new(),
+            archiver: None,
+            chaos: None,
+            simulation: None,
             migrate: false,
-            validate: true,
+            validation: SchemaValidation::Enforce,
         }
     }
 
@@ -128,6 +373,104 @@ impl WorkerBuilder {
         self
     }
 
+    /// Wrap the execution of every task with a [`TaskLayer`].
+    ///
+    /// See the [`layer`](crate::layer) module for more details.
+    pub fn layer(mut self, layer: Box<dyn TaskLayer>) -> Self {
+        self.layers.push(layer);
+        self
+    }
+
+    /// Set the SQL policy that is checked before any workflow runs a SQL
+    /// statement.
+    ///
+    /// This applies to all tasks that do not have their own policy set via
+    /// [`program_sql_policy`](Self::program_sql_policy) or
+    /// [`queue_sql_policy`](Self::queue_sql_policy). See the
+    /// [`policy`](crate::policy) module for more details.
+    pub fn sql_policy(mut self, policy: Box<dyn SqlPolicy>) -> Self {
+        self.sql_policies.default = Some(policy.into());
+        self
+    }
+
+    /// Set the SQL policy for tasks running the program with the provided
+    /// name.
+    ///
+    /// This takes precedence over the policy set by
+    /// [`sql_policy`](Self::sql_policy). Programs without a name can only be
+    /// restricted by the default policy or by the policy for their queue.
+    pub fn program_sql_policy(
+        mut self,
+        program: impl Into<String>,
+        policy: Box<dyn SqlPolicy>,
+    ) -> Self {
+        self.sql_policies
+            .programs
+            .insert(program.into(), policy.into());
+        self
+    }
+
+    /// Set the SQL policy for tasks in the queue of the provided tenant.
+    ///
+    /// Tasks without a tenant are in the `""` queue. This takes precedence
+    /// over the policy set by [`sql_policy`](Self::sql_policy). If the task's
+    /// program also has a policy set via
+    /// [`program_sql_policy`](Self::program_sql_policy) then statements must
+    /// be allowed by both of them.
+    pub fn queue_sql_policy(
+        mut self,
+        queue: impl Into<String>,
+        policy: Box<dyn SqlPolicy>,
+    ) -> Self {
+        self.sql_policies.queues.insert(queue.into(), policy.into());
+        self
+    }
+
+    /// Launch tasks from messages received from a custom [`TaskSource`].
+    ///
+    /// This is in addition to any sources configured via
+    /// [`Config::sources`]. The name must be unique among all the sources of
+    /// the worker. See the [`ingest`](crate::ingest) module for more details.
+    pub fn source(
+        mut self,
+        name: impl Into<String>,
+        source: Box<dyn TaskSource>,
+        mapping: TaskMapping,
+    ) -> Self {
+        self.sources.push(Source::new(name.into(), mapping, source));
+        self
+    }
+
+    /// Archive completed tasks with a custom [`Archiver`] before they are
+    /// cleaned up.
+    ///
+    /// This takes precedence over [`Config::archive`]. See the
+    /// [`archive`](crate::archive) module for more details.
+    pub fn archiver(mut self, archiver: Box<dyn Archiver>) -> Self {
+        self.archiver = Some(archiver);
+        self
+    }
+
+    /// Inject the faults in `chaos` into the tasks run by the worker.
+    ///
+    /// This is meant for testing. See the [`chaos`](crate::chaos) module for
+    /// more details.
+    #[cfg(feature = "chaos")]
+    pub fn chaos(mut self, chaos: Arc<Chaos>) -> Self {
+        self.chaos = Some(chaos);
+        self
+    }
+
+    /// Run the tasks of the worker within `simulation`.
+    ///
+    /// This is meant for testing. See the [`sim`](crate::sim) module for more
+    /// details.
+    #[cfg(feature = "simulation")]
+    pub fn simulation(mut self, simulation: Arc<Simulation>) -> Self {
+        self.simulation = Some(simulation);
+        self
+    }
+
     /// Whether the database should be automatically migrated on runner startup
     /// if the schema version in the database differs from what we expect.
     ///
@@ -150,13 +493,33 @@ impl WorkerBuilder {
     }
 
     /// Validate that the database matches what this worker needs.
+    ///
+    /// This is a shorthand for setting [`WorkerBuilder::schema_validation`] to
+    /// either [`SchemaValidation::Enforce`] or [`SchemaValidation::Disabled`].
     pub fn validate_database(mut self, validate: bool) -> Self {
-        self.validate = validate;
+        self.validation = match validate {
+            true => SchemaValidation::Enforce,
+            false => SchemaValidation::Disabled,
+        };
+        self
+    }
+
+    /// How the database schema should be validated when the worker is built.
+    ///
+    /// Validation compares the tables, columns, indexes, and other objects in
+    /// the `durable` schema against the ones expected by this version of the
+    /// worker, in addition to checking the migration version. It is skipped
+    /// if the worker is set to [`migrate`](WorkerBuilder::migrate) the
+    /// database.
+    ///
+    /// This is [`SchemaValidation::Enforce`] by default.
+    pub fn schema_validation(mut self, validation: SchemaValidation) -> Self {
+        self.validation = validation;
         self
     }
 
     pub async fn build(self) -> anyhow::Result<Worker> {
-        let migrator = crate::migrate::Migrator::new();
+        let migrator = crate::migrate::Migrator::new().with_schema(self.config.schema.clone());
         let mut conn = self.pool.acquire().await?;
         if self.migrate {
             let options = crate::migrate::Options {
@@ -169,35 +532,85 @@ impl WorkerBuilder {
                 .migrate(&mut conn, &options)
                 .await
                 .context("failed to migrate the database")?;
-        } else if self.validate {
-            let version = migrator
-                .read_database_version(&mut conn)
-                .await?
-                .unwrap_or(0);
-            let latest = migrator.latest_version();
-
-            if version != latest {
-                anyhow::bail!(
-                    "database version does not match that required by this durable worker \
-                     (expected {latest}, got {version} instead)"
-                )
+        } else if self.validation != SchemaValidation::Disabled {
+            let report = migrator
+                .check_schema(&mut conn)
+                .await
+                .context("failed to read the database schema")?;
+
+            for drift in report.drift() {
+                if !drift.is_breaking() {
+                    tracing::warn!("database schema drift: {drift}");
+                }
+            }
+
+            if !report.is_compatible() {
+                if self.validation == SchemaValidation::Enforce {
+                    return Err(SchemaValidationError::new(report).into());
+                }
+
+                if let Some(message) = report.version_message() {
+                    tracing::warn!("database schema drift: {message}");
+                }
+
+                for drift in report.drift().iter().filter(|drift| drift.is_breaking()) {
+                    tracing::warn!("database schema drift: {drift}");
+                }
             }
         }
         drop(conn);
 
-        let shared = Arc::new(SharedState {
-            shutdown: ShutdownFlag::new(),
-            client: self.client.unwrap_or_default(),
-            notifications: broadcast::channel(128).0,
-            leader: Mailbox::new(-1),
-            suspend: Notify::new(),
-            cache: Mutex::new(uluru::LRUCache::new()),
-            compile_sema: Semaphore::new(self.config.max_concurrent_compilations),
-            pool: self.pool,
-            config: self.config,
-            plugins: self.plugins,
-            metrics: SharedMetrics::new(),
-        });
+        let client = self.client.unwrap_or_default();
+        let mut sources = self.sources;
+        for config in &self.config.sources {
+            let source = Source::connect(config, &client)
+                .with_context(|| format!("failed to set up source `{}`", config.name))?;
+            sources.push(source);
+        }
+
+        let mut names = HashSet::new();
+        for source in &sources {
+            if !names.insert(source.name()) {
+                anyhow::bail!("multiple sources have the name `{}`", source.name());
+            }
+        }
+
+        let archiver = match (self.archiver, &self.config.archive) {
+            (Some(archiver), _) => Some(archiver),
+            (None, Some(archive)) => Some(
+                archive::connect(archive, &self.config, &self.pool, &client)
+                    .context("failed to set up the task archiver")?,
+            ),
+            (None, None) => None,
+        };
+
+        let webhooks = match &self.config.webhooks {
+            Some(config) => {
+                Some(WebhookServer::bind(config).context("failed to set up the webhook server")?)
+            }
+            None => None,
+        };
+
+        let api = match &self.config.api {
+            Some(config) => Some(
+                ApiServer::bind(config, &self.pool, &self.config.schema)
+                    .context("failed to set up the management API")?,
+            ),
+            None => None,
+        };
+
+        let mut shared = SharedState::new(
+            self.pool,
+            client,
+            self.config,
+            self.plugins.into_iter().map(Arc::from).collect(),
+        );
+        shared.sql_policies = self.sql_policies;
+        shared.layers = self.layers.into_iter().map(Arc::from).collect();
+        shared.archiver = archiver;
+        shared.chaos = self.chaos;
+        shared.simulation = self.simulation;
+        let shared = Arc::new(shared);
 
         let mut config = self.wasmtime_config.unwrap_or_else(|| {
             let mut config = wasmtime::Config::new();
@@ -207,23 +620,46 @@ impl WorkerBuilder {
             config
         });
 
+        let epoch_interval = shared
+            .config
+            .epoch_interval
+            .filter(|interval| !interval.is_zero());
+
         config.async_support(true);
+        config.epoch_interruption(epoch_interval.is_some());
+        if let Some(pool) = &shared.config.instance_pool {
+            config.allocation_strategy(wasmtime::InstanceAllocationStrategy::Pooling(
+                pool.allocation_config(shared.config.max_tasks),
+            ));
+        }
 
         let engine = wasmtime::Engine::new(&config)?;
+        let epoch = epoch_interval
+            .map(|interval| EpochTicker::spawn(&engine, interval))
+            .transpose()
+            .context("failed to spawn the epoch ticker thread")?;
         let event_source = match self.event_source {
             Some(source) => source,
-            None => Box::new(PgEventSource::new(&shared.pool).await?),
+            None => Box::new(PgEventSource::new(&shared.pool, &shared.schema).await?),
         };
 
         Ok(Worker {
             shared,
             engine,
+            _epoch: epoch,
             event_source,
+            sources,
+            webhooks,
+            api,
 
             // A worker id of -1 should never overlap with an existing worker id.
             worker_id: -1,
             tasks: JoinSet::new(),
             blocked: false,
+            buffered: VecDeque::new(),
+            claim_ahead_paused: false,
+            next_wakeup: None,
+            quotas: HashMap::new(),
 
             active_tasks: metrics::gauge!("durable.active_tasks"),
         })
@@ -251,22 +687,114 @@ impl WorkerHandle {
     pub fn reset(&self) {
         self.shared.shutdown.reset();
     }
+
+    /// Get the id of the worker, if it is currently running.
+    pub fn worker_id(&self) -> Option<i64> {
+        match self.shared.worker_id.load(Ordering::Relaxed) {
+            -1 => None,
+            id => Some(id),
+        }
+    }
+
+    /// Get the id of the worker that this worker believes is the current
+    /// cluster leader.
+    ///
+    /// The current leader is also visible in the `durable.leader` view.
+    pub fn leader_id(&self) -> Option<i64> {
+        match self.shared.leader.get() {
+            -1 => None,
+            id => Some(id),
+        }
+    }
+
+    /// Whether this worker is currently the cluster leader.
+    pub fn is_leader(&self) -> bool {
+        self.worker_id()
+            .is_some_and(|id| Some(id) == self.leader_id())
+    }
+
+    /// Get a stream that yields the id of the new leader whenever this worker
+    /// observes a change in leadership.
+    ///
+    /// Changes that happen in quick succession may be coalesced, so only the
+    /// most recent leader is guaranteed to be yielded.
+    pub fn leader_changes(&self) -> impl Stream<Item = Option<i64>> + '_ {
+        use futures_util::StreamExt;
+
+        self.shared
+            .leader
+            .stream()
+            .map(|id| if id == -1 { None } else { Some(id) })
+    }
+
+    /// Subscribe to the lifecycle events emitted by this worker.
+    ///
+    /// Only events emitted after this is called are received. Subscribers
+    /// that fall too far behind will see a [`RecvError::Lagged`] error and
+    /// miss some events.
+    ///
+    /// [`RecvError::Lagged`]: tokio::sync::broadcast::error::RecvError::Lagged
+    pub fn subscribe(&self) -> broadcast::Receiver<WorkerEvent> {
+        self.shared.lifecycle.subscribe()
+    }
+
+    /// Get load signals for this worker and its cluster.
+    ///
+    /// The queue stats are read from the `durable.queue_stats` table, which is
+    /// refreshed by the cluster leader every
+    /// [`queue_stats_interval`](Config::queue_stats_interval).
+    pub async fn stats(&self) -> sqlx::Result<WorkerStats> {
+        Ok(WorkerStats {
+            active_tasks: self.shared.active_tasks.load(Ordering::Relaxed),
+            max_tasks: self.shared.config.max_tasks,
+            memory_usage: self.shared.memory.used(),
+            memory_budget: self.shared.config.memory_budget,
+            queues: stats::load(&self.shared.schema, &self.shared.pool).await?,
+        })
+    }
 }
 
 struct ProgramCache {
     id: i64,
     value: Arc<Cached<Component, ClonableAnyhowError>>,
+
+    /// The pre-instantiated program, if the worker has an instance pool.
+    pre: Arc<OnceLock<InstancePre<Task>>>,
 }
 
 pub struct Worker {
     shared: Arc<SharedState>,
     engine: wasmtime::Engine,
+    /// Keeps the engine's epoch ticking while the worker is alive.
+    _epoch: Option<EpochTicker>,
     event_source: Box<dyn EventSource>,
+    sources: Vec<Source>,
+    webhooks: Option<WebhookServer>,
+    api: Option<ApiServer>,
 
     worker_id: i64,
     tasks: JoinSet<()>,
     blocked: bool,
 
+    /// Tasks that have been claimed by this worker but not yet started.
+    ///
+    /// See [`Config::claim_ahead`].
+    buffered: VecDeque<BufferedTask>,
+
+    /// Whether this worker has released tasks that it claimed ahead of time.
+    ///
+    /// Until one of its own tasks completes, the worker only claims tasks
+    /// that it can start right away. Otherwise it would race other workers
+    /// to claim the tasks it just released.
+    claim_ahead_paused: bool,
+
+    /// When the next task that was launched with a delay becomes claimable.
+    next_wakeup: Option<Instant>,
+
+    /// Permits for the tasks of each tenant with a quota that are running on
+    /// this worker.
+    quotas: HashMap<String, Arc<Semaphore>>,
+
     /// A metric tracking how many tasks are currently active on this worker.
     active_tasks: Gauge,
 }
@@ -279,18 +807,35 @@ impl Worker {
     }
 
     pub async fn run(&mut self) -> anyhow::Result<()> {
+        let webhooks = self
+            .webhooks
+            .as_ref()
+            .map(|server| server.try_clone())
+            .transpose()
+            .context("failed to clone the webhook server listener")?;
+        let api = self
+            .api
+            .as_ref()
+            .map(|server| server.try_clone())
+            .transpose()
+            .context("failed to clone the management API listener")?;
+
         self.worker_id = sqlx::query!(
             "
-            INSERT INTO durable.worker(heartbeat_at)
-            VALUES (CURRENT_TIMESTAMP)
+            INSERT INTO durable.worker(heartbeat_at, instance)
+            VALUES (CURRENT_TIMESTAMP, $1)
             RETURNING id
-            "
+            ",
+            self.shared.config.instance
         )
-        .fetch_one(&self.shared.pool)
+        .fetch_one(self.shared.schema.on(&self.shared.pool))
         .await?
         .id;
 
         tracing::info!("durable worker id is {}", self.worker_id);
+        self.shared
+            .worker_id
+            .store(self.worker_id, Ordering::Relaxed);
 
         self.load_leader_id().await?;
 
@@ -299,10 +844,24 @@ impl Worker {
             .instrument(tracing::info_span!("heartbeat"));
         let validate = Self::validate_workers(self.shared.clone(), self.worker_id)
             .instrument(tracing::info_span!("validate_workers"));
+        let sweep = Self::sweep_workers(self.shared.clone(), self.worker_id)
+            .instrument(tracing::info_span!("sweep_workers"));
         let leader = Self::leader(self.shared.clone(), self.worker_id)
             .instrument(tracing::info_span!("leader"));
         let cleanup = Self::task_cleanup(self.shared.clone(), worker_id)
             .instrument(tracing::info_span!("task_cleanup"));
+        let dedupe_cleanup = Self::ingest_cleanup(self.shared.clone(), worker_id)
+            .instrument(tracing::info_span!("ingest_cleanup"));
+        let queue_stats = Self::queue_stats(self.shared.clone(), worker_id)
+            .instrument(tracing::info_span!("queue_stats"));
+        let retention = Self::retention(self.shared.clone(), worker_id)
+            .instrument(tracing::info_span!("retention"));
+        let mut sources = std::mem::take(&mut self.sources);
+        let ingest = Self::ingest(self.shared.clone(), &mut sources)
+            .instrument(tracing::info_span!("ingest"));
+        let webhooks = Self::webhooks(self.shared.clone(), webhooks)
+            .instrument(tracing::info_span!("webhooks"));
+        let api = Self::api(self.shared.clone(), api).instrument(tracing::info_span!("api"));
         let process = self
             .process_events()
             .instrument(tracing::info_span!("process"));
@@ -312,25 +871,72 @@ impl Worker {
         //
         // Spawned tasks are put into their own joinset because running everything in a
         // single task is not reasonable.
-        let (heartbeat, validate, leader, process, cleanup) =
-            (heartbeat, validate, leader, process, cleanup)
-                .join()
-                .instrument(tracing::info_span!("worker", worker_id))
-                .await;
+        let (
+            heartbeat,
+            validate,
+            sweep,
+            leader,
+            process,
+            cleanup,
+            dedupe_cleanup,
+            queue_stats,
+            retention,
+            ingest,
+            webhooks,
+            api,
+        ) = (
+            heartbeat,
+            validate,
+            sweep,
+            leader,
+            process,
+            cleanup,
+            dedupe_cleanup,
+            queue_stats,
+            retention,
+            ingest,
+            webhooks,
+            api,
+        )
+            .join()
+            .instrument(tracing::info_span!("worker", worker_id))
+            .await;
 
-        tracing::info!("deleting worker database entry");
-        let result = sqlx::query!("DELETE FROM durable.worker WHERE id = $1", self.worker_id)
-            .execute(&self.shared.pool)
-            .await
-            .context("failed to delete the worker entry from the database");
+        self.sources = sources;
+
+        // A worker that was killed by fault injection leaves its entry behind, the
+        // same as one that crashed would.
+        let killed = match &self.shared.chaos {
+            Some(chaos) => chaos.take_killed(),
+            None => false,
+        };
+
+        let result = if killed {
+            tracing::warn!("worker was killed, leaving its database entry behind");
+            Ok(Default::default())
+        } else {
+            tracing::info!("deleting worker database entry");
+            sqlx::query!("DELETE FROM durable.worker WHERE id = $1", self.worker_id)
+                .execute(self.shared.schema.on(&self.shared.pool))
+                .await
+                .context("failed to delete the worker entry from the database")
+        };
+        self.shared.worker_id.store(-1, Ordering::Relaxed);
 
         self.tasks.abort_all();
 
         process?;
         validate?;
+        sweep?;
         heartbeat?;
         leader?;
         cleanup?;
+        dedupe_cleanup?;
+        queue_stats?;
+        retention?;
+        ingest?;
+        webhooks?;
+        api?;
         result?;
 
         Ok(())
@@ -357,7 +963,7 @@ impl Worker {
                 RETURNING id",
                 worker_id
             )
-            .fetch_optional(&shared.pool)
+            .fetch_optional(shared.schema.on(&shared.pool))
             .await?;
 
             // Our record is gone from the database. This means that some other worker
@@ -369,11 +975,11 @@ impl Worker {
                 anyhow::bail!("worker entry was deleted from the database");
             }
 
-            let mut interval = shared.config.heartbeat_interval;
-            let jitter = rand::rng().random_range(0..(interval / 4).as_nanos());
-            interval -= Duration::from_nanos(jitter as u64);
+            let interval = shared.config.heartbeat_interval;
+            let jitter = shared.config.heartbeat_jitter.clamp(0.0, 1.0);
+            let jitter = interval.mul_f64(jitter * rand::rng().random::<f64>());
 
-            next += interval;
+            next += interval - jitter;
         }
 
         Ok(())
@@ -410,43 +1016,20 @@ impl Worker {
             }
 
             let mut tx = shared.pool.begin().await?;
-            let timeout = shared.config.heartbeat_timeout.into_pg_interval();
 
-            let mut result = if let Some(following) = following.take() {
-                sqlx::query!(
-                    "
-                    DELETE FROM durable.worker
-                    WHERE id = $1
-                      AND CURRENT_TIMESTAMP - heartbeat_at > $2
-                    ",
-                    following,
-                    timeout
-                )
-                .execute(&mut *tx)
-                .await?
-            } else {
-                Default::default()
+            let mut expired = match following.take() {
+                Some(following) => {
+                    Self::expire_workers(&shared, &mut tx, worker_id, Some(following)).await?
+                }
+                None => 0,
             };
 
-            if result.rows_affected() > 0 {
-                result.extend(std::iter::once(
-                    sqlx::query!(
-                        "
-                        DELETE FROM durable.worker
-                        WHERE CURRENT_TIMESTAMP - heartbeat_at > $2
-                        AND NOT id = $1
-                        ",
-                        worker_id,
-                        timeout
-                    )
-                    .execute(&mut *tx)
-                    .await?,
-                ));
+            if expired > 0 {
+                expired += Self::expire_workers(&shared, &mut tx, worker_id, None).await?;
 
                 tracing::debug!(
                     target: "durable_runtime::validate_workers",
-                    "deleted {} expired workers",
-                    result.rows_affected()
+                    "deleted {expired} expired workers"
                 );
             }
 
@@ -482,7 +1065,7 @@ impl Worker {
                 "#,
                 worker_id
             )
-            .fetch_optional(&mut *tx)
+            .fetch_optional(shared.schema.on(&mut *tx))
             .await?;
 
             tx.commit().await?;
@@ -516,6 +1099,135 @@ impl Worker {
         Ok(())
     }
 
+    /// This task is responsible for having the leader regularly delete all
+    /// expired workers, in addition to the checks done by `validate_workers`.
+    async fn sweep_workers(shared: Arc<SharedState>, worker_id: i64) -> anyhow::Result<()> {
+        let period = match shared.config.worker_sweep_interval {
+            Some(period) if !period.is_zero() => period,
+            _ => {
+                shared.shutdown.wait().await;
+                return Ok(());
+            }
+        };
+
+        let _guard = ShutdownGuard::new(&shared.shutdown);
+        let mut shutdown = std::pin::pin!(shared.shutdown.wait());
+
+        let mut leader_id = shared.leader.get();
+        let mut leader_stream = std::pin::pin!(shared.leader.stream());
+
+        let mut interval = tokio::time::interval(period);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Skip);
+
+        'outer: loop {
+            tokio::select! {
+                biased;
+
+                _ = shutdown.as_mut() => break 'outer,
+                _ = interval.tick(), if leader_id == worker_id => (),
+                new_leader = leader_stream.as_mut().next() => {
+                    leader_id = new_leader;
+                    continue 'outer;
+                }
+            }
+
+            let result = async {
+                let mut tx = shared.pool.begin().await?;
+                let expired = Self::expire_workers(&shared, &mut tx, worker_id, None).await?;
+                tx.commit().await?;
+
+                anyhow::Ok(expired)
+            };
+
+            match result.await {
+                Ok(0) => (),
+                Ok(expired) => tracing::info!("deleted {expired} expired workers"),
+                Err(e) => tracing::warn!("failed to sweep for expired workers: {e:#}"),
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Delete workers whose heartbeat has expired and hand the tasks that were
+    /// running on them back to the queue.
+    ///
+    /// If `only` is set then only that worker is checked. Returns the number
+    /// of workers that were deleted.
+    async fn expire_workers(
+        shared: &SharedState,
+        conn: &mut sqlx::PgConnection,
+        worker_id: i64,
+        only: Option<i64>,
+    ) -> anyhow::Result<u64> {
+        let expired = sqlx::query_scalar!(
+            "
+            SELECT id
+             FROM durable.worker
+            WHERE CURRENT_TIMESTAMP - heartbeat_at > $2
+              AND NOT id = $1
+              AND ($3::bigint IS NULL OR id = $3)
+            FOR UPDATE SKIP LOCKED
+            ",
+            worker_id,
+            shared.config.heartbeat_timeout.into_pg_interval(),
+            only
+        )
+        .fetch_all(shared.schema.on(&mut *conn))
+        .await?;
+
+        if expired.is_empty() {
+            return Ok(0);
+        }
+
+        // Deleting the workers would clear running_on for their tasks anyway, but
+        // doing it explicitly lets us tell the other workers about them.
+        let reassigned = sqlx::query_scalar!(
+            "
+            UPDATE durable.task
+              SET running_on = NULL
+            WHERE running_on = ANY($1::bigint[])
+            RETURNING id
+            ",
+            &expired
+        )
+        .fetch_all(shared.schema.on(&mut *conn))
+        .await?;
+
+        sqlx::query!(
+            "DELETE FROM durable.worker WHERE id = ANY($1::bigint[])",
+            &expired
+        )
+        .execute(shared.schema.on(&mut *conn))
+        .await?;
+
+        // A single notification is enough to have every worker try to claim tasks.
+        if let Some(&id) = reassigned.first() {
+            let payload = serde_json::json!({ "id": id, "running_on": null });
+
+            sqlx::query("SELECT pg_notify('durable:task', $1)")
+                .bind(payload.to_string())
+                .execute(shared.schema.on(&mut *conn))
+                .await?;
+
+            tracing::info!(
+                "reassigned {} tasks from expired workers {expired:?}",
+                reassigned.len()
+            );
+        }
+
+        shared
+            .metrics
+            .workers_expired
+            .increment(expired.len() as u64);
+        shared
+            .metrics
+            .tasks_reassigned
+            .increment(reassigned.len() as u64);
+
+        Ok(expired.len() as u64)
+    }
+
     async fn leader(shared: Arc<SharedState>, worker_id: i64) -> anyhow::Result<()> {
         let _guard = ShutdownGuard::new(&shared.shutdown);
         let mut shutdown = std::pin::pin!(shared.shutdown.wait());
@@ -559,6 +1271,7 @@ impl Worker {
                       running_on = (
                         SELECT id
                          FROM durable.worker
+                        WHERE worker.instance IS NOT DISTINCT FROM task.instance
                         ORDER BY random() + task.id
                         LIMIT 1
                       )
@@ -567,7 +1280,7 @@ impl Worker {
                 ",
                 shared.config.suspend_margin.into_pg_interval()
             )
-            .execute(&mut *conn)
+            .execute(shared.schema.on(&mut *conn))
             .await?;
 
             let count = result.rows_affected();
@@ -585,7 +1298,7 @@ impl Worker {
                 LIMIT 1
                 "#
             )
-            .fetch_optional(&mut *conn)
+            .fetch_optional(shared.schema.on(&mut *conn))
             .await?
             .map(|record| record.wakeup_at);
 
@@ -647,6 +1360,25 @@ impl Worker {
 
             // We do cleanup
             loop {
+                if let Some(archiver) = &shared.archiver {
+                    match archive::archive_batch(
+                        &shared.schema,
+                        &mut conn,
+                        &**archiver,
+                        cleanup_age,
+                        limit,
+                    )
+                    .await
+                    {
+                        Ok(count) if count < limit as u64 => break,
+                        Ok(_) => continue,
+                        Err(e) => {
+                            tracing::error!("failed to archive old durable tasks: {e:#}");
+                            break;
+                        }
+                    }
+                }
+
                 let result = sqlx::query!(
                     r#"
                     DELETE FROM durable.task
@@ -661,7 +1393,7 @@ impl Worker {
                     interval,
                     limit
                 )
-                .execute(&mut *conn)
+                .execute(shared.schema.on(&mut *conn))
                 .await;
 
                 match result {
@@ -678,14 +1410,248 @@ impl Worker {
         Ok(())
     }
 
-    async fn process_events(&mut self) -> anyhow::Result<()> {
-        let shutdown = self.shared.shutdown.clone();
-        let _guard = ShutdownGuard::new(&shutdown);
-        let mut shutdown = std::pin::pin!(shutdown.wait());
-        let (tx, mut rx) = tokio::sync::mpsc::channel::<i64>(1024);
+    /// This task is responsible for deleting dedupe keys of ingested messages
+    /// once they have expired.
+    async fn ingest_cleanup(shared: Arc<SharedState>, worker_id: i64) -> anyhow::Result<()> {
+        let _guard = ShutdownGuard::new(&shared.shutdown);
+        let mut shutdown = std::pin::pin!(shared.shutdown.wait());
 
-        self.spawn_new_tasks(&tx).await?;
-        self.load_leader_id().await?;
+        let mut leader_id = shared.leader.get();
+        let mut leader_stream = std::pin::pin!(shared.leader.stream());
+
+        let mut interval = tokio::time::interval(Duration::from_secs(3600));
+        interval.set_missed_tick_behavior(MissedTickBehavior::Skip);
+
+        'outer: loop {
+            tokio::select! {
+                biased;
+
+                _ = shutdown.as_mut() => break 'outer,
+                _ = interval.tick(), if leader_id == worker_id => (),
+                new_leader = leader_stream.as_mut().next() => {
+                    leader_id = new_leader;
+                    continue 'outer;
+                }
+            }
+
+            let limit = shared.config.cleanup_batch_limit as i64;
+            let window = shared.config.ingest_dedupe_window.into_pg_interval();
+            let mut conn = match shared.pool.acquire().await {
+                Ok(conn) => conn,
+                Err(e) => {
+                    tracing::warn!(
+                        "failed to acquire a connection to clean up ingest dedupe keys: {e}"
+                    );
+                    continue;
+                }
+            };
+
+            loop {
+                let result = sqlx::query!(
+                    r#"
+                    DELETE FROM durable.ingest_dedupe
+                    WHERE ingest_dedupe.ctid = ANY(ARRAY(
+                        SELECT ctid
+                        FROM durable.ingest_dedupe
+                        WHERE created_at < NOW() - $1::interval
+                        LIMIT $2
+                        FOR UPDATE
+                    ))
+                    "#,
+                    window,
+                    limit
+                )
+                .execute(shared.schema.on(&mut *conn))
+                .await;
+
+                match result {
+                    Ok(result) if result.rows_affected() < limit as u64 => break,
+                    Ok(_) => (),
+                    Err(e) => {
+                        tracing::error!("failed to clean up expired ingest dedupe keys: {e}");
+                        break;
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// This task is responsible for pruning old events and logs and for
+    /// managing the partitions of the tables that hold them.
+    async fn retention(shared: Arc<SharedState>, worker_id: i64) -> anyhow::Result<()> {
+        let config = &shared.config;
+        let partition_size = config.partition_size.filter(|&size| size != 0);
+        if config.event_retention.is_none()
+            && config.log_retention.is_none()
+            && partition_size.is_none()
+        {
+            shared.shutdown.wait().await;
+            return Ok(());
+        }
+
+        let _guard = ShutdownGuard::new(&shared.shutdown);
+        let mut shutdown = std::pin::pin!(shared.shutdown.wait());
+
+        let mut leader_id = shared.leader.get();
+        let mut leader_stream = std::pin::pin!(shared.leader.stream());
+
+        let mut interval = tokio::time::interval(Duration::from_secs(3600));
+        interval.set_missed_tick_behavior(MissedTickBehavior::Skip);
+
+        'outer: loop {
+            tokio::select! {
+                biased;
+
+                _ = shutdown.as_mut() => break 'outer,
+                _ = interval.tick(), if leader_id == worker_id => (),
+                new_leader = leader_stream.as_mut().next() => {
+                    leader_id = new_leader;
+                    continue 'outer;
+                }
+            }
+
+            let limit = config.cleanup_batch_limit as i64;
+            let mut conn = match shared.pool.acquire().await {
+                Ok(conn) => conn,
+                Err(e) => {
+                    tracing::warn!("failed to acquire a connection to prune old events: {e}");
+                    continue;
+                }
+            };
+
+            if let Some(age) = config.event_retention {
+                if let Err(e) = retention::prune_events(&shared.schema, &mut conn, age, limit).await
+                {
+                    tracing::error!("failed to prune the events of old tasks: {e}");
+                }
+            }
+
+            if let Some(age) = config.log_retention {
+                if let Err(e) = retention::prune_logs(&shared.schema, &mut conn, age, limit).await {
+                    tracing::error!("failed to prune the logs of old tasks: {e}");
+                }
+            }
+
+            if let Some(size) = partition_size {
+                if let Err(e) =
+                    retention::maintain_partitions(&shared.schema, &mut conn, size).await
+                {
+                    tracing::error!("failed to maintain event and log partitions: {e}");
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// This task is responsible for keeping the `durable.queue_stats` table up
+    /// to date.
+    async fn queue_stats(shared: Arc<SharedState>, worker_id: i64) -> anyhow::Result<()> {
+        let period = match shared.config.queue_stats_interval {
+            Some(period) if !period.is_zero() => period,
+            _ => {
+                shared.shutdown.wait().await;
+                return Ok(());
+            }
+        };
+
+        let _guard = ShutdownGuard::new(&shared.shutdown);
+        let mut shutdown = std::pin::pin!(shared.shutdown.wait());
+
+        let mut leader_id = shared.leader.get();
+        let mut leader_stream = std::pin::pin!(shared.leader.stream());
+        let mut reported = HashSet::new();
+
+        let mut interval = tokio::time::interval(period);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Skip);
+
+        'outer: loop {
+            tokio::select! {
+                biased;
+
+                _ = shutdown.as_mut() => break 'outer,
+                _ = interval.tick(), if leader_id == worker_id => (),
+                new_leader = leader_stream.as_mut().next() => {
+                    leader_id = new_leader;
+                    continue 'outer;
+                }
+            }
+
+            let mut conn = match shared.pool.acquire().await {
+                Ok(conn) => conn,
+                Err(e) => {
+                    tracing::warn!("failed to acquire a connection to refresh queue stats: {e}");
+                    continue;
+                }
+            };
+
+            match stats::refresh(&shared.schema, &mut conn).await {
+                Ok(stats) => stats::record_metrics(&stats, &mut reported),
+                Err(e) => tracing::error!("failed to refresh queue stats: {e}"),
+            }
+        }
+
+        Ok(())
+    }
+
+    /// This task is responsible for launching tasks from the messages received
+    /// by each of the worker's sources.
+    async fn ingest(shared: Arc<SharedState>, sources: &mut [Source]) -> anyhow::Result<()> {
+        if sources.is_empty() {
+            shared.shutdown.wait().await;
+            return Ok(());
+        }
+
+        let _guard = ShutdownGuard::new(&shared.shutdown);
+        sources
+            .iter_mut()
+            .map(|source| {
+                let span = tracing::info_span!("source", name = source.name());
+                source.run(&shared).instrument(span)
+            })
+            .collect::<Vec<_>>()
+            .join()
+            .await;
+
+        Ok(())
+    }
+
+    /// This task serves the webhook server, if one is configured.
+    async fn webhooks(
+        shared: Arc<SharedState>,
+        server: Option<WebhookServer>,
+    ) -> anyhow::Result<()> {
+        let Some(server) = server else {
+            shared.shutdown.wait().await;
+            return Ok(());
+        };
+
+        let _guard = ShutdownGuard::new(&shared.shutdown);
+        server.serve(shared.clone()).await
+    }
+
+    /// This task serves the management API, if one is configured.
+    async fn api(shared: Arc<SharedState>, server: Option<ApiServer>) -> anyhow::Result<()> {
+        let Some(server) = server else {
+            shared.shutdown.wait().await;
+            return Ok(());
+        };
+
+        let _guard = ShutdownGuard::new(&shared.shutdown);
+        server.serve(shared.clone()).await
+    }
+
+    async fn process_events(&mut self) -> anyhow::Result<()> {
+        let shutdown = self.shared.shutdown.clone();
+        let _guard = ShutdownGuard::new(&shutdown);
+        let mut shutdown = std::pin::pin!(shutdown.wait());
+        let (tx, mut rx) = tokio::sync::mpsc::channel::<i64>(1024);
+
+        self.spawn_new_tasks(&tx).await?;
+        self.load_leader_id().await?;
+        self.load_pg_listeners().await?;
 
         'outer: loop {
             let event = tokio::select! {
@@ -695,6 +1661,10 @@ impl Worker {
                 _ = self.tasks.join_next(), if !self.tasks.is_empty() => LoopEvent::TaskComplete,
                 id = rx.recv() => LoopEvent::TaskFailed(id.expect("failed task channel closed unexpectedly")),
                 event = self.event_source.next() => LoopEvent::Event(event?),
+                _ = tokio::time::sleep_until(self.next_wakeup.unwrap_or_else(Instant::now)),
+                    if self.next_wakeup.is_some() => LoopEvent::Wakeup,
+                _ = tokio::time::sleep_until(self.buffer_deadline().unwrap_or_else(Instant::now)),
+                    if !self.buffered.is_empty() => LoopEvent::BufferExpired,
             };
 
             // Clean up any tasks that have completed already.
@@ -703,12 +1673,23 @@ impl Worker {
             let event = match event {
                 LoopEvent::Event(event) => event,
                 LoopEvent::TaskComplete => {
+                    self.claim_ahead_paused = false;
+                    self.spawn_buffered_tasks(&tx)?;
+
                     if self.blocked {
                         self.spawn_new_tasks(&tx).await?;
                     }
 
                     continue;
                 }
+                LoopEvent::Wakeup => {
+                    self.spawn_new_tasks(&tx).await?;
+                    continue;
+                }
+                LoopEvent::BufferExpired => {
+                    self.release_buffered_tasks().await?;
+                    continue;
+                }
                 LoopEvent::TaskFailed(id) => {
                     let mut failed = vec![id];
 
@@ -733,7 +1714,7 @@ impl Worker {
                         &failed,
                         self.worker_id
                     )
-                    .execute(&self.shared.pool)
+                    .execute(self.shared.schema.on(&self.shared.pool))
                     .await?;
 
                     continue;
@@ -765,10 +1746,22 @@ impl Worker {
                     self.load_leader_id().await?;
                 }
 
+                Event::StatementCache(event::StatementCache { wasm }) => {
+                    self.shared.statement_cache.invalidate(wasm);
+                }
+
+                Event::PgListen(event::PgListen { channel }) => {
+                    self.pg_listen(channel).await?;
+                }
+                Event::PgNotify(notify) => {
+                    self.deliver_pg_notification(notify).await?;
+                }
+
                 // We don't know what we missed so do everything.
                 Event::Lagged => {
                     self.spawn_new_tasks(&tx).await?;
                     self.load_leader_id().await?;
+                    self.load_pg_listeners().await?;
                     self.shared.suspend.notify_waiters();
                 }
             }
@@ -779,14 +1772,12 @@ impl Worker {
 
     async fn load_leader_id(&mut self) -> anyhow::Result<()> {
         let record = sqlx::query!(
-            "
-            SELECT id
-             FROM durable.worker
-            ORDER BY started_at ASC, id ASC
-            LIMIT 1
-            "
+            r#"
+            SELECT id as "id!"
+             FROM durable.leader
+            "#
         )
-        .fetch_optional(&self.shared.pool)
+        .fetch_optional(self.shared.schema.on(&self.shared.pool))
         .await?;
 
         let new_leader = match record {
@@ -794,7 +1785,118 @@ impl Worker {
             None => -1,
         };
 
-        self.shared.leader.store(new_leader);
+        // Storing a value wakes up everything watching for leadership changes, so
+        // only do so if the leader actually changed.
+        if new_leader != self.shared.leader.get() {
+            self.shared.leader.store(new_leader);
+
+            let _ = self
+                .shared
+                .lifecycle
+                .send(WorkerEvent::LeaderChanged(LeaderChanged {
+                    leader: (new_leader != -1).then_some(new_leader),
+                    is_self: new_leader == self.worker_id,
+                }));
+        }
+
+        let is_leader = new_leader == self.worker_id;
+        self.shared
+            .metrics
+            .is_leader
+            .set(if is_leader { 1.0 } else { 0.0 });
+
+        Ok(())
+    }
+
+    /// Listen on exactly the channels that tasks are currently waiting on for
+    /// a postgres notification.
+    async fn load_pg_listeners(&mut self) -> anyhow::Result<()> {
+        let channels: HashSet<String> = sqlx::query_scalar!(
+            r#"
+            SELECT DISTINCT channel as "channel!"
+             FROM durable.pg_listener
+            "#
+        )
+        .fetch_all(self.shared.schema.on(&self.shared.pool))
+        .await?
+        .into_iter()
+        .collect();
+
+        let current = self.shared.pg_channels.borrow().clone();
+        for channel in current.difference(&channels) {
+            self.event_source.unlisten(channel).await?;
+        }
+        for channel in channels.difference(&current) {
+            self.event_source.listen(channel).await?;
+        }
+
+        self.shared.pg_channels.send_replace(channels);
+
+        Ok(())
+    }
+
+    /// Start listening on a channel that a task has started waiting on.
+    async fn pg_listen(&mut self, channel: String) -> anyhow::Result<()> {
+        if self.shared.pg_channels.borrow().contains(&channel) {
+            return Ok(());
+        }
+
+        self.event_source.listen(&channel).await?;
+        self.shared.pg_channels.send_modify(|channels| {
+            channels.insert(channel);
+        });
+
+        Ok(())
+    }
+
+    /// Deliver a postgres notification to every task that is waiting on its
+    /// channel.
+    ///
+    /// Every worker receives the notification but only the first one to get
+    /// here delivers it, since delivering it removes the waiting tasks from
+    /// `durable.pg_listener`.
+    async fn deliver_pg_notification(&mut self, notify: event::PgNotify) -> anyhow::Result<()> {
+        sqlx::query!(
+            r#"
+            WITH listeners AS (
+                DELETE FROM durable.pg_listener
+                WHERE channel = $1
+                RETURNING task_id
+            )
+            INSERT INTO durable.notification(task_id, event, data)
+            SELECT
+                task_id,
+                'durable:pg-notification',
+                jsonb_build_object('channel', $1::text, 'payload', $2::text)
+             FROM listeners
+            "#,
+            notify.channel,
+            notify.payload
+        )
+        .execute(self.shared.schema.on(&self.shared.pool))
+        .await?;
+
+        let waiting = sqlx::query_scalar!(
+            r#"
+            SELECT EXISTS(
+                SELECT 1
+                 FROM durable.pg_listener
+                WHERE channel = $1
+            ) as "waiting!"
+            "#,
+            notify.channel
+        )
+        .fetch_one(self.shared.schema.on(&self.shared.pool))
+        .await?;
+
+        // A task that starts waiting on the channel after this point will emit a
+        // `durable:pg-listen` event, which has us listen on it again.
+        if !waiting {
+            self.event_source.unlisten(&notify.channel).await?;
+            self.shared.pg_channels.send_modify(|channels| {
+                channels.remove(&notify.channel);
+            });
+        }
 
         Ok(())
     }
@@ -802,12 +1904,40 @@ impl Worker {
     /// Spawn all new tasks that are scheduled on this server and also those
     /// that aren't scheduled on any server.
     async fn spawn_new_tasks(&mut self, failure: &mpsc::Sender<i64>) -> anyhow::Result<()> {
-        let max_tasks = self.shared.config.max_tasks;
-        let allowed = max_tasks.saturating_sub(self.tasks.len());
+        // Tasks that were claimed ahead of time go first.
+        self.spawn_buffered_tasks(failure)?;
+
+        if self.over_memory_budget() {
+            // We'll check again once one of our own tasks completes and frees up its
+            // memory.
+            self.blocked = true;
+            self.next_wakeup = None;
+            return Ok(());
+        }
+
+        let config = &self.shared.config;
+        let capacity = if self.claim_ahead_paused {
+            config.max_tasks
+        } else {
+            config.max_tasks + config.claim_ahead
+        };
+        let batch_size = config.claim_batch_size.max(1);
+        let allowed = capacity.saturating_sub(self.tasks.len() + self.buffered.len());
         if allowed == 0 {
+            // We'll check again once one of our own tasks completes.
+            self.next_wakeup = None;
             return Ok(());
         }
 
+        // There's no point in claiming tasks for tenants that are already at their
+        // quota since we would just have to hand them back.
+        let exhausted: Vec<String> = self
+            .quotas
+            .iter()
+            .filter(|(_, quota)| quota.available_permits() == 0)
+            .map(|(tenant, _)| tenant.clone())
+            .collect();
+
         let mut tx = self.shared.pool.begin().await?;
         let tasks = sqlx::query_as!(
             TaskData,
@@ -815,31 +1945,113 @@ impl Worker {
             WITH selected AS (
                 SELECT id
                  FROM durable.task
-                WHERE (state IN ('ready', 'active') AND running_on IS NULL)
-                   OR (state = 'ready' AND running_on = $1)
+                WHERE ((state IN ('ready', 'active') AND running_on IS NULL)
+                   OR (state = 'ready' AND running_on = $1))
+                  AND (tenant IS NULL OR NOT tenant = ANY($3::text[]))
+                  AND instance IS NOT DISTINCT FROM $4
+                  AND (wakeup_at IS NULL OR wakeup_at <= NOW())
+                  AND paused_at IS NULL
+                  AND NOT EXISTS(
+                    SELECT 1
+                     FROM durable.program_pause p
+                    WHERE p.wasm = task.wasm
+                  )
+                  AND NOT EXISTS(
+                    SELECT 1
+                     FROM durable.queue_pause q
+                    WHERE q.queue = COALESCE(task.tenant, '')
+                  )
+                  AND NOT EXISTS(
+                    SELECT 1
+                     FROM durable.task_dependency dep
+                     JOIN durable.task parent ON parent.id = dep.depends_on
+                    WHERE dep.task_id = task.id
+                      AND NOT (
+                        parent.state = 'complete'
+                        OR (parent.state = 'failed' AND NOT dep.propagate_failure)
+                      )
+                  )
                 ORDER BY id ASC
                 FOR NO KEY UPDATE SKIP LOCKED
                 LIMIT $2
             )
             UPDATE durable.task
               SET running_on = $1,
-                  state = 'active'
+                  state = 'active',
+                  wakeup_at = NULL,
+                  attempt = task.attempt + 1
              FROM selected
             WHERE selected.id = task.id
             RETURNING
                 task.id         as id,
                 task.name       as name,
                 task.created_at as created_at,
+                task.attempt    as attempt,
+                task.labels     as "labels: Json<BTreeMap<String, String>>",
                 task.wasm       as "wasm!",
-                task.data       as "data!: Json<Box<RawValue>>"
+                COALESCE(
+                    (SELECT data FROM durable.task_payload WHERE task_id = task.id),
+                    task.data
+                )               as "data!: Json<Box<RawValue>>",
+                task.sql_context as "sql_context: Json<BTreeMap<String, String>>",
+                task.entrypoint as entrypoint,
+                task.tenant     as tenant
             "#,
             self.worker_id,
-            allowed as i64
+            allowed.min(batch_size) as i64,
+            &exhausted,
+            config.instance
         )
-        .fetch_all(&mut *tx)
+        .fetch_all(self.shared.schema.on(&mut *tx))
         .await?;
 
-        if tasks.len() + self.tasks.len() >= max_tasks {
+        // If the batch was full then there may be more tasks waiting. We come back for
+        // them once any other pending events have been handled.
+        let more_ready = tasks.len() == batch_size && allowed > batch_size;
+
+        let mut claimed = Vec::with_capacity(tasks.len());
+        let mut deferred = Vec::new();
+        for task in tasks {
+            let quota = task
+                .tenant
+                .as_deref()
+                .and_then(|tenant| Some((tenant, self.shared.config.quota_for(tenant)?)));
+            let Some((tenant, limit)) = quota else {
+                claimed.push((task, None));
+                continue;
+            };
+
+            let quota = self
+                .quotas
+                .entry(tenant.to_owned())
+                .or_insert_with(|| Arc::new(Semaphore::new(limit)));
+            match quota.clone().try_acquire_owned() {
+                Ok(permit) => claimed.push((task, Some(permit))),
+                Err(_) => deferred.push(task.id),
+            }
+        }
+
+        if !deferred.is_empty() {
+            // Hand these back so that another worker can run them. They don't trigger a
+            // notification so we need to check again once our own tasks complete.
+            sqlx::query!(
+                "
+                UPDATE durable.task
+                  SET running_on = NULL,
+                      state = 'ready'
+                WHERE id = ANY($1::bigint[])
+                ",
+                &deferred
+            )
+            .execute(self.shared.schema.on(&mut *tx))
+            .await?;
+
+            self.blocked = true;
+        }
+
+        let tasks = claimed;
+
+        if tasks.len() + self.tasks.len() + self.buffered.len() >= capacity {
             sqlx::query!(
                 "
                 UPDATE durable.task
@@ -849,19 +2061,66 @@ impl Worker {
                 ",
                 self.worker_id
             )
-            .execute(&mut *tx)
+            .execute(self.shared.schema.on(&mut *tx))
             .await?;
 
             self.blocked = true;
         }
 
+        // Tasks launched with a delay don't generate a notification when they become
+        // claimable so we need to set a timer for the earliest one.
+        let wakeup_at = sqlx::query_scalar!(
+            r#"
+            SELECT MIN(wakeup_at) as wakeup_at
+             FROM durable.task
+            WHERE state = 'ready'
+              AND wakeup_at > NOW()
+              AND (running_on IS NULL OR running_on = $1)
+            "#,
+            self.worker_id
+        )
+        .fetch_one(self.shared.schema.on(&mut *tx))
+        .await?;
+
+        self.next_wakeup = wakeup_at.map(|wakeup_at| {
+            let delay = wakeup_at
+                .signed_duration_since(Utc::now())
+                .to_std()
+                .unwrap_or(Duration::ZERO);
+
+            Instant::now() + delay
+        });
+
+        if more_ready {
+            self.next_wakeup = Some(Instant::now());
+        }
+
         tx.commit().await?;
 
         if !tasks.is_empty() {
-            tracing::debug!("launching {} tasks", tasks.len());
+            tracing::debug!("claimed {} tasks", tasks.len());
         }
 
-        for task in tasks {
+        let claimed_at = Instant::now();
+        self.buffered
+            .extend(tasks.into_iter().map(|(task, permit)| BufferedTask {
+                task,
+                permit,
+                claimed_at,
+            }));
+
+        self.spawn_buffered_tasks(failure)
+    }
+
+    /// Start as many of the claimed tasks as the worker has room for.
+    fn spawn_buffered_tasks(&mut self, failure: &mpsc::Sender<i64>) -> anyhow::Result<()> {
+        let max_tasks = self.shared.config.max_tasks;
+
+        while self.tasks.len() < max_tasks && !self.over_memory_budget() {
+            let Some(BufferedTask { task, permit, .. }) = self.buffered.pop_front() else {
+                break;
+            };
+
             let shared = self.shared.clone();
             let engine = self.engine.clone();
             let worker_id = self.worker_id;
@@ -877,6 +2136,8 @@ impl Worker {
             let active_tasks = self.active_tasks.clone();
             let future = async move {
                 let _guard = MetricSpan::enter(active_tasks);
+                let _active = ActiveTaskGuard::enter(shared.clone());
+                let _permit: Option<OwnedSemaphorePermit> = permit;
                 let task_id = task.id;
                 if let Err(e) = Self::run_task(shared, engine, task, worker_id)
                     .instrument(tracing::info_span!("task", task_id))
@@ -906,6 +2167,83 @@ impl Worker {
         Ok(())
     }
 
+    /// Whether the tasks on this worker are using more memory than allowed by
+    /// [`Config::memory_budget`].
+    ///
+    /// A worker with no running tasks is never over budget so that it can
+    /// always make progress.
+    fn over_memory_budget(&self) -> bool {
+        let Some(budget) = self.shared.config.memory_budget else {
+            return false;
+        };
+
+        !self.tasks.is_empty() && self.shared.memory.used() >= budget
+    }
+
+    /// When the oldest task claimed ahead of time should be released.
+    fn buffer_deadline(&self) -> Option<Instant> {
+        let task = self.buffered.front()?;
+        Some(task.claimed_at + self.shared.config.claim_ahead_timeout)
+    }
+
+    /// Release tasks that were claimed ahead of time but have waited longer
+    /// than [`Config::claim_ahead_timeout`] to be started, so that other
+    /// workers can run them instead.
+    async fn release_buffered_tasks(&mut self) -> anyhow::Result<()> {
+        let now = Instant::now();
+        let mut released = Vec::new();
+        while self
+            .buffer_deadline()
+            .is_some_and(|deadline| deadline <= now)
+        {
+            if let Some(buffered) = self.buffered.pop_front() {
+                released.push(buffered.task.id);
+            }
+        }
+
+        if released.is_empty() {
+            return Ok(());
+        }
+
+        tracing::debug!("releasing {} tasks claimed ahead of time", released.len());
+
+        let mut tx = self.shared.pool.begin().await?;
+        let released = sqlx::query_scalar!(
+            "
+            UPDATE durable.task
+              SET state = 'ready',
+                  running_on = NULL
+            WHERE id = ANY($1::bigint[])
+              AND running_on = $2
+            RETURNING id
+            ",
+            &released,
+            self.worker_id
+        )
+        .fetch_all(self.shared.schema.on(&mut *tx))
+        .await?;
+
+        // Going from active to ready doesn't notify the workers, so we need to
+        // let them know that the released tasks can be claimed again.
+        let payloads: Vec<_> = released
+            .iter()
+            .map(|&id| serde_json::json!({ "id": id, "running_on": null }).to_string())
+            .collect();
+        sqlx::query("SELECT pg_notify('durable:task', payload) FROM unnest($1::text[]) AS payload")
+            .bind(&payloads)
+            .execute(self.shared.schema.on(&mut *tx))
+            .await?;
+
+        tx.commit().await?;
+
+        // Check again once our own tasks complete, in case the released tasks are not
+        // picked up by another worker.
+        self.blocked = true;
+        self.claim_ahead_paused = true;
+
+        Ok(())
+    }
+
     async fn run_task(
         shared: Arc<SharedState>,
         engine: wasmtime::Engine,
@@ -913,15 +2251,39 @@ impl Worker {
         worker_id: i64,
     ) -> anyhow::Result<()> {
         let task_id = task.id;
+        let task_name = task.name.clone();
+        let start = Instant::now();
+        let info = TaskInfo {
+            id: task.id,
+            name: task.name.clone(),
+            wasm: task.wasm,
+            tenant: task.tenant.clone(),
+            labels: task.labels.0.clone(),
+            attempt: task.attempt,
+            created_at: task.created_at,
+            started: start.into_std(),
+        };
 
         shared.metrics.task_spawn.increment(1);
+        let _ = shared.lifecycle.send(WorkerEvent::TaskStarted(TaskStarted {
+            id: task_id,
+            name: task.name.clone(),
+            attempt: task.attempt,
+            tenant: task.tenant.clone(),
+        }));
 
         // We are using the loop here to do some early breaks.
         #[allow(clippy::never_loop)]
-        let status = loop {
-            let future = Self::run_task_impl(shared.clone(), engine, task, worker_id);
+        let (status, failure) = loop {
+            let future = async {
+                for layer in &shared.layers {
+                    layer.before(&info).await?;
+                }
+
+                Self::run_task_impl(shared.clone(), engine, task, worker_id).await
+            };
             break match AssertUnwindSafe(future).catch_unwind().await {
-                Ok(Ok(status)) => status,
+                Ok(Ok(result)) => result,
                 Ok(Err(error)) => {
                     match find_sqlx_error(&error) {
                         // These errors are external to the runtime and should usually be resolvable
@@ -950,14 +2312,22 @@ impl Worker {
                                 task_id,
                                 worker_id
                             )
-                            .execute(&shared.pool)
+                            .execute(shared.schema.on(&shared.pool))
                             .await?;
 
-                            break TaskStatus::Suspend;
+                            // Going from active to ready doesn't notify the workers, so we need to
+                            // let them know that the task can be claimed again.
+                            let payload = serde_json::json!({ "id": task_id, "running_on": null });
+                            sqlx::query("SELECT pg_notify('durable:task', $1)")
+                                .bind(payload.to_string())
+                                .execute(shared.schema.on(&shared.pool))
+                                .await?;
+
+                            break (TaskStatus::Suspend, None);
                         }
                         Some(sqlx::Error::PoolClosed) => {
                             // Nothing we can do, since we can't make database queries.
-                            break TaskStatus::Suspend;
+                            break (TaskStatus::Suspend, None);
                         }
                         _ => (),
                     }
@@ -971,14 +2341,17 @@ impl Worker {
                         LOG_ERROR_INDEX,
                         message
                     )
-                    .execute(&shared.pool)
+                    .execute(shared.schema.on(&shared.pool))
                     .await;
 
                     if let Err(e) = result {
                         tracing::error!("failed to save error logs to the database: {e}");
                     }
 
-                    TaskStatus::ExitFailure
+                    (
+                        TaskStatus::ExitFailure,
+                        Some(TaskFailure::from_error(&error)),
+                    )
                 }
                 Err(payload) => {
                     let message: &str = if let Some(message) = payload.downcast_ref::<String>() {
@@ -998,29 +2371,44 @@ impl Worker {
                         LOG_PANIC_INDEX,
                         format!("task panicked: {message}\n")
                     )
-                    .execute(&shared.pool)
+                    .execute(shared.schema.on(&shared.pool))
                     .await;
 
                     if let Err(e) = result {
                         tracing::error!("failed to save error logs to the database: {e}");
                     }
 
-                    TaskStatus::ExitFailure
+                    (
+                        TaskStatus::ExitFailure,
+                        Some(TaskFailure::new(format!("task panicked: {message}"))),
+                    )
                 }
             };
         };
 
-        match status {
+        let outcome = match status {
             TaskStatus::NotScheduledOnWorker => {
                 tracing::debug!("task {task_id} was taken by another worker");
 
                 // Don't do anything since we no longer own this task.
                 shared.metrics.task_taken.increment(1);
+
+                TaskOutcome::Taken
             }
             TaskStatus::Suspend => {
                 // The task should have set itself to the suspended state before
                 // returning this error code. Nothing we need to do here.
                 shared.metrics.task_suspend.increment(1);
+
+                let _ = shared
+                    .lifecycle
+                    .send(WorkerEvent::TaskSuspended(TaskSuspended {
+                        id: task_id,
+                        name: task_name,
+                        elapsed: start.elapsed(),
+                    }));
+
+                TaskOutcome::Suspended
             }
             TaskStatus::ExitSuccess => {
                 sqlx::query!(
@@ -1030,29 +2418,67 @@ impl Worker {
                         running_on = NULL,
                         wasm = NULL
                     WHERE id = $1
+                      AND running_on = $2
                     ",
-                    task_id
+                    task_id,
+                    worker_id
                 )
-                .execute(&shared.pool)
+                .execute(shared.schema.on(&shared.pool))
                 .await?;
 
                 shared.metrics.task_complete.increment(1);
+
+                let _ = shared
+                    .lifecycle
+                    .send(WorkerEvent::TaskCompleted(TaskCompleted {
+                        id: task_id,
+                        name: task_name,
+                        elapsed: start.elapsed(),
+                    }));
+
+                TaskOutcome::Completed
             }
             TaskStatus::ExitFailure => {
+                let failure = failure.unwrap_or_else(|| TaskFailure::new("the task failed"));
+
                 sqlx::query!(
                     "UPDATE durable.task
                     SET state = 'failed',
                         completed_at = CURRENT_TIMESTAMP,
                         running_on = NULL,
-                        wasm = NULL
-                    WHERE id = $1",
-                    task_id
+                        failure_message = $3,
+                        failure_label = $4,
+                        failure_index = $5,
+                        failure_backtrace = $6
+                    WHERE id = $1
+                      AND running_on = $2",
+                    task_id,
+                    worker_id,
+                    &failure.message,
+                    failure.label,
+                    failure.index,
+                    failure.backtrace
                 )
-                .execute(&shared.pool)
+                .execute(shared.schema.on(&shared.pool))
                 .await?;
 
                 shared.metrics.task_failed.increment(1);
+
+                let _ = shared.lifecycle.send(WorkerEvent::TaskFailed(TaskFailed {
+                    id: task_id,
+                    name: task_name,
+                    elapsed: start.elapsed(),
+                    message: failure.message.clone(),
+                }));
+
+                TaskOutcome::Failed {
+                    message: failure.message,
+                }
             }
+        };
+
+        for layer in shared.layers.iter().rev() {
+            layer.after(&info, &outcome).await;
         }
 
         tracing::trace!("task exited with status {status:?}");
@@ -1060,33 +2486,136 @@ impl Worker {
         Ok(())
     }
 
+    /// Attempt to load a precompiled artifact for the program with id `wasm`.
+    ///
+    /// Returns `Ok(None)` if there is no artifact compatible with `engine` or
+    /// if the artifact could not be loaded.
+    async fn load_precompiled(
+        shared: &SharedState,
+        engine: &wasmtime::Engine,
+        wasm: i64,
+    ) -> anyhow::Result<Option<Component>> {
+        let compat = durable_migrate::compat_hash(engine.precompile_compatibility_hash());
+        let artifact = sqlx::query_scalar!(
+            "SELECT artifact
+              FROM durable.wasm_artifact
+             WHERE wasm = $1
+               AND compat = $2",
+            wasm,
+            &compat as &[u8]
+        )
+        .fetch_optional(shared.schema.on(&shared.pool))
+        .await?;
+
+        let artifact = match artifact {
+            Some(artifact) => artifact,
+            None => return Ok(None),
+        };
+
+        let start = Instant::now();
+        let result = tokio::task::spawn_blocking({
+            let engine = engine.clone();
+
+            // SAFETY: Loading precompiled artifacts is gated behind the
+            //         load_precompiled_programs config option, which documents that
+            //         the database must be trusted when it is enabled.
+            move || unsafe { Component::deserialize(&engine, &artifact) }
+        })
+        .await
+        .context("component deserialization panicked")?;
+
+        match result {
+            Ok(component) => {
+                tracing::debug!(
+                    target: "durable_runtime::worker::task_compile",
+                    id = wasm,
+                    "loading precompiled module took {}",
+                    humantime::Duration::from(start.elapsed())
+                );
+
+                Ok(Some(component))
+            }
+            Err(e) => {
+                tracing::warn!(
+                    target: "durable_runtime::worker::task_compile",
+                    id = wasm,
+                    "failed to load precompiled module, falling back to compiling it: {e}"
+                );
+
+                Ok(None)
+            }
+        }
+    }
+
+    /// Load the component for a program, either from its precompiled artifact
+    /// or by compiling it.
+    async fn load_program(
+        shared: &SharedState,
+        engine: &wasmtime::Engine,
+        wasm: i64,
+    ) -> anyhow::Result<Component> {
+        if shared.config.load_precompiled_programs {
+            if let Some(component) = Self::load_precompiled(shared, engine, wasm).await? {
+                return Ok(component);
+            }
+        }
+
+        let record = sqlx::query!("SELECT wasm FROM durable.wasm WHERE id = $1", wasm)
+            .fetch_one(shared.schema.on(&shared.pool))
+            .await?;
+
+        // If an error occurs then we just allow ourselves to proceed anyway.
+        let _permit = shared.compile_sema.acquire().await;
+
+        let bytes = record.wasm;
+        let start = Instant::now();
+        let component = tokio::task::spawn_blocking({
+            let engine = engine.clone();
+            move || Component::new(&engine, &bytes)
+        })
+        .await
+        .context("component compilation panicked")??;
+
+        let elapsed = start.elapsed();
+        tracing::debug!(
+            target: "durable_runtime::worker::task_compile",
+            id = wasm,
+            "compiling new module took {}",
+            humantime::Duration::from(elapsed)
+        );
+
+        shared.metrics.wasm_compile_latency.record(elapsed);
+
+        Ok(component)
+    }
+
     async fn run_task_impl(
         shared: Arc<SharedState>,
         engine: wasmtime::Engine,
         task: TaskData,
         worker_id: i64,
-    ) -> anyhow::Result<TaskStatus> {
+    ) -> anyhow::Result<(TaskStatus, Option<TaskFailure>)> {
         use wasmtime::component::*;
 
-        use crate::bindings::Imports;
-
         // tracing::info!(
         //     target: "durable_runtime::worker::task_launch",
         //     "launching task `{}`", task.name);
-        let component = {
+        let (component, pre) = {
             let mut cache = shared.cache.lock().await;
 
             match cache.find(|entry| entry.id == task.wasm) {
-                Some(entry) => entry.value.clone(),
+                Some(entry) => (entry.value.clone(), entry.pre.clone()),
                 None => {
                     let cached = Arc::new(Cached::new());
+                    let pre = Arc::new(OnceLock::new());
 
                     cache.insert(ProgramCache {
                         id: task.wasm,
                         value: cached.clone(),
+                        pre: pre.clone(),
                     });
 
-                    cached
+                    (cached, pre)
                 }
             }
         };
@@ -1095,65 +2624,120 @@ impl Worker {
         // once. Compiling one is an expensive operation, so if
         let component = component
             .get_or_compute(|| async {
-                let record = sqlx::query!("SELECT wasm FROM durable.wasm WHERE id = $1", task.wasm)
-                    .fetch_one(&shared.pool)
-                    .await
-                    .map_err(anyhow::Error::from)?;
-
-                // If an error occurs then we just allow ourselves to proceed anyway.
-                let _permit = shared.compile_sema.acquire().await;
-
-                let wasm = record.wasm;
-                let start = Instant::now();
-                let component = tokio::task::spawn_blocking({
-                    let engine = engine.clone();
-                    move || Component::new(&engine, &wasm)
-                })
-                .await
-                .context("component compilation panicked")?
-                .map_err(anyhow::Error::from)?;
-
-                let elapsed = start.elapsed();
-                tracing::debug!(
-                    target: "durable_runtime::worker::task_compile",
-                    id = task.wasm,
-                    "compiling new module took {}",
-                    humantime::Duration::from(elapsed)
-                );
-
-                shared.metrics.wasm_compile_latency.record(elapsed);
+                let component = Self::load_program(&shared, &engine, task.wasm).await?;
+
+                // The imports of a program never change, so they only need to be checked
+                // once per worker rather than every time a task is launched.
+                let deprecated = crate::plugin::durable::version::check_imports(
+                    &engine,
+                    &component,
+                    shared.config.deny_deprecated_interfaces,
+                )?;
+                if !deprecated.is_empty() {
+                    tracing::warn!(
+                        target: "durable_runtime::worker::task_compile",
+                        id = task.wasm,
+                        "program imports deprecated durable:core interfaces: {}",
+                        deprecated.join(", ")
+                    );
+                    shared.metrics.deprecated_interface_imports.increment(1);
+                }
 
                 Ok(component)
             })
             .await?;
 
+        let sql_policy = shared
+            .sql_policies
+            .resolve(
+                &shared.schema,
+                &shared.pool,
+                task.wasm,
+                task.tenant.as_deref(),
+            )
+            .await
+            .context("failed to resolve the SQL policy for the task")?;
+
         let task_id = task.id;
+        let entrypoint = task.entrypoint.clone();
         let mut task = Task {
             state: TaskState::new(shared.clone(), task, worker_id),
             plugins: Default::default(),
             resources: crate::Resources::default(),
         };
+        task.state.set_sql_policy(sql_policy);
+        task.state.wait_for_turn().await;
+
+        let pooled = shared.config.instance_pool.is_some();
+        let instance_pre = match pre.get() {
+            // The program has already been pre-instantiated so the plugins
+            // only need to set up their state.
+            Some(instance_pre) if pooled => {
+                for plugin in shared.plugins.iter() {
+                    plugin
+                        .setup_state(&engine, &mut task)
+                        .with_context(|| format!("failed to set up plugin `{}`", plugin.name()))?;
+                }
 
-        let mut linker = Linker::new(&engine);
-        for plugin in shared.plugins.iter() {
-            plugin
-                .setup(&mut linker, &mut task)
-                .with_context(|| format!("failed to set up plugin `{}`", plugin.name()))?;
-        }
+                instance_pre.clone()
+            }
+            _ => {
+                let mut linker = Linker::new(&engine);
+                for plugin in shared.plugins.iter() {
+                    plugin
+                        .setup(&mut linker, &mut task)
+                        .with_context(|| format!("failed to set up plugin `{}`", plugin.name()))?;
+                }
+
+                // Workflows can check which interfaces are available via
+                // durable:core/capabilities, so imports that the worker doesn't provide
+                // only fail once they are actually called.
+                if !shared.config.strict_imports {
+                    linker
+                        .define_unknown_imports_as_traps(&component)
+                        .context("failed to define unknown imports as traps")?;
+                }
+
+                let instance_pre = linker
+                    .instantiate_pre(&component)
+                    .context("failed to instantiate the wasm component")?;
+                if pooled {
+                    let _ = pre.set(instance_pre.clone());
+                }
 
-        // linker
-        //     .define_unknown_imports_as_traps(&component)
-        //     .context("failed to define unknown imports as traps")?;
+                instance_pre
+            }
+        };
 
         let mut store = wasmtime::Store::new(&engine, task);
+        store.limiter(|task| task.state.memory_mut());
+
+        if shared.config.epoch_interval.is_some_and(|i| !i.is_zero()) {
+            // Every time the epoch ticks over we give tokio a chance to run other tasks.
+            // Workflows stuck in a CPU-bound loop are then still aborted when the worker
+            // shuts down.
+            let shared = shared.clone();
+            store.set_epoch_deadline(1);
+            store.epoch_deadline_callback(move |_| {
+                if shared.shutdown.is_raised() {
+                    return Err(anyhow::Error::new(TaskStatus::NotScheduledOnWorker));
+                }
 
-        let instance = Imports::instantiate_async(&mut store, &component, &linker)
+                Ok(wasmtime::UpdateDeadline::Yield(1))
+            });
+        }
+
+        let instance = instance_pre
+            .instantiate_async(&mut store)
             .await
             .context("failed to instantiate the wasm component")?;
-        let guest = instance.wasi_cli_run();
+
+        store
+            .data_mut()
+            .run_plugin_hooks(|plugin, task| plugin.on_task_start(task))?;
 
         let mut error = None;
-        let status = match guest.call_run(&mut store).await {
+        let status = match call_guest(&mut store, &instance, entrypoint.as_deref()).await {
             Ok(Ok(())) => TaskStatus::ExitSuccess,
             Ok(Err(())) => TaskStatus::ExitFailure,
             Err(e) => {
@@ -1167,7 +2751,7 @@ impl Worker {
         };
 
         if !status.is_final() {
-            return Ok(status);
+            return Ok((status, None));
         }
 
         // Some errors are recoverable. We handle those at up one method so that retries
@@ -1177,32 +2761,20 @@ impl Worker {
             error => error,
         };
 
-        if let Some(txn) = store.data_mut().state.transaction_mut() {
-            let index = txn.index();
-            let logs = std::mem::take(&mut txn.logs);
-
-            if !logs.is_empty() {
-                tracing::debug!(
-                    target: "durable::task_log",
-                    "task {}: {}",
-                    task_id,
-                    logs.trim_end()
-                );
+        // Make sure that any output the workflow wrote after its last transaction
+        // (e.g. a panic message) makes it into the task logs.
+        if let Err(e) = store.data_mut().state.flush_logs().await {
+            tracing::error!("failed to save remaining logs to the database: {e}");
+        }
 
-                if let Err(e) = sqlx::query!(
-                    "INSERT INTO durable.log(task_id, index, message)
-                     VALUES ($1, $2, $3)",
-                    task_id,
-                    index,
-                    logs
-                )
-                .execute(&shared.pool)
-                .await
-                {
-                    tracing::error!("failed to save remaining logs to the database: {e}");
-                }
+        let failure = match (&error, status) {
+            (Some(error), _) => Some(TaskFailure::from_error(error)),
+            (None, TaskStatus::ExitFailure) => {
+                Some(TaskFailure::new("the workflow returned an error"))
             }
-        }
+            _ => None,
+        };
+        let failure = failure.map(|failure| failure.with_state(&store.data().state));
 
         if let Some(error) = error {
             let message = format!("{error:?}\n");
@@ -1219,7 +2791,7 @@ impl Worker {
                 LOG_ERROR_INDEX,
                 message
             )
-            .execute(&shared.pool)
+            .execute(shared.schema.on(&shared.pool))
             .await;
 
             if let Err(e) = result {
@@ -1227,11 +2799,58 @@ impl Worker {
             }
         }
 
-        Ok(status)
+        let _ = store.data_mut().run_plugin_hooks(|plugin, task| {
+            plugin.on_task_complete(task, status);
+            Ok(())
+        });
+
+        Ok((status, failure))
     }
 }
 
-fn as_task_exit(error: &anyhow::Error) -> Option<TaskStatus> {
+/// Run the workflow within an instantiated program.
+///
+/// Tasks without an entrypoint run the program's `wasi:cli/run` export. Tasks
+/// with one call the `durable:core/workflow` export with the entrypoint name.
+pub(crate) async fn call_guest(
+    store: &mut wasmtime::Store<Task>,
+    instance: &wasmtime::component::Instance,
+    entrypoint: Option<&str>,
+) -> anyhow::Result<Result<(), ()>> {
+    use crate::bindings::Imports;
+
+    const WORKFLOW_INTERFACE: &str = "durable:core/workflow@2.7.0";
+
+    let entrypoint = match entrypoint {
+        Some(entrypoint) => entrypoint,
+        None => {
+            let guest = Imports::new(&mut *store, instance)?;
+            return guest.wasi_cli_run().call_run(&mut *store).await;
+        }
+    };
+
+    let interface = instance
+        .get_export(&mut *store, None, WORKFLOW_INTERFACE)
+        .with_context(|| {
+            format!(
+                "task has entrypoint `{entrypoint}` but the program does not export \
+                 `{WORKFLOW_INTERFACE}`"
+            )
+        })?;
+    let run = instance
+        .get_export(&mut *store, Some(&interface), "run")
+        .with_context(|| format!("`{WORKFLOW_INTERFACE}` export has no `run` function"))?;
+    let run = instance
+        .get_typed_func::<(&str,), (Result<(), ()>,)>(&mut *store, &run)
+        .with_context(|| format!("`{WORKFLOW_INTERFACE}#run` has the wrong type"))?;
+
+    let (result,) = run.call_async(&mut *store, (entrypoint,)).await?;
+    run.post_return_async(&mut *store).await?;
+
+    Ok(result)
+}
+
+pub(crate) fn as_task_exit(error: &anyhow::Error) -> Option<TaskStatus> {
     error
         .chain()
         .filter_map(|e| e.downcast_ref::<TaskStatus>())
@@ -1241,22 +2860,34 @@ fn as_task_exit(error: &anyhow::Error) -> Option<TaskStatus> {
 
 pub struct PgEventSource {
     listener: sqlx::postgres::PgListener,
+    schema: SchemaRename,
 }
 
 impl PgEventSource {
-    pub async fn new(pool: &sqlx::PgPool) -> sqlx::Result<Self> {
+    pub async fn new(pool: &sqlx::PgPool, schema: &SchemaRename) -> sqlx::Result<Self> {
         let mut listener = sqlx::postgres::PgListener::connect_with(pool).await?;
 
+        let channels = [
+            "durable:task",
+            "durable:task-suspend",
+            "durable:notification",
+            "durable:worker",
+            "durable:statement-cache",
+            "durable:pg-listen",
+        ];
         listener
-            .listen_all([
-                "durable:task",
-                "durable:task-suspend",
-                "durable:notification",
-                "durable:worker",
-            ])
+            .listen_all(
+                channels
+                    .map(|channel| schema.channel(channel))
+                    .iter()
+                    .map(|c| &**c),
+            )
             .await?;
 
-        Ok(Self { listener })
+        Ok(Self {
+            listener,
+            schema: schema.clone(),
+        })
     }
 }
 
@@ -1282,20 +2913,40 @@ impl EventSource for PgEventSource {
                 Ok(Some(event)) => {
                     tracing::trace!("received event {}: {}", event.channel(), event.payload());
 
-                    match event.channel() {
-                        "durable:task" => Ok(parse_event("durable:task", &event, Event::Task)),
-                        "durable:task-suspend" => Ok(parse_event(
+                    // Channels are prefixed with the schema name instead of
+                    // `durable` when the schema has been renamed.
+                    let channel = event
+                        .channel()
+                        .strip_prefix(self.schema.to())
+                        .and_then(|channel| channel.strip_prefix(':'));
+                    let Some(channel) = channel else {
+                        // Anything else is a channel that a task is waiting on.
+                        return Ok(Event::PgNotify(event::PgNotify {
+                            channel: event.channel().to_owned(),
+                            payload: event.payload().to_owned(),
+                        }));
+                    };
+
+                    match channel {
+                        "task" => Ok(parse_event("durable:task", &event, Event::Task)),
+                        "task-suspend" => Ok(parse_event(
                             "durable:task-suspend",
                             &event,
                             Event::TaskSuspend,
                         )),
-                        "durable:notification" => Ok(parse_event(
+                        "notification" => Ok(parse_event(
                             "durable:notification",
                             &event,
                             Event::Notification,
                         )),
-                        "durable:worker" => {
-                            Ok(parse_event("durable:worker", &event, Event::Worker))
+                        "worker" => Ok(parse_event("durable:worker", &event, Event::Worker)),
+                        "statement-cache" => Ok(parse_event(
+                            "durable:statement-cache",
+                            &event,
+                            Event::StatementCache,
+                        )),
+                        "pg-listen" => {
+                            Ok(parse_event("durable:pg-listen", &event, Event::PgListen))
                         }
                         _ => continue,
                     }
@@ -1324,12 +2975,31 @@ impl EventSource for PgEventSource {
             };
         }
     }
+
+    async fn listen(&mut self, channel: &str) -> anyhow::Result<()> {
+        self.listener.listen(channel).await?;
+        Ok(())
+    }
+
+    async fn unlisten(&mut self, channel: &str) -> anyhow::Result<()> {
+        self.listener.unlisten(channel).await?;
+        Ok(())
+    }
 }
 
 enum LoopEvent {
     Event(Event),
     TaskComplete,
     TaskFailed(i64),
+    Wakeup,
+    BufferExpired,
+}
+
+/// A task that has been claimed by the worker but not yet started.
+struct BufferedTask {
+    task: TaskData,
+    permit: Option<OwnedSemaphorePermit>,
+    claimed_at: Instant,
 }
 
 fn find_sqlx_error(error: &anyhow::Error) -> Option<&sqlx::Error> {
@@ -40,7 +40,7 @@ pub mod durable {
                     unreachable!();
                     #[cfg(target_arch = "wasm32")]
                     {
-                        #[link(wasm_import_module = "durable:core/sql@2.5.0")]
+                        #[link(wasm_import_module = "durable:core/sql@2.7.0")]
                         extern "C" {
                             #[link_name = "[resource-drop]type-info"]
                             fn drop(_: u32);
@@ -181,6 +181,25 @@ pub mod durable {
                     }
                 }
             }
+            /// A value of a type that the runtime does not natively support.
+            #[derive(Clone, serde::Deserialize, serde::Serialize)]
+            pub struct RawValue {
+                /// The oid of the value's type.
+                pub oid: u32,
+                /// The value, in the postgres binary format for its type.
+                pub data: _rt::Vec<u8>,
+            }
+            impl ::core::fmt::Debug for RawValue {
+                fn fmt(
+                    &self,
+                    f: &mut ::core::fmt::Formatter<'_>,
+                ) -> ::core::fmt::Result {
+                    f.debug_struct("RawValue")
+                        .field("oid", &self.oid)
+                        .field("data", &self.data)
+                        .finish()
+                }
+            }
             /// A database value.
             ///
             /// This is opaque so that new value types can be added in the future
@@ -213,7 +232,7 @@ pub mod durable {
                     unreachable!();
                     #[cfg(target_arch = "wasm32")]
                     {
-                        #[link(wasm_import_module = "durable:core/sql@2.5.0")]
+                        #[link(wasm_import_module = "durable:core/sql@2.7.0")]
                         extern "C" {
                             #[link_name = "[resource-drop]value"]
                             fn drop(_: u32);
@@ -422,6 +441,143 @@ pub mod durable {
                     }
                 }
             }
+            impl ::core::fmt::Display for Error {
+                fn fmt(
+                    &self,
+                    f: &mut ::core::fmt::Formatter<'_>,
+                ) -> ::core::fmt::Result {
+                    write!(f, "{:?}", self)
+                }
+            }
+            impl std::error::Error for Error {}
+            /// A description of a single output column of a statement.
+            #[derive(serde::Deserialize, serde::Serialize)]
+            pub struct ColumnDescription {
+                pub name: _rt::String,
+                pub type_info: TypeInfo,
+                /// Whether the column can be null, if this could be determined.
+                pub nullable: Option<bool>,
+            }
+            impl ::core::fmt::Debug for ColumnDescription {
+                fn fmt(
+                    &self,
+                    f: &mut ::core::fmt::Formatter<'_>,
+                ) -> ::core::fmt::Result {
+                    f.debug_struct("ColumnDescription")
+                        .field("name", &self.name)
+                        .field("type-info", &self.type_info)
+                        .field("nullable", &self.nullable)
+                        .finish()
+                }
+            }
+            /// The parameters and output columns of a statement.
+            #[derive(serde::Deserialize, serde::Serialize)]
+            pub struct StatementDescription {
+                pub parameters: _rt::Vec<TypeInfo>,
+                pub columns: _rt::Vec<ColumnDescription>,
+            }
+            impl ::core::fmt::Debug for StatementDescription {
+                fn fmt(
+                    &self,
+                    f: &mut ::core::fmt::Formatter<'_>,
+                ) -> ::core::fmt::Result {
+                    f.debug_struct("StatementDescription")
+                        .field("parameters", &self.parameters)
+                        .field("columns", &self.columns)
+                        .finish()
+                }
+            }
+            /// A notification sent via a postgres `NOTIFY` command.
+            #[derive(Clone, serde::Deserialize, serde::Serialize)]
+            pub struct PgNotification {
+                pub channel: _rt::String,
+                pub payload: _rt::String,
+            }
+            impl ::core::fmt::Debug for PgNotification {
+                fn fmt(
+                    &self,
+                    f: &mut ::core::fmt::Formatter<'_>,
+                ) -> ::core::fmt::Result {
+                    f.debug_struct("PgNotification")
+                        .field("channel", &self.channel)
+                        .field("payload", &self.payload)
+                        .finish()
+                }
+            }
+            /// Options for a query run via `query2`.
+            #[repr(C)]
+            #[derive(Clone, Copy, serde::Deserialize, serde::Serialize)]
+            pub struct Options2 {
+                /// Allows the runtime to limit the number of rows returned.
+                ///
+                /// Setting limit > 1 means that all rows will be returned.
+                pub limit: u8,
+                /// Whether the runtime should keep the state in its query cache.
+                pub persistent: bool,
+                /// Whether the runtime should collect execution statistics for the
+                /// query. These can be retrieved via `last-query-stats` once all results of
+                /// the query have been fetched.
+                ///
+                /// Collecting statistics requires that the runtime also run the query
+                /// under `EXPLAIN ANALYZE` within a savepoint that is then rolled back.
+                /// This means that the query is executed twice.
+                pub collect_stats: bool,
+            }
+            impl ::core::fmt::Debug for Options2 {
+                fn fmt(
+                    &self,
+                    f: &mut ::core::fmt::Formatter<'_>,
+                ) -> ::core::fmt::Result {
+                    f.debug_struct("Options2")
+                        .field("limit", &self.limit)
+                        .field("persistent", &self.persistent)
+                        .field("collect-stats", &self.collect_stats)
+                        .finish()
+                }
+            }
+            /// Execution statistics for a query.
+            #[repr(C)]
+            #[derive(Clone, Copy, serde::Deserialize, serde::Serialize)]
+            pub struct QueryStats {
+                /// The time, in nanoseconds, that the runtime spent executing the
+                /// query and fetching all of its results.
+                pub duration: u64,
+                /// The execution time, in nanoseconds, reported by the database via
+                /// `EXPLAIN ANALYZE`.
+                pub execution_time: Option<u64>,
+                /// The number of table rows scanned while executing the query, as
+                /// reported by `EXPLAIN ANALYZE`.
+                pub rows_scanned: Option<u64>,
+            }
+            impl ::core::fmt::Debug for QueryStats {
+                fn fmt(
+                    &self,
+                    f: &mut ::core::fmt::Formatter<'_>,
+                ) -> ::core::fmt::Result {
+                    f.debug_struct("QueryStats")
+                        .field("duration", &self.duration)
+                        .field("execution-time", &self.execution_time)
+                        .field("rows-scanned", &self.rows_scanned)
+                        .finish()
+                }
+            }
+            /// A single statement within a batch run via `batch`.
+            #[derive(serde::Deserialize, serde::Serialize)]
+            pub struct BatchStatement {
+                pub sql: _rt::String,
+                pub params: _rt::Vec<Value>,
+            }
+            impl ::core::fmt::Debug for BatchStatement {
+                fn fmt(
+                    &self,
+                    f: &mut ::core::fmt::Formatter<'_>,
+                ) -> ::core::fmt::Result {
+                    f.debug_struct("BatchStatement")
+                        .field("sql", &self.sql)
+                        .field("params", &self.params)
+                        .finish()
+                }
+            }
             impl TypeInfo {
                 #[allow(unused_unsafe, clippy::all)]
                 /// The database system name of this type.
@@ -438,7 +594,7 @@ pub mod durable {
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
                         #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "durable:core/sql@2.5.0")]
+                        #[link(wasm_import_module = "durable:core/sql@2.7.0")]
                         extern "C" {
                             #[link_name = "[method]type-info.name"]
                             fn wit_import(_: i32, _: *mut u8);
@@ -462,7 +618,7 @@ pub mod durable {
                 pub fn compatible(&self, other: &TypeInfo) -> bool {
                     unsafe {
                         #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "durable:core/sql@2.5.0")]
+                        #[link(wasm_import_module = "durable:core/sql@2.7.0")]
                         extern "C" {
                             #[link_name = "[method]type-info.compatible"]
                             fn wit_import(_: i32, _: i32) -> i32;
@@ -485,7 +641,7 @@ pub mod durable {
                 pub fn equal(&self, other: &TypeInfo) -> bool {
                     unsafe {
                         #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "durable:core/sql@2.5.0")]
+                        #[link(wasm_import_module = "durable:core/sql@2.7.0")]
                         extern "C" {
                             #[link_name = "[method]type-info.equal"]
                             fn wit_import(_: i32, _: i32) -> i32;
@@ -508,7 +664,7 @@ pub mod durable {
                 pub fn clone(&self) -> TypeInfo {
                     unsafe {
                         #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "durable:core/sql@2.5.0")]
+                        #[link(wasm_import_module = "durable:core/sql@2.7.0")]
                         extern "C" {
                             #[link_name = "[method]type-info.clone"]
                             fn wit_import(_: i32) -> i32;
@@ -536,7 +692,7 @@ pub mod durable {
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
                         #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "durable:core/sql@2.5.0")]
+                        #[link(wasm_import_module = "durable:core/sql@2.7.0")]
                         extern "C" {
                             #[link_name = "[method]type-info.serialize"]
                             fn wit_import(_: i32, _: *mut u8);
@@ -596,7 +752,7 @@ pub mod durable {
                         let len0 = vec0.len();
                         let ptr1 = ret_area.0.as_mut_ptr().cast::<u8>();
                         #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "durable:core/sql@2.5.0")]
+                        #[link(wasm_import_module = "durable:core/sql@2.7.0")]
                         extern "C" {
                             #[link_name = "[static]type-info.deserialize"]
                             fn wit_import(_: *mut u8, _: usize, _: *mut u8);
@@ -656,7 +812,7 @@ pub mod durable {
                         let len0 = vec0.len();
                         let ptr1 = ret_area.0.as_mut_ptr().cast::<u8>();
                         #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "durable:core/sql@2.5.0")]
+                        #[link(wasm_import_module = "durable:core/sql@2.7.0")]
                         extern "C" {
                             #[link_name = "[static]type-info.with-name"]
                             fn wit_import(_: *mut u8, _: usize, _: *mut u8);
@@ -705,7 +861,7 @@ pub mod durable {
                 pub fn boolean() -> TypeInfo {
                     unsafe {
                         #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "durable:core/sql@2.5.0")]
+                        #[link(wasm_import_module = "durable:core/sql@2.7.0")]
                         extern "C" {
                             #[link_name = "[static]type-info.boolean"]
                             fn wit_import() -> i32;
@@ -724,7 +880,7 @@ pub mod durable {
                 pub fn float4() -> TypeInfo {
                     unsafe {
                         #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "durable:core/sql@2.5.0")]
+                        #[link(wasm_import_module = "durable:core/sql@2.7.0")]
                         extern "C" {
                             #[link_name = "[static]type-info.float4"]
                             fn wit_import() -> i32;
@@ -743,7 +899,7 @@ pub mod durable {
                 pub fn float8() -> TypeInfo {
                     unsafe {
                         #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "durable:core/sql@2.5.0")]
+                        #[link(wasm_import_module = "durable:core/sql@2.7.0")]
                         extern "C" {
                             #[link_name = "[static]type-info.float8"]
                             fn wit_import() -> i32;
@@ -762,7 +918,7 @@ pub mod durable {
                 pub fn int1() -> TypeInfo {
                     unsafe {
                         #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "durable:core/sql@2.5.0")]
+                        #[link(wasm_import_module = "durable:core/sql@2.7.0")]
                         extern "C" {
                             #[link_name = "[static]type-info.int1"]
                             fn wit_import() -> i32;
@@ -781,7 +937,7 @@ pub mod durable {
                 pub fn int2() -> TypeInfo {
                     unsafe {
                         #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "durable:core/sql@2.5.0")]
+                        #[link(wasm_import_module = "durable:core/sql@2.7.0")]
                         extern "C" {
                             #[link_name = "[static]type-info.int2"]
                             fn wit_import() -> i32;
@@ -800,7 +956,7 @@ pub mod durable {
                 pub fn int4() -> TypeInfo {
                     unsafe {
                         #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "durable:core/sql@2.5.0")]
+                        #[link(wasm_import_module = "durable:core/sql@2.7.0")]
                         extern "C" {
                             #[link_name = "[static]type-info.int4"]
                             fn wit_import() -> i32;
@@ -819,7 +975,7 @@ pub mod durable {
                 pub fn int8() -> TypeInfo {
                     unsafe {
                         #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "durable:core/sql@2.5.0")]
+                        #[link(wasm_import_module = "durable:core/sql@2.7.0")]
                         extern "C" {
                             #[link_name = "[static]type-info.int8"]
                             fn wit_import() -> i32;
@@ -838,7 +994,7 @@ pub mod durable {
                 pub fn text() -> TypeInfo {
                     unsafe {
                         #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "durable:core/sql@2.5.0")]
+                        #[link(wasm_import_module = "durable:core/sql@2.7.0")]
                         extern "C" {
                             #[link_name = "[static]type-info.text"]
                             fn wit_import() -> i32;
@@ -857,7 +1013,7 @@ pub mod durable {
                 pub fn bytea() -> TypeInfo {
                     unsafe {
                         #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "durable:core/sql@2.5.0")]
+                        #[link(wasm_import_module = "durable:core/sql@2.7.0")]
                         extern "C" {
                             #[link_name = "[static]type-info.bytea"]
                             fn wit_import() -> i32;
@@ -876,7 +1032,7 @@ pub mod durable {
                 pub fn timestamptz() -> TypeInfo {
                     unsafe {
                         #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "durable:core/sql@2.5.0")]
+                        #[link(wasm_import_module = "durable:core/sql@2.7.0")]
                         extern "C" {
                             #[link_name = "[static]type-info.timestamptz"]
                             fn wit_import() -> i32;
@@ -895,7 +1051,7 @@ pub mod durable {
                 pub fn timestamp() -> TypeInfo {
                     unsafe {
                         #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "durable:core/sql@2.5.0")]
+                        #[link(wasm_import_module = "durable:core/sql@2.7.0")]
                         extern "C" {
                             #[link_name = "[static]type-info.timestamp"]
                             fn wit_import() -> i32;
@@ -914,7 +1070,7 @@ pub mod durable {
                 pub fn uuid() -> TypeInfo {
                     unsafe {
                         #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "durable:core/sql@2.5.0")]
+                        #[link(wasm_import_module = "durable:core/sql@2.7.0")]
                         extern "C" {
                             #[link_name = "[static]type-info.uuid"]
                             fn wit_import() -> i32;
@@ -933,7 +1089,7 @@ pub mod durable {
                 pub fn jsonb() -> TypeInfo {
                     unsafe {
                         #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "durable:core/sql@2.5.0")]
+                        #[link(wasm_import_module = "durable:core/sql@2.7.0")]
                         extern "C" {
                             #[link_name = "[static]type-info.jsonb"]
                             fn wit_import() -> i32;
@@ -952,7 +1108,7 @@ pub mod durable {
                 pub fn inet() -> TypeInfo {
                     unsafe {
                         #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "durable:core/sql@2.5.0")]
+                        #[link(wasm_import_module = "durable:core/sql@2.7.0")]
                         extern "C" {
                             #[link_name = "[static]type-info.inet"]
                             fn wit_import() -> i32;
@@ -971,7 +1127,7 @@ pub mod durable {
                 pub fn boolean_array() -> TypeInfo {
                     unsafe {
                         #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "durable:core/sql@2.5.0")]
+                        #[link(wasm_import_module = "durable:core/sql@2.7.0")]
                         extern "C" {
                             #[link_name = "[static]type-info.boolean-array"]
                             fn wit_import() -> i32;
@@ -990,7 +1146,7 @@ pub mod durable {
                 pub fn float4_array() -> TypeInfo {
                     unsafe {
                         #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "durable:core/sql@2.5.0")]
+                        #[link(wasm_import_module = "durable:core/sql@2.7.0")]
                         extern "C" {
                             #[link_name = "[static]type-info.float4-array"]
                             fn wit_import() -> i32;
@@ -1009,7 +1165,7 @@ pub mod durable {
                 pub fn float8_array() -> TypeInfo {
                     unsafe {
                         #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "durable:core/sql@2.5.0")]
+                        #[link(wasm_import_module = "durable:core/sql@2.7.0")]
                         extern "C" {
                             #[link_name = "[static]type-info.float8-array"]
                             fn wit_import() -> i32;
@@ -1028,7 +1184,7 @@ pub mod durable {
                 pub fn int1_array() -> TypeInfo {
                     unsafe {
                         #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "durable:core/sql@2.5.0")]
+                        #[link(wasm_import_module = "durable:core/sql@2.7.0")]
                         extern "C" {
                             #[link_name = "[static]type-info.int1-array"]
                             fn wit_import() -> i32;
@@ -1047,7 +1203,7 @@ pub mod durable {
                 pub fn int2_array() -> TypeInfo {
                     unsafe {
                         #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "durable:core/sql@2.5.0")]
+                        #[link(wasm_import_module = "durable:core/sql@2.7.0")]
                         extern "C" {
                             #[link_name = "[static]type-info.int2-array"]
                             fn wit_import() -> i32;
@@ -1066,7 +1222,7 @@ pub mod durable {
                 pub fn int4_array() -> TypeInfo {
                     unsafe {
                         #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "durable:core/sql@2.5.0")]
+                        #[link(wasm_import_module = "durable:core/sql@2.7.0")]
                         extern "C" {
                             #[link_name = "[static]type-info.int4-array"]
                             fn wit_import() -> i32;
@@ -1085,7 +1241,7 @@ pub mod durable {
                 pub fn int8_array() -> TypeInfo {
                     unsafe {
                         #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "durable:core/sql@2.5.0")]
+                        #[link(wasm_import_module = "durable:core/sql@2.7.0")]
                         extern "C" {
                             #[link_name = "[static]type-info.int8-array"]
                             fn wit_import() -> i32;
@@ -1104,7 +1260,7 @@ pub mod durable {
                 pub fn text_array() -> TypeInfo {
                     unsafe {
                         #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "durable:core/sql@2.5.0")]
+                        #[link(wasm_import_module = "durable:core/sql@2.7.0")]
                         extern "C" {
                             #[link_name = "[static]type-info.text-array"]
                             fn wit_import() -> i32;
@@ -1123,7 +1279,7 @@ pub mod durable {
                 pub fn bytea_array() -> TypeInfo {
                     unsafe {
                         #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "durable:core/sql@2.5.0")]
+                        #[link(wasm_import_module = "durable:core/sql@2.7.0")]
                         extern "C" {
                             #[link_name = "[static]type-info.bytea-array"]
                             fn wit_import() -> i32;
@@ -1142,7 +1298,7 @@ pub mod durable {
                 pub fn timestamptz_array() -> TypeInfo {
                     unsafe {
                         #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "durable:core/sql@2.5.0")]
+                        #[link(wasm_import_module = "durable:core/sql@2.7.0")]
                         extern "C" {
                             #[link_name = "[static]type-info.timestamptz-array"]
                             fn wit_import() -> i32;
@@ -1161,7 +1317,7 @@ pub mod durable {
                 pub fn timestamp_array() -> TypeInfo {
                     unsafe {
                         #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "durable:core/sql@2.5.0")]
+                        #[link(wasm_import_module = "durable:core/sql@2.7.0")]
                         extern "C" {
                             #[link_name = "[static]type-info.timestamp-array"]
                             fn wit_import() -> i32;
@@ -1180,7 +1336,7 @@ pub mod durable {
                 pub fn uuid_array() -> TypeInfo {
                     unsafe {
                         #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "durable:core/sql@2.5.0")]
+                        #[link(wasm_import_module = "durable:core/sql@2.7.0")]
                         extern "C" {
                             #[link_name = "[static]type-info.uuid-array"]
                             fn wit_import() -> i32;
@@ -1199,7 +1355,7 @@ pub mod durable {
                 pub fn jsonb_array() -> TypeInfo {
                     unsafe {
                         #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "durable:core/sql@2.5.0")]
+                        #[link(wasm_import_module = "durable:core/sql@2.7.0")]
                         extern "C" {
                             #[link_name = "[static]type-info.jsonb-array"]
                             fn wit_import() -> i32;
@@ -1218,7 +1374,7 @@ pub mod durable {
                 pub fn inet_array() -> TypeInfo {
                     unsafe {
                         #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "durable:core/sql@2.5.0")]
+                        #[link(wasm_import_module = "durable:core/sql@2.7.0")]
                         extern "C" {
                             #[link_name = "[static]type-info.inet-array"]
                             fn wit_import() -> i32;
@@ -1240,7 +1396,7 @@ pub mod durable {
                 pub fn is_null(&self) -> bool {
                     unsafe {
                         #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "durable:core/sql@2.5.0")]
+                        #[link(wasm_import_module = "durable:core/sql@2.7.0")]
                         extern "C" {
                             #[link_name = "[method]value.is-null"]
                             fn wit_import(_: i32) -> i32;
@@ -1260,7 +1416,7 @@ pub mod durable {
                 pub fn type_info(&self) -> TypeInfo {
                     unsafe {
                         #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "durable:core/sql@2.5.0")]
+                        #[link(wasm_import_module = "durable:core/sql@2.7.0")]
                         extern "C" {
                             #[link_name = "[method]value.type-info"]
                             fn wit_import(_: i32) -> i32;
@@ -1280,7 +1436,7 @@ pub mod durable {
                 pub fn clone(&self) -> Value {
                     unsafe {
                         #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "durable:core/sql@2.5.0")]
+                        #[link(wasm_import_module = "durable:core/sql@2.7.0")]
                         extern "C" {
                             #[link_name = "[method]value.clone"]
                             fn wit_import(_: i32) -> i32;
@@ -1308,7 +1464,7 @@ pub mod durable {
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
                         #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "durable:core/sql@2.5.0")]
+                        #[link(wasm_import_module = "durable:core/sql@2.7.0")]
                         extern "C" {
                             #[link_name = "[method]value.serialize"]
                             fn wit_import(_: i32, _: *mut u8);
@@ -1368,7 +1524,7 @@ pub mod durable {
                         let len0 = vec0.len();
                         let ptr1 = ret_area.0.as_mut_ptr().cast::<u8>();
                         #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "durable:core/sql@2.5.0")]
+                        #[link(wasm_import_module = "durable:core/sql@2.7.0")]
                         extern "C" {
                             #[link_name = "[static]value.deserialize"]
                             fn wit_import(_: *mut u8, _: usize, _: *mut u8);
@@ -1417,7 +1573,7 @@ pub mod durable {
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
                         #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "durable:core/sql@2.5.0")]
+                        #[link(wasm_import_module = "durable:core/sql@2.7.0")]
                         extern "C" {
                             #[link_name = "[method]value.as-boolean"]
                             fn wit_import(_: i32, _: *mut u8);
@@ -1453,7 +1609,7 @@ pub mod durable {
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
                         #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "durable:core/sql@2.5.0")]
+                        #[link(wasm_import_module = "durable:core/sql@2.7.0")]
                         extern "C" {
                             #[link_name = "[method]value.as-float4"]
                             fn wit_import(_: i32, _: *mut u8);
@@ -1489,7 +1645,7 @@ pub mod durable {
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
                         #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "durable:core/sql@2.5.0")]
+                        #[link(wasm_import_module = "durable:core/sql@2.7.0")]
                         extern "C" {
                             #[link_name = "[method]value.as-float8"]
                             fn wit_import(_: i32, _: *mut u8);
@@ -1525,7 +1681,7 @@ pub mod durable {
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
                         #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "durable:core/sql@2.5.0")]
+                        #[link(wasm_import_module = "durable:core/sql@2.7.0")]
                         extern "C" {
                             #[link_name = "[method]value.as-int1"]
                             fn wit_import(_: i32, _: *mut u8);
@@ -1561,7 +1717,7 @@ pub mod durable {
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
                         #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "durable:core/sql@2.5.0")]
+                        #[link(wasm_import_module = "durable:core/sql@2.7.0")]
                         extern "C" {
                             #[link_name = "[method]value.as-int2"]
                             fn wit_import(_: i32, _: *mut u8);
@@ -1597,7 +1753,7 @@ pub mod durable {
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
                         #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "durable:core/sql@2.5.0")]
+                        #[link(wasm_import_module = "durable:core/sql@2.7.0")]
                         extern "C" {
                             #[link_name = "[method]value.as-int4"]
                             fn wit_import(_: i32, _: *mut u8);
@@ -1633,7 +1789,7 @@ pub mod durable {
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
                         #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "durable:core/sql@2.5.0")]
+                        #[link(wasm_import_module = "durable:core/sql@2.7.0")]
                         extern "C" {
                             #[link_name = "[method]value.as-int8"]
                             fn wit_import(_: i32, _: *mut u8);
@@ -1669,7 +1825,7 @@ pub mod durable {
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
                         #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "durable:core/sql@2.5.0")]
+                        #[link(wasm_import_module = "durable:core/sql@2.7.0")]
                         extern "C" {
                             #[link_name = "[method]value.as-text"]
                             fn wit_import(_: i32, _: *mut u8);
@@ -1712,7 +1868,7 @@ pub mod durable {
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
                         #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "durable:core/sql@2.5.0")]
+                        #[link(wasm_import_module = "durable:core/sql@2.7.0")]
                         extern "C" {
                             #[link_name = "[method]value.as-bytea"]
                             fn wit_import(_: i32, _: *mut u8);
@@ -1750,7 +1906,7 @@ pub mod durable {
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
                         #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "durable:core/sql@2.5.0")]
+                        #[link(wasm_import_module = "durable:core/sql@2.7.0")]
                         extern "C" {
                             #[link_name = "[method]value.as-timestamptz"]
                             fn wit_import(_: i32, _: *mut u8);
@@ -1792,7 +1948,7 @@ pub mod durable {
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
                         #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "durable:core/sql@2.5.0")]
+                        #[link(wasm_import_module = "durable:core/sql@2.7.0")]
                         extern "C" {
                             #[link_name = "[method]value.as-timestamp"]
                             fn wit_import(_: i32, _: *mut u8);
@@ -1832,7 +1988,7 @@ pub mod durable {
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
                         #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "durable:core/sql@2.5.0")]
+                        #[link(wasm_import_module = "durable:core/sql@2.7.0")]
                         extern "C" {
                             #[link_name = "[method]value.as-uuid"]
                             fn wit_import(_: i32, _: *mut u8);
@@ -1873,7 +2029,7 @@ pub mod durable {
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
                         #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "durable:core/sql@2.5.0")]
+                        #[link(wasm_import_module = "durable:core/sql@2.7.0")]
                         extern "C" {
                             #[link_name = "[method]value.as-json"]
                             fn wit_import(_: i32, _: *mut u8);
@@ -1916,7 +2072,7 @@ pub mod durable {
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
                         #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "durable:core/sql@2.5.0")]
+                        #[link(wasm_import_module = "durable:core/sql@2.7.0")]
                         extern "C" {
                             #[link_name = "[method]value.as-inet"]
                             fn wit_import(_: i32, _: *mut u8);
@@ -1978,7 +2134,7 @@ pub mod durable {
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
                         #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "durable:core/sql@2.5.0")]
+                        #[link(wasm_import_module = "durable:core/sql@2.7.0")]
                         extern "C" {
                             #[link_name = "[method]value.as-boolean-array"]
                             fn wit_import(_: i32, _: *mut u8);
@@ -2027,7 +2183,7 @@ pub mod durable {
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
                         #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "durable:core/sql@2.5.0")]
+                        #[link(wasm_import_module = "durable:core/sql@2.7.0")]
                         extern "C" {
                             #[link_name = "[method]value.as-float4-array"]
                             fn wit_import(_: i32, _: *mut u8);
@@ -2065,7 +2221,7 @@ pub mod durable {
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
                         #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "durable:core/sql@2.5.0")]
+                        #[link(wasm_import_module = "durable:core/sql@2.7.0")]
                         extern "C" {
                             #[link_name = "[method]value.as-float8-array"]
                             fn wit_import(_: i32, _: *mut u8);
@@ -2103,7 +2259,7 @@ pub mod durable {
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
                         #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "durable:core/sql@2.5.0")]
+                        #[link(wasm_import_module = "durable:core/sql@2.7.0")]
                         extern "C" {
                             #[link_name = "[method]value.as-int1-array"]
                             fn wit_import(_: i32, _: *mut u8);
@@ -2141,7 +2297,7 @@ pub mod durable {
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
                         #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "durable:core/sql@2.5.0")]
+                        #[link(wasm_import_module = "durable:core/sql@2.7.0")]
                         extern "C" {
                             #[link_name = "[method]value.as-int2-array"]
                             fn wit_import(_: i32, _: *mut u8);
@@ -2179,7 +2335,7 @@ pub mod durable {
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
                         #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "durable:core/sql@2.5.0")]
+                        #[link(wasm_import_module = "durable:core/sql@2.7.0")]
                         extern "C" {
                             #[link_name = "[method]value.as-int4-array"]
                             fn wit_import(_: i32, _: *mut u8);
@@ -2217,7 +2373,7 @@ pub mod durable {
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
                         #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "durable:core/sql@2.5.0")]
+                        #[link(wasm_import_module = "durable:core/sql@2.7.0")]
                         extern "C" {
                             #[link_name = "[method]value.as-int8-array"]
                             fn wit_import(_: i32, _: *mut u8);
@@ -2255,7 +2411,7 @@ pub mod durable {
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
                         #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "durable:core/sql@2.5.0")]
+                        #[link(wasm_import_module = "durable:core/sql@2.7.0")]
                         extern "C" {
                             #[link_name = "[method]value.as-text-array"]
                             fn wit_import(_: i32, _: *mut u8);
@@ -2311,7 +2467,7 @@ pub mod durable {
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
                         #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "durable:core/sql@2.5.0")]
+                        #[link(wasm_import_module = "durable:core/sql@2.7.0")]
                         extern "C" {
                             #[link_name = "[method]value.as-bytea-array"]
                             fn wit_import(_: i32, _: *mut u8);
@@ -2362,7 +2518,7 @@ pub mod durable {
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
                         #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "durable:core/sql@2.5.0")]
+                        #[link(wasm_import_module = "durable:core/sql@2.7.0")]
                         extern "C" {
                             #[link_name = "[method]value.as-timestamptz-array"]
                             fn wit_import(_: i32, _: *mut u8);
@@ -2400,7 +2556,7 @@ pub mod durable {
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
                         #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "durable:core/sql@2.5.0")]
+                        #[link(wasm_import_module = "durable:core/sql@2.7.0")]
                         extern "C" {
                             #[link_name = "[method]value.as-timestamp-array"]
                             fn wit_import(_: i32, _: *mut u8);
@@ -2438,7 +2594,7 @@ pub mod durable {
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
                         #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "durable:core/sql@2.5.0")]
+                        #[link(wasm_import_module = "durable:core/sql@2.7.0")]
                         extern "C" {
                             #[link_name = "[method]value.as-uuid-array"]
                             fn wit_import(_: i32, _: *mut u8);
@@ -2476,7 +2632,7 @@ pub mod durable {
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
                         #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "durable:core/sql@2.5.0")]
+                        #[link(wasm_import_module = "durable:core/sql@2.7.0")]
                         extern "C" {
                             #[link_name = "[method]value.as-json-array"]
                             fn wit_import(_: i32, _: *mut u8);
@@ -2532,7 +2688,7 @@ pub mod durable {
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
                         #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "durable:core/sql@2.5.0")]
+                        #[link(wasm_import_module = "durable:core/sql@2.7.0")]
                         extern "C" {
                             #[link_name = "[method]value.as-inet-array"]
                             fn wit_import(_: i32, _: *mut u8);
@@ -2596,13 +2752,61 @@ pub mod durable {
                     }
                 }
             }
+            impl Value {
+                #[allow(unused_unsafe, clippy::all)]
+                /// Get the raw representation of a value whose type the runtime does
+                /// not natively support.
+                ///
+                /// Such values are otherwise opaque, but they can still be passed back
+                /// to the database as query parameters. This returns none for values
+                /// of supported types, including NULL values.
+                pub fn as_raw(&self) -> Option<RawValue> {
+                    unsafe {
+                        #[repr(align(4))]
+                        struct RetArea([::core::mem::MaybeUninit<u8>; 16]);
+                        let mut ret_area = RetArea(
+                            [::core::mem::MaybeUninit::uninit(); 16],
+                        );
+                        let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
+                        #[cfg(target_arch = "wasm32")]
+                        #[link(wasm_import_module = "durable:core/sql@2.7.0")]
+                        extern "C" {
+                            #[link_name = "[method]value.as-raw"]
+                            fn wit_import(_: i32, _: *mut u8);
+                        }
+                        #[cfg(not(target_arch = "wasm32"))]
+                        fn wit_import(_: i32, _: *mut u8) {
+                            unreachable!()
+                        }
+                        wit_import((self).handle() as i32, ptr0);
+                        let l1 = i32::from(*ptr0.add(0).cast::<u8>());
+                        match l1 {
+                            0 => None,
+                            1 => {
+                                let e = {
+                                    let l2 = *ptr0.add(4).cast::<i32>();
+                                    let l3 = *ptr0.add(8).cast::<*mut u8>();
+                                    let l4 = *ptr0.add(12).cast::<usize>();
+                                    let len5 = l4;
+                                    RawValue {
+                                        oid: l2 as u32,
+                                        data: _rt::Vec::from_raw_parts(l3.cast(), len5, len5),
+                                    }
+                                };
+                                Some(e)
+                            }
+                            _ => _rt::invalid_enum_discriminant(),
+                        }
+                    }
+                }
+            }
             impl Value {
                 #[allow(unused_unsafe, clippy::all)]
                 /// Create a null value with the provided type info.
                 pub fn null(tyinfo: TypeInfo) -> Value {
                     unsafe {
                         #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "durable:core/sql@2.5.0")]
+                        #[link(wasm_import_module = "durable:core/sql@2.7.0")]
                         extern "C" {
                             #[link_name = "[static]value.null"]
                             fn wit_import(_: i32) -> i32;
@@ -2621,7 +2825,7 @@ pub mod durable {
                 pub fn boolean(value: bool) -> Value {
                     unsafe {
                         #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "durable:core/sql@2.5.0")]
+                        #[link(wasm_import_module = "durable:core/sql@2.7.0")]
                         extern "C" {
                             #[link_name = "[static]value.boolean"]
                             fn wit_import(_: i32) -> i32;
@@ -2645,7 +2849,7 @@ pub mod durable {
                 pub fn float4(value: f32) -> Value {
                     unsafe {
                         #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "durable:core/sql@2.5.0")]
+                        #[link(wasm_import_module = "durable:core/sql@2.7.0")]
                         extern "C" {
                             #[link_name = "[static]value.float4"]
                             fn wit_import(_: f32) -> i32;
@@ -2664,7 +2868,7 @@ pub mod durable {
                 pub fn float8(value: f64) -> Value {
                     unsafe {
                         #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "durable:core/sql@2.5.0")]
+                        #[link(wasm_import_module = "durable:core/sql@2.7.0")]
                         extern "C" {
                             #[link_name = "[static]value.float8"]
                             fn wit_import(_: f64) -> i32;
@@ -2683,7 +2887,7 @@ pub mod durable {
                 pub fn int1(value: i8) -> Value {
                     unsafe {
                         #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "durable:core/sql@2.5.0")]
+                        #[link(wasm_import_module = "durable:core/sql@2.7.0")]
                         extern "C" {
                             #[link_name = "[static]value.int1"]
                             fn wit_import(_: i32) -> i32;
@@ -2702,7 +2906,7 @@ pub mod durable {
                 pub fn int2(value: i16) -> Value {
                     unsafe {
                         #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "durable:core/sql@2.5.0")]
+                        #[link(wasm_import_module = "durable:core/sql@2.7.0")]
                         extern "C" {
                             #[link_name = "[static]value.int2"]
                             fn wit_import(_: i32) -> i32;
@@ -2721,7 +2925,7 @@ pub mod durable {
                 pub fn int4(value: i32) -> Value {
                     unsafe {
                         #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "durable:core/sql@2.5.0")]
+                        #[link(wasm_import_module = "durable:core/sql@2.7.0")]
                         extern "C" {
                             #[link_name = "[static]value.int4"]
                             fn wit_import(_: i32) -> i32;
@@ -2740,7 +2944,7 @@ pub mod durable {
                 pub fn int8(value: i64) -> Value {
                     unsafe {
                         #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "durable:core/sql@2.5.0")]
+                        #[link(wasm_import_module = "durable:core/sql@2.7.0")]
                         extern "C" {
                             #[link_name = "[static]value.int8"]
                             fn wit_import(_: i64) -> i32;
@@ -2762,7 +2966,7 @@ pub mod durable {
                         let ptr0 = vec0.as_ptr().cast::<u8>();
                         let len0 = vec0.len();
                         #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "durable:core/sql@2.5.0")]
+                        #[link(wasm_import_module = "durable:core/sql@2.7.0")]
                         extern "C" {
                             #[link_name = "[static]value.text"]
                             fn wit_import(_: *mut u8, _: usize) -> i32;
@@ -2784,7 +2988,7 @@ pub mod durable {
                         let ptr0 = vec0.as_ptr().cast::<u8>();
                         let len0 = vec0.len();
                         #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "durable:core/sql@2.5.0")]
+                        #[link(wasm_import_module = "durable:core/sql@2.7.0")]
                         extern "C" {
                             #[link_name = "[static]value.bytea"]
                             fn wit_import(_: *mut u8, _: usize) -> i32;
@@ -2808,7 +3012,7 @@ pub mod durable {
                             offset: offset0,
                         } = value;
                         #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "durable:core/sql@2.5.0")]
+                        #[link(wasm_import_module = "durable:core/sql@2.7.0")]
                         extern "C" {
                             #[link_name = "[static]value.timestamptz"]
                             fn wit_import(_: i64, _: i32, _: i32) -> i32;
@@ -2835,7 +3039,7 @@ pub mod durable {
                             subsec_nanos: subsec_nanos0,
                         } = value;
                         #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "durable:core/sql@2.5.0")]
+                        #[link(wasm_import_module = "durable:core/sql@2.7.0")]
                         extern "C" {
                             #[link_name = "[static]value.timestamp"]
                             fn wit_import(_: i64, _: i32) -> i32;
@@ -2858,7 +3062,7 @@ pub mod durable {
                     unsafe {
                         let Uuid { hi: hi0, lo: lo0 } = value;
                         #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "durable:core/sql@2.5.0")]
+                        #[link(wasm_import_module = "durable:core/sql@2.7.0")]
                         extern "C" {
                             #[link_name = "[static]value.uuid"]
                             fn wit_import(_: i64, _: i64) -> i32;
@@ -2880,7 +3084,7 @@ pub mod durable {
                         let ptr0 = vec0.as_ptr().cast::<u8>();
                         let len0 = vec0.len();
                         #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "durable:core/sql@2.5.0")]
+                        #[link(wasm_import_module = "durable:core/sql@2.7.0")]
                         extern "C" {
                             #[link_name = "[static]value.jsonb"]
                             fn wit_import(_: *mut u8, _: usize) -> i32;
@@ -2926,7 +3130,7 @@ pub mod durable {
                         };
                         let ptr4 = ret_area.0.as_mut_ptr().cast::<u8>();
                         #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "durable:core/sql@2.5.0")]
+                        #[link(wasm_import_module = "durable:core/sql@2.7.0")]
                         extern "C" {
                             #[link_name = "[static]value.inet"]
                             fn wit_import(_: i32, _: i64, _: i64, _: i32, _: *mut u8);
@@ -2972,7 +3176,7 @@ pub mod durable {
                         let ptr0 = vec0.as_ptr().cast::<u8>();
                         let len0 = vec0.len();
                         #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "durable:core/sql@2.5.0")]
+                        #[link(wasm_import_module = "durable:core/sql@2.7.0")]
                         extern "C" {
                             #[link_name = "[static]value.enum-value"]
                             fn wit_import(_: *mut u8, _: usize, _: i32) -> i32;
@@ -3019,7 +3223,7 @@ pub mod durable {
                             }
                         }
                         #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "durable:core/sql@2.5.0")]
+                        #[link(wasm_import_module = "durable:core/sql@2.7.0")]
                         extern "C" {
                             #[link_name = "[static]value.boolean-array"]
                             fn wit_import(_: *mut u8, _: usize) -> i32;
@@ -3044,7 +3248,7 @@ pub mod durable {
                         let ptr0 = vec0.as_ptr().cast::<u8>();
                         let len0 = vec0.len();
                         #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "durable:core/sql@2.5.0")]
+                        #[link(wasm_import_module = "durable:core/sql@2.7.0")]
                         extern "C" {
                             #[link_name = "[static]value.float4-array"]
                             fn wit_import(_: *mut u8, _: usize) -> i32;
@@ -3066,7 +3270,7 @@ pub mod durable {
                         let ptr0 = vec0.as_ptr().cast::<u8>();
                         let len0 = vec0.len();
                         #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "durable:core/sql@2.5.0")]
+                        #[link(wasm_import_module = "durable:core/sql@2.7.0")]
                         extern "C" {
                             #[link_name = "[static]value.float8-array"]
                             fn wit_import(_: *mut u8, _: usize) -> i32;
@@ -3088,7 +3292,7 @@ pub mod durable {
                         let ptr0 = vec0.as_ptr().cast::<u8>();
                         let len0 = vec0.len();
                         #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "durable:core/sql@2.5.0")]
+                        #[link(wasm_import_module = "durable:core/sql@2.7.0")]
                         extern "C" {
                             #[link_name = "[static]value.int1-array"]
                             fn wit_import(_: *mut u8, _: usize) -> i32;
@@ -3110,7 +3314,7 @@ pub mod durable {
                         let ptr0 = vec0.as_ptr().cast::<u8>();
                         let len0 = vec0.len();
                         #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "durable:core/sql@2.5.0")]
+                        #[link(wasm_import_module = "durable:core/sql@2.7.0")]
                         extern "C" {
                             #[link_name = "[static]value.int2-array"]
                             fn wit_import(_: *mut u8, _: usize) -> i32;
@@ -3132,7 +3336,7 @@ pub mod durable {
                         let ptr0 = vec0.as_ptr().cast::<u8>();
                         let len0 = vec0.len();
                         #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "durable:core/sql@2.5.0")]
+                        #[link(wasm_import_module = "durable:core/sql@2.7.0")]
                         extern "C" {
                             #[link_name = "[static]value.int4-array"]
                             fn wit_import(_: *mut u8, _: usize) -> i32;
@@ -3154,7 +3358,7 @@ pub mod durable {
                         let ptr0 = vec0.as_ptr().cast::<u8>();
                         let len0 = vec0.len();
                         #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "durable:core/sql@2.5.0")]
+                        #[link(wasm_import_module = "durable:core/sql@2.7.0")]
                         extern "C" {
                             #[link_name = "[static]value.int8-array"]
                             fn wit_import(_: *mut u8, _: usize) -> i32;
@@ -3198,7 +3402,7 @@ pub mod durable {
                             }
                         }
                         #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "durable:core/sql@2.5.0")]
+                        #[link(wasm_import_module = "durable:core/sql@2.7.0")]
                         extern "C" {
                             #[link_name = "[static]value.text-array"]
                             fn wit_import(_: *mut u8, _: usize) -> i32;
@@ -3245,7 +3449,7 @@ pub mod durable {
                             }
                         }
                         #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "durable:core/sql@2.5.0")]
+                        #[link(wasm_import_module = "durable:core/sql@2.7.0")]
                         extern "C" {
                             #[link_name = "[static]value.bytea-array"]
                             fn wit_import(_: *mut u8, _: usize) -> i32;
@@ -3270,7 +3474,7 @@ pub mod durable {
                         let ptr0 = vec0.as_ptr().cast::<u8>();
                         let len0 = vec0.len();
                         #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "durable:core/sql@2.5.0")]
+                        #[link(wasm_import_module = "durable:core/sql@2.7.0")]
                         extern "C" {
                             #[link_name = "[static]value.timestamptz-array"]
                             fn wit_import(_: *mut u8, _: usize) -> i32;
@@ -3292,7 +3496,7 @@ pub mod durable {
                         let ptr0 = vec0.as_ptr().cast::<u8>();
                         let len0 = vec0.len();
                         #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "durable:core/sql@2.5.0")]
+                        #[link(wasm_import_module = "durable:core/sql@2.7.0")]
                         extern "C" {
                             #[link_name = "[static]value.timestamp-array"]
                             fn wit_import(_: *mut u8, _: usize) -> i32;
@@ -3314,7 +3518,7 @@ pub mod durable {
                         let ptr0 = vec0.as_ptr().cast::<u8>();
                         let len0 = vec0.len();
                         #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "durable:core/sql@2.5.0")]
+                        #[link(wasm_import_module = "durable:core/sql@2.7.0")]
                         extern "C" {
                             #[link_name = "[static]value.uuid-array"]
                             fn wit_import(_: *mut u8, _: usize) -> i32;
@@ -3358,7 +3562,7 @@ pub mod durable {
                             }
                         }
                         #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "durable:core/sql@2.5.0")]
+                        #[link(wasm_import_module = "durable:core/sql@2.7.0")]
                         extern "C" {
                             #[link_name = "[static]value.jsonb-array"]
                             fn wit_import(_: *mut u8, _: usize) -> i32;
@@ -3422,7 +3626,7 @@ pub mod durable {
                         }
                         let ptr4 = ret_area.0.as_mut_ptr().cast::<u8>();
                         #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "durable:core/sql@2.5.0")]
+                        #[link(wasm_import_module = "durable:core/sql@2.7.0")]
                         extern "C" {
                             #[link_name = "[static]value.inet-array"]
                             fn wit_import(_: *mut u8, _: usize, _: *mut u8);
@@ -3493,7 +3697,7 @@ pub mod durable {
                             }
                         }
                         #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "durable:core/sql@2.5.0")]
+                        #[link(wasm_import_module = "durable:core/sql@2.7.0")]
                         extern "C" {
                             #[link_name = "[static]value.enum-array"]
                             fn wit_import(_: *mut u8, _: usize, _: i32) -> i32;
@@ -3546,7 +3750,7 @@ pub mod durable {
                     }
                     let Options { limit: limit2, persistent: persistent2 } = options;
                     #[cfg(target_arch = "wasm32")]
-                    #[link(wasm_import_module = "durable:core/sql@2.5.0")]
+                    #[link(wasm_import_module = "durable:core/sql@2.7.0")]
                     extern "C" {
                         #[link_name = "query"]
                         fn wit_import(
@@ -3594,7 +3798,7 @@ pub mod durable {
                     let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 72]);
                     let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
                     #[cfg(target_arch = "wasm32")]
-                    #[link(wasm_import_module = "durable:core/sql@2.5.0")]
+                    #[link(wasm_import_module = "durable:core/sql@2.7.0")]
                     extern "C" {
                         #[link_name = "fetch"]
                         fn wit_import(_: *mut u8);
@@ -3834,6 +4038,2200 @@ pub mod durable {
                     }
                 }
             }
+            #[allow(unused_unsafe, clippy::all)]
+            /// Create a new savepoint within the current database transaction.
+            ///
+            /// Savepoints nest. Each savepoint must later either be released via
+            /// `release-savepoint` or rolled back via `rollback-savepoint`. Any
+            /// savepoints that are still active when the transaction completes are
+            /// committed along with the rest of the transaction.
+            pub fn savepoint() -> Result<(), Error> {
+                unsafe {
+                    #[repr(align(4))]
+                    struct RetArea([::core::mem::MaybeUninit<u8>; 56]);
+                    let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 56]);
+                    let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(wasm_import_module = "durable:core/sql@2.7.0")]
+                    extern "C" {
+                        #[link_name = "savepoint"]
+                        fn wit_import(_: *mut u8);
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    fn wit_import(_: *mut u8) {
+                        unreachable!()
+                    }
+                    wit_import(ptr0);
+                    let l1 = i32::from(*ptr0.add(0).cast::<u8>());
+                    match l1 {
+                        0 => {
+                            let e = ();
+                            Ok(e)
+                        }
+                        1 => {
+                            let e = {
+                                let l2 = i32::from(*ptr0.add(4).cast::<u8>());
+                                let v37 = match l2 {
+                                    0 => {
+                                        let e37 = {
+                                            let l3 = *ptr0.add(8).cast::<*mut u8>();
+                                            let l4 = *ptr0.add(12).cast::<usize>();
+                                            let len5 = l4;
+                                            let bytes5 = _rt::Vec::from_raw_parts(
+                                                l3.cast(),
+                                                len5,
+                                                len5,
+                                            );
+                                            let l6 = *ptr0.add(16).cast::<*mut u8>();
+                                            let l7 = *ptr0.add(20).cast::<usize>();
+                                            let len8 = l7;
+                                            let bytes8 = _rt::Vec::from_raw_parts(
+                                                l6.cast(),
+                                                len8,
+                                                len8,
+                                            );
+                                            ColumnDecodeError {
+                                                index: _rt::string_lift(bytes5),
+                                                source: _rt::string_lift(bytes8),
+                                            }
+                                        };
+                                        Error::ColumnDecode(e37)
+                                    }
+                                    1 => {
+                                        let e37 = {
+                                            let l9 = *ptr0.add(8).cast::<*mut u8>();
+                                            let l10 = *ptr0.add(12).cast::<usize>();
+                                            let len11 = l10;
+                                            let bytes11 = _rt::Vec::from_raw_parts(
+                                                l9.cast(),
+                                                len11,
+                                                len11,
+                                            );
+                                            _rt::string_lift(bytes11)
+                                        };
+                                        Error::TypeNotFound(e37)
+                                    }
+                                    2 => {
+                                        let e37 = {
+                                            let l12 = *ptr0.add(8).cast::<*mut u8>();
+                                            let l13 = *ptr0.add(12).cast::<usize>();
+                                            let len14 = l13;
+                                            let bytes14 = _rt::Vec::from_raw_parts(
+                                                l12.cast(),
+                                                len14,
+                                                len14,
+                                            );
+                                            _rt::string_lift(bytes14)
+                                        };
+                                        Error::Encode(e37)
+                                    }
+                                    3 => {
+                                        let e37 = {
+                                            let l15 = *ptr0.add(8).cast::<*mut u8>();
+                                            let l16 = *ptr0.add(12).cast::<usize>();
+                                            let len17 = l16;
+                                            let bytes17 = _rt::Vec::from_raw_parts(
+                                                l15.cast(),
+                                                len17,
+                                                len17,
+                                            );
+                                            _rt::string_lift(bytes17)
+                                        };
+                                        Error::Decode(e37)
+                                    }
+                                    4 => {
+                                        let e37 = {
+                                            let l18 = *ptr0.add(8).cast::<*mut u8>();
+                                            let l19 = *ptr0.add(12).cast::<usize>();
+                                            let len20 = l19;
+                                            let bytes20 = _rt::Vec::from_raw_parts(
+                                                l18.cast(),
+                                                len20,
+                                                len20,
+                                            );
+                                            let l21 = i32::from(*ptr0.add(16).cast::<u8>());
+                                            let l22 = i32::from(*ptr0.add(20).cast::<u8>());
+                                            let l26 = i32::from(*ptr0.add(32).cast::<u8>());
+                                            let l30 = i32::from(*ptr0.add(44).cast::<u8>());
+                                            DatabaseError {
+                                                message: _rt::string_lift(bytes20),
+                                                kind: DatabaseErrorKind::_lift(l21 as u8),
+                                                code: match l22 {
+                                                    0 => None,
+                                                    1 => {
+                                                        let e = {
+                                                            let l23 = *ptr0.add(24).cast::<*mut u8>();
+                                                            let l24 = *ptr0.add(28).cast::<usize>();
+                                                            let len25 = l24;
+                                                            let bytes25 = _rt::Vec::from_raw_parts(
+                                                                l23.cast(),
+                                                                len25,
+                                                                len25,
+                                                            );
+                                                            _rt::string_lift(bytes25)
+                                                        };
+                                                        Some(e)
+                                                    }
+                                                    _ => _rt::invalid_enum_discriminant(),
+                                                },
+                                                constraint: match l26 {
+                                                    0 => None,
+                                                    1 => {
+                                                        let e = {
+                                                            let l27 = *ptr0.add(36).cast::<*mut u8>();
+                                                            let l28 = *ptr0.add(40).cast::<usize>();
+                                                            let len29 = l28;
+                                                            let bytes29 = _rt::Vec::from_raw_parts(
+                                                                l27.cast(),
+                                                                len29,
+                                                                len29,
+                                                            );
+                                                            _rt::string_lift(bytes29)
+                                                        };
+                                                        Some(e)
+                                                    }
+                                                    _ => _rt::invalid_enum_discriminant(),
+                                                },
+                                                table: match l30 {
+                                                    0 => None,
+                                                    1 => {
+                                                        let e = {
+                                                            let l31 = *ptr0.add(48).cast::<*mut u8>();
+                                                            let l32 = *ptr0.add(52).cast::<usize>();
+                                                            let len33 = l32;
+                                                            let bytes33 = _rt::Vec::from_raw_parts(
+                                                                l31.cast(),
+                                                                len33,
+                                                                len33,
+                                                            );
+                                                            _rt::string_lift(bytes33)
+                                                        };
+                                                        Some(e)
+                                                    }
+                                                    _ => _rt::invalid_enum_discriminant(),
+                                                },
+                                            }
+                                        };
+                                        Error::Database(e37)
+                                    }
+                                    n => {
+                                        debug_assert_eq!(n, 5, "invalid enum discriminant");
+                                        let e37 = {
+                                            let l34 = *ptr0.add(8).cast::<*mut u8>();
+                                            let l35 = *ptr0.add(12).cast::<usize>();
+                                            let len36 = l35;
+                                            let bytes36 = _rt::Vec::from_raw_parts(
+                                                l34.cast(),
+                                                len36,
+                                                len36,
+                                            );
+                                            _rt::string_lift(bytes36)
+                                        };
+                                        Error::Other(e37)
+                                    }
+                                };
+                                v37
+                            };
+                            Err(e)
+                        }
+                        _ => _rt::invalid_enum_discriminant(),
+                    }
+                }
+            }
+            #[allow(unused_unsafe, clippy::all)]
+            /// Release the most recently created savepoint, keeping all changes made
+            /// since it was created.
+            ///
+            /// This returns an error if there is no active savepoint.
+            pub fn release_savepoint() -> Result<(), Error> {
+                unsafe {
+                    #[repr(align(4))]
+                    struct RetArea([::core::mem::MaybeUninit<u8>; 56]);
+                    let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 56]);
+                    let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(wasm_import_module = "durable:core/sql@2.7.0")]
+                    extern "C" {
+                        #[link_name = "release-savepoint"]
+                        fn wit_import(_: *mut u8);
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    fn wit_import(_: *mut u8) {
+                        unreachable!()
+                    }
+                    wit_import(ptr0);
+                    let l1 = i32::from(*ptr0.add(0).cast::<u8>());
+                    match l1 {
+                        0 => {
+                            let e = ();
+                            Ok(e)
+                        }
+                        1 => {
+                            let e = {
+                                let l2 = i32::from(*ptr0.add(4).cast::<u8>());
+                                let v37 = match l2 {
+                                    0 => {
+                                        let e37 = {
+                                            let l3 = *ptr0.add(8).cast::<*mut u8>();
+                                            let l4 = *ptr0.add(12).cast::<usize>();
+                                            let len5 = l4;
+                                            let bytes5 = _rt::Vec::from_raw_parts(
+                                                l3.cast(),
+                                                len5,
+                                                len5,
+                                            );
+                                            let l6 = *ptr0.add(16).cast::<*mut u8>();
+                                            let l7 = *ptr0.add(20).cast::<usize>();
+                                            let len8 = l7;
+                                            let bytes8 = _rt::Vec::from_raw_parts(
+                                                l6.cast(),
+                                                len8,
+                                                len8,
+                                            );
+                                            ColumnDecodeError {
+                                                index: _rt::string_lift(bytes5),
+                                                source: _rt::string_lift(bytes8),
+                                            }
+                                        };
+                                        Error::ColumnDecode(e37)
+                                    }
+                                    1 => {
+                                        let e37 = {
+                                            let l9 = *ptr0.add(8).cast::<*mut u8>();
+                                            let l10 = *ptr0.add(12).cast::<usize>();
+                                            let len11 = l10;
+                                            let bytes11 = _rt::Vec::from_raw_parts(
+                                                l9.cast(),
+                                                len11,
+                                                len11,
+                                            );
+                                            _rt::string_lift(bytes11)
+                                        };
+                                        Error::TypeNotFound(e37)
+                                    }
+                                    2 => {
+                                        let e37 = {
+                                            let l12 = *ptr0.add(8).cast::<*mut u8>();
+                                            let l13 = *ptr0.add(12).cast::<usize>();
+                                            let len14 = l13;
+                                            let bytes14 = _rt::Vec::from_raw_parts(
+                                                l12.cast(),
+                                                len14,
+                                                len14,
+                                            );
+                                            _rt::string_lift(bytes14)
+                                        };
+                                        Error::Encode(e37)
+                                    }
+                                    3 => {
+                                        let e37 = {
+                                            let l15 = *ptr0.add(8).cast::<*mut u8>();
+                                            let l16 = *ptr0.add(12).cast::<usize>();
+                                            let len17 = l16;
+                                            let bytes17 = _rt::Vec::from_raw_parts(
+                                                l15.cast(),
+                                                len17,
+                                                len17,
+                                            );
+                                            _rt::string_lift(bytes17)
+                                        };
+                                        Error::Decode(e37)
+                                    }
+                                    4 => {
+                                        let e37 = {
+                                            let l18 = *ptr0.add(8).cast::<*mut u8>();
+                                            let l19 = *ptr0.add(12).cast::<usize>();
+                                            let len20 = l19;
+                                            let bytes20 = _rt::Vec::from_raw_parts(
+                                                l18.cast(),
+                                                len20,
+                                                len20,
+                                            );
+                                            let l21 = i32::from(*ptr0.add(16).cast::<u8>());
+                                            let l22 = i32::from(*ptr0.add(20).cast::<u8>());
+                                            let l26 = i32::from(*ptr0.add(32).cast::<u8>());
+                                            let l30 = i32::from(*ptr0.add(44).cast::<u8>());
+                                            DatabaseError {
+                                                message: _rt::string_lift(bytes20),
+                                                kind: DatabaseErrorKind::_lift(l21 as u8),
+                                                code: match l22 {
+                                                    0 => None,
+                                                    1 => {
+                                                        let e = {
+                                                            let l23 = *ptr0.add(24).cast::<*mut u8>();
+                                                            let l24 = *ptr0.add(28).cast::<usize>();
+                                                            let len25 = l24;
+                                                            let bytes25 = _rt::Vec::from_raw_parts(
+                                                                l23.cast(),
+                                                                len25,
+                                                                len25,
+                                                            );
+                                                            _rt::string_lift(bytes25)
+                                                        };
+                                                        Some(e)
+                                                    }
+                                                    _ => _rt::invalid_enum_discriminant(),
+                                                },
+                                                constraint: match l26 {
+                                                    0 => None,
+                                                    1 => {
+                                                        let e = {
+                                                            let l27 = *ptr0.add(36).cast::<*mut u8>();
+                                                            let l28 = *ptr0.add(40).cast::<usize>();
+                                                            let len29 = l28;
+                                                            let bytes29 = _rt::Vec::from_raw_parts(
+                                                                l27.cast(),
+                                                                len29,
+                                                                len29,
+                                                            );
+                                                            _rt::string_lift(bytes29)
+                                                        };
+                                                        Some(e)
+                                                    }
+                                                    _ => _rt::invalid_enum_discriminant(),
+                                                },
+                                                table: match l30 {
+                                                    0 => None,
+                                                    1 => {
+                                                        let e = {
+                                                            let l31 = *ptr0.add(48).cast::<*mut u8>();
+                                                            let l32 = *ptr0.add(52).cast::<usize>();
+                                                            let len33 = l32;
+                                                            let bytes33 = _rt::Vec::from_raw_parts(
+                                                                l31.cast(),
+                                                                len33,
+                                                                len33,
+                                                            );
+                                                            _rt::string_lift(bytes33)
+                                                        };
+                                                        Some(e)
+                                                    }
+                                                    _ => _rt::invalid_enum_discriminant(),
+                                                },
+                                            }
+                                        };
+                                        Error::Database(e37)
+                                    }
+                                    n => {
+                                        debug_assert_eq!(n, 5, "invalid enum discriminant");
+                                        let e37 = {
+                                            let l34 = *ptr0.add(8).cast::<*mut u8>();
+                                            let l35 = *ptr0.add(12).cast::<usize>();
+                                            let len36 = l35;
+                                            let bytes36 = _rt::Vec::from_raw_parts(
+                                                l34.cast(),
+                                                len36,
+                                                len36,
+                                            );
+                                            _rt::string_lift(bytes36)
+                                        };
+                                        Error::Other(e37)
+                                    }
+                                };
+                                v37
+                            };
+                            Err(e)
+                        }
+                        _ => _rt::invalid_enum_discriminant(),
+                    }
+                }
+            }
+            #[allow(unused_unsafe, clippy::all)]
+            /// Roll back all changes made since the most recently created savepoint
+            /// and then remove it.
+            ///
+            /// This returns an error if there is no active savepoint.
+            pub fn rollback_savepoint() -> Result<(), Error> {
+                unsafe {
+                    #[repr(align(4))]
+                    struct RetArea([::core::mem::MaybeUninit<u8>; 56]);
+                    let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 56]);
+                    let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(wasm_import_module = "durable:core/sql@2.7.0")]
+                    extern "C" {
+                        #[link_name = "rollback-savepoint"]
+                        fn wit_import(_: *mut u8);
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    fn wit_import(_: *mut u8) {
+                        unreachable!()
+                    }
+                    wit_import(ptr0);
+                    let l1 = i32::from(*ptr0.add(0).cast::<u8>());
+                    match l1 {
+                        0 => {
+                            let e = ();
+                            Ok(e)
+                        }
+                        1 => {
+                            let e = {
+                                let l2 = i32::from(*ptr0.add(4).cast::<u8>());
+                                let v37 = match l2 {
+                                    0 => {
+                                        let e37 = {
+                                            let l3 = *ptr0.add(8).cast::<*mut u8>();
+                                            let l4 = *ptr0.add(12).cast::<usize>();
+                                            let len5 = l4;
+                                            let bytes5 = _rt::Vec::from_raw_parts(
+                                                l3.cast(),
+                                                len5,
+                                                len5,
+                                            );
+                                            let l6 = *ptr0.add(16).cast::<*mut u8>();
+                                            let l7 = *ptr0.add(20).cast::<usize>();
+                                            let len8 = l7;
+                                            let bytes8 = _rt::Vec::from_raw_parts(
+                                                l6.cast(),
+                                                len8,
+                                                len8,
+                                            );
+                                            ColumnDecodeError {
+                                                index: _rt::string_lift(bytes5),
+                                                source: _rt::string_lift(bytes8),
+                                            }
+                                        };
+                                        Error::ColumnDecode(e37)
+                                    }
+                                    1 => {
+                                        let e37 = {
+                                            let l9 = *ptr0.add(8).cast::<*mut u8>();
+                                            let l10 = *ptr0.add(12).cast::<usize>();
+                                            let len11 = l10;
+                                            let bytes11 = _rt::Vec::from_raw_parts(
+                                                l9.cast(),
+                                                len11,
+                                                len11,
+                                            );
+                                            _rt::string_lift(bytes11)
+                                        };
+                                        Error::TypeNotFound(e37)
+                                    }
+                                    2 => {
+                                        let e37 = {
+                                            let l12 = *ptr0.add(8).cast::<*mut u8>();
+                                            let l13 = *ptr0.add(12).cast::<usize>();
+                                            let len14 = l13;
+                                            let bytes14 = _rt::Vec::from_raw_parts(
+                                                l12.cast(),
+                                                len14,
+                                                len14,
+                                            );
+                                            _rt::string_lift(bytes14)
+                                        };
+                                        Error::Encode(e37)
+                                    }
+                                    3 => {
+                                        let e37 = {
+                                            let l15 = *ptr0.add(8).cast::<*mut u8>();
+                                            let l16 = *ptr0.add(12).cast::<usize>();
+                                            let len17 = l16;
+                                            let bytes17 = _rt::Vec::from_raw_parts(
+                                                l15.cast(),
+                                                len17,
+                                                len17,
+                                            );
+                                            _rt::string_lift(bytes17)
+                                        };
+                                        Error::Decode(e37)
+                                    }
+                                    4 => {
+                                        let e37 = {
+                                            let l18 = *ptr0.add(8).cast::<*mut u8>();
+                                            let l19 = *ptr0.add(12).cast::<usize>();
+                                            let len20 = l19;
+                                            let bytes20 = _rt::Vec::from_raw_parts(
+                                                l18.cast(),
+                                                len20,
+                                                len20,
+                                            );
+                                            let l21 = i32::from(*ptr0.add(16).cast::<u8>());
+                                            let l22 = i32::from(*ptr0.add(20).cast::<u8>());
+                                            let l26 = i32::from(*ptr0.add(32).cast::<u8>());
+                                            let l30 = i32::from(*ptr0.add(44).cast::<u8>());
+                                            DatabaseError {
+                                                message: _rt::string_lift(bytes20),
+                                                kind: DatabaseErrorKind::_lift(l21 as u8),
+                                                code: match l22 {
+                                                    0 => None,
+                                                    1 => {
+                                                        let e = {
+                                                            let l23 = *ptr0.add(24).cast::<*mut u8>();
+                                                            let l24 = *ptr0.add(28).cast::<usize>();
+                                                            let len25 = l24;
+                                                            let bytes25 = _rt::Vec::from_raw_parts(
+                                                                l23.cast(),
+                                                                len25,
+                                                                len25,
+                                                            );
+                                                            _rt::string_lift(bytes25)
+                                                        };
+                                                        Some(e)
+                                                    }
+                                                    _ => _rt::invalid_enum_discriminant(),
+                                                },
+                                                constraint: match l26 {
+                                                    0 => None,
+                                                    1 => {
+                                                        let e = {
+                                                            let l27 = *ptr0.add(36).cast::<*mut u8>();
+                                                            let l28 = *ptr0.add(40).cast::<usize>();
+                                                            let len29 = l28;
+                                                            let bytes29 = _rt::Vec::from_raw_parts(
+                                                                l27.cast(),
+                                                                len29,
+                                                                len29,
+                                                            );
+                                                            _rt::string_lift(bytes29)
+                                                        };
+                                                        Some(e)
+                                                    }
+                                                    _ => _rt::invalid_enum_discriminant(),
+                                                },
+                                                table: match l30 {
+                                                    0 => None,
+                                                    1 => {
+                                                        let e = {
+                                                            let l31 = *ptr0.add(48).cast::<*mut u8>();
+                                                            let l32 = *ptr0.add(52).cast::<usize>();
+                                                            let len33 = l32;
+                                                            let bytes33 = _rt::Vec::from_raw_parts(
+                                                                l31.cast(),
+                                                                len33,
+                                                                len33,
+                                                            );
+                                                            _rt::string_lift(bytes33)
+                                                        };
+                                                        Some(e)
+                                                    }
+                                                    _ => _rt::invalid_enum_discriminant(),
+                                                },
+                                            }
+                                        };
+                                        Error::Database(e37)
+                                    }
+                                    n => {
+                                        debug_assert_eq!(n, 5, "invalid enum discriminant");
+                                        let e37 = {
+                                            let l34 = *ptr0.add(8).cast::<*mut u8>();
+                                            let l35 = *ptr0.add(12).cast::<usize>();
+                                            let len36 = l35;
+                                            let bytes36 = _rt::Vec::from_raw_parts(
+                                                l34.cast(),
+                                                len36,
+                                                len36,
+                                            );
+                                            _rt::string_lift(bytes36)
+                                        };
+                                        Error::Other(e37)
+                                    }
+                                };
+                                v37
+                            };
+                            Err(e)
+                        }
+                        _ => _rt::invalid_enum_discriminant(),
+                    }
+                }
+            }
+            #[allow(unused_unsafe, clippy::all)]
+            /// Start a `COPY ... FROM STDIN` operation within the current database
+            /// transaction.
+            ///
+            /// The statement must be a `COPY` statement that reads from `STDIN`. The
+            /// data to be copied is then sent in chunks via `copy-in-send` and the
+            /// operation is completed by calling either `copy-in-finish` or
+            /// `copy-in-abort`.
+            ///
+            /// Starting a new COPY operation, or running a query, while another COPY
+            /// operation is in progress will abort the operation in progress.
+            pub fn copy_in_start(statement: &str) -> Result<(), Error> {
+                unsafe {
+                    #[repr(align(4))]
+                    struct RetArea([::core::mem::MaybeUninit<u8>; 56]);
+                    let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 56]);
+                    let vec0 = statement;
+                    let ptr0 = vec0.as_ptr().cast::<u8>();
+                    let len0 = vec0.len();
+                    let ptr1 = ret_area.0.as_mut_ptr().cast::<u8>();
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(wasm_import_module = "durable:core/sql@2.7.0")]
+                    extern "C" {
+                        #[link_name = "copy-in-start"]
+                        fn wit_import(_: *mut u8, _: usize, _: *mut u8);
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    fn wit_import(_: *mut u8, _: usize, _: *mut u8) {
+                        unreachable!()
+                    }
+                    wit_import(ptr0.cast_mut(), len0, ptr1);
+                    let l2 = i32::from(*ptr1.add(0).cast::<u8>());
+                    match l2 {
+                        0 => {
+                            let e = ();
+                            Ok(e)
+                        }
+                        1 => {
+                            let e = {
+                                let l3 = i32::from(*ptr1.add(4).cast::<u8>());
+                                let v38 = match l3 {
+                                    0 => {
+                                        let e38 = {
+                                            let l4 = *ptr1.add(8).cast::<*mut u8>();
+                                            let l5 = *ptr1.add(12).cast::<usize>();
+                                            let len6 = l5;
+                                            let bytes6 = _rt::Vec::from_raw_parts(
+                                                l4.cast(),
+                                                len6,
+                                                len6,
+                                            );
+                                            let l7 = *ptr1.add(16).cast::<*mut u8>();
+                                            let l8 = *ptr1.add(20).cast::<usize>();
+                                            let len9 = l8;
+                                            let bytes9 = _rt::Vec::from_raw_parts(
+                                                l7.cast(),
+                                                len9,
+                                                len9,
+                                            );
+                                            ColumnDecodeError {
+                                                index: _rt::string_lift(bytes6),
+                                                source: _rt::string_lift(bytes9),
+                                            }
+                                        };
+                                        Error::ColumnDecode(e38)
+                                    }
+                                    1 => {
+                                        let e38 = {
+                                            let l10 = *ptr1.add(8).cast::<*mut u8>();
+                                            let l11 = *ptr1.add(12).cast::<usize>();
+                                            let len12 = l11;
+                                            let bytes12 = _rt::Vec::from_raw_parts(
+                                                l10.cast(),
+                                                len12,
+                                                len12,
+                                            );
+                                            _rt::string_lift(bytes12)
+                                        };
+                                        Error::TypeNotFound(e38)
+                                    }
+                                    2 => {
+                                        let e38 = {
+                                            let l13 = *ptr1.add(8).cast::<*mut u8>();
+                                            let l14 = *ptr1.add(12).cast::<usize>();
+                                            let len15 = l14;
+                                            let bytes15 = _rt::Vec::from_raw_parts(
+                                                l13.cast(),
+                                                len15,
+                                                len15,
+                                            );
+                                            _rt::string_lift(bytes15)
+                                        };
+                                        Error::Encode(e38)
+                                    }
+                                    3 => {
+                                        let e38 = {
+                                            let l16 = *ptr1.add(8).cast::<*mut u8>();
+                                            let l17 = *ptr1.add(12).cast::<usize>();
+                                            let len18 = l17;
+                                            let bytes18 = _rt::Vec::from_raw_parts(
+                                                l16.cast(),
+                                                len18,
+                                                len18,
+                                            );
+                                            _rt::string_lift(bytes18)
+                                        };
+                                        Error::Decode(e38)
+                                    }
+                                    4 => {
+                                        let e38 = {
+                                            let l19 = *ptr1.add(8).cast::<*mut u8>();
+                                            let l20 = *ptr1.add(12).cast::<usize>();
+                                            let len21 = l20;
+                                            let bytes21 = _rt::Vec::from_raw_parts(
+                                                l19.cast(),
+                                                len21,
+                                                len21,
+                                            );
+                                            let l22 = i32::from(*ptr1.add(16).cast::<u8>());
+                                            let l23 = i32::from(*ptr1.add(20).cast::<u8>());
+                                            let l27 = i32::from(*ptr1.add(32).cast::<u8>());
+                                            let l31 = i32::from(*ptr1.add(44).cast::<u8>());
+                                            DatabaseError {
+                                                message: _rt::string_lift(bytes21),
+                                                kind: DatabaseErrorKind::_lift(l22 as u8),
+                                                code: match l23 {
+                                                    0 => None,
+                                                    1 => {
+                                                        let e = {
+                                                            let l24 = *ptr1.add(24).cast::<*mut u8>();
+                                                            let l25 = *ptr1.add(28).cast::<usize>();
+                                                            let len26 = l25;
+                                                            let bytes26 = _rt::Vec::from_raw_parts(
+                                                                l24.cast(),
+                                                                len26,
+                                                                len26,
+                                                            );
+                                                            _rt::string_lift(bytes26)
+                                                        };
+                                                        Some(e)
+                                                    }
+                                                    _ => _rt::invalid_enum_discriminant(),
+                                                },
+                                                constraint: match l27 {
+                                                    0 => None,
+                                                    1 => {
+                                                        let e = {
+                                                            let l28 = *ptr1.add(36).cast::<*mut u8>();
+                                                            let l29 = *ptr1.add(40).cast::<usize>();
+                                                            let len30 = l29;
+                                                            let bytes30 = _rt::Vec::from_raw_parts(
+                                                                l28.cast(),
+                                                                len30,
+                                                                len30,
+                                                            );
+                                                            _rt::string_lift(bytes30)
+                                                        };
+                                                        Some(e)
+                                                    }
+                                                    _ => _rt::invalid_enum_discriminant(),
+                                                },
+                                                table: match l31 {
+                                                    0 => None,
+                                                    1 => {
+                                                        let e = {
+                                                            let l32 = *ptr1.add(48).cast::<*mut u8>();
+                                                            let l33 = *ptr1.add(52).cast::<usize>();
+                                                            let len34 = l33;
+                                                            let bytes34 = _rt::Vec::from_raw_parts(
+                                                                l32.cast(),
+                                                                len34,
+                                                                len34,
+                                                            );
+                                                            _rt::string_lift(bytes34)
+                                                        };
+                                                        Some(e)
+                                                    }
+                                                    _ => _rt::invalid_enum_discriminant(),
+                                                },
+                                            }
+                                        };
+                                        Error::Database(e38)
+                                    }
+                                    n => {
+                                        debug_assert_eq!(n, 5, "invalid enum discriminant");
+                                        let e38 = {
+                                            let l35 = *ptr1.add(8).cast::<*mut u8>();
+                                            let l36 = *ptr1.add(12).cast::<usize>();
+                                            let len37 = l36;
+                                            let bytes37 = _rt::Vec::from_raw_parts(
+                                                l35.cast(),
+                                                len37,
+                                                len37,
+                                            );
+                                            _rt::string_lift(bytes37)
+                                        };
+                                        Error::Other(e38)
+                                    }
+                                };
+                                v38
+                            };
+                            Err(e)
+                        }
+                        _ => _rt::invalid_enum_discriminant(),
+                    }
+                }
+            }
+            #[allow(unused_unsafe, clippy::all)]
+            /// Send a chunk of data for the COPY operation that is currently in
+            /// progress.
+            ///
+            /// Chunks do not need to line up with row boundaries.
+            pub fn copy_in_send(data: &[u8]) -> Result<(), Error> {
+                unsafe {
+                    #[repr(align(4))]
+                    struct RetArea([::core::mem::MaybeUninit<u8>; 56]);
+                    let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 56]);
+                    let vec0 = data;
+                    let ptr0 = vec0.as_ptr().cast::<u8>();
+                    let len0 = vec0.len();
+                    let ptr1 = ret_area.0.as_mut_ptr().cast::<u8>();
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(wasm_import_module = "durable:core/sql@2.7.0")]
+                    extern "C" {
+                        #[link_name = "copy-in-send"]
+                        fn wit_import(_: *mut u8, _: usize, _: *mut u8);
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    fn wit_import(_: *mut u8, _: usize, _: *mut u8) {
+                        unreachable!()
+                    }
+                    wit_import(ptr0.cast_mut(), len0, ptr1);
+                    let l2 = i32::from(*ptr1.add(0).cast::<u8>());
+                    match l2 {
+                        0 => {
+                            let e = ();
+                            Ok(e)
+                        }
+                        1 => {
+                            let e = {
+                                let l3 = i32::from(*ptr1.add(4).cast::<u8>());
+                                let v38 = match l3 {
+                                    0 => {
+                                        let e38 = {
+                                            let l4 = *ptr1.add(8).cast::<*mut u8>();
+                                            let l5 = *ptr1.add(12).cast::<usize>();
+                                            let len6 = l5;
+                                            let bytes6 = _rt::Vec::from_raw_parts(
+                                                l4.cast(),
+                                                len6,
+                                                len6,
+                                            );
+                                            let l7 = *ptr1.add(16).cast::<*mut u8>();
+                                            let l8 = *ptr1.add(20).cast::<usize>();
+                                            let len9 = l8;
+                                            let bytes9 = _rt::Vec::from_raw_parts(
+                                                l7.cast(),
+                                                len9,
+                                                len9,
+                                            );
+                                            ColumnDecodeError {
+                                                index: _rt::string_lift(bytes6),
+                                                source: _rt::string_lift(bytes9),
+                                            }
+                                        };
+                                        Error::ColumnDecode(e38)
+                                    }
+                                    1 => {
+                                        let e38 = {
+                                            let l10 = *ptr1.add(8).cast::<*mut u8>();
+                                            let l11 = *ptr1.add(12).cast::<usize>();
+                                            let len12 = l11;
+                                            let bytes12 = _rt::Vec::from_raw_parts(
+                                                l10.cast(),
+                                                len12,
+                                                len12,
+                                            );
+                                            _rt::string_lift(bytes12)
+                                        };
+                                        Error::TypeNotFound(e38)
+                                    }
+                                    2 => {
+                                        let e38 = {
+                                            let l13 = *ptr1.add(8).cast::<*mut u8>();
+                                            let l14 = *ptr1.add(12).cast::<usize>();
+                                            let len15 = l14;
+                                            let bytes15 = _rt::Vec::from_raw_parts(
+                                                l13.cast(),
+                                                len15,
+                                                len15,
+                                            );
+                                            _rt::string_lift(bytes15)
+                                        };
+                                        Error::Encode(e38)
+                                    }
+                                    3 => {
+                                        let e38 = {
+                                            let l16 = *ptr1.add(8).cast::<*mut u8>();
+                                            let l17 = *ptr1.add(12).cast::<usize>();
+                                            let len18 = l17;
+                                            let bytes18 = _rt::Vec::from_raw_parts(
+                                                l16.cast(),
+                                                len18,
+                                                len18,
+                                            );
+                                            _rt::string_lift(bytes18)
+                                        };
+                                        Error::Decode(e38)
+                                    }
+                                    4 => {
+                                        let e38 = {
+                                            let l19 = *ptr1.add(8).cast::<*mut u8>();
+                                            let l20 = *ptr1.add(12).cast::<usize>();
+                                            let len21 = l20;
+                                            let bytes21 = _rt::Vec::from_raw_parts(
+                                                l19.cast(),
+                                                len21,
+                                                len21,
+                                            );
+                                            let l22 = i32::from(*ptr1.add(16).cast::<u8>());
+                                            let l23 = i32::from(*ptr1.add(20).cast::<u8>());
+                                            let l27 = i32::from(*ptr1.add(32).cast::<u8>());
+                                            let l31 = i32::from(*ptr1.add(44).cast::<u8>());
+                                            DatabaseError {
+                                                message: _rt::string_lift(bytes21),
+                                                kind: DatabaseErrorKind::_lift(l22 as u8),
+                                                code: match l23 {
+                                                    0 => None,
+                                                    1 => {
+                                                        let e = {
+                                                            let l24 = *ptr1.add(24).cast::<*mut u8>();
+                                                            let l25 = *ptr1.add(28).cast::<usize>();
+                                                            let len26 = l25;
+                                                            let bytes26 = _rt::Vec::from_raw_parts(
+                                                                l24.cast(),
+                                                                len26,
+                                                                len26,
+                                                            );
+                                                            _rt::string_lift(bytes26)
+                                                        };
+                                                        Some(e)
+                                                    }
+                                                    _ => _rt::invalid_enum_discriminant(),
+                                                },
+                                                constraint: match l27 {
+                                                    0 => None,
+                                                    1 => {
+                                                        let e = {
+                                                            let l28 = *ptr1.add(36).cast::<*mut u8>();
+                                                            let l29 = *ptr1.add(40).cast::<usize>();
+                                                            let len30 = l29;
+                                                            let bytes30 = _rt::Vec::from_raw_parts(
+                                                                l28.cast(),
+                                                                len30,
+                                                                len30,
+                                                            );
+                                                            _rt::string_lift(bytes30)
+                                                        };
+                                                        Some(e)
+                                                    }
+                                                    _ => _rt::invalid_enum_discriminant(),
+                                                },
+                                                table: match l31 {
+                                                    0 => None,
+                                                    1 => {
+                                                        let e = {
+                                                            let l32 = *ptr1.add(48).cast::<*mut u8>();
+                                                            let l33 = *ptr1.add(52).cast::<usize>();
+                                                            let len34 = l33;
+                                                            let bytes34 = _rt::Vec::from_raw_parts(
+                                                                l32.cast(),
+                                                                len34,
+                                                                len34,
+                                                            );
+                                                            _rt::string_lift(bytes34)
+                                                        };
+                                                        Some(e)
+                                                    }
+                                                    _ => _rt::invalid_enum_discriminant(),
+                                                },
+                                            }
+                                        };
+                                        Error::Database(e38)
+                                    }
+                                    n => {
+                                        debug_assert_eq!(n, 5, "invalid enum discriminant");
+                                        let e38 = {
+                                            let l35 = *ptr1.add(8).cast::<*mut u8>();
+                                            let l36 = *ptr1.add(12).cast::<usize>();
+                                            let len37 = l36;
+                                            let bytes37 = _rt::Vec::from_raw_parts(
+                                                l35.cast(),
+                                                len37,
+                                                len37,
+                                            );
+                                            _rt::string_lift(bytes37)
+                                        };
+                                        Error::Other(e38)
+                                    }
+                                };
+                                v38
+                            };
+                            Err(e)
+                        }
+                        _ => _rt::invalid_enum_discriminant(),
+                    }
+                }
+            }
+            #[allow(unused_unsafe, clippy::all)]
+            /// Complete the COPY operation that is currently in progress.
+            ///
+            /// Returns the number of rows that were copied.
+            pub fn copy_in_finish() -> Result<u64, Error> {
+                unsafe {
+                    #[repr(align(8))]
+                    struct RetArea([::core::mem::MaybeUninit<u8>; 64]);
+                    let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 64]);
+                    let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(wasm_import_module = "durable:core/sql@2.7.0")]
+                    extern "C" {
+                        #[link_name = "copy-in-finish"]
+                        fn wit_import(_: *mut u8);
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    fn wit_import(_: *mut u8) {
+                        unreachable!()
+                    }
+                    wit_import(ptr0);
+                    let l1 = i32::from(*ptr0.add(0).cast::<u8>());
+                    match l1 {
+                        0 => {
+                            let e = {
+                                let l2 = *ptr0.add(8).cast::<i64>();
+                                l2 as u64
+                            };
+                            Ok(e)
+                        }
+                        1 => {
+                            let e = {
+                                let l3 = i32::from(*ptr0.add(8).cast::<u8>());
+                                let v38 = match l3 {
+                                    0 => {
+                                        let e38 = {
+                                            let l4 = *ptr0.add(12).cast::<*mut u8>();
+                                            let l5 = *ptr0.add(16).cast::<usize>();
+                                            let len6 = l5;
+                                            let bytes6 = _rt::Vec::from_raw_parts(
+                                                l4.cast(),
+                                                len6,
+                                                len6,
+                                            );
+                                            let l7 = *ptr0.add(20).cast::<*mut u8>();
+                                            let l8 = *ptr0.add(24).cast::<usize>();
+                                            let len9 = l8;
+                                            let bytes9 = _rt::Vec::from_raw_parts(
+                                                l7.cast(),
+                                                len9,
+                                                len9,
+                                            );
+                                            ColumnDecodeError {
+                                                index: _rt::string_lift(bytes6),
+                                                source: _rt::string_lift(bytes9),
+                                            }
+                                        };
+                                        Error::ColumnDecode(e38)
+                                    }
+                                    1 => {
+                                        let e38 = {
+                                            let l10 = *ptr0.add(12).cast::<*mut u8>();
+                                            let l11 = *ptr0.add(16).cast::<usize>();
+                                            let len12 = l11;
+                                            let bytes12 = _rt::Vec::from_raw_parts(
+                                                l10.cast(),
+                                                len12,
+                                                len12,
+                                            );
+                                            _rt::string_lift(bytes12)
+                                        };
+                                        Error::TypeNotFound(e38)
+                                    }
+                                    2 => {
+                                        let e38 = {
+                                            let l13 = *ptr0.add(12).cast::<*mut u8>();
+                                            let l14 = *ptr0.add(16).cast::<usize>();
+                                            let len15 = l14;
+                                            let bytes15 = _rt::Vec::from_raw_parts(
+                                                l13.cast(),
+                                                len15,
+                                                len15,
+                                            );
+                                            _rt::string_lift(bytes15)
+                                        };
+                                        Error::Encode(e38)
+                                    }
+                                    3 => {
+                                        let e38 = {
+                                            let l16 = *ptr0.add(12).cast::<*mut u8>();
+                                            let l17 = *ptr0.add(16).cast::<usize>();
+                                            let len18 = l17;
+                                            let bytes18 = _rt::Vec::from_raw_parts(
+                                                l16.cast(),
+                                                len18,
+                                                len18,
+                                            );
+                                            _rt::string_lift(bytes18)
+                                        };
+                                        Error::Decode(e38)
+                                    }
+                                    4 => {
+                                        let e38 = {
+                                            let l19 = *ptr0.add(12).cast::<*mut u8>();
+                                            let l20 = *ptr0.add(16).cast::<usize>();
+                                            let len21 = l20;
+                                            let bytes21 = _rt::Vec::from_raw_parts(
+                                                l19.cast(),
+                                                len21,
+                                                len21,
+                                            );
+                                            let l22 = i32::from(*ptr0.add(20).cast::<u8>());
+                                            let l23 = i32::from(*ptr0.add(24).cast::<u8>());
+                                            let l27 = i32::from(*ptr0.add(36).cast::<u8>());
+                                            let l31 = i32::from(*ptr0.add(48).cast::<u8>());
+                                            DatabaseError {
+                                                message: _rt::string_lift(bytes21),
+                                                kind: DatabaseErrorKind::_lift(l22 as u8),
+                                                code: match l23 {
+                                                    0 => None,
+                                                    1 => {
+                                                        let e = {
+                                                            let l24 = *ptr0.add(28).cast::<*mut u8>();
+                                                            let l25 = *ptr0.add(32).cast::<usize>();
+                                                            let len26 = l25;
+                                                            let bytes26 = _rt::Vec::from_raw_parts(
+                                                                l24.cast(),
+                                                                len26,
+                                                                len26,
+                                                            );
+                                                            _rt::string_lift(bytes26)
+                                                        };
+                                                        Some(e)
+                                                    }
+                                                    _ => _rt::invalid_enum_discriminant(),
+                                                },
+                                                constraint: match l27 {
+                                                    0 => None,
+                                                    1 => {
+                                                        let e = {
+                                                            let l28 = *ptr0.add(40).cast::<*mut u8>();
+                                                            let l29 = *ptr0.add(44).cast::<usize>();
+                                                            let len30 = l29;
+                                                            let bytes30 = _rt::Vec::from_raw_parts(
+                                                                l28.cast(),
+                                                                len30,
+                                                                len30,
+                                                            );
+                                                            _rt::string_lift(bytes30)
+                                                        };
+                                                        Some(e)
+                                                    }
+                                                    _ => _rt::invalid_enum_discriminant(),
+                                                },
+                                                table: match l31 {
+                                                    0 => None,
+                                                    1 => {
+                                                        let e = {
+                                                            let l32 = *ptr0.add(52).cast::<*mut u8>();
+                                                            let l33 = *ptr0.add(56).cast::<usize>();
+                                                            let len34 = l33;
+                                                            let bytes34 = _rt::Vec::from_raw_parts(
+                                                                l32.cast(),
+                                                                len34,
+                                                                len34,
+                                                            );
+                                                            _rt::string_lift(bytes34)
+                                                        };
+                                                        Some(e)
+                                                    }
+                                                    _ => _rt::invalid_enum_discriminant(),
+                                                },
+                                            }
+                                        };
+                                        Error::Database(e38)
+                                    }
+                                    n => {
+                                        debug_assert_eq!(n, 5, "invalid enum discriminant");
+                                        let e38 = {
+                                            let l35 = *ptr0.add(12).cast::<*mut u8>();
+                                            let l36 = *ptr0.add(16).cast::<usize>();
+                                            let len37 = l36;
+                                            let bytes37 = _rt::Vec::from_raw_parts(
+                                                l35.cast(),
+                                                len37,
+                                                len37,
+                                            );
+                                            _rt::string_lift(bytes37)
+                                        };
+                                        Error::Other(e38)
+                                    }
+                                };
+                                v38
+                            };
+                            Err(e)
+                        }
+                        _ => _rt::invalid_enum_discriminant(),
+                    }
+                }
+            }
+            #[allow(unused_unsafe, clippy::all)]
+            /// Abort the COPY operation that is currently in progress, discarding all
+            /// data that has been sent so far.
+            ///
+            /// The message is recorded in the database logs as the reason for the
+            /// abort.
+            pub fn copy_in_abort(message: &str) -> Result<(), Error> {
+                unsafe {
+                    #[repr(align(4))]
+                    struct RetArea([::core::mem::MaybeUninit<u8>; 56]);
+                    let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 56]);
+                    let vec0 = message;
+                    let ptr0 = vec0.as_ptr().cast::<u8>();
+                    let len0 = vec0.len();
+                    let ptr1 = ret_area.0.as_mut_ptr().cast::<u8>();
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(wasm_import_module = "durable:core/sql@2.7.0")]
+                    extern "C" {
+                        #[link_name = "copy-in-abort"]
+                        fn wit_import(_: *mut u8, _: usize, _: *mut u8);
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    fn wit_import(_: *mut u8, _: usize, _: *mut u8) {
+                        unreachable!()
+                    }
+                    wit_import(ptr0.cast_mut(), len0, ptr1);
+                    let l2 = i32::from(*ptr1.add(0).cast::<u8>());
+                    match l2 {
+                        0 => {
+                            let e = ();
+                            Ok(e)
+                        }
+                        1 => {
+                            let e = {
+                                let l3 = i32::from(*ptr1.add(4).cast::<u8>());
+                                let v38 = match l3 {
+                                    0 => {
+                                        let e38 = {
+                                            let l4 = *ptr1.add(8).cast::<*mut u8>();
+                                            let l5 = *ptr1.add(12).cast::<usize>();
+                                            let len6 = l5;
+                                            let bytes6 = _rt::Vec::from_raw_parts(
+                                                l4.cast(),
+                                                len6,
+                                                len6,
+                                            );
+                                            let l7 = *ptr1.add(16).cast::<*mut u8>();
+                                            let l8 = *ptr1.add(20).cast::<usize>();
+                                            let len9 = l8;
+                                            let bytes9 = _rt::Vec::from_raw_parts(
+                                                l7.cast(),
+                                                len9,
+                                                len9,
+                                            );
+                                            ColumnDecodeError {
+                                                index: _rt::string_lift(bytes6),
+                                                source: _rt::string_lift(bytes9),
+                                            }
+                                        };
+                                        Error::ColumnDecode(e38)
+                                    }
+                                    1 => {
+                                        let e38 = {
+                                            let l10 = *ptr1.add(8).cast::<*mut u8>();
+                                            let l11 = *ptr1.add(12).cast::<usize>();
+                                            let len12 = l11;
+                                            let bytes12 = _rt::Vec::from_raw_parts(
+                                                l10.cast(),
+                                                len12,
+                                                len12,
+                                            );
+                                            _rt::string_lift(bytes12)
+                                        };
+                                        Error::TypeNotFound(e38)
+                                    }
+                                    2 => {
+                                        let e38 = {
+                                            let l13 = *ptr1.add(8).cast::<*mut u8>();
+                                            let l14 = *ptr1.add(12).cast::<usize>();
+                                            let len15 = l14;
+                                            let bytes15 = _rt::Vec::from_raw_parts(
+                                                l13.cast(),
+                                                len15,
+                                                len15,
+                                            );
+                                            _rt::string_lift(bytes15)
+                                        };
+                                        Error::Encode(e38)
+                                    }
+                                    3 => {
+                                        let e38 = {
+                                            let l16 = *ptr1.add(8).cast::<*mut u8>();
+                                            let l17 = *ptr1.add(12).cast::<usize>();
+                                            let len18 = l17;
+                                            let bytes18 = _rt::Vec::from_raw_parts(
+                                                l16.cast(),
+                                                len18,
+                                                len18,
+                                            );
+                                            _rt::string_lift(bytes18)
+                                        };
+                                        Error::Decode(e38)
+                                    }
+                                    4 => {
+                                        let e38 = {
+                                            let l19 = *ptr1.add(8).cast::<*mut u8>();
+                                            let l20 = *ptr1.add(12).cast::<usize>();
+                                            let len21 = l20;
+                                            let bytes21 = _rt::Vec::from_raw_parts(
+                                                l19.cast(),
+                                                len21,
+                                                len21,
+                                            );
+                                            let l22 = i32::from(*ptr1.add(16).cast::<u8>());
+                                            let l23 = i32::from(*ptr1.add(20).cast::<u8>());
+                                            let l27 = i32::from(*ptr1.add(32).cast::<u8>());
+                                            let l31 = i32::from(*ptr1.add(44).cast::<u8>());
+                                            DatabaseError {
+                                                message: _rt::string_lift(bytes21),
+                                                kind: DatabaseErrorKind::_lift(l22 as u8),
+                                                code: match l23 {
+                                                    0 => None,
+                                                    1 => {
+                                                        let e = {
+                                                            let l24 = *ptr1.add(24).cast::<*mut u8>();
+                                                            let l25 = *ptr1.add(28).cast::<usize>();
+                                                            let len26 = l25;
+                                                            let bytes26 = _rt::Vec::from_raw_parts(
+                                                                l24.cast(),
+                                                                len26,
+                                                                len26,
+                                                            );
+                                                            _rt::string_lift(bytes26)
+                                                        };
+                                                        Some(e)
+                                                    }
+                                                    _ => _rt::invalid_enum_discriminant(),
+                                                },
+                                                constraint: match l27 {
+                                                    0 => None,
+                                                    1 => {
+                                                        let e = {
+                                                            let l28 = *ptr1.add(36).cast::<*mut u8>();
+                                                            let l29 = *ptr1.add(40).cast::<usize>();
+                                                            let len30 = l29;
+                                                            let bytes30 = _rt::Vec::from_raw_parts(
+                                                                l28.cast(),
+                                                                len30,
+                                                                len30,
+                                                            );
+                                                            _rt::string_lift(bytes30)
+                                                        };
+                                                        Some(e)
+                                                    }
+                                                    _ => _rt::invalid_enum_discriminant(),
+                                                },
+                                                table: match l31 {
+                                                    0 => None,
+                                                    1 => {
+                                                        let e = {
+                                                            let l32 = *ptr1.add(48).cast::<*mut u8>();
+                                                            let l33 = *ptr1.add(52).cast::<usize>();
+                                                            let len34 = l33;
+                                                            let bytes34 = _rt::Vec::from_raw_parts(
+                                                                l32.cast(),
+                                                                len34,
+                                                                len34,
+                                                            );
+                                                            _rt::string_lift(bytes34)
+                                                        };
+                                                        Some(e)
+                                                    }
+                                                    _ => _rt::invalid_enum_discriminant(),
+                                                },
+                                            }
+                                        };
+                                        Error::Database(e38)
+                                    }
+                                    n => {
+                                        debug_assert_eq!(n, 5, "invalid enum discriminant");
+                                        let e38 = {
+                                            let l35 = *ptr1.add(8).cast::<*mut u8>();
+                                            let l36 = *ptr1.add(12).cast::<usize>();
+                                            let len37 = l36;
+                                            let bytes37 = _rt::Vec::from_raw_parts(
+                                                l35.cast(),
+                                                len37,
+                                                len37,
+                                            );
+                                            _rt::string_lift(bytes37)
+                                        };
+                                        Error::Other(e38)
+                                    }
+                                };
+                                v38
+                            };
+                            Err(e)
+                        }
+                        _ => _rt::invalid_enum_discriminant(),
+                    }
+                }
+            }
+            #[allow(unused_unsafe, clippy::all)]
+            /// Prepare a statement within the current database transaction and
+            /// describe its parameters and output columns, without executing it.
+            pub fn describe(sql: &str) -> Result<StatementDescription, Error> {
+                unsafe {
+                    #[repr(align(4))]
+                    struct RetArea([::core::mem::MaybeUninit<u8>; 56]);
+                    let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 56]);
+                    let vec0 = sql;
+                    let ptr0 = vec0.as_ptr().cast::<u8>();
+                    let len0 = vec0.len();
+                    let ptr1 = ret_area.0.as_mut_ptr().cast::<u8>();
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(wasm_import_module = "durable:core/sql@2.7.0")]
+                    extern "C" {
+                        #[link_name = "describe"]
+                        fn wit_import(_: *mut u8, _: usize, _: *mut u8);
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    fn wit_import(_: *mut u8, _: usize, _: *mut u8) {
+                        unreachable!()
+                    }
+                    wit_import(ptr0.cast_mut(), len0, ptr1);
+                    let l2 = i32::from(*ptr1.add(0).cast::<u8>());
+                    match l2 {
+                        0 => {
+                            let e = {
+                                let l3 = *ptr1.add(4).cast::<*mut u8>();
+                                let l4 = *ptr1.add(8).cast::<usize>();
+                                let base6 = l3;
+                                let len6 = l4;
+                                let mut result6 = _rt::Vec::with_capacity(len6);
+                                for i in 0..len6 {
+                                    let base = base6.add(i * 4);
+                                    let e6 = {
+                                        let l5 = *base.add(0).cast::<i32>();
+                                        TypeInfo::from_handle(l5 as u32)
+                                    };
+                                    result6.push(e6);
+                                }
+                                _rt::cabi_dealloc(base6, len6 * 4, 4);
+                                let l7 = *ptr1.add(12).cast::<*mut u8>();
+                                let l8 = *ptr1.add(16).cast::<usize>();
+                                let base15 = l7;
+                                let len15 = l8;
+                                let mut result15 = _rt::Vec::with_capacity(len15);
+                                for i in 0..len15 {
+                                    let base = base15.add(i * 16);
+                                    let e15 = {
+                                        let l9 = *base.add(0).cast::<*mut u8>();
+                                        let l10 = *base.add(4).cast::<usize>();
+                                        let len11 = l10;
+                                        let bytes11 = _rt::Vec::from_raw_parts(
+                                            l9.cast(),
+                                            len11,
+                                            len11,
+                                        );
+                                        let l12 = *base.add(8).cast::<i32>();
+                                        let l13 = i32::from(*base.add(12).cast::<u8>());
+                                        ColumnDescription {
+                                            name: _rt::string_lift(bytes11),
+                                            type_info: TypeInfo::from_handle(l12 as u32),
+                                            nullable: match l13 {
+                                                0 => None,
+                                                1 => {
+                                                    let e = {
+                                                        let l14 = i32::from(*base.add(13).cast::<u8>());
+                                                        _rt::bool_lift(l14 as u8)
+                                                    };
+                                                    Some(e)
+                                                }
+                                                _ => _rt::invalid_enum_discriminant(),
+                                            },
+                                        }
+                                    };
+                                    result15.push(e15);
+                                }
+                                _rt::cabi_dealloc(base15, len15 * 16, 4);
+                                StatementDescription {
+                                    parameters: result6,
+                                    columns: result15,
+                                }
+                            };
+                            Ok(e)
+                        }
+                        1 => {
+                            let e = {
+                                let l16 = i32::from(*ptr1.add(4).cast::<u8>());
+                                let v51 = match l16 {
+                                    0 => {
+                                        let e51 = {
+                                            let l17 = *ptr1.add(8).cast::<*mut u8>();
+                                            let l18 = *ptr1.add(12).cast::<usize>();
+                                            let len19 = l18;
+                                            let bytes19 = _rt::Vec::from_raw_parts(
+                                                l17.cast(),
+                                                len19,
+                                                len19,
+                                            );
+                                            let l20 = *ptr1.add(16).cast::<*mut u8>();
+                                            let l21 = *ptr1.add(20).cast::<usize>();
+                                            let len22 = l21;
+                                            let bytes22 = _rt::Vec::from_raw_parts(
+                                                l20.cast(),
+                                                len22,
+                                                len22,
+                                            );
+                                            ColumnDecodeError {
+                                                index: _rt::string_lift(bytes19),
+                                                source: _rt::string_lift(bytes22),
+                                            }
+                                        };
+                                        Error::ColumnDecode(e51)
+                                    }
+                                    1 => {
+                                        let e51 = {
+                                            let l23 = *ptr1.add(8).cast::<*mut u8>();
+                                            let l24 = *ptr1.add(12).cast::<usize>();
+                                            let len25 = l24;
+                                            let bytes25 = _rt::Vec::from_raw_parts(
+                                                l23.cast(),
+                                                len25,
+                                                len25,
+                                            );
+                                            _rt::string_lift(bytes25)
+                                        };
+                                        Error::TypeNotFound(e51)
+                                    }
+                                    2 => {
+                                        let e51 = {
+                                            let l26 = *ptr1.add(8).cast::<*mut u8>();
+                                            let l27 = *ptr1.add(12).cast::<usize>();
+                                            let len28 = l27;
+                                            let bytes28 = _rt::Vec::from_raw_parts(
+                                                l26.cast(),
+                                                len28,
+                                                len28,
+                                            );
+                                            _rt::string_lift(bytes28)
+                                        };
+                                        Error::Encode(e51)
+                                    }
+                                    3 => {
+                                        let e51 = {
+                                            let l29 = *ptr1.add(8).cast::<*mut u8>();
+                                            let l30 = *ptr1.add(12).cast::<usize>();
+                                            let len31 = l30;
+                                            let bytes31 = _rt::Vec::from_raw_parts(
+                                                l29.cast(),
+                                                len31,
+                                                len31,
+                                            );
+                                            _rt::string_lift(bytes31)
+                                        };
+                                        Error::Decode(e51)
+                                    }
+                                    4 => {
+                                        let e51 = {
+                                            let l32 = *ptr1.add(8).cast::<*mut u8>();
+                                            let l33 = *ptr1.add(12).cast::<usize>();
+                                            let len34 = l33;
+                                            let bytes34 = _rt::Vec::from_raw_parts(
+                                                l32.cast(),
+                                                len34,
+                                                len34,
+                                            );
+                                            let l35 = i32::from(*ptr1.add(16).cast::<u8>());
+                                            let l36 = i32::from(*ptr1.add(20).cast::<u8>());
+                                            let l40 = i32::from(*ptr1.add(32).cast::<u8>());
+                                            let l44 = i32::from(*ptr1.add(44).cast::<u8>());
+                                            DatabaseError {
+                                                message: _rt::string_lift(bytes34),
+                                                kind: DatabaseErrorKind::_lift(l35 as u8),
+                                                code: match l36 {
+                                                    0 => None,
+                                                    1 => {
+                                                        let e = {
+                                                            let l37 = *ptr1.add(24).cast::<*mut u8>();
+                                                            let l38 = *ptr1.add(28).cast::<usize>();
+                                                            let len39 = l38;
+                                                            let bytes39 = _rt::Vec::from_raw_parts(
+                                                                l37.cast(),
+                                                                len39,
+                                                                len39,
+                                                            );
+                                                            _rt::string_lift(bytes39)
+                                                        };
+                                                        Some(e)
+                                                    }
+                                                    _ => _rt::invalid_enum_discriminant(),
+                                                },
+                                                constraint: match l40 {
+                                                    0 => None,
+                                                    1 => {
+                                                        let e = {
+                                                            let l41 = *ptr1.add(36).cast::<*mut u8>();
+                                                            let l42 = *ptr1.add(40).cast::<usize>();
+                                                            let len43 = l42;
+                                                            let bytes43 = _rt::Vec::from_raw_parts(
+                                                                l41.cast(),
+                                                                len43,
+                                                                len43,
+                                                            );
+                                                            _rt::string_lift(bytes43)
+                                                        };
+                                                        Some(e)
+                                                    }
+                                                    _ => _rt::invalid_enum_discriminant(),
+                                                },
+                                                table: match l44 {
+                                                    0 => None,
+                                                    1 => {
+                                                        let e = {
+                                                            let l45 = *ptr1.add(48).cast::<*mut u8>();
+                                                            let l46 = *ptr1.add(52).cast::<usize>();
+                                                            let len47 = l46;
+                                                            let bytes47 = _rt::Vec::from_raw_parts(
+                                                                l45.cast(),
+                                                                len47,
+                                                                len47,
+                                                            );
+                                                            _rt::string_lift(bytes47)
+                                                        };
+                                                        Some(e)
+                                                    }
+                                                    _ => _rt::invalid_enum_discriminant(),
+                                                },
+                                            }
+                                        };
+                                        Error::Database(e51)
+                                    }
+                                    n => {
+                                        debug_assert_eq!(n, 5, "invalid enum discriminant");
+                                        let e51 = {
+                                            let l48 = *ptr1.add(8).cast::<*mut u8>();
+                                            let l49 = *ptr1.add(12).cast::<usize>();
+                                            let len50 = l49;
+                                            let bytes50 = _rt::Vec::from_raw_parts(
+                                                l48.cast(),
+                                                len50,
+                                                len50,
+                                            );
+                                            _rt::string_lift(bytes50)
+                                        };
+                                        Error::Other(e51)
+                                    }
+                                };
+                                v51
+                            };
+                            Err(e)
+                        }
+                        _ => _rt::invalid_enum_discriminant(),
+                    }
+                }
+            }
+            #[allow(unused_unsafe, clippy::all)]
+            /// Block this task until a postgres notification is sent on `channel`,
+            /// then return it.
+            ///
+            /// The runtime only starts listening on the channel once this is called, so
+            /// notifications sent before that point are not delivered. The task may be
+            /// suspended while it waits, in which case the notification is recorded
+            /// by whichever worker receives it and the task is woken back up.
+            ///
+            /// Channels prefixed with the name of durable's schema (e.g. `durable:`)
+            /// are reserved and listening on them results in a trap.
+            ///
+            /// Calling this function from within a transaction results in a trap.
+            pub fn listen(channel: &str) -> PgNotification {
+                unsafe {
+                    #[repr(align(4))]
+                    struct RetArea([::core::mem::MaybeUninit<u8>; 16]);
+                    let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 16]);
+                    let vec0 = channel;
+                    let ptr0 = vec0.as_ptr().cast::<u8>();
+                    let len0 = vec0.len();
+                    let ptr1 = ret_area.0.as_mut_ptr().cast::<u8>();
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(wasm_import_module = "durable:core/sql@2.7.0")]
+                    extern "C" {
+                        #[link_name = "listen"]
+                        fn wit_import(_: *mut u8, _: usize, _: *mut u8);
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    fn wit_import(_: *mut u8, _: usize, _: *mut u8) {
+                        unreachable!()
+                    }
+                    wit_import(ptr0.cast_mut(), len0, ptr1);
+                    let l2 = *ptr1.add(0).cast::<*mut u8>();
+                    let l3 = *ptr1.add(4).cast::<usize>();
+                    let len4 = l3;
+                    let bytes4 = _rt::Vec::from_raw_parts(l2.cast(), len4, len4);
+                    let l5 = *ptr1.add(8).cast::<*mut u8>();
+                    let l6 = *ptr1.add(12).cast::<usize>();
+                    let len7 = l6;
+                    let bytes7 = _rt::Vec::from_raw_parts(l5.cast(), len7, len7);
+                    PgNotification {
+                        channel: _rt::string_lift(bytes4),
+                        payload: _rt::string_lift(bytes7),
+                    }
+                }
+            }
+            #[allow(unused_unsafe, clippy::all)]
+            /// Make a query to the database, with support for additional options.
+            ///
+            /// This behaves exactly like `query` otherwise.
+            pub fn query2(sql: &str, params: _rt::Vec<Value>, options: Options2) {
+                unsafe {
+                    let vec0 = sql;
+                    let ptr0 = vec0.as_ptr().cast::<u8>();
+                    let len0 = vec0.len();
+                    let vec1 = &params;
+                    let len1 = vec1.len();
+                    let layout1 = _rt::alloc::Layout::from_size_align_unchecked(
+                        vec1.len() * 4,
+                        4,
+                    );
+                    let result1 = if layout1.size() != 0 {
+                        let ptr = _rt::alloc::alloc(layout1).cast::<u8>();
+                        if ptr.is_null() {
+                            _rt::alloc::handle_alloc_error(layout1);
+                        }
+                        ptr
+                    } else {
+                        { ::core::ptr::null_mut() }
+                    };
+                    for (i, e) in vec1.into_iter().enumerate() {
+                        let base = result1.add(i * 4);
+                        {
+                            *base.add(0).cast::<i32>() = (e).take_handle() as i32;
+                        }
+                    }
+                    let Options2 {
+                        limit: limit2,
+                        persistent: persistent2,
+                        collect_stats: collect_stats2,
+                    } = options;
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(wasm_import_module = "durable:core/sql@2.7.0")]
+                    extern "C" {
+                        #[link_name = "query2"]
+                        fn wit_import(
+                            _: *mut u8,
+                            _: usize,
+                            _: *mut u8,
+                            _: usize,
+                            _: i32,
+                            _: i32,
+                            _: i32,
+                        );
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    fn wit_import(
+                        _: *mut u8,
+                        _: usize,
+                        _: *mut u8,
+                        _: usize,
+                        _: i32,
+                        _: i32,
+                        _: i32,
+                    ) {
+                        unreachable!()
+                    }
+                    wit_import(
+                        ptr0.cast_mut(),
+                        len0,
+                        result1,
+                        len1,
+                        _rt::as_i32(limit2),
+                        match persistent2 {
+                            true => 1,
+                            false => 0,
+                        },
+                        match collect_stats2 {
+                            true => 1,
+                            false => 0,
+                        },
+                    );
+                    if layout1.size() != 0 {
+                        _rt::alloc::dealloc(result1.cast(), layout1);
+                    }
+                }
+            }
+            #[allow(unused_unsafe, clippy::all)]
+            /// Get the execution statistics for the last query made within the
+            /// current transaction.
+            ///
+            /// This returns none if the last query did not request statistics or if
+            /// not all of its results have been fetched yet.
+            pub fn last_query_stats() -> Option<QueryStats> {
+                unsafe {
+                    #[repr(align(8))]
+                    struct RetArea([::core::mem::MaybeUninit<u8>; 48]);
+                    let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 48]);
+                    let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(wasm_import_module = "durable:core/sql@2.7.0")]
+                    extern "C" {
+                        #[link_name = "last-query-stats"]
+                        fn wit_import(_: *mut u8);
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    fn wit_import(_: *mut u8) {
+                        unreachable!()
+                    }
+                    wit_import(ptr0);
+                    let l1 = i32::from(*ptr0.add(0).cast::<u8>());
+                    match l1 {
+                        0 => None,
+                        1 => {
+                            let e = {
+                                let l2 = *ptr0.add(8).cast::<i64>();
+                                let l3 = i32::from(*ptr0.add(16).cast::<u8>());
+                                let l5 = i32::from(*ptr0.add(32).cast::<u8>());
+                                QueryStats {
+                                    duration: l2 as u64,
+                                    execution_time: match l3 {
+                                        0 => None,
+                                        1 => {
+                                            let e = {
+                                                let l4 = *ptr0.add(24).cast::<i64>();
+                                                l4 as u64
+                                            };
+                                            Some(e)
+                                        }
+                                        _ => _rt::invalid_enum_discriminant(),
+                                    },
+                                    rows_scanned: match l5 {
+                                        0 => None,
+                                        1 => {
+                                            let e = {
+                                                let l6 = *ptr0.add(40).cast::<i64>();
+                                                l6 as u64
+                                            };
+                                            Some(e)
+                                        }
+                                        _ => _rt::invalid_enum_discriminant(),
+                                    },
+                                }
+                            };
+                            Some(e)
+                        }
+                        _ => _rt::invalid_enum_discriminant(),
+                    }
+                }
+            }
+            #[allow(unused_unsafe, clippy::all)]
+            /// Run a batch of statements within the current database transaction.
+            ///
+            /// The statements are run one after another, in order, without returning
+            /// to the workflow in between. Any rows returned by the statements are
+            /// discarded.
+            ///
+            /// The results are then fetched by calling `fetch` until it returns none.
+            /// Each statement produces a single `count` result. If a statement fails
+            /// then `fetch` returns its error and the remaining statements are not
+            /// run.
+            pub fn batch(statements: &[BatchStatement]) {
+                unsafe {
+                    let mut cleanup_list = _rt::Vec::new();
+                    let vec3 = statements;
+                    let len3 = vec3.len();
+                    let layout3 = _rt::alloc::Layout::from_size_align_unchecked(
+                        vec3.len() * 16,
+                        4,
+                    );
+                    let result3 = if layout3.size() != 0 {
+                        let ptr = _rt::alloc::alloc(layout3).cast::<u8>();
+                        if ptr.is_null() {
+                            _rt::alloc::handle_alloc_error(layout3);
+                        }
+                        ptr
+                    } else {
+                        { ::core::ptr::null_mut() }
+                    };
+                    for (i, e) in vec3.into_iter().enumerate() {
+                        let base = result3.add(i * 16);
+                        {
+                            let BatchStatement { sql: sql0, params: params0 } = e;
+                            let vec1 = sql0;
+                            let ptr1 = vec1.as_ptr().cast::<u8>();
+                            let len1 = vec1.len();
+                            *base.add(4).cast::<usize>() = len1;
+                            *base.add(0).cast::<*mut u8>() = ptr1.cast_mut();
+                            let vec2 = params0;
+                            let len2 = vec2.len();
+                            let layout2 = _rt::alloc::Layout::from_size_align_unchecked(
+                                vec2.len() * 4,
+                                4,
+                            );
+                            let result2 = if layout2.size() != 0 {
+                                let ptr = _rt::alloc::alloc(layout2).cast::<u8>();
+                                if ptr.is_null() {
+                                    _rt::alloc::handle_alloc_error(layout2);
+                                }
+                                ptr
+                            } else {
+                                { ::core::ptr::null_mut() }
+                            };
+                            for (i, e) in vec2.into_iter().enumerate() {
+                                let base = result2.add(i * 4);
+                                {
+                                    *base.add(0).cast::<i32>() = (e).take_handle() as i32;
+                                }
+                            }
+                            *base.add(12).cast::<usize>() = len2;
+                            *base.add(8).cast::<*mut u8>() = result2;
+                            cleanup_list.extend_from_slice(&[(result2, layout2)]);
+                        }
+                    }
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(wasm_import_module = "durable:core/sql@2.7.0")]
+                    extern "C" {
+                        #[link_name = "batch"]
+                        fn wit_import(_: *mut u8, _: usize);
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    fn wit_import(_: *mut u8, _: usize) {
+                        unreachable!()
+                    }
+                    wit_import(result3, len3);
+                    if layout3.size() != 0 {
+                        _rt::alloc::dealloc(result3.cast(), layout3);
+                    }
+                    for (ptr, layout) in cleanup_list {
+                        if layout.size() != 0 {
+                            _rt::alloc::dealloc(ptr.cast(), layout);
+                        }
+                    }
+                }
+            }
+            #[allow(unused_unsafe, clippy::all)]
+            /// Evict the prepared statements that this program has cached on the
+            /// worker's database connections.
+            ///
+            /// This should be called after making schema changes that alter the
+            /// result types of statements that the program has already run. If this
+            /// is called within a database transaction then the statements cached on
+            /// the current connection are evicted immediately. All other connections
+            /// are cleared once the transaction commits.
+            pub fn clear_statement_cache() {
+                unsafe {
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(wasm_import_module = "durable:core/sql@2.7.0")]
+                    extern "C" {
+                        #[link_name = "clear-statement-cache"]
+                        fn wit_import();
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    fn wit_import() {
+                        unreachable!()
+                    }
+                    wit_import();
+                }
+            }
+            #[allow(unused_unsafe, clippy::all)]
+            /// Ask the database how it would run a statement within the current
+            /// database transaction, without running it.
+            ///
+            /// This returns the output of `EXPLAIN (FORMAT JSON)` for the statement.
+            /// The statement is checked against the worker's SQL policy exactly as
+            /// if it were being run.
+            pub fn explain(sql: &str, params: _rt::Vec<Value>) -> Result<_rt::String, Error> {
+                unsafe {
+                    #[repr(align(4))]
+                    struct RetArea([::core::mem::MaybeUninit<u8>; 56]);
+                    let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 56]);
+                    let vec0 = sql;
+                    let ptr0 = vec0.as_ptr().cast::<u8>();
+                    let len0 = vec0.len();
+                    let vec1 = &params;
+                    let len1 = vec1.len();
+                    let layout1 = _rt::alloc::Layout::from_size_align_unchecked(
+                        vec1.len() * 4,
+                        4,
+                    );
+                    let result1 = if layout1.size() != 0 {
+                        let ptr = _rt::alloc::alloc(layout1).cast::<u8>();
+                        if ptr.is_null() {
+                            _rt::alloc::handle_alloc_error(layout1);
+                        }
+                        ptr
+                    } else {
+                        { ::core::ptr::null_mut() }
+                    };
+                    for (i, e) in vec1.into_iter().enumerate() {
+                        let base = result1.add(i * 4);
+                        {
+                            *base.add(0).cast::<i32>() = (e).take_handle() as i32;
+                        }
+                    }
+                    let ptr2 = ret_area.0.as_mut_ptr().cast::<u8>();
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(wasm_import_module = "durable:core/sql@2.7.0")]
+                    extern "C" {
+                        #[link_name = "explain"]
+                        fn wit_import(_: *mut u8, _: usize, _: *mut u8, _: usize, _: *mut u8);
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    fn wit_import(_: *mut u8, _: usize, _: *mut u8, _: usize, _: *mut u8) {
+                        unreachable!()
+                    }
+                    wit_import(ptr0.cast_mut(), len0, result1, len1, ptr2);
+                    let l3 = i32::from(*ptr2.add(0).cast::<u8>());
+                    if layout1.size() != 0 {
+                        _rt::alloc::dealloc(result1.cast(), layout1);
+                    }
+                    match l3 {
+                        0 => {
+                            let e = {
+                                let l4 = *ptr2.add(4).cast::<*mut u8>();
+                                let l5 = *ptr2.add(8).cast::<usize>();
+                                let len6 = l5;
+                                let bytes6 = _rt::Vec::from_raw_parts(l4.cast(), len6, len6);
+                                _rt::string_lift(bytes6)
+                            };
+                            Ok(e)
+                        }
+                        1 => {
+                            let e = {
+                                let l7 = i32::from(*ptr2.add(4).cast::<u8>());
+                                let v42 = match l7 {
+                                    0 => {
+                                        let e42 = {
+                                            let l8 = *ptr2.add(8).cast::<*mut u8>();
+                                            let l9 = *ptr2.add(12).cast::<usize>();
+                                            let len10 = l9;
+                                            let bytes10 = _rt::Vec::from_raw_parts(
+                                                l8.cast(),
+                                                len10,
+                                                len10,
+                                            );
+                                            let l11 = *ptr2.add(16).cast::<*mut u8>();
+                                            let l12 = *ptr2.add(20).cast::<usize>();
+                                            let len13 = l12;
+                                            let bytes13 = _rt::Vec::from_raw_parts(
+                                                l11.cast(),
+                                                len13,
+                                                len13,
+                                            );
+                                            ColumnDecodeError {
+                                                index: _rt::string_lift(bytes10),
+                                                source: _rt::string_lift(bytes13),
+                                            }
+                                        };
+                                        Error::ColumnDecode(e42)
+                                    }
+                                    1 => {
+                                        let e42 = {
+                                            let l14 = *ptr2.add(8).cast::<*mut u8>();
+                                            let l15 = *ptr2.add(12).cast::<usize>();
+                                            let len16 = l15;
+                                            let bytes16 = _rt::Vec::from_raw_parts(
+                                                l14.cast(),
+                                                len16,
+                                                len16,
+                                            );
+                                            _rt::string_lift(bytes16)
+                                        };
+                                        Error::TypeNotFound(e42)
+                                    }
+                                    2 => {
+                                        let e42 = {
+                                            let l17 = *ptr2.add(8).cast::<*mut u8>();
+                                            let l18 = *ptr2.add(12).cast::<usize>();
+                                            let len19 = l18;
+                                            let bytes19 = _rt::Vec::from_raw_parts(
+                                                l17.cast(),
+                                                len19,
+                                                len19,
+                                            );
+                                            _rt::string_lift(bytes19)
+                                        };
+                                        Error::Encode(e42)
+                                    }
+                                    3 => {
+                                        let e42 = {
+                                            let l20 = *ptr2.add(8).cast::<*mut u8>();
+                                            let l21 = *ptr2.add(12).cast::<usize>();
+                                            let len22 = l21;
+                                            let bytes22 = _rt::Vec::from_raw_parts(
+                                                l20.cast(),
+                                                len22,
+                                                len22,
+                                            );
+                                            _rt::string_lift(bytes22)
+                                        };
+                                        Error::Decode(e42)
+                                    }
+                                    4 => {
+                                        let e42 = {
+                                            let l23 = *ptr2.add(8).cast::<*mut u8>();
+                                            let l24 = *ptr2.add(12).cast::<usize>();
+                                            let len25 = l24;
+                                            let bytes25 = _rt::Vec::from_raw_parts(
+                                                l23.cast(),
+                                                len25,
+                                                len25,
+                                            );
+                                            let l26 = i32::from(*ptr2.add(16).cast::<u8>());
+                                            let l27 = i32::from(*ptr2.add(20).cast::<u8>());
+                                            let l31 = i32::from(*ptr2.add(32).cast::<u8>());
+                                            let l35 = i32::from(*ptr2.add(44).cast::<u8>());
+                                            DatabaseError {
+                                                message: _rt::string_lift(bytes25),
+                                                kind: DatabaseErrorKind::_lift(l26 as u8),
+                                                code: match l27 {
+                                                    0 => None,
+                                                    1 => {
+                                                        let e = {
+                                                            let l28 = *ptr2.add(24).cast::<*mut u8>();
+                                                            let l29 = *ptr2.add(28).cast::<usize>();
+                                                            let len30 = l29;
+                                                            let bytes30 = _rt::Vec::from_raw_parts(
+                                                                l28.cast(),
+                                                                len30,
+                                                                len30,
+                                                            );
+                                                            _rt::string_lift(bytes30)
+                                                        };
+                                                        Some(e)
+                                                    }
+                                                    _ => _rt::invalid_enum_discriminant(),
+                                                },
+                                                constraint: match l31 {
+                                                    0 => None,
+                                                    1 => {
+                                                        let e = {
+                                                            let l32 = *ptr2.add(36).cast::<*mut u8>();
+                                                            let l33 = *ptr2.add(40).cast::<usize>();
+                                                            let len34 = l33;
+                                                            let bytes34 = _rt::Vec::from_raw_parts(
+                                                                l32.cast(),
+                                                                len34,
+                                                                len34,
+                                                            );
+                                                            _rt::string_lift(bytes34)
+                                                        };
+                                                        Some(e)
+                                                    }
+                                                    _ => _rt::invalid_enum_discriminant(),
+                                                },
+                                                table: match l35 {
+                                                    0 => None,
+                                                    1 => {
+                                                        let e = {
+                                                            let l36 = *ptr2.add(48).cast::<*mut u8>();
+                                                            let l37 = *ptr2.add(52).cast::<usize>();
+                                                            let len38 = l37;
+                                                            let bytes38 = _rt::Vec::from_raw_parts(
+                                                                l36.cast(),
+                                                                len38,
+                                                                len38,
+                                                            );
+                                                            _rt::string_lift(bytes38)
+                                                        };
+                                                        Some(e)
+                                                    }
+                                                    _ => _rt::invalid_enum_discriminant(),
+                                                },
+                                            }
+                                        };
+                                        Error::Database(e42)
+                                    }
+                                    n => {
+                                        debug_assert_eq!(n, 5, "invalid enum discriminant");
+                                        let e42 = {
+                                            let l39 = *ptr2.add(8).cast::<*mut u8>();
+                                            let l40 = *ptr2.add(12).cast::<usize>();
+                                            let len41 = l40;
+                                            let bytes41 = _rt::Vec::from_raw_parts(
+                                                l39.cast(),
+                                                len41,
+                                                len41,
+                                            );
+                                            _rt::string_lift(bytes41)
+                                        };
+                                        Error::Other(e42)
+                                    }
+                                };
+                                v42
+                            };
+                            Err(e)
+                        }
+                        _ => _rt::invalid_enum_discriminant(),
+                    }
+                }
+            }
         }
     }
 }
@@ -4068,105 +6466,127 @@ mod _rt {
 #[cfg(target_arch = "wasm32")]
 #[link_section = "component-type:wit-bindgen:0.30.0:import-sql:encoded world"]
 #[doc(hidden)]
-pub static __WIT_BINDGEN_COMPONENT_TYPE: [u8; 4877] = *b"\
-\0asm\x0d\0\x01\0\0\x19\x16wit-component-encoding\x04\0\x07\x8c%\x01A\x02\x01A\x02\
-\x01B\xfc\x01\x04\0\x09type-info\x03\x01\x01r\x02\x07secondsx\x0csubsec-nanosy\x04\
-\0\x09timestamp\x03\0\x01\x01r\x03\x07secondsx\x0csubsec-nanosy\x06offsetz\x04\0\
-\x0btimestamptz\x03\0\x03\x01r\x02\x02hiw\x02low\x04\0\x04uuid\x03\0\x05\x01r\x02\
-\x04addry\x06prefix}\x04\0\x0cipv4-network\x03\0\x07\x01o\x02ww\x01r\x02\x04addr\
-\x09\x06prefix}\x04\0\x0cipv6-network\x03\0\x0a\x01q\x02\x02v4\x01\x08\0\x02v6\x01\
-\x0b\0\x04\0\x0aip-network\x03\0\x0c\x04\0\x05value\x03\x01\x01i\x0e\x01r\x02\x04\
-names\x05value\x0f\x04\0\x06column\x03\0\x10\x01p\x11\x01r\x01\x07columns\x12\x04\
-\0\x03row\x03\0\x13\x01q\x02\x05count\x01w\0\x03row\x01\x14\0\x04\0\x0cquery-res\
-ult\x03\0\x15\x01r\x02\x05limit}\x0apersistent\x7f\x04\0\x07options\x03\0\x17\x01\
-r\x02\x05indexs\x06sources\x04\0\x13column-decode-error\x03\0\x19\x01m\x05\x10un\
-ique-violation\x15foreign-key-violation\x12not-null-violation\x0fcheck-violation\
-\x05other\x04\0\x13database-error-kind\x03\0\x1b\x01ks\x01r\x05\x07messages\x04k\
-ind\x1c\x04code\x1d\x0aconstraint\x1d\x05table\x1d\x04\0\x0edatabase-error\x03\0\
-\x1e\x01q\x06\x0dcolumn-decode\x01\x1a\0\x0etype-not-found\x01s\0\x06encode\x01s\
-\0\x06decode\x01s\0\x08database\x01\x1f\0\x05other\x01s\0\x04\0\x05error\x03\0\x20\
-\x01h\0\x01@\x01\x04self\"\0s\x04\0\x16[method]type-info.name\x01#\x01@\x02\x04s\
-elf\"\x05other\"\0\x7f\x04\0\x1c[method]type-info.compatible\x01$\x04\0\x17[meth\
-od]type-info.equal\x01$\x01i\0\x01@\x01\x04self\"\0%\x04\0\x17[method]type-info.\
-clone\x01&\x01j\x01s\x01s\x01@\x01\x04self\"\0'\x04\0\x1b[method]type-info.seria\
-lize\x01(\x01j\x01%\x01s\x01@\x01\x04jsons\0)\x04\0\x1d[static]type-info.deseria\
-lize\x01*\x01@\x01\x04names\0)\x04\0\x1b[static]type-info.with-name\x01+\x01@\0\0\
-%\x04\0\x19[static]type-info.boolean\x01,\x04\0\x18[static]type-info.float4\x01,\
-\x04\0\x18[static]type-info.float8\x01,\x04\0\x16[static]type-info.int1\x01,\x04\
-\0\x16[static]type-info.int2\x01,\x04\0\x16[static]type-info.int4\x01,\x04\0\x16\
-[static]type-info.int8\x01,\x04\0\x16[static]type-info.text\x01,\x04\0\x17[stati\
-c]type-info.bytea\x01,\x04\0\x1d[static]type-info.timestamptz\x01,\x04\0\x1b[sta\
-tic]type-info.timestamp\x01,\x04\0\x16[static]type-info.uuid\x01,\x04\0\x17[stat\
-ic]type-info.jsonb\x01,\x04\0\x16[static]type-info.inet\x01,\x04\0\x1f[static]ty\
-pe-info.boolean-array\x01,\x04\0\x1e[static]type-info.float4-array\x01,\x04\0\x1e\
-[static]type-info.float8-array\x01,\x04\0\x1c[static]type-info.int1-array\x01,\x04\
-\0\x1c[static]type-info.int2-array\x01,\x04\0\x1c[static]type-info.int4-array\x01\
-,\x04\0\x1c[static]type-info.int8-array\x01,\x04\0\x1c[static]type-info.text-arr\
-ay\x01,\x04\0\x1d[static]type-info.bytea-array\x01,\x04\0#[static]type-info.time\
-stamptz-array\x01,\x04\0![static]type-info.timestamp-array\x01,\x04\0\x1c[static\
-]type-info.uuid-array\x01,\x04\0\x1d[static]type-info.jsonb-array\x01,\x04\0\x1c\
-[static]type-info.inet-array\x01,\x01h\x0e\x01@\x01\x04self-\0\x7f\x04\0\x15[met\
-hod]value.is-null\x01.\x01@\x01\x04self-\0%\x04\0\x17[method]value.type-info\x01\
-/\x01@\x01\x04self-\0\x0f\x04\0\x13[method]value.clone\x010\x01@\x01\x04self-\0'\
-\x04\0\x17[method]value.serialize\x011\x01j\x01\x0f\x01s\x01@\x01\x04jsons\02\x04\
-\0\x19[static]value.deserialize\x013\x01k\x7f\x01@\x01\x04self-\04\x04\0\x18[met\
-hod]value.as-boolean\x015\x01kv\x01@\x01\x04self-\06\x04\0\x17[method]value.as-f\
-loat4\x017\x01ku\x01@\x01\x04self-\08\x04\0\x17[method]value.as-float8\x019\x01k\
-~\x01@\x01\x04self-\0:\x04\0\x15[method]value.as-int1\x01;\x01k|\x01@\x01\x04sel\
-f-\0<\x04\0\x15[method]value.as-int2\x01=\x01kz\x01@\x01\x04self-\0>\x04\0\x15[m\
-ethod]value.as-int4\x01?\x01kx\x01@\x01\x04self-\0\xc0\0\x04\0\x15[method]value.\
-as-int8\x01A\x01@\x01\x04self-\0\x1d\x04\0\x15[method]value.as-text\x01B\x01p}\x01\
-k\xc3\0\x01@\x01\x04self-\0\xc4\0\x04\0\x16[method]value.as-bytea\x01E\x01k\x04\x01\
-@\x01\x04self-\0\xc6\0\x04\0\x1c[method]value.as-timestamptz\x01G\x01k\x02\x01@\x01\
-\x04self-\0\xc8\0\x04\0\x1a[method]value.as-timestamp\x01I\x01k\x06\x01@\x01\x04\
-self-\0\xca\0\x04\0\x15[method]value.as-uuid\x01K\x04\0\x15[method]value.as-json\
-\x01B\x01k\x0d\x01@\x01\x04self-\0\xcc\0\x04\0\x15[method]value.as-inet\x01M\x01\
-p\x7f\x01k\xce\0\x01@\x01\x04self-\0\xcf\0\x04\0\x1e[method]value.as-boolean-arr\
-ay\x01P\x01pv\x01k\xd1\0\x01@\x01\x04self-\0\xd2\0\x04\0\x1d[method]value.as-flo\
-at4-array\x01S\x01pu\x01k\xd4\0\x01@\x01\x04self-\0\xd5\0\x04\0\x1d[method]value\
-.as-float8-array\x01V\x01p~\x01k\xd7\0\x01@\x01\x04self-\0\xd8\0\x04\0\x1b[metho\
-d]value.as-int1-array\x01Y\x01p|\x01k\xda\0\x01@\x01\x04self-\0\xdb\0\x04\0\x1b[\
-method]value.as-int2-array\x01\\\x01pz\x01k\xdd\0\x01@\x01\x04self-\0\xde\0\x04\0\
-\x1b[method]value.as-int4-array\x01_\x01px\x01k\xe0\0\x01@\x01\x04self-\0\xe1\0\x04\
-\0\x1b[method]value.as-int8-array\x01b\x01ps\x01k\xe3\0\x01@\x01\x04self-\0\xe4\0\
-\x04\0\x1b[method]value.as-text-array\x01e\x01p\xc3\0\x01k\xe6\0\x01@\x01\x04sel\
-f-\0\xe7\0\x04\0\x1c[method]value.as-bytea-array\x01h\x01p\x04\x01k\xe9\0\x01@\x01\
-\x04self-\0\xea\0\x04\0\"[method]value.as-timestamptz-array\x01k\x01p\x02\x01k\xec\
-\0\x01@\x01\x04self-\0\xed\0\x04\0\x20[method]value.as-timestamp-array\x01n\x01p\
-\x06\x01k\xef\0\x01@\x01\x04self-\0\xf0\0\x04\0\x1b[method]value.as-uuid-array\x01\
-q\x04\0\x1b[method]value.as-json-array\x01e\x01p\x0d\x01k\xf2\0\x01@\x01\x04self\
--\0\xf3\0\x04\0\x1b[method]value.as-inet-array\x01t\x01@\x01\x06tyinfo%\0\x0f\x04\
-\0\x12[static]value.null\x01u\x01@\x01\x05value\x7f\0\x0f\x04\0\x15[static]value\
-.boolean\x01v\x01@\x01\x05valuev\0\x0f\x04\0\x14[static]value.float4\x01w\x01@\x01\
-\x05valueu\0\x0f\x04\0\x14[static]value.float8\x01x\x01@\x01\x05value~\0\x0f\x04\
-\0\x12[static]value.int1\x01y\x01@\x01\x05value|\0\x0f\x04\0\x12[static]value.in\
-t2\x01z\x01@\x01\x05valuez\0\x0f\x04\0\x12[static]value.int4\x01{\x01@\x01\x05va\
-luex\0\x0f\x04\0\x12[static]value.int8\x01|\x01@\x01\x05values\0\x0f\x04\0\x12[s\
-tatic]value.text\x01}\x01@\x01\x05value\xc3\0\0\x0f\x04\0\x13[static]value.bytea\
-\x01~\x01@\x01\x05value\x04\0\x0f\x04\0\x19[static]value.timestamptz\x01\x7f\x01\
-@\x01\x05value\x02\0\x0f\x04\0\x17[static]value.timestamp\x01\x80\x01\x01@\x01\x05\
-value\x06\0\x0f\x04\0\x12[static]value.uuid\x01\x81\x01\x04\0\x13[static]value.j\
-sonb\x01}\x01@\x01\x05value\x0d\02\x04\0\x12[static]value.inet\x01\x82\x01\x01@\x02\
-\x05values\x06tyinfo\"\0\x0f\x04\0\x18[static]value.enum-value\x01\x83\x01\x01@\x01\
-\x05value\xce\0\0\x0f\x04\0\x1b[static]value.boolean-array\x01\x84\x01\x01@\x01\x05\
-value\xd1\0\0\x0f\x04\0\x1a[static]value.float4-array\x01\x85\x01\x01@\x01\x05va\
-lue\xd4\0\0\x0f\x04\0\x1a[static]value.float8-array\x01\x86\x01\x01@\x01\x05valu\
-e\xd7\0\0\x0f\x04\0\x18[static]value.int1-array\x01\x87\x01\x01@\x01\x05value\xda\
-\0\0\x0f\x04\0\x18[static]value.int2-array\x01\x88\x01\x01@\x01\x05value\xdd\0\0\
-\x0f\x04\0\x18[static]value.int4-array\x01\x89\x01\x01@\x01\x05value\xe0\0\0\x0f\
-\x04\0\x18[static]value.int8-array\x01\x8a\x01\x01@\x01\x05value\xe3\0\0\x0f\x04\
-\0\x18[static]value.text-array\x01\x8b\x01\x01@\x01\x05value\xe6\0\0\x0f\x04\0\x19\
-[static]value.bytea-array\x01\x8c\x01\x01@\x01\x05value\xe9\0\0\x0f\x04\0\x1f[st\
-atic]value.timestamptz-array\x01\x8d\x01\x01@\x01\x05value\xec\0\0\x0f\x04\0\x1d\
-[static]value.timestamp-array\x01\x8e\x01\x01@\x01\x05value\xef\0\0\x0f\x04\0\x18\
-[static]value.uuid-array\x01\x8f\x01\x04\0\x19[static]value.jsonb-array\x01\x8b\x01\
-\x01@\x01\x05value\xf2\0\02\x04\0\x18[static]value.inet-array\x01\x90\x01\x01@\x02\
-\x05value\xe3\0\x06tyinfo\"\0\x0f\x04\0\x18[static]value.enum-array\x01\x91\x01\x01\
-p\x0f\x01@\x03\x03sqls\x06params\x92\x01\x07options\x18\x01\0\x04\0\x05query\x01\
-\x93\x01\x01j\x01\x16\x01!\x01k\x94\x01\x01@\0\0\x95\x01\x04\0\x05fetch\x01\x96\x01\
-\x03\x01\x16durable:core/sql@2.5.0\x05\0\x04\x01\x1ddurable:core/import-sql@2.5.\
-0\x04\0\x0b\x10\x01\0\x0aimport-sql\x03\0\0\0G\x09producers\x01\x0cprocessed-by\x02\
-\x0dwit-component\x070.215.0\x10wit-bindgen-rust\x060.30.0";
+pub static __WIT_BINDGEN_COMPONENT_TYPE: [u8; 5710] = *b"\
+\0asm\x0d\0\x01\0\0\x19\x16wit-component-encoding\x04\0\x07\xcd+\x01A\x02\x01A\
+\x02\x01B\xb0\x02\x04\0\x09type-info\x03\x01\x01r\x02\x07secondsx\x0csubsec-nano\
+sy\x04\0\x09timestamp\x03\0\x01\x01r\x03\x07secondsx\x0csubsec-nanosy\x06offsetz\
+\x04\0\x0btimestamptz\x03\0\x03\x01r\x02\x02hiw\x02low\x04\0\x04uuid\x03\0\x05\
+\x01r\x02\x04addry\x06prefix}\x04\0\x0cipv4-network\x03\0\x07\x01o\x02ww\x01r\
+\x02\x04addr\x09\x06prefix}\x04\0\x0cipv6-network\x03\0\x0a\x01q\x02\x02v4\x01\
+\x08\0\x02v6\x01\x0b\0\x04\0\x0aip-network\x03\0\x0c\x04\0\x05value\x03\x01\x01i\
+\x0e\x01r\x02\x04names\x05value\x0f\x04\0\x06column\x03\0\x10\x01p\x11\x01r\x01\
+\x07columns\x12\x04\0\x03row\x03\0\x13\x01q\x02\x05count\x01w\0\x03row\x01\x14\0\
+\x04\0\x0cquery-result\x03\0\x15\x01r\x02\x05limit}\x0apersistent\x7f\x04\0\x07o\
+ptions\x03\0\x17\x01r\x02\x05indexs\x06sources\x04\0\x13column-decode-error\x03\
+\0\x19\x01m\x05\x10unique-violation\x15foreign-key-violation\x12not-null-violati\
+on\x0fcheck-violation\x05other\x04\0\x13database-error-kind\x03\0\x1b\x01ks\x01r\
+\x05\x07messages\x04kind\x1c\x04code\x1d\x0aconstraint\x1d\x05table\x1d\x04\0\
+\x0edatabase-error\x03\0\x1e\x01q\x06\x0dcolumn-decode\x01\x1a\0\x0etype-not-fou\
+nd\x01s\0\x06encode\x01s\0\x06decode\x01s\0\x08database\x01\x1f\0\x05other\x01s\
+\0\x04\0\x05error\x03\0 \x01i\0\x01k\x7f\x01r\x03\x04names\x09type-info\"\x08nul\
+lable#\x04\0\x12column-description\x03\0$\x01p\"\x01p%\x01r\x02\x0aparameters&\
+\x07columns'\x04\0\x15statement-description\x03\0(\x01r\x02\x07channels\x07paylo\
+ads\x04\0\x0fpg-notification\x03\0*\x01r\x03\x05limit}\x0apersistent\x7f\x0dcoll\
+ect-stats\x7f\x04\0\x08options2\x03\0,\x01kw\x01r\x03\x08durationw\x0eexecution-\
+time.\x0crows-scanned.\x04\0\x0bquery-stats\x03\0/\x01p\x0f\x01r\x02\x03sqls\x06\
+params1\x04\0\x0fbatch-statement\x03\02\x01h\0\x01@\x01\x04self4\0s\x04\0\x16[me\
+thod]type-info.name\x015\x01@\x02\x04self4\x05other4\0\x7f\x04\0\x1c[method]type\
+-info.compatible\x016\x04\0\x17[method]type-info.equal\x016\x01@\x01\x04self4\0\
+\"\x04\0\x17[method]type-info.clone\x017\x01j\x01s\x01s\x01@\x01\x04self4\08\x04\
+\0\x1b[method]type-info.serialize\x019\x01j\x01\"\x01s\x01@\x01\x04jsons\0:\x04\
+\0\x1d[static]type-info.deserialize\x01;\x01@\x01\x04names\0:\x04\0\x1b[static]t\
+ype-info.with-name\x01<\x01@\0\0\"\x04\0\x19[static]type-info.boolean\x01=\x04\0\
+\x18[static]type-info.float4\x01=\x04\0\x18[static]type-info.float8\x01=\x04\0\
+\x16[static]type-info.int1\x01=\x04\0\x16[static]type-info.int2\x01=\x04\0\x16[s\
+tatic]type-info.int4\x01=\x04\0\x16[static]type-info.int8\x01=\x04\0\x16[static]\
+type-info.text\x01=\x04\0\x17[static]type-info.bytea\x01=\x04\0\x1d[static]type-\
+info.timestamptz\x01=\x04\0\x1b[static]type-info.timestamp\x01=\x04\0\x16[static\
+]type-info.uuid\x01=\x04\0\x17[static]type-info.jsonb\x01=\x04\0\x16[static]type\
+-info.inet\x01=\x04\0\x1f[static]type-info.boolean-array\x01=\x04\0\x1e[static]t\
+ype-info.float4-array\x01=\x04\0\x1e[static]type-info.float8-array\x01=\x04\0\
+\x1c[static]type-info.int1-array\x01=\x04\0\x1c[static]type-info.int2-array\x01=\
+\x04\0\x1c[static]type-info.int4-array\x01=\x04\0\x1c[static]type-info.int8-arra\
+y\x01=\x04\0\x1c[static]type-info.text-array\x01=\x04\0\x1d[static]type-info.byt\
+ea-array\x01=\x04\0#[static]type-info.timestamptz-array\x01=\x04\0![static]type-\
+info.timestamp-array\x01=\x04\0\x1c[static]type-info.uuid-array\x01=\x04\0\x1d[s\
+tatic]type-info.jsonb-array\x01=\x04\0\x1c[static]type-info.inet-array\x01=\x01h\
+\x0e\x01@\x01\x04self>\0\x7f\x04\0\x15[method]value.is-null\x01?\x01@\x01\x04sel\
+f>\0\"\x04\0\x17[method]value.type-info\x01@\x01@\x01\x04self>\0\x0f\x04\0\x13[m\
+ethod]value.clone\x01A\x01@\x01\x04self>\08\x04\0\x17[method]value.serialize\x01\
+B\x01j\x01\x0f\x01s\x01@\x01\x04jsons\0\xc3\0\x04\0\x19[static]value.deserialize\
+\x01D\x01@\x01\x04self>\0#\x04\0\x18[method]value.as-boolean\x01E\x01kv\x01@\x01\
+\x04self>\0\xc6\0\x04\0\x17[method]value.as-float4\x01G\x01ku\x01@\x01\x04self>\
+\0\xc8\0\x04\0\x17[method]value.as-float8\x01I\x01k~\x01@\x01\x04self>\0\xca\0\
+\x04\0\x15[method]value.as-int1\x01K\x01k|\x01@\x01\x04self>\0\xcc\0\x04\0\x15[m\
+ethod]value.as-int2\x01M\x01kz\x01@\x01\x04self>\0\xce\0\x04\0\x15[method]value.\
+as-int4\x01O\x01kx\x01@\x01\x04self>\0\xd0\0\x04\0\x15[method]value.as-int8\x01Q\
+\x01@\x01\x04self>\0\x1d\x04\0\x15[method]value.as-text\x01R\x01p}\x01k\xd3\0\
+\x01@\x01\x04self>\0\xd4\0\x04\0\x16[method]value.as-bytea\x01U\x01k\x04\x01@\
+\x01\x04self>\0\xd6\0\x04\0\x1c[method]value.as-timestamptz\x01W\x01k\x02\x01@\
+\x01\x04self>\0\xd8\0\x04\0\x1a[method]value.as-timestamp\x01Y\x01k\x06\x01@\x01\
+\x04self>\0\xda\0\x04\0\x15[method]value.as-uuid\x01[\x04\0\x15[method]value.as-\
+json\x01R\x01k\x0d\x01@\x01\x04self>\0\xdc\0\x04\0\x15[method]value.as-inet\x01]\
+\x01p\x7f\x01k\xde\0\x01@\x01\x04self>\0\xdf\0\x04\0\x1e[method]value.as-boolean\
+-array\x01`\x01pv\x01k\xe1\0\x01@\x01\x04self>\0\xe2\0\x04\0\x1d[method]value.as\
+-float4-array\x01c\x01pu\x01k\xe4\0\x01@\x01\x04self>\0\xe5\0\x04\0\x1d[method]v\
+alue.as-float8-array\x01f\x01p~\x01k\xe7\0\x01@\x01\x04self>\0\xe8\0\x04\0\x1b[m\
+ethod]value.as-int1-array\x01i\x01p|\x01k\xea\0\x01@\x01\x04self>\0\xeb\0\x04\0\
+\x1b[method]value.as-int2-array\x01l\x01pz\x01k\xed\0\x01@\x01\x04self>\0\xee\0\
+\x04\0\x1b[method]value.as-int4-array\x01o\x01px\x01k\xf0\0\x01@\x01\x04self>\0\
+\xf1\0\x04\0\x1b[method]value.as-int8-array\x01r\x01ps\x01k\xf3\0\x01@\x01\x04se\
+lf>\0\xf4\0\x04\0\x1b[method]value.as-text-array\x01u\x01p\xd3\0\x01k\xf6\0\x01@\
+\x01\x04self>\0\xf7\0\x04\0\x1c[method]value.as-bytea-array\x01x\x01p\x04\x01k\
+\xf9\0\x01@\x01\x04self>\0\xfa\0\x04\0\"[method]value.as-timestamptz-array\x01{\
+\x01p\x02\x01k\xfc\0\x01@\x01\x04self>\0\xfd\0\x04\0 [method]value.as-timestamp-\
+array\x01~\x01p\x06\x01k\xff\0\x01@\x01\x04self>\0\x80\x01\x04\0\x1b[method]valu\
+e.as-uuid-array\x01\x81\x01\x04\0\x1b[method]value.as-json-array\x01u\x01p\x0d\
+\x01k\x82\x01\x01@\x01\x04self>\0\x83\x01\x04\0\x1b[method]value.as-inet-array\
+\x01\x84\x01\x01@\x01\x06tyinfo\"\0\x0f\x04\0\x12[static]value.null\x01\x85\x01\
+\x01@\x01\x05value\x7f\0\x0f\x04\0\x15[static]value.boolean\x01\x86\x01\x01@\x01\
+\x05valuev\0\x0f\x04\0\x14[static]value.float4\x01\x87\x01\x01@\x01\x05valueu\0\
+\x0f\x04\0\x14[static]value.float8\x01\x88\x01\x01@\x01\x05value~\0\x0f\x04\0\
+\x12[static]value.int1\x01\x89\x01\x01@\x01\x05value|\0\x0f\x04\0\x12[static]val\
+ue.int2\x01\x8a\x01\x01@\x01\x05valuez\0\x0f\x04\0\x12[static]value.int4\x01\x8b\
+\x01\x01@\x01\x05valuex\0\x0f\x04\0\x12[static]value.int8\x01\x8c\x01\x01@\x01\
+\x05values\0\x0f\x04\0\x12[static]value.text\x01\x8d\x01\x01@\x01\x05value\xd3\0\
+\0\x0f\x04\0\x13[static]value.bytea\x01\x8e\x01\x01@\x01\x05value\x04\0\x0f\x04\
+\0\x19[static]value.timestamptz\x01\x8f\x01\x01@\x01\x05value\x02\0\x0f\x04\0\
+\x17[static]value.timestamp\x01\x90\x01\x01@\x01\x05value\x06\0\x0f\x04\0\x12[st\
+atic]value.uuid\x01\x91\x01\x04\0\x13[static]value.jsonb\x01\x8d\x01\x01@\x01\
+\x05value\x0d\0\xc3\0\x04\0\x12[static]value.inet\x01\x92\x01\x01@\x02\x05values\
+\x06tyinfo4\0\x0f\x04\0\x18[static]value.enum-value\x01\x93\x01\x01@\x01\x05valu\
+e\xde\0\0\x0f\x04\0\x1b[static]value.boolean-array\x01\x94\x01\x01@\x01\x05value\
+\xe1\0\0\x0f\x04\0\x1a[static]value.float4-array\x01\x95\x01\x01@\x01\x05value\
+\xe4\0\0\x0f\x04\0\x1a[static]value.float8-array\x01\x96\x01\x01@\x01\x05value\
+\xe7\0\0\x0f\x04\0\x18[static]value.int1-array\x01\x97\x01\x01@\x01\x05value\xea\
+\0\0\x0f\x04\0\x18[static]value.int2-array\x01\x98\x01\x01@\x01\x05value\xed\0\0\
+\x0f\x04\0\x18[static]value.int4-array\x01\x99\x01\x01@\x01\x05value\xf0\0\0\x0f\
+\x04\0\x18[static]value.int8-array\x01\x9a\x01\x01@\x01\x05value\xf3\0\0\x0f\x04\
+\0\x18[static]value.text-array\x01\x9b\x01\x01@\x01\x05value\xf6\0\0\x0f\x04\0\
+\x19[static]value.bytea-array\x01\x9c\x01\x01@\x01\x05value\xf9\0\0\x0f\x04\0\
+\x1f[static]value.timestamptz-array\x01\x9d\x01\x01@\x01\x05value\xfc\0\0\x0f\
+\x04\0\x1d[static]value.timestamp-array\x01\x9e\x01\x01@\x01\x05value\xff\0\0\
+\x0f\x04\0\x18[static]value.uuid-array\x01\x9f\x01\x04\0\x19[static]value.jsonb-\
+array\x01\x9b\x01\x01@\x01\x05value\x82\x01\0\xc3\0\x04\0\x18[static]value.inet-\
+array\x01\xa0\x01\x01@\x02\x05value\xf3\0\x06tyinfo4\0\x0f\x04\0\x18[static]valu\
+e.enum-array\x01\xa1\x01\x01@\x03\x03sqls\x06params1\x07options\x18\x01\0\x04\0\
+\x05query\x01\xa2\x01\x01j\x01\x16\x01!\x01k\xa3\x01\x01@\0\0\xa4\x01\x04\0\x05f\
+etch\x01\xa5\x01\x01j\0\x01!\x01@\0\0\xa6\x01\x04\0\x09savepoint\x01\xa7\x01\x04\
+\0\x11release-savepoint\x01\xa7\x01\x04\0\x12rollback-savepoint\x01\xa7\x01\x01@\
+\x01\x09statements\0\xa6\x01\x04\0\x0dcopy-in-start\x01\xa8\x01\x01@\x01\x04data\
+\xd3\0\0\xa6\x01\x04\0\x0ccopy-in-send\x01\xa9\x01\x01j\x01w\x01!\x01@\0\0\xaa\
+\x01\x04\0\x0ecopy-in-finish\x01\xab\x01\x01@\x01\x07messages\0\xa6\x01\x04\0\
+\x0dcopy-in-abort\x01\xac\x01\x01j\x01)\x01!\x01@\x01\x03sqls\0\xad\x01\x04\0\
+\x08describe\x01\xae\x01\x01@\x01\x07channels\0+\x04\0\x06listen\x01\xaf\x01\x01\
+@\x03\x03sqls\x06params1\x07options-\x01\0\x04\0\x06query2\x01\xb0\x01\x01k0\x01\
+@\0\0\xb1\x01\x04\0\x10last-query-stats\x01\xb2\x01\x01p3\x01@\x01\x0astatements\
+\xb3\x01\x01\0\x04\0\x05batch\x01\xb4\x01\x01@\0\x01\0\x04\0\x15clear-statement-\
+cache\x01\xb5\x01\x01r\x02\x03oidy\x04data\xd3\0\x04\0\x09raw-value\x03\0\xb6\
+\x01\x01k\xb7\x01\x01@\x01\x04self>\0\xb8\x01\x04\0\x14[method]value.as-raw\x01\
+\xb9\x01\x01j\x01s\x01!\x01@\x02\x03sqls\x06params1\0\xba\x01\x04\0\x07explain\
+\x01\xbb\x01\x03\x01\x16durable:core/sql@2.7.0\x05\0\x04\x01\x1ddurable:core/imp\
+ort-sql@2.7.0\x04\0\x0b\x10\x01\0\x0aimport-sql\x03\0\0\0G\x09producers\x01\x0cp\
+rocessed-by\x02\x0dwit-component\x070.215.0\x10wit-bindgen-rust\x060.30.0";
 #[inline(never)]
 #[doc(hidden)]
 pub fn __link_custom_section_describing_imports() {
@@ -1,7 +1,7 @@
 use crate::bindings as sql;
 use crate::driver::{Durable, Value};
 
-#[derive(Default)]
+#[derive(Clone, Default)]
 pub struct Arguments(Vec<Value>);
 
 impl Arguments {
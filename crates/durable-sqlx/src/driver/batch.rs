@@ -0,0 +1,103 @@
+use crate::bindings as sql;
+use crate::driver::error::convert_query_error;
+use crate::driver::{Connection, Durable, QueryResult};
+
+/// A batch of statements to be run by [`Connection::batch`].
+#[derive(Debug, Default)]
+pub struct Batch {
+    statements: Vec<sql::BatchStatement>,
+    error: Option<sqlx::Error>,
+}
+
+impl Batch {
+    /// Add a statement to the batch.
+    ///
+    /// Statements are run in the order they were added. Any rows returned by
+    /// the statement are discarded.
+    pub fn push<'q, E>(&mut self, mut query: E) -> &mut Self
+    where
+        E: sqlx::Execute<'q, Durable>,
+    {
+        let sql = query.sql().to_owned();
+
+        match query.take_arguments() {
+            Ok(params) => self.statements.push(sql::BatchStatement {
+                sql,
+                params: params.unwrap_or_default().into_raw_args(),
+            }),
+            // Report the first encoding error once the batch is run.
+            Err(e) => {
+                self.error.get_or_insert(sqlx::Error::Encode(e));
+            }
+        }
+
+        self
+    }
+
+    /// The number of statements in the batch.
+    pub fn len(&self) -> usize {
+        self.statements.len()
+    }
+
+    /// Whether the batch has no statements.
+    pub fn is_empty(&self) -> bool {
+        self.statements.is_empty()
+    }
+}
+
+impl Connection {
+    /// Run a batch of statements using a single call to the runtime.
+    ///
+    /// Each statement made through the connection normally requires its own
+    /// round-trip between the workflow and the runtime. When running many
+    /// small statements (e.g. a large number of `INSERT`s) this overhead can
+    /// dominate. Statements added to a batch are instead all sent to the
+    /// runtime at once and are then run back-to-back on the database
+    /// connection.
+    ///
+    /// Returns the result of each statement, in the order they were added to
+    /// the batch. If any statement fails then the remaining statements are
+    /// not run and its error is returned.
+    ///
+    /// ```no_run
+    /// # fn example(mut conn: durable::sqlx::Connection) -> durable::sqlx::Result<()> {
+    /// use durable::sqlx;
+    ///
+    /// let results = conn.batch(|batch| {
+    ///     for name in ["alice", "bob", "carol"] {
+    ///         batch.push(sqlx::query("INSERT INTO users(name) VALUES ($1)").bind(name));
+    ///     }
+    /// })?;
+    ///
+    /// assert_eq!(results.len(), 3);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn batch<F>(&mut self, func: F) -> crate::Result<Vec<QueryResult>>
+    where
+        F: FnOnce(&mut Batch),
+    {
+        let mut batch = Batch::default();
+        func(&mut batch);
+
+        if let Some(error) = batch.error {
+            return Err(error.into());
+        }
+
+        if batch.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        sql::batch(&batch.statements);
+
+        let mut results = Vec::with_capacity(batch.len());
+        while let Some(result) = sql::fetch() {
+            match result.map_err(convert_query_error)? {
+                sql::QueryResult::Count(count) => results.push(QueryResult::new(count)),
+                sql::QueryResult::Row(_) => (),
+            }
+        }
+
+        Ok(results)
+    }
+}
@@ -8,7 +8,10 @@ use url::Url;
 
 use crate::bindings as sql;
 use crate::driver::error::{convert_query_error, UnsupportedError};
-use crate::driver::{Arguments, Durable, QueryResult, Row, Statement, TypeInfo};
+use crate::driver::{
+    Arguments, Column, Durable, QueryResult, QueryStats, Row, Statement, TransactionManager,
+    TypeInfo,
+};
 
 #[derive(Debug, Clone)]
 pub enum ConnectOptions {}
@@ -63,8 +66,7 @@ impl FromStr for ConnectOptions {
 /// - The same applies for `ping`ing the connection.
 #[derive(Debug)]
 pub struct Connection {
-    /// The nested savepoint depth that we are at.
-    pub(crate) txn_depth: u32,
+    collect_stats: bool,
 }
 
 impl Connection {
@@ -74,12 +76,174 @@ impl Connection {
     /// can only be one so care must be taken to ensure that there is only ever
     /// one live `Connection` object.
     pub(crate) fn new() -> Self {
-        Self { txn_depth: 0 }
+        Self {
+            collect_stats: false,
+        }
     }
 
-    fn run(&mut self, sql: &str, arguments: Arguments, options: sql::Options) -> QueryIterator {
+    /// Enable or disable collecting execution statistics for queries made
+    /// through this connection.
+    ///
+    /// When enabled, the statistics for each query are available via
+    /// [`QueryResult::stats`] or [`last_query_stats`](Self::last_query_stats).
+    ///
+    /// Note that the runtime collects statistics by first running each query
+    /// under `EXPLAIN ANALYZE` within a savepoint that is then rolled back.
+    /// This means that every query is executed twice, so this should only be
+    /// enabled while investigating slow queries.
+    ///
+    /// ```no_run
+    /// # fn example(mut conn: durable::sqlx::Connection) -> durable::sqlx::Result<()> {
+    /// use std::time::Duration;
+    ///
+    /// conn.set_collect_stats(true);
+    ///
+    /// let result = durable::sqlx::query("UPDATE users SET visits = visits + 1").execute(&mut conn)?;
+    ///
+    /// if let Some(stats) = result.stats() {
+    ///     if stats.duration() > Duration::from_secs(1) {
+    ///         println!("slow query: {stats:?}");
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn set_collect_stats(&mut self, enabled: bool) {
+        self.collect_stats = enabled;
+    }
+
+    /// Get the execution statistics for the last query made within the
+    /// current transaction.
+    ///
+    /// This returns `None` if statistics collection was not enabled for the
+    /// last query or if not all of its results have been fetched.
+    pub fn last_query_stats(&self) -> Option<QueryStats> {
+        sql::last_query_stats().map(QueryStats::from_raw)
+    }
+
+    /// Run `func` within a savepoint in the current database transaction.
+    ///
+    /// If `func` returns an error then all changes it made to the database
+    /// are rolled back, while leaving the rest of the transaction intact.
+    /// Otherwise, the savepoint is released and its changes become part of
+    /// the enclosing transaction.
+    ///
+    /// Savepoints can be nested by calling `savepoint` again on the
+    /// connection passed to `func`.
+    ///
+    /// ```no_run
+    /// # fn example(mut conn: durable::sqlx::Connection) -> durable::sqlx::Result<()> {
+    /// use durable::sqlx;
+    ///
+    /// let inserted = conn.savepoint(|conn| {
+    ///     sqlx::query("INSERT INTO users(name) VALUES ($1)")
+    ///         .bind("alice")
+    ///         .execute(conn)
+    /// });
+    ///
+    /// // A unique constraint violation only undoes the insert above.
+    /// if inserted.is_err() {
+    ///     sqlx::query("UPDATE users SET visits = visits + 1 WHERE name = $1")
+    ///         .bind("alice")
+    ///         .execute(&mut conn)?;
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn savepoint<F, T, E>(&mut self, func: F) -> Result<T, E>
+    where
+        F: FnOnce(&mut Self) -> Result<T, E>,
+        E: From<crate::Error>,
+    {
+        TransactionManager::begin(self).map_err(crate::Error::from)?;
+
+        match func(self) {
+            Ok(value) => {
+                TransactionManager::commit(self).map_err(crate::Error::from)?;
+                Ok(value)
+            }
+            Err(e) => {
+                TransactionManager::rollback(self).map_err(crate::Error::from)?;
+                Err(e)
+            }
+        }
+    }
+
+    /// Describe the parameters and output columns of a statement without
+    /// executing it.
+    ///
+    /// This is the blocking equivalent of [`sqlx::Executor::describe`].
+    ///
+    /// ```no_run
+    /// # fn example(mut conn: durable::sqlx::Connection) -> durable::sqlx::Result<()> {
+    /// let describe = conn.describe("SELECT name FROM users WHERE id = $1")?;
+    ///
+    /// println!("parameters: {:?}", describe.parameters());
+    /// println!("columns: {:?}", describe.columns());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn describe(&mut self, sql: &str) -> crate::Result<sqlx::Describe<Durable>> {
+        Ok(self.describe_raw(sql)?)
+    }
+
+    fn describe_raw(&mut self, sql: &str) -> Result<sqlx::Describe<Durable>, sqlx::Error> {
+        let description = sql::describe(sql).map_err(convert_query_error)?;
+
+        let parameters = description
+            .parameters
+            .into_iter()
+            .map(TypeInfo::new)
+            .collect();
+        let (columns, nullable) = description
+            .columns
+            .into_iter()
+            .enumerate()
+            .map(|(idx, column)| {
+                let type_info = TypeInfo::new(column.type_info);
+
+                (Column::new(idx, column.name, type_info), column.nullable)
+            })
+            .unzip();
+
+        Ok(sqlx::Describe {
+            columns,
+            parameters: Some(sqlx::Either::Left(parameters)),
+            nullable,
+        })
+    }
+
+    /// Run `EXPLAIN (FORMAT JSON)` for `sql` and return the resulting plan.
+    ///
+    /// The host wraps the statement itself so that any SQL policy is checked
+    /// against `sql` and not against the `EXPLAIN` statement.
+    pub(crate) fn explain_raw(
+        &mut self,
+        sql: &str,
+        arguments: Arguments,
+    ) -> Result<String, sqlx::Error> {
+        sql::explain(sql, arguments.into_raw_args()).map_err(convert_query_error)
+    }
+
+    fn run(&mut self, sql: &str, arguments: Arguments, limit: u8) -> QueryIterator {
         let params = arguments.into_raw_args();
-        sql::query(sql, params, options);
+
+        if self.collect_stats {
+            let options = sql::Options2 {
+                limit,
+                persistent: true,
+                collect_stats: true,
+            };
+
+            sql::query2(sql, params, options);
+        } else {
+            let options = sql::Options {
+                limit,
+                persistent: true,
+            };
+
+            sql::query(sql, params, options);
+        }
 
         QueryIterator
     }
@@ -149,17 +313,29 @@ impl<'c> sqlx::Executor<'c> for &'c mut Connection {
 
         let sql = query.sql();
         let params = query.take_arguments().map_err(sqlx::Error::Encode);
-        let options = sql::Options {
-            limit: u8::MAX,
-            persistent: true,
-        };
 
         Box::pin(try_stream! {
             let params = params?.unwrap_or_default();
-            let iter = self.run(sql, params, options);
+            let collect_stats = self.collect_stats;
+            let iter = self.run(sql, params, u8::MAX);
 
+            // Statistics are only available once the query has completed, so we hold
+            // back the last query result until then in order to attach them to it.
+            let mut pending = None;
             for item in iter {
-                yield item?;
+                match item? {
+                    sqlx::Either::Left(result) if collect_stats => {
+                        if let Some(result) = pending.replace(result) {
+                            yield sqlx::Either::Left(result);
+                        }
+                    }
+                    item => yield item,
+                }
+            }
+
+            if let Some(result) = pending {
+                let stats = sql::last_query_stats().map(QueryStats::from_raw);
+                yield sqlx::Either::Left(result.with_stats(stats));
             }
         })
     }
@@ -174,14 +350,10 @@ impl<'c> sqlx::Executor<'c> for &'c mut Connection {
     {
         let sql = query.sql();
         let params = query.take_arguments().map_err(sqlx::Error::Encode);
-        let options = sql::Options {
-            limit: 1,
-            persistent: true,
-        };
 
         Box::pin(async move {
             let params = params?.unwrap_or_default();
-            let iter = self.run(sql, params, options);
+            let iter = self.run(sql, params, 1);
 
             for item in iter {
                 if let sqlx::Either::Right(row) = item? {
@@ -201,17 +373,26 @@ impl<'c> sqlx::Executor<'c> for &'c mut Connection {
     where
         'c: 'e,
     {
-        Box::pin(std::future::ready(Ok(Statement::new(sql))))
+        let statement = self.describe_raw(sql).map(|describe| {
+            let parameters = match describe.parameters {
+                Some(sqlx::Either::Left(parameters)) => parameters,
+                _ => Vec::new(),
+            };
+
+            Statement::new(sql, parameters, describe.columns)
+        });
+
+        Box::pin(std::future::ready(statement))
     }
 
     fn describe<'e, 'q: 'e>(
         self,
-        _: &'q str,
+        sql: &'q str,
     ) -> BoxFuture<'e, Result<sqlx::Describe<Self::Database>, sqlx::Error>>
     where
         'c: 'e,
     {
-        unimplemented!("describe is not implemented for the durable driver")
+        Box::pin(std::future::ready(self.describe_raw(sql)))
     }
 }
 
@@ -0,0 +1,149 @@
+use std::io;
+
+use crate::bindings as sql;
+use crate::driver::error::convert_query_error;
+use crate::driver::Connection;
+
+/// A `COPY ... FROM STDIN` operation that is in progress.
+///
+/// Created by [`Connection::copy_in`] or [`Connection::copy_in_raw`]. Data is
+/// sent to the database either by calling [`send`](CopyIn::send) or via the
+/// [`std::io::Write`] impl. Chunks are forwarded to the database as they are
+/// written and do not need to line up with row boundaries.
+///
+/// The operation must be completed by calling [`finish`](CopyIn::finish). If
+/// it is dropped before then, the copy is aborted and none of the data
+/// that was sent will be written to the table.
+///
+/// Like any other failed statement, an aborted copy leaves the enclosing
+/// database transaction in a failed state. Use [`Connection::savepoint`] if
+/// the transaction needs to keep going after the copy is aborted.
+#[must_use = "the COPY operation will be aborted unless finish is called"]
+pub struct CopyIn<'c> {
+    _conn: &'c mut Connection,
+    done: bool,
+}
+
+impl<'c> CopyIn<'c> {
+    fn start(conn: &'c mut Connection, statement: &str) -> crate::Result<Self> {
+        sql::copy_in_start(statement).map_err(convert_query_error)?;
+
+        Ok(Self {
+            _conn: conn,
+            done: false,
+        })
+    }
+
+    /// Send a chunk of data to the database.
+    pub fn send(&mut self, data: &[u8]) -> crate::Result<()> {
+        sql::copy_in_send(data).map_err(convert_query_error)?;
+        Ok(())
+    }
+
+    /// Complete the copy operation.
+    ///
+    /// Returns the number of rows that were copied into the table.
+    pub fn finish(mut self) -> crate::Result<u64> {
+        self.done = true;
+        Ok(sql::copy_in_finish().map_err(convert_query_error)?)
+    }
+
+    /// Abort the copy operation, discarding all data that has been sent so
+    /// far.
+    ///
+    /// The message is recorded in the database logs as the reason for the
+    /// abort.
+    pub fn abort(mut self, message: &str) -> crate::Result<()> {
+        self.done = true;
+        sql::copy_in_abort(message).map_err(convert_query_error)?;
+        Ok(())
+    }
+}
+
+impl io::Write for CopyIn<'_> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.send(buf).map_err(io::Error::other)?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Drop for CopyIn<'_> {
+    fn drop(&mut self) {
+        if !self.done {
+            let _ = sql::copy_in_abort("CopyIn dropped without calling finish");
+        }
+    }
+}
+
+impl Connection {
+    /// Start a `COPY ... FROM STDIN` operation that loads data into `table`.
+    ///
+    /// `table` may optionally be qualified with a schema (e.g.
+    /// `"public.users"`). If `columns` is empty then data must be provided for
+    /// every column in the table.
+    ///
+    /// The data sent through the returned [`CopyIn`] must be in the postgres
+    /// text `COPY` format: one row per line, with columns separated by tabs.
+    /// Use [`copy_in_raw`](Self::copy_in_raw) if you need to pass other
+    /// options to the `COPY` statement.
+    ///
+    /// ```no_run
+    /// # fn example(mut conn: durable::sqlx::Connection) -> durable::sqlx::Result<()> {
+    /// let mut copy = conn.copy_in("users", &["id", "name"])?;
+    /// for (id, name) in [(1, "alice"), (2, "bob")] {
+    ///     copy.send(format!("{id}\t{name}\n").as_bytes())?;
+    /// }
+    ///
+    /// let rows = copy.finish()?;
+    /// assert_eq!(rows, 2);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn copy_in(&mut self, table: &str, columns: &[&str]) -> crate::Result<CopyIn<'_>> {
+        let mut statement = String::from("COPY ");
+
+        for (i, part) in table.split('.').enumerate() {
+            if i != 0 {
+                statement.push('.');
+            }
+
+            push_identifier(&mut statement, part);
+        }
+
+        if !columns.is_empty() {
+            statement.push_str(" (");
+
+            for (i, column) in columns.iter().enumerate() {
+                if i != 0 {
+                    statement.push_str(", ");
+                }
+
+                push_identifier(&mut statement, column);
+            }
+
+            statement.push(')');
+        }
+
+        statement.push_str(" FROM STDIN");
+
+        self.copy_in_raw(&statement)
+    }
+
+    /// Start a `COPY ... FROM STDIN` operation using the provided statement.
+    ///
+    /// The statement is passed to the database as-is. **Never** include
+    /// untrusted input within it.
+    pub fn copy_in_raw(&mut self, statement: &str) -> crate::Result<CopyIn<'_>> {
+        CopyIn::start(self, statement)
+    }
+}
+
+fn push_identifier(statement: &mut String, ident: &str) {
+    statement.push('"');
+    statement.push_str(&ident.replace('"', "\"\""));
+    statement.push('"');
+}
@@ -1,5 +1,8 @@
+use std::time::Duration;
+
 use serde::{Deserialize, Serialize};
 
+use crate::bindings as sql;
 use crate::driver::{
     Arguments, Column, Connection, Row, Statement, TransactionManager, TypeInfo, Value,
 };
@@ -27,22 +30,84 @@ impl sqlx::Database for Durable {
 #[derive(Copy, Clone, Default, Debug, Serialize, Deserialize)]
 pub struct QueryResult {
     rows_affected: u64,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    stats: Option<QueryStats>,
 }
 
 impl QueryResult {
     pub(crate) fn new(rows_affected: u64) -> Self {
-        Self { rows_affected }
+        Self {
+            rows_affected,
+            stats: None,
+        }
+    }
+
+    pub(crate) fn with_stats(mut self, stats: Option<QueryStats>) -> Self {
+        self.stats = stats;
+        self
     }
 
     pub fn rows_affected(&self) -> u64 {
         self.rows_affected
     }
+
+    /// Execution statistics for the query that produced this result.
+    ///
+    /// These are only available if statistics collection was enabled on the
+    /// connection via [`Connection::set_collect_stats`].
+    pub fn stats(&self) -> Option<QueryStats> {
+        self.stats
+    }
 }
 
 impl Extend<QueryResult> for QueryResult {
     fn extend<T: IntoIterator<Item = QueryResult>>(&mut self, iter: T) {
         for item in iter {
             self.rows_affected += item.rows_affected;
+
+            if item.stats.is_some() {
+                self.stats = item.stats;
+            }
+        }
+    }
+}
+
+/// Execution statistics for a single query.
+///
+/// See [`Connection::set_collect_stats`] for details on how these are
+/// collected.
+#[derive(Copy, Clone, Debug, Serialize, Deserialize)]
+pub struct QueryStats {
+    duration: Duration,
+    execution_time: Option<Duration>,
+    rows_scanned: Option<u64>,
+}
+
+impl QueryStats {
+    pub(crate) fn from_raw(raw: sql::QueryStats) -> Self {
+        Self {
+            duration: Duration::from_nanos(raw.duration),
+            execution_time: raw.execution_time.map(Duration::from_nanos),
+            rows_scanned: raw.rows_scanned,
         }
     }
+
+    /// The time the runtime spent executing the query and fetching all of its
+    /// results.
+    pub fn duration(&self) -> Duration {
+        self.duration
+    }
+
+    /// The execution time reported by the database via `EXPLAIN ANALYZE`, if
+    /// it was available.
+    pub fn execution_time(&self) -> Option<Duration> {
+        self.execution_time
+    }
+
+    /// The number of table rows scanned while executing the query, as reported
+    /// by `EXPLAIN ANALYZE`, if it was available.
+    pub fn rows_scanned(&self) -> Option<u64> {
+        self.rows_scanned
+    }
 }
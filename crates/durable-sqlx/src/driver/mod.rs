@@ -5,7 +5,9 @@
 //! sql query is synchronous on the backend.
 
 mod arguments;
+mod batch;
 mod connection;
+mod copy;
 mod database;
 mod error;
 mod row;
@@ -16,11 +18,14 @@ mod types;
 mod value;
 
 pub use self::arguments::Arguments;
+pub use self::batch::Batch;
 pub use self::connection::{ConnectOptions, Connection};
-pub use self::database::{Durable, QueryResult};
+pub use self::copy::CopyIn;
+pub use self::database::{Durable, QueryResult, QueryStats};
 pub(crate) use self::error::DatabaseError;
 pub use self::row::{Column, Row};
 pub use self::statement::Statement;
 pub use self::transaction::TransactionManager;
 pub use self::type_info::TypeInfo;
+pub use self::types::PgRaw;
 pub use self::value::Value;
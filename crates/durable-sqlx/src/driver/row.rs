@@ -93,13 +93,23 @@ impl sqlx::Row for Row {
     }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Column {
     ordinal: usize,
     name: String,
     type_info: TypeInfo,
 }
 
+impl Column {
+    pub(crate) fn new(ordinal: usize, name: String, type_info: TypeInfo) -> Self {
+        Self {
+            ordinal,
+            name,
+            type_info,
+        }
+    }
+}
+
 impl sqlx::Column for Column {
     type Database = Durable;
 
@@ -1,12 +1,20 @@
 use std::borrow::Cow;
 
-use crate::driver::Durable;
+use crate::driver::{Column, Durable, TypeInfo};
 
-pub struct Statement<'q>(Cow<'q, str>);
+pub struct Statement<'q> {
+    sql: Cow<'q, str>,
+    parameters: Vec<TypeInfo>,
+    columns: Vec<Column>,
+}
 
 impl<'q> Statement<'q> {
-    pub(crate) fn new(sql: &'q str) -> Self {
-        Self(Cow::Borrowed(sql))
+    pub(crate) fn new(sql: &'q str, parameters: Vec<TypeInfo>, columns: Vec<Column>) -> Self {
+        Self {
+            sql: Cow::Borrowed(sql),
+            parameters,
+            columns,
+        }
     }
 }
 
@@ -14,21 +22,25 @@ impl<'q> sqlx::Statement<'q> for Statement<'q> {
     type Database = Durable;
 
     fn to_owned(&self) -> Statement<'static> {
-        Statement(Cow::Owned(self.0.clone().into_owned()))
+        Statement {
+            sql: Cow::Owned(self.sql.clone().into_owned()),
+            parameters: self.parameters.clone(),
+            columns: self.columns.clone(),
+        }
     }
 
     fn sql(&self) -> &str {
-        &self.0
+        &self.sql
     }
 
     fn parameters(
         &self,
     ) -> Option<sqlx::Either<&[<Self::Database as sqlx::Database>::TypeInfo], usize>> {
-        None
+        Some(sqlx::Either::Left(&self.parameters))
     }
 
     fn columns(&self) -> &[<Self::Database as sqlx::Database>::Column] {
-        &[]
+        &self.columns
     }
 
     fn query(
@@ -1,45 +1,22 @@
 use futures_core::future::BoxFuture;
 
+use crate::bindings as sql;
+use crate::driver::error::convert_query_error;
 use crate::driver::{Connection, Durable};
 
 pub enum TransactionManager {}
 
 impl TransactionManager {
-    async fn begin(conn: &mut Connection) -> Result<(), sqlx::Error> {
-        let sql = format!("SAVEPOINT savepoint_{}", conn.txn_depth);
-        sqlx::query(&sql).execute(&mut *conn).await?;
-        conn.txn_depth += 1;
-
-        Ok(())
+    pub(crate) fn begin(_: &mut Connection) -> Result<(), sqlx::Error> {
+        sql::savepoint().map_err(convert_query_error)
     }
 
-    async fn commit(conn: &mut Connection) -> Result<(), sqlx::Error> {
-        if conn.txn_depth == 0 {
-            return Err(sqlx::Error::Protocol(
-                "attempted to commit a database transaction when no transaction was active".into(),
-            ));
-        }
-
-        let sql = format!("RELEASE savepoint_{}", conn.txn_depth - 1);
-        sqlx::query(&sql).execute(&mut *conn).await?;
-        conn.txn_depth -= 1;
-
-        Ok(())
+    pub(crate) fn commit(_: &mut Connection) -> Result<(), sqlx::Error> {
+        sql::release_savepoint().map_err(convert_query_error)
     }
 
-    async fn rollback(conn: &mut Connection) -> Result<(), sqlx::Error> {
-        if conn.txn_depth == 0 {
-            return Err(sqlx::Error::Protocol(
-                "attempted to rollback a database transaction when no transaction was active"
-                    .into(),
-            ));
-        }
-
-        let sql = format!("ROLLBACK TO savepoint_{}", conn.txn_depth - 1);
-        sqlx::query(&sql).execute(&mut *conn).await?;
-        conn.txn_depth -= 1;
-
-        Ok(())
+    pub(crate) fn rollback(_: &mut Connection) -> Result<(), sqlx::Error> {
+        sql::rollback_savepoint().map_err(convert_query_error)
     }
 }
 
@@ -47,18 +24,18 @@ impl sqlx::TransactionManager for TransactionManager {
     type Database = Durable;
 
     fn begin(conn: &mut Connection) -> BoxFuture<'_, Result<(), sqlx::Error>> {
-        Box::pin(Self::begin(conn))
+        Box::pin(std::future::ready(Self::begin(conn)))
     }
 
     fn commit(conn: &mut Connection) -> BoxFuture<'_, Result<(), sqlx::Error>> {
-        Box::pin(Self::commit(conn))
+        Box::pin(std::future::ready(Self::commit(conn)))
     }
 
     fn rollback(conn: &mut Connection) -> BoxFuture<'_, Result<(), sqlx::Error>> {
-        Box::pin(Self::rollback(conn))
+        Box::pin(std::future::ready(Self::rollback(conn)))
     }
 
     fn start_rollback(conn: &mut Connection) {
-        let _ = crate::util::block_on(Self::rollback(conn));
+        let _ = Self::rollback(conn);
     }
 }
@@ -3,7 +3,7 @@ use std::fmt;
 use serde::{Deserialize, Serialize};
 use serde_json::value::RawValue;
 
-use crate::bindings::durable::core::sql;
+use crate::bindings as sql;
 use crate::{Error, Result};
 
 macro_rules! decl_tyinfo_ctor {
@@ -99,13 +99,33 @@ impl fmt::Display for TypeInfo {
 }
 
 impl Serialize for TypeInfo {
+    fn serialize<S>(&self, ser: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        Serialize::serialize(&self.tyinfo, ser)
+    }
+}
+
+impl<'de> Deserialize<'de> for TypeInfo {
+    fn deserialize<D>(de: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let tyinfo: sql::TypeInfo = Deserialize::deserialize(de)?;
+
+        Ok(Self::new(tyinfo))
+    }
+}
+
+impl Serialize for sql::TypeInfo {
     fn serialize<S>(&self, ser: S) -> Result<S::Ok, S::Error>
     where
         S: serde::Serializer,
     {
         use serde::ser::Error;
 
-        let json = self.tyinfo.serialize().map_err(Error::custom)?;
+        let json = self.serialize().map_err(Error::custom)?;
         let json: Box<str> = json.into_boxed_str();
         let json: Box<RawValue> = unsafe { std::mem::transmute(json) };
 
@@ -113,7 +133,7 @@ impl Serialize for TypeInfo {
     }
 }
 
-impl<'de> Deserialize<'de> for TypeInfo {
+impl<'de> Deserialize<'de> for sql::TypeInfo {
     fn deserialize<D>(de: D) -> Result<Self, D::Error>
     where
         D: serde::Deserializer<'de>,
@@ -123,10 +143,7 @@ impl<'de> Deserialize<'de> for TypeInfo {
         let json: Box<RawValue> = Deserialize::deserialize(de)?;
         let tyinfo = sql::TypeInfo::deserialize(json.get()).map_err(Error::custom)?;
 
-        Ok(Self {
-            name: tyinfo.name(),
-            tyinfo,
-        })
+        Ok(tyinfo)
     }
 }
 
@@ -3,7 +3,7 @@ use sqlx::encode::IsNull;
 use sqlx::error::BoxDynError;
 
 use super::unexpected_nonnull_type;
-use crate::bindings::durable::core::sql;
+use crate::bindings as sql;
 use crate::driver::{Durable, TypeInfo, Value};
 
 impl<Tz: TimeZone> sqlx::Encode<'_, Durable> for DateTime<Tz> {
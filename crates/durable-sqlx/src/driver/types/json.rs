@@ -5,7 +5,7 @@ use sqlx::error::BoxDynError;
 use sqlx::types::{Json, JsonRawValue};
 
 use super::{encode_by_ref, unexpected_nonnull_type};
-use crate::bindings::durable::core::sql;
+use crate::bindings as sql;
 use crate::driver::{Durable, TypeInfo, Value};
 
 impl<T> sqlx::Encode<'_, Durable> for Json<T>
@@ -92,10 +92,13 @@ mod int;
 mod ipnetwork;
 #[cfg(feature = "json")]
 mod json;
+mod raw;
 mod text;
 #[cfg(feature = "uuid")]
 mod uuid;
 
+pub use self::raw::PgRaw;
+
 fn unexpected_nullable_type(expected: &TypeInfo, value: &Value) -> BoxDynError {
     format!("expected {expected}, got {} instead", value.type_info()).into()
 }
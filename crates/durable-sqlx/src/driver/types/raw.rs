@@ -0,0 +1,99 @@
+use sqlx::encode::IsNull;
+use sqlx::error::BoxDynError;
+use sqlx::Value as _;
+
+use crate::driver::{Durable, TypeInfo, Value};
+
+/// A value of a postgres type that the runtime does not natively support.
+///
+/// Decoding a column of an unsupported type (e.g. `numeric` or `money`) into
+/// any other type fails, but it can always be decoded as a `PgRaw`. This
+/// gives access to the value in the postgres binary format for its type,
+/// along with the oid of that type.
+///
+/// Binding a `PgRaw` as a query parameter passes the original value back to
+/// the database unchanged. If you need the value as text then cast it to
+/// `text` within the query instead.
+///
+/// ```no_run
+/// # fn example(mut conn: durable::sqlx::Connection) -> durable::sqlx::Result<()> {
+/// use durable::sqlx::types::PgRaw;
+///
+/// let price: PgRaw = durable::sqlx::query_scalar("SELECT 12.50::numeric")
+///     .fetch_one(&mut conn)?;
+/// assert_eq!(price.oid(), 1700);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct PgRaw {
+    value: Value,
+    oid: u32,
+    data: Vec<u8>,
+}
+
+impl PgRaw {
+    /// The oid of the value's type.
+    pub fn oid(&self) -> u32 {
+        self.oid
+    }
+
+    /// The value, in the postgres binary format for its type.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// The type of the value.
+    pub fn type_info(&self) -> TypeInfo {
+        self.value.type_info()
+    }
+}
+
+impl sqlx::Encode<'_, Durable> for PgRaw {
+    fn encode_by_ref(
+        &self,
+        buf: &mut <Durable as sqlx::Database>::ArgumentBuffer<'_>,
+    ) -> Result<IsNull, BoxDynError> {
+        buf.push(self.value.clone());
+        Ok(IsNull::No)
+    }
+
+    fn produces(&self) -> Option<TypeInfo> {
+        Some(self.value.type_info())
+    }
+}
+
+impl sqlx::Decode<'_, Durable> for PgRaw {
+    fn decode(value: <Durable as sqlx::Database>::ValueRef<'_>) -> Result<Self, BoxDynError> {
+        let Some(raw) = value.0.as_raw() else {
+            if value.is_null() {
+                return Err("expected a non-null value, got null instead".into());
+            }
+
+            return Err(format!(
+                "expected a value of an unsupported type, got {} instead",
+                value.type_info()
+            )
+            .into());
+        };
+
+        Ok(Self {
+            value: value.clone(),
+            oid: raw.oid,
+            data: raw.data,
+        })
+    }
+}
+
+impl sqlx::Type<Durable> for PgRaw {
+    fn type_info() -> <Durable as sqlx::Database>::TypeInfo {
+        // The real type is provided by Encode::produces.
+        TypeInfo::bytea()
+    }
+
+    fn compatible(_: &TypeInfo) -> bool {
+        // Whether the value actually has an unsupported type is checked when it is
+        // decoded.
+        true
+    }
+}
@@ -5,7 +5,7 @@ use sqlx::error::BoxDynError;
 use uuid::Uuid;
 
 use super::unexpected_nonnull_type;
-use crate::bindings::durable::core::sql;
+use crate::bindings as sql;
 use crate::driver::{Durable, TypeInfo, Value};
 
 impl sqlx::Encode<'_, Durable> for Uuid {
@@ -169,7 +169,7 @@ mod database_error {
     use serde::{Deserialize, Deserializer, Serialize, Serializer};
     use sqlx::error::{DatabaseError as SqlxDatabaseError, ErrorKind};
 
-    use crate::bindings::durable::core::sql;
+    use crate::bindings as sql;
     use crate::driver::DatabaseError;
 
     #[allow(clippy::borrowed_box)]
@@ -0,0 +1,137 @@
+use std::fmt;
+
+use serde_json::Value as JsonValue;
+use sqlx::Execute;
+
+use crate::driver::{Arguments, Connection, Durable};
+use crate::{Query, TransactionOptions};
+
+/// The plan that the database chose for a query, as returned by [`explain`].
+///
+/// This wraps the output of `EXPLAIN (FORMAT JSON)`. The accessors here cover
+/// the most commonly needed parts of the plan; the full output is available
+/// via [`as_json`](QueryPlan::as_json). Printing the plan with `{}` formats it
+/// as pretty-printed JSON.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct QueryPlan(JsonValue);
+
+impl QueryPlan {
+    /// The root node of the plan.
+    pub fn root(&self) -> &JsonValue {
+        &self.0[0]["Plan"]
+    }
+
+    /// The type of the root node of the plan, e.g. `Seq Scan` or
+    /// `Index Scan`.
+    pub fn node_type(&self) -> Option<&str> {
+        self.root()["Node Type"].as_str()
+    }
+
+    /// The planner's estimate of the total cost of running the query.
+    pub fn total_cost(&self) -> Option<f64> {
+        self.root()["Total Cost"].as_f64()
+    }
+
+    /// The planner's estimate of the number of rows returned by the query.
+    pub fn estimated_rows(&self) -> Option<f64> {
+        self.root()["Plan Rows"].as_f64()
+    }
+
+    /// The full output of `EXPLAIN (FORMAT JSON)`.
+    pub fn as_json(&self) -> &JsonValue {
+        &self.0
+    }
+
+    pub fn into_json(self) -> JsonValue {
+        self.0
+    }
+}
+
+impl fmt::Display for QueryPlan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match serde_json::to_string_pretty(&self.0) {
+            Ok(json) => f.write_str(&json),
+            Err(_) => Err(fmt::Error),
+        }
+    }
+}
+
+/// Ask the database how it would run `query`, without running it.
+///
+/// This runs `EXPLAIN (FORMAT JSON)` for the query in its own read-only
+/// transaction and returns the resulting plan. The plan is recorded in the
+/// workflow's event log like any other transaction, so a workflow that is
+/// restarted will see the same plan again.
+///
+/// This is meant for debugging slow queries from within a workflow, e.g. in
+/// its tests. Use [`Connection::explain`] to explain a query from within an
+/// existing transaction instead.
+///
+/// ```no_run
+/// use durable::sqlx;
+///
+/// let plan = sqlx::explain(sqlx::query("SELECT * FROM users WHERE email = $1").bind("a@b.c"))?;
+/// if plan.node_type() == Some("Seq Scan") {
+///     println!("missing an index on users.email:\n{plan}");
+/// }
+/// # Ok::<_, sqlx::Error>(())
+/// ```
+///
+/// # Restrictions
+/// * Calling this function within another transaction will result in the
+///   workflow being killed immediately via a trap.
+pub fn explain<'q, A>(query: Query<'q, A>) -> crate::Result<QueryPlan>
+where
+    A: sqlx::IntoArguments<'q, Durable> + Send + 'q,
+{
+    let (sql, arguments) = into_parts(query)?;
+    let options = TransactionOptions::new("durable::sqlx::explain").read_only();
+
+    crate::transaction_with(options, |mut conn| {
+        explain_raw(&mut conn, sql, arguments.clone())
+    })
+}
+
+impl Connection {
+    /// Ask the database how it would run `query` within the current
+    /// transaction, without running it.
+    ///
+    /// See [`explain`] for details.
+    ///
+    /// ```no_run
+    /// # fn example(mut conn: durable::sqlx::Connection) -> durable::sqlx::Result<()> {
+    /// use durable::sqlx;
+    ///
+    /// let plan = conn.explain(sqlx::query("SELECT count(*) FROM users"))?;
+    /// println!("estimated cost: {:?}", plan.total_cost());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn explain<'q, A>(&mut self, query: Query<'q, A>) -> crate::Result<QueryPlan>
+    where
+        A: sqlx::IntoArguments<'q, Durable> + Send + 'q,
+    {
+        let (sql, arguments) = into_parts(query)?;
+        explain_raw(self, sql, arguments)
+    }
+}
+
+fn into_parts<'q, A>(query: Query<'q, A>) -> crate::Result<(&'q str, Arguments)>
+where
+    A: sqlx::IntoArguments<'q, Durable> + Send + 'q,
+{
+    let mut query = query.0;
+    let arguments = query
+        .take_arguments()
+        .map_err(crate::Error::Encode)?
+        .unwrap_or_default();
+
+    Ok((query.sql(), arguments))
+}
+
+fn explain_raw(conn: &mut Connection, sql: &str, arguments: Arguments) -> crate::Result<QueryPlan> {
+    let json = conn.explain_raw(sql, arguments)?;
+    let plan = serde_json::from_str(&json).map_err(|e| crate::Error::Decode(e.into()))?;
+    Ok(QueryPlan(plan))
+}
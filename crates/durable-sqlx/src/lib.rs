@@ -26,6 +26,47 @@
 //! # Ok(())
 //! # }
 //! ```
+//!
+//! # Compile-time checked queries
+//! With the `macros` feature enabled this crate provides the same family of
+//! compile-time checked query macros as sqlx does: [`query!`], [`query_as!`],
+//! [`query_scalar!`], along with their `_file` and `_unchecked` variants.
+//! They expand to the same [`Query`], [`QueryAs`], [`QueryScalar`], and
+//! [`Map`] types as the functions in this crate, so they can be executed
+//! against a [`Connection`] in the same way.
+//!
+//! ```ignore
+//! use durable::sqlx;
+//!
+//! struct Event {
+//!     label: String,
+//!     index: i32,
+//! }
+//!
+//! let events = sqlx::transaction("load events", |mut conn| -> sqlx::Result<_> {
+//!     let events = sqlx::query_as!(
+//!         Event,
+//!         "SELECT label, index FROM durable.event WHERE task_id = $1",
+//!         task_id
+//!     )
+//!     .fetch_all(&mut conn)?;
+//!
+//!     Ok(events.len())
+//! })?;
+//! ```
+//!
+//! The macros validate each query against the database in the `DATABASE_URL`
+//! environment variable at compile time. This should be a postgres database
+//! with the same schema as the one that the durable worker runs against.
+//!
+//! ## Offline mode
+//! The macros use the same offline query cache as sqlx. Run
+//! `cargo sqlx prepare` (or `cargo sqlx prepare --workspace`) with a database
+//! available to save the query metadata to the `.sqlx` directory. Builds
+//! will then use the cached metadata whenever `DATABASE_URL` is not set, or
+//! always if `SQLX_OFFLINE=true` is set. The `.sqlx` directory should be
+//! checked into version control so that it is available wherever the workflow
+//! is built.
 
 use driver::{Durable, QueryResult, Row};
 use serde::de::DeserializeOwned;
@@ -39,14 +80,24 @@ extern crate serde;
 
 pub mod driver;
 mod error;
+mod explain;
+mod listen;
 #[cfg(feature = "macros")]
 mod macros;
+mod query_builder;
 mod util;
 
+#[cfg(all(feature = "mock", not(target_family = "wasm")))]
+pub mod mock;
+
 #[cfg(feature = "macros")]
 #[doc(hidden)]
 pub use crate::macros::exports;
 
+#[cfg(all(feature = "mock", not(target_family = "wasm")))]
+use crate::mock::bindings;
+
+#[cfg(not(all(feature = "mock", not(target_family = "wasm"))))]
 mod bindings {
     #![allow(unused_braces, clippy::all)]
 
@@ -58,11 +109,19 @@ mod bindings {
 pub mod types {
     #[cfg(feature = "json")]
     pub use sqlx::types::{Json, JsonRawValue};
+
+    pub use crate::driver::PgRaw;
 }
 
+#[doc(no_inline)]
+pub use durable_core::transaction::TransactionOptions;
+
 #[doc(inline)]
 pub use crate::driver::Connection;
 pub use crate::error::Error;
+pub use crate::explain::{explain, QueryPlan};
+pub use crate::listen::{listen, PgNotification};
+pub use crate::query_builder::{QueryBuilder, Separated};
 
 pub type Result<T, E = Error> = std::result::Result<T, E>;
 
@@ -85,10 +144,67 @@ where
     F: Fn(Connection) -> T,
     T: Serialize + DeserializeOwned,
 {
-    use durable_core::transaction::{transaction_with, TransactionOptions};
+    transaction_with(TransactionOptions::new(label), func)
+}
+
+/// Run a durable transaction with a database transaction, using the provided
+/// options.
+///
+/// This behaves exactly like [`transaction`], except that `options` can be
+/// used to further restrict the database transaction. The transaction is
+/// always a database transaction, regardless of what was passed to
+/// [`TransactionOptions::database`].
+///
+/// ```no_run
+/// use std::time::Duration;
+///
+/// use durable::sqlx::{self, TransactionOptions};
+///
+/// let options = TransactionOptions::new("count users")
+///     .statement_timeout(Duration::from_secs(5))
+///     .read_only();
+///
+/// let count: i64 = sqlx::transaction_with(options, |mut conn| {
+///     sqlx::query_scalar("SELECT count(*) FROM users").fetch_one(&mut conn)
+/// })
+/// .unwrap();
+/// ```
+pub fn transaction_with<F, T>(options: TransactionOptions, func: F) -> T
+where
+    F: Fn(Connection) -> T,
+    T: Serialize + DeserializeOwned,
+{
+    let options = options.database(true);
+    durable_core::transaction::transaction_with(options, || func(Connection::new()))
+}
 
-    let options = TransactionOptions::new(label).database(true);
-    transaction_with(options, || func(Connection::new()))
+/// Evict the prepared statements that this program has cached on the worker's
+/// database connections.
+///
+/// The durable runtime caches the statements for the queries made by a
+/// workflow so that they only need to be parsed and planned once per
+/// connection. Changing the schema in a way that alters the result type of a
+/// query which has already been run (e.g. adding a column to a table that is
+/// read with `SELECT *`) will cause that query to fail until its cached
+/// statement is evicted.
+///
+/// When called within a transaction, the statements cached on the current
+/// connection are evicted immediately. Connections on other workers are
+/// cleared once the transaction commits.
+///
+/// ```no_run
+/// use durable::sqlx;
+///
+/// sqlx::transaction("add a column to users", |mut conn| {
+///     sqlx::query("ALTER TABLE users ADD COLUMN nickname text").execute(&mut conn)?;
+///     sqlx::clear_statement_cache();
+///
+///     Ok::<_, sqlx::Error>(())
+/// })?;
+/// # Ok::<_, sqlx::Error>(())
+/// ```
+pub fn clear_statement_cache() {
+    bindings::clear_statement_cache()
 }
 
 /// Execute a single SQL query as a prepared statement.
@@ -213,7 +329,7 @@ pub fn query(sql: &str) -> Query<driver::Arguments> {
 /// Execute a SQL query as a prepared statement (transparently cached), with the
 /// given arguments.
 ///
-/// See [`query()`][query] for details, such as supported syntax.
+/// See [`query()`] for details, such as supported syntax.
 pub fn query_with<'q, A>(sql: &'q str, arguments: A) -> Query<'q, A>
 where
     A: sqlx::IntoArguments<'q, Durable>,
@@ -239,8 +355,8 @@ where
 /// Execute a single SQL query as a prepared statement (transparently cached)
 /// and extract the first column of each row.
 ///
-/// This is a thin wrapper around [`sqlx::query_scalar`]. See the docs there for
-/// more.
+/// This is a thin wrapper around [`sqlx::query_scalar()`]. See the docs there
+/// for more.
 pub fn query_scalar<'q, O>(sql: &'q str) -> QueryScalar<'q, O, driver::Arguments>
 where
     (O,): for<'r> sqlx::FromRow<'r, Row>,
@@ -0,0 +1,42 @@
+use crate::bindings as sql;
+
+/// A notification sent via a postgres `NOTIFY` command.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PgNotification {
+    /// The channel that the notification was sent on.
+    pub channel: String,
+
+    /// The payload of the notification, or an empty string if none was
+    /// provided.
+    pub payload: String,
+}
+
+/// Block this task until a postgres notification is sent on `channel`, then
+/// return it.
+///
+/// The notification is recorded in the workflow's event log, so a workflow
+/// that is restarted will see the same notification again.
+///
+/// The runtime only starts listening on the channel once this function is
+/// called, so any notifications sent before then will be missed. Like when
+/// waiting for a [durable notification](durable_core::notify), the task will
+/// be suspended if nothing arrives for a while. The notification is then
+/// recorded by whichever worker receives it, which wakes the task back up.
+///
+/// ```no_run
+/// let notification = durable::sqlx::listen("orders");
+/// println!("order {} was updated", notification.payload);
+/// ```
+///
+/// # Traps
+/// Attempting to call this function within a transaction, or on a channel
+/// reserved by durable (e.g. `durable:task`), will result in a trap that
+/// instantly kills the workflow.
+pub fn listen(channel: &str) -> PgNotification {
+    let notification = sql::listen(channel);
+
+    PgNotification {
+        channel: notification.channel,
+        payload: notification.payload,
+    }
+}
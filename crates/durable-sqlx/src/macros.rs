@@ -164,7 +164,7 @@ macro_rules! query_file_as {
 /// semantically valid for the current database.
 #[macro_export]
 macro_rules! query_as_unchecked {
-    (out_struct:path, $query:expr $(, $($args:tt)*)?) => {{
+    ($out_struct:path, $query:expr $(, $($args:tt)*)?) => {{
         use $crate::exports::sqlx;
 
         $crate::exports::into_durable($crate::exports::expand_query!(
@@ -0,0 +1,1075 @@
+//! Canned query results for use with the durable mock runtime.
+//!
+//! When the `mock` feature is enabled and the crate is compiled for a non-wasm
+//! target no queries are sent to a database. Instead, each statement is
+//! matched against the results registered with [`respond`] for the current
+//! thread. Database transactions are run by the mock in `durable-core` just
+//! like any other transaction, so they show up in its event log.
+//!
+//! ```ignore
+//! use durable_sqlx::mock::{self, MockResult, MockRow};
+//!
+//! mock::respond(
+//!     "SELECT name FROM users WHERE id = $1",
+//!     MockResult::rows([MockRow::new().column("name", "alice")]),
+//! );
+//!
+//! let name: String = durable_sqlx::transaction("load the user", |mut conn| {
+//!     durable_sqlx::query_scalar("SELECT name FROM users WHERE id = $1")
+//!         .bind(5)
+//!         .fetch_one(&mut conn)
+//! })?;
+//!
+//! assert_eq!(name, "alice");
+//! assert_eq!(mock::queries()[0].param::<i32>(0), 5);
+//! ```
+//!
+//! # Limitations
+//! - Statements are matched by their SQL text, ignoring differences in
+//!   whitespace. A statement without a registered result fails with an error.
+//! - Savepoints never roll anything back since there is no database state.
+//! - Describing a statement always fails.
+//! - Waiting for a postgres notification when none are queued panics instead
+//!   of blocking forever.
+
+use std::cell::RefCell;
+use std::collections::VecDeque;
+
+use crate::driver::{Arguments, Durable, Value};
+
+thread_local! {
+    static STATE: RefCell<MockState> = RefCell::new(MockState::default());
+}
+
+#[derive(Default)]
+struct MockState {
+    responses: Vec<(String, MockResult)>,
+    queries: Vec<MockQuery>,
+    notifications: VecDeque<bindings::PgNotification>,
+
+    /// The results of the current query that have not yet been fetched.
+    pending: VecDeque<Result<bindings::QueryResult, bindings::Error>>,
+    stats: Option<bindings::QueryStats>,
+    collect_stats: bool,
+    savepoints: usize,
+    copy: Option<(usize, u64)>,
+}
+
+fn with_state<R>(func: impl FnOnce(&mut MockState) -> R) -> R {
+    STATE.with(|state| func(&mut state.borrow_mut()))
+}
+
+fn normalize(sql: &str) -> String {
+    sql.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// A canned result for a statement.
+#[derive(Clone, Debug)]
+pub struct MockResult(Result<(Vec<MockRow>, u64), bindings::Error>);
+
+impl MockResult {
+    /// Create a result that returns `rows`.
+    ///
+    /// The number of rows affected by the statement is the number of rows.
+    pub fn rows(rows: impl IntoIterator<Item = MockRow>) -> Self {
+        let rows: Vec<_> = rows.into_iter().collect();
+        let count = rows.len() as u64;
+
+        Self(Ok((rows, count)))
+    }
+
+    /// Create a result that returns no rows and reports that `count` rows
+    /// were affected by the statement.
+    pub fn affected(count: u64) -> Self {
+        Self(Ok((Vec::new(), count)))
+    }
+
+    /// Create a result that fails with a database error.
+    pub fn error(message: &str) -> Self {
+        Self::database_error(bindings::DatabaseErrorKind::Other, message, None)
+    }
+
+    /// Create a result that fails as if the statement had violated the unique
+    /// constraint `constraint`.
+    pub fn unique_violation(constraint: &str) -> Self {
+        Self::database_error(
+            bindings::DatabaseErrorKind::UniqueViolation,
+            &format!("duplicate key value violates unique constraint \"{constraint}\""),
+            Some(constraint),
+        )
+    }
+
+    fn database_error(
+        kind: bindings::DatabaseErrorKind,
+        message: &str,
+        constraint: Option<&str>,
+    ) -> Self {
+        let code = match kind {
+            bindings::DatabaseErrorKind::UniqueViolation => Some("23505".to_owned()),
+            _ => None,
+        };
+
+        Self(Err(bindings::Error::Database(bindings::DatabaseError {
+            message: message.to_owned(),
+            kind,
+            code,
+            constraint: constraint.map(ToOwned::to_owned),
+            table: None,
+        })))
+    }
+}
+
+/// A row returned by a [`MockResult`].
+#[derive(Clone, Debug, Default)]
+pub struct MockRow(Vec<(String, Value)>);
+
+impl MockRow {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append a column to the row.
+    ///
+    /// # Panics
+    /// Panics if `value` cannot be encoded.
+    pub fn column<'q, T>(mut self, name: &str, value: T) -> Self
+    where
+        T: sqlx::Encode<'q, Durable> + sqlx::Type<Durable> + 'q,
+    {
+        let mut arguments = Arguments::default();
+        sqlx::Arguments::add(&mut arguments, value).expect("failed to encode mock column value");
+
+        let value = arguments
+            .into_raw_args()
+            .pop()
+            .expect("encoding a value produced no arguments");
+        self.0.push((name.to_owned(), Value::new(value)));
+        self
+    }
+}
+
+/// A statement that the workflow has run.
+#[derive(Clone, Debug)]
+pub struct MockQuery {
+    pub sql: String,
+    pub params: Vec<Value>,
+
+    /// The data sent for a `COPY ... FROM STDIN` statement.
+    pub copy_data: Option<Vec<u8>>,
+}
+
+impl MockQuery {
+    /// Decode the parameter at `index`.
+    ///
+    /// # Panics
+    /// Panics if there is no such parameter or it cannot be decoded as a `T`.
+    pub fn param<T>(&self, index: usize) -> T
+    where
+        T: for<'r> sqlx::Decode<'r, Durable>,
+    {
+        let value = self.params.get(index).unwrap_or_else(|| {
+            panic!(
+                "query has {} parameters, not {}",
+                self.params.len(),
+                index + 1
+            )
+        });
+
+        T::decode(value).expect("failed to decode mock query parameter")
+    }
+}
+
+/// Register a canned result for statements with the given SQL.
+///
+/// Each registered result is used for exactly one statement. If multiple
+/// results are registered for the same SQL then they are returned in the
+/// order they were registered.
+pub fn respond(sql: &str, result: MockResult) {
+    with_state(|state| state.responses.push((normalize(sql), result)));
+}
+
+/// Queue up a postgres notification to be returned by [`listen`](crate::listen).
+pub fn push_pg_notification(channel: &str, payload: &str) {
+    let notification = bindings::PgNotification {
+        channel: channel.to_owned(),
+        payload: payload.to_owned(),
+    };
+
+    with_state(|state| state.notifications.push_back(notification));
+}
+
+/// Get all statements that have been run on the current thread.
+pub fn queries() -> Vec<MockQuery> {
+    with_state(|state| state.queries.clone())
+}
+
+/// Reset all registered results and recorded statements for the current
+/// thread.
+pub fn reset() {
+    with_state(|state| *state = MockState::default());
+}
+
+fn assert_in_transaction(function: &str) {
+    if !durable_core::transaction::in_transaction() {
+        panic!("{function} called outside of a database transaction");
+    }
+}
+
+/// Record a statement and take the result registered for it.
+fn run(state: &mut MockState, sql: &str, params: Vec<Value>) -> MockResult {
+    let sql = normalize(sql);
+    let position = state.responses.iter().position(|(other, _)| *other == sql);
+    let result = match position {
+        Some(index) => state.responses.remove(index).1,
+        None => MockResult(Err(bindings::Error::Other(format!(
+            "no mock result registered for query `{sql}`"
+        )))),
+    };
+
+    state.queries.push(MockQuery {
+        sql,
+        params,
+        copy_data: None,
+    });
+    result
+}
+
+/// Native replacements for the functions in the generated bindings.
+pub(crate) mod bindings {
+    // Not every part of the API is used with every combination of features.
+    #![allow(dead_code)]
+
+    use super::*;
+
+    #[derive(Debug)]
+    pub struct TypeInfo {
+        name: String,
+    }
+
+    macro_rules! decl_tyinfo_ctor {
+        ($( $ctor:ident => $name:literal ),* $(,)?) => {
+            impl TypeInfo {
+                $(
+                    pub fn $ctor() -> Self {
+                        Self::named($name)
+                    }
+                )*
+            }
+        };
+    }
+
+    decl_tyinfo_ctor!(
+        boolean => "BOOL",
+        float4 => "FLOAT4",
+        float8 => "FLOAT8",
+        int1 => "\"CHAR\"",
+        int2 => "INT2",
+        int4 => "INT4",
+        int8 => "INT8",
+        text => "TEXT",
+        bytea => "BYTEA",
+        timestamptz => "TIMESTAMPTZ",
+        timestamp => "TIMESTAMP",
+        uuid => "UUID",
+        jsonb => "JSONB",
+        inet => "INET",
+        boolean_array => "BOOL[]",
+        float4_array => "FLOAT4[]",
+        float8_array => "FLOAT8[]",
+        int1_array => "\"CHAR\"[]",
+        int2_array => "INT2[]",
+        int4_array => "INT4[]",
+        int8_array => "INT8[]",
+        text_array => "TEXT[]",
+        bytea_array => "BYTEA[]",
+        timestamptz_array => "TIMESTAMPTZ[]",
+        timestamp_array => "TIMESTAMP[]",
+        uuid_array => "UUID[]",
+        jsonb_array => "JSONB[]",
+        inet_array => "INET[]",
+    );
+
+    impl TypeInfo {
+        fn named(name: &str) -> Self {
+            Self {
+                name: name.to_owned(),
+            }
+        }
+
+        pub fn name(&self) -> String {
+            self.name.clone()
+        }
+
+        pub fn compatible(&self, other: &TypeInfo) -> bool {
+            self.equal(other)
+        }
+
+        pub fn equal(&self, other: &TypeInfo) -> bool {
+            self.name.eq_ignore_ascii_case(&other.name)
+        }
+
+        pub fn clone(&self) -> TypeInfo {
+            Self::named(&self.name)
+        }
+
+        pub fn serialize(&self) -> Result<String, String> {
+            serde_json::to_string(&self.name).map_err(|e| e.to_string())
+        }
+
+        pub fn deserialize(json: &str) -> Result<TypeInfo, String> {
+            let name: String = serde_json::from_str(json).map_err(|e| e.to_string())?;
+            Ok(Self { name })
+        }
+
+        /// Like the runtime, except that there is no database to check that
+        /// the type actually exists.
+        pub fn with_name(name: &str) -> Result<TypeInfo, String> {
+            Ok(Self::named(name))
+        }
+    }
+
+    #[repr(C)]
+    #[derive(Clone, Copy, Debug, serde::Deserialize, serde::Serialize)]
+    pub struct Timestamp {
+        pub seconds: i64,
+        pub subsec_nanos: u32,
+    }
+
+    #[repr(C)]
+    #[derive(Clone, Copy, Debug, serde::Deserialize, serde::Serialize)]
+    pub struct Timestamptz {
+        pub seconds: i64,
+        pub subsec_nanos: u32,
+        pub offset: i32,
+    }
+
+    #[repr(C)]
+    #[derive(Clone, Copy, Debug, serde::Deserialize, serde::Serialize)]
+    pub struct Uuid {
+        pub hi: u64,
+        pub lo: u64,
+    }
+
+    #[repr(C)]
+    #[derive(Clone, Copy, Debug, serde::Deserialize, serde::Serialize)]
+    pub struct Ipv4Network {
+        pub addr: u32,
+        pub prefix: u8,
+    }
+
+    #[repr(C)]
+    #[derive(Clone, Copy, Debug, serde::Deserialize, serde::Serialize)]
+    pub struct Ipv6Network {
+        pub addr: (u64, u64),
+        pub prefix: u8,
+    }
+
+    #[derive(Clone, Copy, Debug, serde::Deserialize, serde::Serialize)]
+    pub enum IpNetwork {
+        V4(Ipv4Network),
+        V6(Ipv6Network),
+    }
+
+    #[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
+    pub struct RawValue {
+        pub oid: u32,
+        pub data: Vec<u8>,
+    }
+
+    #[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
+    #[serde(tag = "type", content = "value")]
+    enum Data {
+        Null,
+        Boolean(bool),
+        Float4(f32),
+        Float8(f64),
+        Int1(i8),
+        Int2(i16),
+        Int4(i32),
+        Int8(i64),
+        Text(String),
+        Bytea(Vec<u8>),
+        Timestamptz(Timestamptz),
+        Timestamp(Timestamp),
+        Uuid(Uuid),
+        Json(String),
+        Inet(IpNetwork),
+        BooleanArray(Vec<bool>),
+        Float4Array(Vec<f32>),
+        Float8Array(Vec<f64>),
+        Int1Array(Vec<i8>),
+        Int2Array(Vec<i16>),
+        Int4Array(Vec<i32>),
+        Int8Array(Vec<i64>),
+        TextArray(Vec<String>),
+        ByteaArray(Vec<Vec<u8>>),
+        TimestamptzArray(Vec<Timestamptz>),
+        TimestampArray(Vec<Timestamp>),
+        UuidArray(Vec<Uuid>),
+        JsonArray(Vec<String>),
+        InetArray(Vec<IpNetwork>),
+    }
+
+    #[derive(Debug)]
+    pub struct Value {
+        type_info: TypeInfo,
+        data: Data,
+    }
+
+    macro_rules! decl_value_accessors {
+        ($( $method:ident => $variant:ident: $ty:ty ),* $(,)?) => {
+            impl Value {
+                $(
+                    pub fn $method(&self) -> Option<$ty> {
+                        match &self.data {
+                            Data::$variant(value) => Some(value.clone()),
+                            _ => None,
+                        }
+                    }
+                )*
+            }
+        };
+    }
+
+    decl_value_accessors!(
+        as_boolean => Boolean: bool,
+        as_float4 => Float4: f32,
+        as_float8 => Float8: f64,
+        as_int1 => Int1: i8,
+        as_int2 => Int2: i16,
+        as_int4 => Int4: i32,
+        as_int8 => Int8: i64,
+        as_text => Text: String,
+        as_bytea => Bytea: Vec<u8>,
+        as_timestamptz => Timestamptz: Timestamptz,
+        as_timestamp => Timestamp: Timestamp,
+        as_uuid => Uuid: Uuid,
+        as_json => Json: String,
+        as_inet => Inet: IpNetwork,
+        as_boolean_array => BooleanArray: Vec<bool>,
+        as_float4_array => Float4Array: Vec<f32>,
+        as_float8_array => Float8Array: Vec<f64>,
+        as_int1_array => Int1Array: Vec<i8>,
+        as_int2_array => Int2Array: Vec<i16>,
+        as_int4_array => Int4Array: Vec<i32>,
+        as_int8_array => Int8Array: Vec<i64>,
+        as_text_array => TextArray: Vec<String>,
+        as_bytea_array => ByteaArray: Vec<Vec<u8>>,
+        as_timestamptz_array => TimestamptzArray: Vec<Timestamptz>,
+        as_timestamp_array => TimestampArray: Vec<Timestamp>,
+        as_uuid_array => UuidArray: Vec<Uuid>,
+        as_json_array => JsonArray: Vec<String>,
+        as_inet_array => InetArray: Vec<IpNetwork>,
+    );
+
+    #[derive(serde::Deserialize, serde::Serialize)]
+    struct ValueRepr {
+        type_info: String,
+        data: Data,
+    }
+
+    impl Value {
+        fn new(type_info: TypeInfo, data: Data) -> Self {
+            Self { type_info, data }
+        }
+
+        pub fn is_null(&self) -> bool {
+            matches!(self.data, Data::Null)
+        }
+
+        pub fn type_info(&self) -> TypeInfo {
+            self.type_info.clone()
+        }
+
+        pub fn clone(&self) -> Value {
+            Self::new(self.type_info.clone(), self.data.clone())
+        }
+
+        pub fn serialize(&self) -> Result<String, String> {
+            let repr = ValueRepr {
+                type_info: self.type_info.name(),
+                data: self.data.clone(),
+            };
+
+            serde_json::to_string(&repr).map_err(|e| e.to_string())
+        }
+
+        pub fn deserialize(json: &str) -> Result<Value, String> {
+            let repr: ValueRepr = serde_json::from_str(json).map_err(|e| e.to_string())?;
+
+            Ok(Self::new(TypeInfo::named(&repr.type_info), repr.data))
+        }
+
+        /// The mock supports every type that it can construct, so there are
+        /// never any raw values.
+        pub fn as_raw(&self) -> Option<RawValue> {
+            None
+        }
+
+        pub fn null(tyinfo: TypeInfo) -> Value {
+            Self::new(tyinfo, Data::Null)
+        }
+
+        pub fn boolean(value: bool) -> Value {
+            Self::new(TypeInfo::boolean(), Data::Boolean(value))
+        }
+
+        pub fn float4(value: f32) -> Value {
+            Self::new(TypeInfo::float4(), Data::Float4(value))
+        }
+
+        pub fn float8(value: f64) -> Value {
+            Self::new(TypeInfo::float8(), Data::Float8(value))
+        }
+
+        pub fn int1(value: i8) -> Value {
+            Self::new(TypeInfo::int1(), Data::Int1(value))
+        }
+
+        pub fn int2(value: i16) -> Value {
+            Self::new(TypeInfo::int2(), Data::Int2(value))
+        }
+
+        pub fn int4(value: i32) -> Value {
+            Self::new(TypeInfo::int4(), Data::Int4(value))
+        }
+
+        pub fn int8(value: i64) -> Value {
+            Self::new(TypeInfo::int8(), Data::Int8(value))
+        }
+
+        pub fn text(value: &str) -> Value {
+            Self::new(TypeInfo::text(), Data::Text(value.to_owned()))
+        }
+
+        pub fn bytea(value: &[u8]) -> Value {
+            Self::new(TypeInfo::bytea(), Data::Bytea(value.to_vec()))
+        }
+
+        pub fn timestamptz(value: Timestamptz) -> Value {
+            Self::new(TypeInfo::timestamptz(), Data::Timestamptz(value))
+        }
+
+        pub fn timestamp(value: Timestamp) -> Value {
+            Self::new(TypeInfo::timestamp(), Data::Timestamp(value))
+        }
+
+        pub fn uuid(value: Uuid) -> Value {
+            Self::new(TypeInfo::uuid(), Data::Uuid(value))
+        }
+
+        pub fn jsonb(value: &str) -> Value {
+            Self::new(TypeInfo::jsonb(), Data::Json(value.to_owned()))
+        }
+
+        pub fn inet(value: IpNetwork) -> Result<Value, String> {
+            check_prefix(&value)?;
+            Ok(Self::new(TypeInfo::inet(), Data::Inet(value)))
+        }
+
+        pub fn enum_value(value: &str, tyinfo: &TypeInfo) -> Value {
+            Self::new(tyinfo.clone(), Data::Text(value.to_owned()))
+        }
+
+        pub fn boolean_array(value: &[bool]) -> Value {
+            Self::new(
+                TypeInfo::boolean_array(),
+                Data::BooleanArray(value.to_vec()),
+            )
+        }
+
+        pub fn float4_array(value: &[f32]) -> Value {
+            Self::new(TypeInfo::float4_array(), Data::Float4Array(value.to_vec()))
+        }
+
+        pub fn float8_array(value: &[f64]) -> Value {
+            Self::new(TypeInfo::float8_array(), Data::Float8Array(value.to_vec()))
+        }
+
+        pub fn int1_array(value: &[i8]) -> Value {
+            Self::new(TypeInfo::int1_array(), Data::Int1Array(value.to_vec()))
+        }
+
+        pub fn int2_array(value: &[i16]) -> Value {
+            Self::new(TypeInfo::int2_array(), Data::Int2Array(value.to_vec()))
+        }
+
+        pub fn int4_array(value: &[i32]) -> Value {
+            Self::new(TypeInfo::int4_array(), Data::Int4Array(value.to_vec()))
+        }
+
+        pub fn int8_array(value: &[i64]) -> Value {
+            Self::new(TypeInfo::int8_array(), Data::Int8Array(value.to_vec()))
+        }
+
+        pub fn text_array(value: &[&str]) -> Value {
+            let value = value.iter().map(|&text| text.to_owned()).collect();
+            Self::new(TypeInfo::text_array(), Data::TextArray(value))
+        }
+
+        pub fn bytea_array(value: &[&[u8]]) -> Value {
+            let value = value.iter().map(|&bytes| bytes.to_vec()).collect();
+            Self::new(TypeInfo::bytea_array(), Data::ByteaArray(value))
+        }
+
+        pub fn timestamptz_array(value: &[Timestamptz]) -> Value {
+            Self::new(
+                TypeInfo::timestamptz_array(),
+                Data::TimestamptzArray(value.to_vec()),
+            )
+        }
+
+        pub fn timestamp_array(value: &[Timestamp]) -> Value {
+            Self::new(
+                TypeInfo::timestamp_array(),
+                Data::TimestampArray(value.to_vec()),
+            )
+        }
+
+        pub fn uuid_array(value: &[Uuid]) -> Value {
+            Self::new(TypeInfo::uuid_array(), Data::UuidArray(value.to_vec()))
+        }
+
+        pub fn jsonb_array(value: &[&str]) -> Value {
+            let value = value.iter().map(|&json| json.to_owned()).collect();
+            Self::new(TypeInfo::jsonb_array(), Data::JsonArray(value))
+        }
+
+        pub fn inet_array(value: &[IpNetwork]) -> Result<Value, String> {
+            value.iter().try_for_each(check_prefix)?;
+            Ok(Self::new(
+                TypeInfo::inet_array(),
+                Data::InetArray(value.to_vec()),
+            ))
+        }
+
+        pub fn enum_array(value: &[&str], tyinfo: &TypeInfo) -> Value {
+            let value = value.iter().map(|&text| text.to_owned()).collect();
+            Self::new(tyinfo.clone(), Data::TextArray(value))
+        }
+    }
+
+    fn check_prefix(network: &IpNetwork) -> Result<(), String> {
+        let (prefix, max) = match network {
+            IpNetwork::V4(network) => (network.prefix, 32),
+            IpNetwork::V6(network) => (network.prefix, 128),
+        };
+
+        if prefix > max {
+            return Err(format!("invalid network prefix {prefix}"));
+        }
+
+        Ok(())
+    }
+
+    #[derive(Debug, serde::Deserialize, serde::Serialize)]
+    pub struct Column {
+        pub name: String,
+        pub value: Value,
+    }
+
+    #[derive(Debug, serde::Deserialize, serde::Serialize)]
+    pub struct Row {
+        pub columns: Vec<Column>,
+    }
+
+    #[derive(Debug, serde::Deserialize, serde::Serialize)]
+    pub enum QueryResult {
+        Count(u64),
+        Row(Row),
+    }
+
+    #[repr(C)]
+    #[derive(Clone, Copy, Debug)]
+    pub struct Options {
+        pub limit: u8,
+        pub persistent: bool,
+    }
+
+    #[repr(C)]
+    #[derive(Clone, Copy, Debug)]
+    pub struct Options2 {
+        pub limit: u8,
+        pub persistent: bool,
+        pub collect_stats: bool,
+    }
+
+    #[repr(C)]
+    #[derive(Clone, Copy, Debug)]
+    pub struct QueryStats {
+        pub duration: u64,
+        pub execution_time: Option<u64>,
+        pub rows_scanned: Option<u64>,
+    }
+
+    #[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
+    pub struct ColumnDecodeError {
+        pub index: String,
+        pub source: String,
+    }
+
+    #[repr(u8)]
+    #[derive(Clone, Copy, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+    pub enum DatabaseErrorKind {
+        UniqueViolation,
+        ForeignKeyViolation,
+        NotNullViolation,
+        CheckViolation,
+        Other,
+    }
+
+    #[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
+    pub struct DatabaseError {
+        pub message: String,
+        pub kind: DatabaseErrorKind,
+        pub code: Option<String>,
+        pub constraint: Option<String>,
+        pub table: Option<String>,
+    }
+
+    #[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
+    pub enum Error {
+        ColumnDecode(ColumnDecodeError),
+        TypeNotFound(String),
+        Encode(String),
+        Decode(String),
+        Database(DatabaseError),
+        Other(String),
+    }
+
+    impl std::fmt::Display for Error {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "{:?}", self)
+        }
+    }
+
+    impl std::error::Error for Error {}
+
+    #[derive(Debug)]
+    pub struct ColumnDescription {
+        pub name: String,
+        pub type_info: TypeInfo,
+        pub nullable: Option<bool>,
+    }
+
+    #[derive(Debug)]
+    pub struct StatementDescription {
+        pub parameters: Vec<TypeInfo>,
+        pub columns: Vec<ColumnDescription>,
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct PgNotification {
+        pub channel: String,
+        pub payload: String,
+    }
+
+    #[derive(Debug)]
+    pub struct BatchStatement {
+        pub sql: String,
+        pub params: Vec<Value>,
+    }
+
+    fn wrap(params: Vec<Value>) -> Vec<crate::driver::Value> {
+        params.into_iter().map(crate::driver::Value::new).collect()
+    }
+
+    fn to_columns(row: MockRow) -> Row {
+        let columns = row
+            .0
+            .into_iter()
+            .map(|(name, value)| Column {
+                name,
+                value: value.0,
+            })
+            .collect();
+
+        Row { columns }
+    }
+
+    /// Like the runtime, except that results come from the responses
+    /// registered via [`respond`].
+    pub fn query(sql: &str, params: Vec<Value>, options: Options) {
+        let options = Options2 {
+            limit: options.limit,
+            persistent: options.persistent,
+            collect_stats: false,
+        };
+
+        query2(sql, params, options)
+    }
+
+    pub fn query2(sql: &str, params: Vec<Value>, options: Options2) {
+        assert_in_transaction("durable::sql::query");
+
+        with_state(|state| {
+            let result = run(state, sql, wrap(params));
+
+            state.pending.clear();
+            state.stats = None;
+            state.collect_stats = options.collect_stats;
+
+            let (rows, count) = match result.0 {
+                Ok(result) => result,
+                Err(e) => {
+                    state.pending.push_back(Err(e));
+                    return;
+                }
+            };
+
+            let rows = rows
+                .into_iter()
+                .map(|row| Ok(QueryResult::Row(to_columns(row))));
+            match options.limit {
+                0 => state.pending.push_back(Ok(QueryResult::Count(count))),
+                1 => {
+                    let row: Vec<_> = rows.take(1).collect();
+                    let count = row.len() as u64;
+
+                    state.pending.push_back(Ok(QueryResult::Count(count)));
+                    state.pending.extend(row);
+                }
+                _ => {
+                    state.pending.extend(rows);
+                    state.pending.push_back(Ok(QueryResult::Count(count)));
+                }
+            }
+        })
+    }
+
+    pub fn fetch() -> Option<Result<QueryResult, Error>> {
+        assert_in_transaction("durable::sql::fetch");
+
+        with_state(|state| {
+            let result = state.pending.pop_front();
+            if result.is_none() && state.collect_stats {
+                state.collect_stats = false;
+                state.stats = Some(QueryStats {
+                    duration: 0,
+                    execution_time: None,
+                    rows_scanned: None,
+                });
+            }
+
+            result
+        })
+    }
+
+    /// Like the runtime, except that the statistics are all zero.
+    pub fn last_query_stats() -> Option<QueryStats> {
+        with_state(|state| state.stats)
+    }
+
+    pub fn batch(statements: &[BatchStatement]) {
+        assert_in_transaction("durable::sql::batch");
+
+        with_state(|state| {
+            state.pending.clear();
+            state.stats = None;
+            state.collect_stats = false;
+
+            for statement in statements {
+                let params = statement.params.iter().map(Value::clone).collect();
+
+                match run(state, &statement.sql, wrap(params)).0 {
+                    Ok((_, count)) => state.pending.push_back(Ok(QueryResult::Count(count))),
+                    Err(e) => {
+                        state.pending.push_back(Err(e));
+                        break;
+                    }
+                }
+            }
+        })
+    }
+
+    pub fn savepoint() -> Result<(), Error> {
+        assert_in_transaction("durable::sql::savepoint");
+
+        with_state(|state| state.savepoints += 1);
+        Ok(())
+    }
+
+    pub fn release_savepoint() -> Result<(), Error> {
+        assert_in_transaction("durable::sql::release_savepoint");
+
+        with_state(|state| match state.savepoints.checked_sub(1) {
+            Some(savepoints) => {
+                state.savepoints = savepoints;
+                Ok(())
+            }
+            None => Err(Error::Other("there is no active savepoint".into())),
+        })
+    }
+
+    /// Like the runtime, except that nothing is actually rolled back.
+    pub fn rollback_savepoint() -> Result<(), Error> {
+        release_savepoint()
+    }
+
+    /// Like the runtime, except that the number of rows copied is taken from
+    /// the response registered for `statement`.
+    pub fn copy_in_start(statement: &str) -> Result<(), Error> {
+        assert_in_transaction("durable::sql::copy_in_start");
+
+        with_state(|state| {
+            let (_, count) = run(state, statement, Vec::new()).0?;
+            let index = state.queries.len() - 1;
+
+            state.queries[index].copy_data = Some(Vec::new());
+            state.copy = Some((index, count));
+            Ok(())
+        })
+    }
+
+    pub fn copy_in_send(data: &[u8]) -> Result<(), Error> {
+        with_state(|state| {
+            let Some((index, _)) = state.copy else {
+                return Err(Error::Other("no COPY operation is in progress".into()));
+            };
+
+            if let Some(buffer) = &mut state.queries[index].copy_data {
+                buffer.extend_from_slice(data);
+            }
+
+            Ok(())
+        })
+    }
+
+    pub fn copy_in_finish() -> Result<u64, Error> {
+        with_state(|state| match state.copy.take() {
+            Some((_, count)) => Ok(count),
+            None => Err(Error::Other("no COPY operation is in progress".into())),
+        })
+    }
+
+    pub fn copy_in_abort(_message: &str) -> Result<(), Error> {
+        with_state(|state| match state.copy.take() {
+            Some(_) => Ok(()),
+            None => Err(Error::Other("no COPY operation is in progress".into())),
+        })
+    }
+
+    pub fn describe(_sql: &str) -> Result<StatementDescription, Error> {
+        Err(Error::Other(
+            "describing statements is not supported by the mock runtime".into(),
+        ))
+    }
+
+    pub fn listen(channel: &str) -> PgNotification {
+        if durable_core::transaction::in_transaction() {
+            panic!("durable::sql::listen called from within a transaction");
+        }
+
+        with_state(|state| {
+            let position = state
+                .notifications
+                .iter()
+                .position(|notification| notification.channel == channel);
+
+            match position {
+                Some(index) => state.notifications.remove(index),
+                None => None,
+            }
+        })
+        .unwrap_or_else(|| {
+            panic!(
+                "the workflow is listening on channel {channel:?} but no notifications have \
+                 been queued with durable_sqlx::mock::push_pg_notification"
+            )
+        })
+    }
+
+    /// The mock does not cache statements so this does nothing.
+    pub fn clear_statement_cache() {}
+
+    pub fn explain(_sql: &str, _params: Vec<Value>) -> Result<String, Error> {
+        Err(Error::Other(
+            "explaining statements is not supported by the mock runtime".into(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn canned_results() {
+        reset();
+        durable_core::mock::reset();
+
+        respond(
+            "SELECT id, name FROM users WHERE id = $1",
+            MockResult::rows([MockRow::new().column("id", 5i64).column("name", "alice")]),
+        );
+        respond(
+            "INSERT INTO users(name) VALUES ($1)",
+            MockResult::unique_violation("users_name_key"),
+        );
+
+        let (id, name): (i64, String) = crate::transaction("load the user", |mut conn| {
+            crate::query_as("SELECT id, name\n  FROM users WHERE id = $1")
+                .bind(5i64)
+                .fetch_one(&mut conn)
+        })
+        .unwrap();
+        assert_eq!(id, 5);
+        assert_eq!(name, "alice");
+
+        let error = crate::transaction("insert the user", |mut conn| {
+            crate::query("INSERT INTO users(name) VALUES ($1)")
+                .bind("alice")
+                .execute(&mut conn)
+        })
+        .unwrap_err();
+        assert!(matches!(
+            error,
+            crate::Error::Database(e) if e.kind() == sqlx::error::ErrorKind::UniqueViolation
+        ));
+
+        let queries = queries();
+        assert_eq!(queries.len(), 2);
+        assert_eq!(queries[0].param::<i64>(0), 5);
+        assert_eq!(queries[1].param::<String>(0), "alice");
+
+        assert_eq!(durable_core::mock::events().len(), 2);
+    }
+
+    #[test]
+    fn unregistered_query() {
+        reset();
+        durable_core::mock::reset();
+
+        let result = crate::transaction("delete the users", |mut conn| {
+            crate::query("DELETE FROM users").execute(&mut conn)
+        });
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn batch() {
+        reset();
+        durable_core::mock::reset();
+
+        respond("UPDATE users SET visits = 0", MockResult::affected(3));
+        respond("DELETE FROM sessions", MockResult::affected(7));
+
+        let counts = crate::transaction("reset the users", |mut conn| {
+            let results = conn.batch(|batch| {
+                batch.push(crate::query("UPDATE users SET visits = 0"));
+                batch.push(crate::query("DELETE FROM sessions"));
+            })?;
+
+            Ok::<_, crate::Error>(
+                results
+                    .iter()
+                    .map(|r| r.rows_affected())
+                    .collect::<Vec<_>>(),
+            )
+        })
+        .unwrap();
+
+        assert_eq!(counts, [3, 7]);
+    }
+}
@@ -0,0 +1,203 @@
+use std::fmt::Display;
+
+use crate::driver::{self, Durable, Row};
+use crate::{Query, QueryAs, QueryScalar};
+
+/// A builder type for constructing queries at runtime.
+///
+/// This is a thin wrapper around [`sqlx::QueryBuilder`]. See the docs there for
+/// more details.
+///
+/// ```no_run
+/// # fn example(mut conn: durable::sqlx::Connection) -> durable::sqlx::Result<()> {
+/// use durable::sqlx::QueryBuilder;
+///
+/// let names = ["alice", "bob", "carol"];
+///
+/// let mut builder = QueryBuilder::new("INSERT INTO users(name) ");
+/// builder.push_values(names, |mut b, name| {
+///     b.push_bind(name);
+/// });
+///
+/// builder.build().execute(&mut conn)?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Default)]
+pub struct QueryBuilder<'args>(sqlx::QueryBuilder<'args, Durable>);
+
+impl<'args> QueryBuilder<'args> {
+    /// Start building a query with an initial SQL fragment, which may be an
+    /// empty string.
+    pub fn new(init: impl Into<String>) -> Self {
+        Self(sqlx::QueryBuilder::new(init))
+    }
+
+    /// Construct a `QueryBuilder` with existing SQL and arguments.
+    ///
+    /// ### Note
+    /// This does *not* check if `arguments` is valid for the given SQL.
+    pub fn with_arguments<A>(init: impl Into<String>, arguments: A) -> Self
+    where
+        A: sqlx::IntoArguments<'args, Durable>,
+    {
+        Self(sqlx::QueryBuilder::with_arguments(init, arguments))
+    }
+
+    /// Append a SQL fragment to the query.
+    ///
+    /// The fragment is pushed as-is. **Never** push untrusted input here, use
+    /// [`push_bind`](Self::push_bind) instead.
+    pub fn push(&mut self, sql: impl Display) -> &mut Self {
+        self.0.push(sql);
+        self
+    }
+
+    /// Push a bind argument placeholder (`$N`) and bind a value to it.
+    ///
+    /// The value is sent separately from the query, so this is safe to use
+    /// with untrusted input.
+    pub fn push_bind<T>(&mut self, value: T) -> &mut Self
+    where
+        T: 'args + sqlx::Encode<'args, Durable> + sqlx::Type<Durable>,
+    {
+        self.0.push_bind(value);
+        self
+    }
+
+    /// Start a list separated by `separator`.
+    ///
+    /// See [`sqlx::QueryBuilder::separated`] for details.
+    pub fn separated<'qb, Sep>(&'qb mut self, separator: Sep) -> Separated<'qb, 'args, Sep>
+    where
+        'args: 'qb,
+        Sep: Display,
+    {
+        Separated(self.0.separated(separator))
+    }
+
+    /// Push a `VALUES` clause where each item in `tuples` represents a row in
+    /// the clause.
+    ///
+    /// See [`sqlx::QueryBuilder::push_values`] for details.
+    pub fn push_values<I, F>(&mut self, tuples: I, mut push_tuple: F) -> &mut Self
+    where
+        I: IntoIterator,
+        F: FnMut(Separated<'_, 'args, &'static str>, I::Item),
+    {
+        self.0.push_values(tuples, |separated, item| {
+            push_tuple(Separated(separated), item)
+        });
+        self
+    }
+
+    /// Push a list of tuples, e.g. for use in a `WHERE (a, b) IN (...)`
+    /// clause.
+    ///
+    /// See [`sqlx::QueryBuilder::push_tuples`] for details.
+    pub fn push_tuples<I, F>(&mut self, tuples: I, mut push_tuple: F) -> &mut Self
+    where
+        I: IntoIterator,
+        F: FnMut(Separated<'_, 'args, &'static str>, I::Item),
+    {
+        self.0.push_tuples(tuples, |separated, item| {
+            push_tuple(Separated(separated), item)
+        });
+        self
+    }
+
+    /// Produce an executable query from this builder.
+    ///
+    /// Calling any method other than [`reset`](Self::reset) after this will
+    /// panic.
+    pub fn build(&mut self) -> Query<'_, driver::Arguments> {
+        Query(self.0.build())
+    }
+
+    /// Produce an executable query from this builder that maps each row to
+    /// `O`.
+    ///
+    /// Calling any method other than [`reset`](Self::reset) after this will
+    /// panic.
+    pub fn build_query_as<'q, O>(&'q mut self) -> QueryAs<'q, O, driver::Arguments>
+    where
+        O: sqlx::FromRow<'q, Row>,
+    {
+        QueryAs(self.0.build_query_as())
+    }
+
+    /// Produce an executable query from this builder that extracts the first
+    /// column of each row.
+    ///
+    /// Calling any method other than [`reset`](Self::reset) after this will
+    /// panic.
+    pub fn build_query_scalar<'q, O>(&'q mut self) -> QueryScalar<'q, O, driver::Arguments>
+    where
+        (O,): for<'r> sqlx::FromRow<'r, Row>,
+    {
+        QueryScalar(self.0.build_query_scalar())
+    }
+
+    /// Reset this builder back to the state it was in immediately after
+    /// [`new`](Self::new).
+    pub fn reset(&mut self) -> &mut Self {
+        self.0.reset();
+        self
+    }
+
+    /// Get the SQL that has been built so far.
+    ///
+    /// This may not be syntactically correct.
+    pub fn sql(&self) -> &str {
+        self.0.sql()
+    }
+
+    /// Deconstruct this builder, returning the built SQL.
+    pub fn into_sql(self) -> String {
+        self.0.into_sql()
+    }
+}
+
+/// A wrapper around [`QueryBuilder`] for creating separated lists.
+///
+/// Returned by [`QueryBuilder::separated`].
+pub struct Separated<'qb, 'args: 'qb, Sep>(
+    sqlx::query_builder::Separated<'qb, 'args, Durable, Sep>,
+);
+
+impl<'args, Sep> Separated<'_, 'args, Sep>
+where
+    Sep: Display,
+{
+    /// Push the separator if this is not the first element, and then push
+    /// `sql`.
+    pub fn push(&mut self, sql: impl Display) -> &mut Self {
+        self.0.push(sql);
+        self
+    }
+
+    /// Push `sql` without a separator.
+    pub fn push_unseparated(&mut self, sql: impl Display) -> &mut Self {
+        self.0.push_unseparated(sql);
+        self
+    }
+
+    /// Push the separator if this is not the first element, and then push a
+    /// bind argument placeholder for `value`.
+    pub fn push_bind<T>(&mut self, value: T) -> &mut Self
+    where
+        T: 'args + sqlx::Encode<'args, Durable> + sqlx::Type<Durable>,
+    {
+        self.0.push_bind(value);
+        self
+    }
+
+    /// Push a bind argument placeholder for `value` without a separator.
+    pub fn push_bind_unseparated<T>(&mut self, value: T) -> &mut Self
+    where
+        T: 'args + sqlx::Encode<'args, Durable> + sqlx::Type<Durable>,
+    {
+        self.0.push_bind_unseparated(value);
+        self
+    }
+}
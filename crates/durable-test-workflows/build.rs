@@ -0,0 +1,3 @@
+fn main() -> durable_bindgen::Result<()> {
+    durable_bindgen::build("test:counter/imports")
+}
@@ -0,0 +1,18 @@
+use std::time::Duration;
+
+fn main() {
+    let approval = durable::approval::wait("never", Duration::from_secs(1));
+    println!("never: {}", approval.is_none());
+
+    let approval = durable::approval::wait("deploy", Duration::from_secs(3600))
+        .expect("deploy was not approved");
+    println!(
+        "{} by {}: {}",
+        approval.key(),
+        approval.approved_by().unwrap_or("nobody"),
+        approval.raw_payload()
+    );
+
+    let notification = durable::notify::wait();
+    println!("notification: {}", notification.event);
+}
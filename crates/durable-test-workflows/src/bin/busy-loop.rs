@@ -0,0 +1,7 @@
+fn main() {
+    // Spin forever without ever calling into the runtime.
+    let mut count = 0u64;
+    loop {
+        count = std::hint::black_box(count.wrapping_add(1));
+    }
+}
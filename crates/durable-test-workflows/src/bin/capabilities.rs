@@ -0,0 +1,12 @@
+fn main() {
+    let capabilities = durable::capabilities();
+
+    for name in ["durable:core/core", "durable:core/object-store"] {
+        println!("{name}: {}", capabilities.has_interface(name));
+    }
+
+    println!(
+        "max_http_timeout: {:?}",
+        capabilities.limits().max_http_timeout
+    );
+}
@@ -0,0 +1,10 @@
+use durable::transaction;
+
+fn main() {
+    transaction("expensive", || println!("doing some expensive work"));
+
+    // Keep going until the worker hands the task off to someone else.
+    loop {
+        durable::checkpoint();
+    }
+}
@@ -0,0 +1,29 @@
+use durable::email::{Attachment, Error, Template};
+
+#[derive(serde::Serialize)]
+struct Context<'a> {
+    name: &'a str,
+}
+
+fn main() {
+    let template = Template::new("Welcome, {{ name }}!")
+        .text("Hi {{ name }}, your report is attached.")
+        .html("<p>Hi {{ name }}, your report is attached.</p>");
+
+    let email = template
+        .render(&Context {
+            name: "Alice & Bob",
+        })
+        .expect("failed to render the template")
+        .to("alice@example.com")
+        .bcc("audit@example.com")
+        .attach(Attachment::new("report.csv", "text/csv", "a,b\n1,2\n"));
+
+    // The email is sent in its own transaction, so it is only sent once even
+    // though the task is still running.
+    match email.send() {
+        Ok(id) => println!("sent: {id}"),
+        Err(Error::NotConfigured) => println!("error: not-configured"),
+        Err(e) => println!("error: {e}"),
+    }
+}
@@ -0,0 +1,20 @@
+use durable::transaction;
+
+fn sync_orders() {
+    transaction("sync-orders", || ());
+}
+
+fn sync_invoices() -> anyhow::Result<()> {
+    transaction("sync-invoices", || ());
+
+    anyhow::bail!("failed to sync invoices")
+}
+
+durable::entrypoints! {
+    "sync-orders" => sync_orders,
+    "sync-invoices" => sync_invoices,
+}
+
+fn main() {
+    transaction("main", || ());
+}
@@ -0,0 +1,19 @@
+use durable::{notify, sqlx};
+
+fn main() -> anyhow::Result<()> {
+    let task = durable::task();
+    let steps: i32 = task.data();
+
+    for step in 0..steps {
+        sqlx::transaction(&format!("step {step}"), |mut conn| {
+            sqlx::query("INSERT INTO exactly_once(task_id, step) VALUES ($1, $2)")
+                .bind(task.id())
+                .bind(step)
+                .execute(&mut conn)
+        })?;
+
+        notify::wait();
+    }
+
+    Ok(())
+}
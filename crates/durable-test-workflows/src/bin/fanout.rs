@@ -0,0 +1,45 @@
+use durable::fanout::{self, Fanout};
+
+fn square() {
+    fanout::child(|x: u64| x * x);
+}
+
+fn checked() {
+    fanout::child(|x: u64| {
+        if x % 2 == 1 {
+            panic!("odd item {x}");
+        }
+
+        x
+    });
+}
+
+durable::entrypoints! {
+    "square" => square,
+    "checked" => checked,
+}
+
+fn main() {
+    let task = durable::task();
+
+    // This should be kept aside while waiting for the child tasks.
+    durable::notify::notify(task.id(), "before", &()).unwrap();
+
+    let items: Vec<u64> = (0..10).collect();
+    let squares: Vec<u64> = Fanout::new("square").batch_size(3).run(&items).unwrap();
+    println!("{squares:?}");
+
+    let results: Vec<Result<u64, _>> = Fanout::new("checked").run_collect(&[2u64, 3, 4]);
+    for result in results {
+        match result {
+            Ok(value) => println!("ok {value}"),
+            Err(_) => println!("failed"),
+        }
+    }
+
+    let result = fanout::map::<u64, u64>("checked", &[1, 2]);
+    println!("fail-fast: {}", result.is_err());
+
+    let notification = durable::notify::wait();
+    println!("notification: {}", notification.event);
+}
@@ -0,0 +1,23 @@
+use std::fs;
+
+fn main() {
+    let input = fs::read_to_string("/data/input.txt").expect("failed to read input file");
+    fs::write("/scratch/output.txt", input.to_uppercase()).expect("failed to write output file");
+
+    assert!(
+        fs::write("/data/input.txt", "overwritten").is_err(),
+        "wrote to a read-only directory"
+    );
+    assert!(
+        fs::read_to_string("/data/../etc/passwd").is_err(),
+        "escaped the preopened directory"
+    );
+
+    let mut entries: Vec<_> = fs::read_dir("/scratch")
+        .expect("failed to read scratch directory")
+        .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+        .collect();
+    entries.sort();
+
+    println!("{}", entries.join(","));
+}
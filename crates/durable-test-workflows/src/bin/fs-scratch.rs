@@ -0,0 +1,19 @@
+use std::fs;
+use std::time::Duration;
+
+use durable::transaction;
+
+fn main() {
+    fs::write("/scratch/outside.txt", "outside").expect("failed to write outside a transaction");
+    transaction("write-inside", || {
+        fs::write("/scratch/inside.txt", "inside").expect("failed to write inside a transaction")
+    });
+
+    // Sleep for long enough that the task gets suspended. Once it resumes, the
+    // file written within the transaction has to come from the snapshot.
+    std::thread::sleep(Duration::from_secs(5));
+
+    let outside = fs::read_to_string("/scratch/outside.txt").unwrap();
+    let inside = fs::read_to_string("/scratch/inside.txt").unwrap();
+    println!("{outside} {inside}");
+}
@@ -0,0 +1,17 @@
+mod bindings {
+    #![allow(clippy::all)]
+
+    include!(concat!(env!("OUT_DIR"), "/bindings.rs"));
+}
+
+use self::bindings::test::counter::counter;
+
+fn main() {
+    let first = durable::host_call!(counter::hit(1));
+    let second = durable::host_call!(counter::hit(2));
+
+    // Both calls are saved as events so these stay the same even if the task
+    // gets restarted.
+    assert_eq!(first, 1);
+    assert_eq!(second, 3);
+}
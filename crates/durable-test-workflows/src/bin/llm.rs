@@ -0,0 +1,26 @@
+use durable::llm::{ChatRequest, Client, Error};
+
+fn main() {
+    let client = match Client::new() {
+        Ok(client) => client,
+        Err(Error::NotConfigured(name)) => {
+            println!("error: not-configured {name}");
+            return;
+        }
+        Err(e) => panic!("{e}"),
+    };
+
+    let request = ChatRequest::new()
+        .system("You are a helpful assistant.")
+        .user("Say hello.");
+
+    let response = client.chat(&request).expect("chat request failed");
+    println!("chat: {}", response.content().unwrap_or_default());
+
+    let mut streamed = String::new();
+    for chunk in client.chat_stream(&request).expect("failed to open stream") {
+        let chunk = chunk.expect("failed to read chunk");
+        streamed.push_str(chunk.content().unwrap_or_default());
+    }
+    println!("stream: {streamed}");
+}
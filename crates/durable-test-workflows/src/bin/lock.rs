@@ -0,0 +1,38 @@
+use std::time::Duration;
+
+use durable::lock::Semaphore;
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+struct Data {
+    permits: u32,
+    #[serde(default)]
+    ttl_ms: Option<u64>,
+
+    /// Wait for a notification before releasing the lock.
+    #[serde(default)]
+    hold: bool,
+
+    /// Leave the lock to be released when the task completes.
+    #[serde(default)]
+    leak: bool,
+}
+
+fn main() {
+    let data: Data = durable::task().data();
+
+    let semaphore = Semaphore::new("resource", data.permits);
+    let guard = semaphore.acquire(data.ttl_ms.map(Duration::from_millis));
+    println!("acquired");
+
+    if data.hold {
+        let notification = durable::notify::wait();
+        println!("{}", notification.event);
+    }
+
+    if data.leak {
+        std::mem::forget(guard);
+    } else {
+        guard.release();
+    }
+}
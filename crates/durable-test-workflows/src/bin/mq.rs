@@ -0,0 +1,11 @@
+use durable::mq::Error;
+
+fn main() {
+    let result = durable::mq::publish("orders.created", b"order 1234", &[("source", b"test")]);
+
+    match result {
+        Ok(()) => println!("published"),
+        Err(Error::NotConfigured) => println!("error: not-configured"),
+        Err(e) => println!("error: {e}"),
+    }
+}
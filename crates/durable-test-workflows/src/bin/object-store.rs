@@ -0,0 +1,28 @@
+use std::time::Duration;
+
+use durable::object_store::{Bucket, Error};
+
+fn describe(error: &Error) -> &'static str {
+    match error {
+        Error::NotConfigured => "not-configured",
+        Error::NotFound => "not-found",
+        Error::TooLarge { .. } => "too-large",
+        Error::Service { .. } => "service",
+        Error::Request(_) => "request",
+        _ => "other",
+    }
+}
+
+fn main() {
+    let bucket = Bucket::new("test");
+
+    match bucket.get("missing.txt") {
+        Ok(_) => println!("get: ok"),
+        Err(e) => println!("get: {}", describe(&e)),
+    }
+
+    match bucket.presign_get("report.csv", Duration::from_secs(60)) {
+        Ok(url) => println!("presign: {url}"),
+        Err(e) => println!("presign: {}", describe(&e)),
+    }
+}
@@ -0,0 +1,6 @@
+use durable::transaction;
+
+fn main() {
+    transaction("before", || ());
+    transaction("panics", || panic!("something went wrong"));
+}
@@ -0,0 +1,4 @@
+fn main() {
+    println!("before the panic");
+    panic!("something went wrong");
+}
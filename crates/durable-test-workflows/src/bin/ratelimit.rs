@@ -0,0 +1,6 @@
+fn main() {
+    let name: String = durable::task().data();
+
+    durable::ratelimit::acquire(&name);
+    println!("acquired {name}");
+}
@@ -0,0 +1,32 @@
+use durable::Error;
+
+fn main() {
+    let result = durable::saga::run("trip", |saga| {
+        let flight = saga.step(
+            "book-flight",
+            || Ok("FL123".to_owned()),
+            |flight: String| {
+                println!("cancel flight {flight}");
+                Ok(())
+            },
+        )?;
+        saga.step(
+            "book-hotel",
+            || Ok(7),
+            |hotel: u32| {
+                println!("cancel hotel {hotel}");
+                Err(Error::msg("hotel cancellation failed"))
+            },
+        )?;
+        saga.step(
+            "book-car",
+            || Err::<(), _>(Error::msg("no cars available")),
+            |_| Ok(()),
+        )?;
+
+        Ok(flight)
+    });
+
+    let error = result.unwrap_err();
+    println!("{error}");
+}
@@ -0,0 +1,17 @@
+use serde::Serialize;
+
+#[derive(Serialize)]
+struct Step {
+    name: &'static str,
+    of: u32,
+}
+
+fn main() {
+    durable::metadata::set("order", "ord_1234");
+    durable::metadata::set("step", &Step { name: "charge", of: 3 });
+
+    durable::transaction("finish", || {
+        durable::metadata::remove("order");
+        durable::metadata::set("done", &true);
+    });
+}
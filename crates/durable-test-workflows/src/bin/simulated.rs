@@ -0,0 +1,23 @@
+use std::time::{Duration, SystemTime};
+
+use durable::{http, notify};
+
+fn main() {
+    let start = SystemTime::now();
+    let notification = notify::wait_timeout(Duration::from_secs(3600));
+    let waited = SystemTime::now()
+        .duration_since(start)
+        .expect("time went backwards");
+
+    let response = http::get("http://simulated.invalid/status")
+        .send()
+        .expect("failed to send the request");
+
+    println!("notified: {}", notification.is_some());
+    println!("waited: {}s", waited.as_secs());
+    println!(
+        "response: {} {}",
+        response.status().as_u16(),
+        response.text().expect("response was not utf-8")
+    );
+}
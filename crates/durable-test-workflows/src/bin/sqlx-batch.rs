@@ -0,0 +1,47 @@
+use durable::sqlx;
+
+fn main() -> anyhow::Result<()> {
+    sqlx::transaction("set up the database schema", |mut conn| {
+        sqlx::query("CREATE TABLE batch_test(id bigint PRIMARY KEY, name text NOT NULL)")
+            .execute(&mut conn)
+    })?;
+
+    let results = sqlx::transaction("insert rows in a batch", |mut conn| {
+        conn.batch(|batch| {
+            for (id, name) in [(1i64, "alice"), (2, "bob"), (3, "carol")] {
+                batch.push(
+                    sqlx::query("INSERT INTO batch_test(id, name) VALUES ($1, $2)")
+                        .bind(id)
+                        .bind(name),
+                );
+            }
+
+            batch.push(sqlx::query("UPDATE batch_test SET name = upper(name)"));
+        })
+    })?;
+
+    let counts: Vec<u64> = results.iter().map(|r| r.rows_affected()).collect();
+    assert_eq!(counts, [1, 1, 1, 3]);
+
+    // A failing statement stops the rest of the batch.
+    let result = sqlx::transaction("insert a duplicate row in a batch", |mut conn| {
+        conn.batch(|batch| {
+            batch.push(sqlx::query(
+                "INSERT INTO batch_test(id, name) VALUES (1, 'dave')",
+            ));
+            batch.push(sqlx::query(
+                "INSERT INTO batch_test(id, name) VALUES (4, 'erin')",
+            ));
+        })
+    });
+
+    assert!(result.is_err());
+
+    let names: Vec<String> = sqlx::transaction("read back the rows", |mut conn| {
+        sqlx::query_scalar("SELECT name FROM batch_test ORDER BY id").fetch_all(&mut conn)
+    })?;
+
+    assert_eq!(names, ["ALICE", "BOB", "CAROL"]);
+
+    Ok(())
+}
@@ -0,0 +1,36 @@
+use durable::sqlx;
+
+fn main() -> anyhow::Result<()> {
+    sqlx::transaction("set up the database schema", |mut conn| {
+        sqlx::query("CREATE TABLE copy_test(id bigint PRIMARY KEY, name text NOT NULL)")
+            .execute(&mut conn)
+    })?;
+
+    let rows = sqlx::transaction("copy rows into the table", |mut conn| {
+        let mut copy = conn.copy_in("copy_test", &["id", "name"])?;
+        copy.send(b"1\talice\n2\tbob\n")?;
+        // Chunks don't need to line up with rows.
+        copy.send(b"3\tca")?;
+        copy.send(b"rol\n")?;
+        copy.finish()
+    })?;
+
+    assert_eq!(rows, 3);
+
+    // Dropping the copy without finishing it discards everything that was sent.
+    sqlx::transaction("abort a copy", |mut conn| {
+        let mut copy = conn.copy_in("copy_test", &["id", "name"])?;
+        copy.send(b"4\tdave\n")?;
+        drop(copy);
+
+        Ok::<_, sqlx::Error>(())
+    })?;
+
+    let names: Vec<String> = sqlx::transaction("read back the rows", |mut conn| {
+        sqlx::query_scalar("SELECT name FROM copy_test ORDER BY id").fetch_all(&mut conn)
+    })?;
+
+    assert_eq!(names, ["alice", "bob", "carol"]);
+
+    Ok(())
+}
@@ -0,0 +1,49 @@
+use ::sqlx::{Column, Either, TypeInfo};
+use durable::sqlx;
+
+fn main() -> anyhow::Result<()> {
+    sqlx::transaction("set up the database schema", |mut conn| {
+        sqlx::query("CREATE TABLE describe_test(id bigint PRIMARY KEY, name text)")
+            .execute(&mut conn)
+    })?;
+
+    let (params, columns) = sqlx::transaction("describe a query", |mut conn| {
+        let describe = conn.describe("SELECT id, name FROM describe_test WHERE id = $1")?;
+
+        let params: Vec<String> = match describe.parameters() {
+            Some(Either::Left(params)) => params.iter().map(|p| p.name().to_owned()).collect(),
+            _ => Vec::new(),
+        };
+        let columns: Vec<(String, String, Option<bool>)> = describe
+            .columns()
+            .iter()
+            .enumerate()
+            .map(|(idx, column)| {
+                (
+                    column.name().to_owned(),
+                    column.type_info().name().to_owned(),
+                    describe.nullable(idx),
+                )
+            })
+            .collect();
+
+        Ok::<_, sqlx::Error>((params, columns))
+    })?;
+
+    assert_eq!(params, ["INT8"]);
+    assert_eq!(
+        columns,
+        [
+            ("id".to_owned(), "INT8".to_owned(), Some(false)),
+            ("name".to_owned(), "TEXT".to_owned(), Some(true)),
+        ]
+    );
+
+    let failed = sqlx::transaction("describe an invalid query", |mut conn| {
+        conn.describe("SELECT * FROM does_not_exist").is_err()
+    });
+
+    assert!(failed);
+
+    Ok(())
+}
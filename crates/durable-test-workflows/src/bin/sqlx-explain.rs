@@ -0,0 +1,27 @@
+use durable::sqlx;
+
+fn main() -> anyhow::Result<()> {
+    sqlx::transaction("set up the database schema", |mut conn| {
+        sqlx::query("CREATE TABLE explain_test(id bigint PRIMARY KEY, name text)")
+            .execute(&mut conn)
+    })?;
+
+    let plan =
+        sqlx::explain(sqlx::query("SELECT name FROM explain_test WHERE id = $1").bind(7i64))?;
+
+    assert!(plan.node_type().is_some());
+    assert!(plan.total_cost().is_some());
+    assert!(plan.estimated_rows().is_some());
+
+    let plan = sqlx::transaction("explain within a transaction", |mut conn| {
+        conn.explain(sqlx::query("SELECT count(*) FROM explain_test"))
+    })?;
+
+    assert_eq!(plan.node_type(), Some("Aggregate"));
+
+    let result = sqlx::explain(sqlx::query("SELECT * FROM does_not_exist"));
+
+    assert!(result.is_err());
+
+    Ok(())
+}
@@ -0,0 +1,6 @@
+fn main() {
+    let notification = durable::sqlx::listen("durable_test_listen");
+
+    assert_eq!(notification.channel, "durable_test_listen");
+    assert_eq!(notification.payload, "hello");
+}
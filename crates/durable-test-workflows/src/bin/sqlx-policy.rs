@@ -0,0 +1,116 @@
+use durable::sqlx;
+
+/// Whether the statement was rejected by the SQL policy on the worker.
+fn is_denied<T>(result: Result<T, sqlx::Error>) -> bool {
+    match result {
+        Err(sqlx::Error::Database(e)) => e.code().as_deref() == Some("42501"),
+        _ => false,
+    }
+}
+
+fn main() -> anyhow::Result<()> {
+    // The worker only allows this program to access the policy_allowed table.
+    let count: i64 = sqlx::transaction("read an allowed table", |mut conn| {
+        sqlx::query_scalar("SELECT count(*) FROM policy_allowed").fetch_one(&mut conn)
+    })?;
+
+    assert_eq!(count, 0);
+
+    let denied = sqlx::transaction("read a denied table", |mut conn| {
+        is_denied(
+            sqlx::query_scalar::<i64>("SELECT count(*) FROM policy_denied").fetch_one(&mut conn),
+        )
+    });
+
+    assert!(denied);
+
+    let denied = sqlx::transaction("join against a denied table", |mut conn| {
+        is_denied(
+            sqlx::query(
+                "INSERT INTO policy_allowed(id) SELECT id FROM policy_denied WHERE id > $1",
+            )
+            .bind(0i64)
+            .execute(&mut conn),
+        )
+    });
+
+    assert!(denied);
+
+    let denied = sqlx::transaction("read a denied table via a function", |mut conn| {
+        is_denied(
+            sqlx::query_scalar::<String>(
+                "SELECT query_to_xml('SELECT * FROM policy_denied', true, true, '')::text",
+            )
+            .fetch_one(&mut conn),
+        )
+    });
+
+    assert!(denied);
+
+    let denied = sqlx::transaction("create a table", |mut conn| {
+        is_denied(sqlx::query("CREATE TABLE policy_other(id bigint)").execute(&mut conn))
+    });
+
+    assert!(denied);
+
+    let denied = sqlx::transaction("create a table from a query", |mut conn| {
+        is_denied(
+            sqlx::query("CREATE TABLE policy_ctas AS SELECT id FROM policy_allowed")
+                .execute(&mut conn),
+        )
+    });
+
+    assert!(denied);
+
+    let denied = sqlx::transaction("select into a new table", |mut conn| {
+        is_denied(
+            sqlx::query("SELECT id INTO policy_select_into FROM policy_allowed").execute(&mut conn),
+        )
+    });
+
+    assert!(denied);
+
+    let denied = sqlx::transaction("describe a denied table", |mut conn| {
+        is_denied(conn.describe("SELECT id FROM policy_denied WHERE id = $1"))
+    });
+
+    assert!(denied);
+
+    let described = sqlx::transaction("describe an allowed table", |mut conn| {
+        conn.describe("SELECT id FROM policy_allowed WHERE id = $1")
+            .map(|describe| describe.columns().len())
+    })?;
+
+    assert_eq!(described, 1);
+
+    let denied = sqlx::transaction("explain a denied table", |mut conn| {
+        is_denied(
+            conn.explain(sqlx::query("SELECT id FROM policy_denied WHERE id = $1").bind(1i64)),
+        )
+    });
+
+    assert!(denied);
+
+    let node_type = sqlx::transaction("explain an allowed table", |mut conn| {
+        conn.explain(sqlx::query("SELECT id FROM policy_allowed WHERE id = $1").bind(1i64))
+            .map(|plan| plan.node_type().map(str::to_owned))
+    })?;
+
+    assert!(node_type.is_some());
+
+    let denied = sqlx::transaction("copy into a denied table", |mut conn| {
+        is_denied(conn.copy_in("public.policy_denied", &["id"]))
+    });
+
+    assert!(denied);
+
+    let rows = sqlx::transaction("copy into an allowed table", |mut conn| {
+        let mut copy = conn.copy_in("policy_allowed", &["id"])?;
+        copy.send(b"1\n2\n")?;
+        copy.finish()
+    })?;
+
+    assert_eq!(rows, 2);
+
+    Ok(())
+}
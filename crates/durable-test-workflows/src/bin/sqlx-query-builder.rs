@@ -0,0 +1,40 @@
+use durable::sqlx::{self, QueryBuilder};
+
+fn main() -> anyhow::Result<()> {
+    sqlx::transaction("set up the database schema", |mut conn| {
+        sqlx::query("CREATE TABLE builder_test(id bigint, name text)").execute(&mut conn)
+    })?;
+
+    let names = ["alice", "bob", "carol"];
+    sqlx::transaction("insert rows with a query builder", |mut conn| {
+        let mut builder = QueryBuilder::new("INSERT INTO builder_test(id, name) ");
+        builder.push_values(names.iter().enumerate(), |mut b, (id, name)| {
+            b.push_bind(id as i64).push_bind(*name);
+        });
+
+        builder.build().execute(&mut conn)
+    })?;
+
+    let selected: Vec<String> = sqlx::transaction("select rows by id", |mut conn| {
+        let mut builder = QueryBuilder::new("SELECT name FROM builder_test WHERE id IN (");
+        let mut separated = builder.separated(", ");
+        for id in [0i64, 2] {
+            separated.push_bind(id);
+        }
+        separated.push_unseparated(") ORDER BY id ASC");
+
+        builder.build_query_scalar().fetch_all(&mut conn)
+    })?;
+
+    assert_eq!(selected, ["alice", "carol"]);
+
+    let count: i64 = sqlx::transaction("count the rows", |mut conn| {
+        sqlx::query_scalar("SELECT count(*) FROM builder_test WHERE id >= $1")
+            .bind(1i64)
+            .fetch_one(&mut conn)
+    })?;
+
+    assert_eq!(count, 2);
+
+    Ok(())
+}
@@ -0,0 +1,55 @@
+use durable::sqlx;
+
+fn main() -> anyhow::Result<()> {
+    sqlx::transaction("set up the database schema", |mut conn| {
+        sqlx::query("CREATE TABLE stats_test(id bigint PRIMARY KEY, value bigint NOT NULL)")
+            .execute(&mut conn)?;
+        sqlx::query("INSERT INTO stats_test SELECT i, 0 FROM generate_series(1, 10) AS i")
+            .execute(&mut conn)
+    })?;
+
+    let (rows_affected, rows_scanned, has_execution_time) =
+        sqlx::transaction("update with stats", |mut conn| {
+            conn.set_collect_stats(true);
+
+            let result =
+                sqlx::query("UPDATE stats_test SET value = value + 1").execute(&mut conn)?;
+            let stats = result.stats().expect("query stats were not collected");
+
+            Ok::<_, sqlx::Error>((
+                result.rows_affected(),
+                stats.rows_scanned(),
+                stats.execution_time().is_some(),
+            ))
+        })?;
+
+    assert_eq!(rows_affected, 10);
+    assert_eq!(rows_scanned, Some(10));
+    assert!(has_execution_time);
+
+    let (values, rows_scanned) = sqlx::transaction("select with stats", |mut conn| {
+        conn.set_collect_stats(true);
+
+        let values: Vec<i64> = sqlx::query_scalar("SELECT value FROM stats_test WHERE id <= 3")
+            .fetch_all(&mut conn)?;
+        let stats = conn
+            .last_query_stats()
+            .expect("query stats were not collected");
+
+        Ok::<_, sqlx::Error>((values, stats.rows_scanned()))
+    })?;
+
+    // The update should only have been applied once, even though collecting stats
+    // runs it twice.
+    assert_eq!(values, [1, 1, 1]);
+    assert!(rows_scanned.is_some());
+
+    let stats = sqlx::transaction("query without stats", |mut conn| {
+        sqlx::query("SELECT 1").execute(&mut conn)?;
+        Ok::<_, sqlx::Error>(conn.last_query_stats().is_some())
+    })?;
+
+    assert!(!stats);
+
+    Ok(())
+}
@@ -0,0 +1,38 @@
+use durable::sqlx;
+use durable::sqlx::types::PgRaw;
+
+fn main() -> anyhow::Result<()> {
+    let equal: bool = sqlx::transaction("round-trip a numeric value", |mut conn| {
+        sqlx::query("CREATE TABLE raw_value_test(id bigint PRIMARY KEY, price numeric)")
+            .execute(&mut conn)?;
+        sqlx::query("INSERT INTO raw_value_test(id, price) VALUES (1, 12.50)")
+            .execute(&mut conn)?;
+
+        let price: PgRaw = sqlx::query_scalar("SELECT price FROM raw_value_test WHERE id = 1")
+            .fetch_one(&mut conn)?;
+
+        // numeric is not one of the types supported by the runtime.
+        assert_eq!(price.oid(), 1700);
+        assert!(!price.as_bytes().is_empty());
+
+        // Casting in the query is how to get at the value as text.
+        let text: String =
+            sqlx::query_scalar("SELECT price::text FROM raw_value_test WHERE id = 1")
+                .fetch_one(&mut conn)?;
+        assert_eq!(text, "12.50");
+
+        sqlx::query("INSERT INTO raw_value_test(id, price) VALUES (2, $1)")
+            .bind(price)
+            .execute(&mut conn)?;
+
+        sqlx::query_scalar(
+            "SELECT a.price = b.price FROM raw_value_test a, raw_value_test b WHERE a.id = 1 AND \
+             b.id = 2",
+        )
+        .fetch_one(&mut conn)
+    })?;
+
+    assert!(equal);
+
+    Ok(())
+}
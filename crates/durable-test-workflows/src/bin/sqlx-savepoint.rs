@@ -0,0 +1,33 @@
+use durable::sqlx;
+
+fn main() -> anyhow::Result<()> {
+    sqlx::transaction("set up the database schema", |mut conn| {
+        sqlx::query("CREATE TABLE savepoint_test(id bigint PRIMARY KEY)").execute(&mut conn)
+    })?;
+
+    let count: i64 = sqlx::transaction("insert rows using savepoints", |mut conn| {
+        sqlx::query("INSERT INTO savepoint_test(id) VALUES (1)").execute(&mut conn)?;
+
+        // This violates the primary key constraint, so only the savepoint should be
+        // rolled back.
+        let result = conn.savepoint(|conn| {
+            sqlx::query("INSERT INTO savepoint_test(id) VALUES (2)").execute(&mut *conn)?;
+            sqlx::query("INSERT INTO savepoint_test(id) VALUES (1)").execute(&mut *conn)
+        });
+        assert!(result.is_err());
+
+        // Nested savepoints are committed along with the enclosing one.
+        conn.savepoint(|conn| {
+            sqlx::query("INSERT INTO savepoint_test(id) VALUES (3)").execute(&mut *conn)?;
+            conn.savepoint(|conn| {
+                sqlx::query("INSERT INTO savepoint_test(id) VALUES (4)").execute(&mut *conn)
+            })
+        })?;
+
+        sqlx::query_scalar("SELECT count(*) FROM savepoint_test").fetch_one(&mut conn)
+    })?;
+
+    assert_eq!(count, 3);
+
+    Ok(())
+}
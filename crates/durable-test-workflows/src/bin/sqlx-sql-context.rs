@@ -0,0 +1,15 @@
+use durable::sqlx;
+
+fn main() -> anyhow::Result<()> {
+    // The context is applied to every database transaction, not just the first.
+    for label in ["first transaction", "second transaction"] {
+        let user_id: Option<String> = sqlx::transaction(label, |mut conn| {
+            sqlx::query_scalar("SELECT current_setting('app.user_id', true)").fetch_one(&mut conn)
+        })?;
+
+        // This is set by the test when launching the task.
+        assert_eq!(user_id.as_deref(), Some("42"));
+    }
+
+    Ok(())
+}
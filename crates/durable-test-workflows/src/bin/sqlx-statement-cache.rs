@@ -0,0 +1,33 @@
+use ::sqlx::Row;
+use durable::sqlx;
+
+fn read_rows() -> sqlx::Result<Vec<usize>> {
+    sqlx::transaction("read the rows", |mut conn| {
+        let rows = sqlx::query("SELECT * FROM statement_cache_test").fetch_all(&mut conn)?;
+
+        Ok(rows.iter().map(|row| row.len()).collect())
+    })
+}
+
+fn main() -> anyhow::Result<()> {
+    sqlx::transaction("set up the database schema", |mut conn| {
+        sqlx::query("CREATE TABLE statement_cache_test(id bigint PRIMARY KEY)")
+            .execute(&mut conn)?;
+        sqlx::query("INSERT INTO statement_cache_test(id) VALUES (1)").execute(&mut conn)
+    })?;
+
+    assert_eq!(read_rows()?, [1]);
+
+    sqlx::transaction("add a column", |mut conn| {
+        sqlx::query("ALTER TABLE statement_cache_test ADD COLUMN name text").execute(&mut conn)?;
+        sqlx::clear_statement_cache();
+
+        Ok::<_, sqlx::Error>(())
+    })?;
+
+    // The statement cached by the first read no longer matches the table, so
+    // this would fail if it had not been evicted.
+    assert_eq!(read_rows()?, [2]);
+
+    Ok(())
+}
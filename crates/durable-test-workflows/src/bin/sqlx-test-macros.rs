@@ -34,8 +34,9 @@ fn main() -> anyhow::Result<()> {
         println!("{idx:02}: {} - {}", event.label, event.value);
     }
 
-    assert_eq!(events.len(), 2);
-    assert_eq!(events[0].label, "wasi:io/streams.output-stream.write");
+    // Printing outside of a transaction doesn't record any events, and the
+    // event for this transaction isn't recorded until it completes.
+    assert!(events.is_empty());
 
     Ok(())
 }
@@ -0,0 +1,37 @@
+use std::time::Duration;
+
+use durable::sqlx::{self, TransactionOptions};
+
+fn main() -> anyhow::Result<()> {
+    sqlx::transaction("set up the database schema", |mut conn| {
+        sqlx::query("CREATE TABLE options_test(id bigint PRIMARY KEY)").execute(&mut conn)
+    })?;
+
+    let options = TransactionOptions::new("insert in a read-only transaction").read_only();
+    let failed = sqlx::transaction_with(options, |mut conn| {
+        sqlx::query("INSERT INTO options_test(id) VALUES (1)")
+            .execute(&mut conn)
+            .is_err()
+    });
+
+    assert!(failed);
+
+    let options = TransactionOptions::new("read in a read-only transaction").read_only();
+    let count: i64 = sqlx::transaction_with(options, |mut conn| {
+        sqlx::query_scalar("SELECT count(*) FROM options_test").fetch_one(&mut conn)
+    })?;
+
+    assert_eq!(count, 0);
+
+    let options = TransactionOptions::new("exceed the statement timeout")
+        .statement_timeout(Duration::from_millis(100));
+    let failed = sqlx::transaction_with(options, |mut conn| {
+        sqlx::query("SELECT pg_sleep(5)")
+            .execute(&mut conn)
+            .is_err()
+    });
+
+    assert!(failed);
+
+    Ok(())
+}
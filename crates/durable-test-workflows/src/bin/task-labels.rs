@@ -0,0 +1,7 @@
+fn main() {
+    let task = durable::task();
+
+    for (key, value) in task.labels() {
+        println!("{key}={value}");
+    }
+}
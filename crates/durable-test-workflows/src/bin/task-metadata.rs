@@ -0,0 +1,12 @@
+fn main() {
+    let task = durable::task();
+
+    println!("queue: {}", task.queue());
+    println!("attempt: {}", task.attempt());
+
+    // The task gets suspended while waiting, so it is resumed as a new attempt.
+    durable::notify::wait();
+
+    println!("attempt: {}", task.attempt());
+    println!("worker: {}", task.worker_id());
+}
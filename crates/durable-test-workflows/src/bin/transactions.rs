@@ -0,0 +1,8 @@
+use durable::transaction;
+
+fn main() {
+    let first = transaction("first", || 1);
+    let second = transaction("second", || first + 1);
+
+    println!("{first} {second}");
+}
@@ -0,0 +1,34 @@
+use durable::workflow::{self, WorkflowDef};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize)]
+struct Input {
+    a: u64,
+    b: u64,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Output {
+    sum: u64,
+}
+
+struct Add;
+
+impl WorkflowDef for Add {
+    type Input = Input;
+    type Output = Output;
+
+    const NAME: &'static str = "add";
+}
+
+fn add() {
+    workflow::run::<Add>(|input| Output {
+        sum: input.a + input.b,
+    });
+}
+
+durable::entrypoints! {
+    "add" => add,
+}
+
+fn main() {}
@@ -0,0 +1,33 @@
+#!/usr/bin/bash
+#
+# Build the conformance workflows that are written in languages other than
+# Rust into the directory passed as the first argument.
+#
+# Each one is skipped if the toolchain that it needs is not installed. The
+# corresponding tests are then skipped as well.
+
+set -euo pipefail
+
+out="$(realpath -m "${1:?usage: build.sh <output-dir>}")"
+here="$(dirname "$(realpath "$0")")"
+wit="$here/../../durable-runtime/wit"
+
+mkdir -p "$out"
+
+if command -v componentize-py > /dev/null; then
+    (
+        cd "$here/python"
+        componentize-py -d "$wit" -w imports componentize app -o "$out/echo-python.wasm"
+    )
+else
+    echo "componentize-py is not installed, skipping the python conformance workflows" >&2
+fi
+
+if command -v jco > /dev/null; then
+    jco componentize "$here/js/app.js" \
+        --wit "$wit" \
+        --world-name imports \
+        --out "$out/echo-js.wasm"
+else
+    echo "jco is not installed, skipping the javascript conformance workflows" >&2
+fi
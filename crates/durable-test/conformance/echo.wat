@@ -0,0 +1,93 @@
+;; The `echo` conformance workflow, written directly in the component model
+;; text format.
+;;
+;; It records the task's data as the result of a single transaction labelled
+;; `echo` and then exits successfully. If the transaction has already been
+;; recorded then it is not run again.
+(component
+  (import "durable:core/core@2.7.0" (instance $core
+    (export "task-data" (func (result string)))
+    (export "transaction-enter"
+      (func (param "label" string) (param "is-db" bool) (result (option string))))
+    (export "transaction-exit" (func (param "data" string)))
+  ))
+
+  ;; Memory and a bump allocator for the canonical ABI. Nothing is ever freed,
+  ;; which is fine for a workflow this small.
+  (core module $Alloc
+    (memory (export "memory") 1)
+    (global $heap (mut i32) (i32.const 1024))
+
+    (func (export "realloc")
+      (param $old i32) (param $old-size i32) (param $align i32) (param $size i32)
+      (result i32)
+      (local $ptr i32)
+      (local.set $ptr
+        (i32.and
+          (i32.add (global.get $heap) (i32.sub (local.get $align) (i32.const 1)))
+          (i32.sub (i32.const 0) (local.get $align))))
+      (global.set $heap (i32.add (local.get $ptr) (local.get $size)))
+
+      (if (i32.gt_u (global.get $heap) (i32.shl (memory.size) (i32.const 16)))
+        (then
+          (if (i32.eq
+                (memory.grow
+                  (i32.add
+                    (i32.shr_u
+                      (i32.sub (global.get $heap) (i32.shl (memory.size) (i32.const 16)))
+                      (i32.const 16))
+                    (i32.const 1)))
+                (i32.const -1))
+            (then unreachable))))
+
+      (local.get $ptr))
+  )
+  (core instance $alloc (instantiate $Alloc))
+  (alias core export $alloc "memory" (core memory $memory))
+  (alias core export $alloc "realloc" (core func $realloc))
+
+  (core func $task-data
+    (canon lower (func $core "task-data") (memory $memory) (realloc $realloc)))
+  (core func $transaction-enter
+    (canon lower (func $core "transaction-enter") (memory $memory) (realloc $realloc)))
+  (core func $transaction-exit
+    (canon lower (func $core "transaction-exit") (memory $memory)))
+
+  (core module $Main
+    (import "env" "memory" (memory 1))
+    (import "durable" "task-data" (func $task-data (param i32)))
+    (import "durable" "transaction-enter" (func $transaction-enter (param i32 i32 i32 i32)))
+    (import "durable" "transaction-exit" (func $transaction-exit (param i32 i32)))
+
+    ;; 0..4:   the transaction label
+    ;; 16..28: the option<string> returned by transaction-enter
+    ;; 32..40: the string returned by task-data
+    (data (i32.const 0) "echo")
+
+    (func (export "run") (result i32)
+      (call $transaction-enter (i32.const 0) (i32.const 4) (i32.const 0) (i32.const 16))
+
+      ;; Only run the transaction if it returned none.
+      (if (i32.eqz (i32.load8_u (i32.const 16)))
+        (then
+          (call $task-data (i32.const 32))
+          (call $transaction-exit (i32.load (i32.const 32)) (i32.load (i32.const 36)))))
+
+      ;; ok
+      (i32.const 0))
+  )
+  (core instance $main (instantiate $Main
+    (with "env" (instance (export "memory" (memory $memory))))
+    (with "durable" (instance
+      (export "task-data" (func $task-data))
+      (export "transaction-enter" (func $transaction-enter))
+      (export "transaction-exit" (func $transaction-exit))
+    ))
+  ))
+
+  (type $run-result (result))
+  (func $run (result $run-result) (canon lift (core func $main "run")))
+
+  (instance $run (export "run" (func $run)))
+  (export "wasi:cli/run@0.2.0" (instance $run))
+)
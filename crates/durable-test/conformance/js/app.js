@@ -0,0 +1,18 @@
+// The `echo` conformance workflow, written in JavaScript.
+//
+// Records the task's data as the result of a single transaction labelled
+// `echo`. See `build.sh` for how this is turned into a component.
+
+import {
+  taskData,
+  transactionEnter,
+  transactionExit,
+} from "durable:core/core@2.7.0";
+
+export const run = {
+  run() {
+    if (transactionEnter("echo", false) === undefined) {
+      transactionExit(taskData());
+    }
+  },
+};
@@ -0,0 +1,14 @@
+"""The `echo` conformance workflow, written in Python.
+
+Records the task's data as the result of a single transaction labelled `echo`.
+See `build.sh` for how this is turned into a component.
+"""
+
+from wit_world import exports
+from wit_world.imports import core
+
+
+class Run(exports.Run):
+    def run(self) -> None:
+        if core.transaction_enter("echo", False) is None:
+            core.transaction_exit(core.task_data())
@@ -2,5 +2,7 @@
 
 target="$(cargo metadata --format-version 1 | jq -r .target_directory)"
 echo "DURABLE_TEST_BIN_DIR=$target/wasm32-wasip1/wasm" >> "$NEXTEST_ENV"
+echo "DURABLE_TEST_CONFORMANCE_DIR=$target/conformance" >> "$NEXTEST_ENV"
 
 cargo component build --profile wasm -p durable-test-workflows --bins
+bash crates/durable-test/conformance/build.sh "$target/conformance"
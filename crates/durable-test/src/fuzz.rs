@@ -0,0 +1,387 @@
+//! Property-based fuzzing of how tasks recover from failures.
+//!
+//! A [`ReplayFuzzer`] runs a workflow program many times. Each run follows a
+//! randomly generated [`Scenario`] that crashes workers, drops their database
+//! connections, delays transactions, and delivers notifications at random
+//! points. Once the task completes the fuzzer checks that:
+//! - the task has an event for every transaction index with no gaps,
+//! - replaying the program against the recorded events makes the same
+//!   transactions with the same labels, and
+//! - any extra [invariants](ReplayFuzzer::invariant), such as a check that
+//!   side effects happened exactly once, hold.
+//!
+//! When a scenario fails it is shrunk down to a minimal failing scenario before
+//! being reported, along with the seed needed to reproduce the run.
+//!
+//! ```ignore
+//! let fuzzer = ReplayFuzzer::from_file(test_binary("my-workflow.wasm"), 4)?
+//!     .notifications(2)
+//!     .cases(16);
+//!
+//! fuzzer.run(&pool).await?;
+//! ```
+
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Context;
+use durable_client::{DurableClient, Program, ProgramOptions, Task};
+use durable_runtime::chaos::{Chaos, Fault, FaultPoint, Injection};
+use durable_runtime::replay::{ReplayOutcome, ReplayTask};
+use durable_runtime::{Config, TaskStatus};
+use futures::future::BoxFuture;
+use proptest::prelude::*;
+use proptest::strategy::ValueTree;
+use proptest::test_runner::{RngAlgorithm, TestRng, TestRunner};
+use serde_json::value::RawValue;
+
+use crate::ReplayHarness;
+
+/// A fault injected into the task at a transaction boundary.
+#[derive(Clone, Debug)]
+pub struct ScenarioFault {
+    pub point: FaultPoint,
+    pub index: i32,
+    pub fault: Fault,
+}
+
+/// The interruptions that the task is subjected to during a single run.
+#[derive(Clone, Debug)]
+pub struct Scenario {
+    /// Faults to inject, in the order that they take priority.
+    pub faults: Vec<ScenarioFault>,
+
+    /// How long after the task is launched each notification is delivered.
+    pub notifications: Vec<Duration>,
+}
+
+/// An additional check run against a task after it has completed.
+pub type Invariant = Box<
+    dyn for<'a> Fn(&'a DurableClient, &'a Task) -> BoxFuture<'a, anyhow::Result<()>> + Send + Sync,
+>;
+
+/// Runs a workflow program under randomly generated failure scenarios.
+///
+/// See the [module docs](self) for details.
+pub struct ReplayFuzzer {
+    wasm: PathBuf,
+    harness: ReplayHarness,
+    data: Box<RawValue>,
+    transactions: i32,
+    notifications: usize,
+    max_faults: usize,
+    cases: u32,
+    seed: u64,
+    timeout: Duration,
+    invariants: Vec<Invariant>,
+}
+
+impl ReplayFuzzer {
+    /// Create a fuzzer for the program at `path`, which makes `transactions`
+    /// transactions when it runs to completion.
+    ///
+    /// Faults are only injected at transaction indices below `transactions`.
+    pub fn from_file(path: impl AsRef<Path>, transactions: i32) -> anyhow::Result<Self> {
+        let wasm = path.as_ref().to_owned();
+        let harness = ReplayHarness::from_file(&wasm)?;
+
+        Ok(Self {
+            wasm,
+            harness,
+            data: RawValue::from_string("null".into())?,
+            transactions,
+            notifications: 0,
+            max_faults: 3,
+            cases: 8,
+            seed: std::env::var("DURABLE_FUZZ_SEED")
+                .ok()
+                .and_then(|seed| seed.parse().ok())
+                .unwrap_or_else(rand_seed),
+            timeout: Duration::from_secs(60),
+            invariants: Vec::new(),
+        })
+    }
+
+    /// The data that each task is launched with.
+    pub fn data(mut self, data: &impl serde::Serialize) -> anyhow::Result<Self> {
+        self.data = serde_json::value::to_raw_value(data)?;
+        Ok(self)
+    }
+
+    /// The number of notifications that the program waits for.
+    ///
+    /// Each one is delivered at a random point after the task is launched.
+    pub fn notifications(mut self, notifications: usize) -> Self {
+        self.notifications = notifications;
+        self
+    }
+
+    /// The maximum number of faults injected in a single scenario.
+    pub fn max_faults(mut self, max_faults: usize) -> Self {
+        self.max_faults = max_faults;
+        self
+    }
+
+    /// The number of scenarios to run.
+    pub fn cases(mut self, cases: u32) -> Self {
+        self.cases = cases;
+        self
+    }
+
+    /// The seed used to generate scenarios.
+    ///
+    /// This defaults to the `DURABLE_FUZZ_SEED` environment variable, or a
+    /// random seed if it is not set. The seed is included in the error when a
+    /// scenario fails.
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// How long a single task is allowed to take before the scenario fails.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Check `invariant` after each task completes.
+    pub fn invariant<F>(mut self, invariant: F) -> Self
+    where
+        F: for<'a> Fn(&'a DurableClient, &'a Task) -> BoxFuture<'a, anyhow::Result<()>>
+            + Send
+            + Sync
+            + 'static,
+    {
+        self.invariants.push(Box::new(invariant));
+        self
+    }
+
+    /// The strategy used to generate scenarios.
+    pub fn strategy(&self) -> impl Strategy<Value = Scenario> {
+        let fault = (
+            prop_oneof![
+                Just(FaultPoint::TransactionEnter),
+                Just(FaultPoint::TransactionExit)
+            ],
+            0..self.transactions.max(1),
+            // Shrinking moves towards the earlier, less disruptive, faults.
+            prop_oneof![
+                (0u64..200).prop_map(|ms| Fault::Delay(Duration::from_millis(ms))),
+                Just(Fault::DropConnection),
+                Just(Fault::KillWorker),
+            ],
+        )
+            .prop_map(|(point, index, fault)| ScenarioFault {
+                point,
+                index,
+                fault,
+            });
+
+        let faults = prop::collection::vec(fault, 0..=self.max_faults);
+        let notifications = prop::collection::vec(
+            (0u64..2000).prop_map(Duration::from_millis),
+            self.notifications,
+        );
+
+        (faults, notifications).prop_map(|(faults, notifications)| Scenario {
+            faults,
+            notifications,
+        })
+    }
+
+    /// Run all the scenarios against the database in `pool`.
+    ///
+    /// Returns an error describing the smallest failing scenario if any of
+    /// them fail.
+    pub async fn run(&self, pool: &sqlx::PgPool) -> anyhow::Result<()> {
+        let client = DurableClient::new(pool.clone())?;
+        let program = client
+            .program(ProgramOptions::from_file(&self.wasm)?)
+            .await?;
+
+        let mut seed = [0u8; 32];
+        seed[..8].copy_from_slice(&self.seed.to_le_bytes());
+        let mut runner = TestRunner::new_with_rng(
+            Default::default(),
+            TestRng::from_seed(RngAlgorithm::ChaCha, &seed),
+        );
+        let strategy = self.strategy();
+
+        for case in 0..self.cases {
+            let mut tree = strategy
+                .new_tree(&mut runner)
+                .map_err(|e| anyhow::anyhow!("failed to generate a scenario: {e}"))?;
+
+            let error = match self
+                .run_case(pool, &client, &program, &tree.current())
+                .await
+            {
+                Ok(()) => continue,
+                Err(e) => e,
+            };
+
+            // Shrink the scenario down to the simplest one that still fails.
+            let mut minimal = Failure {
+                scenario: tree.current(),
+                error,
+            };
+            while tree.simplify() {
+                let scenario = tree.current();
+                match self.run_case(pool, &client, &program, &scenario).await {
+                    Ok(()) => {
+                        if !tree.complicate() {
+                            break;
+                        }
+                    }
+                    Err(error) => minimal = Failure { scenario, error },
+                }
+            }
+
+            anyhow::bail!(
+                "case {case} failed with seed {} (set DURABLE_FUZZ_SEED to reproduce)\n{minimal}",
+                self.seed
+            );
+        }
+
+        Ok(())
+    }
+
+    async fn run_case(
+        &self,
+        pool: &sqlx::PgPool,
+        client: &DurableClient,
+        program: &Program,
+        scenario: &Scenario,
+    ) -> anyhow::Result<()> {
+        let task = client.launch("fuzz", program, &self.data).await?;
+
+        let chaos = Arc::new(Chaos::new());
+        for fault in &scenario.faults {
+            chaos.inject(
+                Injection::new(fault.point, fault.fault.clone())
+                    .task(task.id())
+                    .index(fault.index),
+            );
+        }
+
+        let notifier = {
+            let client = client.clone();
+            let task = task.clone();
+            let mut delays = scenario.notifications.clone();
+            delays.sort();
+
+            tokio::spawn(async move {
+                let start = tokio::time::Instant::now();
+                for (i, delay) in delays.into_iter().enumerate() {
+                    tokio::time::sleep_until(start + delay).await;
+                    task.notify("fuzz", &i, &client).await?;
+                }
+
+                anyhow::Ok(())
+            })
+        };
+
+        let result = self.drive(pool, client, &task, chaos).await;
+        notifier.abort();
+        let status = result?;
+        anyhow::ensure!(
+            status == TaskStatus::ExitSuccess,
+            "task {} exited with {status:?}",
+            task.id()
+        );
+
+        self.check(client, &task).await
+    }
+
+    /// Run workers until `task` completes, replacing each one that is killed.
+    async fn drive(
+        &self,
+        pool: &sqlx::PgPool,
+        client: &DurableClient,
+        task: &Task,
+        chaos: Arc<Chaos>,
+    ) -> anyhow::Result<TaskStatus> {
+        let config = Config::new()
+            .suspend_margin(Duration::from_secs(1))
+            .suspend_timeout(Duration::from_secs(1))
+            .heartbeat_timeout(Duration::from_secs(1))
+            .worker_sweep_interval(Some(Duration::from_secs(1)));
+        let deadline = tokio::time::Instant::now() + self.timeout;
+
+        loop {
+            let mut worker =
+                crate::spawn_chaos_worker(pool.clone(), config.clone(), chaos.clone()).await?;
+
+            tokio::select! {
+                status = task.wait(client, None) => {
+                    return Ok(match status?.success() {
+                        true => TaskStatus::ExitSuccess,
+                        false => TaskStatus::ExitFailure,
+                    });
+                }
+                result = &mut worker => {
+                    // The worker was killed. Its tasks are reassigned to the
+                    // next one once its heartbeat expires.
+                    result.context("worker exited with an error")?;
+                }
+                _ = tokio::time::sleep_until(deadline) => {
+                    anyhow::bail!("task {} did not complete within {:?}", task.id(), self.timeout)
+                }
+            }
+        }
+    }
+
+    /// Check the invariants for a task that has completed.
+    async fn check(&self, client: &DurableClient, task: &Task) -> anyhow::Result<()> {
+        let events = task.events(client).await?;
+        for (index, event) in events.iter().enumerate() {
+            anyhow::ensure!(
+                event.index == index as i32,
+                "task {} is missing the event at index {index}",
+                task.id()
+            );
+        }
+
+        let replay = ReplayTask::new(task.id(), "fuzz", self.data.clone());
+        match self.harness.replay(replay, &events).await? {
+            ReplayOutcome::Exited(TaskStatus::ExitSuccess) => (),
+            ReplayOutcome::Exited(status) => {
+                anyhow::bail!("replaying task {} exited with {status:?}", task.id())
+            }
+            ReplayOutcome::EndOfLog { index, label } => anyhow::bail!(
+                "replaying task {} requested {label:?} at index {index} after the end of the \
+                 recorded events",
+                task.id()
+            ),
+            ReplayOutcome::Diverged(divergence) => {
+                anyhow::bail!("replaying task {} diverged: {divergence}", task.id())
+            }
+        }
+
+        for invariant in &self.invariants {
+            invariant(client, task).await?;
+        }
+
+        Ok(())
+    }
+}
+
+struct Failure {
+    scenario: Scenario,
+    error: anyhow::Error,
+}
+
+impl fmt::Display for Failure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "scenario: {:#?}", self.scenario)?;
+        write!(f, "error: {:?}", self.error)
+    }
+}
+
+fn rand_seed() -> u64 {
+    use std::hash::{BuildHasher, RandomState};
+
+    RandomState::new().hash_one(std::time::SystemTime::now())
+}
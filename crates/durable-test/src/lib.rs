@@ -1,11 +1,20 @@
 use std::future::Future;
+use std::path::Path;
 use std::pin::Pin;
+use std::sync::Arc;
 use std::task::{Context, Poll};
 use std::time::Duration;
 
-use durable_runtime::{Config, WorkerBuilder, WorkerHandle};
+use anyhow::Context as _;
+use durable_runtime::chaos::Chaos;
+use durable_runtime::replay::{Replay, ReplayEvent, ReplayOutcome, ReplayTask};
+use durable_runtime::sim::Simulation;
+use durable_runtime::{Config, TaskStatus, WorkerBuilder, WorkerHandle};
 use futures::FutureExt;
 use tokio::task::JoinHandle;
+use wasmtime::component::Component;
+
+pub mod fuzz;
 
 pub async fn spawn_worker(pool: sqlx::PgPool) -> anyhow::Result<WorkerShutdownGuard> {
     spawn_worker_with(
@@ -21,15 +30,41 @@ pub async fn spawn_worker_with(
     pool: sqlx::PgPool,
     config: Config,
 ) -> anyhow::Result<WorkerShutdownGuard> {
-    let mut wasmconfig = wasmtime::Config::new();
-    wasmconfig
-        .wasm_backtrace_details(wasmtime::WasmBacktraceDetails::Enable)
-        .cranelift_opt_level(wasmtime::OptLevel::None)
-        .debug_info(true)
-        .cache_config_load_default()?;
-    let mut worker = WorkerBuilder::new(pool)
-        .config(config.debug_emit_task_logs(true))
-        .wasmtime_config(wasmconfig)
+    spawn_worker_from(WorkerBuilder::new(pool).config(config.debug_emit_task_logs(true))).await
+}
+
+/// Spawn a worker that injects the faults in `chaos` into the tasks it runs.
+pub async fn spawn_chaos_worker(
+    pool: sqlx::PgPool,
+    config: Config,
+    chaos: Arc<Chaos>,
+) -> anyhow::Result<WorkerShutdownGuard> {
+    spawn_worker_from(
+        WorkerBuilder::new(pool)
+            .config(config.debug_emit_task_logs(true))
+            .chaos(chaos),
+    )
+    .await
+}
+
+/// Spawn a worker that runs its tasks within `simulation`.
+pub async fn spawn_simulated_worker(
+    pool: sqlx::PgPool,
+    config: Config,
+    simulation: Arc<Simulation>,
+) -> anyhow::Result<WorkerShutdownGuard> {
+    spawn_worker_from(
+        WorkerBuilder::new(pool)
+            .config(config.debug_emit_task_logs(true))
+            .simulation(simulation),
+    )
+    .await
+}
+
+/// Spawn a worker using a builder that has already been configured.
+pub async fn spawn_worker_from(builder: WorkerBuilder) -> anyhow::Result<WorkerShutdownGuard> {
+    let mut worker = builder
+        .wasmtime_config(wasmtime_config()?)
         .validate_database(false)
         .build()
         .await?;
@@ -40,6 +75,17 @@ pub async fn spawn_worker_with(
     Ok(WorkerShutdownGuard { handle, task })
 }
 
+fn wasmtime_config() -> anyhow::Result<wasmtime::Config> {
+    let mut config = wasmtime::Config::new();
+    config
+        .wasm_backtrace_details(wasmtime::WasmBacktraceDetails::Enable)
+        .cranelift_opt_level(wasmtime::OptLevel::None)
+        .debug_info(true)
+        .cache_config_load_default()?;
+
+    Ok(config)
+}
+
 pub struct WorkerShutdownGuard {
     handle: WorkerHandle,
     task: JoinHandle<anyhow::Result<()>>,
@@ -73,6 +119,103 @@ impl Drop for WorkerShutdownGuard {
     }
 }
 
+/// Replays a workflow program against event logs recorded by a previous run.
+///
+/// This allows checking whether a program makes the same sequence of
+/// transactions as a previous execution of a task (e.g. one fetched via
+/// [`durable_client::Task::events`]) without needing a database or a worker.
+pub struct ReplayHarness {
+    replay: Replay,
+    component: Component,
+}
+
+impl ReplayHarness {
+    /// Create a new harness for the provided WASM component.
+    pub fn new(wasm: &[u8]) -> anyhow::Result<Self> {
+        let replay = Replay::new(wasmtime_config()?)?;
+        let component = Component::new(replay.engine(), wasm)
+            .context("failed to compile the wasm component")?;
+
+        Ok(Self { replay, component })
+    }
+
+    /// Create a new harness for the WASM component at `path`.
+    pub fn from_file(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let path = path.as_ref();
+        let wasm =
+            std::fs::read(path).with_context(|| format!("failed to read `{}`", path.display()))?;
+
+        Self::new(&wasm)
+    }
+
+    /// Replay the program as `task` against the recorded `events`.
+    pub async fn replay(
+        &self,
+        task: ReplayTask,
+        events: &[durable_client::Event],
+    ) -> anyhow::Result<ReplayOutcome> {
+        let events = events.iter().map(|event| ReplayEvent {
+            index: event.index,
+            label: event.label.clone(),
+            value: event.value.clone(),
+        });
+
+        self.replay.run(&self.component, task, events).await
+    }
+
+    /// Replay the program as `task` against the recorded `events` and assert
+    /// that it makes exactly the transactions that were recorded.
+    ///
+    /// Returns the status that the program exited with.
+    ///
+    /// # Panics
+    /// Panics with a description of where the divergence occurred if the
+    /// program does not match the recorded events.
+    pub async fn assert_replays(
+        &self,
+        task: ReplayTask,
+        events: &[durable_client::Event],
+    ) -> anyhow::Result<TaskStatus> {
+        match self.replay(task, events).await? {
+            ReplayOutcome::Exited(status) => Ok(status),
+            ReplayOutcome::EndOfLog { index, label } => panic!(
+                "workflow requested {label:?} at index {index} after reaching the end of the \
+                 recorded events"
+            ),
+            ReplayOutcome::Diverged(divergence) => panic!("{divergence}"),
+        }
+    }
+
+    /// Assert that `task` recorded each of its transactions exactly once and
+    /// that replaying the program against the recorded events makes the same
+    /// transactions.
+    ///
+    /// This is meant to be used once a task that had faults injected into it
+    /// via [`durable_runtime::chaos`] has completed.
+    ///
+    /// # Panics
+    /// Panics if the recorded events have gaps in them or if the replay does
+    /// not match the recorded events.
+    pub async fn assert_exactly_once(
+        &self,
+        client: &durable_client::DurableClient,
+        task: &durable_client::Task,
+        replay: ReplayTask,
+    ) -> anyhow::Result<TaskStatus> {
+        let events = task.events(client).await?;
+        for (index, event) in events.iter().enumerate() {
+            assert_eq!(
+                event.index,
+                index as i32,
+                "task {} is missing the event at index {index}",
+                task.id()
+            );
+        }
+
+        self.assert_replays(replay, &events).await
+    }
+}
+
 #[ctor::ctor]
 fn setup_tracing() {
     use tracing_subscriber::prelude::*;
@@ -0,0 +1,273 @@
+use std::net::SocketAddr;
+
+use durable_client::DurableClient;
+use durable_runtime::ApiConfig;
+use reqwest::StatusCode;
+use serde_json::Value;
+
+/// Serve the management API on a random local port and return its base URL.
+async fn serve_api(pool: sqlx::PgPool, config: ApiConfig) -> anyhow::Result<String> {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+    let addr = listener.local_addr()?;
+    let router = durable_runtime::api::router(pool, &config);
+
+    tokio::spawn(async move { axum::serve(listener, router).await });
+
+    Ok(format!("http://{addr}"))
+}
+
+fn config() -> ApiConfig {
+    ApiConfig::new(SocketAddr::from(([127, 0, 0, 1], 0)))
+}
+
+#[sqlx::test]
+async fn inspect_and_retry_failed_task(pool: sqlx::PgPool) -> anyhow::Result<()> {
+    let _guard = durable_test::spawn_worker(pool.clone()).await?;
+    let client = DurableClient::new(pool.clone())?;
+    let program = crate::load_binary(&client, "print-then-panic.wasm").await?;
+    let base = serve_api(pool, config()).await?;
+    let http = reqwest::Client::new();
+
+    let task = client
+        .launch("failing task", &program, &serde_json::json!({ "a": 1 }))
+        .await?;
+    let status = task.wait(&client, None).await?;
+    assert!(!status.success());
+
+    let details: Value = http
+        .get(format!("{base}/tasks/{}", task.id()))
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+    assert_eq!(details["name"], "failing task");
+    assert_eq!(details["state"], "failed");
+    assert_eq!(details["data"], serde_json::json!({ "a": 1 }));
+
+    let logs = http
+        .get(format!("{base}/tasks/{}/logs", task.id()))
+        .send()
+        .await?
+        .error_for_status()?
+        .text()
+        .await?;
+    assert!(
+        logs.starts_with("before the panic\n"),
+        "unexpected logs: {logs}"
+    );
+
+    let tasks: Vec<Value> = http
+        .get(format!("{base}/tasks?state=failed"))
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+    assert_eq!(tasks.len(), 1);
+    assert_eq!(tasks[0]["id"], task.id());
+
+    let response = http
+        .post(format!("{base}/tasks/{}/retry", task.id()))
+        .send()
+        .await?;
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let retried: Value = response.json().await?;
+    let retried = durable_client::Task::from_id(retried["id"].as_i64().unwrap());
+    assert_ne!(retried.id(), task.id());
+
+    let status = retried.wait(&client, None).await?;
+    assert!(!status.success());
+
+    // The task has already completed so there is nothing to cancel.
+    let response = http
+        .post(format!("{base}/tasks/{}/cancel", task.id()))
+        .send()
+        .await?;
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+
+    let response = http
+        .get(format!("{base}/tasks/{}", i64::MAX))
+        .send()
+        .await?;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    Ok(())
+}
+
+#[sqlx::test]
+async fn cancel_pending_task(pool: sqlx::PgPool) -> anyhow::Result<()> {
+    // No worker is running so the task stays in the ready state.
+    let client = DurableClient::new(pool.clone())?;
+    let program = crate::load_binary(&client, "task-details.wasm").await?;
+    let base = serve_api(pool, config()).await?;
+    let http = reqwest::Client::new();
+
+    let task = client
+        .launch("pending task", &program, &serde_json::json!(null))
+        .await?;
+
+    http.post(format!("{base}/tasks/{}/cancel", task.id()))
+        .send()
+        .await?
+        .error_for_status()?;
+
+    let status = task.wait(&client, None).await?;
+    assert!(!status.success());
+
+    let logs = http
+        .get(format!("{base}/tasks/{}/logs?follow=true", task.id()))
+        .send()
+        .await?
+        .error_for_status()?
+        .text()
+        .await?;
+    assert_eq!(logs, "task was cancelled\n");
+
+    Ok(())
+}
+
+#[sqlx::test]
+async fn upload_program_and_list_workers(pool: sqlx::PgPool) -> anyhow::Result<()> {
+    let _guard = durable_test::spawn_worker(pool.clone()).await?;
+    let base = serve_api(pool.clone(), config()).await?;
+    let http = reqwest::Client::new();
+
+    let wasm = std::fs::read(crate::test_binary("task-details.wasm"))?;
+    let response = http
+        .post(format!("{base}/programs?name=task-details"))
+        .body(wasm)
+        .send()
+        .await?;
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let program: Value = response.json().await?;
+
+    let name = sqlx::query_scalar!(
+        "SELECT name FROM durable.wasm WHERE id = $1",
+        program["id"].as_i64().unwrap()
+    )
+    .fetch_one(&pool)
+    .await?;
+    assert_eq!(name.as_deref(), Some("task-details"));
+
+    let response = http
+        .post(format!("{base}/programs"))
+        .body("not a wasm program")
+        .send()
+        .await?;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let workers: Vec<Value> = http
+        .get(format!("{base}/workers"))
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+    assert_eq!(workers.len(), 1);
+    assert_eq!(workers[0]["leader"], true);
+
+    Ok(())
+}
+
+#[sqlx::test]
+async fn requests_require_token(pool: sqlx::PgPool) -> anyhow::Result<()> {
+    let base = serve_api(pool, config().token("hunter2")).await?;
+    let http = reqwest::Client::new();
+
+    let response = http.get(format!("{base}/workers")).send().await?;
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    let response = http
+        .get(format!("{base}/workers"))
+        .bearer_auth("hunter3")
+        .send()
+        .await?;
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    let response = http
+        .get(format!("{base}/workers"))
+        .bearer_auth("hunter2")
+        .send()
+        .await?;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    Ok(())
+}
+
+#[sqlx::test]
+async fn tenant_tokens_are_scoped(pool: sqlx::PgPool) -> anyhow::Result<()> {
+    let client = DurableClient::new(pool.clone())?;
+    let team_a = client.with_tenant("team-a");
+    let team_b = client.with_tenant("team-b");
+    let program_a = crate::load_binary(&team_a, "task-details.wasm").await?;
+    let program_b = crate::load_binary(&team_b, "task-details.wasm").await?;
+    let base = serve_api(
+        pool,
+        config()
+            .token("hunter2")
+            .tenant_token("team-a", "correct-horse"),
+    )
+    .await?;
+    let http = reqwest::Client::new();
+
+    let task_a = team_a
+        .launch("task a", &program_a, &serde_json::json!(null))
+        .await?;
+    let task_b = team_b
+        .launch("task b", &program_b, &serde_json::json!(null))
+        .await?;
+
+    let tasks: Vec<Value> = http
+        .get(format!("{base}/tasks"))
+        .bearer_auth("correct-horse")
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+    assert_eq!(tasks.len(), 1);
+    assert_eq!(tasks[0]["id"], task_a.id());
+    assert_eq!(tasks[0]["tenant"], "team-a");
+
+    let response = http
+        .get(format!("{base}/tasks/{}", task_b.id()))
+        .bearer_auth("correct-horse")
+        .send()
+        .await?;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    let response = http
+        .post(format!("{base}/tasks/{}/cancel", task_b.id()))
+        .bearer_auth("correct-horse")
+        .send()
+        .await?;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    let response = http
+        .get(format!("{base}/tasks?tenant=team-b"))
+        .bearer_auth("correct-horse")
+        .send()
+        .await?;
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    let response = http
+        .get(format!("{base}/workers"))
+        .bearer_auth("correct-horse")
+        .send()
+        .await?;
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    let tasks: Vec<Value> = http
+        .get(format!("{base}/tasks?tenant=team-b"))
+        .bearer_auth("hunter2")
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+    assert_eq!(tasks.len(), 1);
+    assert_eq!(tasks[0]["id"], task_b.id());
+
+    Ok(())
+}
@@ -0,0 +1,51 @@
+use durable_client::DurableClient;
+use futures::TryStreamExt;
+
+#[sqlx::test]
+async fn approve_waiting_task(pool: sqlx::PgPool) -> anyhow::Result<()> {
+    let _guard = durable_test::spawn_worker(pool.clone()).await?;
+    let client = DurableClient::new(pool.clone())?;
+    let program = crate::load_binary(&client, "approval.wasm").await?;
+
+    let task = client.launch("approval", &program, &()).await?;
+    task.notify("other", &(), &client).await?;
+    client
+        .approve_as(&task, "deploy", "alice", &serde_json::json!({ "ok": true }))
+        .await?;
+
+    // Each key can only be approved once.
+    let result = client.approve(&task, "deploy", &()).await;
+    assert!(result.is_err());
+
+    let status = task.wait(&client, None).await?;
+    let logs: Vec<String> = task.read_logs(&client).try_collect().await?;
+    assert!(status.success(), "task failed: {}", logs.concat());
+
+    assert_eq!(
+        logs.concat(),
+        "never: true\n\
+         deploy by alice: {\"ok\":true}\n\
+         notification: other\n"
+    );
+
+    let approver = sqlx::query_scalar!(
+        "SELECT approved_by FROM durable.approval WHERE task_id = $1 AND key = 'deploy'",
+        task.id()
+    )
+    .fetch_one(&pool)
+    .await?;
+    assert_eq!(approver.as_deref(), Some("alice"));
+
+    Ok(())
+}
+
+#[sqlx::test]
+async fn approve_missing_task(pool: sqlx::PgPool) -> anyhow::Result<()> {
+    let client = DurableClient::new(pool)?;
+    let task = durable_client::Task::from_id(i64::MAX);
+
+    let result = client.approve(&task, "deploy", &()).await;
+    assert!(result.is_err());
+
+    Ok(())
+}
@@ -0,0 +1,59 @@
+use durable_client::DurableClient;
+use durable_runtime::Config;
+
+#[sqlx::test]
+async fn records_sql_statements(pool: sqlx::PgPool) -> anyhow::Result<()> {
+    let _guard = durable_test::spawn_worker_with(pool.clone(), Config::new().audit(true)).await?;
+    let client = DurableClient::new(pool)?;
+    let program = crate::load_binary(&client, "sqlx-batch.wasm").await?;
+
+    let task = client
+        .launch("audit test", &program, &serde_json::json!(null))
+        .await?;
+    let status = task.wait(&client, None).await?;
+    assert!(status.success());
+
+    let entries = task.audit_log(&client).await?;
+    assert!(entries.iter().all(|entry| entry.kind == "sql"));
+
+    let create = entries
+        .iter()
+        .find(|entry| entry.operation == "CREATE")
+        .expect("the CREATE TABLE statement was not recorded");
+    assert!(create.tables.is_empty());
+
+    let update = entries
+        .iter()
+        .find(|entry| entry.operation == "UPDATE")
+        .expect("the UPDATE statement was not recorded");
+    assert_eq!(update.tables, ["public.batch_test"]);
+    assert_eq!(update.rows_affected, Some(3));
+
+    // The three inserts in the first batch succeed, then the first insert in the
+    // second batch fails and the one after it never runs.
+    let inserts: Vec<_> = entries
+        .iter()
+        .filter(|entry| entry.operation == "INSERT")
+        .map(|entry| entry.rows_affected)
+        .collect();
+    assert_eq!(inserts, [Some(1), Some(1), Some(1), None, None]);
+
+    Ok(())
+}
+
+#[sqlx::test]
+async fn disabled_by_default(pool: sqlx::PgPool) -> anyhow::Result<()> {
+    let _guard = durable_test::spawn_worker(pool.clone()).await?;
+    let client = DurableClient::new(pool)?;
+    let program = crate::load_binary(&client, "sqlx-batch.wasm").await?;
+
+    let task = client
+        .launch("audit test", &program, &serde_json::json!(null))
+        .await?;
+    let status = task.wait(&client, None).await?;
+    assert!(status.success());
+
+    assert!(task.audit_log(&client).await?.is_empty());
+
+    Ok(())
+}
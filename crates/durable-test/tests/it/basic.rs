@@ -1,4 +1,5 @@
-use durable_client::DurableClient;
+use anyhow::Context;
+use durable_client::{DurableClient, LaunchOptions, ProgramOptions};
 use futures::TryStreamExt;
 
 #[sqlx::test]
@@ -10,7 +11,7 @@ async fn check_task_details(pool: sqlx::PgPool) -> anyhow::Result<()> {
     let task = client
         .launch("test task", &program, &serde_json::json!(null))
         .await?;
-    let status = task.wait(&client).await?;
+    let status = task.wait(&client, None).await?;
 
     assert!(status.success());
 
@@ -47,7 +48,7 @@ async fn run_sqlx_test(pool: sqlx::PgPool) -> anyhow::Result<()> {
     let task = client
         .launch("test task", &program, &serde_json::json!(null))
         .await?;
-    let status = task.wait(&client).await?;
+    let status = task.wait(&client, None).await?;
 
     assert!(status.success());
 
@@ -63,7 +64,7 @@ async fn run_sqlx_enum(pool: sqlx::PgPool) -> anyhow::Result<()> {
     let task = client
         .launch("sqlx enum test", &program, &serde_json::json!(null))
         .await?;
-    let status = task.wait(&client).await?;
+    let status = task.wait(&client, None).await?;
 
     assert!(status.success());
 
@@ -79,7 +80,7 @@ async fn run_sqlx_inet(pool: sqlx::PgPool) -> anyhow::Result<()> {
     let task = client
         .launch("sqlx inet test", &program, &serde_json::json!(null))
         .await?;
-    let status = task.wait(&client).await?;
+    let status = task.wait(&client, None).await?;
 
     assert!(status.success());
 
@@ -95,7 +96,7 @@ async fn run_sqlx_macros_test(pool: sqlx::PgPool) -> anyhow::Result<()> {
     let task = client
         .launch("wasm macros test", &program, &serde_json::json!(null))
         .await?;
-    let status = task.wait(&client).await?;
+    let status = task.wait(&client, None).await?;
 
     assert!(status.success());
 
@@ -111,9 +112,552 @@ async fn run_sqlx_use_json(pool: sqlx::PgPool) -> anyhow::Result<()> {
     let task = client
         .launch("sqlx json types test", &program, &serde_json::json!(null))
         .await?;
-    let status = task.wait(&client).await?;
+    let status = task.wait(&client, None).await?;
 
     assert!(status.success());
 
     Ok(())
 }
+
+#[sqlx::test]
+async fn output_is_saved_without_transactions(pool: sqlx::PgPool) -> anyhow::Result<()> {
+    let _guard = durable_test::spawn_worker(pool.clone()).await?;
+    let client = DurableClient::new(pool.clone())?;
+    let program = crate::load_binary(&client, "print-then-panic.wasm").await?;
+
+    let task = client
+        .launch("test task", &program, &serde_json::json!(null))
+        .await?;
+    let status = task.wait(&client, None).await?;
+    assert!(!status.success());
+
+    // Printing outside of a transaction should not record any events.
+    assert!(task.events(&client).await?.is_empty());
+
+    let logs = task
+        .read_logs(&client)
+        .try_fold(String::new(), |mut acc, item| {
+            acc.push_str(&item);
+            std::future::ready(Ok(acc))
+        })
+        .await?;
+
+    assert!(
+        logs.starts_with("before the panic\n"),
+        "unexpected logs: {logs}"
+    );
+    assert!(
+        logs.contains("something went wrong"),
+        "unexpected logs: {logs}"
+    );
+
+    Ok(())
+}
+
+#[sqlx::test]
+async fn launch_with_dedupe_key(pool: sqlx::PgPool) -> anyhow::Result<()> {
+    let client = DurableClient::new(pool)?;
+    let program = crate::load_binary(&client, "task-details.wasm").await?;
+
+    let first = client
+        .launch_many(
+            &program,
+            [
+                LaunchOptions::new("task a", ()).dedupe_key("a"),
+                LaunchOptions::new("task b", ()),
+                LaunchOptions::new("task a again", ()).dedupe_key("a"),
+            ],
+        )
+        .await?;
+    assert_eq!(first[0].id(), first[2].id());
+    assert_ne!(first[0].id(), first[1].id());
+
+    let second = client
+        .launch_many(
+            &program,
+            [
+                LaunchOptions::new("task c", ()).dedupe_key("c"),
+                LaunchOptions::new("task a", ()).dedupe_key("a"),
+            ],
+        )
+        .await?;
+    assert_eq!(second[1].id(), first[0].id());
+    assert!(![first[0].id(), first[1].id()].contains(&second[0].id()));
+
+    // Dedupe keys are scoped to a tenant.
+    let tenant = client.with_tenant("team-a");
+    let program = crate::load_binary(&tenant, "task-details.wasm").await?;
+    let third = tenant
+        .launch_many(&program, [LaunchOptions::new("task a", ()).dedupe_key("a")])
+        .await?;
+    assert_ne!(third[0].id(), first[0].id());
+
+    Ok(())
+}
+
+#[sqlx::test]
+async fn delayed_launch(pool: sqlx::PgPool) -> anyhow::Result<()> {
+    let _guard = durable_test::spawn_worker(pool.clone()).await?;
+    let client = DurableClient::new(pool.clone())?;
+    let program = crate::load_binary(&client, "task-details.wasm").await?;
+
+    let delay = chrono::Duration::seconds(2);
+    let run_at = chrono::Utc::now() + delay;
+    let tasks = client
+        .launch_many(
+            &program,
+            [LaunchOptions::new("delayed task", ()).run_at(run_at)],
+        )
+        .await?;
+    let task = &tasks[0];
+
+    tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+    let state = sqlx::query_scalar!(
+        r#"SELECT state::text as "state!" FROM durable.task WHERE id = $1"#,
+        task.id()
+    )
+    .fetch_one(&pool)
+    .await?;
+    assert_eq!(state, "ready");
+
+    let status = tokio::time::timeout(std::time::Duration::from_secs(30), task.wait(&client, None))
+        .await??;
+    assert!(status.success());
+
+    assert!(chrono::Utc::now() >= run_at);
+
+    Ok(())
+}
+
+#[sqlx::test]
+async fn oversized_data_is_stored_separately(pool: sqlx::PgPool) -> anyhow::Result<()> {
+    let _guard = durable_test::spawn_worker(pool.clone()).await?;
+    let client = DurableClient::new(pool.clone())?.with_max_inline_data(16);
+    let program = crate::load_binary(&client, "task-details.wasm").await?;
+
+    let data = serde_json::json!({ "items": ["a", "b", "c", "d", "e", "f"] });
+    let tasks = client
+        .launch_many(
+            &program,
+            [
+                LaunchOptions::new("small", serde_json::json!([1])),
+                LaunchOptions::new("large", data.clone()),
+            ],
+        )
+        .await?;
+
+    let (inline, payload): (String, Option<String>) = sqlx::query_as(
+        "SELECT task.data::text, payload.data::text
+          FROM durable.task
+          LEFT JOIN durable.task_payload payload ON payload.task_id = task.id
+         WHERE task.id = $1",
+    )
+    .bind(tasks[1].id())
+    .fetch_one(&pool)
+    .await?;
+    assert_eq!(inline, "null");
+    assert_eq!(
+        payload.as_deref().map(serde_json::from_str).transpose()?,
+        Some(data.clone())
+    );
+
+    for task in &tasks {
+        let status = task.wait(&client, None).await?;
+        assert!(status.success());
+    }
+
+    let logs = tasks[1]
+        .read_logs(&client)
+        .try_fold(String::new(), |mut acc, item| {
+            acc.push_str(&item);
+            std::future::ready(Ok(acc))
+        })
+        .await?;
+    assert!(logs.contains(&format!("data: {data}\n")));
+
+    let (count,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM durable.task_payload")
+        .fetch_one(&pool)
+        .await?;
+    assert_eq!(count, 1);
+
+    Ok(())
+}
+
+#[sqlx::test]
+async fn launch_validates_data_schema(pool: sqlx::PgPool) -> anyhow::Result<()> {
+    let client = DurableClient::new(pool.clone())?;
+    let options = ProgramOptions::from_file(crate::test_binary("task-details.wasm"))?.data_schema(
+        serde_json::json!({
+            "type": "object",
+            "properties": { "count": { "type": "integer" } },
+            "required": ["count"]
+        }),
+    );
+    let program = client.program(options).await?;
+
+    client
+        .launch("valid", &program, &serde_json::json!({ "count": 1 }))
+        .await?;
+
+    let error = client
+        .launch("invalid", &program, &serde_json::json!({ "count": "one" }))
+        .await
+        .expect_err("launched a task with invalid data");
+    let validation = error
+        .validation_error()
+        .expect("error was not a validation error");
+    assert_eq!(validation.task(), "invalid");
+    assert_eq!(validation.violations().len(), 1);
+    assert_eq!(validation.violations()[0].path(), "/count");
+
+    let (count,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM durable.task")
+        .fetch_one(&pool)
+        .await?;
+    assert_eq!(count, 1);
+
+    Ok(())
+}
+
+#[sqlx::test]
+async fn panic_records_failure(pool: sqlx::PgPool) -> anyhow::Result<()> {
+    let _guard = durable_test::spawn_worker(pool.clone()).await?;
+    let client = DurableClient::new(pool)?;
+    let program = crate::load_binary(&client, "print-then-panic.wasm").await?;
+
+    let task = client
+        .launch("test task", &program, &serde_json::json!(null))
+        .await?;
+    let status = task.wait(&client, None).await?;
+    assert!(!status.success());
+
+    let failure = status
+        .failure()
+        .context("failed task had no failure details")?;
+    assert!(!failure.message().is_empty());
+
+    // The task never entered a transaction.
+    assert_eq!(failure.label(), None);
+    assert_eq!(failure.index(), None);
+    assert!(failure.backtrace().is_some());
+
+    Ok(())
+}
+
+#[sqlx::test]
+async fn panic_is_reported(pool: sqlx::PgPool) -> anyhow::Result<()> {
+    let _guard = durable_test::spawn_worker(pool.clone()).await?;
+    let client = DurableClient::new(pool)?;
+    let program = crate::load_binary(&client, "panic-in-transaction.wasm").await?;
+
+    let task = client
+        .launch("test task", &program, &serde_json::json!(null))
+        .await?;
+    let status = task.wait(&client, None).await?;
+    assert!(!status.success());
+
+    let failure = status
+        .failure()
+        .context("failed task had no failure details")?;
+    assert!(
+        failure
+            .message()
+            .starts_with("panicked at src/bin/panic-in-transaction.rs:5:"),
+        "unexpected failure message: {:?}",
+        failure.message()
+    );
+    assert!(failure.message().ends_with(": something went wrong"));
+
+    // The failure should point at the transaction the panic happened in, not
+    // the one used by the panic hook to print the message.
+    assert_eq!(failure.label(), Some("panics"));
+    assert_eq!(failure.index(), Some(1));
+
+    Ok(())
+}
+
+#[sqlx::test]
+async fn failed_task_can_be_rerun(pool: sqlx::PgPool) -> anyhow::Result<()> {
+    let _guard = durable_test::spawn_worker(pool.clone()).await?;
+    let client = DurableClient::new(pool)?;
+    let program = crate::load_binary(&client, "print-then-panic.wasm").await?;
+
+    let task = client
+        .launch("test task", &program, &serde_json::json!({ "attempt": 1 }))
+        .await?;
+    let status = task.wait(&client, None).await?;
+    assert!(!status.success());
+
+    let rerun = task.clone_and_launch(&client).await?;
+    assert_ne!(rerun.id(), task.id());
+    assert_eq!(
+        rerun.cloned_from(&client).await?.map(|task| task.id()),
+        Some(task.id())
+    );
+
+    let status = rerun.wait(&client, None).await?;
+    assert!(!status.success());
+
+    Ok(())
+}
+
+#[sqlx::test]
+async fn claim_ahead_runs_all_tasks(pool: sqlx::PgPool) -> anyhow::Result<()> {
+    let config = durable_runtime::Config::new()
+        .max_tasks(2)
+        .claim_batch_size(3)
+        .claim_ahead(2);
+
+    let _guard = durable_test::spawn_worker_with(pool.clone(), config).await?;
+    let client = DurableClient::new(pool)?;
+    let program = crate::load_binary(&client, "task-details.wasm").await?;
+
+    let tasks = client
+        .launch_many(
+            &program,
+            (0..10).map(|i| LaunchOptions::new(format!("task {i}"), ())),
+        )
+        .await?;
+
+    for task in &tasks {
+        let status =
+            tokio::time::timeout(std::time::Duration::from_secs(60), task.wait(&client, None))
+                .await??;
+        assert!(status.success());
+    }
+
+    Ok(())
+}
+
+#[sqlx::test]
+async fn claim_ahead_released_task_runs_on_other_worker(pool: sqlx::PgPool) -> anyhow::Result<()> {
+    use std::time::Duration;
+
+    async fn wait_until_claimed(pool: &sqlx::PgPool, task_id: i64) -> anyhow::Result<()> {
+        loop {
+            let running_on =
+                sqlx::query_scalar!("SELECT running_on FROM durable.task WHERE id = $1", task_id)
+                    .fetch_one(pool)
+                    .await?;
+
+            if running_on.is_some() {
+                return Ok(());
+            }
+
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+    }
+
+    // The blocker never completes, so the first worker can only buffer the
+    // second task and has to release it once the claim-ahead timeout expires.
+    let config = durable_runtime::Config::new()
+        .max_tasks(1)
+        .claim_ahead(1)
+        .claim_ahead_timeout(Duration::from_secs(2))
+        .suspend_margin(Duration::from_secs(600))
+        .suspend_timeout(Duration::from_secs(600));
+
+    let _guard1 = durable_test::spawn_worker_with(pool.clone(), config).await?;
+    let client = DurableClient::new(pool.clone())?;
+    let blocker = crate::load_binary(&client, "notify-wait.wasm").await?;
+    let program = crate::load_binary(&client, "task-details.wasm").await?;
+
+    let blocker = client
+        .launch("blocker", &blocker, &serde_json::json!(null))
+        .await?;
+    tokio::time::timeout(
+        Duration::from_secs(30),
+        wait_until_claimed(&pool, blocker.id()),
+    )
+    .await??;
+
+    let task = client
+        .launch("released", &program, &serde_json::json!(null))
+        .await?;
+    tokio::time::timeout(
+        Duration::from_secs(30),
+        wait_until_claimed(&pool, task.id()),
+    )
+    .await??;
+
+    let _guard2 = durable_test::spawn_worker(pool.clone()).await?;
+
+    let status = tokio::time::timeout(Duration::from_secs(30), task.wait(&client, None)).await??;
+    assert!(status.success());
+
+    let state = sqlx::query_scalar!(
+        r#"SELECT state::text as "state!" FROM durable.task WHERE id = $1"#,
+        blocker.id()
+    )
+    .fetch_one(&pool)
+    .await?;
+    assert_eq!(state, "active");
+
+    Ok(())
+}
+
+#[sqlx::test]
+async fn group_commit_records_events(pool: sqlx::PgPool) -> anyhow::Result<()> {
+    let config = durable_runtime::Config::new()
+        .group_commit_window(std::time::Duration::from_millis(10))
+        .group_commit_max_events(4);
+
+    let _guard = durable_test::spawn_worker_with(pool.clone(), config).await?;
+    let client = DurableClient::new(pool)?;
+    let program = crate::load_binary(&client, "transactions.wasm").await?;
+
+    let tasks = client
+        .launch_many(
+            &program,
+            (0..10).map(|i| LaunchOptions::new(format!("task {i}"), ())),
+        )
+        .await?;
+
+    for task in &tasks {
+        let status = task.wait(&client, None).await?;
+        assert!(status.success());
+
+        let labels: Vec<_> = task
+            .events(&client)
+            .await?
+            .into_iter()
+            .map(|event| event.label)
+            .collect();
+        assert_eq!(labels, ["first", "second"]);
+    }
+
+    Ok(())
+}
+
+#[sqlx::test]
+async fn task_metadata(pool: sqlx::PgPool) -> anyhow::Result<()> {
+    use std::time::Duration;
+
+    let _guard = durable_test::spawn_worker_with(
+        pool.clone(),
+        durable_runtime::Config::new()
+            .suspend_margin(Duration::ZERO)
+            .suspend_timeout(Duration::ZERO),
+    )
+    .await?;
+    let client = DurableClient::new(pool.clone())?.with_tenant("team-a");
+    let program = crate::load_binary(&client, "task-metadata.wasm").await?;
+
+    let task = client.launch("metadata", &program, &()).await?;
+
+    let suspended = async {
+        loop {
+            let state = sqlx::query_scalar!(
+                r#"SELECT state::text as "state!" FROM durable.task WHERE id = $1"#,
+                task.id()
+            )
+            .fetch_one(&pool)
+            .await?;
+
+            if state == "suspended" {
+                break;
+            }
+
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+
+        anyhow::Ok(())
+    };
+    tokio::time::timeout(Duration::from_secs(30), suspended)
+        .await
+        .context("task failed to suspend itself within 30s")??;
+
+    task.notify("resume", &(), &client).await?;
+
+    let status = tokio::time::timeout(Duration::from_secs(30), task.wait(&client, None)).await??;
+    let logs: Vec<String> = task.read_logs(&client).try_collect().await?;
+    assert!(status.success(), "task failed: {}", logs.concat());
+
+    let worker = sqlx::query_scalar!("SELECT id FROM durable.worker")
+        .fetch_one(&pool)
+        .await?;
+    assert_eq!(
+        logs.concat(),
+        format!("queue: team-a\nattempt: 1\nattempt: 2\nworker: {worker}\n")
+    );
+
+    Ok(())
+}
+
+#[sqlx::test]
+async fn task_labels(pool: sqlx::PgPool) -> anyhow::Result<()> {
+    use durable_client::{TaskFilter, TaskState};
+
+    let _guard = durable_test::spawn_worker(pool.clone()).await?;
+    let client = DurableClient::new(pool)?;
+    let program = crate::load_binary(&client, "task-labels.wasm").await?;
+
+    let tasks = client
+        .launch_many(
+            &program,
+            [
+                LaunchOptions::new("acme", ())
+                    .label("customer", "acme")
+                    .label("region", "eu"),
+                LaunchOptions::new("globex", ()).label("customer", "globex"),
+            ],
+        )
+        .await?;
+
+    for task in &tasks {
+        let status = task.wait(&client, None).await?;
+        assert!(status.success());
+    }
+
+    let logs: Vec<String> = tasks[0].read_logs(&client).try_collect().await?;
+    assert_eq!(logs.concat(), "customer=acme\nregion=eu\n");
+
+    let found = client
+        .list_tasks(&TaskFilter::new().label("customer", "acme"))
+        .await?;
+    assert_eq!(found.len(), 1);
+    assert_eq!(found[0].id(), tasks[0].id());
+    assert_eq!(found[0].state(), TaskState::Complete);
+
+    let found = client
+        .list_tasks(
+            &TaskFilter::new()
+                .label("customer", "acme")
+                .label("region", "us"),
+        )
+        .await?;
+    assert!(found.is_empty());
+
+    Ok(())
+}
+
+#[sqlx::test]
+async fn task_metadata_updates(pool: sqlx::PgPool) -> anyhow::Result<()> {
+    let _guard = durable_test::spawn_worker(pool.clone()).await?;
+    let client = DurableClient::new(pool)?;
+    let program = crate::load_binary(&client, "set-metadata.wasm").await?;
+
+    let task = client.launch("metadata", &program, &()).await?;
+    let status = task.wait(&client, None).await?;
+    assert!(status.success());
+
+    // Postgres normalizes jsonb values, which reorders the keys of `step`.
+    let metadata: std::collections::BTreeMap<_, _> = task
+        .metadata(&client)
+        .await?
+        .into_iter()
+        .map(|(key, value)| (key, value.get().to_owned()))
+        .collect();
+    assert_eq!(
+        metadata,
+        [
+            ("done".to_owned(), "true".to_owned()),
+            (
+                "step".to_owned(),
+                r#"{"of": 3, "name": "charge"}"#.to_owned()
+            ),
+        ]
+        .into()
+    );
+
+    Ok(())
+}
@@ -0,0 +1,33 @@
+use std::time::Duration;
+
+use durable_client::DurableClient;
+use durable_runtime::Config;
+use futures::TryStreamExt;
+
+#[sqlx::test]
+async fn reports_worker_capabilities(pool: sqlx::PgPool) -> anyhow::Result<()> {
+    let _guard = durable_test::spawn_worker_with(
+        pool.clone(),
+        Config::new().max_http_timeout(Duration::from_secs(5)),
+    )
+    .await?;
+    let client = DurableClient::new(pool)?;
+    let program = crate::load_binary(&client, "capabilities.wasm").await?;
+
+    let task = client.launch("capabilities", &program, &()).await?;
+    let status = tokio::time::timeout(Duration::from_secs(30), task.wait(&client, None)).await??;
+    let logs: Vec<String> = task.read_logs(&client).try_collect().await?;
+    assert!(status.success(), "task failed: {}", logs.concat());
+
+    assert_eq!(
+        logs.concat(),
+        "durable:core/core: true\ndurable:core/object-store: false\nmax_http_timeout: 5s\n"
+    );
+
+    // The capabilities are recorded so that replaying the task on a different
+    // worker sees the same thing.
+    let events = task.events(&client).await?;
+    assert_eq!(events[0].label, "durable::capabilities");
+
+    Ok(())
+}
@@ -0,0 +1,91 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use durable_client::DurableClient;
+use durable_runtime::chaos::{Chaos, Fault, FaultPoint, Injection};
+use durable_runtime::replay::ReplayTask;
+use durable_runtime::{Config, TaskStatus};
+use durable_test::ReplayHarness;
+use serde_json::value::RawValue;
+
+#[sqlx::test]
+async fn killed_worker_task_is_rerun(pool: sqlx::PgPool) -> anyhow::Result<()> {
+    let client = DurableClient::new(pool.clone())?;
+    let program = crate::load_binary(&client, "transactions.wasm").await?;
+
+    let chaos = Arc::new(Chaos::new());
+    chaos.inject(Injection::new(FaultPoint::TransactionExit, Fault::KillWorker).index(1));
+
+    let first =
+        durable_test::spawn_chaos_worker(pool.clone(), Config::new(), chaos.clone()).await?;
+    let task = client.launch("killed", &program, &()).await?;
+
+    tokio::time::timeout(Duration::from_secs(30), first)
+        .await
+        .expect("worker was not killed within 30s")?;
+
+    let triggered = chaos.triggered();
+    assert_eq!(triggered.len(), 1);
+    assert_eq!(triggered[0].task_id, task.id());
+
+    // Only the first transaction made it into the database before the worker died.
+    assert_eq!(task.events(&client).await?.len(), 1);
+
+    let _second = durable_test::spawn_worker_with(
+        pool,
+        Config::new().heartbeat_timeout(Duration::from_secs(1)),
+    )
+    .await?;
+
+    let status = task.wait(&client, Some(Duration::from_secs(30))).await?;
+    assert!(status.success());
+
+    let harness = ReplayHarness::from_file(crate::test_binary("transactions.wasm"))?;
+    let replay = ReplayTask::new(task.id(), "killed", RawValue::from_string("null".into())?);
+    let status = harness.assert_exactly_once(&client, &task, replay).await?;
+    assert_eq!(status, TaskStatus::ExitSuccess);
+
+    Ok(())
+}
+
+#[sqlx::test]
+async fn dropped_connection_is_retried(pool: sqlx::PgPool) -> anyhow::Result<()> {
+    let client = DurableClient::new(pool.clone())?;
+    let program = crate::load_binary(&client, "transactions.wasm").await?;
+
+    let chaos = Arc::new(Chaos::new());
+    chaos.inject(Injection::new(FaultPoint::TransactionExit, Fault::DropConnection).index(0));
+    chaos.inject(
+        Injection::new(
+            FaultPoint::TransactionExit,
+            Fault::Delay(Duration::from_millis(500)),
+        )
+        .index(1),
+    );
+
+    let _guard = durable_test::spawn_chaos_worker(pool, Config::new(), chaos.clone()).await?;
+    let task = client.launch("dropped", &program, &()).await?;
+
+    let status = task.wait(&client, Some(Duration::from_secs(30))).await?;
+    assert!(status.success());
+
+    let faults: Vec<_> = chaos
+        .triggered()
+        .into_iter()
+        .map(|triggered| (triggered.index, triggered.fault))
+        .collect();
+    assert_eq!(
+        faults,
+        [
+            (0, Fault::DropConnection),
+            (1, Fault::Delay(Duration::from_millis(500)))
+        ]
+    );
+
+    let harness = ReplayHarness::from_file(crate::test_binary("transactions.wasm"))?;
+    let replay = ReplayTask::new(task.id(), "dropped", RawValue::from_string("null".into())?);
+    let status = harness.assert_exactly_once(&client, &task, replay).await?;
+    assert_eq!(status, TaskStatus::ExitSuccess);
+
+    Ok(())
+}
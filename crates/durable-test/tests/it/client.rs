@@ -0,0 +1,33 @@
+use std::time::Duration;
+
+use durable_client::DurableClientBuilder;
+
+#[sqlx::test]
+async fn builder_configures_connections(pool: sqlx::PgPool) -> anyhow::Result<()> {
+    let client = DurableClientBuilder::from_options((*pool.connect_options()).clone())
+        .max_connections(2)
+        .statement_timeout(Duration::from_secs(5))
+        .application_name("durable-test")
+        .search_path(["durable", "public"])
+        .tenant("builder")
+        .connect()
+        .await?;
+
+    assert_eq!(client.tenant(), Some("builder"));
+    assert_eq!(client.pool().options().get_max_connections(), 2);
+
+    let (name, timeout, schemas): (String, String, Vec<String>) = sqlx::query_as(
+        "SELECT
+            current_setting('application_name'),
+            current_setting('statement_timeout'),
+            current_schemas(false)::text[]",
+    )
+    .fetch_one(client.pool())
+    .await?;
+
+    assert_eq!(name, "durable-test");
+    assert_eq!(timeout, "5s");
+    assert_eq!(schemas, ["durable", "public"]);
+
+    Ok(())
+}
@@ -0,0 +1,141 @@
+//! Conformance tests for workflows that are not written in Rust.
+//!
+//! Each conformance workflow implements the same behaviour in a different
+//! language, using nothing but the published `durable:core` WIT package. The
+//! sources live in `crates/durable-test/conformance`. Workflows whose
+//! toolchain was not installed when the tests were set up are skipped.
+
+use std::path::PathBuf;
+
+use durable_client::{DurableClient, Program, ProgramOptions};
+
+fn conformance_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("conformance")
+}
+
+/// Load a conformance workflow built by `conformance/build.sh`, if it exists.
+async fn load_conformance(client: &DurableClient, name: &str) -> anyhow::Result<Option<Program>> {
+    let Some(dir) = std::env::var_os("DURABLE_TEST_CONFORMANCE_DIR") else {
+        eprintln!("DURABLE_TEST_CONFORMANCE_DIR is not set, skipping {name}");
+        return Ok(None);
+    };
+
+    let path = PathBuf::from(dir).join(name);
+    if !path.exists() {
+        eprintln!("{name} was not built, skipping");
+        return Ok(None);
+    }
+
+    let program = client.program(ProgramOptions::from_file(path)?).await?;
+    Ok(Some(program))
+}
+
+/// Run the `echo` workflow and check that it recorded the task data.
+async fn check_echo(client: &DurableClient, program: &Program) -> anyhow::Result<()> {
+    let data = serde_json::json!({
+        "message": "hello from the conformance tests",
+        "values": [1, 2.5, null, true],
+    });
+
+    let task = client.launch("conformance echo", program, &data).await?;
+    let status = task.wait(client, None).await?;
+    assert!(status.success(), "echo workflow failed: {status:?}");
+
+    let events = task.events(client).await?;
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].label, "echo");
+
+    let value: serde_json::Value = serde_json::from_str(events[0].value.get())?;
+    assert_eq!(value, data);
+
+    Ok(())
+}
+
+#[sqlx::test]
+async fn echo_wat(pool: sqlx::PgPool) -> anyhow::Result<()> {
+    let _guard = durable_test::spawn_worker(pool.clone()).await?;
+    let client = DurableClient::new(pool)?;
+
+    let wasm = wat::parse_file(conformance_dir().join("echo.wat"))?;
+    let program = client.program(ProgramOptions::new(wasm)).await?;
+
+    check_echo(&client, &program).await
+}
+
+/// Build the `echo` workflow against an older version of `durable:core`.
+fn echo_with_version(version: &str) -> anyhow::Result<Vec<u8>> {
+    let wat = std::fs::read_to_string(conformance_dir().join("echo.wat"))?
+        .replace("durable:core/core@2.7.0", &format!("durable:core/core@{version}"));
+
+    Ok(wat::parse_str(wat)?)
+}
+
+#[sqlx::test]
+async fn older_interface_version(pool: sqlx::PgPool) -> anyhow::Result<()> {
+    let _guard = durable_test::spawn_worker(pool.clone()).await?;
+    let client = DurableClient::new(pool)?;
+
+    let wasm = echo_with_version("2.0.0")?;
+    let program = client.program(ProgramOptions::new(wasm)).await?;
+
+    check_echo(&client, &program).await
+}
+
+#[sqlx::test]
+async fn deny_deprecated_interface_version(pool: sqlx::PgPool) -> anyhow::Result<()> {
+    let config = durable_runtime::Config::new().deny_deprecated_interfaces(true);
+    let _guard = durable_test::spawn_worker_with(pool.clone(), config).await?;
+    let client = DurableClient::new(pool)?;
+
+    let wasm = echo_with_version("2.0.0")?;
+    let program = client.program(ProgramOptions::new(wasm)).await?;
+
+    let task = client
+        .launch("deprecated echo", &program, &serde_json::json!(null))
+        .await?;
+    let status = task.wait(&client, None).await?;
+    assert!(!status.success());
+
+    Ok(())
+}
+
+#[sqlx::test]
+async fn newer_interface_version(pool: sqlx::PgPool) -> anyhow::Result<()> {
+    let _guard = durable_test::spawn_worker(pool.clone()).await?;
+    let client = DurableClient::new(pool)?;
+
+    let wasm = echo_with_version("2.99.0")?;
+    let program = client.program(ProgramOptions::new(wasm)).await?;
+
+    let task = client
+        .launch("future echo", &program, &serde_json::json!(null))
+        .await?;
+    let status = task.wait(&client, None).await?;
+    assert!(!status.success());
+
+    Ok(())
+}
+
+#[sqlx::test]
+async fn echo_python(pool: sqlx::PgPool) -> anyhow::Result<()> {
+    let _guard = durable_test::spawn_worker(pool.clone()).await?;
+    let client = DurableClient::new(pool)?;
+
+    let Some(program) = load_conformance(&client, "echo-python.wasm").await? else {
+        return Ok(());
+    };
+
+    check_echo(&client, &program).await
+}
+
+#[sqlx::test]
+async fn echo_js(pool: sqlx::PgPool) -> anyhow::Result<()> {
+    let _guard = durable_test::spawn_worker(pool.clone()).await?;
+    let client = DurableClient::new(pool)?;
+
+    let Some(program) = load_conformance(&client, "echo-js.wasm").await? else {
+        return Ok(());
+    };
+
+    check_echo(&client, &program).await
+}
@@ -0,0 +1,85 @@
+use durable_client::{DependencyFailure, DurableClient, LaunchOptions};
+use futures::TryStreamExt;
+
+#[sqlx::test]
+async fn dependent_task_runs_after_dependencies(pool: sqlx::PgPool) -> anyhow::Result<()> {
+    let _guard = durable_test::spawn_worker(pool.clone()).await?;
+    let client = DurableClient::new(pool.clone())?;
+    let program = crate::load_binary(&client, "task-details.wasm").await?;
+
+    let a = client.launch("a", &program, &()).await?;
+    let c = client.launch("c", &program, &()).await?;
+    let b = client
+        .launch_many(&program, [LaunchOptions::new("b", ()).after([&a, &c])])
+        .await?
+        .remove(0);
+
+    let status = b.wait(&client, None).await?;
+    assert!(status.success());
+
+    let ordered = sqlx::query_scalar!(
+        r#"
+        SELECT b.completed_at >= a.completed_at AND b.completed_at >= c.completed_at as "ordered!"
+         FROM durable.task a, durable.task b, durable.task c
+        WHERE a.id = $1
+          AND b.id = $2
+          AND c.id = $3
+        "#,
+        a.id(),
+        b.id(),
+        c.id()
+    )
+    .fetch_one(&pool)
+    .await?;
+    assert!(ordered);
+
+    Ok(())
+}
+
+#[sqlx::test]
+async fn dependency_failure_propagates(pool: sqlx::PgPool) -> anyhow::Result<()> {
+    let _guard = durable_test::spawn_worker(pool.clone()).await?;
+    let client = DurableClient::new(pool)?;
+    let failing = crate::load_binary(&client, "print-then-panic.wasm").await?;
+    let program = crate::load_binary(&client, "task-details.wasm").await?;
+
+    let a = client.launch("a", &failing, &()).await?;
+    let tasks = client
+        .launch_many(
+            &program,
+            [
+                LaunchOptions::new("b", ()).after([&a]),
+                LaunchOptions::new("c", ())
+                    .after([&a])
+                    .dependency_failure(DependencyFailure::Ignore),
+            ],
+        )
+        .await?;
+    let d = client
+        .launch_many(&program, [LaunchOptions::new("d", ()).after(&tasks[..1])])
+        .await?
+        .remove(0);
+
+    assert!(!tasks[0].wait(&client, None).await?.success());
+    assert!(tasks[1].wait(&client, None).await?.success());
+    assert!(!d.wait(&client, None).await?.success());
+
+    let logs: Vec<String> = tasks[0].read_logs(&client).try_collect().await?;
+    assert_eq!(logs, [format!("dependency {} failed\n", a.id())]);
+
+    Ok(())
+}
+
+#[sqlx::test]
+async fn dependencies_must_exist(pool: sqlx::PgPool) -> anyhow::Result<()> {
+    let client = DurableClient::new(pool)?;
+    let program = crate::load_binary(&client, "task-details.wasm").await?;
+
+    let missing = durable_client::Task::from_id(i64::MAX);
+    let result = client
+        .launch_many(&program, [LaunchOptions::new("b", ()).after([&missing])])
+        .await;
+    assert!(result.is_err());
+
+    Ok(())
+}
@@ -0,0 +1,129 @@
+use std::net::SocketAddr;
+
+use durable_client::DurableClient;
+use durable_runtime::{Config, EmailConfig, SmtpConfig, SmtpTls};
+use futures::TryStreamExt;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::TcpListener;
+use tokio::task::JoinHandle;
+
+async fn run_email(pool: sqlx::PgPool, config: Config) -> anyhow::Result<String> {
+    let _guard = durable_test::spawn_worker_with(pool.clone(), config).await?;
+    let client = DurableClient::new(pool)?;
+    let program = crate::load_binary(&client, "email.wasm").await?;
+
+    let task = client
+        .launch("email", &program, &serde_json::json!(null))
+        .await?;
+    let status = task.wait(&client, None).await?;
+    assert!(status.success());
+
+    let logs = task
+        .read_logs(&client)
+        .try_fold(String::new(), |mut acc, item| {
+            acc.push_str(&item);
+            std::future::ready(Ok(acc))
+        })
+        .await?;
+
+    Ok(logs)
+}
+
+/// A minimal SMTP server that accepts a single message and returns the
+/// envelope recipients along with the message data.
+async fn smtp_server() -> anyhow::Result<(SocketAddr, JoinHandle<anyhow::Result<Message>>)> {
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let addr = listener.local_addr()?;
+
+    let handle = tokio::spawn(async move {
+        let (stream, _) = listener.accept().await?;
+        let (read, mut write) = stream.into_split();
+        let mut read = BufReader::new(read);
+        let mut message = Message::default();
+
+        write.write_all(b"220 localhost ESMTP\r\n").await?;
+
+        let mut line = String::new();
+        loop {
+            line.clear();
+            if read.read_line(&mut line).await? == 0 {
+                break;
+            }
+
+            let command = line.trim_end().to_ascii_uppercase();
+            if command.starts_with("EHLO") || command.starts_with("HELO") {
+                write.write_all(b"250 localhost\r\n").await?;
+            } else if let Some(rcpt) = command.strip_prefix("RCPT TO:") {
+                message.recipients.push(rcpt.to_ascii_lowercase());
+                write.write_all(b"250 OK\r\n").await?;
+            } else if command == "DATA" {
+                write.write_all(b"354 go ahead\r\n").await?;
+
+                loop {
+                    line.clear();
+                    read.read_line(&mut line).await?;
+                    if line == ".\r\n" {
+                        break;
+                    }
+                    message.data.push_str(&line);
+                }
+
+                write.write_all(b"250 OK\r\n").await?;
+            } else if command == "QUIT" {
+                write.write_all(b"221 bye\r\n").await?;
+                break;
+            } else {
+                write.write_all(b"250 OK\r\n").await?;
+            }
+        }
+
+        Ok(message)
+    });
+
+    Ok((addr, handle))
+}
+
+#[derive(Default)]
+struct Message {
+    recipients: Vec<String>,
+    data: String,
+}
+
+#[sqlx::test]
+async fn email_not_configured(pool: sqlx::PgPool) -> anyhow::Result<()> {
+    let logs = run_email(pool, Config::new()).await?;
+    assert_eq!(logs, "error: not-configured\n");
+
+    Ok(())
+}
+
+#[sqlx::test]
+async fn email_via_smtp(pool: sqlx::PgPool) -> anyhow::Result<()> {
+    let (addr, server) = smtp_server().await?;
+    let smtp = SmtpConfig::new(addr.ip().to_string())
+        .port(addr.port())
+        .tls(SmtpTls::None);
+    let config = Config::new().email(EmailConfig::new(smtp).default_from("workflows@example.com"));
+
+    let logs = run_email(pool, config).await?;
+    let message = server.await??;
+
+    let id = logs
+        .strip_prefix("sent: ")
+        .and_then(|id| id.strip_suffix('\n'))
+        .expect("the email was not sent");
+    assert!(id.starts_with("<durable."));
+    assert!(id.ends_with("@example.com>"));
+
+    assert_eq!(
+        message.recipients,
+        ["<alice@example.com>", "<audit@example.com>"]
+    );
+    assert!(message.data.contains(&format!("Message-ID: {id}\r\n")));
+    assert!(message.data.contains("Subject: Welcome, Alice & Bob!\r\n"));
+    assert!(message.data.contains("Hi Alice &amp; Bob, your report"));
+    assert!(message.data.contains("filename=\"report.csv\""));
+    assert!(!message.data.contains("audit@example.com"));
+
+    Ok(())
+}
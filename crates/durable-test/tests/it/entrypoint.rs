@@ -0,0 +1,94 @@
+use anyhow::Context;
+use durable_client::{DurableClient, LaunchOptions};
+
+async fn launch_entrypoint(
+    client: &DurableClient,
+    program: &durable_client::Program,
+    entrypoint: Option<&str>,
+) -> anyhow::Result<(bool, Vec<String>)> {
+    let mut options = LaunchOptions::new("entrypoint test", serde_json::json!(null));
+    if let Some(entrypoint) = entrypoint {
+        options = options.entrypoint(entrypoint);
+    }
+
+    let task = client
+        .launch_many(program, [options])
+        .await?
+        .pop()
+        .context("no task was launched")?;
+    let status = task.wait(client, None).await?;
+    let labels = task
+        .events(client)
+        .await?
+        .into_iter()
+        .map(|event| event.label)
+        .collect();
+
+    Ok((status.success(), labels))
+}
+
+#[sqlx::test]
+async fn exported_entrypoints(pool: sqlx::PgPool) -> anyhow::Result<()> {
+    let _guard = durable_test::spawn_worker(pool.clone()).await?;
+    let client = DurableClient::new(pool)?;
+    let program = crate::load_binary(&client, "entrypoints.wasm").await?;
+
+    // Without an entrypoint the task should run main as usual.
+    let (success, labels) = launch_entrypoint(&client, &program, None).await?;
+    assert!(success);
+    assert_eq!(labels, ["main"]);
+
+    let (success, labels) = launch_entrypoint(&client, &program, Some("sync-orders")).await?;
+    assert!(success);
+    assert_eq!(labels, ["sync-orders"]);
+
+    // An entrypoint returning an error should fail the task.
+    let (success, labels) = launch_entrypoint(&client, &program, Some("sync-invoices")).await?;
+    assert!(!success);
+    assert_eq!(labels, ["sync-invoices"]);
+
+    let (success, labels) = launch_entrypoint(&client, &program, Some("missing")).await?;
+    assert!(!success);
+    assert!(labels.is_empty());
+
+    Ok(())
+}
+
+#[sqlx::test]
+async fn entrypoint_without_export_fails(pool: sqlx::PgPool) -> anyhow::Result<()> {
+    let _guard = durable_test::spawn_worker(pool.clone()).await?;
+    let client = DurableClient::new(pool)?;
+    let program = crate::load_binary(&client, "transactions.wasm").await?;
+
+    let (success, labels) = launch_entrypoint(&client, &program, Some("sync-orders")).await?;
+    assert!(!success);
+    assert!(labels.is_empty());
+
+    Ok(())
+}
+
+#[sqlx::test]
+async fn failed_entrypoint_records_failure(pool: sqlx::PgPool) -> anyhow::Result<()> {
+    let _guard = durable_test::spawn_worker(pool.clone()).await?;
+    let client = DurableClient::new(pool)?;
+    let program = crate::load_binary(&client, "entrypoints.wasm").await?;
+
+    let options =
+        LaunchOptions::new("entrypoint test", serde_json::json!(null)).entrypoint("sync-invoices");
+    let task = client
+        .launch_many(&program, [options])
+        .await?
+        .pop()
+        .context("no task was launched")?;
+    let status = task.wait(&client, None).await?;
+    assert!(!status.success());
+
+    let failure = status
+        .failure()
+        .context("failed task had no failure details")?;
+    assert_eq!(failure.label(), Some("sync-invoices"));
+    assert_eq!(failure.index(), Some(0));
+    assert!(failure.backtrace().is_none());
+
+    Ok(())
+}
@@ -0,0 +1,35 @@
+use durable_client::DurableClient;
+use futures::TryStreamExt;
+
+#[sqlx::test]
+async fn fanout_over_child_tasks(pool: sqlx::PgPool) -> anyhow::Result<()> {
+    let _guard = durable_test::spawn_worker(pool.clone()).await?;
+    let client = DurableClient::new(pool.clone())?;
+    let program = crate::load_binary(&client, "fanout.wasm").await?;
+
+    let task = client.launch("fanout", &program, &()).await?;
+    let status = task.wait(&client, None).await?;
+    let logs: Vec<String> = task.read_logs(&client).try_collect().await?;
+    assert!(status.success(), "task failed: {}", logs.concat());
+
+    assert_eq!(
+        logs.concat(),
+        "[0, 1, 4, 9, 16, 25, 36, 49, 64, 81]\n\
+         ok 2\n\
+         failed\n\
+         ok 4\n\
+         fail-fast: true\n\
+         notification: before\n"
+    );
+
+    // 4 batches of squares, 3 checked children, and 2 fail-fast children.
+    let children = sqlx::query_scalar!(
+        r#"SELECT COUNT(*) as "count!" FROM durable.task WHERE parent_id = $1"#,
+        task.id()
+    )
+    .fetch_one(&pool)
+    .await?;
+    assert_eq!(children, 9);
+
+    Ok(())
+}
@@ -0,0 +1,90 @@
+use std::time::Duration;
+
+use durable_client::DurableClient;
+use durable_runtime::{Config, DirPerms, ScratchDir};
+use futures::TryStreamExt;
+
+#[sqlx::test]
+async fn preopened_directories(pool: sqlx::PgPool) -> anyhow::Result<()> {
+    let base = std::env::temp_dir().join(format!("durable-fs-preopen-{}", std::process::id()));
+    let data = base.join("data");
+    let scratch = base.join("scratch");
+    std::fs::create_dir_all(&data)?;
+    std::fs::create_dir_all(&scratch)?;
+    std::fs::write(data.join("input.txt"), "hello from the host")?;
+
+    let config = Config::new()
+        .suspend_margin(Duration::from_secs(1))
+        .suspend_timeout(Duration::from_secs(1))
+        .preopen(&data, "/data", DirPerms::Read)
+        .preopen(&scratch, "/scratch", DirPerms::ReadWrite);
+
+    let _guard = durable_test::spawn_worker_with(pool.clone(), config).await?;
+    let client = DurableClient::new(pool)?;
+    let program = crate::load_binary(&client, "fs-preopen.wasm").await?;
+
+    let task = client
+        .launch("fs-preopen", &program, &serde_json::json!(null))
+        .await?;
+    let status = task.wait(&client, None).await?;
+    assert!(status.success());
+
+    let logs = task
+        .read_logs(&client)
+        .try_fold(String::new(), |mut acc, item| {
+            acc.push_str(&item);
+            std::future::ready(Ok(acc))
+        })
+        .await?;
+
+    assert_eq!(logs, "output.txt\n");
+    assert_eq!(
+        std::fs::read_to_string(scratch.join("output.txt"))?,
+        "HELLO FROM THE HOST"
+    );
+    assert_eq!(
+        std::fs::read_to_string(data.join("input.txt"))?,
+        "hello from the host"
+    );
+
+    std::fs::remove_dir_all(&base)?;
+
+    Ok(())
+}
+
+#[sqlx::test]
+async fn scratch_dir_snapshot(pool: sqlx::PgPool) -> anyhow::Result<()> {
+    let config = Config::new()
+        .suspend_margin(Duration::from_secs(1))
+        .suspend_timeout(Duration::from_secs(1))
+        .scratch_dir(ScratchDir::new("/scratch").snapshot(true));
+
+    let _guard = durable_test::spawn_worker_with(pool.clone(), config).await?;
+    let client = DurableClient::new(pool.clone())?;
+    let program = crate::load_binary(&client, "fs-scratch.wasm").await?;
+
+    let task = client
+        .launch("fs-scratch", &program, &serde_json::json!(null))
+        .await?;
+    let status = task.wait(&client, None).await?;
+    assert!(status.success());
+
+    let logs = task
+        .read_logs(&client)
+        .try_fold(String::new(), |mut acc, item| {
+            acc.push_str(&item);
+            std::future::ready(Ok(acc))
+        })
+        .await?;
+    assert_eq!(logs, "outside inside\n");
+
+    let snapshots: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM durable.event WHERE task_id = $1 AND scratch IS NOT NULL",
+    )
+    .bind(task.id())
+    .fetch_one(&pool)
+    .await?;
+    assert_eq!(snapshots, 1);
+
+    Ok(())
+}
@@ -0,0 +1,41 @@
+use durable_client::Task;
+use durable_test::fuzz::ReplayFuzzer;
+use futures::FutureExt;
+
+const STEPS: i32 = 2;
+
+/// Check that each step of the `exactly-once` workflow inserted its row exactly
+/// once.
+async fn side_effects_once(pool: sqlx::PgPool, task: &Task) -> anyhow::Result<()> {
+    let counts: Vec<(i32, i64)> = sqlx::query_as(
+        "SELECT step, count(*) FROM exactly_once WHERE task_id = $1 GROUP BY step ORDER BY step",
+    )
+    .bind(task.id())
+    .fetch_all(&pool)
+    .await?;
+
+    let expected: Vec<_> = (0..STEPS).map(|step| (step, 1)).collect();
+    anyhow::ensure!(
+        counts == expected,
+        "task {} has side effects {counts:?} instead of {expected:?}",
+        task.id()
+    );
+
+    Ok(())
+}
+
+#[sqlx::test]
+async fn exactly_once_under_faults(pool: sqlx::PgPool) -> anyhow::Result<()> {
+    sqlx::query("CREATE TABLE exactly_once(task_id bigint NOT NULL, step int NOT NULL)")
+        .execute(&pool)
+        .await?;
+
+    // Each step is a database transaction followed by waiting for a notification.
+    let check = pool.clone();
+    ReplayFuzzer::from_file(crate::test_binary("exactly-once.wasm"), STEPS * 2)?
+        .data(&STEPS)?
+        .notifications(STEPS as usize)
+        .invariant(move |_, task| side_effects_once(check.clone(), task).boxed())
+        .run(&pool)
+        .await
+}
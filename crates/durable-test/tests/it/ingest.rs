@@ -0,0 +1,88 @@
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use durable_client::{DurableClient, Task};
+use durable_runtime::ingest::{Message, TaskSource};
+use durable_runtime::{TaskMapping, WorkerBuilder};
+
+/// A source that hands out a fixed sequence of batches and then waits forever.
+struct BatchSource {
+    batches: VecDeque<Vec<Message>>,
+    commits: Arc<AtomicU32>,
+}
+
+#[async_trait::async_trait]
+impl TaskSource for BatchSource {
+    async fn receive(&mut self) -> anyhow::Result<Vec<Message>> {
+        match self.batches.pop_front() {
+            Some(batch) => Ok(batch),
+            None => std::future::pending().await,
+        }
+    }
+
+    async fn commit(&mut self) -> anyhow::Result<()> {
+        self.commits.fetch_add(1, Ordering::SeqCst);
+        Ok(())
+    }
+}
+
+#[sqlx::test]
+async fn ingest_launches_deduplicated_tasks(pool: sqlx::PgPool) -> anyhow::Result<()> {
+    let client = DurableClient::new(pool.clone())?;
+    crate::load_binary(&client, "task-details.wasm").await?;
+
+    let commits = Arc::new(AtomicU32::new(0));
+    let source = BatchSource {
+        batches: VecDeque::from([
+            vec![
+                Message::new("a", r#"{"order": 1}"#),
+                Message::new("b", r#"{"order": 2}"#),
+                Message::new("a", r#"{"order": 1}"#),
+            ],
+            vec![
+                // A redelivery of an earlier message along with one that isn't valid JSON.
+                Message::new("b", r#"{"order": 2}"#),
+                Message::new("c", "not json"),
+            ],
+        ]),
+        commits: commits.clone(),
+    };
+
+    let _guard = durable_test::spawn_worker_from(WorkerBuilder::new(pool.clone()).source(
+        "orders",
+        Box::new(source),
+        TaskMapping::new("task-details.wasm").task_name("ingested"),
+    ))
+    .await?;
+
+    tokio::time::timeout(Duration::from_secs(30), async {
+        while commits.load(Ordering::SeqCst) < 2 {
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+    })
+    .await?;
+
+    let tasks: Vec<(i64, String)> = sqlx::query_as(
+        "SELECT id, data::text FROM durable.task WHERE name = 'ingested' ORDER BY id ASC",
+    )
+    .fetch_all(&pool)
+    .await?;
+    let data: Vec<_> = tasks.iter().map(|(_, data)| data.as_str()).collect();
+    assert_eq!(data, [r#"{"order": 1}"#, r#"{"order": 2}"#]);
+
+    for (id, _) in tasks {
+        let status = Task::from_id(id).wait(&client, None).await?;
+        assert!(status.success());
+    }
+
+    let (keys,): (i64,) = sqlx::query_as(
+        "SELECT COUNT(*) FROM durable.ingest_dedupe WHERE source = 'orders' AND task_id IS NOT NULL",
+    )
+    .fetch_one(&pool)
+    .await?;
+    assert_eq!(keys, 2);
+
+    Ok(())
+}
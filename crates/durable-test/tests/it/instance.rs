@@ -0,0 +1,46 @@
+use std::time::Duration;
+
+use durable_client::{DurableClient, TaskFilter};
+use durable_runtime::Config;
+
+#[sqlx::test]
+async fn workers_only_run_tasks_from_their_instance(pool: sqlx::PgPool) -> anyhow::Result<()> {
+    let _blue =
+        durable_test::spawn_worker_with(pool.clone(), Config::new().instance("blue".into()))
+            .await?;
+    let client = DurableClient::new(pool.clone())?;
+    let blue = client.with_instance("blue");
+    let green = client.with_instance("green");
+    let program = crate::load_binary(&blue, "task-details.wasm").await?;
+
+    let blue_task = blue.launch("blue task", &program, &()).await?;
+    let green_task = green.launch("green task", &program, &()).await?;
+    let default_task = client.launch("default task", &program, &()).await?;
+
+    let status = blue_task.wait(&blue, Some(Duration::from_secs(30))).await?;
+    assert!(status.success());
+
+    for task in [&green_task, &default_task] {
+        let error = task
+            .wait(&client, Some(Duration::from_secs(2)))
+            .await
+            .expect_err("task ran on a worker from another instance");
+        assert!(error.is_timeout());
+    }
+
+    let listed = client
+        .list_tasks(&TaskFilter::new().instance("green"))
+        .await?;
+    assert_eq!(listed.len(), 1);
+    assert_eq!(listed[0].id(), green_task.id());
+    assert_eq!(listed[0].instance(), Some("green"));
+
+    let _green =
+        durable_test::spawn_worker_with(pool, Config::new().instance("green".into())).await?;
+    let status = green_task
+        .wait(&green, Some(Duration::from_secs(30)))
+        .await?;
+    assert!(status.success());
+
+    Ok(())
+}
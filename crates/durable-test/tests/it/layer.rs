@@ -0,0 +1,100 @@
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use durable_client::DurableClient;
+use durable_runtime::layer::{TaskInfo, TaskLayer, TaskOutcome};
+use durable_runtime::WorkerBuilder;
+
+/// A layer that records every hook that it sees.
+#[derive(Clone, Default)]
+struct Recorder {
+    calls: Arc<Mutex<Vec<String>>>,
+    reject: bool,
+}
+
+#[async_trait]
+impl TaskLayer for Recorder {
+    async fn before(&self, task: &TaskInfo) -> anyhow::Result<()> {
+        self.calls
+            .lock()
+            .unwrap()
+            .push(format!("before {} {}", task.name, task.attempt));
+
+        if self.reject {
+            anyhow::bail!("task {} is over quota", task.id);
+        }
+
+        Ok(())
+    }
+
+    async fn after(&self, task: &TaskInfo, outcome: &TaskOutcome) {
+        let outcome = match outcome {
+            TaskOutcome::Completed => "completed",
+            TaskOutcome::Failed { .. } => "failed",
+            TaskOutcome::Suspended => "suspended",
+            TaskOutcome::Taken => "taken",
+            _ => "unknown",
+        };
+
+        self.calls
+            .lock()
+            .unwrap()
+            .push(format!("after {} {outcome}", task.name));
+    }
+}
+
+#[sqlx::test]
+async fn layers_wrap_task_execution(pool: sqlx::PgPool) -> anyhow::Result<()> {
+    let layer = Recorder::default();
+    let _guard = durable_test::spawn_worker_from(
+        WorkerBuilder::new(pool.clone()).layer(Box::new(layer.clone())),
+    )
+    .await?;
+
+    let client = DurableClient::new(pool)?;
+    let program = crate::load_binary(&client, "task-details.wasm").await?;
+    let task = client.launch("layered task", &program, &()).await?;
+    let status = task.wait(&client, Some(Duration::from_secs(30))).await?;
+    assert!(status.success());
+
+    // The after hook runs once the task has been marked as complete, so it
+    // may not have been called yet.
+    tokio::time::timeout(Duration::from_secs(10), async {
+        while layer.calls.lock().unwrap().len() < 2 {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+    })
+    .await
+    .expect("the after hook was not called within 10s");
+
+    assert_eq!(
+        *layer.calls.lock().unwrap(),
+        ["before layered task 1", "after layered task completed"]
+    );
+
+    Ok(())
+}
+
+#[sqlx::test]
+async fn layer_errors_fail_the_task(pool: sqlx::PgPool) -> anyhow::Result<()> {
+    let layer = Recorder {
+        reject: true,
+        ..Recorder::default()
+    };
+    let _guard = durable_test::spawn_worker_from(
+        WorkerBuilder::new(pool.clone()).layer(Box::new(layer.clone())),
+    )
+    .await?;
+
+    let client = DurableClient::new(pool)?;
+    let program = crate::load_binary(&client, "task-details.wasm").await?;
+    let task = client.launch("rejected task", &program, &()).await?;
+    let status = task.wait(&client, Some(Duration::from_secs(30))).await?;
+    assert!(!status.success());
+
+    let failure = status.failure().expect("the task should have a failure");
+    assert!(failure.message().contains("over quota"));
+
+    Ok(())
+}
@@ -0,0 +1,86 @@
+use std::time::Duration;
+
+use durable_client::DurableClient;
+use durable_runtime::{Config, WorkerHandle};
+
+async fn wait_for(handle: &WorkerHandle, mut cond: impl FnMut(&WorkerHandle) -> bool) {
+    tokio::time::timeout(Duration::from_secs(10), async {
+        while !cond(handle) {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+    })
+    .await
+    .expect("worker did not reach the expected state within 10s");
+}
+
+#[sqlx::test]
+async fn leadership_can_be_handed_off(pool: sqlx::PgPool) -> anyhow::Result<()> {
+    let first = durable_test::spawn_worker(pool.clone()).await?;
+    wait_for(&first.handle(), |handle| handle.is_leader()).await;
+
+    let second = durable_test::spawn_worker(pool.clone()).await?;
+    wait_for(&second.handle(), |handle| handle.worker_id().is_some()).await;
+    assert!(!second.handle().is_leader());
+
+    let client = DurableClient::new(pool)?;
+    let leader = client.leader().await?.expect("there should be a leader");
+    assert_eq!(Some(leader.id()), first.handle().worker_id());
+
+    let leader = client
+        .hand_off_leadership()
+        .await?
+        .expect("there should be a leader");
+    assert_eq!(Some(leader.id()), second.handle().worker_id());
+
+    wait_for(&second.handle(), |handle| handle.is_leader()).await;
+    wait_for(&first.handle(), |handle| !handle.is_leader()).await;
+
+    Ok(())
+}
+
+#[sqlx::test]
+async fn tasks_on_expired_workers_are_reassigned(pool: sqlx::PgPool) -> anyhow::Result<()> {
+    let client = DurableClient::new(pool.clone())?;
+    let program = crate::load_binary(&client, "task-details.wasm").await?;
+
+    // A worker that died without deleting its entry.
+    let dead = sqlx::query_scalar!(
+        "
+        INSERT INTO durable.worker(heartbeat_at, leader_eligible)
+        VALUES (NOW() - INTERVAL '1 hour', false)
+        RETURNING id
+        "
+    )
+    .fetch_one(&pool)
+    .await?;
+
+    let task = client.launch("orphaned", &program, &()).await?;
+    sqlx::query!(
+        "UPDATE durable.task SET state = 'active', running_on = $2 WHERE id = $1",
+        task.id(),
+        dead
+    )
+    .execute(&pool)
+    .await?;
+
+    let _guard = durable_test::spawn_worker_with(
+        pool.clone(),
+        Config::new()
+            .heartbeat_timeout(Duration::from_secs(5))
+            .worker_sweep_interval(Some(Duration::from_millis(100))),
+    )
+    .await?;
+
+    let status = task.wait(&client, Some(Duration::from_secs(30))).await?;
+    assert!(status.success());
+
+    let remaining = sqlx::query_scalar!(
+        r#"SELECT COUNT(*) as "count!" FROM durable.worker WHERE id = $1"#,
+        dead
+    )
+    .fetch_one(&pool)
+    .await?;
+    assert_eq!(remaining, 0);
+
+    Ok(())
+}
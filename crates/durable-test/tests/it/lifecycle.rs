@@ -0,0 +1,79 @@
+use std::time::Duration;
+
+use durable_client::DurableClient;
+use durable_runtime::lifecycle::WorkerEvent;
+use tokio::sync::broadcast;
+
+/// Wait for the next event about task `id`.
+async fn next_event(
+    events: &mut broadcast::Receiver<WorkerEvent>,
+    id: i64,
+) -> anyhow::Result<WorkerEvent> {
+    let event = tokio::time::timeout(Duration::from_secs(30), async {
+        loop {
+            let event = events.recv().await?;
+            let task_id = match &event {
+                WorkerEvent::TaskStarted(e) => e.id,
+                WorkerEvent::TaskCompleted(e) => e.id,
+                WorkerEvent::TaskFailed(e) => e.id,
+                WorkerEvent::TaskSuspended(e) => e.id,
+                _ => continue,
+            };
+
+            if task_id == id {
+                break anyhow::Ok(event);
+            }
+        }
+    })
+    .await
+    .expect("no event was received within 30s")?;
+
+    Ok(event)
+}
+
+#[sqlx::test]
+async fn subscribers_see_task_lifecycle(pool: sqlx::PgPool) -> anyhow::Result<()> {
+    let guard = durable_test::spawn_worker(pool.clone()).await?;
+    let mut events = guard.handle().subscribe();
+
+    let client = DurableClient::new(pool)?;
+    let program = crate::load_binary(&client, "task-details.wasm").await?;
+    let task = client.launch("lifecycle task", &program, &()).await?;
+
+    match next_event(&mut events, task.id()).await? {
+        WorkerEvent::TaskStarted(event) => {
+            assert_eq!(event.name, "lifecycle task");
+            assert_eq!(event.attempt, 1);
+        }
+        event => panic!("expected a TaskStarted event, got {event:?}"),
+    }
+
+    match next_event(&mut events, task.id()).await? {
+        WorkerEvent::TaskCompleted(event) => assert_eq!(event.name, "lifecycle task"),
+        event => panic!("expected a TaskCompleted event, got {event:?}"),
+    }
+
+    Ok(())
+}
+
+#[sqlx::test]
+async fn subscribers_see_task_failures(pool: sqlx::PgPool) -> anyhow::Result<()> {
+    let guard = durable_test::spawn_worker(pool.clone()).await?;
+    let mut events = guard.handle().subscribe();
+
+    let client = DurableClient::new(pool)?;
+    let program = crate::load_binary(&client, "print-then-panic.wasm").await?;
+    let task = client.launch("failing task", &program, &()).await?;
+
+    assert!(matches!(
+        next_event(&mut events, task.id()).await?,
+        WorkerEvent::TaskStarted(_)
+    ));
+
+    match next_event(&mut events, task.id()).await? {
+        WorkerEvent::TaskFailed(event) => assert!(!event.message.is_empty()),
+        event => panic!("expected a TaskFailed event, got {event:?}"),
+    }
+
+    Ok(())
+}
@@ -0,0 +1,120 @@
+use durable_client::DurableClient;
+use durable_runtime::{Config, LlmProvider};
+use futures::TryStreamExt;
+use serde_json::{json, Value};
+
+async fn run_llm(pool: sqlx::PgPool, config: Config) -> anyhow::Result<(i64, String)> {
+    let _guard = durable_test::spawn_worker_with(pool.clone(), config).await?;
+    let client = DurableClient::new(pool)?;
+    let program = crate::load_binary(&client, "llm.wasm").await?;
+
+    let task = client.launch("llm", &program, &json!(null)).await?;
+    let status = task.wait(&client, None).await?;
+    assert!(status.success());
+
+    let logs = task
+        .read_logs(&client)
+        .try_fold(String::new(), |mut acc, item| {
+            acc.push_str(&item);
+            std::future::ready(Ok(acc))
+        })
+        .await?;
+
+    Ok((task.id(), logs))
+}
+
+/// A fake OpenAI-compatible server that answers every chat request with
+/// "Hello!".
+async fn serve_provider() -> anyhow::Result<String> {
+    use axum::http::{header, HeaderMap, StatusCode};
+    use axum::response::IntoResponse;
+    use axum::Json;
+
+    async fn chat(headers: HeaderMap, Json(body): Json<Value>) -> axum::response::Response {
+        if headers.get(header::AUTHORIZATION).map(|v| v.as_bytes()) != Some(b"Bearer sk-test") {
+            return StatusCode::UNAUTHORIZED.into_response();
+        }
+
+        let model = body["model"].as_str().unwrap_or_default().to_owned();
+        if body["stream"] != json!(true) {
+            return Json(json!({
+                "id": "chatcmpl-1",
+                "model": model,
+                "choices": [{
+                    "index": 0,
+                    "message": { "role": "assistant", "content": "Hello!" },
+                    "finish_reason": "stop"
+                }],
+                "usage": { "prompt_tokens": 12, "completion_tokens": 3, "total_tokens": 15 }
+            }))
+            .into_response();
+        }
+
+        let chunks = [
+            json!({ "model": model, "choices": [{ "index": 0, "delta": { "role": "assistant" } }] }),
+            json!({ "model": model, "choices": [{ "index": 0, "delta": { "content": "Hel" } }] }),
+            json!({ "model": model, "choices": [{ "index": 0, "delta": { "content": "lo!" } }] }),
+            json!({
+                "model": model,
+                "choices": [],
+                "usage": { "prompt_tokens": 12, "completion_tokens": 2, "total_tokens": 14 }
+            }),
+        ];
+
+        let mut body = String::new();
+        for chunk in chunks {
+            body.push_str(&format!("data: {chunk}\n\n"));
+        }
+        body.push_str("data: [DONE]\n\n");
+
+        ([(header::CONTENT_TYPE, "text/event-stream")], body).into_response()
+    }
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+    let addr = listener.local_addr()?;
+    let router = axum::Router::new().route("/v1/chat/completions", axum::routing::post(chat));
+
+    tokio::spawn(async move { axum::serve(listener, router).await });
+
+    Ok(format!("http://{addr}/v1"))
+}
+
+#[sqlx::test]
+async fn llm_not_configured(pool: sqlx::PgPool) -> anyhow::Result<()> {
+    let (_, logs) = run_llm(pool, Config::new()).await?;
+    assert_eq!(logs, "error: not-configured default\n");
+
+    Ok(())
+}
+
+#[sqlx::test]
+async fn llm_chat_records_usage(pool: sqlx::PgPool) -> anyhow::Result<()> {
+    let base_url = serve_provider().await?;
+    let provider = LlmProvider::new(base_url)
+        .api_key("sk-test")
+        .default_model("test-model");
+    let config = Config::new().llm_provider("default", provider);
+
+    let (task_id, logs) = run_llm(pool.clone(), config).await?;
+    assert_eq!(logs, "chat: Hello!\nstream: Hello!\n");
+
+    let usage: Vec<(String, String, i64, i64)> = sqlx::query_as(
+        "SELECT provider, model, prompt_tokens, completion_tokens
+          FROM durable.llm_usage
+         WHERE task_id = $1
+         ORDER BY index, seq",
+    )
+    .bind(task_id)
+    .fetch_all(&pool)
+    .await?;
+
+    assert_eq!(
+        usage,
+        [
+            ("default".to_owned(), "test-model".to_owned(), 12, 3),
+            ("default".to_owned(), "test-model".to_owned(), 12, 2),
+        ]
+    );
+
+    Ok(())
+}
@@ -0,0 +1,145 @@
+use std::time::Duration;
+
+use durable_client::{DurableClient, Task};
+use futures::TryStreamExt;
+use serde_json::json;
+
+async fn wait_for_holders(pool: &sqlx::PgPool, holders: &[&Task]) -> anyhow::Result<()> {
+    let mut expected: Vec<_> = holders.iter().map(|task| task.id()).collect();
+    expected.sort();
+
+    tokio::time::timeout(Duration::from_secs(30), async {
+        loop {
+            let held = sqlx::query_scalar!(
+                "SELECT task_id FROM durable.lock_lease WHERE name = 'resource' ORDER BY task_id"
+            )
+            .fetch_all(pool)
+            .await?;
+
+            if held == expected {
+                return anyhow::Ok(());
+            }
+
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+    })
+    .await?
+}
+
+async fn wait_success(client: &DurableClient, task: &Task) -> anyhow::Result<String> {
+    let status = tokio::time::timeout(Duration::from_secs(30), task.wait(client, None)).await??;
+    let logs: Vec<String> = task.read_logs(client).try_collect().await?;
+    assert!(status.success(), "task failed: {}", logs.concat());
+
+    Ok(logs.concat())
+}
+
+#[sqlx::test]
+async fn lock_excludes_other_tasks(pool: sqlx::PgPool) -> anyhow::Result<()> {
+    let _guard = durable_test::spawn_worker(pool.clone()).await?;
+    let client = DurableClient::new(pool.clone())?;
+    let program = crate::load_binary(&client, "lock.wasm").await?;
+
+    let a = client
+        .launch("a", &program, &json!({ "permits": 1, "hold": true }))
+        .await?;
+    wait_for_holders(&pool, &[&a]).await?;
+
+    let b = client
+        .launch("b", &program, &json!({ "permits": 1 }))
+        .await?;
+
+    // b should stay blocked for as long as a holds the lock.
+    tokio::time::sleep(Duration::from_millis(500)).await;
+    wait_for_holders(&pool, &[&a]).await?;
+
+    a.notify("release", &(), &client).await?;
+
+    assert_eq!(wait_success(&client, &a).await?, "acquired\nrelease\n");
+    assert_eq!(wait_success(&client, &b).await?, "acquired\n");
+    wait_for_holders(&pool, &[]).await?;
+
+    Ok(())
+}
+
+#[sqlx::test]
+async fn lock_released_on_completion(pool: sqlx::PgPool) -> anyhow::Result<()> {
+    let _guard = durable_test::spawn_worker(pool.clone()).await?;
+    let client = DurableClient::new(pool.clone())?;
+    let program = crate::load_binary(&client, "lock.wasm").await?;
+
+    let a = client
+        .launch(
+            "a",
+            &program,
+            &json!({ "permits": 1, "hold": true, "leak": true }),
+        )
+        .await?;
+    wait_for_holders(&pool, &[&a]).await?;
+
+    let b = client
+        .launch("b", &program, &json!({ "permits": 1, "leak": true }))
+        .await?;
+    a.notify("release", &(), &client).await?;
+
+    wait_success(&client, &a).await?;
+    wait_success(&client, &b).await?;
+    wait_for_holders(&pool, &[]).await?;
+
+    Ok(())
+}
+
+#[sqlx::test]
+async fn lock_ttl_expires(pool: sqlx::PgPool) -> anyhow::Result<()> {
+    let _guard = durable_test::spawn_worker(pool.clone()).await?;
+    let client = DurableClient::new(pool.clone())?;
+    let program = crate::load_binary(&client, "lock.wasm").await?;
+
+    let a = client
+        .launch(
+            "a",
+            &program,
+            &json!({ "permits": 1, "ttl_ms": 1000, "hold": true }),
+        )
+        .await?;
+    wait_for_holders(&pool, &[&a]).await?;
+
+    // b is able to take the lock once a's lease expires, even though a is
+    // still running.
+    let b = client
+        .launch("b", &program, &json!({ "permits": 1 }))
+        .await?;
+    wait_success(&client, &b).await?;
+
+    a.notify("release", &(), &client).await?;
+    wait_success(&client, &a).await?;
+
+    Ok(())
+}
+
+#[sqlx::test]
+async fn semaphore_limits_holders(pool: sqlx::PgPool) -> anyhow::Result<()> {
+    let _guard = durable_test::spawn_worker(pool.clone()).await?;
+    let client = DurableClient::new(pool.clone())?;
+    let program = crate::load_binary(&client, "lock.wasm").await?;
+
+    let data = json!({ "permits": 2, "hold": true });
+    let a = client.launch("a", &program, &data).await?;
+    let b = client.launch("b", &program, &data).await?;
+    wait_for_holders(&pool, &[&a, &b]).await?;
+
+    let c = client.launch("c", &program, &data).await?;
+    tokio::time::sleep(Duration::from_millis(500)).await;
+    wait_for_holders(&pool, &[&a, &b]).await?;
+
+    a.notify("release", &(), &client).await?;
+    wait_success(&client, &a).await?;
+    wait_for_holders(&pool, &[&b, &c]).await?;
+
+    b.notify("release", &(), &client).await?;
+    c.notify("release", &(), &client).await?;
+    wait_success(&client, &b).await?;
+    assert_eq!(wait_success(&client, &c).await?, "acquired\nrelease\n");
+
+    Ok(())
+}
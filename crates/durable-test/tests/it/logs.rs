@@ -0,0 +1,96 @@
+use durable_client::{DurableClient, LogFilter, LogLevel, Task};
+
+async fn insert_log(
+    pool: &sqlx::PgPool,
+    task: &Task,
+    index: i32,
+    message: &str,
+) -> sqlx::Result<()> {
+    sqlx::query(
+        "INSERT INTO durable.log(task_id, index, message)
+         VALUES ($1, $2, $3)
+         ON CONFLICT ON CONSTRAINT log_pkey DO UPDATE
+         SET message = EXCLUDED.message",
+    )
+    .bind(task.id())
+    .bind(index)
+    .bind(message)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+#[sqlx::test]
+async fn logs_are_split_into_entries(pool: sqlx::PgPool) -> anyhow::Result<()> {
+    let client = DurableClient::new(pool.clone())?;
+    let program = crate::load_binary(&client, "task-details.wasm").await?;
+    let task = client.launch("log entries", &program, &()).await?;
+
+    insert_log(&pool, &task, 0, "starting up\nDEBUG connecting\n").await?;
+    insert_log(&pool, &task, 1, "[warn] request timed out\n").await?;
+    // Replacing the logs for a transaction replaces its entries as well.
+    insert_log(
+        &pool,
+        &task,
+        1,
+        "[warn] request timed out\n2024-01-01T00:00:00Z ERROR request Failed\n",
+    )
+    .await?;
+    insert_log(&pool, &task, i32::MAX - 1, "task failed\n").await?;
+
+    let entries = task.logs(&client, &LogFilter::new()).await?;
+    let lines: Vec<_> = entries
+        .iter()
+        .map(|entry| (entry.index(), entry.line(), entry.level(), entry.message()))
+        .collect();
+    assert_eq!(
+        lines,
+        [
+            (0, 0, LogLevel::Info, "starting up"),
+            (0, 1, LogLevel::Debug, "DEBUG connecting"),
+            (1, 0, LogLevel::Warn, "[warn] request timed out"),
+            (
+                1,
+                1,
+                LogLevel::Error,
+                "2024-01-01T00:00:00Z ERROR request Failed"
+            ),
+            (i32::MAX - 1, 0, LogLevel::Error, "task failed"),
+        ]
+    );
+
+    let errors = task
+        .logs(&client, &LogFilter::new().level(LogLevel::Warn))
+        .await?;
+    assert_eq!(errors.len(), 3);
+
+    let search = task
+        .logs(&client, &LogFilter::new().contains("FAILED"))
+        .await?;
+    let messages: Vec<_> = search.iter().map(|entry| entry.message()).collect();
+    assert_eq!(
+        messages,
+        ["2024-01-01T00:00:00Z ERROR request Failed", "task failed"]
+    );
+
+    let first = task.logs(&client, &LogFilter::new().limit(2)).await?;
+    assert_eq!(first.len(), 2);
+    let rest = task
+        .logs(&client, &LogFilter::new().after(&first[1]))
+        .await?;
+    assert_eq!(rest.len(), 3);
+    assert_eq!(rest[0].message(), "[warn] request timed out");
+
+    Ok(())
+}
+
+#[sqlx::test]
+async fn logs_for_missing_task(pool: sqlx::PgPool) -> anyhow::Result<()> {
+    let client = DurableClient::new(pool)?;
+
+    let result = Task::from_id(12345).logs(&client, &LogFilter::new()).await;
+    assert!(result.is_err());
+
+    Ok(())
+}
@@ -3,10 +3,47 @@ use std::path::{Path, PathBuf};
 use anyhow::Context;
 use durable_client::{DurableClient, Program, ProgramOptions};
 
+mod api;
+mod approval;
+mod audit;
 mod basic;
+mod capabilities;
+mod chaos;
+mod client;
+mod conformance;
+mod dependency;
+mod email;
+mod entrypoint;
+mod fanout;
+mod filesystem;
+mod fuzz;
+mod ingest;
+mod instance;
+mod layer;
+mod leader;
+mod lifecycle;
+mod llm;
+mod lock;
+mod logs;
+mod memory;
+mod mq;
 mod notify;
+mod object_store;
+mod pause;
+mod plugin;
+mod pool;
+mod program;
+mod ratelimit;
+mod replay;
+mod remote;
+mod retention;
+mod saga;
+mod schema;
 mod shutdown;
+mod simulation;
 mod sqlx;
+mod tenant;
+mod typed;
 
 async fn load_binary(client: &DurableClient, name: &str) -> anyhow::Result<Program> {
     let program = client
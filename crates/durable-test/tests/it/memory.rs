@@ -0,0 +1,34 @@
+use std::time::Duration;
+
+use durable_client::DurableClient;
+use durable_runtime::Config;
+
+#[sqlx::test]
+async fn memory_budget_runs_tasks_one_at_a_time(pool: sqlx::PgPool) -> anyhow::Result<()> {
+    // A budget of a single byte means that the worker will only ever run one
+    // task at a time, but it must still make progress.
+    let guard =
+        durable_test::spawn_worker_with(pool.clone(), Config::new().memory_budget(1)).await?;
+    let client = DurableClient::new(pool)?;
+    let program = crate::load_binary(&client, "task-details.wasm").await?;
+
+    let mut tasks = Vec::new();
+    for index in 0..4 {
+        tasks.push(
+            client
+                .launch(format!("budgeted task {index}"), &program, &())
+                .await?,
+        );
+    }
+
+    for task in tasks {
+        let status = task.wait(&client, Some(Duration::from_secs(30))).await?;
+        assert!(status.success());
+    }
+
+    let stats = guard.handle().stats().await?;
+    assert_eq!(stats.memory_budget, Some(1));
+    assert_eq!(stats.memory_usage, 0);
+
+    Ok(())
+}
@@ -0,0 +1,115 @@
+use std::net::SocketAddr;
+
+use anyhow::Context;
+use durable_client::DurableClient;
+use durable_runtime::{Config, NatsConfig};
+use futures::TryStreamExt;
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::TcpListener;
+use tokio::sync::mpsc;
+
+async fn run_mq(pool: sqlx::PgPool, config: Config) -> anyhow::Result<String> {
+    let _guard = durable_test::spawn_worker_with(pool.clone(), config).await?;
+    let client = DurableClient::new(pool)?;
+    let program = crate::load_binary(&client, "mq.wasm").await?;
+
+    let task = client
+        .launch("mq", &program, &serde_json::json!(null))
+        .await?;
+    let status = task.wait(&client, None).await?;
+    assert!(status.success());
+
+    let logs = task
+        .read_logs(&client)
+        .try_fold(String::new(), |mut acc, item| {
+            acc.push_str(&item);
+            std::future::ready(Ok(acc))
+        })
+        .await?;
+
+    Ok(logs)
+}
+
+/// A minimal NATS server that accepts a single connection and forwards the
+/// subject and raw contents (headers followed by the payload) of each published
+/// message.
+async fn nats_server() -> anyhow::Result<(SocketAddr, mpsc::UnboundedReceiver<(String, String)>)> {
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let addr = listener.local_addr()?;
+    let (tx, rx) = mpsc::unbounded_channel();
+
+    tokio::spawn(async move {
+        let (stream, _) = listener.accept().await?;
+        let (read, mut write) = stream.into_split();
+        let mut read = BufReader::new(read);
+
+        let info = serde_json::json!({
+            "server_id": "test",
+            "server_name": "test",
+            "version": "2.10.0",
+            "go": "go1.22",
+            "host": addr.ip().to_string(),
+            "port": addr.port(),
+            "headers": true,
+            "max_payload": 1048576,
+            "proto": 1,
+        });
+        write
+            .write_all(format!("INFO {info}\r\n").as_bytes())
+            .await?;
+
+        let mut line = String::new();
+        loop {
+            line.clear();
+            if read.read_line(&mut line).await? == 0 {
+                return anyhow::Ok(());
+            }
+
+            let mut parts = line.split_whitespace();
+            match parts.next() {
+                Some("PING") => write.write_all(b"PONG\r\n").await?,
+                Some("HPUB") => {
+                    let parts: Vec<_> = parts.collect();
+                    let [subject, _, len] = parts[..] else {
+                        anyhow::bail!("unexpected HPUB line: {line}");
+                    };
+
+                    let mut data = vec![0u8; len.parse::<usize>()? + 2];
+                    read.read_exact(&mut data).await?;
+                    data.truncate(data.len() - 2);
+
+                    let _ = tx.send((subject.to_owned(), String::from_utf8(data)?));
+                }
+                _ => (),
+            }
+        }
+    });
+
+    Ok((addr, rx))
+}
+
+#[sqlx::test]
+async fn mq_not_configured(pool: sqlx::PgPool) -> anyhow::Result<()> {
+    let logs = run_mq(pool, Config::new()).await?;
+    assert_eq!(logs, "error: not-configured\n");
+
+    Ok(())
+}
+
+#[sqlx::test]
+async fn mq_publish_nats(pool: sqlx::PgPool) -> anyhow::Result<()> {
+    let (addr, mut messages) = nats_server().await?;
+    let config = Config::new().mq(NatsConfig::new(format!("nats://{addr}")).into());
+
+    let logs = run_mq(pool, config).await?;
+    assert_eq!(logs, "published\n");
+
+    let (subject, data) = messages.recv().await.context("no message was published")?;
+    assert_eq!(subject, "orders.created");
+    assert!(data.starts_with("NATS/1.0\r\n"));
+    assert!(data.contains("source: test\r\n"));
+    assert!(data.contains("durable-idempotency-key: "));
+    assert!(data.ends_with("\r\n\r\norder 1234"));
+
+    Ok(())
+}
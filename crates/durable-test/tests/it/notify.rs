@@ -17,7 +17,7 @@ async fn notify_self(pool: sqlx::PgPool) -> anyhow::Result<()> {
         .launch("notify self test", &program, &serde_json::json!(null))
         .await?;
 
-    let status = match timeout(Duration::from_secs(30), task.wait(&client)).await {
+    let status = match timeout(Duration::from_secs(30), task.wait(&client, None)).await {
         Ok(result) => result?,
         Err(_) => anyhow::bail!("task failed to complete in under 30s"),
     };
@@ -40,7 +40,7 @@ async fn notify_in_advance(pool: sqlx::PgPool) -> anyhow::Result<()> {
 
     let _guard = durable_test::spawn_worker(pool.clone()).await?;
 
-    let status = match timeout(Duration::from_secs(30), task.wait(&client)).await {
+    let status = match timeout(Duration::from_secs(30), task.wait(&client, None)).await {
         Ok(result) => result?,
         Err(_) => anyhow::bail!("task failed to complete in under 30s"),
     };
@@ -97,7 +97,7 @@ async fn notify_after_suspend(pool: sqlx::PgPool) -> anyhow::Result<()> {
 
     task.notify("notification", &(), &client).await?;
 
-    let status = tokio::time::timeout(Duration::from_secs(30), task.wait(&client))
+    let status = tokio::time::timeout(Duration::from_secs(30), task.wait(&client, None))
         .await
         .context("task failed to complete in under 30s")??;
     assert!(status.success());
@@ -161,10 +161,34 @@ async fn notify_multiple_workers(pool: sqlx::PgPool) -> anyhow::Result<()> {
 
     task.notify("notification", &(), &client).await?;
 
-    let status = tokio::time::timeout(Duration::from_secs(30), task.wait(&client))
+    let status = tokio::time::timeout(Duration::from_secs(30), task.wait(&client, None))
         .await
         .context("task failed to complete in under 30s")??;
     assert!(status.success());
 
     Ok(())
 }
+
+#[sqlx::test]
+async fn wait_times_out(pool: sqlx::PgPool) -> anyhow::Result<()> {
+    let _guard = durable_test::spawn_worker(pool.clone()).await?;
+    let client = DurableClient::new(pool)?;
+    let program = crate::load_binary(&client, "notify-wait.wasm").await?;
+    let task = client
+        .launch("wait timeout test", &program, &serde_json::json!(null))
+        .await?;
+
+    // The task is blocked until it gets a notification so this should time out.
+    let error = task
+        .wait(&client, Some(Duration::from_millis(500)))
+        .await
+        .expect_err("wait completed before the task was notified");
+    assert!(error.is_timeout());
+
+    task.notify("notification", &(), &client).await?;
+
+    let status = task.wait(&client, Some(Duration::from_secs(30))).await?;
+    assert!(status.success());
+
+    Ok(())
+}
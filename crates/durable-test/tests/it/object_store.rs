@@ -0,0 +1,50 @@
+use durable_client::DurableClient;
+use durable_runtime::{Config, ObjectStoreConfig};
+use futures::TryStreamExt;
+
+async fn run_object_store(pool: sqlx::PgPool, config: Config) -> anyhow::Result<Vec<String>> {
+    let _guard = durable_test::spawn_worker_with(pool.clone(), config).await?;
+    let client = DurableClient::new(pool)?;
+    let program = crate::load_binary(&client, "object-store.wasm").await?;
+
+    let task = client
+        .launch("object-store", &program, &serde_json::json!(null))
+        .await?;
+    let status = task.wait(&client, None).await?;
+    assert!(status.success());
+
+    let logs = task
+        .read_logs(&client)
+        .try_fold(String::new(), |mut acc, item| {
+            acc.push_str(&item);
+            std::future::ready(Ok(acc))
+        })
+        .await?;
+
+    Ok(logs.lines().map(|line| line.to_owned()).collect())
+}
+
+#[sqlx::test]
+async fn object_store_not_configured(pool: sqlx::PgPool) -> anyhow::Result<()> {
+    let lines = run_object_store(pool, Config::new()).await?;
+    assert_eq!(lines, ["get: not-configured", "presign: not-configured"]);
+
+    Ok(())
+}
+
+#[sqlx::test]
+async fn object_store_unreachable(pool: sqlx::PgPool) -> anyhow::Result<()> {
+    // Nothing listens on port 1 so requests fail, but presigning happens
+    // entirely on the worker and still succeeds.
+    let store = ObjectStoreConfig::new("http://127.0.0.1:1", "us-east-1", "akid", "secret")
+        .path_style(true);
+    let lines = run_object_store(pool, Config::new().object_store(store)).await?;
+
+    assert_eq!(lines.len(), 2);
+    assert_eq!(lines[0], "get: request");
+    assert!(lines[1].starts_with("presign: http://127.0.0.1:1/test/report.csv?"));
+    assert!(lines[1].contains("X-Amz-Credential=akid%2F"));
+    assert!(lines[1].contains("&X-Amz-Signature="));
+
+    Ok(())
+}
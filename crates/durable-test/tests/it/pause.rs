@@ -0,0 +1,107 @@
+use std::time::Duration;
+
+use durable_client::{DurableClient, PauseTarget, TaskFilter};
+
+#[sqlx::test]
+async fn paused_task_is_not_run(pool: sqlx::PgPool) -> anyhow::Result<()> {
+    let client = DurableClient::new(pool.clone())?;
+    let program = crate::load_binary(&client, "task-details.wasm").await?;
+
+    let task = client.launch("paused", &program, &()).await?;
+    assert!(task.pause(&client).await?);
+    assert!(!task.pause(&client).await?);
+
+    let listed = client.list_tasks(&TaskFilter::new()).await?;
+    assert!(listed[0].paused_at().is_some());
+
+    let _guard = durable_test::spawn_worker(pool).await?;
+
+    let error = task
+        .wait(&client, Some(Duration::from_secs(2)))
+        .await
+        .expect_err("paused task ran to completion");
+    assert!(error.is_timeout());
+
+    assert!(task.resume(&client).await?);
+    assert!(!task.resume(&client).await?);
+
+    let status = task.wait(&client, Some(Duration::from_secs(30))).await?;
+    assert!(status.success());
+
+    Ok(())
+}
+
+#[sqlx::test]
+async fn pause_completed_task(pool: sqlx::PgPool) -> anyhow::Result<()> {
+    let _guard = durable_test::spawn_worker(pool.clone()).await?;
+    let client = DurableClient::new(pool)?;
+    let program = crate::load_binary(&client, "task-details.wasm").await?;
+
+    let task = client.launch("complete", &program, &()).await?;
+    task.wait(&client, None).await?;
+
+    assert!(!task.pause(&client).await?);
+
+    Ok(())
+}
+
+#[sqlx::test]
+async fn paused_program_is_not_run(pool: sqlx::PgPool) -> anyhow::Result<()> {
+    let client = DurableClient::new(pool.clone())?;
+    let program = crate::load_binary(&client, "task-details.wasm").await?;
+
+    assert!(client.pause_program(program.id(), Some("incident")).await?);
+    assert!(!client.pause_program(program.id(), None).await?);
+
+    let pauses = client.pauses().await?;
+    assert_eq!(pauses.len(), 1);
+    assert_eq!(pauses[0].target(), &PauseTarget::Program(program.id()));
+    assert_eq!(pauses[0].reason(), Some("incident"));
+
+    let _guard = durable_test::spawn_worker(pool).await?;
+    let task = client.launch("paused program", &program, &()).await?;
+
+    let error = task
+        .wait(&client, Some(Duration::from_secs(2)))
+        .await
+        .expect_err("task of a paused program ran to completion");
+    assert!(error.is_timeout());
+
+    assert!(client.resume_program(program.id()).await?);
+    assert!(client.pauses().await?.is_empty());
+
+    let status = task.wait(&client, Some(Duration::from_secs(30))).await?;
+    assert!(status.success());
+
+    Ok(())
+}
+
+#[sqlx::test]
+async fn paused_queue_is_not_run(pool: sqlx::PgPool) -> anyhow::Result<()> {
+    let _guard = durable_test::spawn_worker(pool.clone()).await?;
+    let client = DurableClient::new(pool.clone())?;
+    let tenant = client.with_tenant("team-a");
+    let program = crate::load_binary(&tenant, "task-details.wasm").await?;
+
+    assert!(tenant.pause_queue(None).await?);
+
+    let paused = tenant.launch("paused queue", &program, &()).await?;
+
+    // Other queues are unaffected.
+    let other = crate::load_binary(&client, "task-details.wasm").await?;
+    let task = client.launch("other queue", &other, &()).await?;
+    assert!(task.wait(&client, None).await?.success());
+
+    let error = paused
+        .wait(&tenant, Some(Duration::from_secs(2)))
+        .await
+        .expect_err("task in a paused queue ran to completion");
+    assert!(error.is_timeout());
+
+    assert!(tenant.resume_queue().await?);
+
+    let status = paused.wait(&tenant, Some(Duration::from_secs(30))).await?;
+    assert!(status.success());
+
+    Ok(())
+}
@@ -0,0 +1,156 @@
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use durable_client::DurableClient;
+use durable_runtime::plugin::Plugin;
+use durable_runtime::{Config, Task, TaskStatus, WorkerBuilder};
+use wasmtime::component::Linker;
+
+/// A plugin that records every lifecycle hook that gets called.
+#[derive(Clone, Default)]
+struct RecordingPlugin {
+    calls: Arc<Mutex<Vec<String>>>,
+}
+
+/// Per-task state, used to check that state set up in `setup` is visible from
+/// the hooks.
+struct TransactionCount(u32);
+
+impl RecordingPlugin {
+    fn record(&self, call: String) {
+        self.calls.lock().unwrap().push(call);
+    }
+}
+
+impl Plugin for RecordingPlugin {
+    fn name(&self) -> &str {
+        "test:recording"
+    }
+
+    fn setup(&self, _: &mut Linker<Task>, task: &mut Task) -> wasmtime::Result<()> {
+        task.insert_plugin_state(TransactionCount(0));
+        Ok(())
+    }
+
+    fn on_task_start(&self, _: &mut Task) -> wasmtime::Result<()> {
+        self.record("start".into());
+        Ok(())
+    }
+
+    fn on_task_complete(&self, task: &mut Task, status: TaskStatus) {
+        let count = task.plugin_state::<TransactionCount>().unwrap().0;
+        self.record(format!("complete {status:?} after {count}"));
+    }
+
+    fn on_transaction_enter(&self, task: &mut Task) -> wasmtime::Result<()> {
+        let label = task.state.transaction().unwrap().label().to_owned();
+        self.record(format!("enter {label}"));
+        Ok(())
+    }
+
+    fn on_transaction_exit(&self, task: &mut Task) -> wasmtime::Result<()> {
+        let label = task.state.transaction().unwrap().label().to_owned();
+        task.plugin_state_mut::<TransactionCount>().unwrap().0 += 1;
+        self.record(format!("exit {label}"));
+        Ok(())
+    }
+}
+
+#[sqlx::test]
+async fn plugin_lifecycle_hooks(pool: sqlx::PgPool) -> anyhow::Result<()> {
+    let plugin = RecordingPlugin::default();
+    let config = Config::new()
+        .suspend_margin(Duration::from_secs(1))
+        .suspend_timeout(Duration::from_secs(1));
+    let _guard = durable_test::spawn_worker_from(
+        WorkerBuilder::new(pool.clone())
+            .config(config)
+            .plugin(Box::new(plugin.clone())),
+    )
+    .await?;
+
+    let client = DurableClient::new(pool)?;
+    let program = crate::load_binary(&client, "transactions.wasm").await?;
+
+    let task = client
+        .launch("plugin test", &program, &serde_json::json!(null))
+        .await?;
+    let status = task.wait(&client, None).await?;
+    assert!(status.success());
+
+    let calls = plugin.calls.lock().unwrap().clone();
+    assert_eq!(
+        calls,
+        [
+            "start",
+            "enter first",
+            "exit first",
+            "enter second",
+            "exit second",
+            "complete ExitSuccess after 2",
+        ]
+    );
+
+    Ok(())
+}
+
+/// A plugin providing the `test:counter/counter` interface used by the
+/// `host-call` workflow.
+#[derive(Clone, Default)]
+struct CounterPlugin {
+    count: Arc<AtomicU32>,
+}
+
+impl Plugin for CounterPlugin {
+    fn name(&self) -> &str {
+        "test:counter"
+    }
+
+    fn setup(&self, linker: &mut Linker<Task>, _: &mut Task) -> wasmtime::Result<()> {
+        let count = self.count.clone();
+        linker
+            .instance("test:counter/counter")?
+            .func_wrap("hit", move |_, (amount,): (u32,)| {
+                Ok((count.fetch_add(amount, Ordering::SeqCst) + amount,))
+            })?;
+
+        Ok(())
+    }
+}
+
+#[sqlx::test]
+async fn guest_calls_custom_plugin(pool: sqlx::PgPool) -> anyhow::Result<()> {
+    let plugin = CounterPlugin::default();
+    let _guard = durable_test::spawn_worker_from(
+        WorkerBuilder::new(pool.clone()).plugin(Box::new(plugin.clone())),
+    )
+    .await?;
+
+    let client = DurableClient::new(pool)?;
+    let program = crate::load_binary(&client, "host-call.wasm").await?;
+
+    let task = client
+        .launch("host call test", &program, &serde_json::json!(null))
+        .await?;
+    let status = task.wait(&client, None).await?;
+    assert!(status.success());
+
+    // Each call should have been made in its own transaction.
+    let labels: Vec<_> = task
+        .events(&client)
+        .await?
+        .into_iter()
+        .map(|event| event.label)
+        .collect();
+    assert_eq!(
+        labels,
+        [
+            "durable::host_call(counter::hit)",
+            "durable::host_call(counter::hit)"
+        ]
+    );
+    assert_eq!(plugin.count.load(Ordering::SeqCst), 3);
+
+    Ok(())
+}
@@ -0,0 +1,73 @@
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use durable_client::DurableClient;
+use durable_runtime::plugin::Plugin;
+use durable_runtime::{Config, InstancePoolConfig, Task, WorkerBuilder};
+use wasmtime::component::Linker;
+
+/// A plugin that counts how often the worker sets up its linker.
+#[derive(Clone, Default)]
+struct SetupCounter {
+    setup: Arc<AtomicU32>,
+    setup_state: Arc<AtomicU32>,
+}
+
+struct Marker;
+
+impl Plugin for SetupCounter {
+    fn name(&self) -> &str {
+        "test:setup-counter"
+    }
+
+    fn setup(&self, _: &mut Linker<Task>, task: &mut Task) -> wasmtime::Result<()> {
+        self.setup.fetch_add(1, Ordering::SeqCst);
+        task.insert_plugin_state(Marker);
+        Ok(())
+    }
+
+    fn setup_state(&self, _: &wasmtime::Engine, task: &mut Task) -> wasmtime::Result<()> {
+        self.setup_state.fetch_add(1, Ordering::SeqCst);
+        task.insert_plugin_state(Marker);
+        Ok(())
+    }
+
+    fn on_task_start(&self, task: &mut Task) -> wasmtime::Result<()> {
+        anyhow::ensure!(
+            task.plugin_state::<Marker>().is_some(),
+            "plugin state was not set up"
+        );
+        Ok(())
+    }
+}
+
+#[sqlx::test]
+async fn pooled_worker_reuses_pre_instantiated_programs(pool: sqlx::PgPool) -> anyhow::Result<()> {
+    let plugin = SetupCounter::default();
+    let config = Config::new()
+        .max_tasks(4)
+        .instance_pool(InstancePoolConfig::new().warm_slots(2));
+    let _guard = durable_test::spawn_worker_from(
+        WorkerBuilder::new(pool.clone())
+            .config(config)
+            .plugin(Box::new(plugin.clone())),
+    )
+    .await?;
+
+    let client = DurableClient::new(pool)?;
+    let program = crate::load_binary(&client, "task-details.wasm").await?;
+
+    for index in 0..3 {
+        let task = client
+            .launch(format!("pooled task {index}"), &program, &())
+            .await?;
+        let status = task.wait(&client, Some(Duration::from_secs(30))).await?;
+        assert!(status.success());
+    }
+
+    assert_eq!(plugin.setup.load(Ordering::SeqCst), 1);
+    assert_eq!(plugin.setup_state.load(Ordering::SeqCst), 2);
+
+    Ok(())
+}
@@ -0,0 +1,55 @@
+use durable_client::{DurableClient, ProgramOptions};
+
+#[sqlx::test]
+async fn resolve_program_by_name_and_version(pool: sqlx::PgPool) -> anyhow::Result<()> {
+    let client = DurableClient::new(pool)?;
+
+    let mut programs = Vec::new();
+    for (binary, version) in [
+        ("task-details.wasm", "1.2.0"),
+        ("print-then-panic.wasm", "1.4.1"),
+        ("transactions.wasm", "2.0.0"),
+    ] {
+        let options = ProgramOptions::from_file(crate::test_binary(binary))?
+            .name("sync")
+            .version(version.parse()?)
+            .description(format!("sync workflow {version}"))
+            .git_commit("0123abcd");
+        programs.push(client.program(options).await?);
+    }
+
+    let program = client
+        .program_by_name_and_version("sync", "^1.2")
+        .await?
+        .expect("no program matched ^1.2");
+    assert_eq!(program.id(), programs[1].id());
+    assert_eq!(program.version(), Some(&"1.4.1".parse()?));
+
+    let program = client
+        .program_by_name_and_version("sync", "=1.2.0")
+        .await?
+        .expect("no program matched =1.2.0");
+    assert_eq!(program.id(), programs[0].id());
+
+    assert!(client
+        .program_by_name_and_version("sync", "^3")
+        .await?
+        .is_none());
+    assert!(client
+        .program_by_name_and_version("other", "*")
+        .await?
+        .is_none());
+    assert!(client
+        .program_by_name_and_version("sync", "not a version")
+        .await
+        .is_err());
+
+    let listed = client.list_programs(Some("sync")).await?;
+    assert_eq!(listed.len(), 3);
+    assert_eq!(listed[0].id(), programs[2].id());
+    assert_eq!(listed[0].version(), Some(&"2.0.0".parse()?));
+    assert_eq!(listed[0].description(), Some("sync workflow 2.0.0"));
+    assert_eq!(listed[0].git_commit(), Some("0123abcd"));
+
+    Ok(())
+}
@@ -0,0 +1,65 @@
+use std::time::{Duration, Instant};
+
+use durable_client::DurableClient;
+use durable_runtime::{Config, RateLimit};
+use futures::TryStreamExt;
+
+#[sqlx::test]
+async fn rate_limit_spaces_out_tasks(pool: sqlx::PgPool) -> anyhow::Result<()> {
+    let _guard = durable_test::spawn_worker_with(
+        pool.clone(),
+        Config::new()
+            .suspend_margin(Duration::from_secs(1))
+            .suspend_timeout(Duration::from_secs(1))
+            .rate_limit("test-api", RateLimit::new(1, Duration::from_secs(1))),
+    )
+    .await?;
+    let client = DurableClient::new(pool.clone())?;
+    let program = crate::load_binary(&client, "ratelimit.wasm").await?;
+
+    let start = Instant::now();
+    let mut tasks = Vec::new();
+    for i in 0..3 {
+        tasks.push(
+            client
+                .launch(format!("task {i}"), &program, &"test-api")
+                .await?,
+        );
+    }
+
+    for task in &tasks {
+        let status = tokio::time::timeout(Duration::from_secs(30), task.wait(&client, None)).await??;
+        let logs: Vec<String> = task.read_logs(&client).try_collect().await?;
+        assert!(status.success(), "task failed: {}", logs.concat());
+        assert_eq!(logs.concat(), "acquired test-api\n");
+    }
+
+    // The first task uses up the initial token, after which the bucket only
+    // refills once per second.
+    let elapsed = start.elapsed();
+    assert!(
+        elapsed >= Duration::from_millis(1900),
+        "tasks completed too quickly: {elapsed:?}"
+    );
+
+    let waiting =
+        sqlx::query_scalar!(r#"SELECT COUNT(*) as "count!" FROM durable.rate_limit_waiter"#)
+            .fetch_one(&pool)
+            .await?;
+    assert_eq!(waiting, 0);
+
+    Ok(())
+}
+
+#[sqlx::test]
+async fn unknown_rate_limit_fails_task(pool: sqlx::PgPool) -> anyhow::Result<()> {
+    let _guard = durable_test::spawn_worker(pool.clone()).await?;
+    let client = DurableClient::new(pool)?;
+    let program = crate::load_binary(&client, "ratelimit.wasm").await?;
+
+    let task = client.launch("unknown", &program, &"missing").await?;
+    let status = tokio::time::timeout(Duration::from_secs(30), task.wait(&client, None)).await??;
+    assert!(!status.success());
+
+    Ok(())
+}
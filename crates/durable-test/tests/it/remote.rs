@@ -0,0 +1,149 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::extract::{Path, State};
+use axum::http::{header, StatusCode};
+use axum::response::IntoResponse;
+use axum::routing::get;
+use axum::Router;
+use durable_client::{DurableClient, ProgramOptions};
+use sha2::{Digest, Sha256};
+
+fn digest(data: &[u8]) -> String {
+    format!("sha256:{:x}", Sha256::digest(data))
+}
+
+/// Serve a minimal OCI registry containing a single `durable/task-details:v1`
+/// artifact and return its address along with the digest of its manifest.
+///
+/// The registry responds to blob requests with `blob`, which allows tests to
+/// simulate a registry serving content that does not match the manifest.
+async fn serve_registry(wasm: Vec<u8>, blob: Vec<u8>) -> anyhow::Result<(String, String)> {
+    let manifest = serde_json::to_vec(&serde_json::json!({
+        "schemaVersion": 2,
+        "mediaType": "application/vnd.oci.image.manifest.v1+json",
+        "config": {
+            "mediaType": "application/vnd.oci.empty.v1+json",
+            "digest": digest(b"{}"),
+            "size": 2
+        },
+        "layers": [{
+            "mediaType": "application/wasm",
+            "digest": digest(&wasm),
+            "size": wasm.len()
+        }]
+    }))?;
+    let manifest_digest = digest(&manifest);
+    let registry = Arc::new(Registry {
+        manifest,
+        manifest_digest: manifest_digest.clone(),
+        layer_digest: digest(&wasm),
+        wasm,
+        blob,
+    });
+
+    let router = Router::new()
+        .route(
+            "/v2/durable/task-details/manifests/:reference",
+            get(get_manifest),
+        )
+        .route("/v2/durable/task-details/blobs/:digest", get(get_blob))
+        .route("/programs/task-details.wasm", get(get_wasm))
+        .with_state(registry);
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+    let addr = listener.local_addr()?;
+    tokio::spawn(async move { axum::serve(listener, router).await });
+
+    Ok((format!("localhost:{}", addr.port()), manifest_digest))
+}
+
+struct Registry {
+    manifest: Vec<u8>,
+    manifest_digest: String,
+    layer_digest: String,
+    wasm: Vec<u8>,
+    blob: Vec<u8>,
+}
+
+async fn get_manifest(
+    State(registry): State<Arc<Registry>>,
+    Path(reference): Path<String>,
+) -> Result<impl IntoResponse, StatusCode> {
+    if reference != "v1" && reference != registry.manifest_digest {
+        return Err(StatusCode::NOT_FOUND);
+    }
+
+    Ok((
+        [(
+            header::CONTENT_TYPE,
+            "application/vnd.oci.image.manifest.v1+json",
+        )],
+        registry.manifest.clone(),
+    ))
+}
+
+async fn get_blob(
+    State(registry): State<Arc<Registry>>,
+    Path(digest): Path<String>,
+) -> Result<Vec<u8>, StatusCode> {
+    if digest != registry.layer_digest {
+        return Err(StatusCode::NOT_FOUND);
+    }
+
+    Ok(registry.blob.clone())
+}
+
+async fn get_wasm(State(registry): State<Arc<Registry>>) -> Vec<u8> {
+    registry.wasm.clone()
+}
+
+#[sqlx::test]
+async fn load_program_from_url_and_oci(pool: sqlx::PgPool) -> anyhow::Result<()> {
+    let _guard = durable_test::spawn_worker(pool.clone()).await?;
+    let client = DurableClient::new(pool)?;
+    let wasm = std::fs::read(crate::test_binary("task-details.wasm"))?;
+    let (registry, manifest_digest) = serve_registry(wasm.clone(), wasm).await?;
+
+    let options =
+        ProgramOptions::from_url(&format!("http://{registry}/programs/task-details.wasm")).await?;
+    let from_url = client.program(options).await?;
+
+    let options = ProgramOptions::from_oci(&format!("{registry}/durable/task-details:v1")).await?;
+    let by_tag = client.program(options).await?;
+
+    let options = ProgramOptions::from_oci(&format!(
+        "{registry}/durable/task-details@{manifest_digest}"
+    ))
+    .await?;
+    let by_digest = client.program(options).await?;
+
+    for program in [&from_url, &by_tag, &by_digest] {
+        let task = client.launch("remote task", program, &()).await?;
+        let status = task.wait(&client, Some(Duration::from_secs(30))).await?;
+        assert!(status.success());
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn oci_digest_mismatch_is_rejected() -> anyhow::Result<()> {
+    let wasm = std::fs::read(crate::test_binary("task-details.wasm"))?;
+    let (registry, _) = serve_registry(wasm, b"not the program".to_vec()).await?;
+
+    let error = ProgramOptions::from_oci(&format!("{registry}/durable/task-details:v1"))
+        .await
+        .expect_err("loaded a program that did not match its digest");
+    assert!(
+        error.to_string().contains("expected sha256:"),
+        "unexpected error: {error}"
+    );
+
+    let missing = format!("{registry}/durable/task-details@{}", digest(b"missing"));
+    ProgramOptions::from_oci(&missing)
+        .await
+        .expect_err("loaded a manifest that did not match the reference digest");
+
+    Ok(())
+}
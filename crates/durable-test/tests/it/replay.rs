@@ -0,0 +1,66 @@
+use durable_client::DurableClient;
+use durable_runtime::replay::{ReplayOutcome, ReplayTask};
+use durable_runtime::TaskStatus;
+use durable_test::ReplayHarness;
+use serde_json::value::RawValue;
+
+#[sqlx::test]
+async fn replay_recorded_events(pool: sqlx::PgPool) -> anyhow::Result<()> {
+    let _guard = durable_test::spawn_worker(pool.clone()).await?;
+    let client = DurableClient::new(pool)?;
+    let program = crate::load_binary(&client, "transactions.wasm").await?;
+
+    let task = client
+        .launch("test task", &program, &serde_json::json!(null))
+        .await?;
+    let status = task.wait(&client, None).await?;
+    assert!(status.success());
+
+    let events = task.events(&client).await?;
+    assert_eq!(events.len(), 2);
+
+    let harness = ReplayHarness::from_file(crate::test_binary("transactions.wasm"))?;
+    let replay_task = ReplayTask::new(
+        task.id(),
+        "test task",
+        RawValue::from_string("null".into())?,
+    );
+    let status = harness.assert_replays(replay_task.clone(), &events).await?;
+    assert_eq!(status, TaskStatus::ExitSuccess);
+
+    // Replaying with only part of the event log should stop where the log ends.
+    let outcome = harness.replay(replay_task.clone(), &events[..0]).await?;
+    assert!(
+        matches!(outcome, ReplayOutcome::EndOfLog { index: 0, .. }),
+        "unexpected replay outcome: {outcome:?}"
+    );
+
+    // Older runtimes recorded an event for every write to stdout. These should
+    // be skipped over.
+    let mut legacy = events.clone();
+    let mut write = events[0].clone();
+    write.label = "wasi:io/streams.output-stream.write".into();
+    write.value = RawValue::from_string("null".into())?;
+    legacy.insert(1, write.clone());
+    legacy.push(write);
+    for (index, event) in legacy.iter_mut().enumerate() {
+        event.index = index as i32;
+    }
+    let status = harness.assert_replays(replay_task.clone(), &legacy).await?;
+    assert_eq!(status, TaskStatus::ExitSuccess);
+
+    // A different label in the event log should be reported as a divergence.
+    let mut modified = events.clone();
+    modified[0].label = "something else".into();
+    let outcome = harness.replay(replay_task, &modified).await?;
+    match outcome {
+        ReplayOutcome::Diverged(divergence) => {
+            assert_eq!(divergence.index, 0);
+            assert_eq!(divergence.recorded.as_deref(), Some("something else"));
+            assert_eq!(divergence.requested.as_deref(), Some(&*events[0].label));
+        }
+        outcome => panic!("unexpected replay outcome: {outcome:?}"),
+    }
+
+    Ok(())
+}
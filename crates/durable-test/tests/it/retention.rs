@@ -0,0 +1,50 @@
+use std::time::Duration;
+
+use durable_client::DurableClient;
+use durable_runtime::Config;
+
+#[sqlx::test]
+async fn leader_creates_event_partitions(pool: sqlx::PgPool) -> anyhow::Result<()> {
+    let _guard =
+        durable_test::spawn_worker_with(pool.clone(), Config::new().partition_size(Some(100)))
+            .await?;
+
+    tokio::time::timeout(Duration::from_secs(10), async {
+        loop {
+            let exists: bool = sqlx::query_scalar(
+                "SELECT to_regclass('durable.event_1_101') IS NOT NULL
+                    AND to_regclass('durable.log_1_101') IS NOT NULL
+                    AND to_regclass('durable.log_entry_1_101') IS NOT NULL",
+            )
+            .fetch_one(&pool)
+            .await?;
+
+            if exists {
+                break anyhow::Ok(());
+            }
+
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+    })
+    .await
+    .expect("partitions were not created within 10s")?;
+
+    let client = DurableClient::new(pool.clone())?;
+    let program = crate::load_binary(&client, "task-details.wasm").await?;
+    let task = client
+        .launch("test task", &program, &serde_json::json!(null))
+        .await?;
+    let status = task.wait(&client, None).await?;
+    assert!(status.success());
+
+    // The task's events should have been stored in the new partition instead
+    // of the default one.
+    let defaulted: i64 =
+        sqlx::query_scalar("SELECT COUNT(*) FROM durable.event_default WHERE task_id = $1")
+            .bind(task.id())
+            .fetch_one(&pool)
+            .await?;
+    assert_eq!(defaulted, 0);
+
+    Ok(())
+}
@@ -0,0 +1,41 @@
+use durable_client::DurableClient;
+use futures::TryStreamExt;
+
+#[sqlx::test]
+async fn saga_compensates_in_reverse(pool: sqlx::PgPool) -> anyhow::Result<()> {
+    let _guard = durable_test::spawn_worker(pool.clone()).await?;
+    let client = DurableClient::new(pool)?;
+    let program = crate::load_binary(&client, "saga.wasm").await?;
+
+    let task = client.launch("saga", &program, &()).await?;
+    let status = task.wait(&client, None).await?;
+    let logs: Vec<String> = task.read_logs(&client).try_collect().await?;
+    assert!(status.success(), "task failed: {}", logs.concat());
+
+    assert_eq!(
+        logs.concat(),
+        "cancel hotel 7\n\
+         cancel flight FL123\n\
+         no cars available (1 compensation failed)\n"
+    );
+
+    let labels: Vec<String> = task
+        .events(&client)
+        .await?
+        .into_iter()
+        .map(|event| event.label)
+        .filter(|label| label.starts_with("book-") || label.starts_with("durable::saga"))
+        .collect();
+    assert_eq!(
+        labels,
+        [
+            "book-flight",
+            "book-hotel",
+            "book-car",
+            "durable::saga::compensate(trip, book-hotel)",
+            "durable::saga::compensate(trip, book-flight)",
+        ]
+    );
+
+    Ok(())
+}
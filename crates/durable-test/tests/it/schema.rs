@@ -0,0 +1,81 @@
+use durable_client::DurableClient;
+use durable_runtime::migrate::{Migrator, Options, SchemaDrift, SchemaObject};
+use durable_runtime::Config;
+
+#[sqlx::test]
+async fn migrated_schema_has_no_drift(pool: sqlx::PgPool) -> anyhow::Result<()> {
+    let mut conn = pool.acquire().await?;
+    let report = Migrator::new().check_schema(&mut conn).await?;
+
+    assert!(report.drift().is_empty(), "{:#?}", report.drift());
+
+    Ok(())
+}
+
+#[sqlx::test]
+async fn schema_drift_is_reported(pool: sqlx::PgPool) -> anyhow::Result<()> {
+    let mut conn = pool.acquire().await?;
+
+    sqlx::query("DROP INDEX durable.task_parent")
+        .execute(&mut *conn)
+        .await?;
+    sqlx::query("ALTER TABLE durable.task ADD COLUMN extra text")
+        .execute(&mut *conn)
+        .await?;
+
+    let report = Migrator::new().check_schema(&mut conn).await?;
+    let drift = report.drift();
+
+    assert!(drift.contains(&SchemaDrift::Missing(SchemaObject::Index(
+        "task_parent".into()
+    ))));
+    assert!(
+        drift.contains(&SchemaDrift::Unexpected(SchemaObject::Column {
+            table: "task".into(),
+            column: "extra".into(),
+        }))
+    );
+    assert_eq!(drift.iter().filter(|drift| drift.is_breaking()).count(), 1);
+    assert!(!report.is_compatible());
+
+    Ok(())
+}
+
+#[sqlx::test]
+async fn durable_can_be_installed_into_another_schema(pool: sqlx::PgPool) -> anyhow::Result<()> {
+    let migrator = Migrator::new().with_schema("staging");
+    let mut conn = pool.acquire().await?;
+    let options = Options {
+        target: migrator.latest(),
+        ..Default::default()
+    };
+    migrator.migrate(&mut conn, &options).await?;
+
+    let report = migrator.check_schema(&mut conn).await?;
+    assert!(report.drift().is_empty(), "{:#?}", report.drift());
+    assert_eq!(report.database_version(), Some(migrator.latest_version()));
+
+    let _guard =
+        durable_test::spawn_worker_with(pool.clone(), Config::new().schema("staging")).await?;
+    let client = DurableClient::new(pool.clone())?.with_schema("staging");
+    let program = crate::load_binary(&client, "task-details.wasm").await?;
+
+    let task = client
+        .launch("test task", &program, &serde_json::json!(null))
+        .await?;
+    let status = task.wait(&client, None).await?;
+    assert!(status.success());
+
+    // Nothing should have been written to the default installation.
+    let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM durable.task")
+        .fetch_one(&mut *conn)
+        .await?;
+    assert_eq!(count, 0);
+
+    let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM staging.task")
+        .fetch_one(&mut *conn)
+        .await?;
+    assert_eq!(count, 1);
+
+    Ok(())
+}
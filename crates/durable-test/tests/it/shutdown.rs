@@ -1,5 +1,8 @@
 use std::time::Duration;
 
+use durable_client::DurableClient;
+use durable_runtime::Config;
+
 #[sqlx::test]
 async fn shutdown_timeout(pool: sqlx::PgPool) -> anyhow::Result<()> {
     let guard = durable_test::spawn_worker(pool.clone()).await?;
@@ -15,3 +18,71 @@ async fn shutdown_timeout(pool: sqlx::PgPool) -> anyhow::Result<()> {
         }
     }
 }
+
+#[sqlx::test]
+async fn busy_task_does_not_block_worker(pool: sqlx::PgPool) -> anyhow::Result<()> {
+    let guard = durable_test::spawn_worker(pool.clone()).await?;
+    let client = DurableClient::new(pool)?;
+    let busy = crate::load_binary(&client, "busy-loop.wasm").await?;
+    let program = crate::load_binary(&client, "task-details.wasm").await?;
+
+    client
+        .launch("busy task", &busy, &serde_json::json!(null))
+        .await?;
+    let task = client
+        .launch("other task", &program, &serde_json::json!(null))
+        .await?;
+
+    // Without preemption the busy task would hold on to the runtime thread forever.
+    let status = task.wait(&client, Some(Duration::from_secs(10))).await?;
+    assert!(status.success());
+
+    guard.handle().shutdown();
+    match tokio::time::timeout(Duration::from_secs(5), guard).await {
+        Ok(result) => result,
+        Err(_) => {
+            panic!("unable to shut down runtime in under 5s")
+        }
+    }
+}
+
+#[sqlx::test]
+async fn checkpoint_releases_task_on_shutdown(pool: sqlx::PgPool) -> anyhow::Result<()> {
+    // Epoch interruption would also abort the task on shutdown, so disable it to
+    // make sure that it is the checkpoint that gives up the task.
+    let guard = durable_test::spawn_worker_with(
+        pool.clone(),
+        Config::new()
+            .suspend_margin(Duration::from_secs(1))
+            .suspend_timeout(Duration::from_secs(1))
+            .epoch_interval(None),
+    )
+    .await?;
+    let client = DurableClient::new(pool.clone())?;
+    let program = crate::load_binary(&client, "checkpoint-loop.wasm").await?;
+
+    let task = client
+        .launch("checkpoint task", &program, &serde_json::json!(null))
+        .await?;
+
+    // Give the worker a chance to start running the task.
+    tokio::time::sleep(Duration::from_secs(1)).await;
+
+    guard.handle().shutdown();
+    match tokio::time::timeout(Duration::from_secs(5), guard).await {
+        Ok(result) => result?,
+        Err(_) => {
+            panic!("unable to shut down runtime in under 5s")
+        }
+    }
+
+    let state = sqlx::query_scalar!(
+        r#"SELECT state::text as "state!" FROM durable.task WHERE id = $1"#,
+        task.id()
+    )
+    .fetch_one(&pool)
+    .await?;
+    assert_eq!(state, "ready");
+
+    Ok(())
+}
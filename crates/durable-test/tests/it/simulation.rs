@@ -0,0 +1,98 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use durable_client::{DurableClient, Task};
+use durable_runtime::sim::Simulation;
+use durable_runtime::Config;
+use futures::TryStreamExt;
+
+fn simulation(seed: u64) -> Simulation {
+    Simulation::new(seed).http(|request: &reqwest::Request| {
+        http::Response::builder()
+            .status(200)
+            .body(format!("mocked {}", request.url().path()).into_bytes())
+            .unwrap()
+    })
+}
+
+/// Wait until `count` tasks are blocked on the virtual clock.
+async fn wait_for_sleeping(sim: &Simulation, count: usize) {
+    tokio::time::timeout(Duration::from_secs(30), async {
+        while sim.sleeping() < count {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await
+    .expect("tasks did not start waiting within 30s");
+}
+
+async fn logs(client: &DurableClient, task: &Task) -> anyhow::Result<String> {
+    let status = task.wait(client, Some(Duration::from_secs(30))).await?;
+    assert!(status.success());
+
+    Ok(task
+        .read_logs(client)
+        .try_collect::<Vec<_>>()
+        .await?
+        .concat())
+}
+
+#[sqlx::test]
+async fn virtual_clock_drives_timeouts(pool: sqlx::PgPool) -> anyhow::Result<()> {
+    let client = DurableClient::new(pool.clone())?;
+    let program = crate::load_binary(&client, "simulated.wasm").await?;
+
+    let sim = Arc::new(simulation(7));
+    let start = sim.now();
+    let _guard =
+        durable_test::spawn_simulated_worker(pool.clone(), Config::new(), sim.clone()).await?;
+
+    let mut tasks = Vec::new();
+    for i in 0..3 {
+        tasks.push(client.launch(format!("sim-{i}"), &program, &()).await?);
+    }
+
+    // None of the tasks can complete until the clock has moved forward.
+    wait_for_sleeping(&sim, tasks.len()).await;
+    sim.advance(Duration::from_secs(3600));
+
+    for task in &tasks {
+        assert_eq!(
+            logs(&client, task).await?,
+            "notified: false\nwaited: 3600s\nresponse: 200 mocked /status\n"
+        );
+    }
+
+    assert_eq!(sim.now() - start, chrono::TimeDelta::hours(1));
+
+    Ok(())
+}
+
+#[sqlx::test]
+async fn notified_task_wakes_before_the_clock_moves(pool: sqlx::PgPool) -> anyhow::Result<()> {
+    let client = DurableClient::new(pool.clone())?;
+    let program = crate::load_binary(&client, "simulated.wasm").await?;
+
+    let sim = Arc::new(simulation(11));
+    let _guard =
+        durable_test::spawn_simulated_worker(pool.clone(), Config::new(), sim.clone()).await?;
+
+    let woken = client.launch("woken", &program, &()).await?;
+    let sleeping = client.launch("sleeping", &program, &()).await?;
+
+    wait_for_sleeping(&sim, 2).await;
+    woken.notify("wake", &(), &client).await?;
+
+    assert_eq!(
+        logs(&client, &woken).await?,
+        "notified: true\nwaited: 0s\nresponse: 200 mocked /status\n"
+    );
+
+    sim.advance(Duration::from_secs(3600));
+    assert_eq!(
+        logs(&client, &sleeping).await?,
+        "notified: false\nwaited: 3600s\nresponse: 200 mocked /status\n"
+    );
+
+    Ok(())
+}
@@ -1,4 +1,10 @@
-use durable_client::DurableClient;
+use std::time::Duration;
+
+use anyhow::Context;
+use durable_client::{DurableClient, LaunchOptions};
+use durable_runtime::policy::AllowlistPolicy;
+use durable_runtime::{Config, WorkerBuilder};
+use futures::TryStreamExt;
 
 #[sqlx::test(fixtures("extra-table"))]
 async fn enum_insert(pool: sqlx::PgPool) -> anyhow::Result<()> {
@@ -9,7 +15,497 @@ async fn enum_insert(pool: sqlx::PgPool) -> anyhow::Result<()> {
     let task = client
         .launch("enum insert test", &program, &serde_json::json!(null))
         .await?;
-    let status = task.wait(&client).await?;
+    let status = task.wait(&client, None).await?;
+
+    assert!(status.success());
+
+    Ok(())
+}
+
+#[sqlx::test]
+async fn query_builder(pool: sqlx::PgPool) -> anyhow::Result<()> {
+    let _guard = durable_test::spawn_worker(pool.clone()).await?;
+    let client = DurableClient::new(pool)?;
+    let program = crate::load_binary(&client, "sqlx-query-builder.wasm").await?;
+
+    let task = client
+        .launch("query builder test", &program, &serde_json::json!(null))
+        .await?;
+    let status = task.wait(&client, None).await?;
+
+    assert!(status.success());
+
+    Ok(())
+}
+
+#[sqlx::test]
+async fn savepoint(pool: sqlx::PgPool) -> anyhow::Result<()> {
+    let _guard = durable_test::spawn_worker(pool.clone()).await?;
+    let client = DurableClient::new(pool)?;
+    let program = crate::load_binary(&client, "sqlx-savepoint.wasm").await?;
+
+    let task = client
+        .launch("savepoint test", &program, &serde_json::json!(null))
+        .await?;
+    let status = task.wait(&client, None).await?;
+
+    assert!(status.success());
+
+    Ok(())
+}
+
+#[sqlx::test]
+async fn copy_in(pool: sqlx::PgPool) -> anyhow::Result<()> {
+    let _guard = durable_test::spawn_worker(pool.clone()).await?;
+    let client = DurableClient::new(pool)?;
+    let program = crate::load_binary(&client, "sqlx-copy-in.wasm").await?;
+
+    let task = client
+        .launch("copy in test", &program, &serde_json::json!(null))
+        .await?;
+    let status = task.wait(&client, None).await?;
+
+    assert!(status.success());
+
+    Ok(())
+}
+
+#[sqlx::test]
+async fn batch(pool: sqlx::PgPool) -> anyhow::Result<()> {
+    let _guard = durable_test::spawn_worker(pool.clone()).await?;
+    let client = DurableClient::new(pool)?;
+    let program = crate::load_binary(&client, "sqlx-batch.wasm").await?;
+
+    let task = client
+        .launch("batch test", &program, &serde_json::json!(null))
+        .await?;
+    let status = task.wait(&client, None).await?;
+
+    assert!(status.success());
+
+    Ok(())
+}
+
+/// Run the batch workflow on a worker with `config`, returning whether it
+/// succeeded along with its logs.
+async fn run_batch_with(pool: sqlx::PgPool, config: Config) -> anyhow::Result<(bool, String)> {
+    let _guard = durable_test::spawn_worker_with(pool.clone(), config).await?;
+    let client = DurableClient::new(pool)?;
+    let program = crate::load_binary(&client, "sqlx-batch.wasm").await?;
+
+    let task = client
+        .launch("limits test", &program, &serde_json::json!(null))
+        .await?;
+    let status = task.wait(&client, None).await?;
+
+    let logs = task
+        .read_logs(&client)
+        .try_fold(String::new(), |mut acc, item| {
+            acc.push_str(&item);
+            std::future::ready(Ok(acc))
+        })
+        .await?;
+
+    Ok((status.success(), logs))
+}
+
+#[sqlx::test]
+async fn statement_limit(pool: sqlx::PgPool) -> anyhow::Result<()> {
+    // The batch in the workflow runs 4 statements in a single transaction.
+    let config = Config::new().max_statements_per_transaction(3);
+    let (success, logs) = run_batch_with(pool, config).await?;
+
+    assert!(!success);
+    assert!(
+        logs.contains("maximum of 3 statements allowed by the worker"),
+        "unexpected logs: {logs:?}"
+    );
+
+    Ok(())
+}
+
+#[sqlx::test]
+async fn row_limit(pool: sqlx::PgPool) -> anyhow::Result<()> {
+    // The workflow reads back 3 rows at the end.
+    let config = Config::new().max_query_rows(2);
+    let (success, logs) = run_batch_with(pool, config).await?;
+
+    assert!(!success);
+    assert!(
+        logs.contains("maximum of 2 rows allowed by the worker"),
+        "unexpected logs: {logs:?}"
+    );
+
+    Ok(())
+}
+
+#[sqlx::test]
+async fn limits_allow_transactions_within_them(pool: sqlx::PgPool) -> anyhow::Result<()> {
+    let config = Config::new()
+        .max_statements_per_transaction(4)
+        .max_query_rows(3)
+        .max_database_transaction_duration(Duration::from_secs(60));
+    let (success, logs) = run_batch_with(pool, config).await?;
+
+    assert!(success, "task failed with logs: {logs:?}");
+
+    Ok(())
+}
+
+#[sqlx::test]
+async fn describe(pool: sqlx::PgPool) -> anyhow::Result<()> {
+    let _guard = durable_test::spawn_worker(pool.clone()).await?;
+    let client = DurableClient::new(pool)?;
+    let program = crate::load_binary(&client, "sqlx-describe.wasm").await?;
+
+    let task = client
+        .launch("describe test", &program, &serde_json::json!(null))
+        .await?;
+    let status = task.wait(&client, None).await?;
+
+    assert!(status.success());
+
+    Ok(())
+}
+
+#[sqlx::test]
+async fn explain(pool: sqlx::PgPool) -> anyhow::Result<()> {
+    let _guard = durable_test::spawn_worker(pool.clone()).await?;
+    let client = DurableClient::new(pool)?;
+    let program = crate::load_binary(&client, "sqlx-explain.wasm").await?;
+
+    let task = client
+        .launch("explain test", &program, &serde_json::json!(null))
+        .await?;
+    let status = task.wait(&client, None).await?;
+
+    assert!(status.success());
+
+    let trace = task.trace(&client).await?;
+    let explained = trace
+        .iter()
+        .filter(|entry| entry.label == "durable::sqlx::explain")
+        .count();
+
+    // One for the successful plan and one for the query that failed to plan.
+    assert_eq!(explained, 2);
+
+    Ok(())
+}
+
+#[sqlx::test]
+async fn clear_statement_cache(pool: sqlx::PgPool) -> anyhow::Result<()> {
+    let _guard = durable_test::spawn_worker(pool.clone()).await?;
+    let client = DurableClient::new(pool)?;
+    let program = crate::load_binary(&client, "sqlx-statement-cache.wasm").await?;
+
+    let task = client
+        .launch("statement cache test", &program, &serde_json::json!(null))
+        .await?;
+    let status = task.wait(&client, None).await?;
+
+    assert!(status.success());
+
+    Ok(())
+}
+
+#[sqlx::test]
+async fn listen(pool: sqlx::PgPool) -> anyhow::Result<()> {
+    let _guard = durable_test::spawn_worker(pool.clone()).await?;
+    let client = DurableClient::new(pool.clone())?;
+    let program = crate::load_binary(&client, "sqlx-listen.wasm").await?;
+
+    let task = client
+        .launch("listen test", &program, &serde_json::json!(null))
+        .await?;
+
+    // Notifications sent before the task starts listening are dropped, so we keep
+    // sending them until the task completes.
+    let notify = async {
+        loop {
+            let result = sqlx::query("SELECT pg_notify('durable_test_listen', 'hello')")
+                .execute(&pool)
+                .await;
+
+            if let Err(e) = result {
+                break e;
+            }
+
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+    };
+
+    let future = async {
+        tokio::select! {
+            status = task.wait(&client, None) => status,
+            error = notify => Err(error.into()),
+        }
+    };
+
+    let status = tokio::time::timeout(Duration::from_secs(30), future)
+        .await
+        .context("task failed to complete in under 30s")??;
+    assert!(status.success());
+
+    Ok(())
+}
+
+#[sqlx::test]
+async fn listen_while_suspended(pool: sqlx::PgPool) -> anyhow::Result<()> {
+    let _guard = durable_test::spawn_worker_with(
+        pool.clone(),
+        Config::new()
+            .suspend_margin(Duration::ZERO)
+            .suspend_timeout(Duration::ZERO),
+    )
+    .await?;
+    let client = DurableClient::new(pool.clone())?;
+    let program = crate::load_binary(&client, "sqlx-listen.wasm").await?;
+
+    let task = client
+        .launch("listen test", &program, &serde_json::json!(null))
+        .await?;
+
+    let suspended = async {
+        loop {
+            let state: String =
+                sqlx::query_scalar("SELECT state::text FROM durable.task WHERE id = $1")
+                    .bind(task.id())
+                    .fetch_one(&pool)
+                    .await?;
+
+            if state == "suspended" {
+                break;
+            }
+
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+
+        anyhow::Ok(())
+    };
+    tokio::time::timeout(Duration::from_secs(30), suspended)
+        .await
+        .context("task failed to suspend itself within 30s")??;
+
+    // The task is no longer running anywhere, so the worker has to record the
+    // notification for it.
+    sqlx::query("SELECT pg_notify('durable_test_listen', 'hello')")
+        .execute(&pool)
+        .await?;
+
+    let status = tokio::time::timeout(Duration::from_secs(30), task.wait(&client, None))
+        .await
+        .context("task failed to complete in under 30s")??;
+    assert!(status.success());
+
+    let listeners: i64 = sqlx::query_scalar("SELECT count(*) FROM durable.pg_listener")
+        .fetch_one(&pool)
+        .await?;
+    assert_eq!(listeners, 0);
+
+    Ok(())
+}
+
+#[sqlx::test]
+async fn query_stats(pool: sqlx::PgPool) -> anyhow::Result<()> {
+    let _guard = durable_test::spawn_worker(pool.clone()).await?;
+    let client = DurableClient::new(pool)?;
+    let program = crate::load_binary(&client, "sqlx-query-stats.wasm").await?;
+
+    let task = client
+        .launch("query stats test", &program, &serde_json::json!(null))
+        .await?;
+    let status = task.wait(&client, None).await?;
+
+    assert!(status.success());
+
+    Ok(())
+}
+
+#[sqlx::test]
+async fn transaction_options(pool: sqlx::PgPool) -> anyhow::Result<()> {
+    let _guard = durable_test::spawn_worker(pool.clone()).await?;
+    let client = DurableClient::new(pool)?;
+    let program = crate::load_binary(&client, "sqlx-transaction-options.wasm").await?;
+
+    let task = client
+        .launch(
+            "transaction options test",
+            &program,
+            &serde_json::json!(null),
+        )
+        .await?;
+    let status = task.wait(&client, None).await?;
+
+    assert!(status.success());
+
+    Ok(())
+}
+
+#[sqlx::test]
+async fn sql_policy(pool: sqlx::PgPool) -> anyhow::Result<()> {
+    sqlx::query("CREATE TABLE policy_allowed(id bigint PRIMARY KEY)")
+        .execute(&pool)
+        .await?;
+    sqlx::query("CREATE TABLE policy_denied(id bigint PRIMARY KEY)")
+        .execute(&pool)
+        .await?;
+
+    let policy = AllowlistPolicy::new().table("public", "policy_allowed");
+    let _guard = durable_test::spawn_worker_from(
+        WorkerBuilder::new(pool.clone())
+            .config(Config::new().debug_emit_task_logs(true))
+            .program_sql_policy("sqlx-policy.wasm", Box::new(policy)),
+    )
+    .await?;
+    let client = DurableClient::new(pool)?;
+    let program = crate::load_binary(&client, "sqlx-policy.wasm").await?;
+
+    let task = client
+        .launch("sql policy test", &program, &serde_json::json!(null))
+        .await?;
+    let status = task.wait(&client, None).await?;
+
+    assert!(status.success());
+
+    // Neither of the tables that the workflow attempted to create from a query
+    // should exist.
+    let created: Vec<String> = sqlx::query_scalar(
+        "SELECT relname::text FROM pg_class WHERE relname IN ('policy_ctas', \
+         'policy_select_into')",
+    )
+    .fetch_all(client.pool())
+    .await?;
+
+    assert!(created.is_empty(), "tables were created: {created:?}");
+
+    Ok(())
+}
+
+#[sqlx::test]
+async fn queue_sql_policy(pool: sqlx::PgPool) -> anyhow::Result<()> {
+    sqlx::query("CREATE TABLE policy_allowed(id bigint PRIMARY KEY)")
+        .execute(&pool)
+        .await?;
+    sqlx::query("CREATE TABLE policy_denied(id bigint PRIMARY KEY)")
+        .execute(&pool)
+        .await?;
+
+    // The program policy allows both tables but the queue policy only allows
+    // one of them. Statements must be allowed by both.
+    let program_policy = AllowlistPolicy::new().schema("public");
+    let queue_policy = AllowlistPolicy::new().table("public", "policy_allowed");
+    let _guard = durable_test::spawn_worker_from(
+        WorkerBuilder::new(pool.clone())
+            .config(Config::new().debug_emit_task_logs(true))
+            .program_sql_policy("sqlx-policy.wasm", Box::new(program_policy))
+            .queue_sql_policy("policy-tenant", Box::new(queue_policy)),
+    )
+    .await?;
+    let client = DurableClient::new(pool)?.with_tenant("policy-tenant");
+    let program = crate::load_binary(&client, "sqlx-policy.wasm").await?;
+
+    let task = client
+        .launch("queue sql policy test", &program, &serde_json::json!(null))
+        .await?;
+    let status = task.wait(&client, None).await?;
+
+    assert!(status.success());
+
+    Ok(())
+}
+
+#[sqlx::test]
+async fn sql_context(pool: sqlx::PgPool) -> anyhow::Result<()> {
+    let _guard = durable_test::spawn_worker(pool.clone()).await?;
+    let client = DurableClient::new(pool)?;
+    let program = crate::load_binary(&client, "sqlx-sql-context.wasm").await?;
+
+    let options = LaunchOptions::new("sql context test", serde_json::json!(null))
+        .sql_context("app.user_id", "42");
+    let task = client
+        .launch_many(&program, [options])
+        .await?
+        .pop()
+        .context("no task was launched")?;
+    let status = task.wait(&client, None).await?;
+
+    assert!(status.success());
+
+    Ok(())
+}
+
+#[sqlx::test]
+async fn trace_reports_database_transactions(pool: sqlx::PgPool) -> anyhow::Result<()> {
+    let _guard = durable_test::spawn_worker(pool.clone()).await?;
+    let client = DurableClient::new(pool)?;
+    let program = crate::load_binary(&client, "sqlx-query-builder.wasm").await?;
+
+    let task = client
+        .launch("query builder test", &program, &serde_json::json!(null))
+        .await?;
+    let status = task.wait(&client, None).await?;
+    assert!(status.success());
+
+    let trace = task.trace(&client).await?;
+    assert!(!trace.is_empty());
+
+    for (index, entry) in trace.iter().enumerate() {
+        assert_eq!(entry.index, index as i32);
+        assert!(entry.is_db.is_some());
+    }
+
+    Ok(())
+}
+
+#[sqlx::test]
+async fn raw_value(pool: sqlx::PgPool) -> anyhow::Result<()> {
+    let _guard = durable_test::spawn_worker(pool.clone()).await?;
+    let client = DurableClient::new(pool)?;
+    let program = crate::load_binary(&client, "sqlx-raw-value.wasm").await?;
+
+    let task = client
+        .launch("raw value test", &program, &serde_json::json!(null))
+        .await?;
+    let status = task.wait(&client, None).await?;
+
+    assert!(status.success());
+
+    Ok(())
+}
+
+/// Decoding a raw value must not need any connection beyond the one held by
+/// the transaction.
+#[sqlx::test]
+async fn raw_value_single_connection(
+    pool_opts: sqlx::postgres::PgPoolOptions,
+    connect_opts: sqlx::postgres::PgConnectOptions,
+) -> anyhow::Result<()> {
+    // One connection is held by the worker's event listener, which leaves only
+    // the one used by the workflow's transaction.
+    let pool = pool_opts
+        .max_connections(2)
+        .acquire_timeout(Duration::from_secs(5))
+        .connect_with(connect_opts)
+        .await?;
+
+    let _guard = durable_test::spawn_worker_with(
+        pool.clone(),
+        Config::new()
+            .suspend_margin(Duration::from_secs(1))
+            .suspend_timeout(Duration::from_secs(1))
+            .max_workflow_connections(1),
+    )
+    .await?;
+    let client = DurableClient::new(pool)?;
+    let program = crate::load_binary(&client, "sqlx-raw-value.wasm").await?;
+
+    let task = client
+        .launch("raw value test", &program, &serde_json::json!(null))
+        .await?;
+    let status = tokio::time::timeout(Duration::from_secs(30), task.wait(&client, None))
+        .await
+        .context("task did not complete")??;
 
     assert!(status.success());
 
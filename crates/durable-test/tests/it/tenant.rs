@@ -0,0 +1,104 @@
+use std::time::Duration;
+
+use durable_client::DurableClient;
+use durable_runtime::Config;
+use futures::TryStreamExt;
+
+#[sqlx::test]
+async fn tenants_cannot_see_each_others_tasks(pool: sqlx::PgPool) -> anyhow::Result<()> {
+    let _guard = durable_test::spawn_worker(pool.clone()).await?;
+    let client = DurableClient::new(pool)?;
+    let team_a = client.with_tenant("team-a");
+    let team_b = client.with_tenant("team-b");
+    let program = crate::load_binary(&team_a, "task-details.wasm").await?;
+
+    let task = team_a
+        .launch("test task", &program, &serde_json::json!(null))
+        .await?;
+    let status = task.wait(&team_a, None).await?;
+    assert!(status.success());
+
+    assert!(task.wait(&team_b, None).await.is_err());
+    assert!(task.wait(&client, None).await.is_err());
+    assert!(task.events(&team_b).await.is_err());
+    assert!(task
+        .notify(&team_b, "event", &serde_json::json!(null))
+        .await
+        .is_err());
+
+    let logs: Result<Vec<String>, _> = task.read_logs(&team_b).try_collect().await;
+    assert!(logs.is_err());
+
+    // Programs are scoped to the tenant that loaded them.
+    assert!(team_b
+        .launch("test task", &program, &serde_json::json!(null))
+        .await
+        .is_err());
+
+    Ok(())
+}
+
+#[sqlx::test]
+async fn tenant_quota_limits_concurrent_tasks(pool: sqlx::PgPool) -> anyhow::Result<()> {
+    let _guard =
+        durable_test::spawn_worker_with(pool.clone(), Config::new().tenant_quota("team-a", 1))
+            .await?;
+    let client = DurableClient::new(pool)?.with_tenant("team-a");
+    let program = crate::load_binary(&client, "task-details.wasm").await?;
+
+    let mut tasks = Vec::new();
+    for _ in 0..4 {
+        tasks.push(
+            client
+                .launch("test task", &program, &serde_json::json!(null))
+                .await?,
+        );
+    }
+
+    for task in tasks {
+        let status = task.wait(&client, None).await?;
+        assert!(status.success());
+    }
+
+    Ok(())
+}
+
+#[sqlx::test]
+async fn queue_stats_count_pending_tasks(pool: sqlx::PgPool) -> anyhow::Result<()> {
+    // A quota of zero means that none of team-a's tasks will ever start.
+    let guard = durable_test::spawn_worker_with(
+        pool.clone(),
+        Config::new()
+            .tenant_quota("team-a", 0)
+            .queue_stats_interval(Some(Duration::from_millis(100))),
+    )
+    .await?;
+    let client = DurableClient::new(pool)?.with_tenant("team-a");
+    let program = crate::load_binary(&client, "task-details.wasm").await?;
+
+    for _ in 0..2 {
+        client
+            .launch("test task", &program, &serde_json::json!(null))
+            .await?;
+    }
+
+    let queue = tokio::time::timeout(Duration::from_secs(10), async {
+        loop {
+            let stats = guard.handle().stats().await?;
+            if let Some(queue) = stats.queues.into_iter().find(|q| q.queue == "team-a") {
+                if queue.pending == 2 {
+                    break anyhow::Ok(queue);
+                }
+            }
+
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+    })
+    .await
+    .expect("queue stats were not updated within 10s")?;
+
+    assert_eq!(queue.running, 0);
+    assert!(queue.oldest_pending_at.is_some());
+
+    Ok(())
+}
@@ -0,0 +1,74 @@
+use std::time::Duration;
+
+use anyhow::Context;
+use durable_client::{DurableClient, WorkflowDef};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize)]
+struct Input {
+    a: u64,
+    b: u64,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Output {
+    sum: u64,
+}
+
+// This mirrors the definition in the typed workflow.
+struct Add;
+
+impl WorkflowDef for Add {
+    type Input = Input;
+    type Output = Output;
+
+    const NAME: &'static str = "add";
+}
+
+#[sqlx::test]
+async fn typed_workflow_result(pool: sqlx::PgPool) -> anyhow::Result<()> {
+    let _guard = durable_test::spawn_worker(pool.clone()).await?;
+    let client = DurableClient::new(pool)?;
+    let program = crate::load_binary(&client, "typed.wasm").await?;
+
+    let task = client
+        .launch_typed::<Add>(&program, &Input { a: 2, b: 3 })
+        .await?;
+    let status = task.wait(&client, None).await?;
+    assert!(status.success());
+
+    let output = task.result_typed::<Add>(&client).await?;
+    assert_eq!(output, Some(Output { sum: 5 }));
+
+    Ok(())
+}
+
+#[sqlx::test]
+async fn wait_many_returns_results(pool: sqlx::PgPool) -> anyhow::Result<()> {
+    let _guard = durable_test::spawn_worker(pool.clone()).await?;
+    let client = DurableClient::new(pool)?;
+    let program = crate::load_binary(&client, "typed.wasm").await?;
+
+    let mut tasks = Vec::new();
+    for a in 0..4 {
+        tasks.push(
+            client
+                .launch_typed::<Add>(&program, &Input { a, b: 10 })
+                .await?,
+        );
+    }
+
+    let statuses = client
+        .wait_many(&tasks, Some(Duration::from_secs(30)))
+        .await?;
+    assert_eq!(statuses.len(), tasks.len());
+
+    for (a, status) in statuses.iter().enumerate() {
+        assert!(status.success());
+
+        let output: Output = status.result_as().context("task had no result")??;
+        assert_eq!(output, Output { sum: a as u64 + 10 });
+    }
+
+    Ok(())
+}
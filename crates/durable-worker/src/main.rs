@@ -3,7 +3,8 @@ use std::time::Duration;
 
 use anyhow::Context;
 use clap::Parser;
-use durable_runtime::{WorkerBuilder, WorkerHandle};
+use durable_runtime::migrate::SchemaValidation;
+use durable_runtime::{Config, WorkerBuilder, WorkerHandle};
 use tracing_subscriber::layer::SubscriberExt;
 use tracing_subscriber::util::SubscriberInitExt;
 use tracing_subscriber::Layer;
@@ -17,6 +18,39 @@ struct Args {
     /// version does not match the expected version.
     #[arg(long)]
     migrate: bool,
+
+    /// How to handle differences between the database schema and the schema
+    /// expected by this worker.
+    #[arg(long, value_enum, default_value_t = Validation::Enforce)]
+    schema_validation: Validation,
+
+    /// The durable instance that this worker belongs to.
+    ///
+    /// The worker only runs tasks that were launched into the same instance.
+    #[arg(long, env = "DURABLE_INSTANCE")]
+    instance: Option<String>,
+}
+
+#[derive(Copy, Clone, Debug, clap::ValueEnum)]
+enum Validation {
+    /// Refuse to start if the database schema is incompatible.
+    Enforce,
+
+    /// Log any differences but start anyway.
+    Warn,
+
+    /// Do not check the database schema.
+    Disabled,
+}
+
+impl From<Validation> for SchemaValidation {
+    fn from(validation: Validation) -> Self {
+        match validation {
+            Validation::Enforce => Self::Enforce,
+            Validation::Warn => Self::Warn,
+            Validation::Disabled => Self::Disabled,
+        }
+    }
 }
 
 #[tokio::main]
@@ -54,9 +88,16 @@ async fn main() -> anyhow::Result<()> {
     config.profiler(wasmtime::ProfilingStrategy::PerfMap);
     config.debug_info(true);
 
+    let mut worker_config = Config::new();
+    if let Some(instance) = args.instance {
+        worker_config = worker_config.instance(instance);
+    }
+
     let mut worker = WorkerBuilder::new(pool)
+        .config(worker_config)
         .wasmtime_config(config)
         .migrate(args.migrate)
+        .schema_validation(args.schema_validation.into())
         .build()
         .await?;
 
@@ -0,0 +1,57 @@
+//! Typed workflow definitions.
+//!
+//! A [`WorkflowDef`] describes the input a workflow is launched with and the
+//! output it produces. This crate has no dependencies beyond `serde` so that a
+//! definition can live in a crate that is shared by both the workflow itself
+//! (which uses it via `durable`) and the services that launch it (which use it
+//! via `durable-client`).
+//!
+//! ```
+//! use durable_workflow::WorkflowDef;
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize)]
+//! pub struct ResizeInput {
+//!     pub url: String,
+//!     pub width: u32,
+//! }
+//!
+//! #[derive(Serialize, Deserialize)]
+//! pub struct ResizeOutput {
+//!     pub key: String,
+//! }
+//!
+//! pub struct Resize;
+//!
+//! impl WorkflowDef for Resize {
+//!     type Input = ResizeInput;
+//!     type Output = ResizeOutput;
+//!
+//!     const NAME: &'static str = "resize";
+//! }
+//! ```
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+/// The definition of a workflow with a typed input and output.
+///
+/// The workflow is launched with its [`Input`] as the task data and is
+/// expected to set its [`Output`] as the result of the task.
+///
+/// [`Input`]: WorkflowDef::Input
+/// [`Output`]: WorkflowDef::Output
+pub trait WorkflowDef {
+    /// The data that the workflow is launched with.
+    type Input: Serialize + DeserializeOwned;
+
+    /// The result that the workflow produces once it completes.
+    type Output: Serialize + DeserializeOwned;
+
+    /// The name of the workflow.
+    ///
+    /// This is used as the entrypoint that tasks for this workflow are
+    /// launched with, so the program must export the workflow under this
+    /// name.
+    const NAME: &'static str;
+}
@@ -0,0 +1,130 @@
+//! Pause a workflow until it has been approved.
+//!
+//! A workflow waits for an approval under a key that it chooses. Approvals
+//! are given from outside the workflow using `DurableClient::approve` in
+//! `durable-client`, or via the `durable approve` CLI command.
+//!
+//! ```no_run
+//! use std::time::Duration;
+//!
+//! #[derive(serde::Deserialize)]
+//! struct Decision {
+//!     approved: bool,
+//! }
+//!
+//! let timeout = Duration::from_secs(7 * 24 * 60 * 60);
+//! match durable::approval::wait("deploy", timeout) {
+//!     Some(approval) => {
+//!         let decision: Decision = approval.payload().expect("invalid approval payload");
+//!         println!(
+//!             "approved by {:?}: {}",
+//!             approval.approved_by(),
+//!             decision.approved
+//!         );
+//!     }
+//!     None => println!("nobody approved the deploy in time"),
+//! }
+//! ```
+//!
+//! Approvals are stored in the database along with who gave them and when, so
+//! that there is a record of them even after the workflow has completed. Each
+//! key can only be approved once per task.
+//!
+//! Approvals are delivered to the task as notifications. Other notifications
+//! that arrive while waiting for an approval are kept and returned by
+//! [`notify::wait`](crate::notify::wait) later on.
+
+use std::time::{Duration, SystemTime};
+
+use serde::Deserialize;
+use serde_json::value::RawValue;
+
+use crate::notify::Notification;
+
+/// The event of the notification used to deliver approvals to a task.
+pub const APPROVAL_EVENT: &str = "durable:approval";
+
+/// An approval that was given to the current task.
+#[derive(Clone, Debug)]
+pub struct Approval {
+    key: String,
+    approved_by: Option<String>,
+    approved_at: SystemTime,
+    payload: Box<RawValue>,
+}
+
+impl Approval {
+    /// The key that was approved.
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    /// Who gave the approval, if the client reported that.
+    pub fn approved_by(&self) -> Option<&str> {
+        self.approved_by.as_deref()
+    }
+
+    /// The time at which the approval was given.
+    pub fn approved_at(&self) -> SystemTime {
+        self.approved_at
+    }
+
+    /// Deserialize the payload that was given with the approval.
+    pub fn payload<'de, T: Deserialize<'de>>(&'de self) -> serde_json::Result<T> {
+        serde_json::from_str(self.payload.get())
+    }
+
+    /// The JSON payload that was given with the approval.
+    pub fn raw_payload(&self) -> &RawValue {
+        &self.payload
+    }
+}
+
+#[derive(Deserialize)]
+struct ApprovalData {
+    key: String,
+    payload: Box<RawValue>,
+    approved_by: Option<String>,
+}
+
+fn approval_data(notification: &Notification) -> Option<ApprovalData> {
+    if notification.event != APPROVAL_EVENT {
+        return None;
+    }
+
+    notification.json().ok()
+}
+
+/// Block this workflow until `key` has been approved, or until `timeout` has
+/// elapsed.
+///
+/// Returns `None` if `key` was not approved within `timeout`.
+///
+/// # Traps
+/// Attempting to call this function within a transaction will result in a trap
+/// that instantly kills the workflow.
+pub fn wait(key: &str, timeout: Duration) -> Option<Approval> {
+    wait_until(key, SystemTime::now() + timeout)
+}
+
+/// Block this workflow until `key` has been approved, or until `deadline` has
+/// passed.
+///
+/// Returns `None` if `key` was not approved before `deadline`.
+///
+/// # Traps
+/// Attempting to call this function within a transaction will result in a trap
+/// that instantly kills the workflow.
+pub fn wait_until(key: &str, deadline: SystemTime) -> Option<Approval> {
+    let notification = crate::notify::wait_for(Some(deadline), |notification| {
+        approval_data(notification).is_some_and(|data| data.key == key)
+    })?;
+    let data = approval_data(&notification).expect("notification was not an approval");
+
+    Some(Approval {
+        key: data.key,
+        approved_by: data.approved_by,
+        approved_at: notification.created_at,
+        payload: data.payload,
+    })
+}
@@ -0,0 +1,71 @@
+//! Support for calling functions provided by custom host plugins.
+//!
+//! Operators can extend the durable runtime with their own plugins that
+//! expose additional WIT interfaces to workflows. Bindings for these
+//! interfaces can be generated within a workflow crate by calling
+//! `durable_bindgen::build` from its build script:
+//!
+//! ```ignore
+//! // build.rs
+//! fn main() -> durable_bindgen::Result<()> {
+//!     // Reads the WIT files in the `wit` directory of the crate.
+//!     durable_bindgen::build("example:counter/imports")
+//! }
+//! ```
+//!
+//! The bindings can then be included within the crate:
+//!
+//! ```ignore
+//! mod bindings {
+//!     #![allow(clippy::all)]
+//!
+//!     include!(concat!(env!("OUT_DIR"), "/bindings.rs"));
+//! }
+//! ```
+//!
+//! Host functions generally interact with the outside world so calling them
+//! directly from a workflow is not deterministic. Instead, they should be
+//! called within a transaction so that their results are saved and are not
+//! repeated when the workflow is restarted. [`host_call!`] does this for a
+//! single function call, reusing the current transaction if there is one:
+//!
+//! ```ignore
+//! let count = durable::host_call!(bindings::example::counter::counter::count());
+//! ```
+//!
+//! For more complex interactions (e.g. ones involving resources) use
+//! [`maybe_txn`] or [`transaction`](crate::transaction) directly.
+//!
+//! [`host_call!`]: crate::host_call
+
+#[doc(inline)]
+pub use durable_core::transaction::{in_transaction, maybe_txn};
+#[doc(hidden)]
+pub use durable_core::wit_bindgen_rt as rt;
+
+/// Call a function generated by `durable_bindgen` within a transaction.
+///
+/// If the workflow is already within a transaction then the function is
+/// called directly. Otherwise, the call is wrapped in a new transaction whose
+/// label is the path of the function being called.
+///
+/// The function's return type must implement `Serialize` and `Deserialize`.
+/// Types generated with `durable_bindgen::build` derive both.
+///
+/// ```ignore
+/// let count = durable::host_call!(bindings::example::counter::counter::count());
+/// ```
+#[macro_export]
+macro_rules! host_call {
+    ($first:ident $(:: $rest:ident)* ( $($arg:expr),* $(,)? )) => {
+        $crate::bindgen::maybe_txn(
+            concat!(
+                "durable::host_call(",
+                stringify!($first),
+                $("::", stringify!($rest),)*
+                ")"
+            ),
+            || $first $(:: $rest)* ($($arg),*),
+        )
+    };
+}
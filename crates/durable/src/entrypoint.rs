@@ -0,0 +1,94 @@
+use std::fmt;
+
+/// Export several named workflows from a single program.
+///
+/// Normally, a durable program runs a single workflow: its `main` function.
+/// This macro exports the `durable:core/workflow` interface from the program
+/// so that tasks can instead be launched with a named entrypoint (via
+/// `LaunchOptions::entrypoint` in `durable-client`). The runtime will then
+/// call the function registered under that name.
+///
+/// ```no_run
+/// fn sync_orders() -> Result<(), String> {
+///     // ...
+///     # Ok(())
+/// }
+///
+/// fn sync_invoices() {
+///     // ...
+/// }
+///
+/// durable::entrypoints! {
+///     "sync-orders" => sync_orders,
+///     "sync-invoices" => sync_invoices,
+/// }
+///
+/// // Tasks launched without an entrypoint still run `main`.
+/// fn main() {
+///     sync_orders().unwrap();
+/// }
+/// ```
+///
+/// Each function takes no arguments and returns either `()` or a
+/// `Result<(), E>` where `E: Debug`. Returning an error marks the task as
+/// failed, just like returning an error from `main` does.
+///
+/// Launching a task with an entrypoint that is not registered here causes the
+/// task to fail.
+///
+/// This macro should only be used once per program.
+#[macro_export]
+macro_rules! entrypoints {
+    { $( $name:literal => $func:path ),* $(,)? } => {
+        const _: () = {
+            struct DurableEntrypoints;
+
+            impl $crate::exports::Guest for DurableEntrypoints {
+                fn run(entrypoint: ::std::string::String) -> ::std::result::Result<(), ()> {
+                    match entrypoint.as_str() {
+                        $( $name => $crate::exports::EntrypointResult::into_result($func()), )*
+                        _ => $crate::exports::unknown_entrypoint(&entrypoint),
+                    }
+                }
+            }
+
+            $crate::exports::export_workflow!(
+                DurableEntrypoints with_types_in $crate::exports::bindings
+            );
+        };
+    };
+}
+
+// These mirror the `result` type used by the WIT interface.
+#[doc(hidden)]
+#[allow(clippy::result_unit_err)]
+pub mod exports {
+    pub use durable_core::export_bindings as bindings;
+    pub use durable_core::export_bindings::{__export_workflow as export_workflow, Guest};
+
+    use super::*;
+
+    /// The return types that are allowed for a function passed to
+    /// [`entrypoints!`](crate::entrypoints).
+    pub trait EntrypointResult {
+        fn into_result(self) -> Result<(), ()>;
+    }
+
+    impl EntrypointResult for () {
+        fn into_result(self) -> Result<(), ()> {
+            Ok(())
+        }
+    }
+
+    impl<E: fmt::Debug> EntrypointResult for Result<(), E> {
+        fn into_result(self) -> Result<(), ()> {
+            // This matches what the standard library does when main returns an error.
+            self.map_err(|e| eprintln!("Error: {e:?}"))
+        }
+    }
+
+    pub fn unknown_entrypoint(entrypoint: &str) -> Result<(), ()> {
+        eprintln!("program has no entrypoint named `{entrypoint}`");
+        Err(())
+    }
+}
@@ -1,5 +1,5 @@
 use std::error::Error as StdError;
-use std::fmt::{self, Display};
+use std::fmt::{self, Display, Write};
 use std::iter::FusedIterator;
 use std::ops::Deref;
 
@@ -10,7 +10,27 @@ use std::ops::Deref;
 /// Many error types in Rust are not serializable. That is where this error
 /// steps in: it can be serialized, even if the underlying error type cannot.
 ///
-/// It does this by serializing all the messages involved in the error chain.
+/// It does this by serializing all the messages involved in the error chain,
+/// along with the [`ErrorKind`] of the error.
+///
+/// # Matching on errors
+/// Since the original error types are not preserved, each error is tagged with
+/// an [`ErrorKind`] describing the class of failure that caused it. The kind is
+/// determined when the error is created from one of the error types in this
+/// crate (e.g. an HTTP error has kind [`ErrorKind::Http`]) and survives
+/// serialization, so it can be reliably matched on:
+///
+/// ```
+/// # fn fetch() -> durable::Result<()> { Ok(()) }
+/// use durable::ErrorKind;
+///
+/// match fetch() {
+///     Ok(()) => (),
+///     Err(e) if e.kind() == ErrorKind::Http => { /* retry later */ }
+///     Err(e) => return Err(e),
+/// }
+/// # Ok::<(), durable::Error>(())
+/// ```
 ///
 /// # Limitations
 /// Ultimately, the way this error type works is by serializing the [`Display`]
@@ -30,6 +50,17 @@ impl Error {
         error.into()
     }
 
+    /// Create a new error object with an explicit [`ErrorKind`].
+    ///
+    /// Use this if the kind that would be inferred by [`Error::new`] is not the
+    /// one you want.
+    pub fn with_kind<E: StdError + Send + Sync + 'static>(kind: ErrorKind, error: E) -> Self {
+        Self(ErrorImpl {
+            kind,
+            error: Box::new(error),
+        })
+    }
+
     /// Create a new error object from a printable error message.
     ///
     /// If the argument implements [`std::error::Error`], prefer [`Error::new`]
@@ -41,13 +72,89 @@ impl Error {
         })
     }
 
+    /// Create a new error indicating that the workflow has given up.
+    ///
+    /// The returned error has kind [`ErrorKind::Abort`].
+    pub fn abort<M: Display>(message: M) -> Self {
+        Self::with_kind(
+            ErrorKind::Abort,
+            ErrorFrame {
+                message: message.to_string(),
+                source: None,
+            },
+        )
+    }
+
+    /// The class of failure that caused this error.
+    pub fn kind(&self) -> ErrorKind {
+        self.0.kind
+    }
+
+    /// The stable error code of this error.
+    ///
+    /// This is a shorthand for `self.kind().code()`.
+    pub fn code(&self) -> &'static str {
+        self.kind().code()
+    }
+
+    /// Replace the [`ErrorKind`] of this error.
+    pub fn set_kind(&mut self, kind: ErrorKind) {
+        self.0.kind = kind;
+    }
+
+    /// Wrap this error with some additional context.
+    ///
+    /// The context becomes the top-level message of the error and the
+    /// original error becomes its cause. The [`ErrorKind`] of the error is
+    /// preserved.
+    pub fn context<C: Display>(self, context: C) -> Self {
+        Self(ErrorImpl {
+            kind: self.0.kind,
+            error: Box::new(ContextError {
+                message: context.to_string(),
+                source: self.0.error,
+            }),
+        })
+    }
+
+    /// Create a new error object from an [`anyhow::Error`].
+    ///
+    /// The cause chain of the `anyhow::Error` is preserved and used to
+    /// determine the [`ErrorKind`] of the error.
+    #[cfg(feature = "anyhow")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "anyhow")))]
+    pub fn from_anyhow(error: anyhow::Error) -> Self {
+        let error: Box<dyn StdError + Send + Sync> = error.into();
+
+        Self(ErrorImpl {
+            kind: ErrorKind::of(&*error),
+            error,
+        })
+    }
+
+    /// Format the message of this error along with all of its causes.
+    fn report(&self) -> String {
+        let mut report = self.to_string();
+        let mut causes = self.chain().skip(1).peekable();
+
+        if causes.peek().is_some() {
+            report.push_str("\n\nCaused by:");
+
+            for cause in causes {
+                let _ = write!(report, "\n    {cause}");
+            }
+        }
+
+        report
+    }
+
     /// An iterator over the chain of source errors contained by this error.
     ///
     /// This iterator will visit every error in the cause chain of this error
     /// object, beginning with the error that this error object was created
     /// from.
     pub fn chain(&self) -> Causes<'_> {
-        Causes(self.source())
+        Causes(Some(&*self.0.error))
     }
 
     /// Convert this error into a type which implements [`std::error::Error`].
@@ -83,27 +190,226 @@ where
     E: StdError + Send + Sync + 'static,
 {
     fn from(error: E) -> Self {
-        Self(ErrorImpl(Box::new(error)))
+        Self::with_kind(ErrorKind::of(&error), error)
+    }
+}
+
+/// The class of failure that caused an [`Error`].
+///
+/// Each kind has a stable [`code`](ErrorKind::code) which is what gets stored
+/// when the error is serialized.
+#[non_exhaustive]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum ErrorKind {
+    /// An HTTP request failed.
+    Http,
+
+    /// A SQL query failed.
+    Sql,
+
+    /// Sending a notification to another task failed.
+    Notify,
+
+    /// The workflow decided to give up (see [`Error::abort`]).
+    Abort,
+
+    /// A task or host call trapped.
+    Trap,
+
+    /// The operation was cancelled before it could complete.
+    Cancelled,
+
+    /// Any other error.
+    Other,
+}
+
+impl ErrorKind {
+    /// The stable error code for this kind.
+    pub fn code(self) -> &'static str {
+        match self {
+            Self::Http => "http",
+            Self::Sql => "sql",
+            Self::Notify => "notify",
+            Self::Abort => "abort",
+            Self::Trap => "trap",
+            Self::Cancelled => "cancelled",
+            Self::Other => "other",
+        }
+    }
+
+    /// Look up the kind with the given error code.
+    ///
+    /// Unknown error codes map to [`ErrorKind::Other`].
+    pub fn from_code(code: &str) -> Self {
+        match code {
+            "http" => Self::Http,
+            "sql" => Self::Sql,
+            "notify" => Self::Notify,
+            "abort" => Self::Abort,
+            "trap" => Self::Trap,
+            "cancelled" => Self::Cancelled,
+            _ => Self::Other,
+        }
+    }
+
+    /// Determine the kind of an error by looking through its cause chain for
+    /// one of the error types that durable knows about.
+    fn of(error: &(dyn StdError + 'static)) -> Self {
+        let mut current = Some(error);
+
+        while let Some(error) = current {
+            if let Some(error) = error.downcast_ref::<ErrorImpl>() {
+                return error.kind;
+            }
+
+            #[cfg(feature = "notify")]
+            if error.is::<durable_core::notify::NotifyError>() {
+                return Self::Notify;
+            }
+
+            #[cfg(feature = "http")]
+            if error.is::<durable_http::Error>() {
+                return Self::Http;
+            }
+
+            #[cfg(feature = "sqlx")]
+            if error.is::<durable_sqlx::Error>() {
+                return Self::Sql;
+            }
+
+            current = error.source();
+        }
+
+        Self::Other
+    }
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.code())
+    }
+}
+
+impl From<Error> for Box<dyn StdError + Send + Sync> {
+    fn from(error: Error) -> Self {
+        Box::new(error.0)
+    }
+}
+
+#[cfg(feature = "anyhow")]
+#[cfg_attr(docsrs, doc(cfg(feature = "anyhow")))]
+impl From<Error> for anyhow::Error {
+    fn from(error: Error) -> Self {
+        anyhow::Error::new(error.0)
+    }
+}
+
+/// Extension methods for results whose error can be converted into an
+/// [`Error`].
+///
+/// This allows errors to be propagated within a workflow in much the same way
+/// as with `anyhow`:
+///
+/// ```no_run
+/// use durable::ResultExt;
+///
+/// fn load_config() -> durable::Result<String> {
+///     let config = std::fs::read_to_string("config.json").context("failed to read config")?;
+///     Ok(config)
+/// }
+///
+/// let config = load_config().or_abort("unable to start the workflow");
+/// ```
+///
+/// With the `anyhow` feature enabled, an `anyhow::Result` can be converted
+/// using `.map_err(Error::from_anyhow)`.
+pub trait ResultExt<T> {
+    /// Wrap the error with some additional context.
+    ///
+    /// See [`Error::context`].
+    fn context<C: Display>(self, context: C) -> Result<T, Error>;
+
+    /// Wrap the error with some additional context that is only evaluated if
+    /// an error occurred.
+    fn with_context<C: Display, F: FnOnce() -> C>(self, context: F) -> Result<T, Error>;
+
+    /// Unwrap the value or abort the workflow.
+    ///
+    /// If this is an error then the workflow is aborted with `message`,
+    /// followed by the error and every error in its cause chain.
+    ///
+    /// See [`abort`](crate::abort).
+    fn or_abort<M: Display>(self, message: M) -> T;
+}
+
+impl<T, E> ResultExt<T> for Result<T, E>
+where
+    E: Into<Error>,
+{
+    fn context<C: Display>(self, context: C) -> Result<T, Error> {
+        self.map_err(|e| e.into().context(context))
+    }
+
+    fn with_context<C: Display, F: FnOnce() -> C>(self, context: F) -> Result<T, Error> {
+        self.map_err(|e| e.into().context(context()))
+    }
+
+    fn or_abort<M: Display>(self, message: M) -> T {
+        match self {
+            Ok(value) => value,
+            Err(e) => durable_core::abort(&e.into().context(message).report()),
+        }
     }
 }
 
-struct ErrorImpl(Box<dyn StdError + Send + Sync>);
+struct ErrorImpl {
+    kind: ErrorKind,
+    error: Box<dyn StdError + Send + Sync>,
+}
 
 impl fmt::Debug for ErrorImpl {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        fmt::Debug::fmt(&self.0, f)
+        fmt::Debug::fmt(&self.error, f)
     }
 }
 
 impl fmt::Display for ErrorImpl {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        fmt::Display::fmt(&self.0, f)
+        fmt::Display::fmt(&self.error, f)
     }
 }
 
+// ErrorImpl displays as the error it wraps, so it forwards to that error's source
+// instead of reporting the wrapped error as a separate cause.
 impl StdError for ErrorImpl {
     fn source(&self) -> Option<&(dyn StdError + 'static)> {
-        Some(&*self.0)
+        self.error.source()
+    }
+}
+
+struct ContextError {
+    message: String,
+    source: Box<dyn StdError + Send + Sync>,
+}
+
+impl fmt::Debug for ContextError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ContextError")
+            .field("message", &self.message)
+            .field("source", &self.source)
+            .finish()
+    }
+}
+
+impl fmt::Display for ContextError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.message.fmt(f)
+    }
+}
+
+impl StdError for ContextError {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        Some(&*self.source)
     }
 }
 
@@ -145,7 +451,7 @@ impl StdError for ErrorFrame {
     }
 }
 
-/// An iterator over
+/// An iterator over the chain of source errors of an [`Error`].
 pub struct Causes<'a>(Option<&'a (dyn StdError + 'static)>);
 
 impl<'a> Iterator for Causes<'a> {
@@ -168,19 +474,23 @@ impl<'a> Iterator for Causes<'a> {
 impl<'a> FusedIterator for Causes<'a> {}
 
 mod serialization {
-    use serde::ser::SerializeSeq;
+    use serde::de::{MapAccess, SeqAccess, Visitor};
+    use serde::ser::{SerializeMap, SerializeSeq};
     use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
     use super::*;
 
-    impl Serialize for Error {
+    /// Serializes the messages in the cause chain of an error.
+    struct Messages<'a>(&'a Error);
+
+    impl Serialize for Messages<'_> {
         fn serialize<S>(&self, ser: S) -> Result<S::Ok, S::Error>
         where
             S: Serializer,
         {
             let mut seq = ser.serialize_seq(None)?;
 
-            for cause in self.chain() {
+            for cause in self.0.chain() {
                 // Avoid extra copies if we got an error frame
                 if let Some(cause) = cause.downcast_ref::<ErrorFrame>() {
                     seq.serialize_element(&cause.message)?;
@@ -193,26 +503,97 @@ mod serialization {
         }
     }
 
+    // Errors with no particular kind are serialized as just the list of messages,
+    // which is what older versions of this crate expect. Everything else is
+    // serialized as a `{ "kind": ..., "messages": [...] }` object.
+    impl Serialize for Error {
+        fn serialize<S>(&self, ser: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            if self.kind() == ErrorKind::Other {
+                return Messages(self).serialize(ser);
+            }
+
+            let mut map = ser.serialize_map(Some(2))?;
+            map.serialize_entry("kind", self.code())?;
+            map.serialize_entry("messages", &Messages(self))?;
+            map.end()
+        }
+    }
+
+    fn from_messages<E>(kind: ErrorKind, messages: Vec<String>) -> Result<Error, E>
+    where
+        E: serde::de::Error,
+    {
+        let frames = messages.into_iter().rev().fold(None, |acc, msg| {
+            Some(Box::new(ErrorFrame {
+                message: msg,
+                source: acc,
+            }))
+        });
+
+        match frames {
+            Some(frames) => Ok(Error(ErrorImpl {
+                kind,
+                error: frames,
+            })),
+            None => Err(E::invalid_length(
+                0,
+                &"expected a sequence of non-zero length",
+            )),
+        }
+    }
+
+    struct ErrorVisitor;
+
+    impl<'de> Visitor<'de> for ErrorVisitor {
+        type Value = Error;
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            f.write_str("a sequence of error messages or an error object")
+        }
+
+        fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+        where
+            A: SeqAccess<'de>,
+        {
+            let mut messages = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+            while let Some(message) = seq.next_element()? {
+                messages.push(message);
+            }
+
+            from_messages(ErrorKind::Other, messages)
+        }
+
+        fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+        where
+            A: MapAccess<'de>,
+        {
+            let mut kind = None;
+            let mut messages = None;
+
+            while let Some(key) = map.next_key::<String>()? {
+                match key.as_str() {
+                    "kind" => kind = Some(ErrorKind::from_code(&map.next_value::<String>()?)),
+                    "messages" => messages = Some(map.next_value::<Vec<String>>()?),
+                    _ => {
+                        map.next_value::<serde::de::IgnoredAny>()?;
+                    }
+                }
+            }
+
+            let messages = messages.ok_or_else(|| serde::de::Error::missing_field("messages"))?;
+            from_messages(kind.unwrap_or(ErrorKind::Other), messages)
+        }
+    }
+
     impl<'de> Deserialize<'de> for Error {
         fn deserialize<D>(de: D) -> Result<Self, D::Error>
         where
             D: Deserializer<'de>,
         {
-            let messages = <Vec<String>>::deserialize(de)?;
-            let frames = messages.into_iter().rev().fold(None, |acc, msg| {
-                Some(Box::new(ErrorFrame {
-                    message: msg,
-                    source: acc,
-                }))
-            });
-
-            match frames {
-                Some(frames) => Ok(Self(ErrorImpl(frames))),
-                None => Err(serde::de::Error::invalid_length(
-                    0,
-                    &"expected a sequence of non-zero length",
-                )),
-            }
+            de.deserialize_any(ErrorVisitor)
         }
     }
 }
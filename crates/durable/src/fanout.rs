@@ -0,0 +1,294 @@
+//! Map over a collection of items using child tasks.
+//!
+//! A fan-out splits a collection of items into batches and launches a child
+//! task for each batch. The child tasks run an entrypoint exported by the same
+//! program as the current task (see [`entrypoints!`](crate::entrypoints)).
+//! The current task then waits for all of its children to complete and
+//! returns their results, in the same order as the items.
+//!
+//! ```no_run
+//! use durable::fanout::{self, Fanout};
+//!
+//! fn square() {
+//!     fanout::child(|x: u64| x * x);
+//! }
+//!
+//! durable::entrypoints! {
+//!     "square" => square,
+//! }
+//!
+//! fn main() {
+//!     let items: Vec<u64> = (0..100).collect();
+//!     let squares: Vec<u64> = Fanout::new("square")
+//!         .batch_size(10)
+//!         .run(&items)
+//!         .expect("a child task failed");
+//!
+//!     assert_eq!(squares[9], 81);
+//! }
+//! ```
+//!
+//! Waiting for the children is done using notifications, so the current task
+//! is suspended while it waits. Any other notifications that arrive in the
+//! meantime are kept and returned by [`notify::wait`](crate::notify::wait)
+//! later on.
+//!
+//! # Failures
+//! If a child task fails then every item in its batch fails. How this is
+//! handled depends on how the fan-out is run:
+//! - [`Fanout::run`] returns an error as soon as any child task fails. The
+//!   remaining child tasks keep running but their results are discarded.
+//! - [`Fanout::run_collect`] waits for every child task to complete and returns
+//!   a result for each item.
+
+use std::collections::HashMap;
+use std::fmt;
+
+use durable_core::tasks::{self, Child, ChildComplete};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::value::RawValue;
+
+use crate::Task;
+
+/// Launch one child task per item running `entrypoint` and return their
+/// results, failing as soon as any child task fails.
+///
+/// This is a shortcut for `Fanout::new(entrypoint).run(items)`.
+pub fn map<T, R>(entrypoint: &str, items: &[T]) -> Result<Vec<R>, FanoutError>
+where
+    T: Serialize,
+    R: DeserializeOwned,
+{
+    Fanout::new(entrypoint).run(items)
+}
+
+/// Run the current task as a fan-out child.
+///
+/// This reads the batch of items that the current task was launched with,
+/// calls `func` for each item, and sets the results as the result of the
+/// current task. It should be called from the entrypoint that was passed to
+/// [`Fanout::new`].
+///
+/// # Panics
+/// Panics if the task data is not a batch of `T`s or if the results cannot be
+/// serialized to JSON.
+pub fn child<T, R>(func: impl FnMut(T) -> R)
+where
+    T: DeserializeOwned,
+    R: Serialize,
+{
+    let task = Task::current();
+    let items: Vec<T> = task.data();
+    let results: Vec<R> = items.into_iter().map(func).collect();
+
+    let data = serde_json::value::to_raw_value(&results)
+        .unwrap_or_else(|e| panic!("failed to serialize fanout results: {e}"));
+    tasks::set_result(&data);
+}
+
+/// A builder for a fan-out over child tasks.
+#[derive(Clone, Debug)]
+pub struct Fanout {
+    entrypoint: String,
+    name: Option<String>,
+    batch_size: usize,
+}
+
+impl Fanout {
+    /// Create a fan-out whose child tasks run `entrypoint`.
+    pub fn new(entrypoint: impl Into<String>) -> Self {
+        Self {
+            entrypoint: entrypoint.into(),
+            name: None,
+            batch_size: 1,
+        }
+    }
+
+    /// Set the prefix used to name the child tasks.
+    ///
+    /// Child tasks are named `<name>-<batch index>`. By default, the name of
+    /// the entrypoint is used.
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Set the number of items processed by each child task.
+    ///
+    /// By default, each child task processes a single item.
+    ///
+    /// # Panics
+    /// Panics if `batch_size` is zero.
+    pub fn batch_size(mut self, batch_size: usize) -> Self {
+        assert!(batch_size != 0, "fanout batch size must be non-zero");
+
+        self.batch_size = batch_size;
+        self
+    }
+
+    /// Run the fan-out and return the results for all items.
+    ///
+    /// This returns an error as soon as any child task fails.
+    ///
+    /// # Panics
+    /// Panics if the items cannot be serialized to JSON.
+    pub fn run<T, R>(&self, items: &[T]) -> Result<Vec<R>, FanoutError>
+    where
+        T: Serialize,
+        R: DeserializeOwned,
+    {
+        self.execute(items, true)?.into_iter().collect()
+    }
+
+    /// Run the fan-out and return a result for each item.
+    ///
+    /// This waits for all child tasks to complete, even if some of them fail.
+    ///
+    /// # Panics
+    /// Panics if the items cannot be serialized to JSON.
+    pub fn run_collect<T, R>(&self, items: &[T]) -> Vec<Result<R, FanoutError>>
+    where
+        T: Serialize,
+        R: DeserializeOwned,
+    {
+        match self.execute(items, false) {
+            Ok(results) => results,
+            Err(_) => unreachable!("fanout returned early without fail-fast enabled"),
+        }
+    }
+
+    fn execute<T, R>(
+        &self,
+        items: &[T],
+        fail_fast: bool,
+    ) -> Result<Vec<Result<R, FanoutError>>, FanoutError>
+    where
+        T: Serialize,
+        R: DeserializeOwned,
+    {
+        if items.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let batches: Vec<&[T]> = items.chunks(self.batch_size).collect();
+        let data: Vec<Box<RawValue>> = batches
+            .iter()
+            .map(|batch| {
+                serde_json::value::to_raw_value(batch)
+                    .unwrap_or_else(|e| panic!("failed to serialize fanout items: {e}"))
+            })
+            .collect();
+
+        let prefix = self.name.as_deref().unwrap_or(&self.entrypoint);
+        let names: Vec<String> = (0..batches.len())
+            .map(|index| format!("{prefix}-{index}"))
+            .collect();
+        let children: Vec<Child> = names
+            .iter()
+            .zip(&data)
+            .map(|(name, data)| Child {
+                name,
+                entrypoint: Some(self.entrypoint.as_str()),
+                data,
+            })
+            .collect();
+
+        let ids = tasks::launch(&children);
+        let mut pending: HashMap<i64, usize> = ids
+            .iter()
+            .enumerate()
+            .map(|(index, &id)| (id, index))
+            .collect();
+        let mut results: Vec<Option<Result<Vec<R>, FanoutError>>> =
+            batches.iter().map(|_| None).collect();
+
+        while !pending.is_empty() {
+            let complete = crate::notify::wait_for_child(|id| pending.contains_key(&id));
+            let index = pending
+                .remove(&complete.id)
+                .expect("received a notification for a child that was not pending");
+
+            let result = parse_result(&complete, batches[index].len());
+            if fail_fast {
+                if let Err(e) = result {
+                    crate::notify::abandon_children(pending.into_keys());
+                    return Err(e);
+                }
+            }
+
+            results[index] = Some(result);
+        }
+
+        let mut output = Vec::with_capacity(items.len());
+        for (batch, result) in batches.iter().zip(results) {
+            match result.expect("all child tasks have completed") {
+                Ok(values) => output.extend(values.into_iter().map(Ok)),
+                Err(e) => output.extend(batch.iter().map(|_| Err(e.clone()))),
+            }
+        }
+
+        Ok(output)
+    }
+}
+
+fn parse_result<R>(complete: &ChildComplete, len: usize) -> Result<Vec<R>, FanoutError>
+where
+    R: DeserializeOwned,
+{
+    let task = complete.id;
+    if !complete.success {
+        return Err(FanoutError::ChildFailed { task });
+    }
+
+    let invalid = |message: String| FanoutError::InvalidResult { task, message };
+    let result = complete
+        .result
+        .as_ref()
+        .ok_or_else(|| invalid("the child task did not set a result".into()))?;
+    let values: Vec<R> = serde_json::from_str(result.get()).map_err(|e| invalid(e.to_string()))?;
+
+    if values.len() != len {
+        return Err(invalid(format!(
+            "expected {len} results but the child task returned {}",
+            values.len()
+        )));
+    }
+
+    Ok(values)
+}
+
+/// An error for an item in a fan-out.
+#[non_exhaustive]
+#[derive(Clone, Debug)]
+pub enum FanoutError {
+    /// The child task processing this item failed.
+    ChildFailed { task: i64 },
+
+    /// The result of the child task processing this item could not be
+    /// deserialized.
+    InvalidResult { task: i64, message: String },
+}
+
+impl FanoutError {
+    /// The id of the child task that processed this item.
+    pub fn task(&self) -> i64 {
+        match *self {
+            Self::ChildFailed { task } => task,
+            Self::InvalidResult { task, .. } => task,
+        }
+    }
+}
+
+impl fmt::Display for FanoutError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ChildFailed { task } => write!(f, "child task {task} failed"),
+            Self::InvalidResult { task, message } => {
+                write!(f, "child task {task} returned an invalid result: {message}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for FanoutError {}
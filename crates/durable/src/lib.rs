@@ -8,42 +8,160 @@
 //! If you are just looking to do some things in the middle of your workflow,
 //! then
 //! - the [`http`] module allows you to make HTTP requests,
+//! - the [`email`] module allows you to send emails,
+//! - the [`llm`] module allows you to make requests to OpenAI-compatible LLM
+//!   APIs,
+//! - the [`mq`] module allows you to publish messages to a message queue,
+//! - the [`object_store`] module allows you to read and write objects in an
+//!   S3-compatible object store,
 //! - the [`sqlx`] module allows you to make SQL queries to the database that
 //!   the worker is using,
 //! - the [`notify`] module allows you to wait for notifications by external
-//!   services.
+//!   services,
+//! - the [`approval`] module allows you to pause until someone approves the
+//!   workflow,
+//! - the [`fanout`] module allows you to map over a collection of items using
+//!   child tasks,
+//! - the [`saga`] module allows you to undo completed steps when a later step
+//!   fails,
+//! - the [`lock`] module allows you to keep workflows from running concurrently
+//!   against the same resource,
+//! - the [`ratelimit`] module allows you to share the rate limit of an external
+//!   service between workflows,
+//! - the [`workflow`] module allows you to give a workflow a typed input and
+//!   output that is shared with the services that launch it.
 //!
 //! Otherwise, you can get the data this task was started with via the [`Task`]
-//! object.
+//! object, and find out what the worker running it supports via
+//! [`capabilities`].
 //!
 //! # Features
+//! The `notify`, `approval`, `fanout`, `lock`, and `ratelimit` features are
+//! enabled by default. The rest are disabled by default.
+//!
+//! - `notify` - enables the [`notify`] module.
+//! - `approval` - enables the [`approval`] module. This also enables the
+//!   `notify` feature.
+//! - `fanout` - enables the [`fanout`] module. This also enables the `notify`
+//!   feature.
+//! - `lock` - enables the [`lock`] module. This also enables the `notify`
+//!   feature.
+//! - `ratelimit` - enables the [`ratelimit`] module. This also enables the
+//!   `notify` feature.
 //! - `http` - enables the [`http`] module and everything within.
+//! - `email` - enables the [`email`] module and everything within.
+//! - `llm` - enables the [`llm`] module and everything within. This also
+//!   enables the `http` feature.
+//! - `mq` - enables the [`mq`] module and everything within.
+//! - `object-store` - enables the [`object_store`] module and everything
+//!   within.
 //! - `sqlx` - enables the [`sqlx`] module and everything within.
+//! - `sqlx-macros` - enables the compile-time checked query macros in the
+//!   [`sqlx`] module (e.g. [`sqlx::query!`]).
+//! - `anyhow` - enables conversions between [`Error`] and `anyhow::Error`.
+//! - `mock` - when building for a non-wasm target, replaces the runtime with an
+//!   in-memory mock so that workflow code can be unit tested using `cargo
+//!   test`. See the [`mock`] module for details.
+//!
+//! # Minimal builds
+//! Each feature pulls in the bindings for the runtime interfaces that it uses,
+//! and those end up as imports of the compiled workflow component. Workflows
+//! that only need transactions can leave all of them out:
+//!
+//! ```toml
+//! [dependencies]
+//! durable = { version = "0.5", default-features = false }
+//! ```
+//!
+//! Features can then be added back one at a time as they are needed. Most of
+//! the remaining size of a workflow comes from the standard library and from
+//! the workflow's own dependencies. Building with size optimizations helps
+//! considerably there; this repository's `minimal` cargo profile is a good
+//! starting point:
+//!
+//! ```toml
+//! [profile.minimal]
+//! inherits = "release"
+//! opt-level = "z"
+//! lto = true
+//! codegen-units = 1
+//! debug = false
+//! strip = true
+//! ```
+//!
+//! Workflows can then be built with `cargo component build --profile minimal`.
 
 #![cfg_attr(docsrs, feature(doc_cfg))]
 
+use std::collections::BTreeMap;
 use std::time::SystemTime;
 
 use serde::de::Deserialize;
 pub use serde_json::value::RawValue;
 
+#[doc(inline)]
+#[cfg(feature = "email")]
+#[cfg_attr(docsrs, doc(cfg(feature = "email")))]
+pub extern crate durable_email as email;
+
 #[doc(inline)]
 #[cfg(feature = "http")]
 #[cfg_attr(docsrs, doc(cfg(feature = "http")))]
 pub extern crate durable_http as http;
 
+#[doc(inline)]
+#[cfg(feature = "llm")]
+#[cfg_attr(docsrs, doc(cfg(feature = "llm")))]
+pub extern crate durable_llm as llm;
+
+#[doc(inline)]
+#[cfg(feature = "mq")]
+#[cfg_attr(docsrs, doc(cfg(feature = "mq")))]
+pub extern crate durable_mq as mq;
+
+#[doc(inline)]
+#[cfg(feature = "object-store")]
+#[cfg_attr(docsrs, doc(cfg(feature = "object-store")))]
+pub extern crate durable_object_store as object_store;
+
 #[doc(inline)]
 #[cfg(feature = "sqlx")]
 #[cfg_attr(docsrs, doc(cfg(feature = "sqlx")))]
 pub extern crate durable_sqlx as sqlx;
 
+#[cfg(feature = "approval")]
+#[cfg_attr(docsrs, doc(cfg(feature = "approval")))]
+pub mod approval;
+pub mod bindgen;
+mod entrypoint;
 mod error;
+#[cfg(feature = "fanout")]
+#[cfg_attr(docsrs, doc(cfg(feature = "fanout")))]
+pub mod fanout;
+#[cfg(feature = "lock")]
+#[cfg_attr(docsrs, doc(cfg(feature = "lock")))]
+pub mod lock;
+pub mod metadata;
+#[cfg(feature = "notify")]
+#[cfg_attr(docsrs, doc(cfg(feature = "notify")))]
 pub mod notify;
+#[cfg(feature = "ratelimit")]
+#[cfg_attr(docsrs, doc(cfg(feature = "ratelimit")))]
+pub mod ratelimit;
+pub mod saga;
+pub mod workflow;
 
 #[doc(inline)]
-pub use durable_core::{abort, transaction::transaction};
+pub use durable_core::capabilities::{Capabilities, WorkerLimits};
+#[doc(inline)]
+#[cfg(all(feature = "mock", not(target_family = "wasm")))]
+#[cfg_attr(docsrs, doc(cfg(feature = "mock")))]
+pub use durable_core::mock;
+pub use durable_core::{abort, checkpoint, transaction::transaction, yield_now};
 
-pub use crate::error::{Causes, Error};
+#[doc(hidden)]
+pub use crate::entrypoint::exports;
+pub use crate::error::{Causes, Error, ErrorKind, ResultExt};
 
 pub type Result<T> = std::result::Result<T, Error>;
 
@@ -54,6 +172,8 @@ pub struct Task {
     name: String,
     data: Box<RawValue>,
     created_at: SystemTime,
+    queue: String,
+    labels: BTreeMap<String, String>,
 }
 
 impl Task {
@@ -64,6 +184,8 @@ impl Task {
             name: durable_core::task_name(),
             data: durable_core::task_data(),
             created_at: durable_core::task_created_at(),
+            queue: durable_core::task_queue(),
+            labels: durable_core::task_labels().into_iter().collect(),
         }
     }
 
@@ -85,6 +207,48 @@ impl Task {
         self.created_at
     }
 
+    /// The name of the queue that this task was launched into.
+    ///
+    /// Tasks are queued by tenant, so this is the tenant that the task belongs
+    /// to, or an empty string if it does not belong to one.
+    pub fn queue(&self) -> &str {
+        &self.queue
+    }
+
+    /// The labels that this task was launched with.
+    ///
+    /// Child tasks inherit the labels of the task that launched them.
+    pub fn labels(&self) -> &BTreeMap<String, String> {
+        &self.labels
+    }
+
+    /// Get the value of a single label, if the task has it.
+    pub fn label(&self, key: &str) -> Option<&str> {
+        self.labels.get(key).map(String::as_str)
+    }
+
+    /// The number of times that a worker has started running this task,
+    /// including the current one.
+    ///
+    /// This is 1 the first time that the task runs and goes up every time the
+    /// task is resumed, either after being suspended or after the worker that
+    /// was running it went away. Workflows can use it to, for example, give up
+    /// on an external service after a number of attempts.
+    ///
+    /// The attempt is recorded in a transaction when this is called outside of
+    /// one, so it only changes between calls, not when the task is replayed.
+    pub fn attempt(&self) -> u32 {
+        durable_core::transaction::maybe_txn("durable::task::attempt", durable_core::task_attempt)
+    }
+
+    /// The id of the worker that is running this task.
+    ///
+    /// Like [`attempt`](Task::attempt), this is recorded in a transaction when
+    /// called outside of one.
+    pub fn worker_id(&self) -> i64 {
+        durable_core::transaction::maybe_txn("durable::task::worker_id", durable_core::worker_id)
+    }
+
     /// The data that this task was created with, deserialized as a `T`.
     ///
     /// # Panics
@@ -113,3 +277,23 @@ impl Task {
 pub fn task() -> Task {
     Task::current()
 }
+
+/// Find out which interfaces and limits the worker running this task has.
+///
+/// Which interfaces are available depends on how the worker was configured and
+/// on the plugins that it has registered. Checking for an interface before
+/// using it allows a workflow to fall back to something else, or to fail with
+/// a useful message, instead of trapping on the first call to it.
+///
+/// ```no_run
+/// let capabilities = durable::capabilities();
+/// if !capabilities.has_interface("durable:core/object-store") {
+///     panic!("this workflow needs a worker with an object store");
+/// }
+/// ```
+///
+/// The result is recorded in a transaction, so it does not change if the task
+/// is resumed on a different worker.
+pub fn capabilities() -> Capabilities {
+    durable_core::transaction::maybe_txn("durable::capabilities", Capabilities::current)
+}
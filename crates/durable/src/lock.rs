@@ -0,0 +1,190 @@
+//! Keep workflows from running concurrently against the same resource.
+//!
+//! A lock is identified by its name and is shared between all tasks running
+//! on the cluster. Acquiring a lock that is held by another task blocks the
+//! workflow (and suspends the task) until the lock is released.
+//!
+//! ```no_run
+//! use std::time::Duration;
+//!
+//! let guard = durable::lock::acquire("billing-export", Some(Duration::from_secs(3600)));
+//! // Only one task at a time will get here.
+//! guard.release();
+//! ```
+//!
+//! A [`Semaphore`] allows a fixed number of tasks to hold the lock at once:
+//!
+//! ```no_run
+//! use durable::lock::Semaphore;
+//!
+//! let api = Semaphore::new("partner-api", 4);
+//! let _permit = api.acquire(None);
+//! // At most 4 tasks at a time will get here.
+//! ```
+//!
+//! All tasks using a lock should agree on the number of permits it has.
+//!
+//! # Releasing locks
+//! A lock is released when any of the following happen:
+//! - the [`LockGuard`] is released or dropped,
+//! - the ttl given when acquiring the lock expires, or
+//! - the task holding the lock completes or fails.
+//!
+//! Dropping a [`LockGuard`] within a transaction does not release the lock,
+//! since the runtime does not allow that. It will be released by one of the
+//! other ways instead.
+
+use std::time::{Duration, SystemTime};
+
+use durable_core::lock::{AcquireResult, LOCK_RELEASED_EVENT};
+use serde::Deserialize;
+
+use crate::notify::Notification;
+
+/// A named semaphore that allows up to a fixed number of tasks to hold it at
+/// once.
+#[derive(Clone, Debug)]
+pub struct Semaphore {
+    name: String,
+    permits: u32,
+}
+
+impl Semaphore {
+    /// Create a semaphore with the name `name` and `permits` permits.
+    ///
+    /// # Panics
+    /// Panics if `permits` is zero.
+    pub fn new(name: impl Into<String>, permits: u32) -> Self {
+        assert!(permits > 0, "a semaphore must have at least one permit");
+
+        Self {
+            name: name.into(),
+            permits,
+        }
+    }
+
+    /// The name of this semaphore.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The number of permits that this semaphore has.
+    pub fn permits(&self) -> u32 {
+        self.permits
+    }
+
+    /// Acquire a permit, blocking until one is available.
+    ///
+    /// If `ttl` is set then the permit is released automatically once that
+    /// much time has passed, even if the current task still holds it.
+    ///
+    /// # Traps
+    /// Attempting to call this function within a transaction will result in a
+    /// trap that instantly kills the workflow.
+    pub fn acquire(&self, ttl: Option<Duration>) -> LockGuard {
+        loop {
+            let expires_in = match durable_core::lock::try_acquire(&self.name, self.permits, ttl) {
+                AcquireResult::Acquired => return self.guard(),
+                AcquireResult::Held { expires_in } => expires_in,
+            };
+
+            // Wake up either when a permit is released or when the first of
+            // the held permits expires, whichever happens first, and try
+            // again.
+            let deadline = expires_in.map(|expires_in| SystemTime::now() + expires_in);
+            crate::notify::wait_for(deadline, |notification| {
+                is_release_of(notification, &self.name)
+            });
+        }
+    }
+
+    /// Acquire a permit if one is available right now.
+    ///
+    /// # Traps
+    /// Attempting to call this function within a transaction will result in a
+    /// trap that instantly kills the workflow.
+    pub fn try_acquire(&self, ttl: Option<Duration>) -> Option<LockGuard> {
+        match durable_core::lock::try_acquire(&self.name, self.permits, ttl) {
+            AcquireResult::Acquired => Some(self.guard()),
+            AcquireResult::Held { .. } => None,
+        }
+    }
+
+    fn guard(&self) -> LockGuard {
+        LockGuard {
+            name: self.name.clone(),
+        }
+    }
+}
+
+/// A permit for a lock held by the current task.
+///
+/// The permit is released when this guard is dropped.
+#[derive(Debug)]
+#[must_use = "the lock is released as soon as the guard is dropped"]
+pub struct LockGuard {
+    name: String,
+}
+
+impl LockGuard {
+    /// The name of the lock that this guard holds a permit for.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Release the permit held by this guard.
+    ///
+    /// This is the same as dropping the guard.
+    ///
+    /// # Traps
+    /// Attempting to call this function within a transaction will result in a
+    /// trap that instantly kills the workflow.
+    pub fn release(self) {}
+}
+
+impl Drop for LockGuard {
+    fn drop(&mut self) {
+        if durable_core::transaction::in_transaction() {
+            return;
+        }
+
+        durable_core::lock::release(&self.name);
+    }
+}
+
+/// Acquire the lock `name`, blocking until it is available.
+///
+/// This is the same as acquiring a [`Semaphore`] with a single permit.
+///
+/// # Traps
+/// Attempting to call this function within a transaction will result in a trap
+/// that instantly kills the workflow.
+pub fn acquire(name: &str, ttl: Option<Duration>) -> LockGuard {
+    Semaphore::new(name, 1).acquire(ttl)
+}
+
+/// Acquire the lock `name` if it is available right now.
+///
+/// This is the same as acquiring a [`Semaphore`] with a single permit.
+///
+/// # Traps
+/// Attempting to call this function within a transaction will result in a trap
+/// that instantly kills the workflow.
+pub fn try_acquire(name: &str, ttl: Option<Duration>) -> Option<LockGuard> {
+    Semaphore::new(name, 1).try_acquire(ttl)
+}
+
+#[derive(Deserialize)]
+struct LockReleased {
+    name: String,
+}
+
+fn is_release_of(notification: &Notification, name: &str) -> bool {
+    if notification.event != LOCK_RELEASED_EVENT {
+        return false;
+    }
+
+    notification
+        .json::<LockReleased>()
+        .is_ok_and(|released| released.name == name)
+}
@@ -0,0 +1,44 @@
+//! Publish structured status about the current task.
+//!
+//! Task metadata is a small JSON object attached to the task that the workflow
+//! can update as it runs, for example to record which external order it is
+//! currently processing. Clients can read it at any point while the task is
+//! running via `Task::metadata` in `durable-client`, `durable task metadata`,
+//! or the task details returned by the HTTP API.
+//!
+//! ```no_run
+//! durable::metadata::set("order-id", "ord_1234");
+//! durable::metadata::set("step", &serde_json::json!({ "name": "charge", "of": 3 }));
+//! // ...
+//! durable::metadata::remove("order-id");
+//! ```
+//!
+//! Updates are recorded in a transaction when made outside of one, so they are
+//! not repeated when the workflow is replayed. Within a database transaction
+//! the update is applied immediately and is not rolled back if the transaction
+//! is.
+
+use serde::Serialize;
+
+/// Set `key` in the metadata of the current task to `value`.
+///
+/// # Panics
+/// Panics if `value` cannot be serialized to JSON.
+///
+/// # Traps
+/// Values larger than 16 KiB once serialized will result in a trap that
+/// instantly kills the workflow.
+pub fn set<T: ?Sized + Serialize>(key: &str, value: &T) {
+    let value = serde_json::to_string(value).expect("failed to serialize task metadata to json");
+
+    durable_core::transaction::maybe_txn("durable::metadata::set", || {
+        durable_core::set_metadata(key, Some(&value))
+    })
+}
+
+/// Remove `key` from the metadata of the current task.
+pub fn remove(key: &str) {
+    durable_core::transaction::maybe_txn("durable::metadata::remove", || {
+        durable_core::set_metadata(key, None)
+    })
+}
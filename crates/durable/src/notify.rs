@@ -10,10 +10,24 @@
 //! just needs to arrange for a notification to be posted to the right task, and
 //! it will pick up where it left off.
 
+use std::cell::RefCell;
+use std::collections::{HashSet, VecDeque};
+use std::time::{Duration, SystemTime};
+
+use durable_core::lock::LOCK_RELEASED_EVENT;
 #[doc(inline)]
 pub use durable_core::notify::{Notification, NotifyError, NotifyErrorKind};
+use durable_core::tasks::{ChildComplete, CHILD_COMPLETE_EVENT};
 use serde::Serialize;
 
+thread_local! {
+    // Notifications that arrived while waiting for child tasks to complete.
+    static STASHED: RefCell<VecDeque<Notification>> = RefCell::new(VecDeque::new());
+
+    // Child tasks whose completion notifications should be dropped.
+    static ABANDONED: RefCell<HashSet<i64>> = RefCell::new(HashSet::new());
+}
+
 /// Block this workflow until a new notification arrives, and return that
 /// notification.
 ///
@@ -26,7 +40,104 @@ use serde::Serialize;
 /// Attempting to call this function within a transaction will result in a trap
 /// that instantly kills the workflow.
 pub fn wait() -> Notification {
-    durable_core::notify::wait()
+    wait_for(None, is_external).expect("waiting for a notification without a deadline timed out")
+}
+
+/// Block this workflow until a new notification arrives or until `deadline`
+/// has passed.
+///
+/// Returns `None` if no notification arrived before `deadline`.
+///
+/// # Traps
+/// Attempting to call this function within a transaction will result in a trap
+/// that instantly kills the workflow.
+pub fn wait_until(deadline: SystemTime) -> Option<Notification> {
+    wait_for(Some(deadline), is_external)
+}
+
+/// Block this workflow until a new notification arrives or until `timeout` has
+/// elapsed.
+///
+/// Returns `None` if no notification arrived within `timeout`.
+///
+/// # Traps
+/// Attempting to call this function within a transaction will result in a trap
+/// that instantly kills the workflow.
+pub fn wait_timeout(timeout: Duration) -> Option<Notification> {
+    wait_until(SystemTime::now() + timeout)
+}
+
+/// Whether a notification should be returned by [`wait`].
+///
+/// Lock release notifications are an implementation detail of
+/// [`lock`](crate::lock) and are never returned.
+fn is_external(notification: &Notification) -> bool {
+    notification.event != LOCK_RELEASED_EVENT
+}
+
+/// Wait for a notification matching `filter`.
+///
+/// Other notifications received while waiting are stashed so that they are
+/// returned by later calls to [`wait`]. Lock release notifications that don't
+/// match `filter` are dropped instead.
+pub(crate) fn wait_for(
+    deadline: Option<SystemTime>,
+    mut filter: impl FnMut(&Notification) -> bool,
+) -> Option<Notification> {
+    let stashed = STASHED.with_borrow_mut(|stash| {
+        let index = stash.iter().position(&mut filter)?;
+        stash.remove(index)
+    });
+    if stashed.is_some() {
+        return stashed;
+    }
+
+    loop {
+        let notification = match deadline {
+            Some(deadline) => durable_core::notify::wait_until(deadline)?,
+            None => durable_core::notify::wait(),
+        };
+
+        if let Some(complete) = child_complete(&notification) {
+            if ABANDONED.with_borrow_mut(|ids| ids.remove(&complete.id)) {
+                continue;
+            }
+        }
+
+        if filter(&notification) {
+            return Some(notification);
+        }
+
+        // These are only useful to whoever is currently waiting on the lock.
+        if notification.event == LOCK_RELEASED_EVENT {
+            continue;
+        }
+
+        STASHED.with_borrow_mut(|stash| stash.push_back(notification));
+    }
+}
+
+/// Wait for a child task matching `filter` to complete.
+pub(crate) fn wait_for_child(mut filter: impl FnMut(i64) -> bool) -> ChildComplete {
+    let notification = wait_for(None, |notification| {
+        child_complete(notification).is_some_and(|complete| filter(complete.id))
+    })
+    .expect("waiting for a notification without a deadline timed out");
+
+    child_complete(&notification).expect("notification was not a child completion")
+}
+
+/// Drop the completion notifications for these child tasks when they arrive.
+pub(crate) fn abandon_children(ids: impl IntoIterator<Item = i64>) {
+    ABANDONED.with_borrow_mut(|abandoned| abandoned.extend(ids));
+}
+
+fn child_complete(notification: &Notification) -> Option<ChildComplete> {
+    if notification.event != CHILD_COMPLETE_EVENT {
+        return None;
+    }
+
+    notification.json().ok()
 }
 
 /// Send a notification to another durable task.
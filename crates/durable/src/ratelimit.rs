@@ -0,0 +1,33 @@
+//! Share the rate limit of an external service between all tasks.
+//!
+//! Rate limits are token buckets that are configured on the workers. Each call
+//! to [`acquire`] takes a single token, blocking the workflow (and suspending
+//! the task) until one is available.
+//!
+//! ```no_run
+//! durable::ratelimit::acquire("github-api");
+//! // ... make a request to the GitHub API ...
+//! ```
+//!
+//! The state of each rate limit is kept in the database, so a rate limit is
+//! shared by all tasks on all workers. Tasks that have to wait for a token are
+//! given one in the order that they started waiting.
+//!
+//! Notifications that arrive while waiting on a rate limit are kept and
+//! returned by [`notify::wait`](crate::notify::wait) later on.
+
+use std::time::SystemTime;
+
+/// Take a token from the rate limit `name`, blocking until one is available.
+///
+/// # Traps
+/// Attempting to call this function within a transaction, or with the name of a
+/// rate limit that is not configured on the worker, will result in a trap that
+/// instantly kills the workflow.
+pub fn acquire(name: &str) {
+    while let Some(wait) = durable_core::ratelimit::try_acquire(name) {
+        // Nothing matches the filter, so this just sleeps until the deadline
+        // while keeping any notifications that arrive in the meantime.
+        crate::notify::wait_for(Some(SystemTime::now() + wait), |_| false);
+    }
+}
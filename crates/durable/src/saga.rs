@@ -0,0 +1,201 @@
+//! Undo completed steps of a workflow when a later step fails.
+//!
+//! A saga is a sequence of steps, each of which has a compensation that undoes
+//! it. If a step fails then the compensations for all the steps that completed
+//! before it are run, in reverse order.
+//!
+//! ```no_run
+//! # fn book_flight() -> durable::Result<String> { Ok("FL123".into()) }
+//! # fn cancel_flight(_: &str) -> durable::Result<()> { Ok(()) }
+//! # fn book_hotel() -> durable::Result<String> { Ok("HT456".into()) }
+//! # fn cancel_hotel(_: &str) -> durable::Result<()> { Ok(()) }
+//! let booking = durable::saga::run("book-trip", |saga| {
+//!     let flight = saga.step("book-flight", book_flight, |flight: String| {
+//!         cancel_flight(&flight)
+//!     })?;
+//!     let hotel = saga.step("book-hotel", book_hotel, |hotel: String| {
+//!         cancel_hotel(&hotel)
+//!     })?;
+//!
+//!     Ok((flight, hotel))
+//! });
+//!
+//! // If booking the hotel failed then the flight has already been cancelled.
+//! let (flight, hotel) = booking.expect("failed to book the trip");
+//! ```
+//!
+//! # Durability
+//! Each step runs within its own transaction, and so does each compensation.
+//! The value returned by a step is stored as part of that transaction's event
+//! and is passed to its compensation, so compensations are registered again
+//! with the same values when the workflow is restarted. Once a compensation
+//! has run, it will not be run again.
+//!
+//! Since steps and compensations run within transactions, they cannot call
+//! functions that must not be called within a transaction (e.g.
+//! [`notify::wait`](crate::notify::wait)).
+//!
+//! Compensations are only run when a step returns an error. If a step panics
+//! then the workflow fails without running any compensations.
+
+use std::fmt;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::value::RawValue;
+
+use crate::{transaction, Error};
+
+/// Run `func` as a saga named `name`.
+///
+/// If `func` returns an error then the compensations for all steps that
+/// completed are run in reverse order.
+pub fn run<'a, T>(
+    name: &str,
+    func: impl FnOnce(&mut Saga<'a>) -> crate::Result<T>,
+) -> Result<T, SagaError> {
+    let mut saga = Saga::new(name);
+
+    match func(&mut saga) {
+        Ok(value) => Ok(value),
+        Err(error) => Err(SagaError {
+            error,
+            compensation_errors: saga.compensate(),
+        }),
+    }
+}
+
+/// A sequence of steps that can be undone.
+///
+/// Usually you will want to use [`run`] instead of creating one of these
+/// directly.
+pub struct Saga<'a> {
+    name: String,
+    compensations: Vec<Compensation<'a>>,
+}
+
+struct Compensation<'a> {
+    label: String,
+    func: Box<dyn Fn() -> crate::Result<()> + 'a>,
+}
+
+impl<'a> Saga<'a> {
+    /// Create a new saga with no completed steps.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            compensations: Vec::new(),
+        }
+    }
+
+    /// The name of this saga.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Run `action` within a transaction labelled `label`.
+    ///
+    /// If it succeeds then `compensate` is registered to undo it and is called
+    /// with the value returned by `action` if the saga is compensated.
+    pub fn step<T, F, C>(&mut self, label: &str, action: F, compensate: C) -> crate::Result<T>
+    where
+        F: Fn() -> crate::Result<T>,
+        C: Fn(T) -> crate::Result<()> + 'a,
+        T: Serialize + DeserializeOwned + 'a,
+    {
+        let value = transaction(label, action)?;
+        let stored = serde_json::value::to_raw_value(&value).unwrap_or_else(|e| {
+            panic!("failed to serialize the result of saga step {label:?}: {e}")
+        });
+
+        self.compensations.push(Compensation {
+            label: label.to_owned(),
+            func: Box::new(move || compensate(parse_stored(&stored)?)),
+        });
+
+        Ok(value)
+    }
+
+    /// Run the compensations for all completed steps, in reverse order.
+    ///
+    /// A compensation that fails does not stop the remaining compensations from
+    /// being run. Returns the errors from all compensations that failed.
+    pub fn compensate(self) -> Vec<Error> {
+        let mut errors = Vec::new();
+
+        for compensation in self.compensations.into_iter().rev() {
+            let label = format!(
+                "durable::saga::compensate({}, {})",
+                self.name, compensation.label
+            );
+
+            if let Err(e) = transaction(&label, &compensation.func) {
+                errors.push(e);
+            }
+        }
+
+        errors
+    }
+}
+
+impl fmt::Debug for Saga<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let labels: Vec<_> = self.compensations.iter().map(|c| &c.label).collect();
+
+        f.debug_struct("Saga")
+            .field("name", &self.name)
+            .field("steps", &labels)
+            .finish()
+    }
+}
+
+fn parse_stored<T: DeserializeOwned>(stored: &RawValue) -> crate::Result<T> {
+    serde_json::from_str(stored.get()).map_err(Error::new)
+}
+
+/// The error returned when a saga fails.
+#[derive(Debug)]
+pub struct SagaError {
+    error: Error,
+    compensation_errors: Vec<Error>,
+}
+
+impl SagaError {
+    /// The error that caused the saga to fail.
+    pub fn error(&self) -> &Error {
+        &self.error
+    }
+
+    /// Convert this into the error that caused the saga to fail.
+    pub fn into_error(self) -> Error {
+        self.error
+    }
+
+    /// The errors from any compensations that failed.
+    pub fn compensation_errors(&self) -> &[Error] {
+        &self.compensation_errors
+    }
+
+    /// Whether all completed steps were successfully undone.
+    pub fn compensated(&self) -> bool {
+        self.compensation_errors.is_empty()
+    }
+}
+
+impl fmt::Display for SagaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.error, f)?;
+
+        match self.compensation_errors.len() {
+            0 => Ok(()),
+            1 => write!(f, " (1 compensation failed)"),
+            n => write!(f, " ({n} compensations failed)"),
+        }
+    }
+}
+
+impl std::error::Error for SagaError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&*self.error)
+    }
+}
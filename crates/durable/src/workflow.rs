@@ -0,0 +1,71 @@
+//! Workflows with a typed input and output.
+//!
+//! A [`WorkflowDef`] is usually defined in a crate that is shared between the
+//! workflow and the services that launch it. Those services can then use
+//! `DurableClient::launch_typed` and `Task::result_typed` from
+//! `durable-client` to launch the workflow and read its result without having
+//! to worry about the two sides getting out of sync.
+//!
+//! Within the program, the workflow is exported as an entrypoint with the same
+//! name as [`WorkflowDef::NAME`] and uses [`run`] to read its input and set
+//! its output.
+//!
+//! ```no_run
+//! use durable::workflow::{self, WorkflowDef};
+//!
+//! struct Add;
+//!
+//! impl WorkflowDef for Add {
+//!     type Input = (u64, u64);
+//!     type Output = u64;
+//!
+//!     const NAME: &'static str = "add";
+//! }
+//!
+//! fn add() {
+//!     workflow::run::<Add>(|(a, b)| a + b);
+//! }
+//!
+//! durable::entrypoints! {
+//!     "add" => add,
+//! }
+//!
+//! fn main() {}
+//! ```
+
+use durable_core::tasks;
+
+#[doc(inline)]
+pub use durable_workflow::WorkflowDef;
+
+use crate::Task;
+
+/// Run the current task as an instance of workflow `W`.
+///
+/// This reads the task data as a `W::Input`, calls `func` with it, and sets
+/// the value it returns as the result of the current task.
+///
+/// # Panics
+/// Panics if the task data is not a valid `W::Input` or if the output cannot
+/// be serialized to JSON.
+pub fn run<W>(func: impl FnOnce(W::Input) -> W::Output)
+where
+    W: WorkflowDef,
+{
+    let task = Task::current();
+    let input: W::Input = serde_json::from_str(task.raw_data().get()).unwrap_or_else(|e| {
+        panic!(
+            "task data is not a valid input for workflow `{}`: {e}",
+            W::NAME
+        )
+    });
+    let output = func(input);
+
+    let data = serde_json::value::to_raw_value(&output).unwrap_or_else(|e| {
+        panic!(
+            "failed to serialize the output of workflow `{}`: {e}",
+            W::NAME
+        )
+    });
+    tasks::set_result(&data);
+}
@@ -19,7 +19,67 @@ impl Bindings {
             "durable:core/import-core",
             Options::new().with("wasi:clocks/wall-clock@0.2.0"),
         )?;
+        generator.generate_file(
+            "durable-core",
+            "durable:core/export-workflow",
+            "src/exports.rs",
+            Options::new().with_pub_export_macro("__export_workflow"),
+        )?;
+        generator.generate_file(
+            "durable-core",
+            "durable:core/import-notify",
+            "src/notify_bindings.rs",
+            Options::new().with("wasi:clocks/wall-clock@0.2.0"),
+        )?;
+        generator.generate_file(
+            "durable-core",
+            "durable:core/import-lock",
+            "src/lock_bindings.rs",
+            Options::new(),
+        )?;
+        generator.generate_file(
+            "durable-core",
+            "durable:core/import-panic",
+            "src/panic_bindings.rs",
+            Options::new(),
+        )?;
+        generator.generate_file(
+            "durable-core",
+            "durable:core/import-scheduler",
+            "src/scheduler_bindings.rs",
+            Options::new(),
+        )?;
+        generator.generate_file(
+            "durable-core",
+            "durable:core/import-capabilities",
+            "src/capabilities_bindings.rs",
+            Options::new(),
+        )?;
+        generator.generate_file(
+            "durable-core",
+            "durable:core/import-ratelimit",
+            "src/ratelimit_bindings.rs",
+            Options::new(),
+        )?;
+        generator.generate_file(
+            "durable-core",
+            "durable:core/import-tasks",
+            "src/tasks_bindings.rs",
+            Options::new(),
+        )?;
+        generator.generate_for_crate(
+            "durable-email",
+            "durable:core/import-email",
+            Options::new(),
+        )?;
         generator.generate_for_crate("durable-http", "durable:core/import-http", Options::new())?;
+        generator.generate_for_crate("durable-llm", "durable:core/import-llm", Options::new())?;
+        generator.generate_for_crate("durable-mq", "durable:core/import-mq", Options::new())?;
+        generator.generate_for_crate(
+            "durable-object-store",
+            "durable:core/import-object-store",
+            Options::new(),
+        )?;
         generator.generate_for_crate(
             "durable-sqlx",
             "durable:core/import-sql",
@@ -34,9 +94,19 @@ impl Bindings {
 
 impl Generator {
     fn generate_for_crate(&self, name: &str, world: &str, options: Options) -> anyhow::Result<()> {
+        self.generate_file(name, world, "src/bindings.rs", options)
+    }
+
+    fn generate_file(
+        &self,
+        name: &str,
+        world: &str,
+        output: &str,
+        options: Options,
+    ) -> anyhow::Result<()> {
         let crate_dir = self.workspace_root.join("crates").join(name);
         let wit_dir = crate_dir.join("wit");
-        let output = crate_dir.join("src/bindings.rs");
+        let output = crate_dir.join(output);
 
         durable_bindgen::generate(&wit_dir, &output, world, options)?;
 
@@ -9,6 +9,7 @@ mod gen;
 mod migrate;
 mod package;
 mod publish;
+mod wit;
 
 #[derive(Debug, clap::Parser)]
 pub struct Args {
@@ -23,6 +24,7 @@ pub enum Command {
     Dev(self::dev::Dev),
     Package(self::package::Package),
     Publish(self::publish::Publish),
+    Wit(self::wit::Wit),
 }
 
 fn main() -> anyhow::Result<()> {
@@ -43,6 +45,7 @@ fn main() -> anyhow::Result<()> {
         Command::Dev(cmd) => cmd.run(),
         Command::Package(cmd) => cmd.run(),
         Command::Publish(cmd) => cmd.run(),
+        Command::Wit(cmd) => cmd.run(),
     }
 }
 
@@ -1,7 +1,9 @@
 use std::str::FromStr;
 
 use anyhow::Context;
-use durable_migrate::{Migrator, Options, Table, Target, TransactionMode};
+use durable_migrate::{
+    ChecksumMode, MigrationLock, Migrator, Options, Table, Target, TransactionMode,
+};
 use sqlx::Connection;
 
 /// Apply migrations to the database.
@@ -48,6 +50,20 @@ pub enum Command {
 
     /// Recreate the database then migrate to a target version.
     Reset,
+
+    /// Update the checksums stored in the database to match the current
+    /// migrations, then migrate to a target version.
+    ///
+    /// Use this after intentionally editing a migration that has already been
+    /// applied.
+    Repair,
+
+    /// Record all migrations up to the target version as applied without
+    /// running them.
+    ///
+    /// This is for databases that already have the schema, e.g. because they
+    /// were set up by hand. The target version is required.
+    Baseline,
 }
 
 impl Migrate {
@@ -80,6 +96,8 @@ impl Migrate {
             None => match self.command {
                 Command::Apply => Target::Latest,
                 Command::Reset => Target::Latest,
+                Command::Repair => Target::Latest,
+                Command::Baseline => anyhow::bail!("baseline requires a --target version"),
                 Command::Revert => {
                     let latest = match migrator.read_database_version(&mut conn, &table).await? {
                         Some(version) => version,
@@ -106,6 +124,16 @@ impl Migrate {
             dry_run: self.dry_run,
             migration_table: table,
             prefer_local_revert: true,
+            checksum_mode: match self.command {
+                Command::Repair => ChecksumMode::Repair,
+                _ => ChecksumMode::Verify,
+            },
+            baseline: match (&self.command, target) {
+                (Command::Baseline, Target::Version(version)) => Some(version),
+                _ => None,
+            },
+            lock: Some(MigrationLock::default()),
+            schema_rename: None,
         };
 
         if matches!(self.command, Command::Reset) {
@@ -0,0 +1,99 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+
+/// Package and publish the `durable:core` WIT package.
+///
+/// This is the package that workflows written in languages other than Rust
+/// (e.g. via componentize-py or jco) build against.
+#[derive(Debug, clap::Args)]
+pub struct Wit {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, clap::Subcommand)]
+pub enum Command {
+    /// Encode the WIT package as a binary `.wasm` file.
+    ///
+    /// The package is written to `target/wit/durable-core@<version>.wasm`
+    /// unless `--output` is provided.
+    Package {
+        #[arg(long, short)]
+        output: Option<PathBuf>,
+    },
+
+    /// Package the WIT and publish it to an OCI registry using `wkg`.
+    ///
+    /// Publishing a version that already exists in the registry will fail, so
+    /// the package version needs to be bumped whenever the WIT changes.
+    Publish {
+        /// The registry to publish to, if not the default one configured for
+        /// `wkg`.
+        #[arg(long)]
+        registry: Option<String>,
+    },
+}
+
+impl Wit {
+    pub fn run(self) -> anyhow::Result<()> {
+        match self.command {
+            Command::Package { output } => {
+                package(output)?;
+            }
+            Command::Publish { registry } => {
+                let path = package(None)?;
+
+                let sh = xshell::Shell::new()?;
+                let mut cmd = xshell::cmd!(sh, "wkg publish {path}");
+                if let Some(registry) = registry {
+                    cmd = cmd.arg("--registry").arg(registry);
+                }
+
+                cmd.run()?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn package(output: Option<PathBuf>) -> anyhow::Result<PathBuf> {
+    let root = crate::workspace_root()?;
+    let witdir = root.join("crates/durable-runtime/wit");
+    let version = package_version(&witdir)?;
+
+    let output = match output {
+        Some(output) => output,
+        None => root
+            .join("target/wit")
+            .join(format!("durable-core@{version}.wasm")),
+    };
+
+    if let Some(parent) = output.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("failed to create `{}`", parent.display()))?;
+    }
+
+    let sh = xshell::Shell::new()?;
+    xshell::cmd!(sh, "wasm-tools component wit {witdir} --wasm -o {output}").run()?;
+
+    println!("Wrote durable:core@{version} to {}", output.display());
+
+    Ok(output)
+}
+
+/// Read the version out of the `package` declaration in the WIT directory.
+fn package_version(witdir: &Path) -> anyhow::Result<String> {
+    let path = witdir.join("imports.wit");
+    let wit = std::fs::read_to_string(&path)
+        .with_context(|| format!("failed to read `{}`", path.display()))?;
+
+    let version = wit
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("package durable:core@"))
+        .and_then(|rest| rest.strip_suffix(';'))
+        .with_context(|| format!("`{}` has no versioned package declaration", path.display()))?;
+
+    Ok(version.to_owned())
+}